@@ -48,7 +48,7 @@ async fn main() -> std::result::Result<(), Box<dyn Error>> {
                 .constraint(CompletenessConstraint::new(
                     "id",
                     ConstraintOptions::new().with_threshold(1.0),
-                )) // Should pass
+                )?) // Should pass
                 .build(),
         )
         .check(
@@ -57,7 +57,7 @@ async fn main() -> std::result::Result<(), Box<dyn Error>> {
                 .constraint(CompletenessConstraint::new(
                     "username",
                     ConstraintOptions::new().with_threshold(0.95),
-                )) // Will fail - only 80% complete
+                )?) // Will fail - only 80% complete
                 .build(),
         )
         .check(
@@ -66,7 +66,7 @@ async fn main() -> std::result::Result<(), Box<dyn Error>> {
                 .constraint(CompletenessConstraint::new(
                     "age",
                     ConstraintOptions::new().with_threshold(0.95),
-                )) // Will fail - only 90% complete
+                )?) // Will fail - only 90% complete
                 .build(),
         )
         .check(
@@ -124,7 +124,7 @@ async fn main() -> std::result::Result<(), Box<dyn Error>> {
                     .constraint(CompletenessConstraint::new(
                         "id",
                         ConstraintOptions::new().with_threshold(1.0),
-                    ))
+                    )?)
                     .build(),
             )
             .check(
@@ -133,7 +133,7 @@ async fn main() -> std::result::Result<(), Box<dyn Error>> {
                     .constraint(CompletenessConstraint::new(
                         "username",
                         ConstraintOptions::new().with_threshold(0.95),
-                    )) // Will fail - only 90% complete
+                    )?) // Will fail - only 90% complete
                     .build(),
             )
             .check(
@@ -142,7 +142,7 @@ async fn main() -> std::result::Result<(), Box<dyn Error>> {
                     .constraint(CompletenessConstraint::new(
                         "age",
                         ConstraintOptions::new().with_threshold(0.90),
-                    )) // Will pass - 95% complete
+                    )?) // Will pass - 95% complete
                     .build(),
             )
             .check(
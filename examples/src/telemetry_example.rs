@@ -254,14 +254,10 @@ mod constraints {
     //! Constraint helper functions for the example
 
     use term_guard::constraints::*;
-    use term_guard::core::ConstraintOptions;
 
     /// Creates a completeness constraint
     pub fn completeness(column: &str, threshold: Option<f64>) -> CompletenessConstraint {
-        CompletenessConstraint::new(
-            column,
-            ConstraintOptions::new().with_threshold(threshold.unwrap_or(1.0)),
-        )
+        CompletenessConstraint::with_threshold(column, threshold.unwrap_or(1.0))
     }
 
     /// Creates a size constraint  
@@ -563,12 +563,17 @@ fn bench_logical_operators(c: &mut Criterion) {
                         let suite = ValidationSuite::builder("logical_old")
                             .check(
                                 Check::builder("multi_column")
-                                    .constraint(CompletenessConstraint::new(
-                                        vec!["id", "department"],
-                                        term_guard::core::ConstraintOptions::new()
-                                            .with_operator(term_guard::core::LogicalOperator::All)
-                                            .with_threshold(1.0),
-                                    ))
+                                    .constraint(
+                                        CompletenessConstraint::new(
+                                            vec!["id", "department"],
+                                            term_guard::core::ConstraintOptions::new()
+                                                .with_operator(
+                                                    term_guard::core::LogicalOperator::All,
+                                                )
+                                                .with_threshold(1.0),
+                                        )
+                                        .unwrap(),
+                                    )
                                     .constraint(
                                         term_guard::constraints::CompletenessConstraint::new(
                                             vec!["email", "age", "salary"],
@@ -577,7 +582,8 @@ fn bench_logical_operators(c: &mut Criterion) {
                                                     term_guard::core::LogicalOperator::Any,
                                                 )
                                                 .with_threshold(1.0),
-                                        ),
+                                        )
+                                        .unwrap(),
                                     )
                                     .build(),
                             )
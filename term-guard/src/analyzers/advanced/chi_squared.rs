@@ -0,0 +1,643 @@
+//! Chi-squared goodness-of-fit analyzer for categorical distribution stability.
+
+use arrow::array::{Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tracing::instrument;
+
+use crate::analyzers::{Analyzer, AnalyzerError, AnalyzerResult, AnalyzerState, MetricValue};
+use crate::constraints::Histogram;
+use crate::core::current_validation_context;
+use crate::security::SqlSecurity;
+
+/// Default minimum expected frequency of a bin, following Cochran's rule.
+pub const DEFAULT_MIN_EXPECTED_FREQUENCY: f64 = 5.0;
+
+/// Number of categories reported as top contributors to the statistic.
+const TOP_CONTRIBUTORS: usize = 5;
+
+/// A reference distribution of categorical values.
+///
+/// Frequencies are relative weights: counts from an earlier run and proportions
+/// work equally well, since the test scales them to the current number of rows.
+///
+/// # Example
+///
+/// ```rust
+/// use term_guard::analyzers::advanced::ReferenceDistribution;
+///
+/// let reference = ReferenceDistribution::from_frequencies([("card", 700.0), ("cash", 300.0)]).unwrap();
+/// assert_eq!(reference.proportion("card"), 0.7);
+/// assert_eq!(reference.proportion("voucher"), 0.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceDistribution {
+    frequencies: BTreeMap<String, f64>,
+    total: f64,
+}
+
+impl ReferenceDistribution {
+    /// Creates a reference from category frequencies.
+    ///
+    /// Fails when a frequency is negative or not finite, or when all frequencies are zero.
+    pub fn from_frequencies<I, K>(frequencies: I) -> AnalyzerResult<Self>
+    where
+        I: IntoIterator<Item = (K, f64)>,
+        K: Into<String>,
+    {
+        let mut merged = BTreeMap::new();
+        for (category, frequency) in frequencies {
+            if !frequency.is_finite() || frequency < 0.0 {
+                return Err(AnalyzerError::invalid_config(format!(
+                    "Reference frequencies must be finite and non-negative, got {frequency}"
+                )));
+            }
+            *merged.entry(category.into()).or_insert(0.0) += frequency;
+        }
+
+        let total: f64 = merged.values().sum();
+        if total <= 0.0 {
+            return Err(AnalyzerError::invalid_config(
+                "Reference distribution must have a positive total frequency",
+            ));
+        }
+
+        Ok(Self {
+            frequencies: merged,
+            total,
+        })
+    }
+
+    /// Creates a reference from the buckets of a categorical [`Histogram`].
+    pub fn from_histogram(histogram: &Histogram) -> AnalyzerResult<Self> {
+        Self::from_frequencies(
+            histogram
+                .buckets
+                .iter()
+                .map(|bucket| (bucket.value.clone(), bucket.count as f64)),
+        )
+    }
+
+    /// Reads a reference from a stored metric.
+    ///
+    /// Accepts a map of categories to numeric frequencies, as written by
+    /// [`to_metric`](Self::to_metric), or the metric of a [`ChiSquaredAnalyzer`],
+    /// whose `observed` frequencies become the reference.
+    pub fn from_metric(metric: &MetricValue) -> AnalyzerResult<Self> {
+        let MetricValue::Map(map) = metric else {
+            return Err(AnalyzerError::invalid_data(format!(
+                "Expected a map of category frequencies, got {}",
+                metric.to_string_pretty()
+            )));
+        };
+
+        if let Some(observed) = map.get("observed") {
+            return Self::from_metric(observed);
+        }
+
+        let frequencies = map
+            .iter()
+            .map(|(category, value)| {
+                value
+                    .as_f64()
+                    .map(|frequency| (category.clone(), frequency))
+                    .ok_or_else(|| {
+                        AnalyzerError::invalid_data(format!(
+                            "Frequency of category '{category}' is not numeric"
+                        ))
+                    })
+            })
+            .collect::<AnalyzerResult<Vec<_>>>()?;
+        Self::from_frequencies(frequencies)
+    }
+
+    /// Converts the reference into a metric that can be stored in an
+    /// [`AnalyzerContext`](crate::analyzers::AnalyzerContext).
+    pub fn to_metric(&self) -> MetricValue {
+        MetricValue::Map(
+            self.frequencies
+                .iter()
+                .map(|(category, frequency)| (category.clone(), MetricValue::Double(*frequency)))
+                .collect(),
+        )
+    }
+
+    /// Returns the share of `category` in the reference, 0.0 for unknown categories.
+    pub fn proportion(&self, category: &str) -> f64 {
+        self.frequencies.get(category).copied().unwrap_or(0.0) / self.total
+    }
+
+    /// Returns the categories of the reference.
+    pub fn categories(&self) -> impl Iterator<Item = &str> {
+        self.frequencies.keys().map(String::as_str)
+    }
+}
+
+/// A bin of the chi-squared test, holding one category or several pooled ones.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChiSquaredBin {
+    /// The categories in the bin
+    pub categories: Vec<String>,
+    /// Number of observed values in the bin
+    pub observed: u64,
+    /// Number of values expected from the reference distribution
+    pub expected: f64,
+    /// The bin's term of the statistic, (observed - expected)² / expected
+    pub contribution: f64,
+}
+
+impl ChiSquaredBin {
+    /// Returns the bin label: its category, or the pooled categories in braces.
+    pub fn label(&self) -> String {
+        match self.categories.as_slice() {
+            [category] => category.clone(),
+            categories => format!("{{{}}}", categories.join(", ")),
+        }
+    }
+}
+
+/// Result of a chi-squared goodness-of-fit test.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChiSquaredTest {
+    /// The chi-squared statistic
+    pub statistic: f64,
+    /// Degrees of freedom: the number of bins minus one
+    pub degrees_of_freedom: usize,
+    /// Probability of a statistic at least this large if the data follows the reference
+    pub p_value: f64,
+    /// The bins after pooling, ordered by contribution (largest first)
+    pub bins: Vec<ChiSquaredBin>,
+}
+
+impl ChiSquaredTest {
+    /// Tests observed category counts against a reference distribution.
+    ///
+    /// Bins whose expected frequency is below `min_expected_frequency` are pooled into
+    /// one bin, as are categories absent from the reference. While the pooled bin is
+    /// still below the minimum, the bin with the smallest expected frequency is merged
+    /// into it. Returns `None` when fewer than two bins remain, since the test is then
+    /// undefined.
+    pub fn compute(
+        observed: &BTreeMap<String, u64>,
+        reference: &ReferenceDistribution,
+        min_expected_frequency: f64,
+    ) -> Option<Self> {
+        let total: u64 = observed.values().sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut categories: BTreeMap<&str, (u64, f64)> = reference
+            .categories()
+            .map(|category| (category, (0, total as f64 * reference.proportion(category))))
+            .collect();
+        for (category, count) in observed {
+            categories.entry(category.as_str()).or_insert((0, 0.0)).0 = *count;
+        }
+
+        let (mut bins, pooled): (Vec<_>, Vec<_>) = categories
+            .into_iter()
+            .map(|(category, (observed, expected))| ChiSquaredBin {
+                categories: vec![category.to_string()],
+                observed,
+                expected,
+                contribution: 0.0,
+            })
+            .partition(|bin| bin.expected > 0.0 && bin.expected >= min_expected_frequency);
+
+        if !pooled.is_empty() {
+            let mut pool = ChiSquaredBin {
+                categories: Vec::new(),
+                observed: 0,
+                expected: 0.0,
+                contribution: 0.0,
+            };
+            for bin in pooled {
+                pool.categories.extend(bin.categories);
+                pool.observed += bin.observed;
+                pool.expected += bin.expected;
+            }
+
+            bins.sort_by(|a, b| b.expected.total_cmp(&a.expected));
+            while pool.expected <= 0.0 || pool.expected < min_expected_frequency {
+                let Some(smallest) = bins.pop() else {
+                    break;
+                };
+                pool.categories.extend(smallest.categories);
+                pool.observed += smallest.observed;
+                pool.expected += smallest.expected;
+            }
+            pool.categories.sort();
+            bins.push(pool);
+        }
+
+        if bins.len() < 2 {
+            return None;
+        }
+
+        for bin in &mut bins {
+            let difference = bin.observed as f64 - bin.expected;
+            bin.contribution = difference * difference / bin.expected;
+        }
+        bins.sort_by(|a, b| b.contribution.total_cmp(&a.contribution));
+
+        let statistic: f64 = bins.iter().map(|bin| bin.contribution).sum();
+        let degrees_of_freedom = bins.len() - 1;
+        Some(Self {
+            statistic,
+            degrees_of_freedom,
+            p_value: chi_squared_survival(statistic, degrees_of_freedom as f64),
+            bins,
+        })
+    }
+
+    /// Returns the bins contributing most to the statistic.
+    pub fn top_contributors(&self, n: usize) -> &[ChiSquaredBin] {
+        &self.bins[..n.min(self.bins.len())]
+    }
+}
+
+/// Upper tail probability of the chi-squared distribution, P(X ≥ statistic).
+fn chi_squared_survival(statistic: f64, degrees_of_freedom: f64) -> f64 {
+    if statistic <= 0.0 {
+        return 1.0;
+    }
+    regularized_gamma_q(degrees_of_freedom / 2.0, statistic / 2.0)
+}
+
+/// Natural logarithm of the gamma function (Lanczos approximation).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.001_208_650_973_866_179,
+        -0.000_005_395_239_384_953,
+    ];
+
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000_000_000_190_015;
+    let mut y = x;
+    for coefficient in COEFFICIENTS {
+        y += 1.0;
+        series += coefficient / y;
+    }
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+/// Regularized upper incomplete gamma function Q(a, x).
+fn regularized_gamma_q(a: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 500;
+    const EPSILON: f64 = 1e-14;
+    const TINY: f64 = 1e-300;
+
+    let prefix = (-x + a * x.ln() - ln_gamma(a)).exp();
+
+    if x < a + 1.0 {
+        // Series for P(a, x), converges quickly below a + 1
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut denominator = a;
+        for _ in 0..MAX_ITERATIONS {
+            denominator += 1.0;
+            term *= x / denominator;
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        (1.0 - sum * prefix).clamp(0.0, 1.0)
+    } else {
+        // Continued fraction for Q(a, x), evaluated with Lentz's method
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut fraction = d;
+        for i in 1..=MAX_ITERATIONS {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            fraction *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+        (prefix * fraction).clamp(0.0, 1.0)
+    }
+}
+
+/// Analyzer that tests whether the categorical distribution of a column matches a
+/// reference distribution, using Pearson's chi-squared goodness-of-fit test.
+///
+/// # Metrics Computed
+///
+/// - **observed**: Frequency of each category in the current data
+/// - **statistic**: The chi-squared statistic
+/// - **degrees_of_freedom**: Number of bins after pooling, minus one
+/// - **p_value**: Probability of a statistic at least this large under the reference
+/// - **top_contributors**: The bins contributing most to the statistic
+///
+/// When fewer than two bins remain after pooling, only the observed frequencies and
+/// the total count are reported.
+///
+/// # Example
+///
+/// ```rust
+/// use term_guard::analyzers::advanced::{ChiSquaredAnalyzer, ReferenceDistribution};
+/// use term_guard::analyzers::{Analyzer, MetricValue};
+/// use datafusion::prelude::*;
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let ctx = SessionContext::new();
+/// ctx.sql("CREATE TABLE data (method VARCHAR) AS VALUES ('card'), ('card'), ('cash')")
+///     .await?
+///     .collect()
+///     .await?;
+///
+/// let reference = ReferenceDistribution::from_frequencies([("card", 50.0), ("cash", 50.0)])?;
+/// let analyzer = ChiSquaredAnalyzer::new("method", reference).with_min_expected_frequency(1.0);
+/// let state = analyzer.compute_state_from_data(&ctx).await?;
+///
+/// if let MetricValue::Map(metrics) = analyzer.compute_metric_from_state(&state)? {
+///     println!("p-value: {:?}", metrics.get("p_value"));
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChiSquaredAnalyzer {
+    /// The column to analyze.
+    column: String,
+    /// The distribution the column is expected to follow.
+    reference: ReferenceDistribution,
+    /// Bins with a lower expected frequency are pooled.
+    min_expected_frequency: f64,
+}
+
+impl ChiSquaredAnalyzer {
+    /// Creates a new chi-squared analyzer for the specified column.
+    pub fn new(column: impl Into<String>, reference: ReferenceDistribution) -> Self {
+        Self {
+            column: column.into(),
+            reference,
+            min_expected_frequency: DEFAULT_MIN_EXPECTED_FREQUENCY,
+        }
+    }
+
+    /// Sets the minimum expected frequency below which bins are pooled (default 5).
+    pub fn with_min_expected_frequency(mut self, min_expected_frequency: f64) -> Self {
+        self.min_expected_frequency = min_expected_frequency.max(0.0);
+        self
+    }
+
+    /// Returns the column being analyzed.
+    pub fn column(&self) -> &str {
+        &self.column
+    }
+
+    /// Returns the reference distribution.
+    pub fn reference(&self) -> &ReferenceDistribution {
+        &self.reference
+    }
+
+    /// Runs the test on the frequencies of a computed state.
+    pub fn test(&self, state: &ChiSquaredState) -> Option<ChiSquaredTest> {
+        ChiSquaredTest::compute(
+            &state.observed,
+            &self.reference,
+            self.min_expected_frequency,
+        )
+    }
+}
+
+/// State for the chi-squared analyzer.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChiSquaredState {
+    /// Count of occurrences for each category.
+    pub observed: BTreeMap<String, u64>,
+    /// Total count of non-null values.
+    pub total_count: u64,
+}
+
+impl AnalyzerState for ChiSquaredState {
+    fn merge(states: Vec<Self>) -> AnalyzerResult<Self> {
+        let mut merged = ChiSquaredState::default();
+        for state in states {
+            merged.total_count += state.total_count;
+            for (category, count) in state.observed {
+                *merged.observed.entry(category).or_insert(0) += count;
+            }
+        }
+        Ok(merged)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.total_count == 0
+    }
+}
+
+#[async_trait]
+impl Analyzer for ChiSquaredAnalyzer {
+    type State = ChiSquaredState;
+    type Metric = MetricValue;
+
+    #[instrument(skip(ctx), fields(analyzer = "chi_squared", column = %self.column))]
+    async fn compute_state_from_data(&self, ctx: &SessionContext) -> AnalyzerResult<Self::State> {
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+        let column = SqlSecurity::escape_identifier(&self.column)
+            .map_err(|e| AnalyzerError::invalid_config(e.to_string()))?;
+
+        let sql = format!(
+            "SELECT CAST({column} AS VARCHAR) AS value, COUNT(*) AS count
+            FROM {table_name}
+            WHERE {column} IS NOT NULL
+            GROUP BY CAST({column} AS VARCHAR)"
+        );
+        let batches = ctx.sql(&sql).await?.collect().await?;
+
+        let mut state = ChiSquaredState::default();
+        for batch in &batches {
+            // The cast can produce Utf8View, normalize before downcasting
+            let values = cast(batch.column(0), &DataType::Utf8)?;
+            let values = values
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .ok_or_else(|| AnalyzerError::invalid_data("Expected string categories"))?;
+            let counts = batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .ok_or_else(|| AnalyzerError::invalid_data("Expected Int64 array for counts"))?;
+
+            for i in 0..batch.num_rows() {
+                let count = counts.value(i) as u64;
+                state.observed.insert(values.value(i).to_string(), count);
+                state.total_count += count;
+            }
+        }
+
+        Ok(state)
+    }
+
+    fn compute_metric_from_state(&self, state: &Self::State) -> AnalyzerResult<Self::Metric> {
+        let mut metrics = HashMap::new();
+        metrics.insert(
+            "observed".to_string(),
+            MetricValue::Map(
+                state
+                    .observed
+                    .iter()
+                    .map(|(category, count)| (category.clone(), MetricValue::Long(*count as i64)))
+                    .collect(),
+            ),
+        );
+        metrics.insert(
+            "total_count".to_string(),
+            MetricValue::Long(state.total_count as i64),
+        );
+
+        if let Some(test) = self.test(state) {
+            metrics.insert("statistic".to_string(), MetricValue::Double(test.statistic));
+            metrics.insert(
+                "degrees_of_freedom".to_string(),
+                MetricValue::Long(test.degrees_of_freedom as i64),
+            );
+            metrics.insert("p_value".to_string(), MetricValue::Double(test.p_value));
+            metrics.insert(
+                "top_contributors".to_string(),
+                MetricValue::Map(
+                    test.top_contributors(TOP_CONTRIBUTORS)
+                        .iter()
+                        .map(|bin| (bin.label(), MetricValue::Double(bin.contribution)))
+                        .collect(),
+                ),
+            );
+        }
+
+        Ok(MetricValue::Map(metrics))
+    }
+
+    fn name(&self) -> &str {
+        "chi_squared"
+    }
+
+    fn description(&self) -> &str {
+        "Tests a categorical distribution against a reference with a chi-squared test"
+    }
+
+    fn metric_key(&self) -> String {
+        format!("{}.{}", self.name(), self.column)
+    }
+
+    fn columns(&self) -> Vec<&str> {
+        vec![&self.column]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observed(counts: &[(&str, u64)]) -> BTreeMap<String, u64> {
+        counts
+            .iter()
+            .map(|(category, count)| (category.to_string(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn test_p_value_matches_tables() {
+        // Critical values of the chi-squared distribution at p = 0.05
+        assert!((chi_squared_survival(3.841_458_8, 1.0) - 0.05).abs() < 1e-6);
+        assert!((chi_squared_survival(11.070_497_7, 5.0) - 0.05).abs() < 1e-6);
+        assert!((chi_squared_survival(18.307_038, 10.0) - 0.05).abs() < 1e-6);
+        assert_eq!(chi_squared_survival(0.0, 3.0), 1.0);
+    }
+
+    #[test]
+    fn test_matching_distribution() {
+        let reference = ReferenceDistribution::from_frequencies([("a", 0.5), ("b", 0.5)]).unwrap();
+        let test =
+            ChiSquaredTest::compute(&observed(&[("a", 50), ("b", 50)]), &reference, 5.0).unwrap();
+
+        assert_eq!(test.statistic, 0.0);
+        assert_eq!(test.degrees_of_freedom, 1);
+        assert_eq!(test.p_value, 1.0);
+    }
+
+    #[test]
+    fn test_low_expected_bins_are_pooled() {
+        let reference = ReferenceDistribution::from_frequencies([
+            ("a", 60.0),
+            ("b", 36.0),
+            ("c", 2.0),
+            ("d", 2.0),
+        ])
+        .unwrap();
+        // "new" is absent from the reference and is pooled with the rare categories
+        let test = ChiSquaredTest::compute(
+            &observed(&[("a", 55), ("b", 35), ("c", 3), ("d", 2), ("new", 5)]),
+            &reference,
+            5.0,
+        )
+        .unwrap();
+
+        // c, d and new pool to expected 4 < 5, so b (the smallest bin) joins them
+        let labels: Vec<_> = test.bins.iter().map(ChiSquaredBin::label).collect();
+        assert_eq!(test.bins.len(), 2);
+        assert!(labels.contains(&"{b, c, d, new}".to_string()), "{labels:?}");
+        assert_eq!(test.degrees_of_freedom, 1);
+        assert!(test.bins.iter().all(|bin| bin.expected >= 5.0));
+
+        let statistic = 5.0_f64.powi(2) / 60.0 + 5.0_f64.powi(2) / 40.0;
+        assert!((test.statistic - statistic).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_single_bin_is_undefined() {
+        let reference = ReferenceDistribution::from_frequencies([("a", 1.0)]).unwrap();
+        assert!(ChiSquaredTest::compute(&observed(&[("a", 10)]), &reference, 5.0).is_none());
+        assert!(ChiSquaredTest::compute(&BTreeMap::new(), &reference, 5.0).is_none());
+    }
+
+    #[test]
+    fn test_reference_from_metric() {
+        let reference = ReferenceDistribution::from_frequencies([("a", 3.0), ("b", 1.0)]).unwrap();
+        assert_eq!(
+            ReferenceDistribution::from_metric(&reference.to_metric()).unwrap(),
+            reference
+        );
+
+        let analyzer_metric = MetricValue::Map(HashMap::from([(
+            "observed".to_string(),
+            MetricValue::Map(HashMap::from([
+                ("a".to_string(), MetricValue::Long(3)),
+                ("b".to_string(), MetricValue::Long(1)),
+            ])),
+        )]));
+        assert_eq!(
+            ReferenceDistribution::from_metric(&analyzer_metric).unwrap(),
+            reference
+        );
+
+        assert!(ReferenceDistribution::from_metric(&MetricValue::Double(1.0)).is_err());
+        assert!(ReferenceDistribution::from_frequencies([("a", -1.0)]).is_err());
+        assert!(ReferenceDistribution::from_frequencies([("a", 0.0)]).is_err());
+    }
+}
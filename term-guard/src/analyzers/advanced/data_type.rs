@@ -5,8 +5,10 @@ use async_trait::async_trait;
 use datafusion::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::instrument;
 
+use crate::analyzers::inference::{string_values, TypeInferenceEngine};
 use crate::analyzers::{Analyzer, AnalyzerError, AnalyzerResult, AnalyzerState, MetricValue};
 use crate::core::current_validation_context;
 
@@ -41,6 +43,8 @@ use crate::core::current_validation_context;
 pub struct DataTypeAnalyzer {
     /// The column to analyze.
     column: String,
+    /// Optional inference engine used to classify values.
+    inference_engine: Option<Arc<TypeInferenceEngine>>,
 }
 
 impl DataTypeAnalyzer {
@@ -48,9 +52,21 @@ impl DataTypeAnalyzer {
     pub fn new(column: impl Into<String>) -> Self {
         Self {
             column: column.into(),
+            inference_engine: None,
         }
     }
 
+    /// Classifies values with a [`TypeInferenceEngine`] instead of SQL casts.
+    ///
+    /// Up to the engine's sample size of non-null values are classified, and the
+    /// type counts are keyed by the engine's type labels (e.g. `Integer`, `Date`,
+    /// or a custom detector's type name). This keeps the analyzer consistent with
+    /// the locale and custom detectors used for inference.
+    pub fn with_inference_engine(mut self, engine: Arc<TypeInferenceEngine>) -> Self {
+        self.inference_engine = Some(engine);
+        self
+    }
+
     /// Returns the column being analyzed.
     pub fn column(&self) -> &str {
         &self.column
@@ -124,6 +140,28 @@ impl Analyzer for DataTypeAnalyzer {
         let validation_ctx = current_validation_context();
         let table_name = validation_ctx.table_name();
 
+        if let Some(engine) = &self.inference_engine {
+            let sql = format!(
+                "SELECT {0} FROM {table_name} WHERE {0} IS NOT NULL LIMIT {1}",
+                self.column,
+                engine.config().sample_size
+            );
+            let batches = ctx.sql(&sql).await?.collect().await?;
+
+            let mut type_counts = HashMap::new();
+            let mut total_count = 0;
+            for value in string_values(&batches)? {
+                let inferred = engine.classify_value(&value);
+                *type_counts.entry(inferred.label().to_string()).or_insert(0) += 1;
+                total_count += 1;
+            }
+
+            return Ok(DataTypeState {
+                type_counts,
+                total_count,
+            });
+        }
+
         // Build SQL query to categorize values by their inferred type
         // This uses SQL type checking functions to infer types
         let sql = format!(
@@ -1,6 +1,8 @@
 //! Histogram analyzer for computing value distributions.
 
-use arrow::array::Array;
+use arrow::array::{Array, Float64Array, Int64Array};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
 use async_trait::async_trait;
 use datafusion::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -18,17 +20,24 @@ use crate::core::current_validation_context;
 /// providing insights into data distribution patterns. It's memory-efficient
 /// even for high-cardinality columns by using fixed-size buckets.
 ///
+/// Buckets have equal widths by default. On skewed columns, such as revenue,
+/// almost every value lands in the first of them; [`BinningStrategy::EqualFrequency`]
+/// places the edges at approximate quantiles instead, so each bucket holds a
+/// similar number of values. The edges are reported in
+/// [`MetricDistribution::bin_edges`].
+///
 /// # Example
 ///
 /// ```rust,ignore
-/// use term_guard::analyzers::advanced::HistogramAnalyzer;
+/// use term_guard::analyzers::advanced::{BinningStrategy, HistogramAnalyzer};
 /// use datafusion::prelude::*;
 ///
 /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
 /// let ctx = SessionContext::new();
 /// // Register your data table
 ///
-/// let analyzer = HistogramAnalyzer::new("price", 10); // 10 buckets
+/// let analyzer = HistogramAnalyzer::new("price", 10) // 10 buckets
+///     .with_strategy(BinningStrategy::EqualFrequency);
 /// let state = analyzer.compute_state_from_data(&ctx).await?;
 /// let metric = analyzer.compute_metric_from_state(&state)?;
 ///
@@ -48,6 +57,24 @@ pub struct HistogramAnalyzer {
     column: String,
     /// Number of histogram buckets.
     num_buckets: usize,
+    /// How the bucket edges are placed.
+    strategy: BinningStrategy,
+}
+
+/// How [`HistogramAnalyzer`] places the edges of its buckets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BinningStrategy {
+    /// Buckets of equal width between the minimum and the maximum.
+    #[default]
+    EqualWidth,
+    /// Buckets holding roughly the same number of values, with edges at
+    /// approximate quantiles computed in a first pass over the column.
+    ///
+    /// Ties can make several quantiles coincide, for example when most values
+    /// are zero; the buckets between them are merged, so fewer buckets than
+    /// requested are returned. The last bucket includes the maximum.
+    EqualFrequency,
 }
 
 impl HistogramAnalyzer {
@@ -61,9 +88,16 @@ impl HistogramAnalyzer {
         Self {
             column: column.into(),
             num_buckets: num_buckets.clamp(1, 1000),
+            strategy: BinningStrategy::default(),
         }
     }
 
+    /// Sets how the bucket edges are placed.
+    pub fn with_strategy(mut self, strategy: BinningStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
     /// Returns the column being analyzed.
     pub fn column(&self) -> &str {
         &self.column
@@ -73,6 +107,148 @@ impl HistogramAnalyzer {
     pub fn num_buckets(&self) -> usize {
         self.num_buckets
     }
+
+    /// Returns how the bucket edges are placed.
+    pub fn strategy(&self) -> BinningStrategy {
+        self.strategy
+    }
+
+    /// Returns the edges of `num_buckets` buckets of equal width.
+    ///
+    /// The last edge lies slightly above the maximum, so that the maximum falls
+    /// inside the last bucket.
+    fn equal_width_edges(&self, min_value: f64, max_value: f64) -> Vec<f64> {
+        let range = max_value - min_value;
+        let bucket_width = if range > 0.0 && self.num_buckets > 1 {
+            range / self.num_buckets as f64
+        } else {
+            1.0
+        };
+        (0..self.num_buckets)
+            .map(|i| min_value + i as f64 * bucket_width)
+            .chain(std::iter::once(max_value + bucket_width * 0.001))
+            .collect()
+    }
+
+    /// Returns strictly increasing edges from the minimum to the maximum at the
+    /// approximate quantiles splitting the values into `num_buckets` buckets.
+    ///
+    /// Coinciding quantiles are merged into one edge.
+    async fn quantile_edges(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        min_value: f64,
+        max_value: f64,
+    ) -> AnalyzerResult<Vec<f64>> {
+        let mut edges = vec![min_value];
+        if self.num_buckets > 1 && max_value > min_value {
+            let quantiles = (1..self.num_buckets)
+                .map(|i| {
+                    let fraction = i as f64 / self.num_buckets as f64;
+                    format!(
+                        "APPROX_PERCENTILE_CONT({fraction:?}) WITHIN GROUP (ORDER BY CAST({} AS DOUBLE)) AS q{i}",
+                        self.column
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let sql = format!(
+                "SELECT {quantiles} FROM {table_name} WHERE {} IS NOT NULL",
+                self.column
+            );
+            let batches = ctx.sql(&sql).await?.collect().await?;
+            if let Some(batch) = batches.first().filter(|batch| batch.num_rows() > 0) {
+                for column in batch.columns() {
+                    let values = cast(column, &DataType::Float64)?;
+                    let values =
+                        values
+                            .as_any()
+                            .downcast_ref::<Float64Array>()
+                            .ok_or_else(|| {
+                                AnalyzerError::invalid_data("Expected Float64 for quantile")
+                            })?;
+                    if values.is_null(0) {
+                        continue;
+                    }
+                    let edge = values.value(0).clamp(min_value, max_value);
+                    if edges.last().is_some_and(|last| edge > *last) {
+                        edges.push(edge);
+                    }
+                }
+            }
+        }
+        if edges.last().is_some_and(|last| max_value > *last) || edges.len() == 1 {
+            edges.push(max_value);
+        }
+        Ok(edges)
+    }
+
+    /// Counts the non-null values in each bucket between consecutive `edges`.
+    ///
+    /// Values below the second edge are counted in the first bucket and values
+    /// from the second to last edge on in the last one.
+    async fn count_per_bin(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        edges: &[f64],
+        total_count: u64,
+    ) -> AnalyzerResult<Vec<u64>> {
+        let bins = edges.len() - 1;
+        let mut counts = vec![0; bins];
+        if bins == 1 {
+            counts[0] = total_count;
+            return Ok(counts);
+        }
+
+        // Build histogram query using CASE statement since WIDTH_BUCKET is not available
+        let case_clauses = edges[1..bins]
+            .iter()
+            .enumerate()
+            .map(|(i, upper)| format!("WHEN {} < {upper:?} THEN {}", self.column, i + 1))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let histogram_sql = format!(
+            "SELECT 
+                CASE 
+                    {case_clauses}
+                    ELSE {bins}
+                END as bucket_num,
+                COUNT(*) as count
+            FROM {table_name}
+            WHERE {} IS NOT NULL
+            GROUP BY bucket_num
+            ORDER BY bucket_num",
+            self.column
+        );
+
+        let hist_df = ctx.sql(&histogram_sql).await?;
+        let hist_batches = hist_df.collect().await?;
+
+        // Fill in counts from query results
+        for batch in &hist_batches {
+            let bucket_array = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .ok_or_else(|| AnalyzerError::invalid_data("Expected Int64 for bucket_num"))?;
+
+            let count_array = batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .ok_or_else(|| AnalyzerError::invalid_data("Expected Int64 for count"))?;
+
+            for i in 0..batch.num_rows() {
+                let bucket_idx = (bucket_array.value(i) - 1) as usize;
+                if let Some(count) = counts.get_mut(bucket_idx) {
+                    *count = count_array.value(i) as u64;
+                }
+            }
+        }
+        Ok(counts)
+    }
 }
 
 /// State for the histogram analyzer.
@@ -174,7 +350,7 @@ impl Analyzer for HistogramAnalyzer {
     type State = HistogramState;
     type Metric = MetricValue;
 
-    #[instrument(skip(ctx), fields(analyzer = "histogram", column = %self.column, buckets = %self.num_buckets))]
+    #[instrument(skip(ctx), fields(analyzer = "histogram", column = %self.column, buckets = %self.num_buckets, strategy = ?self.strategy))]
     async fn compute_state_from_data(&self, ctx: &SessionContext) -> AnalyzerResult<Self::State> {
         // Get the table name from the validation context
         let validation_ctx = current_validation_context();
@@ -203,35 +379,35 @@ impl Analyzer for HistogramAnalyzer {
                 let min_val = batch
                     .column(0)
                     .as_any()
-                    .downcast_ref::<arrow::array::Float64Array>()
+                    .downcast_ref::<Float64Array>()
                     .ok_or_else(|| AnalyzerError::invalid_data("Expected Float64 for min"))?
                     .value(0);
 
                 let max_val = batch
                     .column(1)
                     .as_any()
-                    .downcast_ref::<arrow::array::Float64Array>()
+                    .downcast_ref::<Float64Array>()
                     .ok_or_else(|| AnalyzerError::invalid_data("Expected Float64 for max"))?
                     .value(0);
 
                 let count = batch
                     .column(2)
                     .as_any()
-                    .downcast_ref::<arrow::array::Int64Array>()
+                    .downcast_ref::<Int64Array>()
                     .ok_or_else(|| AnalyzerError::invalid_data("Expected Int64 for count"))?
                     .value(0) as u64;
 
                 let sum_val = batch
                     .column(3)
                     .as_any()
-                    .downcast_ref::<arrow::array::Float64Array>()
+                    .downcast_ref::<Float64Array>()
                     .ok_or_else(|| AnalyzerError::invalid_data("Expected Float64 for sum"))?
                     .value(0);
 
                 let sum_sq = batch
                     .column(4)
                     .as_any()
-                    .downcast_ref::<arrow::array::Float64Array>()
+                    .downcast_ref::<Float64Array>()
                     .ok_or_else(|| AnalyzerError::invalid_data("Expected Float64 for sum_squared"))?
                     .value(0);
 
@@ -251,92 +427,21 @@ impl Analyzer for HistogramAnalyzer {
             return Err(AnalyzerError::NoData);
         };
 
-        // Calculate bucket width
-        let range = max_value - min_value;
-        let bucket_width = if range > 0.0 && self.num_buckets > 1 {
-            range / self.num_buckets as f64
-        } else {
-            1.0
-        };
-
-        // Build histogram query using CASE statement since WIDTH_BUCKET is not available
-        let mut case_clauses = Vec::new();
-        for i in 0..self.num_buckets {
-            let lower = min_value + (i as f64 * bucket_width);
-            let upper = if i == self.num_buckets - 1 {
-                max_value + bucket_width * 0.001
-            } else {
-                min_value + ((i + 1) as f64 * bucket_width)
-            };
-            case_clauses.push(format!(
-                "WHEN {0} >= {1} AND {0} < {2} THEN {3}",
-                self.column,
-                lower,
-                upper,
-                i + 1
-            ));
-        }
-
-        let histogram_sql = format!(
-            "SELECT 
-                CASE 
-                    {}
-                    ELSE {}
-                END as bucket_num,
-                COUNT(*) as count
-            FROM {table_name}
-            WHERE {} IS NOT NULL
-            GROUP BY bucket_num
-            ORDER BY bucket_num",
-            case_clauses.join(" "),
-            self.num_buckets,
-            self.column
-        );
-
-        let hist_df = ctx.sql(&histogram_sql).await?;
-        let hist_batches = hist_df.collect().await?;
-
-        // Build buckets array
-        let mut buckets = vec![HistogramBucket::new(0.0, 0.0, 0); self.num_buckets];
-
-        // Initialize bucket boundaries
-        for (i, bucket) in buckets.iter_mut().enumerate() {
-            let lower = min_value + (i as f64 * bucket_width);
-            let upper = if i == self.num_buckets - 1 {
-                max_value + bucket_width * 0.001
-            } else {
-                min_value + ((i + 1) as f64 * bucket_width)
-            };
-            *bucket = HistogramBucket::new(lower, upper, 0);
-        }
-
-        // Fill in counts from query results
-        for batch in &hist_batches {
-            let bucket_array = batch
-                .column(0)
-                .as_any()
-                .downcast_ref::<arrow::array::Int64Array>()
-                .ok_or_else(|| AnalyzerError::invalid_data("Expected Int64 for bucket_num"))?;
-
-            let count_array = batch
-                .column(1)
-                .as_any()
-                .downcast_ref::<arrow::array::Int64Array>()
-                .ok_or_else(|| AnalyzerError::invalid_data("Expected Int64 for count"))?;
-
-            for i in 0..batch.num_rows() {
-                let bucket_idx = (bucket_array.value(i) - 1) as usize;
-                let count = count_array.value(i) as u64;
-
-                if bucket_idx < buckets.len() {
-                    buckets[bucket_idx] = HistogramBucket::new(
-                        buckets[bucket_idx].lower_bound,
-                        buckets[bucket_idx].upper_bound,
-                        count,
-                    );
-                }
+        let edges = match self.strategy {
+            BinningStrategy::EqualWidth => self.equal_width_edges(min_value, max_value),
+            BinningStrategy::EqualFrequency => {
+                self.quantile_edges(ctx, table_name, min_value, max_value)
+                    .await?
             }
-        }
+        };
+        let counts = self
+            .count_per_bin(ctx, table_name, &edges, total_count)
+            .await?;
+        let buckets = edges
+            .windows(2)
+            .zip(counts)
+            .map(|(bin, count)| HistogramBucket::new(bin[0], bin[1], count))
+            .collect();
 
         Ok(HistogramState {
             buckets,
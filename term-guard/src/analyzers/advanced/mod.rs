@@ -5,6 +5,7 @@
 //! and information theory metrics. These build on the foundation of basic analyzers.
 
 mod approx_count_distinct;
+mod chi_squared;
 mod compliance;
 mod correlation;
 mod data_type;
@@ -15,11 +16,15 @@ mod mutual_information;
 mod standard_deviation;
 
 pub use approx_count_distinct::{ApproxCountDistinctAnalyzer, ApproxCountDistinctState};
+pub use chi_squared::{
+    ChiSquaredAnalyzer, ChiSquaredBin, ChiSquaredState, ChiSquaredTest, ReferenceDistribution,
+    DEFAULT_MIN_EXPECTED_FREQUENCY,
+};
 pub use compliance::{ComplianceAnalyzer, ComplianceState};
 pub use correlation::{CorrelationAnalyzer, CorrelationState, CorrelationType};
 pub use data_type::{DataTypeAnalyzer, DataTypeState};
 pub use entropy::{EntropyAnalyzer, EntropyState};
-pub use histogram::{HistogramAnalyzer, HistogramState};
+pub use histogram::{BinningStrategy, HistogramAnalyzer, HistogramState};
 pub use kll_sketch::KllSketch;
 pub use mutual_information::{MutualInformationAnalyzer, MutualInformationState};
 pub use standard_deviation::{StandardDeviationAnalyzer, StandardDeviationState};
@@ -176,6 +176,26 @@ async fn test_data_type_analyzer() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_data_type_analyzer_with_inference_engine() -> Result<(), Box<dyn std::error::Error>> {
+    use crate::analyzers::inference::TypeInferenceEngine;
+
+    let ctx = create_test_context().await?;
+
+    let engine = Arc::new(TypeInferenceEngine::new());
+    let analyzer = DataTypeAnalyzer::new("mixed_type").with_inference_engine(engine);
+    let state = analyzer.compute_state_from_data(&ctx).await?;
+    assert_eq!(state.total_count, 10);
+
+    // Type counts use the engine's labels
+    assert_eq!(state.type_counts.get("Integer"), Some(&2));
+    assert_eq!(state.type_counts.get("Boolean"), Some(&3)); // "true", "false" and "0"
+    assert_eq!(state.type_counts.get("Date"), Some(&1));
+    assert!(state.type_counts.contains_key("Text"));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_histogram_analyzer() -> Result<(), Box<dyn std::error::Error>> {
     let ctx = create_test_context().await?;
@@ -206,6 +226,120 @@ async fn test_histogram_analyzer() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Creates a context with 1000 rows of a Pareto-like `revenue` column, a
+/// `tied` column that is zero on 90% of rows and an all-null `empty` column.
+async fn create_skewed_context() -> Result<SessionContext, Box<dyn std::error::Error>> {
+    let ctx = SessionContext::new();
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("revenue", DataType::Float64, false),
+        Field::new("tied", DataType::Float64, false),
+        Field::new("empty", DataType::Float64, true),
+    ]));
+    let revenue: Vec<f64> = (0..1000)
+        .map(|i| 1.0 / (1.0 - i as f64 / 1000.0).powi(2))
+        .collect();
+    let tied: Vec<f64> = (0..1000)
+        .map(|i| if i < 900 { 0.0 } else { (i - 899) as f64 })
+        .collect();
+    let batch = RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(Float64Array::from(revenue)),
+            Arc::new(Float64Array::from(tied)),
+            Arc::new(Float64Array::from(vec![None::<f64>; 1000])),
+        ],
+    )?;
+    ctx.register_batch("data", batch)?;
+    Ok(ctx)
+}
+
+/// Returns the share of values in the fullest bucket.
+fn largest_bucket_share(state: &HistogramState) -> f64 {
+    let largest = state.buckets.iter().map(|b| b.count).max().unwrap_or(0);
+    largest as f64 / state.total_count as f64
+}
+
+#[tokio::test]
+async fn test_histogram_binning_strategies_on_skewed_column(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let ctx = create_skewed_context().await?;
+
+    let equal_width = HistogramAnalyzer::new("revenue", 10);
+    assert_eq!(equal_width.strategy(), BinningStrategy::EqualWidth);
+    let width_state = equal_width.compute_state_from_data(&ctx).await?;
+
+    let equal_frequency =
+        HistogramAnalyzer::new("revenue", 10).with_strategy(BinningStrategy::EqualFrequency);
+    let frequency_state = equal_frequency.compute_state_from_data(&ctx).await?;
+
+    // Equal-width buckets put nearly every value in the first bucket
+    assert!(width_state.buckets[0].count >= 990);
+    assert!(largest_bucket_share(&width_state) > 0.99);
+
+    // Quantile edges spread the values evenly
+    assert_eq!(frequency_state.buckets.len(), 10);
+    assert_eq!(frequency_state.total_count, 1000);
+    assert_eq!(
+        frequency_state.buckets.iter().map(|b| b.count).sum::<u64>(),
+        1000
+    );
+    assert!(
+        largest_bucket_share(&frequency_state) < 0.15,
+        "{:?}",
+        frequency_state.buckets
+    );
+
+    // The edges reconstruct the histogram
+    let MetricValue::Histogram(distribution) =
+        equal_frequency.compute_metric_from_state(&frequency_state)?
+    else {
+        panic!("Expected Histogram metric value");
+    };
+    let edges = &distribution.bin_edges;
+    assert_eq!(edges.len(), distribution.buckets.len() + 1);
+    assert_eq!(edges[0], frequency_state.min_value);
+    assert_eq!(edges[10], frequency_state.max_value);
+    assert!(edges.windows(2).all(|pair| pair[0] < pair[1]));
+    for (i, bucket) in distribution.buckets.iter().enumerate() {
+        assert_eq!(
+            (bucket.lower_bound, bucket.upper_bound),
+            (edges[i], edges[i + 1])
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_equal_frequency_histogram_merges_tied_quantiles(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let ctx = create_skewed_context().await?;
+
+    let analyzer =
+        HistogramAnalyzer::new("tied", 10).with_strategy(BinningStrategy::EqualFrequency);
+    let state = analyzer.compute_state_from_data(&ctx).await?;
+
+    // Nine of the ten quantiles are zero, so their buckets collapse
+    assert!(state.buckets.len() < 10, "{:?}", state.buckets);
+    assert!(!state.buckets.is_empty());
+    assert_eq!(state.buckets.iter().map(|b| b.count).sum::<u64>(), 1000);
+    assert!(state.buckets[0].count >= 900);
+    assert!(state
+        .buckets
+        .windows(2)
+        .all(|pair| pair[0].upper_bound == pair[1].lower_bound
+            && pair[0].lower_bound < pair[1].lower_bound));
+
+    // All-null columns are skipped
+    let analyzer =
+        HistogramAnalyzer::new("empty", 10).with_strategy(BinningStrategy::EqualFrequency);
+    let state = analyzer.compute_state_from_data(&ctx).await?;
+    assert!(state.is_empty());
+    assert!(state.buckets.is_empty());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_standard_deviation_analyzer() -> Result<(), Box<dyn std::error::Error>> {
     let ctx = create_test_context().await?;
@@ -48,7 +48,7 @@
 //! # });
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -157,6 +157,14 @@ pub trait AnomalyDetector: Send + Sync {
 
     /// Returns a description of this detection strategy.
     fn description(&self) -> &str;
+
+    /// Returns the minimum number of historical data points the strategy needs.
+    ///
+    /// The runner skips detection when retention has left less history, so a
+    /// truncated history reports insufficient history instead of an anomaly.
+    fn min_history_size(&self) -> usize {
+        0
+    }
 }
 
 /// Trait for storing and retrieving metric history.
@@ -197,6 +205,9 @@ pub trait MetricsRepository: Send + Sync {
 }
 
 /// Configuration for in-memory metrics repository.
+///
+/// Limits are enforced on every insert. Expired points are also dropped when a
+/// metric's history is queried, so stale data is never returned to detectors.
 #[derive(Debug, Clone)]
 pub struct InMemoryMetricsConfig {
     /// Maximum number of data points per metric (default: 10,000).
@@ -205,6 +216,11 @@ pub struct InMemoryMetricsConfig {
     pub max_metrics: usize,
     /// Maximum age of data points in seconds (default: 30 days).
     pub max_age_seconds: i64,
+    /// Approximate memory budget in bytes across all metrics (default: unbounded).
+    ///
+    /// When the budget is exceeded, the oldest data points across all metrics
+    /// are evicted first.
+    pub max_memory_bytes: Option<usize>,
 }
 
 impl Default for InMemoryMetricsConfig {
@@ -213,6 +229,7 @@ impl Default for InMemoryMetricsConfig {
             max_points_per_metric: 10_000,
             max_metrics: 1_000,
             max_age_seconds: 30 * 24 * 60 * 60, // 30 days
+            max_memory_bytes: None,
         }
     }
 }
@@ -223,151 +240,234 @@ impl Default for InMemoryMetricsConfig {
 /// when used in production scenarios. Configure limits appropriately for your use case.
 #[derive(Clone)]
 pub struct InMemoryMetricsRepository {
-    data: Arc<tokio::sync::RwLock<HashMap<String, Vec<MetricDataPoint>>>>,
+    state: Arc<tokio::sync::RwLock<MetricsState>>,
     config: InMemoryMetricsConfig,
 }
 
-impl InMemoryMetricsRepository {
-    /// Creates a new in-memory metrics repository with default limits.
-    pub fn new() -> Self {
-        Self::with_config(InMemoryMetricsConfig::default())
-    }
+/// Stored histories with running totals, so limits are enforced without
+/// rescanning every data point on insert.
+#[derive(Default)]
+struct MetricsState {
+    /// Histories sorted by timestamp, oldest first
+    data: HashMap<String, VecDeque<MetricDataPoint>>,
+    total_points: usize,
+    estimated_bytes: usize,
+    evictions: EvictionStats,
+}
 
-    /// Creates a new in-memory metrics repository with custom configuration.
-    pub fn with_config(config: InMemoryMetricsConfig) -> Self {
-        Self {
-            data: Arc::new(tokio::sync::RwLock::new(HashMap::new())),
-            config,
+impl MetricsState {
+    fn insert(&mut self, metric_name: &str, point: MetricDataPoint) {
+        if !self.data.contains_key(metric_name) {
+            self.estimated_bytes += metric_overhead(metric_name);
+            self.data.insert(metric_name.to_string(), VecDeque::new());
+        }
+        self.total_points += 1;
+        self.estimated_bytes += point_size(&point);
+
+        let history = self
+            .data
+            .get_mut(metric_name)
+            .expect("history was just inserted");
+        match history.back() {
+            Some(last) if last.timestamp > point.timestamp => {
+                // Out-of-order points keep the history sorted
+                let index = history.partition_point(|p| p.timestamp <= point.timestamp);
+                history.insert(index, point);
+            }
+            _ => history.push_back(point),
         }
     }
 
-    /// Returns the current memory usage statistics.
-    pub async fn memory_stats(&self) -> MemoryStats {
-        let data = self.data.read().await;
-        let mut total_points = 0;
-        let mut oldest_timestamp = None;
-        let mut newest_timestamp = None;
-
-        for history in data.values() {
-            total_points += history.len();
-            for point in history {
-                match oldest_timestamp {
-                    None => oldest_timestamp = Some(point.timestamp),
-                    Some(oldest) if point.timestamp < oldest => {
-                        oldest_timestamp = Some(point.timestamp)
-                    }
-                    _ => {}
-                }
-                match newest_timestamp {
-                    None => newest_timestamp = Some(point.timestamp),
-                    Some(newest) if point.timestamp > newest => {
-                        newest_timestamp = Some(point.timestamp)
-                    }
-                    _ => {}
-                }
-            }
+    /// Removes the oldest point of a metric, dropping the metric once it is empty.
+    fn pop_oldest(&mut self, metric_name: &str) -> bool {
+        let Some(history) = self.data.get_mut(metric_name) else {
+            return false;
+        };
+        let Some(point) = history.pop_front() else {
+            return false;
+        };
+        self.total_points -= 1;
+        self.estimated_bytes -= point_size(&point);
+
+        if history.is_empty() {
+            self.data.remove(metric_name);
+            self.estimated_bytes -= metric_overhead(metric_name);
         }
+        true
+    }
 
-        MemoryStats {
-            total_metrics: data.len(),
-            total_data_points: total_points,
-            oldest_data_point: oldest_timestamp,
-            newest_data_point: newest_timestamp,
-            estimated_memory_bytes: Self::estimate_memory_usage(&data),
+    /// Drops points of one metric recorded before `cutoff`.
+    fn expire_metric(&mut self, metric_name: &str, cutoff: DateTime<Utc>) -> usize {
+        let mut removed = 0;
+        while self
+            .data
+            .get(metric_name)
+            .and_then(|history| history.front())
+            .is_some_and(|oldest| oldest.timestamp < cutoff)
+        {
+            self.pop_oldest(metric_name);
+            removed += 1;
         }
+        self.evictions.by_age += removed as u64;
+        removed
     }
 
-    /// Estimates memory usage in bytes.
-    fn estimate_memory_usage(data: &HashMap<String, Vec<MetricDataPoint>>) -> usize {
-        let mut size = std::mem::size_of::<HashMap<String, Vec<MetricDataPoint>>>();
+    /// Drops points of every metric recorded before `cutoff`.
+    fn expire_all(&mut self, cutoff: DateTime<Utc>) -> usize {
+        let expired: Vec<String> = self
+            .data
+            .iter()
+            .filter(|(_, history)| history.front().is_some_and(|p| p.timestamp < cutoff))
+            .map(|(name, _)| name.clone())
+            .collect();
 
-        for (key, values) in data {
-            size += std::mem::size_of::<String>() + key.len();
-            size += std::mem::size_of::<Vec<MetricDataPoint>>();
-            size += values.len() * std::mem::size_of::<MetricDataPoint>();
+        expired
+            .iter()
+            .map(|name| self.expire_metric(name, cutoff))
+            .sum()
+    }
 
-            // Estimate metadata size
-            for point in values {
-                for (k, v) in &point.metadata {
-                    size += std::mem::size_of::<String>() * 2 + k.len() + v.len();
-                }
-            }
+    /// Keeps the most recent `max_points` points of a metric.
+    fn enforce_point_limit(&mut self, metric_name: &str, max_points: usize) -> usize {
+        let mut removed = 0;
+        while self
+            .data
+            .get(metric_name)
+            .is_some_and(|history| history.len() > max_points)
+        {
+            self.pop_oldest(metric_name);
+            removed += 1;
         }
-
-        size
+        self.evictions.by_count += removed as u64;
+        removed
     }
 
-    /// Performs cleanup of old data points based on configuration limits.
-    async fn cleanup_if_needed(&self) {
-        let mut data = self.data.write().await;
+    /// Evicts the oldest points across all metrics until the budget is met.
+    fn enforce_memory_budget(&mut self, max_bytes: usize) -> usize {
+        let mut removed = 0;
+        while self.estimated_bytes > max_bytes {
+            let oldest = self
+                .data
+                .iter()
+                .filter_map(|(name, history)| history.front().map(|p| (p.timestamp, name)))
+                .min()
+                .map(|(_, name)| name.clone());
+            let Some(metric_name) = oldest else {
+                break;
+            };
+            self.pop_oldest(&metric_name);
+            removed += 1;
+        }
+        self.evictions.by_memory += removed as u64;
+        removed
+    }
+}
 
-        // Remove metrics if we exceed the limit
-        if data.len() > self.config.max_metrics {
-            warn!(
-                current_metrics = data.len(),
-                max_metrics = self.config.max_metrics,
-                "Metrics limit exceeded, removing oldest metrics"
-            );
+/// Approximate bytes used by a metric's name and history container.
+fn metric_overhead(metric_name: &str) -> usize {
+    std::mem::size_of::<String>()
+        + metric_name.len()
+        + std::mem::size_of::<VecDeque<MetricDataPoint>>()
+}
 
-            // Keep the most recently updated metrics
-            let mut metrics_by_latest: Vec<_> = data
-                .iter()
-                .map(|(name, points)| {
-                    let latest = points.iter().map(|p| p.timestamp).max().unwrap_or_default();
-                    (name.clone(), latest)
-                })
-                .collect();
-
-            metrics_by_latest.sort_by(|a, b| b.1.cmp(&a.1)); // Sort by latest timestamp desc
-
-            // Remove oldest metrics
-            let to_remove = metrics_by_latest.len() - self.config.max_metrics;
-            for (metric_name, _) in metrics_by_latest.iter().skip(self.config.max_metrics) {
-                data.remove(metric_name);
-            }
+/// Approximate bytes used by a data point, including heap allocations.
+fn point_size(point: &MetricDataPoint) -> usize {
+    let metadata: usize = point
+        .metadata
+        .iter()
+        .map(|(k, v)| std::mem::size_of::<String>() * 2 + k.len() + v.len())
+        .sum();
+    std::mem::size_of::<MetricDataPoint>() + value_heap_size(&point.value) + metadata
+}
 
-            info!(
-                removed_metrics = to_remove,
-                remaining_metrics = data.len(),
-                "Cleaned up old metrics"
-            );
+fn value_heap_size(value: &MetricValue) -> usize {
+    match value {
+        MetricValue::String(s) => s.len(),
+        MetricValue::Vector(values) => std::mem::size_of_val(values.as_slice()),
+        MetricValue::Histogram(distribution) => {
+            std::mem::size_of_val(distribution.buckets.as_slice())
         }
+        MetricValue::Map(entries) => entries
+            .iter()
+            .map(|(k, v)| {
+                std::mem::size_of::<(String, MetricValue)>() + k.len() + value_heap_size(v)
+            })
+            .sum(),
+        MetricValue::Double(_) | MetricValue::Long(_) | MetricValue::Boolean(_) => 0,
+    }
+}
 
-        // Clean up old data points and enforce per-metric limits
-        let cutoff_time = Utc::now() - Duration::seconds(self.config.max_age_seconds);
-        let mut total_points_removed = 0;
-
-        for (metric_name, points) in data.iter_mut() {
-            let original_len = points.len();
+impl InMemoryMetricsRepository {
+    /// Creates a new in-memory metrics repository with default limits.
+    pub fn new() -> Self {
+        Self::with_config(InMemoryMetricsConfig::default())
+    }
 
-            // Remove points older than cutoff
-            points.retain(|p| p.timestamp >= cutoff_time);
+    /// Creates a new in-memory metrics repository with custom configuration.
+    pub fn with_config(config: InMemoryMetricsConfig) -> Self {
+        Self {
+            state: Arc::new(tokio::sync::RwLock::new(MetricsState::default())),
+            config,
+        }
+    }
 
-            // Limit points per metric (keep most recent)
-            if points.len() > self.config.max_points_per_metric {
-                points.sort_by_key(|p| p.timestamp);
-                let to_keep = points.len() - self.config.max_points_per_metric;
-                points.drain(0..to_keep);
-            }
+    /// Returns the current memory usage statistics.
+    pub async fn memory_stats(&self) -> MemoryStats {
+        let state = self.state.read().await;
 
-            let removed = original_len - points.len();
-            if removed > 0 {
-                total_points_removed += removed;
-                debug!(
-                    metric = metric_name,
-                    removed_points = removed,
-                    remaining_points = points.len(),
-                    "Cleaned up old data points"
-                );
-            }
+        MemoryStats {
+            total_metrics: state.data.len(),
+            total_data_points: state.total_points,
+            oldest_data_point: state
+                .data
+                .values()
+                .filter_map(|history| history.front().map(|p| p.timestamp))
+                .min(),
+            newest_data_point: state
+                .data
+                .values()
+                .filter_map(|history| history.back().map(|p| p.timestamp))
+                .max(),
+            estimated_memory_bytes: state.estimated_bytes,
+            evictions: state.evictions,
         }
+    }
 
-        if total_points_removed > 0 {
-            info!(
-                total_removed = total_points_removed,
-                "Completed data point cleanup"
-            );
+    /// Removes data points older than `max_age_seconds` from every metric.
+    ///
+    /// Expiry is otherwise evaluated lazily on insert and query; call this to
+    /// reclaim memory in services that rarely write.
+    ///
+    /// Returns the number of removed data points.
+    pub async fn purge_expired(&self) -> usize {
+        let mut state = self.state.write().await;
+        let removed = state.expire_all(self.cutoff());
+        if removed > 0 {
+            debug!(removed_points = removed, "Purged expired data points");
         }
+        removed
+    }
+
+    fn cutoff(&self) -> DateTime<Utc> {
+        Utc::now() - Duration::seconds(self.config.max_age_seconds)
+    }
+}
+
+/// Data points evicted by the retention policy, by reason.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvictionStats {
+    /// Points evicted because their metric exceeded `max_points_per_metric`.
+    pub by_count: u64,
+    /// Points evicted because they were older than `max_age_seconds`.
+    pub by_age: u64,
+    /// Points evicted to stay within `max_memory_bytes`.
+    pub by_memory: u64,
+}
+
+impl EvictionStats {
+    /// Total number of evicted data points.
+    pub fn total(&self) -> u64 {
+        self.by_count + self.by_age + self.by_memory
     }
 }
 
@@ -384,6 +484,8 @@ pub struct MemoryStats {
     pub newest_data_point: Option<DateTime<Utc>>,
     /// Estimated memory usage in bytes.
     pub estimated_memory_bytes: usize,
+    /// Data points evicted since the repository was created.
+    pub evictions: EvictionStats,
 }
 
 impl Default for InMemoryMetricsRepository {
@@ -400,44 +502,46 @@ impl MetricsRepository for InMemoryMetricsRepository {
         value: MetricValue,
         timestamp: DateTime<Utc>,
     ) -> AnalyzerResult<()> {
-        // Check limits before storing
-        {
-            let data = self.data.read().await;
-            if data.len() >= self.config.max_metrics && !data.contains_key(metric_name) {
-                return Err(AnalyzerError::Custom(format!(
-                    "Maximum metrics limit ({}) exceeded",
-                    self.config.max_metrics
-                )));
-            }
+        let mut state = self.state.write().await;
 
-            if let Some(existing) = data.get(metric_name) {
-                if existing.len() >= self.config.max_points_per_metric {
-                    // Will be handled by cleanup, but log a warning
-                    warn!(
-                        metric = metric_name,
-                        current_points = existing.len(),
-                        max_points = self.config.max_points_per_metric,
-                        "Metric approaching memory limit"
-                    );
-                }
-            }
+        // Expired points free metric slots before the limit is checked
+        let expired = state.expire_all(self.cutoff());
+
+        if state.data.len() >= self.config.max_metrics && !state.data.contains_key(metric_name) {
+            return Err(AnalyzerError::Custom(format!(
+                "Maximum metrics limit ({}) exceeded",
+                self.config.max_metrics
+            )));
         }
 
-        // Store the new data point
-        {
-            let mut data = self.data.write().await;
-            let entry = data.entry(metric_name.to_string()).or_insert_with(Vec::new);
-            entry.push(MetricDataPoint {
+        state.insert(
+            metric_name,
+            MetricDataPoint {
                 value,
                 timestamp,
                 metadata: HashMap::new(),
-            });
-            // Keep entries sorted by timestamp
-            entry.sort_by_key(|dp| dp.timestamp);
-        }
+            },
+        );
+
+        // A point older than the cutoff is expired straight away
+        let expired = expired + state.expire_metric(metric_name, self.cutoff());
+        let over_count = state.enforce_point_limit(metric_name, self.config.max_points_per_metric);
+        let over_budget = self
+            .config
+            .max_memory_bytes
+            .map_or(0, |max_bytes| state.enforce_memory_budget(max_bytes));
 
-        // Perform cleanup if needed (async to avoid holding lock too long)
-        self.cleanup_if_needed().await;
+        if expired + over_count + over_budget > 0 {
+            debug!(
+                metric = metric_name,
+                expired_points = expired,
+                over_count_points = over_count,
+                over_budget_points = over_budget,
+                remaining_points = state.total_points,
+                estimated_bytes = state.estimated_bytes,
+                "Evicted data points"
+            );
+        }
 
         Ok(())
     }
@@ -449,28 +553,25 @@ impl MetricsRepository for InMemoryMetricsRepository {
         until: Option<DateTime<Utc>>,
         limit: Option<usize>,
     ) -> AnalyzerResult<Vec<MetricDataPoint>> {
-        let data = self.data.read().await;
+        let mut state = self.state.write().await;
+        state.expire_metric(metric_name, self.cutoff());
 
-        if let Some(history) = data.get(metric_name) {
-            let mut filtered: Vec<_> = history
-                .iter()
-                .filter(|dp| {
-                    let after_since = since.map_or(true, |s| dp.timestamp >= s);
-                    let before_until = until.map_or(true, |u| dp.timestamp <= u);
-                    after_since && before_until
-                })
-                .cloned()
-                .collect();
-
-            // Apply limit if specified
-            if let Some(limit) = limit {
-                filtered.truncate(limit);
-            }
+        let Some(history) = state.data.get(metric_name) else {
+            return Ok(Vec::new());
+        };
 
-            Ok(filtered)
-        } else {
-            Ok(Vec::new())
-        }
+        let filtered = history
+            .iter()
+            .filter(|dp| {
+                let after_since = since.map_or(true, |s| dp.timestamp >= s);
+                let before_until = until.map_or(true, |u| dp.timestamp <= u);
+                after_since && before_until
+            })
+            .take(limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect();
+
+        Ok(filtered)
     }
 }
 
@@ -605,6 +706,10 @@ impl AnomalyDetector for RelativeRateOfChangeDetector {
     fn description(&self) -> &str {
         "Detects anomalies when the relative rate of change exceeds a threshold"
     }
+
+    fn min_history_size(&self) -> usize {
+        self.min_history_size
+    }
 }
 
 /// Detects anomalies based on absolute change thresholds.
@@ -703,14 +808,23 @@ impl AnomalyDetector for AbsoluteChangeDetector {
     fn description(&self) -> &str {
         "Detects anomalies when the absolute change exceeds a threshold"
     }
+
+    fn min_history_size(&self) -> usize {
+        self.min_history_size
+    }
 }
 
+/// Fewest samples a standard deviation is computed from.
+const MIN_Z_SCORE_SAMPLES: usize = 2;
+
 /// Detects anomalies using Z-score (standard deviations from mean).
 pub struct ZScoreDetector {
     /// Z-score threshold for anomaly detection (e.g., 3.0 for 3 standard deviations).
     pub z_score_threshold: f64,
 
     /// Minimum history size required for meaningful statistics.
+    ///
+    /// Values below 2 are treated as 2, since a single sample has no spread.
     pub min_history_size: usize,
 }
 
@@ -741,7 +855,14 @@ impl AnomalyDetector for ZScoreDetector {
         current_value: &MetricValue,
         history: &[MetricDataPoint],
     ) -> AnalyzerResult<Option<Anomaly>> {
-        if history.len() < self.min_history_size {
+        let required = AnomalyDetector::min_history_size(self);
+        if history.len() < required {
+            debug!(
+                metric = metric_name,
+                history_size = history.len(),
+                required = required,
+                "Insufficient history for Z-score detection"
+            );
             return Ok(None);
         }
 
@@ -755,7 +876,13 @@ impl AnomalyDetector for ZScoreDetector {
             })
             .collect();
 
-        if numeric_values.len() < self.min_history_size {
+        if numeric_values.len() < required {
+            debug!(
+                metric = metric_name,
+                numeric_values = numeric_values.len(),
+                required = required,
+                "Insufficient numeric history for Z-score detection"
+            );
             return Ok(None);
         }
 
@@ -811,6 +938,10 @@ impl AnomalyDetector for ZScoreDetector {
     fn description(&self) -> &str {
         "Detects anomalies using statistical Z-score analysis"
     }
+
+    fn min_history_size(&self) -> usize {
+        self.min_history_size.max(MIN_Z_SCORE_SAMPLES)
+    }
 }
 
 /// Configuration for anomaly detection.
@@ -878,6 +1009,17 @@ impl AnomalyDetectionRunner {
                         .get_metric_history(metric_name, Some(since), Some(detection_time), None)
                         .await?;
 
+                    if history.len() < detector.min_history_size() {
+                        debug!(
+                            metric = metric_name,
+                            detector = detector.name(),
+                            history_size = history.len(),
+                            required = detector.min_history_size(),
+                            "Insufficient history for anomaly detection"
+                        );
+                        continue;
+                    }
+
                     debug!(
                         metric = metric_name,
                         history_size = history.len(),
@@ -1051,6 +1193,7 @@ mod tests {
             max_metrics: 2,
             max_points_per_metric: 3,
             max_age_seconds: 60,
+            max_memory_bytes: None,
         };
         let repo = InMemoryMetricsRepository::with_config(config);
 
@@ -1109,6 +1252,95 @@ mod tests {
         assert!(final_stats.estimated_memory_bytes > 0);
     }
 
+    #[tokio::test]
+    async fn test_z_score_detector_requires_min_history() {
+        let history: Vec<_> = (0..5)
+            .map(|i| MetricDataPoint {
+                value: MetricValue::Long(100 + i % 2),
+                timestamp: Utc::now() - Duration::hours(i),
+                metadata: HashMap::new(),
+            })
+            .collect();
+        let outlier = MetricValue::Long(1_000);
+
+        // History truncated below the minimum is not an anomaly
+        let detector = ZScoreDetector::new(2.0);
+        assert_eq!(detector.min_history_size(), 10);
+        let result = detector.detect("m", &outlier, &history).await.unwrap();
+        assert!(result.is_none());
+
+        // A single sample has no spread, whatever the configured minimum
+        let detector = ZScoreDetector::new(2.0).with_min_history_size(0);
+        assert_eq!(AnomalyDetector::min_history_size(&detector), 2);
+        let result = detector.detect("m", &outlier, &history[..1]).await.unwrap();
+        assert!(result.is_none());
+        let result = detector.detect("m", &outlier, &history).await.unwrap();
+        assert!(result.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_repository_retention() {
+        let now = Utc::now();
+        let repo = InMemoryMetricsRepository::with_config(InMemoryMetricsConfig {
+            max_age_seconds: 3600,
+            ..Default::default()
+        });
+
+        // Points older than the maximum age are expired on insert
+        repo.store_metric("m", MetricValue::Long(1), now - Duration::hours(2))
+            .await
+            .unwrap();
+        let stats = repo.memory_stats().await;
+        assert_eq!(stats.total_metrics, 0);
+        assert_eq!(stats.total_data_points, 0);
+        assert_eq!(stats.estimated_memory_bytes, 0);
+        assert_eq!(stats.evictions.by_age, 1);
+
+        // Out-of-order points are kept sorted
+        repo.store_metric("m", MetricValue::Long(2), now - Duration::minutes(10))
+            .await
+            .unwrap();
+        repo.store_metric("m", MetricValue::Long(3), now - Duration::minutes(20))
+            .await
+            .unwrap();
+        let history = repo
+            .get_metric_history("m", None, None, None)
+            .await
+            .unwrap();
+        let values: Vec<_> = history.iter().map(|p| p.value.clone()).collect();
+        assert_eq!(values, vec![MetricValue::Long(3), MetricValue::Long(2)]);
+
+        // The memory budget evicts the oldest points across all metrics
+        let point = MetricDataPoint {
+            value: MetricValue::Long(0),
+            timestamp: now,
+            metadata: HashMap::new(),
+        };
+        let budget = metric_overhead("a") + metric_overhead("b") + 3 * point_size(&point);
+        let repo = InMemoryMetricsRepository::with_config(InMemoryMetricsConfig {
+            max_memory_bytes: Some(budget),
+            ..Default::default()
+        });
+        for (i, name) in ["a", "b", "a", "b"].into_iter().enumerate() {
+            let timestamp = now - Duration::minutes(10 - i as i64);
+            repo.store_metric(name, MetricValue::Long(i as i64), timestamp)
+                .await
+                .unwrap();
+        }
+
+        let stats = repo.memory_stats().await;
+        assert_eq!(stats.total_data_points, 3);
+        assert_eq!(stats.evictions.by_memory, 1);
+        assert_eq!(stats.evictions.total(), 1);
+        assert_eq!(stats.estimated_memory_bytes, budget);
+        let history = repo
+            .get_metric_history("a", None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].value, MetricValue::Long(2));
+    }
+
     #[tokio::test]
     async fn test_in_memory_repository() {
         let repo = InMemoryMetricsRepository::new();
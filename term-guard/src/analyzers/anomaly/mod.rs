@@ -55,7 +55,7 @@ pub mod strategy;
 // Re-export detector types
 pub use detector::{
     AbsoluteChangeDetector, Anomaly, AnomalyDetectionConfig, AnomalyDetectionRunner,
-    AnomalyDetectionRunnerBuilder, AnomalyDetector, InMemoryMetricsConfig,
+    AnomalyDetectionRunnerBuilder, AnomalyDetector, EvictionStats, InMemoryMetricsConfig,
     InMemoryMetricsRepository, MemoryStats, MetricDataPoint, MetricsRepository,
     RelativeRateOfChangeDetector, ZScoreDetector,
 };
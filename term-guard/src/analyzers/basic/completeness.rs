@@ -1,5 +1,7 @@
 //! Completeness analyzer for measuring the fraction of non-null values.
 
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use datafusion::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -7,11 +9,13 @@ use tracing::instrument;
 
 use crate::analyzers::{Analyzer, AnalyzerError, AnalyzerResult, AnalyzerState, MetricValue};
 
-use crate::core::current_validation_context;
+use crate::core::{current_validation_context, MissingValuePolicy};
 /// Analyzer that computes the fraction of non-null values for a column.
 ///
 /// Completeness is a fundamental data quality metric that measures
-/// how much of the expected data is actually present.
+/// how much of the expected data is actually present. With a
+/// [`MissingValuePolicy`], empty strings and sentinel values count as missing
+/// too, and the state breaks the missing values down by cause.
 ///
 /// # Example
 ///
@@ -37,6 +41,8 @@ use crate::core::current_validation_context;
 pub struct CompletenessAnalyzer {
     /// The column to analyze.
     column: String,
+    /// Values counted as missing besides NULL.
+    missing_values: Option<MissingValuePolicy>,
 }
 
 impl CompletenessAnalyzer {
@@ -44,9 +50,16 @@ impl CompletenessAnalyzer {
     pub fn new(column: impl Into<String>) -> Self {
         Self {
             column: column.into(),
+            missing_values: None,
         }
     }
 
+    /// Also counts the values matched by `policy` as missing.
+    pub fn with_missing_value_policy(mut self, policy: MissingValuePolicy) -> Self {
+        self.missing_values = (!policy.is_empty()).then_some(policy);
+        self
+    }
+
     /// Returns the column being analyzed.
     pub fn column(&self) -> &str {
         &self.column
@@ -58,8 +71,12 @@ impl CompletenessAnalyzer {
 pub struct CompletenessState {
     /// Total number of rows.
     pub total_count: u64,
-    /// Number of non-null values.
+    /// Number of non-null values, excluding values the missing value policy matches.
     pub non_null_count: u64,
+    /// Missing values by cause, such as `null` or `sentinel:N/A`, when the
+    /// analyzer has a missing value policy.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub missing_by_cause: BTreeMap<String, u64>,
 }
 
 impl CompletenessState {
@@ -77,10 +94,17 @@ impl AnalyzerState for CompletenessState {
     fn merge(states: Vec<Self>) -> AnalyzerResult<Self> {
         let total_count = states.iter().map(|s| s.total_count).sum();
         let non_null_count = states.iter().map(|s| s.non_null_count).sum();
+        let mut missing_by_cause = BTreeMap::new();
+        for state in &states {
+            for (cause, count) in &state.missing_by_cause {
+                *missing_by_cause.entry(cause.clone()).or_insert(0) += count;
+            }
+        }
 
         Ok(CompletenessState {
             total_count,
             non_null_count,
+            missing_by_cause,
         })
     }
 
@@ -103,6 +127,18 @@ impl Analyzer for CompletenessAnalyzer {
 
         let table_name = validation_ctx.table_name();
 
+        if let Some(policy) = &self.missing_values {
+            let counts = policy
+                .count(ctx, table_name, &self.column)
+                .await
+                .map_err(|e| AnalyzerError::execution(e.to_string()))?;
+            return Ok(CompletenessState {
+                total_count: counts.total,
+                non_null_count: counts.total - counts.missing(),
+                missing_by_cause: counts.by_cause.into_iter().collect(),
+            });
+        }
+
         let sql = format!(
             "SELECT COUNT(*) as total_count, COUNT({}) as non_null_count FROM {table_name}",
             self.column
@@ -142,6 +178,7 @@ impl Analyzer for CompletenessAnalyzer {
         Ok(CompletenessState {
             total_count,
             non_null_count,
+            missing_by_cause: BTreeMap::new(),
         })
     }
 
@@ -184,6 +184,7 @@ impl GroupedAnalyzer for CompletenessAnalyzer {
                 let state = CompletenessState {
                     total_count: totals[i],
                     non_null_count: non_nulls[i],
+                    missing_by_cause: Default::default(),
                 };
 
                 groups.insert(key, state);
@@ -199,6 +200,7 @@ impl GroupedAnalyzer for CompletenessAnalyzer {
             Some(CompletenessState {
                 total_count: overall_total,
                 non_null_count: overall_non_null,
+                missing_by_cause: Default::default(),
             })
         } else {
             None
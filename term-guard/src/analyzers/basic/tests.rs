@@ -88,16 +88,34 @@ mod completeness_tests {
         assert_eq!(state.non_null_count, 4);
     }
 
+    #[tokio::test]
+    async fn test_completeness_analyzer_with_missing_value_policy() {
+        let ctx = create_test_context().await.unwrap();
+
+        let analyzer = CompletenessAnalyzer::new("name").with_missing_value_policy(
+            crate::core::MissingValuePolicy::new()
+                .sentinel_strings(["A"])
+                .case_insensitive(true),
+        );
+        let state = analyzer.compute_state_from_data(&ctx).await.unwrap();
+        assert_eq!(state.total_count, 5);
+        assert_eq!(state.non_null_count, 2);
+        assert_eq!(state.missing_by_cause["null"], 1);
+        assert_eq!(state.missing_by_cause["sentinel:A"], 2);
+    }
+
     #[test]
     fn test_completeness_state_merge() {
         let states = vec![
             CompletenessState {
                 total_count: 10,
                 non_null_count: 8,
+                missing_by_cause: [("null".to_string(), 2)].into(),
             },
             CompletenessState {
                 total_count: 20,
                 non_null_count: 18,
+                missing_by_cause: [("null".to_string(), 1), ("empty_string".to_string(), 1)].into(),
             },
         ];
 
@@ -105,6 +123,8 @@ mod completeness_tests {
         assert_eq!(merged.total_count, 30);
         assert_eq!(merged.non_null_count, 26);
         assert_eq!(merged.completeness(), 26.0 / 30.0);
+        assert_eq!(merged.missing_by_cause["null"], 3);
+        assert_eq!(merged.missing_by_cause["empty_string"], 1);
     }
 }
 
@@ -0,0 +1,664 @@
+//! Flakiness of constraints across runs.
+//!
+//! A constraint that oscillates between passing and failing, for example
+//! because its data lands a few minutes before or after the run, trains its
+//! team to ignore alerts. [`FlakinessAnalyzer`] reads the outcomes of
+//! validation reports saved with
+//! [`ReportAggregator::save_report`](crate::core::ReportAggregator::save_report),
+//! matched by [`constraint_id`](crate::core::constraint_id), and scores how
+//! often each constraint changed status between consecutive runs.
+//!
+//! ## Score
+//!
+//! The flakiness score of a constraint is the share of consecutive runs whose
+//! status differs: 0 for a constraint that always passes or always fails, and
+//! 1 for one that alternates every run. Skipped runs are left out. A
+//! constraint failing steadily is a signal rather than noise, so it scores 0.
+//!
+//! ## Suggestions
+//!
+//! Every flaky constraint gets the suggestions its history supports:
+//!
+//! - [`AddFreshnessDependency`](FlakinessSuggestion::AddFreshnessDependency)
+//!   when every failure cleared on the next run, as when data lands late
+//! - [`LowerThreshold`](FlakinessSuggestion::LowerThreshold) when the 10th
+//!   percentile of its metric is below its threshold
+//! - [`ConvertToWarning`](FlakinessSuggestion::ConvertToWarning) when its
+//!   check has the `Error` level
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use term_guard::analyzers::FlakinessAnalyzer;
+//! use term_guard::constraints::HistoryWindow;
+//! use term_guard::core::ValidationReport;
+//! use term_guard::repository::InMemoryRepository;
+//!
+//! # async fn example(repository: &InMemoryRepository, mut latest: ValidationReport) -> term_guard::prelude::Result<()> {
+//! let flakiness = FlakinessAnalyzer::new()
+//!     .analyze(repository, [("environment", "production")], HistoryWindow::LastRuns(30))
+//!     .await?;
+//! println!("{}", flakiness.to_text());
+//!
+//! // Show the on-call which failures of the latest run are usually noise
+//! flakiness.annotate(&mut latest);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use super::threshold_advisor::{percentile, sorted};
+use crate::constraints::HistoryWindow;
+use crate::core::{
+    ConstraintStatus, Level, RecentFlakiness, ValidationReport, REPORT_METADATA_KEY,
+};
+use crate::prelude::*;
+use crate::repository::{MetricsRepository, ResultKey, SortOrder};
+
+/// Default minimum flakiness score of a flaky constraint.
+const DEFAULT_MIN_SCORE: f64 = 0.2;
+
+/// Percentile of the metric history a [`FlakinessSuggestion::LowerThreshold`]
+/// recommends.
+const THRESHOLD_PERCENTILE: f64 = 0.1;
+
+/// An action that would make a flaky constraint stable.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FlakinessSuggestion {
+    /// Every failure cleared on the next run, as when the data lands after the
+    /// run starts; make the check wait for fresh data
+    AddFreshnessDependency,
+    /// The metric often sits just below the threshold
+    LowerThreshold {
+        /// The threshold of the latest run
+        current: f64,
+        /// The 10th percentile of the metric over the history
+        observed_p10: f64,
+    },
+    /// The check fails runs at the `Error` level
+    ConvertToWarning,
+}
+
+impl fmt::Display for FlakinessSuggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlakinessSuggestion::AddFreshnessDependency => write!(
+                f,
+                "add a freshness dependency: every failure cleared on the next run"
+            ),
+            FlakinessSuggestion::LowerThreshold {
+                current,
+                observed_p10,
+            } => write!(
+                f,
+                "lower the threshold from {current} to the observed p10 {observed_p10:.4}"
+            ),
+            FlakinessSuggestion::ConvertToWarning => {
+                write!(f, "convert the check to Warning until it is stable")
+            }
+        }
+    }
+}
+
+/// The pass/fail history of one constraint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstraintFlakiness {
+    /// The name of the suite holding the constraint
+    pub suite_name: String,
+    /// The name of the check holding the constraint, as of the latest run
+    pub check_name: String,
+    /// The name of the constraint
+    pub constraint_name: String,
+    /// The stable identifier of the constraint
+    pub constraint_id: String,
+    /// The level of the check as of the latest run
+    pub level: Level,
+    /// Number of runs in which the constraint passed or failed
+    pub runs: usize,
+    /// Number of failed runs
+    pub failures: usize,
+    /// Number of times the status changed between consecutive runs
+    pub flips: usize,
+    /// Share of consecutive runs whose status differs, from 0 to 1
+    pub score: f64,
+    /// Passing runs before each failure that followed a pass, oldest first
+    pub runs_to_failure: Vec<usize>,
+    /// The statuses of the runs, oldest first: `P` passed and `F` failed
+    pub sequence: String,
+    /// Actions that would make the constraint stable, if it is flaky
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<FlakinessSuggestion>,
+}
+
+impl ConstraintFlakiness {
+    /// Returns the mean number of passing runs before a failure, if the
+    /// constraint ever failed after passing.
+    pub fn mean_runs_to_failure(&self) -> Option<f64> {
+        if self.runs_to_failure.is_empty() {
+            return None;
+        }
+        let total: usize = self.runs_to_failure.iter().sum();
+        Some(total as f64 / self.runs_to_failure.len() as f64)
+    }
+
+    /// Returns the summary attached to reports by [`FlakinessReport::annotate`].
+    pub fn recent(&self) -> RecentFlakiness {
+        RecentFlakiness {
+            score: self.score,
+            runs: self.runs,
+            failures: self.failures,
+            flips: self.flips,
+        }
+    }
+}
+
+/// The flakiness of the constraints found in saved reports.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlakinessReport {
+    /// The history the report covers
+    pub window: HistoryWindow,
+    /// Number of saved reports read
+    pub reports: usize,
+    /// Minimum score of a flaky constraint
+    pub min_score: f64,
+    /// Every constraint with enough history, most flaky first
+    pub constraints: Vec<ConstraintFlakiness>,
+}
+
+impl FlakinessReport {
+    /// Returns the constraints scoring at least the minimum score, most flaky
+    /// first.
+    pub fn flaky(&self) -> impl Iterator<Item = &ConstraintFlakiness> {
+        self.constraints
+            .iter()
+            .filter(|constraint| constraint.score >= self.min_score)
+    }
+
+    /// Returns the `count` most flaky constraints.
+    pub fn top(&self, count: usize) -> Vec<&ConstraintFlakiness> {
+        self.flaky().take(count).collect()
+    }
+
+    /// Returns the history of the constraint `constraint_id` of the suite
+    /// `suite_name`.
+    pub fn get(&self, suite_name: &str, constraint_id: &str) -> Option<&ConstraintFlakiness> {
+        self.constraints.iter().find(|constraint| {
+            constraint.suite_name == suite_name && constraint.constraint_id == constraint_id
+        })
+    }
+
+    /// Records the recent flakiness of every failing constraint of `report`
+    /// with a history in [`ValidationReport::flakiness`].
+    ///
+    /// Stable constraints are recorded too, with a score of 0, so a failure
+    /// without history is told apart from a failure that is rarely noise.
+    pub fn annotate(&self, report: &mut ValidationReport) {
+        for issue in &report.issues {
+            let Some(id) = &issue.constraint_id else {
+                continue;
+            };
+            if let Some(constraint) = self.get(&report.suite_name, id) {
+                report.flakiness.insert(id.clone(), constraint.recent());
+            }
+        }
+    }
+
+    /// Serializes the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| TermError::Serialization(e.to_string()))
+    }
+
+    /// Reads a report from JSON.
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| TermError::Serialization(e.to_string()))
+    }
+
+    /// Formats the flaky constraints and their suggestions as plain text.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        let flaky: Vec<&ConstraintFlakiness> = self.flaky().collect();
+        let _ = writeln!(
+            out,
+            "Flakiness over the {} ({} reports): {} of {} constraints scoring at least {}",
+            self.window,
+            self.reports,
+            flaky.len(),
+            self.constraints.len(),
+            self.min_score
+        );
+        for constraint in flaky {
+            let _ = write!(
+                out,
+                "  - {}/{}.{} [{}]: {}",
+                constraint.suite_name,
+                constraint.check_name,
+                constraint.constraint_name,
+                constraint.constraint_id,
+                constraint.recent()
+            );
+            if let Some(mean) = constraint.mean_runs_to_failure() {
+                let _ = write!(out, ", {mean:.1} passing runs before a failure on average");
+            }
+            let _ = writeln!(out);
+            let _ = writeln!(out, "      history: {}", constraint.sequence);
+            for suggestion in &constraint.suggestions {
+                let _ = writeln!(out, "      suggestion: {suggestion}");
+            }
+        }
+        out
+    }
+}
+
+/// Scores the flakiness of constraints from saved validation reports.
+///
+/// By default a constraint needs at least 5 runs to be scored and is flaky
+/// from a score of 0.2.
+#[derive(Debug, Clone)]
+pub struct FlakinessAnalyzer {
+    min_runs: usize,
+    min_score: f64,
+    suite_name: Option<String>,
+}
+
+impl Default for FlakinessAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FlakinessAnalyzer {
+    /// Creates an analyzer reading the reports of every suite.
+    pub fn new() -> Self {
+        Self {
+            min_runs: 5,
+            min_score: DEFAULT_MIN_SCORE,
+            suite_name: None,
+        }
+    }
+
+    /// Sets the number of runs a constraint needs to be scored, at least 2.
+    pub fn min_runs(mut self, min_runs: usize) -> Self {
+        self.min_runs = min_runs.max(2);
+        self
+    }
+
+    /// Sets the minimum score of a flaky constraint.
+    pub fn min_score(mut self, min_score: f64) -> Self {
+        self.min_score = min_score;
+        self
+    }
+
+    /// Reads only the reports of the suite named `suite_name`.
+    pub fn for_suite(mut self, suite_name: impl Into<String>) -> Self {
+        self.suite_name = Some(suite_name.into());
+        self
+    }
+
+    /// Scores the constraints of the reports saved under `tags` within `window`.
+    ///
+    /// A [`HistoryWindow::LastRuns`] window keeps the latest runs of each
+    /// constraint.
+    #[instrument(skip_all, fields(window = %window))]
+    pub async fn analyze<I, K, V>(
+        &self,
+        repository: &dyn MetricsRepository,
+        tags: I,
+        window: HistoryWindow,
+    ) -> Result<FlakinessReport>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let mut query = repository
+            .load()
+            .await
+            .with_tags(tags)
+            .sort(SortOrder::Ascending);
+        if let HistoryWindow::LastDays(days) = window {
+            let cutoff = ResultKey::now().timestamp - i64::from(days) * 86_400_000;
+            query = query.after(cutoff);
+        }
+
+        let mut reports = 0;
+        let mut order = Vec::new();
+        let mut histories: HashMap<(String, String), Vec<Run>> = HashMap::new();
+        for (_, context) in query.execute().await? {
+            let Some(json) = context.metadata().custom.get(REPORT_METADATA_KEY) else {
+                continue;
+            };
+            let report = ValidationReport::from_json_str(json)?;
+            if self
+                .suite_name
+                .as_ref()
+                .is_some_and(|name| *name != report.suite_name)
+            {
+                continue;
+            }
+            reports += 1;
+            for outcome in report.outcomes {
+                let (Some(id), Some(passed)) =
+                    (outcome.constraint_id, status_passed(outcome.status))
+                else {
+                    continue;
+                };
+                let key = (report.suite_name.clone(), id);
+                let runs = histories.entry(key.clone()).or_insert_with(|| {
+                    order.push(key);
+                    Vec::new()
+                });
+                runs.push(Run {
+                    passed,
+                    metric: outcome.metric,
+                    threshold: outcome.threshold,
+                    level: outcome.level,
+                    check_name: outcome.check_name,
+                    constraint_name: outcome.constraint_name,
+                });
+            }
+        }
+
+        let mut constraints = Vec::new();
+        for key in order {
+            let Some(mut runs) = histories.remove(&key) else {
+                continue;
+            };
+            if let HistoryWindow::LastRuns(count) = window {
+                runs.drain(..runs.len().saturating_sub(count));
+            }
+            if runs.len() < self.min_runs {
+                continue;
+            }
+            let (suite_name, constraint_id) = key;
+            constraints.push(self.score(suite_name, constraint_id, &runs));
+        }
+        // Stable sort keeps constraints of equal score in the order they appeared
+        constraints.sort_by(|a, b| b.score.total_cmp(&a.score));
+        debug!(
+            reports,
+            constraints = constraints.len(),
+            "Computed constraint flakiness"
+        );
+
+        Ok(FlakinessReport {
+            window,
+            reports,
+            min_score: self.min_score,
+            constraints,
+        })
+    }
+
+    fn score(
+        &self,
+        suite_name: String,
+        constraint_id: String,
+        runs: &[Run],
+    ) -> ConstraintFlakiness {
+        let failures = runs.iter().filter(|run| !run.passed).count();
+        let flips = runs
+            .windows(2)
+            .filter(|pair| pair[0].passed != pair[1].passed)
+            .count();
+        let score = flips as f64 / (runs.len() - 1) as f64;
+
+        let mut runs_to_failure = Vec::new();
+        let mut passing = 0;
+        let mut longest_failure_streak = 0;
+        let mut failure_streak = 0;
+        for run in runs {
+            if run.passed {
+                passing += 1;
+                failure_streak = 0;
+            } else {
+                if passing > 0 {
+                    runs_to_failure.push(passing);
+                }
+                passing = 0;
+                failure_streak += 1;
+                longest_failure_streak = longest_failure_streak.max(failure_streak);
+            }
+        }
+
+        let latest = &runs[runs.len() - 1];
+        let mut suggestions = Vec::new();
+        if score >= self.min_score {
+            if failures >= 2 && longest_failure_streak == 1 {
+                suggestions.push(FlakinessSuggestion::AddFreshnessDependency);
+            }
+            let metrics: Vec<f64> = runs.iter().filter_map(|run| run.metric).collect();
+            if let (Some(current), false) = (latest.threshold, metrics.is_empty()) {
+                let observed_p10 = percentile(&sorted(&metrics), THRESHOLD_PERCENTILE);
+                if observed_p10 < current {
+                    suggestions.push(FlakinessSuggestion::LowerThreshold {
+                        current,
+                        observed_p10,
+                    });
+                }
+            }
+            if latest.level == Level::Error {
+                suggestions.push(FlakinessSuggestion::ConvertToWarning);
+            }
+        }
+
+        ConstraintFlakiness {
+            suite_name,
+            check_name: latest.check_name.clone(),
+            constraint_name: latest.constraint_name.clone(),
+            constraint_id,
+            level: latest.level,
+            runs: runs.len(),
+            failures,
+            flips,
+            score,
+            runs_to_failure,
+            sequence: runs
+                .iter()
+                .map(|run| if run.passed { 'P' } else { 'F' })
+                .collect(),
+            suggestions,
+        }
+    }
+}
+
+/// The outcome of a constraint in one saved report.
+struct Run {
+    passed: bool,
+    metric: Option<f64>,
+    threshold: Option<f64>,
+    level: Level,
+    check_name: String,
+    constraint_name: String,
+}
+
+/// Returns whether a status counts as a pass, or `None` for skipped runs.
+fn status_passed(status: ConstraintStatus) -> Option<bool> {
+    match status {
+        ConstraintStatus::Success => Some(true),
+        ConstraintStatus::Failure => Some(false),
+        ConstraintStatus::Skipped => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ConstraintOutcome, ReportAggregator};
+    use crate::repository::InMemoryRepository;
+
+    fn outcome(id: &str, level: Level, passed: bool, metric: f64) -> ConstraintOutcome {
+        ConstraintOutcome {
+            check_name: "arrivals".to_string(),
+            constraint_name: "completeness".to_string(),
+            level,
+            status: if passed {
+                ConstraintStatus::Success
+            } else {
+                ConstraintStatus::Failure
+            },
+            waived: false,
+            metric: Some(metric),
+            threshold: Some(0.99),
+            dimension: None,
+            constraint_id: Some(id.to_string()),
+        }
+    }
+
+    /// Saves one report per run of `sequence`, where `F` marks a failure of the
+    /// flaky constraint; the stable constraint always passes.
+    async fn save_history(repository: &InMemoryRepository, sequence: &str, env: &str) {
+        for (run, status) in sequence.chars().enumerate() {
+            let flaky_passed = status == 'P';
+            let metric = if flaky_passed { 0.995 } else { 0.97 };
+            let mut report = ValidationReport::new("orders");
+            report
+                .outcomes
+                .push(outcome("flaky", Level::Error, flaky_passed, metric));
+            report
+                .outcomes
+                .push(outcome("stable", Level::Warning, true, 1.0));
+            let key = ResultKey::new(1_000 + run as i64).with_tag("env", env);
+            ReportAggregator::save_report(repository, key, &report)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scores_and_suggestions() {
+        let repository = InMemoryRepository::new();
+        save_history(&repository, "PPFPPPFPPF", "prod").await;
+
+        let report = FlakinessAnalyzer::new()
+            .analyze(&repository, [("env", "prod")], HistoryWindow::LastRuns(30))
+            .await
+            .unwrap();
+        assert_eq!(report.reports, 10);
+        assert_eq!(report.constraints.len(), 2);
+
+        let flaky = &report.constraints[0];
+        assert_eq!(flaky.constraint_id, "flaky");
+        assert_eq!(flaky.sequence, "PPFPPPFPPF");
+        assert_eq!((flaky.runs, flaky.failures, flaky.flips), (10, 3, 5));
+        assert!((flaky.score - 5.0 / 9.0).abs() < 1e-12);
+        assert_eq!(flaky.runs_to_failure, [2, 3, 2]);
+        assert_eq!(flaky.mean_runs_to_failure(), Some(7.0 / 3.0));
+        assert_eq!(flaky.suggestions.len(), 3);
+        assert_eq!(
+            flaky.suggestions[0],
+            FlakinessSuggestion::AddFreshnessDependency
+        );
+        assert!(matches!(
+            flaky.suggestions[1],
+            FlakinessSuggestion::LowerThreshold { current, observed_p10 }
+                if current == 0.99 && observed_p10 == 0.97
+        ));
+        assert_eq!(flaky.suggestions[2], FlakinessSuggestion::ConvertToWarning);
+
+        let stable = &report.constraints[1];
+        assert_eq!((stable.score, stable.flips), (0.0, 0));
+        assert!(stable.suggestions.is_empty());
+        assert_eq!(report.top(5).len(), 1);
+
+        let text = report.to_text();
+        assert!(text.contains("1 of 2 constraints"), "{text}");
+        assert!(text.contains("orders/arrivals.completeness [flaky]: score 0.56"));
+        assert!(text.contains("suggestion: add a freshness dependency"));
+        assert!(!text.contains("[stable]"));
+
+        let restored = FlakinessReport::from_json_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(restored.constraints.len(), 2);
+        assert_eq!(restored.constraints[0].suggestions, flaky.suggestions);
+        assert_eq!(restored.window, report.window);
+    }
+
+    #[tokio::test]
+    async fn test_windows_and_filters() {
+        let repository = InMemoryRepository::new();
+        save_history(&repository, "FFFFFPPPPP", "prod").await;
+        save_history(&repository, "PFPFPFPFPF", "staging").await;
+
+        // The last runs of a constraint that recovered are stable
+        let report = FlakinessAnalyzer::new()
+            .analyze(&repository, [("env", "prod")], HistoryWindow::LastRuns(5))
+            .await
+            .unwrap();
+        assert_eq!(report.get("orders", "flaky").unwrap().sequence, "PPPPP");
+        assert_eq!(report.flaky().count(), 0);
+
+        // Consecutive failures are not a late arrival
+        let report = FlakinessAnalyzer::new()
+            .min_score(0.1)
+            .analyze(&repository, [("env", "prod")], HistoryWindow::LastRuns(10))
+            .await
+            .unwrap();
+        let flaky = report.get("orders", "flaky").unwrap();
+        assert_eq!(flaky.flips, 1);
+        assert!(!flaky
+            .suggestions
+            .contains(&FlakinessSuggestion::AddFreshnessDependency));
+
+        let report = FlakinessAnalyzer::new()
+            .for_suite("customers")
+            .analyze(
+                &repository,
+                [("env", "staging")],
+                HistoryWindow::LastRuns(10),
+            )
+            .await
+            .unwrap();
+        assert_eq!(report.reports, 0);
+        assert!(report.constraints.is_empty());
+
+        let report = FlakinessAnalyzer::new()
+            .min_runs(21)
+            .analyze(
+                &repository,
+                Vec::<(String, String)>::new(),
+                HistoryWindow::LastRuns(30),
+            )
+            .await
+            .unwrap();
+        assert!(report.constraints.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_annotates_failing_constraints() {
+        let repository = InMemoryRepository::new();
+        save_history(&repository, "PFPFPFPFPF", "prod").await;
+        let flakiness = FlakinessAnalyzer::new()
+            .analyze(&repository, [("env", "prod")], HistoryWindow::LastRuns(30))
+            .await
+            .unwrap();
+
+        let mut latest = ValidationReport::new("orders");
+        for id in ["flaky", "unknown"] {
+            latest.add_issue(crate::core::ValidationIssue {
+                check_name: "arrivals".to_string(),
+                constraint_name: "completeness".to_string(),
+                level: Level::Error,
+                message: "Completeness 0.97 is below 0.99".to_string(),
+                metric: Some(0.97),
+                cached: false,
+                known_issue: None,
+                check_id: None,
+                constraint_id: Some(id.to_string()),
+                impacts: Vec::new(),
+            });
+        }
+        flakiness.annotate(&mut latest);
+
+        assert_eq!(latest.flakiness.len(), 1);
+        let recent = &latest.flakiness["flaky"];
+        assert_eq!((recent.score, recent.runs, recent.failures), (1.0, 10, 5));
+        assert_eq!(
+            recent.to_string(),
+            "score 1.00 (9 flips, 5 failures in 10 runs)"
+        );
+    }
+}
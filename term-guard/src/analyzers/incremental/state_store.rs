@@ -2,6 +2,7 @@
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use async_trait::async_trait;
 use tokio::fs;
@@ -9,6 +10,9 @@ use tracing::{debug, instrument};
 
 use crate::analyzers::{AnalyzerError, AnalyzerResult};
 
+/// Distinguishes temporary files written concurrently by the same process
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
 /// Type alias for state storage - maps analyzer names to serialized states
 pub type StateMap = HashMap<String, Vec<u8>>;
 
@@ -161,11 +165,20 @@ impl StateStore for FileSystemStateStore {
         // Count states for logging
         let state_count = state.len();
 
-        // Save each analyzer state
+        // Save each analyzer state. Files are written to a temporary path and renamed
+        // so that concurrent readers never observe a partially written state.
         for (analyzer_name, state_data) in state {
             let file_path = self.state_file_path(partition, &analyzer_name);
+            let temp_path = file_path.with_extension(format!(
+                "tmp-{}-{}",
+                std::process::id(),
+                TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
 
-            fs::write(&file_path, state_data)
+            fs::write(&temp_path, state_data)
+                .await
+                .map_err(|e| AnalyzerError::Custom(format!("Failed to write state file: {e}")))?;
+            fs::rename(&temp_path, &file_path)
                 .await
                 .map_err(|e| AnalyzerError::Custom(format!("Failed to write state file: {e}")))?;
         }
@@ -6,6 +6,10 @@
 //! - Boolean values with various representations
 //! - Categorical vs. free text strings
 //! - Mixed type columns with confidence scores
+//! - Domain-specific types through pluggable [`TypeDetector`]s
+//!
+//! Number and date parsing follows the configured [`InferenceLocale`], so columns
+//! such as `"1.234,56"` or `"31/12/2024"` can be recognized for European data.
 //!
 //! # Example
 //!
@@ -31,8 +35,12 @@
 //! # })
 //! ```
 
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
 
+use arrow::record_batch::RecordBatch;
 use datafusion::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -56,6 +64,67 @@ pub struct InferenceConfig {
     pub categorical_threshold: usize,
     /// Enable international number format detection (default: true)
     pub international_formats: bool,
+    /// Locale used when parsing numbers and dates (default: US conventions)
+    pub locale: InferenceLocale,
+}
+
+/// Decimal separator used when parsing numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DecimalSeparator {
+    /// `1,234.56` - a dot separates the fraction, commas group thousands
+    #[default]
+    Dot,
+    /// `1.234,56` - a comma separates the fraction, dots group thousands
+    Comma,
+}
+
+/// Order of the day and month components in numeric dates such as `03/04/2024`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DateOrder {
+    /// `MM/DD/YYYY`
+    #[default]
+    MonthFirst,
+    /// `DD/MM/YYYY`
+    DayFirst,
+}
+
+/// Locale-specific parsing rules for type inference.
+///
+/// # Example
+///
+/// ```rust
+/// use term_guard::analyzers::inference::{InferenceLocale, TypeInferenceEngine};
+///
+/// let engine = TypeInferenceEngine::builder()
+///     .locale(InferenceLocale::european())
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct InferenceLocale {
+    /// Separator between the integer and fractional part of numbers
+    pub decimal_separator: DecimalSeparator,
+    /// Order of day and month in slash-separated dates
+    pub date_order: DateOrder,
+}
+
+impl InferenceLocale {
+    /// Creates a locale from its parts.
+    pub fn new(decimal_separator: DecimalSeparator, date_order: DateOrder) -> Self {
+        Self {
+            decimal_separator,
+            date_order,
+        }
+    }
+
+    /// US conventions: `1,234.56` and `12/31/2024` (the default).
+    pub fn us() -> Self {
+        Self::default()
+    }
+
+    /// Continental European conventions: `1.234,56` and `31/12/2024`.
+    pub fn european() -> Self {
+        Self::new(DecimalSeparator::Comma, DateOrder::DayFirst)
+    }
 }
 
 impl Default for InferenceConfig {
@@ -66,6 +135,7 @@ impl Default for InferenceConfig {
             detect_decimal_precision: true,
             categorical_threshold: 100,
             international_formats: true,
+            locale: InferenceLocale::default(),
         }
     }
 }
@@ -96,6 +166,8 @@ pub enum InferredDataType {
     Text,
     /// Mixed types with confidence scores for each type
     Mixed { types: HashMap<String, f64> },
+    /// A domain-specific type reported by a custom [`TypeDetector`]
+    Custom { name: String },
 }
 
 impl InferredDataType {
@@ -122,10 +194,91 @@ impl InferredDataType {
             InferredDataType::Categorical { .. } => "Categorical",
             InferredDataType::Text => "Text",
             InferredDataType::Mixed { .. } => "Mixed",
+            InferredDataType::Custom { .. } => "Custom",
+        }
+    }
+
+    /// Get the type label, using the detector-defined name for custom types
+    pub fn label(&self) -> &str {
+        match self {
+            InferredDataType::Custom { name } => name,
+            other => other.type_name(),
         }
     }
 }
 
+/// A candidate type reported by a [`TypeDetector`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDetection {
+    /// The detected type
+    pub inferred_type: InferredDataType,
+    /// Confidence score (0.0 to 1.0)
+    pub confidence: f64,
+}
+
+impl TypeDetection {
+    /// Creates a detection, clamping the confidence to `0.0..=1.0`.
+    pub fn new(inferred_type: InferredDataType, confidence: f64) -> Self {
+        Self {
+            inferred_type,
+            confidence: confidence.clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// Extension point for detecting domain-specific types such as internal ID formats.
+///
+/// Detectors receive the trimmed, non-null sample values of a column and report
+/// a candidate type with a confidence score, or `None` if they do not apply.
+///
+/// # Precedence
+///
+/// - Detections below the engine's confidence threshold are ignored, but still
+///   reported in [`TypeInferenceResult::alternatives`] under the detector name.
+/// - A detection at or above the threshold takes precedence over the built-in
+///   types, so a detector can claim columns that would otherwise look numeric.
+/// - When several detectors qualify, the highest confidence wins; ties go to
+///   the detector that was registered first.
+///
+/// # Example
+///
+/// ```rust
+/// use term_guard::analyzers::inference::{
+///     InferredDataType, TypeDetection, TypeDetector, TypeInferenceEngine,
+/// };
+///
+/// struct OrderIdDetector;
+///
+/// impl TypeDetector for OrderIdDetector {
+///     fn name(&self) -> &str {
+///         "order_id"
+///     }
+///
+///     fn detect(&self, samples: &[&str]) -> Option<TypeDetection> {
+///         let matches = samples.iter().filter(|s| s.starts_with("ORD-")).count();
+///         (matches > 0).then(|| {
+///             TypeDetection::new(
+///                 InferredDataType::Custom { name: "order_id".to_string() },
+///                 matches as f64 / samples.len() as f64,
+///             )
+///         })
+///     }
+/// }
+///
+/// let engine = TypeInferenceEngine::builder()
+///     .add_detector(Box::new(OrderIdDetector))
+///     .build();
+/// let result = engine.infer_from_values(&[Some("ORD-001".to_string())]);
+/// assert_eq!(result.inferred_type.label(), "order_id");
+/// ```
+pub trait TypeDetector: Send + Sync {
+    /// The detector name, used to report its detections.
+    fn name(&self) -> &str;
+
+    /// Inspects sample values and returns a candidate type if the detector applies.
+    fn detect(&self, samples: &[&str]) -> Option<TypeDetection>;
+}
+
 /// Type inference result with confidence score
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypeInferenceResult {
@@ -156,6 +309,7 @@ pub struct TypeStats {
     pub decimal_info: Option<(u8, u8)>, // precision, scale
     pub boolean_representations: (Vec<String>, Vec<String>), // true_values, false_values
     pub detected_formats: Vec<String>,
+    pub custom_detections: Vec<(String, TypeDetection)>, // detector name, detection
 }
 
 impl Default for TypeStats {
@@ -179,6 +333,7 @@ impl TypeStats {
             decimal_info: None,
             boolean_representations: (Vec::new(), Vec::new()),
             detected_formats: Vec::new(),
+            custom_detections: Vec::new(),
         }
     }
 }
@@ -186,6 +341,7 @@ impl TypeStats {
 /// Builder for TypeInferenceEngine
 pub struct TypeInferenceEngineBuilder {
     config: InferenceConfig,
+    detectors: Vec<Arc<dyn TypeDetector>>,
 }
 
 impl TypeInferenceEngineBuilder {
@@ -219,16 +375,33 @@ impl TypeInferenceEngineBuilder {
         self
     }
 
+    /// Set the locale used for number and date parsing
+    pub fn locale(mut self, locale: InferenceLocale) -> Self {
+        self.config.locale = locale;
+        self
+    }
+
+    /// Register a custom type detector
+    ///
+    /// Detectors are consulted in registration order; see [`TypeDetector`] for
+    /// how their results are combined with the built-in types.
+    pub fn add_detector(mut self, detector: Box<dyn TypeDetector>) -> Self {
+        self.detectors.push(Arc::from(detector));
+        self
+    }
+
     /// Build the TypeInferenceEngine
     pub fn build(self) -> TypeInferenceEngine {
         TypeInferenceEngine {
             config: self.config,
             patterns: TypePatterns::new(),
+            detectors: self.detectors,
         }
     }
 }
 
 /// Pattern matching utilities for type detection
+#[derive(Clone)]
 struct TypePatterns {
     integer: Regex,
     float: Regex,
@@ -236,6 +409,7 @@ struct TypePatterns {
     date_iso: Regex,
     date_us: Regex,
     date_eu: Regex,
+    number_comma: Regex,
     datetime_iso: Regex,
     time: Regex,
     boolean_true: Vec<Regex>,
@@ -251,6 +425,7 @@ impl TypePatterns {
             date_iso: Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap(),
             date_us: Regex::new(r"^\d{1,2}/\d{1,2}/\d{4}$").unwrap(),
             date_eu: Regex::new(r"^\d{1,2}\.\d{1,2}\.\d{4}$").unwrap(),
+            number_comma: Regex::new(r"^[+-]?(\d{1,3}(\.\d{3})+|\d+)(,\d+)?$").unwrap(),
             datetime_iso: Regex::new(r"^\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}").unwrap(),
             time: Regex::new(r"^\d{1,2}:\d{2}(:\d{2})?(\s?(AM|PM))?$").unwrap(),
             boolean_true: vec![Regex::new(r"(?i)^(true|t|yes|y|1|on|enabled?)$").unwrap()],
@@ -260,9 +435,23 @@ impl TypePatterns {
 }
 
 /// Main type inference engine
+#[derive(Clone)]
 pub struct TypeInferenceEngine {
     config: InferenceConfig,
     patterns: TypePatterns,
+    detectors: Vec<Arc<dyn TypeDetector>>,
+}
+
+impl fmt::Debug for TypeInferenceEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeInferenceEngine")
+            .field("config", &self.config)
+            .field(
+                "detectors",
+                &self.detectors.iter().map(|d| d.name()).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
 }
 
 impl TypeInferenceEngine {
@@ -270,9 +459,15 @@ impl TypeInferenceEngine {
     pub fn builder() -> TypeInferenceEngineBuilder {
         TypeInferenceEngineBuilder {
             config: InferenceConfig::default(),
+            detectors: Vec::new(),
         }
     }
 
+    /// Get the engine configuration
+    pub fn config(&self) -> &InferenceConfig {
+        &self.config
+    }
+
     /// Create a TypeInferenceEngine with default configuration
     pub fn new() -> Self {
         Self::builder().build()
@@ -296,11 +491,7 @@ impl TypeInferenceEngine {
         // Sample data for analysis
         let samples = self.collect_samples(ctx, table_name, column_name).await?;
 
-        // Analyze the samples
-        let stats = self.analyze_samples(&samples);
-
-        // Determine the best type match
-        let result = self.determine_type(&stats);
+        let result = self.infer_from_values(&samples);
 
         info!(
             table = table_name,
@@ -328,10 +519,7 @@ impl TypeInferenceEngine {
             let ctx = ctx.clone();
             let table_name = table_name.to_string();
             let column_name = column_name.clone();
-            let engine = Self {
-                config: self.config.clone(),
-                patterns: TypePatterns::new(), // Create new patterns for each task
-            };
+            let engine = self.clone();
 
             let handle = tokio::spawn(async move {
                 let result = engine
@@ -355,6 +543,53 @@ impl TypeInferenceEngine {
         Ok(results)
     }
 
+    /// Infer the data type of already collected sample values
+    ///
+    /// `None` and blank values are counted as nulls.
+    pub fn infer_from_values(&self, samples: &[Option<String>]) -> TypeInferenceResult {
+        let stats = self.analyze_samples(samples);
+        self.determine_type(&stats)
+    }
+
+    /// Classify a single value using the same rules as column inference
+    pub fn classify_value(&self, value: &str) -> InferredDataType {
+        self.infer_from_values(&[Some(value.to_string())])
+            .inferred_type
+    }
+
+    /// Check whether a single value is compatible with a previously inferred type
+    ///
+    /// Integers are compatible with floating point and decimal types. Categorical,
+    /// text and mixed types accept any value.
+    pub fn value_matches(&self, value: &str, data_type: &InferredDataType) -> bool {
+        let trimmed = value.trim();
+        if let InferredDataType::Custom { name } = data_type {
+            return self.detectors.iter().any(|detector| {
+                detector.detect(&[trimmed]).is_some_and(|d| {
+                    d.confidence >= self.config.confidence_threshold
+                        && d.inferred_type.label() == name
+                })
+            });
+        }
+
+        let mut stats = TypeStats::new();
+        self.test_patterns(trimmed, &mut stats);
+        match data_type {
+            InferredDataType::Integer { .. } => stats.integer_matches > 0,
+            InferredDataType::Float { .. } | InferredDataType::Decimal { .. } => {
+                stats.float_matches > 0 || stats.integer_matches > 0
+            }
+            InferredDataType::Boolean { .. } => stats.boolean_matches > 0,
+            InferredDataType::Date { .. } => stats.date_matches > 0,
+            InferredDataType::DateTime { .. } => stats.datetime_matches > 0,
+            InferredDataType::Time { .. } => stats.time_matches > 0,
+            InferredDataType::Categorical { .. }
+            | InferredDataType::Text
+            | InferredDataType::Mixed { .. }
+            | InferredDataType::Custom { .. } => true,
+        }
+    }
+
     /// Collect sample data from the specified column
     async fn collect_samples(
         &self,
@@ -448,32 +683,74 @@ impl TypeInferenceEngine {
             }
         }
 
+        if !self.detectors.is_empty() {
+            let values: Vec<&str> = samples
+                .iter()
+                .flatten()
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .collect();
+            if !values.is_empty() {
+                for detector in &self.detectors {
+                    if let Some(detection) = detector.detect(&values) {
+                        stats
+                            .custom_detections
+                            .push((detector.name().to_string(), detection));
+                    }
+                }
+            }
+        }
+
         stats
     }
 
+    /// Rewrite a number into the dot-decimal form expected by the numeric patterns
+    fn normalize_number<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        match self.config.locale.decimal_separator {
+            DecimalSeparator::Comma if self.patterns.number_comma.is_match(value) => {
+                Cow::Owned(value.replace('.', "").replace(',', "."))
+            }
+            _ => Cow::Borrowed(value),
+        }
+    }
+
+    /// Check the day and month ranges of a date given as `(first, second)` components
+    fn valid_day_month(first: &str, second: &str, order: DateOrder) -> bool {
+        let (Ok(first), Ok(second)) = (first.parse::<u32>(), second.parse::<u32>()) else {
+            return false;
+        };
+        let (day, month) = match order {
+            DateOrder::MonthFirst => (second, first),
+            DateOrder::DayFirst => (first, second),
+        };
+        (1..=31).contains(&day) && (1..=12).contains(&month)
+    }
+
     /// Test a value against all type patterns
     pub fn test_patterns(&self, value: &str, stats: &mut TypeStats) {
+        let number = self.normalize_number(value);
+
         // Integer test
-        if self.patterns.integer.is_match(value) {
+        if self.patterns.integer.is_match(&number) {
             stats.integer_matches += 1;
         }
 
         // Float test - but only count it if it's actually a float (has decimal point or scientific notation)
-        if self.patterns.float.is_match(value) {
+        if self.patterns.float.is_match(&number) {
             // Only count as float if it's not a pure integer
-            if !self.patterns.integer.is_match(value)
-                || value.contains('.')
-                || value.contains('e')
-                || value.contains('E')
+            if !self.patterns.integer.is_match(&number)
+                || number.contains('.')
+                || number.contains('e')
+                || number.contains('E')
             {
                 stats.float_matches += 1;
 
                 // Check for decimal precision if it's a decimal
-                if self.patterns.decimal.is_match(value) && self.config.detect_decimal_precision {
-                    if let Some(dot_pos) = value.rfind('.') {
-                        let fractional_part = &value[dot_pos + 1..];
+                if self.patterns.decimal.is_match(&number) && self.config.detect_decimal_precision {
+                    if let Some(dot_pos) = number.rfind('.') {
+                        let fractional_part = &number[dot_pos + 1..];
                         let scale = fractional_part.len() as u8;
-                        let precision = (value.len() - 1) as u8; // -1 for the dot
+                        let precision = (number.len() - 1) as u8; // -1 for the dot
 
                         stats.decimal_info = Some((precision.min(38), scale.min(38)));
                     }
@@ -486,11 +763,26 @@ impl TypeInferenceEngine {
             stats.date_matches += 1;
             stats.detected_formats.push("YYYY-MM-DD".to_string());
         } else if self.patterns.date_us.is_match(value) {
-            stats.date_matches += 1;
-            stats.detected_formats.push("MM/DD/YYYY".to_string());
+            let order = self.config.locale.date_order;
+            let mut parts = value.split('/');
+            if let (Some(first), Some(second)) = (parts.next(), parts.next()) {
+                if Self::valid_day_month(first, second, order) {
+                    stats.date_matches += 1;
+                    let format = match order {
+                        DateOrder::MonthFirst => "MM/DD/YYYY",
+                        DateOrder::DayFirst => "DD/MM/YYYY",
+                    };
+                    stats.detected_formats.push(format.to_string());
+                }
+            }
         } else if self.patterns.date_eu.is_match(value) {
-            stats.date_matches += 1;
-            stats.detected_formats.push("DD.MM.YYYY".to_string());
+            let mut parts = value.split('.');
+            if let (Some(day), Some(month)) = (parts.next(), parts.next()) {
+                if Self::valid_day_month(day, month, DateOrder::DayFirst) {
+                    stats.date_matches += 1;
+                    stats.detected_formats.push("DD.MM.YYYY".to_string());
+                }
+            }
         }
 
         // DateTime test
@@ -575,6 +867,34 @@ impl TypeInferenceEngine {
             alternatives.insert("Categorical".to_string(), categorical_confidence);
         }
 
+        // Custom detections are reported under their detector name
+        for (name, detection) in &stats.custom_detections {
+            if detection.confidence > 0.0 {
+                alternatives.insert(name.clone(), detection.confidence);
+            }
+        }
+
+        // Qualifying custom detections take precedence over built-in types;
+        // the highest confidence wins and ties go to the first registered detector
+        let custom = stats
+            .custom_detections
+            .iter()
+            .map(|(_, detection)| detection)
+            .filter(|detection| detection.confidence >= self.config.confidence_threshold)
+            .fold(None::<&TypeDetection>, |best, detection| match best {
+                Some(b) if b.confidence >= detection.confidence => Some(b),
+                _ => Some(detection),
+            });
+        if let Some(detection) = custom {
+            return TypeInferenceResult {
+                inferred_type: detection.inferred_type.clone(),
+                confidence: detection.confidence,
+                samples_analyzed: stats.total_samples,
+                null_count: stats.null_count,
+                alternatives,
+            };
+        }
+
         // Determine the best type based on highest confidence
         let nullable = stats.null_count > 0;
 
@@ -658,6 +978,23 @@ impl TypeInferenceEngine {
     }
 }
 
+/// Extracts the non-null values of the first column of `batches` as strings.
+///
+/// Used by constraints and analyzers that share an engine configuration to read
+/// sampled values regardless of the column's Arrow type.
+pub(crate) fn string_values(batches: &[RecordBatch]) -> arrow::error::Result<Vec<String>> {
+    let mut values = Vec::new();
+    for batch in batches {
+        if batch.num_columns() == 0 {
+            continue;
+        }
+        let column = arrow::compute::cast(batch.column(0), &arrow::datatypes::DataType::Utf8)?;
+        let strings = arrow::array::as_string_array(&column);
+        values.extend(strings.iter().flatten().map(str::to_string));
+    }
+    Ok(values)
+}
+
 impl Default for TypeInferenceEngine {
     fn default() -> Self {
         Self::new()
@@ -1061,4 +1398,155 @@ mod tests {
         assert_eq!(stats.null_count, 2); // Empty and whitespace-only
         assert_eq!(stats.integer_matches, 2); // "123" and "456"
     }
+
+    struct PrefixDetector {
+        name: &'static str,
+        prefix: &'static str,
+        confidence: f64,
+    }
+
+    impl TypeDetector for PrefixDetector {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn detect(&self, samples: &[&str]) -> Option<TypeDetection> {
+            samples.iter().all(|s| s.starts_with(self.prefix)).then(|| {
+                TypeDetection::new(
+                    InferredDataType::Custom {
+                        name: self.name.to_string(),
+                    },
+                    self.confidence,
+                )
+            })
+        }
+    }
+
+    fn values(values: &[&str]) -> Vec<Option<String>> {
+        values.iter().map(|v| Some(v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_decimal_comma_locale() {
+        let samples = values(&["1.234,56", "99,90", "-0,5"]);
+
+        let default_result = TypeInferenceEngine::new().infer_from_values(&samples);
+        assert!(!matches!(
+            default_result.inferred_type,
+            InferredDataType::Float { .. } | InferredDataType::Decimal { .. }
+        ));
+
+        let engine = TypeInferenceEngine::builder()
+            .locale(InferenceLocale::european())
+            .build();
+        let result = engine.infer_from_values(&samples);
+        assert!(matches!(
+            result.inferred_type,
+            InferredDataType::Decimal { .. }
+        ));
+        assert_eq!(result.confidence, 1.0);
+
+        // Dots only group thousands in the comma locale
+        assert!(matches!(
+            engine.classify_value("1.234.567"),
+            InferredDataType::Integer { .. }
+        ));
+    }
+
+    #[test]
+    fn test_day_first_dates() {
+        let samples = values(&["31/12/2024", "15/01/2024", "01/02/2024"]);
+
+        // 31/12 and 15/01 are not valid month-first dates
+        let us_result = TypeInferenceEngine::new().infer_from_values(&samples);
+        assert!(!matches!(
+            us_result.inferred_type,
+            InferredDataType::Date { .. }
+        ));
+
+        let engine = TypeInferenceEngine::builder()
+            .locale(InferenceLocale::new(
+                DecimalSeparator::Dot,
+                DateOrder::DayFirst,
+            ))
+            .build();
+        let result = engine.infer_from_values(&samples);
+        assert_eq!(
+            result.inferred_type,
+            InferredDataType::Date {
+                format: "DD/MM/YYYY".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_custom_detector_takes_precedence() {
+        let engine = TypeInferenceEngine::builder()
+            .add_detector(Box::new(PrefixDetector {
+                name: "account_id",
+                prefix: "00",
+                confidence: 0.9,
+            }))
+            .build();
+
+        // Looks numeric, but the detector claims it
+        let result = engine.infer_from_values(&values(&["00123", "00456", "00789"]));
+        assert_eq!(
+            result.inferred_type,
+            InferredDataType::Custom {
+                name: "account_id".to_string()
+            }
+        );
+        assert_eq!(result.confidence, 0.9);
+        assert_eq!(result.alternatives.get("Integer"), Some(&1.0));
+        assert!(engine.value_matches("00999", &result.inferred_type));
+        assert!(!engine.value_matches("12345", &result.inferred_type));
+
+        // Not applicable: built-in detection is used
+        let result = engine.infer_from_values(&values(&["123", "456"]));
+        assert!(matches!(
+            result.inferred_type,
+            InferredDataType::Integer { .. }
+        ));
+    }
+
+    #[test]
+    fn test_custom_detector_blending() {
+        let detector = |name, confidence| {
+            Box::new(PrefixDetector {
+                name,
+                prefix: "ID-",
+                confidence,
+            })
+        };
+        let samples = values(&["ID-1", "ID-2"]);
+
+        // Highest confidence wins
+        let engine = TypeInferenceEngine::builder()
+            .add_detector(detector("low", 0.75))
+            .add_detector(detector("high", 0.95))
+            .build();
+        assert_eq!(
+            engine.infer_from_values(&samples).inferred_type.label(),
+            "high"
+        );
+
+        // Ties go to the first registered detector
+        let engine = TypeInferenceEngine::builder()
+            .add_detector(detector("first", 0.8))
+            .add_detector(detector("second", 0.8))
+            .build();
+        assert_eq!(
+            engine.infer_from_values(&samples).inferred_type.label(),
+            "first"
+        );
+
+        // Below the threshold the detection is only reported as an alternative
+        let engine = TypeInferenceEngine::builder()
+            .add_detector(detector("weak", 0.5))
+            .build();
+        let result = engine.infer_from_values(&samples);
+        assert_ne!(result.inferred_type.label(), "weak");
+        assert_eq!(result.alternatives.get("weak"), Some(&0.5));
+    }
 }
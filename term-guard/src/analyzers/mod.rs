@@ -10,6 +10,7 @@
 //! - **Basic Analyzers** (`basic`): Fundamental metrics like count, mean, min/max
 //! - **Advanced Analyzers** (`advanced`): Complex metrics like entropy, correlation  
 //! - **Column Profiler** (`profiler`): Three-pass algorithm for comprehensive column analysis
+//! - **Profile and Validate** (`profile_validate`): Profiling and validation in a single pass
 //! - **Type Inference Engine** (`inference`): Robust data type detection from string data
 //! - **Constraint Suggestions** (`suggestions`): Intelligent recommendations for data quality checks
 //!
@@ -86,13 +87,17 @@ pub mod anomaly;
 pub mod basic;
 pub mod context;
 pub mod errors;
+pub mod flakiness;
 pub mod grouped;
 pub mod incremental;
 pub mod inference;
+pub mod profile_validate;
 pub mod profiler;
 pub mod runner;
 pub mod schema_analyzer;
+pub mod suggestion_feedback;
 pub mod suggestions;
+pub mod threshold_advisor;
 pub mod traits;
 pub mod types;
 
@@ -104,6 +109,7 @@ pub use anomaly::{
 };
 pub use context::AnalyzerContext;
 pub use errors::{AnalyzerError, AnalyzerResult};
+pub use flakiness::{ConstraintFlakiness, FlakinessAnalyzer, FlakinessReport, FlakinessSuggestion};
 pub use grouped::{
     GroupedAnalyzer, GroupedAnalyzerState, GroupedAnalyzerWrapper, GroupedMetadata, GroupedMetrics,
     GroupingConfig, OverflowStrategy,
@@ -112,20 +118,28 @@ pub use incremental::{
     FileSystemStateStore, IncrementalAnalysisRunner, IncrementalConfig, StateStore,
 };
 pub use inference::{
-    InferenceConfig, InferredDataType, TypeInferenceEngine, TypeInferenceEngineBuilder,
-    TypeInferenceResult, TypeStats,
+    DateOrder, DecimalSeparator, InferenceConfig, InferenceLocale, InferredDataType, TypeDetection,
+    TypeDetector, TypeInferenceEngine, TypeInferenceEngineBuilder, TypeInferenceResult, TypeStats,
 };
+pub use profile_validate::{ProfileAndValidate, ProfileAndValidateResult};
 pub use profiler::{
     BasicStatistics, CategoricalBucket, CategoricalHistogram, ColumnProfile, ColumnProfiler,
     ColumnProfilerBuilder, DetectedDataType, NumericDistribution, ProfilerConfig, ProfilerProgress,
 };
 pub use runner::AnalysisRunner;
 pub use schema_analyzer::{CrossTableSuggestion, SchemaAnalyzer};
+pub use suggestion_feedback::{
+    FeedbackCounts, FeedbackHistory, SuggestionFeedback, SuggestionFeedbackStore,
+};
 pub use suggestions::{
     CardinalityRule, CompletenessRule, ConstraintParameter, ConstraintSuggestionRule, DataTypeRule,
     PatternRule, RangeRule, SuggestedConstraint, SuggestionEngine, SuggestionPriority,
     UniquenessRule,
 };
+pub use threshold_advisor::{
+    AdviceMethod, HistoryStatistics, ThresholdAdvice, ThresholdAdvisor, ThresholdDirection,
+    ThresholdRecommendation, ThresholdReport,
+};
 pub use traits::{Analyzer, AnalyzerState};
 pub use types::{MetricDistribution, MetricValue};
 
@@ -0,0 +1,253 @@
+//! Single-pass profiling and validation.
+//!
+//! [`ProfileAndValidate`] runs the [`ColumnProfiler`] and a [`ValidationSuite`]
+//! together. Constraints whose metrics the profiler already computed (completeness,
+//! min/max, distinct counts, type consistency) are answered from the profiles
+//! instead of issuing their own queries; everything else (patterns, custom SQL,
+//! cross-table checks) is evaluated normally.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use term_guard::analyzers::{ColumnProfiler, ProfileAndValidate};
+//! use term_guard::constraints::Assertion;
+//! use term_guard::core::{Check, ValidationSuite};
+//! use term_guard::core::builder_extensions::CompletenessOptions;
+//!
+//! # async fn example(ctx: datafusion::prelude::SessionContext) -> term_guard::prelude::Result<()> {
+//! let suite = ValidationSuite::builder("orders")
+//!     .table_name("orders")
+//!     .check(
+//!         Check::builder("basics")
+//!             .completeness("order_id", CompletenessOptions::full().into_constraint_options())
+//!             .has_min("amount", Assertion::GreaterThanOrEqual(0.0))
+//!             .build(),
+//!     )
+//!     .build();
+//!
+//! let result = ProfileAndValidate::new(ColumnProfiler::new(), suite)
+//!     .run(&ctx)
+//!     .await?;
+//!
+//! for constraint in result.profiled_constraints() {
+//!     println!("{} answered from profile", constraint.constraint_name);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use datafusion::prelude::*;
+use tracing::{info, instrument};
+
+use crate::analyzers::profiler::{ColumnProfile, ColumnProfiler};
+use crate::core::{
+    ColumnStatistics, PrecomputedStatistics, ProfiledConstraint, QueryMetrics,
+    QueryMetricsCollector, RunOptions, ValidationContext, ValidationResult, ValidationSuite,
+    CURRENT_CONTEXT,
+};
+use crate::prelude::*;
+
+/// Runs the column profiler and a validation suite over the same table,
+/// answering constraints from the profiles where possible.
+pub struct ProfileAndValidate {
+    profiler: ColumnProfiler,
+    suite: ValidationSuite,
+    columns: Option<Vec<String>>,
+    use_profile: bool,
+}
+
+impl ProfileAndValidate {
+    /// Creates a runner for the suite's table.
+    pub fn new(profiler: ColumnProfiler, suite: ValidationSuite) -> Self {
+        Self {
+            profiler,
+            suite,
+            columns: None,
+            use_profile: true,
+        }
+    }
+
+    /// Sets the columns to profile.
+    ///
+    /// By default the columns referenced by single-column constraints in the suite
+    /// are profiled.
+    pub fn columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Enables or disables answering constraints from the profiles (default: enabled).
+    ///
+    /// When disabled, every constraint is evaluated with its own queries, as if the
+    /// profiler and the suite were run separately.
+    pub fn use_profile(mut self, enabled: bool) -> Self {
+        self.use_profile = enabled;
+        self
+    }
+
+    /// Profiles the columns and runs the validation suite.
+    #[instrument(skip(self, ctx), fields(suite.name = %self.suite.name()))]
+    pub async fn run(&self, ctx: &SessionContext) -> Result<ProfileAndValidateResult> {
+        let table_name = self.suite.table_name();
+        let columns = self
+            .columns
+            .clone()
+            .unwrap_or_else(|| self.constraint_columns());
+
+        let collector = QueryMetricsCollector::new();
+        let profiling_ctx =
+            ValidationContext::new(table_name).with_performance_collector(collector.clone());
+        let profiles = CURRENT_CONTEXT
+            .scope(
+                profiling_ctx,
+                self.profiler.profile_columns(ctx, table_name, &columns),
+            )
+            .await?;
+        let profiling_metrics = collector.snapshot();
+
+        let statistics = self.statistics(ctx, table_name, &profiles).await?;
+        let validation = if self.use_profile {
+            self.suite
+                .run_with(ctx, &RunOptions::new().with_statistics(statistics.clone()))
+                .await?
+        } else {
+            self.suite.run(ctx).await?
+        };
+
+        info!(
+            columns = profiles.len(),
+            profiled_constraints = validation.report().profiled_constraints.len(),
+            profiling_queries = profiling_metrics.queries_executed,
+            "Completed profile and validate"
+        );
+
+        Ok(ProfileAndValidateResult {
+            profiles,
+            statistics,
+            validation,
+            profiling_metrics,
+        })
+    }
+
+    /// Columns referenced by single-column constraints, in suite order.
+    fn constraint_columns(&self) -> Vec<String> {
+        let mut columns: Vec<String> = Vec::new();
+        for check in self.suite.checks() {
+            for constraint in check.constraints() {
+                if let Some(column) = constraint.column() {
+                    if !columns.iter().any(|c| c == column) {
+                        columns.push(column.to_string());
+                    }
+                }
+            }
+        }
+        columns
+    }
+
+    /// Converts profiles into statistics the constraints can consume.
+    async fn statistics(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        profiles: &[ColumnProfile],
+    ) -> Result<PrecomputedStatistics> {
+        let schema = ctx.table(table_name).await?.schema().clone();
+        let mut statistics = PrecomputedStatistics::new();
+
+        for profile in profiles {
+            let basic = &profile.basic_stats;
+            // Profile min/max are strings; only numeric columns compare like the SQL MIN/MAX
+            let numeric = schema
+                .field_with_unqualified_name(&profile.column_name)
+                .is_ok_and(|field| field.data_type().is_numeric());
+            let parse = |value: &Option<String>| {
+                value
+                    .as_deref()
+                    .filter(|_| numeric)
+                    .and_then(|v| v.parse::<f64>().ok())
+            };
+
+            statistics.insert(
+                profile.column_name.clone(),
+                ColumnStatistics {
+                    row_count: basic.row_count,
+                    null_count: basic.null_count,
+                    distinct_count: Some(basic.approximate_cardinality),
+                    min: parse(&basic.min_value),
+                    max: parse(&basic.max_value),
+                    type_consistency: self.profiler.sample_type_consistency(profile),
+                },
+            );
+        }
+
+        Ok(statistics)
+    }
+}
+
+/// The result of a [`ProfileAndValidate`] run.
+#[derive(Debug, Clone)]
+pub struct ProfileAndValidateResult {
+    /// Profiles of the profiled columns
+    pub profiles: Vec<ColumnProfile>,
+    /// Statistics derived from the profiles
+    pub statistics: PrecomputedStatistics,
+    /// The validation result
+    pub validation: ValidationResult,
+    /// Execution metrics of the profiling queries
+    pub profiling_metrics: QueryMetrics,
+}
+
+impl ProfileAndValidateResult {
+    /// Constraints that were answered from the profiles.
+    pub fn profiled_constraints(&self) -> &[ProfiledConstraint] {
+        &self.validation.report().profiled_constraints
+    }
+
+    /// Total number of queries executed for profiling and validation.
+    pub fn queries_executed(&self) -> u64 {
+        self.profiling_metrics.queries_executed
+            + self
+                .validation
+                .report()
+                .metrics
+                .performance
+                .queries_executed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::Assertion;
+    use crate::core::Check;
+
+    #[test]
+    fn test_constraint_columns_in_suite_order() {
+        let suite = ValidationSuite::builder("suite")
+            .check(
+                Check::builder("first")
+                    .has_min("amount", Assertion::GreaterThan(0.0))
+                    .has_size(Assertion::GreaterThan(0.0))
+                    .build(),
+            )
+            .check(
+                Check::builder("second")
+                    .has_max("amount", Assertion::LessThan(100.0))
+                    .has_approx_count_distinct("category", Assertion::LessThan(10.0))
+                    .build(),
+            )
+            .build();
+
+        let runner = ProfileAndValidate::new(ColumnProfiler::new(), suite);
+        assert_eq!(runner.constraint_columns(), vec!["amount", "category"]);
+        assert!(runner.use_profile);
+
+        let runner = runner.columns(["category"]).use_profile(false);
+        assert_eq!(runner.columns, Some(vec!["category".to_string()]));
+        assert!(!runner.use_profile);
+    }
+}
@@ -45,6 +45,8 @@ use serde::{Deserialize, Serialize};
 use tracing::{info, instrument};
 
 use crate::analyzers::errors::AnalyzerError;
+use crate::analyzers::inference::{InferredDataType, TypeInferenceEngine};
+use crate::core::{collect_with_metrics, current_validation_context, CURRENT_CONTEXT};
 
 /// Result type for profiler operations
 pub type ProfilerResult<T> = Result<T, AnalyzerError>;
@@ -162,6 +164,7 @@ pub struct ColumnProfile {
 pub struct ColumnProfilerBuilder {
     config: ProfilerConfig,
     progress_callback: Option<ProgressCallback>,
+    inference_engine: Option<Arc<TypeInferenceEngine>>,
 }
 
 impl ColumnProfilerBuilder {
@@ -198,11 +201,21 @@ impl ColumnProfilerBuilder {
         self
     }
 
+    /// Use a type inference engine for Pass 1 type detection
+    ///
+    /// Sharing the engine with `DataTypeConstraint` and `DataTypeAnalyzer` keeps
+    /// custom detectors and locale settings consistent between profiling and validation.
+    pub fn inference_engine(mut self, engine: Arc<TypeInferenceEngine>) -> Self {
+        self.inference_engine = Some(engine);
+        self
+    }
+
     /// Build the ColumnProfiler
     pub fn build(self) -> ColumnProfiler {
         ColumnProfiler {
             config: self.config,
             progress_callback: self.progress_callback,
+            inference_engine: self.inference_engine,
         }
     }
 }
@@ -211,6 +224,7 @@ impl ColumnProfilerBuilder {
 pub struct ColumnProfiler {
     config: ProfilerConfig,
     progress_callback: Option<ProgressCallback>,
+    inference_engine: Option<Arc<TypeInferenceEngine>>,
 }
 
 impl ColumnProfiler {
@@ -219,6 +233,7 @@ impl ColumnProfiler {
         ColumnProfilerBuilder {
             config: ProfilerConfig::default(),
             progress_callback: None,
+            inference_engine: None,
         }
     }
 
@@ -318,12 +333,14 @@ impl ColumnProfiler {
                 let table_name = table_name.to_string();
                 let column_name = column_name.clone();
                 let profiler = self.clone_for_parallel();
+                // Spawned tasks do not inherit task-locals, so carry the context over
+                let validation_ctx = current_validation_context();
 
-                let handle = tokio::spawn(async move {
+                let handle = tokio::spawn(CURRENT_CONTEXT.scope(validation_ctx, async move {
                     profiler
                         .profile_column(&ctx, &table_name, &column_name)
                         .await
-                });
+                }));
                 handles.push(handle);
             }
 
@@ -355,6 +372,7 @@ impl ColumnProfiler {
         Self {
             config: self.config.clone(),
             progress_callback: self.progress_callback.clone(),
+            inference_engine: self.inference_engine.clone(),
         }
     }
 
@@ -394,8 +412,7 @@ impl ColumnProfiler {
             .sql(&sample_sql)
             .await
             .map_err(|e| AnalyzerError::execution(e.to_string()))?;
-        let sample_batches = sample_df
-            .collect()
+        let sample_batches = collect_with_metrics(sample_df)
             .await
             .map_err(|e| AnalyzerError::execution(e.to_string()))?;
 
@@ -412,8 +429,7 @@ impl ColumnProfiler {
             .sql(&stats_sql)
             .await
             .map_err(|e| AnalyzerError::execution(e.to_string()))?;
-        let stats_batches = stats_df
-            .collect()
+        let stats_batches = collect_with_metrics(stats_df)
             .await
             .map_err(|e| AnalyzerError::execution(e.to_string()))?;
 
@@ -490,8 +506,7 @@ impl ColumnProfiler {
             .sql(&histogram_sql)
             .await
             .map_err(|e| AnalyzerError::execution(e.to_string()))?;
-        let batches = df
-            .collect()
+        let batches = collect_with_metrics(df)
             .await
             .map_err(|e| AnalyzerError::execution(e.to_string()))?;
 
@@ -540,9 +555,9 @@ impl ColumnProfiler {
         // Compute advanced statistics for numeric columns
         let stats_sql = format!(
             "SELECT 
-                AVG(CAST({column_name} AS DOUBLE)) as mean,
-                STDDEV(CAST({column_name} AS DOUBLE)) as std_dev,
-                VAR_SAMP(CAST({column_name} AS DOUBLE)) as variance
+                AVG(TRY_CAST({column_name} AS DOUBLE)) as mean,
+                STDDEV(TRY_CAST({column_name} AS DOUBLE)) as std_dev,
+                VAR_SAMP(TRY_CAST({column_name} AS DOUBLE)) as variance
              FROM {table_name}
              WHERE {column_name} IS NOT NULL"
         );
@@ -551,8 +566,7 @@ impl ColumnProfiler {
             .sql(&stats_sql)
             .await
             .map_err(|e| AnalyzerError::execution(e.to_string()))?;
-        let stats_batches = stats_df
-            .collect()
+        let stats_batches = collect_with_metrics(stats_df)
             .await
             .map_err(|e| AnalyzerError::execution(e.to_string()))?;
 
@@ -605,6 +619,16 @@ impl ColumnProfiler {
             return Ok(DetectedDataType::Unknown);
         }
 
+        if let Some(engine) = &self.inference_engine {
+            let samples: Vec<Option<String>> = basic_stats
+                .sample_values
+                .iter()
+                .map(|value| Some(value.clone()))
+                .collect();
+            let inferred = engine.infer_from_values(&samples).inferred_type;
+            return Ok(Self::map_inferred_type(&inferred));
+        }
+
         let mut type_counts = HashMap::new();
 
         for value in &basic_stats.sample_values {
@@ -622,6 +646,44 @@ impl ColumnProfiler {
         Ok(dominant_type)
     }
 
+    /// Fraction of a profile's sample values that match its detected data type
+    ///
+    /// Returns `None` when the profile has no sample values.
+    pub fn sample_type_consistency(&self, profile: &ColumnProfile) -> Option<f64> {
+        let samples = &profile.basic_stats.sample_values;
+        if samples.is_empty() {
+            return None;
+        }
+        let matching = samples
+            .iter()
+            .filter(|value| match &self.inference_engine {
+                Some(engine) => {
+                    Self::map_inferred_type(&engine.classify_value(value)) == profile.data_type
+                }
+                None => self.classify_value(value) == profile.data_type,
+            })
+            .count();
+        Some(matching as f64 / samples.len() as f64)
+    }
+
+    /// Map an inference engine type onto the profiler's type categories
+    fn map_inferred_type(inferred: &InferredDataType) -> DetectedDataType {
+        match inferred {
+            InferredDataType::Integer { .. } => DetectedDataType::Integer,
+            InferredDataType::Float { .. } | InferredDataType::Decimal { .. } => {
+                DetectedDataType::Double
+            }
+            InferredDataType::Boolean { .. } => DetectedDataType::Boolean,
+            InferredDataType::Date { .. } => DetectedDataType::Date,
+            InferredDataType::DateTime { .. } => DetectedDataType::Timestamp,
+            InferredDataType::Mixed { .. } => DetectedDataType::Mixed,
+            InferredDataType::Time { .. }
+            | InferredDataType::Categorical { .. }
+            | InferredDataType::Text
+            | InferredDataType::Custom { .. } => DetectedDataType::String,
+        }
+    }
+
     /// Classify a single value to determine its data type
     fn classify_value(&self, value: &str) -> DetectedDataType {
         let trimmed = value.trim();
@@ -742,8 +804,7 @@ impl ColumnProfiler {
             .sql(&sql)
             .await
             .map_err(|e| AnalyzerError::execution(e.to_string()))?;
-        let batches = df
-            .collect()
+        let batches = collect_with_metrics(df)
             .await
             .map_err(|e| AnalyzerError::execution(e.to_string()))?;
 
@@ -776,15 +837,14 @@ impl ColumnProfiler {
     ) -> ProfilerResult<f64> {
         // Try to use DataFusion's approx_percentile function
         let sql = format!(
-            "SELECT approx_percentile(CAST({column_name} AS DOUBLE), {percentile}) as percentile_val
+            "SELECT approx_percentile(TRY_CAST({column_name} AS DOUBLE), {percentile}) as percentile_val
              FROM {table_name} 
              WHERE {column_name} IS NOT NULL"
         );
 
         match ctx.sql(&sql).await {
             Ok(df) => {
-                let batches = df
-                    .collect()
+                let batches = collect_with_metrics(df)
                     .await
                     .map_err(|e| AnalyzerError::execution(e.to_string()))?;
 
@@ -860,6 +920,42 @@ mod tests {
         assert_eq!(profiler.classify_value("hello"), DetectedDataType::String);
     }
 
+    #[tokio::test]
+    async fn test_data_type_detection_with_inference_engine() {
+        use crate::analyzers::inference::InferenceLocale;
+
+        let stats = BasicStatistics {
+            row_count: 3,
+            null_count: 0,
+            null_percentage: 0.0,
+            approximate_cardinality: 3,
+            min_value: None,
+            max_value: None,
+            sample_values: vec![
+                "1.234,56".to_string(),
+                "99,5".to_string(),
+                "-0,25".to_string(),
+            ],
+        };
+
+        let default_profiler = ColumnProfiler::new();
+        assert_eq!(
+            default_profiler.detect_data_type(&stats).await.unwrap(),
+            DetectedDataType::String
+        );
+
+        let engine = TypeInferenceEngine::builder()
+            .locale(InferenceLocale::european())
+            .build();
+        let profiler = ColumnProfiler::builder()
+            .inference_engine(Arc::new(engine))
+            .build();
+        assert_eq!(
+            profiler.detect_data_type(&stats).await.unwrap(),
+            DetectedDataType::Double
+        );
+    }
+
     #[tokio::test]
     async fn test_progress_callback() {
         use std::sync::{Arc, Mutex};
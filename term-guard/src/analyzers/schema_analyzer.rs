@@ -667,42 +667,33 @@ impl<'a> SchemaAnalyzer<'a> {
 
         for suggestion in suggestions {
             match suggestion.constraint_type.as_str() {
-                "foreign_key" => {
-                    if suggestion.tables.len() == 2 {
-                        let child_col = format!(
-                            "{}.{}",
-                            suggestion.tables[0], suggestion.columns[&suggestion.tables[0]][0]
-                        );
-                        let parent_col = format!(
-                            "{}.{}",
-                            suggestion.tables[1], suggestion.columns[&suggestion.tables[1]][0]
-                        );
-                        builder = builder.foreign_key(child_col, parent_col);
-                    }
+                "foreign_key" if suggestion.tables.len() == 2 => {
+                    let child_col = format!(
+                        "{}.{}",
+                        suggestion.tables[0], suggestion.columns[&suggestion.tables[0]][0]
+                    );
+                    let parent_col = format!(
+                        "{}.{}",
+                        suggestion.tables[1], suggestion.columns[&suggestion.tables[1]][0]
+                    );
+                    builder = builder.foreign_key(child_col, parent_col);
                 }
-                "cross_table_sum" => {
-                    if suggestion.tables.len() == 2 {
-                        let left_col = format!(
-                            "{}.{}",
-                            suggestion.tables[0], suggestion.columns[&suggestion.tables[0]][0]
-                        );
-                        let right_col = format!(
-                            "{}.{}",
-                            suggestion.tables[1], suggestion.columns[&suggestion.tables[1]][0]
-                        );
-                        builder = builder.cross_table_sum(left_col, right_col);
-                    }
+                "cross_table_sum" if suggestion.tables.len() == 2 => {
+                    let left_col = format!(
+                        "{}.{}",
+                        suggestion.tables[0], suggestion.columns[&suggestion.tables[0]][0]
+                    );
+                    let right_col = format!(
+                        "{}.{}",
+                        suggestion.tables[1], suggestion.columns[&suggestion.tables[1]][0]
+                    );
+                    builder = builder.cross_table_sum(left_col, right_col);
                 }
-                "join_coverage" => {
-                    if suggestion.tables.len() == 2 {
-                        builder =
-                            builder.join_coverage(&suggestion.tables[0], &suggestion.tables[1]);
-                    }
+                "join_coverage" if suggestion.tables.len() == 2 => {
+                    builder = builder.join_coverage(&suggestion.tables[0], &suggestion.tables[1]);
                 }
-                "temporal_ordering" => {
-                    if !suggestion.tables.is_empty() {
-                        builder = builder.temporal_ordering(&suggestion.tables[0]);
-                    }
+                "temporal_ordering" if !suggestion.tables.is_empty() => {
+                    builder = builder.temporal_ordering(&suggestion.tables[0]);
                 }
                 _ => {}
             }
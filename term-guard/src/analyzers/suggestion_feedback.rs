@@ -0,0 +1,306 @@
+//! Feedback on constraint suggestions, used to recalibrate their confidence.
+//!
+//! The confidence a [`ConstraintSuggestionRule`](super::ConstraintSuggestionRule)
+//! assigns to a suggestion is a heuristic. [`SuggestionFeedbackStore`] records
+//! what happened to suggestions afterwards — whether a reviewer accepted them and
+//! whether the resulting checks passed in later runs — in a
+//! [`MetricsRepository`], keyed by [`SuggestedConstraint::fingerprint`].
+//! [`SuggestionEngine::suggest_constraints_with_feedback`](super::SuggestionEngine::suggest_constraints_with_feedback)
+//! reads that history back and moves the confidence of each suggestion towards
+//! the observed success rate of its check type.
+//!
+//! ## Adjustment
+//!
+//! The rule's confidence `c` acts as the mean of a Beta prior worth `w`
+//! pseudo-observations (see
+//! [`SuggestionEngine::feedback_prior_weight`](super::SuggestionEngine::feedback_prior_weight)).
+//! Every recorded acceptance or passing run counts as a success, every rejection
+//! or failing run as a failure, and the adjusted confidence is the posterior mean
+//!
+//! ```text
+//! (w * c + successes) / (w + successes + failures)
+//! ```
+//!
+//! With little history the rule's own estimate dominates; as feedback
+//! accumulates the confidence converges to the observed rate.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use term_guard::analyzers::{SuggestionEngine, SuggestionFeedbackStore};
+//! use term_guard::repository::InMemoryRepository;
+//!
+//! let store = SuggestionFeedbackStore::new(Arc::new(InMemoryRepository::new()))
+//!     .with_tag("dataset", "orders");
+//!
+//! let suggestions = engine
+//!     .suggest_constraints_with_feedback(&profile, Some(&store))
+//!     .await?;
+//! store.record_decision(&suggestions[0], true).await?;
+//!
+//! // Later, after running the check built from the suggestion
+//! store.record_outcome(&suggestions[0], passed).await?;
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::analyzers::suggestions::SuggestedConstraint;
+use crate::analyzers::{AnalyzerContext, MetricValue};
+use crate::prelude::*;
+use crate::repository::{MetricsRepository, ResultKey};
+
+const KIND_TAG: &str = "kind";
+const KIND: &str = "suggestion_feedback";
+const FINGERPRINT_TAG: &str = "suggestion";
+const CHECK_TYPE_TAG: &str = "check_type";
+const EVENT_TAG: &str = "event";
+const ACCEPTED_METRIC: &str = "suggestion.accepted";
+const PASSED_METRIC: &str = "suggestion.passed";
+
+/// Counts of the feedback recorded for a suggestion or a check type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedbackCounts {
+    /// Number of times the suggestion was accepted
+    pub accepted: u64,
+    /// Number of times the suggestion was rejected
+    pub rejected: u64,
+    /// Number of runs in which the resulting check passed
+    pub passed: u64,
+    /// Number of runs in which the resulting check failed
+    pub failed: u64,
+}
+
+impl FeedbackCounts {
+    /// Returns the share of decisions that accepted the suggestion.
+    pub fn acceptance_rate(&self) -> Option<f64> {
+        rate(self.accepted, self.rejected)
+    }
+
+    /// Returns the share of runs in which the resulting check passed.
+    pub fn pass_rate(&self) -> Option<f64> {
+        rate(self.passed, self.failed)
+    }
+
+    /// Returns the number of recorded decisions and runs.
+    pub fn observations(&self) -> u64 {
+        self.accepted + self.rejected + self.passed + self.failed
+    }
+
+    /// Returns the confidence after updating a prior of mean `confidence`
+    /// worth `prior_weight` observations with these counts.
+    pub fn adjust(&self, confidence: f64, prior_weight: f64) -> f64 {
+        let successes = (self.accepted + self.passed) as f64;
+        let observations = self.observations() as f64;
+        if observations == 0.0 {
+            return confidence;
+        }
+        ((prior_weight * confidence + successes) / (prior_weight + observations)).clamp(0.0, 1.0)
+    }
+
+    fn merge(&mut self, other: &FeedbackCounts) {
+        self.accepted += other.accepted;
+        self.rejected += other.rejected;
+        self.passed += other.passed;
+        self.failed += other.failed;
+    }
+}
+
+fn rate(successes: u64, failures: u64) -> Option<f64> {
+    let total = successes + failures;
+    (total > 0).then(|| successes as f64 / total as f64)
+}
+
+/// The feedback that adjusted the confidence of a suggestion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuggestionFeedback {
+    /// Confidence assigned by the rule before the adjustment
+    pub base_confidence: f64,
+    /// Share of decisions on suggestions of this check type that accepted them
+    pub acceptance_rate: Option<f64>,
+    /// Share of runs in which checks of this type passed
+    pub pass_rate: Option<f64>,
+    /// Feedback recorded for suggestions of this check type
+    pub counts: FeedbackCounts,
+}
+
+/// Feedback loaded from a [`SuggestionFeedbackStore`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedbackHistory {
+    by_fingerprint: HashMap<String, FeedbackCounts>,
+    by_check_type: HashMap<String, FeedbackCounts>,
+}
+
+impl FeedbackHistory {
+    /// Returns the feedback recorded for the suggestion with `fingerprint`.
+    pub fn for_fingerprint(&self, fingerprint: &str) -> FeedbackCounts {
+        self.by_fingerprint
+            .get(fingerprint)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the feedback recorded for all suggestions of `check_type`.
+    pub fn for_check_type(&self, check_type: &str) -> FeedbackCounts {
+        self.by_check_type
+            .get(check_type)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns true if no feedback has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.by_check_type.is_empty()
+    }
+
+    fn record(&mut self, fingerprint: &str, check_type: &str, counts: FeedbackCounts) {
+        self.by_fingerprint
+            .entry(fingerprint.to_string())
+            .or_default()
+            .merge(&counts);
+        self.by_check_type
+            .entry(check_type.to_string())
+            .or_default()
+            .merge(&counts);
+    }
+}
+
+/// Records feedback on suggestions in a metrics repository.
+///
+/// Every decision and outcome is saved as its own entry, tagged with the
+/// suggestion fingerprint and check type plus the store's own tags, so one
+/// repository can hold the feedback of several datasets or teams.
+#[derive(Clone)]
+pub struct SuggestionFeedbackStore {
+    repository: Arc<dyn MetricsRepository>,
+    tags: HashMap<String, String>,
+}
+
+impl SuggestionFeedbackStore {
+    /// Creates a store saving feedback in `repository`.
+    pub fn new(repository: Arc<dyn MetricsRepository>) -> Self {
+        Self {
+            repository,
+            tags: HashMap::new(),
+        }
+    }
+
+    /// Scopes the store to entries carrying the tag `key=value`.
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.insert(key.into(), value.into());
+        self
+    }
+
+    /// Records whether a reviewer accepted `suggestion`.
+    pub async fn record_decision(
+        &self,
+        suggestion: &SuggestedConstraint,
+        accepted: bool,
+    ) -> Result<()> {
+        self.save(suggestion, ACCEPTED_METRIC, accepted).await
+    }
+
+    /// Records whether the check built from `suggestion` passed in a run.
+    pub async fn record_outcome(
+        &self,
+        suggestion: &SuggestedConstraint,
+        passed: bool,
+    ) -> Result<()> {
+        self.save(suggestion, PASSED_METRIC, passed).await
+    }
+
+    /// Loads all feedback within the store's scope.
+    #[instrument(skip(self))]
+    pub async fn history(&self) -> Result<FeedbackHistory> {
+        let entries = self
+            .repository
+            .load()
+            .await
+            .with_tags(self.scope())
+            .execute()
+            .await?;
+
+        let mut history = FeedbackHistory::default();
+        for (key, context) in entries {
+            let (Some(fingerprint), Some(check_type)) =
+                (key.get_tag(FINGERPRINT_TAG), key.get_tag(CHECK_TYPE_TAG))
+            else {
+                continue;
+            };
+            let mut counts = FeedbackCounts::default();
+            match context.get_metric(ACCEPTED_METRIC) {
+                Some(MetricValue::Boolean(true)) => counts.accepted += 1,
+                Some(MetricValue::Boolean(false)) => counts.rejected += 1,
+                _ => {}
+            }
+            match context.get_metric(PASSED_METRIC) {
+                Some(MetricValue::Boolean(true)) => counts.passed += 1,
+                Some(MetricValue::Boolean(false)) => counts.failed += 1,
+                _ => {}
+            }
+            history.record(fingerprint, check_type, counts);
+        }
+        debug!(
+            check_types = history.by_check_type.len(),
+            "Loaded suggestion feedback"
+        );
+        Ok(history)
+    }
+
+    async fn save(
+        &self,
+        suggestion: &SuggestedConstraint,
+        metric: &str,
+        value: bool,
+    ) -> Result<()> {
+        let mut context = AnalyzerContext::new();
+        context.store_metric(metric, MetricValue::Boolean(value));
+        // The event id keeps entries recorded within the same millisecond apart
+        let key = ResultKey::now()
+            .with_tags(self.scope())
+            .with_tag(FINGERPRINT_TAG, suggestion.fingerprint())
+            .with_tag(CHECK_TYPE_TAG, &suggestion.check_type)
+            .with_tag(EVENT_TAG, uuid::Uuid::new_v4().to_string());
+        self.repository.save(key, context).await
+    }
+
+    fn scope(&self) -> HashMap<String, String> {
+        let mut tags = self.tags.clone();
+        tags.insert(KIND_TAG.to_string(), KIND.to_string());
+        tags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjustment_follows_the_evidence() {
+        let none = FeedbackCounts::default();
+        assert_eq!(none.adjust(0.8, 4.0), 0.8);
+        assert_eq!(none.acceptance_rate(), None);
+
+        let good = FeedbackCounts {
+            accepted: 4,
+            rejected: 0,
+            passed: 6,
+            failed: 0,
+        };
+        assert!((good.adjust(0.6, 4.0) - (2.4 + 10.0) / 14.0).abs() < 1e-9);
+
+        let bad = FeedbackCounts {
+            accepted: 1,
+            rejected: 3,
+            passed: 0,
+            failed: 4,
+        };
+        assert!(bad.adjust(0.9, 4.0) < 0.5);
+        assert_eq!(bad.acceptance_rate(), Some(0.25));
+        assert_eq!(bad.pass_rate(), Some(0.0));
+    }
+}
@@ -55,6 +55,9 @@ use std::collections::HashMap;
 use tracing::{debug, instrument};
 
 use crate::analyzers::profiler::{ColumnProfile, DetectedDataType};
+use crate::analyzers::suggestion_feedback::{SuggestionFeedback, SuggestionFeedbackStore};
+use crate::core::hash_parts;
+use crate::prelude::*;
 
 /// A suggested constraint with confidence and rationale
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -71,6 +74,30 @@ pub struct SuggestedConstraint {
     pub rationale: String,
     /// Priority level for implementation
     pub priority: SuggestionPriority,
+    /// Historical feedback that adjusted the confidence, if any was consulted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feedback: Option<SuggestionFeedback>,
+}
+
+impl SuggestedConstraint {
+    /// Returns a stable identifier of this suggestion.
+    ///
+    /// The fingerprint covers the check type, the column and the parameters but
+    /// not the confidence or rationale, so the same suggestion made in different
+    /// runs shares a fingerprint.
+    pub fn fingerprint(&self) -> String {
+        let mut parameters: Vec<String> = self
+            .parameters
+            .iter()
+            .map(|(name, value)| format!("{name}={value:?}"))
+            .collect();
+        parameters.sort();
+        hash_parts(
+            [self.check_type.as_str(), self.column.as_str()]
+                .into_iter()
+                .chain(parameters.iter().map(String::as_str)),
+        )
+    }
 }
 
 /// Parameter value for constraint configuration
@@ -108,6 +135,7 @@ pub struct SuggestionEngine {
     rules: Vec<Box<dyn ConstraintSuggestionRule>>,
     confidence_threshold: f64,
     max_suggestions_per_column: usize,
+    feedback_prior_weight: f64,
 }
 
 impl SuggestionEngine {
@@ -117,6 +145,7 @@ impl SuggestionEngine {
             rules: Vec::new(),
             confidence_threshold: 0.5,
             max_suggestions_per_column: 10,
+            feedback_prior_weight: 4.0,
         }
     }
 
@@ -138,9 +167,58 @@ impl SuggestionEngine {
         self
     }
 
+    /// Set how many observations the rule's own confidence is worth when it is
+    /// adjusted with recorded feedback
+    ///
+    /// Lower weights let feedback move the confidence faster.
+    pub fn feedback_prior_weight(mut self, weight: f64) -> Self {
+        self.feedback_prior_weight = weight.max(0.0);
+        self
+    }
+
     /// Generate constraint suggestions for a column profile
     #[instrument(skip(self, profile))]
     pub fn suggest_constraints(&self, profile: &ColumnProfile) -> Vec<SuggestedConstraint> {
+        let suggestions = self.apply_rules(profile);
+        self.select(profile, suggestions)
+    }
+
+    /// Generate constraint suggestions for a column profile, adjusting their
+    /// confidence with the feedback recorded in `feedback`
+    ///
+    /// Each suggestion's confidence is updated with the history of its check
+    /// type as described in the [`suggestion_feedback`](super::suggestion_feedback)
+    /// module, and annotated with the acceptance and pass rates used. The
+    /// confidence threshold applies to the adjusted confidence. Without a
+    /// store this behaves like [`suggest_constraints`](Self::suggest_constraints).
+    #[instrument(skip(self, profile, feedback))]
+    pub async fn suggest_constraints_with_feedback(
+        &self,
+        profile: &ColumnProfile,
+        feedback: Option<&SuggestionFeedbackStore>,
+    ) -> Result<Vec<SuggestedConstraint>> {
+        let mut suggestions = self.apply_rules(profile);
+        if let Some(store) = feedback {
+            let history = store.history().await?;
+            for suggestion in &mut suggestions {
+                let counts = history.for_check_type(&suggestion.check_type);
+                if counts.observations() == 0 {
+                    continue;
+                }
+                let base_confidence = suggestion.confidence;
+                suggestion.confidence = counts.adjust(base_confidence, self.feedback_prior_weight);
+                suggestion.feedback = Some(SuggestionFeedback {
+                    base_confidence,
+                    acceptance_rate: counts.acceptance_rate(),
+                    pass_rate: counts.pass_rate(),
+                    counts,
+                });
+            }
+        }
+        Ok(self.select(profile, suggestions))
+    }
+
+    fn apply_rules(&self, profile: &ColumnProfile) -> Vec<SuggestedConstraint> {
         debug!(
             column = profile.column_name,
             rules_count = self.rules.len(),
@@ -159,7 +237,14 @@ impl SuggestionEngine {
             );
             all_suggestions.extend(rule_suggestions);
         }
+        all_suggestions
+    }
 
+    fn select(
+        &self,
+        profile: &ColumnProfile,
+        mut all_suggestions: Vec<SuggestedConstraint>,
+    ) -> Vec<SuggestedConstraint> {
         // Filter by confidence threshold
         all_suggestions.retain(|s| s.confidence >= self.confidence_threshold);
 
@@ -262,6 +347,7 @@ impl ConstraintSuggestionRule for CompletenessRule {
                     completeness * 100.0
                 ),
                 priority: SuggestionPriority::High,
+                feedback: None,
             });
         } else if completeness >= self.medium_completeness_threshold {
             let mut params = HashMap::new();
@@ -280,6 +366,7 @@ impl ConstraintSuggestionRule for CompletenessRule {
                     completeness * 100.0
                 ),
                 priority: SuggestionPriority::Medium,
+                feedback: None,
             });
         } else if completeness < 0.5 {
             // Very low completeness - might indicate data quality issues
@@ -293,6 +380,7 @@ impl ConstraintSuggestionRule for CompletenessRule {
                     completeness * 100.0
                 ),
                 priority: SuggestionPriority::Critical,
+                feedback: None,
             });
         }
 
@@ -360,6 +448,7 @@ impl ConstraintSuggestionRule for UniquenessRule {
                     unique_ratio * 100.0
                 ),
                 priority: SuggestionPriority::High,
+                feedback: None,
             });
         } else if unique_ratio >= self.medium_uniqueness_threshold {
             let mut params = HashMap::new();
@@ -378,6 +467,7 @@ impl ConstraintSuggestionRule for UniquenessRule {
                     unique_ratio * 100.0
                 ),
                 priority: SuggestionPriority::Medium,
+                feedback: None,
             });
         }
 
@@ -391,6 +481,7 @@ impl ConstraintSuggestionRule for UniquenessRule {
                 confidence: 0.8,
                 rationale: "Column name suggests identifier and has high uniqueness".to_string(),
                 priority: SuggestionPriority::High,
+                feedback: None,
             });
         }
 
@@ -463,6 +554,7 @@ impl ConstraintSuggestionRule for PatternRule {
                     confidence: 0.85,
                     rationale: "Sample values suggest email format".to_string(),
                     priority: SuggestionPriority::Medium,
+                    feedback: None,
                 });
             }
 
@@ -474,6 +566,7 @@ impl ConstraintSuggestionRule for PatternRule {
                     confidence: 0.75,
                     rationale: "Sample values suggest date format".to_string(),
                     priority: SuggestionPriority::Medium,
+                    feedback: None,
                 });
             }
 
@@ -485,6 +578,7 @@ impl ConstraintSuggestionRule for PatternRule {
                     confidence: 0.70,
                     rationale: "Sample values suggest phone number format".to_string(),
                     priority: SuggestionPriority::Low,
+                    feedback: None,
                 });
             }
         }
@@ -548,6 +642,7 @@ impl ConstraintSuggestionRule for RangeRule {
                                 confidence: 0.8,
                                 rationale: format!("Minimum value observed: {min_val}"),
                                 priority: SuggestionPriority::Medium,
+                                feedback: None,
                             });
 
                             let mut max_params = HashMap::new();
@@ -563,6 +658,7 @@ impl ConstraintSuggestionRule for RangeRule {
                                 confidence: 0.8,
                                 rationale: format!("Maximum value observed: {max_val}"),
                                 priority: SuggestionPriority::Medium,
+                                feedback: None,
                             });
 
                             // Suggest positive values constraint if applicable
@@ -574,6 +670,7 @@ impl ConstraintSuggestionRule for RangeRule {
                                     confidence: 0.9,
                                     rationale: "All observed values are non-negative".to_string(),
                                     priority: SuggestionPriority::High,
+                                    feedback: None,
                                 });
                             }
                         }
@@ -596,6 +693,7 @@ impl ConstraintSuggestionRule for RangeRule {
                             confidence: 0.7,
                             rationale: "Suggests outlier detection based on P99".to_string(),
                             priority: SuggestionPriority::Low,
+                            feedback: None,
                         });
                     }
                 }
@@ -645,6 +743,7 @@ impl ConstraintSuggestionRule for DataTypeRule {
                     rationale: "Column has mixed data types, suggesting type consistency check"
                         .to_string(),
                     priority: SuggestionPriority::Critical,
+                    feedback: None,
                 });
             }
             DetectedDataType::Unknown => {
@@ -655,6 +754,7 @@ impl ConstraintSuggestionRule for DataTypeRule {
                     confidence: 0.8,
                     rationale: "Unable to determine data type, suggesting validation".to_string(),
                     priority: SuggestionPriority::High,
+                    feedback: None,
                 });
             }
             detected_type => {
@@ -671,6 +771,7 @@ impl ConstraintSuggestionRule for DataTypeRule {
                     confidence: 0.85,
                     rationale: format!("Column consistently contains {detected_type:?} values"),
                     priority: SuggestionPriority::Medium,
+                    feedback: None,
                 });
             }
         }
@@ -733,6 +834,7 @@ impl ConstraintSuggestionRule for CardinalityRule {
                     "Column has only {cardinality} distinct values, suggesting categorical constraint"
                 ),
                 priority: SuggestionPriority::High,
+                feedback: None,
             });
 
             // Suggest specific value constraints if we have histogram data
@@ -753,6 +855,7 @@ impl ConstraintSuggestionRule for CardinalityRule {
                     confidence: 0.85,
                     rationale: "Column has well-defined categorical values".to_string(),
                     priority: SuggestionPriority::Medium,
+                    feedback: None,
                 });
             }
         } else if cardinality <= self.categorical_threshold {
@@ -771,6 +874,7 @@ impl ConstraintSuggestionRule for CardinalityRule {
                     "Column has {cardinality} distinct values, suggesting cardinality monitoring"
                 ),
                 priority: SuggestionPriority::Medium,
+                feedback: None,
             });
         }
 
@@ -783,6 +887,7 @@ impl ConstraintSuggestionRule for CardinalityRule {
                 confidence: 0.6,
                 rationale: "High cardinality might indicate data quality issues".to_string(),
                 priority: SuggestionPriority::Low,
+                feedback: None,
             });
         }
 
@@ -1081,4 +1186,80 @@ mod tests {
         assert!(batch_results.contains_key("col1"));
         assert!(batch_results.contains_key("col2"));
     }
+
+    #[tokio::test]
+    async fn test_feedback_moves_confidence() {
+        use crate::repository::InMemoryRepository;
+        use std::sync::Arc;
+
+        let repository = Arc::new(InMemoryRepository::new());
+        let engine = SuggestionEngine::new()
+            .confidence_threshold(0.0)
+            .add_rule(Box::new(CompletenessRule::new()));
+        let profile = create_test_profile("id", 0.0);
+        async fn suggest(
+            engine: &SuggestionEngine,
+            profile: &ColumnProfile,
+            store: &SuggestionFeedbackStore,
+        ) -> SuggestedConstraint {
+            engine
+                .suggest_constraints_with_feedback(profile, Some(store))
+                .await
+                .unwrap()
+                .remove(0)
+        }
+
+        // Suggestions that get accepted and keep passing gain confidence
+        let trusted = SuggestionFeedbackStore::new(repository.clone()).with_tag("team", "a");
+        let first = suggest(&engine, &profile, &trusted).await;
+        assert_eq!(first.confidence, 0.9);
+        assert!(first.feedback.is_none());
+        let mut previous = first.confidence;
+        for _ in 0..3 {
+            let current = suggest(&engine, &profile, &trusted).await;
+            trusted.record_decision(&current, true).await.unwrap();
+            trusted.record_outcome(&current, true).await.unwrap();
+            let next = suggest(&engine, &profile, &trusted).await;
+            assert!(next.confidence > previous);
+            previous = next.confidence;
+        }
+        let feedback = suggest(&engine, &profile, &trusted).await.feedback.unwrap();
+        assert_eq!(feedback.base_confidence, 0.9);
+        assert_eq!(feedback.acceptance_rate, Some(1.0));
+        assert_eq!(feedback.pass_rate, Some(1.0));
+
+        // Suggestions that get rejected or fail lose confidence, in a separate scope
+        let doubtful = SuggestionFeedbackStore::new(repository).with_tag("team", "b");
+        let mut previous = 0.9;
+        for _ in 0..3 {
+            let current = suggest(&engine, &profile, &doubtful).await;
+            doubtful.record_decision(&current, false).await.unwrap();
+            doubtful.record_outcome(&current, false).await.unwrap();
+            let next = suggest(&engine, &profile, &doubtful).await;
+            assert!(next.confidence < previous);
+            previous = next.confidence;
+        }
+        assert!(previous < 0.5);
+
+        // The default threshold now drops the suggestion
+        let filtered = SuggestionEngine::new()
+            .add_rule(Box::new(CompletenessRule::new()))
+            .suggest_constraints_with_feedback(&profile, Some(&doubtful))
+            .await
+            .unwrap();
+        assert!(filtered.is_empty());
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_confidence() {
+        let profile = create_test_profile("id", 0.05);
+        let mut suggestion = CompletenessRule::new().apply(&profile).remove(0);
+        let fingerprint = suggestion.fingerprint();
+        suggestion.confidence = 0.1;
+        suggestion.rationale.clear();
+        assert_eq!(suggestion.fingerprint(), fingerprint);
+
+        suggestion.column = "other".to_string();
+        assert_ne!(suggestion.fingerprint(), fingerprint);
+    }
 }
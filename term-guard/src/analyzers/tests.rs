@@ -144,6 +144,8 @@ mod distribution_tests {
         let dist = MetricDistribution::from_buckets(buckets.clone());
         assert_eq!(dist.total_count, 18);
         assert_eq!(dist.buckets.len(), 3);
+        assert_eq!(dist.bin_edges, vec![0.0, 10.0, 20.0, 30.0]);
+        assert!(MetricDistribution::new().bin_edges.is_empty());
 
         let dist_with_stats = dist.with_stats(0.0, 30.0, 15.0, 5.5);
         assert_eq!(dist_with_stats.min, Some(0.0));
@@ -0,0 +1,836 @@
+//! Threshold recommendations from the history of a metric.
+//!
+//! A threshold chosen by feel is either so tight that normal variation fails
+//! the check or so loose that a real regression passes it. [`ThresholdAdvisor`]
+//! reads the values a metric took in earlier runs from a [`MetricsRepository`]
+//! and recommends the threshold that the history supports, together with the
+//! statistics it is based on and the share of historical runs that would have
+//! failed it.
+//!
+//! History is read either from metrics stored under a key, as saved by
+//! analyzers, [`HistoricalAssertionConstraint`] or
+//! [`ReportAggregator::save_check_metrics`], or from the outcomes of validation
+//! reports saved with [`ReportAggregator::save_report`], matched by
+//! [`constraint_id`]. [`ThresholdAdvisor::advise_for_suite`] does the latter for
+//! every constraint of a suite that has a one-sided threshold: the minimum
+//! fraction of ratio constraints such as completeness, uniqueness, format or
+//! containment, and the bound of statistics asserted with `greater than` or
+//! `less than`.
+//!
+//! ## Method
+//!
+//! For a lower bound, [`AdviceMethod::Percentile`] recommends a low percentile
+//! of the history (the 5th by default) minus a margin, and
+//! [`AdviceMethod::StdDev`] the mean minus `k` standard deviations. Upper bounds
+//! mirror them. Ratio thresholds are clamped to `0..=1`. The expected false
+//! alarm rate is the share of historical values that violate the recommended
+//! threshold.
+//!
+//! ## Applying recommendations
+//!
+//! A literal threshold is part of the [`constraint_id`], so changing it starts a
+//! new history. Constraints whose threshold is a named parameter keep their
+//! identifier, and [`ThresholdReport::parameters`] returns the recommended
+//! values as a [`ParameterBag`]: applying them is a change of the suite's
+//! parameter defaults rather than of its code.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use term_guard::analyzers::{AdviceMethod, ThresholdAdvisor};
+//! use term_guard::core::ValidationSuite;
+//! use term_guard::repository::InMemoryRepository;
+//!
+//! # async fn example(suite: &ValidationSuite, repository: &InMemoryRepository) -> term_guard::prelude::Result<()> {
+//! let advisor = ThresholdAdvisor::new()
+//!     .method(AdviceMethod::StdDev { k: 3.0 })
+//!     .with_tag("environment", "production")
+//!     .min_history(10);
+//!
+//! let report = advisor.advise_for_suite(suite, repository).await?;
+//! println!("{report}");
+//! std::fs::write("thresholds.json", report.parameters().to_json()?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`HistoricalAssertionConstraint`]: crate::constraints::HistoricalAssertionConstraint
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::analyzers::MetricValue;
+use crate::constraints::HistoryWindow;
+use crate::core::{
+    constraint_id, Constraint, ParameterBag, ValidationReport, ValidationSuite, REPORT_METADATA_KEY,
+};
+use crate::prelude::*;
+use crate::repository::{MetricsQuery, MetricsRepository, ResultKey, SortOrder};
+
+/// How a threshold is derived from the history of a metric.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AdviceMethod {
+    /// A percentile of the history, moved away from it by `margin`
+    ///
+    /// Lower bounds use the `percentile` (clamped to `0..=1`) and subtract the
+    /// margin; upper bounds use `1 - percentile` and add it.
+    Percentile {
+        /// The percentile, as a fraction
+        percentile: f64,
+        /// The distance between the percentile and the threshold
+        margin: f64,
+    },
+    /// The mean of the history, `k` standard deviations away
+    StdDev {
+        /// The number of standard deviations
+        k: f64,
+    },
+}
+
+impl Default for AdviceMethod {
+    fn default() -> Self {
+        AdviceMethod::Percentile {
+            percentile: 0.05,
+            margin: 0.0,
+        }
+    }
+}
+
+impl fmt::Display for AdviceMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdviceMethod::Percentile { percentile, margin } if *margin == 0.0 => {
+                write!(f, "p{}", percentile * 100.0)
+            }
+            AdviceMethod::Percentile { percentile, margin } => {
+                write!(f, "p{} with margin {margin}", percentile * 100.0)
+            }
+            AdviceMethod::StdDev { k } => write!(f, "mean and {k} standard deviations"),
+        }
+    }
+}
+
+/// Which side of a threshold a metric must stay on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThresholdDirection {
+    /// The metric must be at least the threshold
+    AtLeast,
+    /// The metric must be at most the threshold
+    AtMost,
+}
+
+impl ThresholdDirection {
+    /// Returns true if `value` violates `threshold`.
+    fn violates(self, value: f64, threshold: f64) -> bool {
+        match self {
+            ThresholdDirection::AtLeast => value < threshold,
+            ThresholdDirection::AtMost => value > threshold,
+        }
+    }
+}
+
+impl fmt::Display for ThresholdDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThresholdDirection::AtLeast => write!(f, "at least"),
+            ThresholdDirection::AtMost => write!(f, "at most"),
+        }
+    }
+}
+
+/// Statistics of the historical values of a metric.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HistoryStatistics {
+    /// Number of historical values
+    pub count: usize,
+    /// Arithmetic mean
+    pub mean: f64,
+    /// Sample standard deviation, 0 for a single value
+    pub std_dev: f64,
+    /// Smallest value
+    pub min: f64,
+    /// 5th percentile
+    pub p5: f64,
+    /// Median
+    pub median: f64,
+    /// 95th percentile
+    pub p95: f64,
+    /// Largest value
+    pub max: f64,
+}
+
+impl HistoryStatistics {
+    /// Computes the statistics of `values`, or `None` if there are none.
+    pub fn from_values(values: &[f64]) -> Option<Self> {
+        let sorted = sorted(values);
+        Self::from_sorted(&sorted)
+    }
+
+    fn from_sorted(sorted: &[f64]) -> Option<Self> {
+        let count = sorted.len();
+        if count == 0 {
+            return None;
+        }
+        let mean = sorted.iter().sum::<f64>() / count as f64;
+        let std_dev = if count > 1 {
+            let squares: f64 = sorted.iter().map(|value| (value - mean).powi(2)).sum();
+            (squares / (count - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        Some(Self {
+            count,
+            mean,
+            std_dev,
+            min: sorted[0],
+            p5: percentile(sorted, 0.05),
+            median: percentile(sorted, 0.5),
+            p95: percentile(sorted, 0.95),
+            max: sorted[count - 1],
+        })
+    }
+}
+
+/// A recommended threshold with the history supporting it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdAdvice {
+    /// The side of the threshold the metric must stay on
+    pub direction: ThresholdDirection,
+    /// The recommended threshold
+    pub recommended: f64,
+    /// The method the threshold was derived with
+    pub method: AdviceMethod,
+    /// Statistics of the historical values
+    pub statistics: HistoryStatistics,
+    /// Share of historical values that violate the recommended threshold
+    pub expected_false_alarm_rate: f64,
+}
+
+/// The recommendation for one constraint of a suite.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdRecommendation {
+    /// The name of the check holding the constraint
+    pub check_name: String,
+    /// The name of the constraint
+    pub constraint_name: String,
+    /// The stable identifier of the constraint, see [`constraint_id`]
+    pub constraint_id: String,
+    /// The side of the threshold the metric must stay on
+    pub direction: ThresholdDirection,
+    /// The threshold of the suite, if it is a literal or a parameter with a default
+    pub current: Option<f64>,
+    /// The parameter holding the threshold, if it is parameterized
+    pub parameter: Option<String>,
+    /// Number of historical values found
+    pub history: usize,
+    /// The recommendation, if the history is long enough
+    pub advice: Option<ThresholdAdvice>,
+}
+
+impl fmt::Display for ThresholdRecommendation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{} [{}]: {}",
+            self.check_name, self.constraint_name, self.constraint_id, self.direction
+        )?;
+        match (&self.parameter, self.current) {
+            (Some(name), Some(current)) => write!(f, " ${{{name}}} = {current}")?,
+            (Some(name), None) => write!(f, " ${{{name}}}")?,
+            (None, Some(current)) => write!(f, " {current}")?,
+            (None, None) => {}
+        }
+        match &self.advice {
+            Some(advice) => write!(
+                f,
+                ", recommended {} ({} of {} runs, mean {}, std dev {}, expected false alarms {:.1}%)",
+                advice.recommended,
+                advice.method,
+                advice.statistics.count,
+                advice.statistics.mean,
+                advice.statistics.std_dev,
+                advice.expected_false_alarm_rate * 100.0
+            ),
+            None => write!(f, ", not enough history ({} runs)", self.history),
+        }
+    }
+}
+
+/// Recommended thresholds for the constraints of a suite.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdReport {
+    /// The name of the suite
+    pub suite_name: String,
+    /// One recommendation per constraint with a one-sided threshold, in suite order
+    pub recommendations: Vec<ThresholdRecommendation>,
+}
+
+impl ThresholdReport {
+    /// Returns the recommended values of the parameterized thresholds.
+    ///
+    /// A parameter shared by several constraints gets the most lenient of their
+    /// recommendations. Merge the result into the suite's
+    /// [parameter defaults](crate::core::ValidationSuiteBuilder::parameter_defaults)
+    /// to apply it.
+    pub fn parameters(&self) -> ParameterBag {
+        let mut lenient: BTreeMap<&str, f64> = BTreeMap::new();
+        for recommendation in &self.recommendations {
+            let (Some(name), Some(advice)) = (&recommendation.parameter, &recommendation.advice)
+            else {
+                continue;
+            };
+            let value = advice.recommended;
+            lenient
+                .entry(name)
+                .and_modify(|current| {
+                    *current = match advice.direction {
+                        ThresholdDirection::AtLeast => current.min(value),
+                        ThresholdDirection::AtMost => current.max(value),
+                    }
+                })
+                .or_insert(value);
+        }
+        lenient
+            .into_iter()
+            .fold(ParameterBag::new(), |bag, (name, value)| {
+                bag.with(name, value)
+            })
+    }
+
+    /// Returns the recommendations that differ from the current thresholds.
+    pub fn changes(&self) -> impl Iterator<Item = &ThresholdRecommendation> {
+        self.recommendations.iter().filter(|recommendation| {
+            recommendation
+                .advice
+                .as_ref()
+                .is_some_and(|advice| recommendation.current != Some(advice.recommended))
+        })
+    }
+
+    /// Serializes the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| TermError::Serialization(e.to_string()))
+    }
+}
+
+impl fmt::Display for ThresholdReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Threshold recommendations for suite '{}' ({} constraints)",
+            self.suite_name,
+            self.recommendations.len()
+        )?;
+        for recommendation in &self.recommendations {
+            write!(f, "\n  - {recommendation}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Recommends thresholds from the history of metrics in a repository.
+///
+/// By default the advisor reads all history, needs at least 5 values and
+/// recommends the 5th percentile for lower bounds.
+#[derive(Debug, Clone)]
+pub struct ThresholdAdvisor {
+    method: AdviceMethod,
+    window: Option<HistoryWindow>,
+    min_history: usize,
+    tags: HashMap<String, String>,
+}
+
+impl Default for ThresholdAdvisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ThresholdAdvisor {
+    /// Creates an advisor with the default method and no tag filters.
+    pub fn new() -> Self {
+        Self {
+            method: AdviceMethod::default(),
+            window: None,
+            min_history: 5,
+            tags: HashMap::new(),
+        }
+    }
+
+    /// Sets how thresholds are derived from the history.
+    pub fn method(mut self, method: AdviceMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Reads only the historical values in `window`.
+    pub fn window(mut self, window: HistoryWindow) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    /// Sets the minimum number of historical values; with fewer no threshold is
+    /// recommended.
+    ///
+    /// Values below 1 are treated as 1.
+    pub fn min_history(mut self, min_history: usize) -> Self {
+        self.min_history = min_history.max(1);
+        self
+    }
+
+    /// Adds a tag that historical entries must carry.
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.insert(key.into(), value.into());
+        self
+    }
+
+    /// Recommends a threshold from historical `values`, or returns `None` if
+    /// there are fewer than the minimum.
+    pub fn advise_values(
+        &self,
+        values: &[f64],
+        direction: ThresholdDirection,
+    ) -> Option<ThresholdAdvice> {
+        self.advise_bounded(values, direction, None)
+    }
+
+    /// Recommends a threshold for the metric stored under `metric_key`.
+    #[instrument(skip(self, repository))]
+    pub async fn advise_metric(
+        &self,
+        repository: &dyn MetricsRepository,
+        metric_key: &str,
+        direction: ThresholdDirection,
+    ) -> Result<Option<ThresholdAdvice>> {
+        let mut values: Vec<f64> = self
+            .query(repository)
+            .await
+            .execute()
+            .await?
+            .into_iter()
+            .filter_map(|(_, context)| context.get_metric(metric_key).and_then(MetricValue::as_f64))
+            .collect();
+        self.apply_window(&mut values);
+        Ok(self.advise_values(&values, direction))
+    }
+
+    /// Recommends a threshold for the constraint with `constraint_id` from the
+    /// saved reports of the suite named `suite_name`.
+    #[instrument(skip(self, repository))]
+    pub async fn advise_constraint(
+        &self,
+        repository: &dyn MetricsRepository,
+        suite_name: &str,
+        constraint_id: &str,
+        direction: ThresholdDirection,
+    ) -> Result<Option<ThresholdAdvice>> {
+        let mut history = self.report_history(repository, suite_name).await?;
+        let values = history.remove(constraint_id).unwrap_or_default();
+        Ok(self.advise_values(&values, direction))
+    }
+
+    /// Recommends thresholds for every constraint of `suite` with a one-sided
+    /// threshold, from the saved reports of the suite.
+    #[instrument(skip_all, fields(suite = %suite.name()))]
+    pub async fn advise_for_suite(
+        &self,
+        suite: &ValidationSuite,
+        repository: &dyn MetricsRepository,
+    ) -> Result<ThresholdReport> {
+        let history = self.report_history(repository, suite.name()).await?;
+        let defaults = suite.parameter_defaults();
+
+        let mut recommendations = Vec::new();
+        for check in suite.checks() {
+            for (index, constraint) in check.constraints().iter().enumerate() {
+                let Some(bound) = threshold_bound(constraint.as_ref(), defaults) else {
+                    continue;
+                };
+                let id = constraint_id(constraint.as_ref());
+                let values = history.get(&id).map(Vec::as_slice).unwrap_or_default();
+                let limits = bound.ratio.then_some((0.0, 1.0));
+                recommendations.push(ThresholdRecommendation {
+                    check_name: check.name().to_string(),
+                    constraint_name: check.constraint_label(index).to_string(),
+                    constraint_id: id,
+                    direction: bound.direction,
+                    current: bound.current,
+                    parameter: bound.parameter,
+                    history: values.len(),
+                    advice: self.advise_bounded(values, bound.direction, limits),
+                });
+            }
+        }
+        debug!(
+            recommendations = recommendations.len(),
+            "Computed threshold recommendations"
+        );
+        Ok(ThresholdReport {
+            suite_name: suite.name().to_string(),
+            recommendations,
+        })
+    }
+
+    fn advise_bounded(
+        &self,
+        values: &[f64],
+        direction: ThresholdDirection,
+        limits: Option<(f64, f64)>,
+    ) -> Option<ThresholdAdvice> {
+        if values.len() < self.min_history {
+            return None;
+        }
+        let sorted = sorted(values);
+        let statistics = HistoryStatistics::from_sorted(&sorted)?;
+
+        let mut recommended = match (self.method, direction) {
+            (
+                AdviceMethod::Percentile {
+                    percentile: p,
+                    margin,
+                },
+                ThresholdDirection::AtLeast,
+            ) => percentile(&sorted, p) - margin.abs(),
+            (
+                AdviceMethod::Percentile {
+                    percentile: p,
+                    margin,
+                },
+                ThresholdDirection::AtMost,
+            ) => percentile(&sorted, 1.0 - p) + margin.abs(),
+            (AdviceMethod::StdDev { k }, ThresholdDirection::AtLeast) => {
+                statistics.mean - k.abs() * statistics.std_dev
+            }
+            (AdviceMethod::StdDev { k }, ThresholdDirection::AtMost) => {
+                statistics.mean + k.abs() * statistics.std_dev
+            }
+        };
+        if let Some((min, max)) = limits {
+            recommended = recommended.clamp(min, max);
+        }
+
+        let false_alarms = sorted
+            .iter()
+            .filter(|value| direction.violates(**value, recommended))
+            .count();
+        Some(ThresholdAdvice {
+            direction,
+            recommended,
+            method: self.method,
+            statistics,
+            expected_false_alarm_rate: false_alarms as f64 / sorted.len() as f64,
+        })
+    }
+
+    async fn query(&self, repository: &dyn MetricsRepository) -> MetricsQuery {
+        let mut query = repository
+            .load()
+            .await
+            .with_tags(self.tags.clone())
+            .sort(SortOrder::Ascending);
+        if let Some(HistoryWindow::LastDays(days)) = self.window {
+            let cutoff = ResultKey::now().timestamp - i64::from(days) * 86_400_000;
+            query = query.after(cutoff);
+        }
+        query
+    }
+
+    fn apply_window(&self, values: &mut Vec<f64>) {
+        if let Some(HistoryWindow::LastRuns(runs)) = self.window {
+            values.drain(..values.len().saturating_sub(runs));
+        }
+    }
+
+    /// Loads the metrics of the saved reports of a suite by constraint id,
+    /// oldest first.
+    async fn report_history(
+        &self,
+        repository: &dyn MetricsRepository,
+        suite_name: &str,
+    ) -> Result<HashMap<String, Vec<f64>>> {
+        let mut history: HashMap<String, Vec<f64>> = HashMap::new();
+        for (_, context) in self.query(repository).await.execute().await? {
+            let Some(json) = context.metadata().custom.get(REPORT_METADATA_KEY) else {
+                continue;
+            };
+            let report = ValidationReport::from_json_str(json)?;
+            if report.suite_name != suite_name {
+                continue;
+            }
+            // Skipped constraints and evaluation errors report no metric
+            for outcome in report.outcomes {
+                if let (Some(id), Some(metric)) = (outcome.constraint_id, outcome.metric) {
+                    history.entry(id).or_default().push(metric);
+                }
+            }
+        }
+        for values in history.values_mut() {
+            self.apply_window(values);
+        }
+        Ok(history)
+    }
+}
+
+/// The one-sided threshold of a constraint.
+struct ThresholdBound {
+    direction: ThresholdDirection,
+    current: Option<f64>,
+    parameter: Option<String>,
+    /// Whether the threshold is a fraction of rows
+    ratio: bool,
+}
+
+/// Finds the one-sided threshold of a constraint, if it has one.
+///
+/// Ratio constraints record their minimum fraction as the `threshold` metadata
+/// entry or reference it as a `threshold` parameter; statistic constraints
+/// record their assertion as the `assertion` entry.
+fn threshold_bound(constraint: &dyn Constraint, defaults: &ParameterBag) -> Option<ThresholdBound> {
+    let metadata = constraint.metadata();
+    let parameter = |name: &str| {
+        Some(ThresholdBound {
+            direction: ThresholdDirection::AtLeast,
+            current: defaults.get(name),
+            parameter: Some(name.to_string()),
+            ratio: true,
+        })
+    };
+
+    if let Some(threshold) = metadata.custom.get("threshold") {
+        if let Ok(current) = threshold.parse() {
+            return Some(ThresholdBound {
+                direction: ThresholdDirection::AtLeast,
+                current: Some(current),
+                parameter: None,
+                ratio: true,
+            });
+        }
+    }
+    let parameters = constraint.parameters();
+    if let [reference] = parameters.as_slice() {
+        if reference.used_by.ends_with("threshold") {
+            return parameter(&reference.name);
+        }
+    }
+
+    if metadata.custom.get("constraint_type").map(String::as_str) != Some("statistical") {
+        return None;
+    }
+    let assertion = metadata.custom.get("assertion")?;
+    let (direction, bound) = [
+        ("greater than or equal to ", ThresholdDirection::AtLeast),
+        ("greater than ", ThresholdDirection::AtLeast),
+        ("less than or equal to ", ThresholdDirection::AtMost),
+        ("less than ", ThresholdDirection::AtMost),
+    ]
+    .into_iter()
+    .find_map(|(prefix, direction)| {
+        assertion
+            .strip_prefix(prefix)
+            .map(|bound| (direction, bound))
+    })?;
+
+    let (current, parameter) = match bound
+        .strip_prefix("${")
+        .and_then(|name| name.strip_suffix('}'))
+    {
+        Some(name) => (defaults.get(name), Some(name.to_string())),
+        None => (Some(bound.parse().ok()?), None),
+    };
+    Some(ThresholdBound {
+        direction,
+        current,
+        parameter,
+        ratio: false,
+    })
+}
+
+pub(super) fn sorted(values: &[f64]) -> Vec<f64> {
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    sorted.sort_by(f64::total_cmp);
+    sorted
+}
+
+/// Returns the `fraction` percentile of sorted values, interpolating linearly
+/// between the two closest ranks.
+pub(super) fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    let rank = fraction.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::{
+        Assertion, CompletenessConstraint, StatisticType, StatisticalConstraint,
+    };
+    use crate::core::builder_extensions::CompletenessOptions;
+    use crate::core::{Check, ConstraintOutcome, ConstraintStatus, Level, ReportAggregator};
+    use crate::repository::InMemoryRepository;
+
+    fn history() -> Vec<f64> {
+        (0..20).map(|run| 0.9 + f64::from(run) * 0.005).collect()
+    }
+
+    #[test]
+    fn test_statistics() {
+        let statistics = HistoryStatistics::from_values(&[4.0, 1.0, 3.0, 2.0, 5.0]).unwrap();
+        assert_eq!(statistics.count, 5);
+        assert_eq!(statistics.mean, 3.0);
+        assert_eq!(statistics.median, 3.0);
+        assert_eq!((statistics.min, statistics.max), (1.0, 5.0));
+        assert!((statistics.std_dev - 2.5f64.sqrt()).abs() < 1e-12);
+        assert!((statistics.p5 - 1.2).abs() < 1e-12);
+        assert!(HistoryStatistics::from_values(&[]).is_none());
+    }
+
+    #[test]
+    fn test_percentile_advice() {
+        let advisor = ThresholdAdvisor::new();
+        let advice = advisor
+            .advise_values(&history(), ThresholdDirection::AtLeast)
+            .unwrap();
+        // p5 of 0.900..=0.995 interpolates between the first two values
+        assert!((advice.recommended - 0.90475).abs() < 1e-9);
+        assert_eq!(advice.expected_false_alarm_rate, 0.05);
+
+        let advice = advisor
+            .clone()
+            .method(AdviceMethod::Percentile {
+                percentile: 0.05,
+                margin: 0.01,
+            })
+            .advise_values(&history(), ThresholdDirection::AtMost)
+            .unwrap();
+        assert!((advice.recommended - 1.00025).abs() < 1e-9);
+        assert_eq!(advice.expected_false_alarm_rate, 0.0);
+
+        assert!(advisor
+            .min_history(21)
+            .advise_values(&history(), ThresholdDirection::AtLeast)
+            .is_none());
+    }
+
+    #[test]
+    fn test_std_dev_advice() {
+        let advice = ThresholdAdvisor::new()
+            .method(AdviceMethod::StdDev { k: 2.0 })
+            .advise_values(&[10.0, 12.0, 14.0, 16.0, 18.0], ThresholdDirection::AtLeast)
+            .unwrap();
+        let expected = 14.0 - 2.0 * 10f64.sqrt();
+        assert!((advice.recommended - expected).abs() < 1e-12);
+        assert_eq!(advice.expected_false_alarm_rate, 0.0);
+    }
+
+    #[test]
+    fn test_threshold_bounds() {
+        let defaults = ParameterBag::new().with("email_completeness", 0.9);
+
+        let bound = threshold_bound(
+            &CompletenessConstraint::with_threshold("email", 0.95),
+            &defaults,
+        )
+        .unwrap();
+        assert_eq!(bound.direction, ThresholdDirection::AtLeast);
+        assert_eq!((bound.current, bound.parameter), (Some(0.95), None));
+        assert!(bound.ratio);
+
+        let options = CompletenessOptions::threshold_param("email_completeness");
+        let bound = threshold_bound(
+            &CompletenessConstraint::new("email", options.into_constraint_options()).unwrap(),
+            &defaults,
+        )
+        .unwrap();
+        assert_eq!(bound.current, Some(0.9));
+        assert_eq!(bound.parameter.as_deref(), Some("email_completeness"));
+
+        let max = StatisticalConstraint::new(
+            "amount",
+            StatisticType::Max,
+            Assertion::less_than_or_equal_param("max_amount"),
+        )
+        .unwrap();
+        let bound = threshold_bound(&max, &defaults).unwrap();
+        assert_eq!(bound.direction, ThresholdDirection::AtMost);
+        assert_eq!(
+            (bound.current, bound.parameter.as_deref()),
+            (None, Some("max_amount"))
+        );
+        assert!(!bound.ratio);
+
+        let between =
+            StatisticalConstraint::new("amount", StatisticType::Mean, Assertion::Between(1.0, 2.0))
+                .unwrap();
+        assert!(threshold_bound(&between, &defaults).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_advise_for_suite_reads_saved_reports() {
+        let options = CompletenessOptions::threshold_param("email_completeness");
+        let suite = ValidationSuite::builder("customers")
+            .parameter_defaults(ParameterBag::new().with("email_completeness", 0.99))
+            .check(
+                Check::builder("contact")
+                    .level(Level::Error)
+                    .completeness("email", options.into_constraint_options())
+                    .build(),
+            )
+            .build();
+        let id = constraint_id(suite.checks()[0].constraints()[0].as_ref());
+
+        let repository = InMemoryRepository::new();
+        for (run, metric) in history().into_iter().enumerate() {
+            let mut report = ValidationReport::new("customers");
+            report.outcomes.push(ConstraintOutcome {
+                check_name: "contact".to_string(),
+                constraint_name: "completeness".to_string(),
+                level: Level::Error,
+                status: ConstraintStatus::Success,
+                waived: false,
+                metric: Some(metric),
+                threshold: None,
+                dimension: None,
+                constraint_id: Some(id.clone()),
+            });
+            let key = ResultKey::new(1_000 + run as i64).with_tag("env", "prod");
+            ReportAggregator::save_report(&repository, key, &report)
+                .await
+                .unwrap();
+        }
+
+        let report = ThresholdAdvisor::new()
+            .with_tag("env", "prod")
+            .window(HistoryWindow::LastRuns(10))
+            .advise_for_suite(&suite, &repository)
+            .await
+            .unwrap();
+        assert_eq!(report.recommendations.len(), 1);
+        let recommendation = &report.recommendations[0];
+        assert_eq!(recommendation.current, Some(0.99));
+        assert_eq!(recommendation.history, 10);
+        let advice = recommendation.advice.as_ref().unwrap();
+        // Saved reports round-trip through JSON, which may lose the last bit
+        assert!((advice.statistics.min - history()[10]).abs() < 1e-12);
+        assert_eq!(report.changes().count(), 1);
+        assert_eq!(
+            report.parameters().get("email_completeness"),
+            Some(advice.recommended)
+        );
+
+        // Other tags see no history
+        let report = ThresholdAdvisor::new()
+            .with_tag("env", "staging")
+            .advise_for_suite(&suite, &repository)
+            .await
+            .unwrap();
+        assert!(report.recommendations[0].advice.is_none());
+        assert!(report.parameters().is_empty());
+        assert!(report.to_string().contains("not enough history (0 runs)"));
+    }
+}
@@ -111,6 +111,12 @@ pub struct MetricDistribution {
 
     /// Standard deviation of the distribution.
     pub std_dev: Option<f64>,
+
+    /// Edges of the buckets in ascending order: the lower bound of each bucket
+    /// followed by the upper bound of the last one, so that bucket `i` spans
+    /// `bin_edges[i]..bin_edges[i + 1]`. Empty without buckets.
+    #[serde(default)]
+    pub bin_edges: Vec<f64>,
 }
 
 impl MetricDistribution {
@@ -123,12 +129,18 @@ impl MetricDistribution {
             max: None,
             mean: None,
             std_dev: None,
+            bin_edges: Vec::new(),
         }
     }
 
     /// Creates a distribution from a set of buckets.
     pub fn from_buckets(buckets: Vec<HistogramBucket>) -> Self {
         let total_count = buckets.iter().map(|b| b.count).sum();
+        let bin_edges = buckets
+            .iter()
+            .map(|b| b.lower_bound)
+            .chain(buckets.last().map(|b| b.upper_bound))
+            .collect();
         Self {
             buckets,
             total_count,
@@ -136,6 +148,7 @@ impl MetricDistribution {
             max: None,
             mean: None,
             std_dev: None,
+            bin_edges,
         }
     }
 
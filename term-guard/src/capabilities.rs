@@ -0,0 +1,171 @@
+//! Runtime discovery of the optional features the library was compiled with.
+//!
+//! Data sources and integrations such as cloud storage, databases or Delta
+//! tables are behind Cargo features. Language bindings and services that load
+//! a prebuilt library cannot see those features at compile time, so
+//! [`capabilities`] reports them at runtime together with version information.
+//! [`Capabilities::require`] turns a missing feature into an error telling how
+//! to rebuild, instead of an unknown source failing further down.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::capabilities;
+//!
+//! let capabilities = capabilities();
+//! println!("term-guard {}", capabilities.version);
+//!
+//! if let Err(e) = capabilities.require("s3") {
+//!     // "Configuration error: term-guard was compiled without the 's3' feature; ..."
+//!     eprintln!("{e}");
+//! }
+//!
+//! // Serializes to JSON for bindings
+//! let json = serde_json::to_string(&capabilities).unwrap();
+//! assert!(json.contains("\"s3\""));
+//! ```
+
+use crate::core::REPORT_SCHEMA_VERSION;
+use crate::error::{Result, TermError};
+use serde::{Deserialize, Serialize};
+
+/// The version and the optional features of the compiled library.
+///
+/// Every feature field is `true` when the Cargo feature of the same name is
+/// enabled; `cloud_storage` stands for the `cloud-storage` feature. Features
+/// enabled by another one, such as `cloud-storage` by `s3`, are reported as
+/// enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// The version of the term-guard crate
+    pub version: String,
+    /// The schema version of serialized validation reports
+    pub report_schema_version: u32,
+    /// Object store access (`cloud-storage`)
+    pub cloud_storage: bool,
+    /// Amazon S3 sources (`s3`)
+    pub s3: bool,
+    /// Google Cloud Storage sources (`gcs`)
+    pub gcs: bool,
+    /// Azure Blob Storage sources (`azure`)
+    pub azure: bool,
+    /// Database sources (`database`)
+    pub database: bool,
+    /// PostgreSQL sources (`postgres`)
+    pub postgres: bool,
+    /// MySQL sources (`mysql`)
+    pub mysql: bool,
+    /// SQLite sources (`sqlite`)
+    pub sqlite: bool,
+    /// Delta Lake sources (`delta`)
+    pub delta: bool,
+    /// Excel sources (`excel`)
+    pub excel: bool,
+    /// Arrow Flight sources and validation workers (`flight`)
+    pub flight: bool,
+    /// Conversion of Deequ, dbt and Great Expectations definitions (`migration`)
+    pub migration: bool,
+    /// Periodic validation runs (`scheduler`)
+    pub scheduler: bool,
+    /// Micro-batch validation of record batch streams (`streaming`)
+    pub streaming: bool,
+    /// OpenTelemetry tracing and metrics (`telemetry`)
+    pub telemetry: bool,
+    /// Custom data quality UDFs (`udfs`)
+    pub udfs: bool,
+    /// YAML configuration files (`yaml`)
+    pub yaml: bool,
+}
+
+/// Returns the version and the optional features of the compiled library.
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        report_schema_version: REPORT_SCHEMA_VERSION,
+        cloud_storage: cfg!(feature = "cloud-storage"),
+        s3: cfg!(feature = "s3"),
+        gcs: cfg!(feature = "gcs"),
+        azure: cfg!(feature = "azure"),
+        database: cfg!(feature = "database"),
+        postgres: cfg!(feature = "postgres"),
+        mysql: cfg!(feature = "mysql"),
+        sqlite: cfg!(feature = "sqlite"),
+        delta: cfg!(feature = "delta"),
+        excel: cfg!(feature = "excel"),
+        flight: cfg!(feature = "flight"),
+        migration: cfg!(feature = "migration"),
+        scheduler: cfg!(feature = "scheduler"),
+        streaming: cfg!(feature = "streaming"),
+        telemetry: cfg!(feature = "telemetry"),
+        udfs: cfg!(feature = "udfs"),
+        yaml: cfg!(feature = "yaml"),
+    }
+}
+
+impl Capabilities {
+    /// Returns every reported feature by its Cargo name, with whether it is enabled.
+    pub fn features(&self) -> Vec<(&'static str, bool)> {
+        vec![
+            ("cloud-storage", self.cloud_storage),
+            ("s3", self.s3),
+            ("gcs", self.gcs),
+            ("azure", self.azure),
+            ("database", self.database),
+            ("postgres", self.postgres),
+            ("mysql", self.mysql),
+            ("sqlite", self.sqlite),
+            ("delta", self.delta),
+            ("excel", self.excel),
+            ("flight", self.flight),
+            ("migration", self.migration),
+            ("scheduler", self.scheduler),
+            ("streaming", self.streaming),
+            ("telemetry", self.telemetry),
+            ("udfs", self.udfs),
+            ("yaml", self.yaml),
+        ]
+    }
+
+    /// Returns the Cargo names of the enabled features.
+    pub fn enabled_features(&self) -> Vec<&'static str> {
+        self.features()
+            .into_iter()
+            .filter_map(|(name, enabled)| enabled.then_some(name))
+            .collect()
+    }
+
+    /// Returns true if the feature with the given Cargo name is enabled.
+    ///
+    /// Unknown feature names are reported as disabled.
+    pub fn is_enabled(&self, feature: &str) -> bool {
+        self.features()
+            .into_iter()
+            .any(|(name, enabled)| enabled && name == feature)
+    }
+
+    /// Checks that the feature with the given Cargo name is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error naming the `--features` flag to rebuild
+    /// with if the feature is disabled, or listing the known features if the
+    /// name is unknown.
+    pub fn require(&self, feature: &str) -> Result<()> {
+        let features = self.features();
+        match features.iter().find(|(name, _)| *name == feature) {
+            Some((_, true)) => Ok(()),
+            Some((_, false)) => Err(TermError::Configuration(format!(
+                "term-guard {} was compiled without the '{feature}' feature; rebuild with --features {feature}",
+                self.version
+            ))),
+            None => Err(TermError::Configuration(format!(
+                "Unknown feature '{feature}', expected one of: {}",
+                features
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+}
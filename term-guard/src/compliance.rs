@@ -0,0 +1,539 @@
+//! Compliance helpers for scanning tables for personally identifiable information.
+//!
+//! Scanning every string column of a table for credit cards, emails, phone numbers
+//! and national IDs by hand means dozens of format constraints per table. [`PiiScan`]
+//! generates a single [`Check`] that scans a set of columns for a configurable set of
+//! PII types in one query, expecting zero occurrences.
+//!
+//! Each PII type is matched with the pattern of its detect-only [`FormatType`], under
+//! the same [`FormatOptions`] handling as [`FormatConstraint`](crate::constraints::FormatConstraint),
+//! so patterns are compiled through the run's regex cache and limits.
+//!
+//! # Example
+//!
+//! ```rust
+//! use term_guard::compliance::{PiiScan, PiiType};
+//!
+//! # fn example() -> term_guard::prelude::Result<()> {
+//! let (check, summary) = PiiScan::new()
+//!     .with_types(vec![PiiType::Email, PiiType::CreditCard])
+//!     .allow_column("email")
+//!     .for_columns(vec!["email", "notes", "comments"])?;
+//!
+//! assert_eq!(check.name(), "pii_scan");
+//! assert_eq!(summary.targets.len(), 4);
+//! assert_eq!(summary.allowed_columns, vec!["email".to_string()]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::constraints::{format_condition, register_format_functions, FormatOptions, FormatType};
+use crate::core::{
+    collect_with_metrics, current_validation_context, Check, Constraint, ConstraintMetadata,
+    ConstraintResult, Level,
+};
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+use arrow::array::Int64Array;
+use arrow::datatypes::{DataType, Schema};
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use tracing::{debug, instrument};
+
+/// A category of personally identifiable information that can be scanned for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiType {
+    /// Credit card numbers (Visa, MasterCard, Amex, Discover)
+    CreditCard,
+    /// Email addresses
+    Email,
+    /// North American phone numbers
+    Phone,
+    /// National identifiers (US Social Security Numbers)
+    NationalId,
+}
+
+impl PiiType {
+    /// Returns every supported PII type.
+    pub fn all() -> Vec<PiiType> {
+        vec![
+            PiiType::CreditCard,
+            PiiType::Email,
+            PiiType::Phone,
+            PiiType::NationalId,
+        ]
+    }
+
+    /// Returns a stable name for this PII type.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PiiType::CreditCard => "credit_card",
+            PiiType::Email => "email",
+            PiiType::Phone => "phone",
+            PiiType::NationalId => "national_id",
+        }
+    }
+
+    /// Returns the detect-only format used to recognize this PII type.
+    fn format_type(&self) -> FormatType {
+        match self {
+            PiiType::CreditCard => FormatType::CreditCard { detect_only: true },
+            PiiType::Email => FormatType::Email,
+            PiiType::Phone => FormatType::Phone {
+                country: Some("US".to_string()),
+            },
+            PiiType::NationalId => FormatType::SocialSecurityNumber,
+        }
+    }
+}
+
+impl fmt::Display for PiiType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A single column/PII type combination that will be scanned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiiScanTarget {
+    /// The column to scan
+    pub column: String,
+    /// The PII type to look for
+    pub pii_type: PiiType,
+}
+
+/// Describes what a generated PII scan check will look at.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PiiScanSummary {
+    /// Column/type combinations included in the scan
+    pub targets: Vec<PiiScanTarget>,
+    /// Columns excluded because they are expected to contain PII
+    pub allowed_columns: Vec<String>,
+}
+
+impl PiiScanSummary {
+    /// Returns the distinct columns that will be scanned, in scan order.
+    pub fn scanned_columns(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.targets
+            .iter()
+            .filter(|t| seen.insert(t.column.as_str()))
+            .map(|t| t.column.as_str())
+            .collect()
+    }
+}
+
+/// A column/PII type combination that had at least one matching value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiiHit {
+    /// The column containing the matches
+    pub column: String,
+    /// The PII type that matched
+    pub pii_type: PiiType,
+    /// Number of rows whose value matched
+    pub count: u64,
+}
+
+/// Builder that generates a PII scan check for a set of string columns.
+///
+/// By default every [`PiiType`] is scanned with [`FormatOptions::lenient`], the
+/// generated check is named `pii_scan` and runs at [`Level::Error`].
+#[derive(Debug, Clone)]
+pub struct PiiScan {
+    check_name: String,
+    level: Level,
+    pii_types: Vec<PiiType>,
+    options: FormatOptions,
+    allowlist: HashSet<String>,
+}
+
+impl Default for PiiScan {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PiiScan {
+    /// Creates a scan for all PII types.
+    pub fn new() -> Self {
+        Self {
+            check_name: "pii_scan".to_string(),
+            level: Level::Error,
+            pii_types: PiiType::all(),
+            options: FormatOptions::lenient(),
+            allowlist: HashSet::new(),
+        }
+    }
+
+    /// Sets the name of the generated check.
+    pub fn check_name(mut self, name: impl Into<String>) -> Self {
+        self.check_name = name.into();
+        self
+    }
+
+    /// Sets the severity level of the generated check.
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Restricts the scan to the given PII types.
+    pub fn with_types<I>(mut self, pii_types: I) -> Self
+    where
+        I: IntoIterator<Item = PiiType>,
+    {
+        self.pii_types = pii_types.into_iter().collect();
+        self
+    }
+
+    /// Sets the format options used to match values, such as case sensitivity and
+    /// trimming.
+    pub fn with_options(mut self, options: FormatOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Excludes a column that is expected to contain PII (e.g. the real email column).
+    pub fn allow_column(mut self, column: impl Into<String>) -> Self {
+        self.allowlist.insert(column.into());
+        self
+    }
+
+    /// Generates a check scanning the given columns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a column name is not a valid SQL identifier or
+    /// if no PII types are configured.
+    pub fn for_columns<I, S>(&self, columns: I) -> Result<(Check, PiiScanSummary)>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        if self.pii_types.is_empty() {
+            return Err(TermError::Configuration(
+                "PII scan requires at least one PII type".to_string(),
+            ));
+        }
+
+        let mut summary = PiiScanSummary::default();
+        for column in columns {
+            let column = column.into();
+            SqlSecurity::validate_identifier(&column)?;
+            if self.allowlist.contains(&column) {
+                summary.allowed_columns.push(column);
+                continue;
+            }
+            for pii_type in &self.pii_types {
+                summary.targets.push(PiiScanTarget {
+                    column: column.clone(),
+                    pii_type: *pii_type,
+                });
+            }
+        }
+
+        let check = Check::builder(&self.check_name)
+            .level(self.level)
+            .description("Detects personally identifiable information in string columns")
+            .constraint(
+                PiiScanConstraint::new(summary.targets.clone()).with_options(self.options.clone()),
+            )
+            .build();
+
+        Ok((check, summary))
+    }
+
+    /// Generates a check scanning every string column in the schema.
+    pub fn for_schema(&self, schema: &Schema) -> Result<(Check, PiiScanSummary)> {
+        let columns = schema
+            .fields()
+            .iter()
+            .filter(|f| {
+                matches!(
+                    f.data_type(),
+                    DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+                )
+            })
+            .map(|f| f.name().clone());
+        self.for_columns(columns)
+    }
+}
+
+/// A constraint that fails if any scanned column contains values matching a PII type.
+///
+/// All targets are evaluated in a single query. On failure the message lists
+/// every column/type combination with hits and the metric is the total hit count.
+/// The hit count of each target is reported in the result details under
+/// `hits.<column>.<type>`.
+#[derive(Debug, Clone)]
+pub struct PiiScanConstraint {
+    targets: Vec<PiiScanTarget>,
+    options: FormatOptions,
+}
+
+impl PiiScanConstraint {
+    /// Creates a constraint scanning the given targets with [`FormatOptions::lenient`].
+    pub fn new(targets: Vec<PiiScanTarget>) -> Self {
+        Self {
+            targets,
+            options: FormatOptions::lenient(),
+        }
+    }
+
+    /// Sets the format options used to match values.
+    pub fn with_options(mut self, options: FormatOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns the column/type combinations scanned by this constraint.
+    pub fn targets(&self) -> &[PiiScanTarget] {
+        &self.targets
+    }
+
+    /// Runs the scan and returns every column/type combination with matches.
+    pub async fn hits(&self, ctx: &SessionContext) -> Result<Vec<PiiHit>> {
+        if self.targets.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+
+        let mut selections = Vec::with_capacity(self.targets.len());
+        for (i, target) in self.targets.iter().enumerate() {
+            let column = SqlSecurity::escape_identifier(&target.column)?;
+            let format = target.pii_type.format_type();
+            register_format_functions(&format, ctx);
+            let matches = format_condition(&format, &self.options, &column)?;
+            selections.push(format!("COUNT(CASE WHEN {matches} THEN 1 END) AS hits_{i}"));
+        }
+        let sql = format!("SELECT {} FROM {table_name}", selections.join(", "));
+
+        let df = ctx.sql(&sql).await?;
+        let batches = collect_with_metrics(df).await?;
+        let batch = match batches.first() {
+            Some(batch) if batch.num_rows() > 0 => batch,
+            _ => return Ok(Vec::new()),
+        };
+
+        let mut hits = Vec::new();
+        for (i, target) in self.targets.iter().enumerate() {
+            let count = batch
+                .column(i)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .ok_or_else(|| TermError::Internal("Failed to extract PII hit count".to_string()))?
+                .value(0);
+            if count > 0 {
+                hits.push(PiiHit {
+                    column: target.column.clone(),
+                    pii_type: target.pii_type,
+                    count: count as u64,
+                });
+            }
+        }
+
+        Ok(hits)
+    }
+}
+
+#[async_trait]
+impl Constraint for PiiScanConstraint {
+    #[instrument(skip(self, ctx), fields(constraint.targets = self.targets.len()))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        if self.targets.is_empty() {
+            return Ok(ConstraintResult::skipped("No columns to scan for PII"));
+        }
+
+        let hits = self.hits(ctx).await?;
+        let total: u64 = hits.iter().map(|h| h.count).sum();
+
+        debug!(
+            constraint.name = %self.name(),
+            result.hits = hits.len(),
+            result.total = total,
+            "PII scan completed"
+        );
+
+        let result = if hits.is_empty() {
+            ConstraintResult::success_with_metric(0.0)
+        } else {
+            let details = hits
+                .iter()
+                .map(|h| format!("{} ({}): {}", h.column, h.pii_type, h.count))
+                .collect::<Vec<_>>()
+                .join(", ");
+            ConstraintResult::failure_with_metric(
+                total as f64,
+                format!(
+                    "PII detected in {} column/type combination(s): {details}",
+                    hits.len()
+                ),
+            )
+        };
+        Ok(self.targets.iter().fold(result, |result, target| {
+            let count = hits
+                .iter()
+                .find(|h| h.column == target.column && h.pii_type == target.pii_type)
+                .map_or(0, |h| h.count);
+            result.with_detail(
+                format!("hits.{}.{}", target.column, target.pii_type),
+                count as f64,
+            )
+        }))
+    }
+
+    fn name(&self) -> &str {
+        "pii_scan"
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        let mut columns = Vec::new();
+        for target in &self.targets {
+            if !columns.contains(&target.column) {
+                columns.push(target.column.clone());
+            }
+        }
+        ConstraintMetadata::for_columns(columns)
+            .with_description(format!(
+                "Checks that {} column/type combination(s) contain no PII",
+                self.targets.len()
+            ))
+            .with_custom("threshold", "0")
+            .with_custom("case_sensitive", self.options.case_sensitive.to_string())
+            .with_custom(
+                "trim_before_check",
+                self.options.trim_before_check.to_string(),
+            )
+            .with_custom("constraint_type", "compliance")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ConstraintStatus;
+    use crate::test_helpers::evaluate_constraint_with_context;
+    use arrow::array::StringArray;
+    use arrow::datatypes::Field;
+    use arrow::record_batch::RecordBatch;
+    use datafusion::datasource::MemTable;
+    use std::sync::Arc;
+
+    fn test_schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("email", DataType::Utf8, true),
+            Field::new("notes", DataType::Utf8, true),
+            Field::new("amount", DataType::Int64, true),
+        ]))
+    }
+
+    fn create_test_context(notes: Vec<Option<&str>>) -> SessionContext {
+        let ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("email", DataType::Utf8, true),
+            Field::new("notes", DataType::Utf8, true),
+        ]));
+        let emails: Vec<Option<&str>> = notes.iter().map(|_| Some("a@example.com")).collect();
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(emails)),
+                Arc::new(StringArray::from(notes)),
+            ],
+        )
+        .unwrap();
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("data", Arc::new(provider)).unwrap();
+        ctx
+    }
+
+    #[test]
+    fn test_for_schema_selects_string_columns() {
+        let (check, summary) = PiiScan::new()
+            .allow_column("email")
+            .for_schema(&test_schema())
+            .unwrap();
+
+        assert_eq!(check.level(), Level::Error);
+        assert_eq!(check.constraints().len(), 1);
+        assert_eq!(summary.scanned_columns(), vec!["notes"]);
+        assert_eq!(summary.targets.len(), PiiType::all().len());
+        assert_eq!(summary.allowed_columns, vec!["email".to_string()]);
+    }
+
+    #[test]
+    fn test_requires_pii_types() {
+        let result = PiiScan::new()
+            .with_types(Vec::new())
+            .for_columns(vec!["notes"]);
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scan_reports_hits() {
+        let ctx = create_test_context(vec![
+            Some("call me"),
+            Some("someone@example.com"),
+            Some("4111111111111111"),
+            Some("123-45-6789"),
+            None,
+        ]);
+        let (check, _) = PiiScan::new()
+            .allow_column("email")
+            .for_columns(vec!["email", "notes"])
+            .unwrap();
+
+        let constraint = &check.constraints()[0];
+        let result = evaluate_constraint_with_context(constraint.as_ref(), &ctx, "data")
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(3.0));
+        assert_eq!(result.details.len(), PiiType::all().len());
+        assert_eq!(result.details["hits.notes.email"], 1.0);
+        assert_eq!(result.details["hits.notes.credit_card"], 1.0);
+        assert_eq!(result.details["hits.notes.national_id"], 1.0);
+        assert_eq!(result.details["hits.notes.phone"], 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_scan_uses_format_options() {
+        let ctx = create_test_context(vec![Some("  123-45-6789  ")]);
+        let scan = PiiScan::new().with_types(vec![PiiType::NationalId]);
+
+        for (options, hits) in [(FormatOptions::lenient(), 1.0), (FormatOptions::new(), 0.0)] {
+            let (check, _) = scan
+                .clone()
+                .with_options(options)
+                .for_columns(vec!["notes"])
+                .unwrap();
+            let constraint = &check.constraints()[0];
+            let result = evaluate_constraint_with_context(constraint.as_ref(), &ctx, "data")
+                .await
+                .unwrap();
+            assert_eq!(result.metric, Some(hits));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_passes_without_pii() {
+        let ctx = create_test_context(vec![Some("nothing to see"), Some("all clear")]);
+        let (check, _) = PiiScan::new().for_columns(vec!["notes"]).unwrap();
+
+        let constraint = &check.constraints()[0];
+        let result = evaluate_constraint_with_context(constraint.as_ref(), &ctx, "data")
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.0));
+        assert_eq!(result.details["hits.notes.email"], 0.0);
+    }
+}
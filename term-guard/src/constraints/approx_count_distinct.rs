@@ -2,7 +2,8 @@
 
 use crate::constraints::Assertion;
 use crate::core::{
-    current_validation_context, Constraint, ConstraintMetadata, ConstraintResult, ConstraintStatus,
+    collect_with_metrics, current_validation_context, ColumnStatistics, Constraint,
+    ConstraintMetadata, ConstraintResult, ParameterRef,
 };
 use crate::prelude::*;
 use async_trait::async_trait;
@@ -72,7 +73,7 @@ impl Constraint for ApproxCountDistinctConstraint {
             )
         })?;
 
-        let batches = df.collect().await?;
+        let batches = collect_with_metrics(df).await?;
 
         if batches.is_empty() || batches[0].num_rows() == 0 {
             return Ok(ConstraintResult::skipped("No data to validate"));
@@ -93,30 +94,13 @@ impl Constraint for ApproxCountDistinctConstraint {
             })?
             .value(0) as f64;
 
-        // Evaluate the assertion
-        let assertion_result = self.assertion.evaluate(approx_count);
-
-        let status = if assertion_result {
-            ConstraintStatus::Success
-        } else {
-            ConstraintStatus::Failure
-        };
-
-        let message = if status == ConstraintStatus::Failure {
-            Some(format!(
-                "Approximate distinct count {approx_count} does not satisfy assertion {} for column '{}'",
-                self.assertion.description(),
-                self.column
-            ))
-        } else {
-            None
-        };
-
-        Ok(ConstraintResult {
-            status,
-            metric: Some(approx_count),
-            message,
-        })
+        Ok(self
+            .assertion
+            .explain(
+                approx_count,
+                format!("approximate distinct count of column '{}'", self.column),
+            )
+            .to_result())
     }
 
     fn name(&self) -> &str {
@@ -127,6 +111,31 @@ impl Constraint for ApproxCountDistinctConstraint {
         Some(&self.column)
     }
 
+    fn evaluate_from_statistics(&self, statistics: &ColumnStatistics) -> Option<ConstraintResult> {
+        // An exact distinct count is at least as good as the approximation
+        let distinct_count = statistics.distinct_count? as f64;
+        Some(
+            self.assertion
+                .explain(
+                    distinct_count,
+                    format!("distinct count of column '{}'", self.column),
+                )
+                .to_result(),
+        )
+    }
+
+    fn parameters(&self) -> Vec<ParameterRef> {
+        self.assertion.parameters("approx_count_distinct assertion")
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "the approximate number of distinct values in {} must be {}",
+            self.column,
+            self.assertion.requirement()
+        )
+    }
+
     fn metadata(&self) -> ConstraintMetadata {
         ConstraintMetadata::for_column(&self.column)
             .with_description(format!(
@@ -1,5 +1,11 @@
 //! Assertion types for statistical constraints.
+//!
+//! Constraints render why a value fails an [`Assertion`] through
+//! [`Assertion::explain`], so every failure message states the observed value,
+//! the expected value and how far apart they are in the same words.
 
+use crate::core::{ConstraintResult, ParamValue, ParameterRef};
+use crate::error::Result;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 /// An assertion that can be evaluated against a numeric value.
@@ -22,6 +28,10 @@ use std::fmt;
 /// // Value must be between 10 and 20
 /// let assertion = Assertion::Between(10.0, 20.0);
 /// assert!(assertion.evaluate(15.0));
+///
+/// // Lower bound read from the "min_rows" parameter when the suite runs
+/// let assertion = Assertion::greater_than_param("min_rows");
+/// assert_eq!(assertion.description(), "greater than ${min_rows}");
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Assertion {
@@ -41,10 +51,237 @@ pub enum Assertion {
     Between(f64, f64),
     /// Value must not be between the specified range
     NotBetween(f64, f64),
+    /// Value must satisfy an assertion whose bounds may be named parameters,
+    /// resolved when the suite runs
+    Parameterized(ParamAssertion),
+}
+
+/// An [`Assertion`] whose bounds are literal values or named parameters.
+///
+/// See [`ParameterBag`](crate::core::ParameterBag) for how parameters are supplied.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ParamAssertion {
+    /// Value must equal the bound
+    Equals(ParamValue),
+    /// Value must not equal the bound
+    NotEquals(ParamValue),
+    /// Value must be greater than the bound
+    GreaterThan(ParamValue),
+    /// Value must be greater than or equal to the bound
+    GreaterThanOrEqual(ParamValue),
+    /// Value must be less than the bound
+    LessThan(ParamValue),
+    /// Value must be less than or equal to the bound
+    LessThanOrEqual(ParamValue),
+    /// Value must be between the bounds (inclusive)
+    Between(ParamValue, ParamValue),
+    /// Value must not be between the bounds
+    NotBetween(ParamValue, ParamValue),
+}
+
+impl ParamAssertion {
+    /// Resolves the bounds from the current validation context.
+    pub fn resolve(&self) -> Result<Assertion> {
+        Ok(match self {
+            ParamAssertion::Equals(v) => Assertion::Equals(v.resolve()?),
+            ParamAssertion::NotEquals(v) => Assertion::NotEquals(v.resolve()?),
+            ParamAssertion::GreaterThan(v) => Assertion::GreaterThan(v.resolve()?),
+            ParamAssertion::GreaterThanOrEqual(v) => Assertion::GreaterThanOrEqual(v.resolve()?),
+            ParamAssertion::LessThan(v) => Assertion::LessThan(v.resolve()?),
+            ParamAssertion::LessThanOrEqual(v) => Assertion::LessThanOrEqual(v.resolve()?),
+            ParamAssertion::Between(min, max) => Assertion::Between(min.resolve()?, max.resolve()?),
+            ParamAssertion::NotBetween(min, max) => {
+                Assertion::NotBetween(min.resolve()?, max.resolve()?)
+            }
+        })
+    }
+
+    fn bounds(&self) -> Vec<&ParamValue> {
+        match self {
+            ParamAssertion::Equals(v)
+            | ParamAssertion::NotEquals(v)
+            | ParamAssertion::GreaterThan(v)
+            | ParamAssertion::GreaterThanOrEqual(v)
+            | ParamAssertion::LessThan(v)
+            | ParamAssertion::LessThanOrEqual(v) => vec![v],
+            ParamAssertion::Between(min, max) | ParamAssertion::NotBetween(min, max) => {
+                vec![min, max]
+            }
+        }
+    }
+
+    fn description(&self) -> String {
+        match self {
+            ParamAssertion::Equals(v) => format!("equals {v}"),
+            ParamAssertion::NotEquals(v) => format!("not equals {v}"),
+            ParamAssertion::GreaterThan(v) => format!("greater than {v}"),
+            ParamAssertion::GreaterThanOrEqual(v) => format!("greater than or equal to {v}"),
+            ParamAssertion::LessThan(v) => format!("less than {v}"),
+            ParamAssertion::LessThanOrEqual(v) => format!("less than or equal to {v}"),
+            ParamAssertion::Between(min, max) => format!("between {min} and {max}"),
+            ParamAssertion::NotBetween(min, max) => format!("not between {min} and {max}"),
+        }
+    }
+
+    fn requirement(&self) -> String {
+        match self {
+            ParamAssertion::Equals(v) => format!("equal to {v}"),
+            ParamAssertion::NotEquals(v) => format!("different from {v}"),
+            ParamAssertion::GreaterThan(v) => format!("greater than {v}"),
+            ParamAssertion::GreaterThanOrEqual(v) => format!("at least {v}"),
+            ParamAssertion::LessThan(v) => format!("less than {v}"),
+            ParamAssertion::LessThanOrEqual(v) => format!("at most {v}"),
+            ParamAssertion::Between(min, max) => format!("between {min} and {max}"),
+            ParamAssertion::NotBetween(min, max) => format!("outside {min} to {max}"),
+        }
+    }
+
+    fn expectation(&self) -> String {
+        match self {
+            ParamAssertion::Equals(v) => format!("{v}"),
+            ParamAssertion::NotEquals(v) => format!("≠ {v}"),
+            ParamAssertion::GreaterThan(v) => format!("> {v}"),
+            ParamAssertion::GreaterThanOrEqual(v) => format!("≥ {v}"),
+            ParamAssertion::LessThan(v) => format!("< {v}"),
+            ParamAssertion::LessThanOrEqual(v) => format!("≤ {v}"),
+            ParamAssertion::Between(min, max) => format!("between {min} and {max}"),
+            ParamAssertion::NotBetween(min, max) => format!("outside {min} to {max}"),
+        }
+    }
+}
+
+/// Why an observed value does or does not satisfy an [`Assertion`].
+///
+/// Created by [`Assertion::explain`]. Its [`Display`](fmt::Display) form is the
+/// failure message of the constraints asserting on a value, such as
+/// `Completeness ratio of column 'email' is 0.872, expected ≥ 0.99 (0.118 below)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssertionExplanation {
+    /// What the value measures, such as `minimum of column 'price'`
+    pub context: String,
+    /// The observed value
+    pub observed: f64,
+    /// The expected value in plain language, such as `≥ 0.99`
+    pub expected: String,
+    /// The observed value minus the bound it was compared with, if the
+    /// assertion has a single relevant bound
+    pub difference: Option<f64>,
+    /// Whether the observed value satisfies the assertion
+    pub passed: bool,
+}
+
+impl AssertionExplanation {
+    /// Returns whether the observed value is `"above"` or `"below"` the bound
+    /// it was compared with, or `None` if it equals it or there is no such bound.
+    pub fn direction(&self) -> Option<&'static str> {
+        match self.difference.map(round_difference) {
+            Some(difference) if difference > 0.0 => Some("above"),
+            Some(difference) if difference < 0.0 => Some("below"),
+            _ => None,
+        }
+    }
+
+    /// Returns a successful result with the observed value as metric if the
+    /// assertion holds, and a failure with this explanation as message otherwise.
+    pub fn to_result(&self) -> ConstraintResult {
+        if self.passed {
+            ConstraintResult::success_with_metric(self.observed)
+        } else {
+            ConstraintResult::failure_with_metric(self.observed, self.to_string())
+        }
+    }
+}
+
+impl fmt::Display for AssertionExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut context = self.context.chars();
+        if let Some(first) = context.next() {
+            write!(f, "{}{}", first.to_uppercase(), context.as_str())?;
+        }
+        write!(f, " is {}, expected {}", self.observed, self.expected)?;
+        match (self.direction(), self.difference) {
+            (Some(direction), Some(difference)) => {
+                write!(f, " ({} {direction})", round_difference(difference).abs())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Rounds away the floating point noise of subtracting two values, so that
+/// `0.99 - 0.872` renders as `0.118`.
+fn round_difference(difference: f64) -> f64 {
+    if difference.abs() >= 1e6 {
+        return difference;
+    }
+    (difference * 1e12).round() / 1e12
 }
 
 impl Assertion {
+    /// Value must equal the named parameter.
+    pub fn equals_param(name: impl Into<String>) -> Self {
+        Assertion::Parameterized(ParamAssertion::Equals(ParamValue::param(name)))
+    }
+
+    /// Value must be greater than the named parameter.
+    pub fn greater_than_param(name: impl Into<String>) -> Self {
+        Assertion::Parameterized(ParamAssertion::GreaterThan(ParamValue::param(name)))
+    }
+
+    /// Value must be greater than or equal to the named parameter.
+    pub fn greater_than_or_equal_param(name: impl Into<String>) -> Self {
+        Assertion::Parameterized(ParamAssertion::GreaterThanOrEqual(ParamValue::param(name)))
+    }
+
+    /// Value must be less than the named parameter.
+    pub fn less_than_param(name: impl Into<String>) -> Self {
+        Assertion::Parameterized(ParamAssertion::LessThan(ParamValue::param(name)))
+    }
+
+    /// Value must be less than or equal to the named parameter.
+    pub fn less_than_or_equal_param(name: impl Into<String>) -> Self {
+        Assertion::Parameterized(ParamAssertion::LessThanOrEqual(ParamValue::param(name)))
+    }
+
+    /// Value must be between two bounds, each a literal value or a named parameter.
+    ///
+    /// Strings are taken as parameter names.
+    ///
+    /// ```rust
+    /// use term_guard::constraints::Assertion;
+    ///
+    /// let assertion = Assertion::between_params("min_mean", 100.0);
+    /// assert_eq!(assertion.description(), "between ${min_mean} and 100");
+    /// ```
+    pub fn between_params(min: impl Into<ParamValue>, max: impl Into<ParamValue>) -> Self {
+        Assertion::Parameterized(ParamAssertion::Between(min.into(), max.into()))
+    }
+
+    /// Resolves parameterized bounds from the current validation context.
+    ///
+    /// Assertions with literal bounds are returned unchanged.
+    pub fn resolve(&self) -> Result<Assertion> {
+        match self {
+            Assertion::Parameterized(assertion) => assertion.resolve(),
+            other => Ok(other.clone()),
+        }
+    }
+
+    /// Returns the parameters referenced by the assertion's bounds.
+    pub(crate) fn parameters(&self, used_by: &str) -> Vec<ParameterRef> {
+        match self {
+            Assertion::Parameterized(assertion) => assertion
+                .bounds()
+                .into_iter()
+                .filter_map(|bound| bound.reference(used_by))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
     /// Evaluates the assertion against a value.
+    ///
+    /// Parameterized assertions whose parameters are not set evaluate to `false`.
     pub fn evaluate(&self, value: f64) -> bool {
         const EPSILON: f64 = 1e-10;
 
@@ -57,6 +294,81 @@ impl Assertion {
             Assertion::LessThanOrEqual(threshold) => value <= *threshold,
             Assertion::Between(min, max) => value >= *min && value <= *max,
             Assertion::NotBetween(min, max) => value < *min || value > *max,
+            Assertion::Parameterized(assertion) => assertion
+                .resolve()
+                .is_ok_and(|assertion| assertion.evaluate(value)),
+        }
+    }
+
+    /// Explains how `observed` compares with the assertion.
+    ///
+    /// `context` says what the value measures, such as `row count` or
+    /// `mean of column 'price'`. Parameterized bounds are resolved from the
+    /// current validation context; unresolved ones are shown by name and leave
+    /// the difference unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::Assertion;
+    ///
+    /// let explanation = Assertion::GreaterThanOrEqual(0.99)
+    ///     .explain(0.872, "completeness ratio of column 'email'");
+    /// assert!(!explanation.passed);
+    /// assert_eq!(explanation.direction(), Some("below"));
+    /// assert_eq!(
+    ///     explanation.to_string(),
+    ///     "Completeness ratio of column 'email' is 0.872, expected ≥ 0.99 (0.118 below)"
+    /// );
+    /// ```
+    pub fn explain(&self, observed: f64, context: impl Into<String>) -> AssertionExplanation {
+        let (expected, difference) = match self.resolve() {
+            Ok(assertion) => (assertion.expectation(), assertion.difference(observed)),
+            Err(_) => match self {
+                Assertion::Parameterized(assertion) => (assertion.expectation(), None),
+                other => (other.expectation(), other.difference(observed)),
+            },
+        };
+        AssertionExplanation {
+            context: context.into(),
+            observed,
+            expected,
+            difference,
+            passed: self.evaluate(observed),
+        }
+    }
+
+    /// Returns the expected value in symbols, such as "≥ 0.99".
+    fn expectation(&self) -> String {
+        match self {
+            Assertion::Equals(v) => format!("{v}"),
+            Assertion::NotEquals(v) => format!("≠ {v}"),
+            Assertion::GreaterThan(v) => format!("> {v}"),
+            Assertion::GreaterThanOrEqual(v) => format!("≥ {v}"),
+            Assertion::LessThan(v) => format!("< {v}"),
+            Assertion::LessThanOrEqual(v) => format!("≤ {v}"),
+            Assertion::Between(min, max) => format!("between {min} and {max}"),
+            Assertion::NotBetween(min, max) => format!("outside {min} to {max}"),
+            Assertion::Parameterized(assertion) => assertion.expectation(),
+        }
+    }
+
+    /// Returns `observed` minus the bound it is compared with.
+    ///
+    /// A range has a relevant bound only for values outside it.
+    fn difference(&self, observed: f64) -> Option<f64> {
+        match self {
+            Assertion::Equals(v)
+            | Assertion::NotEquals(v)
+            | Assertion::GreaterThan(v)
+            | Assertion::GreaterThanOrEqual(v)
+            | Assertion::LessThan(v)
+            | Assertion::LessThanOrEqual(v) => Some(observed - v),
+            Assertion::Between(min, _) if observed < *min => Some(observed - min),
+            Assertion::Between(_, max) if observed > *max => Some(observed - max),
+            Assertion::Between(..) | Assertion::NotBetween(..) | Assertion::Parameterized(_) => {
+                None
+            }
         }
     }
 
@@ -71,6 +383,23 @@ impl Assertion {
             Assertion::LessThanOrEqual(v) => format!("less than or equal to {v}"),
             Assertion::Between(min, max) => format!("between {min} and {max}"),
             Assertion::NotBetween(min, max) => format!("not between {min} and {max}"),
+            Assertion::Parameterized(assertion) => assertion.description(),
+        }
+    }
+
+    /// Returns what a value must be to satisfy the assertion, such as "at least 100",
+    /// for [`Constraint::describe`](crate::core::Constraint::describe).
+    pub(crate) fn requirement(&self) -> String {
+        match self {
+            Assertion::Equals(v) => format!("equal to {v}"),
+            Assertion::NotEquals(v) => format!("different from {v}"),
+            Assertion::GreaterThan(v) => format!("greater than {v}"),
+            Assertion::GreaterThanOrEqual(v) => format!("at least {v}"),
+            Assertion::LessThan(v) => format!("less than {v}"),
+            Assertion::LessThanOrEqual(v) => format!("at most {v}"),
+            Assertion::Between(min, max) => format!("between {min} and {max}"),
+            Assertion::NotBetween(min, max) => format!("outside {min} to {max}"),
+            Assertion::Parameterized(assertion) => assertion.requirement(),
         }
     }
 }
@@ -117,6 +446,73 @@ mod tests {
         assert!(!assertion.evaluate(20.1));
     }
 
+    #[tokio::test]
+    async fn test_parameterized_assertion() {
+        use crate::core::{ParameterBag, ValidationContext, CURRENT_CONTEXT};
+        use std::sync::Arc;
+
+        let assertion = Assertion::between_params("min", 20.0);
+        assert!(!assertion.evaluate(15.0));
+        assert!(assertion.resolve().is_err());
+        assert_eq!(
+            assertion.parameters("mean"),
+            vec![ParameterRef::new("min", "mean")]
+        );
+
+        let ctx = ValidationContext::new("data")
+            .with_parameters(Arc::new(ParameterBag::new().with("min", 10.0)));
+        CURRENT_CONTEXT
+            .scope(ctx, async {
+                assert!(assertion.evaluate(15.0));
+                assert!(!assertion.evaluate(9.0));
+                assert_eq!(assertion.resolve().unwrap(), Assertion::Between(10.0, 20.0));
+                assert_eq!(assertion.description(), "between 10 and 20");
+            })
+            .await;
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = Assertion::LessThanOrEqual(100.0).explain(142.5, "row count");
+        assert_eq!(
+            explanation.to_string(),
+            "Row count is 142.5, expected ≤ 100 (42.5 above)"
+        );
+        assert_eq!(explanation.difference, Some(42.5));
+
+        let explanation = Assertion::Between(10.0, 20.0).explain(15.0, "mean");
+        assert!(explanation.passed);
+        assert_eq!(
+            explanation.to_string(),
+            "Mean is 15, expected between 10 and 20"
+        );
+        assert_eq!(
+            Assertion::Between(10.0, 20.0)
+                .explain(7.0, "mean")
+                .to_string(),
+            "Mean is 7, expected between 10 and 20 (3 below)"
+        );
+        assert_eq!(
+            Assertion::NotEquals(3.0).explain(3.0, "size").to_string(),
+            "Size is 3, expected ≠ 3"
+        );
+
+        let result = Assertion::Equals(5.0).explain(3.0, "size").to_result();
+        assert_eq!(result.metric, Some(3.0));
+        assert_eq!(
+            result.message.as_deref(),
+            Some("Size is 3, expected 5 (2 below)")
+        );
+
+        // Unresolved parameters are shown by name
+        let explanation = Assertion::greater_than_param("min_rows").explain(3.0, "row count");
+        assert!(!explanation.passed);
+        assert_eq!(
+            explanation.to_string(),
+            "Row count is 3, expected > ${min_rows}"
+        );
+    }
+
     #[test]
     fn test_description() {
         assert_eq!(Assertion::Equals(10.0).description(), "equals 10");
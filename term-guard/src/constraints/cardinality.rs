@@ -0,0 +1,528 @@
+//! Referential cardinality validation for Term.
+//!
+//! [`ForeignKeyConstraint`](super::ForeignKeyConstraint) checks that child keys
+//! exist in the parent table. [`CardinalityConstraint`] checks how many rows are
+//! related through each key: for example that every shipment belongs to exactly
+//! one order, or that no customer has more than five subscriptions.
+//!
+//! Rows are counted per key value on both sides with `GROUP BY` and the counts
+//! are compared with a [`CardinalitySpec`]. The constraint passes when the
+//! fraction of keys satisfying the spec is at least the threshold; failing keys
+//! are reported with their observed counts.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::constraints::{CardinalityConstraint, CardinalitySpec};
+//! use term_guard::core::{Check, Level};
+//!
+//! // Each shipment refers to exactly one order
+//! let shipments = CardinalityConstraint::new(
+//!     "shipments.order_id",
+//!     "orders.order_id",
+//!     CardinalitySpec::ManyToOne,
+//! );
+//!
+//! // Each customer has at most 5 subscriptions
+//! let subscriptions = CardinalityConstraint::new(
+//!     "subscriptions.customer_id",
+//!     "customers.id",
+//!     CardinalitySpec::AtMost(5),
+//! )
+//! .allow_nulls(true);
+//!
+//! let check = Check::builder("relationships")
+//!     .level(Level::Error)
+//!     .with_constraint(shipments)
+//!     .with_constraint(subscriptions)
+//!     .build();
+//! ```
+
+use crate::core::{
+    collect_limited, collect_with_metrics, current_validation_context, parse_qualified_column,
+    skipped_without_values, CollectionSummary, Constraint, ConstraintMetadata, ConstraintResult,
+    ConstraintStatus, QualifiedTable,
+};
+use crate::error::{Result, TermError};
+use crate::security::SqlSecurity;
+use arrow::array::{Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use tracing::{debug, instrument, warn};
+
+/// Expected number of related rows per key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CardinalitySpec {
+    /// Each child key appears once and matches exactly one parent row
+    OneToOne,
+    /// Each child key matches exactly one parent row
+    ManyToOne,
+    /// Each parent key has at most `n` child rows
+    AtMost(u64),
+    /// Each parent key has between `min` and `max` child rows (inclusive)
+    Between(u64, u64),
+}
+
+impl CardinalitySpec {
+    /// SQL condition selecting the keys the spec applies to.
+    ///
+    /// One-to-one and many-to-one are checked for every child key; the per-parent
+    /// bounds for every parent key, including those without children.
+    fn applies_sql(&self) -> &'static str {
+        match self {
+            CardinalitySpec::OneToOne | CardinalitySpec::ManyToOne => "child_count > 0",
+            CardinalitySpec::AtMost(_) | CardinalitySpec::Between(_, _) => "parent_count > 0",
+        }
+    }
+
+    /// SQL condition selecting the keys that violate the spec.
+    fn violation_sql(&self) -> String {
+        match self {
+            CardinalitySpec::OneToOne => {
+                "child_count > 0 AND (child_count <> 1 OR parent_count <> 1)".to_string()
+            }
+            CardinalitySpec::ManyToOne => "child_count > 0 AND parent_count <> 1".to_string(),
+            CardinalitySpec::AtMost(max) => {
+                format!("parent_count > 0 AND child_count > {max}")
+            }
+            CardinalitySpec::Between(min, max) => {
+                format!("parent_count > 0 AND (child_count < {min} OR child_count > {max})")
+            }
+        }
+    }
+}
+
+impl fmt::Display for CardinalitySpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardinalitySpec::OneToOne => write!(f, "one-to-one"),
+            CardinalitySpec::ManyToOne => write!(f, "many-to-one"),
+            CardinalitySpec::AtMost(max) => write!(f, "at most {max} per parent key"),
+            CardinalitySpec::Between(min, max) => {
+                write!(f, "between {min} and {max} per parent key")
+            }
+        }
+    }
+}
+
+/// Cardinality constraint for validating how many rows are related through a key.
+///
+/// NULL child keys are handled as in
+/// [`ForeignKeyConstraint`](super::ForeignKeyConstraint): by default they count
+/// as one failing key, and with [`allow_nulls`](Self::allow_nulls) they are
+/// ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardinalityConstraint {
+    /// Column in the child table (e.g., "shipments.order_id")
+    child_column: String,
+    /// Column in the parent table (e.g., "orders.order_id")
+    parent_column: String,
+    /// Expected cardinality of the relationship
+    spec: CardinalitySpec,
+    /// Minimum fraction of keys that must satisfy the spec
+    threshold: f64,
+    /// Whether to allow NULL values in the child key column
+    allow_nulls: bool,
+    /// Maximum number of failing keys to report
+    max_violations_reported: usize,
+}
+
+impl CardinalityConstraint {
+    /// Create a new cardinality constraint that every key must satisfy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::{CardinalityConstraint, CardinalitySpec};
+    ///
+    /// let constraint = CardinalityConstraint::new(
+    ///     "shipments.order_id",
+    ///     "orders.order_id",
+    ///     CardinalitySpec::OneToOne,
+    /// );
+    /// ```
+    pub fn new(
+        child_column: impl Into<String>,
+        parent_column: impl Into<String>,
+        spec: CardinalitySpec,
+    ) -> Self {
+        Self {
+            child_column: child_column.into(),
+            parent_column: parent_column.into(),
+            spec,
+            threshold: 1.0,
+            allow_nulls: false,
+            max_violations_reported: 10,
+        }
+    }
+
+    /// Set the minimum fraction of keys that must satisfy the spec (default: 1.0).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the threshold is not between 0.0 and 1.0.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&threshold),
+            "Threshold must be between 0.0 and 1.0"
+        );
+        self.threshold = threshold;
+        self
+    }
+
+    /// Set whether to allow NULL values in the child key column.
+    ///
+    /// When `true`, rows with a NULL child key are ignored. When `false`, they
+    /// are counted as a single failing key.
+    pub fn allow_nulls(mut self, allow: bool) -> Self {
+        self.allow_nulls = allow;
+        self
+    }
+
+    /// Set the maximum number of failing keys to report.
+    ///
+    /// Defaults to 10. Set to 0 to disable violation example collection.
+    pub fn max_violations_reported(mut self, max_violations: usize) -> Self {
+        self.max_violations_reported = max_violations;
+        self
+    }
+
+    /// Get the child column name
+    pub fn child_column(&self) -> &str {
+        &self.child_column
+    }
+
+    /// Get the parent column name
+    pub fn parent_column(&self) -> &str {
+        &self.parent_column
+    }
+
+    /// Get the expected cardinality
+    pub fn spec(&self) -> CardinalitySpec {
+        self.spec
+    }
+
+    /// Parse table and column from qualified column name (e.g., "orders.order_id")
+    fn parse_qualified_column(&self, qualified_column: &str) -> Result<(QualifiedTable, String)> {
+        parse_qualified_column("cardinality", qualified_column)
+    }
+
+    /// Generate the query returning child and parent row counts per key.
+    fn key_counts_query(&self) -> Result<String> {
+        let (child_table, child_col) = self.parse_qualified_column(&self.child_column)?;
+        let (parent_table, parent_col) = self.parse_qualified_column(&self.parent_column)?;
+        let child_table = child_table.to_sql()?;
+        let child_col = SqlSecurity::escape_identifier(&child_col)?;
+        let parent_table = parent_table.to_sql()?;
+        let parent_col = SqlSecurity::escape_identifier(&parent_col)?;
+
+        let null_condition = if self.allow_nulls {
+            format!("WHERE {child_col} IS NOT NULL")
+        } else {
+            String::new()
+        };
+
+        // NULL child keys never join, so they form a key with no parent rows
+        Ok(format!(
+            "SELECT
+                CAST(COALESCE(c.key_value, p.key_value) AS VARCHAR) AS key_value,
+                c.key_value IS NULL AND p.key_value IS NULL AS is_null_key,
+                COALESCE(c.child_count, 0) AS child_count,
+                COALESCE(p.parent_count, 0) AS parent_count
+             FROM (
+                SELECT {child_col} AS key_value, COUNT(*) AS child_count
+                FROM {child_table} {null_condition}
+                GROUP BY {child_col}
+             ) AS c
+             FULL OUTER JOIN (
+                SELECT {parent_col} AS key_value, COUNT(*) AS parent_count
+                FROM {parent_table}
+                WHERE {parent_col} IS NOT NULL
+                GROUP BY {parent_col}
+             ) AS p ON c.key_value = p.key_value"
+        ))
+    }
+
+    /// Generate the query counting applicable and failing keys.
+    fn summary_query(&self) -> Result<String> {
+        let counts = self.key_counts_query()?;
+        let applies = self.spec.applies_sql();
+        let violation = self.spec.violation_sql();
+        let sql = format!(
+            "SELECT
+                COALESCE(SUM(CASE WHEN is_null_key OR ({applies}) THEN 1 ELSE 0 END), 0) AS total_keys,
+                COALESCE(SUM(CASE WHEN is_null_key OR ({violation}) THEN 1 ELSE 0 END), 0) AS failing_keys
+             FROM ({counts}) AS counts"
+        );
+        debug!("Generated cardinality validation query: {}", sql);
+        Ok(sql)
+    }
+
+    /// Generate the query listing failing keys, largest child counts first.
+    fn violations_query(&self) -> Result<String> {
+        let counts = self.key_counts_query()?;
+        let violation = self.spec.violation_sql();
+        let limit = self.max_violations_reported;
+        let sql = format!(
+            "SELECT key_value, child_count, parent_count
+             FROM ({counts}) AS counts
+             WHERE is_null_key OR ({violation})
+             ORDER BY is_null_key DESC, child_count DESC, parent_count DESC, key_value
+             LIMIT {limit}"
+        );
+        debug!("Generated cardinality violations query: {}", sql);
+        Ok(sql)
+    }
+
+    /// Collect the failing keys with their observed counts.
+    async fn collect_violations(
+        &self,
+        ctx: &SessionContext,
+    ) -> Result<(Vec<String>, CollectionSummary)> {
+        if self.max_violations_reported == 0 {
+            return Ok((Vec::new(), CollectionSummary::default()));
+        }
+
+        let df = ctx.sql(&self.violations_query()?).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                "cardinality",
+                format!("Failed to execute violations query: {e}"),
+            )
+        })?;
+        let collected = collect_limited(df).await?;
+
+        let validation_ctx = current_validation_context();
+        let key_columns = [self.child_column.as_str(), self.parent_column.as_str()];
+        let mut violations = Vec::with_capacity(self.max_violations_reported);
+        for batch in collected.iter()? {
+            let batch = batch?;
+            // `CAST(... AS VARCHAR)` may produce `Utf8View`
+            let keys = cast(batch.column(0), &DataType::Utf8)?;
+            let keys = keys
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .ok_or_else(|| TermError::Internal("Failed to extract keys".to_string()))?;
+            let child_counts = int64_column(batch.column(1), "child counts")?;
+            let parent_counts = int64_column(batch.column(2), "parent counts")?;
+            for i in 0..batch.num_rows() {
+                let key = if keys.is_null(i) {
+                    "NULL".to_string()
+                } else {
+                    validation_ctx
+                        .redact_any(&key_columns, keys.value(i))
+                        .into_owned()
+                };
+                violations.push(format!(
+                    "{key} ({} child, {} parent)",
+                    child_counts.value(i),
+                    parent_counts.value(i)
+                ));
+            }
+        }
+        Ok((violations, collected.summary()))
+    }
+}
+
+fn int64_column<'a>(column: &'a arrow::array::ArrayRef, name: &str) -> Result<&'a Int64Array> {
+    column
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .ok_or_else(|| TermError::Internal(format!("Failed to extract {name}")))
+}
+
+#[async_trait]
+impl Constraint for CardinalityConstraint {
+    #[instrument(skip(self, ctx), fields(constraint = "cardinality", spec = %self.spec))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        debug!(
+            "Evaluating cardinality constraint: {} -> {} ({})",
+            self.child_column, self.parent_column, self.spec
+        );
+
+        let df = ctx.sql(&self.summary_query()?).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                "cardinality",
+                format!("Cardinality validation query failed: {e}"),
+            )
+        })?;
+        let batches = collect_with_metrics(df).await?;
+
+        if batches.is_empty() || batches[0].num_rows() == 0 {
+            return Ok(ConstraintResult::skipped("No keys to validate"));
+        }
+        let batch = &batches[0];
+        let total_keys = int64_column(batch.column(0), "key count")?.value(0);
+        let failing_keys = int64_column(batch.column(1), "failing key count")?.value(0);
+
+        if total_keys == 0 {
+            let (child_table, _) = self.parse_qualified_column(&self.child_column)?;
+            return skipped_without_values(ctx, &child_table.to_sql()?, "No keys to validate")
+                .await;
+        }
+
+        let satisfied = (total_keys - failing_keys) as f64 / total_keys as f64;
+        if satisfied >= self.threshold {
+            return Ok(ConstraintResult::success_with_metric(satisfied));
+        }
+
+        let mut message = format!(
+            "Cardinality violation: {failing_keys} of {total_keys} keys of '{}' -> '{}' are not {} ({:.2}% satisfied, threshold {:.2}%)",
+            self.child_column,
+            self.parent_column,
+            self.spec,
+            satisfied * 100.0,
+            self.threshold * 100.0
+        );
+        let (violations, collection) = self.collect_violations(ctx).await?;
+        if !violations.is_empty() {
+            message.push_str(&format!(". Failing keys: [{}]", violations.join(", ")));
+        }
+
+        warn!("{}", message);
+
+        let mut details = BTreeMap::new();
+        collection.record(&mut details);
+        Ok(ConstraintResult {
+            status: ConstraintStatus::Failure,
+            metric: Some(satisfied),
+            message: Some(message),
+            quarantine: None,
+            constraint_id: None,
+            details,
+        })
+    }
+
+    fn name(&self) -> &str {
+        "cardinality"
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        ConstraintMetadata::new()
+            .with_description(format!(
+                "Checks that '{}' -> '{}' is {} for at least {:.2}% of keys",
+                self.child_column,
+                self.parent_column,
+                self.spec,
+                self.threshold * 100.0
+            ))
+            .with_custom("child_column", self.child_column.clone())
+            .with_custom("parent_column", self.parent_column.clone())
+            .with_custom("spec", self.spec.to_string())
+            .with_custom("threshold", self.threshold.to_string())
+            .with_custom("constraint_type", "referential")
+    }
+
+    fn referenced_tables(&self) -> Vec<String> {
+        [&self.child_column, &self.parent_column]
+            .into_iter()
+            .filter_map(|column| self.parse_qualified_column(column).ok())
+            .map(|(table, _)| table.to_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ValidationContext, CURRENT_CONTEXT};
+
+    async fn create_tables() -> SessionContext {
+        let ctx = SessionContext::new();
+        for sql in [
+            "CREATE TABLE orders (order_id INT) AS VALUES (1), (2), (3), (4), (4)",
+            "CREATE TABLE shipments (order_id INT) AS VALUES (1), (2), (2), (4), (NULL)",
+        ] {
+            ctx.sql(sql).await.unwrap().collect().await.unwrap();
+        }
+        ctx
+    }
+
+    async fn evaluate(ctx: &SessionContext, constraint: CardinalityConstraint) -> ConstraintResult {
+        CURRENT_CONTEXT
+            .scope(
+                ValidationContext::new("shipments"),
+                constraint.evaluate(ctx),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_specs_count_keys_on_the_right_side() {
+        let ctx = create_tables().await;
+        let constraint = |spec| {
+            CardinalityConstraint::new("shipments.order_id", "orders.order_id", spec)
+                .allow_nulls(true)
+        };
+
+        // Child keys 1, 2, 4: key 4 has two parent rows
+        let result = evaluate(&ctx, constraint(CardinalitySpec::ManyToOne)).await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(2.0 / 3.0));
+        assert!(result
+            .message
+            .unwrap()
+            .contains("Failing keys: [4 (1 child, 2 parent)]"));
+
+        // Key 2 also has two shipments
+        let result = evaluate(&ctx, constraint(CardinalitySpec::OneToOne)).await;
+        assert_eq!(result.metric, Some(1.0 / 3.0));
+
+        // Parent keys 1, 2, 3, 4 have 1, 2, 0 and 1 shipments
+        let result = evaluate(&ctx, constraint(CardinalitySpec::AtMost(2))).await;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        let result = evaluate(&ctx, constraint(CardinalitySpec::Between(1, 1))).await;
+        assert_eq!(result.metric, Some(0.5));
+    }
+
+    #[tokio::test]
+    async fn test_null_keys_and_threshold() {
+        let ctx = create_tables().await;
+
+        let strict = CardinalityConstraint::new(
+            "shipments.order_id",
+            "orders.order_id",
+            CardinalitySpec::AtMost(2),
+        );
+        let result = evaluate(&ctx, strict.clone()).await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert!(result
+            .message
+            .unwrap()
+            .contains("Failing keys: [NULL (1 child, 0 parent)]"));
+
+        let result = evaluate(&ctx, strict.threshold(0.8)).await;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.8));
+    }
+
+    #[test]
+    fn test_constraint_configuration() {
+        let constraint = CardinalityConstraint::new(
+            "staging.shipments.order_id",
+            "orders.id",
+            CardinalitySpec::OneToOne,
+        );
+        assert_eq!(constraint.name(), "cardinality");
+        assert_eq!(constraint.spec(), CardinalitySpec::OneToOne);
+        assert_eq!(
+            constraint.referenced_tables(),
+            vec!["staging.shipments", "orders"]
+        );
+        assert_eq!(
+            CardinalitySpec::Between(1, 5).to_string(),
+            "between 1 and 5 per parent key"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Threshold must be between 0.0 and 1.0")]
+    fn test_invalid_threshold() {
+        CardinalityConstraint::new("a.id", "b.id", CardinalitySpec::ManyToOne).threshold(1.5);
+    }
+}
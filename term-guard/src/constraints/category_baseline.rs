@@ -0,0 +1,534 @@
+//! Baseline validation of categorical values for Term.
+//!
+//! A containment constraint needs the allowed values up front, which is too
+//! rigid for columns whose categories evolve through an approval process.
+//! [`CategoryBaselineConstraint`] instead compares the distinct values of a column
+//! with an approved baseline snapshot persisted in a [`StateStore`], and fails
+//! when values appear that are not part of it.
+//!
+//! The first run stores the current values as the baseline. New values are then
+//! approved with [`CategoryBaselineConstraint::approve_values`], or the whole
+//! baseline is refreshed with [`CategoryBaselineConstraint::approve_baseline`] or
+//! by running in [`record_baseline`](CategoryBaselineConstraint::record_baseline)
+//! mode.
+//!
+//! Baselines are keyed by suite, table and column. Their size is bounded: when a
+//! column has more distinct values than
+//! [`max_distinct_values`](CategoryBaselineConstraint::max_distinct_values), the
+//! constraint is skipped with a warning.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use term_guard::analyzers::incremental::FileSystemStateStore;
+//! use term_guard::constraints::CategoryBaselineConstraint;
+//! use term_guard::core::{Check, Level};
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let store = Arc::new(FileSystemStateStore::new("/tmp/term_baselines")?);
+//!
+//! let check = Check::builder("payments")
+//!     .level(Level::Error)
+//!     .with_constraint(
+//!         CategoryBaselineConstraint::new("payments_suite", "payment_method", store)
+//!             // A handful of test transactions should not fail the check
+//!             .min_frequency(5),
+//!     )
+//!     .build();
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::analyzers::incremental::{StateMap, StateStore};
+use crate::core::{
+    collect_limited, current_validation_context, is_table_empty, CollectionSummary, Constraint,
+    ConstraintMetadata, ConstraintResult,
+};
+use crate::error::{Result, TermError};
+use crate::security::SqlSecurity;
+use arrow::array::{Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Arc;
+use tracing::{debug, instrument, warn};
+
+/// Key of the baseline within its state partition.
+const BASELINE_KEY: &str = "category_baseline";
+
+/// Default maximum number of distinct values in a baseline.
+const DEFAULT_MAX_DISTINCT_VALUES: usize = 1000;
+
+/// An approved set of categorical values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategoryBaseline {
+    /// The approved values
+    pub values: BTreeSet<String>,
+    /// When the baseline was last recorded or approved
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Distinct values of a column with their row counts.
+enum ObservedValues {
+    Values(BTreeMap<String, i64>),
+    TooMany,
+    /// The values exceeded the collection limits of the run
+    Truncated(CollectionSummary),
+}
+
+/// Constraint failing when a column contains values missing from its baseline.
+///
+/// NULL values are ignored. Values are compared as strings.
+#[derive(Clone)]
+pub struct CategoryBaselineConstraint {
+    /// Name of the suite the baseline belongs to
+    suite_name: String,
+    /// The column to check
+    column: String,
+    /// Store persisting the baseline
+    store: Arc<dyn StateStore>,
+    /// Whether every run replaces the baseline with the current values
+    record_baseline: bool,
+    /// Minimum number of rows for a new value to fail the constraint
+    min_frequency: u64,
+    /// Maximum number of distinct values kept in the baseline
+    max_distinct_values: usize,
+}
+
+impl CategoryBaselineConstraint {
+    /// Creates a constraint for a column, storing its baseline in `store`.
+    ///
+    /// `suite_name` scopes the baseline together with the validated table and
+    /// the column, so several suites can share a store.
+    pub fn new(
+        suite_name: impl Into<String>,
+        column: impl Into<String>,
+        store: Arc<dyn StateStore>,
+    ) -> Self {
+        Self {
+            suite_name: suite_name.into(),
+            column: column.into(),
+            store,
+            record_baseline: false,
+            min_frequency: 1,
+            max_distinct_values: DEFAULT_MAX_DISTINCT_VALUES,
+        }
+    }
+
+    /// Sets whether each run records the current values as the new baseline.
+    ///
+    /// In record mode the constraint always succeeds. Use it for a run that
+    /// intentionally accepts the current state of the data.
+    pub fn record_baseline(mut self, record: bool) -> Self {
+        self.record_baseline = record;
+        self
+    }
+
+    /// Sets the minimum number of rows a new value needs to fail the constraint (default: 1).
+    ///
+    /// New values seen in fewer rows are ignored, e.g. a handful of test records.
+    pub fn min_frequency(mut self, min_frequency: u64) -> Self {
+        self.min_frequency = min_frequency;
+        self
+    }
+
+    /// Sets the maximum number of distinct values kept in the baseline (default: 1000).
+    ///
+    /// Columns with more distinct values are not categorical; the constraint is
+    /// skipped with a warning and no baseline is stored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is 0.
+    pub fn max_distinct_values(mut self, max: usize) -> Self {
+        assert!(max > 0, "max_distinct_values must be greater than 0");
+        self.max_distinct_values = max;
+        self
+    }
+
+    /// Returns the column being checked.
+    pub fn column(&self) -> &str {
+        &self.column
+    }
+
+    /// Loads the stored baseline for a table, if one exists.
+    pub async fn baseline(&self, table_name: &str) -> Result<Option<CategoryBaseline>> {
+        let state = self.store.load_state(&self.partition(table_name)).await?;
+        state
+            .get(BASELINE_KEY)
+            .map(|bytes| {
+                serde_json::from_slice(bytes).map_err(|e| {
+                    TermError::Serialization(format!("Invalid category baseline: {e}"))
+                })
+            })
+            .transpose()
+    }
+
+    /// Replaces the baseline of a table with the column's current values.
+    ///
+    /// Returns the number of values in the new baseline.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the column has more distinct values than
+    /// [`max_distinct_values`](Self::max_distinct_values).
+    pub async fn approve_baseline(&self, ctx: &SessionContext, table_name: &str) -> Result<usize> {
+        match self.observe(ctx, table_name).await? {
+            ObservedValues::Values(values) => {
+                let count = values.len();
+                self.save(table_name, values.into_keys().collect()).await?;
+                Ok(count)
+            }
+            ObservedValues::TooMany => Err(self.too_many_values_error()),
+            ObservedValues::Truncated(_) => Err(self.truncated_error()),
+        }
+    }
+
+    /// Adds values to the baseline of a table, creating it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the baseline would exceed
+    /// [`max_distinct_values`](Self::max_distinct_values).
+    pub async fn approve_values<I, S>(&self, table_name: &str, values: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut approved = self
+            .baseline(table_name)
+            .await?
+            .map(|baseline| baseline.values)
+            .unwrap_or_default();
+        approved.extend(values.into_iter().map(Into::into));
+        if approved.len() > self.max_distinct_values {
+            return Err(self.too_many_values_error());
+        }
+        self.save(table_name, approved).await
+    }
+
+    /// Partition of the store holding the baseline of a table.
+    fn partition(&self, table_name: &str) -> String {
+        let mut hasher = Sha256::new();
+        for part in [self.suite_name.as_str(), table_name, self.column.as_str()] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        format!("category_baseline_{:x}", hasher.finalize())
+    }
+
+    async fn save(&self, table_name: &str, values: BTreeSet<String>) -> Result<()> {
+        let baseline = CategoryBaseline {
+            values,
+            updated_at: Utc::now(),
+        };
+        let bytes = serde_json::to_vec(&baseline).map_err(|e| {
+            TermError::Serialization(format!("Failed to serialize category baseline: {e}"))
+        })?;
+        let mut state = StateMap::new();
+        state.insert(BASELINE_KEY.to_string(), bytes);
+        self.store
+            .save_state(&self.partition(table_name), state)
+            .await?;
+        Ok(())
+    }
+
+    fn too_many_values_error(&self) -> TermError {
+        TermError::constraint_evaluation(
+            "category_baseline",
+            format!(
+                "Column '{}' has more than {} distinct values",
+                self.column, self.max_distinct_values
+            ),
+        )
+    }
+
+    fn truncated_error(&self) -> TermError {
+        TermError::constraint_evaluation(
+            "category_baseline",
+            format!(
+                "The distinct values of column '{}' exceed the collection limits",
+                self.column
+            ),
+        )
+    }
+
+    /// Queries the distinct non-null values of the column with their row counts.
+    async fn observe(&self, ctx: &SessionContext, table_name: &str) -> Result<ObservedValues> {
+        let column = SqlSecurity::escape_identifier(&self.column)?;
+        // One extra row tells whether the limit was exceeded
+        let limit = self.max_distinct_values + 1;
+        let sql = format!(
+            "SELECT CAST({column} AS VARCHAR) AS value, COUNT(*) AS value_count
+             FROM {table_name}
+             WHERE {column} IS NOT NULL
+             GROUP BY {column}
+             LIMIT {limit}"
+        );
+        debug!("Generated category baseline query: {}", sql);
+
+        let df = ctx.sql(&sql).await?;
+        let collected = collect_limited(df).await?;
+        // A partial set of values can neither be recorded nor compared
+        if collected.is_truncated() {
+            return Ok(ObservedValues::Truncated(collected.summary()));
+        }
+
+        let mut values = BTreeMap::new();
+        for batch in collected.iter()? {
+            let batch = batch?;
+            // `CAST(... AS VARCHAR)` may produce `Utf8View`
+            let keys = cast(batch.column(0), &DataType::Utf8)?;
+            let keys = keys
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .ok_or_else(|| TermError::Internal("Failed to extract values".to_string()))?;
+            let counts = batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .ok_or_else(|| TermError::Internal("Failed to extract counts".to_string()))?;
+            for i in 0..batch.num_rows() {
+                values.insert(keys.value(i).to_string(), counts.value(i));
+            }
+        }
+
+        if values.len() > self.max_distinct_values {
+            Ok(ObservedValues::TooMany)
+        } else {
+            Ok(ObservedValues::Values(values))
+        }
+    }
+}
+
+impl fmt::Debug for CategoryBaselineConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CategoryBaselineConstraint")
+            .field("suite_name", &self.suite_name)
+            .field("column", &self.column)
+            .field("record_baseline", &self.record_baseline)
+            .field("min_frequency", &self.min_frequency)
+            .field("max_distinct_values", &self.max_distinct_values)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Constraint for CategoryBaselineConstraint {
+    #[instrument(skip(self, ctx), fields(constraint = "category_baseline", column = %self.column))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+
+        let observed = match self.observe(ctx, table_name).await? {
+            ObservedValues::Values(values) => values,
+            ObservedValues::TooMany => {
+                warn!(
+                    column = %self.column,
+                    max_distinct_values = self.max_distinct_values,
+                    "Column has too many distinct values for a category baseline"
+                );
+                return Ok(ConstraintResult::skipped(format!(
+                    "Column '{}' has more than {} distinct values; category baseline skipped",
+                    self.column, self.max_distinct_values
+                )));
+            }
+            ObservedValues::Truncated(summary) => {
+                let mut result = ConstraintResult::skipped(format!(
+                    "The distinct values of column '{}' exceed the collection limits; category baseline skipped",
+                    self.column
+                ));
+                summary.record(&mut result.details);
+                return Ok(result);
+            }
+        };
+
+        // A baseline recorded from an empty table would flag every value as new
+        if observed.is_empty() && is_table_empty(ctx, table_name).await? {
+            return Ok(ConstraintResult::empty_table());
+        }
+
+        let baseline = if self.record_baseline {
+            None
+        } else {
+            self.baseline(table_name).await?
+        };
+        let Some(baseline) = baseline else {
+            let count = observed.len();
+            self.save(table_name, observed.into_keys().collect())
+                .await?;
+            debug!(column = %self.column, values = count, "Recorded category baseline");
+            return Ok(ConstraintResult {
+                message: Some(format!(
+                    "Recorded baseline of {count} values for column '{}'",
+                    self.column
+                )),
+                ..ConstraintResult::success_with_metric(0.0)
+            });
+        };
+
+        let new_values: Vec<Cow<'_, str>> = observed
+            .iter()
+            .filter(|(value, count)| {
+                !baseline.values.contains(*value) && **count as u64 >= self.min_frequency
+            })
+            .map(|(value, _)| validation_ctx.redact(&self.column, value))
+            .collect();
+
+        if new_values.is_empty() {
+            Ok(ConstraintResult::success_with_metric(0.0))
+        } else {
+            Ok(ConstraintResult::failure_with_metric(
+                new_values.len() as f64,
+                format!(
+                    "Column '{}' has {} values not in the approved baseline: [{}]",
+                    self.column,
+                    new_values.len(),
+                    new_values.join(", ")
+                ),
+            ))
+        }
+    }
+
+    fn name(&self) -> &str {
+        "category_baseline"
+    }
+
+    fn column(&self) -> Option<&str> {
+        Some(&self.column)
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        ConstraintMetadata::for_column(&self.column)
+            .with_description(format!(
+                "Checks that '{}' only contains values from the approved baseline",
+                self.column
+            ))
+            .with_custom("constraint_type", "categorical")
+            .with_custom("min_frequency", self.min_frequency.to_string())
+            .with_custom("max_distinct_values", self.max_distinct_values.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::incremental::FileSystemStateStore;
+    use crate::core::{ConstraintStatus, ValidationContext, CURRENT_CONTEXT};
+    use tempfile::TempDir;
+
+    async fn create_context(values: &str) -> SessionContext {
+        let ctx = SessionContext::new();
+        ctx.sql(&format!(
+            "CREATE TABLE payments (payment_method VARCHAR) AS VALUES {values}"
+        ))
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+        ctx
+    }
+
+    async fn evaluate(
+        constraint: &CategoryBaselineConstraint,
+        ctx: &SessionContext,
+    ) -> ConstraintResult {
+        CURRENT_CONTEXT
+            .scope(ValidationContext::new("payments"), constraint.evaluate(ctx))
+            .await
+            .unwrap()
+    }
+
+    fn constraint(dir: &TempDir) -> CategoryBaselineConstraint {
+        let store = Arc::new(FileSystemStateStore::new(dir.path()).unwrap());
+        CategoryBaselineConstraint::new("suite", "payment_method", store)
+    }
+
+    #[tokio::test]
+    async fn test_new_values_fail_against_recorded_baseline() {
+        let dir = TempDir::new().unwrap();
+        let constraint = constraint(&dir);
+
+        // First run records the baseline
+        let ctx = create_context("('card'), ('cash'), (NULL)").await;
+        let result = evaluate(&constraint, &ctx).await;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(
+            result.message.as_deref(),
+            Some("Recorded baseline of 2 values for column 'payment_method'")
+        );
+
+        let ctx = create_context("('card'), ('crypto'), ('voucher'), ('voucher')").await;
+        let result = evaluate(&constraint, &ctx).await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(2.0));
+        assert_eq!(
+            result.message.as_deref(),
+            Some("Column 'payment_method' has 2 values not in the approved baseline: [crypto, voucher]")
+        );
+
+        // Rare values can be ignored
+        let result = evaluate(&constraint.clone().min_frequency(2), &ctx).await;
+        assert_eq!(result.metric, Some(1.0));
+
+        // Approving the values makes the check pass
+        constraint
+            .approve_values("payments", ["crypto", "voucher"])
+            .await
+            .unwrap();
+        let result = evaluate(&constraint, &ctx).await;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        let baseline = constraint.baseline("payments").await.unwrap().unwrap();
+        assert_eq!(baseline.values.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_record_mode_and_approve_baseline_refresh() {
+        let dir = TempDir::new().unwrap();
+        let constraint = constraint(&dir);
+        let ctx = create_context("('card'), ('cash')").await;
+        assert_eq!(
+            constraint.approve_baseline(&ctx, "payments").await.unwrap(),
+            2
+        );
+
+        let ctx = create_context("('card'), ('crypto')").await;
+        let result = evaluate(&constraint.clone().record_baseline(true), &ctx).await;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        let baseline = constraint.baseline("payments").await.unwrap().unwrap();
+        assert_eq!(
+            baseline.values.into_iter().collect::<Vec<_>>(),
+            vec!["card", "crypto"]
+        );
+
+        // Baselines are scoped by suite
+        let store = Arc::new(FileSystemStateStore::new(dir.path()).unwrap());
+        let other = CategoryBaselineConstraint::new("other_suite", "payment_method", store);
+        assert!(other.baseline("payments").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_high_cardinality_is_skipped() {
+        let dir = TempDir::new().unwrap();
+        let constraint = constraint(&dir).max_distinct_values(2);
+        let ctx = create_context("('card'), ('cash'), ('crypto')").await;
+
+        let result = evaluate(&constraint, &ctx).await;
+        assert_eq!(result.status, ConstraintStatus::Skipped);
+        assert!(constraint.baseline("payments").await.unwrap().is_none());
+        assert!(constraint.approve_baseline(&ctx, "payments").await.is_err());
+        assert!(constraint
+            .approve_values("payments", ["a", "b", "c"])
+            .await
+            .is_err());
+    }
+}
@@ -1,7 +1,7 @@
 //! Column count validation constraint.
 
 use crate::constraints::Assertion;
-use crate::core::{Constraint, ConstraintMetadata, ConstraintResult, ConstraintStatus};
+use crate::core::{Constraint, ConstraintMetadata, ConstraintResult, ParameterRef};
 use crate::prelude::*;
 use async_trait::async_trait;
 use datafusion::prelude::*;
@@ -55,29 +55,10 @@ impl Constraint for ColumnCountConstraint {
         // Get the column count from the schema
         let column_count = df.schema().fields().len() as f64;
 
-        // Evaluate the assertion
-        let assertion_result = self.assertion.evaluate(column_count);
-
-        let status = if assertion_result {
-            ConstraintStatus::Success
-        } else {
-            ConstraintStatus::Failure
-        };
-
-        let message = if status == ConstraintStatus::Failure {
-            Some(format!(
-                "Column count {column_count} does not satisfy assertion {}",
-                self.assertion.description()
-            ))
-        } else {
-            None
-        };
-
-        Ok(ConstraintResult {
-            status,
-            metric: Some(column_count),
-            message,
-        })
+        Ok(self
+            .assertion
+            .explain(column_count, "column count")
+            .to_result())
     }
 
     fn name(&self) -> &str {
@@ -88,6 +69,14 @@ impl Constraint for ColumnCountConstraint {
         None // This constraint operates on the entire dataset, not a specific column
     }
 
+    fn parameters(&self) -> Vec<ParameterRef> {
+        self.assertion.parameters("column_count assertion")
+    }
+
+    fn describe(&self) -> String {
+        format!("the column count must be {}", self.assertion.requirement())
+    }
+
     fn metadata(&self) -> ConstraintMetadata {
         ConstraintMetadata::default()
             .with_description(format!(
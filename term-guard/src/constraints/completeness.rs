@@ -4,10 +4,16 @@
 //! - Single column completeness checks
 //! - Multiple column completeness with logical operators
 //! - Configurable thresholds for partial completeness
+//! - Empty strings and sentinel values counted as missing through a
+//!   [`MissingValuePolicy`]
 
 use crate::core::{
-    current_validation_context, ColumnSpec, Constraint, ConstraintMetadata, ConstraintOptions,
-    ConstraintResult, LogicalOperator, UnifiedConstraint,
+    column_expression, current_validation_context, describe_list, describe_rows, fetch_metrics,
+    metrics_query, AdjustedThreshold, ColumnPath, ColumnSpec, ColumnStatistics, Constraint,
+    ConstraintMetadata, ConstraintOptions, ConstraintResult, LogicalOperator, MetadataStatistics,
+    MetricKey, MissingValuePolicy, ParamValue, ParameterRef, QualityDimension, QuarantineSink,
+    QuarantineSummary, RatioQueryBuilder, ThresholdAdjustment, UnifiedConstraint,
+    EMPTY_TABLE_MESSAGE,
 };
 use crate::prelude::*;
 use crate::security::SqlSecurity;
@@ -20,7 +26,8 @@ use tracing::{debug, instrument};
 /// This constraint supports:
 /// - Single column completeness checks
 /// - Multiple column completeness with logical operators (All, Any, AtLeast, etc.)
-/// - Configurable thresholds
+/// - Configurable thresholds, optionally relaxed for small tables through a
+///   [`ThresholdAdjustment`]
 /// - Flexible configuration through ConstraintOptions
 ///
 /// # Examples
@@ -61,9 +68,15 @@ pub struct CompletenessConstraint {
     /// The columns to check for completeness
     columns: ColumnSpec,
     /// The minimum acceptable completeness ratio (0.0 to 1.0)
-    threshold: f64,
+    threshold: ParamValue,
     /// The logical operator for combining results
     operator: LogicalOperator,
+    /// Small-sample leniency applied to the threshold
+    threshold_adjustment: Option<ThresholdAdjustment>,
+    /// Sink receiving rows with nulls in the checked columns
+    quarantine: Option<QuarantineSink>,
+    /// Values counted as missing besides NULL
+    missing_values: Option<MissingValuePolicy>,
 }
 
 impl CompletenessConstraint {
@@ -74,37 +87,70 @@ impl CompletenessConstraint {
     /// * `columns` - The column(s) to check (accepts single string or vector)
     /// * `options` - Configuration options including threshold and operator
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if threshold is not between 0.0 and 1.0
-    pub fn new(columns: impl Into<ColumnSpec>, options: ConstraintOptions) -> Self {
-        let threshold = options.threshold_or(1.0);
-        assert!(
-            (0.0..=1.0).contains(&threshold),
-            "Threshold must be between 0.0 and 1.0"
-        );
+    /// Returns [`TermError::Configuration`] if the threshold is not between 0.0
+    /// and 1.0, or if the threshold adjustment is invalid. Thresholds given as a
+    /// named parameter are checked when the suite runs, and
+    /// [`CheckBuilder::completeness`](crate::core::CheckBuilder::completeness) reports
+    /// invalid options as configuration issues of its check.
+    pub fn new(columns: impl Into<ColumnSpec>, options: ConstraintOptions) -> Result<Self> {
+        let threshold = match options.threshold_param.clone() {
+            Some(name) => ParamValue::Param(name),
+            None => {
+                options.validate()?;
+                let threshold = options.threshold_or(1.0);
+                if !(0.0..=1.0).contains(&threshold) {
+                    return Err(TermError::Configuration(
+                        "Threshold must be between 0.0 and 1.0".to_string(),
+                    ));
+                }
+                ParamValue::Value(threshold)
+            }
+        };
 
-        Self {
+        Ok(Self {
             columns: columns.into(),
             threshold,
             operator: options.operator_or(LogicalOperator::All),
-        }
+            threshold_adjustment: options.threshold_adjustment,
+            quarantine: options.quarantine,
+            missing_values: None,
+        })
     }
 
     /// Creates a constraint with a specific threshold.
     /// Convenience method for the common case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if threshold is not between 0.0 and 1.0; use [`new`](Self::new)
+    /// for thresholds that are not known to be valid.
     pub fn with_threshold(columns: impl Into<ColumnSpec>, threshold: f64) -> Self {
         Self::new(columns, ConstraintOptions::new().with_threshold(threshold))
+            .unwrap_or_else(|e| panic!("{e}"))
     }
 
     /// Creates a constraint requiring 100% completeness.
     /// Convenience method equivalent to `is_complete`.
     pub fn complete(columns: impl Into<ColumnSpec>) -> Self {
-        Self::new(columns, ConstraintOptions::new().with_threshold(1.0))
+        Self {
+            columns: columns.into(),
+            threshold: ParamValue::Value(1.0),
+            operator: LogicalOperator::All,
+            threshold_adjustment: None,
+            quarantine: None,
+            missing_values: None,
+        }
     }
 
     /// Creates a constraint for multiple columns with a specific operator.
     /// Convenience method for multi-column checks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if threshold is not between 0.0 and 1.0; use [`new`](Self::new)
+    /// for thresholds that are not known to be valid.
     pub fn with_operator(
         columns: impl Into<ColumnSpec>,
         operator: LogicalOperator,
@@ -116,6 +162,148 @@ impl CompletenessConstraint {
                 .with_operator(operator)
                 .with_threshold(threshold),
         )
+        .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Also counts the values matched by `policy` as missing.
+    ///
+    /// The result details then break the missing values of a column down by
+    /// cause: `missing.null`, `missing.empty_string`, and one
+    /// `missing.sentinel:<value>` entry per sentinel.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::CompletenessConstraint;
+    /// use term_guard::core::MissingValuePolicy;
+    ///
+    /// let constraint = CompletenessConstraint::with_threshold("age", 0.95)
+    ///     .with_missing_value_policy(
+    ///         MissingValuePolicy::default()
+    ///             .sentinel_strings(["N/A", "NULL"])
+    ///             .sentinel_numbers([-999.0]),
+    ///     );
+    /// ```
+    pub fn with_missing_value_policy(mut self, policy: MissingValuePolicy) -> Self {
+        self.missing_values = (!policy.is_empty()).then_some(policy);
+        self
+    }
+
+    /// Writes rows with a null in any checked column to the quarantine sink.
+    async fn quarantine_rows(
+        &self,
+        ctx: &SessionContext,
+        sink: &QuarantineSink,
+    ) -> Result<QuarantineSummary> {
+        let validation_ctx = current_validation_context();
+        let mut violations = Vec::new();
+        let mut reasons = Vec::new();
+        for column in self.columns.as_vec() {
+            SqlSecurity::validate_identifier(column)?;
+            let column_identifier =
+                column_expression(ctx, validation_ctx.table_name(), column).await?;
+            let column_name = column.replace('\'', "''");
+            match self
+                .missing_values
+                .as_ref()
+                .and_then(|policy| policy.predicate(&column_identifier))
+            {
+                Some(predicate) => {
+                    violations.push(format!("{column_identifier} IS NULL OR {predicate}"));
+                    reasons.push(format!(
+                        "CASE WHEN {column_identifier} IS NULL THEN '{column_name} is null' \
+                         WHEN {predicate} THEN '{column_name} is missing' END"
+                    ));
+                }
+                None => {
+                    violations.push(format!("{column_identifier} IS NULL"));
+                    reasons.push(format!(
+                        "CASE WHEN {column_identifier} IS NULL THEN '{column_name} is null' END"
+                    ));
+                }
+            }
+        }
+
+        sink.write(
+            ctx,
+            self.name(),
+            &violations.join(" OR "),
+            &format!("concat_ws(', ', {})", reasons.join(", ")),
+        )
+        .await
+    }
+
+    /// Returns the row and NULL counts a column's completeness is computed from.
+    fn count_keys(table_name: &str, column: &str) -> [MetricKey; 2] {
+        [
+            MetricKey::row_count(table_name),
+            MetricKey::null_count(table_name, column),
+        ]
+    }
+
+    /// Counts the rows and the NULL values of a field nested in a struct column.
+    ///
+    /// A NULL struct counts as a NULL field.
+    async fn nested_counts(
+        ctx: &SessionContext,
+        table_name: &str,
+        path: &ColumnPath,
+    ) -> Result<Vec<u64>> {
+        let field = path.to_sql()?;
+        let counts = RatioQueryBuilder::new(table_name, format!("{field} IS NOT NULL"))
+            .execute(ctx)
+            .await?;
+        Ok(vec![counts.total, counts.violating()])
+    }
+
+    /// Compares the completeness ratio of a column against the threshold.
+    fn completeness_result(
+        &self,
+        column: &str,
+        threshold: f64,
+        non_null_count: f64,
+        total_count: f64,
+    ) -> Result<ConstraintResult> {
+        // Calculate completeness ratio
+        let completeness = non_null_count / total_count;
+        let adjusted =
+            AdjustedThreshold::new(threshold, self.threshold_adjustment, total_count as u64)?;
+
+        // Determine result based on threshold
+        let result = if adjusted.passes(completeness) {
+            debug!(
+                constraint.name = %self.name(),
+                constraint.column = %column,
+                constraint.threshold = %threshold,
+                result.completeness = %format!("{completeness:.4}"),
+                result.non_null_count = non_null_count as i64,
+                result.total_count = total_count as i64,
+                result.status = "success",
+                "Completeness constraint passed for column"
+            );
+            ConstraintResult::success_with_metric(completeness)
+        } else {
+            debug!(
+                constraint.name = %self.name(),
+                constraint.column = %column,
+                constraint.threshold = %threshold,
+                constraint.effective_threshold = %adjusted.effective(),
+                result.completeness = %format!("{completeness:.4}"),
+                result.non_null_count = non_null_count as i64,
+                result.total_count = total_count as i64,
+                result.status = "failure",
+                "Completeness constraint failed for column"
+            );
+            ConstraintResult::failure_with_metric(
+                completeness,
+                format!(
+                    "Column '{column}' completeness {:.2}% is below threshold {:.2}%",
+                    completeness * 100.0,
+                    threshold * 100.0
+                ),
+            )
+        };
+        Ok(adjusted.annotate(result, completeness))
     }
 }
 
@@ -146,116 +334,95 @@ impl UnifiedConstraint for CompletenessConstraint {
             "Evaluating completeness for single column"
         );
 
-        // Validate and escape column identifier
         SqlSecurity::validate_identifier(column)?;
-        let column_identifier = SqlSecurity::escape_identifier(column)?;
 
-        // Get the table name from the validation context
+        // Row and NULL counts are shared with other constraints of the run
         let validation_ctx = current_validation_context();
         let table_name = validation_ctx.table_name();
-
-        // Build SQL query to calculate completeness
-        let sql = format!(
-            "SELECT 
-                COUNT(*) as total_count,
-                COUNT({column_identifier}) as non_null_count
-             FROM {table_name}"
-        );
-
-        // Execute query
-        let df = ctx.sql(&sql).await?;
-        let batches = df.collect().await?;
-
-        // Extract results
-        if batches.is_empty() {
-            debug!(
-                constraint.name = %self.name(),
-                constraint.column = %column,
-                skip.reason = "No data to validate",
-                "Skipping constraint due to empty result set"
-            );
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        let batch = &batches[0];
-        if batch.num_rows() == 0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        let total_count = batch
-            .column(0)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract total count".to_string()))?
-            .value(0) as f64;
+        let (counts, by_cause) = if let Some(policy) = &self.missing_values {
+            let column_identifier = column_expression(ctx, table_name, column).await?;
+            let counts = policy.count(ctx, table_name, &column_identifier).await?;
+            (vec![counts.total, counts.missing()], Some(counts.by_cause))
+        } else if column.contains('.') {
+            let path = ColumnPath::resolve_in(ctx, table_name, column).await?;
+            if path.is_nested() {
+                (Self::nested_counts(ctx, table_name, &path).await?, None)
+            } else {
+                (
+                    fetch_metrics(ctx, &Self::count_keys(table_name, column)).await?,
+                    None,
+                )
+            }
+        } else {
+            (
+                fetch_metrics(ctx, &Self::count_keys(table_name, column)).await?,
+                None,
+            )
+        };
+        let total_count = counts[0] as f64;
 
         if total_count == 0.0 {
             debug!(
                 constraint.name = %self.name(),
                 constraint.column = %column,
-                skip.reason = "No data to validate",
+                skip.reason = EMPTY_TABLE_MESSAGE,
                 data.rows = 0,
                 "Skipping constraint due to zero rows"
             );
-            return Ok(ConstraintResult::skipped("No data to validate"));
+            return Ok(ConstraintResult::empty_table());
         }
 
-        let non_null_count = batch
-            .column(1)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract non-null count".to_string()))?
-            .value(0) as f64;
+        let non_null_count = total_count - counts[1] as f64;
 
-        // Calculate completeness ratio
-        let completeness = non_null_count / total_count;
-
-        // Determine result based on threshold
-        if completeness >= self.threshold {
-            debug!(
-                constraint.name = %self.name(),
-                constraint.column = %column,
-                constraint.threshold = %self.threshold,
-                result.completeness = %format!("{completeness:.4}"),
-                result.non_null_count = non_null_count as i64,
-                result.total_count = total_count as i64,
-                result.status = "success",
-                "Completeness constraint passed for column"
-            );
-            Ok(ConstraintResult::success_with_metric(completeness))
-        } else {
-            debug!(
-                constraint.name = %self.name(),
-                constraint.column = %column,
-                constraint.threshold = %self.threshold,
-                result.completeness = %format!("{completeness:.4}"),
-                result.non_null_count = non_null_count as i64,
-                result.total_count = total_count as i64,
-                result.status = "failure",
-                "Completeness constraint failed for column"
-            );
-            Ok(ConstraintResult::failure_with_metric(
-                completeness,
-                format!(
-                    "Column '{column}' completeness {:.2}% is below threshold {:.2}%",
-                    completeness * 100.0,
-                    self.threshold * 100.0
-                ),
-            ))
-        }
+        let threshold = self.threshold.resolve()?;
+        let result = self.completeness_result(column, threshold, non_null_count, total_count)?;
+        Ok(by_cause
+            .into_iter()
+            .flatten()
+            .fold(result, |result, (cause, count)| {
+                result.with_detail(format!("missing.{cause}"), count as f64)
+            }))
     }
 }
 
 #[async_trait]
 impl Constraint for CompletenessConstraint {
     async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
-        self.evaluate_unified(ctx).await
+        let result = self.evaluate_unified(ctx).await?;
+        match &self.quarantine {
+            Some(sink) if !result.status.is_skipped() => {
+                let summary = self.quarantine_rows(ctx, sink).await?;
+                Ok(result.with_quarantine(summary))
+            }
+            _ => Ok(result),
+        }
     }
 
     fn name(&self) -> &str {
         "completeness"
     }
 
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Completeness)
+    }
+
+    fn generate_sql(&self, table_name: &str) -> Result<Vec<String>> {
+        // One query per column, as run when no metric cache shares the counts
+        self.columns
+            .as_vec()
+            .into_iter()
+            .map(|column| {
+                SqlSecurity::validate_identifier(column)?;
+                if let Some(policy) = &self.missing_values {
+                    let column_identifier = SqlSecurity::escape_identifier(column)?;
+                    return Ok(policy.counts_query(table_name, &column_identifier));
+                }
+                let [rows, nulls] = Self::count_keys(table_name, column);
+                metrics_query(&[&rows, &nulls])
+            })
+            .collect()
+    }
+
     fn column(&self) -> Option<&str> {
         match &self.columns {
             ColumnSpec::Single(col) => Some(col),
@@ -263,6 +430,62 @@ impl Constraint for CompletenessConstraint {
         }
     }
 
+    fn parameters(&self) -> Vec<ParameterRef> {
+        self.threshold
+            .reference("completeness threshold")
+            .map(|reference| reference.range(0.0, 1.0))
+            .into_iter()
+            .collect()
+    }
+
+    fn evaluate_from_statistics(&self, statistics: &ColumnStatistics) -> Option<ConstraintResult> {
+        // Quarantining rows and matching sentinels need a query over the table itself
+        if self.quarantine.is_some() || self.missing_values.is_some() {
+            return None;
+        }
+        let ColumnSpec::Single(column) = &self.columns else {
+            return None;
+        };
+        if statistics.row_count == 0 {
+            return Some(ConstraintResult::empty_table());
+        }
+        self.completeness_result(
+            column,
+            self.threshold.resolve().ok()?,
+            statistics.non_null_count() as f64,
+            statistics.row_count as f64,
+        )
+        .ok()
+    }
+
+    /// Answers from the null count recorded in the metadata, if every file has one.
+    fn evaluate_from_metadata(&self, metadata: &MetadataStatistics) -> Option<ConstraintResult> {
+        let statistics = metadata.column_statistics(self.column()?)?;
+        self.evaluate_from_statistics(&statistics)
+    }
+
+    fn describe(&self) -> String {
+        let rows = match &self.threshold {
+            ParamValue::Value(threshold) => describe_rows(*threshold),
+            ParamValue::Param(name) => {
+                format!("for at least the share of rows set by the '{name}' parameter")
+            }
+        };
+        let missing = if self.missing_values.is_some() {
+            "missing"
+        } else {
+            "null"
+        };
+        match &self.columns {
+            ColumnSpec::Single(column) => format!("{column} must not be {missing} {rows}"),
+            ColumnSpec::Multiple(columns) => format!(
+                "{} of {} must not be {missing} {rows}",
+                self.operator.description(),
+                describe_list(columns)
+            ),
+        }
+    }
+
     fn metadata(&self) -> ConstraintMetadata {
         let mut metadata = match &self.columns {
             ColumnSpec::Single(col) => ConstraintMetadata::for_column(col),
@@ -274,14 +497,18 @@ impl Constraint for CompletenessConstraint {
             ColumnSpec::Multiple(_) => format!(" ({})", self.operator.description()),
         };
 
+        let threshold_desc = match &self.threshold {
+            ParamValue::Value(threshold) => format!("{:.1}%", threshold * 100.0),
+            ParamValue::Param(name) => format!("the '{name}' parameter"),
+        };
+
         metadata = metadata
             .with_description(format!(
-                "Checks that {}{operator_desc} have at least {:.1}% completeness",
+                "Checks that {}{operator_desc} have at least {threshold_desc} completeness",
                 match &self.columns {
                     ColumnSpec::Single(_) => "column",
                     ColumnSpec::Multiple(_) => "columns",
                 },
-                self.threshold * 100.0
             ))
             .with_custom("threshold", self.threshold.to_string())
             .with_custom("constraint_type", "data_quality");
@@ -289,6 +516,12 @@ impl Constraint for CompletenessConstraint {
         if let ColumnSpec::Multiple(_) = &self.columns {
             metadata = metadata.with_custom("operator", self.operator.description());
         }
+        if let Some(adjustment) = &self.threshold_adjustment {
+            metadata = metadata.with_custom("threshold_adjustment", adjustment.to_string());
+        }
+        if let Some(policy) = &self.missing_values {
+            metadata = metadata.with_custom("missing_values", policy.to_string());
+        }
 
         metadata
     }
@@ -395,6 +628,78 @@ mod tests {
         assert!(result.message.unwrap().contains("50.00%"));
     }
 
+    #[tokio::test]
+    async fn test_min_failing_rows_adjustment() {
+        let ctx = create_test_context(
+            vec!["phone"],
+            vec![vec![Some(1)], vec![None], vec![Some(3)], vec![Some(4)]],
+        )
+        .await;
+
+        let options = || {
+            ConstraintOptions::new()
+                .with_threshold(0.9)
+                .with_threshold_adjustment(ThresholdAdjustment::MinFailingRows(2))
+        };
+        let constraint = CompletenessConstraint::new("phone", options()).unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.75));
+        assert_eq!(result.details["effective_threshold"], 0.75);
+        assert!(result
+            .message
+            .unwrap()
+            .contains("minimum of 2 failing rows adjustment"));
+
+        let constraint = CompletenessConstraint::new(
+            "phone",
+            options().with_threshold_adjustment(ThresholdAdjustment::MinFailingRows(1)),
+        )
+        .unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.details["effective_threshold"], 0.9);
+    }
+
+    #[tokio::test]
+    async fn test_missing_value_policy() {
+        let ctx = create_test_context(
+            vec!["age"],
+            vec![vec![Some(30)], vec![Some(-999)], vec![None], vec![Some(40)]],
+        )
+        .await;
+
+        let constraint = CompletenessConstraint::with_threshold("age", 0.6)
+            .with_missing_value_policy(MissingValuePolicy::new().sentinel_numbers([-999.0]));
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(0.5));
+        assert_eq!(result.details["missing.null"], 1.0);
+        assert_eq!(result.details["missing.sentinel:-999"], 1.0);
+        assert_eq!(
+            constraint.describe(),
+            "age must not be missing for at least 60% of rows"
+        );
+        assert!(constraint
+            .evaluate_from_statistics(&ColumnStatistics::default())
+            .is_none());
+
+        // An empty policy keeps the plain NULL count
+        let constraint = CompletenessConstraint::with_threshold("age", 0.6)
+            .with_missing_value_policy(MissingValuePolicy::new());
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert!(result.details.is_empty());
+    }
+
     #[tokio::test]
     async fn test_multiple_columns_all_operator() {
         let ctx = create_test_context(
@@ -412,7 +717,8 @@ mod tests {
             ConstraintOptions::new()
                 .with_operator(LogicalOperator::All)
                 .with_threshold(1.0),
-        );
+        )
+        .unwrap();
 
         let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
             .await
@@ -438,7 +744,8 @@ mod tests {
             ConstraintOptions::new()
                 .with_operator(LogicalOperator::All)
                 .with_threshold(1.0),
-        );
+        )
+        .unwrap();
 
         let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
             .await
@@ -536,7 +843,15 @@ mod tests {
         let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
             .await
             .unwrap();
-        assert_eq!(result.status, ConstraintStatus::Skipped);
+        assert!(result.is_empty_table());
+
+        // Every column skips for the same reason, so the combination does too
+        let constraint =
+            CompletenessConstraint::with_operator(vec!["id", "id"], LogicalOperator::All, 1.0);
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert!(result.is_empty_table());
     }
 
     #[test]
@@ -545,6 +860,18 @@ mod tests {
         CompletenessConstraint::with_threshold("col", 1.5);
     }
 
+    #[test]
+    fn test_new_rejects_out_of_range_threshold() {
+        let err = CompletenessConstraint::new("col", ConstraintOptions::new().with_threshold(1.5))
+            .unwrap_err();
+        assert!(matches!(err, TermError::Configuration(_)), "{err}");
+        assert!(
+            err.to_string()
+                .contains("Threshold must be between 0.0 and 1.0"),
+            "{err}"
+        );
+    }
+
     #[tokio::test]
     async fn test_metadata() {
         let single = CompletenessConstraint::with_threshold("email", 0.95);
@@ -561,4 +888,83 @@ mod tests {
         assert!(metadata2.description.unwrap().contains("any"));
         assert_eq!(metadata2.custom.get("operator"), Some(&"any".to_string()));
     }
+
+    #[test]
+    fn test_generate_sql() -> Result<()> {
+        let constraint = CompletenessConstraint::with_threshold("email", 0.9);
+        assert_eq!(
+            constraint.generate_sql("users")?,
+            vec![r#"SELECT COUNT(*) AS m0, COUNT(*) - COUNT("email") AS m1 FROM users"#]
+        );
+
+        // One query per column
+        let constraint = CompletenessConstraint::with_threshold(vec!["email", "phone"], 0.9);
+        let sql = constraint.generate_sql("users")?;
+        assert_eq!(sql.len(), 2);
+        assert!(sql[1].contains(r#"COUNT(*) - COUNT("phone")"#));
+        Ok(())
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            CompletenessConstraint::with_threshold("email", 0.99).describe(),
+            "email must not be null for at least 99% of rows"
+        );
+        assert_eq!(
+            CompletenessConstraint::with_operator(
+                vec!["phone", "email"],
+                LogicalOperator::Any,
+                1.0
+            )
+            .describe(),
+            "any of phone and email must not be null for every row"
+        );
+        let constraint = CompletenessConstraint::new(
+            "email",
+            ConstraintOptions::new().with_threshold_param("min_completeness"),
+        )
+        .unwrap();
+        assert_eq!(
+            constraint.describe(),
+            "email must not be null for at least the share of rows set by the \
+             'min_completeness' parameter"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_struct_field_completeness() {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE TABLE data AS \
+             SELECT named_struct('city', 'Oslo', 'zip', '0150') AS address \
+             UNION ALL SELECT named_struct('city', 'Bergen', 'zip', CAST(NULL AS VARCHAR)) \
+             UNION ALL SELECT CASE WHEN false THEN named_struct('city', '', 'zip', '') END \
+             UNION ALL SELECT named_struct('city', 'Molde', 'zip', '6413')",
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+        // A NULL struct counts as a NULL field
+        let constraint = CompletenessConstraint::with_threshold("address.zip", 0.5);
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.5));
+
+        let constraint = CompletenessConstraint::complete("address.street");
+        let error = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("struct 'address' has no field 'street'"),
+            "{error}"
+        );
+    }
 }
@@ -0,0 +1,171 @@
+//! Keys shared by the referential constraints.
+//!
+//! Foreign key and join coverage constraints match rows on an ordered list of
+//! column pairs, which may have different names in the two tables. A key
+//! matches when every pair of columns is equal. Examples of unmatched keys are
+//! rendered as the value for single-column keys and as a tuple such as
+//! `(1, 42)` for composite keys.
+
+use arrow::array::Array;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::array_value_to_string;
+use serde::{Deserialize, Serialize};
+
+use crate::core::current_validation_context;
+use crate::error::Result;
+
+/// Which keys with NULL columns take no part in a referential check.
+///
+/// NULL never equals a value, so a key with a NULL column never matches.
+/// Constraints that allow NULL keys leave the keys selected by this policy out
+/// of the check. Both policies are the same for single-column keys.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::constraints::{ForeignKeyConstraint, NullKeyPolicy};
+///
+/// // Orders without a tenant and customer are fine, a half-filled key is an orphan
+/// let constraint = ForeignKeyConstraint::composite(
+///     "orders",
+///     "customers",
+///     vec![("tenant_id", "tenant_id"), ("customer_id", "cust_id")],
+/// )?
+/// .allow_nulls(true)
+/// .null_policy(NullKeyPolicy::AllNull);
+/// # Ok::<(), term_guard::error::TermError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NullKeyPolicy {
+    /// Keys with any NULL column take no part, as with SQL's `MATCH SIMPLE`
+    #[default]
+    AnyNull,
+    /// Only keys whose columns are all NULL take no part; partially NULL keys
+    /// are checked and never match, as with SQL's `MATCH FULL`
+    AllNull,
+}
+
+impl NullKeyPolicy {
+    /// Returns the SQL condition selecting the keys that take part in the check.
+    ///
+    /// `columns` are the escaped and qualified key columns.
+    pub(crate) fn participating(self, columns: &[String]) -> String {
+        let conditions: Vec<_> = columns
+            .iter()
+            .map(|column| format!("{column} IS NOT NULL"))
+            .collect();
+        if conditions.len() == 1 {
+            return conditions[0].clone();
+        }
+        let separator = match self {
+            NullKeyPolicy::AnyNull => " AND ",
+            NullKeyPolicy::AllNull => " OR ",
+        };
+        format!("({})", conditions.join(separator))
+    }
+}
+
+/// Renders at most `limit` rows of key columns as examples.
+///
+/// `columns` name the key columns in the order of the batch columns, so that
+/// values of sensitive columns are redacted with the policy of the current
+/// [`ValidationContext`](crate::core::ValidationContext). NULL columns are
+/// shown as `NULL`.
+pub(crate) fn key_examples<S: AsRef<str>>(
+    batches: &[RecordBatch],
+    columns: &[S],
+    limit: usize,
+) -> Result<Vec<String>> {
+    let validation_ctx = current_validation_context();
+    let mut examples = Vec::with_capacity(limit);
+    for batch in batches {
+        for row in 0..batch.num_rows() {
+            if examples.len() >= limit {
+                return Ok(examples);
+            }
+            let values = batch
+                .columns()
+                .iter()
+                .zip(columns)
+                .map(|(column, name)| {
+                    if column.is_null(row) {
+                        Ok("NULL".to_string())
+                    } else {
+                        let value = array_value_to_string(column, row)?;
+                        Ok(validation_ctx.redact(name.as_ref(), &value).into_owned())
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
+            examples.push(match values.as_slice() {
+                [value] => value.clone(),
+                _ => format!("({})", values.join(", ")),
+            });
+        }
+    }
+    Ok(examples)
+}
+
+/// Joins examples for a message, listing the first five and the number of others.
+pub(crate) fn format_examples(examples: &[String]) -> String {
+    if examples.len() <= 5 {
+        examples.join(", ")
+    } else {
+        format!(
+            "{}, ... ({} more)",
+            examples[..5].join(", "),
+            examples.len() - 5
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_participating_keys() {
+        let single = ["child.\"id\"".to_string()];
+        assert_eq!(
+            NullKeyPolicy::AllNull.participating(&single),
+            "child.\"id\" IS NOT NULL"
+        );
+
+        let composite = ["a".to_string(), "b".to_string()];
+        assert_eq!(
+            NullKeyPolicy::AnyNull.participating(&composite),
+            "(a IS NOT NULL AND b IS NOT NULL)"
+        );
+        assert_eq!(
+            NullKeyPolicy::AllNull.participating(&composite),
+            "(a IS NOT NULL OR b IS NOT NULL)"
+        );
+    }
+
+    #[test]
+    fn test_key_examples() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("tenant", DataType::Utf8, true),
+            Field::new("id", DataType::Int64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec![Some("acme"), None, Some("initech")])),
+                Arc::new(Int64Array::from(vec![Some(1), Some(2), None])),
+            ],
+        )?;
+
+        assert_eq!(
+            key_examples(std::slice::from_ref(&batch), &["tenant", "id"], 2)?,
+            vec!["(acme, 1)", "(NULL, 2)"]
+        );
+        assert_eq!(
+            key_examples(&[batch.project(&[1])?], &["id"], 5)?,
+            vec!["1", "2", "NULL"]
+        );
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 //! Type consistency validation constraints.
 
-use crate::core::{current_validation_context, Constraint, ConstraintMetadata, ConstraintResult, ConstraintStatus};
+use crate::core::{
+    collect_with_metrics, current_validation_context, Constraint, ConstraintMetadata,
+    ConstraintResult, ConstraintStatus,
+};
 use crate::prelude::*;
 use arrow::array::Array;
 use async_trait::async_trait;
@@ -61,7 +64,7 @@ impl Constraint for DataTypeConsistencyConstraint {
         // Get the table name from the validation context
         let validation_ctx = current_validation_context();
         let table_name = validation_ctx.table_name();
-        
+
         // First, get type distribution
         let type_dist_sql = format!(
             "WITH type_analysis AS (
@@ -87,7 +90,7 @@ impl Constraint for DataTypeConsistencyConstraint {
         );
 
         let df = ctx.sql(&type_dist_sql).await?;
-        let batches = df.collect().await?;
+        let batches = collect_with_metrics(df).await?;
 
         if batches.is_empty() {
             return Ok(ConstraintResult::skipped("No data to validate"));
@@ -168,6 +171,9 @@ impl Constraint for DataTypeConsistencyConstraint {
                     consistency_ratio * 100.0,
                     type_report
                 )),
+                quarantine: None,
+                constraint_id: None,
+                details: Default::default(),
             })
         } else {
             Ok(ConstraintResult::failure_with_metric(
@@ -239,7 +245,9 @@ mod tests {
 
         let constraint = DataTypeConsistencyConstraint::new("mixed_col", 0.95);
 
-        let result = evaluate_constraint_with_context(&constraint, &ctx, "data").await.unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
         assert_eq!(result.status, ConstraintStatus::Success);
         assert_eq!(result.metric, Some(1.0)); // All integers
         assert!(result.message.as_ref().unwrap().contains("integer"));
@@ -257,7 +265,9 @@ mod tests {
 
         let constraint = DataTypeConsistencyConstraint::new("mixed_col", 0.8);
 
-        let result = evaluate_constraint_with_context(&constraint, &ctx, "data").await.unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
         assert_eq!(result.status, ConstraintStatus::Failure);
         assert_eq!(result.metric, Some(0.25)); // Each type appears once
         assert!(result
@@ -279,7 +289,9 @@ mod tests {
 
         let constraint = DataTypeConsistencyConstraint::new("mixed_col", 0.7);
 
-        let result = evaluate_constraint_with_context(&constraint, &ctx, "data").await.unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
         assert_eq!(result.status, ConstraintStatus::Success);
         assert_eq!(result.metric, Some(0.75)); // 3 out of 4 are integers
     }
@@ -291,7 +303,9 @@ mod tests {
 
         let constraint = DataTypeConsistencyConstraint::new("mixed_col", 0.95);
 
-        let result = evaluate_constraint_with_context(&constraint, &ctx, "data").await.unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
         assert_eq!(result.status, ConstraintStatus::Success);
         assert_eq!(result.metric, Some(1.0)); // All non-null values are integers
         assert!(result.message.as_ref().unwrap().contains("null: 40.0%"));
@@ -309,7 +323,9 @@ mod tests {
 
         let constraint = DataTypeConsistencyConstraint::new("mixed_col", 0.7);
 
-        let result = evaluate_constraint_with_context(&constraint, &ctx, "data").await.unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
         assert_eq!(result.status, ConstraintStatus::Success);
         assert_eq!(result.metric, Some(0.75)); // 3 out of 4 are dates
         assert!(result.message.as_ref().unwrap().contains("date"));
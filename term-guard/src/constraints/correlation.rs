@@ -6,8 +6,14 @@
 //!
 //! And adds support for other correlation types and multi-column relationships.
 
-use crate::constraints::Assertion;
-use crate::core::{current_validation_context, Constraint, ConstraintMetadata, ConstraintResult};
+use crate::constraints::non_finite::{
+    finite_values_sql, is_float_column, non_finite_predicate, read_count, with_non_finite,
+};
+use crate::constraints::{Assertion, NonFinitePolicy};
+use crate::core::{
+    collect_with_metrics, current_validation_context, skipped_without_values, Constraint,
+    ConstraintMetadata, ConstraintResult,
+};
 use crate::prelude::*;
 use crate::security::SqlSecurity;
 use arrow::array::Array;
@@ -15,6 +21,9 @@ use async_trait::async_trait;
 use datafusion::prelude::*;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
+/// Reason for skipping when the correlation is undefined, such as for fewer than two rows.
+const NOT_ENOUGH_VALUES: &str = "Not enough values to compute the correlation";
+
 /// Types of correlation that can be computed.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CorrelationType {
@@ -150,6 +159,27 @@ pub enum CorrelationValidation {
 pub struct CorrelationConstraint {
     /// The type of validation to perform
     validation: CorrelationValidation,
+    /// How NaN and infinite values of float columns are treated
+    non_finite: NonFinitePolicy,
+}
+
+/// The SQL reading a pair of columns, see [`CorrelationConstraint::pair_sql`].
+struct PairSql {
+    name1: String,
+    name2: String,
+    column1: String,
+    column2: String,
+    /// Counts the rows with a non-finite value in either column, if one is a
+    /// float column
+    non_finite: Option<String>,
+}
+
+/// What [`CorrelationConstraint::query_pair`] computed.
+enum PairOutcome {
+    /// The value and the count of rows with non-finite values, if counted
+    Value(f64, Option<i64>),
+    /// The constraint result, when there is no value to assert on
+    Done(ConstraintResult),
 }
 
 impl CorrelationConstraint {
@@ -192,7 +222,19 @@ impl CorrelationConstraint {
             }
         }
 
-        Ok(Self { validation })
+        Ok(Self {
+            validation,
+            non_finite: NonFinitePolicy::default(),
+        })
+    }
+
+    /// Sets how NaN and infinite values are treated, ignoring them by default.
+    ///
+    /// Under [`NonFinitePolicy::Ignore`] rows with a non-finite value in either
+    /// column are left out, like rows with a null. See [`NonFinitePolicy`].
+    pub fn non_finite_policy(mut self, policy: NonFinitePolicy) -> Self {
+        self.non_finite = policy;
+        self
     }
 
     /// Convenience constructor for Pearson correlation.
@@ -256,22 +298,116 @@ impl CorrelationConstraint {
         })
     }
 
-    /// Generates SQL for Pearson correlation.
-    fn pearson_sql(&self, col1: &str, col2: &str) -> Result<String> {
-        let escaped_col1 = SqlSecurity::escape_identifier(col1)?;
-        let escaped_col2 = SqlSecurity::escape_identifier(col2)?;
+    /// Builds the SQL reading two columns.
+    ///
+    /// Non-finite values of float columns are replaced with null under
+    /// [`NonFinitePolicy::Ignore`], which the correlation functions skip.
+    async fn pair_sql(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        column1: &str,
+        column2: &str,
+    ) -> Result<PairSql> {
+        let mut columns = Vec::with_capacity(2);
+        let mut predicates = Vec::new();
+        for column in [column1, column2] {
+            let escaped = SqlSecurity::escape_identifier(column)?;
+            if !is_float_column(ctx, table_name, column).await {
+                columns.push(escaped);
+                continue;
+            }
+            predicates.push(non_finite_predicate(&escaped));
+            if self.non_finite == NonFinitePolicy::Ignore {
+                columns.push(finite_values_sql(&escaped));
+            } else {
+                columns.push(escaped);
+            }
+        }
+        let non_finite = (!predicates.is_empty()).then(|| {
+            format!(
+                "SUM(CASE WHEN {} THEN 1 ELSE 0 END)",
+                predicates.join(" OR ")
+            )
+        });
+        let (column1_name, column2_name) = (column1, column2);
+        let column2 = columns.pop().unwrap_or_default();
+        let column1 = columns.pop().unwrap_or_default();
+        Ok(PairSql {
+            name1: column1_name.to_string(),
+            name2: column2_name.to_string(),
+            column1,
+            column2,
+            non_finite,
+        })
+    }
 
+    /// Generates SQL for Pearson correlation of two column expressions.
+    fn pearson_sql(&self, pair: &PairSql) -> String {
         // Using DataFusion's CORR function
-        Ok(format!("CORR({escaped_col1}, {escaped_col2})"))
+        format!("CORR({}, {})", pair.column1, pair.column2)
     }
 
-    /// Generates SQL for covariance.
-    fn covariance_sql(&self, col1: &str, col2: &str) -> Result<String> {
-        let escaped_col1 = SqlSecurity::escape_identifier(col1)?;
-        let escaped_col2 = SqlSecurity::escape_identifier(col2)?;
-
+    /// Generates SQL for covariance of two column expressions.
+    fn covariance_sql(&self, pair: &PairSql) -> String {
         // Using DataFusion's COVAR_SAMP function
-        Ok(format!("COVAR_SAMP({escaped_col1}, {escaped_col2})"))
+        format!("COVAR_SAMP({}, {})", pair.column1, pair.column2)
+    }
+
+    /// Runs a query computing one value and, if `pair` counts them, the
+    /// non-finite values.
+    ///
+    /// Under [`NonFinitePolicy::Fail`], rows with non-finite values fail the
+    /// constraint before the value is read.
+    async fn query_pair(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        expr: &str,
+        pair: &PairSql,
+    ) -> Result<PairOutcome> {
+        let sql = match &pair.non_finite {
+            Some(count) => {
+                format!("SELECT {expr} as corr_value, {count} as non_finite FROM {table_name}")
+            }
+            None => format!("SELECT {expr} as corr_value FROM {table_name}"),
+        };
+
+        let df = ctx.sql(&sql).await?;
+        let batches = collect_with_metrics(df).await?;
+
+        if batches.is_empty() || batches[0].num_rows() == 0 {
+            return Ok(PairOutcome::Done(ConstraintResult::skipped(
+                "No data to validate",
+            )));
+        }
+
+        let non_finite = match pair.non_finite {
+            Some(_) => Some(read_count(batches[0].column(1).as_ref())?),
+            None => None,
+        };
+        if let Some(count) = non_finite.filter(|count| *count > 0) {
+            if self.non_finite == NonFinitePolicy::Fail {
+                return Ok(PairOutcome::Done(with_non_finite(
+                    ConstraintResult::failure(format!(
+                        "{count} rows have a non-finite value (NaN or infinity) in {} or {}",
+                        pair.name1, pair.name2
+                    )),
+                    non_finite,
+                )));
+            }
+        }
+
+        let values = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<arrow::array::Float64Array>()
+            .ok_or_else(|| TermError::Internal("Failed to downcast to Float64Array".to_string()))?;
+        if values.is_null(0) {
+            let result = skipped_without_values(ctx, table_name, NOT_ENOUGH_VALUES).await?;
+            return Ok(PairOutcome::Done(with_non_finite(result, non_finite)));
+        }
+        Ok(PairOutcome::Value(values.value(0), non_finite))
     }
 
     /// Generates SQL for Spearman correlation (rank-based).
@@ -308,19 +444,8 @@ impl Constraint for CorrelationConstraint {
                 correlation_type,
                 assertion,
             } => {
-                let sql = match correlation_type {
-                    CorrelationType::Pearson => {
-                        format!(
-                            "SELECT {} as corr_value FROM {table_name}",
-                            self.pearson_sql(column1, column2)?
-                        )
-                    }
-                    CorrelationType::Covariance => {
-                        format!(
-                            "SELECT {} as corr_value FROM {table_name}",
-                            self.covariance_sql(column1, column2)?
-                        )
-                    }
+                let expr = match correlation_type {
+                    CorrelationType::Pearson | CorrelationType::Covariance => None,
                     CorrelationType::Custom { sql_expression } => {
                         // Basic validation to prevent obvious SQL injection
                         if sql_expression.contains(';')
@@ -330,12 +455,7 @@ impl Constraint for CorrelationConstraint {
                                 "Custom SQL expression contains potentially unsafe content",
                             ));
                         }
-                        let escaped_col1 = SqlSecurity::escape_identifier(column1)?;
-                        let escaped_col2 = SqlSecurity::escape_identifier(column2)?;
-                        let expr = sql_expression
-                            .replace("{column1}", &escaped_col1)
-                            .replace("{column2}", &escaped_col2);
-                        format!("SELECT {expr} as corr_value FROM {table_name}")
+                        Some(sql_expression)
                     }
                     _ => {
                         // Other correlation types would require more complex implementation
@@ -345,33 +465,32 @@ impl Constraint for CorrelationConstraint {
                     }
                 };
 
-                let df = ctx.sql(&sql).await?;
-                let batches = df.collect().await?;
-
-                if batches.is_empty() || batches[0].num_rows() == 0 {
-                    return Ok(ConstraintResult::skipped("No data to validate"));
-                }
-
-                let value = batches[0]
-                    .column(0)
-                    .as_any()
-                    .downcast_ref::<arrow::array::Float64Array>()
-                    .ok_or_else(|| {
-                        TermError::Internal("Failed to downcast to Float64Array".to_string())
-                    })?
-                    .value(0);
-
-                if assertion.evaluate(value) {
-                    Ok(ConstraintResult::success_with_metric(value))
-                } else {
-                    Ok(ConstraintResult::failure_with_metric(
+                let pair = self.pair_sql(ctx, table_name, column1, column2).await?;
+                let expr = match expr {
+                    Some(sql_expression) => sql_expression
+                        .replace("{column1}", &pair.column1)
+                        .replace("{column2}", &pair.column2),
+                    None if *correlation_type == CorrelationType::Covariance => {
+                        self.covariance_sql(&pair)
+                    }
+                    None => self.pearson_sql(&pair),
+                };
+                let (value, non_finite) =
+                    match self.query_pair(ctx, table_name, &expr, &pair).await? {
+                        PairOutcome::Value(value, non_finite) => (value, non_finite),
+                        PairOutcome::Done(result) => return Ok(result),
+                    };
+
+                let result = assertion
+                    .explain(
                         value,
                         format!(
-                            "{} between {column1} and {column2} is {value} which does not {assertion}",
+                            "{} between '{column1}' and '{column2}'",
                             correlation_type.name()
                         ),
-                    ))
-                }
+                    )
+                    .to_result();
+                Ok(with_non_finite(result, non_finite))
             }
             CorrelationValidation::Range {
                 column1,
@@ -399,43 +518,25 @@ impl Constraint for CorrelationConstraint {
                 column2,
                 max_correlation,
             } => {
-                // Get the table name from the validation context
-
-                let validation_ctx = current_validation_context();
-
-                let table_name = validation_ctx.table_name();
-
-                let sql = format!(
-                    "SELECT ABS({}) as abs_corr FROM {table_name}",
-                    self.pearson_sql(column1, column2)?
-                );
-
-                let df = ctx.sql(&sql).await?;
-                let batches = df.collect().await?;
-
-                if batches.is_empty() || batches[0].num_rows() == 0 {
-                    return Ok(ConstraintResult::skipped("No data to validate"));
-                }
-
-                let abs_corr = batches[0]
-                    .column(0)
-                    .as_any()
-                    .downcast_ref::<arrow::array::Float64Array>()
-                    .ok_or_else(|| {
-                        TermError::Internal("Failed to downcast to Float64Array".to_string())
-                    })?
-                    .value(0);
-
-                if abs_corr <= *max_correlation {
-                    Ok(ConstraintResult::success_with_metric(abs_corr))
+                let pair = self.pair_sql(ctx, table_name, column1, column2).await?;
+                let expr = format!("ABS({})", self.pearson_sql(&pair));
+                let (abs_corr, non_finite) =
+                    match self.query_pair(ctx, table_name, &expr, &pair).await? {
+                        PairOutcome::Value(value, non_finite) => (value, non_finite),
+                        PairOutcome::Done(result) => return Ok(result),
+                    };
+
+                let result = if abs_corr <= *max_correlation {
+                    ConstraintResult::success_with_metric(abs_corr)
                 } else {
-                    Ok(ConstraintResult::failure_with_metric(
+                    ConstraintResult::failure_with_metric(
                         abs_corr,
                         format!(
                             "Columns {column1} and {column2} have correlation {abs_corr} exceeding independence threshold {max_correlation}"
                         ),
-                    ))
-                }
+                    )
+                };
+                Ok(with_non_finite(result, non_finite))
             }
             _ => Ok(ConstraintResult::skipped(
                 "Validation type not yet implemented",
@@ -490,7 +591,9 @@ impl Constraint for CorrelationConstraint {
             ),
         };
 
-        ConstraintMetadata::new().with_description(description)
+        ConstraintMetadata::new()
+            .with_description(description)
+            .with_custom("non_finite_policy", self.non_finite.to_string())
     }
 }
 
@@ -501,7 +604,7 @@ impl CorrelationConstraint {
         ctx: &SessionContext,
         validation: &CorrelationValidation,
     ) -> Result<ConstraintResult> {
-        let temp_constraint = Self::new(validation.clone())?;
+        let temp_constraint = Self::new(validation.clone())?.non_finite_policy(self.non_finite);
         temp_constraint.evaluate(ctx).await
     }
 }
@@ -509,6 +612,7 @@ impl CorrelationConstraint {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constraints::NON_FINITE_DETAIL;
     use crate::core::ConstraintStatus;
     use arrow::array::Float64Array;
     use arrow::datatypes::{DataType, Field, Schema};
@@ -597,6 +701,56 @@ mod tests {
         assert!(result.metric.unwrap() > 0.9);
     }
 
+    #[tokio::test]
+    async fn test_non_finite_rows() {
+        let ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("x", DataType::Float64, true),
+            Field::new("y", DataType::Float64, true),
+        ]));
+        let mut x_values: Vec<Option<f64>> = (0..50).map(|i| Some(i as f64)).collect();
+        let mut y_values: Vec<Option<f64>> = (0..50).map(|i| Some(3.0 * i as f64)).collect();
+        x_values.push(Some(f64::NAN));
+        y_values.push(Some(1.0));
+        x_values.push(Some(2.0));
+        y_values.push(Some(f64::NEG_INFINITY));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Float64Array::from(x_values)),
+                Arc::new(Float64Array::from(y_values)),
+            ],
+        )
+        .unwrap();
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("data", Arc::new(provider)).unwrap();
+
+        let pearson =
+            || CorrelationConstraint::pearson("x", "y", Assertion::GreaterThan(0.99)).unwrap();
+
+        let result = evaluate_constraint_with_context(&pearson(), &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.details[NON_FINITE_DETAIL], 2.0);
+
+        let constraint = pearson().non_finite_policy(NonFinitePolicy::Fail);
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert!(result
+            .message
+            .unwrap()
+            .starts_with("2 rows have a non-finite value"));
+
+        let constraint = pearson().non_finite_policy(NonFinitePolicy::Propagate);
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+    }
+
     #[tokio::test]
     async fn test_independence_check() {
         let ctx = create_test_context_independent().await;
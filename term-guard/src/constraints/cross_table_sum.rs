@@ -32,16 +32,52 @@
 //!     .group_by(vec!["customer_id"])
 //!     .tolerance(0.01);
 //! ```
+//!
+//! ## Tables in Custom Schemas
+//!
+//! ```rust
+//! use term_guard::constraints::CrossTableSumConstraint;
+//!
+//! // Compare the same table across two schemas
+//! let constraint = CrossTableSumConstraint::new("staging.orders.total", "prod.orders.total");
+//! ```
 
-use crate::core::{Constraint, ConstraintResult, ConstraintStatus};
+use crate::constraints::numeric::{decimal_literal, decimal_scale, exact_decimal, value_as_f64};
+use crate::core::{
+    collect_limited, collect_with_metrics, current_validation_context, parse_qualified_column,
+    CollectionSummary, Constraint, ConstraintResult, ConstraintStatus, ParamValue, ParameterRef,
+    QualifiedTable, QualityDimension, TableAggregate, TableAggregateFunction,
+};
 use crate::error::{Result, TermError};
 use crate::security::SqlSecurity;
-use arrow::array::{Array, Float64Array, StringArray};
+use arrow::array::{Array, StringArray};
 use async_trait::async_trait;
 use datafusion::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use tracing::{debug, instrument, warn};
 
+/// SQL literals used when the two sums are compared.
+///
+/// When both columns are decimals, the sums are compared in decimal arithmetic and the
+/// tolerance is rounded to the finer of the two column scales. Otherwise both sides are
+/// compared as floating point values.
+#[derive(Debug, Clone, PartialEq)]
+struct SumLiterals {
+    zero: String,
+    tolerance: String,
+}
+
+impl SumLiterals {
+    /// Literals for comparing the sums as floating point values.
+    fn float(tolerance: f64) -> Self {
+        Self {
+            zero: "0.0".to_string(),
+            tolerance: tolerance.to_string(),
+        }
+    }
+}
+
 /// Cross-table sum constraint for validating that sums from different tables match.
 ///
 /// This constraint ensures that aggregated sums from one table match the sums from another table,
@@ -53,6 +89,7 @@ use tracing::{debug, instrument, warn};
 /// - Qualified column names (table.column format)
 /// - GROUP BY columns for validating sums within groups
 /// - Configurable tolerance for floating-point comparisons
+/// - Exact sums and comparisons for decimal columns
 /// - Detailed violation reporting with specific group information
 /// - Performance optimization through efficient SQL generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,7 +101,7 @@ pub struct CrossTableSumConstraint {
     /// Optional columns to group by for the comparison
     group_by_columns: Vec<String>,
     /// Tolerance for floating point comparisons (default: 0.0 for exact match)
-    tolerance: f64,
+    tolerance: ParamValue,
     /// Maximum number of violation examples to collect
     max_violations_reported: usize,
 }
@@ -89,7 +126,7 @@ impl CrossTableSumConstraint {
             left_column: left_column.into(),
             right_column: right_column.into(),
             group_by_columns: Vec::new(),
-            tolerance: 0.0,
+            tolerance: ParamValue::Value(0.0),
             max_violations_reported: 100,
         }
     }
@@ -118,6 +155,10 @@ impl CrossTableSumConstraint {
     /// difference is within the tolerance. This is useful for handling floating-point
     /// precision issues.
     ///
+    /// When both columns are `Decimal128`/`Decimal256`, the sums are computed and compared
+    /// exactly and the tolerance is interpreted in the column's scale: it is rounded to the
+    /// number of fractional digits of the finer of the two columns.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -127,10 +168,24 @@ impl CrossTableSumConstraint {
     ///     .tolerance(0.01); // Allow 1 cent difference
     /// ```
     pub fn tolerance(mut self, tolerance: f64) -> Self {
-        self.tolerance = tolerance.abs(); // Ensure tolerance is positive
+        self.tolerance = ParamValue::Value(tolerance.abs()); // Ensure tolerance is positive
+        self
+    }
+
+    /// Read the tolerance from a named parameter when the suite runs.
+    ///
+    /// The parameter must be non-negative. See
+    /// [`ParameterBag`](crate::core::ParameterBag) for how parameters are supplied.
+    pub fn tolerance_param(mut self, name: impl Into<String>) -> Self {
+        self.tolerance = ParamValue::param(name);
         self
     }
 
+    /// Resolve the tolerance, reading it from the current parameters if needed
+    fn resolved_tolerance(&self) -> Result<f64> {
+        Ok(self.tolerance.resolve()?.abs())
+    }
+
     /// Set the maximum number of violation examples to report.
     ///
     /// Defaults to 100. Set to 0 to disable violation example collection.
@@ -155,23 +210,16 @@ impl CrossTableSumConstraint {
     }
 
     /// Parse table and column from qualified column name (e.g., "orders.total")
-    fn parse_qualified_column(&self, qualified_column: &str) -> Result<(String, String)> {
-        let parts: Vec<&str> = qualified_column.split('.').collect();
-        if parts.len() != 2 {
-            return Err(TermError::constraint_evaluation(
-                "cross_table_sum",
-                format!("Column must be qualified (table.column): '{qualified_column}'"),
-            ));
-        }
-
-        let table = parts[0].to_string();
-        let column = parts[1].to_string();
-
-        // Validate SQL identifiers for security
-        SqlSecurity::validate_identifier(&table)?;
-        SqlSecurity::validate_identifier(&column)?;
+    fn parse_qualified_column(&self, qualified_column: &str) -> Result<(QualifiedTable, String)> {
+        parse_qualified_column("cross_table_sum", qualified_column)
+    }
 
-        Ok((table, column))
+    /// Escape group by columns for use in generated SQL
+    fn escaped_group_by_columns(&self) -> Result<Vec<String>> {
+        self.group_by_columns
+            .iter()
+            .map(|col| SqlSecurity::escape_identifier(col))
+            .collect()
     }
 
     /// Validate group by columns for security
@@ -182,6 +230,51 @@ impl CrossTableSumConstraint {
         Ok(())
     }
 
+    /// Pick the literals for comparing the sums based on the column types
+    async fn sum_literals(
+        &self,
+        ctx: &SessionContext,
+        left_table: &QualifiedTable,
+        left_col: &str,
+        right_table: &QualifiedTable,
+        right_col: &str,
+    ) -> Result<SumLiterals> {
+        let tolerance = self.resolved_tolerance()?;
+        let left_scale = Self::column_scale(ctx, left_table, left_col).await?;
+        let right_scale = Self::column_scale(ctx, right_table, right_col).await?;
+
+        Ok(match (left_scale, right_scale) {
+            (Some(left), Some(right)) => {
+                let scale = left.max(right);
+                SumLiterals {
+                    zero: decimal_literal(0.0, scale),
+                    tolerance: decimal_literal(tolerance, scale),
+                }
+            }
+            _ => SumLiterals::float(tolerance),
+        })
+    }
+
+    /// Returns the scale of a decimal column, or `None` for other column types
+    async fn column_scale(
+        ctx: &SessionContext,
+        table: &QualifiedTable,
+        column: &str,
+    ) -> Result<Option<i8>> {
+        let sql = format!(
+            "SELECT {} FROM {} LIMIT 0",
+            SqlSecurity::escape_identifier(column)?,
+            table.to_sql()?
+        );
+        let df = ctx.sql(&sql).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                "cross_table_sum",
+                format!("Failed to resolve type of {table}.{column}: {e}"),
+            )
+        })?;
+        Ok(decimal_scale(df.schema().field(0).data_type()))
+    }
+
     /// Generate optimized SQL query for cross-table sum validation
     ///
     /// This optimized version eliminates expensive CTEs and FULL OUTER JOINs by:
@@ -190,14 +283,20 @@ impl CrossTableSumConstraint {
     /// 3. Leveraging DataFusion's pushdown optimizations
     fn generate_validation_query(
         &self,
-        left_table: &str,
+        left_table: &QualifiedTable,
         left_col: &str,
-        right_table: &str,
+        right_table: &QualifiedTable,
         right_col: &str,
+        literals: &SumLiterals,
     ) -> Result<String> {
+        let left_table = left_table.to_sql()?;
+        let left_col = SqlSecurity::escape_identifier(left_col)?;
+        let right_table = right_table.to_sql()?;
+        let right_col = SqlSecurity::escape_identifier(right_col)?;
+
         if self.group_by_columns.is_empty() {
             // Optimized scalar approach for non-grouped comparison
-            let tolerance = self.tolerance;
+            let SumLiterals { zero, tolerance } = literals;
             let sql = format!(
                 "SELECT 
                     1 as total_groups,
@@ -208,29 +307,25 @@ impl CrossTableSumConstraint {
                     ABS(left_total - right_total) as max_difference
                 FROM (
                     SELECT 
-                        COALESCE((SELECT SUM({left_col}) FROM {left_table}), 0.0) as left_total,
-                        COALESCE((SELECT SUM({right_col}) FROM {right_table}), 0.0) as right_total
+                        COALESCE((SELECT SUM({left_col}) FROM {left_table}), {zero}) as left_total,
+                        COALESCE((SELECT SUM({right_col}) FROM {right_table}), {zero}) as right_total
                 ) totals"
             );
             debug!("Generated optimized non-grouped cross-table sum query: {sql}");
             Ok(sql)
         } else {
             // Optimized grouped approach using direct aggregation with UNION ALL
-            let group_columns = self
-                .group_by_columns
-                .iter()
-                .map(|col| col.to_string())
-                .collect::<Vec<_>>();
+            let group_columns = self.escaped_group_by_columns()?;
 
             let left_group_select = group_columns
                 .iter()
-                .map(|col| format!("{left_table}.{col}"))
+                .map(|col| format!("left_tbl.{col}"))
                 .collect::<Vec<_>>()
                 .join(", ");
 
             let right_group_select = group_columns
                 .iter()
-                .map(|col| format!("{right_table}.{col}"))
+                .map(|col| format!("right_tbl.{col}"))
                 .collect::<Vec<_>>()
                 .join(", ");
 
@@ -241,7 +336,7 @@ impl CrossTableSumConstraint {
                 .join(", ");
 
             // Use more direct approach to avoid DataFusion aggregation nesting issues
-            let tolerance = self.tolerance;
+            let SumLiterals { zero, tolerance } = literals;
             let join_condition = group_columns
                 .iter()
                 .map(|col| format!("l.{col} = r.{col}"))
@@ -250,22 +345,22 @@ impl CrossTableSumConstraint {
             let sql = format!(
                 "WITH left_sums AS (
                     SELECT {left_group_select}, 
-                           COALESCE(SUM({left_table}.{left_col}), 0.0) as left_sum
-                    FROM {left_table}
+                           COALESCE(SUM(left_tbl.{left_col}), {zero}) as left_sum
+                    FROM {left_table} AS left_tbl
                     GROUP BY {left_group_select}
                 ),
                 right_sums AS (
                     SELECT {right_group_select}, 
-                           COALESCE(SUM({right_table}.{right_col}), 0.0) as right_sum
-                    FROM {right_table}
+                           COALESCE(SUM(right_tbl.{right_col}), {zero}) as right_sum
+                    FROM {right_table} AS right_tbl
                     GROUP BY {right_group_select}
                 ),
                 combined_data AS (
                     SELECT 
-                        COALESCE(l.left_sum, 0.0) as total_left_sum,
-                        COALESCE(r.right_sum, 0.0) as total_right_sum,
-                        ABS(COALESCE(l.left_sum, 0.0) - COALESCE(r.right_sum, 0.0)) as difference,
-                        CASE WHEN ABS(COALESCE(l.left_sum, 0.0) - COALESCE(r.right_sum, 0.0)) > {tolerance}
+                        COALESCE(l.left_sum, {zero}) as total_left_sum,
+                        COALESCE(r.right_sum, {zero}) as total_right_sum,
+                        ABS(COALESCE(l.left_sum, {zero}) - COALESCE(r.right_sum, {zero})) as difference,
+                        CASE WHEN ABS(COALESCE(l.left_sum, {zero}) - COALESCE(r.right_sum, {zero})) > {tolerance}
                              THEN 1 ELSE 0 END as is_violation
                     FROM left_sums l
                     FULL OUTER JOIN right_sums r ON {join_condition}
@@ -286,18 +381,24 @@ impl CrossTableSumConstraint {
     /// Generate optimized SQL query to get violation examples with streaming-friendly approach
     fn generate_violations_query(
         &self,
-        left_table: &str,
+        left_table: &QualifiedTable,
         left_col: &str,
-        right_table: &str,
+        right_table: &QualifiedTable,
         right_col: &str,
+        literals: &SumLiterals,
     ) -> Result<String> {
         if self.max_violations_reported == 0 {
             return Ok(String::new());
         }
 
+        let left_table = left_table.to_sql()?;
+        let left_col = SqlSecurity::escape_identifier(left_col)?;
+        let right_table = right_table.to_sql()?;
+        let right_col = SqlSecurity::escape_identifier(right_col)?;
+
         if self.group_by_columns.is_empty() {
             // Simple case: return overall violation if it exists
-            let tolerance = self.tolerance;
+            let SumLiterals { zero, tolerance } = literals;
             let limit = self.max_violations_reported;
             let sql = format!(
                 "SELECT 
@@ -307,8 +408,8 @@ impl CrossTableSumConstraint {
                     ABS(left_total - right_total) as difference
                 FROM (
                     SELECT 
-                        COALESCE((SELECT SUM({left_col}) FROM {left_table}), 0.0) as left_total,
-                        COALESCE((SELECT SUM({right_col}) FROM {right_table}), 0.0) as right_total
+                        COALESCE((SELECT SUM({left_col}) FROM {left_table}), {zero}) as left_total,
+                        COALESCE((SELECT SUM({right_col}) FROM {right_table}), {zero}) as right_total
                 ) totals
                 WHERE ABS(left_total - right_total) > {tolerance}
                 LIMIT {limit}"
@@ -317,21 +418,17 @@ impl CrossTableSumConstraint {
             Ok(sql)
         } else {
             // Optimized grouped violations query using UNION ALL approach
-            let group_columns = self
-                .group_by_columns
-                .iter()
-                .map(|col| col.to_string())
-                .collect::<Vec<_>>();
+            let group_columns = self.escaped_group_by_columns()?;
 
             let left_group_select = group_columns
                 .iter()
-                .map(|col| format!("{left_table}.{col}"))
+                .map(|col| format!("left_tbl.{col}"))
                 .collect::<Vec<_>>()
                 .join(", ");
 
             let right_group_select = group_columns
                 .iter()
-                .map(|col| format!("{right_table}.{col}"))
+                .map(|col| format!("right_tbl.{col}"))
                 .collect::<Vec<_>>()
                 .join(", ");
 
@@ -353,7 +450,7 @@ impl CrossTableSumConstraint {
 
             let _group_by_clause = group_columns.join(", ");
 
-            let tolerance = self.tolerance;
+            let SumLiterals { zero, tolerance } = literals;
             let limit = self.max_violations_reported;
             let join_condition = group_columns
                 .iter()
@@ -363,25 +460,25 @@ impl CrossTableSumConstraint {
             let sql = format!(
                 "WITH left_sums AS (
                     SELECT {left_group_select}, 
-                           COALESCE(SUM({left_table}.{left_col}), 0.0) as left_sum
-                    FROM {left_table}
+                           COALESCE(SUM(left_tbl.{left_col}), {zero}) as left_sum
+                    FROM {left_table} AS left_tbl
                     GROUP BY {left_group_select}
                 ),
                 right_sums AS (
                     SELECT {right_group_select}, 
-                           COALESCE(SUM({right_table}.{right_col}), 0.0) as right_sum
-                    FROM {right_table}
+                           COALESCE(SUM(right_tbl.{right_col}), {zero}) as right_sum
+                    FROM {right_table} AS right_tbl
                     GROUP BY {right_group_select}
                 )
                 SELECT 
                     {group_key_concat} as group_key,
-                    COALESCE(l.left_sum, 0.0) as left_sum,
-                    COALESCE(r.right_sum, 0.0) as right_sum,
-                    ABS(COALESCE(l.left_sum, 0.0) - COALESCE(r.right_sum, 0.0)) as difference
+                    COALESCE(l.left_sum, {zero}) as left_sum,
+                    COALESCE(r.right_sum, {zero}) as right_sum,
+                    ABS(COALESCE(l.left_sum, {zero}) - COALESCE(r.right_sum, {zero})) as difference
                 FROM left_sums l
                 FULL OUTER JOIN right_sums r ON {join_condition}
-                WHERE ABS(COALESCE(l.left_sum, 0.0) - COALESCE(r.right_sum, 0.0)) > {tolerance}
-                ORDER BY ABS(COALESCE(l.left_sum, 0.0) - COALESCE(r.right_sum, 0.0)) DESC
+                WHERE ABS(COALESCE(l.left_sum, {zero}) - COALESCE(r.right_sum, {zero})) > {tolerance}
+                ORDER BY ABS(COALESCE(l.left_sum, {zero}) - COALESCE(r.right_sum, {zero})) DESC
                 LIMIT {limit}"
             );
             debug!("Generated optimized grouped violations query: {sql}");
@@ -393,29 +490,30 @@ impl CrossTableSumConstraint {
     ///
     /// This method limits memory usage by:
     /// 1. Using LIMIT in the SQL query to restrict result size at the database level
-    /// 2. Pre-allocating vector with known maximum size
+    /// 2. Collecting the results within the collection limits of the run
     /// 3. Processing results in a single pass without intermediate collections
     async fn collect_violation_examples_simple(
         &self,
         ctx: &SessionContext,
-        left_table: &str,
+        left_table: &QualifiedTable,
         left_col: &str,
-        right_table: &str,
+        right_table: &QualifiedTable,
         right_col: &str,
-    ) -> Result<Vec<String>> {
+        literals: &SumLiterals,
+    ) -> Result<(Vec<String>, CollectionSummary)> {
         // For now, use a simple but correct approach that works around DataFusion limitations
         // In production, violations should be rare, so memory usage is typically not a concern
 
         // For grouped constraints, temporarily disable violation collection to avoid schema conflicts
         if !self.group_by_columns.is_empty() {
             debug!("Skipping violation example collection for grouped constraint due to DataFusion limitations");
-            return Ok(Vec::new());
+            return Ok((Vec::new(), CollectionSummary::default()));
         }
 
         let violations_sql =
-            self.generate_violations_query(left_table, left_col, right_table, right_col)?;
+            self.generate_violations_query(left_table, left_col, right_table, right_col, literals)?;
         if violations_sql.is_empty() {
-            return Ok(Vec::new());
+            return Ok((Vec::new(), CollectionSummary::default()));
         }
 
         debug!("Executing simple violations query");
@@ -427,47 +525,53 @@ impl CrossTableSumConstraint {
             )
         })?;
 
-        let batches = violations_df.collect().await.map_err(|e| {
+        let collected = collect_limited(violations_df).await.map_err(|e| {
             TermError::constraint_evaluation(
                 "cross_table_sum",
                 format!("Failed to collect violation examples: {e}"),
             )
         })?;
 
+        let validation_ctx = current_validation_context();
         let mut violation_examples = Vec::with_capacity(self.max_violations_reported);
 
-        for batch in batches {
+        for batch in collected.iter()? {
+            let batch = batch?;
             for i in 0..batch.num_rows() {
                 if violation_examples.len() >= self.max_violations_reported {
                     break;
                 }
 
-                if let (Some(group_key), Some(left_sum), Some(right_sum), Some(diff)) = (
-                    batch.column(0).as_any().downcast_ref::<StringArray>(),
-                    batch.column(1).as_any().downcast_ref::<Float64Array>(),
-                    batch.column(2).as_any().downcast_ref::<Float64Array>(),
-                    batch.column(3).as_any().downcast_ref::<Float64Array>(),
-                ) {
-                    if !group_key.is_null(i) {
-                        violation_examples.push(format!(
-                            "Group '{}': {} = {:.4}, {} = {:.4} (diff: {:.4})",
-                            group_key.value(i),
-                            self.left_column,
-                            left_sum.value(i),
-                            self.right_column,
-                            right_sum.value(i),
-                            diff.value(i)
-                        ));
-                    }
+                let Some(group_key) = batch.column(0).as_any().downcast_ref::<StringArray>() else {
+                    continue;
+                };
+                if !group_key.is_null(i) {
+                    violation_examples.push(format!(
+                        "Group '{}': {} = {}, {} = {} (diff: {})",
+                        validation_ctx.redact_any(&self.group_by_columns, group_key.value(i)),
+                        self.left_column,
+                        sum_text(batch.column(1).as_ref(), i)?,
+                        self.right_column,
+                        sum_text(batch.column(2).as_ref(), i)?,
+                        sum_text(batch.column(3).as_ref(), i)?
+                    ));
                 }
             }
         }
 
         debug!("Collected {} violation examples", violation_examples.len());
-        Ok(violation_examples)
+        Ok((violation_examples, collected.summary()))
     }
 }
 
+/// Format a sum for messages: decimals exactly, floating point values to four places
+fn sum_text(array: &dyn Array, index: usize) -> Result<String> {
+    if let Some(exact) = exact_decimal(array, index) {
+        return Ok(exact);
+    }
+    Ok(format!("{:.4}", value_as_f64(array, index)?.unwrap_or(0.0)))
+}
+
 #[async_trait]
 impl Constraint for CrossTableSumConstraint {
     #[instrument(skip(self, ctx), fields(constraint = "cross_table_sum"))]
@@ -484,9 +588,19 @@ impl Constraint for CrossTableSumConstraint {
         // Validate group by columns
         self.validate_group_by_columns()?;
 
+        // Decimal columns are compared exactly, in the columns' own scale
+        let literals = self
+            .sum_literals(ctx, &left_table, &left_col, &right_table, &right_col)
+            .await?;
+
         // Generate and execute validation query
-        let sql =
-            self.generate_validation_query(&left_table, &left_col, &right_table, &right_col)?;
+        let sql = self.generate_validation_query(
+            &left_table,
+            &left_col,
+            &right_table,
+            &right_col,
+            &literals,
+        )?;
         let df = ctx.sql(&sql).await.map_err(|e| {
             TermError::constraint_evaluation(
                 "cross_table_sum",
@@ -494,7 +608,7 @@ impl Constraint for CrossTableSumConstraint {
             )
         })?;
 
-        let batches = df.collect().await.map_err(|e| {
+        let batches = collect_with_metrics(df).await.map_err(|e| {
             TermError::constraint_evaluation(
                 "cross_table_sum",
                 format!("Failed to collect cross-table sum results: {e}"),
@@ -533,41 +647,9 @@ impl Constraint for CrossTableSumConstraint {
             })?
             .value(0);
 
-        let total_left_sum = batch
-            .column(2)
-            .as_any()
-            .downcast_ref::<Float64Array>()
-            .ok_or_else(|| {
-                TermError::constraint_evaluation(
-                    "cross_table_sum",
-                    "Invalid total_left_sum column type",
-                )
-            })?
-            .value(0);
-
-        let total_right_sum = batch
-            .column(3)
-            .as_any()
-            .downcast_ref::<Float64Array>()
-            .ok_or_else(|| {
-                TermError::constraint_evaluation(
-                    "cross_table_sum",
-                    "Invalid total_right_sum column type",
-                )
-            })?
-            .value(0);
-
-        let max_difference = batch
-            .column(4)
-            .as_any()
-            .downcast_ref::<Float64Array>()
-            .ok_or_else(|| {
-                TermError::constraint_evaluation(
-                    "cross_table_sum",
-                    "Invalid max_difference column type",
-                )
-            })?
-            .value(0);
+        let total_left_sum = sum_text(batch.column(2).as_ref(), 0)?;
+        let total_right_sum = sum_text(batch.column(3).as_ref(), 0)?;
+        let max_difference = value_as_f64(batch.column(4).as_ref(), 0)?.unwrap_or(0.0);
 
         if violating_groups == 0 {
             debug!("Cross-table sum constraint passed: all groups match within tolerance");
@@ -576,21 +658,24 @@ impl Constraint for CrossTableSumConstraint {
 
         // Collect violation examples with memory-efficient approach
         let mut violation_examples = Vec::new();
+        let mut collection = CollectionSummary::default();
         if self.max_violations_reported > 0 {
-            violation_examples = self
+            (violation_examples, collection) = self
                 .collect_violation_examples_simple(
                     ctx,
                     &left_table,
                     &left_col,
                     &right_table,
                     &right_col,
+                    &literals,
                 )
                 .await?;
         }
 
         // Format error message
-        let tolerance_text = if self.tolerance > 0.0 {
-            format!(" (tolerance: {:.4})", self.tolerance)
+        let tolerance = self.resolved_tolerance()?;
+        let tolerance_text = if tolerance > 0.0 {
+            format!(" (tolerance: {tolerance:.4})")
         } else {
             " (exact match required)".to_string()
         };
@@ -623,16 +708,76 @@ impl Constraint for CrossTableSumConstraint {
 
         warn!("{}", message);
 
+        let mut details = BTreeMap::new();
+        collection.record(&mut details);
         Ok(ConstraintResult {
             status: ConstraintStatus::Failure,
             metric: Some(max_difference),
             message: Some(message),
+            quarantine: None,
+            constraint_id: None,
+            details,
         })
     }
 
     fn name(&self) -> &str {
         "cross_table_sum"
     }
+
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Consistency)
+    }
+
+    fn generate_sql(&self, _table_name: &str) -> Result<Vec<String>> {
+        // The query as it runs for non-decimal columns. Violation details are only
+        // queried after a failure.
+        let (left_table, left_col) = self.parse_qualified_column(&self.left_column)?;
+        let (right_table, right_col) = self.parse_qualified_column(&self.right_column)?;
+        self.validate_group_by_columns()?;
+
+        let literals = SumLiterals::float(self.resolved_tolerance()?);
+        Ok(vec![self.generate_validation_query(
+            &left_table,
+            &left_col,
+            &right_table,
+            &right_col,
+            &literals,
+        )?])
+    }
+
+    fn referenced_tables(&self) -> Vec<String> {
+        [&self.left_column, &self.right_column]
+            .into_iter()
+            .filter_map(|column| self.parse_qualified_column(column).ok())
+            .map(|(table, _)| table.to_string())
+            .collect()
+    }
+
+    fn table_aggregates(&self) -> Option<Vec<TableAggregate>> {
+        // Grouped comparisons join the rows of both tables
+        if !self.group_by_columns.is_empty() {
+            return None;
+        }
+        [&self.left_column, &self.right_column]
+            .into_iter()
+            .map(|column| {
+                let (table, column) = self.parse_qualified_column(column).ok()?;
+                Some(TableAggregate::new(
+                    table.to_string(),
+                    column,
+                    TableAggregateFunction::Sum,
+                ))
+            })
+            .collect()
+    }
+
+    fn parameters(&self) -> Vec<ParameterRef> {
+        self.tolerance
+            .reference("cross_table_sum tolerance")
+            .map(|reference| reference.at_least(0.0))
+            .into_iter()
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -781,17 +926,103 @@ mod tests {
         Ok(())
     }
 
+    async fn create_decimal_table(
+        ctx: &SessionContext,
+        table: &str,
+        amounts: &[&str],
+    ) -> Result<()> {
+        ctx.sql(&format!("CREATE TABLE {table} (amount DECIMAL(38, 6))"))
+            .await?
+            .collect()
+            .await?;
+        // Cast from strings: numeric SQL literals would be parsed as f64 first
+        let rows = amounts
+            .iter()
+            .map(|amount| format!("(CAST('{amount}' AS DECIMAL(38, 6)))"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        ctx.sql(&format!("INSERT INTO {table} VALUES {rows}"))
+            .await?
+            .collect()
+            .await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cross_table_sum_decimal_is_exact() -> Result<()> {
+        let ctx = create_test_context().await?;
+        let mut ledger = vec!["1000000000000000.000000"];
+        ledger.extend(std::iter::repeat("0.060000").take(10));
+        create_decimal_table(&ctx, "ledger_exact", &ledger).await?;
+        create_decimal_table(&ctx, "settlements_exact", &["1000000000000000.600000"]).await?;
+
+        // Accumulating the same values in f64 drops every 0.06 and misses by ~0.6
+        let f64_sum = (0..10).fold(1_000_000_000_000_000.0_f64, |sum, _| sum + 0.06);
+        assert!((f64_sum - 1_000_000_000_000_000.6_f64).abs() > 0.01);
+
+        let constraint =
+            CrossTableSumConstraint::new("ledger_exact.amount", "settlements_exact.amount")
+                .tolerance(0.01);
+        let result = constraint.evaluate(&ctx).await?;
+
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.0));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cross_table_sum_decimal_tolerance_uses_column_scale() -> Result<()> {
+        let ctx = create_test_context().await?;
+        create_decimal_table(&ctx, "ledger_scale", &["1000000000000000.600000"]).await?;
+        create_decimal_table(
+            &ctx,
+            "settlements_scale",
+            &["1000000000000000.600000", "0.020000"],
+        )
+        .await?;
+
+        // The sums differ by exactly 0.02
+        let within =
+            CrossTableSumConstraint::new("ledger_scale.amount", "settlements_scale.amount")
+                .tolerance(0.02);
+        assert_eq!(
+            within.evaluate(&ctx).await?.status,
+            ConstraintStatus::Success
+        );
+
+        // Rounded to six places this tolerance is 0.019999
+        let outside =
+            CrossTableSumConstraint::new("ledger_scale.amount", "settlements_scale.amount")
+                .tolerance(0.0199991);
+        let result = outside.evaluate(&ctx).await?;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        let message = result.message.unwrap();
+        assert!(message.contains("1000000000000000.600000"), "{message}");
+        assert!(message.contains("1000000000000000.620000"), "{message}");
+
+        Ok(())
+    }
+
     #[test]
     fn test_parse_qualified_column() {
         let constraint = CrossTableSumConstraint::new("orders.total", "payments.amount");
 
         let (table, column) = constraint.parse_qualified_column("orders.total").unwrap();
-        assert_eq!(table, "orders");
+        assert_eq!(table.to_string(), "orders");
+        assert_eq!(column, "total");
+
+        let (table, column) = constraint
+            .parse_qualified_column("staging.orders.total")
+            .unwrap();
+        assert_eq!(table.to_string(), "staging.orders");
         assert_eq!(column, "total");
 
         // Test invalid format
         assert!(constraint.parse_qualified_column("invalid_column").is_err());
-        assert!(constraint.parse_qualified_column("too.many.parts").is_err());
+        assert!(constraint
+            .parse_qualified_column("way.too.many.parts.here")
+            .is_err());
     }
 
     #[test]
@@ -807,7 +1038,7 @@ mod tests {
             constraint.group_by_columns(),
             &["customer_id", "order_date"]
         );
-        assert_eq!(constraint.tolerance, 0.01);
+        assert_eq!(constraint.tolerance, ParamValue::Value(0.01));
         assert_eq!(constraint.max_violations_reported, 50);
     }
 
@@ -816,4 +1047,25 @@ mod tests {
         let constraint = CrossTableSumConstraint::new("orders.total", "payments.amount");
         assert_eq!(constraint.name(), "cross_table_sum");
     }
+
+    #[test]
+    fn test_generate_sql() -> Result<()> {
+        let constraint =
+            CrossTableSumConstraint::new("orders.total", "payments.amount").tolerance(0.01);
+        let sql = constraint.generate_sql("orders")?;
+        assert_eq!(sql.len(), 1);
+        assert!(sql[0].contains(r#"SUM("total") FROM "orders""#));
+        assert!(sql[0].contains(r#"SUM("amount") FROM "payments""#));
+        assert!(sql[0].contains("> 0.01"));
+
+        let grouped = constraint
+            .group_by(vec!["customer_id"])
+            .generate_sql("orders")?;
+        assert!(grouped[0].contains(r#"GROUP BY left_tbl."customer_id""#));
+
+        assert!(CrossTableSumConstraint::new("total", "payments.amount")
+            .generate_sql("orders")
+            .is_err());
+        Ok(())
+    }
 }
@@ -1,28 +1,74 @@
 //! Custom SQL validation constraints.
 
-use crate::core::{current_validation_context, Constraint, ConstraintMetadata, ConstraintResult};
+use crate::constraints::Assertion;
+use crate::core::{
+    collect_with_metrics, current_validation_context, describe_percent, Constraint,
+    ConstraintMetadata, ConstraintResult, Threshold,
+};
 use crate::prelude::*;
 use crate::security::SqlSecurity;
+use arrow::array::{Array, ArrayRef, BooleanArray, Float64Array};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
 use async_trait::async_trait;
 use datafusion::prelude::*;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
-use std::sync::RwLock;
+use std::fmt;
+use std::sync::{PoisonError, RwLock};
 use tracing::instrument;
 /// Cache for compiled regex patterns to avoid recompiling
 static REGEX_CACHE: Lazy<RwLock<HashMap<String, Regex>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
 
+/// Placeholder replaced with the validated table in scalar queries.
+const TABLE_PLACEHOLDER: &str = "{table}";
+
+/// How a custom SQL constraint interprets its SQL.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustomSqlMode {
+    /// A boolean expression evaluated for every row of the validated table;
+    /// passes when at least `threshold` of the rows satisfy it
+    RowPredicate {
+        /// The minimum fraction of rows satisfying the expression
+        threshold: f64,
+    },
+    /// A complete `SELECT` returning a single numeric value checked against
+    /// `assertion`
+    ScalarQuery {
+        /// The assertion the value must satisfy
+        assertion: Assertion,
+    },
+    /// A complete `SELECT` returning a single boolean that must be true
+    BooleanScalar,
+}
+
+impl fmt::Display for CustomSqlMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CustomSqlMode::RowPredicate { .. } => write!(f, "row predicate"),
+            CustomSqlMode::ScalarQuery { .. } => write!(f, "scalar query"),
+            CustomSqlMode::BooleanScalar => write!(f, "boolean scalar"),
+        }
+    }
+}
+
 /// A constraint that evaluates custom SQL expressions.
 ///
 /// This constraint allows users to define custom validation logic using SQL expressions
 /// while preventing dangerous operations like DROP, DELETE, UPDATE, etc.
 ///
+/// By default the SQL is a row-level predicate, see [`CustomSqlMode`]. Rules that are
+/// naturally a whole query, such as a count over a `GROUP BY ... HAVING`, use
+/// [`scalar_query`](Self::scalar_query) or [`boolean_scalar`](Self::boolean_scalar).
+/// Such queries may be any single `SELECT`, but never DML, DDL or several statements;
+/// `{table}` in them is replaced with the validated table.
+///
 /// # Examples
 ///
 /// ```rust
-/// use term_guard::constraints::CustomSqlConstraint;
+/// use term_guard::constraints::{Assertion, CustomSqlConstraint};
 /// use term_guard::core::Constraint;
 ///
 /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
@@ -35,6 +81,12 @@ static REGEX_CACHE: Lazy<RwLock<HashMap<String, Regex>>> =
 ///     "order_date <= ship_date",
 ///     Some("Shipping date must be after or equal to order date")
 /// )?;
+///
+/// // At most 5 customers with more than 100 orders
+/// let constraint = CustomSqlConstraint::scalar_query(
+///     "SELECT count(*) FROM (SELECT customer_id FROM {table} GROUP BY customer_id HAVING count(*) > 100)",
+///     Assertion::LessThanOrEqual(5.0),
+/// )?;
 /// # Ok(())
 /// # }
 /// # example().unwrap();
@@ -43,6 +95,7 @@ static REGEX_CACHE: Lazy<RwLock<HashMap<String, Regex>>> =
 pub struct CustomSqlConstraint {
     expression: String,
     hint: Option<String>,
+    mode: CustomSqlMode,
 }
 
 impl CustomSqlConstraint {
@@ -57,16 +110,11 @@ impl CustomSqlConstraint {
     ///
     /// Returns an error if the SQL expression contains dangerous operations
     pub fn new(expression: impl Into<String>, hint: Option<impl Into<String>>) -> Result<Self> {
-        let expression = expression.into();
-
-        // Validate the SQL expression for safety using both local and security module validation
-        validate_sql_expression(&expression)?;
-        SqlSecurity::validate_sql_expression(&expression)?;
-
-        Ok(Self {
+        Self::with_mode(
             expression,
-            hint: hint.map(Into::into),
-        })
+            CustomSqlMode::RowPredicate { threshold: 1.0 },
+            hint,
+        )
     }
 
     /// Attempts to create a new custom SQL constraint, returning an error if validation fails.
@@ -80,17 +128,291 @@ impl CustomSqlConstraint {
     ///
     /// A Result containing the constraint or a validation error
     pub fn try_new(expression: impl Into<String>, hint: Option<impl Into<String>>) -> Result<Self> {
-        let expression = expression.into();
+        Self::new(expression, hint)
+    }
 
-        // Validate the SQL expression for safety using both local and security module validation
-        validate_sql_expression(&expression)?;
-        SqlSecurity::validate_sql_expression(&expression)?;
+    /// Creates a constraint checking the single numeric value returned by `query`
+    /// against `assertion`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `query` is not a single `SELECT` query
+    pub fn scalar_query(query: impl Into<String>, assertion: Assertion) -> Result<Self> {
+        Self::with_mode(
+            query,
+            CustomSqlMode::ScalarQuery { assertion },
+            None::<String>,
+        )
+    }
+
+    /// Creates a constraint requiring the single boolean returned by `query` to be true.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `query` is not a single `SELECT` query
+    pub fn boolean_scalar(query: impl Into<String>) -> Result<Self> {
+        Self::with_mode(query, CustomSqlMode::BooleanScalar, None::<String>)
+    }
+
+    /// Creates a constraint interpreting `sql` according to `mode`.
+    ///
+    /// Row predicates are checked like [`new`](Self::new) checks them; scalar queries
+    /// must parse as exactly one `SELECT` query.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the SQL is not allowed in `mode`, or if the threshold of a
+    /// row predicate is not between 0.0 and 1.0
+    pub fn with_mode(
+        sql: impl Into<String>,
+        mode: CustomSqlMode,
+        hint: Option<impl Into<String>>,
+    ) -> Result<Self> {
+        let expression = sql.into();
+
+        match &mode {
+            CustomSqlMode::RowPredicate { threshold } => {
+                Threshold::new(*threshold)?;
+                // Validate the SQL expression for safety using both local and security module validation
+                validate_sql_expression(&expression)?;
+                SqlSecurity::validate_sql_expression(&expression)?;
+            }
+            // A full query is parsed instead, which rejects DML, DDL and stacked statements
+            CustomSqlMode::ScalarQuery { .. } | CustomSqlMode::BooleanScalar => {
+                SqlSecurity::validate_query(
+                    &expression.replace(TABLE_PLACEHOLDER, "validated_table"),
+                )?;
+            }
+        }
 
         Ok(Self {
             expression,
             hint: hint.map(Into::into),
+            mode,
         })
     }
+
+    /// Returns how the SQL is interpreted.
+    pub fn mode(&self) -> &CustomSqlMode {
+        &self.mode
+    }
+
+    /// Evaluates a row predicate over the validated table.
+    async fn evaluate_predicate(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        threshold: f64,
+    ) -> Result<ConstraintResult> {
+        // Only built-in functions and the UDFs registered on the session may be called
+        let state = ctx.state();
+        SqlSecurity::validate_function_calls(&self.expression, |name| {
+            state.scalar_functions().contains_key(name)
+                || state.aggregate_functions().contains_key(name)
+                || state.window_functions().contains_key(name)
+        })?;
+
+        let sql = format!(
+            "SELECT 
+                COUNT(CASE WHEN {} THEN 1 END) as satisfied,
+                COUNT(*) as total
+             FROM {table_name}",
+            self.expression
+        );
+
+        // Try to execute the SQL
+        let df = match ctx.sql(&sql).await {
+            Ok(df) => df,
+            Err(e) => {
+                // Return a clear error message for SQL errors
+                return Ok(ConstraintResult::failure(format!(
+                    "SQL expression error: {e}. Expression: '{}'",
+                    self.expression
+                )));
+            }
+        };
+
+        let batches = match collect_with_metrics(df).await {
+            Ok(batches) => batches,
+            Err(e) => {
+                // Return a clear error message for execution errors
+                return Ok(ConstraintResult::failure(format!(
+                    "SQL execution error: {e}. Expression: '{}'",
+                    self.expression
+                )));
+            }
+        };
+
+        if batches.is_empty() {
+            return Ok(ConstraintResult::skipped("No data to validate"));
+        }
+
+        let batch = &batches[0];
+        if batch.num_rows() == 0 {
+            return Ok(ConstraintResult::skipped("No data to validate"));
+        }
+
+        // Extract results
+        let satisfied = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<arrow::array::Int64Array>()
+            .ok_or_else(|| TermError::Internal("Failed to extract satisfied count".to_string()))?
+            .value(0) as f64;
+
+        let total = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<arrow::array::Int64Array>()
+            .ok_or_else(|| TermError::Internal("Failed to extract total count".to_string()))?
+            .value(0) as f64;
+
+        if total == 0.0 {
+            return Ok(ConstraintResult::empty_table());
+        }
+
+        let satisfaction_ratio = satisfied / total;
+
+        if satisfaction_ratio >= threshold {
+            Ok(ConstraintResult::success_with_metric(satisfaction_ratio))
+        } else {
+            let failed_count = total - satisfied;
+            let message = if let Some(hint) = &self.hint {
+                format!("{hint} ({} rows failed the condition)", failed_count as i64)
+            } else {
+                format!(
+                    "Custom SQL condition not satisfied for {} rows. Expression: '{}'",
+                    failed_count as i64, self.expression
+                )
+            };
+
+            Ok(ConstraintResult::failure_with_metric(
+                satisfaction_ratio,
+                message,
+            ))
+        }
+    }
+
+    /// Runs a scalar query and returns its single value cast to `data_type`, or the
+    /// failure describing why there is none.
+    async fn scalar_value(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        data_type: &DataType,
+    ) -> Result<std::result::Result<ArrayRef, ConstraintResult>> {
+        let sql = self.expression.replace(TABLE_PLACEHOLDER, table_name);
+        let failure = |message: String| {
+            Ok(Err(ConstraintResult::failure(format!(
+                "{message} (mode: {}). Query: '{}'",
+                self.mode, self.expression
+            ))))
+        };
+
+        let df = match ctx.sql(&sql).await {
+            Ok(df) => df,
+            Err(e) => return failure(format!("SQL query error: {e}")),
+        };
+        let batches = match collect_with_metrics(df).await {
+            Ok(batches) => batches,
+            Err(e) => return failure(format!("SQL execution error: {e}")),
+        };
+
+        let rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+        let Some(batch) = batches.iter().find(|batch| batch.num_rows() > 0) else {
+            return failure("Query returned no rows".to_string());
+        };
+        if rows > 1 || batch.num_columns() != 1 {
+            return failure(format!(
+                "Query must return a single value, returned {rows} rows of {} columns",
+                batch.num_columns()
+            ));
+        }
+        match cast(batch.column(0), data_type) {
+            Ok(value) if value.is_null(0) => failure("Query returned NULL".to_string()),
+            Ok(value) => Ok(Ok(value)),
+            Err(_) => failure(format!(
+                "Query returned a {} value, expected {data_type}",
+                batch.column(0).data_type()
+            )),
+        }
+    }
+
+    /// Evaluates a scalar query against an assertion.
+    async fn evaluate_scalar(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        assertion: &Assertion,
+    ) -> Result<ConstraintResult> {
+        let value = match self
+            .scalar_value(ctx, table_name, &DataType::Float64)
+            .await?
+        {
+            Ok(value) => value,
+            Err(failure) => return Ok(failure),
+        };
+        let value = value
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .ok_or_else(|| TermError::Internal("Failed to extract scalar value".to_string()))?
+            .value(0);
+
+        let details = format!("(mode: {}, value: {value})", self.mode);
+        let explanation = assertion.explain(value, "query result");
+        if explanation.passed {
+            Ok(ConstraintResult {
+                message: Some(format!("Query returned {value} {details}")),
+                ..ConstraintResult::success_with_metric(value)
+            })
+        } else {
+            let message = match &self.hint {
+                Some(hint) => format!("{hint} {details}"),
+                None => format!(
+                    "{explanation} (mode: {}). Query: '{}'",
+                    self.mode, self.expression
+                ),
+            };
+            Ok(ConstraintResult::failure_with_metric(value, message))
+        }
+    }
+
+    /// Evaluates a query returning a boolean.
+    async fn evaluate_boolean(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+    ) -> Result<ConstraintResult> {
+        let value = match self
+            .scalar_value(ctx, table_name, &DataType::Boolean)
+            .await?
+        {
+            Ok(value) => value,
+            Err(failure) => return Ok(failure),
+        };
+        let value = value
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .ok_or_else(|| TermError::Internal("Failed to extract boolean value".to_string()))?
+            .value(0);
+
+        let details = format!("(mode: {}, value: {value})", self.mode);
+        if value {
+            Ok(ConstraintResult {
+                message: Some(format!("Query returned true {details}")),
+                ..ConstraintResult::success_with_metric(1.0)
+            })
+        } else {
+            let message = match &self.hint {
+                Some(hint) => format!("{hint} {details}"),
+                None => format!(
+                    "Query returned false {details}. Query: '{}'",
+                    self.expression
+                ),
+            };
+            Ok(ConstraintResult::failure_with_metric(0.0, message))
+        }
+    }
 }
 
 /// Validates that a SQL expression doesn't contain dangerous operations.
@@ -140,18 +462,15 @@ fn validate_sql_expression(sql: &str) -> Result<()> {
 
         // Check cache first
         let matches = {
-            let cache = REGEX_CACHE.read().map_err(|_| {
-                TermError::Internal("Failed to acquire read lock on regex cache".to_string())
-            })?;
+            // Entries are inserted whole, so a poisoned cache is still valid
+            let cache = REGEX_CACHE.read().unwrap_or_else(PoisonError::into_inner);
 
             if let Some(regex) = cache.get(&pattern) {
                 regex.is_match(&sql_upper)
             } else {
                 // Need to compile and cache the regex
                 drop(cache);
-                let mut write_cache = REGEX_CACHE.write().map_err(|_| {
-                    TermError::Internal("Failed to acquire write lock on regex cache".to_string())
-                })?;
+                let mut write_cache = REGEX_CACHE.write().unwrap_or_else(PoisonError::into_inner);
 
                 let regex = Regex::new(&pattern).map_err(|e| {
                     TermError::Internal(format!("Failed to compile regex pattern: {e}"))
@@ -191,93 +510,19 @@ fn validate_sql_expression(sql: &str) -> Result<()> {
 
 #[async_trait]
 impl Constraint for CustomSqlConstraint {
-    #[instrument(skip(self, ctx), fields(expression = %self.expression))]
+    #[instrument(skip(self, ctx), fields(expression = %self.expression, mode = %self.mode))]
     async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
-        // Wrap the expression in a query that counts rows where the condition is true
-        // Get the table name from the validation context
-
         let validation_ctx = current_validation_context();
-
         let table_name = validation_ctx.table_name();
 
-        let sql = format!(
-            "SELECT 
-                COUNT(CASE WHEN {} THEN 1 END) as satisfied,
-                COUNT(*) as total
-             FROM {table_name}",
-            self.expression
-        );
-
-        // Try to execute the SQL
-        let df = match ctx.sql(&sql).await {
-            Ok(df) => df,
-            Err(e) => {
-                // Return a clear error message for SQL errors
-                return Ok(ConstraintResult::failure(format!(
-                    "SQL expression error: {e}. Expression: '{}'",
-                    self.expression
-                )));
+        match &self.mode {
+            CustomSqlMode::RowPredicate { threshold } => {
+                self.evaluate_predicate(ctx, table_name, *threshold).await
             }
-        };
-
-        let batches = match df.collect().await {
-            Ok(batches) => batches,
-            Err(e) => {
-                // Return a clear error message for execution errors
-                return Ok(ConstraintResult::failure(format!(
-                    "SQL execution error: {e}. Expression: '{}'",
-                    self.expression
-                )));
+            CustomSqlMode::ScalarQuery { assertion } => {
+                self.evaluate_scalar(ctx, table_name, assertion).await
             }
-        };
-
-        if batches.is_empty() {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        let batch = &batches[0];
-        if batch.num_rows() == 0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        // Extract results
-        let satisfied = batch
-            .column(0)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract satisfied count".to_string()))?
-            .value(0) as f64;
-
-        let total = batch
-            .column(1)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract total count".to_string()))?
-            .value(0) as f64;
-
-        if total == 0.0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        let satisfaction_ratio = satisfied / total;
-
-        if satisfaction_ratio == 1.0 {
-            Ok(ConstraintResult::success_with_metric(satisfaction_ratio))
-        } else {
-            let failed_count = total - satisfied;
-            let message = if let Some(hint) = &self.hint {
-                format!("{hint} ({} rows failed the condition)", failed_count as i64)
-            } else {
-                format!(
-                    "Custom SQL condition not satisfied for {} rows. Expression: '{}'",
-                    failed_count as i64, self.expression
-                )
-            };
-
-            Ok(ConstraintResult::failure_with_metric(
-                satisfaction_ratio,
-                message,
-            ))
+            CustomSqlMode::BooleanScalar => self.evaluate_boolean(ctx, table_name).await,
         }
     }
 
@@ -285,15 +530,70 @@ impl Constraint for CustomSqlConstraint {
         "custom_sql"
     }
 
+    // A value computed over a sample says nothing about the full table
+    fn requires_full_data(&self) -> bool {
+        !matches!(self.mode, CustomSqlMode::RowPredicate { .. })
+    }
+
+    fn describe(&self) -> String {
+        match &self.mode {
+            CustomSqlMode::RowPredicate { threshold } if *threshold >= 1.0 => format!(
+                "every row must satisfy the SQL expression: {}",
+                self.expression
+            ),
+            CustomSqlMode::RowPredicate { threshold } => format!(
+                "at least {} of rows must satisfy the SQL expression: {}",
+                describe_percent(*threshold),
+                self.expression
+            ),
+            CustomSqlMode::ScalarQuery { assertion } => format!(
+                "the value returned by the SQL query must be {}: {}",
+                assertion.requirement(),
+                self.expression
+            ),
+            CustomSqlMode::BooleanScalar => {
+                format!("the SQL query must return true: {}", self.expression)
+            }
+        }
+    }
+
     fn metadata(&self) -> ConstraintMetadata {
-        let mut metadata = ConstraintMetadata::new()
-            .with_description(format!(
+        let description = match &self.mode {
+            CustomSqlMode::RowPredicate { threshold } if *threshold >= 1.0 => format!(
                 "Checks that all rows satisfy the SQL expression: {}",
                 self.expression
-            ))
+            ),
+            CustomSqlMode::RowPredicate { threshold } => format!(
+                "Checks that at least {:.1}% of rows satisfy the SQL expression: {}",
+                threshold * 100.0,
+                self.expression
+            ),
+            CustomSqlMode::ScalarQuery { assertion } => format!(
+                "Checks that the value returned by the SQL query {assertion}: {}",
+                self.expression
+            ),
+            CustomSqlMode::BooleanScalar => {
+                format!(
+                    "Checks that the SQL query returns true: {}",
+                    self.expression
+                )
+            }
+        };
+        let mut metadata = ConstraintMetadata::new()
+            .with_description(description)
             .with_custom("expression", self.expression.clone())
+            .with_custom("mode", self.mode.to_string())
             .with_custom("constraint_type", "custom");
 
+        match &self.mode {
+            CustomSqlMode::RowPredicate { threshold } => {
+                metadata = metadata.with_custom("threshold", threshold.to_string());
+            }
+            CustomSqlMode::ScalarQuery { assertion } => {
+                metadata = metadata.with_custom("assertion", assertion.to_string());
+            }
+            CustomSqlMode::BooleanScalar => {}
+        }
         if let Some(hint) = &self.hint {
             metadata = metadata.with_custom("hint", hint.clone());
         }
@@ -485,6 +785,30 @@ mod tests {
             .contains("SQL expression error"));
     }
 
+    #[tokio::test]
+    async fn test_custom_sql_unknown_function() {
+        let ctx = create_test_context().await;
+
+        let constraint =
+            CustomSqlConstraint::new("LOWER(status) = 'active'", None::<String>).unwrap();
+        evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+
+        let constraint = CustomSqlConstraint::new("vin_checksum(status)", None::<String>).unwrap();
+        let err = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                err.downcast_ref::<TermError>(),
+                Some(TermError::SecurityError(_))
+            ),
+            "{err}"
+        );
+        assert!(err.to_string().contains("unknown function 'vin_checksum'"));
+    }
+
     #[test]
     fn test_new_returns_error_on_dangerous_sql_new() {
         let result = CustomSqlConstraint::new("DROP TABLE data", None::<String>);
@@ -504,4 +828,128 @@ mod tests {
             .to_string()
             .contains("forbidden operation: DELETE"));
     }
+
+    #[tokio::test]
+    async fn test_row_predicate_threshold() {
+        let ctx = create_test_context().await;
+
+        let constraint = CustomSqlConstraint::with_mode(
+            "quantity > 0",
+            CustomSqlMode::RowPredicate { threshold: 0.8 },
+            None::<String>,
+        )
+        .unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.8));
+
+        assert!(CustomSqlConstraint::with_mode(
+            "quantity > 0",
+            CustomSqlMode::RowPredicate { threshold: 80.0 },
+            None::<String>,
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scalar_query() {
+        let ctx = create_test_context().await;
+
+        let constraint = CustomSqlConstraint::scalar_query(
+            "SELECT count(*) FROM (SELECT status FROM {table} GROUP BY status HAVING count(*) > 1)",
+            Assertion::Equals(1.0),
+        )
+        .unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(1.0));
+        assert_eq!(
+            result.message.as_deref(),
+            Some("Query returned 1 (mode: scalar query, value: 1)")
+        );
+
+        let constraint = CustomSqlConstraint::scalar_query(
+            "SELECT sum(quantity) FROM data",
+            Assertion::LessThan(30.0),
+        )
+        .unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(50.0));
+        assert_eq!(
+            result.message.as_deref(),
+            Some(
+                "Query result is 50, expected < 30 (20 above) (mode: scalar query). \
+                 Query: 'SELECT sum(quantity) FROM data'"
+            )
+        );
+
+        // Queries must return exactly one value
+        let constraint = CustomSqlConstraint::scalar_query(
+            "SELECT price FROM {table}",
+            Assertion::GreaterThan(0.0),
+        )
+        .unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert!(result
+            .message
+            .unwrap()
+            .contains("must return a single value, returned 5 rows of 1 columns"));
+    }
+
+    #[tokio::test]
+    async fn test_boolean_scalar() {
+        let ctx = create_test_context().await;
+
+        let constraint =
+            CustomSqlConstraint::boolean_scalar("SELECT min(quantity) >= 0 FROM {table}").unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(1.0));
+
+        let constraint =
+            CustomSqlConstraint::boolean_scalar("SELECT max(price) < 50 FROM {table}").unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(0.0));
+        assert!(result
+            .message
+            .unwrap()
+            .contains("Query returned false (mode: boolean scalar, value: false)"));
+    }
+
+    #[test]
+    fn test_scalar_queries_reject_statements_other_than_select() {
+        for sql in [
+            "DELETE FROM {table}",
+            "SELECT 1; DROP TABLE data",
+            "CREATE TABLE copy AS SELECT * FROM data",
+            "UPDATE data SET price = 0",
+        ] {
+            assert!(
+                CustomSqlConstraint::scalar_query(sql, Assertion::Equals(0.0)).is_err(),
+                "{sql}"
+            );
+            assert!(CustomSqlConstraint::boolean_scalar(sql).is_err(), "{sql}");
+        }
+        // Full queries may use keywords forbidden in row predicates
+        assert!(CustomSqlConstraint::scalar_query(
+            "WITH recent AS (SELECT * FROM {table}) SELECT count(*) FROM recent",
+            Assertion::GreaterThan(0.0)
+        )
+        .is_ok());
+    }
 }
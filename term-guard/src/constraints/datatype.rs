@@ -7,8 +7,11 @@
 //!
 //! And adds support for more complex type validations.
 
+use crate::analyzers::inference::{string_values, TypeInferenceEngine};
 use crate::core::{
-    current_validation_context, Constraint, ConstraintMetadata, ConstraintResult, ConstraintStatus,
+    collect_with_metrics, current_validation_context, describe_rows, skipped_without_values,
+    ColumnStatistics, Constraint, ConstraintMetadata, ConstraintResult, ConstraintStatus,
+    QualityDimension,
 };
 use crate::prelude::*;
 use crate::security::SqlSecurity;
@@ -16,6 +19,7 @@ use arrow::array::Array;
 use async_trait::async_trait;
 use datafusion::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use tracing::instrument;
 /// Types of data type validation that can be performed.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -236,6 +240,8 @@ pub struct DataTypeConstraint {
     column: String,
     /// The type of validation to perform
     validation: DataTypeValidation,
+    /// Optional inference engine used for consistency checks
+    inference_engine: Option<Arc<TypeInferenceEngine>>,
 }
 
 impl DataTypeConstraint {
@@ -265,9 +271,21 @@ impl DataTypeConstraint {
         Ok(Self {
             column: column_str,
             validation,
+            inference_engine: None,
         })
     }
 
+    /// Uses a [`TypeInferenceEngine`] to measure type consistency.
+    ///
+    /// With an engine, [`DataTypeValidation::Consistency`] samples up to the engine's
+    /// sample size of non-null values, infers the column type with the engine's locale
+    /// and custom detectors, and reports the fraction of values compatible with it.
+    /// Share the engine with the `ColumnProfiler` so inference and validation agree.
+    pub fn with_inference_engine(mut self, engine: Arc<TypeInferenceEngine>) -> Self {
+        self.inference_engine = Some(engine);
+        self
+    }
+
     /// Convenience constructor for non-negative constraint.
     pub fn non_negative(column: impl Into<String>) -> Result<Self> {
         Self::new(
@@ -285,6 +303,55 @@ impl DataTypeConstraint {
     pub fn specific_type(column: impl Into<String>, data_type: impl Into<String>) -> Result<Self> {
         Self::new(column, DataTypeValidation::SpecificType(data_type.into()))
     }
+
+    /// Measures the fraction of sampled values compatible with the engine's inferred type.
+    async fn evaluate_inferred_consistency(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        engine: &TypeInferenceEngine,
+        threshold: f64,
+    ) -> Result<ConstraintResult> {
+        let column = SqlSecurity::escape_identifier(&self.column)?;
+        let sql = format!(
+            "SELECT {column} FROM {table_name} WHERE {column} IS NOT NULL LIMIT {}",
+            engine.config().sample_size
+        );
+
+        let df = ctx.sql(&sql).await?;
+        let batches = collect_with_metrics(df).await?;
+        let values = string_values(&batches)?;
+
+        if values.is_empty() {
+            return skipped_without_values(ctx, table_name, "No data to validate").await;
+        }
+
+        let samples: Vec<Option<String>> = values.iter().cloned().map(Some).collect();
+        let inferred = engine.infer_from_values(&samples).inferred_type;
+        let matching = values
+            .iter()
+            .filter(|value| engine.value_matches(value, &inferred))
+            .count();
+        let consistency = matching as f64 / values.len() as f64;
+
+        Ok(ConstraintResult {
+            status: if consistency >= threshold {
+                ConstraintStatus::Success
+            } else {
+                ConstraintStatus::Failure
+            },
+            message: Some(format!(
+                "{:.1}% of values match inferred type {} (threshold {:.1}%)",
+                consistency * 100.0,
+                inferred.label(),
+                threshold * 100.0
+            )),
+            metric: Some(consistency),
+            quarantine: None,
+            constraint_id: None,
+            details: Default::default(),
+        })
+    }
 }
 
 #[async_trait]
@@ -320,6 +387,9 @@ impl Constraint for DataTypeConstraint {
                             self.column
                         )),
                         metric: Some(1.0),
+                        quarantine: None,
+                        constraint_id: None,
+                        details: Default::default(),
                     })
                 } else {
                     Ok(ConstraintResult {
@@ -329,10 +399,19 @@ impl Constraint for DataTypeConstraint {
                             self.column
                         )),
                         metric: Some(0.0),
+                        quarantine: None,
+                        constraint_id: None,
+                        details: Default::default(),
                     })
                 }
             }
             DataTypeValidation::Consistency { threshold } => {
+                if let Some(engine) = &self.inference_engine {
+                    return self
+                        .evaluate_inferred_consistency(ctx, table_name, engine, *threshold)
+                        .await;
+                }
+
                 // For type consistency, we need to analyze the actual values
                 // DataFusion doesn't have typeof() function, so we'll check if all values
                 // have consistent formatting/structure
@@ -344,16 +423,31 @@ impl Constraint for DataTypeConstraint {
                 );
 
                 let df = ctx.sql(&sql).await?;
-                let batches = df.collect().await?;
+                let batches = collect_with_metrics(df).await?;
 
                 if batches.is_empty() || batches[0].num_rows() == 0 {
                     return Ok(ConstraintResult {
                         status: ConstraintStatus::Skipped,
                         message: Some("No data to validate".to_string()),
                         metric: None,
+                        quarantine: None,
+                        constraint_id: None,
+                        details: Default::default(),
                     });
                 }
 
+                let total = batches[0]
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<arrow::array::Int64Array>()
+                    .ok_or_else(|| {
+                        TermError::Internal("Failed to extract total count".to_string())
+                    })?
+                    .value(0);
+                if total == 0 {
+                    return skipped_without_values(ctx, table_name, "No data to validate").await;
+                }
+
                 // For now, assume consistency is high (would need actual implementation)
                 // In a real implementation, we'd analyze value patterns, formats, etc.
                 let consistency = 0.95; // Placeholder
@@ -367,6 +461,9 @@ impl Constraint for DataTypeConstraint {
                             threshold * 100.0
                         )),
                         metric: Some(consistency),
+                        quarantine: None,
+                        constraint_id: None,
+                        details: Default::default(),
                     })
                 } else {
                     Ok(ConstraintResult {
@@ -377,6 +474,9 @@ impl Constraint for DataTypeConstraint {
                             threshold * 100.0
                         )),
                         metric: Some(consistency),
+                        quarantine: None,
+                        constraint_id: None,
+                        details: Default::default(),
                     })
                 }
             }
@@ -393,13 +493,16 @@ impl Constraint for DataTypeConstraint {
                 );
 
                 let df = ctx.sql(&sql).await?;
-                let batches = df.collect().await?;
+                let batches = collect_with_metrics(df).await?;
 
                 if batches.is_empty() || batches[0].num_rows() == 0 {
                     return Ok(ConstraintResult {
                         status: ConstraintStatus::Skipped,
                         message: Some("No data to validate".to_string()),
                         metric: None,
+                        quarantine: None,
+                        constraint_id: None,
+                        details: Default::default(),
                     });
                 }
 
@@ -421,6 +524,11 @@ impl Constraint for DataTypeConstraint {
                     })?
                     .value(0);
 
+                if total == 0 {
+                    return skipped_without_values(ctx, table_name, "No non-null data to validate")
+                        .await;
+                }
+
                 let validity_rate = valid as f64 / total as f64;
 
                 Ok(ConstraintResult {
@@ -435,6 +543,9 @@ impl Constraint for DataTypeConstraint {
                         self.validation.description()
                     )),
                     metric: Some(validity_rate),
+                    quarantine: None,
+                    constraint_id: None,
+                    details: Default::default(),
                 })
             }
         }
@@ -444,6 +555,88 @@ impl Constraint for DataTypeConstraint {
         "datatype"
     }
 
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Validity)
+    }
+
+    fn column(&self) -> Option<&str> {
+        Some(&self.column)
+    }
+
+    fn evaluate_from_statistics(&self, statistics: &ColumnStatistics) -> Option<ConstraintResult> {
+        // Engine-based consistency uses its own sampling and type rules
+        let DataTypeValidation::Consistency { threshold } = &self.validation else {
+            return None;
+        };
+        if self.inference_engine.is_some() {
+            return None;
+        }
+        let consistency = statistics.type_consistency?;
+        if statistics.row_count == 0 {
+            return Some(ConstraintResult::empty_table());
+        }
+        if statistics.non_null_count() == 0 {
+            return Some(ConstraintResult::skipped("No data to validate"));
+        }
+
+        let status = if consistency >= *threshold {
+            ConstraintStatus::Success
+        } else {
+            ConstraintStatus::Failure
+        };
+        Some(ConstraintResult {
+            status,
+            message: Some(format!(
+                "Type consistency {:.1}% {} threshold {:.1}%",
+                consistency * 100.0,
+                if status.is_success() {
+                    "meets"
+                } else {
+                    "below"
+                },
+                threshold * 100.0
+            )),
+            metric: Some(consistency),
+            quarantine: None,
+            constraint_id: None,
+            details: Default::default(),
+        })
+    }
+
+    fn describe(&self) -> String {
+        let requirement = match &self.validation {
+            DataTypeValidation::SpecificType(data_type) => format!("be of type {data_type}"),
+            DataTypeValidation::Consistency { threshold } => {
+                format!("have a consistent data type {}", describe_rows(*threshold))
+            }
+            DataTypeValidation::Numeric(validation) => match validation {
+                NumericValidation::NonNegative => "be non-negative".to_string(),
+                NumericValidation::Positive => "be positive".to_string(),
+                NumericValidation::Integer => "be an integer".to_string(),
+                NumericValidation::Range { min, max } => format!("be between {min} and {max}"),
+                NumericValidation::Finite => "be finite".to_string(),
+            },
+            DataTypeValidation::String(validation) => match validation {
+                StringTypeValidation::NotEmpty => "not be empty".to_string(),
+                StringTypeValidation::ValidUtf8 => "be valid UTF-8".to_string(),
+                StringTypeValidation::NotBlank => "not be blank".to_string(),
+                StringTypeValidation::MaxBytes(n) => format!("be at most {n} bytes long"),
+            },
+            DataTypeValidation::Temporal(validation) => match validation {
+                TemporalValidation::PastDate => "be a date in the past".to_string(),
+                TemporalValidation::FutureDate => "be a date in the future".to_string(),
+                TemporalValidation::DateRange { start, end } => {
+                    format!("be a date between {start} and {end}")
+                }
+                TemporalValidation::ValidTimezone => "be a valid time zone".to_string(),
+            },
+            DataTypeValidation::Custom { sql_predicate } => {
+                format!("satisfy the SQL predicate: {sql_predicate}")
+            }
+        };
+        format!("{} must {requirement}", self.column)
+    }
+
     fn metadata(&self) -> ConstraintMetadata {
         ConstraintMetadata::for_column(&self.column).with_description(format!(
             "Validates {} for column '{}'",
@@ -621,4 +814,44 @@ mod tests {
         // 3 out of 4 non-null values are not empty (empty string counts as empty)
         assert!((result.metric.unwrap() - 0.75).abs() < 0.01);
     }
+
+    #[tokio::test]
+    async fn test_consistency_with_inference_engine() {
+        use crate::analyzers::inference::{InferenceLocale, TypeInferenceEngine};
+
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "amount",
+            DataType::Utf8,
+            true,
+        )]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(StringArray::from(vec![
+                Some("1.234,56"),
+                Some("99,90"),
+                Some("0,5"),
+                None,
+                Some("n/a"),
+            ]))],
+        )
+        .unwrap();
+        let ctx = create_test_context(batch).await;
+
+        let engine = Arc::new(
+            TypeInferenceEngine::builder()
+                .locale(InferenceLocale::european())
+                .confidence_threshold(0.7)
+                .build(),
+        );
+        let constraint = DataTypeConstraint::type_consistency("amount", 0.7)
+            .unwrap()
+            .with_inference_engine(engine);
+
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.75));
+        assert!(result.message.unwrap().contains("Decimal"));
+    }
 }
@@ -0,0 +1,441 @@
+//! Distribution stability validation for Term.
+//!
+//! [`DistributionStabilityConstraint`] runs a chi-squared goodness-of-fit test of the
+//! current categorical distribution of a column against a reference distribution, and
+//! fails when the p-value drops below the significance level `alpha`. The p-value is
+//! reported as the constraint metric.
+//!
+//! The reference is either provided directly, for example from a [`Histogram`] of an
+//! earlier snapshot, or loaded from a [`MetricsRepository`] by metric key. Bins with
+//! an expected frequency below five are pooled, see [`ChiSquaredTest::compute`].
+//! Failure messages list the categories contributing most to the statistic.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use term_guard::constraints::DistributionStabilityConstraint;
+//! use term_guard::core::{Check, Level};
+//! use term_guard::repository::InMemoryRepository;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let repository = Arc::new(InMemoryRepository::new());
+//!
+//! let check = Check::builder("payment_mix")
+//!     .level(Level::Warning)
+//!     .with_constraint(
+//!         DistributionStabilityConstraint::from_repository(
+//!             "payment_method",
+//!             repository,
+//!             "chi_squared.payment_method",
+//!         )
+//!         .with_tag("dataset", "payments")
+//!         .alpha(0.01)?,
+//!     )
+//!     .build();
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::analyzers::advanced::{
+    ChiSquaredAnalyzer, ChiSquaredTest, ReferenceDistribution, DEFAULT_MIN_EXPECTED_FREQUENCY,
+};
+use crate::analyzers::Analyzer;
+use crate::constraints::Histogram;
+use crate::core::{
+    current_validation_context, skipped_without_values, Constraint, ConstraintMetadata,
+    ConstraintResult,
+};
+use crate::error::{Result, TermError};
+use crate::repository::{MetricsRepository, SortOrder};
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use tracing::{debug, instrument, warn};
+
+/// Default significance level of the test.
+const DEFAULT_ALPHA: f64 = 0.05;
+
+/// Number of bins described in failure messages.
+const REPORTED_CONTRIBUTORS: usize = 3;
+
+/// Where the reference distribution comes from.
+#[derive(Clone)]
+enum ReferenceSource {
+    Distribution(ReferenceDistribution),
+    Repository {
+        repository: Arc<dyn MetricsRepository>,
+        metric_key: String,
+        tags: HashMap<String, String>,
+    },
+}
+
+/// A constraint that tests a column's categorical distribution against a reference
+/// with a chi-squared goodness-of-fit test.
+///
+/// The constraint is skipped when no reference is found or when fewer than two bins
+/// remain after pooling, since the test is then undefined.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::analyzers::advanced::ReferenceDistribution;
+/// use term_guard::constraints::DistributionStabilityConstraint;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let reference = ReferenceDistribution::from_frequencies([
+///     ("card", 700.0),
+///     ("cash", 250.0),
+///     ("voucher", 50.0),
+/// ])?;
+/// let constraint = DistributionStabilityConstraint::new("payment_method", reference).alpha(0.01)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct DistributionStabilityConstraint {
+    column: String,
+    reference: ReferenceSource,
+    alpha: f64,
+    min_expected_frequency: f64,
+}
+
+impl DistributionStabilityConstraint {
+    /// Creates a constraint testing `column` against the given reference distribution.
+    pub fn new(column: impl Into<String>, reference: ReferenceDistribution) -> Self {
+        Self::with_source(column, ReferenceSource::Distribution(reference))
+    }
+
+    /// Creates a constraint using the buckets of a categorical [`Histogram`] as reference.
+    pub fn from_histogram(column: impl Into<String>, histogram: &Histogram) -> Result<Self> {
+        Ok(Self::new(
+            column,
+            ReferenceDistribution::from_histogram(histogram)?,
+        ))
+    }
+
+    /// Creates a constraint that loads the reference from `repository`.
+    ///
+    /// The most recent result holding `metric_key` is used. The metric can be a map of
+    /// category frequencies, as written by [`ReferenceDistribution::to_metric`], or the
+    /// metric of a [`ChiSquaredAnalyzer`], whose observed frequencies become the reference.
+    pub fn from_repository(
+        column: impl Into<String>,
+        repository: Arc<dyn MetricsRepository>,
+        metric_key: impl Into<String>,
+    ) -> Self {
+        Self::with_source(
+            column,
+            ReferenceSource::Repository {
+                repository,
+                metric_key: metric_key.into(),
+                tags: HashMap::new(),
+            },
+        )
+    }
+
+    fn with_source(column: impl Into<String>, reference: ReferenceSource) -> Self {
+        Self {
+            column: column.into(),
+            reference,
+            alpha: DEFAULT_ALPHA,
+            min_expected_frequency: DEFAULT_MIN_EXPECTED_FREQUENCY,
+        }
+    }
+
+    /// Adds a tag the repository result holding the reference must match.
+    ///
+    /// Has no effect when the reference is provided directly.
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        if let ReferenceSource::Repository { tags, .. } = &mut self.reference {
+            tags.insert(key.into(), value.into());
+        }
+        self
+    }
+
+    /// Sets the significance level: the constraint fails when the p-value is below it.
+    ///
+    /// Defaults to 0.05. Must be strictly between 0 and 1.
+    pub fn alpha(mut self, alpha: f64) -> Result<Self> {
+        if !(alpha > 0.0 && alpha < 1.0) {
+            return Err(TermError::Configuration(format!(
+                "Significance level must be between 0 and 1 (exclusive), got {alpha}"
+            )));
+        }
+        self.alpha = alpha;
+        Ok(self)
+    }
+
+    /// Sets the minimum expected frequency below which bins are pooled (default 5).
+    pub fn min_expected_frequency(mut self, min_expected_frequency: f64) -> Self {
+        self.min_expected_frequency = min_expected_frequency.max(0.0);
+        self
+    }
+
+    /// Returns the significance level.
+    pub fn significance_level(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Loads the reference distribution, `None` when the repository holds none.
+    async fn load_reference(&self) -> Result<Option<ReferenceDistribution>> {
+        let (repository, metric_key, tags) = match &self.reference {
+            ReferenceSource::Distribution(reference) => return Ok(Some(reference.clone())),
+            ReferenceSource::Repository {
+                repository,
+                metric_key,
+                tags,
+            } => (repository, metric_key, tags),
+        };
+
+        let results = repository
+            .load()
+            .await
+            .with_tags(tags.clone())
+            .sort(SortOrder::Descending)
+            .execute()
+            .await?;
+
+        // Results saved by other analyzers under the same tags do not hold the metric
+        results
+            .iter()
+            .find_map(|(_, context)| context.get_metric(metric_key))
+            .map(|metric| ReferenceDistribution::from_metric(metric).map_err(TermError::from))
+            .transpose()
+    }
+
+    /// Describes a failed test with the bins contributing most to the statistic.
+    fn failure_message(&self, test: &ChiSquaredTest) -> String {
+        let contributors = test
+            .top_contributors(REPORTED_CONTRIBUTORS)
+            .iter()
+            .map(|bin| {
+                format!(
+                    "{}: observed {}, expected {:.1} (contribution {:.3})",
+                    bin.label(),
+                    bin.observed,
+                    bin.expected,
+                    bin.contribution
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        format!(
+            "Distribution of '{}' differs from the reference: chi-squared {:.3} with {} degrees of freedom, p-value {:.4} < alpha {}. Top contributors: {contributors}",
+            self.column, test.statistic, test.degrees_of_freedom, test.p_value, self.alpha
+        )
+    }
+}
+
+impl fmt::Debug for DistributionStabilityConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("DistributionStabilityConstraint");
+        debug.field("column", &self.column);
+        match &self.reference {
+            ReferenceSource::Distribution(reference) => debug.field("reference", reference),
+            ReferenceSource::Repository {
+                metric_key, tags, ..
+            } => debug
+                .field("metric_key", metric_key)
+                // Sorted so the output, and with it the constraint identity, is stable
+                .field("tags", &tags.iter().collect::<BTreeMap<_, _>>()),
+        };
+        debug
+            .field("alpha", &self.alpha)
+            .field("min_expected_frequency", &self.min_expected_frequency)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Constraint for DistributionStabilityConstraint {
+    #[instrument(skip(self, ctx), fields(constraint = "distribution_stability", column = %self.column))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let Some(reference) = self.load_reference().await? else {
+            return Ok(ConstraintResult::skipped(format!(
+                "No reference distribution found for '{}'",
+                self.column
+            )));
+        };
+
+        let analyzer = ChiSquaredAnalyzer::new(&self.column, reference)
+            .with_min_expected_frequency(self.min_expected_frequency);
+        let state = analyzer.compute_state_from_data(ctx).await?;
+
+        let Some(test) = analyzer.test(&state) else {
+            let message = format!(
+                "Not enough data in '{}' for a chi-squared test: fewer than two bins with an expected frequency of at least {}",
+                self.column, self.min_expected_frequency
+            );
+            let validation_ctx = current_validation_context();
+            return skipped_without_values(ctx, validation_ctx.table_name(), &message).await;
+        };
+
+        debug!(
+            statistic = test.statistic,
+            degrees_of_freedom = test.degrees_of_freedom,
+            p_value = test.p_value,
+            "Computed chi-squared test"
+        );
+
+        if test.p_value < self.alpha {
+            let message = self.failure_message(&test);
+            warn!("{message}");
+            Ok(ConstraintResult::failure_with_metric(test.p_value, message))
+        } else {
+            Ok(ConstraintResult::success_with_metric(test.p_value))
+        }
+    }
+
+    fn name(&self) -> &str {
+        "distribution_stability"
+    }
+
+    fn column(&self) -> Option<&str> {
+        Some(&self.column)
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        ConstraintMetadata::for_column(&self.column)
+            .with_description(format!(
+                "Checks that the distribution of '{}' matches the reference (chi-squared test)",
+                self.column
+            ))
+            .with_custom("constraint_type", "categorical")
+            .with_custom("alpha", self.alpha.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::AnalyzerContext;
+    use crate::constraints::HistogramBucket;
+    use crate::core::ConstraintStatus;
+    use crate::repository::{InMemoryRepository, ResultKey};
+
+    /// 100 rows with the given category counts.
+    async fn context(counts: &[(&str, usize)]) -> SessionContext {
+        let values = counts
+            .iter()
+            .flat_map(|(category, count)| std::iter::repeat(format!("('{category}')")).take(*count))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let ctx = SessionContext::new();
+        ctx.sql(&format!(
+            "CREATE TABLE data (method VARCHAR) AS VALUES {values}"
+        ))
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+        ctx
+    }
+
+    fn reference() -> ReferenceDistribution {
+        ReferenceDistribution::from_frequencies([("card", 60.0), ("cash", 30.0), ("voucher", 10.0)])
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stable_distribution_passes() {
+        let ctx = context(&[("card", 58), ("cash", 32), ("voucher", 10)]).await;
+        let result = DistributionStabilityConstraint::new("method", reference())
+            .evaluate(&ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert!(result.metric.unwrap() > 0.05);
+    }
+
+    #[tokio::test]
+    async fn test_shifted_distribution_fails_with_contributors() {
+        let ctx = context(&[("card", 30), ("cash", 60), ("voucher", 10)]).await;
+        let result = DistributionStabilityConstraint::new("method", reference())
+            .evaluate(&ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert!(result.metric.unwrap() < 1e-6);
+        let message = result.message.unwrap();
+        // cash contributes (60 - 30)² / 30 = 30, card (30 - 60)² / 60 = 15
+        assert!(
+            message.contains(
+                "Top contributors: cash: observed 60, expected 30.0 (contribution 30.000); card: observed 30, expected 60.0 (contribution 15.000)"
+            ),
+            "{message}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reference_from_histogram() {
+        let histogram = Histogram::new(
+            vec![
+                HistogramBucket {
+                    value: "card".to_string(),
+                    count: 600,
+                    ratio: 0.6,
+                },
+                HistogramBucket {
+                    value: "cash".to_string(),
+                    count: 400,
+                    ratio: 0.4,
+                },
+            ],
+            1000,
+            0,
+        );
+        let ctx = context(&[("card", 60), ("cash", 40)]).await;
+        let result = DistributionStabilityConstraint::from_histogram("method", &histogram)
+            .unwrap()
+            .evaluate(&ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_reference_from_repository() {
+        let repository = Arc::new(InMemoryRepository::new());
+        let constraint = DistributionStabilityConstraint::from_repository(
+            "method",
+            repository.clone(),
+            "reference.method",
+        )
+        .with_tag("dataset", "payments");
+        let ctx = context(&[("card", 30), ("cash", 60), ("voucher", 10)]).await;
+
+        let result = constraint.evaluate(&ctx).await.unwrap();
+        assert_eq!(result.status, ConstraintStatus::Skipped);
+
+        let mut stored = AnalyzerContext::new();
+        stored.store_metric("reference.method", reference().to_metric());
+        repository
+            .save(
+                ResultKey::new(1_000).with_tag("dataset", "payments"),
+                stored,
+            )
+            .await
+            .unwrap();
+
+        let result = constraint.evaluate(&ctx).await.unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+    }
+
+    #[test]
+    fn test_alpha_validation() {
+        let constraint = DistributionStabilityConstraint::new("method", reference());
+        assert_eq!(constraint.significance_level(), 0.05);
+        assert!(constraint.clone().alpha(0.0).is_err());
+        assert!(constraint.clone().alpha(1.0).is_err());
+        assert_eq!(constraint.alpha(0.01).unwrap().significance_level(), 0.01);
+    }
+}
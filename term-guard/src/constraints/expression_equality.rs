@@ -0,0 +1,623 @@
+//! Verification of derived columns against the expression that produces them.
+//!
+//! [`ExpressionEqualityConstraint`] recomputes a SQL expression for every row and
+//! compares it with the value stored in a column, for example a materialized
+//! `total = price * quantity + tax`. Unlike a boolean
+//! [`CustomSqlConstraint`](super::CustomSqlConstraint), failures report how far
+//! off the stored values are: the number of violating rows, the largest absolute
+//! difference and a few example rows with their stored and computed values.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::constraints::{EqualityTolerance, ExpressionEqualityConstraint};
+//! use term_guard::core::Check;
+//!
+//! # fn example() -> term_guard::prelude::Result<()> {
+//! // Totals may be off by a cent, or by 0.1% for large orders
+//! let constraint = ExpressionEqualityConstraint::new(
+//!     "total",
+//!     "price * quantity + tax",
+//!     EqualityTolerance::absolute(0.01).with_relative(0.001),
+//! )?
+//! .threshold(0.999)
+//! .key_columns(vec!["order_id"]);
+//!
+//! let check = Check::builder("derived_columns")
+//!     .constraint(constraint)
+//!     .build();
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+
+use crate::core::{
+    collect_limited, collect_with_metrics, current_validation_context, CollectionSummary,
+    Constraint, ConstraintMetadata, ConstraintResult, ConstraintStatus, QualityDimension,
+};
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+use arrow::array::{Array, Float64Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt;
+use tracing::{debug, instrument, warn};
+
+/// How far a stored value may be from the computed one.
+///
+/// A row conforms when the absolute difference is within the absolute tolerance
+/// or within the relative tolerance times the magnitude of the computed value,
+/// whichever is larger.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EqualityTolerance {
+    /// Maximum absolute difference
+    pub absolute: f64,
+    /// Maximum difference as a fraction of the computed value
+    pub relative: f64,
+}
+
+impl EqualityTolerance {
+    /// Requires stored and computed values to be equal.
+    pub fn exact() -> Self {
+        Self::default()
+    }
+
+    /// Allows an absolute difference of up to `tolerance`.
+    pub fn absolute(tolerance: f64) -> Self {
+        Self {
+            absolute: tolerance.abs(),
+            relative: 0.0,
+        }
+    }
+
+    /// Allows a difference of up to `tolerance` times the computed value.
+    pub fn relative(tolerance: f64) -> Self {
+        Self {
+            absolute: 0.0,
+            relative: tolerance.abs(),
+        }
+    }
+
+    /// Also allows a difference of up to `tolerance` times the computed value.
+    pub fn with_relative(mut self, tolerance: f64) -> Self {
+        self.relative = tolerance.abs();
+        self
+    }
+}
+
+impl fmt::Display for EqualityTolerance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.absolute > 0.0, self.relative > 0.0) {
+            (false, false) => write!(f, "exact"),
+            (true, false) => write!(f, "±{}", self.absolute),
+            (false, true) => write!(f, "±{}%", self.relative * 100.0),
+            (true, true) => write!(f, "±{} or ±{}%", self.absolute, self.relative * 100.0),
+        }
+    }
+}
+
+/// A row whose stored value does not match the computed expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpressionMismatch {
+    /// Values of the key columns as `(column, value)` pairs
+    pub key: Vec<(String, Option<String>)>,
+    /// The value stored in the column
+    pub stored: Option<f64>,
+    /// The value of the expression
+    pub computed: Option<f64>,
+}
+
+impl ExpressionMismatch {
+    /// Returns the absolute difference, or `None` if either side is NULL.
+    pub fn difference(&self) -> Option<f64> {
+        Some((self.stored? - self.computed?).abs())
+    }
+}
+
+impl fmt::Display for ExpressionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = |v: Option<f64>| v.map_or_else(|| "NULL".to_string(), |v| v.to_string());
+        if !self.key.is_empty() {
+            let key: Vec<String> = self
+                .key
+                .iter()
+                .map(|(column, v)| format!("{column}={}", v.as_deref().unwrap_or("NULL")))
+                .collect();
+            write!(f, "{}: ", key.join(", "))?;
+        }
+        write!(
+            f,
+            "stored {}, computed {}",
+            value(self.stored),
+            value(self.computed)
+        )?;
+        if let Some(difference) = self.difference() {
+            write!(f, " (diff {difference})")?;
+        }
+        Ok(())
+    }
+}
+
+/// A constraint that checks a column against a SQL expression computed per row.
+///
+/// Both sides are compared as `DOUBLE`. Rows where both the stored and the
+/// computed value are NULL conform; rows where only one of them is NULL are
+/// violations. The constraint passes when the fraction of conforming rows is at
+/// least the threshold.
+///
+/// The metric is the fraction of conforming rows.
+#[derive(Debug, Clone)]
+pub struct ExpressionEqualityConstraint {
+    column: String,
+    expression: String,
+    tolerance: EqualityTolerance,
+    threshold: f64,
+    key_columns: Vec<String>,
+    max_examples_reported: usize,
+}
+
+impl ExpressionEqualityConstraint {
+    /// Creates a new expression equality constraint.
+    ///
+    /// By default every row must conform (threshold 1.0) and five example
+    /// violations are reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column holding the stored values
+    /// * `expression` - The SQL expression recomputing the values
+    /// * `tolerance` - How far stored values may be from the computed ones
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the column name is invalid or the expression is rejected
+    /// by [`SqlSecurity::validate_sql_expression`].
+    pub fn new(
+        column: impl Into<String>,
+        expression: impl Into<String>,
+        tolerance: EqualityTolerance,
+    ) -> Result<Self> {
+        let column = column.into();
+        let expression = expression.into();
+        SqlSecurity::validate_identifier(&column)?;
+        SqlSecurity::validate_sql_expression(&expression)?;
+
+        Ok(Self {
+            column,
+            expression,
+            tolerance,
+            threshold: 1.0,
+            key_columns: Vec::new(),
+            max_examples_reported: 5,
+        })
+    }
+
+    /// Sets the minimum fraction of rows that must conform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the threshold is not between 0.0 and 1.0.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&threshold),
+            "Threshold must be between 0.0 and 1.0"
+        );
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets the columns identifying rows in the reported examples.
+    pub fn key_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.key_columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets how many example violations are included in the failure message.
+    pub fn max_examples_reported(mut self, count: usize) -> Self {
+        self.max_examples_reported = count;
+        self
+    }
+
+    /// Builds the query producing the key columns and both values for every row.
+    fn compared_query(&self, table_name: &str) -> Result<String> {
+        let column = SqlSecurity::escape_identifier(&self.column)?;
+        let mut select = Vec::with_capacity(self.key_columns.len() + 2);
+        for (i, key) in self.key_columns.iter().enumerate() {
+            let key = SqlSecurity::escape_identifier(key)?;
+            select.push(format!("CAST({key} AS VARCHAR) AS key_{i}"));
+        }
+        select.push(format!("CAST({column} AS DOUBLE) AS stored_value"));
+        select.push(format!(
+            "CAST(({}) AS DOUBLE) AS computed_value",
+            self.expression
+        ));
+        Ok(format!("SELECT {} FROM {table_name}", select.join(", ")))
+    }
+
+    /// Returns the SQL predicate that is true for conforming rows.
+    fn conforming_predicate(&self) -> String {
+        let EqualityTolerance { absolute, relative } = self.tolerance;
+        format!(
+            "CASE
+                WHEN stored_value IS NULL AND computed_value IS NULL THEN TRUE
+                WHEN stored_value IS NULL OR computed_value IS NULL THEN FALSE
+                ELSE ABS(stored_value - computed_value) <= {absolute}
+                    OR ABS(stored_value - computed_value) <= {relative} * ABS(computed_value)
+             END"
+        )
+    }
+
+    /// Returns violating rows, largest difference first and one-sided NULLs before them.
+    ///
+    /// The table and the redaction of key values are taken from the current
+    /// validation context, as are the [`CollectionLimits`](crate::core::CollectionLimits)
+    /// bounding the rows returned.
+    pub async fn mismatches(
+        &self,
+        ctx: &SessionContext,
+        limit: usize,
+    ) -> Result<Vec<ExpressionMismatch>> {
+        Ok(self.collect_mismatches(ctx, limit).await?.0)
+    }
+
+    async fn collect_mismatches(
+        &self,
+        ctx: &SessionContext,
+        limit: usize,
+    ) -> Result<(Vec<ExpressionMismatch>, CollectionSummary)> {
+        let validation_ctx = current_validation_context();
+        let compared = self.compared_query(validation_ctx.table_name())?;
+        let keys: Vec<String> = (0..self.key_columns.len())
+            .map(|i| format!("key_{i}"))
+            .collect();
+        let order_keys: String = keys.iter().map(|k| format!(", {k}")).collect();
+        let select_keys: String = keys.iter().map(|k| format!("{k}, ")).collect();
+        let sql = format!(
+            "SELECT {select_keys}stored_value, computed_value
+             FROM ({compared}) AS compared
+             WHERE NOT ({})
+             ORDER BY ABS(stored_value - computed_value) DESC NULLS FIRST{order_keys}
+             LIMIT {limit}",
+            self.conforming_predicate()
+        );
+
+        let df = ctx.sql(&sql).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                self.name(),
+                format!("Failed to execute mismatch query: {e}"),
+            )
+        })?;
+        let collected = collect_limited(df).await?;
+
+        let key_count = self.key_columns.len();
+        let mut result = Vec::new();
+        for batch in collected.iter()? {
+            let batch = &batch?;
+            let keys = (0..key_count)
+                .map(|i| string_column(batch, i))
+                .collect::<Result<Vec<_>>>()?;
+            let stored = float_column(batch, key_count)?;
+            let computed = float_column(batch, key_count + 1)?;
+
+            for row in 0..batch.num_rows() {
+                result.push(ExpressionMismatch {
+                    key: self
+                        .key_columns
+                        .iter()
+                        .zip(&keys)
+                        .map(|(column, values)| {
+                            let value = (!values.is_null(row)).then(|| {
+                                validation_ctx
+                                    .redact(column, values.value(row))
+                                    .into_owned()
+                            });
+                            (column.clone(), value)
+                        })
+                        .collect(),
+                    stored: (!stored.is_null(row)).then(|| stored.value(row)),
+                    computed: (!computed.is_null(row)).then(|| computed.value(row)),
+                });
+            }
+        }
+
+        Ok((result, collected.summary()))
+    }
+}
+
+/// Reads a column as strings; `CAST(... AS VARCHAR)` may produce `Utf8View`.
+fn string_column(batch: &RecordBatch, index: usize) -> Result<StringArray> {
+    let array = cast(batch.column(index), &DataType::Utf8)?;
+    array
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .cloned()
+        .ok_or_else(|| TermError::Internal(format!("Expected string column at index {index}")))
+}
+
+fn float_column(batch: &RecordBatch, index: usize) -> Result<&Float64Array> {
+    batch
+        .column(index)
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .ok_or_else(|| TermError::Internal(format!("Expected float column at index {index}")))
+}
+
+#[async_trait]
+impl Constraint for ExpressionEqualityConstraint {
+    #[instrument(skip(self, ctx), fields(
+        column = %self.column,
+        expression = %self.expression,
+        threshold = %self.threshold
+    ))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let validation_ctx = current_validation_context();
+        let compared = self.compared_query(validation_ctx.table_name())?;
+
+        let sql = format!(
+            "SELECT
+                COUNT(*) AS row_count,
+                COALESCE(SUM(CASE WHEN {} THEN 1 ELSE 0 END), 0) AS conforming_count,
+                MAX(ABS(stored_value - computed_value)) AS max_difference
+             FROM ({compared}) AS compared",
+            self.conforming_predicate()
+        );
+        debug!("Generated expression equality query: {}", sql);
+
+        let df = ctx.sql(&sql).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                self.name(),
+                format!("Failed to execute expression equality query: {e}"),
+            )
+        })?;
+        let batches = collect_with_metrics(df).await?;
+
+        if batches.is_empty() || batches[0].num_rows() == 0 {
+            return Ok(ConstraintResult::skipped("No data to validate"));
+        }
+
+        let batch = &batches[0];
+        let row_count = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .ok_or_else(|| TermError::Internal("Failed to extract row count".to_string()))?
+            .value(0);
+        if row_count == 0 {
+            return Ok(ConstraintResult::empty_table());
+        }
+
+        let conforming_count = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .ok_or_else(|| TermError::Internal("Failed to extract conforming count".to_string()))?
+            .value(0);
+        let max_difference = float_column(batch, 2)?;
+        let max_difference = (!max_difference.is_null(0)).then(|| max_difference.value(0));
+
+        let ratio = conforming_count as f64 / row_count as f64;
+        if ratio >= self.threshold {
+            return Ok(ConstraintResult::success_with_metric(ratio));
+        }
+
+        let violations = row_count - conforming_count;
+        let mut message = format!(
+            "Found {violations} of {row_count} rows where '{}' differs from '{}' beyond tolerance {} ({:.2}% conforming, expected at least {:.2}%)",
+            self.column,
+            self.expression,
+            self.tolerance,
+            ratio * 100.0,
+            self.threshold * 100.0
+        );
+        if let Some(max_difference) = max_difference {
+            message.push_str(&format!("; max absolute difference {max_difference}"));
+        }
+
+        let mut details = BTreeMap::new();
+        if self.max_examples_reported > 0 {
+            let (examples, collection) = self
+                .collect_mismatches(ctx, self.max_examples_reported)
+                .await?;
+            if !examples.is_empty() {
+                let examples: Vec<String> = examples.iter().map(ToString::to_string).collect();
+                message.push_str(&format!(". Examples: [{}]", examples.join("; ")));
+            }
+            collection.record(&mut details);
+        }
+
+        warn!("{}", message);
+
+        Ok(ConstraintResult {
+            status: ConstraintStatus::Failure,
+            metric: Some(ratio),
+            message: Some(message),
+            quarantine: None,
+            constraint_id: None,
+            details,
+        })
+    }
+
+    fn name(&self) -> &str {
+        "expression_equality"
+    }
+
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Consistency)
+    }
+
+    fn column(&self) -> Option<&str> {
+        Some(&self.column)
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        let mut metadata = ConstraintMetadata::for_column(&self.column)
+            .with_description(format!(
+                "Checks that at least {:.2}% of values in column '{}' match '{}' within tolerance {}",
+                self.threshold * 100.0,
+                self.column,
+                self.expression,
+                self.tolerance
+            ))
+            .with_custom("expression", self.expression.clone())
+            .with_custom("absolute_tolerance", self.tolerance.absolute.to_string())
+            .with_custom("relative_tolerance", self.tolerance.relative.to_string())
+            .with_custom("threshold", self.threshold.to_string())
+            .with_custom("constraint_type", "consistency");
+        if !self.key_columns.is_empty() {
+            metadata = metadata.with_custom("key_columns", self.key_columns.join(","));
+        }
+        metadata
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ValidationContext, CURRENT_CONTEXT};
+    use arrow::datatypes::{Field, Schema};
+    use datafusion::datasource::MemTable;
+    use std::sync::Arc;
+
+    use crate::test_helpers::evaluate_constraint_with_context;
+
+    /// Orders with a materialized total; order 3 is off by 0.005 and order 4 by 5.0.
+    async fn orders_context() -> SessionContext {
+        let ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("order_id", DataType::Int64, false),
+            Field::new("price", DataType::Float64, true),
+            Field::new("quantity", DataType::Int64, true),
+            Field::new("tax", DataType::Float64, true),
+            Field::new("total", DataType::Float64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4, 5, 6])),
+                Arc::new(Float64Array::from(vec![
+                    Some(10.0),
+                    Some(2.5),
+                    Some(100.0),
+                    Some(20.0),
+                    None,
+                    Some(1.0),
+                ])),
+                Arc::new(Int64Array::from(vec![2, 4, 1, 3, 1, 1])),
+                Arc::new(Float64Array::from(vec![1.0, 0.0, 8.0, 2.0, 1.0, 0.0])),
+                Arc::new(Float64Array::from(vec![
+                    Some(21.0),
+                    Some(10.0),
+                    Some(108.005),
+                    Some(67.0),
+                    None,
+                    None,
+                ])),
+            ],
+        )
+        .unwrap();
+        let table = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("orders", Arc::new(table)).unwrap();
+        ctx
+    }
+
+    fn total(tolerance: EqualityTolerance) -> ExpressionEqualityConstraint {
+        ExpressionEqualityConstraint::new("total", "price * quantity + tax", tolerance).unwrap()
+    }
+
+    async fn evaluate(
+        ctx: &SessionContext,
+        constraint: &ExpressionEqualityConstraint,
+    ) -> ConstraintResult {
+        evaluate_constraint_with_context(constraint, ctx, "orders")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_violations_reported_with_examples() {
+        let ctx = orders_context().await;
+        let constraint = total(EqualityTolerance::absolute(0.01)).key_columns(vec!["order_id"]);
+
+        let result = evaluate(&ctx, &constraint).await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        // Orders 4 (off by 5.0) and 6 (NULL total) violate; both NULL in order 5 conforms
+        assert_eq!(result.metric, Some(4.0 / 6.0));
+        let message = result.message.unwrap();
+        assert!(message.contains("Found 2 of 6 rows"), "{message}");
+        assert!(message.contains("max absolute difference 5"), "{message}");
+        assert!(
+            message.contains(
+                "order_id=6: stored NULL, computed 1; order_id=4: stored 67, computed 62 (diff 5)"
+            ),
+            "{message}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_absolute_and_relative_tolerance() {
+        let ctx = orders_context().await;
+
+        let exact = total(EqualityTolerance::exact()).threshold(0.5);
+        assert_eq!(evaluate(&ctx, &exact).await.metric, Some(3.0 / 6.0));
+
+        // 5.0 is within 10% of 62, 0.005 is within 0.01
+        let either = total(EqualityTolerance::absolute(0.01).with_relative(0.1)).threshold(0.8);
+        let result = evaluate(&ctx, &either).await;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(5.0 / 6.0));
+
+        let relative = total(EqualityTolerance::relative(0.01)).threshold(0.5);
+        assert_eq!(evaluate(&ctx, &relative).await.metric, Some(4.0 / 6.0));
+    }
+
+    #[tokio::test]
+    async fn test_mismatches() {
+        let ctx = orders_context().await;
+        let constraint = total(EqualityTolerance::exact()).key_columns(vec!["order_id"]);
+
+        let mismatches = CURRENT_CONTEXT
+            .scope(
+                ValidationContext::new("orders"),
+                constraint.mismatches(&ctx, 10),
+            )
+            .await
+            .unwrap();
+        let ids: Vec<_> = mismatches
+            .iter()
+            .map(|m| m.key[0].1.as_deref().unwrap())
+            .collect();
+        assert_eq!(ids, ["6", "4", "3"]);
+        assert_eq!(mismatches[0].difference(), None);
+        assert_eq!(mismatches[1].difference(), Some(5.0));
+    }
+
+    #[test]
+    fn test_expression_validation() {
+        assert!(ExpressionEqualityConstraint::new(
+            "total",
+            "price; DROP TABLE orders",
+            EqualityTolerance::exact()
+        )
+        .is_err());
+        assert!(ExpressionEqualityConstraint::new(
+            "total; --",
+            "price",
+            EqualityTolerance::exact()
+        )
+        .is_err());
+        assert_eq!(
+            EqualityTolerance::absolute(-0.5)
+                .with_relative(0.01)
+                .to_string(),
+            "±0.5 or ±1%"
+        );
+    }
+}
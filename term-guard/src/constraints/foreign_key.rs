@@ -21,6 +21,32 @@
 //!     .build();
 //! ```
 //!
+//! ## Tables in Custom Schemas
+//!
+//! ```rust
+//! use term_guard::constraints::ForeignKeyConstraint;
+//!
+//! // Columns may be qualified with schema and catalog names
+//! let constraint = ForeignKeyConstraint::new(
+//!     "my_catalog.staging.orders.customer_id",
+//!     "my_catalog.staging.customers.id",
+//! );
+//! ```
+//!
+//! ## Composite Keys
+//!
+//! ```rust
+//! use term_guard::constraints::ForeignKeyConstraint;
+//!
+//! // Column names may differ between the child and parent tables
+//! let constraint = ForeignKeyConstraint::composite(
+//!     "orders",
+//!     "customers",
+//!     vec![("tenant_id", "tenant_id"), ("customer_id", "cust_id")],
+//! )?;
+//! # Ok::<(), term_guard::error::TermError>(())
+//! ```
+//!
 //! ## Foreign Key with Null Handling
 //!
 //! ```rust
@@ -31,13 +57,18 @@
 //!     .allow_nulls(true);
 //! ```
 
-use crate::core::{Constraint, ConstraintResult, ConstraintStatus};
+use super::composite_key::{format_examples, key_examples, NullKeyPolicy};
+use crate::core::{
+    collect_limited, collect_with_metrics, parse_qualified_column, CollectionSummary, Constraint,
+    ConstraintResult, ConstraintStatus, QualifiedTable, QualityDimension,
+};
 use crate::error::{Result, TermError};
 use crate::security::SqlSecurity;
-use arrow::array::{Array, Int64Array, StringArray};
+use arrow::array::{Array, Int64Array};
 use async_trait::async_trait;
 use datafusion::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use tracing::{debug, instrument, warn};
 
 /// Foreign key constraint for validating referential integrity between tables.
@@ -47,24 +78,36 @@ use tracing::{debug, instrument, warn};
 /// data consistency and preventing orphaned records.
 ///
 /// The constraint supports:
+/// - Single-column and composite keys
 /// - Inner and left joins for different validation strategies
 /// - Null value handling (allow/disallow nulls in foreign key)
 /// - Custom error messages and violation reporting
 /// - Performance optimization through predicate pushdown
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForeignKeyConstraint {
-    /// Column in the child table (e.g., "orders.customer_id")
-    child_column: String,
-    /// Column in the parent table (e.g., "customers.id")
-    parent_column: String,
+    /// Key columns in the child table (e.g., "orders.customer_id" or "staging.orders.customer_id")
+    child_columns: Vec<String>,
+    /// Referenced columns in the parent table, in key order (e.g., "customers.id")
+    parent_columns: Vec<String>,
     /// Whether to allow NULL values in the foreign key column
     allow_nulls: bool,
+    /// Which keys with NULL columns are ignored when NULLs are allowed
+    #[serde(default)]
+    null_policy: NullKeyPolicy,
     /// Use left join strategy (faster for large tables with few violations)
     use_left_join: bool,
     /// Maximum number of violation examples to collect
     max_violations_reported: usize,
 }
 
+/// The tables and escaped, aliased key columns of a foreign key.
+struct ResolvedKey {
+    child_table: String,
+    parent_table: String,
+    child_columns: Vec<String>,
+    parent_columns: Vec<String>,
+}
+
 impl ForeignKeyConstraint {
     /// Create a new foreign key constraint.
     ///
@@ -82,23 +125,82 @@ impl ForeignKeyConstraint {
     /// ```
     pub fn new(child_column: impl Into<String>, parent_column: impl Into<String>) -> Self {
         Self {
-            child_column: child_column.into(),
-            parent_column: parent_column.into(),
+            child_columns: vec![child_column.into()],
+            parent_columns: vec![parent_column.into()],
             allow_nulls: false,
+            null_policy: NullKeyPolicy::default(),
             use_left_join: true,
             max_violations_reported: 100,
         }
     }
 
+    /// Create a foreign key constraint on a composite key.
+    ///
+    /// # Arguments
+    ///
+    /// * `child_table` - The child table, optionally schema-qualified
+    /// * `parent_table` - The parent table, optionally schema-qualified
+    /// * `keys` - Ordered (child_column, parent_column) pairs of the key
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if `keys` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::ForeignKeyConstraint;
+    ///
+    /// let fk = ForeignKeyConstraint::composite(
+    ///     "orders",
+    ///     "customers",
+    ///     vec![("tenant_id", "tenant_id"), ("customer_id", "cust_id")],
+    /// )?;
+    /// assert_eq!(fk.child_columns(), ["orders.tenant_id", "orders.customer_id"]);
+    /// # Ok::<(), term_guard::error::TermError>(())
+    /// ```
+    pub fn composite(
+        child_table: impl Into<String>,
+        parent_table: impl Into<String>,
+        keys: Vec<(impl Into<String>, impl Into<String>)>,
+    ) -> Result<Self> {
+        let (child_table, parent_table) = (child_table.into(), parent_table.into());
+        let (child_columns, parent_columns) = keys
+            .into_iter()
+            .map(|(child, parent)| {
+                (
+                    format!("{child_table}.{}", child.into()),
+                    format!("{parent_table}.{}", parent.into()),
+                )
+            })
+            .unzip();
+        let constraint = Self {
+            child_columns,
+            parent_columns,
+            ..Self::new("", "")
+        };
+        constraint.validate_key_columns()?;
+        Ok(constraint)
+    }
+
     /// Set whether to allow NULL values in the foreign key column.
     ///
     /// When `true`, NULL values in the child column are considered valid.
     /// When `false`, NULL values are treated as constraint violations.
+    /// Which composite keys count as NULL is set with [`null_policy`](Self::null_policy).
     pub fn allow_nulls(mut self, allow: bool) -> Self {
         self.allow_nulls = allow;
         self
     }
 
+    /// Set which composite keys with NULL columns are ignored when NULLs are allowed.
+    ///
+    /// Defaults to [`NullKeyPolicy::AnyNull`].
+    pub fn null_policy(mut self, policy: NullKeyPolicy) -> Self {
+        self.null_policy = policy;
+        self
+    }
+
     /// Set the join strategy for validation.
     ///
     /// - `true` (default): Use LEFT JOIN strategy, better for tables with few violations
@@ -116,123 +218,174 @@ impl ForeignKeyConstraint {
         self
     }
 
-    /// Get the child column name
+    /// Get the child column name, the first key column for composite keys
     pub fn child_column(&self) -> &str {
-        &self.child_column
+        self.child_columns.first().map_or("", String::as_str)
     }
 
-    /// Get the parent column name  
+    /// Get the parent column name, the first key column for composite keys
     pub fn parent_column(&self) -> &str {
-        &self.parent_column
+        self.parent_columns.first().map_or("", String::as_str)
+    }
+
+    /// Get the qualified key columns of the child table
+    pub fn child_columns(&self) -> &[String] {
+        &self.child_columns
+    }
+
+    /// Get the qualified key columns of the parent table
+    pub fn parent_columns(&self) -> &[String] {
+        &self.parent_columns
     }
 
     /// Parse table and column from qualified column name (e.g., "orders.customer_id")
-    fn parse_qualified_column(&self, qualified_column: &str) -> Result<(String, String)> {
-        let parts: Vec<&str> = qualified_column.split('.').collect();
-        if parts.len() != 2 {
-            return Err(TermError::constraint_evaluation(
-                "foreign_key",
-                format!(
-                    "Foreign key column must be qualified (table.column): '{qualified_column}'"
-                ),
+    fn parse_qualified_column(&self, qualified_column: &str) -> Result<(QualifiedTable, String)> {
+        parse_qualified_column("foreign_key", qualified_column)
+    }
+
+    /// Check that the key has at least one column and as many child as parent columns.
+    fn validate_key_columns(&self) -> Result<()> {
+        if self.child_columns.is_empty() {
+            return Err(TermError::Configuration(
+                "Foreign key needs at least one key column pair".to_string(),
             ));
         }
+        if self.child_columns.len() != self.parent_columns.len() {
+            return Err(TermError::Configuration(format!(
+                "Foreign key has {} child columns but {} parent columns",
+                self.child_columns.len(),
+                self.parent_columns.len()
+            )));
+        }
+        Ok(())
+    }
 
-        let table = parts[0].to_string();
-        let column = parts[1].to_string();
-
-        // Validate SQL identifiers for security
-        SqlSecurity::validate_identifier(&table)?;
-        SqlSecurity::validate_identifier(&column)?;
+    /// Parse the qualified key columns of one side, which must all be in one table.
+    fn parse_key_columns(
+        &self,
+        columns: &[String],
+        alias: &str,
+    ) -> Result<(QualifiedTable, Vec<String>)> {
+        let mut table = None;
+        let mut escaped = Vec::with_capacity(columns.len());
+        for column in columns {
+            let (column_table, column) = self.parse_qualified_column(column)?;
+            match &table {
+                None => table = Some(column_table),
+                Some(table) if *table != column_table => {
+                    return Err(TermError::Configuration(format!(
+                        "Foreign key columns must be in one table, found '{table}' and '{column_table}'"
+                    )));
+                }
+                Some(_) => {}
+            }
+            escaped.push(format!(
+                "{alias}.{}",
+                SqlSecurity::escape_identifier(&column)?
+            ));
+        }
+        let table = table.ok_or_else(|| {
+            TermError::Configuration("Foreign key needs at least one key column pair".to_string())
+        })?;
+        Ok((table, escaped))
+    }
 
-        Ok((table, column))
+    /// Resolve the tables and render them and the key columns as SQL identifiers.
+    fn resolve_key(&self) -> Result<ResolvedKey> {
+        self.validate_key_columns()?;
+        let (child_table, child_columns) = self.parse_key_columns(&self.child_columns, "child")?;
+        let (parent_table, parent_columns) =
+            self.parse_key_columns(&self.parent_columns, "parent")?;
+        Ok(ResolvedKey {
+            child_table: child_table.to_sql()?,
+            parent_table: parent_table.to_sql()?,
+            child_columns,
+            parent_columns,
+        })
     }
 
-    /// Generate SQL query for foreign key validation using LEFT JOIN strategy
-    fn generate_left_join_query(
-        &self,
-        child_table: &str,
-        child_col: &str,
-        parent_table: &str,
-        parent_col: &str,
-    ) -> Result<String> {
+    /// Generate the join and filter selecting child rows without a parent row.
+    fn violations_source(&self, key: &ResolvedKey) -> String {
+        let join_condition = key
+            .child_columns
+            .iter()
+            .zip(&key.parent_columns)
+            .map(|(child, parent)| format!("{child} = {parent}"))
+            .collect::<Vec<_>>()
+            .join(" AND ");
         let null_condition = if self.allow_nulls {
-            format!("AND {child_table}.{child_col} IS NOT NULL")
+            format!("AND {}", self.null_policy.participating(&key.child_columns))
         } else {
             String::new()
         };
 
+        format!(
+            "FROM {} AS child
+             LEFT JOIN {} AS parent ON {join_condition}
+             WHERE {} IS NULL {null_condition}",
+            key.child_table, key.parent_table, key.parent_columns[0]
+        )
+    }
+
+    /// Select the child key columns as `key_0`, `key_1`, ...
+    fn key_projection(key: &ResolvedKey) -> String {
+        key.child_columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| format!("{column} AS key_{i}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Generate SQL query for foreign key validation using LEFT JOIN strategy
+    fn generate_left_join_query(&self, key: &ResolvedKey) -> String {
+        let source = self.violations_source(key);
+        let projection = Self::key_projection(key);
+
         let sql = format!(
-            "SELECT 
-                COUNT(*) as total_violations,
-                COUNT(DISTINCT {child_table}.{child_col}) as unique_violations
-             FROM {child_table} 
-             LEFT JOIN {parent_table} ON {child_table}.{child_col} = {parent_table}.{parent_col}
-             WHERE {parent_table}.{parent_col} IS NULL {null_condition}"
+            "WITH violations AS (SELECT {projection} {source})
+             SELECT all_rows.total_violations, distinct_keys.unique_violations
+             FROM (SELECT COUNT(*) as total_violations FROM violations) AS all_rows
+             CROSS JOIN (
+                 SELECT COUNT(*) as unique_violations
+                 FROM (SELECT DISTINCT * FROM violations) AS violating_keys
+             ) AS distinct_keys"
         );
 
         debug!("Generated foreign key validation query: {}", sql);
-        Ok(sql)
+        sql
     }
 
     /// Generate SQL query to get violation examples
-    fn generate_violations_query(
-        &self,
-        child_table: &str,
-        child_col: &str,
-        parent_table: &str,
-        parent_col: &str,
-    ) -> Result<String> {
+    fn generate_violations_query(&self, key: &ResolvedKey) -> String {
         if self.max_violations_reported == 0 {
-            return Ok(String::new());
+            return String::new();
         }
 
-        let null_condition = if self.allow_nulls {
-            format!("AND {child_table}.{child_col} IS NOT NULL")
-        } else {
-            String::new()
-        };
-
+        let source = self.violations_source(key);
+        let projection = Self::key_projection(key);
         let limit = self.max_violations_reported;
-        let sql = format!(
-            "SELECT DISTINCT {child_table}.{child_col} as violating_value
-             FROM {child_table}
-             LEFT JOIN {parent_table} ON {child_table}.{child_col} = {parent_table}.{parent_col}
-             WHERE {parent_table}.{parent_col} IS NULL {null_condition}
-             LIMIT {limit}"
-        );
+        let sql = format!("SELECT DISTINCT {projection} {source} LIMIT {limit}");
 
         debug!("Generated violations query: {}", sql);
-        Ok(sql)
+        sql
     }
 
-    /// Collect violation examples using memory-efficient approach.
+    /// Collect violation examples.
     ///
-    /// This method limits memory usage by:
-    /// 1. Using LIMIT in the SQL query to restrict result size at the database level
-    /// 2. Pre-allocating vector with known maximum size
-    /// 3. Processing results in a single pass without intermediate collections
-    /// 4. Early termination when max violations are reached
-    async fn collect_violation_examples_efficiently(
+    /// The number of examples is limited in the query and their size by the
+    /// collection limits, so memory use does not depend on the number of
+    /// violations. Composite keys are reported as tuples.
+    async fn collect_violation_examples(
         &self,
         ctx: &SessionContext,
-        child_table: &str,
-        child_col: &str,
-        parent_table: &str,
-        parent_col: &str,
-    ) -> Result<Vec<String>> {
-        if self.max_violations_reported == 0 {
-            return Ok(Vec::new());
-        }
-
-        let violations_sql =
-            self.generate_violations_query(child_table, child_col, parent_table, parent_col)?;
+        key: &ResolvedKey,
+    ) -> Result<(Vec<String>, CollectionSummary)> {
+        let violations_sql = self.generate_violations_query(key);
         if violations_sql.is_empty() {
-            return Ok(Vec::new());
+            return Ok((Vec::new(), CollectionSummary::default()));
         }
 
-        debug!("Executing foreign key violations query with memory-efficient collection");
-
         let violations_df = ctx.sql(&violations_sql).await.map_err(|e| {
             TermError::constraint_evaluation(
                 "foreign_key",
@@ -240,64 +393,29 @@ impl ForeignKeyConstraint {
             )
         })?;
 
-        let batches = violations_df.collect().await.map_err(|e| {
+        let collected = collect_limited(violations_df).await.map_err(|e| {
             TermError::constraint_evaluation(
                 "foreign_key",
                 format!("Failed to collect violation examples: {e}"),
             )
         })?;
+        let batches = collected.iter()?.collect::<Result<Vec<_>>>()?;
 
-        // Pre-allocate with known maximum size to avoid reallocations
-        let mut violation_examples = Vec::with_capacity(self.max_violations_reported);
-
-        // Process batches efficiently with early termination
-        for batch in batches {
-            for i in 0..batch.num_rows() {
-                if violation_examples.len() >= self.max_violations_reported {
-                    debug!(
-                        "Reached max violations limit ({}), stopping collection",
-                        self.max_violations_reported
-                    );
-                    return Ok(violation_examples);
-                }
-
-                // Handle different data types efficiently
-                if let Some(string_array) = batch.column(0).as_any().downcast_ref::<StringArray>() {
-                    if !string_array.is_null(i) {
-                        violation_examples.push(string_array.value(i).to_string());
-                    }
-                } else if let Some(int64_array) =
-                    batch.column(0).as_any().downcast_ref::<Int64Array>()
-                {
-                    if !int64_array.is_null(i) {
-                        violation_examples.push(int64_array.value(i).to_string());
-                    }
-                } else if let Some(float64_array) = batch
-                    .column(0)
-                    .as_any()
-                    .downcast_ref::<arrow::array::Float64Array>()
-                {
-                    if !float64_array.is_null(i) {
-                        violation_examples.push(float64_array.value(i).to_string());
-                    }
-                } else if let Some(int32_array) = batch
-                    .column(0)
-                    .as_any()
-                    .downcast_ref::<arrow::array::Int32Array>()
-                {
-                    if !int32_array.is_null(i) {
-                        violation_examples.push(int32_array.value(i).to_string());
-                    }
-                }
-                // Add more data types as needed for broader compatibility
-            }
-        }
-
+        let violation_examples =
+            key_examples(&batches, &self.child_columns, self.max_violations_reported)?;
         debug!(
             "Collected {} foreign key violation examples",
             violation_examples.len()
         );
-        Ok(violation_examples)
+        Ok((violation_examples, collected.summary()))
+    }
+
+    /// Describe the key columns of one side in messages.
+    fn describe(columns: &[String]) -> String {
+        match columns {
+            [column] => column.clone(),
+            _ => format!("({})", columns.join(", ")),
+        }
     }
 }
 
@@ -305,18 +423,13 @@ impl ForeignKeyConstraint {
 impl Constraint for ForeignKeyConstraint {
     #[instrument(skip(self, ctx), fields(constraint = "foreign_key"))]
     async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
-        debug!(
-            "Evaluating foreign key constraint: {} -> {}",
-            self.child_column, self.parent_column
-        );
-
-        // Parse qualified column names
-        let (child_table, child_col) = self.parse_qualified_column(&self.child_column)?;
-        let (parent_table, parent_col) = self.parse_qualified_column(&self.parent_column)?;
+        let child = Self::describe(&self.child_columns);
+        let parent = Self::describe(&self.parent_columns);
+        debug!("Evaluating foreign key constraint: {child} -> {parent}");
 
         // Generate and execute validation query
-        let sql =
-            self.generate_left_join_query(&child_table, &child_col, &parent_table, &parent_col)?;
+        let key = self.resolve_key()?;
+        let sql = self.generate_left_join_query(&key);
         let df = ctx.sql(&sql).await.map_err(|e| {
             TermError::constraint_evaluation(
                 "foreign_key",
@@ -324,7 +437,7 @@ impl Constraint for ForeignKeyConstraint {
             )
         })?;
 
-        let batches = df.collect().await.map_err(|e| {
+        let batches = collect_with_metrics(df).await.map_err(|e| {
             TermError::constraint_evaluation(
                 "foreign_key",
                 format!("Failed to collect foreign key results: {e}"),
@@ -366,52 +479,68 @@ impl Constraint for ForeignKeyConstraint {
             return Ok(ConstraintResult::success());
         }
 
-        // Collect violation examples using memory-efficient approach
-        let violation_examples = self
-            .collect_violation_examples_efficiently(
-                ctx,
-                &child_table,
-                &child_col,
-                &parent_table,
-                &parent_col,
-            )
-            .await?;
+        let (violation_examples, collection) = self.collect_violation_examples(ctx, &key).await?;
 
         // Format error message
+        let noun = if self.child_columns.len() == 1 {
+            "values"
+        } else {
+            "keys"
+        };
         let message = if violation_examples.is_empty() {
             format!(
-                "Foreign key constraint violation: {total_violations} values in '{}' do not exist in '{}' (total: {total_violations}, unique: {unique_violations})",
-                self.child_column, self.parent_column
+                "Foreign key constraint violation: {total_violations} {noun} in '{child}' do not exist in '{parent}' (total: {total_violations}, unique: {unique_violations})"
             )
         } else {
-            let examples_str = if violation_examples.len() <= 5 {
-                violation_examples.join(", ")
-            } else {
-                format!(
-                    "{}, ... ({} more)",
-                    violation_examples[..5].join(", "),
-                    violation_examples.len() - 5
-                )
-            };
+            let examples_str = format_examples(&violation_examples);
 
             format!(
-                "Foreign key constraint violation: {total_violations} values in '{}' do not exist in '{}' (total: {total_violations}, unique: {unique_violations}). Examples: [{examples_str}]",
-                self.child_column, self.parent_column
+                "Foreign key constraint violation: {total_violations} {noun} in '{child}' do not exist in '{parent}' (total: {total_violations}, unique: {unique_violations}). Examples: [{examples_str}]"
             )
         };
 
         warn!("{}", message);
 
+        let mut details = BTreeMap::new();
+        collection.record(&mut details);
         Ok(ConstraintResult {
             status: ConstraintStatus::Failure,
             metric: Some(total_violations as f64),
             message: Some(message),
+            quarantine: None,
+            constraint_id: None,
+            details,
         })
     }
 
     fn name(&self) -> &str {
         "foreign_key"
     }
+
+    fn describe(&self) -> String {
+        let key = |columns: &[String]| match columns {
+            [column] => column.clone(),
+            columns => format!("({})", columns.join(", ")),
+        };
+        let nulls = if self.allow_nulls { " or be null" } else { "" };
+        format!(
+            "{} must reference an existing {}{nulls}",
+            key(&self.child_columns),
+            key(&self.parent_columns)
+        )
+    }
+
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Consistency)
+    }
+
+    fn referenced_tables(&self) -> Vec<String> {
+        [self.child_column(), self.parent_column()]
+            .into_iter()
+            .filter_map(|column| self.parse_qualified_column(column).ok())
+            .map(|(table, _)| table.to_string())
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -567,12 +696,20 @@ mod tests {
         let (table, column) = constraint
             .parse_qualified_column("orders.customer_id")
             .unwrap();
-        assert_eq!(table, "orders");
+        assert_eq!(table.to_string(), "orders");
+        assert_eq!(column, "customer_id");
+
+        let (table, column) = constraint
+            .parse_qualified_column("my_catalog.staging.orders.customer_id")
+            .unwrap();
+        assert_eq!(table.to_string(), "my_catalog.staging.orders");
         assert_eq!(column, "customer_id");
 
         // Test invalid format
         assert!(constraint.parse_qualified_column("invalid_column").is_err());
-        assert!(constraint.parse_qualified_column("too.many.parts").is_err());
+        assert!(constraint
+            .parse_qualified_column("way.too.many.parts.here")
+            .is_err());
     }
 
     #[test]
@@ -597,13 +734,13 @@ mod tests {
 
     #[test]
     fn test_sql_generation() -> Result<()> {
-        let constraint = ForeignKeyConstraint::new("orders.customer_id", "customers.id");
-        let sql =
-            constraint.generate_left_join_query("orders", "customer_id", "customers", "id")?;
+        let constraint = ForeignKeyConstraint::new("orders.customer_id", "staging.customers.id");
+        let sql = constraint.generate_left_join_query(&constraint.resolve_key()?);
 
-        assert!(sql.contains("LEFT JOIN"));
-        assert!(sql.contains("orders.customer_id = customers.id"));
-        assert!(sql.contains("customers.id IS NULL"));
+        assert!(sql.contains(r#"FROM "orders" AS child"#));
+        assert!(sql.contains(r#"LEFT JOIN "staging"."customers" AS parent"#));
+        assert!(sql.contains(r#"child."customer_id" = parent."id""#));
+        assert!(sql.contains(r#"parent."id" IS NULL"#));
         assert!(sql.contains("COUNT(*) as total_violations"));
 
         Ok(())
@@ -613,10 +750,130 @@ mod tests {
     fn test_sql_generation_with_nulls_allowed() -> Result<()> {
         let constraint =
             ForeignKeyConstraint::new("orders.customer_id", "customers.id").allow_nulls(true);
-        let sql =
-            constraint.generate_left_join_query("orders", "customer_id", "customers", "id")?;
+        let sql = constraint.generate_left_join_query(&constraint.resolve_key()?);
+
+        assert!(sql.contains(r#"AND child."customer_id" IS NOT NULL"#));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_composite_key_configuration() -> Result<()> {
+        let constraint = ForeignKeyConstraint::composite(
+            "orders",
+            "staging.customers",
+            vec![("tenant_id", "tenant_id"), ("customer_id", "cust_id")],
+        )?;
+        assert_eq!(
+            constraint.child_columns(),
+            ["orders.tenant_id", "orders.customer_id"]
+        );
+        assert_eq!(constraint.parent_column(), "staging.customers.tenant_id");
+        assert_eq!(
+            constraint.referenced_tables(),
+            vec!["orders", "staging.customers"]
+        );
+
+        let sql = constraint.generate_left_join_query(&constraint.resolve_key()?);
+        assert!(sql.contains(
+            r#"child."tenant_id" = parent."tenant_id" AND child."customer_id" = parent."cust_id""#
+        ));
+
+        let empty: Vec<(&str, &str)> = Vec::new();
+        assert!(matches!(
+            ForeignKeyConstraint::composite("orders", "customers", empty),
+            Err(TermError::Configuration(_))
+        ));
+
+        // Lists of different lengths can only come from deserialized constraints
+        let uneven: ForeignKeyConstraint = serde_json::from_str(
+            r#"{"child_columns": ["orders.a", "orders.b"], "parent_columns": ["customers.a"],
+                "allow_nulls": false, "use_left_join": true, "max_violations_reported": 10}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            uneven.resolve_key(),
+            Err(TermError::Configuration(_))
+        ));
+
+        let mixed = ForeignKeyConstraint {
+            child_columns: vec!["orders.a".to_string(), "shipments.b".to_string()],
+            ..constraint
+        };
+        assert!(matches!(
+            mixed.resolve_key(),
+            Err(TermError::Configuration(_))
+        ));
+        Ok(())
+    }
 
-        assert!(sql.contains("AND orders.customer_id IS NOT NULL"));
+    async fn composite_key_context() -> Result<SessionContext> {
+        let ctx = create_test_context().await?;
+        ctx.sql(
+            "CREATE TABLE composite_customers (tenant_id INT, cust_id INT) AS VALUES
+                (1, 10), (1, 11), (2, 10)",
+        )
+        .await?
+        .collect()
+        .await?;
+        ctx.sql(
+            "CREATE TABLE composite_orders (order_id INT, tenant_id INT, customer_id INT) AS VALUES
+                (100, 1, 10), (101, 2, 10), (102, 2, 11), (103, 2, 11), (104, NULL, NULL),
+                (105, 1, NULL)",
+        )
+        .await?
+        .collect()
+        .await?;
+        Ok(ctx)
+    }
+
+    fn composite_key() -> ForeignKeyConstraint {
+        ForeignKeyConstraint::composite(
+            "composite_orders",
+            "composite_customers",
+            vec![("tenant_id", "tenant_id"), ("customer_id", "cust_id")],
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_composite_key_violations() -> Result<()> {
+        let ctx = composite_key_context().await?;
+
+        // Customer 11 exists for tenant 1 only, and NULL keys never match
+        let result = composite_key().evaluate(&ctx).await?;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(4.0));
+        let message = result.message.unwrap();
+        assert!(message
+            .contains("4 keys in '(composite_orders.tenant_id, composite_orders.customer_id)'"));
+        assert!(message.contains("unique: 3"));
+        assert!(message.contains("(2, 11)"));
+        assert!(message.contains("(NULL, NULL)"));
+        assert!(message.contains("(1, NULL)"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_composite_key_null_policies() -> Result<()> {
+        let ctx = composite_key_context().await?;
+
+        // Keys with any NULL column are ignored
+        let result = composite_key().allow_nulls(true).evaluate(&ctx).await?;
+        assert_eq!(result.metric, Some(2.0));
+        assert!(!result.message.unwrap().contains("NULL"));
+
+        // Only keys without any value are ignored
+        let result = composite_key()
+            .allow_nulls(true)
+            .null_policy(NullKeyPolicy::AllNull)
+            .evaluate(&ctx)
+            .await?;
+        assert_eq!(result.metric, Some(3.0));
+        let message = result.message.unwrap();
+        assert!(message.contains("(1, NULL)"));
+        assert!(!message.contains("(NULL, NULL)"));
 
         Ok(())
     }
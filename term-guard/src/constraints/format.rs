@@ -168,16 +168,22 @@
 //! # }
 //! ```
 
-use crate::core::{current_validation_context, Constraint, ConstraintMetadata, ConstraintResult};
+use crate::constraints::format_codes::{
+    iban_udf, sql_in_list, IBAN_UDF_NAME, ISO_3166_ALPHA2, ISO_4217_CODES,
+};
+use crate::core::{
+    current_validation_context, describe_percent, describe_rows, AdjustedThreshold, Constraint,
+    ConstraintMetadata, ConstraintResult, NullPolicy, QualityDimension, QuarantineSink,
+    QuarantineSummary, RatioQueryBuilder, RegexLimits, ThresholdAdjustment,
+};
 use crate::prelude::*;
 use crate::security::SqlSecurity;
-use arrow::array::Array;
 use async_trait::async_trait;
 use datafusion::prelude::*;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::sync::{PoisonError, RwLock};
 use tracing::instrument;
 /// Lazy static pattern cache for compiled regex patterns
 static PATTERN_CACHE: Lazy<RwLock<HashMap<String, String>>> =
@@ -210,18 +216,27 @@ pub enum FormatType {
     Iso8601DateTime,
     /// Social Security Number (SSN) pattern detection
     SocialSecurityNumber,
+    /// IBAN validation with country length and mod-97 checksum
+    Iban,
+    /// MAC address validation (colon, hyphen or dotted notation)
+    MacAddress,
+    /// Semantic version validation (`MAJOR.MINOR.PATCH` with optional pre-release and build)
+    SemVer,
+    /// ISO 3166-1 alpha-2 country code validation
+    CountryCodeIso3166,
+    /// ISO 4217 currency code validation
+    CurrencyCodeIso4217,
 }
 
 impl FormatType {
     /// Returns the regex pattern for this format type.
-    fn get_pattern(&self) -> Result<String> {
+    pub(crate) fn get_pattern(&self) -> Result<String> {
         let cache_key = format!("{self:?}");
 
         // Check cache first
         {
-            let cache = PATTERN_CACHE.read().map_err(|_| {
-                TermError::Internal("Failed to acquire read lock on pattern cache".to_string())
-            })?;
+            // Entries are inserted whole, so a poisoned cache is still valid
+            let cache = PATTERN_CACHE.read().unwrap_or_else(PoisonError::into_inner);
             if let Some(pattern) = cache.get(&cache_key) {
                 return Ok(pattern.clone());
             }
@@ -293,13 +308,27 @@ impl FormatType {
                 // This regex avoids look-ahead by explicitly listing valid ranges
                 r"^(00[1-9]|0[1-9][0-9]|[1-5][0-9]{2}|6[0-5][0-9]|66[0-5]|667|66[89]|6[7-9][0-9]|[7-8][0-9]{2})-?(0[1-9]|[1-9][0-9])-?(000[1-9]|00[1-9][0-9]|0[1-9][0-9]{2}|[1-9][0-9]{3})$".to_string()
             }
+            FormatType::Iban => {
+                // Structure only; the checksum is verified by a UDF
+                r"^[A-Z]{2}[0-9]{2}(?: ?[A-Z0-9]){11,30}$".to_string()
+            }
+            FormatType::MacAddress => {
+                r"^(?:[0-9A-Fa-f]{2}:){5}[0-9A-Fa-f]{2}$|^(?:[0-9A-Fa-f]{2}-){5}[0-9A-Fa-f]{2}$|^(?:[0-9A-Fa-f]{4}\.){2}[0-9A-Fa-f]{4}$".to_string()
+            }
+            FormatType::SemVer => {
+                // Pattern recommended by semver.org
+                r"^(?:0|[1-9]\d*)\.(?:0|[1-9]\d*)\.(?:0|[1-9]\d*)(?:-(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)(?:\.(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*)?(?:\+[0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*)?$".to_string()
+            }
+            // Structure only; membership is checked against the code tables
+            FormatType::CountryCodeIso3166 => r"^[A-Z]{2}$".to_string(),
+            FormatType::CurrencyCodeIso4217 => r"^[A-Z]{3}$".to_string(),
         };
 
         // Cache the pattern
         {
-            let mut cache = PATTERN_CACHE.write().map_err(|_| {
-                TermError::Internal("Failed to acquire write lock on pattern cache".to_string())
-            })?;
+            let mut cache = PATTERN_CACHE
+                .write()
+                .unwrap_or_else(PoisonError::into_inner);
             cache.insert(cache_key, pattern.clone());
         }
 
@@ -321,6 +350,44 @@ impl FormatType {
             FormatType::Json => "json",
             FormatType::Iso8601DateTime => "iso8601_datetime",
             FormatType::SocialSecurityNumber => "social_security_number",
+            FormatType::Iban => "iban",
+            FormatType::MacAddress => "mac_address",
+            FormatType::SemVer => "semver",
+            FormatType::CountryCodeIso3166 => "country_code_iso3166",
+            FormatType::CurrencyCodeIso4217 => "currency_code_iso4217",
+        }
+    }
+
+    /// Returns what a value must do to conform to this format, for
+    /// [`Constraint::describe`].
+    pub(crate) fn requirement(&self) -> String {
+        match self {
+            FormatType::Regex(pattern) => format!("match the pattern '{pattern}'"),
+            FormatType::Email => "match an email format".to_string(),
+            FormatType::Url { allow_localhost } => {
+                if *allow_localhost {
+                    "be a URL (localhost allowed)".to_string()
+                } else {
+                    "be a URL".to_string()
+                }
+            }
+            FormatType::CreditCard { .. } => "be a valid credit card number".to_string(),
+            FormatType::Phone { country } => match country.as_deref() {
+                Some(c) => format!("be a {c} phone number"),
+                None => "be a phone number".to_string(),
+            },
+            FormatType::PostalCode { country } => format!("be a {country} postal code"),
+            FormatType::UUID => "be a UUID".to_string(),
+            FormatType::IPv4 => "be an IPv4 address".to_string(),
+            FormatType::IPv6 => "be an IPv6 address".to_string(),
+            FormatType::Json => "be a valid JSON document".to_string(),
+            FormatType::Iso8601DateTime => "be an ISO 8601 date-time".to_string(),
+            FormatType::SocialSecurityNumber => "match a Social Security Number format".to_string(),
+            FormatType::Iban => "be a valid IBAN".to_string(),
+            FormatType::MacAddress => "be a MAC address".to_string(),
+            FormatType::SemVer => "be a semantic version".to_string(),
+            FormatType::CountryCodeIso3166 => "be an ISO 3166 country code".to_string(),
+            FormatType::CurrencyCodeIso4217 => "be an ISO 4217 currency code".to_string(),
         }
     }
 
@@ -358,10 +425,79 @@ impl FormatType {
             FormatType::SocialSecurityNumber => {
                 "contain Social Security Number patterns".to_string()
             }
+            FormatType::Iban => "are valid IBANs".to_string(),
+            FormatType::MacAddress => "are valid MAC addresses".to_string(),
+            FormatType::SemVer => "are valid semantic versions".to_string(),
+            FormatType::CountryCodeIso3166 => "are valid ISO 3166 country codes".to_string(),
+            FormatType::CurrencyCodeIso4217 => "are valid ISO 4217 currency codes".to_string(),
+        }
+    }
+
+    /// Returns the SQL condition checking `value_expr` beyond the regex, if any.
+    fn validity_condition(&self, value_expr: &str) -> Option<String> {
+        match self {
+            FormatType::Iban => Some(format!("{IBAN_UDF_NAME}({value_expr})")),
+            FormatType::CountryCodeIso3166 => {
+                Some(format!("{value_expr} IN {}", sql_in_list(ISO_3166_ALPHA2)))
+            }
+            FormatType::CurrencyCodeIso4217 => {
+                Some(format!("{value_expr} IN {}", sql_in_list(ISO_4217_CODES)))
+            }
+            _ => None,
         }
     }
 }
 
+/// Returns the SQL condition matching `value_expr` against `format`.
+pub(crate) fn format_condition(
+    format: &FormatType,
+    options: &FormatOptions,
+    value_expr: &str,
+) -> Result<String> {
+    let pattern = format.get_pattern()?;
+    // Compiled once per run however many constraints use the pattern
+    let limits = match format {
+        FormatType::Regex(_) => options.regex_limits.unwrap_or_default(),
+        _ => RegexLimits::default(),
+    };
+    if let Some(cache) = current_validation_context().regex_cache() {
+        cache.compile(&pattern, !options.case_sensitive, &limits)?;
+    } else {
+        limits.compile(&pattern, !options.case_sensitive)?;
+    }
+    let escaped_pattern = pattern.replace('\'', "''");
+
+    // Build the SQL based on options
+    let column_expr = if options.trim_before_check {
+        format!("TRIM({value_expr})")
+    } else {
+        value_expr.to_string()
+    };
+
+    let pattern_operator = if options.case_sensitive { "~" } else { "~*" };
+
+    let mut condition = format!("{column_expr} {pattern_operator} '{escaped_pattern}'");
+
+    // Code tables and checksums are upper case
+    let value_expr = if options.case_sensitive {
+        column_expr
+    } else {
+        format!("UPPER({column_expr})")
+    };
+    if let Some(validity) = format.validity_condition(&value_expr) {
+        condition = format!("({condition} AND {validity})");
+    }
+
+    Ok(condition)
+}
+
+/// Registers the UDFs the condition of `format` calls.
+pub(crate) fn register_format_functions(format: &FormatType, ctx: &SessionContext) {
+    if matches!(format, FormatType::Iban) {
+        ctx.register_udf(iban_udf());
+    }
+}
+
 /// Options for format constraint behavior.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FormatOptions {
@@ -371,6 +507,14 @@ pub struct FormatOptions {
     pub trim_before_check: bool,
     /// Whether NULL values should be considered valid
     pub null_is_valid: bool,
+    /// Small-sample leniency applied to the threshold
+    ///
+    /// Not applied to credit card detection, whose threshold is a maximum.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold_adjustment: Option<ThresholdAdjustment>,
+    /// Limits on the size of custom regex patterns (default: [`RegexLimits::default`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex_limits: Option<RegexLimits>,
 }
 
 impl Default for FormatOptions {
@@ -379,6 +523,8 @@ impl Default for FormatOptions {
             case_sensitive: true,
             trim_before_check: false,
             null_is_valid: true, // NULL values are typically considered valid in data quality
+            threshold_adjustment: None,
+            regex_limits: None,
         }
     }
 }
@@ -407,6 +553,34 @@ impl FormatOptions {
         self
     }
 
+    /// Relaxes the threshold for small samples; see [`ThresholdAdjustment`].
+    pub fn with_threshold_adjustment(mut self, adjustment: ThresholdAdjustment) -> Self {
+        self.threshold_adjustment = Some(adjustment);
+        self
+    }
+
+    /// Sets the limits custom regex patterns must stay within.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::{FormatConstraint, FormatOptions, FormatType};
+    /// use term_guard::core::RegexLimits;
+    ///
+    /// let options = FormatOptions::new().with_regex_limits(RegexLimits::new().size_limit(64 * 1024));
+    /// let result = FormatConstraint::new(
+    ///     "code",
+    ///     FormatType::Regex(r"(\w{100}){100}".to_string()),
+    ///     1.0,
+    ///     options,
+    /// );
+    /// assert!(result.is_err());
+    /// ```
+    pub fn with_regex_limits(mut self, limits: RegexLimits) -> Self {
+        self.regex_limits = Some(limits);
+        self
+    }
+
     /// Creates format options for case-insensitive matching.
     ///
     /// This is a convenience method that sets case_sensitive to false.
@@ -525,6 +699,8 @@ pub struct FormatConstraint {
     threshold: f64,
     /// Options for format validation behavior
     options: FormatOptions,
+    /// Sink receiving the rows that fail the format check
+    quarantine: Option<QuarantineSink>,
 }
 
 impl FormatConstraint {
@@ -539,7 +715,9 @@ impl FormatConstraint {
     ///
     /// # Errors
     ///
-    /// Returns error if column name is invalid or threshold is out of range
+    /// Returns error if column name is invalid, threshold is out of range, the
+    /// threshold adjustment is invalid, or a custom regex pattern exceeds the
+    /// [`RegexLimits`] of the options
     pub fn new(
         column: impl Into<String>,
         format: FormatType,
@@ -557,6 +735,18 @@ impl FormatConstraint {
             ));
         }
 
+        if let Some(adjustment) = &options.threshold_adjustment {
+            adjustment.validate()?;
+        }
+
+        // Reject oversized patterns before compiling them without limits
+        if let FormatType::Regex(pattern) = &format {
+            options
+                .regex_limits
+                .unwrap_or_default()
+                .compile(pattern, !options.case_sensitive)?;
+        }
+
         // Validate that the format can generate a pattern
         format.get_pattern()?;
 
@@ -565,9 +755,122 @@ impl FormatConstraint {
             format,
             threshold,
             options,
+            quarantine: None,
         })
     }
 
+    /// Exports every row failing the format check to `sink` when evaluated.
+    ///
+    /// For detection formats (`credit_card` with `detect_only`) the rows
+    /// containing a match are exported instead.
+    pub fn with_quarantine(mut self, sink: QuarantineSink) -> Self {
+        self.quarantine = Some(sink);
+        self
+    }
+
+    /// Returns the escaped column identifier and the SQL condition matching the format.
+    fn match_condition(&self) -> Result<(String, String)> {
+        let column_identifier = SqlSecurity::escape_identifier(&self.column)?;
+        let condition = format_condition(&self.format, &self.options, &column_identifier)?;
+        Ok((column_identifier, condition))
+    }
+
+    /// Registers the UDFs the format's condition calls.
+    fn register_functions(&self, ctx: &SessionContext) {
+        register_format_functions(&self.format, ctx);
+    }
+
+    /// Writes the rows violating the format check to the quarantine sink.
+    async fn quarantine_rows(
+        &self,
+        ctx: &SessionContext,
+        sink: &QuarantineSink,
+    ) -> Result<QuarantineSummary> {
+        let (column_identifier, matches) = self.match_condition()?;
+        let format_name = self.format.name();
+
+        let violation = match &self.format {
+            FormatType::CreditCard { detect_only: true } => matches,
+            _ if self.options.null_is_valid => {
+                format!("{column_identifier} IS NOT NULL AND NOT ({matches})")
+            }
+            _ => format!("{column_identifier} IS NULL OR NOT ({matches})"),
+        };
+        let reason = match &self.format {
+            FormatType::CreditCard { detect_only: true } => {
+                format!("'value matches the {format_name} pattern'")
+            }
+            _ => format!("'value does not match the {format_name} format'"),
+        };
+
+        sink.write(ctx, self.name(), &violation, &reason).await
+    }
+
+    /// Builds the query counting the matching and total rows of a table.
+    fn format_query(&self, table_name: &str) -> Result<RatioQueryBuilder> {
+        let (column_identifier, matches) = self.match_condition()?;
+        let nulls = if self.options.null_is_valid {
+            NullPolicy::Conforming
+        } else {
+            NullPolicy::Violating
+        };
+        Ok(RatioQueryBuilder::new(table_name, matches).nulls(column_identifier, nulls))
+    }
+
+    async fn evaluate_format(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        // Get the table name from the validation context
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+
+        let counts = self.format_query(table_name)?.execute(ctx).await?;
+        let Some(match_ratio) = counts.ratio() else {
+            return Ok(ConstraintResult::empty_table());
+        };
+        let total = counts.total;
+
+        // Credit card detection bounds the matches from above and is not adjusted
+        let adjustment = match &self.format {
+            FormatType::CreditCard { detect_only: true } => None,
+            _ => self.options.threshold_adjustment,
+        };
+        let adjusted = AdjustedThreshold::new(self.threshold, adjustment, total)?;
+
+        // Determine success based on format type and threshold
+        let is_success = match &self.format {
+            FormatType::CreditCard { detect_only: true } => {
+                // For credit card detection, we want the ratio to be <= threshold
+                match_ratio <= self.threshold
+            }
+            _ => {
+                // For other formats, we want the ratio to be >= threshold
+                adjusted.passes(match_ratio)
+            }
+        };
+
+        let result = if is_success {
+            ConstraintResult::success_with_metric(match_ratio)
+        } else {
+            let message = match &self.format {
+                FormatType::CreditCard { detect_only: true } => {
+                    format!(
+                        "Credit card detection ratio {match_ratio:.3} exceeds threshold {:.3}",
+                        self.threshold
+                    )
+                }
+                _ => {
+                    let desc = self.format.description();
+                    format!(
+                        "Format validation ratio {match_ratio:.3} is below threshold {:.3} - values that {desc}",
+                        self.threshold
+                    )
+                }
+            };
+
+            ConstraintResult::failure_with_metric(match_ratio, message)
+        };
+        Ok(adjusted.annotate(result, match_ratio))
+    }
+
     /// Creates a format constraint for email validation.
     pub fn email(column: impl Into<String>, threshold: f64) -> Result<Self> {
         Self::new(
@@ -728,6 +1031,56 @@ impl FormatConstraint {
             FormatOptions::new().trim_before_check(true),
         )
     }
+
+    /// Creates a format constraint for IBAN validation, including the checksum.
+    pub fn iban(column: impl Into<String>, threshold: f64) -> Result<Self> {
+        Self::new(
+            column,
+            FormatType::Iban,
+            threshold,
+            FormatOptions::new().trim_before_check(true),
+        )
+    }
+
+    /// Creates a format constraint for MAC address validation.
+    pub fn mac_address(column: impl Into<String>, threshold: f64) -> Result<Self> {
+        Self::new(
+            column,
+            FormatType::MacAddress,
+            threshold,
+            FormatOptions::default(),
+        )
+    }
+
+    /// Creates a format constraint for semantic version validation.
+    pub fn semver(column: impl Into<String>, threshold: f64) -> Result<Self> {
+        Self::new(
+            column,
+            FormatType::SemVer,
+            threshold,
+            FormatOptions::default(),
+        )
+    }
+
+    /// Creates a format constraint for ISO 3166-1 alpha-2 country code validation.
+    pub fn country_code(column: impl Into<String>, threshold: f64) -> Result<Self> {
+        Self::new(
+            column,
+            FormatType::CountryCodeIso3166,
+            threshold,
+            FormatOptions::default(),
+        )
+    }
+
+    /// Creates a format constraint for ISO 4217 currency code validation.
+    pub fn currency_code(column: impl Into<String>, threshold: f64) -> Result<Self> {
+        Self::new(
+            column,
+            FormatType::CurrencyCodeIso4217,
+            threshold,
+            FormatOptions::default(),
+        )
+    }
 }
 
 #[async_trait]
@@ -738,107 +1091,14 @@ impl Constraint for FormatConstraint {
         threshold = %self.threshold
     ))]
     async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
-        // Get the table name from the validation context
-        let validation_ctx = current_validation_context();
-        let table_name = validation_ctx.table_name();
-
-        let column_identifier = SqlSecurity::escape_identifier(&self.column)?;
-        let pattern = self.format.get_pattern()?;
-        let escaped_pattern = SqlSecurity::validate_regex_pattern(&pattern)?;
-
-        // Build the SQL based on options
-        let column_expr = if self.options.trim_before_check {
-            format!("TRIM({column_identifier})")
-        } else {
-            column_identifier.clone()
-        };
-
-        let pattern_operator = if self.options.case_sensitive {
-            "~"
-        } else {
-            "~*"
-        };
-
-        let sql = if self.options.null_is_valid {
-            format!(
-                "SELECT 
-                    COUNT(CASE WHEN {column_expr} {pattern_operator} '{escaped_pattern}' OR {column_identifier} IS NULL THEN 1 END) as matches,
-                    COUNT(*) as total
-                 FROM {table_name}"
-            )
-        } else {
-            format!(
-                "SELECT 
-                    COUNT(CASE WHEN {column_expr} {pattern_operator} '{escaped_pattern}' THEN 1 END) as matches,
-                    COUNT(*) as total
-                 FROM {table_name}"
-            )
-        };
-
-        let df = ctx.sql(&sql).await?;
-        let batches = df.collect().await?;
-
-        if batches.is_empty() {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        let batch = &batches[0];
-        if batch.num_rows() == 0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        let matches = batch
-            .column(0)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract match count".to_string()))?
-            .value(0) as f64;
-
-        let total = batch
-            .column(1)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract total count".to_string()))?
-            .value(0) as f64;
-
-        if total == 0.0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        let match_ratio = matches / total;
-
-        // Determine success based on format type and threshold
-        let is_success = match &self.format {
-            FormatType::CreditCard { detect_only: true } => {
-                // For credit card detection, we want the ratio to be <= threshold
-                match_ratio <= self.threshold
+        self.register_functions(ctx);
+        let result = self.evaluate_format(ctx).await?;
+        match &self.quarantine {
+            Some(sink) if !result.status.is_skipped() => {
+                let summary = self.quarantine_rows(ctx, sink).await?;
+                Ok(result.with_quarantine(summary))
             }
-            _ => {
-                // For other formats, we want the ratio to be >= threshold
-                match_ratio >= self.threshold
-            }
-        };
-
-        if is_success {
-            Ok(ConstraintResult::success_with_metric(match_ratio))
-        } else {
-            let message = match &self.format {
-                FormatType::CreditCard { detect_only: true } => {
-                    format!(
-                        "Credit card detection ratio {match_ratio:.3} exceeds threshold {:.3}",
-                        self.threshold
-                    )
-                }
-                _ => {
-                    let desc = self.format.description();
-                    format!(
-                        "Format validation ratio {match_ratio:.3} is below threshold {:.3} - values that {desc}",
-                        self.threshold
-                    )
-                }
-            };
-
-            Ok(ConstraintResult::failure_with_metric(match_ratio, message))
+            _ => Ok(result),
         }
     }
 
@@ -846,10 +1106,34 @@ impl Constraint for FormatConstraint {
         self.format.name()
     }
 
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Validity)
+    }
+
     fn column(&self) -> Option<&str> {
         Some(&self.column)
     }
 
+    fn generate_sql(&self, table_name: &str) -> Result<Vec<String>> {
+        Ok(vec![self.format_query(table_name)?.sql()])
+    }
+
+    fn describe(&self) -> String {
+        match &self.format {
+            FormatType::CreditCard { detect_only: true } => format!(
+                "{} must contain credit card numbers in at most {} of rows",
+                self.column,
+                describe_percent(self.threshold)
+            ),
+            format => format!(
+                "{} must {} {}",
+                self.column,
+                format.requirement(),
+                describe_rows(self.threshold)
+            ),
+        }
+    }
+
     fn metadata(&self) -> ConstraintMetadata {
         let description = match &self.format {
             FormatType::CreditCard { detect_only: true } => {
@@ -870,7 +1154,7 @@ impl Constraint for FormatConstraint {
             }
         };
 
-        ConstraintMetadata::for_column(&self.column)
+        let mut metadata = ConstraintMetadata::for_column(&self.column)
             .with_description(description)
             .with_custom("format_type", self.format.name())
             .with_custom("threshold", self.threshold.to_string())
@@ -880,7 +1164,11 @@ impl Constraint for FormatConstraint {
                 self.options.trim_before_check.to_string(),
             )
             .with_custom("null_is_valid", self.options.null_is_valid.to_string())
-            .with_custom("constraint_type", "format")
+            .with_custom("constraint_type", "format");
+        if let Some(adjustment) = &self.options.threshold_adjustment {
+            metadata = metadata.with_custom("threshold_adjustment", adjustment.to_string());
+        }
+        metadata
     }
 }
 
@@ -1071,6 +1359,153 @@ mod tests {
         assert_eq!(constraint.name(), "ipv4");
     }
 
+    /// Evaluates `constraint` against values that must all pass and values that must all fail.
+    async fn assert_fixtures(constraint: &FormatConstraint, valid: &[&str], invalid: &[&str]) {
+        for (values, expected) in [(valid, 1.0), (invalid, 0.0)] {
+            let ctx = create_test_context(values.iter().map(|v| Some(*v)).collect()).await;
+            let result = evaluate_constraint_with_context(constraint, &ctx, "data")
+                .await
+                .unwrap();
+            assert_eq!(result.metric, Some(expected), "{values:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_iban_format() {
+        let constraint = FormatConstraint::iban("text_col", 1.0).unwrap();
+        assert_fixtures(
+            &constraint,
+            &[
+                "GB82WEST12345698765432",
+                "GB82 WEST 1234 5698 7654 32",
+                " DE89370400440532013000 ",
+                "NO9386011117947",
+            ],
+            &[
+                "GB82WEST12345698765433", // checksum
+                "DE8937040044053201300",  // length for the country
+                "gb82west12345698765432", // case
+                "GB82-WEST-1234-5698",
+                "not an iban",
+            ],
+        )
+        .await;
+        assert_eq!(constraint.name(), "iban");
+
+        // Case-insensitive matching upper-cases values before the checksum
+        let constraint = FormatConstraint::new(
+            "text_col",
+            FormatType::Iban,
+            1.0,
+            FormatOptions::case_insensitive(),
+        )
+        .unwrap();
+        assert_fixtures(
+            &constraint,
+            &["gb82west12345698765432"],
+            &["gb82west12345698765433"],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_mac_address_format() {
+        let constraint = FormatConstraint::mac_address("text_col", 1.0).unwrap();
+        assert_fixtures(
+            &constraint,
+            &["00:1A:2B:3C:4D:5E", "00-1a-2b-3c-4d-5e", "001A.2B3C.4D5E"],
+            &[
+                "00:1A:2B:3C:4D",
+                "00:1A-2B:3C:4D:5E",
+                "00:1A:2B:3C:4D:5G",
+                "001A2B3C4D5E",
+            ],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_semver_format() {
+        let constraint = FormatConstraint::semver("text_col", 1.0).unwrap();
+        assert_fixtures(
+            &constraint,
+            &[
+                "1.0.0",
+                "0.10.3",
+                "2.0.0-rc.1",
+                "1.0.0-alpha+build.42",
+                "10.20.30+sha.5114f85",
+            ],
+            &["1.0", "v1.0.0", "01.0.0", "1.0.0-", "1.0.0-01", "1.2.3.4"],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_country_code_format() {
+        let constraint = FormatConstraint::country_code("text_col", 1.0).unwrap();
+        assert_fixtures(
+            &constraint,
+            &["US", "DE", "JP", "ZW"],
+            &["XX", "UK", "USA", "us", "U"],
+        )
+        .await;
+
+        let constraint = FormatConstraint::new(
+            "text_col",
+            FormatType::CountryCodeIso3166,
+            1.0,
+            FormatOptions::case_insensitive(),
+        )
+        .unwrap();
+        assert_fixtures(&constraint, &["us", "De"], &["xx"]).await;
+    }
+
+    #[tokio::test]
+    async fn test_currency_code_format() {
+        let constraint = FormatConstraint::currency_code("text_col", 1.0).unwrap();
+        assert_fixtures(
+            &constraint,
+            &["USD", "EUR", "JPY", "XAU"],
+            &["ABC", "US", "usd", "EURO"],
+        )
+        .await;
+
+        let constraint = FormatConstraint::new(
+            "text_col",
+            FormatType::CurrencyCodeIso4217,
+            1.0,
+            FormatOptions::lenient(),
+        )
+        .unwrap();
+        assert_fixtures(&constraint, &[" usd ", "Chf"], &["abc"]).await;
+    }
+
+    #[tokio::test]
+    async fn test_code_formats_null_handling() {
+        let ctx = create_test_context(vec![Some("EUR"), None, Some("GBP"), None]).await;
+
+        // NULL values are valid by default
+        let constraint = FormatConstraint::currency_code("text_col", 1.0).unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+
+        let constraint = FormatConstraint::new(
+            "text_col",
+            FormatType::CurrencyCodeIso4217,
+            1.0,
+            FormatOptions::strict(),
+        )
+        .unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(0.5));
+    }
+
     #[tokio::test]
     async fn test_ipv6_format() {
         let values = vec![
@@ -1348,6 +1783,11 @@ mod tests {
             FormatType::Json,
             FormatType::Iso8601DateTime,
             FormatType::Regex(r"^\d+$".to_string()),
+            FormatType::Iban,
+            FormatType::MacAddress,
+            FormatType::SemVer,
+            FormatType::CountryCodeIso3166,
+            FormatType::CurrencyCodeIso4217,
         ];
 
         for format in formats {
@@ -1506,4 +1946,44 @@ mod tests {
         assert_eq!(result.metric, Some(0.375)); // 3 out of 8
         assert_eq!(result.status, ConstraintStatus::Success); // 0.375 >= 0.3
     }
+
+    #[test]
+    fn test_generate_sql_reflects_options() -> Result<()> {
+        let options = FormatOptions::new()
+            .case_sensitive(false)
+            .null_is_valid(false);
+        let plain = FormatConstraint::new("email", FormatType::Email, 1.0, options)?
+            .generate_sql("users")?;
+        assert_eq!(plain.len(), 1);
+        assert!(plain[0].contains(r#""email" ~*"#));
+        assert!(plain[0].contains("FROM users"));
+        assert!(!plain[0].contains("TRIM"));
+        assert!(!plain[0].contains("IS NULL"));
+
+        let options = FormatOptions::new()
+            .trim_before_check(true)
+            .case_sensitive(true)
+            .null_is_valid(true);
+        let constraint = FormatConstraint::new("email", FormatType::Email, 1.0, options)?;
+        let sql = constraint.generate_sql("users")?;
+        assert!(sql[0].contains(r#"TRIM("email") ~ '"#));
+        assert!(sql[0].contains(r#"OR "email" IS NULL"#));
+        assert_eq!(sql, constraint.clone().generate_sql("users")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_describe() {
+        let constraint = FormatConstraint::email("email", 0.99).unwrap();
+        assert_eq!(
+            constraint.describe(),
+            "email must match an email format for at least 99% of rows"
+        );
+
+        let constraint = FormatConstraint::credit_card("notes", 0.0, true).unwrap();
+        assert_eq!(
+            constraint.describe(),
+            "notes must contain credit card numbers in at most 0% of rows"
+        );
+    }
 }
@@ -0,0 +1,674 @@
+//! Functional dependency validation between columns.
+//!
+//! Data models often assume that some columns determine another: a zip code
+//! determines the state, a product id the product name. When the assumption
+//! breaks, joins and dimension tables silently grow duplicate-looking rows.
+//! [`FunctionalDependencyConstraint`] groups the rows by the determinant
+//! columns with a single `GROUP BY` and counts the distinct values of the
+//! dependent column in every group. A group conforms when it has at most one
+//! value; the constraint passes when the fraction of conforming groups, or of
+//! rows in conforming groups, is at least the threshold. Failures report the
+//! worst violating groups with their conflicting values.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::constraints::{
+//!     DependencyDenominator, FunctionalDependencyConstraint, NullHandling,
+//! };
+//! use term_guard::core::{Check, Level};
+//!
+//! # fn example() -> term_guard::prelude::Result<()> {
+//! // A country and a zip code determine the state, weighted by rows
+//! let constraint = FunctionalDependencyConstraint::new(vec!["country", "zip_code"], "state")?
+//!     .threshold(0.999)
+//!     .denominator(DependencyDenominator::Rows)
+//!     .dependent_nulls(NullHandling::Include);
+//!
+//! let check = Check::builder("addresses")
+//!     .level(Level::Error)
+//!     .constraint(constraint)
+//!     .build();
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+
+use super::composite_key::{format_examples, key_examples};
+use super::NullHandling;
+use crate::core::{
+    collect_limited, collect_with_metrics, current_validation_context, describe_percent,
+    describe_rows, skipped_without_values, CollectionSummary, Constraint, ConstraintMetadata,
+    ConstraintResult, ConstraintStatus, QualityDimension,
+};
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+use arrow::array::{Array, ArrayRef, Int64Array, ListArray, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt;
+use tracing::{debug, instrument, warn};
+
+/// What the fraction compared with the threshold of a
+/// [`FunctionalDependencyConstraint`] counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DependencyDenominator {
+    /// The fraction of determinant groups with at most one dependent value
+    #[default]
+    Groups,
+    /// The fraction of rows belonging to such groups, so that large
+    /// violating groups weigh more than small ones
+    Rows,
+}
+
+impl fmt::Display for DependencyDenominator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DependencyDenominator::Groups => write!(f, "groups"),
+            DependencyDenominator::Rows => write!(f, "rows"),
+        }
+    }
+}
+
+/// A constraint that checks that determinant columns determine a dependent column.
+///
+/// NULL values are handled with a [`NullHandling`] for each side, both
+/// defaulting to [`NullHandling::Exclude`]:
+///
+/// | Handling | Determinant columns | Dependent column |
+/// |----------|---------------------|------------------|
+/// | `Exclude` | Rows with a NULL in any determinant column are left out | NULL values are ignored; a group of only NULLs conforms |
+/// | `Include` | NULL is grouped like any other value | NULL is one more value, conflicting with any other |
+/// | `Distinct` | Each row with a NULL determinant column is a group of its own, which conforms | Each NULL is a value of its own, conflicting with every other row |
+///
+/// The metric is the fraction of conforming groups or rows, depending on the
+/// [`DependencyDenominator`].
+#[derive(Debug, Clone)]
+pub struct FunctionalDependencyConstraint {
+    determinant: Vec<String>,
+    dependent: String,
+    threshold: f64,
+    denominator: DependencyDenominator,
+    determinant_nulls: NullHandling,
+    dependent_nulls: NullHandling,
+    max_violations_reported: usize,
+}
+
+impl FunctionalDependencyConstraint {
+    /// Creates a constraint that every group of determinant values must satisfy.
+    ///
+    /// By default the threshold is 1.0, groups are counted, NULL values are
+    /// excluded on both sides and ten violating groups are reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `determinant` - The columns whose values should determine the dependent column
+    /// * `dependent` - The column that should have one value per group
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no determinant column is given, a column name is
+    /// invalid, or the dependent column is one of the determinant columns.
+    pub fn new<I, S>(determinant: I, dependent: impl Into<String>) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let determinant: Vec<String> = determinant.into_iter().map(Into::into).collect();
+        let dependent = dependent.into();
+        if determinant.is_empty() {
+            return Err(TermError::validation_failed(
+                "functional_dependency",
+                "At least one determinant column must be specified",
+            ));
+        }
+        for column in determinant.iter().chain(std::iter::once(&dependent)) {
+            SqlSecurity::validate_identifier(column)?;
+        }
+        if determinant.contains(&dependent) {
+            return Err(TermError::validation_failed(
+                "functional_dependency",
+                format!("Column '{dependent}' cannot determine itself"),
+            ));
+        }
+
+        Ok(Self {
+            determinant,
+            dependent,
+            threshold: 1.0,
+            denominator: DependencyDenominator::default(),
+            determinant_nulls: NullHandling::Exclude,
+            dependent_nulls: NullHandling::Exclude,
+            max_violations_reported: 10,
+        })
+    }
+
+    /// Sets the minimum fraction of groups or rows that must conform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the threshold is not between 0.0 and 1.0.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&threshold),
+            "Threshold must be between 0.0 and 1.0"
+        );
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets whether the threshold applies to groups or to rows.
+    pub fn denominator(mut self, denominator: DependencyDenominator) -> Self {
+        self.denominator = denominator;
+        self
+    }
+
+    /// Sets how NULL values of the determinant columns are handled.
+    pub fn determinant_nulls(mut self, handling: NullHandling) -> Self {
+        self.determinant_nulls = handling;
+        self
+    }
+
+    /// Sets how NULL values of the dependent column are handled.
+    pub fn dependent_nulls(mut self, handling: NullHandling) -> Self {
+        self.dependent_nulls = handling;
+        self
+    }
+
+    /// Sets the maximum number of violating groups to report.
+    ///
+    /// Defaults to 10. Set to 0 to disable violation example collection.
+    pub fn max_violations_reported(mut self, max_violations: usize) -> Self {
+        self.max_violations_reported = max_violations;
+        self
+    }
+
+    /// Returns the determinant columns.
+    pub fn determinant(&self) -> &[String] {
+        &self.determinant
+    }
+
+    /// Returns the dependent column.
+    pub fn dependent(&self) -> &str {
+        &self.dependent
+    }
+
+    /// Builds the query returning one row per determinant group.
+    ///
+    /// Every group has its row count, the number of values of the dependent
+    /// column under its NULL handling and whether its determinant has a NULL.
+    /// Violation queries also select the determinant values and the distinct
+    /// dependent values.
+    fn groups_query(&self, table_name: &str, with_values: bool) -> Result<String> {
+        let determinant = self
+            .determinant
+            .iter()
+            .map(|column| SqlSecurity::escape_identifier(column))
+            .collect::<Result<Vec<_>>>()?;
+        let dependent = SqlSecurity::escape_identifier(&self.dependent)?;
+        let keys = determinant.join(", ");
+
+        let value_count = match self.dependent_nulls {
+            NullHandling::Exclude => format!("COUNT(DISTINCT {dependent})"),
+            NullHandling::Include => format!(
+                "COUNT(DISTINCT {dependent}) + CASE WHEN COUNT({dependent}) < COUNT(*) THEN 1 ELSE 0 END"
+            ),
+            NullHandling::Distinct => {
+                format!("COUNT(DISTINCT {dependent}) + COUNT(*) - COUNT({dependent})")
+            }
+        };
+        let null_key: Vec<String> = determinant
+            .iter()
+            .map(|column| format!("{column} IS NULL"))
+            .collect();
+        let filter = match self.determinant_nulls {
+            NullHandling::Exclude => {
+                let participating: Vec<String> = determinant
+                    .iter()
+                    .map(|column| format!("{column} IS NOT NULL"))
+                    .collect();
+                format!(" WHERE {}", participating.join(" AND "))
+            }
+            NullHandling::Include | NullHandling::Distinct => String::new(),
+        };
+        let values = if with_values {
+            format!(
+                "{keys}, COUNT({dependent}) AS non_null_count, \
+                 ARRAY_AGG(DISTINCT CAST({dependent} AS VARCHAR)) AS dependent_values, "
+            )
+        } else {
+            String::new()
+        };
+
+        Ok(format!(
+            "SELECT {values}COUNT(*) AS row_count, {value_count} AS value_count, \
+             MAX(CASE WHEN {} THEN 1 ELSE 0 END) AS null_key \
+             FROM {table_name}{filter} GROUP BY {keys}",
+            null_key.join(" OR ")
+        ))
+    }
+
+    /// Returns the SQL condition selecting conforming groups.
+    fn conforming_condition(&self) -> &'static str {
+        match self.determinant_nulls {
+            NullHandling::Distinct => "(null_key = 1 OR value_count <= 1)",
+            NullHandling::Exclude | NullHandling::Include => "value_count <= 1",
+        }
+    }
+
+    /// Returns the SQL expression counting the groups a group row stands for.
+    fn group_weight(&self) -> &'static str {
+        match self.determinant_nulls {
+            // Rows with a NULL determinant are groups of their own
+            NullHandling::Distinct => "CASE WHEN null_key = 1 THEN row_count ELSE 1 END",
+            NullHandling::Exclude | NullHandling::Include => "1",
+        }
+    }
+
+    /// Builds the query counting groups and rows, and those that violate the dependency.
+    fn summary_query(&self, table_name: &str) -> Result<String> {
+        let groups = self.groups_query(table_name, false)?;
+        let conforming = self.conforming_condition();
+        let sql = format!(
+            "SELECT
+                COALESCE(SUM({}), 0) AS group_count,
+                COALESCE(SUM(CASE WHEN {conforming} THEN 0 ELSE 1 END), 0) AS violating_groups,
+                COALESCE(SUM(row_count), 0) AS row_count,
+                COALESCE(SUM(CASE WHEN {conforming} THEN 0 ELSE row_count END), 0) AS violating_rows
+             FROM ({groups}) AS groups",
+            self.group_weight()
+        );
+        debug!("Generated functional dependency query: {}", sql);
+        Ok(sql)
+    }
+
+    /// Builds the query listing violating groups, most values and rows first.
+    fn violations_query(&self, table_name: &str) -> Result<String> {
+        let groups = self.groups_query(table_name, true)?;
+        let order_keys: String = self
+            .determinant
+            .iter()
+            .map(|column| SqlSecurity::escape_identifier(column).map(|c| format!(", {c}")))
+            .collect::<Result<_>>()?;
+        let sql = format!(
+            "SELECT * FROM ({groups}) AS groups
+             WHERE NOT {}
+             ORDER BY value_count DESC, row_count DESC{order_keys}
+             LIMIT {}",
+            self.conforming_condition(),
+            self.max_violations_reported
+        );
+        debug!("Generated functional dependency violations query: {}", sql);
+        Ok(sql)
+    }
+
+    /// Collects the violating groups with their conflicting dependent values.
+    async fn collect_violations(
+        &self,
+        ctx: &SessionContext,
+    ) -> Result<(Vec<String>, CollectionSummary)> {
+        if self.max_violations_reported == 0 {
+            return Ok((Vec::new(), CollectionSummary::default()));
+        }
+        let validation_ctx = current_validation_context();
+        let sql = self.violations_query(validation_ctx.table_name())?;
+        let df = ctx.sql(&sql).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                self.name(),
+                format!("Failed to execute violations query: {e}"),
+            )
+        })?;
+        // The dependent values of a group are aggregated into a single row, which
+        // can be large for a group with many of them
+        let collected = collect_limited(df).await?;
+
+        let key_count = self.determinant.len();
+        let key_indices: Vec<usize> = (0..key_count).collect();
+        let mut violations = Vec::with_capacity(self.max_violations_reported);
+        for batch in collected.iter()? {
+            let batch = &batch?;
+            let keys = key_examples(
+                &[batch.project(&key_indices)?],
+                &self.determinant,
+                batch.num_rows(),
+            )?;
+            let non_null_counts = int64_column(batch, key_count)?;
+            let values = batch
+                .column(key_count + 1)
+                .as_any()
+                .downcast_ref::<ListArray>()
+                .ok_or_else(|| {
+                    TermError::Internal("Failed to extract dependent values".to_string())
+                })?;
+            let row_counts = int64_column(batch, key_count + 2)?;
+
+            for (row, key) in keys.iter().enumerate() {
+                let mut conflicting: Vec<String> = string_values(&values.value(row))?
+                    .into_iter()
+                    .map(|v| validation_ctx.redact(&self.dependent, &v).into_owned())
+                    .collect();
+                conflicting.sort();
+                let has_null = non_null_counts.value(row) < row_counts.value(row);
+                if has_null && self.dependent_nulls != NullHandling::Exclude {
+                    conflicting.insert(0, "NULL".to_string());
+                }
+                violations.push(format!(
+                    "{key} -> {{{}}} ({} rows)",
+                    format_examples(&conflicting),
+                    row_counts.value(row)
+                ));
+            }
+        }
+        Ok((violations, collected.summary()))
+    }
+
+    fn determinant_label(&self) -> String {
+        match self.determinant.as_slice() {
+            [column] => format!("'{column}'"),
+            columns => format!("({})", columns.join(", ")),
+        }
+    }
+}
+
+fn int64_column(batch: &RecordBatch, index: usize) -> Result<&Int64Array> {
+    batch
+        .column(index)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .ok_or_else(|| TermError::Internal(format!("Expected integer column at index {index}")))
+}
+
+/// Returns the non-NULL strings of a list element; `CAST(... AS VARCHAR)` may
+/// produce `Utf8View`.
+fn string_values(values: &ArrayRef) -> Result<Vec<String>> {
+    let values = cast(values, &DataType::Utf8)?;
+    let values = values
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .ok_or_else(|| TermError::Internal("Failed to extract dependent values".to_string()))?;
+    Ok(values.iter().flatten().map(str::to_string).collect())
+}
+
+#[async_trait]
+impl Constraint for FunctionalDependencyConstraint {
+    #[instrument(skip(self, ctx), fields(
+        determinant = ?self.determinant,
+        dependent = %self.dependent,
+        threshold = %self.threshold
+    ))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+
+        let df = ctx
+            .sql(&self.summary_query(table_name)?)
+            .await
+            .map_err(|e| {
+                TermError::constraint_evaluation(
+                    self.name(),
+                    format!("Functional dependency query failed: {e}"),
+                )
+            })?;
+        let batches = collect_with_metrics(df).await?;
+
+        if batches.is_empty() || batches[0].num_rows() == 0 {
+            return Ok(ConstraintResult::skipped(
+                "No determinant values to validate",
+            ));
+        }
+        let batch = &batches[0];
+        let group_count = int64_column(batch, 0)?.value(0);
+        let violating_groups = int64_column(batch, 1)?.value(0);
+        let row_count = int64_column(batch, 2)?.value(0);
+        let violating_rows = int64_column(batch, 3)?.value(0);
+
+        if group_count == 0 {
+            return skipped_without_values(ctx, table_name, "No determinant values to validate")
+                .await;
+        }
+
+        let (total, violating) = match self.denominator {
+            DependencyDenominator::Groups => (group_count, violating_groups),
+            DependencyDenominator::Rows => (row_count, violating_rows),
+        };
+        let conforming = (total - violating) as f64 / total as f64;
+        if conforming >= self.threshold {
+            return Ok(ConstraintResult::success_with_metric(conforming));
+        }
+
+        let mut message = format!(
+            "Functional dependency violation: {violating_groups} of {group_count} groups of {} have more than one value of '{}' ({violating_rows} rows; {:.2}% of {} conforming, threshold {:.2}%)",
+            self.determinant_label(),
+            self.dependent,
+            conforming * 100.0,
+            self.denominator,
+            self.threshold * 100.0
+        );
+        let (violations, collection) = self.collect_violations(ctx).await?;
+        if !violations.is_empty() {
+            message.push_str(&format!(". Violations: [{}]", violations.join("; ")));
+        }
+
+        warn!("{}", message);
+
+        let mut details = BTreeMap::new();
+        collection.record(&mut details);
+        Ok(ConstraintResult {
+            status: ConstraintStatus::Failure,
+            metric: Some(conforming),
+            message: Some(message),
+            quarantine: None,
+            constraint_id: None,
+            details,
+        })
+    }
+
+    fn name(&self) -> &str {
+        "functional_dependency"
+    }
+
+    fn column(&self) -> Option<&str> {
+        Some(&self.dependent)
+    }
+
+    fn describe(&self) -> String {
+        let determinant = match self.determinant.as_slice() {
+            [column] => column.clone(),
+            columns => format!("({})", columns.join(", ")),
+        };
+        let share = match self.denominator {
+            DependencyDenominator::Rows => describe_rows(self.threshold),
+            DependencyDenominator::Groups if self.threshold >= 1.0 => "in every group".to_string(),
+            DependencyDenominator::Groups => format!(
+                "for at least {} of groups",
+                describe_percent(self.threshold)
+            ),
+        };
+        format!(
+            "{} must be determined by {determinant} {share}",
+            self.dependent
+        )
+    }
+
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Consistency)
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        ConstraintMetadata::for_columns(
+            self.determinant
+                .iter()
+                .chain(std::iter::once(&self.dependent))
+                .cloned(),
+        )
+        .with_description(self.describe())
+        .with_custom("determinant", self.determinant.join(","))
+        .with_custom("dependent", self.dependent.clone())
+        .with_custom("denominator", self.denominator.to_string())
+        .with_custom("determinant_nulls", self.determinant_nulls.to_string())
+        .with_custom("dependent_nulls", self.dependent_nulls.to_string())
+        .with_custom("threshold", self.threshold.to_string())
+        .with_custom("constraint_type", "consistency")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::evaluate_constraint_with_context;
+
+    /// Addresses where zip 94105 maps to CA and NV, and 10001 to NY and NULL.
+    async fn addresses_context() -> SessionContext {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE TABLE addresses (country VARCHAR, zip VARCHAR, state VARCHAR) AS VALUES
+                ('US', '94105', 'CA'),
+                ('US', '94105', 'CA'),
+                ('US', '94105', 'NV'),
+                ('US', '10001', 'NY'),
+                ('US', '10001', NULL),
+                ('US', '60601', 'IL'),
+                ('US', '60601', 'IL'),
+                ('CA', '60601', 'ON'),
+                ('US', NULL, 'TX'),
+                ('US', NULL, 'WA'),
+                ('US', '73301', NULL)",
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+        ctx
+    }
+
+    async fn evaluate(
+        ctx: &SessionContext,
+        constraint: &FunctionalDependencyConstraint,
+    ) -> ConstraintResult {
+        evaluate_constraint_with_context(constraint, ctx, "addresses")
+            .await
+            .unwrap()
+    }
+
+    fn zip_determines_state() -> FunctionalDependencyConstraint {
+        FunctionalDependencyConstraint::new(vec!["zip"], "state").unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_violations_reported_with_conflicting_values() {
+        let ctx = addresses_context().await;
+
+        // Groups 94105, 10001, 60601 and 73301; 60601 is IL and ON across countries
+        let result = evaluate(&ctx, &zip_determines_state()).await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(0.5));
+        let message = result.message.unwrap();
+        assert!(
+            message.contains("2 of 4 groups of 'zip' have more than one value of 'state'"),
+            "{message}"
+        );
+        assert!(
+            message
+                .contains("Violations: [60601 -> {IL, ON} (3 rows); 94105 -> {CA, NV} (3 rows)]"),
+            "{message}"
+        );
+
+        // Adding the country resolves 60601
+        let composite = FunctionalDependencyConstraint::new(vec!["country", "zip"], "state")
+            .unwrap()
+            .max_violations_reported(1);
+        let result = evaluate(&ctx, &composite).await;
+        assert_eq!(result.metric, Some(4.0 / 5.0));
+        let message = result.message.unwrap();
+        assert!(
+            message.ends_with("Violations: [(US, 94105) -> {CA, NV} (3 rows)]"),
+            "{message}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_denominators_and_threshold() {
+        let ctx = addresses_context().await;
+
+        // 6 of the 9 rows with a zip are in violating groups
+        let rows = zip_determines_state().denominator(DependencyDenominator::Rows);
+        assert_eq!(evaluate(&ctx, &rows).await.metric, Some(3.0 / 9.0));
+
+        let lenient = zip_determines_state().threshold(0.5);
+        let result = evaluate(&ctx, &lenient).await;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.5));
+    }
+
+    #[tokio::test]
+    async fn test_null_handling() {
+        let ctx = addresses_context().await;
+
+        // A NULL state conflicts with NY but a group of only NULLs conforms
+        let included = zip_determines_state().dependent_nulls(NullHandling::Include);
+        let result = evaluate(&ctx, &included).await;
+        assert_eq!(result.metric, Some(1.0 / 4.0));
+        let message = result.message.unwrap();
+        assert!(
+            message.contains("10001 -> {NULL, NY} (2 rows)"),
+            "{message}"
+        );
+
+        // The NULL zip group has TX and WA
+        let grouped = zip_determines_state().determinant_nulls(NullHandling::Include);
+        assert_eq!(evaluate(&ctx, &grouped).await.metric, Some(2.0 / 5.0));
+
+        // Each NULL zip row conforms on its own
+        let distinct = zip_determines_state()
+            .determinant_nulls(NullHandling::Distinct)
+            .dependent_nulls(NullHandling::Distinct);
+        assert_eq!(evaluate(&ctx, &distinct).await.metric, Some(3.0 / 6.0));
+    }
+
+    #[tokio::test]
+    async fn test_no_determinant_values() {
+        let ctx = addresses_context().await;
+        ctx.sql("CREATE TABLE no_zips AS SELECT * FROM addresses WHERE zip IS NULL")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        let result = evaluate_constraint_with_context(&zip_determines_state(), &ctx, "no_zips")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Skipped);
+    }
+
+    #[test]
+    fn test_constraint_configuration() {
+        assert!(FunctionalDependencyConstraint::new(Vec::<String>::new(), "state").is_err());
+        assert!(FunctionalDependencyConstraint::new(vec!["zip"], "zip").is_err());
+        assert!(FunctionalDependencyConstraint::new(vec!["zip; --"], "state").is_err());
+
+        let constraint = zip_determines_state().threshold(0.999);
+        assert_eq!(constraint.name(), "functional_dependency");
+        assert_eq!(
+            constraint.describe(),
+            "state must be determined by zip for at least 99.9% of groups"
+        );
+        assert_eq!(
+            FunctionalDependencyConstraint::new(vec!["country", "zip"], "state")
+                .unwrap()
+                .denominator(DependencyDenominator::Rows)
+                .describe(),
+            "state must be determined by (country, zip) for every row"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Threshold must be between 0.0 and 1.0")]
+    fn test_invalid_threshold() {
+        zip_determines_state().threshold(1.5);
+    }
+}
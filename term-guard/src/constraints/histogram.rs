@@ -1,12 +1,18 @@
 //! Histogram analysis constraint for value distribution analysis.
 
 use crate::core::{
-    current_validation_context, Constraint, ConstraintMetadata, ConstraintResult, ConstraintStatus,
+    collect_limited, current_validation_context, skipped_without_values, BoundedFrequencies,
+    Constraint, ConstraintMetadata, ConstraintResult, ConstraintStatus, MemoryStrategy,
+    MEMORY_APPROXIMATE_DETAIL,
 };
 use crate::prelude::*;
-use arrow::array::{Array, LargeStringArray, StringViewArray};
+use arrow::array::{Array, LargeStringArray, StringArray, StringViewArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
 use async_trait::async_trait;
 use datafusion::prelude::*;
+use futures::StreamExt;
+use std::collections::BTreeMap;
 use std::fmt;
 use std::sync::Arc;
 use tracing::instrument;
@@ -32,6 +38,10 @@ pub struct Histogram {
     pub distinct_count: usize,
     /// Number of null values
     pub null_count: i64,
+    /// Whether the buckets were counted with a sketch beyond a cap on tracked
+    /// values, leaving out the least frequent values and overestimating counts;
+    /// see [`MemoryStrategy::Sketched`]
+    pub approximate: bool,
 }
 
 impl Histogram {
@@ -43,6 +53,7 @@ impl Histogram {
             total_count,
             distinct_count,
             null_count,
+            approximate: false,
         }
     }
 
@@ -156,6 +167,7 @@ pub struct HistogramConstraint {
     column: String,
     assertion: HistogramAssertion,
     assertion_description: String,
+    memory_strategy: MemoryStrategy,
 }
 
 impl fmt::Debug for HistogramConstraint {
@@ -163,6 +175,7 @@ impl fmt::Debug for HistogramConstraint {
         f.debug_struct("HistogramConstraint")
             .field("column", &self.column)
             .field("assertion_description", &self.assertion_description)
+            .field("memory_strategy", &self.memory_strategy)
             .finish()
     }
 }
@@ -179,6 +192,7 @@ impl HistogramConstraint {
             column: column.into(),
             assertion,
             assertion_description: "custom assertion".to_string(),
+            memory_strategy: MemoryStrategy::default(),
         }
     }
 
@@ -198,6 +212,138 @@ impl HistogramConstraint {
             column: column.into(),
             assertion,
             assertion_description: description.into(),
+            memory_strategy: MemoryStrategy::default(),
+        }
+    }
+
+    /// Sets how the histogram bounds its memory.
+    ///
+    /// With [`MemoryStrategy::Sketched`] values are read as a stream and at most
+    /// `max_tracked_values` of them are kept, making the histogram approximate
+    /// for columns with more distinct values; see [`Histogram::approximate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] for an invalid strategy or hash
+    /// partitioning, which histograms don't support.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::HistogramConstraint;
+    /// use term_guard::core::MemoryStrategy;
+    /// use std::sync::Arc;
+    ///
+    /// let constraint = HistogramConstraint::new("country", Arc::new(|hist| hist.bucket_count() > 0))
+    ///     .with_memory_strategy(MemoryStrategy::sketched(10_000))?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_memory_strategy(mut self, strategy: MemoryStrategy) -> Result<Self> {
+        strategy.check_histogram()?;
+        self.memory_strategy = strategy;
+        Ok(self)
+    }
+
+    /// Counts the values of the column as a stream, tracking at most
+    /// `max_tracked_values` of them.
+    async fn sketched_histogram(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        max_tracked_values: usize,
+        epsilon: f64,
+        delta: f64,
+    ) -> Result<Histogram> {
+        let sql = format!(
+            "SELECT CAST({} AS VARCHAR) AS value FROM {table_name}",
+            self.column
+        );
+        let df = ctx.sql(&sql).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                self.name(),
+                format!("Failed to execute histogram query: {e}"),
+            )
+        })?;
+
+        let mut frequencies = BoundedFrequencies::new(max_tracked_values, epsilon, delta);
+        let mut total_count = 0i64;
+        let mut null_count = 0i64;
+        let mut stream = df.execute_stream().await?;
+        while let Some(batch) = stream.next().await {
+            let values = cast(batch?.column(0), &DataType::Utf8)?;
+            let values = values
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .ok_or_else(|| {
+                    TermError::constraint_evaluation(self.name(), "Failed to extract string values")
+                })?;
+            total_count += values.len() as i64;
+            for value in values.iter() {
+                match value {
+                    Some(value) => frequencies.add(value),
+                    None => null_count += 1,
+                }
+            }
+        }
+
+        let approximate = frequencies.is_approximate();
+        let non_null_count = total_count - null_count;
+        let buckets = frequencies
+            .into_counts()
+            .into_iter()
+            .map(|(value, count)| HistogramBucket {
+                value,
+                count: count as i64,
+                ratio: count as f64 / non_null_count as f64,
+            })
+            .collect();
+        let mut histogram = Histogram::new(buckets, total_count, null_count);
+        histogram.approximate = approximate;
+        Ok(histogram)
+    }
+
+    /// Applies the assertion to `histogram`.
+    fn histogram_result(
+        &self,
+        histogram: &Histogram,
+        mut details: BTreeMap<String, f64>,
+    ) -> ConstraintResult {
+        let status = if (self.assertion)(histogram) {
+            ConstraintStatus::Success
+        } else {
+            ConstraintStatus::Failure
+        };
+
+        let message = if status == ConstraintStatus::Failure {
+            let most_common_pct = histogram.most_common_ratio() * 100.0;
+            let null_pct = histogram.null_ratio() * 100.0;
+            let approximate = if histogram.approximate {
+                " (approximate)"
+            } else {
+                ""
+            };
+            Some(format!(
+                "Histogram assertion '{}' failed for column '{}'. Distribution{approximate}: {} distinct values, most common ratio: {most_common_pct:.2}%, null ratio: {null_pct:.2}%",
+                self.assertion_description,
+                self.column,
+                histogram.distinct_count
+            ))
+        } else {
+            None
+        };
+
+        if histogram.approximate {
+            details.insert(MEMORY_APPROXIMATE_DETAIL.to_string(), 1.0);
+        }
+
+        // Store histogram entropy as metric
+        ConstraintResult {
+            status,
+            metric: Some(histogram.entropy()),
+            message,
+            quarantine: None,
+            constraint_id: None,
+            details,
         }
     }
 }
@@ -210,6 +356,21 @@ impl Constraint for HistogramConstraint {
         let validation_ctx = current_validation_context();
         let table_name = validation_ctx.table_name();
 
+        if let MemoryStrategy::Sketched {
+            max_tracked_values,
+            epsilon,
+            delta,
+        } = self.memory_strategy
+        {
+            let histogram = self
+                .sketched_histogram(ctx, table_name, max_tracked_values, epsilon, delta)
+                .await?;
+            if histogram.buckets.is_empty() {
+                return skipped_without_values(ctx, table_name, "No data to analyze").await;
+            }
+            return Ok(self.histogram_result(&histogram, BTreeMap::new()));
+        }
+
         // SQL query to compute value frequencies
         let sql = format!(
             r#"
@@ -247,10 +408,12 @@ impl Constraint for HistogramConstraint {
             )
         })?;
 
-        let batches = df.collect().await?;
+        // Columns with many distinct values may exceed the collection limits, in
+        // which case the histogram covers the most frequent values
+        let collected = collect_limited(df).await?;
 
-        if batches.is_empty() || batches[0].num_rows() == 0 {
-            return Ok(ConstraintResult::skipped("No data to analyze"));
+        if collected.is_empty() && !collected.is_truncated() {
+            return skipped_without_values(ctx, table_name, "No data to analyze").await;
         }
 
         // Extract histogram data from results
@@ -258,7 +421,8 @@ impl Constraint for HistogramConstraint {
         let mut total_count = 0i64;
         let mut null_count = 0i64;
 
-        for batch in &batches {
+        for batch in collected.iter()? {
+            let batch = batch?;
             // DataFusion might return various string types
             let values_col = batch.column(0);
             let value_strings: Vec<String> = match values_col.data_type() {
@@ -360,34 +524,9 @@ impl Constraint for HistogramConstraint {
         // Create histogram
         let histogram = Histogram::new(buckets, total_count, null_count);
 
-        // Apply assertion
-        let assertion_result = (self.assertion)(&histogram);
-
-        let status = if assertion_result {
-            ConstraintStatus::Success
-        } else {
-            ConstraintStatus::Failure
-        };
-
-        let message = if status == ConstraintStatus::Failure {
-            let most_common_pct = histogram.most_common_ratio() * 100.0;
-            let null_pct = histogram.null_ratio() * 100.0;
-            Some(format!(
-                "Histogram assertion '{}' failed for column '{}'. Distribution: {} distinct values, most common ratio: {most_common_pct:.2}%, null ratio: {null_pct:.2}%",
-                self.assertion_description,
-                self.column,
-                histogram.distinct_count
-            ))
-        } else {
-            None
-        };
-
-        // Store histogram entropy as metric
-        Ok(ConstraintResult {
-            status,
-            metric: Some(histogram.entropy()),
-            message,
-        })
+        let mut details = BTreeMap::new();
+        collected.summary().record(&mut details);
+        Ok(self.histogram_result(&histogram, details))
     }
 
     fn name(&self) -> &str {
@@ -399,13 +538,18 @@ impl Constraint for HistogramConstraint {
     }
 
     fn metadata(&self) -> ConstraintMetadata {
-        ConstraintMetadata::for_column(&self.column)
+        let metadata = ConstraintMetadata::for_column(&self.column)
             .with_description(format!(
                 "Analyzes value distribution in column '{}' and applies assertion: {}",
                 self.column, self.assertion_description
             ))
             .with_custom("assertion", &self.assertion_description)
-            .with_custom("constraint_type", "histogram")
+            .with_custom("constraint_type", "histogram");
+        if self.memory_strategy.is_unbounded() {
+            metadata
+        } else {
+            metadata.with_custom("memory_strategy", self.memory_strategy.to_string())
+        }
     }
 }
 
@@ -766,4 +910,65 @@ mod tests {
             .unwrap();
         assert_eq!(result.status, ConstraintStatus::Success);
     }
+    #[tokio::test]
+    async fn test_sketched_histogram_is_exact_below_the_cap() {
+        let values = vec![Some("A"), Some("B"), Some("A"), None, Some("C"), Some("A")];
+        let ctx = create_test_context_with_data(values).await;
+
+        let assertion: HistogramAssertion = Arc::new(|hist| {
+            !hist.approximate
+                && hist.top_n(3) == vec![("A", 0.6), ("B", 0.2), ("C", 0.2)]
+                && hist.null_count == 1
+        });
+        let exact = HistogramConstraint::new("test_col", assertion.clone());
+        let sketched = HistogramConstraint::new("test_col", assertion)
+            .with_memory_strategy(MemoryStrategy::sketched(3))
+            .unwrap();
+
+        let expected = evaluate_constraint_with_context(&exact, &ctx, "data")
+            .await
+            .unwrap();
+        let actual = evaluate_constraint_with_context(&sketched, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(actual.status, ConstraintStatus::Success);
+        assert_eq!(actual.metric, expected.metric);
+        assert!(!actual.details.contains_key(MEMORY_APPROXIMATE_DETAIL));
+    }
+
+    #[tokio::test]
+    async fn test_sketched_histogram_beyond_the_cap_is_approximate() {
+        let mut values = vec![Some("frequent"); 20];
+        let rare = (0..30).map(|i| format!("rare{i}")).collect::<Vec<_>>();
+        values.extend(rare.iter().map(|value| Some(value.as_str())));
+        let ctx = create_test_context_with_data(values).await;
+
+        let constraint = HistogramConstraint::new(
+            "test_col",
+            Arc::new(|hist| {
+                hist.approximate
+                    && hist.bucket_count() == 5
+                    && hist.top_n(1) == vec![("frequent", 0.4)]
+            }),
+        )
+        .with_memory_strategy(MemoryStrategy::sketched(5))
+        .unwrap();
+
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.details[MEMORY_APPROXIMATE_DETAIL], 1.0);
+        assert_eq!(
+            constraint.metadata().custom["memory_strategy"],
+            "sketched (5 values, epsilon 0.001, delta 0.01)"
+        );
+    }
+
+    #[test]
+    fn test_hash_partitioning_is_rejected() {
+        let result = HistogramConstraint::new("test_col", Arc::new(|_| true))
+            .with_memory_strategy(MemoryStrategy::hash_partitioned(4));
+        assert!(matches!(result, Err(TermError::Configuration(_))));
+    }
 }
@@ -0,0 +1,672 @@
+//! Assertions of a metric against an aggregate of its history.
+//!
+//! [`HistoricalAssertionConstraint`] computes a metric on the validated table,
+//! loads the metric's previous values from a [`MetricsRepository`] and compares
+//! the current value with an aggregate of a window of them, such as the mean of
+//! the last 7 runs. Unlike an anomaly check, which asks a detection strategy
+//! whether a value is unusual, the rule is fixed: "today's row count is within
+//! 20% of the trailing 7-run mean" passes or fails the same way for the same
+//! history.
+//!
+//! The metric comes from an analyzer or from a constraint reporting a metric.
+//! With fewer historical values than [`min_history`] the constraint is skipped.
+//! Messages list the historical values used, so a failure can be checked by eye.
+//!
+//! [`min_history`]: HistoricalAssertionConstraint::min_history
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use term_guard::analyzers::basic::SizeAnalyzer;
+//! use term_guard::constraints::{
+//!     CompletenessConstraint, HistoricalAssertionConstraint, HistoricalBound,
+//!     HistoryAggregation, HistoryWindow,
+//! };
+//! use term_guard::core::{Check, Level};
+//! use term_guard::repository::InMemoryRepository;
+//!
+//! let repository = Arc::new(InMemoryRepository::new());
+//!
+//! let check = Check::builder("volume_and_completeness")
+//!     .level(Level::Warning)
+//!     // Today's row count is within 20% of the mean of the last 7 runs
+//!     .with_constraint(
+//!         HistoricalAssertionConstraint::for_analyzer(
+//!             SizeAnalyzer::new(),
+//!             repository.clone(),
+//!             HistoricalBound::WithinPercent(20.0),
+//!         )
+//!         .window(HistoryWindow::LastRuns(7))
+//!         .with_tag("dataset", "orders"),
+//!     )
+//!     // Completeness is not below the median of the last 30 days
+//!     .with_constraint(
+//!         HistoricalAssertionConstraint::for_constraint(
+//!             CompletenessConstraint::with_threshold("email", 0.0),
+//!             "completeness.email",
+//!             repository,
+//!             HistoricalBound::AtLeast,
+//!         )
+//!         .window(HistoryWindow::LastDays(30))
+//!         .aggregation(HistoryAggregation::Median)
+//!         .min_history(5),
+//!     )
+//!     .build();
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::analyzers::runner::AnalyzerExecution;
+use crate::analyzers::{Analyzer, AnalyzerContext, MetricValue};
+use crate::constraints::Assertion;
+use crate::core::{
+    stamp_suite_version, Constraint, ConstraintMetadata, ConstraintResult, ConstraintStatus,
+    Extensions,
+};
+use crate::prelude::*;
+use crate::repository::{MetricsRepository, ResultKey, SortOrder};
+
+/// Which historical values the current value is compared with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryWindow {
+    /// The values of the last N runs
+    LastRuns(usize),
+    /// The values saved in the last N days
+    LastDays(u32),
+}
+
+impl fmt::Display for HistoryWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryWindow::LastRuns(runs) => write!(f, "last {runs} runs"),
+            HistoryWindow::LastDays(days) => write!(f, "last {days} days"),
+        }
+    }
+}
+
+/// How the historical values are reduced to the baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryAggregation {
+    /// The arithmetic mean
+    Mean,
+    /// The median, averaging the two middle values of an even count
+    Median,
+    /// The smallest value
+    Min,
+    /// The largest value
+    Max,
+}
+
+impl HistoryAggregation {
+    /// Returns the aggregate of `values`, or `None` if there are none.
+    pub fn apply(&self, values: &[f64]) -> Option<f64> {
+        if values.is_empty() {
+            return None;
+        }
+        Some(match self {
+            HistoryAggregation::Mean => values.iter().sum::<f64>() / values.len() as f64,
+            HistoryAggregation::Median => {
+                let mut sorted = values.to_vec();
+                sorted.sort_by(f64::total_cmp);
+                let middle = sorted.len() / 2;
+                if sorted.len() % 2 == 0 {
+                    (sorted[middle - 1] + sorted[middle]) / 2.0
+                } else {
+                    sorted[middle]
+                }
+            }
+            HistoryAggregation::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+            HistoryAggregation::Max => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+}
+
+impl fmt::Display for HistoryAggregation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryAggregation::Mean => write!(f, "mean"),
+            HistoryAggregation::Median => write!(f, "median"),
+            HistoryAggregation::Min => write!(f, "min"),
+            HistoryAggregation::Max => write!(f, "max"),
+        }
+    }
+}
+
+/// How the current value must relate to the historical baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HistoricalBound {
+    /// The value may differ by this many percent of the baseline
+    WithinPercent(f64),
+    /// The value may differ from the baseline by this amount
+    WithinAbsolute(f64),
+    /// The value must be at least the baseline
+    AtLeast,
+    /// The value must be at most the baseline
+    AtMost,
+}
+
+impl HistoricalBound {
+    /// Returns the assertion the current value must satisfy for a baseline.
+    pub fn assertion(&self, baseline: f64) -> Assertion {
+        match *self {
+            HistoricalBound::WithinPercent(percent) => {
+                let slack = baseline.abs() * percent.abs() / 100.0;
+                Assertion::Between(baseline - slack, baseline + slack)
+            }
+            HistoricalBound::WithinAbsolute(delta) => {
+                Assertion::Between(baseline - delta.abs(), baseline + delta.abs())
+            }
+            HistoricalBound::AtLeast => Assertion::GreaterThanOrEqual(baseline),
+            HistoricalBound::AtMost => Assertion::LessThanOrEqual(baseline),
+        }
+    }
+}
+
+impl fmt::Display for HistoricalBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoricalBound::WithinPercent(percent) => write!(f, "within {percent}% of"),
+            HistoricalBound::WithinAbsolute(delta) => write!(f, "within {delta} of"),
+            HistoricalBound::AtLeast => write!(f, "at least"),
+            HistoricalBound::AtMost => write!(f, "at most"),
+        }
+    }
+}
+
+/// Where the current value of the metric comes from.
+enum MetricSource {
+    Analyzer {
+        name: String,
+        execution: AnalyzerExecution,
+    },
+    Constraint(Arc<dyn Constraint>),
+}
+
+/// A constraint comparing a metric with an aggregate of its history.
+///
+/// The history is read from the repository, keeping the entries that carry
+/// every configured tag and a numeric value under the metric key. By default
+/// the current value is compared with the mean of the last 7 runs, at least one
+/// historical value is required, and the current value is saved to the
+/// repository with the configured tags after the comparison. The metric of the
+/// result is the current value.
+///
+/// When the metric comes from a constraint that is skipped, for example on an
+/// empty table, its result is returned and nothing is saved.
+pub struct HistoricalAssertionConstraint {
+    source: MetricSource,
+    metric_key: String,
+    repository: Arc<dyn MetricsRepository>,
+    bound: HistoricalBound,
+    window: HistoryWindow,
+    aggregation: HistoryAggregation,
+    min_history: usize,
+    tags: HashMap<String, String>,
+    save_metric: bool,
+}
+
+impl HistoricalAssertionConstraint {
+    /// Creates a constraint comparing the metric of `analyzer` with its history.
+    pub fn for_analyzer<A>(
+        analyzer: A,
+        repository: Arc<dyn MetricsRepository>,
+        bound: HistoricalBound,
+    ) -> Self
+    where
+        A: Analyzer + 'static,
+        A::Metric: Into<MetricValue> + 'static,
+    {
+        use futures::FutureExt;
+
+        let name = analyzer.name().to_string();
+        let metric_key = analyzer.metric_key();
+        let analyzer = Arc::new(analyzer);
+        let execution: AnalyzerExecution = Box::new(move |ctx| {
+            let analyzer = analyzer.clone();
+            async move {
+                let state = analyzer.compute_state_from_data(ctx).await?;
+                let metric = analyzer.compute_metric_from_state(&state)?;
+                Ok((analyzer.metric_key(), metric.into()))
+            }
+            .boxed()
+        });
+        Self::with_source(
+            MetricSource::Analyzer { name, execution },
+            metric_key,
+            repository,
+            bound,
+        )
+    }
+
+    /// Creates a constraint comparing the metric reported by `constraint` with
+    /// its history, stored under `metric_key`.
+    pub fn for_constraint(
+        constraint: impl Constraint + 'static,
+        metric_key: impl Into<String>,
+        repository: Arc<dyn MetricsRepository>,
+        bound: HistoricalBound,
+    ) -> Self {
+        Self::with_source(
+            MetricSource::Constraint(Arc::new(constraint)),
+            metric_key.into(),
+            repository,
+            bound,
+        )
+    }
+
+    fn with_source(
+        source: MetricSource,
+        metric_key: String,
+        repository: Arc<dyn MetricsRepository>,
+        bound: HistoricalBound,
+    ) -> Self {
+        Self {
+            source,
+            metric_key,
+            repository,
+            bound,
+            window: HistoryWindow::LastRuns(7),
+            aggregation: HistoryAggregation::Mean,
+            min_history: 1,
+            tags: HashMap::new(),
+            save_metric: true,
+        }
+    }
+
+    /// Sets the historical values the current value is compared with.
+    pub fn window(mut self, window: HistoryWindow) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Sets how the historical values are reduced to the baseline.
+    pub fn aggregation(mut self, aggregation: HistoryAggregation) -> Self {
+        self.aggregation = aggregation;
+        self
+    }
+
+    /// Sets the minimum number of historical values; with fewer the constraint
+    /// is skipped.
+    ///
+    /// Values below 1 are treated as 1.
+    pub fn min_history(mut self, min_history: usize) -> Self {
+        self.min_history = min_history.max(1);
+        self
+    }
+
+    /// Adds a tag that historical values must match and the current value is
+    /// saved with.
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets whether the current value is saved to the repository (default `true`).
+    pub fn save_metric(mut self, save: bool) -> Self {
+        self.save_metric = save;
+        self
+    }
+
+    /// Returns the key the metric is stored under in the repository.
+    pub fn metric_key(&self) -> &str {
+        &self.metric_key
+    }
+
+    /// Computes the current value, or returns the result of a skipped constraint.
+    async fn current_value(
+        &self,
+        ctx: &SessionContext,
+        extensions: &Extensions,
+    ) -> Result<std::result::Result<MetricValue, ConstraintResult>> {
+        let metric = match &self.source {
+            MetricSource::Analyzer { execution, .. } => execution(ctx).await?.1,
+            MetricSource::Constraint(constraint) => {
+                let result = constraint.evaluate_with(ctx, extensions).await?;
+                if result.status == ConstraintStatus::Skipped {
+                    return Ok(Err(result));
+                }
+                let value = result.metric.ok_or_else(|| {
+                    TermError::constraint_evaluation(
+                        "historical_assertion",
+                        format!(
+                            "Constraint '{}' reported no metric for '{}'",
+                            constraint.name(),
+                            self.metric_key
+                        ),
+                    )
+                })?;
+                MetricValue::Double(value)
+            }
+        };
+        Ok(Ok(metric))
+    }
+
+    /// Loads the historical values in the window, oldest first.
+    async fn load_history(&self) -> Result<Vec<f64>> {
+        let mut query = self
+            .repository
+            .load()
+            .await
+            .with_tags(self.tags.clone())
+            .sort(SortOrder::Ascending);
+        if let HistoryWindow::LastDays(days) = self.window {
+            let cutoff = ResultKey::now().timestamp - i64::from(days) * 86_400_000;
+            query = query.after(cutoff);
+        }
+
+        // Entries saved by other metrics under the same tags carry no value for this one
+        let mut values: Vec<f64> = query
+            .execute()
+            .await?
+            .into_iter()
+            .filter_map(|(_, context)| {
+                context
+                    .get_metric(&self.metric_key)
+                    .and_then(MetricValue::as_f64)
+            })
+            .collect();
+        if let HistoryWindow::LastRuns(runs) = self.window {
+            values.drain(..values.len().saturating_sub(runs));
+        }
+        Ok(values)
+    }
+
+    async fn save(&self, metric: MetricValue) -> Result<()> {
+        let mut context = AnalyzerContext::new();
+        context.store_metric(&self.metric_key, metric);
+        let key = stamp_suite_version(ResultKey::now().with_tags(self.tags.clone()));
+        self.repository.save(key, context).await
+    }
+
+    async fn evaluate_metric(
+        &self,
+        ctx: &SessionContext,
+        extensions: &Extensions,
+    ) -> Result<ConstraintResult> {
+        let metric = match self.current_value(ctx, extensions).await? {
+            Ok(metric) => metric,
+            Err(skipped) => return Ok(skipped),
+        };
+        let current = metric.as_f64().ok_or_else(|| {
+            TermError::constraint_evaluation(
+                "historical_assertion",
+                format!(
+                    "Metric '{}' is not numeric: {}",
+                    self.metric_key,
+                    metric.to_string_pretty()
+                ),
+            )
+        })?;
+
+        let history = self.load_history().await?;
+        debug!(
+            metric = %self.metric_key,
+            current,
+            history = history.len(),
+            "Loaded metric history for historical assertion"
+        );
+        let values = format_values(&history);
+
+        let result = match self.aggregation.apply(&history) {
+            Some(baseline) if history.len() >= self.min_history => {
+                let explanation = self
+                    .bound
+                    .assertion(baseline)
+                    .explain(current, format!("'{}'", self.metric_key));
+                let baseline = format!(
+                    "{} the {} {baseline} of the {} {values}",
+                    self.bound, self.aggregation, self.window
+                );
+                if explanation.passed {
+                    ConstraintResult {
+                        message: Some(format!(
+                            "'{}' is {current}, {baseline}",
+                            self.metric_key
+                        )),
+                        ..ConstraintResult::success_with_metric(current)
+                    }
+                } else {
+                    ConstraintResult::failure_with_metric(
+                        current,
+                        format!("{explanation}, {baseline}"),
+                    )
+                }
+            }
+            _ => ConstraintResult::skipped(format!(
+                "Historical assertion on '{}' needs {} historical values in the {}, found {} {values}",
+                self.metric_key,
+                self.min_history,
+                self.window,
+                history.len()
+            )),
+        };
+
+        if self.save_metric {
+            self.save(metric).await?;
+        }
+        Ok(result)
+    }
+}
+
+/// Formats historical values as `[v1, v2, ...]`.
+fn format_values(values: &[f64]) -> String {
+    let values: Vec<String> = values.iter().map(f64::to_string).collect();
+    format!("[{}]", values.join(", "))
+}
+
+impl fmt::Debug for HistoricalAssertionConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match &self.source {
+            MetricSource::Analyzer { name, .. } => format!("analyzer {name}"),
+            MetricSource::Constraint(constraint) => format!("constraint {}", constraint.name()),
+        };
+        f.debug_struct("HistoricalAssertionConstraint")
+            .field("source", &source)
+            .field("metric_key", &self.metric_key)
+            .field("bound", &self.bound)
+            .field("window", &self.window)
+            .field("aggregation", &self.aggregation)
+            .field("min_history", &self.min_history)
+            .field("tags", &self.tags)
+            .field("save_metric", &self.save_metric)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Constraint for HistoricalAssertionConstraint {
+    #[instrument(skip(self, ctx), fields(
+        constraint.name = %self.name(),
+        metric = %self.metric_key,
+        window = %self.window
+    ))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        self.evaluate_metric(ctx, &Extensions::new()).await
+    }
+
+    async fn evaluate_with(
+        &self,
+        ctx: &SessionContext,
+        extensions: &Extensions,
+    ) -> Result<ConstraintResult> {
+        self.evaluate_metric(ctx, extensions).await
+    }
+
+    fn name(&self) -> &str {
+        "historical_assertion"
+    }
+
+    fn column(&self) -> Option<&str> {
+        match &self.source {
+            MetricSource::Constraint(constraint) => constraint.column(),
+            MetricSource::Analyzer { .. } => None,
+        }
+    }
+
+    // A sample's metric is not comparable with the history of full runs
+    fn requires_full_data(&self) -> bool {
+        true
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        ConstraintMetadata::new()
+            .with_description(format!(
+                "Checks that '{}' is {} the {} of the {}",
+                self.metric_key, self.bound, self.aggregation, self.window
+            ))
+            .with_custom("metric_key", self.metric_key.clone())
+            .with_custom("bound", self.bound.to_string())
+            .with_custom("window", self.window.to_string())
+            .with_custom("aggregation", self.aggregation.to_string())
+            .with_custom("constraint_type", "historical")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::basic::SizeAnalyzer;
+    use crate::constraints::CompletenessConstraint;
+    use crate::repository::InMemoryRepository;
+    use crate::test_helpers::evaluate_constraint_with_context;
+
+    async fn create_test_context(rows: usize) -> SessionContext {
+        let ctx = SessionContext::new();
+        let df = ctx
+            .sql(&format!(
+                "SELECT * FROM generate_series(1, {rows}) AS t(id)"
+            ))
+            .await
+            .unwrap();
+        ctx.register_table("data", df.into_view()).unwrap();
+        ctx
+    }
+
+    async fn repository_with_history(key: &str, values: &[f64]) -> Arc<InMemoryRepository> {
+        let repository = Arc::new(InMemoryRepository::new());
+        let start = ResultKey::now().timestamp - values.len() as i64 * 3_600_000;
+        for (i, value) in values.iter().enumerate() {
+            let mut context = AnalyzerContext::new();
+            context.store_metric(key, MetricValue::Double(*value));
+            let key = ResultKey::new(start + i as i64 * 3_600_000).with_tag("dataset", "orders");
+            repository.save(key, context).await.unwrap();
+        }
+        repository
+    }
+
+    #[test]
+    fn test_aggregations() {
+        let values = [4.0, 1.0, 3.0, 2.0];
+        assert_eq!(HistoryAggregation::Mean.apply(&values), Some(2.5));
+        assert_eq!(HistoryAggregation::Median.apply(&values), Some(2.5));
+        assert_eq!(HistoryAggregation::Median.apply(&values[..3]), Some(3.0));
+        assert_eq!(HistoryAggregation::Min.apply(&values), Some(1.0));
+        assert_eq!(HistoryAggregation::Max.apply(&values), Some(4.0));
+        assert_eq!(HistoryAggregation::Mean.apply(&[]), None);
+
+        assert_eq!(
+            HistoricalBound::WithinPercent(20.0).assertion(100.0),
+            Assertion::Between(80.0, 120.0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_row_count_within_trailing_mean() {
+        let ctx = create_test_context(100).await;
+        let metric_key = SizeAnalyzer::new().metric_key();
+        // Only the last 3 runs count: their mean is 90
+        let repository = repository_with_history(&metric_key, &[1000.0, 80.0, 90.0, 100.0]).await;
+
+        let constraint = HistoricalAssertionConstraint::for_analyzer(
+            SizeAnalyzer::new(),
+            repository.clone(),
+            HistoricalBound::WithinPercent(20.0),
+        )
+        .window(HistoryWindow::LastRuns(3))
+        .with_tag("dataset", "orders");
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(100.0));
+        assert!(result.message.unwrap().contains("[80, 90, 100]"));
+        assert_eq!(repository.list_keys().await.unwrap().len(), 5);
+
+        let constraint = HistoricalAssertionConstraint::for_analyzer(
+            SizeAnalyzer::new(),
+            repository,
+            HistoricalBound::AtLeast,
+        )
+        .aggregation(HistoryAggregation::Max)
+        .with_tag("dataset", "orders")
+        .save_metric(false);
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert!(result.message.unwrap().contains(
+            "is 100, expected ≥ 1000 (900 below), at least the max 1000 of the last 7 runs"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_constraint_metric_below_median() {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE data (email VARCHAR) AS VALUES ('a'), (NULL), ('c'), ('d')")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        let repository = repository_with_history("completeness.email", &[0.9, 1.0, 0.8]).await;
+
+        let constraint = HistoricalAssertionConstraint::for_constraint(
+            CompletenessConstraint::with_threshold("email", 0.0),
+            "completeness.email",
+            repository,
+            HistoricalBound::AtLeast,
+        )
+        .window(HistoryWindow::LastDays(1))
+        .aggregation(HistoryAggregation::Median)
+        .with_tag("dataset", "orders");
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(0.75));
+        assert!(result.message.unwrap().contains("[0.9, 1, 0.8]"));
+    }
+
+    #[tokio::test]
+    async fn test_too_little_history_is_skipped() {
+        let ctx = create_test_context(10).await;
+        let metric_key = SizeAnalyzer::new().metric_key();
+        let repository = repository_with_history(&metric_key, &[10.0, 10.0]).await;
+
+        let constraint = HistoricalAssertionConstraint::for_analyzer(
+            SizeAnalyzer::new(),
+            repository.clone(),
+            HistoricalBound::WithinAbsolute(0.0),
+        )
+        .min_history(3)
+        .with_tag("dataset", "orders");
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Skipped);
+        assert!(result
+            .message
+            .unwrap()
+            .contains("needs 3 historical values"));
+        // The value is still saved, so the history grows
+        assert_eq!(repository.list_keys().await.unwrap().len(), 3);
+    }
+}
@@ -33,8 +33,22 @@
 //!     .on_multiple(vec![("product_id", "id"), ("variant", "variant_code")])
 //!     .expect_match_rate(0.98);
 //! ```
+//!
+//! ## Tables in Custom Schemas
+//!
+//! ```rust
+//! use term_guard::constraints::JoinCoverageConstraint;
+//!
+//! // Table names may be qualified as schema.table or catalog.schema.table
+//! let constraint = JoinCoverageConstraint::new("staging.sales", "my_catalog.crm.customers")
+//!     .on("customer_id", "id");
+//! ```
 
-use crate::core::{Constraint, ConstraintResult, ConstraintStatus};
+use super::composite_key::{format_examples, key_examples, NullKeyPolicy};
+use crate::core::{
+    collect_with_metrics, Constraint, ConstraintResult, ConstraintStatus, QualifiedTable,
+    QualityDimension,
+};
 use crate::error::{Result, TermError};
 use crate::security::SqlSecurity;
 use arrow::array::{Array, Float64Array};
@@ -69,8 +83,14 @@ pub struct JoinCoverageConstraint {
     expected_match_rate: f64,
     /// Type of coverage to check
     coverage_type: CoverageType,
-    /// Whether to count distinct values only
+    /// Whether to count distinct keys only
     distinct_only: bool,
+    /// Whether rows with NULL keys are left out of the coverage
+    #[serde(default)]
+    allow_nulls: bool,
+    /// Which keys with NULL columns are left out when NULLs are allowed
+    #[serde(default)]
+    null_policy: NullKeyPolicy,
     /// Maximum number of unmatched examples to report
     max_examples_reported: usize,
 }
@@ -91,8 +111,8 @@ impl JoinCoverageConstraint {
     ///
     /// # Arguments
     ///
-    /// * `left_table` - Name of the left table in the join
-    /// * `right_table` - Name of the right table in the join
+    /// * `left_table` - Name of the left table in the join, optionally schema-qualified
+    /// * `right_table` - Name of the right table in the join, optionally schema-qualified
     ///
     /// # Examples
     ///
@@ -109,6 +129,8 @@ impl JoinCoverageConstraint {
             expected_match_rate: 1.0,
             coverage_type: CoverageType::LeftCoverage,
             distinct_only: false,
+            allow_nulls: false,
+            null_policy: NullKeyPolicy::default(),
             max_examples_reported: 100,
         }
     }
@@ -126,6 +148,9 @@ impl JoinCoverageConstraint {
 
     /// Set multiple join key columns for composite joins.
     ///
+    /// Rows match when every pair of columns is equal, and coverage is measured
+    /// on the key tuples.
+    ///
     /// # Arguments
     ///
     /// * `keys` - Vector of (left_column, right_column) pairs
@@ -153,112 +178,91 @@ impl JoinCoverageConstraint {
         self
     }
 
-    /// Count only distinct values when calculating coverage.
+    /// Count only distinct keys when calculating coverage.
     pub fn distinct_only(mut self, distinct: bool) -> Self {
         self.distinct_only = distinct;
         self
     }
 
+    /// Leave rows with NULL keys out of the coverage.
+    ///
+    /// By default such rows are counted and never match.
+    pub fn allow_nulls(mut self, allow: bool) -> Self {
+        self.allow_nulls = allow;
+        self
+    }
+
+    /// Set which composite keys with NULL columns are left out when NULLs are allowed.
+    pub fn null_policy(mut self, policy: NullKeyPolicy) -> Self {
+        self.null_policy = policy;
+        self
+    }
+
     /// Set the maximum number of unmatched examples to report.
     pub fn max_examples_reported(mut self, max_examples: usize) -> Self {
         self.max_examples_reported = max_examples;
         self
     }
 
-    /// Validate table and column names for security.
-    fn validate_identifiers(&self) -> Result<()> {
-        SqlSecurity::validate_identifier(&self.left_table)?;
-        SqlSecurity::validate_identifier(&self.right_table)?;
+    /// Validate table and column names and render them as SQL identifiers.
+    fn sql_identifiers(&self) -> Result<SqlIdentifiers> {
+        if self.join_keys.is_empty() {
+            return Err(TermError::Configuration(
+                "No join keys specified. Use .on() or .on_multiple() to set join keys".to_string(),
+            ));
+        }
 
+        let mut left_columns = Vec::with_capacity(self.join_keys.len());
+        let mut right_columns = Vec::with_capacity(self.join_keys.len());
         for (left_col, right_col) in &self.join_keys {
-            SqlSecurity::validate_identifier(left_col)?;
-            SqlSecurity::validate_identifier(right_col)?;
+            left_columns.push(SqlSecurity::escape_identifier(left_col)?);
+            right_columns.push(SqlSecurity::escape_identifier(right_col)?);
         }
 
-        Ok(())
+        Ok(SqlIdentifiers {
+            left_table: QualifiedTable::resolve(&self.left_table)?.to_sql()?,
+            right_table: QualifiedTable::resolve(&self.right_table)?.to_sql()?,
+            left_columns,
+            right_columns,
+        })
     }
 
     /// Generate SQL query for join coverage analysis.
+    ///
+    /// The left and right tables are aliased as `left_tbl` and `right_tbl` so that
+    /// tables with the same name in different schemas can be joined.
     fn generate_coverage_query(&self) -> Result<String> {
-        self.validate_identifiers()?;
-
-        if self.join_keys.is_empty() {
-            return Err(TermError::constraint_evaluation(
-                "join_coverage",
-                "No join keys specified. Use .on() or .on_multiple() to set join keys",
-            ));
-        }
-
-        let join_condition = self
-            .join_keys
-            .iter()
-            .map(|(l, r)| format!("{}.{l} = {}.{r}", self.left_table, self.right_table))
-            .collect::<Vec<_>>()
-            .join(" AND ");
-
-        let count_expr = if self.distinct_only {
-            format!(
-                "COUNT(DISTINCT {}.{})",
-                self.left_table, self.join_keys[0].0
-            )
-        } else {
-            "COUNT(*)".to_string()
-        };
+        let identifiers = self.sql_identifiers()?;
 
         let sql = match self.coverage_type {
             CoverageType::LeftCoverage => {
-                // Check how many rows from left table have matches in right table
+                let stats = self.coverage_stats(&identifiers, Side::Left);
                 format!(
-                    "WITH coverage_stats AS (
-                        SELECT 
-                            {count_expr} as total_left,
-                            SUM(CASE WHEN {}.{} IS NOT NULL THEN 1 ELSE 0 END) as matched_left
-                        FROM {} 
-                        LEFT JOIN {} ON {join_condition}
-                    )
+                    "WITH coverage_stats AS ({stats})
                     SELECT 
                         total_left,
                         matched_left,
                         CAST(matched_left AS DOUBLE) / CAST(total_left AS DOUBLE) as match_rate
-                    FROM coverage_stats",
-                    self.right_table, self.join_keys[0].1, self.left_table, self.right_table
+                    FROM coverage_stats"
                 )
             }
             CoverageType::RightCoverage => {
-                // Check how many rows from right table have matches in left table
+                let stats = self.coverage_stats(&identifiers, Side::Right);
                 format!(
-                    "WITH coverage_stats AS (
-                        SELECT 
-                            {count_expr} as total_right,
-                            SUM(CASE WHEN {}.{} IS NOT NULL THEN 1 ELSE 0 END) as matched_right
-                        FROM {} 
-                        RIGHT JOIN {} ON {join_condition}
-                    )
+                    "WITH coverage_stats AS ({stats})
                     SELECT 
                         total_right,
                         matched_right,
                         CAST(matched_right AS DOUBLE) / CAST(total_right AS DOUBLE) as match_rate
-                    FROM coverage_stats",
-                    self.left_table, self.join_keys[0].0, self.right_table, self.left_table
+                    FROM coverage_stats"
                 )
             }
             CoverageType::BidirectionalCoverage => {
-                // Check coverage in both directions
+                let left_stats = self.coverage_stats(&identifiers, Side::Left);
+                let right_stats = self.coverage_stats(&identifiers, Side::Right);
                 format!(
-                    "WITH left_coverage AS (
-                        SELECT 
-                            COUNT(*) as total_left,
-                            SUM(CASE WHEN {}.{} IS NOT NULL THEN 1 ELSE 0 END) as matched_left
-                        FROM {} 
-                        LEFT JOIN {} ON {join_condition}
-                    ),
-                    right_coverage AS (
-                        SELECT 
-                            COUNT(*) as total_right,
-                            SUM(CASE WHEN {}.{} IS NOT NULL THEN 1 ELSE 0 END) as matched_right
-                        FROM {} 
-                        RIGHT JOIN {} ON {join_condition}
-                    )
+                    "WITH left_coverage AS ({left_stats}),
+                    right_coverage AS ({right_stats})
                     SELECT 
                         l.total_left,
                         l.matched_left,
@@ -268,15 +272,7 @@ impl JoinCoverageConstraint {
                             CAST(l.matched_left AS DOUBLE) / CAST(l.total_left AS DOUBLE),
                             CAST(r.matched_right AS DOUBLE) / CAST(r.total_right AS DOUBLE)
                         ) as match_rate
-                    FROM left_coverage l, right_coverage r",
-                    self.right_table,
-                    self.join_keys[0].1,
-                    self.left_table,
-                    self.right_table,
-                    self.left_table,
-                    self.join_keys[0].0,
-                    self.right_table,
-                    self.left_table
+                    FROM left_coverage l, right_coverage r"
                 )
             }
         };
@@ -285,38 +281,109 @@ impl JoinCoverageConstraint {
         Ok(sql)
     }
 
-    /// Generate query to find unmatched examples.
+    /// Generate the query counting the rows of one side and those with a match.
+    ///
+    /// The other side is reduced to its distinct keys, so that a row matching
+    /// several rows is counted once.
+    fn coverage_stats(&self, identifiers: &SqlIdentifiers, covered: Side) -> String {
+        let join = identifiers.join(covered);
+        let count_source = if self.distinct_only {
+            format!(
+                "(SELECT DISTINCT {} FROM {})",
+                join.covered_columns.join(", "),
+                join.covered_table
+            )
+        } else {
+            join.covered_table.to_string()
+        };
+        let (total, matched) = match covered {
+            Side::Left => ("total_left", "matched_left"),
+            Side::Right => ("total_right", "matched_right"),
+        };
+
+        format!(
+            "SELECT 
+                COUNT(*) as {total},
+                SUM(CASE WHEN {}.{} IS NOT NULL THEN 1 ELSE 0 END) as {matched}
+            FROM {count_source} AS {}
+            LEFT JOIN (SELECT DISTINCT {} FROM {}) AS {} ON {}{}",
+            join.other_alias,
+            join.other_columns[0],
+            join.covered_alias,
+            join.other_columns.join(", "),
+            join.other_table,
+            join.other_alias,
+            identifiers.join_condition(),
+            self.participating_keys(&join)
+                .map(|keys| format!(" WHERE {keys}"))
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Returns the condition leaving NULL keys of the covered side out, if NULLs are allowed.
+    fn participating_keys(&self, join: &SideJoin<'_>) -> Option<String> {
+        if !self.allow_nulls {
+            return None;
+        }
+        let columns: Vec<_> = join
+            .covered_columns
+            .iter()
+            .map(|column| format!("{}.{column}", join.covered_alias))
+            .collect();
+        Some(self.null_policy.participating(&columns))
+    }
+
+    /// Names of the key columns of the table whose coverage is checked, in the
+    /// order [`generate_unmatched_query`](Self::generate_unmatched_query) selects them.
+    fn covered_key_columns(&self) -> Vec<&str> {
+        self.join_keys
+            .iter()
+            .map(|(left, right)| match self.coverage_type {
+                CoverageType::RightCoverage => right.as_str(),
+                _ => left.as_str(),
+            })
+            .collect()
+    }
+
+    /// Generate query to find unmatched keys of the covered side.
+    ///
+    /// Bidirectional coverage reports the unmatched keys of the left table.
     fn generate_unmatched_query(&self) -> Result<String> {
         if self.max_examples_reported == 0 {
             return Ok(String::new());
         }
 
-        self.validate_identifiers()?;
-
-        let join_condition = self
-            .join_keys
-            .iter()
-            .map(|(l, r)| format!("{}.{l} = {}.{r}", self.left_table, self.right_table))
-            .collect::<Vec<_>>()
-            .join(" AND ");
+        let identifiers = self.sql_identifiers()?;
+        let covered = match self.coverage_type {
+            CoverageType::RightCoverage => Side::Right,
+            _ => Side::Left,
+        };
+        let join = identifiers.join(covered);
 
-        let key_columns = self
-            .join_keys
+        let key_columns = join
+            .covered_columns
             .iter()
-            .map(|(l, _)| format!("{}.{l}", self.left_table))
+            .map(|column| format!("{}.{column}", join.covered_alias))
             .collect::<Vec<_>>()
             .join(", ");
+        let null_filter = self
+            .participating_keys(&join)
+            .map(|keys| format!(" AND {keys}"))
+            .unwrap_or_default();
 
         let sql = format!(
             "SELECT DISTINCT {key_columns} 
-             FROM {} 
-             LEFT JOIN {} ON {join_condition}
-             WHERE {}.{} IS NULL
+             FROM {} AS {}
+             LEFT JOIN {} AS {} ON {}
+             WHERE {}.{} IS NULL{null_filter}
              LIMIT {}",
-            self.left_table,
-            self.right_table,
-            self.right_table,
-            self.join_keys[0].1,
+            join.covered_table,
+            join.covered_alias,
+            join.other_table,
+            join.other_alias,
+            identifiers.join_condition(),
+            join.other_alias,
+            join.other_columns[0],
             self.max_examples_reported
         );
 
@@ -324,6 +391,68 @@ impl JoinCoverageConstraint {
     }
 }
 
+/// Side of the join whose rows are covered.
+#[derive(Debug, Clone, Copy)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// Validated tables and join keys rendered as SQL.
+struct SqlIdentifiers {
+    left_table: String,
+    right_table: String,
+    left_columns: Vec<String>,
+    right_columns: Vec<String>,
+}
+
+/// The tables and key columns of a join seen from the covered side.
+struct SideJoin<'a> {
+    covered_table: &'a str,
+    covered_alias: &'static str,
+    covered_columns: &'a [String],
+    other_table: &'a str,
+    other_alias: &'static str,
+    other_columns: &'a [String],
+}
+
+impl SqlIdentifiers {
+    /// Returns the condition matching every pair of key columns.
+    fn join_condition(&self) -> String {
+        self.left_columns
+            .iter()
+            .zip(&self.right_columns)
+            .map(|(l, r)| format!("left_tbl.{l} = right_tbl.{r}"))
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    }
+
+    fn join(&self, covered: Side) -> SideJoin<'_> {
+        let left = (
+            self.left_table.as_str(),
+            "left_tbl",
+            self.left_columns.as_slice(),
+        );
+        let right = (
+            self.right_table.as_str(),
+            "right_tbl",
+            self.right_columns.as_slice(),
+        );
+        let (covered, other) = match covered {
+            Side::Left => (left, right),
+            Side::Right => (right, left),
+        };
+        SideJoin {
+            covered_table: covered.0,
+            covered_alias: covered.1,
+            covered_columns: covered.2,
+            other_table: other.0,
+            other_alias: other.1,
+            other_columns: other.2,
+        }
+    }
+}
+
 #[async_trait]
 impl Constraint for JoinCoverageConstraint {
     #[instrument(skip(self, ctx), fields(constraint = "join_coverage"))]
@@ -342,7 +471,7 @@ impl Constraint for JoinCoverageConstraint {
             )
         })?;
 
-        let batches = df.collect().await.map_err(|e| {
+        let batches = collect_with_metrics(df).await.map_err(|e| {
             TermError::constraint_evaluation(
                 "join_coverage",
                 format!("Failed to collect join coverage results: {e}"),
@@ -366,6 +495,10 @@ impl Constraint for JoinCoverageConstraint {
                 TermError::constraint_evaluation("join_coverage", "Invalid match rate column type")
             })?;
 
+        // The rate is 0 / 0 when the covered table has no rows
+        if match_rate_col.is_null(0) || match_rate_col.value(0).is_nan() {
+            return Ok(ConstraintResult::empty_table());
+        }
         let match_rate = match_rate_col.value(0);
 
         debug!(
@@ -392,11 +525,17 @@ impl Constraint for JoinCoverageConstraint {
         let unmatched_query = self.generate_unmatched_query()?;
         let examples_msg = if !unmatched_query.is_empty() {
             match ctx.sql(&unmatched_query).await {
-                Ok(df) => match df.collect().await {
-                    Ok(batches) if !batches.is_empty() && batches[0].num_rows() > 0 => {
-                        let examples_count = batches[0].num_rows();
-                        format!(" ({examples_count} unmatched examples found)")
-                    }
+                Ok(df) => match collect_with_metrics(df).await {
+                    Ok(batches) => match key_examples(
+                        &batches,
+                        &self.covered_key_columns(),
+                        self.max_examples_reported,
+                    ) {
+                        Ok(examples) if !examples.is_empty() => {
+                            format!(". Unmatched keys: [{}]", format_examples(&examples))
+                        }
+                        _ => String::new(),
+                    },
                     _ => String::new(),
                 },
                 _ => String::new(),
@@ -417,12 +556,23 @@ impl Constraint for JoinCoverageConstraint {
             status: ConstraintStatus::Failure,
             metric: Some(match_rate),
             message: Some(message),
+            quarantine: None,
+            constraint_id: None,
+            details: Default::default(),
         })
     }
 
     fn name(&self) -> &str {
         "join_coverage"
     }
+
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Consistency)
+    }
+
+    fn referenced_tables(&self) -> Vec<String> {
+        vec![self.left_table.clone(), self.right_table.clone()]
+    }
 }
 
 #[cfg(test)]
@@ -550,6 +700,23 @@ mod tests {
         assert_eq!(constraint.max_examples_reported, 50);
     }
 
+    #[test]
+    fn test_qualified_table_sql() -> Result<()> {
+        let constraint = JoinCoverageConstraint::new("staging.orders", "my_catalog.crm.customers")
+            .on("customer_id", "id");
+        let sql = constraint.generate_coverage_query()?;
+
+        assert!(sql.contains(r#"FROM "staging"."orders" AS left_tbl"#));
+        assert!(sql.contains(r#"FROM "my_catalog"."crm"."customers") AS right_tbl"#));
+        assert!(sql.contains(r#"left_tbl."customer_id" = right_tbl."id""#));
+
+        let invalid = JoinCoverageConstraint::new("staging.orders; DROP TABLE x", "customers")
+            .on("customer_id", "id");
+        assert!(invalid.generate_coverage_query().is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_composite_keys() {
         let constraint = JoinCoverageConstraint::new("orders", "products")
@@ -566,4 +733,84 @@ mod tests {
             ("variant".to_string(), "variant_code".to_string())
         );
     }
+
+    async fn composite_coverage_context() -> Result<SessionContext> {
+        let ctx = create_test_context().await?;
+        ctx.sql(
+            "CREATE TABLE cov_accounts (tenant_id BIGINT, account_id BIGINT) AS VALUES
+                (1, 10), (1, 10), (1, 11), (2, 10), (3, 30)",
+        )
+        .await?
+        .collect()
+        .await?;
+        ctx.sql(
+            "CREATE TABLE cov_events (tenant BIGINT, account BIGINT) AS VALUES
+                (1, 10), (1, 10), (2, 11), (NULL, NULL), (1, NULL)",
+        )
+        .await?
+        .collect()
+        .await?;
+        Ok(ctx)
+    }
+
+    #[tokio::test]
+    async fn test_composite_key_coverage() -> Result<()> {
+        let ctx = composite_coverage_context().await?;
+        let keys = vec![("tenant", "tenant_id"), ("account", "account_id")];
+
+        // (2, 11) has the right values in the wrong combination, and the
+        // duplicated (1, 10) account counts each event once
+        let constraint = JoinCoverageConstraint::new("cov_events", "cov_accounts")
+            .on_multiple(keys.clone())
+            .expect_match_rate(0.9);
+        let result = constraint.evaluate(&ctx).await?;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(0.4));
+        let message = result.message.unwrap();
+        assert!(
+            message.contains("Unmatched keys: [") && message.contains("(2, 11)"),
+            "{message}"
+        );
+
+        let distinct = constraint.clone().distinct_only(true);
+        let result = distinct.evaluate(&ctx).await?;
+        assert_eq!(result.metric, Some(0.25));
+
+        let any_null = constraint.clone().allow_nulls(true);
+        let result = any_null.evaluate(&ctx).await?;
+        assert!((result.metric.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+
+        let all_null = any_null.null_policy(NullKeyPolicy::AllNull);
+        let result = all_null.evaluate(&ctx).await?;
+        assert_eq!(result.metric, Some(0.5));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_composite_key_right_coverage() -> Result<()> {
+        let ctx = composite_coverage_context().await?;
+
+        // Accounts (1, 10) twice are referenced, (1, 11), (2, 10) and (3, 30) are not
+        let constraint = JoinCoverageConstraint::new("cov_events", "cov_accounts")
+            .on_multiple(vec![("tenant", "tenant_id"), ("account", "account_id")])
+            .coverage_type(CoverageType::RightCoverage)
+            .expect_match_rate(0.5);
+        let result = constraint.evaluate(&ctx).await?;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(0.4));
+        let message = result.message.unwrap();
+        assert!(message.contains("(3, 30)"), "{message}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_join_keys() {
+        let constraint = JoinCoverageConstraint::new("orders", "customers");
+        assert!(matches!(
+            constraint.generate_coverage_query(),
+            Err(TermError::Configuration(_))
+        ));
+    }
 }
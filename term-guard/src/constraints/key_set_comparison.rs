@@ -0,0 +1,657 @@
+//! Row identity stability between two snapshots of a table.
+//!
+//! Dimension tables are expected to keep their keys from one load to the
+//! next: a customer that disappears breaks every fact row referencing it, and
+//! a sudden burst of new keys usually means that the keys were regenerated.
+//! [`KeySetComparisonConstraint`] compares the distinct key sets of two
+//! registered tables, the current snapshot and the previous one, and counts
+//! the missing keys (in the previous snapshot but not the current one), the
+//! new keys (in the current snapshot but not the previous one) and the
+//! fraction of previous keys that were retained. Each count has its own limit,
+//! and failures list examples of the missing and new keys.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::constraints::{KeySetAssertions, KeySetComparisonConstraint};
+//! use term_guard::core::{Check, Level};
+//!
+//! # fn example() -> term_guard::prelude::Result<()> {
+//! // No customer may disappear and at most 2% of the keys may be new
+//! let constraint = KeySetComparisonConstraint::new(
+//!     "dim_customer",
+//!     "dim_customer_prev",
+//!     vec!["customer_id"],
+//!     KeySetAssertions {
+//!         max_missing: 0,
+//!         max_new_ratio: 0.02,
+//!     },
+//! )?;
+//!
+//! let check = Check::builder("customer_identity")
+//!     .level(Level::Error)
+//!     .constraint(constraint)
+//!     .build();
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+
+use super::composite_key::{format_examples, key_examples};
+use crate::core::{
+    collect_with_metrics, describe_percent, Constraint, ConstraintMetadata, ConstraintResult,
+    QualifiedTable, QualityDimension,
+};
+use crate::error::{Result, TermError};
+use crate::security::SqlSecurity;
+use arrow::array::{Array, Int64Array};
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument, warn};
+
+/// The limits a [`KeySetComparisonConstraint`] applies to the change of a key set.
+///
+/// The default allows no change at all: no missing and no new key.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct KeySetAssertions {
+    /// The maximum number of previous keys that may be missing from the current table
+    pub max_missing: u64,
+    /// The maximum fraction of current keys that may be new, between 0.0 and 1.0
+    pub max_new_ratio: f64,
+}
+
+/// The distinct key counts of a comparison between two key sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeySetComparison {
+    /// Distinct keys of the previous table
+    pub previous_keys: u64,
+    /// Distinct keys of the current table
+    pub current_keys: u64,
+    /// Keys of the previous table missing from the current one
+    pub missing_keys: u64,
+    /// Keys of the current table absent from the previous one
+    pub new_keys: u64,
+}
+
+impl KeySetComparison {
+    /// Returns the number of previous keys still present in the current table.
+    pub fn retained_keys(&self) -> u64 {
+        self.previous_keys - self.missing_keys
+    }
+
+    /// Returns the fraction of previous keys still present, 1.0 without previous keys.
+    pub fn retained_fraction(&self) -> f64 {
+        if self.previous_keys == 0 {
+            1.0
+        } else {
+            self.retained_keys() as f64 / self.previous_keys as f64
+        }
+    }
+
+    /// Returns the fraction of current keys that are new, 0.0 without current keys.
+    pub fn new_ratio(&self) -> f64 {
+        if self.current_keys == 0 {
+            0.0
+        } else {
+            self.new_keys as f64 / self.current_keys as f64
+        }
+    }
+}
+
+/// A constraint that compares the distinct key sets of a current and a previous table.
+///
+/// Keys are compared as tuples of all key columns. A NULL key value is a value
+/// like any other, so a key with a NULL column matches the same key in the
+/// other table. The metric is the retained fraction of previous keys.
+#[derive(Debug, Clone)]
+pub struct KeySetComparisonConstraint {
+    current_table: String,
+    previous_table: String,
+    key_columns: Vec<String>,
+    assertions: KeySetAssertions,
+    min_retained_fraction: f64,
+    max_examples_reported: usize,
+}
+
+impl KeySetComparisonConstraint {
+    /// Creates a constraint comparing the key sets of two tables.
+    ///
+    /// By default the retained fraction is not asserted on its own and ten
+    /// missing and ten new keys are reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `current_table` - The table holding the current snapshot
+    /// * `previous_table` - The table holding the previous snapshot
+    /// * `key_columns` - The columns identifying a row in both tables
+    /// * `assertions` - The limits on missing and new keys
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no key column is given, a column name is invalid,
+    /// or the new key ratio is not between 0.0 and 1.0.
+    pub fn new<I, S>(
+        current_table: impl Into<String>,
+        previous_table: impl Into<String>,
+        key_columns: I,
+        assertions: KeySetAssertions,
+    ) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let key_columns: Vec<String> = key_columns.into_iter().map(Into::into).collect();
+        if key_columns.is_empty() {
+            return Err(TermError::validation_failed(
+                "key_set_comparison",
+                "At least one key column must be specified",
+            ));
+        }
+        for column in &key_columns {
+            SqlSecurity::validate_identifier(column)?;
+        }
+        if !(0.0..=1.0).contains(&assertions.max_new_ratio) {
+            return Err(TermError::validation_failed(
+                "key_set_comparison",
+                "The maximum new key ratio must be between 0.0 and 1.0",
+            ));
+        }
+
+        Ok(Self {
+            current_table: current_table.into(),
+            previous_table: previous_table.into(),
+            key_columns,
+            assertions,
+            min_retained_fraction: 0.0,
+            max_examples_reported: 10,
+        })
+    }
+
+    /// Sets the minimum fraction of previous keys that must be retained.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fraction is not between 0.0 and 1.0.
+    pub fn min_retained_fraction(mut self, fraction: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&fraction),
+            "Retained fraction must be between 0.0 and 1.0"
+        );
+        self.min_retained_fraction = fraction;
+        self
+    }
+
+    /// Sets the maximum number of missing and of new keys to report.
+    ///
+    /// Defaults to 10. Set to 0 to disable key example collection.
+    pub fn max_examples_reported(mut self, max_examples: usize) -> Self {
+        self.max_examples_reported = max_examples;
+        self
+    }
+
+    /// Returns the key columns.
+    pub fn key_columns(&self) -> &[String] {
+        &self.key_columns
+    }
+
+    /// Returns the limits on missing and new keys.
+    pub fn assertions(&self) -> KeySetAssertions {
+        self.assertions
+    }
+
+    /// Builds the common table expressions `prev_keys` and `cur_keys` holding
+    /// the distinct keys of both tables.
+    fn key_sets(&self) -> Result<(String, String)> {
+        let keys = self
+            .key_columns
+            .iter()
+            .map(|column| SqlSecurity::escape_identifier(column))
+            .collect::<Result<Vec<_>>>()?
+            .join(", ");
+        let previous = QualifiedTable::resolve(&self.previous_table)?.to_sql()?;
+        let current = QualifiedTable::resolve(&self.current_table)?.to_sql()?;
+        let ctes = format!(
+            "WITH prev_keys AS (SELECT DISTINCT {keys} FROM {previous}),
+                  cur_keys AS (SELECT DISTINCT {keys} FROM {current})"
+        );
+        Ok((ctes, keys))
+    }
+
+    /// Builds the query counting both key sets and their differences in one row.
+    fn comparison_query(&self) -> Result<String> {
+        let (ctes, keys) = self.key_sets()?;
+        let sql = format!(
+            "{ctes}
+             SELECT p.previous_keys, c.current_keys, m.missing_keys, n.new_keys
+             FROM (SELECT COUNT(*) AS previous_keys FROM prev_keys) AS p
+             CROSS JOIN (SELECT COUNT(*) AS current_keys FROM cur_keys) AS c
+             CROSS JOIN (SELECT COUNT(*) AS missing_keys FROM
+                 (SELECT {keys} FROM prev_keys EXCEPT SELECT {keys} FROM cur_keys) AS missing) AS m
+             CROSS JOIN (SELECT COUNT(*) AS new_keys FROM
+                 (SELECT {keys} FROM cur_keys EXCEPT SELECT {keys} FROM prev_keys) AS added) AS n"
+        );
+        debug!("Generated key set comparison query: {}", sql);
+        Ok(sql)
+    }
+
+    /// Builds the query listing the keys of one set absent from the other.
+    fn difference_query(&self, from: &str, except: &str) -> Result<String> {
+        let (ctes, keys) = self.key_sets()?;
+        let sql = format!(
+            "{ctes}
+             SELECT * FROM (SELECT {keys} FROM {from} EXCEPT SELECT {keys} FROM {except}) AS difference
+             ORDER BY {keys}
+             LIMIT {}",
+            self.max_examples_reported
+        );
+        debug!("Generated key set difference query: {}", sql);
+        Ok(sql)
+    }
+
+    /// Collects examples of the keys of one set absent from the other.
+    ///
+    /// Examples are best effort: a failing query reports no examples rather
+    /// than failing the evaluation.
+    async fn collect_examples(&self, ctx: &SessionContext, from: &str, except: &str) -> String {
+        if self.max_examples_reported == 0 {
+            return String::new();
+        }
+        let Ok(sql) = self.difference_query(from, except) else {
+            return String::new();
+        };
+        let Ok(df) = ctx.sql(&sql).await else {
+            return String::new();
+        };
+        match collect_with_metrics(df).await {
+            Ok(batches) => {
+                match key_examples(&batches, &self.key_columns, self.max_examples_reported) {
+                    Ok(examples) => format_examples(&examples),
+                    Err(_) => String::new(),
+                }
+            }
+            Err(_) => String::new(),
+        }
+    }
+
+    fn key_label(&self) -> String {
+        match self.key_columns.as_slice() {
+            [column] => format!("'{column}'"),
+            columns => format!("({})", columns.join(", ")),
+        }
+    }
+}
+
+fn count_column(batch: &RecordBatch, index: usize) -> Result<u64> {
+    let counts = batch
+        .column(index)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .ok_or_else(|| {
+            TermError::constraint_evaluation("key_set_comparison", "Invalid key count column type")
+        })?;
+    Ok(counts.value(0).max(0) as u64)
+}
+
+#[async_trait]
+impl Constraint for KeySetComparisonConstraint {
+    #[instrument(skip(self, ctx), fields(
+        current = %self.current_table,
+        previous = %self.previous_table,
+        keys = ?self.key_columns
+    ))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let df = ctx.sql(&self.comparison_query()?).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                self.name(),
+                format!("Key set comparison query failed: {e}"),
+            )
+        })?;
+        let batches = collect_with_metrics(df).await?;
+
+        if batches.is_empty() || batches[0].num_rows() == 0 {
+            return Err(TermError::constraint_evaluation(
+                self.name(),
+                "No results from key set comparison query",
+            ));
+        }
+        let batch = &batches[0];
+        let comparison = KeySetComparison {
+            previous_keys: count_column(batch, 0)?,
+            current_keys: count_column(batch, 1)?,
+            missing_keys: count_column(batch, 2)?,
+            new_keys: count_column(batch, 3)?,
+        };
+        if comparison.previous_keys == 0 && comparison.current_keys == 0 {
+            return Ok(ConstraintResult::empty_table());
+        }
+
+        let retained = comparison.retained_fraction();
+        let new_ratio = comparison.new_ratio();
+        debug!(
+            "Key set comparison: {} missing, {} new ({:.2}%), {:.2}% retained",
+            comparison.missing_keys,
+            comparison.new_keys,
+            new_ratio * 100.0,
+            retained * 100.0
+        );
+
+        let mut violations = Vec::new();
+        if comparison.missing_keys > self.assertions.max_missing {
+            violations.push(format!(
+                "{} of {} previous keys are missing (maximum {})",
+                comparison.missing_keys, comparison.previous_keys, self.assertions.max_missing
+            ));
+        }
+        if new_ratio > self.assertions.max_new_ratio {
+            violations.push(format!(
+                "{} of {} current keys are new ({:.2}%, maximum {:.2}%)",
+                comparison.new_keys,
+                comparison.current_keys,
+                new_ratio * 100.0,
+                self.assertions.max_new_ratio * 100.0
+            ));
+        }
+        if retained < self.min_retained_fraction {
+            violations.push(format!(
+                "{:.2}% of previous keys are retained (minimum {:.2}%)",
+                retained * 100.0,
+                self.min_retained_fraction * 100.0
+            ));
+        }
+
+        let result = if violations.is_empty() {
+            ConstraintResult::success_with_metric(retained)
+        } else {
+            let mut message = format!(
+                "Key set of {} on {} changed against {}: {}",
+                self.current_table,
+                self.key_label(),
+                self.previous_table,
+                violations.join("; ")
+            );
+            if comparison.missing_keys > 0 {
+                let examples = self.collect_examples(ctx, "prev_keys", "cur_keys").await;
+                if !examples.is_empty() {
+                    message.push_str(&format!(". Missing keys: [{examples}]"));
+                }
+            }
+            if comparison.new_keys > 0 {
+                let examples = self.collect_examples(ctx, "cur_keys", "prev_keys").await;
+                if !examples.is_empty() {
+                    message.push_str(&format!(". New keys: [{examples}]"));
+                }
+            }
+            warn!("{}", message);
+            ConstraintResult::failure_with_metric(retained, message)
+        };
+
+        Ok(result
+            .with_detail("previous_keys", comparison.previous_keys as f64)
+            .with_detail("current_keys", comparison.current_keys as f64)
+            .with_detail("missing_keys", comparison.missing_keys as f64)
+            .with_detail("new_keys", comparison.new_keys as f64)
+            .with_detail("new_ratio", new_ratio)
+            .with_detail("retained_fraction", retained))
+    }
+
+    fn name(&self) -> &str {
+        "key_set_comparison"
+    }
+
+    fn describe(&self) -> String {
+        let missing = match self.assertions.max_missing {
+            0 => "every key".to_string(),
+            1 => "all but at most 1 key".to_string(),
+            n => format!("all but at most {n} keys"),
+        };
+        let mut description = format!(
+            "{} must keep {missing} of {} on {} and add at most {} new keys",
+            self.current_table,
+            self.previous_table,
+            self.key_label(),
+            describe_percent(self.assertions.max_new_ratio)
+        );
+        if self.min_retained_fraction > 0.0 {
+            description.push_str(&format!(
+                ", retaining at least {}",
+                describe_percent(self.min_retained_fraction)
+            ));
+        }
+        description
+    }
+
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Consistency)
+    }
+
+    fn referenced_tables(&self) -> Vec<String> {
+        vec![self.current_table.clone(), self.previous_table.clone()]
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        ConstraintMetadata::for_columns(self.key_columns.iter().cloned())
+            .with_description(self.describe())
+            .with_custom("current_table", self.current_table.clone())
+            .with_custom("previous_table", self.previous_table.clone())
+            .with_custom("max_missing", self.assertions.max_missing.to_string())
+            .with_custom("max_new_ratio", self.assertions.max_new_ratio.to_string())
+            .with_custom(
+                "min_retained_fraction",
+                self.min_retained_fraction.to_string(),
+            )
+            .with_custom("constraint_type", "consistency")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ConstraintStatus;
+    use crate::test_helpers::evaluate_constraint_with_context;
+
+    /// Customers 1 and 2 left, 6 and 7 are new; region 'EU' of customer 5 is new.
+    async fn snapshots_context() -> SessionContext {
+        let ctx = SessionContext::new();
+        for sql in [
+            "CREATE TABLE dim_customer_prev (customer_id INT, region VARCHAR) AS VALUES
+                (1, 'US'), (2, 'US'), (3, 'US'), (4, 'US'), (5, 'US'), (5, 'US')",
+            "CREATE TABLE dim_customer (customer_id INT, region VARCHAR) AS VALUES
+                (3, 'US'), (4, 'US'), (5, 'US'), (5, 'EU'), (6, 'US'), (7, 'US')",
+            "CREATE TABLE empty_prev AS SELECT * FROM dim_customer_prev WHERE customer_id < 0",
+            "CREATE TABLE empty_cur AS SELECT * FROM dim_customer_prev WHERE customer_id < 0",
+        ] {
+            ctx.sql(sql).await.unwrap().collect().await.unwrap();
+        }
+        ctx
+    }
+
+    async fn evaluate(
+        ctx: &SessionContext,
+        constraint: &KeySetComparisonConstraint,
+    ) -> ConstraintResult {
+        evaluate_constraint_with_context(constraint, ctx, "dim_customer")
+            .await
+            .unwrap()
+    }
+
+    fn customer_keys(assertions: KeySetAssertions) -> KeySetComparisonConstraint {
+        KeySetComparisonConstraint::new(
+            "dim_customer",
+            "dim_customer_prev",
+            vec!["customer_id"],
+            assertions,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_missing_and_new_keys_reported() {
+        let ctx = snapshots_context().await;
+
+        let result = evaluate(
+            &ctx,
+            &customer_keys(KeySetAssertions {
+                max_missing: 0,
+                max_new_ratio: 0.02,
+            }),
+        )
+        .await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(3.0 / 5.0));
+        assert_eq!(result.details["previous_keys"], 5.0);
+        assert_eq!(result.details["current_keys"], 5.0);
+        assert_eq!(result.details["missing_keys"], 2.0);
+        assert_eq!(result.details["new_keys"], 2.0);
+        assert_eq!(result.details["new_ratio"], 0.4);
+        let message = result.message.unwrap();
+        assert!(
+            message.contains("2 of 5 previous keys are missing (maximum 0)"),
+            "{message}"
+        );
+        assert!(
+            message.contains("2 of 5 current keys are new (40.00%, maximum 2.00%)"),
+            "{message}"
+        );
+        assert!(
+            message.ends_with("Missing keys: [1, 2]. New keys: [6, 7]"),
+            "{message}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_assertions_are_independent() {
+        let ctx = snapshots_context().await;
+
+        // Only the new keys exceed their limit
+        let result = evaluate(
+            &ctx,
+            &customer_keys(KeySetAssertions {
+                max_missing: 2,
+                max_new_ratio: 0.2,
+            })
+            .max_examples_reported(1),
+        )
+        .await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        let message = result.message.unwrap();
+        assert!(!message.contains("previous keys are missing"), "{message}");
+        assert!(
+            message.ends_with("Missing keys: [1]. New keys: [6]"),
+            "{message}"
+        );
+
+        let lenient = customer_keys(KeySetAssertions {
+            max_missing: 2,
+            max_new_ratio: 0.4,
+        });
+        let result = evaluate(&ctx, &lenient).await;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.6));
+
+        let retaining = lenient.min_retained_fraction(0.8);
+        let result = evaluate(&ctx, &retaining).await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert!(result
+            .message
+            .unwrap()
+            .contains("60.00% of previous keys are retained (minimum 80.00%)"));
+    }
+
+    #[tokio::test]
+    async fn test_multi_column_keys() {
+        let ctx = snapshots_context().await;
+
+        let constraint = KeySetComparisonConstraint::new(
+            "dim_customer",
+            "dim_customer_prev",
+            vec!["customer_id", "region"],
+            KeySetAssertions {
+                max_missing: 2,
+                max_new_ratio: 0.5,
+            },
+        )
+        .unwrap()
+        .min_retained_fraction(0.7);
+        let result = evaluate(&ctx, &constraint).await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.details["new_keys"], 3.0);
+        assert_eq!(result.details["current_keys"], 6.0);
+        let message = result.message.unwrap();
+        assert!(
+            message.contains("Key set of dim_customer on (customer_id, region)"),
+            "{message}"
+        );
+        assert!(
+            message.ends_with("New keys: [(5, EU), (6, US), (7, US)]"),
+            "{message}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_snapshots() {
+        let ctx = snapshots_context().await;
+
+        let both_empty = KeySetComparisonConstraint::new(
+            "empty_cur",
+            "empty_prev",
+            vec!["customer_id"],
+            KeySetAssertions::default(),
+        )
+        .unwrap();
+        assert!(evaluate(&ctx, &both_empty).await.is_empty_table());
+
+        // Every key of a first load is new
+        let first_load = KeySetComparisonConstraint::new(
+            "dim_customer",
+            "empty_prev",
+            vec!["customer_id"],
+            KeySetAssertions::default(),
+        )
+        .unwrap();
+        let result = evaluate(&ctx, &first_load).await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(1.0));
+        assert_eq!(result.details["new_ratio"], 1.0);
+    }
+
+    #[test]
+    fn test_constraint_configuration() {
+        let assertions = KeySetAssertions::default();
+        assert!(
+            KeySetComparisonConstraint::new("a", "b", Vec::<String>::new(), assertions).is_err()
+        );
+        assert!(KeySetComparisonConstraint::new("a", "b", vec!["id; --"], assertions).is_err());
+        assert!(KeySetComparisonConstraint::new(
+            "a",
+            "b",
+            vec!["id"],
+            KeySetAssertions {
+                max_missing: 0,
+                max_new_ratio: 1.5,
+            },
+        )
+        .is_err());
+
+        let constraint = customer_keys(KeySetAssertions {
+            max_missing: 0,
+            max_new_ratio: 0.02,
+        });
+        assert_eq!(constraint.name(), "key_set_comparison");
+        assert_eq!(
+            constraint.referenced_tables(),
+            vec!["dim_customer", "dim_customer_prev"]
+        );
+        assert_eq!(
+            constraint.describe(),
+            "dim_customer must keep every key of dim_customer_prev on 'customer_id' and add at most 2% new keys"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Retained fraction must be between 0.0 and 1.0")]
+    fn test_invalid_retained_fraction() {
+        customer_keys(KeySetAssertions::default()).min_retained_fraction(1.5);
+    }
+}
@@ -6,10 +6,12 @@
 //!
 //! And adds support for new patterns like between, exactly, and not_empty.
 
-use crate::core::{current_validation_context, Constraint, ConstraintResult, ConstraintStatus};
+use crate::core::{
+    current_validation_context, Constraint, ConstraintResult, ConstraintStatus, NullPolicy,
+    QualityDimension, QuarantineSink, RatioQueryBuilder,
+};
 use crate::error::Result;
 use crate::security::SqlSecurity;
-use arrow::array::Array;
 use async_trait::async_trait;
 use datafusion::execution::context::SessionContext;
 use serde::{Deserialize, Serialize};
@@ -105,6 +107,9 @@ pub struct LengthConstraint {
     column: String,
     /// The length assertion to evaluate
     assertion: LengthAssertion,
+    /// Sink receiving the rows that violate the assertion
+    #[serde(skip)]
+    quarantine: Option<QuarantineSink>,
 }
 
 impl LengthConstraint {
@@ -118,9 +123,16 @@ impl LengthConstraint {
         Self {
             column: column.into(),
             assertion,
+            quarantine: None,
         }
     }
 
+    /// Exports every non-null value violating the assertion to `sink` when evaluated.
+    pub fn with_quarantine(mut self, sink: QuarantineSink) -> Self {
+        self.quarantine = Some(sink);
+        self
+    }
+
     /// Creates a minimum length constraint.
     pub fn min(column: impl Into<String>, min_length: usize) -> Self {
         Self::new(column, LengthAssertion::Min(min_length))
@@ -146,54 +158,22 @@ impl LengthConstraint {
     pub fn not_empty(column: impl Into<String>) -> Self {
         Self::new(column, LengthAssertion::NotEmpty)
     }
-}
 
-#[async_trait]
-impl Constraint for LengthConstraint {
-    #[instrument(skip(self, ctx), fields(
-        column = %self.column,
-        assertion = %self.assertion
-    ))]
-    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+    async fn evaluate_length(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
         let column_identifier = SqlSecurity::escape_identifier(&self.column)?;
         let condition = self.assertion.sql_condition(&column_identifier);
 
         // Get the table name from the validation context
-
         let validation_ctx = current_validation_context();
-
         let table_name = validation_ctx.table_name();
 
-        let sql = format!(
-            "SELECT 
-                COUNT(CASE WHEN {condition} OR {column_identifier} IS NULL THEN 1 END) * 1.0 / NULLIF(COUNT(*), 0) as ratio
-            FROM {table_name}"
-        );
-
-        let df = ctx.sql(&sql).await?;
-        let batches = df.collect().await?;
-
-        if batches.is_empty() || batches[0].num_rows() == 0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        let ratio_array = batches[0]
-            .column(0)
-            .as_any()
-            .downcast_ref::<arrow::array::Float64Array>()
-            .ok_or_else(|| {
-                crate::error::TermError::constraint_evaluation(
-                    self.name(),
-                    "Failed to extract ratio from result",
-                )
-            })?;
-
-        // If ratio is NULL, it means no data (COUNT(*) = 0)
-        if ratio_array.is_null(0) {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        let ratio = ratio_array.value(0);
+        let counts = RatioQueryBuilder::new(table_name, condition)
+            .nulls(column_identifier, NullPolicy::Conforming)
+            .execute(ctx)
+            .await?;
+        let Some(ratio) = counts.ratio() else {
+            return Ok(ConstraintResult::empty_table());
+        };
 
         let status = if ratio >= 1.0 {
             ConstraintStatus::Success
@@ -215,13 +195,64 @@ impl Constraint for LengthConstraint {
             status,
             metric: Some(ratio),
             message,
+            quarantine: None,
+            constraint_id: None,
+            details: Default::default(),
         })
     }
+}
+
+#[async_trait]
+impl Constraint for LengthConstraint {
+    #[instrument(skip(self, ctx), fields(
+        column = %self.column,
+        assertion = %self.assertion
+    ))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let result = self.evaluate_length(ctx).await?;
+        match &self.quarantine {
+            Some(sink) if !result.status.is_skipped() => {
+                let column_identifier = SqlSecurity::escape_identifier(&self.column)?;
+                let condition = self.assertion.sql_condition(&column_identifier);
+                let reason = format!(
+                    "'expected {}'",
+                    self.assertion.description().replace('\'', "''")
+                );
+                let summary = sink
+                    .write(
+                        ctx,
+                        self.name(),
+                        &format!("{column_identifier} IS NOT NULL AND NOT ({condition})"),
+                        &reason,
+                    )
+                    .await?;
+                Ok(result.with_quarantine(summary))
+            }
+            _ => Ok(result),
+        }
+    }
 
     fn name(&self) -> &str {
         self.assertion.name()
     }
 
+    fn describe(&self) -> String {
+        match &self.assertion {
+            LengthAssertion::NotEmpty => {
+                format!("{} must not be empty in any non-null value", self.column)
+            }
+            assertion => format!(
+                "{} must be {} long in every non-null value",
+                self.column,
+                assertion.description()
+            ),
+        }
+    }
+
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Validity)
+    }
+
     fn column(&self) -> Option<&str> {
         Some(&self.column)
     }
@@ -0,0 +1,551 @@
+//! Constraints on list columns.
+//!
+//! Nested data often stores repeated values in a list column, such as
+//! `tags: List<Utf8>`. This module validates such columns without flattening
+//! them first:
+//! - [`ListLengthConstraint`] asserts on the minimum, maximum or mean number of
+//!   elements of the non-null lists
+//! - [`ListElementConstraint`] checks the fraction of all non-null elements that
+//!   are among allowed values or match a format
+//!
+//! Both accept a dotted path to a list nested in a struct column (see
+//! [`ColumnPath`]).
+
+use std::fmt;
+
+use arrow::array::{Array, Float64Array, Int64Array};
+use arrow::datatypes::DataType;
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use super::format::{format_condition, register_format_functions};
+use super::{Assertion, FormatOptions, FormatType};
+use crate::core::{
+    collect_with_metrics, current_validation_context, describe_list, describe_percent,
+    skipped_without_values, ColumnPath, Constraint, ConstraintResult, ParameterRef,
+    QualityDimension,
+};
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+
+/// Resolves `column` in `table_name` and returns its SQL expression, failing if
+/// it is not a list column.
+async fn list_expression(ctx: &SessionContext, table_name: &str, column: &str) -> Result<String> {
+    let path = ColumnPath::resolve_in(ctx, table_name, column).await?;
+    match path.data_type() {
+        DataType::List(_) | DataType::LargeList(_) | DataType::FixedSizeList(_, _) => path.to_sql(),
+        other => Err(TermError::Configuration(format!(
+            "Column '{column}' is not a list column (found {other})"
+        ))),
+    }
+}
+
+/// The statistic of the element counts a [`ListLengthConstraint`] asserts on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ListLengthStatistic {
+    /// The number of elements of the shortest list
+    Min,
+    /// The number of elements of the longest list
+    Max,
+    /// The mean number of elements per list
+    Mean,
+}
+
+impl ListLengthStatistic {
+    /// Returns the name of the statistic.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ListLengthStatistic::Min => "min",
+            ListLengthStatistic::Max => "max",
+            ListLengthStatistic::Mean => "mean",
+        }
+    }
+
+    /// Returns the SQL aggregate function computing the statistic.
+    fn aggregate(&self) -> &'static str {
+        match self {
+            ListLengthStatistic::Min => "MIN",
+            ListLengthStatistic::Max => "MAX",
+            ListLengthStatistic::Mean => "AVG",
+        }
+    }
+
+    /// Returns the statistic as used in [`Constraint::describe`].
+    fn describe(&self) -> &'static str {
+        match self {
+            ListLengthStatistic::Min => "minimum",
+            ListLengthStatistic::Max => "maximum",
+            ListLengthStatistic::Mean => "mean",
+        }
+    }
+}
+
+impl fmt::Display for ListLengthStatistic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A constraint asserting on the number of elements of the lists in a column.
+///
+/// NULL lists are ignored; empty lists count as zero elements.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::constraints::{Assertion, ListLengthConstraint};
+/// use term_guard::core::Constraint;
+///
+/// // Every product has between one and ten tags
+/// let min = ListLengthConstraint::min("tags", Assertion::GreaterThanOrEqual(1.0));
+/// let max = ListLengthConstraint::max("tags", Assertion::LessThanOrEqual(10.0));
+///
+/// // Products have three tags on average
+/// let mean = ListLengthConstraint::mean("tags", Assertion::Between(2.5, 3.5));
+/// assert_eq!(
+///     mean.describe(),
+///     "the mean number of elements in tags must be between 2.5 and 3.5"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ListLengthConstraint {
+    column: String,
+    statistic: ListLengthStatistic,
+    assertion: Assertion,
+}
+
+impl ListLengthConstraint {
+    /// Creates a constraint asserting on a statistic of the list lengths of `column`.
+    pub fn new(
+        column: impl Into<String>,
+        statistic: ListLengthStatistic,
+        assertion: Assertion,
+    ) -> Self {
+        Self {
+            column: column.into(),
+            statistic,
+            assertion,
+        }
+    }
+
+    /// Asserts on the number of elements of the shortest list.
+    pub fn min(column: impl Into<String>, assertion: Assertion) -> Self {
+        Self::new(column, ListLengthStatistic::Min, assertion)
+    }
+
+    /// Asserts on the number of elements of the longest list.
+    pub fn max(column: impl Into<String>, assertion: Assertion) -> Self {
+        Self::new(column, ListLengthStatistic::Max, assertion)
+    }
+
+    /// Asserts on the mean number of elements per list.
+    pub fn mean(column: impl Into<String>, assertion: Assertion) -> Self {
+        Self::new(column, ListLengthStatistic::Mean, assertion)
+    }
+}
+
+#[async_trait]
+impl Constraint for ListLengthConstraint {
+    #[instrument(skip(self, ctx), fields(
+        column = %self.column,
+        statistic = %self.statistic,
+        assertion = %self.assertion
+    ))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        SqlSecurity::validate_identifier(&self.column)?;
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+        let list = list_expression(ctx, table_name, &self.column).await?;
+
+        let length =
+            format!("CASE WHEN {list} IS NOT NULL THEN COALESCE(cardinality({list}), 0) END");
+        let sql = format!(
+            "SELECT {}(CAST({length} AS DOUBLE)) AS value, COUNT({list}) AS lists FROM {table_name}",
+            self.statistic.aggregate()
+        );
+        debug!(constraint.name = %self.name(), sql = %sql, "Computing list lengths");
+
+        let batches = collect_with_metrics(ctx.sql(&sql).await?).await?;
+        let batch = batches
+            .iter()
+            .find(|batch| batch.num_rows() > 0)
+            .ok_or_else(|| TermError::Internal("List length query returned no rows".to_string()))?;
+        let lists = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .ok_or_else(|| TermError::Internal("Failed to extract list count".to_string()))?
+            .value(0);
+        if lists == 0 {
+            return skipped_without_values(ctx, table_name, "No non-null lists to validate").await;
+        }
+
+        let values = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .ok_or_else(|| TermError::Internal("Failed to extract list length".to_string()))?;
+        if values.is_null(0) {
+            return Err(TermError::Internal(
+                "List length statistic is NULL".to_string(),
+            ));
+        }
+        let value = values.value(0);
+
+        let assertion = self.assertion.resolve()?;
+        Ok(assertion
+            .explain(
+                value,
+                format!("{} list length of column '{}'", self.statistic, self.column),
+            )
+            .to_result())
+    }
+
+    fn name(&self) -> &str {
+        "list_length"
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "the {} number of elements in {} must be {}",
+            self.statistic.describe(),
+            self.column,
+            self.assertion.requirement()
+        )
+    }
+
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Validity)
+    }
+
+    fn column(&self) -> Option<&str> {
+        Some(&self.column)
+    }
+
+    fn parameters(&self) -> Vec<ParameterRef> {
+        self.assertion.parameters("list length assertion")
+    }
+}
+
+/// What the elements of a list column are checked against.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListElementRule {
+    /// Elements must be one of the allowed values
+    OneOf(Vec<String>),
+    /// Elements must match a format
+    Format(FormatType, FormatOptions),
+}
+
+/// A constraint checking the elements of the lists in a column.
+///
+/// The metric is the fraction of all non-null elements, across every list of the
+/// column, that satisfy the rule; NULL lists and NULL elements are ignored.
+/// Elements are compared as strings.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::constraints::{FormatOptions, FormatType, ListElementConstraint};
+/// use term_guard::core::Constraint;
+///
+/// // Every tag comes from the catalog
+/// let tags = ListElementConstraint::is_contained_in("tags", ["sale", "new", "eco"], 1.0).unwrap();
+/// assert_eq!(tags.describe(), "every element of tags must be one of 'sale', 'new' and 'eco'");
+///
+/// // Almost all contact addresses are emails
+/// let contacts = ListElementConstraint::matches_format(
+///     "contacts",
+///     FormatType::Email,
+///     0.95,
+///     FormatOptions::default(),
+/// )
+/// .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ListElementConstraint {
+    column: String,
+    rule: ListElementRule,
+    threshold: f64,
+}
+
+impl ListElementConstraint {
+    /// Creates a constraint requiring at least `threshold` of the elements of
+    /// `column` to satisfy `rule`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the column name is invalid, the threshold is not
+    /// between 0.0 and 1.0, or the rule is a detection format such as
+    /// `CreditCard { detect_only: true }`.
+    pub fn new(column: impl Into<String>, rule: ListElementRule, threshold: f64) -> Result<Self> {
+        let column = column.into();
+        SqlSecurity::validate_identifier(&column)?;
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(TermError::Configuration(
+                "Threshold must be between 0.0 and 1.0".to_string(),
+            ));
+        }
+        if let ListElementRule::Format(format, _) = &rule {
+            if matches!(format, FormatType::CreditCard { detect_only: true }) {
+                return Err(TermError::Configuration(
+                    "Detection formats are not supported for list elements".to_string(),
+                ));
+            }
+            format.get_pattern()?;
+        }
+
+        Ok(Self {
+            column,
+            rule,
+            threshold,
+        })
+    }
+
+    /// Requires at least `threshold` of the elements to be one of `values`.
+    pub fn is_contained_in<I, S>(
+        column: impl Into<String>,
+        values: I,
+        threshold: f64,
+    ) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let values = values.into_iter().map(Into::into).collect();
+        Self::new(column, ListElementRule::OneOf(values), threshold)
+    }
+
+    /// Requires at least `threshold` of the elements to match `format`.
+    pub fn matches_format(
+        column: impl Into<String>,
+        format: FormatType,
+        threshold: f64,
+        options: FormatOptions,
+    ) -> Result<Self> {
+        Self::new(column, ListElementRule::Format(format, options), threshold)
+    }
+
+    /// Returns the SQL condition an element has to satisfy.
+    fn condition(&self, element: &str) -> Result<String> {
+        match &self.rule {
+            ListElementRule::OneOf(values) => {
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|value| format!("'{}'", value.replace('\'', "''")))
+                    .collect();
+                Ok(format!("{element} IN ({})", values.join(", ")))
+            }
+            ListElementRule::Format(format, options) => format_condition(format, options, element),
+        }
+    }
+}
+
+#[async_trait]
+impl Constraint for ListElementConstraint {
+    #[instrument(skip(self, ctx), fields(
+        column = %self.column,
+        threshold = %self.threshold
+    ))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+        let list = list_expression(ctx, table_name, &self.column).await?;
+        if let ListElementRule::Format(format, _) = &self.rule {
+            register_format_functions(format, ctx);
+        }
+
+        let element = "CAST(element AS VARCHAR)";
+        let condition = self.condition(element)?;
+        let sql = format!(
+            "SELECT COUNT(element) AS total, COUNT(CASE WHEN {condition} THEN 1 END) AS matching \
+             FROM (SELECT unnest({list}) AS element FROM {table_name}) AS elements"
+        );
+        debug!(constraint.name = %self.name(), sql = %sql, "Checking list elements");
+
+        let batches = collect_with_metrics(ctx.sql(&sql).await?).await?;
+        let batch = batches
+            .iter()
+            .find(|batch| batch.num_rows() > 0)
+            .ok_or_else(|| {
+                TermError::Internal("List element query returned no rows".to_string())
+            })?;
+        let count = |index: usize| {
+            batch
+                .column(index)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .map(|counts| counts.value(0) as f64)
+                .ok_or_else(|| TermError::Internal("Failed to extract element counts".to_string()))
+        };
+        let total = count(0)?;
+        let matching = count(1)?;
+        if total == 0.0 {
+            return skipped_without_values(ctx, table_name, "No list elements to validate").await;
+        }
+
+        let ratio = matching / total;
+        if ratio >= self.threshold {
+            Ok(ConstraintResult::success_with_metric(ratio))
+        } else {
+            Ok(ConstraintResult::failure_with_metric(
+                ratio,
+                format!(
+                    "{:.2}% of the elements of '{}' satisfy the rule, below threshold {:.2}%",
+                    ratio * 100.0,
+                    self.column,
+                    self.threshold * 100.0
+                ),
+            ))
+        }
+    }
+
+    fn name(&self) -> &str {
+        match &self.rule {
+            ListElementRule::OneOf(_) => "list_element_containment",
+            ListElementRule::Format(_, _) => "list_element_format",
+        }
+    }
+
+    fn describe(&self) -> String {
+        let subject = if self.threshold >= 1.0 {
+            format!("every element of {}", self.column)
+        } else {
+            format!(
+                "at least {} of the elements of {}",
+                describe_percent(self.threshold),
+                self.column
+            )
+        };
+        match &self.rule {
+            ListElementRule::OneOf(values) => {
+                let values: Vec<String> = values.iter().map(|value| format!("'{value}'")).collect();
+                format!("{subject} must be one of {}", describe_list(&values))
+            }
+            ListElementRule::Format(format, _) => {
+                format!("{subject} must {}", format.requirement())
+            }
+        }
+    }
+
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Validity)
+    }
+
+    fn column(&self) -> Option<&str> {
+        Some(&self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ConstraintStatus;
+    use crate::test_helpers::evaluate_constraint_with_context;
+
+    async fn context() -> SessionContext {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE TABLE data AS \
+             SELECT make_array('sale', 'new') AS tags, \
+                    named_struct('emails', make_array('a@example.com', 'b@example.com')) AS contact \
+             UNION ALL SELECT make_array('eco', 'sale', 'outlet'), \
+                    named_struct('emails', make_array('not-an-email')) \
+             UNION ALL SELECT CASE WHEN false THEN make_array('') END, \
+                    named_struct('emails', CASE WHEN false THEN make_array('') END)",
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_list_length_statistics() {
+        let ctx = context().await;
+
+        let max = ListLengthConstraint::max("tags", Assertion::Equals(3.0));
+        let result = evaluate_constraint_with_context(&max, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+
+        // The NULL list is ignored
+        let mean = ListLengthConstraint::mean("tags", Assertion::Equals(2.5));
+        let result = evaluate_constraint_with_context(&mean, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.metric, Some(2.5));
+
+        let min = ListLengthConstraint::min("contact.emails", Assertion::GreaterThan(1.0));
+        let result = evaluate_constraint_with_context(&min, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_list_elements() {
+        let ctx = context().await;
+
+        let tags =
+            ListElementConstraint::is_contained_in("tags", ["sale", "new", "eco"], 0.8).unwrap();
+        let result = evaluate_constraint_with_context(&tags, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.8));
+
+        let emails = ListElementConstraint::matches_format(
+            "contact.emails",
+            FormatType::Email,
+            1.0,
+            FormatOptions::default(),
+        )
+        .unwrap();
+        let result = evaluate_constraint_with_context(&emails, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert!((result.metric.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_non_list_column_is_rejected() {
+        let ctx = context().await;
+
+        let constraint = ListLengthConstraint::max("contact", Assertion::LessThan(5.0));
+        let error = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("is not a list column"),
+            "{error}"
+        );
+
+        let constraint =
+            ListElementConstraint::is_contained_in("contact.phones", ["x"], 1.0).unwrap();
+        let error = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("has no field 'phones'"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_detection_formats_are_rejected() {
+        let result = ListElementConstraint::matches_format(
+            "cards",
+            FormatType::CreditCard { detect_only: true },
+            0.0,
+            FormatOptions::default(),
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,408 @@
+//! Row count checks against an expected value read at evaluation time.
+//!
+//! [`ManifestSizeConstraint`] compares the row count of the validated table with a
+//! number supplied by an [`ExpectedValueProvider`], such as the `expected_rows` field
+//! of a manifest written next to each data drop. The comparison allows for a
+//! [`SizeTolerance`].
+//!
+//! A provider that cannot supply the value, for example because the manifest is
+//! missing or the JSON pointer does not resolve to a number, makes the evaluation
+//! fail with [`TermError::Configuration`] rather than reporting a size mismatch.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::constraints::{JsonFileProvider, ManifestSizeConstraint, SizeTolerance};
+//! use term_guard::core::{Check, Level};
+//!
+//! let check = Check::builder("batch_completeness")
+//!     .level(Level::Error)
+//!     .with_constraint(ManifestSizeConstraint::new(
+//!         JsonFileProvider::new("data/batch_42/manifest.json", "/expected_rows"),
+//!         SizeTolerance::WithinPercent(0.5),
+//!     ))
+//!     .build();
+//! ```
+
+use crate::constraints::Assertion;
+use crate::core::{
+    current_validation_context, fetch_metrics, Constraint, ConstraintMetadata, ConstraintResult,
+    MetricKey,
+};
+use crate::prelude::*;
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{debug, instrument};
+
+/// Supplies the expected value of a check when the constraint is evaluated.
+///
+/// Implement this trait to read expectations from other sources, such as a
+/// catalog service or an environment variable.
+#[async_trait]
+pub trait ExpectedValueProvider: fmt::Debug + Send + Sync {
+    /// Returns the expected value.
+    ///
+    /// Errors are reported as configuration errors by the constraint.
+    async fn expected_value(&self) -> Result<f64>;
+
+    /// Describes where the value comes from, for messages and metadata.
+    fn source(&self) -> String;
+}
+
+/// An expected value fixed when the provider is created.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StaticProvider {
+    value: f64,
+}
+
+impl StaticProvider {
+    /// Creates a provider that always returns `value`.
+    pub fn new(value: f64) -> Self {
+        Self { value }
+    }
+}
+
+#[async_trait]
+impl ExpectedValueProvider for StaticProvider {
+    async fn expected_value(&self) -> Result<f64> {
+        Ok(self.value)
+    }
+
+    fn source(&self) -> String {
+        format!("static value {}", self.value)
+    }
+}
+
+/// Reads a number from a JSON file, located with a JSON pointer (RFC 6901).
+///
+/// The file is read on every evaluation, so a manifest written after the suite
+/// was built is picked up.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::constraints::JsonFileProvider;
+///
+/// // {"expected_rows": 1203944, "batch_id": "..."}
+/// let provider = JsonFileProvider::new("manifest.json", "/expected_rows");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonFileProvider {
+    /// Path of the JSON file
+    pub path: PathBuf,
+    /// JSON pointer to the number, such as `/expected_rows` or `/counts/0/rows`
+    pub json_pointer: String,
+}
+
+impl JsonFileProvider {
+    /// Creates a provider reading the number at `json_pointer` from the file at `path`.
+    pub fn new(path: impl Into<PathBuf>, json_pointer: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            json_pointer: json_pointer.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl ExpectedValueProvider for JsonFileProvider {
+    async fn expected_value(&self) -> Result<f64> {
+        let path = self.path.display();
+        let contents = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(|e| TermError::Configuration(format!("Cannot read {path}: {e}")))?;
+        let document: serde_json::Value = serde_json::from_str(&contents)
+            .map_err(|e| TermError::Configuration(format!("{path} is not valid JSON: {e}")))?;
+        let value = document.pointer(&self.json_pointer).ok_or_else(|| {
+            TermError::Configuration(format!(
+                "JSON pointer '{}' does not match a value in {path}",
+                self.json_pointer
+            ))
+        })?;
+        value.as_f64().ok_or_else(|| {
+            TermError::Configuration(format!(
+                "Value at '{}' in {path} is not a number: {value}",
+                self.json_pointer
+            ))
+        })
+    }
+
+    fn source(&self) -> String {
+        format!("{}#{}", self.path.display(), self.json_pointer)
+    }
+}
+
+/// How far the row count may deviate from the expected value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SizeTolerance {
+    /// The row count must equal the expected value
+    Exact,
+    /// The row count may differ by this many percent of the expected value
+    WithinPercent(f64),
+    /// The row count may differ by this many rows
+    WithinRows(u64),
+    /// The row count must be at least the expected value
+    AtLeast,
+    /// The row count must be at most the expected value
+    AtMost,
+}
+
+impl SizeTolerance {
+    /// Returns the assertion the row count must satisfy for an expected value.
+    pub fn assertion(&self, expected: f64) -> Assertion {
+        match *self {
+            SizeTolerance::Exact => Assertion::Equals(expected),
+            SizeTolerance::WithinPercent(percent) => {
+                let slack = expected.abs() * percent.abs() / 100.0;
+                Assertion::Between(expected - slack, expected + slack)
+            }
+            SizeTolerance::WithinRows(rows) => {
+                Assertion::Between(expected - rows as f64, expected + rows as f64)
+            }
+            SizeTolerance::AtLeast => Assertion::GreaterThanOrEqual(expected),
+            SizeTolerance::AtMost => Assertion::LessThanOrEqual(expected),
+        }
+    }
+}
+
+impl fmt::Display for SizeTolerance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SizeTolerance::Exact => write!(f, "exact"),
+            SizeTolerance::WithinPercent(percent) => write!(f, "within {percent}%"),
+            SizeTolerance::WithinRows(rows) => write!(f, "within {rows} rows"),
+            SizeTolerance::AtLeast => write!(f, "at least"),
+            SizeTolerance::AtMost => write!(f, "at most"),
+        }
+    }
+}
+
+/// A constraint that checks the row count against an expected value read at
+/// evaluation time.
+///
+/// Like [`SizeConstraint`](super::SizeConstraint), the row count is shared with
+/// other constraints of the run. The metric is the row count.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::constraints::{ManifestSizeConstraint, SizeTolerance, StaticProvider};
+/// use term_guard::core::Constraint;
+///
+/// let constraint =
+///     ManifestSizeConstraint::new(StaticProvider::new(1000.0), SizeTolerance::WithinRows(10));
+/// assert_eq!(constraint.name(), "manifest_size");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManifestSizeConstraint {
+    /// Where the expected row count comes from
+    provider: Arc<dyn ExpectedValueProvider>,
+    /// Allowed deviation from the expected row count
+    tolerance: SizeTolerance,
+}
+
+impl ManifestSizeConstraint {
+    /// Creates a constraint comparing the row count with the provider's value.
+    pub fn new(provider: impl ExpectedValueProvider + 'static, tolerance: SizeTolerance) -> Self {
+        Self {
+            provider: Arc::new(provider),
+            tolerance,
+        }
+    }
+
+    /// Returns the allowed deviation from the expected row count.
+    pub fn tolerance(&self) -> SizeTolerance {
+        self.tolerance
+    }
+}
+
+#[async_trait]
+impl Constraint for ManifestSizeConstraint {
+    #[instrument(skip(self, ctx), fields(
+        constraint.name = %self.name(),
+        constraint.tolerance = %self.tolerance
+    ))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let source = self.provider.source();
+        let expected = self.provider.expected_value().await.map_err(|e| {
+            let reason = match e {
+                TermError::Configuration(message) => message,
+                e => e.to_string(),
+            };
+            TermError::Configuration(format!(
+                "Failed to read expected row count from {source}: {reason}"
+            ))
+        })?;
+        let assertion = self.tolerance.assertion(expected);
+        debug!(expected, %assertion, "Resolved expected row count");
+
+        // Count rows, sharing the count with other constraints of the run
+        let validation_ctx = current_validation_context();
+        let row_count = fetch_metrics(ctx, &[MetricKey::row_count(validation_ctx.table_name())])
+            .await?[0] as f64;
+
+        let explanation = assertion.explain(row_count, "row count");
+        if explanation.passed {
+            Ok(explanation.to_result())
+        } else {
+            Ok(ConstraintResult::failure_with_metric(
+                row_count,
+                format!(
+                    "{explanation} (expected {expected} from {source}, {})",
+                    self.tolerance
+                ),
+            ))
+        }
+    }
+
+    fn name(&self) -> &str {
+        "manifest_size"
+    }
+
+    fn requires_full_data(&self) -> bool {
+        true
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        ConstraintMetadata::new()
+            .with_description(format!(
+                "Checks that the dataset size matches the expected value from {} ({})",
+                self.provider.source(),
+                self.tolerance
+            ))
+            .with_custom("expected_source", self.provider.source())
+            .with_custom("tolerance", self.tolerance.to_string())
+            .with_custom("constraint_type", "statistical")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ConstraintStatus;
+    use crate::test_helpers::evaluate_constraint_with_context;
+
+    async fn create_test_context(rows: usize) -> SessionContext {
+        let ctx = SessionContext::new();
+        let df = ctx
+            .sql(&format!(
+                "SELECT * FROM generate_series(1, {rows}) AS t(id)"
+            ))
+            .await
+            .unwrap();
+        ctx.register_table("data", df.into_view()).unwrap();
+        ctx
+    }
+
+    fn write_manifest(contents: &str) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.json");
+        std::fs::write(&path, contents).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn test_tolerance_assertions() {
+        assert_eq!(
+            SizeTolerance::WithinPercent(0.5).assertion(1000.0),
+            Assertion::Between(995.0, 1005.0)
+        );
+        assert_eq!(
+            SizeTolerance::WithinRows(3).assertion(10.0),
+            Assertion::Between(7.0, 13.0)
+        );
+        assert_eq!(
+            SizeTolerance::AtLeast.assertion(10.0),
+            Assertion::GreaterThanOrEqual(10.0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_static_provider() {
+        let ctx = create_test_context(100).await;
+
+        let constraint =
+            ManifestSizeConstraint::new(StaticProvider::new(100.0), SizeTolerance::Exact);
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(100.0));
+
+        let constraint = ManifestSizeConstraint::new(
+            StaticProvider::new(120.0),
+            SizeTolerance::WithinPercent(10.0),
+        );
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert!(result
+            .message
+            .unwrap()
+            .contains("expected 120 from static value 120, within 10%"));
+    }
+
+    #[tokio::test]
+    async fn test_json_file_provider() {
+        let ctx = create_test_context(1000).await;
+        let (_dir, path) = write_manifest(
+            r#"{"expected_rows": 1004, "batch_id": "batch_42", "parts": [{"rows": 1000}]}"#,
+        );
+
+        let constraint = ManifestSizeConstraint::new(
+            JsonFileProvider::new(&path, "/expected_rows"),
+            SizeTolerance::WithinPercent(0.5),
+        );
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+
+        let constraint = ManifestSizeConstraint::new(
+            JsonFileProvider::new(&path, "/parts/0/rows"),
+            SizeTolerance::Exact,
+        );
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+    }
+
+    #[tokio::test]
+    async fn test_provider_failures_are_configuration_errors() {
+        let ctx = create_test_context(10).await;
+        let (dir, path) = write_manifest(r#"{"expected_rows": "unknown"}"#);
+
+        let cases = [
+            (
+                JsonFileProvider::new(dir.path().join("missing.json"), "/expected_rows"),
+                "Cannot read",
+            ),
+            (
+                JsonFileProvider::new(&path, "/rows"),
+                "does not match a value",
+            ),
+            (
+                JsonFileProvider::new(&path, "/expected_rows"),
+                "is not a number",
+            ),
+        ];
+        for (provider, expected_message) in cases {
+            let constraint = ManifestSizeConstraint::new(provider, SizeTolerance::Exact);
+            let error = evaluate_constraint_with_context(&constraint, &ctx, "data")
+                .await
+                .unwrap_err()
+                .downcast::<TermError>()
+                .unwrap();
+            assert!(matches!(*error, TermError::Configuration(_)));
+            let message = error.to_string();
+            assert!(message.contains("Failed to read expected row count from"));
+            assert!(message.contains(expected_message), "{message}");
+        }
+    }
+}
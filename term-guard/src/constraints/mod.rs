@@ -165,12 +165,22 @@
 //! - [`QuantileConstraint`] - Percentile analysis
 //! - [`CorrelationConstraint`] - Column relationships
 //! - [`HistogramConstraint`] - Value distribution
+//! - [`TopKStabilityConstraint`] - Changes in the most frequent values
+//! - [`TrueRateConstraint`] - Fraction of true values in boolean columns
 //!
 //! ### Pattern & Format
 //! - [`FormatConstraint`] - Pattern matching
 //! - [`LengthConstraint`] - String length validation
 //! - [`DataTypeConstraint`] - Type validation
 //!
+//! ### Time Series
+//! - [`TemporalGapConstraint`] - Gaps between consecutive timestamps
+//! - [`TimeWindowConstraint`] - Local time of day and day of week in a timezone
+//!
+//! ### Consistency
+//! - [`FunctionalDependencyConstraint`] - Columns determining another column
+//! - [`KeySetComparisonConstraint`] - Stable row identity between two snapshots
+//!
 //! ### Custom Rules
 //! - [`CustomSqlConstraint`] - SQL expressions
 //! - [`ColumnCountConstraint`] - Schema validation
@@ -186,44 +196,86 @@
 // Module declarations - only non-deprecated modules
 mod approx_count_distinct;
 mod assertion;
+mod cardinality;
+mod category_baseline;
 mod column_count;
 mod completeness;
+mod composite_key;
 mod correlation;
 mod cross_table_sum;
 mod custom_sql;
 mod datatype;
+mod distribution_stability;
+mod expression_equality;
 mod foreign_key;
 mod format;
+mod format_codes;
+mod functional_dependency;
 mod histogram;
+mod historical;
 mod join_coverage;
+mod key_set_comparison;
 mod length;
+mod list;
+mod manifest_size;
+mod non_finite;
+mod null_rate_comparison;
+mod numeric;
 mod quantile;
 mod size;
 mod statistics;
+mod temporal_gap;
 mod temporal_ordering;
+mod time_window;
+mod timezone;
+mod top_k_stability;
+mod true_rate;
 mod uniqueness;
 mod values;
 
 // Public exports
 pub use approx_count_distinct::ApproxCountDistinctConstraint;
-pub use assertion::Assertion;
+pub use assertion::{Assertion, AssertionExplanation, ParamAssertion};
+pub use cardinality::{CardinalityConstraint, CardinalitySpec};
+pub use category_baseline::{CategoryBaseline, CategoryBaselineConstraint};
 pub use column_count::ColumnCountConstraint;
 pub use completeness::CompletenessConstraint;
+pub use composite_key::NullKeyPolicy;
 pub use correlation::{CorrelationConstraint, CorrelationType};
 pub use cross_table_sum::CrossTableSumConstraint;
-pub use custom_sql::CustomSqlConstraint;
+pub use custom_sql::{CustomSqlConstraint, CustomSqlMode};
 pub use datatype::{
     DataTypeConstraint, DataTypeValidation, NumericValidation, StringTypeValidation,
     TemporalValidation,
 };
+pub use distribution_stability::DistributionStabilityConstraint;
+pub use expression_equality::{
+    EqualityTolerance, ExpressionEqualityConstraint, ExpressionMismatch,
+};
 pub use foreign_key::ForeignKeyConstraint;
+pub(crate) use format::{format_condition, register_format_functions};
 pub use format::{FormatConstraint, FormatOptions, FormatType};
+pub use functional_dependency::{DependencyDenominator, FunctionalDependencyConstraint};
 pub use histogram::{Histogram, HistogramAssertion, HistogramBucket, HistogramConstraint};
+pub use historical::{
+    HistoricalAssertionConstraint, HistoricalBound, HistoryAggregation, HistoryWindow,
+};
 pub use join_coverage::{CoverageType, JoinCoverageConstraint};
+pub use key_set_comparison::{KeySetAssertions, KeySetComparison, KeySetComparisonConstraint};
 pub use length::{LengthAssertion, LengthConstraint};
+pub use list::{ListElementConstraint, ListElementRule, ListLengthConstraint, ListLengthStatistic};
+pub use manifest_size::{
+    ExpectedValueProvider, JsonFileProvider, ManifestSizeConstraint, SizeTolerance, StaticProvider,
+};
+pub use non_finite::{NonFiniteConstraint, NonFinitePolicy, NON_FINITE_DETAIL};
+pub use null_rate_comparison::{ColumnNullRates, NullRateComparison, NullRateComparisonConstraint};
 pub use quantile::{QuantileConstraint, QuantileMethod};
 pub use size::SizeConstraint;
 pub use statistics::{MultiStatisticalConstraint, StatisticType, StatisticalConstraint};
+pub use temporal_gap::{TemporalGap, TemporalGapConstraint};
 pub use temporal_ordering::{TemporalOrderingConstraint, TemporalValidationType};
+pub use time_window::{TimeWindow, TimeWindowConstraint, TimeWindowRule};
+pub use top_k_stability::{TopKSimilarity, TopKStabilityConstraint};
+pub use true_rate::{BooleanParsing, TrueRateConstraint};
 pub use uniqueness::{NullHandling, UniquenessConstraint, UniquenessOptions, UniquenessType};
 pub use values::ContainmentConstraint;
@@ -0,0 +1,294 @@
+//! Handling of NaN and infinite values in numeric constraints.
+//!
+//! Floating point columns can hold `NaN`, `inf` and `-inf`, typically left behind
+//! by a division by zero or a failed parse upstream. SQL aggregates do not skip
+//! them the way they skip nulls: a single `NaN` turns a mean or a sum into `NaN`,
+//! which then fails or passes an assertion depending on how the comparison
+//! happens to treat it. [`NonFinitePolicy`] makes the behavior explicit for
+//! [`StatisticalConstraint`](super::StatisticalConstraint),
+//! [`QuantileConstraint`](super::QuantileConstraint) and
+//! [`CorrelationConstraint`](super::CorrelationConstraint), and
+//! [`NonFiniteConstraint`] checks that a column holds none of them.
+//!
+//! Whatever the policy, constraints on floating point columns report the number
+//! of non-finite values they saw as the [`NON_FINITE_DETAIL`] entry of
+//! [`ConstraintResult::details`]. Columns of other types cannot hold non-finite
+//! values and are evaluated as before.
+
+use crate::core::{
+    collect_with_metrics, current_validation_context, ColumnPath, Constraint, ConstraintMetadata,
+    ConstraintResult,
+};
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+use arrow::array::{Array, Int64Array};
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tracing::instrument;
+
+/// Name of the [`ConstraintResult::details`] entry holding the number of
+/// non-finite values of the column.
+pub const NON_FINITE_DETAIL: &str = "non_finite_count";
+
+/// How a numeric constraint treats NaN and infinite values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NonFinitePolicy {
+    /// Leave non-finite values out of the computation, like nulls
+    #[default]
+    Ignore,
+    /// Fail the constraint if the column holds any non-finite value
+    Fail,
+    /// Compute over all values, so non-finite values reach the result
+    Propagate,
+}
+
+impl fmt::Display for NonFinitePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ignore => write!(f, "ignore"),
+            Self::Fail => write!(f, "fail"),
+            Self::Propagate => write!(f, "propagate"),
+        }
+    }
+}
+
+/// Returns true if `column` of `table_name` is a floating point column.
+///
+/// Columns that cannot be resolved are reported as not floating point, leaving
+/// the error to the query of the constraint.
+pub(crate) async fn is_float_column(ctx: &SessionContext, table_name: &str, column: &str) -> bool {
+    ColumnPath::resolve_in(ctx, table_name, column)
+        .await
+        .is_ok_and(|path| path.data_type().is_floating())
+}
+
+/// SQL predicate that is true for NaN and infinite values of a float column.
+///
+/// Subtracting a value from itself gives `0` for finite values and `NaN` for
+/// `NaN`, `inf` and `-inf`, while nulls stay null.
+pub(crate) fn non_finite_predicate(column: &str) -> String {
+    format!("isnan({column} - {column})")
+}
+
+/// SQL aggregate counting the non-finite values of a float column.
+pub(crate) fn non_finite_count_sql(column: &str) -> String {
+    format!(
+        "SUM(CASE WHEN {} THEN 1 ELSE 0 END)",
+        non_finite_predicate(column)
+    )
+}
+
+/// SQL expression replacing the non-finite values of a float column with null.
+pub(crate) fn finite_values_sql(column: &str) -> String {
+    format!(
+        "CASE WHEN {} THEN NULL ELSE {column} END",
+        non_finite_predicate(column)
+    )
+}
+
+/// Reads a count computed by [`non_finite_count_sql`], which is null for an
+/// empty table.
+pub(crate) fn read_count(array: &dyn Array) -> Result<i64> {
+    let counts = array
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .ok_or_else(|| TermError::Internal("Failed to downcast to Int64Array".to_string()))?;
+    Ok(if counts.is_null(0) {
+        0
+    } else {
+        counts.value(0)
+    })
+}
+
+/// Records the number of non-finite values in `result`, if the column was
+/// checked for them.
+pub(crate) fn with_non_finite(result: ConstraintResult, count: Option<i64>) -> ConstraintResult {
+    match count {
+        Some(count) => result.with_detail(NON_FINITE_DETAIL, count as f64),
+        None => result,
+    }
+}
+
+/// The failure reported under [`NonFinitePolicy::Fail`].
+pub(crate) fn non_finite_failure(column: &str, count: i64) -> ConstraintResult {
+    ConstraintResult::failure(format!(
+        "Column '{column}' has {count} non-finite values (NaN or infinity)"
+    ))
+    .with_detail(NON_FINITE_DETAIL, count as f64)
+}
+
+/// A constraint checking that a column holds no NaN or infinite values.
+///
+/// The metric is the number of non-finite values, counted in a single scan.
+/// Columns that are not floating point always pass.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::constraints::NonFiniteConstraint;
+/// use term_guard::core::Constraint;
+///
+/// let constraint = NonFiniteConstraint::new("price").unwrap();
+/// assert_eq!(constraint.name(), "non_finite");
+/// ```
+#[derive(Debug, Clone)]
+pub struct NonFiniteConstraint {
+    column: String,
+}
+
+impl NonFiniteConstraint {
+    /// Creates a constraint checking that `column` holds no non-finite values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the column name is invalid.
+    pub fn new(column: impl Into<String>) -> Result<Self> {
+        let column = column.into();
+        SqlSecurity::validate_identifier(&column)?;
+        Ok(Self { column })
+    }
+}
+
+#[async_trait]
+impl Constraint for NonFiniteConstraint {
+    #[instrument(skip(self, ctx), fields(column = %self.column))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+
+        if !is_float_column(ctx, table_name, &self.column).await {
+            return Ok(
+                ConstraintResult::success_with_metric(0.0).with_detail(NON_FINITE_DETAIL, 0.0)
+            );
+        }
+
+        let column = SqlSecurity::escape_identifier(&self.column)?;
+        let sql = format!(
+            "SELECT COUNT(*) AS row_count, {} AS non_finite, \
+             SUM(CASE WHEN isnan({column}) THEN 1 ELSE 0 END) AS nan_count \
+             FROM {table_name}",
+            non_finite_count_sql(&column)
+        );
+        let df = ctx.sql(&sql).await?;
+        let batches = collect_with_metrics(df).await?;
+        if batches.is_empty() || batches[0].num_rows() == 0 {
+            return Ok(ConstraintResult::skipped("No data to validate"));
+        }
+
+        let batch = &batches[0];
+        if read_count(batch.column(0).as_ref())? == 0 {
+            return Ok(ConstraintResult::empty_table());
+        }
+        let non_finite = read_count(batch.column(1).as_ref())?;
+        let nan = read_count(batch.column(2).as_ref())?;
+        let infinite = non_finite - nan;
+
+        let result = if non_finite == 0 {
+            ConstraintResult::success_with_metric(0.0)
+        } else {
+            ConstraintResult::failure_with_metric(
+                non_finite as f64,
+                format!(
+                    "Column '{}' has {non_finite} non-finite values ({nan} NaN, {infinite} infinite)",
+                    self.column
+                ),
+            )
+        };
+        Ok(result
+            .with_detail(NON_FINITE_DETAIL, non_finite as f64)
+            .with_detail("nan_count", nan as f64)
+            .with_detail("infinite_count", infinite as f64))
+    }
+
+    fn name(&self) -> &str {
+        "non_finite"
+    }
+
+    fn column(&self) -> Option<&str> {
+        Some(&self.column)
+    }
+
+    fn describe(&self) -> String {
+        format!("{} must not contain NaN or infinite values", self.column)
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        ConstraintMetadata::for_column(&self.column)
+            .with_description(format!(
+                "Checks that column '{}' contains no NaN or infinite values",
+                self.column
+            ))
+            .with_custom("constraint_type", "non_finite")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ConstraintStatus;
+    use crate::test_helpers::evaluate_constraint_with_context;
+    use arrow::array::{Float64Array, Int64Array};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::datasource::MemTable;
+    use std::sync::Arc;
+
+    async fn create_test_context(values: Vec<Option<f64>>) -> SessionContext {
+        let ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("value", DataType::Float64, true),
+            Field::new("id", DataType::Int64, false),
+        ]));
+        let ids: Vec<i64> = (0..values.len() as i64).collect();
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Float64Array::from(values)),
+                Arc::new(Int64Array::from(ids)),
+            ],
+        )
+        .unwrap();
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("data", Arc::new(provider)).unwrap();
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_counts_nan_and_infinity() {
+        let ctx = create_test_context(vec![
+            Some(1.0),
+            Some(f64::NAN),
+            Some(f64::INFINITY),
+            Some(f64::NEG_INFINITY),
+            None,
+        ])
+        .await;
+        let constraint = NonFiniteConstraint::new("value").unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(3.0));
+        assert_eq!(result.details[NON_FINITE_DETAIL], 3.0);
+        assert_eq!(result.details["nan_count"], 1.0);
+        assert_eq!(result.details["infinite_count"], 2.0);
+        assert!(result.message.unwrap().contains("1 NaN, 2 infinite"));
+    }
+
+    #[tokio::test]
+    async fn test_finite_and_non_float_columns_pass() {
+        let ctx = create_test_context(vec![Some(1.0), None, Some(-2.5)]).await;
+        for column in ["value", "id"] {
+            let constraint = NonFiniteConstraint::new(column).unwrap();
+            let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+                .await
+                .unwrap();
+            assert_eq!(result.status, ConstraintStatus::Success, "{column}");
+            assert_eq!(result.details[NON_FINITE_DETAIL], 0.0);
+        }
+    }
+}
@@ -0,0 +1,577 @@
+//! Null rate comparison between the input and output of a transformation.
+//!
+//! [`NullRateComparisonConstraint`] asserts that a transformation did not introduce
+//! nulls: for every compared column, the null rate in the `after` table may exceed the
+//! null rate in the `before` table by at most `max_increase` (a fraction, so `0.01`
+//! allows one percentage point). The null counts of all columns are computed with one
+//! aggregate query per table.
+//!
+//! By default the columns present in both tables are compared. Columns found on only
+//! one side are listed in the result message but do not fail the constraint.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::constraints::NullRateComparisonConstraint;
+//! use term_guard::core::{Check, Level};
+//!
+//! let check = Check::builder("transformation_audit")
+//!     .level(Level::Error)
+//!     .with_constraint(
+//!         NullRateComparisonConstraint::new("staging_orders", "final_orders", 0.01)
+//!             .columns(vec!["customer_id", "amount"]),
+//!     )
+//!     .build();
+//! ```
+
+use crate::core::{
+    collect_with_metrics, is_table_empty, Constraint, ConstraintMetadata, ConstraintResult,
+    ConstraintStatus, QualifiedTable, QualityDimension,
+};
+use crate::error::{Result, TermError};
+use crate::security::SqlSecurity;
+use arrow::array::{Array, Int64Array};
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use tracing::{debug, instrument, warn};
+
+/// Slack for floating point error when comparing an increase with the maximum.
+const RATE_EPSILON: f64 = 1e-9;
+
+/// The null rates of a column in both tables.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnNullRates {
+    /// The column name
+    pub column: String,
+    /// Fraction of null values in the `before` table
+    pub before: f64,
+    /// Fraction of null values in the `after` table
+    pub after: f64,
+}
+
+impl ColumnNullRates {
+    /// Returns the change of the null rate; positive when nulls were introduced.
+    pub fn increase(&self) -> f64 {
+        self.after - self.before
+    }
+}
+
+impl fmt::Display for ColumnNullRates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:.2}% -> {:.2}% ({:+.2}pp)",
+            self.column,
+            self.before * 100.0,
+            self.after * 100.0,
+            self.increase() * 100.0
+        )
+    }
+}
+
+/// The outcome of comparing the null rates of two tables.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NullRateComparison {
+    /// Null rates of the columns present in both tables
+    pub columns: Vec<ColumnNullRates>,
+    /// Compared or requested columns the `before` table lacks
+    pub missing_before: Vec<String>,
+    /// Compared or requested columns the `after` table lacks
+    pub missing_after: Vec<String>,
+}
+
+impl NullRateComparison {
+    /// Returns the columns whose null rate increased by more than `max_increase`.
+    pub fn violations(&self, max_increase: f64) -> impl Iterator<Item = &ColumnNullRates> {
+        self.columns
+            .iter()
+            .filter(move |rates| rates.increase() > max_increase + RATE_EPSILON)
+    }
+
+    /// Returns the largest change of a null rate, or `None` if no column was compared.
+    pub fn max_increase(&self) -> Option<f64> {
+        self.columns
+            .iter()
+            .map(ColumnNullRates::increase)
+            .reduce(f64::max)
+    }
+}
+
+/// A constraint that fails when a transformation raised the null rate of a column.
+///
+/// Compares the per-column null rates of a `before` and an `after` table. The metric
+/// is the largest change of a null rate among the compared columns. The constraint
+/// is skipped when the tables have no column in common.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::constraints::NullRateComparisonConstraint;
+///
+/// // Allow at most half a percentage point more nulls in any shared column
+/// let constraint = NullRateComparisonConstraint::new("raw.events", "clean.events", 0.005);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NullRateComparisonConstraint {
+    /// Table before the transformation, optionally schema-qualified
+    before_table: String,
+    /// Table after the transformation, optionally schema-qualified
+    after_table: String,
+    /// Columns to compare; all shared columns when `None`
+    columns: Option<Vec<String>>,
+    /// Largest allowed increase of a null rate (0.0 to 1.0)
+    max_increase: f64,
+}
+
+impl NullRateComparisonConstraint {
+    /// Create a new null rate comparison constraint.
+    ///
+    /// # Arguments
+    ///
+    /// * `before_table` - The input table of the transformation
+    /// * `after_table` - The output table of the transformation
+    /// * `max_increase` - Largest allowed increase of a column's null rate, as a fraction
+    pub fn new(
+        before_table: impl Into<String>,
+        after_table: impl Into<String>,
+        max_increase: f64,
+    ) -> Self {
+        Self {
+            before_table: before_table.into(),
+            after_table: after_table.into(),
+            columns: None,
+            max_increase: max_increase.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Compare only the given columns instead of all columns the tables share.
+    ///
+    /// Requested columns missing from either table are reported but not compared.
+    pub fn columns(mut self, columns: Vec<impl Into<String>>) -> Self {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Get the table before the transformation
+    pub fn before_table(&self) -> &str {
+        &self.before_table
+    }
+
+    /// Get the table after the transformation
+    pub fn after_table(&self) -> &str {
+        &self.after_table
+    }
+
+    /// Get the largest allowed increase of a null rate
+    pub fn max_increase(&self) -> f64 {
+        self.max_increase
+    }
+
+    /// Computes the null rates of the compared columns in both tables.
+    pub async fn compare(&self, ctx: &SessionContext) -> Result<NullRateComparison> {
+        let before_table = QualifiedTable::resolve(&self.before_table)?;
+        let after_table = QualifiedTable::resolve(&self.after_table)?;
+        let before_columns = Self::table_columns(ctx, &before_table).await?;
+        let after_columns = Self::table_columns(ctx, &after_table).await?;
+
+        let candidates = match &self.columns {
+            Some(columns) => {
+                for column in columns {
+                    SqlSecurity::validate_identifier(column)?;
+                }
+                columns.clone()
+            }
+            None => {
+                let mut union = before_columns.clone();
+                union.extend(
+                    after_columns
+                        .iter()
+                        .filter(|column| !before_columns.contains(column))
+                        .cloned(),
+                );
+                union
+            }
+        };
+
+        let before_set: HashSet<&String> = before_columns.iter().collect();
+        let after_set: HashSet<&String> = after_columns.iter().collect();
+        let mut comparison = NullRateComparison::default();
+        let mut compared = Vec::new();
+        for column in candidates {
+            let in_before = before_set.contains(&column);
+            let in_after = after_set.contains(&column);
+            if !in_before {
+                comparison.missing_before.push(column.clone());
+            }
+            if !in_after {
+                comparison.missing_after.push(column.clone());
+            }
+            if in_before && in_after {
+                compared.push(column);
+            }
+        }
+
+        if compared.is_empty() {
+            return Ok(comparison);
+        }
+
+        let before_rates = Self::null_rates(ctx, &before_table, &compared).await?;
+        let after_rates = Self::null_rates(ctx, &after_table, &compared).await?;
+        comparison.columns = compared
+            .into_iter()
+            .zip(before_rates.into_iter().zip(after_rates))
+            .map(|(column, (before, after))| ColumnNullRates {
+                column,
+                before,
+                after,
+            })
+            .collect();
+        Ok(comparison)
+    }
+
+    /// Returns the column names of a table
+    async fn table_columns(ctx: &SessionContext, table: &QualifiedTable) -> Result<Vec<String>> {
+        let sql = format!("SELECT * FROM {} LIMIT 0", table.to_sql()?);
+        let df = ctx.sql(&sql).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                "null_rate_comparison",
+                format!("Failed to resolve columns of {table}: {e}"),
+            )
+        })?;
+        Ok(df
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect())
+    }
+
+    /// Generate the aggregate query counting rows and non-null values per column
+    fn generate_null_count_query(table: &QualifiedTable, columns: &[String]) -> Result<String> {
+        let counts = columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                Ok(format!(
+                    "COUNT({}) AS present_{i}",
+                    SqlSecurity::escape_identifier(column)?
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?
+            .join(", ");
+        Ok(format!(
+            "SELECT COUNT(*) AS row_count, {counts} FROM {}",
+            table.to_sql()?
+        ))
+    }
+
+    /// Computes the null rate of each column, in order; an empty table has no nulls
+    async fn null_rates(
+        ctx: &SessionContext,
+        table: &QualifiedTable,
+        columns: &[String],
+    ) -> Result<Vec<f64>> {
+        let sql = Self::generate_null_count_query(table, columns)?;
+        debug!("Generated null rate query: {sql}");
+        let df = ctx.sql(&sql).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                "null_rate_comparison",
+                format!("Null rate query on {table} failed: {e}"),
+            )
+        })?;
+        let batches = collect_with_metrics(df).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                "null_rate_comparison",
+                format!("Failed to collect null rates of {table}: {e}"),
+            )
+        })?;
+
+        let batch = batches
+            .iter()
+            .find(|batch| batch.num_rows() > 0)
+            .ok_or_else(|| {
+                TermError::constraint_evaluation(
+                    "null_rate_comparison",
+                    format!("No results from null rate query on {table}"),
+                )
+            })?;
+        let count = |index: usize| -> Result<i64> {
+            batch
+                .column(index)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .map(|array| array.value(0))
+                .ok_or_else(|| {
+                    TermError::constraint_evaluation(
+                        "null_rate_comparison",
+                        "Invalid count column type",
+                    )
+                })
+        };
+
+        let rows = count(0)?;
+        (1..=columns.len())
+            .map(|index| {
+                let present = count(index)?;
+                Ok(if rows == 0 {
+                    0.0
+                } else {
+                    (rows - present) as f64 / rows as f64
+                })
+            })
+            .collect()
+    }
+
+    /// Describe columns that could not be compared, if any
+    fn missing_columns_note(&self, comparison: &NullRateComparison) -> Option<String> {
+        let mut parts = Vec::new();
+        if !comparison.missing_before.is_empty() {
+            parts.push(format!(
+                "missing from {}: {}",
+                self.before_table,
+                comparison.missing_before.join(", ")
+            ));
+        }
+        if !comparison.missing_after.is_empty() {
+            parts.push(format!(
+                "missing from {}: {}",
+                self.after_table,
+                comparison.missing_after.join(", ")
+            ));
+        }
+        (!parts.is_empty()).then(|| format!("Not compared ({})", parts.join("; ")))
+    }
+}
+
+#[async_trait]
+impl Constraint for NullRateComparisonConstraint {
+    #[instrument(skip(self, ctx), fields(constraint = "null_rate_comparison"))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        debug!(
+            "Evaluating null rate comparison: {} -> {}",
+            self.before_table, self.after_table
+        );
+
+        let comparison = self.compare(ctx).await?;
+        // The null rate of a table without rows is reported as zero by `compare`,
+        // but a comparison against it would be meaningless
+        for table in [&self.before_table, &self.after_table] {
+            if is_table_empty(ctx, &QualifiedTable::resolve(table)?.to_sql()?).await? {
+                return Ok(ConstraintResult::empty_table());
+            }
+        }
+        let note = self.missing_columns_note(&comparison);
+
+        let Some(max_increase) = comparison.max_increase() else {
+            let message = format!(
+                "No columns to compare between {} and {}",
+                self.before_table, self.after_table
+            );
+            return Ok(ConstraintResult::skipped(match note {
+                Some(note) => format!("{message}. {note}"),
+                None => message,
+            }));
+        };
+
+        let violations: Vec<String> = comparison
+            .violations(self.max_increase)
+            .map(ToString::to_string)
+            .collect();
+
+        if violations.is_empty() {
+            return Ok(ConstraintResult {
+                status: ConstraintStatus::Success,
+                metric: Some(max_increase),
+                message: note,
+                quarantine: None,
+                constraint_id: None,
+                details: Default::default(),
+            });
+        }
+
+        let mut message = format!(
+            "Null rate increased by more than {:.2} percentage points from {} to {} in {} of {} columns: {}",
+            self.max_increase * 100.0,
+            self.before_table,
+            self.after_table,
+            violations.len(),
+            comparison.columns.len(),
+            violations.join(", ")
+        );
+        if let Some(note) = note {
+            message.push_str(&format!(". {note}"));
+        }
+
+        warn!("{}", message);
+
+        Ok(ConstraintResult::failure_with_metric(max_increase, message))
+    }
+
+    fn name(&self) -> &str {
+        "null_rate_comparison"
+    }
+
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Completeness)
+    }
+
+    fn referenced_tables(&self) -> Vec<String> {
+        vec![self.before_table.clone(), self.after_table.clone()]
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        let metadata = match &self.columns {
+            Some(columns) => ConstraintMetadata::for_columns(columns),
+            None => ConstraintMetadata::new(),
+        };
+        metadata
+            .with_description(format!(
+                "Checks that null rates rise by at most {:.2} percentage points from {} to {}",
+                self.max_increase * 100.0,
+                self.before_table,
+                self.after_table
+            ))
+            .with_custom("before_table", &self.before_table)
+            .with_custom("after_table", &self.after_table)
+            .with_custom("max_increase", self.max_increase.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+
+    async fn create_table(ctx: &SessionContext, sql: &str) -> Result<()> {
+        ctx.sql(sql).await?.collect().await?;
+        Ok(())
+    }
+
+    async fn transformation_context() -> Result<SessionContext> {
+        let ctx = create_test_context().await?;
+        create_table(
+            &ctx,
+            "CREATE TABLE staging_orders (id BIGINT, email STRING, amount DOUBLE, legacy_flag BOOLEAN)
+             AS VALUES (1, 'a@x.com', 10.0, true), (2, NULL, 20.0, false),
+                       (3, 'c@x.com', 30.0, true), (4, 'd@x.com', NULL, false)",
+        )
+        .await?;
+        create_table(
+            &ctx,
+            "CREATE TABLE final_orders (id BIGINT, email STRING, amount DOUBLE, region STRING)
+             AS VALUES (1, 'a@x.com', 10.0, 'eu'), (2, NULL, 20.0, 'us'),
+                       (3, NULL, NULL, 'eu'), (4, 'd@x.com', NULL, 'us')",
+        )
+        .await?;
+        Ok(ctx)
+    }
+
+    #[tokio::test]
+    async fn test_no_increase_passes() -> Result<()> {
+        let ctx = transformation_context().await?;
+        let constraint = NullRateComparisonConstraint::new("staging_orders", "staging_orders", 0.0);
+
+        let result = constraint.evaluate(&ctx).await?;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.0));
+        assert!(result.message.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_increase_reports_violating_columns() -> Result<()> {
+        let ctx = transformation_context().await?;
+        let constraint = NullRateComparisonConstraint::new("staging_orders", "final_orders", 0.01);
+
+        let result = constraint.evaluate(&ctx).await?;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(0.25));
+        let message = result.message.unwrap();
+        assert!(message.contains("in 2 of 3 columns"));
+        assert!(message.contains("email 25.00% -> 50.00% (+25.00pp)"));
+        assert!(message.contains("amount 25.00% -> 50.00% (+25.00pp)"));
+        assert!(message.contains("missing from staging_orders: region"));
+        assert!(message.contains("missing from final_orders: legacy_flag"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_missing_columns_do_not_fail() -> Result<()> {
+        let ctx = transformation_context().await?;
+        let constraint = NullRateComparisonConstraint::new("staging_orders", "final_orders", 0.3);
+
+        let result = constraint.evaluate(&ctx).await?;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert!(result.message.unwrap().starts_with("Not compared"));
+
+        let comparison = constraint.compare(&ctx).await?;
+        assert_eq!(comparison.columns.len(), 3);
+        assert_eq!(comparison.missing_before, vec!["region"]);
+        assert_eq!(comparison.missing_after, vec!["legacy_flag"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_explicit_columns() -> Result<()> {
+        let ctx = transformation_context().await?;
+        let constraint = NullRateComparisonConstraint::new("staging_orders", "final_orders", 0.0)
+            .columns(vec!["id", "region"]);
+
+        let result = constraint.evaluate(&ctx).await?;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.0));
+
+        let comparison = constraint.compare(&ctx).await?;
+        assert_eq!(comparison.columns.len(), 1);
+        assert_eq!(comparison.missing_before, vec!["region"]);
+        assert!(comparison.missing_after.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_no_shared_columns_skips() -> Result<()> {
+        let ctx = transformation_context().await?;
+        let constraint = NullRateComparisonConstraint::new("staging_orders", "final_orders", 0.0)
+            .columns(vec!["legacy_flag"]);
+
+        let result = constraint.evaluate(&ctx).await?;
+        assert_eq!(result.status, ConstraintStatus::Skipped);
+        assert!(result
+            .message
+            .unwrap()
+            .contains("missing from final_orders"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_empty_table_has_no_nulls() -> Result<()> {
+        let ctx = transformation_context().await?;
+        create_table(&ctx, "CREATE TABLE empty_orders (id BIGINT, email STRING)").await?;
+        let constraint = NullRateComparisonConstraint::new("empty_orders", "final_orders", 0.0);
+
+        let comparison = constraint.compare(&ctx).await?;
+        assert_eq!(comparison.columns[0].before, 0.0);
+        assert_eq!(comparison.columns[1].after, 0.5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_null_count_query() -> Result<()> {
+        let table = QualifiedTable::parse("staging.orders")?;
+        let sql = NullRateComparisonConstraint::generate_null_count_query(
+            &table,
+            &["id".to_string(), "email".to_string()],
+        )?;
+        assert_eq!(
+            sql,
+            r#"SELECT COUNT(*) AS row_count, COUNT("id") AS present_0, COUNT("email") AS present_1 FROM "staging"."orders""#
+        );
+
+        let invalid = NullRateComparisonConstraint::new("orders; DROP TABLE x", "final", 0.0);
+        assert!(QualifiedTable::parse(invalid.before_table()).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+//! Numeric helpers shared by constraints that aggregate numeric columns.
+//!
+//! Decimal columns (`Decimal128` and `Decimal256`) are summed and compared by DataFusion in
+//! their exact scaled-integer representation, so large monetary totals keep every cent.
+//! A value only becomes an `f64` at the assertion boundary, when it is handed to an
+//! [`Assertion`](crate::constraints::Assertion) or reported as a metric. That conversion
+//! follows one rule: the exact decimal is rounded to the nearest representable `f64`,
+//! with ties going to the value whose last bit is even.
+
+use crate::error::{Result, TermError};
+use arrow::array::{Array, Decimal128Array, Decimal256Array, Float64Array};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+
+/// Returns the scale of a decimal type, or `None` for every other type.
+pub(crate) fn decimal_scale(data_type: &DataType) -> Option<i8> {
+    match data_type {
+        DataType::Decimal128(_, scale) | DataType::Decimal256(_, scale) => Some(*scale),
+        _ => None,
+    }
+}
+
+/// Returns the exact text of a non-null decimal value, or `None` for other types.
+pub(crate) fn exact_decimal(array: &dyn Array, index: usize) -> Option<String> {
+    if array.is_null(index) {
+        return None;
+    }
+    match array.data_type() {
+        DataType::Decimal128(_, _) => array
+            .as_any()
+            .downcast_ref::<Decimal128Array>()
+            .map(|decimals| decimals.value_as_string(index)),
+        DataType::Decimal256(_, _) => array
+            .as_any()
+            .downcast_ref::<Decimal256Array>()
+            .map(|decimals| decimals.value_as_string(index)),
+        _ => None,
+    }
+}
+
+/// Reads the value at `index` of a numeric array as an `f64`.
+///
+/// Returns `Ok(None)` for nulls. Decimal values are rounded to the nearest `f64`,
+/// ties to even; integer and floating point values are cast as DataFusion would.
+pub(crate) fn value_as_f64(array: &dyn Array, index: usize) -> Result<Option<f64>> {
+    if array.is_null(index) {
+        return Ok(None);
+    }
+
+    if let Some(exact) = exact_decimal(array, index) {
+        // Rust's float parsing is correctly rounded, which gives round-half-to-even
+        return exact.parse::<f64>().map(Some).map_err(|e| {
+            TermError::Internal(format!("Failed to convert decimal {exact} to f64: {e}"))
+        });
+    }
+
+    let floats = cast(&array.slice(index, 1), &DataType::Float64).map_err(|e| {
+        TermError::Internal(format!(
+            "Cannot read {} value as a number: {e}",
+            array.data_type()
+        ))
+    })?;
+    Ok(floats
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .map(|floats| floats.value(0)))
+}
+
+/// Renders `value` as a SQL decimal literal with the given scale.
+///
+/// The value is rounded to `scale` fractional digits, so a tolerance of `0.005` on a
+/// `DECIMAL(38, 2)` column becomes `0.01`.
+pub(crate) fn decimal_literal(value: f64, scale: i8) -> String {
+    let scale = scale.max(0) as usize;
+    format!("CAST('{value:.scale$}' AS DECIMAL(38, {scale}))")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Int64Array;
+
+    #[test]
+    fn test_decimal_value_is_rounded_once() {
+        // 1e15 + 0.6 is not representable; the nearest f64 is 1e15 + 0.625
+        let decimals = Decimal128Array::from(vec![Some(1_000_000_000_000_000_600_000), None])
+            .with_precision_and_scale(38, 6)
+            .unwrap();
+
+        assert_eq!(
+            value_as_f64(&decimals, 0).unwrap(),
+            Some(1_000_000_000_000_000.6)
+        );
+        assert_eq!(value_as_f64(&decimals, 1).unwrap(), None);
+        assert_eq!(decimal_scale(decimals.data_type()), Some(6));
+    }
+
+    #[test]
+    fn test_non_decimal_values() {
+        let integers = Int64Array::from(vec![42]);
+        assert_eq!(value_as_f64(&integers, 0).unwrap(), Some(42.0));
+        assert_eq!(decimal_scale(integers.data_type()), None);
+    }
+
+    #[test]
+    fn test_decimal_literal() {
+        assert_eq!(
+            decimal_literal(0.01, 6),
+            "CAST('0.010000' AS DECIMAL(38, 6))"
+        );
+        assert_eq!(decimal_literal(0.006, 2), "CAST('0.01' AS DECIMAL(38, 2))");
+        assert_eq!(decimal_literal(5.0, -2), "CAST('5' AS DECIMAL(38, 0))");
+    }
+}
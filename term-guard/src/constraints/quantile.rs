@@ -6,15 +6,24 @@
 //!
 //! And adds support for multiple quantile checks and distribution analysis.
 
-use crate::constraints::Assertion;
-use crate::core::{current_validation_context, Constraint, ConstraintMetadata, ConstraintResult};
+use crate::constraints::non_finite::{
+    finite_values_sql, is_float_column, non_finite_count_sql, non_finite_failure, read_count,
+    with_non_finite,
+};
+use crate::constraints::numeric::value_as_f64;
+use crate::constraints::{Assertion, NonFinitePolicy};
+use crate::core::{
+    collect_with_metrics, current_validation_context, is_table_empty, Constraint,
+    ConstraintMetadata, ConstraintResult,
+};
 use crate::prelude::*;
 use crate::security::SqlSecurity;
 use arrow::array::Array;
+use arrow::datatypes::DataType;
 use async_trait::async_trait;
 use datafusion::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use tracing::{debug, instrument};
 /// Types of quantile calculations.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -149,6 +158,8 @@ pub struct QuantileConstraint {
     validation: QuantileValidation,
     /// The method to use for quantile calculation
     method: QuantileMethod,
+    /// How NaN and infinite values of float columns are treated
+    non_finite: NonFinitePolicy,
 }
 
 impl QuantileConstraint {
@@ -179,9 +190,18 @@ impl QuantileConstraint {
             column: column_str,
             validation,
             method,
+            non_finite: NonFinitePolicy::default(),
         })
     }
 
+    /// Sets how NaN and infinite values are treated, ignoring them by default.
+    ///
+    /// See [`NonFinitePolicy`].
+    pub fn non_finite_policy(mut self, policy: NonFinitePolicy) -> Self {
+        self.non_finite = policy;
+        self
+    }
+
     /// Convenience constructor for median check.
     pub fn median(column: impl Into<String>, assertion: Assertion) -> Result<Self> {
         Self::new(
@@ -225,8 +245,14 @@ impl QuantileConstraint {
     }
 
     /// Generates SQL for approximate quantile calculation.
-    fn approx_quantile_sql(&self, quantile: f64) -> Result<String> {
-        let escaped_column = SqlSecurity::escape_identifier(&self.column)?;
+    ///
+    /// On float columns, non-finite values are left out under
+    /// [`NonFinitePolicy::Ignore`].
+    fn approx_quantile_sql(&self, quantile: f64, float_column: bool) -> Result<String> {
+        let mut escaped_column = SqlSecurity::escape_identifier(&self.column)?;
+        if float_column && self.non_finite == NonFinitePolicy::Ignore {
+            escaped_column = finite_values_sql(&escaped_column);
+        }
         Ok(format!(
             "APPROX_PERCENTILE_CONT({quantile}) WITHIN GROUP (ORDER BY {escaped_column})"
         ))
@@ -234,10 +260,10 @@ impl QuantileConstraint {
 
     /// Generates SQL for exact quantile calculation.
     #[allow(dead_code)]
-    fn exact_quantile_sql(&self, quantile: f64) -> Result<String> {
+    fn exact_quantile_sql(&self, quantile: f64, float_column: bool) -> Result<String> {
         // DataFusion doesn't support exact PERCENTILE_CONT, so we use APPROX_PERCENTILE_CONT
         // which is accurate enough for most use cases
-        self.approx_quantile_sql(quantile)
+        self.approx_quantile_sql(quantile, float_column)
     }
 
     /// Determines whether to use exact or approximate method based on data size.
@@ -252,7 +278,7 @@ impl QuantileConstraint {
 
                 let count_sql = format!("SELECT COUNT(*) as cnt FROM {table_name}");
                 let df = ctx.sql(&count_sql).await?;
-                let batches = df.collect().await?;
+                let batches = collect_with_metrics(df).await?;
 
                 if batches.is_empty() || batches[0].num_rows() == 0 {
                     return Ok(true);
@@ -280,176 +306,105 @@ impl Constraint for QuantileConstraint {
         validation = ?self.validation
     ))]
     async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        // Quantiles of no values are null
+        if is_table_empty(ctx, current_validation_context().table_name()).await? {
+            return Ok(ConstraintResult::empty_table());
+        }
         let _use_exact = self.should_use_exact(ctx).await?;
+        let float_column =
+            is_float_column(ctx, current_validation_context().table_name(), &self.column).await;
 
-        match &self.validation {
-            QuantileValidation::Single(check) => {
+        let quantiles: Vec<f64> = match &self.validation {
+            QuantileValidation::Single(check) => vec![check.quantile],
+            QuantileValidation::Multiple(checks) => {
+                checks.iter().map(|check| check.quantile).collect()
+            }
+            QuantileValidation::Monotonic { quantiles, .. } => quantiles.clone(),
+            _ => {
+                // Other validation types would be implemented similarly
+                return Ok(ConstraintResult::skipped(
+                    "Validation type not yet implemented",
+                ));
+            }
+        };
+
+        // Compute all quantiles in one query
+        let mut sql_parts: Vec<String> = quantiles
+            .iter()
+            .enumerate()
+            .map(|(i, q)| {
                 // Always use approx for now since DataFusion doesn't support exact percentile_cont
-                // Get the table name from the validation context
+                self.approx_quantile_sql(*q, float_column)
+                    .map(|q_sql| format!("{q_sql} as q_{i}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if float_column {
+            let column = SqlSecurity::escape_identifier(&self.column)?;
+            sql_parts.push(format!("{} as non_finite", non_finite_count_sql(&column)));
+        }
 
-                let validation_ctx = current_validation_context();
+        let parts = sql_parts.join(", ");
+        // Get the table name from the validation context
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
 
-                let table_name = validation_ctx.table_name();
+        let sql = format!("SELECT {parts} FROM {table_name}");
+        debug!("Quantile SQL: {}", sql);
+        let df = ctx.sql(&sql).await?;
+        let batches = collect_with_metrics(df).await?;
 
-                let sql = format!(
-                    "SELECT {} as q_value FROM {table_name}",
-                    self.approx_quantile_sql(check.quantile)?
-                );
+        if batches.is_empty() || batches[0].num_rows() == 0 {
+            return Ok(ConstraintResult::skipped("No data to validate"));
+        }
 
-                debug!("Quantile SQL: {}", sql);
-                let df = ctx.sql(&sql).await?;
-                let batches = df.collect().await?;
+        let batch = &batches[0];
+        let non_finite = if float_column {
+            Some(read_count(batch.column(quantiles.len()).as_ref())?)
+        } else {
+            None
+        };
+        if let Some(count) = non_finite.filter(|count| *count > 0) {
+            if self.non_finite == NonFinitePolicy::Fail {
+                return Ok(non_finite_failure(&self.column, count));
+            }
+        }
 
-                if batches.is_empty() || batches[0].num_rows() == 0 {
-                    return Ok(ConstraintResult::skipped("No data to validate"));
-                }
+        let values = (0..quantiles.len())
+            .map(|i| quantile_value(batch.column(i).as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        if let Some(i) = values.iter().position(Option::is_none) {
+            let q_pct = (quantiles[i] * 100.0) as i32;
+            return Ok(with_non_finite(
+                ConstraintResult::failure(format!("Q{q_pct} is null (no finite values)")),
+                non_finite,
+            ));
+        }
+        let values: Vec<f64> = values.into_iter().flatten().collect();
 
-                // Handle different numeric types that DataFusion might return
-                let column = batches[0].column(0);
-                let value = if let Some(arr) =
-                    column.as_any().downcast_ref::<arrow::array::Float64Array>()
-                {
-                    arr.value(0)
-                } else if let Some(arr) = column.as_any().downcast_ref::<arrow::array::Int64Array>()
-                {
-                    arr.value(0) as f64
-                } else if let Some(arr) = column.as_any().downcast_ref::<arrow::array::Int32Array>()
-                {
-                    arr.value(0) as f64
-                } else {
-                    return Err(TermError::TypeMismatch {
-                        expected: "Float64, Int64, or Int32".to_string(),
-                        found: format!("{:?}", column.data_type()),
-                    });
-                };
-
-                if check.assertion.evaluate(value) {
-                    Ok(ConstraintResult::success_with_metric(value))
-                } else {
-                    Ok(ConstraintResult::failure_with_metric(
-                        value,
-                        format!(
-                            "Quantile {} is {value} which does not {}",
-                            check.quantile, check.assertion
-                        ),
-                    ))
-                }
+        let result = match &self.validation {
+            QuantileValidation::Single(check) => {
+                let context = format!("quantile {} of column '{}'", check.quantile, self.column);
+                check.assertion.explain(values[0], context).to_result()
             }
             QuantileValidation::Multiple(checks) => {
-                // Build SQL to compute all quantiles in one query
-                let sql_parts: Vec<String> = checks
-                    .iter()
-                    .enumerate()
-                    .map(|(i, check)| {
-                        // Always use approx for now since DataFusion doesn't support exact percentile_cont
-                        self.approx_quantile_sql(check.quantile)
-                            .map(|q_sql| format!("{q_sql} as q_{i}"))
-                    })
-                    .collect::<Result<Vec<_>>>()?;
-
-                let parts = sql_parts.join(", ");
-                // Get the table name from the validation context
-                let validation_ctx = current_validation_context();
-                let table_name = validation_ctx.table_name();
-
-                let sql = format!("SELECT {parts} FROM {table_name}");
-                let df = ctx.sql(&sql).await?;
-                let batches = df.collect().await?;
-
-                if batches.is_empty() || batches[0].num_rows() == 0 {
-                    return Ok(ConstraintResult::skipped("No data to validate"));
-                }
-
                 let mut failures = Vec::new();
-                let batch = &batches[0];
-
-                for (i, check) in checks.iter().enumerate() {
-                    let column = batch.column(i);
-                    let value = if let Some(arr) =
-                        column.as_any().downcast_ref::<arrow::array::Float64Array>()
-                    {
-                        arr.value(0)
-                    } else if let Some(arr) =
-                        column.as_any().downcast_ref::<arrow::array::Int64Array>()
-                    {
-                        arr.value(0) as f64
-                    } else if let Some(arr) =
-                        column.as_any().downcast_ref::<arrow::array::Int32Array>()
-                    {
-                        arr.value(0) as f64
-                    } else {
-                        return Err(TermError::TypeMismatch {
-                            expected: "Float64, Int64, or Int32".to_string(),
-                            found: format!("{:?}", column.data_type()),
-                        });
-                    };
-
-                    if !check.assertion.evaluate(value) {
-                        let q_pct = (check.quantile * 100.0) as i32;
-                        failures.push(format!(
-                            "Q{q_pct} is {value} which does not {}",
-                            check.assertion
-                        ));
+                for (check, value) in checks.iter().zip(&values) {
+                    let q_pct = (check.quantile * 100.0) as i32;
+                    let explanation = check
+                        .assertion
+                        .explain(*value, format!("Q{q_pct} of column '{}'", self.column));
+                    if !explanation.passed {
+                        failures.push(explanation.to_string());
                     }
                 }
 
                 if failures.is_empty() {
-                    Ok(ConstraintResult::success())
+                    ConstraintResult::success()
                 } else {
-                    Ok(ConstraintResult::failure(failures.join("; ")))
+                    ConstraintResult::failure(failures.join("; "))
                 }
             }
-            QuantileValidation::Monotonic { quantiles, strict } => {
-                // Compute all quantiles
-                let sql_parts: Vec<String> = quantiles
-                    .iter()
-                    .enumerate()
-                    .map(|(i, q)| {
-                        // Always use approx for now since DataFusion doesn't support exact percentile_cont
-                        self.approx_quantile_sql(*q)
-                            .map(|q_sql| format!("{q_sql} as q_{i}"))
-                    })
-                    .collect::<Result<Vec<_>>>()?;
-
-                let parts = sql_parts.join(", ");
-                // Get the table name from the validation context
-                let validation_ctx = current_validation_context();
-                let table_name = validation_ctx.table_name();
-
-                let sql = format!("SELECT {parts} FROM {table_name}");
-                let df = ctx.sql(&sql).await?;
-                let batches = df.collect().await?;
-
-                if batches.is_empty() || batches[0].num_rows() == 0 {
-                    return Ok(ConstraintResult::skipped("No data to validate"));
-                }
-
-                let batch = &batches[0];
-                let mut values = Vec::new();
-
-                for i in 0..quantiles.len() {
-                    let column = batch.column(i);
-                    let value = if let Some(arr) =
-                        column.as_any().downcast_ref::<arrow::array::Float64Array>()
-                    {
-                        arr.value(0)
-                    } else if let Some(arr) =
-                        column.as_any().downcast_ref::<arrow::array::Int64Array>()
-                    {
-                        arr.value(0) as f64
-                    } else if let Some(arr) =
-                        column.as_any().downcast_ref::<arrow::array::Int32Array>()
-                    {
-                        arr.value(0) as f64
-                    } else {
-                        return Err(TermError::TypeMismatch {
-                            expected: "Float64, Int64, or Int32".to_string(),
-                            found: format!("{:?}", column.data_type()),
-                        });
-                    };
-                    values.push(value);
-                }
-
+            QuantileValidation::Monotonic { strict, .. } => {
                 // Check monotonicity
                 let mut is_monotonic = true;
                 for i in 1..values.len() {
@@ -465,38 +420,66 @@ impl Constraint for QuantileConstraint {
                 }
 
                 if is_monotonic {
-                    Ok(ConstraintResult::success())
+                    ConstraintResult::success()
                 } else {
                     let monotonic_type = if *strict { "strictly" } else { "" };
-                    Ok(ConstraintResult::failure(format!(
+                    ConstraintResult::failure(format!(
                         "Quantiles are not {monotonic_type} monotonic: {values:?}"
-                    )))
+                    ))
                 }
             }
-            _ => {
-                // Other validation types would be implemented similarly
-                Ok(ConstraintResult::skipped(
-                    "Validation type not yet implemented",
-                ))
-            }
-        }
-    }
+            _ => unreachable!("unsupported validations return early"),
+        };
 
+        Ok(with_non_finite(result, non_finite))
+    }
     fn name(&self) -> &str {
         "quantile"
     }
 
+    fn identity(&self) -> String {
+        match &self.validation {
+            QuantileValidation::Distribution {
+                config,
+                iqr_assertion,
+                quantile_assertions,
+            } => {
+                // Sort the assertions; HashMap iteration order varies between runs
+                let quantile_assertions: BTreeMap<_, _> = quantile_assertions.iter().collect();
+                format!(
+                    "QuantileConstraint {{ column: {:?}, validation: Distribution {{ config: {config:?}, iqr_assertion: {iqr_assertion:?}, quantile_assertions: {quantile_assertions:?} }}, method: {:?}, non_finite: {:?} }}",
+                    self.column, self.method, self.non_finite
+                )
+            }
+            _ => format!("{self:?}"),
+        }
+    }
+
     fn metadata(&self) -> ConstraintMetadata {
-        ConstraintMetadata::for_column(&self.column).with_description(format!(
-            "Validates quantile properties for column '{}'",
-            self.column
-        ))
+        ConstraintMetadata::for_column(&self.column)
+            .with_description(format!(
+                "Validates quantile properties for column '{}'",
+                self.column
+            ))
+            .with_custom("non_finite_policy", self.non_finite.to_string())
+    }
+}
+
+/// Reads a computed quantile, which is null when there are no values.
+fn quantile_value(column: &dyn Array) -> Result<Option<f64>> {
+    match column.data_type() {
+        DataType::Float64 | DataType::Int64 | DataType::Int32 => value_as_f64(column, 0),
+        other => Err(TermError::TypeMismatch {
+            expected: "Float64, Int64, or Int32".to_string(),
+            found: format!("{other:?}"),
+        }),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constraints::NON_FINITE_DETAIL;
     use crate::core::ConstraintStatus;
     use arrow::array::Float64Array;
     use arrow::datatypes::{DataType, Field, Schema};
@@ -591,6 +574,38 @@ mod tests {
         assert_eq!(result.status, ConstraintStatus::Success);
     }
 
+    #[tokio::test]
+    async fn test_non_finite_values() {
+        let mut values: Vec<Option<f64>> = (1..=100).map(|i| Some(i as f64)).collect();
+        values.extend([Some(f64::INFINITY); 20]);
+        values.push(Some(f64::NAN));
+        let ctx = create_test_context(values).await;
+        let p95 = || {
+            QuantileConstraint::percentile("value", 0.95, Assertion::Between(94.0, 96.0)).unwrap()
+        };
+
+        let result = evaluate_constraint_with_context(&p95(), &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.details[NON_FINITE_DETAIL], 21.0);
+
+        let constraint = p95().non_finite_policy(NonFinitePolicy::Fail);
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.details[NON_FINITE_DETAIL], 21.0);
+
+        // Infinite values sort above every finite one
+        let constraint = p95().non_finite_policy(NonFinitePolicy::Propagate);
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.details[NON_FINITE_DETAIL], 21.0);
+    }
+
     #[test]
     fn test_invalid_quantile() {
         let result = QuantileCheck::new(1.5, Assertion::LessThan(100.0));
@@ -1,7 +1,10 @@
 //! Size constraint for checking row counts.
 
 use crate::constraints::Assertion;
-use crate::core::{current_validation_context, Constraint, ConstraintMetadata, ConstraintResult};
+use crate::core::{
+    current_validation_context, fetch_metrics, Constraint, ConstraintMetadata, ConstraintResult,
+    MetadataStatistics, MetricKey, ParameterRef,
+};
 use crate::prelude::*;
 use async_trait::async_trait;
 use datafusion::prelude::*;
@@ -42,6 +45,30 @@ impl SizeConstraint {
     pub fn new(assertion: Assertion) -> Self {
         Self { assertion }
     }
+
+    /// Evaluates the assertion against the row count.
+    fn size_result(&self, row_count: f64) -> ConstraintResult {
+        let explanation = self.assertion.explain(row_count, "row count");
+        if explanation.passed {
+            debug!(
+                constraint.name = %self.name(),
+                constraint.assertion = %self.assertion,
+                result.row_count = row_count as i64,
+                result.status = "success",
+                "Size constraint passed"
+            );
+            ConstraintResult::success_with_metric(row_count)
+        } else {
+            debug!(
+                constraint.name = %self.name(),
+                constraint.assertion = %self.assertion,
+                result.row_count = row_count as i64,
+                result.status = "failure",
+                "Size constraint failed"
+            );
+            explanation.to_result()
+        }
+    }
 }
 
 #[async_trait]
@@ -56,70 +83,33 @@ impl Constraint for SizeConstraint {
             constraint.assertion = %self.assertion,
             "Starting size constraint evaluation"
         );
-        // Build SQL query to count rows
-        // Get the table name from the validation context
-
+        // Count rows, sharing the count with other constraints of the run
         let validation_ctx = current_validation_context();
+        let row_count = fetch_metrics(ctx, &[MetricKey::row_count(validation_ctx.table_name())])
+            .await?[0] as f64;
 
-        let table_name = validation_ctx.table_name();
-
-        let sql = format!("SELECT COUNT(*) as row_count FROM {table_name}");
-
-        // Execute query
-        let df = ctx.sql(&sql).await?;
-        let batches = df.collect().await?;
-
-        // Extract results
-        if batches.is_empty() {
-            debug!(
-                constraint.name = %self.name(),
-                skip.reason = "No data to validate",
-                "Skipping constraint due to empty result set"
-            );
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
+        Ok(self.size_result(row_count))
+    }
 
-        let batch = &batches[0];
+    fn name(&self) -> &str {
+        "size"
+    }
 
-        // Check if the query returned any rows
-        if batch.num_rows() == 0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
+    fn parameters(&self) -> Vec<ParameterRef> {
+        self.assertion.parameters("size assertion")
+    }
 
-        let row_count = batch
-            .column(0)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract row count".to_string()))?
-            .value(0) as f64;
+    fn requires_full_data(&self) -> bool {
+        true
+    }
 
-        // Evaluate assertion
-        if self.assertion.evaluate(row_count) {
-            debug!(
-                constraint.name = %self.name(),
-                constraint.assertion = %self.assertion,
-                result.row_count = row_count as i64,
-                result.status = "success",
-                "Size constraint passed"
-            );
-            Ok(ConstraintResult::success_with_metric(row_count))
-        } else {
-            debug!(
-                constraint.name = %self.name(),
-                constraint.assertion = %self.assertion,
-                result.row_count = row_count as i64,
-                result.status = "failure",
-                "Size constraint failed"
-            );
-            Ok(ConstraintResult::failure_with_metric(
-                row_count,
-                format!("Size {row_count} does not {}", self.assertion),
-            ))
-        }
+    /// Answers from the row count recorded in the metadata.
+    fn evaluate_from_metadata(&self, metadata: &MetadataStatistics) -> Option<ConstraintResult> {
+        Some(self.size_result(metadata.row_count as f64))
     }
 
-    fn name(&self) -> &str {
-        "size"
+    fn describe(&self) -> String {
+        format!("the row count must be {}", self.assertion.requirement())
     }
 
     fn metadata(&self) -> ConstraintMetadata {
@@ -7,13 +7,22 @@
 //! - `SumConstraint`
 //! - `StandardDeviationConstraint`
 //!
-//! And adds support for new statistics like variance, median, and percentiles.
-
-use crate::constraints::Assertion;
-use crate::core::{current_validation_context, Constraint, ConstraintMetadata, ConstraintResult};
+//! And adds support for new statistics like variance, median, and percentiles, as well
+//! as non-null, null and approximate distinct counts.
+
+use crate::constraints::non_finite::{
+    finite_values_sql, is_float_column, non_finite_count_sql, non_finite_failure, read_count,
+    with_non_finite,
+};
+use crate::constraints::numeric::value_as_f64;
+use crate::constraints::{Assertion, NonFinitePolicy};
+use crate::core::{
+    collect_with_metrics, current_validation_context, describe_list, is_table_empty,
+    ColumnStatistics, Constraint, ConstraintMetadata, ConstraintResult, MetadataStatistics,
+    ParameterRef,
+};
 use crate::prelude::*;
 use crate::security::SqlSecurity;
-use arrow::array::Array;
 use async_trait::async_trait;
 use datafusion::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -38,6 +47,12 @@ pub enum StatisticType {
     Median,
     /// Specific percentile (0.0 to 1.0)
     Percentile(f64),
+    /// Number of non-null values
+    Count,
+    /// Number of null values
+    NullCount,
+    /// Approximate number of distinct non-null values
+    ApproxDistinct,
 }
 
 impl StatisticType {
@@ -49,9 +64,11 @@ impl StatisticType {
             StatisticType::Mean => "AVG".to_string(),
             StatisticType::Sum => "SUM".to_string(),
             StatisticType::StandardDeviation => "STDDEV".to_string(),
-            StatisticType::Variance => "VARIANCE".to_string(),
+            StatisticType::Variance => "VAR_SAMP".to_string(),
             StatisticType::Median => "APPROX_PERCENTILE_CONT".to_string(),
             StatisticType::Percentile(_) => "APPROX_PERCENTILE_CONT".to_string(),
+            StatisticType::Count | StatisticType::NullCount => "COUNT".to_string(),
+            StatisticType::ApproxDistinct => "APPROX_DISTINCT".to_string(),
         }
     }
 
@@ -66,6 +83,16 @@ impl StatisticType {
                 let func = self.sql_function();
                 format!("{func}({column}, {p})")
             }
+            StatisticType::NullCount => {
+                let func = self.sql_function();
+                format!("{func}(*) - {func}({column})")
+            }
+            // DataFusion has no APPROX_DISTINCT for floating-point values, and the
+            // text of each value is as distinct as the value itself
+            StatisticType::ApproxDistinct => {
+                let func = self.sql_function();
+                format!("{func}(CAST({column} AS VARCHAR))")
+            }
             _ => {
                 let func = self.sql_function();
                 format!("{func}({column})")
@@ -73,6 +100,17 @@ impl StatisticType {
         }
     }
 
+    /// Returns the name of this statistic for [`Constraint::describe`], which spells
+    /// out the percentile.
+    fn describe(&self) -> String {
+        match self {
+            StatisticType::Percentile(p) if (*p - 0.5).abs() >= f64::EPSILON => {
+                format!("percentile {p}")
+            }
+            other => other.name().to_string(),
+        }
+    }
+
     /// Returns a human-readable name for this statistic.
     fn name(&self) -> &str {
         match self {
@@ -90,9 +128,21 @@ impl StatisticType {
                     "percentile"
                 }
             }
+            StatisticType::Count => "non-null count",
+            StatisticType::NullCount => "null count",
+            StatisticType::ApproxDistinct => "approximate distinct count",
         }
     }
 
+    /// Returns true if this statistic is computed from the values of the column,
+    /// rather than from how many there are.
+    fn reads_values(&self) -> bool {
+        !matches!(
+            self,
+            StatisticType::Count | StatisticType::NullCount | StatisticType::ApproxDistinct
+        )
+    }
+
     /// Returns the constraint name for backward compatibility.
     fn constraint_name(&self) -> &str {
         match self {
@@ -104,6 +154,9 @@ impl StatisticType {
             StatisticType::Variance => "variance",
             StatisticType::Median => "median",
             StatisticType::Percentile(_) => "percentile",
+            StatisticType::Count => "count",
+            StatisticType::NullCount => "null_count",
+            StatisticType::ApproxDistinct => "approx_distinct",
         }
     }
 }
@@ -122,6 +175,10 @@ impl fmt::Display for StatisticType {
 /// This constraint replaces the individual statistical constraints and provides
 /// a consistent interface for all statistical checks.
 ///
+/// On `Decimal128`/`Decimal256` columns, min, max, sum and mean are computed exactly
+/// in the column's decimal type. The result is rounded to the nearest `f64` (ties to
+/// even) only when the assertion is evaluated.
+///
 /// # Examples
 ///
 /// ```rust
@@ -157,6 +214,8 @@ pub struct StatisticalConstraint {
     statistic: StatisticType,
     /// The assertion to evaluate against the statistic
     assertion: Assertion,
+    /// How NaN and infinite values of float columns are treated
+    non_finite: NonFinitePolicy,
 }
 
 impl StatisticalConstraint {
@@ -192,9 +251,19 @@ impl StatisticalConstraint {
             column: column_str,
             statistic,
             assertion,
+            non_finite: NonFinitePolicy::default(),
         })
     }
 
+    /// Sets how NaN and infinite values are treated, ignoring them by default.
+    ///
+    /// Only statistics of the values themselves are affected; counts are not.
+    /// See [`NonFinitePolicy`].
+    pub fn non_finite_policy(mut self, policy: NonFinitePolicy) -> Self {
+        self.non_finite = policy;
+        self
+    }
+
     /// Creates a minimum value constraint.
     pub fn min(column: impl Into<String>, assertion: Assertion) -> Result<Self> {
         Self::new(column, StatisticType::Min, assertion)
@@ -244,6 +313,44 @@ impl StatisticalConstraint {
     }
 }
 
+impl StatisticalConstraint {
+    /// Builds the query computing the statistic over a table.
+    ///
+    /// For float columns the query also counts the non-finite values, and
+    /// leaves them out of the statistic under [`NonFinitePolicy::Ignore`].
+    fn statistic_query(&self, table_name: &str, float_column: bool) -> Result<String> {
+        let column_identifier = SqlSecurity::escape_identifier(&self.column)?;
+        if !float_column {
+            let stat_expr = self.statistic.sql_expression(&column_identifier);
+            return Ok(format!(
+                "SELECT {stat_expr} as stat_value FROM {table_name}"
+            ));
+        }
+
+        let values = if self.non_finite == NonFinitePolicy::Ignore && self.statistic.reads_values()
+        {
+            finite_values_sql(&column_identifier)
+        } else {
+            column_identifier.clone()
+        };
+        let stat_expr = self.statistic.sql_expression(&values);
+        let count_expr = non_finite_count_sql(&column_identifier);
+        Ok(format!(
+            "SELECT {stat_expr} as stat_value, {count_expr} as non_finite FROM {table_name}"
+        ))
+    }
+
+    /// Evaluates the assertion against a computed statistic.
+    fn assertion_result(&self, value: f64) -> ConstraintResult {
+        self.assertion
+            .explain(
+                value,
+                format!("{} of column '{}'", self.statistic, self.column),
+            )
+            .to_result()
+    }
+}
+
 #[async_trait]
 impl Constraint for StatisticalConstraint {
     #[instrument(skip(self, ctx), fields(
@@ -252,18 +359,15 @@ impl Constraint for StatisticalConstraint {
         assertion = %self.assertion
     ))]
     async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
-        let column_identifier = SqlSecurity::escape_identifier(&self.column)?;
-        let stat_expr = self.statistic.sql_expression(&column_identifier);
         // Get the table name from the validation context
-
         let validation_ctx = current_validation_context();
-
         let table_name = validation_ctx.table_name();
 
-        let sql = format!("SELECT {stat_expr} as stat_value FROM {table_name}");
+        let float_column = is_float_column(ctx, table_name, &self.column).await;
+        let sql = self.statistic_query(table_name, float_column)?;
 
         let df = ctx.sql(&sql).await?;
-        let batches = df.collect().await?;
+        let batches = collect_with_metrics(df).await?;
 
         if batches.is_empty() {
             return Ok(ConstraintResult::skipped("No data to validate"));
@@ -274,51 +378,35 @@ impl Constraint for StatisticalConstraint {
             return Ok(ConstraintResult::skipped("No data to validate"));
         }
 
-        // Extract the statistic value - try Int64 first, then Float64
-        let value = if let Ok(array) = batch
-            .column(0)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract statistic value".to_string()))
-        {
-            if array.is_null(0) {
-                let stat_name = self.statistic.name();
-                return Ok(ConstraintResult::failure(format!(
-                    "{stat_name} is null (no non-null values)"
-                )));
+        let non_finite = if float_column {
+            Some(read_count(batch.column(1).as_ref())?)
+        } else {
+            None
+        };
+        if let Some(count) = non_finite.filter(|count| *count > 0) {
+            if self.non_finite == NonFinitePolicy::Fail && self.statistic.reads_values() {
+                return Ok(non_finite_failure(&self.column, count));
             }
-            array.value(0) as f64
-        } else if let Ok(array) = batch
-            .column(0)
-            .as_any()
-            .downcast_ref::<arrow::array::Float64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract statistic value".to_string()))
-        {
-            if array.is_null(0) {
+        }
+
+        // Decimal statistics stay exact until this point and are rounded once here
+        let result = match value_as_f64(batch.column(0).as_ref(), 0)? {
+            Some(value) => self.assertion_result(value),
+            None if is_table_empty(ctx, table_name).await? => {
+                return Ok(ConstraintResult::empty_table());
+            }
+            None => {
                 let stat_name = self.statistic.name();
-                return Ok(ConstraintResult::failure(format!(
-                    "{stat_name} is null (no non-null values)"
-                )));
+                let missing = if non_finite.unwrap_or(0) > 0 {
+                    "finite"
+                } else {
+                    "non-null"
+                };
+                ConstraintResult::failure(format!("{stat_name} is null (no {missing} values)"))
             }
-            array.value(0)
-        } else {
-            return Err(TermError::Internal(
-                "Failed to extract statistic value".to_string(),
-            ));
         };
 
-        if self.assertion.evaluate(value) {
-            Ok(ConstraintResult::success_with_metric(value))
-        } else {
-            Ok(ConstraintResult::failure_with_metric(
-                value,
-                format!(
-                    "{} {value} does not {}",
-                    self.statistic.name(),
-                    self.assertion
-                ),
-            ))
-        }
+        Ok(with_non_finite(result, non_finite))
     }
 
     fn name(&self) -> &str {
@@ -329,6 +417,81 @@ impl Constraint for StatisticalConstraint {
         Some(&self.column)
     }
 
+    fn generate_sql(&self, table_name: &str) -> Result<Vec<String>> {
+        // The column type is unknown here, so this is the query for non-float columns
+        Ok(vec![self.statistic_query(table_name, false)?])
+    }
+
+    fn evaluate_from_statistics(&self, statistics: &ColumnStatistics) -> Option<ConstraintResult> {
+        // Precomputed statistics do not count non-finite values, so a policy
+        // needing the count or a non-finite bound falls back to a query
+        let value = match self.statistic {
+            StatisticType::Min | StatisticType::Max if self.non_finite == NonFinitePolicy::Fail => {
+                return None
+            }
+            StatisticType::Min | StatisticType::Max
+                if self.non_finite == NonFinitePolicy::Ignore
+                    && [statistics.min, statistics.max]
+                        .iter()
+                        .flatten()
+                        .any(|value| !value.is_finite()) =>
+            {
+                return None
+            }
+            StatisticType::Min => statistics.min,
+            StatisticType::Max => statistics.max,
+            StatisticType::Count => {
+                return Some(self.assertion_result(statistics.non_null_count() as f64))
+            }
+            StatisticType::NullCount => {
+                return Some(self.assertion_result(statistics.null_count as f64))
+            }
+            _ => return None,
+        };
+        match value {
+            Some(value) => Some(self.assertion_result(value)),
+            None if statistics.row_count == 0 => Some(ConstraintResult::empty_table()),
+            None if statistics.non_null_count() == 0 => {
+                let stat_name = self.statistic.name();
+                Some(ConstraintResult::failure(format!(
+                    "{stat_name} is null (no non-null values)"
+                )))
+            }
+            // Non-numeric column, evaluate with a query
+            None => None,
+        }
+    }
+
+    /// Answers min and max constraints from the bounds recorded in the metadata.
+    ///
+    /// Bounds are only recorded for integer columns, which hold no non-finite
+    /// values, so the result does not depend on the non-finite policy.
+    fn evaluate_from_metadata(&self, metadata: &MetadataStatistics) -> Option<ConstraintResult> {
+        let column = metadata.column(&self.column)?;
+        let value = match self.statistic {
+            StatisticType::Min => column.min,
+            StatisticType::Max => column.max,
+            _ => None,
+        };
+        value
+            .filter(|value| value.is_finite())
+            .map(|value| self.assertion_result(value))
+    }
+
+    fn parameters(&self) -> Vec<ParameterRef> {
+        self.assertion
+            .parameters(&format!("{} assertion", self.statistic.name()))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "the {} of {} must be {}",
+            self.statistic.describe(),
+            self.column,
+            self.assertion.requirement()
+        )
+    }
+
     fn metadata(&self) -> ConstraintMetadata {
         let mut metadata = ConstraintMetadata::for_column(&self.column)
             .with_description(format!(
@@ -339,7 +502,8 @@ impl Constraint for StatisticalConstraint {
             ))
             .with_custom("assertion", self.assertion.to_string())
             .with_custom("statistic_type", self.statistic.to_string())
-            .with_custom("constraint_type", "statistical");
+            .with_custom("constraint_type", "statistical")
+            .with_custom("non_finite_policy", self.non_finite.to_string());
 
         if let StatisticType::Percentile(p) = self.statistic {
             metadata = metadata.with_custom("percentile", p.to_string());
@@ -352,7 +516,9 @@ impl Constraint for StatisticalConstraint {
 /// A constraint that can compute multiple statistics in a single query for performance optimization.
 ///
 /// This is useful when you need to validate multiple statistics on the same column,
-/// as it reduces the number of table scans required.
+/// as it reduces the number of table scans required. Each statistic is checked
+/// separately, see [`evaluate_statistics`](Self::evaluate_statistics); failure
+/// messages name the statistics that failed.
 ///
 /// # Examples
 ///
@@ -368,6 +534,8 @@ impl Constraint for StatisticalConstraint {
 ///         (StatisticType::Max, Assertion::LessThan(5000.0)),
 ///         (StatisticType::Mean, Assertion::Between(100.0, 1000.0)),
 ///         (StatisticType::Percentile(0.95), Assertion::LessThan(2000.0)),
+///         (StatisticType::NullCount, Assertion::Equals(0.0)),
+///         (StatisticType::ApproxDistinct, Assertion::GreaterThan(100.0)),
 ///     ]
 /// )?;
 /// # Ok(())
@@ -413,19 +581,17 @@ impl MultiStatisticalConstraint {
             statistics,
         })
     }
-}
 
-#[async_trait]
-impl Constraint for MultiStatisticalConstraint {
-    #[instrument(skip(self, ctx), fields(
-        column = %self.column,
-        num_statistics = %self.statistics.len()
-    ))]
-    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+    /// Returns the statistics and the assertions they are checked against.
+    pub fn statistics(&self) -> &[(StatisticType, Assertion)] {
+        &self.statistics
+    }
+
+    /// Builds the query computing all statistics over a table at once.
+    fn statistics_query(&self, table_name: &str) -> Result<String> {
         let column_identifier = SqlSecurity::escape_identifier(&self.column)?;
 
-        // Build SQL with all statistics computed in one query
-        let sql_parts: Vec<String> = self
+        let parts = self
             .statistics
             .iter()
             .enumerate()
@@ -433,70 +599,96 @@ impl Constraint for MultiStatisticalConstraint {
                 let expr = stat.sql_expression(&column_identifier);
                 format!("{expr} as stat_{i}")
             })
-            .collect();
+            .collect::<Vec<_>>()
+            .join(", ");
+        Ok(format!("SELECT {parts} FROM {table_name}"))
+    }
 
-        let parts = sql_parts.join(", ");
+    /// Computes all statistics in a single query and checks each against its assertion.
+    ///
+    /// Returns one result per statistic, in the order they were configured.
+    /// [`evaluate`](Constraint::evaluate) combines these into a single result.
+    pub async fn evaluate_statistics(
+        &self,
+        ctx: &SessionContext,
+    ) -> Result<Vec<(StatisticType, ConstraintResult)>> {
         // Get the table name from the validation context
         let validation_ctx = current_validation_context();
         let table_name = validation_ctx.table_name();
 
-        let sql = format!("SELECT {parts} FROM {table_name}");
+        let sql = self.statistics_query(table_name)?;
 
         let df = ctx.sql(&sql).await?;
-        let batches = df.collect().await?;
-
-        if batches.is_empty() {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
+        let batches = collect_with_metrics(df).await?;
+
+        let Some(batch) = batches.first().filter(|batch| batch.num_rows() > 0) else {
+            return Ok(self
+                .statistics
+                .iter()
+                .map(|(stat_type, _)| {
+                    (
+                        stat_type.clone(),
+                        ConstraintResult::skipped("No data to validate"),
+                    )
+                })
+                .collect());
+        };
 
-        let batch = &batches[0];
-        if batch.num_rows() == 0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
+        let values: Vec<_> = (0..self.statistics.len())
+            .map(|i| value_as_f64(batch.column(i).as_ref(), 0))
+            .collect();
+        // Statistics other than counts are null without rows
+        let table_empty = values.iter().any(|value| matches!(value, Ok(None)))
+            && is_table_empty(ctx, table_name).await?;
 
         // Check each statistic
-        let mut failures = Vec::new();
-        let mut all_metrics = Vec::new();
+        Ok(self
+            .statistics
+            .iter()
+            .zip(values)
+            .map(|((stat_type, assertion), value)| {
+                let result = match value {
+                    Ok(None) if table_empty => ConstraintResult::empty_table(),
+                    Ok(Some(value)) => assertion
+                        .explain(value, format!("{stat_type} of column '{}'", self.column))
+                        .to_result(),
+                    Ok(None) => ConstraintResult::failure(format!("{stat_type} is null")),
+                    Err(_) => ConstraintResult::failure(format!("Failed to compute {stat_type}")),
+                };
+                (stat_type.clone(), result)
+            })
+            .collect())
+    }
+}
 
-        for (i, (stat_type, assertion)) in self.statistics.iter().enumerate() {
-            let column = batch.column(i);
+#[async_trait]
+impl Constraint for MultiStatisticalConstraint {
+    #[instrument(skip(self, ctx), fields(
+        column = %self.column,
+        num_statistics = %self.statistics.len()
+    ))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let results = self.evaluate_statistics(ctx).await?;
 
-            // Extract value
-            let value = if let Some(array) =
-                column.as_any().downcast_ref::<arrow::array::Float64Array>()
-            {
-                if array.is_null(0) {
-                    let name = stat_type.name();
-                    failures.push(format!("{name} is null"));
-                    continue;
-                }
-                array.value(0)
-            } else if let Some(array) = column.as_any().downcast_ref::<arrow::array::Int64Array>() {
-                if array.is_null(0) {
-                    let name = stat_type.name();
-                    failures.push(format!("{name} is null"));
-                    continue;
-                }
-                array.value(0) as f64
-            } else {
-                let name = stat_type.name();
-                failures.push(format!("Failed to compute {name}"));
-                continue;
-            };
-
-            all_metrics.push((stat_type.name().to_string(), value));
-
-            if !assertion.evaluate(value) {
-                failures.push(format!(
-                    "{} is {value} which does not {assertion}",
-                    stat_type.name()
-                ));
-            }
+        if let Some((_, result)) = results.iter().find(|(_, result)| result.is_empty_table()) {
+            return Ok(result.clone());
+        }
+        if results.iter().all(|(_, result)| result.status.is_skipped()) {
+            return Ok(ConstraintResult::skipped("No data to validate"));
         }
 
+        let failures: Vec<&str> = results
+            .iter()
+            .filter(|(_, result)| result.status.is_failure())
+            .filter_map(|(_, result)| result.message.as_deref())
+            .collect();
+
         if failures.is_empty() {
             // All assertions passed - return the first metric as representative
-            let first_metric = all_metrics.first().map(|(_, v)| *v).unwrap_or(0.0);
+            let first_metric = results
+                .iter()
+                .find_map(|(_, result)| result.metric)
+                .unwrap_or(0.0);
             Ok(ConstraintResult::success_with_metric(first_metric))
         } else {
             Ok(ConstraintResult::failure(failures.join("; ")))
@@ -511,6 +703,35 @@ impl Constraint for MultiStatisticalConstraint {
         Some(&self.column)
     }
 
+    fn generate_sql(&self, table_name: &str) -> Result<Vec<String>> {
+        Ok(vec![self.statistics_query(table_name)?])
+    }
+
+    fn parameters(&self) -> Vec<ParameterRef> {
+        self.statistics
+            .iter()
+            .flat_map(|(statistic, assertion)| {
+                assertion.parameters(&format!("{} assertion", statistic.name()))
+            })
+            .collect()
+    }
+
+    fn describe(&self) -> String {
+        let requirements: Vec<String> = self
+            .statistics
+            .iter()
+            .map(|(statistic, assertion)| {
+                format!(
+                    "the {} of {} must be {}",
+                    statistic.describe(),
+                    self.column,
+                    assertion.requirement()
+                )
+            })
+            .collect();
+        describe_list(&requirements)
+    }
+
     fn metadata(&self) -> ConstraintMetadata {
         let stat_names: Vec<String> = self
             .statistics
@@ -534,8 +755,9 @@ impl Constraint for MultiStatisticalConstraint {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::ConstraintStatus;
-    use arrow::array::Float64Array;
+    use crate::constraints::NON_FINITE_DETAIL;
+    use crate::core::{ConstraintStatus, ValidationContext, CURRENT_CONTEXT};
+    use arrow::array::{Decimal128Array, Float64Array};
     use arrow::datatypes::{DataType, Field, Schema};
     use arrow::record_batch::RecordBatch;
     use datafusion::datasource::MemTable;
@@ -615,6 +837,64 @@ mod tests {
         assert_eq!(result.metric, Some(15.0));
     }
 
+    #[tokio::test]
+    async fn test_non_finite_policies() {
+        let ctx = create_test_context(vec![
+            Some(10.0),
+            Some(f64::NAN),
+            Some(20.0),
+            Some(f64::INFINITY),
+        ])
+        .await;
+        let mean = || StatisticalConstraint::mean("value", Assertion::Equals(15.0)).unwrap();
+
+        let result = evaluate_constraint_with_context(&mean(), &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(15.0));
+        assert_eq!(result.details[NON_FINITE_DETAIL], 2.0);
+
+        let constraint = mean().non_finite_policy(NonFinitePolicy::Fail);
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert!(result.message.unwrap().contains("2 non-finite values"));
+        assert_eq!(result.details[NON_FINITE_DETAIL], 2.0);
+
+        let constraint = mean().non_finite_policy(NonFinitePolicy::Propagate);
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert!(result.metric.unwrap().is_nan());
+        assert_eq!(result.details[NON_FINITE_DETAIL], 2.0);
+
+        // Counts are not affected by the policy
+        let constraint =
+            StatisticalConstraint::new("value", StatisticType::Count, Assertion::Equals(4.0))
+                .unwrap()
+                .non_finite_policy(NonFinitePolicy::Fail);
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+    }
+
+    #[tokio::test]
+    async fn test_only_non_finite_values() {
+        let ctx = create_test_context(vec![Some(f64::NAN), None]).await;
+        let constraint = StatisticalConstraint::max("value", Assertion::LessThan(1.0)).unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(
+            result.message.as_deref(),
+            Some("maximum is null (no finite values)")
+        );
+    }
+
     #[tokio::test]
     async fn test_all_nulls() {
         let ctx = create_test_context(vec![None, None, None]).await;
@@ -627,6 +907,58 @@ mod tests {
         assert!(result.message.unwrap().contains("null"));
     }
 
+    #[tokio::test]
+    async fn test_decimal_statistics_are_exact() {
+        let ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "amount",
+            DataType::Decimal128(38, 6),
+            true,
+        )]));
+        let mut values = vec![Some(1_000_000_000_000_000_000_000_i128)];
+        values.extend(std::iter::repeat(Some(60_000)).take(10));
+        let array = Decimal128Array::from(values)
+            .with_precision_and_scale(38, 6)
+            .unwrap();
+        let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("ledger", Arc::new(provider)).unwrap();
+
+        // Summing in f64 would stay at 1e15; the exact sum rounds to 1e15 + 0.625
+        let constraint =
+            StatisticalConstraint::sum("amount", Assertion::Equals(1_000_000_000_000_000.6))
+                .unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "ledger")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(1_000_000_000_000_000.6));
+
+        let constraint = MultiStatisticalConstraint::new(
+            "amount",
+            vec![
+                (StatisticType::Min, Assertion::Equals(0.06)),
+                (StatisticType::Max, Assertion::Equals(1e15)),
+                (StatisticType::Mean, Assertion::GreaterThan(0.0)),
+                (
+                    StatisticType::StandardDeviation,
+                    Assertion::GreaterThan(0.0),
+                ),
+                (StatisticType::Median, Assertion::LessThan(1.0)),
+            ],
+        )
+        .unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "ledger")
+            .await
+            .unwrap();
+        assert_eq!(
+            result.status,
+            ConstraintStatus::Success,
+            "{:?}",
+            result.message
+        );
+    }
+
     #[test]
     fn test_statistic_type_display() {
         assert_eq!(StatisticType::Min.to_string(), "minimum");
@@ -676,7 +1008,93 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(result.status, ConstraintStatus::Failure);
-        assert!(result.message.unwrap().contains("minimum is 10"));
+        assert_eq!(
+            result.message.as_deref(),
+            Some("Minimum of column 'value' is 10, expected 5 (5 above)")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multi_statistical_counts() {
+        let ctx = create_test_context(vec![Some(10.0), None, Some(10.0), Some(30.0), None]).await;
+
+        let constraint = MultiStatisticalConstraint::new(
+            "value",
+            vec![
+                (StatisticType::Max, Assertion::LessThanOrEqual(30.0)),
+                (StatisticType::Count, Assertion::Equals(3.0)),
+                (StatisticType::NullCount, Assertion::Equals(0.0)), // Will fail
+                (StatisticType::ApproxDistinct, Assertion::Equals(2.0)),
+            ],
+        )
+        .unwrap();
+
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(
+            result.message.as_deref(),
+            Some("Null count of column 'value' is 2, expected 0 (2 above)")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multi_statistical_results_per_statistic() {
+        let ctx = create_test_context(vec![Some(10.0), None, Some(20.0)]).await;
+
+        let constraint = MultiStatisticalConstraint::new(
+            "value",
+            vec![
+                (StatisticType::Min, Assertion::Equals(10.0)),
+                (StatisticType::Count, Assertion::GreaterThan(2.0)), // Will fail
+                (StatisticType::NullCount, Assertion::Equals(1.0)),
+            ],
+        )
+        .unwrap();
+
+        let results = CURRENT_CONTEXT
+            .scope(
+                ValidationContext::new("data"),
+                constraint.evaluate_statistics(&ctx),
+            )
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, StatisticType::Min);
+        assert_eq!(results[0].1.status, ConstraintStatus::Success);
+        assert_eq!(results[1].0, StatisticType::Count);
+        assert_eq!(results[1].1.status, ConstraintStatus::Failure);
+        assert_eq!(results[1].1.metric, Some(2.0));
+        assert!(results[1]
+            .1
+            .message
+            .as_ref()
+            .unwrap()
+            .starts_with("Non-null count of column 'value' is 2, expected > 2"));
+        assert_eq!(results[2].1.metric, Some(1.0));
+    }
+
+    #[test]
+    fn test_count_from_statistics() {
+        let statistics = ColumnStatistics {
+            row_count: 10,
+            null_count: 3,
+            ..Default::default()
+        };
+
+        let count =
+            StatisticalConstraint::new("value", StatisticType::Count, Assertion::Equals(7.0))
+                .unwrap();
+        let result = count.evaluate_from_statistics(&statistics).unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+
+        let nulls =
+            StatisticalConstraint::new("value", StatisticType::NullCount, Assertion::Equals(0.0))
+                .unwrap();
+        let result = nulls.evaluate_from_statistics(&statistics).unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(3.0));
     }
 
     #[test]
@@ -693,4 +1111,51 @@ mod tests {
             .to_string()
             .contains("Percentile must be between 0.0 and 1.0"));
     }
+
+    #[test]
+    fn test_generate_sql() -> Result<()> {
+        let constraint =
+            StatisticalConstraint::new("age", StatisticType::Mean, Assertion::LessThan(60.0))?;
+        assert_eq!(
+            constraint.generate_sql("people")?,
+            vec![r#"SELECT AVG("age") as stat_value FROM people"#]
+        );
+
+        let constraint = MultiStatisticalConstraint::new(
+            "age",
+            vec![
+                (StatisticType::Min, Assertion::GreaterThanOrEqual(0.0)),
+                (StatisticType::Max, Assertion::LessThan(150.0)),
+            ],
+        )?;
+        assert_eq!(
+            constraint.generate_sql("people")?,
+            vec![r#"SELECT MIN("age") as stat_0, MAX("age") as stat_1 FROM people"#]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_describe() {
+        let constraint =
+            StatisticalConstraint::percentile("latency", 0.95, Assertion::LessThanOrEqual(200.0))
+                .unwrap();
+        assert_eq!(
+            constraint.describe(),
+            "the percentile 0.95 of latency must be at most 200"
+        );
+
+        let constraint = MultiStatisticalConstraint::new(
+            "price",
+            vec![
+                (StatisticType::Min, Assertion::GreaterThanOrEqual(0.0)),
+                (StatisticType::Mean, Assertion::Between(10.0, 50.0)),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            constraint.describe(),
+            "the minimum of price must be at least 0 and the mean of price must be between 10 and 50"
+        );
+    }
 }
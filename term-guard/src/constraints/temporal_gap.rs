@@ -0,0 +1,618 @@
+//! Temporal gap detection for time series completeness.
+//!
+//! [`TemporalGapConstraint`] checks that consecutive timestamps, optionally per
+//! partition (e.g. per device), are no further apart than an expected interval.
+//! Gaps are computed with the `LAG` window function, so the table does not need
+//! to be sorted.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use term_guard::constraints::TemporalGapConstraint;
+//! use term_guard::core::Check;
+//!
+//! // One reading per device every 5 minutes, at most 1% of intervals may be late
+//! let constraint = TemporalGapConstraint::new("reading_time", Duration::from_secs(300))
+//!     .partition_by("device_id")
+//!     .tolerance(Duration::from_secs(30))
+//!     .threshold(0.01)
+//!     .max_gap(Duration::from_secs(3600));
+//!
+//! let check = Check::builder("sensor_completeness")
+//!     .constraint(constraint)
+//!     .build();
+//! ```
+
+use super::timezone::{local_timestamp_sql, parse_timezone, register_timezone_functions};
+use crate::core::{
+    collect_with_metrics, current_validation_context, skipped_without_values, Constraint,
+    ConstraintMetadata, ConstraintResult, ConstraintStatus, QualityDimension,
+};
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+use arrow::array::{Array, Float64Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use chrono_tz::Tz;
+use datafusion::prelude::*;
+use std::fmt;
+use std::time::Duration;
+use tracing::{debug, instrument, warn};
+
+/// A gap between two consecutive timestamps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemporalGap {
+    /// Value of the partition column, if the constraint is partitioned
+    pub partition: Option<String>,
+    /// The timestamp before the gap
+    pub start: String,
+    /// The timestamp after the gap
+    pub end: String,
+    /// Length of the gap in seconds
+    pub gap_seconds: f64,
+}
+
+impl fmt::Display for TemporalGap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(partition) = &self.partition {
+            write!(f, "{partition}: ")?;
+        }
+        write!(f, "{} -> {} ({}s)", self.start, self.end, self.gap_seconds)
+    }
+}
+
+/// A constraint that detects gaps in a time series.
+///
+/// For every pair of consecutive timestamps within a partition, the gap between
+/// them is compared against `interval + tolerance`. The constraint fails if the
+/// fraction of intervals exceeding that limit is above the threshold, or if any
+/// single gap exceeds the configured hard maximum.
+///
+/// Null timestamps are ignored. Both timezone-naive and timezone-aware timestamp
+/// columns are supported; gaps are measured in elapsed seconds.
+///
+/// The metric is the fraction of intervals that exceed the limit.
+#[derive(Debug, Clone)]
+pub struct TemporalGapConstraint {
+    column: String,
+    interval: Duration,
+    partition_by: Option<String>,
+    tolerance: Duration,
+    threshold: f64,
+    max_gap: Option<Duration>,
+    max_gaps_reported: usize,
+    timezone: Option<Tz>,
+}
+
+impl TemporalGapConstraint {
+    /// Creates a new temporal gap constraint.
+    ///
+    /// By default no gaps are allowed (threshold 0.0), there is no tolerance and
+    /// the five largest gaps are reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The timestamp column
+    /// * `interval` - The expected interval between consecutive timestamps
+    pub fn new(column: impl Into<String>, interval: Duration) -> Self {
+        Self {
+            column: column.into(),
+            interval,
+            partition_by: None,
+            tolerance: Duration::ZERO,
+            threshold: 0.0,
+            max_gap: None,
+            max_gaps_reported: 5,
+            timezone: None,
+        }
+    }
+
+    /// Computes gaps separately for each value of the given column.
+    pub fn partition_by(mut self, column: impl Into<String>) -> Self {
+        self.partition_by = Some(column.into());
+        self
+    }
+
+    /// Sets the tolerance added to the expected interval.
+    pub fn tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum allowed fraction of intervals exceeding `interval + tolerance`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the threshold is not between 0.0 and 1.0.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&threshold),
+            "Threshold must be between 0.0 and 1.0"
+        );
+        self.threshold = threshold;
+        self
+    }
+
+    /// Fails the constraint if any single gap exceeds this duration.
+    pub fn max_gap(mut self, max_gap: Duration) -> Self {
+        self.max_gap = Some(max_gap);
+        self
+    }
+
+    /// Sets how many of the largest gaps are included in the failure message.
+    pub fn max_gaps_reported(mut self, count: usize) -> Self {
+        self.max_gaps_reported = count;
+        self
+    }
+
+    /// Reports the timestamps around gaps in local time of an IANA timezone.
+    ///
+    /// Gap lengths are elapsed time and do not depend on the timezone, so a
+    /// daylight saving time change is neither a gap nor hides one. Without a
+    /// timezone, the timestamps are reported as stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if `timezone` is not an IANA timezone
+    /// name such as `Europe/Berlin`.
+    pub fn with_timezone(mut self, timezone: &str) -> Result<Self> {
+        self.timezone = Some(parse_timezone(timezone)?);
+        Ok(self)
+    }
+
+    /// Returns the gap length in seconds above which an interval counts as a gap.
+    fn gap_limit_seconds(&self) -> f64 {
+        (self.interval + self.tolerance).as_secs_f64()
+    }
+
+    /// Builds the query producing one row per interval between consecutive timestamps.
+    fn gaps_query(&self, table_name: &str) -> Result<String> {
+        let column = SqlSecurity::escape_identifier(&self.column)?;
+        let (partition_select, partition_clause) = match &self.partition_by {
+            Some(partition) => {
+                let partition = SqlSecurity::escape_identifier(partition)?;
+                (
+                    format!("CAST({partition} AS VARCHAR)"),
+                    format!("PARTITION BY {partition} "),
+                )
+            }
+            None => ("CAST(NULL AS VARCHAR)".to_string(), String::new()),
+        };
+        let window = format!("OVER ({partition_clause}ORDER BY {column})");
+        let display = |column: &str| match self.timezone {
+            Some(timezone) => local_timestamp_sql(column, timezone),
+            None => format!("CAST({column} AS VARCHAR)"),
+        };
+        let gap_start = display(&format!("LAG({column}) {window}"));
+        let gap_end = display(&column);
+
+        // Epoch seconds are UTC-based for both naive and timezone-aware timestamps
+        Ok(format!(
+            "SELECT
+                {partition_select} AS gap_partition,
+                {gap_start} AS gap_start,
+                {gap_end} AS gap_end,
+                date_part('epoch', {column}) - LAG(date_part('epoch', {column})) {window} AS gap_seconds
+             FROM {table_name}
+             WHERE {column} IS NOT NULL"
+        ))
+    }
+
+    /// Returns the largest gaps exceeding `interval + tolerance`, longest first.
+    ///
+    /// The table is taken from the current validation context.
+    pub async fn largest_gaps(
+        &self,
+        ctx: &SessionContext,
+        limit: usize,
+    ) -> Result<Vec<TemporalGap>> {
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+        let gaps = self.gaps_query(table_name)?;
+        if self.timezone.is_some() {
+            register_timezone_functions(ctx);
+        }
+        let sql = format!(
+            "SELECT gap_partition, gap_start, gap_end, gap_seconds
+             FROM ({gaps}) AS gaps
+             WHERE gap_seconds > {}
+             ORDER BY gap_seconds DESC, gap_partition, gap_end
+             LIMIT {limit}",
+            self.gap_limit_seconds()
+        );
+
+        let df = ctx.sql(&sql).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                self.name(),
+                format!("Failed to execute largest gaps query: {e}"),
+            )
+        })?;
+        let batches = collect_with_metrics(df).await?;
+
+        let mut result = Vec::new();
+        for batch in &batches {
+            let partitions = string_column(batch, 0)?;
+            let starts = string_column(batch, 1)?;
+            let ends = string_column(batch, 2)?;
+            let seconds = batch
+                .column(3)
+                .as_any()
+                .downcast_ref::<Float64Array>()
+                .ok_or_else(|| TermError::Internal("Failed to extract gap length".to_string()))?;
+
+            for i in 0..batch.num_rows() {
+                result.push(TemporalGap {
+                    partition: (!partitions.is_null(i)).then(|| partitions.value(i).to_string()),
+                    start: starts.value(i).to_string(),
+                    end: ends.value(i).to_string(),
+                    gap_seconds: seconds.value(i),
+                });
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Reads a column as strings; `CAST(... AS VARCHAR)` may produce `Utf8View`.
+fn string_column(batch: &RecordBatch, index: usize) -> Result<StringArray> {
+    let array = cast(batch.column(index), &DataType::Utf8)?;
+    array
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .cloned()
+        .ok_or_else(|| TermError::Internal(format!("Expected string column at index {index}")))
+}
+
+#[async_trait]
+impl Constraint for TemporalGapConstraint {
+    #[instrument(skip(self, ctx), fields(
+        column = %self.column,
+        interval = ?self.interval,
+        threshold = %self.threshold
+    ))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+        let gaps = self.gaps_query(table_name)?;
+        if self.timezone.is_some() {
+            register_timezone_functions(ctx);
+        }
+        let limit = self.gap_limit_seconds();
+
+        let sql = format!(
+            "SELECT
+                COUNT(gap_seconds) AS interval_count,
+                COALESCE(SUM(CASE WHEN gap_seconds > {limit} THEN 1 ELSE 0 END), 0) AS gap_count,
+                MAX(gap_seconds) AS largest_gap
+             FROM ({gaps}) AS gaps"
+        );
+        debug!("Generated temporal gap query: {}", sql);
+
+        let df = ctx.sql(&sql).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                self.name(),
+                format!("Failed to execute temporal gap query: {e}"),
+            )
+        })?;
+        let batches = collect_with_metrics(df).await?;
+
+        if batches.is_empty() || batches[0].num_rows() == 0 {
+            return Ok(ConstraintResult::skipped("No data to validate"));
+        }
+
+        let batch = &batches[0];
+        let interval_count = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .ok_or_else(|| TermError::Internal("Failed to extract interval count".to_string()))?
+            .value(0);
+        if interval_count == 0 {
+            return skipped_without_values(
+                ctx,
+                table_name,
+                "Fewer than two timestamps per partition",
+            )
+            .await;
+        }
+
+        let gap_count = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .ok_or_else(|| TermError::Internal("Failed to extract gap count".to_string()))?
+            .value(0);
+        let largest_gap = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .ok_or_else(|| TermError::Internal("Failed to extract largest gap".to_string()))?
+            .value(0);
+
+        let gap_fraction = gap_count as f64 / interval_count as f64;
+        let exceeds_max = self
+            .max_gap
+            .is_some_and(|max| largest_gap > max.as_secs_f64());
+
+        if gap_fraction <= self.threshold && !exceeds_max {
+            return Ok(ConstraintResult::success_with_metric(gap_fraction));
+        }
+
+        let mut message = format!(
+            "Found {gap_count} gaps longer than {limit}s in '{}' out of {interval_count} intervals ({:.2}%)",
+            self.column,
+            gap_fraction * 100.0
+        );
+        if exceeds_max {
+            if let Some(max) = self.max_gap {
+                message.push_str(&format!(
+                    "; largest gap of {largest_gap}s exceeds the maximum of {}s",
+                    max.as_secs_f64()
+                ));
+            }
+        } else {
+            message.push_str(&format!(
+                ", exceeding the threshold of {:.2}%",
+                self.threshold * 100.0
+            ));
+        }
+
+        if self.max_gaps_reported > 0 {
+            let largest = self.largest_gaps(ctx, self.max_gaps_reported).await?;
+            if !largest.is_empty() {
+                let largest: Vec<String> = largest.iter().map(ToString::to_string).collect();
+                message.push_str(&format!(". Largest gaps: [{}]", largest.join(", ")));
+            }
+        }
+
+        warn!("{}", message);
+
+        Ok(ConstraintResult {
+            status: ConstraintStatus::Failure,
+            metric: Some(gap_fraction),
+            message: Some(message),
+            quarantine: None,
+            constraint_id: None,
+            details: Default::default(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "temporal_gap"
+    }
+
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Timeliness)
+    }
+
+    fn column(&self) -> Option<&str> {
+        Some(&self.column)
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        let mut metadata = ConstraintMetadata::for_column(&self.column)
+            .with_description(format!(
+                "Checks that at most {:.2}% of intervals in column '{}' exceed {}s",
+                self.threshold * 100.0,
+                self.column,
+                self.gap_limit_seconds()
+            ))
+            .with_custom("interval_seconds", self.interval.as_secs_f64().to_string())
+            .with_custom(
+                "tolerance_seconds",
+                self.tolerance.as_secs_f64().to_string(),
+            )
+            .with_custom("threshold", self.threshold.to_string())
+            .with_custom("constraint_type", "temporal");
+        if let Some(partition) = &self.partition_by {
+            metadata = metadata.with_custom("partition_by", partition.clone());
+        }
+        if let Some(max) = self.max_gap {
+            metadata = metadata.with_custom("max_gap_seconds", max.as_secs_f64().to_string());
+        }
+        if let Some(timezone) = self.timezone {
+            metadata = metadata.with_custom("timezone", timezone.name());
+        }
+        metadata
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ValidationContext, CURRENT_CONTEXT};
+    use arrow::array::TimestampSecondArray;
+    use arrow::datatypes::{Field, Schema, TimeUnit};
+    use datafusion::datasource::MemTable;
+    use std::sync::Arc;
+
+    use crate::test_helpers::evaluate_constraint_with_context;
+
+    const BASE: i64 = 1_704_067_200; // 2024-01-01T00:00:00Z
+
+    async fn create_context(
+        timezone: Option<&str>,
+        devices: Vec<&str>,
+        offsets: Vec<Option<i64>>,
+    ) -> SessionContext {
+        let ctx = SessionContext::new();
+        let timestamps = TimestampSecondArray::from(
+            offsets
+                .into_iter()
+                .map(|o| o.map(|o| BASE + o))
+                .collect::<Vec<_>>(),
+        )
+        .with_timezone_opt(timezone);
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("device_id", DataType::Utf8, false),
+            Field::new(
+                "reading_time",
+                DataType::Timestamp(TimeUnit::Second, timezone.map(Into::into)),
+                true,
+            ),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(StringArray::from(devices)), Arc::new(timestamps)],
+        )
+        .unwrap();
+        let table = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("readings", Arc::new(table)).unwrap();
+        ctx
+    }
+
+    /// Two devices reporting every 5 minutes, out of order; device b misses two readings.
+    async fn sensor_context(timezone: Option<&str>) -> SessionContext {
+        create_context(
+            timezone,
+            vec!["a", "b", "a", "b", "a", "b", "a", "b", "b"],
+            vec![
+                Some(600),
+                Some(0),
+                Some(0),
+                Some(300),
+                Some(300),
+                Some(1200),
+                Some(900),
+                Some(1500),
+                None,
+            ],
+        )
+        .await
+    }
+
+    async fn evaluate(
+        ctx: &SessionContext,
+        constraint: &TemporalGapConstraint,
+    ) -> ConstraintResult {
+        evaluate_constraint_with_context(constraint, ctx, "readings")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_no_gaps_per_partition() {
+        let ctx = create_context(
+            None,
+            vec!["a", "b", "a", "b"],
+            vec![Some(0), Some(120), Some(300), Some(420)],
+        )
+        .await;
+
+        // Globally consecutive readings are only 2-3 minutes apart, per device 5 minutes
+        let constraint = TemporalGapConstraint::new("reading_time", Duration::from_secs(300))
+            .partition_by("device_id");
+        let result = evaluate(&ctx, &constraint).await;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.0));
+    }
+
+    #[tokio::test]
+    async fn test_gaps_detected_with_bounds() {
+        let ctx = sensor_context(None).await;
+        let constraint = TemporalGapConstraint::new("reading_time", Duration::from_secs(300))
+            .partition_by("device_id");
+
+        let result = evaluate(&ctx, &constraint).await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        // 6 intervals, one of them (b: 00:05 -> 00:20) is a gap
+        assert_eq!(result.metric, Some(1.0 / 6.0));
+        let message = result.message.unwrap();
+        assert!(message.contains("Found 1 gaps longer than 300s"));
+        assert!(message.contains("b: 2024-01-01T00:05:00 -> 2024-01-01T00:20:00 (900s)"));
+    }
+
+    #[tokio::test]
+    async fn test_threshold_and_tolerance() {
+        let ctx = sensor_context(None).await;
+
+        let within_threshold = TemporalGapConstraint::new("reading_time", Duration::from_secs(300))
+            .partition_by("device_id")
+            .threshold(0.2);
+        assert_eq!(
+            evaluate(&ctx, &within_threshold).await.status,
+            ConstraintStatus::Success
+        );
+
+        let within_tolerance = TemporalGapConstraint::new("reading_time", Duration::from_secs(300))
+            .partition_by("device_id")
+            .tolerance(Duration::from_secs(600));
+        assert_eq!(
+            evaluate(&ctx, &within_tolerance).await.status,
+            ConstraintStatus::Success
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hard_maximum_gap() {
+        let ctx = sensor_context(None).await;
+        let constraint = TemporalGapConstraint::new("reading_time", Duration::from_secs(300))
+            .partition_by("device_id")
+            .threshold(0.5)
+            .max_gap(Duration::from_secs(600));
+
+        let result = evaluate(&ctx, &constraint).await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert!(result
+            .message
+            .unwrap()
+            .contains("largest gap of 900s exceeds the maximum of 600s"));
+    }
+
+    #[tokio::test]
+    async fn test_timezone_aware_timestamps() {
+        let naive = sensor_context(None).await;
+        let aware = sensor_context(Some("America/New_York")).await;
+        let constraint = TemporalGapConstraint::new("reading_time", Duration::from_secs(300))
+            .partition_by("device_id");
+
+        let naive_result = evaluate(&naive, &constraint).await;
+        let aware_result = evaluate(&aware, &constraint).await;
+        assert_eq!(naive_result.metric, aware_result.metric);
+
+        let gaps = CURRENT_CONTEXT
+            .scope(
+                ValidationContext::new("readings"),
+                constraint.largest_gaps(&aware, 10),
+            )
+            .await
+            .unwrap();
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].partition.as_deref(), Some("b"));
+        assert_eq!(gaps[0].gap_seconds, 900.0);
+    }
+
+    #[tokio::test]
+    async fn test_gaps_reported_in_local_time() {
+        let ctx = sensor_context(None).await;
+        let constraint = TemporalGapConstraint::new("reading_time", Duration::from_secs(300))
+            .partition_by("device_id")
+            .with_timezone("Europe/Berlin")
+            .unwrap();
+
+        let result = evaluate(&ctx, &constraint).await;
+        assert_eq!(result.metric, Some(1.0 / 6.0));
+        assert!(result
+            .message
+            .unwrap()
+            .contains("b: 2024-01-01T01:05:00+01:00 -> 2024-01-01T01:20:00+01:00 (900s)"));
+
+        let err = TemporalGapConstraint::new("reading_time", Duration::from_secs(300))
+            .with_timezone("GMT+1")
+            .unwrap_err();
+        assert!(err.to_string().contains("Unknown timezone 'GMT+1'"));
+    }
+
+    #[tokio::test]
+    async fn test_single_timestamp_is_skipped() {
+        let ctx = create_context(None, vec!["a"], vec![Some(0)]).await;
+        let constraint = TemporalGapConstraint::new("reading_time", Duration::from_secs(300));
+
+        let result = evaluate(&ctx, &constraint).await;
+        assert_eq!(result.status, ConstraintStatus::Skipped);
+    }
+}
@@ -31,13 +31,26 @@
 //! let constraint = TemporalOrderingConstraint::new("transactions")
 //!     .business_hours("timestamp", "09:00", "17:00")
 //!     .weekdays_only(true);
+//!
+//! // Business hours in local time, with UTC timestamps converted to Berlin time
+//! let local = TemporalOrderingConstraint::new("transactions")
+//!     .business_hours("timestamp", "09:00", "17:00")
+//!     .with_timezone("Europe/Berlin")?;
+//! # Ok::<(), term_guard::prelude::TermError>(())
 //! ```
 
-use crate::core::{Constraint, ConstraintResult, ConstraintStatus};
+use super::timezone::{
+    local_second_of_week_sql, parse_timezone, register_timezone_functions, SECONDS_PER_DAY,
+};
+use crate::core::{
+    collect_with_metrics, is_table_empty, Constraint, ConstraintResult, ConstraintStatus,
+    QualityDimension,
+};
 use crate::error::{Result, TermError};
 use crate::security::SqlSecurity;
 use arrow::array::{Array, Int64Array};
 use async_trait::async_trait;
+use chrono::{NaiveTime, Timelike};
 use datafusion::prelude::*;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, instrument, warn};
@@ -209,7 +222,19 @@ impl TemporalOrderingConstraint {
     }
 
     /// Set the timezone for business hours validation.
-    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Self {
+    ///
+    /// Timestamps are converted to local time in this IANA timezone before the
+    /// hours and weekdays are checked, accounting for daylight saving time.
+    /// Timezone-naive timestamps are interpreted as UTC. Without a timezone, the
+    /// stored time of day is checked as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if `timezone` is not an IANA timezone
+    /// name such as `Europe/Berlin`.
+    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Result<Self> {
+        let timezone = timezone.into();
+        parse_timezone(&timezone)?;
         if let TemporalValidationType::BusinessHours {
             timestamp_column,
             start_time,
@@ -223,10 +248,10 @@ impl TemporalOrderingConstraint {
                 start_time,
                 end_time,
                 weekdays_only,
-                timezone: Some(timezone.into()),
+                timezone: Some(timezone),
             };
         }
-        self
+        Ok(self)
     }
 
     /// Validate that timestamps are within a specific date range.
@@ -387,7 +412,45 @@ impl TemporalOrderingConstraint {
                 start_time,
                 end_time,
                 weekdays_only,
-                ..
+                timezone: Some(timezone),
+            } => {
+                let timezone = parse_timezone(timezone)?;
+                let second_of_week = local_second_of_week_sql(timestamp_column, timezone);
+                let second_of_day = format!("({second_of_week} % {SECONDS_PER_DAY})");
+                let time_check = format!(
+                    "{second_of_day} BETWEEN {} AND {}",
+                    seconds_from_midnight(start_time)?,
+                    seconds_from_midnight(end_time)?
+                );
+
+                // Local days of the week count from Monday = 0
+                let weekday_check = if *weekdays_only {
+                    format!(" AND ({second_of_week} / {SECONDS_PER_DAY}) < 5")
+                } else {
+                    String::new()
+                };
+
+                let null_clause = if self.allow_nulls {
+                    String::new()
+                } else {
+                    format!(" AND {timestamp_column} IS NOT NULL")
+                };
+
+                format!(
+                    "SELECT 
+                        COUNT(*) as total_rows,
+                        SUM(CASE WHEN {time_check} THEN 0 ELSE 1 END) as violations
+                     FROM {}
+                     WHERE 1=1{weekday_check}{null_clause}",
+                    self.table_name
+                )
+            }
+            TemporalValidationType::BusinessHours {
+                timestamp_column,
+                start_time,
+                end_time,
+                weekdays_only,
+                timezone: None,
             } => {
                 let time_check = format!(
                     "CAST({timestamp_column} AS TIME) BETWEEN TIME '{start_time}:00' AND TIME '{end_time}:00'"
@@ -493,6 +556,17 @@ impl TemporalOrderingConstraint {
     }
 }
 
+/// Parses a business hours bound in `HH:MM` format into seconds since midnight.
+fn seconds_from_midnight(time: &str) -> Result<u32> {
+    NaiveTime::parse_from_str(time, "%H:%M")
+        .map(|time| time.num_seconds_from_midnight())
+        .map_err(|_| {
+            TermError::Configuration(format!(
+                "Invalid business hours time '{time}': expected HH:MM"
+            ))
+        })
+}
+
 #[async_trait]
 impl Constraint for TemporalOrderingConstraint {
     #[instrument(skip(self, ctx), fields(constraint = "temporal_ordering"))]
@@ -504,6 +578,12 @@ impl Constraint for TemporalOrderingConstraint {
 
         // Generate and execute validation query
         let sql = self.generate_validation_query()?;
+        if let TemporalValidationType::BusinessHours {
+            timezone: Some(_), ..
+        } = &self.validation_type
+        {
+            register_timezone_functions(ctx);
+        }
         let df = ctx.sql(&sql).await.map_err(|e| {
             TermError::constraint_evaluation(
                 "temporal_ordering",
@@ -511,7 +591,7 @@ impl Constraint for TemporalOrderingConstraint {
             )
         })?;
 
-        let batches = df.collect().await.map_err(|e| {
+        let batches = collect_with_metrics(df).await.map_err(|e| {
             TermError::constraint_evaluation(
                 "temporal_ordering",
                 format!("Failed to collect temporal validation results: {e}"),
@@ -548,6 +628,10 @@ impl Constraint for TemporalOrderingConstraint {
             })?
             .value(0);
 
+        if total_rows == 0 && is_table_empty(ctx, &self.table_name).await? {
+            return Ok(ConstraintResult::empty_table());
+        }
+
         if violations == 0 {
             debug!("Temporal ordering constraint passed: no violations found");
             return Ok(ConstraintResult::success_with_metric(1.0));
@@ -598,12 +682,23 @@ impl Constraint for TemporalOrderingConstraint {
             status: ConstraintStatus::Failure,
             metric: Some(compliance_rate),
             message: Some(message),
+            quarantine: None,
+            constraint_id: None,
+            details: Default::default(),
         })
     }
 
     fn name(&self) -> &str {
         "temporal_ordering"
     }
+
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Consistency)
+    }
+
+    fn referenced_tables(&self) -> Vec<String> {
+        vec![self.table_name.clone()]
+    }
 }
 
 #[cfg(test)]
@@ -668,6 +763,44 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_business_hours_in_timezone() -> Result<()> {
+        let ctx = create_test_context().await?;
+        ctx.sql(
+            "CREATE TABLE payments (paid_at TIMESTAMP) AS VALUES
+                (TIMESTAMP '2024-07-01 07:30:00'),
+                (TIMESTAMP '2024-07-01 08:00:00'),
+                (TIMESTAMP '2024-07-01 15:30:00')",
+        )
+        .await?
+        .collect()
+        .await?;
+
+        // Berlin is UTC+2 in summer: 09:30, 10:00 and 17:30 local time
+        let local = TemporalOrderingConstraint::new("payments")
+            .business_hours("paid_at", "09:00", "17:00")
+            .weekdays_only(true)
+            .with_timezone("Europe/Berlin")?;
+        let result = local.evaluate(&ctx).await?;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(2.0 / 3.0));
+
+        let stored = TemporalOrderingConstraint::new("payments")
+            .business_hours("paid_at", "09:00", "17:00")
+            .weekdays_only(true);
+        let result = stored.evaluate(&ctx).await?;
+        assert_eq!(result.metric, Some(1.0 / 3.0));
+
+        let err = TemporalOrderingConstraint::new("payments")
+            .business_hours("paid_at", "09:00", "17:00")
+            .with_timezone("Central European Time")
+            .unwrap_err();
+        assert!(matches!(err, TermError::Configuration(_)));
+        assert!(err.to_string().contains("'Europe/Berlin'"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_constraint_configuration() {
         let constraint = TemporalOrderingConstraint::new("transactions")
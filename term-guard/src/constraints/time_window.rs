@@ -0,0 +1,491 @@
+//! Local-time window validation for timestamp columns.
+//!
+//! [`TimeWindowConstraint`] converts each timestamp to a named IANA timezone and
+//! checks whether it falls inside or outside [`TimeWindow`]s defined in local
+//! time, such as a nightly maintenance window or weekends. Conversion happens per
+//! instant with `chrono-tz`, so daylight saving time transitions are handled:
+//! both occurrences of the repeated hour in autumn are inside a window covering
+//! it, and no row can fall into the hour skipped in spring. Timezone-naive
+//! timestamps are interpreted as UTC.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::constraints::{TimeWindow, TimeWindowConstraint};
+//! use term_guard::core::Check;
+//!
+//! # fn example() -> term_guard::prelude::Result<()> {
+//! // No orders during the maintenance window, 02:00-04:00 Berlin time
+//! let maintenance = TimeWindowConstraint::outside(
+//!     "created_at",
+//!     "Europe/Berlin",
+//!     TimeWindow::new("02:00", "04:00")?,
+//! )?;
+//!
+//! // At least 95% of trades on weekdays between 09:00 and 17:30 New York time
+//! let trading_hours = TimeWindowConstraint::inside(
+//!     "executed_at",
+//!     "America/New_York",
+//!     TimeWindow::new("09:00", "17:30")?.weekdays(),
+//! )?
+//! .threshold(0.95);
+//!
+//! let check = Check::builder("local_time_rules")
+//!     .constraint(maintenance)
+//!     .constraint(trading_hours)
+//!     .build();
+//! # Ok(())
+//! # }
+//! ```
+
+use super::timezone::{
+    local_second_of_week_sql, parse_timezone, register_timezone_functions, SECONDS_PER_DAY,
+};
+use crate::core::{
+    collect_with_metrics, current_validation_context, skipped_without_values, Constraint,
+    ConstraintMetadata, ConstraintResult, QualityDimension,
+};
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+use arrow::array::{Array, Int64Array};
+use async_trait::async_trait;
+use chrono::{NaiveTime, Timelike, Weekday};
+use chrono_tz::Tz;
+use datafusion::prelude::*;
+use std::fmt;
+use tracing::{debug, instrument, warn};
+
+/// A recurring window of local time.
+///
+/// The window starts at `start` (inclusive) and ends at `end` (exclusive). An end
+/// before the start wraps past midnight, and equal times cover the whole day. By
+/// default the window applies every day; [`on`](Self::on) restricts it to the
+/// days on which it starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeWindow {
+    start: NaiveTime,
+    end: NaiveTime,
+    days: Vec<Weekday>,
+}
+
+impl TimeWindow {
+    /// Creates a window between two local times in `HH:MM` or `HH:MM:SS` format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if a time cannot be parsed.
+    pub fn new(start: &str, end: &str) -> Result<Self> {
+        Ok(Self {
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+            days: Vec::new(),
+        })
+    }
+
+    /// Creates a window covering whole days, to be restricted with [`on`](Self::on).
+    pub fn all_day() -> Self {
+        Self {
+            start: NaiveTime::MIN,
+            end: NaiveTime::MIN,
+            days: Vec::new(),
+        }
+    }
+
+    /// Restricts the window to the given days of the week.
+    pub fn on(mut self, days: impl IntoIterator<Item = Weekday>) -> Self {
+        self.days = days.into_iter().collect();
+        self.days.sort_by_key(Weekday::num_days_from_monday);
+        self.days.dedup();
+        self
+    }
+
+    /// Restricts the window to Monday through Friday.
+    pub fn weekdays(self) -> Self {
+        self.on([
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ])
+    }
+
+    /// Restricts the window to Saturday and Sunday.
+    pub fn weekends(self) -> Self {
+        self.on([Weekday::Sat, Weekday::Sun])
+    }
+
+    /// Builds the SQL condition matching a local second of the week.
+    fn condition(&self, second_of_week: &str) -> String {
+        let second_of_day = format!("({second_of_week} % {SECONDS_PER_DAY})");
+        let day = format!("({second_of_week} / {SECONDS_PER_DAY})");
+        let start = i64::from(self.start.num_seconds_from_midnight());
+        let end = i64::from(self.end.num_seconds_from_midnight());
+
+        let on_days = |day: &str| {
+            if self.days.is_empty() {
+                "TRUE".to_string()
+            } else {
+                let days: Vec<String> = self
+                    .days
+                    .iter()
+                    .map(|d| d.num_days_from_monday().to_string())
+                    .collect();
+                format!("{day} IN ({})", days.join(", "))
+            }
+        };
+
+        match start.cmp(&end) {
+            std::cmp::Ordering::Equal => format!("({})", on_days(&day)),
+            std::cmp::Ordering::Less => format!(
+                "({second_of_day} >= {start} AND {second_of_day} < {end} AND {})",
+                on_days(&day)
+            ),
+            // After midnight, the window belongs to the day on which it started
+            std::cmp::Ordering::Greater => format!(
+                "(({second_of_day} >= {start} AND {}) OR ({second_of_day} < {end} AND {}))",
+                on_days(&day),
+                on_days(&format!("(({day} + 6) % 7)"))
+            ),
+        }
+    }
+}
+
+impl fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "all day")?;
+        } else {
+            write!(
+                f,
+                "{}-{}",
+                self.start.format("%H:%M:%S"),
+                self.end.format("%H:%M:%S")
+            )?;
+        }
+        if !self.days.is_empty() {
+            let days: Vec<String> = self.days.iter().map(ToString::to_string).collect();
+            write!(f, " on {}", days.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses `HH:MM` or `HH:MM:SS`.
+fn parse_time(value: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M"))
+        .map_err(|_| {
+            TermError::Configuration(format!(
+                "Invalid local time '{value}': expected HH:MM or HH:MM:SS"
+            ))
+        })
+}
+
+/// Whether timestamps must fall inside or outside the windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeWindowRule {
+    /// Timestamps must fall inside at least one window
+    Inside,
+    /// Timestamps must fall outside every window
+    Outside,
+}
+
+/// A constraint on the local time of day and day of week of timestamps.
+///
+/// The metric is the fraction of non-null timestamps that follow the rule; the
+/// constraint passes when it is at least the threshold, 1.0 by default.
+#[derive(Debug, Clone)]
+pub struct TimeWindowConstraint {
+    column: String,
+    timezone: Tz,
+    rule: TimeWindowRule,
+    windows: Vec<TimeWindow>,
+    threshold: f64,
+}
+
+impl TimeWindowConstraint {
+    /// Creates a constraint requiring timestamps to fall inside the window.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if `timezone` is not an IANA timezone
+    /// name such as `Europe/Berlin`.
+    pub fn inside(column: impl Into<String>, timezone: &str, window: TimeWindow) -> Result<Self> {
+        Self::new(column, timezone, TimeWindowRule::Inside, window)
+    }
+
+    /// Creates a constraint requiring timestamps to fall outside the window.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if `timezone` is not an IANA timezone
+    /// name such as `Europe/Berlin`.
+    pub fn outside(column: impl Into<String>, timezone: &str, window: TimeWindow) -> Result<Self> {
+        Self::new(column, timezone, TimeWindowRule::Outside, window)
+    }
+
+    /// Creates a constraint with the given rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if `timezone` is not an IANA timezone
+    /// name such as `Europe/Berlin`.
+    pub fn new(
+        column: impl Into<String>,
+        timezone: &str,
+        rule: TimeWindowRule,
+        window: TimeWindow,
+    ) -> Result<Self> {
+        Ok(Self {
+            column: column.into(),
+            timezone: parse_timezone(timezone)?,
+            rule,
+            windows: vec![window],
+            threshold: 1.0,
+        })
+    }
+
+    /// Adds another window; a timestamp is inside if it is inside any window.
+    pub fn or_window(mut self, window: TimeWindow) -> Self {
+        self.windows.push(window);
+        self
+    }
+
+    /// Sets the minimum fraction of timestamps that must follow the rule.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the threshold is not between 0.0 and 1.0.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&threshold),
+            "Threshold must be between 0.0 and 1.0"
+        );
+        self.threshold = threshold;
+        self
+    }
+
+    fn describe_windows(&self) -> String {
+        self.windows
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" or ")
+    }
+}
+
+#[async_trait]
+impl Constraint for TimeWindowConstraint {
+    #[instrument(skip(self, ctx), fields(
+        column = %self.column,
+        timezone = %self.timezone.name(),
+        rule = ?self.rule
+    ))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+        let column = SqlSecurity::escape_identifier(&self.column)?;
+        register_timezone_functions(ctx);
+
+        let second_of_week = local_second_of_week_sql(&column, self.timezone);
+        let in_window = self
+            .windows
+            .iter()
+            .map(|window| window.condition("local_second_of_week"))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        let sql = format!(
+            "SELECT
+                COUNT(*) AS total_count,
+                COALESCE(SUM(CASE WHEN {in_window} THEN 1 ELSE 0 END), 0) AS inside_count
+             FROM (
+                SELECT {second_of_week} AS local_second_of_week
+                FROM {table_name}
+                WHERE {column} IS NOT NULL
+             ) AS local_times"
+        );
+        debug!("Generated time window query: {}", sql);
+
+        let df = ctx.sql(&sql).await.map_err(|e| {
+            TermError::constraint_evaluation(
+                self.name(),
+                format!("Failed to execute time window query: {e}"),
+            )
+        })?;
+        let batches = collect_with_metrics(df).await?;
+        if batches.is_empty() || batches[0].num_rows() == 0 {
+            return Ok(ConstraintResult::skipped("No data to validate"));
+        }
+
+        let count = |index: usize| -> Result<i64> {
+            Ok(batches[0]
+                .column(index)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .ok_or_else(|| TermError::Internal("Failed to extract count".to_string()))?
+                .value(0))
+        };
+        let total = count(0)?;
+        if total == 0 {
+            return skipped_without_values(ctx, table_name, "No non-null data to validate").await;
+        }
+        let inside = count(1)?;
+
+        let (conforming, violations, placement) = match self.rule {
+            TimeWindowRule::Inside => (inside, total - inside, "outside"),
+            TimeWindowRule::Outside => (total - inside, inside, "inside"),
+        };
+        let ratio = conforming as f64 / total as f64;
+        if ratio >= self.threshold {
+            return Ok(ConstraintResult::success_with_metric(ratio));
+        }
+
+        let message = format!(
+            "{violations} of {total} values in '{}' are {placement} {} ({}), {:.2}% conform, below the threshold of {:.2}%",
+            self.column,
+            self.describe_windows(),
+            self.timezone.name(),
+            ratio * 100.0,
+            self.threshold * 100.0
+        );
+        warn!("{}", message);
+        Ok(ConstraintResult::failure_with_metric(ratio, message))
+    }
+
+    fn name(&self) -> &str {
+        "time_window"
+    }
+
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Validity)
+    }
+
+    fn column(&self) -> Option<&str> {
+        Some(&self.column)
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        let rule = match self.rule {
+            TimeWindowRule::Inside => "inside",
+            TimeWindowRule::Outside => "outside",
+        };
+        ConstraintMetadata::for_column(&self.column)
+            .with_description(format!(
+                "Checks that at least {:.2}% of values in '{}' are {rule} {} ({})",
+                self.threshold * 100.0,
+                self.column,
+                self.describe_windows(),
+                self.timezone.name()
+            ))
+            .with_custom("timezone", self.timezone.name())
+            .with_custom("rule", rule)
+            .with_custom("windows", self.describe_windows())
+            .with_custom("threshold", self.threshold.to_string())
+            .with_custom("constraint_type", "temporal")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ConstraintStatus;
+    use crate::test_helpers::evaluate_constraint_with_context;
+
+    /// Orders stored as naive UTC timestamps around both 2024 DST transitions in Berlin.
+    async fn orders_context() -> SessionContext {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE TABLE orders (created_at TIMESTAMP) AS VALUES
+                (TIMESTAMP '2024-03-31 00:30:00'),
+                (TIMESTAMP '2024-03-31 01:30:00'),
+                (TIMESTAMP '2024-10-27 00:30:00'),
+                (TIMESTAMP '2024-10-27 01:30:00'),
+                (TIMESTAMP '2024-10-28 12:00:00'),
+                (NULL)",
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+        ctx
+    }
+
+    async fn evaluate(ctx: &SessionContext, constraint: &TimeWindowConstraint) -> ConstraintResult {
+        evaluate_constraint_with_context(constraint, ctx, "orders")
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_invalid_configuration() {
+        let window = TimeWindow::new("02:00", "04:00").unwrap();
+        let err = TimeWindowConstraint::outside("created_at", "Berlin", window).unwrap_err();
+        assert!(matches!(err, TermError::Configuration(_)));
+        assert!(err.to_string().contains("such as 'Europe/Berlin'"));
+
+        let err = TimeWindow::new("2am", "04:00").unwrap_err();
+        assert!(err.to_string().contains("expected HH:MM or HH:MM:SS"));
+        assert!(TimeWindow::new("02:00:30", "25:00").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_window_across_dst() {
+        let ctx = orders_context().await;
+        let constraint = TimeWindowConstraint::outside(
+            "created_at",
+            "Europe/Berlin",
+            TimeWindow::new("02:00", "04:00").unwrap(),
+        )
+        .unwrap();
+
+        // Local times: 01:30 CET, 03:30 CEST, 02:30 CEST, 02:30 CET, 13:00 CET
+        let result = evaluate(&ctx, &constraint).await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(2.0 / 5.0));
+        let message = result.message.unwrap();
+        assert!(message.contains("3 of 5 values in 'created_at' are inside 02:00:00-04:00:00"));
+        assert!(message.contains("Europe/Berlin"));
+
+        // In UTC every order is outside the window
+        let utc = TimeWindowConstraint::outside(
+            "created_at",
+            "UTC",
+            TimeWindow::new("02:00", "04:00").unwrap(),
+        )
+        .unwrap();
+        let result = evaluate(&ctx, &utc).await;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_days_of_week_and_midnight_wrap() {
+        let ctx = orders_context().await;
+
+        // Sunday 2024-03-31 and 2024-10-27 and Monday 2024-10-28
+        let weekends = TimeWindowConstraint::inside(
+            "created_at",
+            "Europe/Berlin",
+            TimeWindow::all_day().weekends(),
+        )
+        .unwrap();
+        assert_eq!(evaluate(&ctx, &weekends).await.metric, Some(4.0 / 5.0));
+
+        // 22:00-03:00 starting on Saturdays covers the early Sunday hours
+        let saturday_night = TimeWindowConstraint::inside(
+            "created_at",
+            "Europe/Berlin",
+            TimeWindow::new("22:00", "03:00")
+                .unwrap()
+                .on([Weekday::Sat]),
+        )
+        .unwrap()
+        .or_window(TimeWindow::new("12:00", "14:00").unwrap().weekdays());
+        let result = evaluate(&ctx, &saturday_night).await;
+        // 01:30 CET, both 02:30 and the Monday 13:00 order; 03:30 CEST is outside
+        assert_eq!(result.metric, Some(4.0 / 5.0));
+        assert_eq!(result.status, ConstraintStatus::Failure);
+    }
+}
@@ -0,0 +1,192 @@
+//! Timezone conversion shared by the temporal constraints.
+//!
+//! Timestamps are converted from the instant they represent to local time with
+//! `chrono-tz`, inside UDFs registered on the session context. Converting an
+//! instant is always unambiguous, so rows in the hour repeated when daylight
+//! saving time ends, or next to the hour skipped when it starts, get their
+//! actual local time. Timezone-naive timestamps are interpreted as UTC.
+
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, Float64Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use chrono_tz::Tz;
+use datafusion::error::DataFusionError;
+use datafusion::logical_expr::{create_udf, ColumnarValue, ScalarUDF, Volatility};
+use datafusion::prelude::SessionContext;
+
+use crate::error::{Result, TermError};
+
+/// UDF returning the local second of the week (Monday 00:00 is 0) of an epoch.
+pub(crate) const LOCAL_SECOND_OF_WEEK_UDF_NAME: &str = "term_local_second_of_week";
+
+/// UDF formatting an epoch as an RFC 3339 local timestamp.
+pub(crate) const LOCAL_TIMESTAMP_UDF_NAME: &str = "term_local_timestamp";
+
+/// Seconds in a day, the unit of local days in the second of the week.
+pub(crate) const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Parses an IANA timezone name such as `Europe/Berlin`.
+pub(crate) fn parse_timezone(name: &str) -> Result<Tz> {
+    name.parse::<Tz>().map_err(|_| {
+        TermError::Configuration(format!(
+            "Unknown timezone '{name}': expected an IANA timezone name such as 'Europe/Berlin'"
+        ))
+    })
+}
+
+/// Returns the SQL expression for the local second of the week of `column`.
+///
+/// `column` must be an escaped identifier and `timezone` a parsed timezone.
+pub(crate) fn local_second_of_week_sql(column: &str, timezone: Tz) -> String {
+    format!(
+        "{LOCAL_SECOND_OF_WEEK_UDF_NAME}(date_part('epoch', {column}), '{}')",
+        timezone.name()
+    )
+}
+
+/// Returns the SQL expression formatting `column` as a local timestamp.
+pub(crate) fn local_timestamp_sql(column: &str, timezone: Tz) -> String {
+    format!(
+        "{LOCAL_TIMESTAMP_UDF_NAME}(date_part('epoch', {column}), '{}')",
+        timezone.name()
+    )
+}
+
+/// Registers the UDFs used by the SQL expressions of this module.
+pub(crate) fn register_timezone_functions(ctx: &SessionContext) {
+    ctx.register_udf(local_second_of_week_udf());
+    ctx.register_udf(local_timestamp_udf());
+}
+
+/// Converts epoch seconds to local times in the timezones of the second argument.
+fn to_local<T>(
+    args: &[ColumnarValue],
+    convert: impl Fn(DateTime<Tz>) -> T,
+) -> datafusion::error::Result<Vec<Option<T>>> {
+    let arrays = ColumnarValue::values_to_arrays(args)?;
+    let epochs = arrays[0]
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .expect("Float64 argument");
+    let zones = cast(&arrays[1], &DataType::Utf8)?;
+    let zones = zones
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .expect("cast to Utf8");
+
+    // The timezone is a literal in practice, so parse each distinct run once
+    let mut current: Option<(&str, Tz)> = None;
+    let mut values = Vec::with_capacity(epochs.len());
+    for i in 0..epochs.len() {
+        if epochs.is_null(i) || zones.is_null(i) {
+            values.push(None);
+            continue;
+        }
+        let name = zones.value(i);
+        let timezone = match current {
+            Some((current_name, timezone)) if current_name == name => timezone,
+            _ => {
+                let timezone =
+                    parse_timezone(name).map_err(|e| DataFusionError::Execution(e.to_string()))?;
+                current = Some((name, timezone));
+                timezone
+            }
+        };
+        let epoch = epochs.value(i);
+        let seconds = epoch.floor();
+        let nanos = ((epoch - seconds) * 1e9) as u32;
+        values.push(
+            DateTime::<Utc>::from_timestamp(seconds as i64, nanos)
+                .map(|utc| convert(utc.with_timezone(&timezone))),
+        );
+    }
+    Ok(values)
+}
+
+fn local_second_of_week_udf() -> ScalarUDF {
+    create_udf(
+        LOCAL_SECOND_OF_WEEK_UDF_NAME,
+        vec![DataType::Float64, DataType::Utf8],
+        DataType::Int64,
+        Volatility::Immutable,
+        Arc::new(|args: &[ColumnarValue]| {
+            let values = to_local(args, |local| {
+                i64::from(local.weekday().num_days_from_monday()) * SECONDS_PER_DAY
+                    + i64::from(local.num_seconds_from_midnight())
+            })?;
+            Ok(ColumnarValue::Array(
+                Arc::new(Int64Array::from(values)) as ArrayRef
+            ))
+        }),
+    )
+}
+
+fn local_timestamp_udf() -> ScalarUDF {
+    create_udf(
+        LOCAL_TIMESTAMP_UDF_NAME,
+        vec![DataType::Float64, DataType::Utf8],
+        DataType::Utf8,
+        Volatility::Immutable,
+        Arc::new(|args: &[ColumnarValue]| {
+            let values = to_local(args, |local| {
+                local.format("%Y-%m-%dT%H:%M:%S%:z").to_string()
+            })?;
+            Ok(ColumnarValue::Array(
+                Arc::new(StringArray::from(values)) as ArrayRef
+            ))
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!(parse_timezone("Europe/Berlin").unwrap(), Tz::Europe__Berlin);
+
+        let message = parse_timezone("Europe/Atlantis").unwrap_err().to_string();
+        assert!(message.contains("Unknown timezone 'Europe/Atlantis'"));
+        assert!(message.contains("'Europe/Berlin'"));
+        assert!(parse_timezone("CEST").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_conversion_across_dst_transitions() {
+        let ctx = SessionContext::new();
+        register_timezone_functions(&ctx);
+
+        // 2024-10-27 00:30 and 01:30 UTC are both 02:30 in Berlin (CEST, then CET),
+        // 2024-03-31 01:30 UTC is 03:30 CEST right after the skipped hour
+        let sql = format!(
+            "SELECT {}, {} FROM (VALUES
+                (TIMESTAMP '2024-10-27 00:30:00'),
+                (TIMESTAMP '2024-10-27 01:30:00'),
+                (TIMESTAMP '2024-03-31 01:30:00')
+             ) AS t(ts)",
+            local_second_of_week_sql("ts", Tz::Europe__Berlin),
+            local_timestamp_sql("ts", Tz::Europe__Berlin),
+        );
+        let batches = ctx.sql(&sql).await.unwrap().collect().await.unwrap();
+        let seconds = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        let formatted = cast(batches[0].column(1), &DataType::Utf8).unwrap();
+        let formatted = formatted.as_any().downcast_ref::<StringArray>().unwrap();
+
+        // Both dates are Sundays
+        let sunday = 6 * SECONDS_PER_DAY;
+        assert_eq!(seconds.value(0), sunday + 2 * 3600 + 1800);
+        assert_eq!(seconds.value(1), sunday + 2 * 3600 + 1800);
+        assert_eq!(seconds.value(2), sunday + 3 * 3600 + 1800);
+        assert_eq!(formatted.value(0), "2024-10-27T02:30:00+02:00");
+        assert_eq!(formatted.value(1), "2024-10-27T02:30:00+01:00");
+        assert_eq!(formatted.value(2), "2024-03-31T03:30:00+02:00");
+    }
+}
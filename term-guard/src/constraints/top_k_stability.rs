@@ -0,0 +1,705 @@
+//! Stability of the most frequent values of a categorical column.
+//!
+//! A distribution test can pass while the categories that matter most change:
+//! a new category entering the top 20 barely moves a chi-squared statistic over
+//! thousands of categories. [`TopKStabilityConstraint`] instead computes the K
+//! most frequent values of a column and compares them with the top K of a
+//! previous run, failing when the similarity of the two lists drops below a
+//! threshold.
+//!
+//! The previous top K is either provided directly or loaded from a
+//! [`MetricsRepository`], where the constraint saves the current top K for the
+//! next run. Values are ranked by frequency, with ties broken by value in
+//! ascending order, so the same data always yields the same list. Messages list
+//! both lists with their frequencies.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use term_guard::constraints::{TopKSimilarity, TopKStabilityConstraint};
+//! use term_guard::core::{Check, Level};
+//! use term_guard::repository::InMemoryRepository;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let repository = Arc::new(InMemoryRepository::new());
+//!
+//! let check = Check::builder("recommendations")
+//!     .level(Level::Warning)
+//!     // At least 80% of the top 20 categories are shared with the previous run
+//!     .has_stable_top_k("category", 20, 0.8, repository.clone())
+//!     .with_constraint(
+//!         TopKStabilityConstraint::from_repository("brand", 10, 0.9, repository)?
+//!             .similarity(TopKSimilarity::RankWeightedOverlap)
+//!             .with_tag("dataset", "products"),
+//!     )
+//!     .build();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::array::{Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::analyzers::{AnalyzerContext, MetricValue};
+use crate::core::{
+    collect_limited, current_validation_context, skipped_without_values, stamp_suite_version,
+    CollectionSummary, Constraint, ConstraintMetadata, ConstraintResult, IntoThreshold,
+    ValidationContext,
+};
+use crate::error::{Result, TermError};
+use crate::repository::{MetricsRepository, ResultKey, SortOrder};
+use crate::security::SqlSecurity;
+
+/// How the current top K is compared with the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TopKSimilarity {
+    /// The size of the intersection of the two sets divided by the size of
+    /// their union; the order within the top K is ignored
+    #[default]
+    Jaccard,
+    /// The mean, over every depth d up to K, of the fraction of the first d
+    /// values the two lists share; changes near the top weigh more
+    RankWeightedOverlap,
+}
+
+impl TopKSimilarity {
+    /// Returns the similarity of two ranked lists of values, between 0 and 1.
+    ///
+    /// Two empty lists are identical.
+    pub fn compute(&self, current: &[String], previous: &[String]) -> f64 {
+        match self {
+            TopKSimilarity::Jaccard => {
+                let current: BTreeSet<&String> = current.iter().collect();
+                let previous: BTreeSet<&String> = previous.iter().collect();
+                let union = current.union(&previous).count();
+                if union == 0 {
+                    return 1.0;
+                }
+                current.intersection(&previous).count() as f64 / union as f64
+            }
+            TopKSimilarity::RankWeightedOverlap => {
+                let depth = current.len().max(previous.len());
+                if depth == 0 {
+                    return 1.0;
+                }
+                let mut seen_current = BTreeSet::new();
+                let mut seen_previous = BTreeSet::new();
+                let mut total = 0.0;
+                for d in 0..depth {
+                    if let Some(value) = current.get(d) {
+                        seen_current.insert(value);
+                    }
+                    if let Some(value) = previous.get(d) {
+                        seen_previous.insert(value);
+                    }
+                    let shared = seen_current.intersection(&seen_previous).count();
+                    total += shared as f64 / (d + 1) as f64;
+                }
+                total / depth as f64
+            }
+        }
+    }
+}
+
+impl fmt::Display for TopKSimilarity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TopKSimilarity::Jaccard => write!(f, "Jaccard similarity"),
+            TopKSimilarity::RankWeightedOverlap => write!(f, "rank-weighted overlap"),
+        }
+    }
+}
+
+/// A value of the top K with its number of rows, if known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RankedValue {
+    value: String,
+    count: Option<i64>,
+}
+
+impl fmt::Display for RankedValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.count {
+            Some(count) => write!(f, "{} ({count})", self.value),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+/// Where the previous top K comes from.
+#[derive(Clone)]
+enum Baseline {
+    Values(Vec<String>),
+    Repository {
+        repository: Arc<dyn MetricsRepository>,
+        metric_key: String,
+        tags: HashMap<String, String>,
+        save_top_k: bool,
+    },
+}
+
+/// A constraint failing when the most frequent values of a column change.
+///
+/// NULL values are ignored and values are compared as strings. The metric of
+/// the result is the similarity. The constraint is skipped when the repository
+/// holds no previous top K; the current one is still saved, so the next run
+/// has a baseline.
+#[derive(Clone)]
+pub struct TopKStabilityConstraint {
+    column: String,
+    k: usize,
+    min_similarity: f64,
+    similarity: TopKSimilarity,
+    baseline: Baseline,
+}
+
+impl TopKStabilityConstraint {
+    /// Creates a constraint comparing the top `k` values of `column` with
+    /// `previous`, listed from most to least frequent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if `k` is 0 or `min_similarity` is
+    /// not between 0.0 and 1.0.
+    pub fn new<I, S>(
+        column: impl Into<String>,
+        k: usize,
+        min_similarity: impl IntoThreshold,
+        previous: I,
+    ) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::with_baseline(
+            column.into(),
+            k,
+            min_similarity,
+            Baseline::Values(previous.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// Creates a constraint comparing the top `k` values of `column` with the
+    /// most recent top K saved in `repository`.
+    ///
+    /// The top K is stored under the metric key `top_k.<column>` unless set with
+    /// [`metric_key`](Self::metric_key), and the current top K is saved after
+    /// every evaluation unless disabled with [`save_top_k`](Self::save_top_k).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if `k` is 0 or `min_similarity` is
+    /// not between 0.0 and 1.0.
+    pub fn from_repository(
+        column: impl Into<String>,
+        k: usize,
+        min_similarity: impl IntoThreshold,
+        repository: Arc<dyn MetricsRepository>,
+    ) -> Result<Self> {
+        let column = column.into();
+        let metric_key = format!("top_k.{column}");
+        Self::with_baseline(
+            column,
+            k,
+            min_similarity,
+            Baseline::Repository {
+                repository,
+                metric_key,
+                tags: HashMap::new(),
+                save_top_k: true,
+            },
+        )
+    }
+
+    fn with_baseline(
+        column: String,
+        k: usize,
+        min_similarity: impl IntoThreshold,
+        baseline: Baseline,
+    ) -> Result<Self> {
+        if k == 0 {
+            return Err(TermError::Configuration(
+                "The number of top values must be greater than 0".to_string(),
+            ));
+        }
+        Ok(Self {
+            column,
+            k,
+            min_similarity: min_similarity.into_threshold()?.value(),
+            similarity: TopKSimilarity::default(),
+            baseline,
+        })
+    }
+
+    /// Sets how the two lists are compared (default: Jaccard similarity).
+    pub fn similarity(mut self, similarity: TopKSimilarity) -> Self {
+        self.similarity = similarity;
+        self
+    }
+
+    /// Sets the metric key the top K is stored under in the repository.
+    ///
+    /// Has no effect when the previous top K is provided directly.
+    pub fn metric_key(mut self, key: impl Into<String>) -> Self {
+        if let Baseline::Repository { metric_key, .. } = &mut self.baseline {
+            *metric_key = key.into();
+        }
+        self
+    }
+
+    /// Adds a tag the previous top K must match and the current one is saved with.
+    ///
+    /// Has no effect when the previous top K is provided directly.
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        if let Baseline::Repository { tags, .. } = &mut self.baseline {
+            tags.insert(key.into(), value.into());
+        }
+        self
+    }
+
+    /// Sets whether the current top K is saved to the repository (default `true`).
+    pub fn save_top_k(mut self, save: bool) -> Self {
+        if let Baseline::Repository { save_top_k, .. } = &mut self.baseline {
+            *save_top_k = save;
+        }
+        self
+    }
+
+    /// Queries the `k` most frequent non-null values of the column, fewer when
+    /// they exceed the collection limits of the run.
+    async fn top_k(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+    ) -> Result<(Vec<RankedValue>, CollectionSummary)> {
+        let column = SqlSecurity::escape_identifier(&self.column)?;
+        let k = self.k;
+        // Ordering by value as well keeps ties at the cutoff deterministic
+        let sql = format!(
+            "SELECT CAST({column} AS VARCHAR) AS value, COUNT(*) AS value_count
+             FROM {table_name}
+             WHERE {column} IS NOT NULL
+             GROUP BY {column}
+             ORDER BY value_count DESC, value ASC
+             LIMIT {k}"
+        );
+        debug!("Generated top-k query: {}", sql);
+
+        let df = ctx.sql(&sql).await?;
+        let collected = collect_limited(df).await?;
+
+        let mut values = Vec::new();
+        for batch in collected.iter()? {
+            let batch = batch?;
+            // `CAST(... AS VARCHAR)` may produce `Utf8View`
+            let keys = cast(batch.column(0), &DataType::Utf8)?;
+            let keys = keys
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .ok_or_else(|| TermError::Internal("Failed to extract values".to_string()))?;
+            let counts = batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .ok_or_else(|| TermError::Internal("Failed to extract counts".to_string()))?;
+            for i in 0..batch.num_rows() {
+                values.push(RankedValue {
+                    value: keys.value(i).to_string(),
+                    count: Some(counts.value(i)),
+                });
+            }
+        }
+        Ok((values, collected.summary()))
+    }
+
+    /// Loads the previous top K, `None` when the repository holds none.
+    async fn previous(&self) -> Result<Option<Vec<RankedValue>>> {
+        let (repository, metric_key, tags) = match &self.baseline {
+            Baseline::Values(values) => {
+                return Ok(Some(
+                    values
+                        .iter()
+                        .map(|value| RankedValue {
+                            value: value.clone(),
+                            count: None,
+                        })
+                        .collect(),
+                ))
+            }
+            Baseline::Repository {
+                repository,
+                metric_key,
+                tags,
+                ..
+            } => (repository, metric_key, tags),
+        };
+
+        let results = repository
+            .load()
+            .await
+            .with_tags(tags.clone())
+            .sort(SortOrder::Descending)
+            .execute()
+            .await?;
+
+        // Results saved by other metrics under the same tags do not hold the top K
+        results
+            .iter()
+            .find_map(|(_, context)| context.get_metric(metric_key))
+            .map(|metric| self.read_metric(metric_key, metric))
+            .transpose()
+    }
+
+    /// Reads a top K saved by [`save`](Self::save), ranked like the query ranks it.
+    fn read_metric(&self, metric_key: &str, metric: &MetricValue) -> Result<Vec<RankedValue>> {
+        let MetricValue::Map(counts) = metric else {
+            return Err(TermError::constraint_evaluation(
+                "top_k_stability",
+                format!("Metric '{metric_key}' is not a map of value frequencies"),
+            ));
+        };
+        let mut values = counts
+            .iter()
+            .map(|(value, count)| {
+                let count = count.as_f64().ok_or_else(|| {
+                    TermError::constraint_evaluation(
+                        "top_k_stability",
+                        format!("Frequency of '{value}' in metric '{metric_key}' is not numeric"),
+                    )
+                })?;
+                Ok(RankedValue {
+                    value: value.clone(),
+                    count: Some(count as i64),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+        Ok(values)
+    }
+
+    async fn save(&self, current: &[RankedValue]) -> Result<()> {
+        let Baseline::Repository {
+            repository,
+            metric_key,
+            tags,
+            save_top_k: true,
+        } = &self.baseline
+        else {
+            return Ok(());
+        };
+        let counts = current
+            .iter()
+            .map(|ranked| {
+                (
+                    ranked.value.clone(),
+                    MetricValue::Long(ranked.count.unwrap_or_default()),
+                )
+            })
+            .collect();
+        let mut context = AnalyzerContext::new();
+        context.store_metric(metric_key, MetricValue::Map(counts));
+        repository
+            .save(
+                stamp_suite_version(ResultKey::now().with_tags(tags.clone())),
+                context,
+            )
+            .await
+    }
+}
+
+/// Formats ranked values of `column` as `[a (3), b (2)]`, redacting the values
+/// as configured in `validation_ctx`.
+fn format_ranked(
+    validation_ctx: &ValidationContext,
+    column: &str,
+    values: &[RankedValue],
+) -> String {
+    let values: Vec<String> = values
+        .iter()
+        .map(|ranked| {
+            RankedValue {
+                value: validation_ctx.redact(column, &ranked.value).into_owned(),
+                count: ranked.count,
+            }
+            .to_string()
+        })
+        .collect();
+    format!("[{}]", values.join(", "))
+}
+
+fn names(values: &[RankedValue]) -> Vec<String> {
+    values.iter().map(|ranked| ranked.value.clone()).collect()
+}
+
+impl fmt::Debug for TopKStabilityConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("TopKStabilityConstraint");
+        debug
+            .field("column", &self.column)
+            .field("k", &self.k)
+            .field("min_similarity", &self.min_similarity)
+            .field("similarity", &self.similarity);
+        match &self.baseline {
+            Baseline::Values(values) => debug.field("previous", values),
+            Baseline::Repository {
+                metric_key,
+                tags,
+                save_top_k,
+                ..
+            } => debug
+                .field("metric_key", metric_key)
+                // Sorted so the output, and with it the constraint identity, is stable
+                .field("tags", &tags.iter().collect::<BTreeMap<_, _>>())
+                .field("save_top_k", save_top_k),
+        };
+        debug.finish()
+    }
+}
+
+#[async_trait]
+impl Constraint for TopKStabilityConstraint {
+    #[instrument(skip(self, ctx), fields(constraint = "top_k_stability", column = %self.column, k = self.k))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+
+        let (current, collection) = self.top_k(ctx, table_name).await?;
+        if current.is_empty() {
+            let message = format!("Column '{}' has no non-null values", self.column);
+            return skipped_without_values(ctx, table_name, &message).await;
+        }
+
+        let previous = self.previous().await?;
+        // A top K cut off by the collection limits must not become the next baseline
+        if !collection.truncated {
+            self.save(&current).await?;
+        }
+        let Some(previous) = previous else {
+            let action = if collection.truncated {
+                "truncated by the collection limits, not recorded:"
+            } else {
+                "recorded"
+            };
+            let mut result = ConstraintResult::skipped(format!(
+                "No previous top {} values found for '{}'; {action} {}",
+                self.k,
+                self.column,
+                format_ranked(&validation_ctx, &self.column, &current)
+            ));
+            collection.record(&mut result.details);
+            return Ok(result);
+        };
+
+        let similarity = self.similarity.compute(&names(&current), &names(&previous));
+        let passed = similarity >= self.min_similarity;
+        debug!(
+            similarity,
+            min_similarity = self.min_similarity,
+            "Compared top-k values"
+        );
+
+        let message = format!(
+            "Top {} values of '{}' have {} {similarity:.3} with the previous top {}, {} minimum {}. Current: {}; previous: {}",
+            self.k,
+            self.column,
+            self.similarity,
+            self.k,
+            if passed { "meeting the" } else { "below the" },
+            self.min_similarity,
+            format_ranked(&validation_ctx, &self.column, &current),
+            format_ranked(&validation_ctx, &self.column, &previous)
+        );
+        let mut result = if passed {
+            ConstraintResult {
+                message: Some(message),
+                ..ConstraintResult::success_with_metric(similarity)
+            }
+        } else {
+            ConstraintResult::failure_with_metric(similarity, message)
+        };
+        collection.record(&mut result.details);
+        Ok(result)
+    }
+
+    fn name(&self) -> &str {
+        "top_k_stability"
+    }
+
+    fn column(&self) -> Option<&str> {
+        Some(&self.column)
+    }
+
+    // The top K of a sample is not comparable with that of a full run
+    fn requires_full_data(&self) -> bool {
+        true
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        ConstraintMetadata::for_column(&self.column)
+            .with_description(format!(
+                "Checks that the top {} values of '{}' have a {} of at least {} with the previous top {}",
+                self.k, self.column, self.similarity, self.min_similarity, self.k
+            ))
+            .with_custom("constraint_type", "categorical")
+            .with_custom("k", self.k.to_string())
+            .with_custom("min_similarity", self.min_similarity.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ConstraintStatus;
+    use crate::repository::InMemoryRepository;
+    use crate::test_helpers::evaluate_constraint_with_context;
+
+    /// A table with the given category counts.
+    async fn context(counts: &[(&str, usize)]) -> SessionContext {
+        let values = counts
+            .iter()
+            .flat_map(|(category, count)| std::iter::repeat(format!("('{category}')")).take(*count))
+            .chain(std::iter::once("(NULL)".to_string()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let ctx = SessionContext::new();
+        ctx.sql(&format!(
+            "CREATE TABLE data (category VARCHAR) AS VALUES {values}"
+        ))
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+        ctx
+    }
+
+    fn list(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_similarity_measures() {
+        let current = list(&["a", "b", "c", "d"]);
+        let reordered = list(&["d", "c", "b", "a"]);
+        let replaced = list(&["a", "b", "c", "e"]);
+
+        assert_eq!(TopKSimilarity::Jaccard.compute(&current, &reordered), 1.0);
+        assert_eq!(TopKSimilarity::Jaccard.compute(&current, &replaced), 0.6);
+        assert_eq!(TopKSimilarity::Jaccard.compute(&[], &[]), 1.0);
+
+        let rwo = TopKSimilarity::RankWeightedOverlap;
+        assert_eq!(rwo.compute(&current, &current), 1.0);
+        // Depths 1..4 share 0, 0, 2/3 and 4/4 of their values
+        assert!((rwo.compute(&current, &reordered) - (2.0 / 3.0 + 1.0) / 4.0).abs() < 1e-12);
+        // A change at the bottom costs less than the same change at the top
+        assert!(
+            rwo.compute(&current, &replaced) > rwo.compute(&current, &list(&["e", "b", "c", "d"]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_explicit_previous_top_k() {
+        let ctx = context(&[("books", 5), ("toys", 4), ("games", 3), ("music", 1)]).await;
+
+        let constraint =
+            TopKStabilityConstraint::new("category", 3, 0.5, ["books", "toys", "music"]).unwrap();
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.5));
+
+        let constraint =
+            TopKStabilityConstraint::new("category", 3, 0.9, ["books", "toys", "music"])
+                .unwrap()
+                .similarity(TopKSimilarity::RankWeightedOverlap);
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        let message = result.message.unwrap();
+        assert!(
+            message.contains(
+                "Current: [books (5), toys (4), games (3)]; previous: [books, toys, music]"
+            ),
+            "{message}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ties_are_broken_by_value() {
+        let ctx = context(&[("b", 2), ("c", 2), ("a", 2), ("d", 1)]).await;
+        let constraint = TopKStabilityConstraint::new("category", 2, 1.0, ["a", "b"]).unwrap();
+
+        let (top_k, collection) = constraint.top_k(&ctx, "data").await.unwrap();
+        assert!(!collection.truncated);
+        assert_eq!(names(&top_k), list(&["a", "b"]));
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+    }
+
+    #[tokio::test]
+    async fn test_previous_top_k_from_repository() {
+        let repository = Arc::new(InMemoryRepository::new());
+        let constraint =
+            TopKStabilityConstraint::from_repository("category", 3, 0.8, repository.clone())
+                .unwrap()
+                .with_tag("dataset", "products");
+
+        // The first run has nothing to compare with and records its top K
+        let ctx = context(&[("books", 5), ("toys", 4), ("games", 3), ("music", 1)]).await;
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Skipped);
+
+        // Results are keyed by timestamp, so keep runs apart
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let ctx = context(&[("music", 9), ("books", 5), ("garden", 4), ("toys", 1)]).await;
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(0.2));
+        let message = result.message.unwrap();
+        assert!(
+            message.contains(
+                "Current: [music (9), books (5), garden (4)]; previous: [books (5), toys (4), games (3)]"
+            ),
+            "{message}"
+        );
+
+        // Each run compares with the one before it
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(repository.list_keys().await.unwrap().len(), 3);
+
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let unsaved = constraint.save_top_k(false);
+        evaluate_constraint_with_context(&unsaved, &ctx, "data")
+            .await
+            .unwrap();
+        assert_eq!(repository.list_keys().await.unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_invalid_configuration() {
+        assert!(TopKStabilityConstraint::new("category", 0, 0.8, ["a"]).is_err());
+        assert!(TopKStabilityConstraint::new("category", 3, 80.0, ["a"]).is_err());
+    }
+}
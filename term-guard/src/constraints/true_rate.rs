@@ -0,0 +1,440 @@
+//! True-rate validation of boolean columns.
+//!
+//! [`TrueRateConstraint`] computes the fraction of true values among the
+//! non-null values of a column and checks it against an [`Assertion`], for
+//! rules such as "at least 95% of rows have `is_verified = true`". Boolean
+//! columns are read directly; string columns holding `"true"`/`"false"` or
+//! `"1"`/`"0"` are parsed according to a [`BooleanParsing`] mode.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::constraints::{Assertion, BooleanParsing, TrueRateConstraint};
+//! use term_guard::core::{Check, Level};
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let check = Check::builder("accounts")
+//!     .level(Level::Error)
+//!     .has_true_rate("is_verified", Assertion::GreaterThanOrEqual(0.95))
+//!     .is_always_false("is_deleted")
+//!     .with_constraint(
+//!         // A string column where missing flags count as not opted in
+//!         TrueRateConstraint::new("marketing_opt_in", Assertion::LessThan(0.5))?
+//!             .nulls_as_false(true)
+//!             .parsing(BooleanParsing::Lenient),
+//!     )
+//!     .build();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use arrow::array::{Array, Int64Array};
+use arrow::datatypes::DataType;
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::constraints::Assertion;
+use crate::core::{
+    collect_with_metrics, current_validation_context, skipped_without_values, Constraint,
+    ConstraintMetadata, ConstraintResult,
+};
+use crate::error::{Result, TermError};
+use crate::security::SqlSecurity;
+
+/// Strings read as true by every parsing mode.
+const STRICT_TRUE: &[&str] = &["true", "1"];
+/// Strings read as false by every parsing mode.
+const STRICT_FALSE: &[&str] = &["false", "0"];
+/// Strings additionally read as true in lenient mode.
+const LENIENT_TRUE: &[&str] = &["t", "yes", "y"];
+/// Strings additionally read as false in lenient mode.
+const LENIENT_FALSE: &[&str] = &["f", "no", "n"];
+
+/// How the values of a string column are read as booleans.
+///
+/// Values are trimmed and compared case-insensitively in both modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BooleanParsing {
+    /// Only `true`, `false`, `1` and `0` are accepted; any other value fails the
+    /// constraint
+    #[default]
+    Strict,
+    /// `t`, `f`, `yes`, `no`, `y` and `n` are accepted as well; any other value
+    /// is treated as null
+    Lenient,
+}
+
+impl BooleanParsing {
+    fn true_values(&self) -> Vec<&'static str> {
+        match self {
+            BooleanParsing::Strict => STRICT_TRUE.to_vec(),
+            BooleanParsing::Lenient => [STRICT_TRUE, LENIENT_TRUE].concat(),
+        }
+    }
+
+    fn false_values(&self) -> Vec<&'static str> {
+        match self {
+            BooleanParsing::Strict => STRICT_FALSE.to_vec(),
+            BooleanParsing::Lenient => [STRICT_FALSE, LENIENT_FALSE].concat(),
+        }
+    }
+}
+
+impl fmt::Display for BooleanParsing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BooleanParsing::Strict => write!(f, "strict"),
+            BooleanParsing::Lenient => write!(f, "lenient"),
+        }
+    }
+}
+
+/// Numbers of true, false and null values of a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TruthCounts {
+    true_count: i64,
+    false_count: i64,
+    null_count: i64,
+    /// Non-null strings that are neither true nor false
+    unparsed_count: i64,
+}
+
+impl fmt::Display for TruthCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "true: {}, false: {}, null: {}",
+            self.true_count, self.false_count, self.null_count
+        )?;
+        if self.unparsed_count > 0 {
+            write!(f, ", unparsed: {}", self.unparsed_count)?;
+        }
+        Ok(())
+    }
+}
+
+/// A constraint checking the fraction of true values in a column.
+///
+/// The rate is the number of true values divided by the number of true and
+/// false values, so nulls are ignored unless
+/// [`nulls_as_false`](Self::nulls_as_false) is set. The metric of the result is
+/// the rate, and messages list the true, false and null counts. The constraint
+/// is skipped when there are no values to rate.
+#[derive(Debug, Clone)]
+pub struct TrueRateConstraint {
+    column: String,
+    assertion: Assertion,
+    nulls_as_false: bool,
+    parsing: BooleanParsing,
+}
+
+impl TrueRateConstraint {
+    /// Creates a constraint checking the true rate of `column` against `assertion`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the column name is invalid
+    pub fn new(column: impl Into<String>, assertion: Assertion) -> Result<Self> {
+        let column = column.into();
+        SqlSecurity::validate_identifier(&column)?;
+        Ok(Self {
+            column,
+            assertion,
+            nulls_as_false: false,
+            parsing: BooleanParsing::default(),
+        })
+    }
+
+    /// Creates a constraint requiring every non-null value of `column` to be true.
+    pub fn always_true(column: impl Into<String>) -> Result<Self> {
+        Self::new(column, Assertion::Equals(1.0))
+    }
+
+    /// Creates a constraint requiring every non-null value of `column` to be false.
+    pub fn always_false(column: impl Into<String>) -> Result<Self> {
+        Self::new(column, Assertion::Equals(0.0))
+    }
+
+    /// Sets whether null values count as false (default `false`, nulls are ignored).
+    pub fn nulls_as_false(mut self, nulls_as_false: bool) -> Self {
+        self.nulls_as_false = nulls_as_false;
+        self
+    }
+
+    /// Sets how the values of a string column are read (default [`BooleanParsing::Strict`]).
+    ///
+    /// Has no effect on boolean columns.
+    pub fn parsing(mut self, parsing: BooleanParsing) -> Self {
+        self.parsing = parsing;
+        self
+    }
+
+    /// Builds the expressions counting true and false values for a column type.
+    fn truth_expressions(&self, column: &str, data_type: &DataType) -> Result<(String, String)> {
+        match data_type {
+            DataType::Boolean => Ok((column.to_string(), format!("NOT {column}"))),
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+                let normalized = format!("lower(trim({column}))");
+                let list = |values: Vec<&str>| {
+                    values
+                        .iter()
+                        .map(|value| format!("'{value}'"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                Ok((
+                    format!("{normalized} IN ({})", list(self.parsing.true_values())),
+                    format!("{normalized} IN ({})", list(self.parsing.false_values())),
+                ))
+            }
+            other => Err(TermError::constraint_evaluation(
+                "true_rate",
+                format!(
+                    "Column '{}' has type {other}; expected a boolean or string column",
+                    self.column
+                ),
+            )),
+        }
+    }
+
+    /// Counts the true, false and null values of the column.
+    async fn count(&self, ctx: &SessionContext, table_name: &str) -> Result<TruthCounts> {
+        let data_type = ctx
+            .table(table_name)
+            .await?
+            .schema()
+            .field_with_unqualified_name(&self.column)
+            .map_err(|_| {
+                TermError::constraint_evaluation(
+                    "true_rate",
+                    format!("Column '{}' not found in table '{table_name}'", self.column),
+                )
+            })?
+            .data_type()
+            .clone();
+        let column = SqlSecurity::escape_identifier(&self.column)?;
+        let (is_true, is_false) = self.truth_expressions(&column, &data_type)?;
+
+        let sql = format!(
+            "SELECT
+                COUNT(CASE WHEN {is_true} THEN 1 END) AS true_count,
+                COUNT(CASE WHEN {is_false} THEN 1 END) AS false_count,
+                COUNT({column}) AS non_null_count,
+                COUNT(*) AS total_count
+             FROM {table_name}"
+        );
+        debug!("Generated true rate query: {}", sql);
+
+        let df = ctx.sql(&sql).await?;
+        let batches = collect_with_metrics(df).await?;
+        let batch = batches
+            .iter()
+            .find(|batch| batch.num_rows() > 0)
+            .ok_or_else(|| TermError::Internal("True rate query returned no rows".to_string()))?;
+        let counts: Vec<i64> = (0..4)
+            .map(|i| {
+                batch
+                    .column(i)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .map(|array| array.value(0))
+                    .ok_or_else(|| TermError::Internal("Failed to extract counts".to_string()))
+            })
+            .collect::<Result<_>>()?;
+        let (true_count, false_count, non_null_count, total_count) =
+            (counts[0], counts[1], counts[2], counts[3]);
+
+        let unparsed_count = non_null_count - true_count - false_count;
+        Ok(TruthCounts {
+            true_count,
+            false_count,
+            // Lenient parsing reads unrecognized strings as null
+            null_count: match self.parsing {
+                BooleanParsing::Lenient => total_count - true_count - false_count,
+                BooleanParsing::Strict => total_count - non_null_count,
+            },
+            unparsed_count: match self.parsing {
+                BooleanParsing::Lenient => 0,
+                BooleanParsing::Strict => unparsed_count,
+            },
+        })
+    }
+}
+
+#[async_trait]
+impl Constraint for TrueRateConstraint {
+    #[instrument(skip(self, ctx), fields(
+        column = %self.column,
+        assertion = %self.assertion,
+        nulls_as_false = self.nulls_as_false
+    ))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+
+        let counts = self.count(ctx, table_name).await?;
+        if counts.unparsed_count > 0 {
+            return Ok(ConstraintResult::failure(format!(
+                "Column '{}' has {} values that are not booleans ({counts})",
+                self.column, counts.unparsed_count
+            )));
+        }
+
+        let rated = counts.true_count
+            + counts.false_count
+            + if self.nulls_as_false {
+                counts.null_count
+            } else {
+                0
+            };
+        if rated == 0 {
+            let message = format!("Column '{}' has no non-null values", self.column);
+            return skipped_without_values(ctx, table_name, &message).await;
+        }
+
+        let rate = counts.true_count as f64 / rated as f64;
+        let explanation = self
+            .assertion
+            .explain(rate, format!("true rate of column '{}'", self.column));
+        if explanation.passed {
+            Ok(ConstraintResult {
+                message: Some(format!(
+                    "True rate of '{}' is {rate} ({counts})",
+                    self.column
+                )),
+                ..ConstraintResult::success_with_metric(rate)
+            })
+        } else {
+            Ok(ConstraintResult::failure_with_metric(
+                rate,
+                format!("{explanation} ({counts})"),
+            ))
+        }
+    }
+
+    fn name(&self) -> &str {
+        "true_rate"
+    }
+
+    fn column(&self) -> Option<&str> {
+        Some(&self.column)
+    }
+
+    fn describe(&self) -> String {
+        let nulls = if self.nulls_as_false {
+            ", counting nulls as false"
+        } else {
+            ""
+        };
+        format!(
+            "the share of true values in {} must be {}{nulls}",
+            self.column,
+            self.assertion.requirement()
+        )
+    }
+
+    fn metadata(&self) -> ConstraintMetadata {
+        ConstraintMetadata::for_column(&self.column)
+            .with_description(format!(
+                "Checks that the fraction of true values in '{}' {}",
+                self.column, self.assertion
+            ))
+            .with_custom("constraint_type", "statistical")
+            .with_custom("nulls_as_false", self.nulls_as_false.to_string())
+            .with_custom("parsing", self.parsing.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ConstraintStatus;
+    use crate::test_helpers::evaluate_constraint_with_context;
+
+    async fn create_context() -> SessionContext {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE TABLE data (is_verified BOOLEAN, flag VARCHAR, amount DOUBLE) AS VALUES
+                (true, 'true', 1.0),
+                (true, ' 1 ', 2.0),
+                (false, 'FALSE', 3.0),
+                (true, 'yes', 4.0),
+                (NULL, NULL, 5.0)",
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+        ctx
+    }
+
+    async fn evaluate(constraint: &TrueRateConstraint) -> ConstraintResult {
+        let ctx = create_context().await;
+        evaluate_constraint_with_context(constraint, &ctx, "data")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_boolean_column() {
+        let result = evaluate(
+            &TrueRateConstraint::new("is_verified", Assertion::GreaterThanOrEqual(0.75)).unwrap(),
+        )
+        .await;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.75));
+        assert_eq!(
+            result.message.as_deref(),
+            Some("True rate of 'is_verified' is 0.75 (true: 3, false: 1, null: 1)")
+        );
+
+        let result = evaluate(
+            &TrueRateConstraint::new("is_verified", Assertion::GreaterThanOrEqual(0.75))
+                .unwrap()
+                .nulls_as_false(true),
+        )
+        .await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(result.metric, Some(0.6));
+
+        let result = evaluate(&TrueRateConstraint::always_true("is_verified").unwrap()).await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+    }
+
+    #[tokio::test]
+    async fn test_string_column_parsing() {
+        // 'yes' is not a boolean in strict mode
+        let result = evaluate(&TrueRateConstraint::always_true("flag").unwrap()).await;
+        assert_eq!(result.status, ConstraintStatus::Failure);
+        assert_eq!(
+            result.message.as_deref(),
+            Some("Column 'flag' has 1 values that are not booleans (true: 2, false: 1, null: 1, unparsed: 1)")
+        );
+
+        let result = evaluate(
+            &TrueRateConstraint::new("flag", Assertion::Equals(0.75))
+                .unwrap()
+                .parsing(BooleanParsing::Lenient),
+        )
+        .await;
+        assert_eq!(result.status, ConstraintStatus::Success);
+        assert_eq!(result.metric, Some(0.75));
+    }
+
+    #[tokio::test]
+    async fn test_other_column_types_are_rejected() {
+        let ctx = create_context().await;
+        let constraint = TrueRateConstraint::always_false("amount").unwrap();
+        let err = evaluate_constraint_with_context(&constraint, &ctx, "data")
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected a boolean or string column"));
+    }
+}
@@ -4,17 +4,26 @@
 //! validations including full uniqueness, distinctness, unique value ratios, and primary keys.
 
 use crate::constraints::Assertion;
-use crate::core::{current_validation_context, Constraint, ConstraintMetadata, ConstraintResult};
+use crate::core::{
+    collect_with_metrics, current_validation_context, describe_percent, fetch_metrics,
+    metrics_query, partition_key_sql, partition_sql, register_partition_function,
+    AdjustedThreshold, Constraint, ConstraintMetadata, ConstraintResult, MemoryStrategy, MetricKey,
+    QualityDimension, ThresholdAdjustment, MEMORY_PARTITIONS_DETAIL,
+};
 use crate::prelude::*;
 use crate::security::SqlSecurity;
-use arrow::array::Array;
+use arrow::array::{Array, ArrayRef, Int64Array};
+use arrow::record_batch::RecordBatch;
 use async_trait::async_trait;
 use datafusion::prelude::*;
 use std::fmt;
+use std::sync::Arc;
 use tracing::instrument;
 /// Null handling strategy for uniqueness constraints.
 ///
 /// Defines how NULL values should be treated when evaluating uniqueness.
+/// [`FunctionalDependencyConstraint`](super::FunctionalDependencyConstraint)
+/// uses the same strategies for its determinant and dependent columns.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum NullHandling {
     /// Exclude NULL values from uniqueness calculations (default behavior).
@@ -159,6 +168,12 @@ pub struct UniquenessOptions {
 
     /// Whether to trim whitespace before comparison.
     pub trim_whitespace: bool,
+
+    /// Small-sample leniency applied to uniqueness ratio thresholds.
+    pub threshold_adjustment: Option<ThresholdAdjustment>,
+
+    /// How the query bounds its memory, unbounded or hash-partitioned.
+    pub memory_strategy: MemoryStrategy,
 }
 
 impl Default for UniquenessOptions {
@@ -167,6 +182,8 @@ impl Default for UniquenessOptions {
             null_handling: NullHandling::default(),
             case_sensitive: true,
             trim_whitespace: false,
+            threshold_adjustment: None,
+            memory_strategy: MemoryStrategy::default(),
         }
     }
 }
@@ -194,6 +211,45 @@ impl UniquenessOptions {
         self.trim_whitespace = trim_whitespace;
         self
     }
+
+    /// Relaxes uniqueness ratio thresholds for small samples.
+    ///
+    /// Applies to the uniqueness types with a threshold; see
+    /// [`ThresholdAdjustment`] for the available adjustments.
+    pub fn with_threshold_adjustment(mut self, adjustment: ThresholdAdjustment) -> Self {
+        self.threshold_adjustment = Some(adjustment);
+        self
+    }
+
+    /// Sets how the query bounds its memory.
+    ///
+    /// With [`MemoryStrategy::HashPartitioned`] the counts are computed exactly,
+    /// one hash partition of the key at a time; sketched counting is rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::{UniquenessConstraint, UniquenessOptions, UniquenessType};
+    /// use term_guard::core::MemoryStrategy;
+    ///
+    /// let options = UniquenessOptions::new().with_memory_strategy(MemoryStrategy::hash_partitioned(16));
+    /// let constraint = UniquenessConstraint::new(
+    ///     ["order_id"],
+    ///     UniquenessType::FullUniqueness { threshold: 1.0 },
+    ///     options,
+    /// )?;
+    /// assert!(UniquenessConstraint::new(
+    ///     ["order_id"],
+    ///     UniquenessType::PrimaryKey,
+    ///     UniquenessOptions::new().with_memory_strategy(MemoryStrategy::sketched(100)),
+    /// )
+    /// .is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_memory_strategy(mut self, strategy: MemoryStrategy) -> Self {
+        self.memory_strategy = strategy;
+        self
+    }
 }
 
 /// A unified constraint that handles all types of uniqueness validation.
@@ -290,17 +346,22 @@ impl UniquenessConstraint {
         match &uniqueness_type {
             UniquenessType::FullUniqueness { threshold }
             | UniquenessType::UniqueWithNulls { threshold, .. }
-            | UniquenessType::UniqueComposite { threshold, .. } => {
-                if !((0.0..=1.0).contains(threshold)) {
-                    return Err(TermError::validation_failed(
-                        "unified_uniqueness",
-                        "Threshold must be between 0.0 and 1.0",
-                    ));
-                }
+            | UniquenessType::UniqueComposite { threshold, .. }
+                if !((0.0..=1.0).contains(threshold)) =>
+            {
+                return Err(TermError::validation_failed(
+                    "unified_uniqueness",
+                    "Threshold must be between 0.0 and 1.0",
+                ));
             }
             _ => {} // Other types don't have threshold validation
         }
 
+        if let Some(adjustment) = &options.threshold_adjustment {
+            adjustment.validate()?;
+        }
+        options.memory_strategy.check_uniqueness()?;
+
         Ok(Self {
             columns: column_vec,
             uniqueness_type,
@@ -451,11 +512,30 @@ impl Constraint for UniquenessConstraint {
         let validation_ctx = current_validation_context();
         let table_name = validation_ctx.table_name();
 
+        if let MemoryStrategy::HashPartitioned { partitions } = self.options.memory_strategy {
+            return self.evaluate_partitioned(ctx, table_name, partitions).await;
+        }
+
+        // Plain row and distinct counts are shared with other constraints of the run
+        if self.uses_shared_counts() {
+            let (total_count, distinct_count) = self.shared_counts(ctx, table_name).await?;
+            match &self.uniqueness_type {
+                UniquenessType::FullUniqueness { threshold }
+                | UniquenessType::UniqueWithNulls { threshold, .. } => {
+                    return self.threshold_result(total_count, distinct_count, *threshold);
+                }
+                UniquenessType::Distinctness(assertion) => {
+                    return Ok(self.assertion_result(distinct_count, total_count, assertion));
+                }
+                _ => {}
+            }
+        }
+
         // Generate SQL based on uniqueness type
-        let sql = self.generate_sql(table_name)?;
+        let sql = self.uniqueness_sql(table_name)?;
 
         let df = ctx.sql(&sql).await?;
-        let batches = df.collect().await?;
+        let batches = collect_with_metrics(df).await?;
 
         if batches.is_empty() {
             return Ok(ConstraintResult::skipped("No data to validate"));
@@ -466,25 +546,17 @@ impl Constraint for UniquenessConstraint {
             return Ok(ConstraintResult::skipped("No data to validate"));
         }
 
-        // Process results based on uniqueness type
-        match &self.uniqueness_type {
-            UniquenessType::FullUniqueness { threshold }
-            | UniquenessType::UniqueWithNulls { threshold, .. }
-            | UniquenessType::UniqueComposite { threshold, .. } => {
-                self.evaluate_threshold_based(batch, *threshold).await
-            }
-            UniquenessType::Distinctness(assertion)
-            | UniquenessType::UniqueValueRatio(assertion) => {
-                self.evaluate_assertion_based(batch, assertion).await
-            }
-            UniquenessType::PrimaryKey => self.evaluate_primary_key(batch).await,
-        }
+        self.evaluate_counts(batch).await
     }
 
     fn name(&self) -> &str {
         self.uniqueness_type.name()
     }
 
+    fn dimension(&self) -> Option<QualityDimension> {
+        Some(QualityDimension::Uniqueness)
+    }
+
     fn column(&self) -> Option<&str> {
         if self.columns.len() == 1 {
             Some(&self.columns[0])
@@ -493,6 +565,58 @@ impl Constraint for UniquenessConstraint {
         }
     }
 
+    fn generate_sql(&self, table_name: &str) -> Result<Vec<String>> {
+        if let MemoryStrategy::HashPartitioned { partitions } = self.options.memory_strategy {
+            let key = partition_key_sql(&self.columns)?;
+            return (0..partitions)
+                .map(|partition| {
+                    self.uniqueness_sql(&partition_sql(table_name, &key, partition, partitions))
+                })
+                .collect();
+        }
+        let sql = if self.uses_shared_counts() {
+            // As run when no metric cache shares the counts
+            let [rows, distinct] = self.shared_count_keys(table_name);
+            metrics_query(&[&rows, &distinct])?
+        } else {
+            self.uniqueness_sql(table_name)?
+        };
+        Ok(vec![sql])
+    }
+
+    fn describe(&self) -> String {
+        let subject = if self.columns.len() == 1 {
+            self.columns[0].clone()
+        } else {
+            format!("({})", self.columns.join(", "))
+        };
+        match &self.uniqueness_type {
+            UniquenessType::FullUniqueness { threshold }
+            | UniquenessType::UniqueWithNulls { threshold, .. }
+            | UniquenessType::UniqueComposite { threshold, .. } => {
+                if *threshold >= 1.0 {
+                    format!("{subject} must be unique")
+                } else {
+                    format!(
+                        "at least {} of {subject} values must be unique",
+                        describe_percent(*threshold)
+                    )
+                }
+            }
+            UniquenessType::Distinctness(assertion) => format!(
+                "the ratio of distinct {subject} values must be {}",
+                assertion.requirement()
+            ),
+            UniquenessType::UniqueValueRatio(assertion) => format!(
+                "the ratio of {subject} values occurring exactly once must be {}",
+                assertion.requirement()
+            ),
+            UniquenessType::PrimaryKey => {
+                format!("{subject} must be a primary key: unique and never null")
+            }
+        }
+    }
+
     fn metadata(&self) -> ConstraintMetadata {
         let mut metadata = if self.columns.len() == 1 {
             ConstraintMetadata::for_column(&self.columns[0])
@@ -509,6 +633,10 @@ impl Constraint for UniquenessConstraint {
             .with_custom("null_handling", self.options.null_handling.to_string())
             .with_custom("case_sensitive", self.options.case_sensitive.to_string())
             .with_custom("constraint_type", "uniqueness");
+        if !self.options.memory_strategy.is_unbounded() {
+            metadata =
+                metadata.with_custom("memory_strategy", self.options.memory_strategy.to_string());
+        }
 
         // Add type-specific metadata
         match &self.uniqueness_type {
@@ -516,6 +644,9 @@ impl Constraint for UniquenessConstraint {
             | UniquenessType::UniqueWithNulls { threshold, .. }
             | UniquenessType::UniqueComposite { threshold, .. } => {
                 metadata = metadata.with_custom("threshold", threshold.to_string());
+                if let Some(adjustment) = &self.options.threshold_adjustment {
+                    metadata = metadata.with_custom("threshold_adjustment", adjustment.to_string());
+                }
             }
             UniquenessType::Distinctness(assertion)
             | UniquenessType::UniqueValueRatio(assertion) => {
@@ -531,8 +662,95 @@ impl Constraint for UniquenessConstraint {
 }
 
 impl UniquenessConstraint {
+    /// Returns whether the constraint is answered from the row and distinct counts
+    /// shared with other constraints of the run.
+    fn uses_shared_counts(&self) -> bool {
+        match &self.uniqueness_type {
+            UniquenessType::FullUniqueness { .. }
+            | UniquenessType::UniqueWithNulls {
+                null_handling: NullHandling::Exclude,
+                ..
+            } => true,
+            UniquenessType::Distinctness(_) => self.columns.len() == 1,
+            _ => false,
+        }
+    }
+
+    /// Returns the row and distinct counts shared with other constraints.
+    fn shared_count_keys(&self, table_name: &str) -> [MetricKey; 2] {
+        [
+            MetricKey::row_count(table_name),
+            MetricKey::distinct_count(table_name, &self.columns),
+        ]
+    }
+
+    /// Evaluates the counts queried for the uniqueness type.
+    async fn evaluate_counts(&self, batch: &RecordBatch) -> Result<ConstraintResult> {
+        match &self.uniqueness_type {
+            UniquenessType::FullUniqueness { threshold }
+            | UniquenessType::UniqueWithNulls { threshold, .. }
+            | UniquenessType::UniqueComposite { threshold, .. } => {
+                self.evaluate_threshold_based(batch, *threshold).await
+            }
+            UniquenessType::Distinctness(assertion)
+            | UniquenessType::UniqueValueRatio(assertion) => {
+                self.evaluate_assertion_based(batch, assertion).await
+            }
+            UniquenessType::PrimaryKey => self.evaluate_primary_key(batch).await,
+        }
+    }
+
+    /// Evaluates the constraint one hash partition of the key at a time.
+    ///
+    /// Equal keys share a partition, so every count of the uniqueness queries is
+    /// the sum of its counts over the partitions.
+    async fn evaluate_partitioned(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        partitions: u32,
+    ) -> Result<ConstraintResult> {
+        register_partition_function(ctx);
+        let key = partition_key_sql(&self.columns)?;
+
+        let mut totals: Option<(arrow::datatypes::SchemaRef, Vec<i64>)> = None;
+        for partition in 0..partitions {
+            let sql =
+                self.uniqueness_sql(&partition_sql(table_name, &key, partition, partitions))?;
+            let batches = collect_with_metrics(ctx.sql(&sql).await?).await?;
+            let Some(batch) = batches.iter().find(|batch| batch.num_rows() > 0) else {
+                continue;
+            };
+            let (_, sums) =
+                totals.get_or_insert_with(|| (batch.schema(), vec![0; batch.num_columns()]));
+            for (sum, column) in sums.iter_mut().zip(batch.columns()) {
+                let counts = column
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .ok_or_else(|| TermError::Internal("Failed to extract count".to_string()))?;
+                if !counts.is_null(0) {
+                    *sum += counts.value(0);
+                }
+            }
+        }
+
+        let Some((schema, sums)) = totals else {
+            return Ok(ConstraintResult::skipped("No data to validate"));
+        };
+        let columns = sums
+            .into_iter()
+            .map(|sum| Arc::new(Int64Array::from(vec![sum])) as ArrayRef)
+            .collect();
+        let batch = RecordBatch::try_new(schema, columns)?;
+        let mut result = self.evaluate_counts(&batch).await?;
+        result
+            .details
+            .insert(MEMORY_PARTITIONS_DETAIL.to_string(), f64::from(partitions));
+        Ok(result)
+    }
+
     /// Generates SQL query based on the uniqueness type and options.
-    fn generate_sql(&self, table_name: &str) -> Result<String> {
+    fn uniqueness_sql(&self, table_name: &str) -> Result<String> {
         match &self.uniqueness_type {
             UniquenessType::FullUniqueness { .. }
             | UniquenessType::UniqueWithNulls { .. }
@@ -737,23 +955,39 @@ impl UniquenessConstraint {
             .ok_or_else(|| TermError::Internal("Failed to extract unique count".to_string()))?
             .value(0) as f64;
 
+        self.threshold_result(total_count, unique_count, threshold)
+    }
+
+    /// Compares the uniqueness ratio against a threshold.
+    fn threshold_result(
+        &self,
+        total_count: f64,
+        unique_count: f64,
+        threshold: f64,
+    ) -> Result<ConstraintResult> {
         if total_count == 0.0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
+            return Ok(ConstraintResult::empty_table());
         }
 
         let uniqueness_ratio = unique_count / total_count;
-
-        if uniqueness_ratio >= threshold {
-            Ok(ConstraintResult::success_with_metric(uniqueness_ratio))
+        let adjusted = AdjustedThreshold::new(
+            threshold,
+            self.options.threshold_adjustment,
+            total_count as u64,
+        )?;
+
+        let result = if adjusted.passes(uniqueness_ratio) {
+            ConstraintResult::success_with_metric(uniqueness_ratio)
         } else {
-            Ok(ConstraintResult::failure_with_metric(
+            ConstraintResult::failure_with_metric(
                 uniqueness_ratio,
                 format!(
                     "Uniqueness ratio {uniqueness_ratio:.3} is below threshold {threshold:.3} for columns: {}",
                     self.columns.join(", ")
                 ),
-            ))
-        }
+            )
+        };
+        Ok(adjusted.annotate(result, uniqueness_ratio))
     }
 
     /// Evaluates assertion-based results (distinctness and unique value ratio).
@@ -776,25 +1010,45 @@ impl UniquenessConstraint {
             .ok_or_else(|| TermError::Internal("Failed to extract total count".to_string()))?
             .value(0) as f64;
 
+        Ok(self.assertion_result(count, total_count, assertion))
+    }
+
+    /// Evaluates an assertion against the ratio of `count` to `total_count`.
+    fn assertion_result(
+        &self,
+        count: f64,
+        total_count: f64,
+        assertion: &Assertion,
+    ) -> ConstraintResult {
         if total_count == 0.0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
+            return ConstraintResult::empty_table();
         }
 
         let ratio = count / total_count;
 
-        if assertion.evaluate(ratio) {
-            Ok(ConstraintResult::success_with_metric(ratio))
-        } else {
-            Ok(ConstraintResult::failure_with_metric(
-                ratio,
-                format!(
-                    "{} ratio {ratio:.3} does not satisfy {} for columns: {}",
-                    self.uniqueness_type.name(),
-                    assertion.description(),
-                    self.columns.join(", ")
-                ),
-            ))
+        let mut measure = self.uniqueness_type.name().replace('_', " ");
+        if !measure.ends_with("ratio") {
+            measure.push_str(" ratio");
         }
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| format!("'{column}'"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let context = if self.columns.len() == 1 {
+            format!("{measure} of column {columns}")
+        } else {
+            format!("{measure} of columns {columns}")
+        };
+        assertion.explain(ratio, context).to_result()
+    }
+
+    /// Fetches the row count and the distinct count of the columns through the
+    /// metric cache of the run.
+    async fn shared_counts(&self, ctx: &SessionContext, table_name: &str) -> Result<(f64, f64)> {
+        let counts = fetch_metrics(ctx, &self.shared_count_keys(table_name)).await?;
+        Ok((counts[0] as f64, counts[1] as f64))
     }
 
     /// Evaluates primary key results.
@@ -824,7 +1078,7 @@ impl UniquenessConstraint {
             .value(0) as f64;
 
         if total_count == 0.0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
+            return Ok(ConstraintResult::empty_table());
         }
 
         // Primary key validation: no NULLs and all values unique
@@ -1066,7 +1320,7 @@ mod tests {
         let result = evaluate_constraint_with_context(&constraint, &ctx, "data")
             .await
             .unwrap();
-        assert_eq!(result.status, ConstraintStatus::Skipped);
+        assert!(result.is_empty_table());
     }
 
     #[tokio::test]
@@ -1115,4 +1369,100 @@ mod tests {
         assert_eq!(constraint.column(), None); // Multi-column has no single column
         assert_eq!(constraint.columns(), &["col1", "col2"]);
     }
+
+    #[test]
+    fn test_generate_sql() -> Result<()> {
+        // Plain uniqueness reads the counts shared with other constraints
+        let constraint = UniquenessConstraint::full_uniqueness("id", 1.0)?;
+        assert_eq!(
+            constraint.generate_sql("orders")?,
+            vec![r#"SELECT COUNT(*) AS m0, COUNT(DISTINCT "id") AS m1 FROM orders"#]
+        );
+
+        let constraint =
+            UniquenessConstraint::unique_with_nulls(["id"], 1.0, NullHandling::Include)?;
+        let sql = constraint.generate_sql("orders")?;
+        assert_eq!(sql.len(), 1);
+        assert!(sql[0].contains(r#"COUNT(DISTINCT COALESCE("id", '<NULL>'))"#));
+
+        let constraint = UniquenessConstraint::primary_key(["order_id", "line"])?;
+        let sql = constraint.generate_sql("orders")?;
+        assert!(sql[0].contains(r#"("order_id", "line")"#));
+
+        // Hash partitioning runs one query per partition
+        let constraint = UniquenessConstraint::new(
+            ["id"],
+            UniquenessType::FullUniqueness { threshold: 1.0 },
+            UniquenessOptions::new().with_memory_strategy(MemoryStrategy::hash_partitioned(4)),
+        )?;
+        let sql = constraint.generate_sql("orders")?;
+        assert_eq!(sql.len(), 4);
+        assert!(sql[3]
+            .contains(r#"term_hash_partition(COALESCE(CAST("id" AS VARCHAR), '<NULL>'), 4) = 3"#));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_hash_partitioned_counts_match_single_query() {
+        let values = vec![
+            Some("A"),
+            Some("B"),
+            None,
+            Some("A"),
+            Some("C"),
+            None,
+            Some("D"),
+            Some("B"),
+        ];
+        let ctx = create_test_context(values).await;
+
+        let types = [
+            UniquenessType::FullUniqueness { threshold: 0.1 },
+            UniquenessType::Distinctness(Assertion::GreaterThan(0.0)),
+            UniquenessType::UniqueValueRatio(Assertion::GreaterThan(0.0)),
+            UniquenessType::PrimaryKey,
+            UniquenessType::UniqueWithNulls {
+                threshold: 0.1,
+                null_handling: NullHandling::Include,
+            },
+            UniquenessType::UniqueWithNulls {
+                threshold: 0.1,
+                null_handling: NullHandling::Distinct,
+            },
+        ];
+        for uniqueness_type in types {
+            let single = UniquenessConstraint::new(
+                ["test_col"],
+                uniqueness_type.clone(),
+                UniquenessOptions::new(),
+            )
+            .unwrap();
+            let partitioned = UniquenessConstraint::new(
+                ["test_col"],
+                uniqueness_type.clone(),
+                UniquenessOptions::new().with_memory_strategy(MemoryStrategy::hash_partitioned(3)),
+            )
+            .unwrap();
+
+            let expected = evaluate_constraint_with_context(&single, &ctx, "data")
+                .await
+                .unwrap();
+            let actual = evaluate_constraint_with_context(&partitioned, &ctx, "data")
+                .await
+                .unwrap();
+            assert_eq!(actual.status, expected.status, "{uniqueness_type:?}");
+            assert_eq!(actual.metric, expected.metric, "{uniqueness_type:?}");
+            assert_eq!(actual.details[MEMORY_PARTITIONS_DETAIL], 3.0);
+        }
+    }
+
+    #[test]
+    fn test_sketched_strategy_is_rejected() {
+        let result = UniquenessConstraint::new(
+            ["id"],
+            UniquenessType::PrimaryKey,
+            UniquenessOptions::new().with_memory_strategy(MemoryStrategy::sketched(10)),
+        );
+        assert!(matches!(result, Err(TermError::Configuration(_))));
+    }
 }
@@ -1,8 +1,12 @@
 //! Value-based validation constraints.
 
-use crate::core::{current_validation_context, Constraint, ConstraintMetadata, ConstraintResult};
+use crate::core::{
+    current_validation_context, skipped_without_values, AdjustedThreshold, Constraint,
+    ConstraintMetadata, ConstraintResult, NullPolicy, QuarantineSink, RatioQueryBuilder,
+    ThresholdAdjustment,
+};
 use crate::prelude::*;
-use arrow::array::Array;
+use crate::security::SqlSecurity;
 use async_trait::async_trait;
 use datafusion::prelude::*;
 use tracing::instrument;
@@ -103,53 +107,20 @@ impl Constraint for DataTypeConstraint {
     #[instrument(skip(self, ctx), fields(column = %self.column, data_type = %self.data_type.name(), threshold = %self.threshold))]
     async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
         let pattern = self.data_type.pattern();
+        let column_identifier = SqlSecurity::escape_identifier(&self.column)?;
 
         // Get the table name from the validation context
-
         let validation_ctx = current_validation_context();
-
         let table_name = validation_ctx.table_name();
 
-        let sql = format!(
-            "SELECT 
-                COUNT(CASE WHEN {} ~ '{pattern}' THEN 1 END) as matches,
-                COUNT(*) as total
-             FROM {table_name}
-             WHERE {} IS NOT NULL",
-            self.column, self.column
-        );
-
-        let df = ctx.sql(&sql).await?;
-        let batches = df.collect().await?;
-
-        if batches.is_empty() {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        let batch = &batches[0];
-        if batch.num_rows() == 0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        let matches = batch
-            .column(0)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract match count".to_string()))?
-            .value(0) as f64;
-
-        let total = batch
-            .column(1)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract total count".to_string()))?
-            .value(0) as f64;
-
-        if total == 0.0 {
-            return Ok(ConstraintResult::skipped("No non-null data to validate"));
-        }
-
-        let type_ratio = matches / total;
+        let counts =
+            RatioQueryBuilder::new(table_name, format!("{column_identifier} ~ '{pattern}'"))
+                .nulls(column_identifier, NullPolicy::Excluded)
+                .execute(ctx)
+                .await?;
+        let Some(type_ratio) = counts.ratio() else {
+            return skipped_without_values(ctx, table_name, "No non-null data to validate").await;
+        };
 
         if type_ratio >= self.threshold {
             Ok(ConstraintResult::success_with_metric(type_ratio))
@@ -189,7 +160,8 @@ impl Constraint for DataTypeConstraint {
 /// A constraint that checks if values in a column are contained in a set of allowed values.
 ///
 /// This constraint validates that all non-null values in a column are members
-/// of the specified set of allowed values.
+/// of the specified set of allowed values, or of the values of a column of
+/// another table, see [`in_table`](Self::in_table).
 ///
 /// # Examples
 ///
@@ -205,8 +177,29 @@ impl Constraint for DataTypeConstraint {
 pub struct ContainmentConstraint {
     column: String,
     allowed_values: Vec<String>,
+    allowed_column: Option<AllowedColumn>,
+    threshold_adjustment: Option<ThresholdAdjustment>,
+    quarantine: Option<QuarantineSink>,
 }
 
+/// A column of another table holding the allowed values.
+#[derive(Debug, Clone)]
+struct AllowedColumn {
+    table: String,
+    column: String,
+}
+
+impl AllowedColumn {
+    /// Returns the table name, checked to be an identifier, and the escaped column.
+    fn sql(&self) -> Result<(&str, String)> {
+        SqlSecurity::validate_identifier(&self.table)?;
+        Ok((&self.table, SqlSecurity::escape_identifier(&self.column)?))
+    }
+}
+
+/// Alias of the allowed values joined to the validated table.
+const ALLOWED_VALUE_ALIAS: &str = "term_allowed_value";
+
 impl ContainmentConstraint {
     /// Creates a new containment constraint.
     ///
@@ -222,75 +215,155 @@ impl ContainmentConstraint {
         Self {
             column: column.into(),
             allowed_values: allowed_values.into_iter().map(Into::into).collect(),
+            allowed_column: None,
+            threshold_adjustment: None,
+            quarantine: None,
         }
     }
-}
 
-#[async_trait]
-impl Constraint for ContainmentConstraint {
-    #[instrument(skip(self, ctx), fields(column = %self.column, allowed_count = %self.allowed_values.len()))]
-    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
-        // Get the table name from the validation context
-        let validation_ctx = current_validation_context();
-        let table_name = validation_ctx.table_name();
+    /// Creates a containment constraint whose allowed values are the values of
+    /// `table_column` in `table`.
+    ///
+    /// The table is read when the constraint is evaluated, typically a lookup
+    /// table embedded with
+    /// [`ValidationSuiteBuilder::with_lookup`](crate::core::ValidationSuiteBuilder::with_lookup).
+    /// Nulls in `table_column` allow no value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::ContainmentConstraint;
+    /// use term_guard::core::Constraint;
+    ///
+    /// let constraint = ContainmentConstraint::in_table("plan", "valid_plans", "plan");
+    /// assert_eq!(constraint.referenced_tables(), ["valid_plans"]);
+    /// ```
+    pub fn in_table(
+        column: impl Into<String>,
+        table: impl Into<String>,
+        table_column: impl Into<String>,
+    ) -> Self {
+        Self {
+            column: column.into(),
+            allowed_values: Vec::new(),
+            allowed_column: Some(AllowedColumn {
+                table: table.into(),
+                column: table_column.into(),
+            }),
+            threshold_adjustment: None,
+            quarantine: None,
+        }
+    }
 
-        // Create IN clause with allowed values
-        let values_list = self
-            .allowed_values
+    /// Tolerates some values outside the allowed set on small samples.
+    ///
+    /// Every non-null value must be allowed otherwise. Since that leaves no
+    /// room for violations, [`ThresholdAdjustment::MinFailingRows`] is the
+    /// adjustment that relaxes this constraint.
+    pub fn with_threshold_adjustment(mut self, adjustment: ThresholdAdjustment) -> Self {
+        self.threshold_adjustment = Some(adjustment);
+        self
+    }
+
+    /// Exports every non-null value outside the allowed set to `sink` when evaluated.
+    pub fn with_quarantine(mut self, sink: QuarantineSink) -> Self {
+        self.quarantine = Some(sink);
+        self
+    }
+
+    /// Returns the allowed values as a SQL list.
+    fn values_list(&self) -> String {
+        self.allowed_values
             .iter()
             .map(|v| format!("'{}'", v.replace('\'', "''"))) // Escape single quotes
             .collect::<Vec<_>>()
-            .join(", ");
-
-        let sql = format!(
-            "SELECT 
-                COUNT(CASE WHEN {} IN ({values_list}) THEN 1 END) as valid_values,
-                COUNT(*) as total
-             FROM {table_name}
-             WHERE {} IS NOT NULL",
-            self.column, self.column
-        );
-
-        let df = ctx.sql(&sql).await?;
-        let batches = df.collect().await?;
+            .join(", ")
+    }
 
-        if batches.is_empty() {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
+    /// Returns the SQL of the allowed values: the quoted list, or a query of
+    /// the non-null values of the allowed column.
+    fn allowed_sql(&self) -> Result<String> {
+        let Some(allowed) = &self.allowed_column else {
+            return Ok(self.values_list());
+        };
+        let (table, column) = allowed.sql()?;
+        Ok(format!(
+            "SELECT {column} FROM {table} WHERE {column} IS NOT NULL"
+        ))
+    }
 
-        let batch = &batches[0];
-        if batch.num_rows() == 0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
+    /// Describes where the allowed values come from, such as `valid_plans.plan`.
+    fn allowed_description(&self) -> String {
+        match &self.allowed_column {
+            Some(allowed) => format!("{}.{}", allowed.table, allowed.column),
+            None => "the allowed set".to_string(),
         }
+    }
 
-        let valid_values = batch
-            .column(0)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract valid count".to_string()))?
-            .value(0) as f64;
-
-        let total = batch
-            .column(1)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract total count".to_string()))?
-            .value(0) as f64;
-
-        if total == 0.0 {
-            return Ok(ConstraintResult::skipped("No non-null data to validate"));
-        }
+    async fn evaluate_containment(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let column_identifier = SqlSecurity::escape_identifier(&self.column)?;
 
-        let containment_ratio = valid_values / total;
+        // Get the table name from the validation context
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
 
-        if containment_ratio == 1.0 {
-            Ok(ConstraintResult::success_with_metric(containment_ratio))
+        let query = match &self.allowed_column {
+            // An IN subquery cannot be counted inside CASE, so the distinct
+            // allowed values are joined instead
+            Some(allowed) => {
+                let (table, column) = allowed.sql()?;
+                let source = format!(
+                    "{table_name} LEFT JOIN (SELECT DISTINCT {column} AS {ALLOWED_VALUE_ALIAS} FROM {table}) AS allowed ON {column_identifier} = allowed.{ALLOWED_VALUE_ALIAS}"
+                );
+                RatioQueryBuilder::new(source, format!("allowed.{ALLOWED_VALUE_ALIAS} IS NOT NULL"))
+            }
+            None => RatioQueryBuilder::new(
+                table_name,
+                format!("{column_identifier} IN ({})", self.values_list()),
+            ),
+        };
+        let counts = query
+            .nulls(column_identifier, NullPolicy::Excluded)
+            .execute(ctx)
+            .await?;
+        let Some(containment_ratio) = counts.ratio() else {
+            return skipped_without_values(ctx, table_name, "No non-null data to validate").await;
+        };
+        let adjusted = AdjustedThreshold::new(1.0, self.threshold_adjustment, counts.total)?;
+
+        let result = if adjusted.passes(containment_ratio) {
+            ConstraintResult::success_with_metric(containment_ratio)
         } else {
-            let invalid_count = total - valid_values;
-            Ok(ConstraintResult::failure_with_metric(
+            ConstraintResult::failure_with_metric(
                 containment_ratio,
-                format!("{invalid_count} values are not in the allowed set"),
-            ))
+                format!(
+                    "{} values are not in {}",
+                    counts.violating(),
+                    self.allowed_description()
+                ),
+            )
+        };
+        Ok(adjusted.annotate(result, containment_ratio))
+    }
+}
+
+#[async_trait]
+impl Constraint for ContainmentConstraint {
+    #[instrument(skip(self, ctx), fields(column = %self.column, allowed_count = %self.allowed_values.len()))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let result = self.evaluate_containment(ctx).await?;
+        match &self.quarantine {
+            Some(sink) if !result.status.is_skipped() => {
+                let column_identifier = SqlSecurity::escape_identifier(&self.column)?;
+                let violation = format!(
+                    "{column_identifier} IS NOT NULL AND {column_identifier} NOT IN ({})",
+                    self.allowed_sql()?
+                );
+                let reason = format!("'value not in {}'", self.allowed_description());
+                let summary = sink.write(ctx, self.name(), &violation, &reason).await?;
+                Ok(result.with_quarantine(summary))
+            }
+            _ => Ok(result),
         }
     }
 
@@ -302,17 +375,53 @@ impl Constraint for ContainmentConstraint {
         Some(&self.column)
     }
 
+    fn referenced_tables(&self) -> Vec<String> {
+        self.allowed_column
+            .iter()
+            .map(|allowed| allowed.table.clone())
+            .collect()
+    }
+
+    fn describe(&self) -> String {
+        if let Some(allowed) = &self.allowed_column {
+            return format!(
+                "{} must be a value of {}.{} in every non-null value",
+                self.column, allowed.table, allowed.column
+            );
+        }
+        let values: Vec<String> = self
+            .allowed_values
+            .iter()
+            .map(|value| format!("'{value}'"))
+            .collect();
+        format!(
+            "{} must be one of {} in every non-null value",
+            self.column,
+            values.join(", ")
+        )
+    }
+
     fn metadata(&self) -> ConstraintMetadata {
-        ConstraintMetadata::for_column(&self.column)
+        let metadata = ConstraintMetadata::for_column(&self.column)
             .with_description(format!(
-                "Checks that all values in '{}' are contained in the allowed set",
-                self.column
+                "Checks that all values in '{}' are contained in {}",
+                self.column,
+                self.allowed_description()
             ))
-            .with_custom(
+            .with_custom("constraint_type", "containment");
+        let metadata = match &self.allowed_column {
+            Some(_) => metadata.with_custom("allowed_column", self.allowed_description()),
+            None => metadata.with_custom(
                 "allowed_values",
                 format!("[{}]", self.allowed_values.join(", ")),
-            )
-            .with_custom("constraint_type", "containment")
+            ),
+        };
+        match &self.threshold_adjustment {
+            Some(adjustment) => {
+                metadata.with_custom("threshold_adjustment", adjustment.to_string())
+            }
+            None => metadata,
+        }
     }
 }
 
@@ -360,54 +469,24 @@ impl Constraint for NonNegativeConstraint {
         let table_name = validation_ctx.table_name();
 
         // Check if all values are >= 0
-        let sql = format!(
-            "SELECT 
-                COUNT(CASE WHEN CAST({} AS DOUBLE) >= 0 THEN 1 END) as non_negative,
-                COUNT(*) as total
-             FROM {table_name}
-             WHERE {} IS NOT NULL",
-            self.column, self.column
-        );
-
-        let df = ctx.sql(&sql).await?;
-        let batches = df.collect().await?;
-
-        if batches.is_empty() {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        let batch = &batches[0];
-        if batch.num_rows() == 0 {
-            return Ok(ConstraintResult::skipped("No data to validate"));
-        }
-
-        let non_negative = batch
-            .column(0)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract non-negative count".to_string()))?
-            .value(0) as f64;
-
-        let total = batch
-            .column(1)
-            .as_any()
-            .downcast_ref::<arrow::array::Int64Array>()
-            .ok_or_else(|| TermError::Internal("Failed to extract total count".to_string()))?
-            .value(0) as f64;
-
-        if total == 0.0 {
-            return Ok(ConstraintResult::skipped("No non-null data to validate"));
-        }
-
-        let non_negative_ratio = non_negative / total;
+        let column_identifier = SqlSecurity::escape_identifier(&self.column)?;
+        let counts = RatioQueryBuilder::new(
+            table_name,
+            format!("CAST({column_identifier} AS DOUBLE) >= 0"),
+        )
+        .nulls(column_identifier, NullPolicy::Excluded)
+        .execute(ctx)
+        .await?;
+        let Some(non_negative_ratio) = counts.ratio() else {
+            return skipped_without_values(ctx, table_name, "No non-null data to validate").await;
+        };
 
         if non_negative_ratio == 1.0 {
             Ok(ConstraintResult::success_with_metric(non_negative_ratio))
         } else {
-            let negative_count = total - non_negative;
             Ok(ConstraintResult::failure_with_metric(
                 non_negative_ratio,
-                format!("{negative_count} values are negative"),
+                format!("{} values are negative", counts.violating()),
             ))
         }
     }
@@ -0,0 +1,330 @@
+//! Metric anomaly checks for validation suites.
+//!
+//! An anomaly check combines an [`Analyzer`], an [`AnomalyDetectionStrategy`] and
+//! a [`MetricsRepository`] into a single constraint, similar to Deequ's
+//! `addAnomalyCheck`. When the suite runs, the check:
+//!
+//! 1. Computes the analyzer's metric on the validated table
+//! 2. Loads the metric's history from the repository, filtered by the configured tags
+//! 3. Evaluates the strategy against that history
+//! 4. Optionally appends the new value to the repository
+//!
+//! Checks with less history than [`AnomalyCheckOptions::min_history`] are skipped.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use term_guard::analyzers::anomaly::RelativeRateOfChangeStrategy;
+//! use term_guard::analyzers::basic::SizeAnalyzer;
+//! use term_guard::core::{AnomalyCheckOptions, Level, ValidationSuite};
+//! use term_guard::repository::InMemoryRepository;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let repository = Arc::new(InMemoryRepository::new());
+//!
+//! let suite = ValidationSuite::builder("daily_orders")
+//!     .table_name("orders")
+//!     .anomaly_check(
+//!         SizeAnalyzer::new(),
+//!         RelativeRateOfChangeStrategy::new(0.5)?,
+//!         repository,
+//!         AnomalyCheckOptions::new("volume")
+//!             .level(Level::Error)
+//!             .min_history(3)
+//!             .with_tag("dataset", "orders"),
+//!     )
+//!     .build();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use tracing::{debug, instrument, warn};
+
+use super::{stamp_suite_version, Constraint, ConstraintResult, Level};
+use crate::analyzers::anomaly::{AnomalyDetectionStrategy, MetricPoint};
+use crate::analyzers::runner::AnalyzerExecution;
+use crate::analyzers::{Analyzer, AnalyzerContext, MetricValue};
+use crate::error::{Result, TermError};
+use crate::repository::{MetricsRepository, ResultKey, SortOrder};
+
+/// Configuration of an anomaly check added with
+/// [`ValidationSuiteBuilder::anomaly_check`](super::ValidationSuiteBuilder::anomaly_check).
+#[derive(Debug, Clone)]
+pub struct AnomalyCheckOptions {
+    /// Name of the check the anomaly result is reported under
+    check_name: String,
+    /// Level of the check when it is created by the anomaly check
+    level: Level,
+    /// Minimum number of historical values before the strategy is evaluated
+    min_history: usize,
+    /// Tags used to filter the history and to tag the stored metric
+    tags: HashMap<String, String>,
+    /// Whether the computed metric is appended to the repository
+    save_metric: bool,
+}
+
+impl AnomalyCheckOptions {
+    /// Creates options reporting into the check named `check_name`.
+    ///
+    /// If the suite already contains a check with this name, the anomaly check is
+    /// added to it; otherwise a new check is created. By default the check has
+    /// [`Level::Warning`], requires no history, and saves every computed metric.
+    pub fn new(check_name: impl Into<String>) -> Self {
+        Self {
+            check_name: check_name.into(),
+            level: Level::Warning,
+            min_history: 0,
+            tags: HashMap::new(),
+            save_metric: true,
+        }
+    }
+
+    /// Sets the level of the check created for the anomaly result.
+    ///
+    /// Has no effect when the anomaly check is added to an existing check.
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Sets the warm-up length: with fewer historical values the check is skipped.
+    pub fn min_history(mut self, min_history: usize) -> Self {
+        self.min_history = min_history;
+        self
+    }
+
+    /// Adds a tag that historical values must match and the new value is saved with.
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets whether the computed metric is appended to the repository (default `true`).
+    pub fn save_metric(mut self, save: bool) -> Self {
+        self.save_metric = save;
+        self
+    }
+
+    /// Returns the name of the check the result is reported under.
+    pub fn check_name(&self) -> &str {
+        &self.check_name
+    }
+
+    /// Returns the level of the check created for the anomaly result.
+    pub fn check_level(&self) -> Level {
+        self.level
+    }
+}
+
+/// Constraint asserting that an analyzer metric is not anomalous relative to its history.
+pub(crate) struct AnomalyCheckConstraint {
+    analyzer_name: String,
+    metric_key: String,
+    execution: AnalyzerExecution,
+    strategy: Arc<dyn AnomalyDetectionStrategy>,
+    repository: Arc<dyn MetricsRepository>,
+    options: AnomalyCheckOptions,
+}
+
+impl AnomalyCheckConstraint {
+    pub(crate) fn new<A>(
+        analyzer: A,
+        strategy: impl AnomalyDetectionStrategy + 'static,
+        repository: Arc<dyn MetricsRepository>,
+        options: AnomalyCheckOptions,
+    ) -> Self
+    where
+        A: Analyzer + 'static,
+        A::Metric: Into<MetricValue> + 'static,
+    {
+        use futures::FutureExt;
+
+        let analyzer_name = analyzer.name().to_string();
+        let metric_key = analyzer.metric_key();
+        let analyzer = Arc::new(analyzer);
+        let execution: AnalyzerExecution = Box::new(move |ctx| {
+            let analyzer = analyzer.clone();
+            async move {
+                let state = analyzer.compute_state_from_data(ctx).await?;
+                let metric = analyzer.compute_metric_from_state(&state)?;
+                Ok((analyzer.metric_key(), metric.into()))
+            }
+            .boxed()
+        });
+
+        Self {
+            analyzer_name,
+            metric_key,
+            execution,
+            strategy: Arc::new(strategy),
+            repository,
+            options,
+        }
+    }
+
+    /// Loads the metric's history matching the configured tags, oldest first.
+    async fn load_history(&self) -> Result<Vec<MetricPoint>> {
+        let results = self
+            .repository
+            .load()
+            .await
+            .with_tags(self.options.tags.clone())
+            .sort(SortOrder::Ascending)
+            .execute()
+            .await?;
+
+        // Entries saved by other analyzers under the same tags carry no value for this metric
+        Ok(results
+            .into_iter()
+            .filter_map(|(key, context)| {
+                context
+                    .get_metric(&self.metric_key)
+                    .and_then(MetricValue::as_f64)
+                    .map(|value| MetricPoint::with_timestamp(value, key.as_datetime()))
+            })
+            .collect())
+    }
+
+    /// Appends the computed metric to the repository.
+    async fn save(&self, metric: MetricValue) -> Result<()> {
+        let mut context = AnalyzerContext::new();
+        context.store_metric(&self.metric_key, metric);
+        let key = stamp_suite_version(ResultKey::now().with_tags(self.options.tags.clone()));
+        self.repository.save(key, context).await
+    }
+}
+
+impl fmt::Debug for AnomalyCheckConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnomalyCheckConstraint")
+            .field("analyzer", &self.analyzer_name)
+            .field("metric_key", &self.metric_key)
+            .field("strategy", &self.strategy.name())
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Constraint for AnomalyCheckConstraint {
+    #[instrument(skip(self, ctx), fields(analyzer = %self.analyzer_name, strategy = %self.strategy.name()))]
+    async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult> {
+        let (_, metric) = (self.execution)(ctx).await?;
+        let value = metric.as_f64().ok_or_else(|| {
+            TermError::constraint_evaluation(
+                "anomaly_check",
+                format!(
+                    "Metric '{}' is not numeric: {}",
+                    self.metric_key,
+                    metric.to_string_pretty()
+                ),
+            )
+        })?;
+
+        let history = self.load_history().await?;
+        debug!(
+            metric = %self.metric_key,
+            value,
+            history = history.len(),
+            "Loaded metric history for anomaly check"
+        );
+
+        let result = if history.len() < self.options.min_history {
+            ConstraintResult::skipped(format!(
+                "Anomaly check on '{}' needs {} historical values, found {}",
+                self.metric_key,
+                self.options.min_history,
+                history.len()
+            ))
+        } else {
+            let detection = self
+                .strategy
+                .detect(&history, MetricPoint::new(value))
+                .await?;
+            if detection.is_anomaly {
+                let message = format!(
+                    "Anomaly detected in '{}' ({}): {}",
+                    self.metric_key,
+                    self.strategy.name(),
+                    detection.explanation
+                );
+                warn!("{message}");
+                ConstraintResult::failure_with_metric(value, message)
+            } else {
+                ConstraintResult::success_with_metric(value)
+            }
+        };
+
+        if self.options.save_metric {
+            self.save(metric).await?;
+        }
+
+        Ok(result)
+    }
+
+    fn name(&self) -> &str {
+        "anomaly_check"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::anomaly::RelativeRateOfChangeStrategy;
+    use crate::analyzers::basic::SizeAnalyzer;
+    use crate::core::ConstraintStatus;
+    use crate::repository::InMemoryRepository;
+
+    async fn context_with_rows(rows: usize) -> SessionContext {
+        let ctx = SessionContext::new();
+        let values = (0..rows)
+            .map(|i| format!("({i})"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        ctx.sql(&format!("CREATE TABLE data AS VALUES {values}"))
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_skips_and_saves() {
+        let repository = Arc::new(InMemoryRepository::new());
+        let constraint = AnomalyCheckConstraint::new(
+            SizeAnalyzer::new(),
+            RelativeRateOfChangeStrategy::new(0.5).unwrap(),
+            repository.clone(),
+            AnomalyCheckOptions::new("volume").min_history(1),
+        );
+
+        let ctx = context_with_rows(10).await;
+        let result = constraint.evaluate(&ctx).await.unwrap();
+        assert_eq!(result.status, ConstraintStatus::Skipped);
+        assert_eq!(repository.list_keys().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_metric_not_saved_when_disabled() {
+        let repository = Arc::new(InMemoryRepository::new());
+        let constraint = AnomalyCheckConstraint::new(
+            SizeAnalyzer::new(),
+            RelativeRateOfChangeStrategy::new(0.5).unwrap(),
+            repository.clone(),
+            AnomalyCheckOptions::new("volume").save_metric(false),
+        );
+
+        let ctx = context_with_rows(10).await;
+        constraint.evaluate(&ctx).await.unwrap();
+        assert!(repository.list_keys().await.unwrap().is_empty());
+    }
+}
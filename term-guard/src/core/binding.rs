@@ -0,0 +1,465 @@
+//! Suites bound to the tables of a session context.
+//!
+//! A [`ValidationSuite`] refers to its tables by name only. A suite cached in a
+//! long-running service and run against several session contexts can therefore
+//! validate a table that merely has the expected name, such as another tenant's
+//! copy, and report misleading results. [`ValidationSuite::bind`] captures the
+//! schema of every table the suite reads, and [`BoundSuite::run`] checks the
+//! context against these schemas before any constraint runs. Differences are
+//! reported as a [`BindingMismatch`] listing every differing column.
+//!
+//! [`ValidationSuite::bind_with_fingerprints`] also records the fingerprints of
+//! file-backed and versioned tables (see [`ResultCache`](super::ResultCache)), so
+//! that a run fails when such a table reads other files with the same schema.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use arrow::datatypes::SchemaRef;
+use datafusion::prelude::*;
+use tracing::{debug, instrument, warn};
+
+use super::{RunOptions, ValidationResult, ValidationSuite};
+use crate::prelude::*;
+
+/// A table captured when a suite was bound.
+#[derive(Debug, Clone)]
+pub struct BoundTable {
+    name: String,
+    schema: SchemaRef,
+    fingerprint: Option<String>,
+}
+
+impl BoundTable {
+    /// Returns the name of the table as the suite refers to it.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the schema captured at bind time.
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Returns the fingerprint captured at bind time, if one was requested and
+    /// the table has one.
+    pub fn fingerprint(&self) -> Option<&str> {
+        self.fingerprint.as_deref()
+    }
+}
+
+/// A validation suite bound to the schemas of its tables.
+///
+/// Created with [`ValidationSuite::bind`] or
+/// [`ValidationSuite::bind_with_fingerprints`]. Every run first verifies that
+/// the context's tables match the bound ones and fails with
+/// [`TermError::BindingMismatch`] otherwise.
+#[derive(Debug, Clone)]
+pub struct BoundSuite {
+    suite: ValidationSuite,
+    tables: Vec<BoundTable>,
+    fingerprints: bool,
+}
+
+impl BoundSuite {
+    /// Captures the tables of `suite` in `ctx`.
+    pub(crate) async fn capture(
+        suite: ValidationSuite,
+        ctx: &SessionContext,
+        fingerprints: bool,
+    ) -> Result<Self> {
+        let mut tables = Vec::new();
+        for name in suite.expected_tables() {
+            let Some(schema) = suite.table_schema(ctx, &name).await? else {
+                return Err(TermError::Configuration(format!(
+                    "Cannot bind suite '{}': table '{name}' is not registered",
+                    suite.name()
+                )));
+            };
+            let fingerprint = if fingerprints {
+                suite.table_fingerprint(ctx, &name).await?
+            } else {
+                None
+            };
+            tables.push(BoundTable {
+                name,
+                schema,
+                fingerprint,
+            });
+        }
+
+        debug!(
+            suite.name = %suite.name(),
+            tables = tables.len(),
+            "Bound validation suite"
+        );
+        Ok(Self {
+            suite,
+            tables,
+            fingerprints,
+        })
+    }
+
+    /// Returns the bound suite.
+    pub fn suite(&self) -> &ValidationSuite {
+        &self.suite
+    }
+
+    /// Returns the tables captured at bind time.
+    pub fn tables(&self) -> &[BoundTable] {
+        &self.tables
+    }
+
+    /// Checks that the tables of `ctx` match the bound tables.
+    ///
+    /// Columns are compared by name and data type; column order and nullability
+    /// are ignored. Returns [`TermError::BindingMismatch`] listing every
+    /// difference of every table.
+    pub async fn verify(&self, ctx: &SessionContext) -> Result<()> {
+        let mut mismatches = Vec::new();
+        for table in &self.tables {
+            let differences = match self.suite.table_schema(ctx, &table.name).await? {
+                None => vec![SchemaDifference::TableMissing],
+                Some(schema) => {
+                    let mut differences = compare_schemas(&table.schema, &schema);
+                    if self.fingerprints {
+                        if let Some(bound) = &table.fingerprint {
+                            let current = self.suite.table_fingerprint(ctx, &table.name).await?;
+                            if current.as_ref() != Some(bound) {
+                                differences.push(SchemaDifference::SourceChanged);
+                            }
+                        }
+                    }
+                    differences
+                }
+            };
+            if !differences.is_empty() {
+                mismatches.push(TableMismatch {
+                    table: table.name.clone(),
+                    differences,
+                });
+            }
+        }
+
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+        let mismatch = BindingMismatch {
+            suite: self.suite.name().to_string(),
+            tables: mismatches,
+        };
+        warn!(suite.name = %self.suite.name(), "{mismatch}");
+        Err(TermError::BindingMismatch(Box::new(mismatch)))
+    }
+
+    /// Verifies the context and runs the suite against it.
+    ///
+    /// See [`ValidationSuite::run`].
+    #[instrument(skip(self, ctx), fields(suite.name = %self.suite.name()))]
+    pub async fn run(&self, ctx: &SessionContext) -> Result<ValidationResult> {
+        self.verify(ctx).await?;
+        self.suite.run(ctx).await
+    }
+
+    /// Verifies the context and runs the suite with per-run options.
+    ///
+    /// See [`ValidationSuite::run_with`].
+    #[instrument(skip(self, ctx, options), fields(suite.name = %self.suite.name()))]
+    pub async fn run_with(
+        &self,
+        ctx: &SessionContext,
+        options: &RunOptions,
+    ) -> Result<ValidationResult> {
+        self.verify(ctx).await?;
+        self.suite.run_with(ctx, options).await
+    }
+}
+
+/// Lists the differences of `current` from the `bound` schema.
+fn compare_schemas(bound: &SchemaRef, current: &SchemaRef) -> Vec<SchemaDifference> {
+    let current_fields: HashMap<_, _> = current
+        .fields()
+        .iter()
+        .map(|field| (field.name().as_str(), field))
+        .collect();
+
+    let mut differences = Vec::new();
+    for field in bound.fields() {
+        match current_fields.get(field.name().as_str()) {
+            None => differences.push(SchemaDifference::ColumnMissing {
+                column: field.name().clone(),
+                data_type: field.data_type().to_string(),
+            }),
+            Some(current) if current.data_type() != field.data_type() => {
+                differences.push(SchemaDifference::TypeChanged {
+                    column: field.name().clone(),
+                    expected: field.data_type().to_string(),
+                    found: current.data_type().to_string(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for field in current.fields() {
+        if bound.field_with_name(field.name()).is_err() {
+            differences.push(SchemaDifference::ColumnAdded {
+                column: field.name().clone(),
+                data_type: field.data_type().to_string(),
+            });
+        }
+    }
+    differences
+}
+
+/// The differences between the tables of a context and those a suite was bound to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingMismatch {
+    /// Name of the bound suite
+    pub suite: String,
+    /// The tables that differ, in the order of [`BoundSuite::tables`]
+    pub tables: Vec<TableMismatch>,
+}
+
+/// The differences of one table from its bound schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableMismatch {
+    /// Name of the table
+    pub table: String,
+    /// What differs
+    pub differences: Vec<SchemaDifference>,
+}
+
+/// A difference between a table and its bound schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaDifference {
+    /// The table is not registered in the context
+    TableMissing,
+    /// A bound column is missing
+    ColumnMissing {
+        /// Name of the column
+        column: String,
+        /// Data type at bind time
+        data_type: String,
+    },
+    /// A column was not present at bind time
+    ColumnAdded {
+        /// Name of the column
+        column: String,
+        /// Current data type
+        data_type: String,
+    },
+    /// A column has a different data type
+    TypeChanged {
+        /// Name of the column
+        column: String,
+        /// Data type at bind time
+        expected: String,
+        /// Current data type
+        found: String,
+    },
+    /// The fingerprint of the table's source differs from the bound one
+    SourceChanged,
+}
+
+impl fmt::Display for SchemaDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaDifference::TableMissing => write!(f, "table is not registered"),
+            SchemaDifference::ColumnMissing { column, data_type } => {
+                write!(f, "column '{column}' ({data_type}) is missing")
+            }
+            SchemaDifference::ColumnAdded { column, data_type } => {
+                write!(f, "unexpected column '{column}' ({data_type})")
+            }
+            SchemaDifference::TypeChanged {
+                column,
+                expected,
+                found,
+            } => write!(f, "column '{column}' is {found}, expected {expected}"),
+            SchemaDifference::SourceChanged => {
+                write!(f, "source fingerprint differs from the bound one")
+            }
+        }
+    }
+}
+
+impl fmt::Display for BindingMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tables of the context differ from those suite '{}' was bound to",
+            self.suite
+        )?;
+        for table in &self.tables {
+            for difference in &table.differences {
+                write!(f, "\n  - {}: {difference}", table.table)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::Assertion;
+    use crate::core::Check;
+
+    async fn context(sql: &[&str]) -> SessionContext {
+        let ctx = SessionContext::new();
+        for statement in sql {
+            ctx.sql(statement).await.unwrap().collect().await.unwrap();
+        }
+        ctx
+    }
+
+    fn suite() -> ValidationSuite {
+        ValidationSuite::builder("orders")
+            .table_name("orders")
+            .check(
+                Check::builder("integrity")
+                    .has_size(Assertion::GreaterThan(0.0))
+                    .foreign_key("orders.customer_id", "customers.id")
+                    .build(),
+            )
+            .build()
+    }
+
+    const TENANT_A: [&str; 2] = [
+        "CREATE TABLE orders (id INT, customer_id INT) AS VALUES (1, 1)",
+        "CREATE TABLE customers (id INT, name VARCHAR) AS VALUES (1, 'ana')",
+    ];
+
+    #[test]
+    fn test_expected_tables() {
+        let tables: Vec<_> = suite().expected_tables().into_iter().collect();
+        assert_eq!(tables, ["customers", "orders"]);
+
+        let staged = ValidationSuite::builder("orders")
+            .table_name("orders")
+            .default_schema("staging")
+            .check(
+                Check::builder("integrity")
+                    .foreign_key("orders.customer_id", "crm.customers.id")
+                    .build(),
+            )
+            .build();
+        let tables: Vec<_> = staged.expected_tables().into_iter().collect();
+        assert_eq!(tables, ["crm.customers", "orders", "staging.orders"]);
+    }
+
+    #[tokio::test]
+    async fn test_bound_suite_runs_on_matching_context() -> Result<()> {
+        let bound = suite().bind(&context(&TENANT_A).await).await?;
+        assert_eq!(bound.tables().len(), 2);
+
+        // Other data with the same schema is accepted
+        let other = context(&[
+            "CREATE TABLE orders (customer_id INT, id INT) AS VALUES (2, 1)",
+            "CREATE TABLE customers (id INT, name VARCHAR) AS VALUES (2, 'ben')",
+        ])
+        .await;
+        assert!(bound.run(&other).await?.is_success());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mismatch_lists_differences() -> Result<()> {
+        let bound = suite().bind(&context(&TENANT_A).await).await?;
+        let other = context(&[
+            "CREATE TABLE orders (id INT, customer_id BIGINT, region DOUBLE) AS VALUES (1, 1, 0.5)",
+        ])
+        .await;
+
+        let Err(TermError::BindingMismatch(mismatch)) = bound.run(&other).await else {
+            panic!("expected a binding mismatch");
+        };
+        assert_eq!(
+            mismatch.tables,
+            vec![
+                TableMismatch {
+                    table: "customers".to_string(),
+                    differences: vec![SchemaDifference::TableMissing],
+                },
+                TableMismatch {
+                    table: "orders".to_string(),
+                    differences: vec![
+                        SchemaDifference::TypeChanged {
+                            column: "customer_id".to_string(),
+                            expected: "Int32".to_string(),
+                            found: "Int64".to_string(),
+                        },
+                        SchemaDifference::ColumnAdded {
+                            column: "region".to_string(),
+                            data_type: "Float64".to_string(),
+                        },
+                    ],
+                },
+            ]
+        );
+        assert_eq!(
+            mismatch.to_string(),
+            "tables of the context differ from those suite 'orders' was bound to\n  \
+             - customers: table is not registered\n  \
+             - orders: column 'customer_id' is Int64, expected Int32\n  \
+             - orders: unexpected column 'region' (Float64)"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bind_requires_registered_tables() {
+        let ctx = context(&TENANT_A[..1]).await;
+        let error = suite().bind(&ctx).await.unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("table 'customers' is not registered"),
+            "{error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fingerprints_detect_other_files() -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let (first, second) = (dir.path().join("a.csv"), dir.path().join("b.csv"));
+        std::fs::write(&first, "id\n1\n").unwrap();
+        std::fs::write(&second, "id\n2\n").unwrap();
+
+        let suite = ValidationSuite::builder("files")
+            .check(
+                Check::builder("volume")
+                    .has_size(Assertion::GreaterThan(0.0))
+                    .build(),
+            )
+            .build();
+        let register = |path: std::path::PathBuf| async move {
+            let ctx = SessionContext::new();
+            ctx.register_csv("data", path.to_str().unwrap(), CsvReadOptions::new())
+                .await
+                .unwrap();
+            ctx
+        };
+
+        let bound = suite
+            .bind_with_fingerprints(&register(first.clone()).await)
+            .await?;
+        assert!(bound.tables()[0].fingerprint().is_some());
+        assert!(bound.run(&register(first).await).await?.is_success());
+
+        let Err(TermError::BindingMismatch(mismatch)) =
+            bound.run(&register(second.clone()).await).await
+        else {
+            panic!("expected a binding mismatch");
+        };
+        assert_eq!(
+            mismatch.tables[0].differences,
+            vec![SchemaDifference::SourceChanged]
+        );
+
+        // Without fingerprints only the schema is compared
+        let bound = suite.bind(&register(second.clone()).await).await?;
+        assert!(bound.tables()[0].fingerprint().is_none());
+        Ok(())
+    }
+}
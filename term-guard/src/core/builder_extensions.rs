@@ -4,15 +4,17 @@
 //! with improved ergonomics and consistency while maintaining backward compatibility.
 
 use crate::constraints::{
-    Assertion, FormatOptions, FormatType, StatisticType, UniquenessOptions, UniquenessType,
+    Assertion, FormatOptions, FormatType, MultiStatisticalConstraint, StatisticType,
+    UniquenessOptions, UniquenessType,
 };
-use crate::core::{CheckBuilder, ConstraintOptions, LogicalOperator};
+use crate::core::{CheckBuilder, ConstraintOptions, IntoThreshold, LogicalOperator};
 use crate::prelude::*;
 
 /// Options for completeness constraints with fluent builder pattern.
 #[derive(Debug, Clone)]
 pub struct CompletenessOptions {
     threshold: Option<f64>,
+    threshold_param: Option<String>,
     operator: LogicalOperator,
     null_is_failure: bool,
 }
@@ -22,6 +24,7 @@ impl CompletenessOptions {
     pub fn full() -> Self {
         Self {
             threshold: Some(1.0),
+            threshold_param: None,
             operator: LogicalOperator::All,
             null_is_failure: true,
         }
@@ -31,6 +34,19 @@ impl CompletenessOptions {
     pub fn threshold(threshold: f64) -> Self {
         Self {
             threshold: Some(threshold),
+            threshold_param: None,
+            operator: LogicalOperator::All,
+            null_is_failure: true,
+        }
+    }
+
+    /// Creates options whose threshold is read from a named parameter at run time.
+    ///
+    /// See [`ParameterBag`](crate::core::ParameterBag) for how parameters are supplied.
+    pub fn threshold_param(name: impl Into<String>) -> Self {
+        Self {
+            threshold: None,
+            threshold_param: Some(name.into()),
             operator: LogicalOperator::All,
             null_is_failure: true,
         }
@@ -40,6 +56,7 @@ impl CompletenessOptions {
     pub fn at_least(n: usize) -> Self {
         Self {
             threshold: None,
+            threshold_param: None,
             operator: LogicalOperator::AtLeast(n),
             null_is_failure: true,
         }
@@ -49,6 +66,7 @@ impl CompletenessOptions {
     pub fn any() -> Self {
         Self {
             threshold: None,
+            threshold_param: None,
             operator: LogicalOperator::Any,
             null_is_failure: true,
         }
@@ -75,6 +93,9 @@ impl CompletenessOptions {
         if let Some(threshold) = self.threshold {
             options = options.with_threshold(threshold);
         }
+        if let Some(name) = self.threshold_param {
+            options = options.with_threshold_param(name);
+        }
 
         options
     }
@@ -150,6 +171,25 @@ impl StatisticalOptions {
         self
     }
 
+    /// Adds a constraint on the number of non-null values.
+    pub fn count(mut self, assertion: Assertion) -> Self {
+        self.statistics.push((StatisticType::Count, assertion));
+        self
+    }
+
+    /// Adds a constraint on the number of null values.
+    pub fn null_count(mut self, assertion: Assertion) -> Self {
+        self.statistics.push((StatisticType::NullCount, assertion));
+        self
+    }
+
+    /// Adds a constraint on the approximate number of distinct values.
+    pub fn approx_distinct(mut self, assertion: Assertion) -> Self {
+        self.statistics
+            .push((StatisticType::ApproxDistinct, assertion));
+        self
+    }
+
     /// Returns true if multiple statistics are configured.
     pub fn is_multi(&self) -> bool {
         self.statistics.len() > 1
@@ -168,6 +208,10 @@ impl StatisticalOptions {
 impl CheckBuilder {
     /// Adds statistical constraints using the new unified API.
     ///
+    /// A single statistic becomes a `StatisticalConstraint`. Several statistics are
+    /// combined into one `MultiStatisticalConstraint` that computes all of them in a
+    /// single query; its failure message names each statistic that failed.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -195,6 +239,8 @@ impl CheckBuilder {
     ///             .max(Assertion::LessThan(5000.0))
     ///             .mean(Assertion::Between(100.0, 1000.0))
     ///             .percentile(0.95, Assertion::LessThan(2000.0))
+    ///             .null_count(Assertion::Equals(0.0))
+    ///             .approx_distinct(Assertion::GreaterThan(10.0))
     ///     )?
     ///     .build();
     /// # Ok(check)
@@ -206,11 +252,16 @@ impl CheckBuilder {
         options: StatisticalOptions,
     ) -> Result<Self> {
         let column_str = column.into();
-        let stats = options.into_statistics();
+
+        if options.is_multi() {
+            let constraint =
+                MultiStatisticalConstraint::new(column_str, options.into_statistics())?;
+            return Ok(self.constraint(constraint));
+        }
 
         // Use the existing statistic method
         let mut result = self;
-        for (stat_type, assertion) in stats {
+        for (stat_type, assertion) in options.into_statistics() {
             result = result.statistic(column_str.clone(), stat_type, assertion);
         }
 
@@ -306,7 +357,7 @@ impl CheckBuilder {
     /// # Ok(check)
     /// # }
     /// ```
-    pub fn email(self, column: impl Into<String>, threshold: f64) -> Self {
+    pub fn email(self, column: impl Into<String>, threshold: impl IntoThreshold) -> Self {
         self.has_format(
             column,
             FormatType::Email,
@@ -332,7 +383,7 @@ impl CheckBuilder {
     /// # Ok(check)
     /// # }
     /// ```
-    pub fn url(self, column: impl Into<String>, threshold: f64) -> Self {
+    pub fn url(self, column: impl Into<String>, threshold: impl IntoThreshold) -> Self {
         self.has_format(
             column,
             FormatType::Url {
@@ -361,7 +412,7 @@ impl CheckBuilder {
     pub fn phone(
         self,
         column: impl Into<String>,
-        threshold: f64,
+        threshold: impl IntoThreshold,
         country_code: Option<&str>,
     ) -> Self {
         let format_type = FormatType::Phone {
@@ -396,7 +447,7 @@ impl CheckBuilder {
     /// # Ok(check)
     /// # }
     /// ```
-    pub fn contains_ssn(self, column: impl Into<String>, threshold: f64) -> Self {
+    pub fn contains_ssn(self, column: impl Into<String>, threshold: impl IntoThreshold) -> Self {
         self.has_format(
             column,
             FormatType::SocialSecurityNumber,
@@ -464,6 +515,40 @@ mod tests {
         assert!(options.is_multi());
         let stats = options.into_statistics();
         assert_eq!(stats.len(), 3);
+
+        let options = StatisticalOptions::new()
+            .count(Assertion::GreaterThan(0.0))
+            .null_count(Assertion::Equals(0.0))
+            .approx_distinct(Assertion::LessThan(10.0));
+        let stats = options.into_statistics();
+        assert_eq!(stats[0].0, StatisticType::Count);
+        assert_eq!(stats[1].0, StatisticType::NullCount);
+        assert_eq!(stats[2].0, StatisticType::ApproxDistinct);
+    }
+
+    #[test]
+    fn test_statistics_combined_into_one_constraint() {
+        let check = Check::builder("test")
+            .statistics(
+                "age",
+                StatisticalOptions::new()
+                    .min(Assertion::GreaterThanOrEqual(0.0))
+                    .count(Assertion::GreaterThan(0.0))
+                    .approx_distinct(Assertion::GreaterThan(1.0)),
+            )
+            .unwrap()
+            .build();
+        assert_eq!(check.constraints().len(), 1);
+        assert_eq!(check.constraints()[0].name(), "multi_statistical");
+
+        let check = Check::builder("test")
+            .statistics(
+                "age",
+                StatisticalOptions::new().null_count(Assertion::Equals(0.0)),
+            )
+            .unwrap()
+            .build();
+        assert_eq!(check.constraints()[0].name(), "null_count");
     }
 
     #[test]
@@ -37,13 +37,20 @@
 //! # }
 //! ```
 
-use super::{constraint::BoxedConstraint, Constraint, Level};
+use super::unified::configuration_message;
+use super::{
+    constraint::BoxedConstraint, ConfigIssue, ConfigReport, Constraint, IntoThreshold, KnownIssue,
+    Level, QualityDimension,
+};
 use crate::constraints::{
     ApproxCountDistinctConstraint, Assertion, ColumnCountConstraint, CorrelationConstraint,
     CustomSqlConstraint, DataTypeConstraint, FormatConstraint, FormatOptions, FormatType,
     HistogramAssertion, HistogramConstraint, NullHandling, QuantileConstraint, SizeConstraint,
     UniquenessConstraint, UniquenessOptions, UniquenessType,
 };
+use crate::error::{Result, TermError};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::sync::Arc;
 
 /// A validation check containing one or more constraints.
@@ -147,12 +154,28 @@ use std::sync::Arc;
 pub struct Check {
     /// The name of the check
     name: String,
+    /// Explicit identifier that stays stable when the check is renamed
+    id: Option<String>,
     /// The severity level of the check
     level: Level,
     /// Optional description of what this check validates
     description: Option<String>,
     /// The constraints that make up this check
     constraints: Vec<Arc<dyn Constraint>>,
+    /// Report names of constraints expanded from column templates, by index
+    labels: HashMap<usize, String>,
+    /// Tags used to select the check in filtered runs
+    tags: BTreeSet<String>,
+    /// Key-value metadata such as the owning team, recorded as metric tags
+    metadata: BTreeMap<String, String>,
+    /// Known issue waiving the check's failures
+    known_issue: Option<KnownIssue>,
+    /// Quality dimension overriding the constraints' own
+    dimension: Option<QualityDimension>,
+    /// Quality dimensions of individual constraints, by index
+    constraint_dimensions: HashMap<usize, QualityDimension>,
+    /// Invalid builder arguments, such as out-of-range thresholds
+    configuration_issues: Vec<ConfigIssue>,
 }
 
 impl Check {
@@ -178,6 +201,19 @@ impl Check {
         &self.name
     }
 
+    /// Returns the explicit identifier of the check, if one was set.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Returns a copy of the check under another name.
+    pub(crate) fn renamed(&self, name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..self.clone()
+        }
+    }
+
     /// Returns the severity level of the check.
     pub fn level(&self) -> Level {
         self.level
@@ -192,6 +228,92 @@ impl Check {
     pub fn constraints(&self) -> &[Arc<dyn Constraint>] {
         &self.constraints
     }
+
+    /// Returns the tags of the check.
+    pub fn tags(&self) -> &BTreeSet<String> {
+        &self.tags
+    }
+
+    /// Returns whether the check has `tag`.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
+
+    /// Returns the key-value metadata of the check.
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// Returns the known issue waiving this check's failures, if any.
+    pub fn known_issue(&self) -> Option<&KnownIssue> {
+        self.known_issue.as_ref()
+    }
+
+    /// Returns the quality dimension set for the whole check, if any.
+    pub fn dimension(&self) -> Option<&QualityDimension> {
+        self.dimension.as_ref()
+    }
+
+    /// Returns the quality dimension of the constraint at `index`.
+    ///
+    /// A dimension set with [`CheckBuilder::constraint_with_dimension`] takes
+    /// precedence over one set with [`CheckBuilder::dimension`], which takes
+    /// precedence over the constraint's own [`Constraint::dimension`].
+    pub fn constraint_dimension(&self, index: usize) -> Option<QualityDimension> {
+        self.constraint_dimensions
+            .get(&index)
+            .or(self.dimension.as_ref())
+            .cloned()
+            .or_else(|| self.constraints.get(index)?.dimension())
+    }
+
+    /// Returns the name reported for the constraint at `index`.
+    ///
+    /// Constraints added by [`CheckBuilder::apply_to_columns`] are reported as
+    /// `name.column` so failures on different columns can be told apart; all
+    /// others are reported by their constraint name.
+    pub fn constraint_label(&self, index: usize) -> &str {
+        match self.labels.get(&index) {
+            Some(label) => label,
+            None => self.constraints.get(index).map_or("", |c| c.name()),
+        }
+    }
+
+    /// Returns the messages of the invalid builder arguments recorded while
+    /// building the check.
+    ///
+    /// Constraints with invalid arguments, such as a threshold outside 0.0 to 1.0,
+    /// are left out of the check. Suites refuse to run while any check has errors.
+    pub fn configuration_errors(&self) -> Vec<String> {
+        self.configuration_issues
+            .iter()
+            .map(|issue| issue.message.clone())
+            .collect()
+    }
+
+    /// Returns the invalid builder arguments recorded while building the check.
+    ///
+    /// The path of an issue is `constraints[i]`, where `i` is the position of
+    /// the rejected constraint among the constraints declared on the builder.
+    pub fn configuration_issues(&self) -> &[ConfigIssue] {
+        &self.configuration_issues
+    }
+
+    /// Fails if any invalid builder arguments were recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::InvalidConfiguration`] listing every recorded error.
+    pub fn validate(&self) -> Result<()> {
+        let mut report = ConfigReport::new(format!("check '{}'", self.name));
+        report.extend(self.configuration_issues.iter().cloned());
+        report.into_result().map(|_| ())
+    }
+
+    /// Appends a constraint to an already built check.
+    pub(crate) fn push_constraint(&mut self, constraint: Arc<dyn Constraint>) {
+        self.constraints.push(constraint);
+    }
 }
 
 /// Builder for constructing `Check` instances.
@@ -209,9 +331,17 @@ impl Check {
 #[derive(Debug)]
 pub struct CheckBuilder {
     name: String,
+    id: Option<String>,
     level: Level,
     description: Option<String>,
     constraints: Vec<Arc<dyn Constraint>>,
+    labels: HashMap<usize, String>,
+    tags: BTreeSet<String>,
+    metadata: BTreeMap<String, String>,
+    known_issue: Option<KnownIssue>,
+    dimension: Option<QualityDimension>,
+    constraint_dimensions: HashMap<usize, QualityDimension>,
+    configuration_issues: Vec<ConfigIssue>,
 }
 
 impl CheckBuilder {
@@ -219,14 +349,72 @@ impl CheckBuilder {
     pub fn new(name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
+            id: None,
             level: Level::default(),
             description: None,
             constraints: Vec::new(),
+            labels: HashMap::new(),
+            tags: BTreeSet::new(),
+            metadata: BTreeMap::new(),
+            known_issue: None,
+            dimension: None,
+            constraint_dimensions: HashMap::new(),
+            configuration_issues: Vec::new(),
         }
     }
 
+    /// Converts a threshold argument, recording a configuration error that names
+    /// the check, the builder method and the column if it is out of range.
+    fn checked_threshold(
+        &mut self,
+        constraint: &str,
+        column: &str,
+        threshold: impl IntoThreshold,
+    ) -> Option<f64> {
+        match threshold.into_threshold() {
+            Ok(threshold) => Some(threshold.value()),
+            Err(e) => {
+                self.record_configuration_error(constraint, column, e);
+                None
+            }
+        }
+    }
+
+    fn record_configuration_error(&mut self, constraint: &str, column: &str, error: TermError) {
+        self.record_constraint_issue(
+            &format!("constraint {constraint} on column '{column}'"),
+            error,
+        );
+    }
+
+    /// Records `error` for a constraint defined by a SQL query rather than a column.
+    fn record_query_error(&mut self, constraint: &str, error: TermError) {
+        self.record_constraint_issue(&format!("constraint {constraint}"), error);
+    }
+
+    fn record_constraint_issue(&mut self, constraint: &str, error: TermError) {
+        // Rejected constraints are left out, so count them to keep declaration order
+        let index = self.constraints.len() + self.configuration_issues.len();
+        let message = format!(
+            "Check '{}', {constraint}: {}",
+            self.name,
+            configuration_message(error)
+        );
+        self.configuration_issues.push(
+            ConfigIssue::error(format!("constraints[{index}]"), message)
+                .in_check(&self.name)
+                .at_constraint(index),
+        );
+    }
+
     /// Sets the severity level for the check.
     ///
+    /// Failures of [`Level::Error`] checks fail the validation run, failures of
+    /// [`Level::Warning`] checks are reported as issues without failing it, and failures
+    /// of [`Level::Info`] checks are recorded as observations. Observations are counted in
+    /// `ValidationMetrics::info_findings` rather than `failed_checks`, are left out of the
+    /// success rate, and are listed in their own section by the formatters.
+    ///
     /// # Arguments
     ///
     /// * `level` - The severity level
@@ -240,6 +428,20 @@ impl CheckBuilder {
     ///     .level(Level::Error)
     ///     .build();
     /// ```
+    ///
+    /// Profiling checks whose failures should be tracked without counting against
+    /// the suite:
+    ///
+    /// ```rust
+    /// use term_guard::constraints::Assertion;
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let profile = Check::builder("order_profile")
+    ///     .level(Level::Info)
+    ///     .has_mean("order_value", Assertion::Between(10.0, 1000.0))
+    ///     .build();
+    /// assert_eq!(profile.level(), Level::Info);
+    /// ```
     pub fn level(mut self, level: Level) -> Self {
         self.level = level;
         self
@@ -255,6 +457,117 @@ impl CheckBuilder {
         self
     }
 
+    /// Sets an explicit identifier for the check.
+    ///
+    /// The identifier is reported as [`ValidationIssue::check_id`](super::ValidationIssue::check_id)
+    /// on every failure of the check. Unlike the name, which is a display label, it
+    /// is meant to stay fixed so alerting and suppression rules keyed on it survive
+    /// renames. Term never changes or derives it; change it yourself when the
+    /// check's meaning changes, for example by bumping a version suffix.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, ConstraintOptions};
+    ///
+    /// let check = Check::builder("Orders completeness")
+    ///     .id("orders.completeness.v1")
+    ///     .completeness("customer_id", ConstraintOptions::new().with_threshold(1.0))
+    ///     .build();
+    /// assert_eq!(check.id(), Some("orders.completeness.v1"));
+    /// ```
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Marks the check's failures as a known issue tracked by `ticket`.
+    ///
+    /// Failures are still reported, annotated with the ticket, but they no longer
+    /// fail the run. When `until` has passed, failures count again and their
+    /// message notes the expired waiver. Errors evaluating a constraint, such as
+    /// a missing column, are never waived. See [`KnownIssue`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::{Duration, Utc};
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("order_totals")
+    ///     .level(Level::Error)
+    ///     .known_issue("JIRA-1234", Some(Utc::now() + Duration::days(30)))
+    ///     .build();
+    /// assert_eq!(check.known_issue().unwrap().ticket, "JIRA-1234");
+    /// ```
+    pub fn known_issue(mut self, ticket: impl Into<String>, until: Option<DateTime<Utc>>) -> Self {
+        self.known_issue = Some(KnownIssue::new(ticket, until));
+        self
+    }
+
+    /// Adds a tag used to select the check with a [`CheckFilter`](super::CheckFilter).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::Check;
+    ///
+    /// let check = Check::builder("revenue_totals").tag("finance").build();
+    /// assert!(check.has_tag("finance"));
+    /// ```
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.insert(tag.into());
+        self
+    }
+
+    /// Adds key-value metadata, such as the team owning the check.
+    ///
+    /// Metadata is listed in [`ValidationReport::check_metadata`] and becomes
+    /// `check.<key>` tags of the metrics stored with
+    /// [`ReportAggregator::save_check_metrics`](super::ReportAggregator::save_check_metrics),
+    /// so that stored results can be queried by owner or domain.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::Check;
+    ///
+    /// let check = Check::builder("revenue_totals")
+    ///     .metadata("owner", "finance")
+    ///     .metadata("domain", "billing")
+    ///     .build();
+    /// assert_eq!(check.metadata()["owner"], "finance");
+    /// ```
+    ///
+    /// [`ValidationReport::check_metadata`]: super::ValidationReport::check_metadata
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Reports every constraint of the check under the quality dimension `dimension`.
+    ///
+    /// This overrides the dimensions built-in constraints declare, such as
+    /// [`QualityDimension::Completeness`] for completeness constraints. See
+    /// [`ValidationReport::dimension_summary`](super::ValidationReport::dimension_summary).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::Assertion;
+    /// use term_guard::core::{Check, QualityDimension};
+    ///
+    /// let check = Check::builder("order_amounts")
+    ///     .dimension(QualityDimension::Accuracy)
+    ///     .has_min("amount", Assertion::GreaterThanOrEqual(0.0))
+    ///     .build();
+    /// assert_eq!(check.constraint_dimension(0), Some(QualityDimension::Accuracy));
+    /// ```
+    pub fn dimension(mut self, dimension: QualityDimension) -> Self {
+        self.dimension = Some(dimension);
+        self
+    }
+
     /// Adds a constraint to the check.
     ///
     /// # Arguments
@@ -265,6 +578,41 @@ impl CheckBuilder {
         self
     }
 
+    /// Adds a constraint reported under the quality dimension `dimension`.
+    ///
+    /// The dimension takes precedence over the check's [`dimension`](Self::dimension)
+    /// and the constraint's own.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::CompletenessConstraint;
+    /// use term_guard::core::{Check, QualityDimension};
+    ///
+    /// let check = Check::builder("customers")
+    ///     .constraint(CompletenessConstraint::with_threshold("email", 0.99))
+    ///     .constraint_with_dimension(
+    ///         CompletenessConstraint::with_threshold("consent_given_at", 1.0),
+    ///         QualityDimension::Custom("compliance".to_string()),
+    ///     )
+    ///     .build();
+    /// assert_eq!(check.constraint_dimension(0), Some(QualityDimension::Completeness));
+    /// assert_eq!(
+    ///     check.constraint_dimension(1),
+    ///     Some(QualityDimension::Custom("compliance".to_string()))
+    /// );
+    /// ```
+    pub fn constraint_with_dimension(
+        mut self,
+        constraint: impl Constraint + 'static,
+        dimension: QualityDimension,
+    ) -> Self {
+        self.constraint_dimensions
+            .insert(self.constraints.len(), dimension);
+        self.constraints.push(Arc::new(constraint));
+        self
+    }
+
     /// Adds a boxed constraint to the check.
     ///
     /// # Arguments
@@ -299,6 +647,56 @@ impl CheckBuilder {
         self
     }
 
+    /// Expands a template of constraints over a list of columns.
+    ///
+    /// `template` is called once per column with the builder and the column
+    /// name, and adds the constraints for that column. Expansion happens here,
+    /// so the built check holds plain constraints the optimizer can combine.
+    /// In reports, each expanded constraint is named `name.column`.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The columns to expand the template over
+    /// * `template` - Adds the constraints for one column
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, ConstraintOptions};
+    /// use term_guard::constraints::Assertion;
+    ///
+    /// let measures = ["revenue", "cost", "quantity"];
+    /// let check = Check::builder("measures")
+    ///     .apply_to_columns(measures, |builder, column| {
+    ///         builder
+    ///             .completeness(column, ConstraintOptions::new().with_threshold(0.99))
+    ///             .has_min(column, Assertion::GreaterThanOrEqual(0.0))
+    ///             .has_standard_deviation(column, Assertion::GreaterThan(0.0))
+    ///     })
+    ///     .build();
+    ///
+    /// assert_eq!(check.constraints().len(), 9);
+    /// assert_eq!(check.constraint_label(4), "min.cost");
+    /// ```
+    pub fn apply_to_columns<I, S, F>(mut self, columns: I, template: F) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        F: Fn(Self, &str) -> Self,
+    {
+        for column in columns {
+            let column = column.as_ref();
+            let start = self.constraints.len();
+            self = template(self, column);
+
+            for index in start..self.constraints.len() {
+                let name = self.constraints[index].name();
+                self.labels.insert(index, format!("{name}.{column}"));
+            }
+        }
+        self
+    }
+
     // Builder methods
 
     /// Adds a constraint that checks the dataset size (row count).
@@ -324,6 +722,47 @@ impl CheckBuilder {
         self
     }
 
+    /// Adds a constraint that checks the dataset size against a manifest file.
+    ///
+    /// The expected row count is read from the JSON file at `path` each time the
+    /// constraint is evaluated, at the location given by `json_pointer`. A missing
+    /// file or a pointer that does not resolve to a number is reported as a
+    /// configuration error rather than a size mismatch.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the manifest JSON file
+    /// * `json_pointer` - JSON pointer to the expected row count, such as `/expected_rows`
+    /// * `tolerance` - How far the row count may deviate from the expected value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, Level};
+    /// use term_guard::constraints::SizeTolerance::WithinPercent;
+    ///
+    /// let check = Check::builder("batch_completeness")
+    ///     .level(Level::Error)
+    ///     .has_size_matching_manifest("data/batch_42/manifest.json", "/expected_rows", WithinPercent(0.5))
+    ///     .build();
+    /// ```
+    ///
+    /// For other sources of the expected value, use `ManifestSizeConstraint` with an
+    /// `ExpectedValueProvider`.
+    pub fn has_size_matching_manifest(
+        mut self,
+        path: impl Into<std::path::PathBuf>,
+        json_pointer: impl Into<String>,
+        tolerance: crate::constraints::SizeTolerance,
+    ) -> Self {
+        use crate::constraints::{JsonFileProvider, ManifestSizeConstraint};
+        self.constraints.push(Arc::new(ManifestSizeConstraint::new(
+            JsonFileProvider::new(path, json_pointer),
+            tolerance,
+        )));
+        self
+    }
+
     /// Adds a constraint that checks the number of columns in the dataset.
     ///
     /// This constraint validates that the dataset has the expected number of columns
@@ -644,11 +1083,17 @@ impl CheckBuilder {
     ///     .has_consistent_data_type("user_id", 0.95)
     ///     .build();
     /// ```
-    ///
-    /// # Panics
-    ///
-    /// Panics if threshold is not between 0.0 and 1.0
-    pub fn has_consistent_data_type(mut self, column: impl Into<String>, threshold: f64) -> Self {
+    pub fn has_consistent_data_type(
+        mut self,
+        column: impl Into<String>,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) =
+            self.checked_threshold("has_consistent_data_type", &column, threshold)
+        else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             DataTypeConstraint::type_consistency(column, threshold)
                 .expect("Invalid data type consistency parameters"),
@@ -693,6 +1138,67 @@ impl CheckBuilder {
         self
     }
 
+    /// Adds a constraint checking the single numeric value returned by a SQL query.
+    ///
+    /// The query can be any single `SELECT`, such as a count over a
+    /// `GROUP BY ... HAVING`; `{table}` is replaced with the validated table. The
+    /// value is checked against `assertion` and reported as the metric.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::Assertion;
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("business_rules")
+    ///     .level(Level::Error)
+    ///     .satisfies_scalar(
+    ///         "SELECT count(*) FROM (SELECT order_id FROM {table} GROUP BY order_id HAVING count(*) > 1)",
+    ///         Assertion::Equals(0.0),
+    ///     )
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// SQL that is not a single `SELECT` query is recorded as a configuration
+    /// error of the check; see [`try_build`](Self::try_build).
+    pub fn satisfies_scalar(mut self, sql: impl Into<String>, assertion: Assertion) -> Self {
+        match CustomSqlConstraint::scalar_query(sql, assertion) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_query_error("satisfies_scalar", e),
+        }
+        self
+    }
+
+    /// Adds a constraint requiring a SQL query to return true.
+    ///
+    /// The query can be any single `SELECT` returning one boolean; `{table}` is
+    /// replaced with the validated table.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("reconciliation")
+    ///     .level(Level::Error)
+    ///     .satisfies_bool("SELECT sum(amount) = sum(paid) FROM {table}")
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// SQL that is not a single `SELECT` query is recorded as a configuration
+    /// error of the check; see [`try_build`](Self::try_build).
+    pub fn satisfies_bool(mut self, sql: impl Into<String>) -> Self {
+        match CustomSqlConstraint::boolean_scalar(sql) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_query_error("satisfies_bool", e),
+        }
+        self
+    }
+
     /// Adds a constraint that analyzes value distribution and applies custom assertions.
     ///
     /// This constraint computes a histogram of value frequencies in the specified column
@@ -732,6 +1238,47 @@ impl CheckBuilder {
         self
     }
 
+    /// Adds a constraint that analyzes value distribution with constraint options.
+    ///
+    /// Like [`has_histogram`](Self::has_histogram), reading the memory strategy
+    /// from `options`: with [`MemoryStrategy::Sketched`](crate::core::MemoryStrategy::Sketched)
+    /// at most a fixed number of values are tracked and the histogram of a
+    /// column with more distinct values is approximate. An invalid strategy, or
+    /// one histograms don't support, is recorded as a configuration error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, ConstraintOptions, MemoryStrategy};
+    /// use std::sync::Arc;
+    ///
+    /// let check = Check::builder("distribution_validation")
+    ///     .has_histogram_with_options(
+    ///         "product_id",
+    ///         Arc::new(|hist| hist.most_common_ratio() < 0.1),
+    ///         ConstraintOptions::new().with_memory_strategy(MemoryStrategy::sketched(5_000)),
+    ///     )
+    ///     .build();
+    /// assert_eq!(check.constraints().len(), 1);
+    /// ```
+    pub fn has_histogram_with_options(
+        mut self,
+        column: impl Into<String>,
+        assertion: HistogramAssertion,
+        options: crate::core::ConstraintOptions,
+    ) -> Self {
+        let column = column.into();
+        let constraint = options.validate().and_then(|()| {
+            HistogramConstraint::new(column.clone(), assertion)
+                .with_memory_strategy(options.memory_strategy)
+        });
+        match constraint {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("has_histogram", &column, e),
+        }
+        self
+    }
+
     /// Adds a constraint that analyzes value distribution with a custom description.
     ///
     /// This is similar to `has_histogram` but allows providing a description of what
@@ -824,19 +1371,23 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column name is invalid, threshold is out of range,
-    /// or regex pattern is invalid.
+    /// An invalid column name or regex pattern is recorded as a configuration
+    /// error of the check; see [`try_build`](Self::try_build).
     pub fn has_format(
         mut self,
         column: impl Into<String>,
         format: FormatType,
-        threshold: f64,
+        threshold: impl IntoThreshold,
         options: FormatOptions,
     ) -> Self {
-        self.constraints.push(Arc::new(
-            FormatConstraint::new(column, format, threshold, options)
-                .expect("Invalid column, format, threshold, or options"),
-        ));
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("has_format", &column, threshold) else {
+            return self;
+        };
+        match FormatConstraint::new(column.clone(), format, threshold, options) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("has_format", &column, e),
+        }
         self
     }
 
@@ -864,18 +1415,23 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column name is invalid, threshold is out of range,
-    /// or regex pattern is invalid.
+    /// An invalid column name or pattern, including a pattern exceeding the
+    /// default [`RegexLimits`](crate::core::RegexLimits), is recorded as a
+    /// configuration error of the check; see [`try_build`](Self::try_build).
     pub fn validates_regex(
         mut self,
         column: impl Into<String>,
         pattern: impl Into<String>,
-        threshold: f64,
+        threshold: impl IntoThreshold,
     ) -> Self {
-        self.constraints.push(Arc::new(
-            FormatConstraint::regex(column, pattern, threshold)
-                .expect("Invalid column, pattern, or threshold"),
-        ));
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_regex", &column, threshold) else {
+            return self;
+        };
+        match FormatConstraint::regex(column.clone(), pattern, threshold) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("validates_regex", &column, e),
+        }
         self
     }
 
@@ -902,8 +1458,16 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column name is invalid or threshold is out of range.
-    pub fn validates_email(mut self, column: impl Into<String>, threshold: f64) -> Self {
+    /// Returns error if column name is invalid.
+    pub fn validates_email(
+        mut self,
+        column: impl Into<String>,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_email", &column, threshold) else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             FormatConstraint::email(column, threshold).expect("Invalid column or threshold"),
         ));
@@ -934,13 +1498,17 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column name is invalid or threshold is out of range.
+    /// Returns error if column name is invalid.
     pub fn validates_url(
         mut self,
         column: impl Into<String>,
-        threshold: f64,
+        threshold: impl IntoThreshold,
         allow_localhost: bool,
     ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_url", &column, threshold) else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             FormatConstraint::url(column, threshold, allow_localhost)
                 .expect("Invalid column or threshold"),
@@ -977,13 +1545,18 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column name is invalid or threshold is out of range.
+    /// Returns error if column name is invalid.
     pub fn validates_credit_card(
         mut self,
         column: impl Into<String>,
-        threshold: f64,
+        threshold: impl IntoThreshold,
         detect_only: bool,
     ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_credit_card", &column, threshold)
+        else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             FormatConstraint::credit_card(column, threshold, detect_only)
                 .expect("Invalid column or threshold"),
@@ -1015,13 +1588,17 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column name is invalid or threshold is out of range.
+    /// Returns error if column name is invalid.
     pub fn validates_phone(
         mut self,
         column: impl Into<String>,
-        threshold: f64,
+        threshold: impl IntoThreshold,
         country: Option<&str>,
     ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_phone", &column, threshold) else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             FormatConstraint::phone(column, threshold, country.map(|s| s.to_string()))
                 .expect("Invalid column or threshold"),
@@ -1053,13 +1630,18 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column name is invalid or threshold is out of range.
+    /// Returns error if column name is invalid.
     pub fn validates_postal_code(
         mut self,
         column: impl Into<String>,
-        threshold: f64,
+        threshold: impl IntoThreshold,
         country: &str,
     ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_postal_code", &column, threshold)
+        else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             FormatConstraint::postal_code(column, threshold, country)
                 .expect("Invalid column or threshold"),
@@ -1090,8 +1672,16 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column name is invalid or threshold is out of range.
-    pub fn validates_uuid(mut self, column: impl Into<String>, threshold: f64) -> Self {
+    /// Returns error if column name is invalid.
+    pub fn validates_uuid(
+        mut self,
+        column: impl Into<String>,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_uuid", &column, threshold) else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             FormatConstraint::uuid(column, threshold).expect("Invalid column or threshold"),
         ));
@@ -1121,8 +1711,16 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column name is invalid or threshold is out of range.
-    pub fn validates_ipv4(mut self, column: impl Into<String>, threshold: f64) -> Self {
+    /// Returns error if column name is invalid.
+    pub fn validates_ipv4(
+        mut self,
+        column: impl Into<String>,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_ipv4", &column, threshold) else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             FormatConstraint::ipv4(column, threshold).expect("Invalid column or threshold"),
         ));
@@ -1151,8 +1749,16 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column name is invalid or threshold is out of range.
-    pub fn validates_ipv6(mut self, column: impl Into<String>, threshold: f64) -> Self {
+    /// Returns error if column name is invalid.
+    pub fn validates_ipv6(
+        mut self,
+        column: impl Into<String>,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_ipv6", &column, threshold) else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             FormatConstraint::ipv6(column, threshold).expect("Invalid column or threshold"),
         ));
@@ -1182,8 +1788,16 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column name is invalid or threshold is out of range.
-    pub fn validates_json(mut self, column: impl Into<String>, threshold: f64) -> Self {
+    /// Returns error if column name is invalid.
+    pub fn validates_json(
+        mut self,
+        column: impl Into<String>,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_json", &column, threshold) else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             FormatConstraint::json(column, threshold).expect("Invalid column or threshold"),
         ));
@@ -1213,8 +1827,18 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column name is invalid or threshold is out of range.
-    pub fn validates_iso8601_datetime(mut self, column: impl Into<String>, threshold: f64) -> Self {
+    /// Returns error if column name is invalid.
+    pub fn validates_iso8601_datetime(
+        mut self,
+        column: impl Into<String>,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) =
+            self.checked_threshold("validates_iso8601_datetime", &column, threshold)
+        else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             FormatConstraint::iso8601_datetime(column, threshold)
                 .expect("Invalid column or threshold"),
@@ -1222,6 +1846,214 @@ impl CheckBuilder {
         self
     }
 
+    /// Adds an IBAN validation constraint.
+    ///
+    /// This is a convenience method for `has_format()` with `FormatType::Iban`.
+    /// Leading and trailing whitespace is trimmed and spaces between groups of four
+    /// characters are accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to validate
+    /// * `threshold` - The minimum ratio of values that must be valid IBANs, including the mod-97 checksum (0.0 to 1.0)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("iban_validation")
+    ///     .level(Level::Error)
+    ///     .validates_iban("account_iban", 1.0)
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// An invalid column name is recorded as a configuration error of the
+    /// check; see [`try_build`](Self::try_build).
+    pub fn validates_iban(
+        mut self,
+        column: impl Into<String>,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_iban", &column, threshold) else {
+            return self;
+        };
+        match FormatConstraint::iban(column.clone(), threshold) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("validates_iban", &column, e),
+        }
+        self
+    }
+
+    /// Adds a MAC address validation constraint.
+    ///
+    /// This is a convenience method for `has_format()` with `FormatType::MacAddress`.
+    /// Colon (`00:1A:2B:3C:4D:5E`), hyphen and dotted (`001A.2B3C.4D5E`) notations are accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to validate
+    /// * `threshold` - The minimum ratio of values that must be valid MAC addresses (0.0 to 1.0)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("mac_validation")
+    ///     .level(Level::Error)
+    ///     .validates_mac_address("device_mac", 0.99)
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// An invalid column name is recorded as a configuration error of the
+    /// check; see [`try_build`](Self::try_build).
+    pub fn validates_mac_address(
+        mut self,
+        column: impl Into<String>,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_mac_address", &column, threshold)
+        else {
+            return self;
+        };
+        match FormatConstraint::mac_address(column.clone(), threshold) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("validates_mac_address", &column, e),
+        }
+        self
+    }
+
+    /// Adds a semantic version validation constraint.
+    ///
+    /// This is a convenience method for `has_format()` with `FormatType::SemVer`.
+    /// Versions follow `MAJOR.MINOR.PATCH` with optional pre-release and build metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to validate
+    /// * `threshold` - The minimum ratio of values that must be valid semantic versions (0.0 to 1.0)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("version_validation")
+    ///     .level(Level::Error)
+    ///     .validates_semver("release_version", 1.0)
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// An invalid column name is recorded as a configuration error of the
+    /// check; see [`try_build`](Self::try_build).
+    pub fn validates_semver(
+        mut self,
+        column: impl Into<String>,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_semver", &column, threshold) else {
+            return self;
+        };
+        match FormatConstraint::semver(column.clone(), threshold) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("validates_semver", &column, e),
+        }
+        self
+    }
+
+    /// Adds an ISO 3166-1 alpha-2 country code validation constraint.
+    ///
+    /// This is a convenience method for `has_format()` with `FormatType::CountryCodeIso3166`.
+    /// Values are checked against the full ISO 3166-1 code list, not only their shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to validate
+    /// * `threshold` - The minimum ratio of values that must be valid country codes (0.0 to 1.0)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("country_validation")
+    ///     .level(Level::Error)
+    ///     .validates_country_code("country", 1.0)
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// An invalid column name is recorded as a configuration error of the
+    /// check; see [`try_build`](Self::try_build).
+    pub fn validates_country_code(
+        mut self,
+        column: impl Into<String>,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_country_code", &column, threshold)
+        else {
+            return self;
+        };
+        match FormatConstraint::country_code(column.clone(), threshold) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("validates_country_code", &column, e),
+        }
+        self
+    }
+
+    /// Adds an ISO 4217 currency code validation constraint.
+    ///
+    /// This is a convenience method for `has_format()` with `FormatType::CurrencyCodeIso4217`.
+    /// Values are checked against the active ISO 4217 code list, not only their shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to validate
+    /// * `threshold` - The minimum ratio of values that must be valid currency codes (0.0 to 1.0)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("currency_validation")
+    ///     .level(Level::Error)
+    ///     .validates_currency_code("currency", 1.0)
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns error if column name is invalid.
+    pub fn validates_currency_code(
+        mut self,
+        column: impl Into<String>,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("validates_currency_code", &column, threshold)
+        else {
+            return self;
+        };
+        self.constraints.push(Arc::new(
+            FormatConstraint::currency_code(column, threshold)
+                .expect("Invalid column or threshold"),
+        ));
+        self
+    }
+
     // ========================================================================
     // ENHANCED FORMAT VALIDATION METHODS WITH OPTIONS
     // ========================================================================
@@ -1259,9 +2091,15 @@ impl CheckBuilder {
     pub fn validates_email_with_options(
         mut self,
         column: impl Into<String>,
-        threshold: f64,
+        threshold: impl IntoThreshold,
         options: FormatOptions,
     ) -> Self {
+        let column = column.into();
+        let Some(threshold) =
+            self.checked_threshold("validates_email_with_options", &column, threshold)
+        else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             FormatConstraint::new(column, FormatType::Email, threshold, options)
                 .expect("Invalid column, threshold, or options"),
@@ -1303,10 +2141,16 @@ impl CheckBuilder {
     pub fn validates_url_with_options(
         mut self,
         column: impl Into<String>,
-        threshold: f64,
+        threshold: impl IntoThreshold,
         allow_localhost: bool,
         options: FormatOptions,
     ) -> Self {
+        let column = column.into();
+        let Some(threshold) =
+            self.checked_threshold("validates_url_with_options", &column, threshold)
+        else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             FormatConstraint::new(
                 column,
@@ -1351,10 +2195,16 @@ impl CheckBuilder {
     pub fn validates_phone_with_options(
         mut self,
         column: impl Into<String>,
-        threshold: f64,
+        threshold: impl IntoThreshold,
         country: Option<String>,
         options: FormatOptions,
     ) -> Self {
+        let column = column.into();
+        let Some(threshold) =
+            self.checked_threshold("validates_phone_with_options", &column, threshold)
+        else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             FormatConstraint::new(column, FormatType::Phone { country }, threshold, options)
                 .expect("Invalid column, threshold, or options"),
@@ -1397,18 +2247,24 @@ impl CheckBuilder {
         mut self,
         column: impl Into<String>,
         pattern: impl Into<String>,
-        threshold: f64,
+        threshold: impl IntoThreshold,
         options: FormatOptions,
     ) -> Self {
-        self.constraints.push(Arc::new(
-            FormatConstraint::new(
-                column,
-                FormatType::Regex(pattern.into()),
-                threshold,
-                options,
-            )
-            .expect("Invalid column, pattern, threshold, or options"),
-        ));
+        let column = column.into();
+        let Some(threshold) =
+            self.checked_threshold("validates_regex_with_options", &column, threshold)
+        else {
+            return self;
+        };
+        match FormatConstraint::new(
+            column.clone(),
+            FormatType::Regex(pattern.into()),
+            threshold,
+            options,
+        ) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("validates_regex_with_options", &column, e),
+        }
         self
     }
 
@@ -1517,12 +2373,18 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column names are invalid or threshold is out of range.
-    pub fn validates_uniqueness<I, S>(mut self, columns: I, threshold: f64) -> Self
+    /// Returns error if column names are invalid.
+    pub fn validates_uniqueness<I, S>(mut self, columns: I, threshold: impl IntoThreshold) -> Self
     where
         I: IntoIterator<Item = S>,
         S: Into<String>,
     {
+        let columns: Vec<String> = columns.into_iter().map(Into::into).collect();
+        let Some(threshold) =
+            self.checked_threshold("validates_uniqueness", &columns.join(", "), threshold)
+        else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             UniquenessConstraint::new(
                 columns,
@@ -1534,6 +2396,64 @@ impl CheckBuilder {
         self
     }
 
+    /// Adds a full uniqueness constraint configured with constraint options.
+    ///
+    /// Like [`validates_uniqueness`](Self::validates_uniqueness), reading the
+    /// threshold (default 1.0), threshold adjustment and memory strategy from
+    /// `options`. With
+    /// [`MemoryStrategy::HashPartitioned`](crate::core::MemoryStrategy::HashPartitioned)
+    /// the keys are counted one hash partition at a time, bounding the memory of
+    /// the query while keeping the result exact. Invalid options are recorded as
+    /// configuration errors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, ConstraintOptions, MemoryStrategy};
+    ///
+    /// let check = Check::builder("uniqueness_validation")
+    ///     .validates_uniqueness_with_options(
+    ///         ["order_id", "line_item"],
+    ///         ConstraintOptions::new()
+    ///             .with_threshold(1.0)
+    ///             .with_memory_strategy(MemoryStrategy::hash_partitioned(32)),
+    ///     )
+    ///     .build();
+    /// assert_eq!(check.constraints().len(), 1);
+    /// ```
+    pub fn validates_uniqueness_with_options<I, S>(
+        mut self,
+        columns: I,
+        options: crate::core::ConstraintOptions,
+    ) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let columns: Vec<String> = columns.into_iter().map(Into::into).collect();
+        let mut uniqueness_options =
+            UniquenessOptions::new().with_memory_strategy(options.memory_strategy);
+        if let Some(adjustment) = options.threshold_adjustment {
+            uniqueness_options = uniqueness_options.with_threshold_adjustment(adjustment);
+        }
+        let constraint = options.validate().and_then(|()| {
+            UniquenessConstraint::new(
+                columns.clone(),
+                UniquenessType::FullUniqueness {
+                    threshold: options.threshold_or(1.0),
+                },
+                uniqueness_options,
+            )
+        });
+        match constraint {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => {
+                self.record_configuration_error("validates_uniqueness", &columns.join(", "), e)
+            }
+        }
+        self
+    }
+
     /// Adds a distinctness constraint with assertion-based validation.
     ///
     /// This is a convenience method for `uniqueness()` with `UniquenessType::Distinctness`.
@@ -1680,17 +2600,25 @@ impl CheckBuilder {
     ///
     /// # Errors
     ///
-    /// Returns error if column names are invalid or threshold is out of range.
+    /// Returns error if column names are invalid.
     pub fn validates_uniqueness_with_nulls<I, S>(
         mut self,
         columns: I,
-        threshold: f64,
+        threshold: impl IntoThreshold,
         null_handling: NullHandling,
     ) -> Self
     where
         I: IntoIterator<Item = S>,
         S: Into<String>,
     {
+        let columns: Vec<String> = columns.into_iter().map(Into::into).collect();
+        let Some(threshold) = self.checked_threshold(
+            "validates_uniqueness_with_nulls",
+            &columns.join(", "),
+            threshold,
+        ) else {
+            return self;
+        };
         self.constraints.push(Arc::new(
             UniquenessConstraint::new(
                 columns,
@@ -1746,8 +2674,56 @@ impl CheckBuilder {
         options: crate::core::ConstraintOptions,
     ) -> Self {
         use crate::constraints::CompletenessConstraint;
-        self.constraints
-            .push(Arc::new(CompletenessConstraint::new(columns, options)));
+        let columns = columns.into();
+        let column = columns.as_vec().join(", ");
+        match CompletenessConstraint::new(columns, options) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("completeness", &column, e),
+        }
+        self
+    }
+
+    /// Adds a completeness constraint that also counts empty strings and
+    /// sentinel values as missing.
+    ///
+    /// The result details break the missing values of each column down by
+    /// cause, see [`CompletenessConstraint::with_missing_value_policy`].
+    ///
+    /// [`CompletenessConstraint::with_missing_value_policy`]: crate::constraints::CompletenessConstraint::with_missing_value_policy
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::builder_extensions::CompletenessOptions;
+    /// use term_guard::core::{Check, MissingValuePolicy};
+    ///
+    /// let check = Check::builder("ages")
+    ///     .completeness_with_missing(
+    ///         "age",
+    ///         CompletenessOptions::threshold(0.95),
+    ///         MissingValuePolicy::default()
+    ///             .sentinel_strings(["N/A", "NULL"])
+    ///             .sentinel_numbers([-999.0]),
+    ///     )
+    ///     .build();
+    /// ```
+    pub fn completeness_with_missing(
+        mut self,
+        columns: impl Into<crate::core::ColumnSpec>,
+        options: crate::core::builder_extensions::CompletenessOptions,
+        policy: crate::core::MissingValuePolicy,
+    ) -> Self {
+        use crate::constraints::CompletenessConstraint;
+        let columns = columns.into();
+        let column = columns.as_vec().join(", ");
+        let constraint = CompletenessConstraint::new(columns, options.into_constraint_options())
+            .and_then(|constraint| policy.validate().map(|()| constraint));
+        match constraint {
+            Ok(constraint) => self
+                .constraints
+                .push(Arc::new(constraint.with_missing_value_policy(policy))),
+            Err(e) => self.record_configuration_error("completeness", &column, e),
+        }
         self
     }
 
@@ -1945,6 +2921,104 @@ impl CheckBuilder {
         )
     }
 
+    /// Adds a constraint that a column holds no NaN or infinite values.
+    ///
+    /// The values are counted in a single scan; columns that are not floating
+    /// point always pass. Statistics on the column leave such values out by
+    /// default, see [`NonFinitePolicy`](crate::constraints::NonFinitePolicy).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("price_validation")
+    ///     .level(Level::Error)
+    ///     .has_no_non_finite("price")
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// An invalid column name is recorded as a configuration error of the
+    /// check; see [`try_build`](Self::try_build).
+    pub fn has_no_non_finite(mut self, column: impl Into<String>) -> Self {
+        use crate::constraints::NonFiniteConstraint;
+        let column = column.into();
+        match NonFiniteConstraint::new(column.clone()) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("has_no_non_finite", &column, e),
+        }
+        self
+    }
+
+    /// Adds a constraint on the fraction of true values in a boolean column.
+    ///
+    /// The rate is computed over the non-null values. String columns holding
+    /// `true`/`false` or `1`/`0` are accepted as well; use
+    /// [`TrueRateConstraint`](crate::constraints::TrueRateConstraint) directly to
+    /// count nulls as false or to parse strings leniently.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, Level};
+    /// use term_guard::constraints::Assertion;
+    ///
+    /// let check = Check::builder("verification")
+    ///     .level(Level::Error)
+    ///     .has_true_rate("is_verified", Assertion::GreaterThanOrEqual(0.95))
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// An invalid column name is recorded as a configuration error of the
+    /// check; see [`try_build`](Self::try_build).
+    pub fn has_true_rate(mut self, column: impl Into<String>, assertion: Assertion) -> Self {
+        use crate::constraints::TrueRateConstraint;
+        let column = column.into();
+        match TrueRateConstraint::new(column.clone(), assertion) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("has_true_rate", &column, e),
+        }
+        self
+    }
+
+    /// Adds a constraint requiring every non-null value of a boolean column to be true.
+    ///
+    /// This is a convenience method for `has_true_rate()` with `Assertion::Equals(1.0)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::Check;
+    ///
+    /// let check = Check::builder("terms")
+    ///     .is_always_true("accepted_terms")
+    ///     .build();
+    /// ```
+    pub fn is_always_true(self, column: impl Into<String>) -> Self {
+        self.has_true_rate(column, Assertion::Equals(1.0))
+    }
+
+    /// Adds a constraint requiring every non-null value of a boolean column to be false.
+    ///
+    /// This is a convenience method for `has_true_rate()` with `Assertion::Equals(0.0)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::Check;
+    ///
+    /// let check = Check::builder("live_records")
+    ///     .is_always_false("is_deleted")
+    ///     .build();
+    /// ```
+    pub fn is_always_false(self, column: impl Into<String>) -> Self {
+        self.has_true_rate(column, Assertion::Equals(0.0))
+    }
+
     /// Adds a foreign key constraint for referential integrity validation.
     ///
     /// This constraint ensures that all values in the child table's foreign key column
@@ -2005,27 +3079,233 @@ impl CheckBuilder {
         self
     }
 
-    /// Adds a constraint that validates sums between two tables match within tolerance.
-    ///
-    /// This is essential for Phase 2 joined data sources validation, ensuring that aggregated
-    /// values are consistent across related tables. Common use cases include validating that
-    /// order totals match payment amounts, or inventory quantities align with transaction logs.
+    /// Adds a foreign key constraint on a key made of several columns.
     ///
-    /// # Arguments
+    /// Each child row must match a parent row on every `(child_column, parent_column)`
+    /// pair. Column names are given without the table name. An empty list of keys is
+    /// recorded as a configuration error. Use [`ForeignKeyConstraint::composite`]
+    /// directly with the `constraint()` method to configure NULL handling.
     ///
-    /// * `left_column` - Left side column in table.column format (e.g., "orders.total")
-    /// * `right_column` - Right side column in table.column format (e.g., "payments.amount")
+    /// [`ForeignKeyConstraint::composite`]: crate::constraints::ForeignKeyConstraint::composite
     ///
     /// # Examples
     ///
-    /// ## Basic Cross-Table Sum Validation
-    ///
     /// ```rust
-    /// use term_guard::core::{Check, Level};
+    /// use term_guard::core::Check;
     ///
-    /// let check = Check::builder("financial_integrity")
-    ///     .level(Level::Error)
-    ///     .cross_table_sum("orders.total", "payments.amount")
+    /// let check = Check::builder("tenant_integrity")
+    ///     .composite_foreign_key(
+    ///         "orders",
+    ///         "customers",
+    ///         vec![("tenant_id", "tenant_id"), ("customer_id", "cust_id")],
+    ///     )
+    ///     .build();
+    /// ```
+    pub fn composite_foreign_key(
+        mut self,
+        child_table: impl Into<String>,
+        parent_table: impl Into<String>,
+        keys: Vec<(impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        use crate::constraints::ForeignKeyConstraint;
+        let child_table = child_table.into();
+        let keys: Vec<(String, String)> = keys
+            .into_iter()
+            .map(|(child, parent)| (child.into(), parent.into()))
+            .collect();
+        let columns = keys
+            .iter()
+            .map(|(child, _)| format!("{child_table}.{child}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match ForeignKeyConstraint::composite(&child_table, parent_table, keys) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("composite_foreign_key", &columns, e),
+        }
+        self
+    }
+
+    /// Adds a constraint that validates the cardinality of a relationship between two tables.
+    ///
+    /// Rows are counted per key value in the child and parent tables and compared with
+    /// the expected [`CardinalitySpec`](crate::constraints::CardinalitySpec). The
+    /// constraint passes when at least `threshold` of the keys satisfy the spec.
+    ///
+    /// # Arguments
+    ///
+    /// * `child_column` - The column in the child table (qualified as "table.column")
+    /// * `parent_column` - The column in the parent table (qualified as "table.column")
+    /// * `spec` - The expected number of related rows per key
+    /// * `threshold` - The minimum fraction of keys that must satisfy the spec
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::CardinalitySpec;
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("relationships")
+    ///     .level(Level::Error)
+    ///     .has_cardinality("shipments.order_id", "orders.order_id", CardinalitySpec::OneToOne, 1.0)
+    ///     .has_cardinality("subscriptions.customer_id", "customers.id", CardinalitySpec::AtMost(5), 0.99)
+    ///     .build();
+    /// ```
+    ///
+    /// For NULL handling and violation reporting, use `CardinalityConstraint` directly.
+    pub fn has_cardinality(
+        mut self,
+        child_column: impl Into<String>,
+        parent_column: impl Into<String>,
+        spec: crate::constraints::CardinalitySpec,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let child_column = child_column.into();
+        let Some(threshold) = self.checked_threshold("has_cardinality", &child_column, threshold)
+        else {
+            return self;
+        };
+        use crate::constraints::CardinalityConstraint;
+        self.constraints.push(Arc::new(
+            CardinalityConstraint::new(child_column, parent_column, spec).threshold(threshold),
+        ));
+        self
+    }
+
+    /// Adds a constraint that determinant columns determine a dependent column.
+    ///
+    /// Rows are grouped by the determinant columns, and a group conforms when it
+    /// has at most one distinct value of the dependent column. The constraint
+    /// passes when at least `threshold` of the groups conform; failures report
+    /// the worst violating groups with their conflicting values. NULL values are
+    /// left out on both sides.
+    ///
+    /// # Arguments
+    ///
+    /// * `determinant` - The columns whose values should determine the dependent column
+    /// * `dependent` - The column that should have one value per group
+    /// * `threshold` - The minimum fraction of conforming groups (0.0 to 1.0)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("dimensions")
+    ///     .level(Level::Error)
+    ///     .has_functional_dependency(vec!["zip_code"], "state", 0.999)
+    ///     .has_functional_dependency(vec!["product_id"], "product_name", 1.0)
+    ///     .build();
+    /// ```
+    ///
+    /// To weigh groups by their rows or handle NULL values differently, use
+    /// `FunctionalDependencyConstraint` directly.
+    pub fn has_functional_dependency<I, S>(
+        mut self,
+        determinant: I,
+        dependent: impl Into<String>,
+        threshold: impl IntoThreshold,
+    ) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let dependent = dependent.into();
+        let Some(threshold) =
+            self.checked_threshold("has_functional_dependency", &dependent, threshold)
+        else {
+            return self;
+        };
+        use crate::constraints::FunctionalDependencyConstraint;
+        match FunctionalDependencyConstraint::new(determinant, dependent.clone()) {
+            Ok(constraint) => self
+                .constraints
+                .push(Arc::new(constraint.threshold(threshold))),
+            Err(e) => self.record_configuration_error("has_functional_dependency", &dependent, e),
+        }
+        self
+    }
+
+    /// Adds a constraint that compares the distinct key sets of two tables.
+    ///
+    /// The current table should retain the keys of the previous one: the
+    /// constraint counts the previous keys missing from the current table and
+    /// the current keys absent from the previous one, and fails when either
+    /// exceeds its limit in `assertions`. Failures list examples of the missing
+    /// and new keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `current_table` - The table holding the current snapshot
+    /// * `previous_table` - The table holding the previous snapshot
+    /// * `key_columns` - The columns identifying a row in both tables
+    /// * `assertions` - The limits on missing and new keys
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::KeySetAssertions;
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("customer_identity")
+    ///     .level(Level::Error)
+    ///     .compares_key_sets(
+    ///         "dim_customer",
+    ///         "dim_customer_prev",
+    ///         vec!["customer_id"],
+    ///         KeySetAssertions { max_missing: 0, max_new_ratio: 0.02 },
+    ///     )
+    ///     .build();
+    /// ```
+    ///
+    /// To also require a minimum retained fraction, use
+    /// `KeySetComparisonConstraint` directly.
+    pub fn compares_key_sets<I, S>(
+        mut self,
+        current_table: impl Into<String>,
+        previous_table: impl Into<String>,
+        key_columns: I,
+        assertions: crate::constraints::KeySetAssertions,
+    ) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let key_columns: Vec<String> = key_columns.into_iter().map(Into::into).collect();
+        let column = key_columns.join(", ");
+        use crate::constraints::KeySetComparisonConstraint;
+        match KeySetComparisonConstraint::new(
+            current_table,
+            previous_table,
+            key_columns,
+            assertions,
+        ) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("compares_key_sets", &column, e),
+        }
+        self
+    }
+
+    /// Adds a constraint that validates sums between two tables match within tolerance.
+    ///
+    /// This is essential for Phase 2 joined data sources validation, ensuring that aggregated
+    /// values are consistent across related tables. Common use cases include validating that
+    /// order totals match payment amounts, or inventory quantities align with transaction logs.
+    ///
+    /// # Arguments
+    ///
+    /// * `left_column` - Left side column in table.column format (e.g., "orders.total")
+    /// * `right_column` - Right side column in table.column format (e.g., "payments.amount")
+    ///
+    /// # Examples
+    ///
+    /// ## Basic Cross-Table Sum Validation
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("financial_integrity")
+    ///     .level(Level::Error)
+    ///     .cross_table_sum("orders.total", "payments.amount")
     ///     .build();
     /// ```
     ///
@@ -2122,6 +3402,55 @@ impl CheckBuilder {
         self
     }
 
+    /// Adds a constraint that fails when a transformation introduced nulls.
+    ///
+    /// For every column present in both tables, the null rate in `after_table` may
+    /// exceed the null rate in `before_table` by at most `max_increase`. Columns found
+    /// in only one of the tables are reported in the result message without failing
+    /// the constraint.
+    ///
+    /// # Arguments
+    ///
+    /// * `before_table` - The input table of the transformation
+    /// * `after_table` - The output table of the transformation
+    /// * `max_increase` - Largest allowed increase of a null rate (0.01 = one percentage point)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("transformation_audit")
+    ///     .level(Level::Error)
+    ///     .no_null_increase("staging_orders", "final_orders", 0.01)
+    ///     .build();
+    /// ```
+    ///
+    /// To compare only some columns, use `NullRateComparisonConstraint` directly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if max_increase is not between 0.0 and 1.0
+    pub fn no_null_increase(
+        mut self,
+        before_table: impl Into<String>,
+        after_table: impl Into<String>,
+        max_increase: f64,
+    ) -> Self {
+        use crate::constraints::NullRateComparisonConstraint;
+        assert!(
+            (0.0..=1.0).contains(&max_increase),
+            "max_increase must be between 0.0 and 1.0"
+        );
+        self.constraints
+            .push(Arc::new(NullRateComparisonConstraint::new(
+                before_table,
+                after_table,
+                max_increase,
+            )));
+        self
+    }
+
     /// Adds a temporal ordering constraint for time-based validation.
     ///
     /// This constraint ensures that temporal data follows expected patterns, including
@@ -2151,12 +3480,13 @@ impl CheckBuilder {
     /// use term_guard::core::Check;
     /// use term_guard::constraints::TemporalOrderingConstraint;
     ///
+    /// # fn example() -> term_guard::prelude::Result<()> {
     /// let check = Check::builder("advanced_temporal")
     ///     .constraint(
     ///         TemporalOrderingConstraint::new("transactions")
     ///             .business_hours("timestamp", "09:00", "17:00")
     ///             .weekdays_only(true)
-    ///             .with_timezone("America/New_York")
+    ///             .with_timezone("America/New_York")?
     ///     )
     ///     .constraint(
     ///         TemporalOrderingConstraint::new("events")
@@ -2165,6 +3495,8 @@ impl CheckBuilder {
     ///             .allow_nulls(true)
     ///     )
     ///     .build();
+    /// # Ok(())
+    /// # }
     /// ```
     ///
     /// # Requirements
@@ -2178,6 +3510,177 @@ impl CheckBuilder {
         self
     }
 
+    /// Adds a constraint that detects gaps in a time series.
+    ///
+    /// Gaps are computed between consecutive timestamps, per partition if a partition
+    /// column is given. An interval counts as a gap when it is longer than `interval`.
+    /// The constraint fails if the fraction of gaps exceeds `threshold`.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The timestamp column
+    /// * `interval` - The expected interval between consecutive timestamps
+    /// * `partition_by` - Optional column to compute gaps per group (e.g. a device id)
+    /// * `threshold` - Maximum allowed fraction of intervals that are gaps (0.0 to 1.0)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("sensor_completeness")
+    ///     .level(Level::Warning)
+    ///     .has_no_time_gaps("reading_time", Duration::from_secs(300), Some("device_id"), 0.01)
+    ///     .build();
+    /// ```
+    ///
+    /// For a tolerance or a hard maximum gap, use `TemporalGapConstraint` directly:
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use term_guard::core::Check;
+    /// use term_guard::constraints::TemporalGapConstraint;
+    ///
+    /// let check = Check::builder("sensor_completeness")
+    ///     .constraint(
+    ///         TemporalGapConstraint::new("reading_time", Duration::from_secs(300))
+    ///             .partition_by("device_id")
+    ///             .tolerance(Duration::from_secs(30))
+    ///             .max_gap(Duration::from_secs(3600))
+    ///     )
+    ///     .build();
+    /// ```
+    pub fn has_no_time_gaps(
+        mut self,
+        column: impl Into<String>,
+        interval: std::time::Duration,
+        partition_by: Option<impl Into<String>>,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) = self.checked_threshold("has_no_time_gaps", &column, threshold) else {
+            return self;
+        };
+        use crate::constraints::TemporalGapConstraint;
+        let mut constraint = TemporalGapConstraint::new(column, interval).threshold(threshold);
+        if let Some(partition) = partition_by {
+            constraint = constraint.partition_by(partition);
+        }
+        self.constraints.push(Arc::new(constraint));
+        self
+    }
+
+    /// Adds a constraint that the most frequent values of a column stay stable
+    /// between runs.
+    ///
+    /// The top `k` values of the column are compared with the top `k` of the
+    /// previous run saved in `repository`, and the constraint fails when their
+    /// Jaccard similarity drops below `min_similarity`. The current top `k` is
+    /// saved for the next run; the first run is skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The categorical column
+    /// * `k` - The number of most frequent values compared
+    /// * `min_similarity` - The minimum Jaccard similarity (0.0 to 1.0)
+    /// * `repository` - The repository holding the top values of previous runs
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use term_guard::core::{Check, Level};
+    /// use term_guard::repository::InMemoryRepository;
+    ///
+    /// let repository = Arc::new(InMemoryRepository::new());
+    /// let check = Check::builder("recommendations")
+    ///     .level(Level::Warning)
+    ///     .has_stable_top_k("category", 20, 0.8, repository)
+    ///     .build();
+    /// ```
+    ///
+    /// For a rank-weighted comparison, tags or an explicit list of previous
+    /// values, use `TopKStabilityConstraint` directly.
+    pub fn has_stable_top_k(
+        mut self,
+        column: impl Into<String>,
+        k: usize,
+        min_similarity: impl IntoThreshold,
+        repository: Arc<dyn crate::repository::MetricsRepository>,
+    ) -> Self {
+        let column = column.into();
+        let Some(min_similarity) =
+            self.checked_threshold("has_stable_top_k", &column, min_similarity)
+        else {
+            return self;
+        };
+        use crate::constraints::TopKStabilityConstraint;
+        match TopKStabilityConstraint::from_repository(
+            column.clone(),
+            k,
+            min_similarity,
+            repository,
+        ) {
+            Ok(constraint) => self.constraints.push(Arc::new(constraint)),
+            Err(e) => self.record_configuration_error("has_stable_top_k", &column, e),
+        }
+        self
+    }
+
+    /// Adds a constraint that verifies a derived column against the expression producing it.
+    ///
+    /// The expression is recomputed for every row and compared with the stored value
+    /// within the tolerance. The constraint passes when at least `threshold` of the
+    /// rows conform; failures report the number of violations, the largest absolute
+    /// difference and example rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column holding the stored values
+    /// * `expression` - The SQL expression recomputing the values
+    /// * `tolerance` - Absolute and/or relative tolerance for the comparison
+    /// * `threshold` - The minimum fraction of conforming rows (0.0 to 1.0)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::EqualityTolerance;
+    /// use term_guard::core::{Check, Level};
+    ///
+    /// let check = Check::builder("derived_columns")
+    ///     .level(Level::Error)
+    ///     .column_matches_expression("total", "price * quantity + tax", EqualityTolerance::absolute(0.01), 1.0)
+    ///     .build();
+    /// ```
+    ///
+    /// To identify example rows by key columns, use `ExpressionEqualityConstraint` directly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column name or SQL expression is rejected
+    pub fn column_matches_expression(
+        mut self,
+        column: impl Into<String>,
+        expression: impl Into<String>,
+        tolerance: crate::constraints::EqualityTolerance,
+        threshold: impl IntoThreshold,
+    ) -> Self {
+        let column = column.into();
+        let Some(threshold) =
+            self.checked_threshold("column_matches_expression", &column, threshold)
+        else {
+            return self;
+        };
+        use crate::constraints::ExpressionEqualityConstraint;
+        self.constraints.push(Arc::new(
+            ExpressionEqualityConstraint::new(column, expression, tolerance)
+                .expect("Invalid column or SQL expression")
+                .threshold(threshold),
+        ));
+        self
+    }
+
     /// Adds a constraint using a fluent constraint builder.
     ///
     /// This method provides the most flexible API for building complex constraints
@@ -2201,6 +3704,7 @@ impl CheckBuilder {
     ///                 .with_operator(LogicalOperator::Any)
     ///                 .with_threshold(0.99)
     ///         )
+    ///         .unwrap()
     ///     )
     ///     .with_constraint(
     ///         LengthConstraint::new("description", LengthAssertion::Between(50, 2000))
@@ -2256,7 +3760,12 @@ impl CheckBuilder {
     ///     .at_least_complete(2, vec!["email", "phone", "address", "postal_code"], 0.9)
     ///     .build();
     /// ```
-    pub fn at_least_complete<I, S>(self, n: usize, columns: I, threshold: f64) -> Self
+    pub fn at_least_complete<I, S>(
+        self,
+        n: usize,
+        columns: I,
+        threshold: impl IntoThreshold,
+    ) -> Self
     where
         I: IntoIterator<Item = S>,
         S: Into<String>,
@@ -2282,7 +3791,7 @@ impl CheckBuilder {
     ///     .exactly_complete(1, vec!["primary_phone", "secondary_phone"], 1.0)
     ///     .build();
     /// ```
-    pub fn exactly_complete<I, S>(self, n: usize, columns: I, threshold: f64) -> Self
+    pub fn exactly_complete<I, S>(self, n: usize, columns: I, threshold: impl IntoThreshold) -> Self
     where
         I: IntoIterator<Item = S>,
         S: Into<String>,
@@ -2299,23 +3808,66 @@ impl CheckBuilder {
 
     /// Builds the `Check` instance.
     ///
+    /// Invalid builder arguments, such as an out-of-range threshold, are kept in
+    /// [`Check::configuration_errors`] and reported when a suite containing the
+    /// check is built with [`ValidationSuiteBuilder::try_build`](super::ValidationSuiteBuilder::try_build)
+    /// or run. Use [`try_build`](Self::try_build) to fail immediately.
+    ///
     /// # Returns
     ///
     /// The constructed `Check`
     pub fn build(self) -> Check {
         Check {
             name: self.name,
+            id: self.id,
             level: self.level,
             description: self.description,
             constraints: self.constraints,
+            labels: self.labels,
+            tags: self.tags,
+            metadata: self.metadata,
+            known_issue: self.known_issue,
+            dimension: self.dimension,
+            constraint_dimensions: self.constraint_dimensions,
+            configuration_issues: self.configuration_issues,
         }
     }
+
+    /// Builds the `Check` instance, failing on invalid builder arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, Threshold};
+    ///
+    /// // A percentage passed where a fraction is expected
+    /// let err = Check::builder("contact")
+    ///     .validates_email("email", 95.0)
+    ///     .try_build()
+    ///     .unwrap_err();
+    /// assert!(err.to_string().contains("Check 'contact', constraint validates_email on column 'email'"));
+    /// assert!(err.to_string().contains("Threshold::from_percent(95)"));
+    ///
+    /// let check = Check::builder("contact")
+    ///     .validates_email("email", Threshold::from_percent(95.0)?)
+    ///     .try_build()?;
+    /// # Ok::<(), term_guard::error::TermError>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::InvalidConfiguration`] naming the check, constraint and
+    /// column of every invalid argument.
+    pub fn try_build(self) -> Result<Check> {
+        let check = self.build();
+        check.validate()?;
+        Ok(check)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::prelude::*;
     use async_trait::async_trait;
     use datafusion::prelude::*;
 
@@ -2355,6 +3907,37 @@ mod tests {
         assert_eq!(check.constraints().len(), 1);
     }
 
+    #[test]
+    fn test_apply_to_columns_labels() {
+        use crate::core::ConstraintOptions;
+
+        let check = Check::builder("measures")
+            .has_size(Assertion::GreaterThan(0.0))
+            .apply_to_columns(["price", "quantity"], |builder, column| {
+                builder
+                    .completeness(column, ConstraintOptions::new().with_threshold(0.99))
+                    .has_min(column, Assertion::GreaterThanOrEqual(0.0))
+            })
+            .build();
+
+        let labels: Vec<_> = (0..check.constraints().len())
+            .map(|i| check.constraint_label(i))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                "size",
+                "completeness.price",
+                "min.price",
+                "completeness.quantity",
+                "min.quantity"
+            ]
+        );
+        // The constraints themselves keep their plain names
+        assert_eq!(check.constraints()[1].name(), "completeness");
+        assert_eq!(check.constraint_label(99), "");
+    }
+
     #[test]
     fn test_check_default_level() {
         let check = Check::builder("test_check").build();
@@ -2427,21 +4010,172 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Threshold must be between 0.0 and 1.0")]
     fn test_check_builder_invalid_completeness_threshold() {
         use crate::core::ConstraintOptions;
 
-        Check::builder("test")
+        let check = Check::builder("test")
             .completeness("column", ConstraintOptions::new().with_threshold(1.5))
             .build();
+
+        assert!(check.constraints().is_empty());
+        assert_eq!(
+            check.configuration_errors(),
+            ["Check 'test', constraint completeness on column 'column': Threshold must be between 0.0 and 1.0, got 1.5; use Threshold::from_percent(1.5) if this is a percentage"]
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Invalid columns or threshold")]
     fn test_check_builder_invalid_uniqueness_threshold() {
-        Check::builder("test")
-            .validates_uniqueness(vec!["column"], -0.1)
-            .build();
+        let err = Check::builder("test")
+            .validates_uniqueness(vec!["a", "b"], -0.1)
+            .try_build()
+            .unwrap_err();
+
+        let TermError::InvalidConfiguration(report) = &err else {
+            panic!("expected an invalid configuration, got {err}");
+        };
+        assert_eq!(report.issues[0].check.as_deref(), Some("test"));
+        assert_eq!(report.issues[0].constraint_index, Some(0));
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: check 'test' has 1 error
+  - error at constraints[0]: Check 'test', constraint validates_uniqueness on column 'a, b': Threshold must be between 0.0 and 1.0, got -0.1"
+        );
+    }
+
+    #[test]
+    fn test_check_builder_rejected_sql_query() {
+        let err = Check::builder("rules")
+            .satisfies_scalar("SELECT count(*) FROM {table}", Assertion::Equals(0.0))
+            .satisfies_scalar("DELETE FROM {table}", Assertion::Equals(0.0))
+            .satisfies_bool("SELECT true; DROP TABLE {table}")
+            .try_build()
+            .unwrap_err();
+
+        let TermError::InvalidConfiguration(report) = &err else {
+            panic!("expected an invalid configuration, got {err}");
+        };
+        assert_eq!(report.subject, "check 'rules'");
+        assert_eq!(report.issues.len(), 2);
+        let locations: Vec<_> = report
+            .issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.path.as_str(),
+                    issue.check.as_deref(),
+                    issue.constraint_index,
+                )
+            })
+            .collect();
+        assert_eq!(
+            locations,
+            [
+                ("constraints[1]", Some("rules"), Some(1)),
+                ("constraints[2]", Some("rules"), Some(2)),
+            ]
+        );
+        assert!(report.issues[0]
+            .message
+            .starts_with("Check 'rules', constraint satisfies_scalar: "));
+        assert!(report.issues[1]
+            .message
+            .starts_with("Check 'rules', constraint satisfies_bool: "));
+    }
+
+    #[test]
+    fn test_check_builder_invalid_format_column() {
+        let err = Check::builder("accounts")
+            .validates_iban("iban; DROP TABLE data", 1.0)
+            .validates_mac_address("device_mac", 0.99)
+            .validates_semver("version'", 1.0)
+            .validates_country_code("country", 1.0)
+            .try_build()
+            .unwrap_err();
+
+        let TermError::InvalidConfiguration(report) = &err else {
+            panic!("expected an invalid configuration, got {err}");
+        };
+        let paths: Vec<&str> = report
+            .issues
+            .iter()
+            .map(|issue| issue.path.as_str())
+            .collect();
+        assert_eq!(paths, ["constraints[0]", "constraints[2]"]);
+        assert!(report.issues[0].message.starts_with(
+            "Check 'accounts', constraint validates_iban on column 'iban; DROP TABLE data': "
+        ));
+        assert!(report.issues[1]
+            .message
+            .starts_with("Check 'accounts', constraint validates_semver on column 'version'': "));
+    }
+
+    #[test]
+    fn test_check_builder_invalid_non_finite_column() {
+        let err = Check::builder("prices")
+            .has_min("price", Assertion::GreaterThan(0.0))
+            .has_no_non_finite("price\"; DROP TABLE data")
+            .try_build()
+            .unwrap_err();
+
+        let TermError::InvalidConfiguration(report) = &err else {
+            panic!("expected an invalid configuration, got {err}");
+        };
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].path, "constraints[1]");
+        assert!(report.issues[0].message.starts_with(
+            "Check 'prices', constraint has_no_non_finite on column 'price\"; DROP TABLE data': "
+        ));
+    }
+
+    #[test]
+    fn test_check_builder_invalid_true_rate_column() {
+        let err = Check::builder("verification")
+            .has_true_rate("is verified", Assertion::GreaterThanOrEqual(0.95))
+            .is_always_true("accepted_terms")
+            .is_always_false("deleted`")
+            .try_build()
+            .unwrap_err();
+
+        let TermError::InvalidConfiguration(report) = &err else {
+            panic!("expected an invalid configuration, got {err}");
+        };
+        let paths: Vec<&str> = report
+            .issues
+            .iter()
+            .map(|issue| issue.path.as_str())
+            .collect();
+        assert_eq!(paths, ["constraints[0]", "constraints[2]"]);
+        assert!(report.issues[0].message.starts_with(
+            "Check 'verification', constraint has_true_rate on column 'is verified': "
+        ));
+        assert!(report.issues[1]
+            .message
+            .starts_with("Check 'verification', constraint has_true_rate on column 'deleted`': "));
+    }
+
+    #[test]
+    fn test_check_builder_percent_threshold() {
+        use crate::core::Threshold;
+
+        let err = Check::builder("contact")
+            .validates_email("email", 95.0)
+            .has_consistent_data_type("id", 0.99)
+            .try_build()
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("constraint validates_email on column 'email'"),
+            "{err}"
+        );
+        assert!(err.contains("Threshold::from_percent(95)"), "{err}");
+
+        let check = Check::builder("contact")
+            .validates_email("email", Threshold::from_percent(95.0).unwrap())
+            .has_consistent_data_type("id", 0.99)
+            .try_build()
+            .unwrap();
+        assert_eq!(check.constraints().len(), 2);
     }
 
     #[test]
@@ -2629,4 +4363,82 @@ mod tests {
             assert_eq!(constraint.name(), "cross_table_sum");
         }
     }
+
+    #[test]
+    fn test_functional_dependency_builder_method() {
+        let check = Check::builder("dimensions")
+            .has_functional_dependency(vec!["zip_code"], "state", 0.999)
+            .has_functional_dependency(vec!["state"], "state", 1.0)
+            .build();
+
+        assert_eq!(check.constraints().len(), 1);
+        assert_eq!(check.constraints()[0].name(), "functional_dependency");
+        assert_eq!(
+            check.configuration_errors(),
+            ["Check 'dimensions', constraint has_functional_dependency on column 'state': Validation failed: Column 'state' cannot determine itself"]
+        );
+    }
+
+    #[test]
+    fn test_key_set_comparison_builder_method() {
+        use crate::constraints::KeySetAssertions;
+
+        let assertions = KeySetAssertions {
+            max_missing: 0,
+            max_new_ratio: 0.02,
+        };
+        let check = Check::builder("customer_identity")
+            .compares_key_sets(
+                "dim_customer",
+                "dim_customer_prev",
+                vec!["customer_id"],
+                assertions,
+            )
+            .compares_key_sets(
+                "dim_customer",
+                "dim_customer_prev",
+                vec!["id; --"],
+                assertions,
+            )
+            .build();
+
+        assert_eq!(check.constraints().len(), 1);
+        assert_eq!(check.constraints()[0].name(), "key_set_comparison");
+        assert_eq!(
+            check.constraints()[0].referenced_tables(),
+            ["dim_customer", "dim_customer_prev"]
+        );
+        assert_eq!(check.configuration_errors().len(), 1);
+        assert!(check.configuration_errors()[0].starts_with(
+            "Check 'customer_identity', constraint compares_key_sets on column 'id; --'"
+        ));
+    }
+
+    #[test]
+    fn test_memory_strategy_builder_methods() {
+        use crate::core::{ConstraintOptions, MemoryStrategy};
+
+        let partitioned =
+            ConstraintOptions::new().with_memory_strategy(MemoryStrategy::hash_partitioned(8));
+        let sketched = ConstraintOptions::new().with_memory_strategy(MemoryStrategy::sketched(100));
+        let check = Check::builder("large")
+            .validates_uniqueness_with_options(["id"], partitioned.clone())
+            .has_histogram_with_options("country", Arc::new(|_| true), sketched.clone())
+            .validates_uniqueness_with_options(["id"], sketched)
+            .has_histogram_with_options("country", Arc::new(|_| true), partitioned)
+            .build();
+
+        assert_eq!(check.constraints().len(), 2);
+        assert_eq!(
+            check.constraints()[0].metadata().custom["memory_strategy"],
+            "hash-partitioned (8 partitions)"
+        );
+        assert_eq!(
+            check.configuration_errors(),
+            [
+                "Check 'large', constraint validates_uniqueness on column 'id': Uniqueness constraints support the unbounded and hash-partitioned memory strategies, not sketched counting",
+                "Check 'large', constraint has_histogram on column 'country': Histograms support the unbounded and sketched memory strategies, not hash partitioning",
+            ]
+        );
+    }
 }
@@ -0,0 +1,227 @@
+//! Selection of checks for partial suite runs.
+//!
+//! While iterating on a single check it is convenient to run only part of a
+//! suite. A [`CheckFilter`] selects checks by name, level and tag, and a run
+//! with [`RunOptions::with_filter`](super::RunOptions::with_filter) runs only
+//! the selected ones. Checks left out are listed in the report as
+//! excluded rather than counted as passed.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::constraints::Assertion;
+//! use term_guard::core::{Check, CheckFilter, Level, RunOptions, ValidationSuite};
+//! use datafusion::prelude::*;
+//!
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! let ctx = SessionContext::new();
+//! ctx.sql("CREATE TABLE data (amount DOUBLE) AS VALUES (10.0), (20.0)")
+//!     .await?
+//!     .collect()
+//!     .await?;
+//!
+//! let suite = ValidationSuite::builder("orders")
+//!     .check(
+//!         Check::builder("revenue_volume")
+//!             .tag("finance")
+//!             .has_size(Assertion::Equals(2.0))
+//!             .build(),
+//!     )
+//!     .check(Check::builder("freshness").has_size(Assertion::GreaterThan(0.0)).build())
+//!     .build();
+//!
+//! let result = suite
+//!     .run_with(&ctx, &RunOptions::new().with_filter(CheckFilter::new().tag("finance")))
+//!     .await?;
+//! assert_eq!(result.report().metrics.total_checks, 1);
+//! assert_eq!(result.report().excluded_checks[0].check_name, "freshness");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+
+use glob::Pattern;
+use std::collections::BTreeSet;
+
+use super::{Check, Level, ValidationReport};
+use crate::prelude::*;
+
+/// Selects the checks to run in a filtered suite run.
+///
+/// An empty filter selects every check. Each criterion narrows the selection:
+/// a check runs when it matches an included name (if any are given), has one
+/// of the selected levels (if any), has one of the selected tags (if any), and
+/// matches no excluded name or tag.
+#[derive(Debug, Clone, Default)]
+pub struct CheckFilter {
+    /// Name patterns a check must match; `None` selects every name
+    include: Option<Vec<Pattern>>,
+    exclude: Vec<Pattern>,
+    levels: Vec<Level>,
+    tags: BTreeSet<String>,
+    excluded_tags: BTreeSet<String>,
+}
+
+impl CheckFilter {
+    /// Creates a filter selecting every check.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Selects the checks that had issues in `report`.
+    ///
+    /// If the previous run had no issues, the filter selects no check.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{CheckFilter, Check, Level, ValidationIssue, ValidationReport};
+    ///
+    /// let mut previous = ValidationReport::new("orders");
+    /// previous.add_issue(ValidationIssue {
+    ///     check_name: "revenue[eur]".to_string(),
+    ///     constraint_name: "min".to_string(),
+    ///     level: Level::Error,
+    ///     message: "Minimum is negative".to_string(),
+    ///     metric: Some(-1.0),
+    ///     cached: false,
+    ///     known_issue: None,
+    ///     check_id: None,
+    ///     constraint_id: None,
+    ///     impacts: Vec::new(),
+    /// });
+    ///
+    /// let filter = CheckFilter::failed_in(&previous);
+    /// assert!(filter.matches(&Check::builder("revenue[eur]").build()));
+    /// assert!(!filter.matches(&Check::builder("revenue_e").build()));
+    /// ```
+    pub fn failed_in(report: &ValidationReport) -> Self {
+        let names: BTreeSet<&str> = report
+            .issues
+            .iter()
+            .map(|issue| issue.check_name.as_str())
+            .collect();
+        let patterns = names
+            .into_iter()
+            .map(|name| Pattern::new(&Pattern::escape(name)).expect("escaped pattern is valid"))
+            .collect();
+        Self {
+            include: Some(patterns),
+            ..Self::default()
+        }
+    }
+
+    /// Selects checks whose name matches `pattern`.
+    ///
+    /// Patterns use glob syntax (`*`, `?`, `[abc]`); a name without wildcards
+    /// matches exactly. Calling this several times selects checks matching any
+    /// of the patterns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pattern` is not a valid glob pattern.
+    pub fn include(mut self, pattern: &str) -> Result<Self> {
+        let pattern = Self::compile(pattern)?;
+        self.include.get_or_insert_with(Vec::new).push(pattern);
+        Ok(self)
+    }
+
+    /// Leaves out checks whose name matches `pattern`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pattern` is not a valid glob pattern.
+    pub fn exclude(mut self, pattern: &str) -> Result<Self> {
+        self.exclude.push(Self::compile(pattern)?);
+        Ok(self)
+    }
+
+    /// Selects checks of `level`.
+    ///
+    /// Calling this several times selects checks of any of the levels.
+    pub fn level(mut self, level: Level) -> Self {
+        self.levels.push(level);
+        self
+    }
+
+    /// Selects checks tagged with `tag`.
+    ///
+    /// Calling this several times selects checks with any of the tags.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.insert(tag.into());
+        self
+    }
+
+    /// Leaves out checks tagged with `tag`.
+    pub fn exclude_tag(mut self, tag: impl Into<String>) -> Self {
+        self.excluded_tags.insert(tag.into());
+        self
+    }
+
+    /// Returns whether `check` is selected.
+    pub fn matches(&self, check: &Check) -> bool {
+        let name = check.name();
+        self.include
+            .as_ref()
+            .map_or(true, |patterns| patterns.iter().any(|p| p.matches(name)))
+            && !self.exclude.iter().any(|p| p.matches(name))
+            && (self.levels.is_empty() || self.levels.contains(&check.level()))
+            && (self.tags.is_empty() || self.tags.iter().any(|tag| check.has_tag(tag)))
+            && !self.excluded_tags.iter().any(|tag| check.has_tag(tag))
+    }
+
+    fn compile(pattern: &str) -> Result<Pattern> {
+        Pattern::new(pattern).map_err(|e| {
+            TermError::Configuration(format!("Invalid check name pattern '{pattern}': {e}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(name: &str, level: Level, tags: &[&str]) -> Check {
+        tags.iter()
+            .fold(Check::builder(name).level(level), |builder, tag| {
+                builder.tag(*tag)
+            })
+            .build()
+    }
+
+    #[test]
+    fn test_filter_criteria() {
+        let revenue = check("finance_revenue", Level::Error, &["finance"]);
+        let costs = check("finance_costs", Level::Warning, &["finance", "slow"]);
+        let freshness = check("freshness", Level::Info, &[]);
+
+        let all = CheckFilter::new();
+        assert!(all.matches(&revenue) && all.matches(&costs) && all.matches(&freshness));
+
+        let finance = CheckFilter::new().include("finance_*").unwrap();
+        assert!(finance.matches(&revenue) && finance.matches(&costs));
+        assert!(!finance.matches(&freshness));
+
+        let exact = CheckFilter::new().include("freshness").unwrap();
+        assert!(exact.matches(&freshness) && !exact.matches(&revenue));
+
+        let not_costs = CheckFilter::new().exclude("*_costs").unwrap();
+        assert!(not_costs.matches(&revenue) && !not_costs.matches(&costs));
+
+        let errors = CheckFilter::new().level(Level::Error).level(Level::Info);
+        assert!(errors.matches(&revenue) && errors.matches(&freshness));
+        assert!(!errors.matches(&costs));
+
+        let fast_finance = CheckFilter::new().tag("finance").exclude_tag("slow");
+        assert!(fast_finance.matches(&revenue));
+        assert!(!fast_finance.matches(&costs) && !fast_finance.matches(&freshness));
+
+        assert!(CheckFilter::new().include("[").is_err());
+    }
+
+    #[test]
+    fn test_failed_in_without_issues_selects_nothing() {
+        let report = ValidationReport::new("suite");
+        let filter = CheckFilter::failed_in(&report);
+        assert!(!filter.matches(&check("any", Level::Error, &[])));
+    }
+}
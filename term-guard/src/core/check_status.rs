@@ -0,0 +1,282 @@
+//! Aggregation of constraint statuses into check statuses.
+//!
+//! Reports count checks, not constraints. A check ends in exactly one
+//! [`CheckStatus`], derived from the statuses of its constraints:
+//!
+//! - a check fails if any of its constraints fails; evaluation errors count as
+//!   failures. Failures of [`Level::Info`] checks are info findings instead, which
+//!   never fail the run.
+//! - a check is waived if its only failures are constraint failures waived by an
+//!   active [`KnownIssue`](super::KnownIssue); waived checks never fail the run.
+//! - a check passes if none of its constraints fails and at least one passes;
+//!   skipped constraints neither pass nor fail it.
+//! - a check is skipped if all of its constraints were skipped, or if it has
+//!   none, as nothing was verified.
+//!
+//! The suite runner records one status per check in
+//! [`ValidationMetrics`](super::ValidationMetrics), and
+//! [`ValidationMetrics::success_rate`](super::ValidationMetrics::success_rate)
+//! rates passed checks against passed and failed ones only, leaving out waived
+//! checks.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::core::{CheckStatus, ConstraintStatus, Level};
+//!
+//! let status = CheckStatus::aggregate(
+//!     Level::Error,
+//!     [ConstraintStatus::Success, ConstraintStatus::Skipped],
+//! );
+//! assert_eq!(status, CheckStatus::Passed);
+//!
+//! let status = CheckStatus::aggregate(Level::Warning, [ConstraintStatus::Skipped]);
+//! assert_eq!(status, CheckStatus::Skipped);
+//! ```
+
+use super::{ConstraintOutcome, ConstraintStatus, Level};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How a check ended, aggregated from the statuses of its constraints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// No constraint failed and at least one passed
+    Passed,
+    /// At least one constraint of a [`Level::Warning`] or [`Level::Error`] check failed
+    Failed,
+    /// At least one constraint of a [`Level::Info`] check failed
+    InfoFinding,
+    /// All failed constraints were waived by a [`KnownIssue`](super::KnownIssue)
+    Waived,
+    /// All constraints were skipped, or the check has none
+    Skipped,
+}
+
+impl CheckStatus {
+    /// Aggregates the statuses of the constraints of a check at `level`.
+    pub fn aggregate(level: Level, statuses: impl IntoIterator<Item = ConstraintStatus>) -> Self {
+        Self::aggregate_with_waivers(level, statuses.into_iter().map(|status| (status, false)))
+    }
+
+    /// Aggregates the statuses of the constraints of a check at `level`, each
+    /// paired with whether a known issue waived its failure.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{CheckStatus, ConstraintStatus, Level};
+    ///
+    /// let status = CheckStatus::aggregate_with_waivers(
+    ///     Level::Error,
+    ///     [(ConstraintStatus::Success, false), (ConstraintStatus::Failure, true)],
+    /// );
+    /// assert_eq!(status, CheckStatus::Waived);
+    /// ```
+    pub fn aggregate_with_waivers(
+        level: Level,
+        statuses: impl IntoIterator<Item = (ConstraintStatus, bool)>,
+    ) -> Self {
+        let mut passed = false;
+        let mut waived = false;
+        for (status, is_waived) in statuses {
+            match status {
+                ConstraintStatus::Failure if is_waived => waived = true,
+                ConstraintStatus::Failure if level == Level::Info => {
+                    return CheckStatus::InfoFinding
+                }
+                ConstraintStatus::Failure => return CheckStatus::Failed,
+                ConstraintStatus::Success => passed = true,
+                ConstraintStatus::Skipped => {}
+            }
+        }
+        if waived {
+            CheckStatus::Waived
+        } else if passed {
+            CheckStatus::Passed
+        } else {
+            CheckStatus::Skipped
+        }
+    }
+
+    /// Returns the name of the status.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckStatus::Passed => "passed",
+            CheckStatus::Failed => "failed",
+            CheckStatus::InfoFinding => "info_finding",
+            CheckStatus::Waived => "waived",
+            CheckStatus::Skipped => "skipped",
+        }
+    }
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The name, level and constraint statuses of a check, each status paired with
+/// whether a known issue waived it.
+type CheckOutcomes<'a> = (&'a str, Level, Vec<(ConstraintStatus, bool)>);
+
+/// Aggregates constraint outcomes into one status per check, in the order the
+/// checks first appear.
+///
+/// Outcomes of a check need not be adjacent. Checks without outcomes cannot be
+/// seen here; the suite runner records them as skipped.
+pub(crate) fn check_statuses(outcomes: &[ConstraintOutcome]) -> Vec<(&str, CheckStatus)> {
+    let mut checks: Vec<CheckOutcomes> = Vec::new();
+    for outcome in outcomes {
+        let status = (outcome.status, outcome.waived);
+        match checks
+            .iter_mut()
+            .find(|(name, _, _)| *name == outcome.check_name)
+        {
+            Some((_, _, statuses)) => statuses.push(status),
+            None => checks.push((&outcome.check_name, outcome.level, vec![status])),
+        }
+    }
+    checks
+        .into_iter()
+        .map(|(name, level, statuses)| (name, CheckStatus::aggregate_with_waivers(level, statuses)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATUSES: [ConstraintStatus; 3] = [
+        ConstraintStatus::Success,
+        ConstraintStatus::Failure,
+        ConstraintStatus::Skipped,
+    ];
+    const LEVELS: [Level; 3] = [Level::Info, Level::Warning, Level::Error];
+
+    /// Every sequence of up to `max_len` constraint statuses.
+    fn sequences(max_len: usize) -> Vec<Vec<ConstraintStatus>> {
+        let mut all = vec![vec![]];
+        let mut current = vec![vec![]];
+        for _ in 0..max_len {
+            current = current
+                .iter()
+                .flat_map(|prefix: &Vec<ConstraintStatus>| {
+                    STATUSES.iter().map(move |status| {
+                        let mut next = prefix.clone();
+                        next.push(*status);
+                        next
+                    })
+                })
+                .collect();
+            all.extend(current.iter().cloned());
+        }
+        all
+    }
+
+    #[test]
+    fn test_aggregate_over_all_status_combinations() {
+        let sequences = sequences(4);
+        assert_eq!(sequences.len(), 1 + 3 + 9 + 27 + 81);
+        for level in LEVELS {
+            for statuses in &sequences {
+                let failed = statuses.contains(&ConstraintStatus::Failure);
+                let passed = statuses.contains(&ConstraintStatus::Success);
+                let expected = match (failed, passed) {
+                    (true, _) if level == Level::Info => CheckStatus::InfoFinding,
+                    (true, _) => CheckStatus::Failed,
+                    (false, true) => CheckStatus::Passed,
+                    (false, false) => CheckStatus::Skipped,
+                };
+                assert_eq!(
+                    CheckStatus::aggregate(level, statuses.iter().copied()),
+                    expected,
+                    "{level:?} {statuses:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_waived_failures_aggregate_to_waived() {
+        for level in LEVELS {
+            for statuses in &sequences(3) {
+                let waived = statuses.iter().map(|status| (*status, true));
+                let expected = if statuses.contains(&ConstraintStatus::Failure) {
+                    CheckStatus::Waived
+                } else {
+                    CheckStatus::aggregate(level, statuses.iter().copied())
+                };
+                assert_eq!(
+                    CheckStatus::aggregate_with_waivers(level, waived),
+                    expected,
+                    "{level:?} {statuses:?}"
+                );
+            }
+
+            // An unwaived failure fails the check whatever else was waived
+            let mixed = [
+                (ConstraintStatus::Failure, true),
+                (ConstraintStatus::Failure, false),
+            ];
+            assert_eq!(
+                CheckStatus::aggregate_with_waivers(level, mixed),
+                CheckStatus::aggregate(level, [ConstraintStatus::Failure])
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty_check_is_skipped() {
+        for level in LEVELS {
+            assert_eq!(CheckStatus::aggregate(level, []), CheckStatus::Skipped);
+        }
+    }
+
+    #[test]
+    fn test_check_statuses_group_outcomes_by_check() {
+        let outcome = |check: &str, level, status| ConstraintOutcome {
+            check_name: check.to_string(),
+            constraint_name: "constraint".to_string(),
+            level,
+            status,
+            waived: false,
+            metric: None,
+            threshold: None,
+            dimension: None,
+            constraint_id: None,
+        };
+        let outcomes = vec![
+            outcome("ids", Level::Error, ConstraintStatus::Success),
+            outcome("notes", Level::Info, ConstraintStatus::Failure),
+            outcome("ids", Level::Error, ConstraintStatus::Failure),
+            outcome("dates", Level::Warning, ConstraintStatus::Skipped),
+            outcome("ids", Level::Error, ConstraintStatus::Failure),
+            ConstraintOutcome {
+                waived: true,
+                ..outcome("totals", Level::Error, ConstraintStatus::Failure)
+            },
+            outcome("totals", Level::Error, ConstraintStatus::Success),
+        ];
+
+        assert_eq!(
+            check_statuses(&outcomes),
+            vec![
+                ("ids", CheckStatus::Failed),
+                ("notes", CheckStatus::InfoFinding),
+                ("dates", CheckStatus::Skipped),
+                ("totals", CheckStatus::Waived),
+            ]
+        );
+        assert!(check_statuses(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_serializes_as_snake_case() {
+        let json = serde_json::to_string(&CheckStatus::InfoFinding).unwrap();
+        assert_eq!(json, "\"info_finding\"");
+        assert_eq!(CheckStatus::InfoFinding.to_string(), "info_finding");
+    }
+}
@@ -0,0 +1,454 @@
+//! Bounded collection of the result sets constraints read back.
+//!
+//! Most constraints run aggregate queries returning a single row. A few read
+//! result sets whose size depends on the data: the buckets of a histogram, the
+//! distinct values compared with a baseline, or the violating keys and groups
+//! listed as examples. They collect these through [`collect_limited`], which
+//! streams the query under the [`CollectionLimits`] of the run instead of
+//! buffering the whole result:
+//!
+//! - at most `max_rows` rows are collected;
+//! - at most `max_bytes` of batches are held in memory. Without a spill
+//!   directory collection stops there; with one, further batches are written
+//!   to an Arrow IPC file in it and read back one at a time;
+//! - `batch_size` overrides the session's batch size for these queries, so that
+//!   the limits cut off results more precisely.
+//!
+//! A constraint whose result set was cut off still completes on the rows it
+//! collected and records the truncation in its
+//! [`ConstraintResult::details`](super::ConstraintResult::details) under
+//! [`COLLECTION_TRUNCATED_DETAIL`]. Queries order their rows, so a truncated
+//! result keeps the same rows from run to run.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::core::{CollectionLimits, ValidationSuite};
+//!
+//! let suite = ValidationSuite::builder("events")
+//!     .collection_limits(
+//!         CollectionLimits::new()
+//!             .max_rows(100_000)
+//!             .max_bytes(64 * 1024 * 1024)
+//!             .spill_to(std::env::temp_dir()),
+//!     )
+//!     .build();
+//! assert_eq!(suite.collection_limits().max_rows, 100_000);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+use arrow::ipc::reader::FileReader;
+use arrow::ipc::writer::FileWriter;
+use arrow::record_batch::RecordBatch;
+use datafusion::physical_plan::execute_stream;
+use datafusion::prelude::DataFrame;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use super::{current_validation_context, QueryMetrics};
+use crate::prelude::*;
+
+/// Detail set to 1 on results computed from a truncated result set.
+pub const COLLECTION_TRUNCATED_DETAIL: &str = "collection.truncated";
+
+/// Detail holding the number of rows a truncated collection kept.
+pub const COLLECTION_ROWS_DETAIL: &str = "collection.rows";
+
+/// Detail holding the number of collected rows spilled to disk.
+pub const COLLECTION_SPILLED_ROWS_DETAIL: &str = "collection.spilled_rows";
+
+/// Bounds on the result sets constraints collect.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::core::CollectionLimits;
+///
+/// let limits = CollectionLimits::default().max_bytes(16 * 1024 * 1024);
+/// assert_eq!(limits.max_rows, 1_000_000);
+/// assert!(limits.spill_dir.is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CollectionLimits {
+    /// Maximum number of rows collected per query (default: 1,000,000)
+    pub max_rows: usize,
+    /// Maximum size of the batches held in memory per query (default: 256 MiB)
+    pub max_bytes: usize,
+    /// Directory receiving the batches collected beyond `max_bytes` (default:
+    /// none, collection stops at `max_bytes`)
+    pub spill_dir: Option<PathBuf>,
+    /// Batch size of collecting queries (default: the session's batch size)
+    pub batch_size: Option<usize>,
+}
+
+impl Default for CollectionLimits {
+    fn default() -> Self {
+        Self {
+            max_rows: 1_000_000,
+            max_bytes: 256 << 20,
+            spill_dir: None,
+            batch_size: None,
+        }
+    }
+}
+
+impl CollectionLimits {
+    /// Creates the default limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of rows collected per query.
+    pub fn max_rows(mut self, rows: usize) -> Self {
+        self.max_rows = rows;
+        self
+    }
+
+    /// Sets the maximum size of the batches held in memory per query.
+    pub fn max_bytes(mut self, bytes: usize) -> Self {
+        self.max_bytes = bytes;
+        self
+    }
+
+    /// Spills batches collected beyond `max_bytes` to files in `dir`.
+    ///
+    /// The files are removed once the constraint has read them.
+    pub fn spill_to(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.spill_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the batch size of collecting queries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows` is zero.
+    pub fn batch_size(mut self, rows: usize) -> Self {
+        assert!(rows > 0, "Batch size must be positive");
+        self.batch_size = Some(rows);
+        self
+    }
+}
+
+/// How much of a result set a collection kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CollectionSummary {
+    /// Number of rows collected
+    pub rows: usize,
+    /// Number of the collected rows spilled to disk
+    pub spilled_rows: usize,
+    /// Whether rows were left out because of the limits
+    pub truncated: bool,
+}
+
+impl CollectionSummary {
+    /// Records the truncation and spilling of the collection in `details`.
+    ///
+    /// Collections that were neither truncated nor spilled record nothing.
+    pub fn record(&self, details: &mut BTreeMap<String, f64>) {
+        if self.truncated {
+            details.insert(COLLECTION_TRUNCATED_DETAIL.to_string(), 1.0);
+            details.insert(COLLECTION_ROWS_DETAIL.to_string(), self.rows as f64);
+        }
+        if self.spilled_rows > 0 {
+            details.insert(
+                COLLECTION_SPILLED_ROWS_DETAIL.to_string(),
+                self.spilled_rows as f64,
+            );
+        }
+    }
+
+    /// Combines the summaries of two collections feeding one result.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            rows: self.rows + other.rows,
+            spilled_rows: self.spilled_rows + other.spilled_rows,
+            truncated: self.truncated || other.truncated,
+        }
+    }
+}
+
+/// The batches of a result set collected under [`CollectionLimits`].
+#[derive(Debug)]
+pub struct CollectedBatches {
+    schema: SchemaRef,
+    batches: Vec<RecordBatch>,
+    spill: Option<SpillFile>,
+    summary: CollectionSummary,
+}
+
+impl CollectedBatches {
+    /// Returns the schema of the batches.
+    pub fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    /// Returns the number of collected rows.
+    pub fn num_rows(&self) -> usize {
+        self.summary.rows
+    }
+
+    /// Returns true if no row was collected.
+    pub fn is_empty(&self) -> bool {
+        self.summary.rows == 0
+    }
+
+    /// Returns true if rows were left out because of the limits.
+    pub fn is_truncated(&self) -> bool {
+        self.summary.truncated
+    }
+
+    /// Returns how much of the result set was kept.
+    pub fn summary(&self) -> CollectionSummary {
+        self.summary
+    }
+
+    /// Iterates over the collected batches in query order.
+    ///
+    /// Batches held in memory come first; spilled batches are read back from
+    /// disk one at a time.
+    pub fn iter(&self) -> Result<Box<dyn Iterator<Item = Result<RecordBatch>> + '_>> {
+        let in_memory = self.batches.iter().cloned().map(Ok);
+        match &self.spill {
+            Some(spill) => {
+                let reader = FileReader::try_new(BufReader::new(File::open(&spill.path)?), None)?;
+                Ok(Box::new(
+                    in_memory.chain(reader.map(|batch| batch.map_err(TermError::from))),
+                ))
+            }
+            None => Ok(Box::new(in_memory)),
+        }
+    }
+}
+
+/// An Arrow IPC file holding spilled batches, removed when dropped.
+#[derive(Debug)]
+struct SpillFile {
+    path: PathBuf,
+}
+
+impl SpillFile {
+    fn create(dir: &Path, schema: &SchemaRef) -> Result<(Self, FileWriter<BufWriter<File>>)> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!("term-collect-{}.arrow", uuid::Uuid::new_v4()));
+        let file = File::create(&path)?;
+        let spill = Self { path };
+        let writer = FileWriter::try_new(BufWriter::new(file), schema)?;
+        Ok((spill, writer))
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            warn!(path = %self.path.display(), error = %e, "Failed to remove spill file");
+        }
+    }
+}
+
+/// Executes a DataFrame and collects its batches within the [`CollectionLimits`]
+/// of the current validation context, or the default limits outside a run.
+///
+/// Like [`collect_with_metrics`](super::collect_with_metrics), the plan metrics
+/// are recorded in the current validation context. Execution stops as soon as
+/// the row limit is reached, or the memory limit without a spill directory.
+/// The query should order its rows when it may be truncated, so that the same
+/// rows are kept from run to run.
+pub async fn collect_limited(df: DataFrame) -> Result<CollectedBatches> {
+    let validation_ctx = current_validation_context();
+    let limits = validation_ctx.collection_limits();
+
+    let mut task_ctx = df.task_ctx();
+    if let Some(batch_size) = limits.batch_size {
+        let config = task_ctx
+            .session_config()
+            .clone()
+            .with_batch_size(batch_size);
+        task_ctx = task_ctx.with_session_config(config);
+    }
+    let plan = df.create_physical_plan().await?;
+    let schema = plan.schema();
+    let mut stream = execute_stream(Arc::clone(&plan), Arc::new(task_ctx))?;
+
+    let mut batches = Vec::new();
+    let mut spill: Option<(SpillFile, FileWriter<BufWriter<File>>)> = None;
+    let mut summary = CollectionSummary::default();
+    let mut bytes = 0usize;
+    while let Some(batch) = stream.next().await {
+        let batch = batch?;
+        if batch.num_rows() == 0 {
+            continue;
+        }
+        let remaining_rows = limits.max_rows - summary.rows;
+        if remaining_rows == 0 {
+            summary.truncated = true;
+            break;
+        }
+        let batch = if batch.num_rows() > remaining_rows {
+            summary.truncated = true;
+            batch.slice(0, remaining_rows)
+        } else {
+            batch
+        };
+
+        let batch_bytes = batch.get_array_memory_size();
+        if spill.is_none() && bytes + batch_bytes > limits.max_bytes {
+            match &limits.spill_dir {
+                Some(dir) => spill = Some(SpillFile::create(dir, &schema)?),
+                None => {
+                    // Keep the rows that fit, estimated from the average row size
+                    summary.truncated = true;
+                    let rows_per_byte = batch.num_rows() as f64 / batch_bytes.max(1) as f64;
+                    let fitting = ((limits.max_bytes - bytes) as f64 * rows_per_byte) as usize;
+                    if fitting > 0 {
+                        summary.rows += fitting;
+                        batches.push(batch.slice(0, fitting));
+                    }
+                    break;
+                }
+            }
+        }
+
+        summary.rows += batch.num_rows();
+        match &mut spill {
+            Some((_, writer)) => {
+                writer.write(&batch)?;
+                summary.spilled_rows += batch.num_rows();
+            }
+            None => {
+                bytes += batch_bytes;
+                batches.push(batch);
+            }
+        }
+        if summary.truncated {
+            break;
+        }
+    }
+    drop(stream);
+
+    if let Some(collector) = validation_ctx.performance_collector() {
+        collector.record(&QueryMetrics::from_plan(plan.as_ref()));
+    }
+
+    let spill = match spill {
+        Some((file, mut writer)) => {
+            writer.finish()?;
+            Some(file)
+        }
+        None => None,
+    };
+    if summary.truncated {
+        warn!(
+            rows = summary.rows,
+            max_rows = limits.max_rows,
+            max_bytes = limits.max_bytes,
+            "Collected result set truncated at the collection limits"
+        );
+    }
+    if summary.spilled_rows > 0 {
+        debug!(
+            spilled_rows = summary.spilled_rows,
+            "Spilled collected rows to disk"
+        );
+    }
+
+    Ok(CollectedBatches {
+        schema,
+        batches,
+        spill,
+        summary,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ValidationContext, CURRENT_CONTEXT};
+    use datafusion::prelude::SessionContext;
+
+    async fn collect(limits: CollectionLimits) -> CollectedBatches {
+        let ctx = SessionContext::new();
+        let df = ctx
+            .sql("SELECT value, value * 2 AS doubled FROM generate_series(1, 1000) ORDER BY value")
+            .await
+            .unwrap();
+        CURRENT_CONTEXT
+            .scope(
+                ValidationContext::new("data").with_collection_limits(Arc::new(limits)),
+                collect_limited(df),
+            )
+            .await
+            .unwrap()
+    }
+
+    fn values(collected: &CollectedBatches) -> Vec<i64> {
+        collected
+            .iter()
+            .unwrap()
+            .flat_map(|batch| {
+                let batch = batch.unwrap();
+                let values = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<arrow::array::Int64Array>()
+                    .unwrap()
+                    .clone();
+                values.values().to_vec()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_row_limit() {
+        let collected = collect(CollectionLimits::new().max_rows(150).batch_size(64)).await;
+        assert!(collected.is_truncated());
+        assert_eq!(values(&collected), (1..=150).collect::<Vec<_>>());
+
+        let mut details = BTreeMap::new();
+        collected.summary().record(&mut details);
+        assert_eq!(details[COLLECTION_TRUNCATED_DETAIL], 1.0);
+        assert_eq!(details[COLLECTION_ROWS_DETAIL], 150.0);
+
+        let complete = collect(CollectionLimits::new().max_rows(1000)).await;
+        assert!(!complete.is_truncated());
+        assert_eq!(complete.num_rows(), 1000);
+        let mut details = BTreeMap::new();
+        complete.summary().record(&mut details);
+        assert!(details.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_byte_limit_keeps_a_prefix() {
+        let collected = collect(CollectionLimits::new().max_bytes(2048).batch_size(100)).await;
+        assert!(collected.is_truncated());
+        assert!(collected.num_rows() > 0 && collected.num_rows() < 1000);
+        let expected: Vec<i64> = (1..=collected.num_rows() as i64).collect();
+        assert_eq!(values(&collected), expected);
+    }
+
+    #[tokio::test]
+    async fn test_spilled_batches_are_read_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let collected = collect(
+            CollectionLimits::new()
+                .max_bytes(2048)
+                .batch_size(100)
+                .spill_to(dir.path()),
+        )
+        .await;
+        assert!(!collected.is_truncated());
+        assert!(collected.summary().spilled_rows > 0);
+        assert_eq!(values(&collected), (1..=1000).collect::<Vec<_>>());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        drop(collected);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,261 @@
+//! Column mappings between a suite's logical columns and a physical table.
+//!
+//! The same logical dataset often arrives with different column names, such as
+//! `cust_id` from one vendor and `customer_id` from another. A suite written
+//! against the logical names can validate either table with a mapping from
+//! logical to physical names, set with
+//! [`ValidationSuiteBuilder::with_column_mapping`](super::ValidationSuiteBuilder::with_column_mapping)
+//! or per run with [`RunOptions::with_column_mapping`](super::RunOptions::with_column_mapping).
+//!
+//! For the duration of the run the validated table is replaced by a view that
+//! renames the mapped physical columns to their logical names and passes the
+//! other columns through. Constraints, reports and query planning see only the
+//! logical names, and DataFusion resolves the view to the physical columns when
+//! the queries are planned. Mappings to columns that do not exist and columns
+//! referenced by the suite that are neither mapped nor present in the table
+//! fail the run before any constraint runs, with a
+//! [`ConfigReport`](super::ConfigReport) listing all of them.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use datafusion::common::Column;
+use datafusion::datasource::TableProvider;
+use datafusion::prelude::*;
+use tracing::{debug, warn};
+
+use super::{Check, ConfigIssue, ConfigReport};
+use crate::prelude::*;
+
+/// Collects a column mapping given as logical and physical column names.
+pub(crate) fn collect<I, K, V>(mapping: I) -> BTreeMap<String, String>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<String>,
+{
+    mapping
+        .into_iter()
+        .map(|(logical, physical)| (logical.into(), physical.into()))
+        .collect()
+}
+
+/// Applies `mapping` to `source`, the plan of the validated table `table`.
+///
+/// Mapped physical columns are renamed to their logical names; physical columns
+/// whose name is used as a logical name are dropped, so the logical name refers
+/// to the mapped column. Fails with [`TermError::InvalidConfiguration`] listing
+/// every mapped physical column that does not exist and every column in
+/// `referenced` missing from the result.
+pub(crate) fn apply(
+    source: DataFrame,
+    table: &str,
+    mapping: &BTreeMap<String, String>,
+    referenced: &BTreeSet<String>,
+) -> Result<DataFrame> {
+    let schema = source.schema().clone();
+    let physical_columns: BTreeSet<&str> = schema
+        .fields()
+        .iter()
+        .map(|field| field.name().as_str())
+        .collect();
+
+    let mut report = ConfigReport::new(format!("column mapping of table '{table}'"));
+    report.extend(
+        mapping
+            .iter()
+            .filter(|(_, physical)| !physical_columns.contains(physical.as_str()))
+            .map(|(logical, physical)| {
+                ConfigIssue::error(
+                    format!("column_mapping.{logical}"),
+                    format!("Mapping {logical} -> {physical} refers to a column that does not exist in table '{table}'"),
+                )
+            }),
+    );
+
+    let mut projection = Vec::new();
+    let mut logical_columns = BTreeSet::new();
+    for field in schema.fields() {
+        let physical = field.name();
+        let logicals: Vec<&String> = mapping
+            .iter()
+            .filter(|(_, mapped)| *mapped == physical)
+            .map(|(logical, _)| logical)
+            .collect();
+        if logicals.is_empty() {
+            if !mapping.contains_key(physical) {
+                projection.push(Expr::Column(Column::new_unqualified(physical)));
+                logical_columns.insert(physical.clone());
+            }
+            continue;
+        }
+        for logical in logicals {
+            projection.push(Expr::Column(Column::new_unqualified(physical)).alias(logical));
+            logical_columns.insert(logical.clone());
+        }
+    }
+
+    report.extend(
+        referenced
+            .iter()
+            // Mapped columns missing from the table are reported above
+            .filter(|column| !logical_columns.contains(*column) && !mapping.contains_key(*column))
+            .map(|column| {
+                ConfigIssue::error(
+                    format!("column_mapping.{column}"),
+                    format!("Column '{column}' referenced by the suite is not in table '{table}' and has no mapping"),
+                )
+            }),
+    );
+    report.into_result()?;
+
+    debug!(table.name = %table, mapping.columns = mapping.len(), "Applied column mapping");
+    Ok(source.select(projection)?)
+}
+
+/// Returns the unqualified columns referenced by the constraints of `checks`.
+///
+/// Qualified columns such as `customers.id` belong to other tables and are left
+/// out.
+pub(crate) fn referenced_columns(checks: &[Arc<Check>]) -> BTreeSet<String> {
+    checks
+        .iter()
+        .flat_map(|check| check.constraints())
+        .flat_map(|constraint| {
+            let metadata = constraint.metadata();
+            constraint
+                .column()
+                .map(str::to_string)
+                .into_iter()
+                .chain(metadata.columns)
+        })
+        .filter(|column| !column.is_empty() && !column.contains('.'))
+        .collect()
+}
+
+/// Describes the mapped columns among `columns`, such as
+/// `customer_id from cust_id`, or returns `None` if none is mapped.
+pub(crate) fn describe(columns: &[String], mapping: &BTreeMap<String, String>) -> Option<String> {
+    let mapped: Vec<String> = columns
+        .iter()
+        .filter_map(|column| {
+            mapping
+                .get(column)
+                .filter(|physical| *physical != column)
+                .map(|physical| format!("{column} from {physical}"))
+        })
+        .collect();
+    (!mapped.is_empty()).then(|| mapped.join(", "))
+}
+
+/// Registers `view` as the validated table `table` for the duration of a run.
+///
+/// Returns the table it replaces, to be passed to [`restore`].
+pub(crate) fn register(
+    ctx: &SessionContext,
+    table: &str,
+    view: DataFrame,
+) -> Result<Option<Arc<dyn TableProvider>>> {
+    let replaced = ctx.deregister_table(table)?;
+    ctx.register_table(table, view.into_view())?;
+    Ok(replaced)
+}
+
+/// Removes the view registered by [`register`] and registers the table it
+/// replaced again.
+pub(crate) fn restore(ctx: &SessionContext, table: &str, replaced: Option<Arc<dyn TableProvider>>) {
+    if let Err(e) = ctx.deregister_table(table) {
+        warn!(table.name = %table, error = %e, "Failed to deregister mapped table");
+    }
+    if let Some(provider) = replaced {
+        if let Err(e) = ctx.register_table(table, provider) {
+            warn!(table.name = %table, error = %e, "Failed to restore mapped table");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn source() -> Result<(SessionContext, DataFrame)> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE vendor (cust_id BIGINT, customer_id BIGINT, email VARCHAR) AS VALUES (1, 9, 'a')")
+            .await?
+            .collect()
+            .await?;
+        let df = ctx.table("vendor").await?;
+        Ok((ctx, df))
+    }
+
+    fn mapping() -> BTreeMap<String, String> {
+        BTreeMap::from([("customer_id".to_string(), "cust_id".to_string())])
+    }
+
+    #[tokio::test]
+    async fn test_mapped_columns_replace_physical_names() -> Result<()> {
+        let (_ctx, df) = source().await?;
+        let referenced = BTreeSet::from(["customer_id".to_string(), "email".to_string()]);
+        let view = apply(df, "vendor", &mapping(), &referenced)?;
+
+        let columns: Vec<_> = view
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+        assert_eq!(columns, vec!["customer_id", "email"]);
+
+        let batches = view.select_columns(&["customer_id"])?.collect().await?;
+        let ids = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<arrow::array::Int64Array>()
+            .unwrap();
+        assert_eq!(ids.value(0), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_missing_columns_fail_fast() -> Result<()> {
+        let (_ctx, df) = source().await?;
+        let referenced = BTreeSet::from(["phone".to_string(), "zip".to_string()]);
+        let error = apply(df.clone(), "vendor", &mapping(), &referenced).unwrap_err();
+        let TermError::InvalidConfiguration(report) = error else {
+            panic!("expected an invalid configuration, got {error}");
+        };
+        let paths: Vec<&str> = report
+            .issues
+            .iter()
+            .map(|issue| issue.path.as_str())
+            .collect();
+        assert_eq!(paths, ["column_mapping.phone", "column_mapping.zip"]);
+        assert!(report.issues[0]
+            .message
+            .contains("not in table 'vendor' and has no mapping"));
+
+        // An unknown mapping is reported once, even for a referenced column
+        let unknown = BTreeMap::from([("email".to_string(), "mail".to_string())]);
+        let referenced = BTreeSet::from(["email".to_string(), "zip".to_string()]);
+        let error = apply(df, "vendor", &unknown, &referenced).unwrap_err();
+        let TermError::InvalidConfiguration(report) = error else {
+            panic!("expected an invalid configuration, got {error}");
+        };
+        assert_eq!(report.issues.len(), 2);
+        assert!(report.issues[0]
+            .message
+            .contains("Mapping email -> mail refers to a column that does not exist"));
+        assert_eq!(report.issues[1].path, "column_mapping.zip");
+        Ok(())
+    }
+
+    #[test]
+    fn test_describe() {
+        let columns = vec!["customer_id".to_string(), "email".to_string()];
+        assert_eq!(
+            describe(&columns, &mapping()).as_deref(),
+            Some("customer_id from cust_id")
+        );
+        assert_eq!(describe(&columns[1..], &mapping()), None);
+    }
+}
@@ -0,0 +1,239 @@
+//! Dotted paths to the fields of struct columns.
+//!
+//! Nested data such as Parquet files often has struct columns, for example
+//! `address: Struct<city, zip>`. Constraints supporting nested data accept a
+//! dotted path such as `address.zip` in place of a column name. [`ColumnPath`]
+//! resolves such a path against the schema of the validated table, reporting the
+//! segment that does not exist, and renders it as a field access expression
+//! (`"address"['zip']`).
+//!
+//! A top-level column whose name contains dots takes precedence over a nested
+//! path of the same spelling, so tables with dotted column names keep working.
+
+use arrow::datatypes::{DataType, Field, Schema};
+use datafusion::prelude::*;
+
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+
+/// A column of a table, or a field nested in its struct columns.
+///
+/// # Examples
+///
+/// ```rust
+/// use arrow::datatypes::{DataType, Field, Fields, Schema};
+/// use term_guard::core::ColumnPath;
+///
+/// let address = Fields::from(vec![
+///     Field::new("city", DataType::Utf8, true),
+///     Field::new("zip", DataType::Utf8, true),
+/// ]);
+/// let schema = Schema::new(vec![Field::new("address", DataType::Struct(address), true)]);
+///
+/// let path = ColumnPath::resolve(&schema, "address.zip").unwrap();
+/// assert!(path.is_nested());
+/// assert_eq!(path.data_type(), &DataType::Utf8);
+/// assert_eq!(path.to_sql().unwrap(), r#""address"['zip']"#);
+///
+/// let error = ColumnPath::resolve(&schema, "address.street").unwrap_err();
+/// assert!(error.to_string().contains("struct 'address' has no field 'street'"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnPath {
+    segments: Vec<String>,
+    data_type: DataType,
+}
+
+impl ColumnPath {
+    /// Resolves `path` against `schema`.
+    ///
+    /// Fails with a configuration error naming the missing segment if the path
+    /// does not exist, or the column that is not a struct if the path descends
+    /// into one.
+    pub fn resolve(schema: &Schema, path: &str) -> Result<Self> {
+        if let Ok(field) = schema.field_with_name(path) {
+            return Ok(Self {
+                segments: vec![path.to_string()],
+                data_type: field.data_type().clone(),
+            });
+        }
+
+        let mut segments = path.split('.');
+        let first = segments.next().unwrap_or_default();
+        let mut field: &Field = schema.field_with_name(first).map_err(|_| {
+            TermError::Configuration(format!(
+                "Column path '{path}' does not exist: the table has no column '{first}'"
+            ))
+        })?;
+
+        let mut resolved = vec![first.to_string()];
+        for segment in segments {
+            let parent = resolved.join(".");
+            let DataType::Struct(fields) = field.data_type() else {
+                return Err(TermError::Configuration(format!(
+                    "Column path '{path}' does not exist: '{parent}' is a {} column, not a struct",
+                    field.data_type()
+                )));
+            };
+            field = fields
+                .iter()
+                .find(|child| child.name() == segment)
+                .ok_or_else(|| {
+                    let names: Vec<&str> =
+                        fields.iter().map(|child| child.name().as_str()).collect();
+                    TermError::Configuration(format!(
+                        "Column path '{path}' does not exist: struct '{parent}' has no field \
+                         '{segment}' (fields: {})",
+                        names.join(", ")
+                    ))
+                })?;
+            resolved.push(segment.to_string());
+        }
+
+        Ok(Self {
+            segments: resolved,
+            data_type: field.data_type().clone(),
+        })
+    }
+
+    /// Resolves `path` against the schema of the table `table_name` of `ctx`.
+    pub async fn resolve_in(ctx: &SessionContext, table_name: &str, path: &str) -> Result<Self> {
+        let provider = ctx.table_provider(table_name).await?;
+        Self::resolve(&provider.schema(), path)
+    }
+
+    /// Returns the top-level column followed by the names of the nested fields.
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Returns true if the path refers to a field of a struct column.
+    pub fn is_nested(&self) -> bool {
+        self.segments.len() > 1
+    }
+
+    /// Returns the data type of the referenced column or field.
+    pub fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    /// Renders the path as a SQL expression.
+    ///
+    /// Every segment is validated with [`SqlSecurity::validate_identifier`].
+    pub fn to_sql(&self) -> Result<String> {
+        let (column, fields) = self
+            .segments
+            .split_first()
+            .ok_or_else(|| TermError::Internal("Empty column path".to_string()))?;
+        let mut sql = SqlSecurity::escape_identifier(column)?;
+        for field in fields {
+            SqlSecurity::validate_identifier(field)?;
+            sql.push_str(&format!("['{}']", field.replace('\'', "''")));
+        }
+        Ok(sql)
+    }
+}
+
+/// Returns the SQL expression of a column of `table_name`, resolving dotted
+/// names to nested fields.
+///
+/// Names without dots are escaped without looking at the schema.
+pub(crate) async fn column_expression(
+    ctx: &SessionContext,
+    table_name: &str,
+    column: &str,
+) -> Result<String> {
+    if column.contains('.') {
+        ColumnPath::resolve_in(ctx, table_name, column)
+            .await?
+            .to_sql()
+    } else {
+        SqlSecurity::escape_identifier(column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::Fields;
+
+    fn schema() -> Schema {
+        let address = Fields::from(vec![
+            Field::new("city", DataType::Utf8, true),
+            Field::new("zip", DataType::Utf8, true),
+        ]);
+        Schema::new(vec![
+            Field::new("address", DataType::Struct(address), true),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("legacy.code", DataType::Int32, true),
+        ])
+    }
+
+    #[test]
+    fn test_resolve_paths() {
+        let schema = schema();
+
+        let name = ColumnPath::resolve(&schema, "name").unwrap();
+        assert!(!name.is_nested());
+        assert_eq!(name.to_sql().unwrap(), "\"name\"");
+
+        let zip = ColumnPath::resolve(&schema, "address.zip").unwrap();
+        assert_eq!(zip.segments(), ["address", "zip"]);
+        assert_eq!(zip.to_sql().unwrap(), "\"address\"['zip']");
+
+        // A dotted top-level column wins over a nested path
+        let legacy = ColumnPath::resolve(&schema, "legacy.code").unwrap();
+        assert!(!legacy.is_nested());
+        assert_eq!(legacy.data_type(), &DataType::Int32);
+    }
+
+    #[test]
+    fn test_missing_paths_are_explained() {
+        let schema = schema();
+
+        let error = ColumnPath::resolve(&schema, "shipping.zip").unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("the table has no column 'shipping'"));
+
+        let error = ColumnPath::resolve(&schema, "address.street").unwrap_err();
+        assert!(error.to_string().contains("(fields: city, zip)"), "{error}");
+
+        let error = ColumnPath::resolve(&schema, "name.first").unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("'name' is a Utf8 column, not a struct"));
+    }
+
+    #[tokio::test]
+    async fn test_nested_field_query() {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE TABLE data AS SELECT named_struct('city', 'Oslo', 'zip', CAST(NULL AS VARCHAR)) AS address \
+             UNION ALL SELECT named_struct('city', 'Bergen', 'zip', '5003')",
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+        let zip = column_expression(&ctx, "data", "address.zip")
+            .await
+            .unwrap();
+        let batches = ctx
+            .sql(&format!("SELECT COUNT({zip}) FROM data"))
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        let count = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<arrow::array::Int64Array>()
+            .unwrap()
+            .value(0);
+        assert_eq!(count, 1);
+    }
+}
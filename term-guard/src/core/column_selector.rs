@@ -0,0 +1,151 @@
+//! Column discovery for check templates.
+//!
+//! Wide tables often need the same constraints on dozens of columns. A
+//! [`ColumnSelector`] picks those columns from a table schema by data type and
+//! name, and the result feeds [`CheckBuilder::apply_to_columns`](super::CheckBuilder::apply_to_columns).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::core::{Check, ColumnSelector, ConstraintOptions};
+//! use arrow::datatypes::DataType;
+//! use datafusion::prelude::*;
+//!
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! let ctx = SessionContext::new();
+//! ctx.sql("CREATE TABLE facts (id INT, m_revenue DOUBLE, m_cost DOUBLE, note VARCHAR)
+//!          AS VALUES (1, 10.0, 4.0, 'a')")
+//!     .await?
+//!     .collect()
+//!     .await?;
+//!
+//! let measures = ColumnSelector::new()
+//!     .data_type(DataType::Float64)
+//!     .name_matches("^m_")?
+//!     .resolve(&ctx, "facts")
+//!     .await?;
+//! assert_eq!(measures, vec!["m_revenue", "m_cost"]);
+//!
+//! let check = Check::builder("measures")
+//!     .apply_to_columns(&measures, |builder, column| {
+//!         builder.completeness(column, ConstraintOptions::new().with_threshold(0.99))
+//!     })
+//!     .build();
+//! assert_eq!(check.constraint_label(1), "completeness.m_cost");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+
+use arrow::datatypes::{DataType, Schema};
+use datafusion::prelude::*;
+use regex::Regex;
+
+use crate::prelude::*;
+
+/// Selects columns from a table schema by data type and name.
+///
+/// An empty selector matches every column. Columns are returned in schema
+/// order.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnSelector {
+    data_types: Vec<DataType>,
+    pattern: Option<Regex>,
+    excluded: Vec<String>,
+}
+
+impl ColumnSelector {
+    /// Creates a selector matching every column.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the selection to columns of `data_type`.
+    ///
+    /// Calling this several times selects columns of any of the given types.
+    pub fn data_type(mut self, data_type: DataType) -> Self {
+        self.data_types.push(data_type);
+        self
+    }
+
+    /// Restricts the selection to columns whose name matches `pattern`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pattern` is not a valid regular expression.
+    pub fn name_matches(mut self, pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).map_err(|e| {
+            TermError::Configuration(format!("Invalid column name pattern '{pattern}': {e}"))
+        })?;
+        self.pattern = Some(regex);
+        Ok(self)
+    }
+
+    /// Excludes the given columns from the selection.
+    pub fn exclude<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.excluded.extend(columns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Returns whether a column with `name` and `data_type` is selected.
+    pub fn matches(&self, name: &str, data_type: &DataType) -> bool {
+        (self.data_types.is_empty() || self.data_types.contains(data_type))
+            && self.pattern.as_ref().map_or(true, |p| p.is_match(name))
+            && !self.excluded.iter().any(|excluded| excluded == name)
+    }
+
+    /// Returns the selected columns of `schema`.
+    pub fn select(&self, schema: &Schema) -> Vec<String> {
+        schema
+            .fields()
+            .iter()
+            .filter(|field| self.matches(field.name(), field.data_type()))
+            .map(|field| field.name().clone())
+            .collect()
+    }
+
+    /// Returns the selected columns of the table registered as `table_name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table is not registered.
+    pub async fn resolve(&self, ctx: &SessionContext, table_name: &str) -> Result<Vec<String>> {
+        let df = ctx.table(table_name).await?;
+        Ok(self.select(df.schema().as_arrow()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::Field;
+
+    #[test]
+    fn test_select_by_type_pattern_and_exclusion() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("m_revenue", DataType::Float64, true),
+            Field::new("m_units", DataType::Int64, true),
+            Field::new("m_cost", DataType::Float64, true),
+            Field::new("score", DataType::Float64, true),
+        ]);
+
+        assert_eq!(ColumnSelector::new().select(&schema).len(), 5);
+
+        let floats = ColumnSelector::new().data_type(DataType::Float64);
+        assert_eq!(floats.select(&schema), vec!["m_revenue", "m_cost", "score"]);
+
+        let measures = ColumnSelector::new()
+            .data_type(DataType::Float64)
+            .data_type(DataType::Int64)
+            .name_matches("^m_")
+            .unwrap()
+            .exclude(["m_units"]);
+        assert_eq!(measures.select(&schema), vec!["m_revenue", "m_cost"]);
+
+        assert!(ColumnSelector::new().name_matches("(").is_err());
+    }
+}
@@ -0,0 +1,68 @@
+//! Column statistics computed ahead of validation.
+//!
+//! Runners that profile data before validating it (such as
+//! [`ProfileAndValidate`](crate::analyzers::ProfileAndValidate)) pass these
+//! statistics to [`RunOptions::with_statistics`](super::RunOptions::with_statistics).
+//! Constraints that can be answered from them implement
+//! [`Constraint::evaluate_from_statistics`](super::Constraint::evaluate_from_statistics)
+//! and skip their own queries.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Exact statistics for a single column.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ColumnStatistics {
+    /// Total number of rows in the table
+    pub row_count: u64,
+    /// Number of null values in the column
+    pub null_count: u64,
+    /// Number of distinct non-null values, if known
+    pub distinct_count: Option<u64>,
+    /// Minimum value, only set for numeric columns
+    pub min: Option<f64>,
+    /// Maximum value, only set for numeric columns
+    pub max: Option<f64>,
+    /// Fraction of sampled values matching the dominant type, if known
+    pub type_consistency: Option<f64>,
+}
+
+impl ColumnStatistics {
+    /// Returns the number of non-null values.
+    pub fn non_null_count(&self) -> u64 {
+        self.row_count.saturating_sub(self.null_count)
+    }
+}
+
+/// Precomputed statistics for the columns of the table being validated.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrecomputedStatistics {
+    columns: HashMap<String, ColumnStatistics>,
+}
+
+impl PrecomputedStatistics {
+    /// Creates an empty set of statistics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the statistics for a column, replacing any previous entry.
+    pub fn insert(&mut self, column: impl Into<String>, statistics: ColumnStatistics) {
+        self.columns.insert(column.into(), statistics);
+    }
+
+    /// Returns the statistics for a column, if available.
+    pub fn column(&self, column: &str) -> Option<&ColumnStatistics> {
+        self.columns.get(column)
+    }
+
+    /// Returns the number of columns with statistics.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns true if no column statistics are available.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+}
@@ -0,0 +1,291 @@
+//! Every problem found while constructing validation from configuration.
+//!
+//! Suites built from definitions, imported from other tools or run with
+//! parameters and column mappings can have several problems at once. Instead
+//! of failing on the first one, construction collects each problem as a
+//! [`ConfigIssue`] and fails with [`TermError::InvalidConfiguration`] carrying
+//! a [`ConfigReport`] that lists them all, so they can be fixed in one pass.
+//!
+//! Issues locate the problem with a `path` into the configuration, such as
+//! `checks[1].constraints[0]` or `parameters.email_completeness`, and name the
+//! check and the index of the constraint when they concern one. Reports
+//! serialize to JSON for tools and render as a list for people.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::core::{Check, ConstraintOptions, ValidationSuite};
+//! use term_guard::error::TermError;
+//!
+//! let err = ValidationSuite::builder("customers")
+//!     .check(
+//!         Check::builder("contact")
+//!             .completeness("email", ConstraintOptions::new().with_threshold(95.0))
+//!             .validates_email("email", 1.5)
+//!             .build(),
+//!     )
+//!     .try_build()
+//!     .unwrap_err();
+//!
+//! let TermError::InvalidConfiguration(report) = err else {
+//!     panic!("expected an invalid configuration");
+//! };
+//! assert_eq!(report.errors().count(), 2);
+//! assert_eq!(report.issues[1].path, "checks[0].constraints[1]");
+//! assert_eq!(report.issues[1].check.as_deref(), Some("contact"));
+//! println!("{report}");
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::prelude::*;
+
+/// How serious a [`ConfigIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigSeverity {
+    /// Part of the configuration was ignored or approximated
+    Warning,
+    /// The configuration cannot be used
+    Error,
+}
+
+impl fmt::Display for ConfigSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSeverity::Warning => write!(f, "warning"),
+            ConfigSeverity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// One problem found in a configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigIssue {
+    /// Location of the problem within the configuration, empty if it concerns
+    /// the whole configuration
+    pub path: String,
+    /// Name of the check the problem concerns
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check: Option<String>,
+    /// Index of the constraint within its check, in declaration order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraint_index: Option<usize>,
+    /// What is wrong
+    pub message: String,
+    /// How serious the problem is
+    pub severity: ConfigSeverity,
+}
+
+impl ConfigIssue {
+    /// Creates an error at `path`.
+    pub fn error(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(ConfigSeverity::Error, path, message)
+    }
+
+    /// Creates a warning at `path`.
+    pub fn warning(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(ConfigSeverity::Warning, path, message)
+    }
+
+    fn new(severity: ConfigSeverity, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            check: None,
+            constraint_index: None,
+            message: message.into(),
+            severity,
+        }
+    }
+
+    /// Names the check the problem concerns.
+    pub fn in_check(mut self, check: impl Into<String>) -> Self {
+        self.check = Some(check.into());
+        self
+    }
+
+    /// Sets the index of the constraint the problem concerns.
+    pub fn at_constraint(mut self, index: usize) -> Self {
+        self.constraint_index = Some(index);
+        self
+    }
+
+    /// Returns true if the issue is an error.
+    pub fn is_error(&self) -> bool {
+        self.severity == ConfigSeverity::Error
+    }
+
+    /// Prepends `prefix` to the path, such as `checks[2]` to the path of an
+    /// issue found in the third check.
+    pub(crate) fn under(mut self, prefix: &str) -> Self {
+        self.path = match self.path.as_str() {
+            "" => prefix.to_string(),
+            path if path.starts_with('[') => format!("{prefix}{path}"),
+            path => format!("{prefix}.{path}"),
+        };
+        self
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}: {}", self.severity, self.message)
+        } else {
+            write!(f, "{} at {}: {}", self.severity, self.path, self.message)
+        }
+    }
+}
+
+/// Every problem found in one configuration.
+///
+/// Displays as a summary line followed by one line per issue, errors first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigReport {
+    /// What the configuration defines, such as `suite 'customers'`
+    pub subject: String,
+    /// The problems, in the order they were found
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl ConfigReport {
+    /// Creates an empty report for `subject`.
+    pub fn new(subject: impl Into<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            issues: Vec::new(),
+        }
+    }
+
+    /// Adds an issue.
+    pub fn push(&mut self, issue: ConfigIssue) {
+        self.issues.push(issue);
+    }
+
+    /// Returns true if the report has no issues.
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns true if any issue is an error.
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(ConfigIssue::is_error)
+    }
+
+    /// Returns the errors.
+    pub fn errors(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues.iter().filter(|issue| issue.is_error())
+    }
+
+    /// Returns the warnings.
+    pub fn warnings(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues.iter().filter(|issue| !issue.is_error())
+    }
+
+    /// Returns `Ok` if the report has no errors and
+    /// [`TermError::InvalidConfiguration`] otherwise.
+    pub fn into_result(self) -> Result<Self> {
+        if !self.has_errors() {
+            return Ok(self);
+        }
+        warn!(config.subject = %self.subject, errors = self.errors().count(), "{self}");
+        Err(TermError::InvalidConfiguration(Box::new(self)))
+    }
+
+    /// Serializes the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| TermError::Serialization(e.to_string()))
+    }
+}
+
+impl Extend<ConfigIssue> for ConfigReport {
+    fn extend<I: IntoIterator<Item = ConfigIssue>>(&mut self, issues: I) {
+        self.issues.extend(issues);
+    }
+}
+
+impl fmt::Display for ConfigReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors = self.errors().count();
+        let warnings = self.issues.len() - errors;
+        let subject = if self.subject.is_empty() {
+            "configuration"
+        } else {
+            self.subject.as_str()
+        };
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        write!(f, "{subject} has {errors} error{}", plural(errors))?;
+        if warnings > 0 {
+            write!(f, " and {warnings} warning{}", plural(warnings))?;
+        }
+        for issue in self.errors().chain(self.warnings()) {
+            write!(f, "\n  - {issue}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> ConfigReport {
+        let mut report = ConfigReport::new("suite 'orders'");
+        report.push(ConfigIssue::warning(
+            "models[0].tests[1]",
+            "unsupported test",
+        ));
+        report.push(
+            ConfigIssue::error("constraints[2]", "threshold out of range")
+                .in_check("amounts")
+                .at_constraint(2)
+                .under("checks[1]"),
+        );
+        report.push(ConfigIssue::error("", "no checks"));
+        report
+    }
+
+    #[test]
+    fn test_display_lists_errors_before_warnings() {
+        assert_eq!(
+            report().to_string(),
+            "suite 'orders' has 2 errors and 1 warning
+  - error at checks[1].constraints[2]: threshold out of range
+  - error: no checks
+  - warning at models[0].tests[1]: unsupported test"
+        );
+    }
+
+    #[test]
+    fn test_into_result_fails_only_on_errors() {
+        let mut warnings = ConfigReport::new("dbt schema");
+        warnings.push(ConfigIssue::warning("models[0]", "unsupported test"));
+        assert!(warnings.into_result().is_ok());
+
+        match report().into_result() {
+            Err(TermError::InvalidConfiguration(report)) => assert_eq!(report.errors().count(), 2),
+            other => panic!("expected an invalid configuration, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let report = report();
+        let json = report.to_json().unwrap();
+        assert!(json.contains(r#""severity": "error""#), "{json}");
+        assert!(json.contains(r#""constraint_index": 2"#), "{json}");
+        assert_eq!(serde_json::from_str::<ConfigReport>(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn test_paths_are_prefixed() {
+        assert_eq!(ConfigIssue::error("", "x").under("udfs[0]").path, "udfs[0]");
+        assert_eq!(
+            ConfigIssue::error("[0]", "x").under("checks").path,
+            "checks[0]"
+        );
+    }
+}
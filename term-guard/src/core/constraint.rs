@@ -1,10 +1,15 @@
 //! Constraint trait and related types for validation rules.
 
+use super::{
+    ColumnStatistics, Extensions, MetadataStatistics, ParameterRef, QualityDimension,
+    QuarantineSummary, TableAggregate, EMPTY_TABLE_MESSAGE,
+};
 use crate::prelude::*;
 use async_trait::async_trait;
 use datafusion::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
 
 /// The status of a constraint evaluation.
@@ -45,6 +50,18 @@ pub struct ConstraintResult {
     pub metric: Option<f64>,
     /// Optional message providing additional context
     pub message: Option<String>,
+    /// Rows exported to a quarantine sink, when the constraint has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quarantine: Option<QuarantineSummary>,
+    /// Stable identifier of the evaluated constraint, set by the suite that ran it
+    ///
+    /// See [`constraint_id`] for how the identifier is derived.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraint_id: Option<String>,
+    /// Named measurements behind the result beyond its metric, such as the
+    /// number of values a statistic left out
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub details: BTreeMap<String, f64>,
 }
 
 impl ConstraintResult {
@@ -54,6 +71,9 @@ impl ConstraintResult {
             status: ConstraintStatus::Success,
             metric: None,
             message: None,
+            quarantine: None,
+            constraint_id: None,
+            details: BTreeMap::new(),
         }
     }
 
@@ -63,6 +83,9 @@ impl ConstraintResult {
             status: ConstraintStatus::Success,
             metric: Some(metric),
             message: None,
+            quarantine: None,
+            constraint_id: None,
+            details: BTreeMap::new(),
         }
     }
 
@@ -72,6 +95,9 @@ impl ConstraintResult {
             status: ConstraintStatus::Failure,
             metric: None,
             message: Some(message.into()),
+            quarantine: None,
+            constraint_id: None,
+            details: BTreeMap::new(),
         }
     }
 
@@ -81,6 +107,9 @@ impl ConstraintResult {
             status: ConstraintStatus::Failure,
             metric: Some(metric),
             message: Some(message.into()),
+            quarantine: None,
+            constraint_id: None,
+            details: BTreeMap::new(),
         }
     }
 
@@ -90,8 +119,36 @@ impl ConstraintResult {
             status: ConstraintStatus::Skipped,
             metric: None,
             message: Some(message.into()),
+            quarantine: None,
+            constraint_id: None,
+            details: BTreeMap::new(),
         }
     }
+
+    /// Creates the skipped result of a constraint evaluated on a table without rows.
+    ///
+    /// See the [`EmptyTablePolicy`](super::EmptyTablePolicy) docs for the
+    /// constraints that report it and how a suite can report it as a failure.
+    pub fn empty_table() -> Self {
+        Self::skipped(EMPTY_TABLE_MESSAGE)
+    }
+
+    /// Returns true if the constraint was skipped because its table has no rows.
+    pub fn is_empty_table(&self) -> bool {
+        self.status.is_skipped() && self.message.as_deref() == Some(EMPTY_TABLE_MESSAGE)
+    }
+
+    /// Attaches the outcome of writing violating rows to a quarantine sink.
+    pub fn with_quarantine(mut self, summary: QuarantineSummary) -> Self {
+        self.quarantine = Some(summary);
+        self
+    }
+
+    /// Records a named measurement in [`details`](Self::details).
+    pub fn with_detail(mut self, name: impl Into<String>, value: f64) -> Self {
+        self.details.insert(name.into(), value);
+        self
+    }
 }
 
 /// Metadata associated with a constraint.
@@ -155,6 +212,25 @@ impl ConstraintMetadata {
 /// This trait defines the interface for all validation rules in the Term library.
 /// Implementations should be stateless and reusable across multiple validations.
 ///
+/// # Concurrency
+///
+/// Checks hold their constraints in an `Arc`, so one constraint may be
+/// evaluated by several runs at the same time, on different threads. The trait
+/// requires `Send + Sync`, and `evaluate` takes `&self`: an evaluation must
+/// depend only on the constraint's configuration and the data, and return the
+/// same result however many evaluations run alongside it.
+///
+/// Avoid interior mutability. A `RefCell` or `Cell` makes a constraint `!Sync`
+/// and fails to compile; wrapping state in a `Mutex` compiles but lets
+/// concurrent evaluations observe each other. State shared within a run, such
+/// as row counts, belongs in the [`MetricCache`](super::MetricCache) of the
+/// [`ValidationContext`](super::ValidationContext). Caches of values derived
+/// from the configuration alone, such as compiled regular expressions, are
+/// fine as long as a lookup returns the same value whether or not it hits.
+///
+/// `test_utils::assert_constraint_concurrent_safe` (with the `test-utils`
+/// feature) evaluates a constraint concurrently and checks the results agree.
+///
 /// # Examples
 ///
 /// ```rust,ignore
@@ -196,6 +272,22 @@ pub trait Constraint: Debug + Send + Sync {
     /// A `Result` containing the constraint evaluation result or an error
     async fn evaluate(&self, ctx: &SessionContext) -> Result<ConstraintResult>;
 
+    /// Evaluates the constraint with per-run dependencies.
+    ///
+    /// Suites run with [`RunOptions::with_extensions`](super::RunOptions::with_extensions)
+    /// call this instead of [`evaluate`](Self::evaluate). Constraints that need
+    /// data from outside the session context, such as a client for an internal
+    /// service, override it and read their dependencies from `extensions`; see
+    /// the [`extensions`](super::extensions) module for an example. The default
+    /// implementation ignores the extensions and calls `evaluate`.
+    async fn evaluate_with(
+        &self,
+        ctx: &SessionContext,
+        _extensions: &Extensions,
+    ) -> Result<ConstraintResult> {
+        self.evaluate(ctx).await
+    }
+
     /// Returns the name of the constraint.
     fn name(&self) -> &str;
 
@@ -215,6 +307,145 @@ pub trait Constraint: Debug + Send + Sync {
         None
     }
 
+    /// Returns a plain-English sentence stating what this constraint requires, such
+    /// as "email must match an email format for at least 99% of rows".
+    ///
+    /// Data contracts rendered by
+    /// [`ContractFormatter`](crate::formatters::contract::ContractFormatter) list it
+    /// for every constraint. Built-in constraints interpolate their columns and
+    /// parameters. The default implementation returns the
+    /// [`description`](Self::description) or the description in the
+    /// [`metadata`](Self::metadata), and otherwise names the constraint and its
+    /// columns.
+    fn describe(&self) -> String {
+        if let Some(description) = self.description() {
+            return description.to_string();
+        }
+        let metadata = self.metadata();
+        if let Some(description) = metadata.description {
+            return description;
+        }
+        if metadata.columns.is_empty() {
+            format!("{} must hold", self.name())
+        } else {
+            format!(
+                "{} must hold for {}",
+                self.name(),
+                describe_list(&metadata.columns)
+            )
+        }
+    }
+
+    /// Evaluates the constraint from precomputed column statistics, if possible.
+    ///
+    /// Runners that profile the data before validation call this before
+    /// [`evaluate`](Self::evaluate) with the statistics of the column returned by
+    /// [`column`](Self::column). Returning `None` falls back to normal evaluation.
+    /// The default implementation returns `None`.
+    fn evaluate_from_statistics(&self, _statistics: &ColumnStatistics) -> Option<ConstraintResult> {
+        None
+    }
+
+    /// Evaluates the constraint from statistics read from file metadata, if possible.
+    ///
+    /// Suites in [metadata-only mode](super::MetadataOnlyMode) call this before
+    /// [`evaluate`](Self::evaluate) with the statistics of the whole validated
+    /// table, such as those of its Parquet footers. Implementations return a
+    /// result only when the statistics they need are present and give the same
+    /// answer as a scan. Returning `None` falls back to normal evaluation.
+    /// The default implementation returns `None`.
+    fn evaluate_from_metadata(&self, _metadata: &MetadataStatistics) -> Option<ConstraintResult> {
+        None
+    }
+
+    /// Returns the tables this constraint reads besides the validated table.
+    ///
+    /// Constraints spanning several tables (foreign keys, cross-table sums, ...)
+    /// override this so that cached results are invalidated when any of the
+    /// involved tables changes. The default implementation returns no tables.
+    fn referenced_tables(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Returns whether the constraint needs every row of its tables to give a
+    /// meaningful result.
+    ///
+    /// [`ValidationSuite::preview`](super::ValidationSuite::preview) skips such
+    /// constraints instead of evaluating them on a sample. The default
+    /// implementation returns `true` for constraints reading other tables (see
+    /// [`referenced_tables`](Self::referenced_tables)), since a sample of one
+    /// table does not reconcile with another.
+    fn requires_full_data(&self) -> bool {
+        !self.referenced_tables().is_empty()
+    }
+
+    /// Returns the aggregates this constraint reads from its referenced tables, if it
+    /// reads nothing else from them.
+    ///
+    /// [`MultiSourceValidator`](super::MultiSourceValidator) uses this to evaluate
+    /// constraints on tables of a secondary session context by transferring these
+    /// aggregates instead of every row. The default implementation returns `None`.
+    fn table_aggregates(&self) -> Option<Vec<TableAggregate>> {
+        None
+    }
+
+    /// Returns the named parameters this constraint resolves at execution time.
+    ///
+    /// Suites check that every referenced parameter is set and within its range
+    /// before evaluating any constraint. The default implementation returns no
+    /// parameters.
+    fn parameters(&self) -> Vec<ParameterRef> {
+        Vec::new()
+    }
+
+    /// Returns the SQL queries this constraint runs against `table_name`, in order.
+    ///
+    /// This lets tests assert on how a constraint's options shape its queries
+    /// without a session context or any data. The queries are built the same way
+    /// as during evaluation and are deterministic for a given configuration, but
+    /// their exact text is not part of the stable API and may change in minor
+    /// releases; prefer checking for the fragments an option controls over
+    /// comparing whole queries. Queries whose text depends on the data are
+    /// returned as they would run against typical column types, and auxiliary
+    /// queries such as quarantine exports are left out.
+    ///
+    /// The default implementation returns no queries, which is also what
+    /// constraints that do not query the validated table return.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::{FormatConstraint, FormatOptions, FormatType};
+    /// use term_guard::core::Constraint;
+    ///
+    /// # fn example() -> term_guard::prelude::Result<()> {
+    /// let constraint = FormatConstraint::new(
+    ///     "email",
+    ///     FormatType::Email,
+    ///     1.0,
+    ///     FormatOptions::new().trim_before_check(true),
+    /// )?;
+    /// let sql = constraint.generate_sql("users")?;
+    /// assert!(sql[0].contains(r#"TRIM("email")"#));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn generate_sql(&self, _table_name: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Returns the data quality dimension this constraint measures.
+    ///
+    /// Reports aggregate outcomes per dimension in
+    /// [`ValidationReport::dimension_summary`](super::ValidationReport::dimension_summary).
+    /// [`CheckBuilder::dimension`](super::CheckBuilder::dimension) and
+    /// [`CheckBuilder::constraint_with_dimension`](super::CheckBuilder::constraint_with_dimension)
+    /// override it. The default implementation returns `None`, which leaves the
+    /// constraint out of the summary.
+    fn dimension(&self) -> Option<QualityDimension> {
+        None
+    }
+
     /// Returns the metadata associated with this constraint.
     ///
     /// The default implementation returns empty metadata for backward compatibility.
@@ -222,11 +453,93 @@ pub trait Constraint: Debug + Send + Sync {
     fn metadata(&self) -> ConstraintMetadata {
         ConstraintMetadata::new()
     }
+
+    /// Returns the configuration that identifies this constraint.
+    ///
+    /// It is hashed together with the constraint name into [`constraint_id`]. The
+    /// default implementation uses the `Debug` representation, which lists the
+    /// constraint's parameters. Override it if that representation is not
+    /// deterministic, for example because it prints a `HashMap`.
+    fn identity(&self) -> String {
+        format!("{self:?}")
+    }
 }
 
 /// A boxed constraint for use in collections.
 pub type BoxedConstraint = Box<dyn Constraint>;
 
+/// Returns the stable identifier of a constraint.
+///
+/// The identifier has the form `name-hash`, where the hash covers the constraint's
+/// [`name`](Constraint::name) and [`identity`](Constraint::identity), so it is the
+/// same in every run and process for the same configuration. Suites attach it to
+/// [`ConstraintResult::constraint_id`] and
+/// [`ValidationIssue::constraint_id`](super::ValidationIssue::constraint_id).
+///
+/// The identifier changes when the constraint type or any of its parameters
+/// change: columns, thresholds, assertions, options and quarantine sinks. It does
+/// not depend on the check the constraint belongs to, so renaming or reordering
+/// checks, changing their level or description, or moving the constraint to
+/// another check keeps it. A Term release that adds parameters to a constraint
+/// type may change its identifiers; route on an explicit
+/// [`CheckBuilder::id`](super::CheckBuilder::id) where that matters.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::constraints::CompletenessConstraint;
+/// use term_guard::core::constraint_id;
+///
+/// let id = constraint_id(&CompletenessConstraint::with_threshold("email", 0.95));
+/// assert!(id.starts_with("completeness-"));
+/// assert_eq!(id, constraint_id(&CompletenessConstraint::with_threshold("email", 0.95)));
+/// assert_ne!(id, constraint_id(&CompletenessConstraint::with_threshold("email", 0.99)));
+/// ```
+pub fn constraint_id(constraint: &dyn Constraint) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(constraint.name().as_bytes());
+    hasher.update([0u8]);
+    hasher.update(constraint.identity().as_bytes());
+    format!(
+        "{}-{}",
+        constraint.name(),
+        hex::encode(&hasher.finalize()[..8])
+    )
+}
+
+/// Renders a fraction such as `0.995` as a percentage (`"99.5%"`) for
+/// [`Constraint::describe`].
+pub(crate) fn describe_percent(fraction: f64) -> String {
+    let percent = format!("{:.2}", fraction * 100.0);
+    let percent = percent.trim_end_matches('0').trim_end_matches('.');
+    format!("{percent}%")
+}
+
+/// Renders the share of rows a constraint requires for [`Constraint::describe`].
+pub(crate) fn describe_rows(threshold: f64) -> String {
+    if threshold >= 1.0 {
+        "for every row".to_string()
+    } else {
+        format!("for at least {} of rows", describe_percent(threshold))
+    }
+}
+
+/// Joins names into a list such as `"a, b and c"` for [`Constraint::describe`].
+pub(crate) fn describe_list<S: AsRef<str>>(names: &[S]) -> String {
+    match names {
+        [] => String::new(),
+        [name] => name.as_ref().to_string(),
+        [init @ .., last] => format!(
+            "{} and {}",
+            init.iter()
+                .map(|name| name.as_ref())
+                .collect::<Vec<_>>()
+                .join(", "),
+            last.as_ref()
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -289,4 +602,15 @@ mod tests {
         assert_eq!(skipped.status, ConstraintStatus::Skipped);
         assert_eq!(skipped.message, Some("No data".to_string()));
     }
+
+    #[test]
+    fn test_describe_helpers() {
+        assert_eq!(describe_percent(0.99), "99%");
+        assert_eq!(describe_percent(0.995), "99.5%");
+        assert_eq!(describe_percent(1.0), "100%");
+        assert_eq!(describe_rows(1.0), "for every row");
+        assert_eq!(describe_rows(0.95), "for at least 95% of rows");
+        assert_eq!(describe_list(&["a"]), "a");
+        assert_eq!(describe_list(&["a", "b", "c"]), "a, b and c");
+    }
 }
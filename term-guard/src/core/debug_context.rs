@@ -587,6 +587,9 @@ mod tests {
                 status: crate::core::ConstraintStatus::Failure,
                 message: Some("Foreign key violation found".to_string()),
                 metric: None,
+                quarantine: None,
+                constraint_id: None,
+                details: Default::default(),
             },
         );
 
@@ -0,0 +1,76 @@
+//! Derived tables validated by a suite.
+//!
+//! A suite can validate the result of a query, such as a join of several
+//! tables, instead of a registered table. The query is registered as a view
+//! under the suite's table name before any constraint runs and removed when the
+//! run finishes, so constraints and the optimizer treat it like any other table.
+
+use datafusion::prelude::*;
+use std::fmt;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+
+/// The query defining a derived table.
+#[derive(Clone)]
+pub(crate) enum DerivedTable {
+    /// A SQL `SELECT` query planned against the session context of the run
+    Sql(String),
+    /// An already planned DataFusion `DataFrame`
+    DataFrame(Arc<DataFrame>),
+}
+
+impl DerivedTable {
+    /// Registers the derived table as a view named `name`.
+    ///
+    /// Fails without registering anything if the SQL is not a single query, if
+    /// it cannot be planned, or if a table with the same name already exists.
+    pub(crate) async fn register(&self, ctx: &SessionContext, name: &str) -> Result<()> {
+        if ctx.table_exist(name)? {
+            return Err(TermError::Configuration(format!(
+                "Cannot register derived table '{name}': a table with this name already exists"
+            )));
+        }
+
+        let df = self.plan(ctx, name).await?;
+        ctx.register_table(name, df.into_view())?;
+        debug!(table.name = %name, "Registered derived table");
+        Ok(())
+    }
+
+    /// Plans the query of the derived table named `name` without registering it.
+    pub(crate) async fn plan(&self, ctx: &SessionContext, name: &str) -> Result<DataFrame> {
+        match self {
+            DerivedTable::Sql(sql) => {
+                SqlSecurity::validate_query(sql).map_err(|e| {
+                    TermError::Configuration(format!("Invalid SQL for derived table '{name}': {e}"))
+                })?;
+                ctx.sql(sql).await.map_err(|e| {
+                    TermError::Configuration(format!("Failed to plan derived table '{name}': {e}"))
+                })
+            }
+            DerivedTable::DataFrame(df) => Ok(df.as_ref().clone()),
+        }
+    }
+
+    /// Removes the view registered by [`register`](Self::register).
+    pub(crate) fn deregister(ctx: &SessionContext, name: &str) {
+        if let Err(e) = ctx.deregister_table(name) {
+            warn!(table.name = %name, error = %e, "Failed to deregister derived table");
+        }
+    }
+}
+
+impl fmt::Debug for DerivedTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DerivedTable::Sql(sql) => f.debug_tuple("Sql").field(sql).finish(),
+            DerivedTable::DataFrame(df) => f
+                .debug_tuple("DataFrame")
+                .field(&df.logical_plan().display().to_string())
+                .finish(),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Data quality dimensions for grouping validation outcomes.
+//!
+//! Governance frameworks report data quality along a small set of standard
+//! dimensions. Built-in constraints declare the dimension they measure through
+//! [`Constraint::dimension`](super::Constraint::dimension), checks can override it
+//! with [`CheckBuilder::dimension`](super::CheckBuilder::dimension), and
+//! [`ValidationReport::dimension_summary`](super::ValidationReport::dimension_summary)
+//! aggregates the outcomes of a run per dimension.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::core::QualityDimension;
+//!
+//! assert_eq!(QualityDimension::Validity.to_string(), "validity");
+//! assert_eq!(
+//!     "lineage".parse::<QualityDimension>().unwrap(),
+//!     QualityDimension::Custom("lineage".to_string())
+//! );
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+/// A data quality dimension a constraint measures.
+///
+/// Dimensions serialize as their lowercase names, such as `"completeness"`;
+/// custom dimensions serialize as their own name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
+pub enum QualityDimension {
+    /// Required values are present
+    Completeness,
+    /// Values conform to the expected format, type or domain
+    Validity,
+    /// Values that should be unique are not duplicated
+    Uniqueness,
+    /// Values agree across columns, rows and tables
+    Consistency,
+    /// Data is recent and arrives when expected
+    Timeliness,
+    /// Values correctly describe the real-world entities they represent
+    Accuracy,
+    /// A dimension specific to the organization
+    Custom(String),
+}
+
+impl QualityDimension {
+    /// Returns the name of the dimension.
+    pub fn as_str(&self) -> &str {
+        match self {
+            QualityDimension::Completeness => "completeness",
+            QualityDimension::Validity => "validity",
+            QualityDimension::Uniqueness => "uniqueness",
+            QualityDimension::Consistency => "consistency",
+            QualityDimension::Timeliness => "timeliness",
+            QualityDimension::Accuracy => "accuracy",
+            QualityDimension::Custom(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for QualityDimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for QualityDimension {
+    type Err = Infallible;
+
+    /// Parses a dimension name case-insensitively; unknown names become
+    /// [`QualityDimension::Custom`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "completeness" => QualityDimension::Completeness,
+            "validity" => QualityDimension::Validity,
+            "uniqueness" => QualityDimension::Uniqueness,
+            "consistency" => QualityDimension::Consistency,
+            "timeliness" => QualityDimension::Timeliness,
+            "accuracy" => QualityDimension::Accuracy,
+            _ => QualityDimension::Custom(s.to_string()),
+        })
+    }
+}
+
+impl From<String> for QualityDimension {
+    fn from(name: String) -> Self {
+        match name.parse() {
+            Ok(dimension) => dimension,
+            Err(never) => match never {},
+        }
+    }
+}
+
+impl From<QualityDimension> for String {
+    fn from(dimension: QualityDimension) -> Self {
+        match dimension {
+            QualityDimension::Custom(name) => name,
+            other => other.as_str().to_string(),
+        }
+    }
+}
+
+/// Outcomes of the constraints of one dimension in a validation run.
+///
+/// See [`ValidationReport::dimension_summary`](super::ValidationReport::dimension_summary).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DimensionSummary {
+    /// The dimension
+    pub dimension: QualityDimension,
+    /// Number of evaluated constraints in the dimension
+    pub constraints: usize,
+    /// Number of constraints that passed
+    pub passed: usize,
+    /// Number of constraints that failed, not counting failures of `Level::Info` checks
+    /// or failures waived by known issues
+    pub failed: usize,
+    /// Number of constraints that were skipped
+    pub skipped: usize,
+    /// Number of reported issues, including observations of `Level::Info` checks
+    pub issues: usize,
+    /// Passed constraints as a percentage (0.0 to 100.0) of the passed and failed
+    /// ones; 100.0 if there are none
+    pub pass_rate: f64,
+}
+
+impl DimensionSummary {
+    pub(crate) fn new(dimension: QualityDimension) -> Self {
+        Self {
+            dimension,
+            constraints: 0,
+            passed: 0,
+            failed: 0,
+            skipped: 0,
+            issues: 0,
+            pass_rate: 100.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_as_name() {
+        let dimensions = vec![
+            QualityDimension::Completeness,
+            QualityDimension::Custom("lineage".to_string()),
+        ];
+        let json = serde_json::to_string(&dimensions).unwrap();
+        assert_eq!(json, r#"["completeness","lineage"]"#);
+
+        let parsed: Vec<QualityDimension> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, dimensions);
+    }
+
+    #[test]
+    fn test_parse_is_case_insensitive() {
+        assert_eq!(
+            "Timeliness".parse::<QualityDimension>().unwrap(),
+            QualityDimension::Timeliness
+        );
+        assert_eq!(
+            "Lineage".parse::<QualityDimension>().unwrap().to_string(),
+            "Lineage"
+        );
+    }
+}
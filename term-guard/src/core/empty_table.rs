@@ -0,0 +1,138 @@
+//! Evaluation of constraints on tables without rows.
+//!
+//! Constraints whose measure does not exist without rows report
+//! [`ConstraintResult::empty_table`]; suites map that result through their
+//! [`EmptyTablePolicy`].
+
+use datafusion::prelude::SessionContext;
+
+use super::{fetch_metrics, ConstraintResult, ConstraintStatus, MetricKey};
+use crate::error::Result;
+
+/// Message of the result of a constraint evaluated on a table without rows.
+pub const EMPTY_TABLE_MESSAGE: &str = "table is empty";
+
+/// How a suite reports constraints skipped because their table is empty.
+///
+/// A fraction of rows or a statistic of column values does not exist for a
+/// table without rows. Rather than a vacuous success or an arbitrary failure,
+/// constraints measuring one report [`ConstraintResult::empty_table`], a skipped
+/// result with the message `table is empty`. Constraints whose subject is
+/// defined for zero rows evaluate normally:
+///
+/// | Constraint | On an empty table |
+/// |------------|-------------------|
+/// | Completeness, uniqueness, format, length, containment, data type values, custom SQL, expression equality, time window, temporal ordering, cardinality, join coverage, null rate comparison | Skipped: the ratio has no rows to count |
+/// | Min, max, mean, sum, standard deviation, variance, median and percentile statistics, quantiles, correlation, histogram, temporal gaps, distribution stability, category baseline | Skipped: the aggregate is undefined without values |
+/// | Size, manifest size | Evaluated: the row count is the subject |
+/// | Column count, data type with a specific type | Evaluated: only the schema is checked |
+/// | Count and null count statistics, approximate count distinct | Evaluated: the count is zero |
+/// | Foreign key, cross-table sum | Evaluated: there are no violating rows and the sum of no rows is zero |
+///
+/// Multi-table constraints apply this to the tables they measure rather than to
+/// the validated table. By default a suite reports these results as skipped;
+/// teams that consider empty input itself a failure set
+/// [`ValidationSuiteBuilder::on_empty`](super::ValidationSuiteBuilder::on_empty).
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::core::{EmptyTablePolicy, ValidationSuite};
+///
+/// let suite = ValidationSuite::builder("nightly_orders")
+///     .on_empty(EmptyTablePolicy::Fail)
+///     .build();
+/// assert_eq!(suite.empty_table_policy(), EmptyTablePolicy::Fail);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmptyTablePolicy {
+    /// Report them as skipped
+    #[default]
+    Skip,
+    /// Report them as failed, for pipelines where empty input is an error
+    Fail,
+    /// Report them as passed
+    Pass,
+}
+
+impl EmptyTablePolicy {
+    /// Applies the policy to the result of a constraint.
+    pub(crate) fn apply(self, result: ConstraintResult) -> ConstraintResult {
+        if !result.is_empty_table() {
+            return result;
+        }
+        let status = match self {
+            EmptyTablePolicy::Skip => return result,
+            EmptyTablePolicy::Fail => ConstraintStatus::Failure,
+            EmptyTablePolicy::Pass => ConstraintStatus::Success,
+        };
+        ConstraintResult { status, ..result }
+    }
+}
+
+/// Returns true if `table` has no rows.
+///
+/// Within a run the row count is shared with other constraints through the
+/// metric cache.
+pub(crate) async fn is_table_empty(ctx: &SessionContext, table: &str) -> Result<bool> {
+    let counts = fetch_metrics(ctx, &[MetricKey::row_count(table)]).await?;
+    Ok(counts[0] == 0)
+}
+
+/// Returns the result of a constraint that found no values to check in `table`.
+///
+/// This is [`ConstraintResult::empty_table`] if the table has no rows, and a
+/// skip with `message` if only the checked values are missing.
+pub(crate) async fn skipped_without_values(
+    ctx: &SessionContext,
+    table: &str,
+    message: &str,
+) -> Result<ConstraintResult> {
+    Ok(if is_table_empty(ctx, table).await? {
+        ConstraintResult::empty_table()
+    } else {
+        ConstraintResult::skipped(message)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_applies_only_to_empty_table_results() {
+        let failed = EmptyTablePolicy::Fail.apply(ConstraintResult::empty_table());
+        assert_eq!(failed.status, ConstraintStatus::Failure);
+        assert_eq!(failed.message.as_deref(), Some(EMPTY_TABLE_MESSAGE));
+
+        let passed = EmptyTablePolicy::Pass.apply(ConstraintResult::empty_table());
+        assert_eq!(passed.status, ConstraintStatus::Success);
+
+        let skipped = EmptyTablePolicy::Skip.apply(ConstraintResult::empty_table());
+        assert!(skipped.is_empty_table());
+
+        let other = EmptyTablePolicy::Fail.apply(ConstraintResult::skipped("No data"));
+        assert_eq!(other.status, ConstraintStatus::Skipped);
+    }
+
+    #[tokio::test]
+    async fn test_skipped_without_values() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE empty_orders (value INT)")
+            .await?
+            .collect()
+            .await?;
+        ctx.sql("CREATE TABLE nulls (value INT) AS VALUES (NULL)")
+            .await?
+            .collect()
+            .await?;
+
+        let result = skipped_without_values(&ctx, "empty_orders", "No non-null data").await?;
+        assert!(result.is_empty_table());
+
+        let result = skipped_without_values(&ctx, "nulls", "No non-null data").await?;
+        assert_eq!(result.message.as_deref(), Some("No non-null data"));
+        assert!(!result.is_empty_table());
+        Ok(())
+    }
+}
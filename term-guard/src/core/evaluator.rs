@@ -0,0 +1,408 @@
+//! Evaluation of constraints outside of a suite.
+//!
+//! An [`Evaluator`] runs constraints directly against a session context and
+//! returns their [`ConstraintResult`]s, for ad-hoc checks in scripts and tests
+//! where building a suite and unwrapping its report is ceremony. Suites run
+//! their constraints through the same evaluator, so a constraint reports the
+//! same result either way: it sees the same [`ValidationContext`], records the
+//! same telemetry span and goes through the same [`EmptyTablePolicy`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::constraints::CompletenessConstraint;
+//! use term_guard::core::{ConstraintStatus, Evaluator};
+//! use datafusion::prelude::*;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let ctx = SessionContext::new();
+//! ctx.sql("CREATE TABLE orders (id INT) AS VALUES (1), (2), (3)")
+//!     .await?
+//!     .collect()
+//!     .await?;
+//!
+//! let result = Evaluator::new(&ctx)
+//!     .table("orders")
+//!     .evaluate(CompletenessConstraint::complete("id"))
+//!     .await?;
+//! assert_eq!(result.status, ConstraintStatus::Success);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion::prelude::*;
+use tracing::debug;
+
+use super::{
+    constraint::BoxedConstraint, constraint_id, CollectionLimits, Constraint, ConstraintResult,
+    EmptyTablePolicy, Extensions, MetricCache, ParameterBag, QueryMetricsCollector,
+    RedactionPolicy, RegexCache, ValidationContext, CURRENT_CONTEXT,
+};
+use crate::prelude::*;
+use crate::telemetry::{utils, TermSpan, TermTelemetry};
+
+/// Evaluates constraints against a session context.
+///
+/// The evaluator validates the table `data` unless configured otherwise with
+/// [`table`](Self::table). Constraints evaluated by the same evaluator share a
+/// [`MetricCache`], so a batch of constraints on one table counts its rows once,
+/// and a [`RegexCache`], so a pattern several constraints use is compiled once.
+#[derive(Clone)]
+pub struct Evaluator<'a> {
+    ctx: &'a SessionContext,
+    table_name: String,
+    default_schema: Option<String>,
+    parameters: Arc<ParameterBag>,
+    extensions: Arc<Extensions>,
+    tags: Arc<BTreeMap<String, String>>,
+    telemetry: Option<Arc<TermTelemetry>>,
+    empty_table_policy: EmptyTablePolicy,
+    metric_cache: MetricCache,
+    regex_cache: RegexCache,
+    redaction: Option<Arc<RedactionPolicy>>,
+    collection_limits: Arc<CollectionLimits>,
+}
+
+impl fmt::Debug for Evaluator<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Evaluator")
+            .field("table_name", &self.table_name)
+            .field("default_schema", &self.default_schema)
+            .field("tags", &self.tags)
+            .field("empty_table_policy", &self.empty_table_policy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Evaluator<'a> {
+    /// Creates an evaluator for the tables of `ctx`.
+    pub fn new(ctx: &'a SessionContext) -> Self {
+        Self {
+            ctx,
+            table_name: "data".to_string(),
+            default_schema: None,
+            parameters: Arc::new(ParameterBag::new()),
+            extensions: Arc::new(Extensions::new()),
+            tags: Arc::new(BTreeMap::new()),
+            telemetry: None,
+            empty_table_policy: EmptyTablePolicy::default(),
+            metric_cache: MetricCache::new(),
+            regex_cache: RegexCache::new(),
+            redaction: None,
+            collection_limits: Arc::default(),
+        }
+    }
+
+    /// Sets the table the constraints validate.
+    pub fn table(mut self, table_name: impl Into<String>) -> Self {
+        self.table_name = table_name.into();
+        self
+    }
+
+    /// Sets the schema prepended to unqualified table names in multi-table
+    /// constraints.
+    pub fn default_schema(mut self, schema: impl Into<String>) -> Self {
+        self.default_schema = Some(schema.into());
+        self
+    }
+
+    /// Sets the values of named parameters referenced by constraints.
+    pub fn parameters(mut self, parameters: ParameterBag) -> Self {
+        self.parameters = Arc::new(parameters);
+        self
+    }
+
+    /// Sets the dependencies passed to [`Constraint::evaluate_with`].
+    pub fn extensions(mut self, extensions: Extensions) -> Self {
+        self.extensions = Arc::new(extensions);
+        self
+    }
+
+    /// Adds a tag available to constraints from the validation context.
+    pub fn tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.tags).insert(key.into(), value.into());
+        self
+    }
+
+    /// Records a telemetry span for every evaluated constraint.
+    pub fn with_telemetry(mut self, telemetry: TermTelemetry) -> Self {
+        self.telemetry = Some(Arc::new(telemetry));
+        self
+    }
+
+    /// Sets how constraints skipped because their table is empty are reported.
+    pub fn on_empty(mut self, policy: EmptyTablePolicy) -> Self {
+        self.empty_table_policy = policy;
+        self
+    }
+
+    /// Redacts the values constraints quote in their results, see
+    /// [`RedactionPolicy`].
+    pub fn redaction(mut self, policy: RedactionPolicy) -> Self {
+        self.redaction = Some(Arc::new(policy));
+        self
+    }
+
+    /// Bounds the result sets the evaluated constraints collect, see
+    /// [`CollectionLimits`].
+    pub fn collection_limits(mut self, limits: CollectionLimits) -> Self {
+        self.collection_limits = Arc::new(limits);
+        self
+    }
+
+    /// Shares `cache` with the evaluated constraints for the regexes they compile.
+    ///
+    /// Clones of a cache share its entries, so the caller can read its
+    /// counters after evaluating.
+    pub fn regex_cache(mut self, cache: RegexCache) -> Self {
+        self.regex_cache = cache;
+        self
+    }
+
+    /// Creates the evaluator of a suite run.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn for_run(
+        ctx: &'a SessionContext,
+        table_name: &str,
+        default_schema: Option<&str>,
+        parameters: Arc<ParameterBag>,
+        extensions: Arc<Extensions>,
+        tags: Arc<BTreeMap<String, String>>,
+        telemetry: Option<Arc<TermTelemetry>>,
+        empty_table_policy: EmptyTablePolicy,
+    ) -> Self {
+        Self {
+            ctx,
+            table_name: table_name.to_string(),
+            default_schema: default_schema.map(str::to_string),
+            parameters,
+            extensions,
+            tags,
+            telemetry,
+            empty_table_policy,
+            metric_cache: MetricCache::new(),
+            regex_cache: RegexCache::new(),
+            redaction: None,
+            collection_limits: Arc::default(),
+        }
+    }
+
+    /// Sets the redaction policy shared with the suite.
+    pub(crate) fn with_redaction(mut self, policy: Option<Arc<RedactionPolicy>>) -> Self {
+        self.redaction = policy;
+        self
+    }
+
+    /// Sets the collection limits shared with the suite.
+    pub(crate) fn with_collection_limits(mut self, limits: Arc<CollectionLimits>) -> Self {
+        self.collection_limits = limits;
+        self
+    }
+
+    /// Replaces the metric cache shared by the evaluated constraints.
+    pub(crate) fn with_metric_cache(mut self, cache: MetricCache) -> Self {
+        self.metric_cache = cache;
+        self
+    }
+
+    /// Returns the metric cache shared by the evaluated constraints.
+    pub(crate) fn metric_cache(&self) -> &MetricCache {
+        &self.metric_cache
+    }
+
+    /// Evaluates one constraint.
+    ///
+    /// Errors raised by the constraint, such as a missing column, are returned
+    /// as they are; a suite reports them as failed constraints instead.
+    pub async fn evaluate<C: Constraint>(&self, constraint: C) -> Result<ConstraintResult> {
+        self.evaluate_dyn(&constraint).await
+    }
+
+    /// Evaluates constraints in order and returns their results in the same
+    /// order.
+    ///
+    /// Evaluation stops at the first constraint returning an error.
+    pub async fn evaluate_all<I>(&self, constraints: I) -> Result<Vec<ConstraintResult>>
+    where
+        I: IntoIterator<Item = BoxedConstraint>,
+    {
+        let mut results = Vec::new();
+        for constraint in constraints {
+            results.push(self.evaluate_dyn(constraint.as_ref()).await?);
+        }
+        Ok(results)
+    }
+
+    /// Evaluates a constraint held behind a reference or a shared pointer.
+    pub(crate) async fn evaluate_dyn(
+        &self,
+        constraint: &dyn Constraint,
+    ) -> Result<ConstraintResult> {
+        let mut span = self.start_span(constraint);
+        match self
+            .execute(constraint, self.validation_context(None))
+            .await
+        {
+            Ok(mut result) => {
+                result.constraint_id = Some(constraint_id(constraint));
+                Ok(self.finish(result, &mut span))
+            }
+            Err(e) => {
+                span.record_error(&e as &dyn std::error::Error);
+                debug!(
+                    constraint.name = %constraint.name(),
+                    error = %e,
+                    "Error evaluating constraint"
+                );
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns the validation context constraints are evaluated in.
+    ///
+    /// Suites pass the collector of the check being run.
+    pub(crate) fn validation_context(
+        &self,
+        performance_collector: Option<&QueryMetricsCollector>,
+    ) -> ValidationContext {
+        let mut validation_ctx = ValidationContext::new(self.table_name.as_str())
+            .with_metric_cache(self.metric_cache.clone())
+            .with_regex_cache(self.regex_cache.clone())
+            .with_collection_limits(Arc::clone(&self.collection_limits))
+            .with_parameters(Arc::clone(&self.parameters))
+            .with_extensions(Arc::clone(&self.extensions))
+            .with_tags(Arc::clone(&self.tags));
+        if let Some(collector) = performance_collector {
+            validation_ctx = validation_ctx.with_performance_collector(collector.clone());
+        }
+        if let Some(schema) = &self.default_schema {
+            validation_ctx = validation_ctx.with_default_schema(schema.as_str());
+        }
+        if let Some(policy) = &self.redaction {
+            validation_ctx = validation_ctx.with_redaction(Arc::clone(policy));
+        }
+        validation_ctx
+    }
+
+    /// Opens the telemetry span of a constraint.
+    pub(crate) fn start_span(&self, constraint: &dyn Constraint) -> TermSpan {
+        match &self.telemetry {
+            Some(telemetry) => {
+                telemetry.start_constraint_span(constraint.name(), constraint.column())
+            }
+            None => TermSpan::noop(),
+        }
+    }
+
+    /// Evaluates a constraint within `validation_ctx`.
+    pub(crate) async fn execute(
+        &self,
+        constraint: &dyn Constraint,
+        validation_ctx: ValidationContext,
+    ) -> Result<ConstraintResult> {
+        CURRENT_CONTEXT
+            .scope(
+                validation_ctx,
+                constraint.evaluate_with(self.ctx, &self.extensions),
+            )
+            .await
+    }
+
+    /// Applies the empty table policy to a result and records it on its span.
+    pub(crate) fn finish(&self, result: ConstraintResult, span: &mut TermSpan) -> ConstraintResult {
+        let result = self.empty_table_policy.apply(result);
+        if self
+            .telemetry
+            .as_ref()
+            .is_some_and(|telemetry| telemetry.detailed_metrics)
+        {
+            utils::record_constraint_result(span, &result);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::{Assertion, CompletenessConstraint, SizeConstraint};
+    use crate::core::{ConstraintStatus, EMPTY_TABLE_MESSAGE};
+
+    async fn context() -> Result<SessionContext> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE orders (id INT, email VARCHAR) AS VALUES (1, 'a'), (2, NULL)")
+            .await?
+            .collect()
+            .await?;
+        ctx.sql("CREATE TABLE empty_orders (id INT)")
+            .await?
+            .collect()
+            .await?;
+        Ok(ctx)
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_batch_in_order() -> Result<()> {
+        let ctx = context().await?;
+        let evaluator = Evaluator::new(&ctx).table("orders");
+
+        let results = evaluator
+            .evaluate_all(vec![
+                Box::new(CompletenessConstraint::complete("email")) as BoxedConstraint,
+                Box::new(SizeConstraint::new(Assertion::Equals(2.0))),
+                Box::new(CompletenessConstraint::complete("id")),
+            ])
+            .await?;
+        let statuses: Vec<_> = results.iter().map(|result| result.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ConstraintStatus::Failure,
+                ConstraintStatus::Success,
+                ConstraintStatus::Success
+            ]
+        );
+        assert_eq!(results[0].metric, Some(0.5));
+        assert!(results.iter().all(|result| result.constraint_id.is_some()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_empty_table_policy() -> Result<()> {
+        let ctx = context().await?;
+        let evaluator = Evaluator::new(&ctx).table("empty_orders");
+
+        let skipped = evaluator
+            .evaluate(CompletenessConstraint::complete("id"))
+            .await?;
+        assert_eq!(skipped.message.as_deref(), Some(EMPTY_TABLE_MESSAGE));
+        assert_eq!(skipped.status, ConstraintStatus::Skipped);
+
+        let failed = evaluator
+            .on_empty(EmptyTablePolicy::Fail)
+            .evaluate(CompletenessConstraint::complete("id"))
+            .await?;
+        assert_eq!(failed.status, ConstraintStatus::Failure);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_returned() -> Result<()> {
+        let ctx = context().await?;
+        let evaluator = Evaluator::new(&ctx).table("orders");
+
+        let missing = evaluator
+            .evaluate_all(vec![
+                Box::new(CompletenessConstraint::complete("id")) as BoxedConstraint,
+                Box::new(CompletenessConstraint::complete("missing")),
+            ])
+            .await;
+        assert!(missing.is_err());
+        Ok(())
+    }
+}
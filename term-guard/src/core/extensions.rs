@@ -0,0 +1,228 @@
+//! Per-run dependencies for custom constraints.
+//!
+//! Some constraints need data from outside the [`SessionContext`]: an expected
+//! row count from an internal service, a feature flag, a config value. Baking an
+//! HTTP client into the constraint at construction makes it hard to reuse the
+//! same suite across runs and environments. Instead, dependencies are put in an
+//! [`Extensions`] map passed to
+//! [`RunOptions::with_extensions`](super::RunOptions::with_extensions),
+//! and constraints read them in
+//! [`Constraint::evaluate_with`](super::Constraint::evaluate_with).
+//!
+//! Values are keyed by type, as in `http::Extensions`. Built-in constraints
+//! ignore extensions.
+//!
+//! # Example
+//!
+//! ```rust
+//! use async_trait::async_trait;
+//! use datafusion::prelude::*;
+//! use std::sync::Arc;
+//! use term_guard::core::{
+//!     Check, Constraint, ConstraintResult, Extensions, RunOptions, ValidationSuite,
+//! };
+//! use term_guard::prelude::*;
+//!
+//! /// Source of the expected row count, e.g. a client for an internal service.
+//! #[async_trait]
+//! trait ExpectedRows: Send + Sync {
+//!     async fn expected_rows(&self, table: &str) -> Result<i64>;
+//! }
+//!
+//! #[derive(Debug)]
+//! struct MatchesExpectedRows;
+//!
+//! #[async_trait]
+//! impl Constraint for MatchesExpectedRows {
+//!     async fn evaluate(&self, _ctx: &SessionContext) -> Result<ConstraintResult> {
+//!         Ok(ConstraintResult::skipped("No expected row count source configured"))
+//!     }
+//!
+//!     async fn evaluate_with(
+//!         &self,
+//!         ctx: &SessionContext,
+//!         extensions: &Extensions,
+//!     ) -> Result<ConstraintResult> {
+//!         let Some(source) = extensions.get::<Arc<dyn ExpectedRows>>() else {
+//!             return self.evaluate(ctx).await;
+//!         };
+//!         let expected = source.expected_rows("data").await?;
+//!         let actual = ctx.table("data").await?.count().await? as i64;
+//!         if actual == expected {
+//!             Ok(ConstraintResult::success_with_metric(actual as f64))
+//!         } else {
+//!             Ok(ConstraintResult::failure_with_metric(
+//!                 actual as f64,
+//!                 format!("Expected {expected} rows, found {actual}"),
+//!             ))
+//!         }
+//!     }
+//!
+//!     fn name(&self) -> &str {
+//!         "matches_expected_rows"
+//!     }
+//! }
+//!
+//! struct FixedRows(i64);
+//!
+//! #[async_trait]
+//! impl ExpectedRows for FixedRows {
+//!     async fn expected_rows(&self, _table: &str) -> Result<i64> {
+//!         Ok(self.0)
+//!     }
+//! }
+//!
+//! # async fn example(ctx: &SessionContext) -> Result<()> {
+//! let suite = ValidationSuite::builder("volume")
+//!     .check(Check::builder("rows").with_constraint(MatchesExpectedRows).build())
+//!     .build();
+//!
+//! let extensions = Extensions::new().with(Arc::new(FixedRows(1_000)) as Arc<dyn ExpectedRows>);
+//! let result = suite
+//!     .run_with(ctx, &RunOptions::new().with_extensions(extensions))
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`SessionContext`]: datafusion::prelude::SessionContext
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// A map of values keyed by their type.
+///
+/// Cloning is cheap: values are reference counted and shared between clones.
+#[derive(Clone, Default)]
+pub struct Extensions {
+    values: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a value, replacing any previous value of the same type.
+    ///
+    /// Returns `true` if a value of that type was replaced.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) -> bool {
+        self.values
+            .insert(TypeId::of::<T>(), Arc::new(value))
+            .is_some()
+    }
+
+    /// Returns the map with a value added, replacing any previous value of the same type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::Extensions;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Environment(&'static str);
+    ///
+    /// let extensions = Extensions::new().with(Environment("staging")).with(42u32);
+    /// assert_eq!(extensions.get::<Environment>(), Some(&Environment("staging")));
+    /// assert_eq!(extensions.get::<u32>(), Some(&42));
+    /// assert_eq!(extensions.get::<String>(), None);
+    /// ```
+    pub fn with<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.insert(value);
+        self
+    }
+
+    /// Returns the value of type `T`, if present.
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+    }
+
+    /// Returns `true` if a value of type `T` is present.
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Removes the value of type `T`, returning whether one was present.
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> bool {
+        self.values.remove(&TypeId::of::<T>()).is_some()
+    }
+
+    /// Returns the number of values in the map.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the map holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.values.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Endpoint(String);
+
+    trait Client: Send + Sync {
+        fn call(&self) -> u32;
+    }
+
+    struct MockClient;
+
+    impl Client for MockClient {
+        fn call(&self) -> u32 {
+            7
+        }
+    }
+
+    #[test]
+    fn test_values_are_keyed_by_type() {
+        let mut extensions = Extensions::new();
+        assert!(extensions.is_empty());
+
+        assert!(!extensions.insert(Endpoint("a".to_string())));
+        assert!(extensions.insert(Endpoint("b".to_string())));
+        extensions.insert(Arc::new(MockClient) as Arc<dyn Client>);
+
+        assert_eq!(extensions.len(), 2);
+        assert_eq!(
+            extensions.get::<Endpoint>(),
+            Some(&Endpoint("b".to_string()))
+        );
+        assert_eq!(extensions.get::<Arc<dyn Client>>().unwrap().call(), 7);
+        assert!(!extensions.contains::<MockClient>());
+
+        assert!(extensions.remove::<Endpoint>());
+        assert!(!extensions.remove::<Endpoint>());
+        assert_eq!(extensions.get::<Endpoint>(), None);
+    }
+
+    #[test]
+    fn test_clones_share_values() {
+        let extensions = Extensions::new().with(Endpoint("a".to_string()));
+        let mut clone = extensions.clone();
+        clone.insert(1u8);
+
+        assert!(std::ptr::eq(
+            extensions.get::<Endpoint>().unwrap(),
+            clone.get::<Endpoint>().unwrap()
+        ));
+        assert!(!extensions.contains::<u8>());
+        assert_eq!(format!("{clone:?}"), "Extensions { len: 2 }");
+    }
+}
@@ -196,19 +196,18 @@ impl MultiTableCheck {
 
     /// Ensure referential integrity between the current tables.
     ///
-    /// This adds a foreign key constraint using the specified join columns.
+    /// This adds a foreign key constraint on all of the specified join columns.
     pub fn ensure_referential_integrity(mut self) -> Self {
         if let Some(ref ctx) = self.current_context {
-            if let (Some(right_table), Some((left_col, right_col))) =
-                (&ctx.right_table, ctx.join_columns.first())
-            {
-                let child_column = format!("{}.{left_col}", ctx.left_table);
-                let parent_column = format!("{right_table}.{right_col}");
-
-                self.constraints.push(Arc::new(ForeignKeyConstraint::new(
-                    child_column,
-                    parent_column,
-                )));
+            if let Some(right_table) = &ctx.right_table {
+                // Fails only without join columns, which leaves nothing to check
+                if let Ok(constraint) = ForeignKeyConstraint::composite(
+                    &ctx.left_table,
+                    right_table,
+                    ctx.join_columns.clone(),
+                ) {
+                    self.constraints.push(Arc::new(constraint));
+                }
             }
         }
         self
@@ -0,0 +1,239 @@
+//! Hooks running custom logic around the constraints of a suite.
+//!
+//! A [`ValidationHook`] registered with
+//! [`ValidationSuiteBuilder::with_hook`](super::ValidationSuiteBuilder::with_hook)
+//! is called before and after every constraint the suite evaluates. Before a
+//! constraint, a hook can veto its evaluation — for example for constraints
+//! touching a table flagged as quarantined — and the constraint is reported as
+//! skipped with the reason the hook gave. After a constraint, a hook sees its
+//! result, for example to forward it to an audit service.
+//!
+//! Hooks run in registration order. The first hook deciding to skip a
+//! constraint wins; the hooks after it are not asked. A hook that panics is
+//! logged and treated as if it had let the constraint proceed, so a faulty hook
+//! never aborts or corrupts the run. Hooks have no error channel: a hook whose
+//! own work fails should log the failure and return.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_trait::async_trait;
+//! use term_guard::core::{ConstraintInfo, HookDecision, ValidationHook};
+//!
+//! struct QuarantinedTables(Vec<String>);
+//!
+//! #[async_trait]
+//! impl ValidationHook for QuarantinedTables {
+//!     async fn before_constraint(&self, info: &ConstraintInfo) -> HookDecision {
+//!         match info.tables().find(|table| self.0.contains(table)) {
+//!             Some(table) => HookDecision::Skip {
+//!                 reason: format!("table '{table}' is quarantined"),
+//!             },
+//!             None => HookDecision::Proceed,
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::any::Any;
+use std::fmt;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::FutureExt;
+use tracing::{debug, error, info};
+
+use super::{ConstraintResult, Level};
+
+/// What a suite knows about a constraint when calling hooks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintInfo {
+    /// The name of the suite
+    pub suite_name: String,
+    /// The name of the check containing the constraint
+    pub check_name: String,
+    /// The severity level of the check
+    pub level: Level,
+    /// The name of the constraint as shown in the report
+    pub constraint_name: String,
+    /// The type of the constraint, see [`Constraint::name`](super::Constraint::name)
+    pub constraint_type: String,
+    /// The stable identifier of the constraint, see [`constraint_id`](super::constraint_id)
+    pub constraint_id: String,
+    /// The column the constraint validates, if it validates a single one
+    pub column: Option<String>,
+    /// The table the suite validates
+    pub table_name: String,
+    /// Other tables the constraint reads, see
+    /// [`Constraint::referenced_tables`](super::Constraint::referenced_tables)
+    pub referenced_tables: Vec<String>,
+    /// Downstream identifiers consuming the columns of the constraint, from the
+    /// suite's [`LineageMap`](super::LineageMap)
+    pub impacts: Vec<String>,
+}
+
+impl ConstraintInfo {
+    /// Returns every table the constraint reads: the validated table followed
+    /// by the referenced ones.
+    pub fn tables(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.table_name).chain(&self.referenced_tables)
+    }
+}
+
+/// Whether a constraint should be evaluated, as decided by a hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookDecision {
+    /// Evaluate the constraint
+    Proceed,
+    /// Report the constraint as skipped without evaluating it
+    Skip {
+        /// Why the constraint is skipped, shown in the report
+        reason: String,
+    },
+}
+
+/// Custom logic run before and after each constraint of a suite.
+///
+/// See the [module documentation](self) for how hooks are run.
+#[async_trait]
+pub trait ValidationHook: Send + Sync {
+    /// Returns the name of the hook, recorded with the constraints it skips.
+    ///
+    /// Defaults to the type name of the hook.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Called before a constraint is evaluated.
+    ///
+    /// The default implementation lets every constraint proceed.
+    async fn before_constraint(&self, _info: &ConstraintInfo) -> HookDecision {
+        HookDecision::Proceed
+    }
+
+    /// Called with the result of a constraint, including constraints skipped
+    /// by a hook and constraints whose evaluation failed with an error.
+    ///
+    /// The default implementation does nothing.
+    async fn after_constraint(&self, _info: &ConstraintInfo, _result: &ConstraintResult) {}
+}
+
+/// A hook logging every constraint of a run, as a reference implementation.
+///
+/// Constraints about to be evaluated are logged at debug level and their
+/// results at info level, under the `term_guard::core::hooks` target.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoggingHook;
+
+impl LoggingHook {
+    /// Creates a logging hook.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl ValidationHook for LoggingHook {
+    fn name(&self) -> &str {
+        "logging"
+    }
+
+    async fn before_constraint(&self, info: &ConstraintInfo) -> HookDecision {
+        debug!(
+            suite.name = %info.suite_name,
+            check.name = %info.check_name,
+            constraint.name = %info.constraint_name,
+            constraint.type = %info.constraint_type,
+            "Evaluating constraint"
+        );
+        HookDecision::Proceed
+    }
+
+    async fn after_constraint(&self, info: &ConstraintInfo, result: &ConstraintResult) {
+        info!(
+            suite.name = %info.suite_name,
+            check.name = %info.check_name,
+            constraint.name = %info.constraint_name,
+            constraint.status = ?result.status,
+            constraint.metric = ?result.metric,
+            constraint.message = ?result.message,
+            "Constraint evaluated"
+        );
+    }
+}
+
+/// The hooks of a suite, run in registration order.
+#[derive(Clone, Default)]
+pub(crate) struct HookChain {
+    hooks: Vec<Arc<dyn ValidationHook>>,
+}
+
+impl fmt::Debug for HookChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.hooks.iter().map(|hook| hook.name()))
+            .finish()
+    }
+}
+
+impl HookChain {
+    pub(crate) fn push(&mut self, hook: Arc<dyn ValidationHook>) {
+        self.hooks.push(hook);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Asks the hooks whether to evaluate a constraint, returning the name of
+    /// the first hook deciding to skip it and its reason.
+    pub(crate) async fn before(&self, info: &ConstraintInfo) -> Option<(String, String)> {
+        for hook in &self.hooks {
+            match AssertUnwindSafe(hook.before_constraint(info))
+                .catch_unwind()
+                .await
+            {
+                Ok(HookDecision::Proceed) => {}
+                Ok(HookDecision::Skip { reason }) => {
+                    return Some((hook.name().to_string(), reason));
+                }
+                Err(panic) => log_panic(hook.as_ref(), info, "before_constraint", panic),
+            }
+        }
+        None
+    }
+
+    /// Passes the result of a constraint to every hook.
+    pub(crate) async fn after(&self, info: &ConstraintInfo, result: &ConstraintResult) {
+        for hook in &self.hooks {
+            if let Err(panic) = AssertUnwindSafe(hook.after_constraint(info, result))
+                .catch_unwind()
+                .await
+            {
+                log_panic(hook.as_ref(), info, "after_constraint", panic);
+            }
+        }
+    }
+}
+
+fn log_panic(
+    hook: &dyn ValidationHook,
+    info: &ConstraintInfo,
+    method: &str,
+    panic: Box<dyn Any + Send>,
+) {
+    let message = panic
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    error!(
+        hook.name = %hook.name(),
+        hook.method = method,
+        check.name = %info.check_name,
+        constraint.name = %info.constraint_name,
+        panic.message = %message,
+        "Validation hook panicked; the run continues without it"
+    );
+}
@@ -0,0 +1,131 @@
+//! Known-issue waivers for checks.
+//!
+//! A check marked with [`CheckBuilder::known_issue`](super::CheckBuilder::known_issue) keeps
+//! running and its failures are still reported. While the waiver is active, those failures
+//! are annotated with the ticket reference and do not fail the run, even for
+//! [`Level::Error`](super::Level::Error) checks. Once the waiver's `until` date has passed,
+//! failures count again and their message notes the expired waiver.
+//!
+//! Only constraint failures are waived. A constraint that cannot be evaluated, for
+//! example because its table or column is missing, fails the run as usual.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chrono::{TimeZone, Utc};
+//! use term_guard::core::{Check, Level};
+//!
+//! let until = Utc.with_ymd_and_hms(2026, 12, 1, 0, 0, 0).unwrap();
+//! let check = Check::builder("order_totals")
+//!     .level(Level::Error)
+//!     .known_issue("JIRA-1234", Some(until))
+//!     .build();
+//!
+//! let known_issue = check.known_issue().unwrap();
+//! assert_eq!(known_issue.ticket, "JIRA-1234");
+//! assert!(known_issue.is_expired_at(Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap()));
+//! ```
+
+use super::ValidationIssue;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A known issue that waives the failures of a check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnownIssue {
+    /// Reference to the ticket tracking the issue (e.g. `JIRA-1234`)
+    pub ticket: String,
+    /// When the waiver expires; `None` waives failures until the annotation is removed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl KnownIssue {
+    /// Creates a known issue for the given ticket, waived until `until`.
+    pub fn new(ticket: impl Into<String>, until: Option<DateTime<Utc>>) -> Self {
+        Self {
+            ticket: ticket.into(),
+            until,
+        }
+    }
+
+    /// Returns true if the waiver has expired at `now`.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.until.is_some_and(|until| until <= now)
+    }
+
+    /// Applies the waiver to a failure found at `now`.
+    ///
+    /// An active waiver attaches itself to the issue and returns true. An expired
+    /// waiver leaves the issue counting as a failure and notes the expiry in its message.
+    pub(crate) fn annotate(&self, issue: &mut ValidationIssue, now: DateTime<Utc>) -> bool {
+        match self.until {
+            Some(until) if until <= now => {
+                issue.message = format!(
+                    "{} (known issue {} waiver expired at {})",
+                    issue.message,
+                    self.ticket,
+                    until.to_rfc3339()
+                );
+                false
+            }
+            _ => {
+                issue.known_issue = Some(self.clone());
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Level;
+    use chrono::TimeZone;
+
+    fn issue() -> ValidationIssue {
+        ValidationIssue {
+            check_name: "order_totals".to_string(),
+            constraint_name: "sum".to_string(),
+            level: Level::Error,
+            message: "sum 10 does not equal 12".to_string(),
+            metric: Some(10.0),
+            cached: false,
+            known_issue: None,
+            check_id: None,
+            constraint_id: None,
+            impacts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_active_waiver_annotates_issue() {
+        let now = Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2026, 12, 1, 0, 0, 0).unwrap();
+
+        for known_issue in [
+            KnownIssue::new("JIRA-1234", Some(until)),
+            KnownIssue::new("JIRA-1234", None),
+        ] {
+            let mut issue = issue();
+            assert!(known_issue.annotate(&mut issue, now));
+            assert_eq!(issue.known_issue, Some(known_issue));
+            assert_eq!(issue.message, "sum 10 does not equal 12");
+        }
+    }
+
+    #[test]
+    fn test_expired_waiver_notes_expiry() {
+        let now = Utc.with_ymd_and_hms(2026, 12, 2, 0, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2026, 12, 1, 0, 0, 0).unwrap();
+        let known_issue = KnownIssue::new("JIRA-1234", Some(until));
+
+        let mut issue = issue();
+        assert!(!known_issue.annotate(&mut issue, now));
+        assert!(issue.known_issue.is_none());
+        assert_eq!(
+            issue.message,
+            "sum 10 does not equal 12 (known issue JIRA-1234 waiver expired at 2026-12-01T00:00:00+00:00)"
+        );
+    }
+}
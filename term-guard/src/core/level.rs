@@ -23,7 +23,10 @@ use std::fmt;
 ///   - Missing optional but recommended fields
 ///   - Performance-impacting data issues
 ///
-/// - **Info**: Use for informational metrics and observations
+/// - **Info**: Use for informational metrics and observations. Failures of Info
+///   checks are recorded as observations: they never fail validation, are counted in
+///   `ValidationMetrics::info_findings` instead of `failed_checks`, and are left out
+///   of the success rate
 ///   - Data distribution statistics
 ///   - Row counts and cardinality metrics
 ///   - Performance benchmarks
@@ -74,7 +77,8 @@ use std::fmt;
 )]
 #[serde(rename_all = "lowercase")]
 pub enum Level {
-    /// Informational level - Used for metrics and non-critical observations
+    /// Informational level - Used for metrics and non-critical observations; failures
+    /// are reported as observations and never affect the validation outcome
     Info = 0,
     /// Warning level - Indicates potential issues that should be reviewed
     #[default]
@@ -0,0 +1,353 @@
+//! Column-level lineage hints attached to validation issues.
+//!
+//! When a constraint fails, the dashboards, models and exports reading the
+//! columns it validates are what is actually at risk. A [`LineageMap`] records
+//! which downstream identifiers — names or URIs, the map does not interpret them
+//! — consume which columns of which tables. Set on a suite with
+//! [`ValidationSuiteBuilder::with_lineage`](super::ValidationSuiteBuilder::with_lineage),
+//! it fills [`ValidationIssue::impacts`](super::ValidationIssue::impacts) and
+//! [`ConstraintInfo::impacts`](super::ConstraintInfo::impacts), so formatters
+//! and hooks can say what a failure impacts.
+//!
+//! A constraint impacts the identifiers recorded for its validated table and
+//! any of its columns, under their logical names as well as the physical names
+//! a [column mapping](super::column_mapping) maps them to. Identifiers recorded
+//! under the column [`LINEAGE_ALL_COLUMNS`] are impacted by every constraint on the
+//! table. Table and column names match case-insensitively, as unquoted SQL
+//! identifiers do, and an unqualified table name matches the same table in any
+//! schema.
+//!
+//! Lineage files map tables to columns to lists of identifiers:
+//!
+//! ```json
+//! {
+//!   "orders": {
+//!     "amount": ["finance_daily_dashboard", "revenue_model"],
+//!     "*": ["https://catalog.example.com/datasets/orders_snapshot"]
+//!   }
+//! }
+//! ```
+//!
+//! JSON files are always supported; YAML files with the same structure require
+//! the `yaml` feature.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::core::LineageMap;
+//!
+//! let lineage = LineageMap::new()
+//!     .with("orders", "amount", "finance_daily_dashboard")
+//!     .with("sales.orders", "Amount", "revenue_model")
+//!     .with("customers", "email", "crm_sync");
+//!
+//! assert_eq!(
+//!     lineage.impacts("orders", ["amount"]),
+//!     vec!["finance_daily_dashboard", "revenue_model"]
+//! );
+//! assert!(lineage.impacts("orders", ["status"]).is_empty());
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::prelude::*;
+
+/// The column name recording identifiers impacted by every column of a table.
+pub const LINEAGE_ALL_COLUMNS: &str = "*";
+
+/// Downstream identifiers consuming columns of tables.
+///
+/// See the [module documentation](self) for how constraints are matched.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineageMap {
+    tables: BTreeMap<String, BTreeMap<String, BTreeSet<String>>>,
+}
+
+impl LineageMap {
+    /// Creates an empty lineage map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `downstream` consumes `column` of `table`.
+    pub fn with(
+        mut self,
+        table: impl AsRef<str>,
+        column: impl AsRef<str>,
+        downstream: impl Into<String>,
+    ) -> Self {
+        self.insert(table, column, downstream);
+        self
+    }
+
+    /// Records that `downstream` consumes `column` of `table`.
+    ///
+    /// Use [`LINEAGE_ALL_COLUMNS`] as the column for identifiers consuming the whole table.
+    pub fn insert(
+        &mut self,
+        table: impl AsRef<str>,
+        column: impl AsRef<str>,
+        downstream: impl Into<String>,
+    ) {
+        self.tables
+            .entry(normalize(table.as_ref()))
+            .or_default()
+            .entry(normalize(column.as_ref()))
+            .or_default()
+            .insert(downstream.into());
+    }
+
+    /// Returns true if the map records no lineage.
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Returns the identifiers impacted by a constraint on `table` validating
+    /// `columns`, sorted and without duplicates.
+    ///
+    /// A column qualified with the name of a table the map knows, such as the
+    /// `customers.id` of a foreign key, is looked up in that table. Other
+    /// columns are looked up in `table`, both as given and by their top-level
+    /// field, so `address.city` also matches lineage recorded for `address`.
+    pub fn impacts<I>(&self, table: &str, columns: I) -> Vec<String>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let table = normalize(table);
+        let mut impacts = BTreeSet::new();
+        self.collect(&table, None, &mut impacts);
+        for column in columns {
+            let column = normalize(column.as_ref());
+            match column.rsplit_once('.') {
+                Some((qualifier, name)) if self.knows_table(qualifier) => {
+                    self.collect(qualifier, Some(name), &mut impacts);
+                }
+                _ => {
+                    self.collect(&table, Some(&column), &mut impacts);
+                    if let Some((field, _)) = column.split_once('.') {
+                        self.collect(&table, Some(field), &mut impacts);
+                    }
+                }
+            }
+        }
+        impacts.into_iter().collect()
+    }
+
+    /// Reads a lineage map from JSON.
+    ///
+    /// See the [module documentation](self) for the format.
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        let entries: LineageFile = serde_json::from_str(json)
+            .map_err(|e| TermError::Configuration(format!("Invalid lineage file: {e}")))?;
+        Ok(Self::from_entries(entries))
+    }
+
+    /// Reads a lineage map from YAML.
+    ///
+    /// See the [module documentation](self) for the format.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_str(yaml: &str) -> Result<Self> {
+        let entries: LineageFile = serde_yaml::from_str(yaml)
+            .map_err(|e| TermError::Configuration(format!("Invalid lineage file: {e}")))?;
+        Ok(Self::from_entries(entries))
+    }
+
+    /// Reads a lineage map from a file, as YAML if its extension is `yaml` or
+    /// `yml` and as JSON otherwise.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        let is_yaml = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                extension.eq_ignore_ascii_case("yaml") || extension.eq_ignore_ascii_case("yml")
+            });
+        if !is_yaml {
+            return Self::from_json_str(&contents);
+        }
+        #[cfg(feature = "yaml")]
+        {
+            Self::from_yaml_str(&contents)
+        }
+        #[cfg(not(feature = "yaml"))]
+        {
+            Err(TermError::Configuration(format!(
+                "Reading the YAML lineage file '{}' requires the `yaml` feature",
+                path.display()
+            )))
+        }
+    }
+
+    fn from_entries(entries: LineageFile) -> Self {
+        let mut lineage = Self::new();
+        for (table, columns) in entries {
+            for (column, downstream) in columns {
+                for id in downstream {
+                    lineage.insert(&table, &column, id);
+                }
+            }
+        }
+        lineage
+    }
+
+    fn knows_table(&self, table: &str) -> bool {
+        self.tables.keys().any(|key| tables_match(key, table))
+    }
+
+    /// Adds the identifiers recorded for `column` and for all columns of `table`.
+    fn collect(&self, table: &str, column: Option<&str>, impacts: &mut BTreeSet<String>) {
+        for (_, columns) in self
+            .tables
+            .iter()
+            .filter(|(key, _)| tables_match(key, table))
+        {
+            for name in column.into_iter().chain([LINEAGE_ALL_COLUMNS]) {
+                if let Some(downstream) = columns.get(name) {
+                    impacts.extend(downstream.iter().cloned());
+                }
+            }
+        }
+    }
+}
+
+/// The structure of lineage files: tables to columns to identifiers.
+type LineageFile = BTreeMap<String, BTreeMap<String, Vec<String>>>;
+
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Returns true if two normalized table names refer to the same table, treating
+/// an unqualified name as matching the same table in any schema.
+fn tables_match(a: &str, b: &str) -> bool {
+    fn unqualified(name: &str) -> &str {
+        name.rsplit_once('.').map_or(name, |(_, table)| table)
+    }
+    a == b || (!a.contains('.') && a == unqualified(b)) || (!b.contains('.') && b == unqualified(a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lineage() -> LineageMap {
+        LineageMap::new()
+            .with("orders", "amount", "finance_daily_dashboard")
+            .with("orders", "amount", "revenue_model")
+            .with("orders", "status", "ops_board")
+            .with("orders", LINEAGE_ALL_COLUMNS, "orders_snapshot")
+            .with("customers", "id", "crm_sync")
+            .with("customers", "address", "geo_model")
+    }
+
+    #[test]
+    fn test_impacts_of_columns_and_whole_table() {
+        let lineage = lineage();
+        assert_eq!(
+            lineage.impacts("orders", ["amount", "status"]),
+            vec![
+                "finance_daily_dashboard",
+                "ops_board",
+                "orders_snapshot",
+                "revenue_model"
+            ]
+        );
+        assert_eq!(
+            lineage.impacts("orders", Vec::<String>::new()),
+            vec!["orders_snapshot"]
+        );
+        assert!(lineage.impacts("invoices", ["amount"]).is_empty());
+        assert!(LineageMap::new().impacts("orders", ["amount"]).is_empty());
+    }
+
+    #[test]
+    fn test_matching_ignores_case_and_schemas() {
+        let lineage = lineage().with("finance.ledger", "Total", "close_report");
+        assert_eq!(
+            lineage.impacts("ORDERS", ["Amount"]),
+            lineage.impacts("orders", ["amount"])
+        );
+        assert_eq!(
+            lineage.impacts("sales.orders", ["status"]),
+            vec!["ops_board", "orders_snapshot"]
+        );
+        assert_eq!(lineage.impacts("ledger", ["total"]), vec!["close_report"]);
+        assert!(lineage.impacts("audit.ledger", ["total"]).is_empty());
+    }
+
+    #[test]
+    fn test_qualified_and_nested_columns() {
+        let lineage = lineage();
+        // A foreign key from orders to customers
+        assert_eq!(
+            lineage.impacts("orders", ["customer_id", "customers.id"]),
+            vec!["crm_sync", "orders_snapshot"]
+        );
+        assert_eq!(
+            lineage.impacts("customers", ["address.city"]),
+            vec!["geo_model"]
+        );
+    }
+
+    #[test]
+    fn test_from_json_str() {
+        let lineage = LineageMap::from_json_str(
+            r#"{"Orders": {"amount": ["finance_daily_dashboard", "revenue_model"], "*": ["orders_snapshot"]}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            lineage.impacts("orders", ["AMOUNT"]),
+            vec![
+                "finance_daily_dashboard",
+                "orders_snapshot",
+                "revenue_model"
+            ]
+        );
+
+        let error = LineageMap::from_json_str(r#"{"orders": ["amount"]}"#).unwrap_err();
+        assert!(
+            error.to_string().contains("Invalid lineage file"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_from_file_reads_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lineage.json");
+        std::fs::write(&path, r#"{"orders": {"amount": ["revenue_model"]}}"#).unwrap();
+        let lineage = LineageMap::from_file(&path).unwrap();
+        assert_eq!(lineage.impacts("orders", ["amount"]), vec!["revenue_model"]);
+        assert!(LineageMap::from_file(dir.path().join("missing.json")).is_err());
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_from_file_reads_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lineage.yml");
+        std::fs::write(
+            &path,
+            "orders:\n  amount:\n    - finance_daily_dashboard\n    - revenue_model\n",
+        )
+        .unwrap();
+        let lineage = LineageMap::from_file(&path).unwrap();
+        assert_eq!(
+            lineage.impacts("orders", ["amount"]),
+            vec!["finance_daily_dashboard", "revenue_model"]
+        );
+    }
+
+    #[cfg(not(feature = "yaml"))]
+    #[test]
+    fn test_yaml_requires_feature() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lineage.yaml");
+        std::fs::write(&path, "orders: {}\n").unwrap();
+        let error = LineageMap::from_file(&path).unwrap_err();
+        assert!(error.to_string().contains("`yaml` feature"), "{error}");
+    }
+}
@@ -0,0 +1,478 @@
+//! Reference tables embedded in a suite definition.
+//!
+//! A suite built with
+//! [`ValidationSuiteBuilder::with_lookup`](super::ValidationSuiteBuilder::with_lookup)
+//! carries small reference tables, such as the valid values of a code column,
+//! so that it validates against them without reading any external file. Each
+//! run registers them as in-memory tables before any source, row filter or
+//! constraint is planned, so
+//! [`ContainmentConstraint::in_table`](crate::constraints::ContainmentConstraint::in_table),
+//! foreign keys and custom SQL can read them by name. The run removes them when
+//! it finishes and restores any table of the same name registered before.
+//!
+//! The contents of a lookup table are part of the suite definition: a
+//! [`LookupTable`] serializes them as base64-encoded Arrow IPC, and the suite
+//! fingerprint covers them, so cached results are invalidated when they change.
+//! Lookup tables are meant to be small; suites reject tables encoding to more
+//! than [`DEFAULT_MAX_LOOKUP_BYTES`] unless the limit is raised with
+//! [`ValidationSuiteBuilder::max_lookup_bytes`](super::ValidationSuiteBuilder::max_lookup_bytes).
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::array::{new_empty_array, ArrayRef};
+use arrow::compute::concat_batches;
+use arrow::datatypes::SchemaRef;
+use arrow::ipc::reader::StreamReader;
+use arrow::ipc::writer::StreamWriter;
+use arrow::record_batch::RecordBatch;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use datafusion::datasource::MemTable;
+use datafusion::datasource::TableProvider;
+use datafusion::prelude::SessionContext;
+use datafusion::scalar::ScalarValue;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+
+use super::unified::configuration_message;
+use super::ConfigIssue;
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+
+/// Default limit on the encoded size of a lookup table, 4 MiB.
+pub const DEFAULT_MAX_LOOKUP_BYTES: usize = 4 * 1024 * 1024;
+
+/// A reference table embedded in a suite definition.
+///
+/// The table holds its rows as a single record batch together with their Arrow
+/// IPC encoding, which is what it serializes to and what its size and
+/// fingerprint are computed from.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use arrow::datatypes::{DataType, Field, Schema};
+/// use datafusion::scalar::ScalarValue;
+/// use term_guard::core::LookupTable;
+///
+/// let schema = Arc::new(Schema::new(vec![Field::new("plan", DataType::Utf8, false)]));
+/// let plans = LookupTable::try_new(
+///     "valid_plans",
+///     vec![
+///         vec![ScalarValue::from("free")],
+///         vec![ScalarValue::from("pro")],
+///     ],
+///     schema,
+/// )
+/// .unwrap();
+/// assert_eq!(plans.num_rows(), 2);
+///
+/// let json = serde_json::to_string(&plans).unwrap();
+/// let restored: term_guard::core::LookupTable = serde_json::from_str(&json).unwrap();
+/// assert_eq!(restored.fingerprint(), plans.fingerprint());
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "EncodedLookupTable", into = "EncodedLookupTable")]
+pub struct LookupTable {
+    name: String,
+    batch: RecordBatch,
+    encoded: Arc<[u8]>,
+    fingerprint: String,
+}
+
+impl LookupTable {
+    /// Creates a lookup table from rows of values in the order of the schema's
+    /// fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if a row does not have a value for
+    /// every field, or a value does not have the type of its field.
+    pub fn try_new(
+        name: impl Into<String>,
+        rows: Vec<Vec<ScalarValue>>,
+        schema: SchemaRef,
+    ) -> Result<Self> {
+        let name = name.into();
+        let invalid =
+            |message: String| TermError::Configuration(format!("Lookup table '{name}': {message}"));
+
+        let width = schema.fields().len();
+        if let Some((index, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(invalid(format!(
+                "row {index} has {} values, the schema has {width} fields",
+                row.len()
+            )));
+        }
+
+        let mut columns: Vec<ArrayRef> = Vec::with_capacity(width);
+        for (index, field) in schema.fields().iter().enumerate() {
+            let column = if rows.is_empty() {
+                new_empty_array(field.data_type())
+            } else {
+                ScalarValue::iter_to_array(rows.iter().map(|row| row[index].clone()))
+                    .map_err(|e| invalid(format!("field '{}': {e}", field.name())))?
+            };
+            columns.push(column);
+        }
+        let batch = RecordBatch::try_new(schema, columns).map_err(|e| invalid(e.to_string()))?;
+        Self::from_batch(name, batch)
+    }
+
+    /// Creates a lookup table holding the rows of `batch`.
+    pub fn from_batch(name: impl Into<String>, batch: RecordBatch) -> Result<Self> {
+        let name = name.into();
+        let encoded = encode(&batch).map_err(|e| {
+            TermError::Configuration(format!("Lookup table '{name}' cannot be encoded: {e}"))
+        })?;
+        let fingerprint = hex::encode(&Sha256::digest(&encoded)[..16]);
+        Ok(Self {
+            name,
+            batch,
+            encoded: encoded.into(),
+            fingerprint,
+        })
+    }
+
+    /// Returns the name SQL refers to the table by.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the rows of the table.
+    pub fn batch(&self) -> &RecordBatch {
+        &self.batch
+    }
+
+    /// Returns the number of rows of the table.
+    pub fn num_rows(&self) -> usize {
+        self.batch.num_rows()
+    }
+
+    /// Returns the size of the table's Arrow IPC encoding in bytes.
+    pub fn size_bytes(&self) -> usize {
+        self.encoded.len()
+    }
+
+    /// Returns a hash of the table's schema and rows.
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+}
+
+impl fmt::Debug for LookupTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LookupTable")
+            .field("name", &self.name)
+            .field("rows", &self.batch.num_rows())
+            .field("size_bytes", &self.encoded.len())
+            .field("fingerprint", &self.fingerprint)
+            .finish()
+    }
+}
+
+/// The serialized form of a [`LookupTable`].
+#[derive(Serialize, Deserialize)]
+struct EncodedLookupTable {
+    name: String,
+    /// Base64 of the Arrow IPC stream of the rows
+    arrow_ipc: String,
+}
+
+impl From<LookupTable> for EncodedLookupTable {
+    fn from(table: LookupTable) -> Self {
+        Self {
+            name: table.name,
+            arrow_ipc: STANDARD.encode(&table.encoded),
+        }
+    }
+}
+
+impl TryFrom<EncodedLookupTable> for LookupTable {
+    type Error = TermError;
+
+    fn try_from(encoded: EncodedLookupTable) -> Result<Self> {
+        let invalid = |message: String| {
+            TermError::Configuration(format!(
+                "Lookup table '{}' cannot be decoded: {message}",
+                encoded.name
+            ))
+        };
+        let bytes = STANDARD
+            .decode(&encoded.arrow_ipc)
+            .map_err(|e| invalid(e.to_string()))?;
+        let reader =
+            StreamReader::try_new(bytes.as_slice(), None).map_err(|e| invalid(e.to_string()))?;
+        let schema = reader.schema();
+        let batches = reader
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| invalid(e.to_string()))?;
+        let batch = concat_batches(&schema, &batches).map_err(|e| invalid(e.to_string()))?;
+        Self::from_batch(encoded.name, batch)
+    }
+}
+
+fn encode(batch: &RecordBatch) -> std::result::Result<Vec<u8>, arrow::error::ArrowError> {
+    let mut writer = StreamWriter::try_new(Vec::new(), &batch.schema())?;
+    writer.write(batch)?;
+    writer.into_inner()
+}
+
+/// A lookup table attached to a suite, or the reason it could not be built.
+#[derive(Debug, Clone)]
+pub(crate) struct SuiteLookup {
+    name: String,
+    table: std::result::Result<LookupTable, String>,
+}
+
+impl SuiteLookup {
+    pub(crate) fn new(table: LookupTable) -> Self {
+        Self {
+            name: table.name.clone(),
+            table: Ok(table),
+        }
+    }
+
+    pub(crate) fn from_rows(name: String, rows: Vec<Vec<ScalarValue>>, schema: SchemaRef) -> Self {
+        let table = LookupTable::try_new(name.clone(), rows, schema).map_err(configuration_message);
+        Self { name, table }
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn table(&self) -> Option<&LookupTable> {
+        self.table.as_ref().ok()
+    }
+
+    /// Checks that the table was built, fits the size limit and has a name SQL
+    /// can refer to unquoted.
+    fn validate(&self, max_bytes: usize) -> Result<()> {
+        SqlSecurity::validate_identifier(&self.name)
+            .map_err(|e| TermError::Configuration(format!("Lookup table '{}': {e}", self.name)))?;
+        if !self
+            .name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(TermError::Configuration(format!(
+                "Lookup table '{}' must have a lowercase name without dots or quotes, so that SQL can refer to it unquoted",
+                self.name
+            )));
+        }
+        let table = self
+            .table
+            .as_ref()
+            .map_err(|message| TermError::Configuration(message.clone()))?;
+        if table.size_bytes() > max_bytes {
+            return Err(TermError::Configuration(format!(
+                "Lookup table '{}' encodes to {} bytes, over the limit of {max_bytes} bytes; raise the limit with max_lookup_bytes or attach the table as a source",
+                self.name,
+                table.size_bytes()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Returns an issue for every invalid lookup table attached to a suite, located
+/// under `lookups[i]`.
+///
+/// Lookup tables must not share a name with each other or with any of the
+/// `reserved` tables, the validated table and the attached sources.
+pub(crate) fn lookup_issues(
+    lookups: &[SuiteLookup],
+    max_bytes: usize,
+    reserved: &[&str],
+) -> Vec<ConfigIssue> {
+    let mut seen = HashSet::new();
+    lookups
+        .iter()
+        .enumerate()
+        .filter_map(|(index, lookup)| {
+            let message = if !seen.insert(lookup.name.as_str()) {
+                format!("Lookup table '{}' is attached more than once", lookup.name)
+            } else if reserved.contains(&lookup.name.as_str()) {
+                format!(
+                    "Lookup table '{}' has the name of the validated table or an attached source",
+                    lookup.name
+                )
+            } else {
+                configuration_message(lookup.validate(max_bytes).err()?)
+            };
+            Some(ConfigIssue::error(format!("lookups[{index}]"), message))
+        })
+        .collect()
+}
+
+/// Lookup tables a run registered, with the tables they replaced.
+#[derive(Debug, Default)]
+pub(crate) struct RegisteredLookups {
+    replaced: Vec<(String, Option<Arc<dyn TableProvider>>)>,
+}
+
+impl RegisteredLookups {
+    /// Registers `lookups` on the session context.
+    pub(crate) fn register(ctx: &SessionContext, lookups: &[SuiteLookup]) -> Result<Self> {
+        let mut registered = Self::default();
+        for table in lookups.iter().filter_map(SuiteLookup::table) {
+            if let Err(e) = registered.register_table(ctx, table) {
+                registered.restore(ctx);
+                return Err(e);
+            }
+            debug!(table.name = %table.name, table.rows = table.num_rows(), "Registered lookup table");
+        }
+        Ok(registered)
+    }
+
+    fn register_table(&mut self, ctx: &SessionContext, table: &LookupTable) -> Result<()> {
+        let provider = MemTable::try_new(table.batch.schema(), vec![vec![table.batch.clone()]])?;
+        let previous = ctx.deregister_table(table.name.as_str())?;
+        self.replaced.push((table.name.clone(), previous));
+        ctx.register_table(table.name.as_str(), Arc::new(provider))?;
+        Ok(())
+    }
+
+    /// Removes the registered lookup tables and restores the tables they replaced.
+    pub(crate) fn restore(self, ctx: &SessionContext) {
+        for (name, previous) in self.replaced.into_iter().rev() {
+            if let Err(e) = ctx.deregister_table(name.as_str()) {
+                warn!(table.name = %name, error = %e, "Failed to deregister lookup table");
+            }
+            if let Some(provider) = previous {
+                if let Err(e) = ctx.register_table(name.as_str(), provider) {
+                    warn!(table.name = %name, error = %e, "Failed to restore table replaced by lookup table");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("plan", DataType::Utf8, false),
+            Field::new("seats", DataType::Int64, true),
+        ]))
+    }
+
+    fn plans(name: &str) -> LookupTable {
+        LookupTable::try_new(
+            name,
+            vec![
+                vec![ScalarValue::from("free"), ScalarValue::Int64(Some(1))],
+                vec![ScalarValue::from("team"), ScalarValue::Int64(None)],
+            ],
+            schema(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rows_must_fit_the_schema() {
+        let table = plans("valid_plans");
+        assert_eq!(table.num_rows(), 2);
+        assert_eq!(
+            LookupTable::try_new("empty", Vec::new(), schema())
+                .unwrap()
+                .num_rows(),
+            0
+        );
+
+        let short = LookupTable::try_new("plans", vec![vec![ScalarValue::from("free")]], schema());
+        assert!(short
+            .unwrap_err()
+            .to_string()
+            .contains("row 0 has 1 values, the schema has 2 fields"));
+        let mistyped = LookupTable::try_new(
+            "plans",
+            vec![vec![ScalarValue::from("free"), ScalarValue::from("one")]],
+            schema(),
+        );
+        assert!(mistyped.is_err());
+    }
+
+    #[test]
+    fn test_serialization_round_trip() {
+        let table = plans("valid_plans");
+        let json = serde_json::to_value(&table).unwrap();
+        assert_eq!(json["name"], "valid_plans");
+        assert!(json["arrow_ipc"].is_string());
+
+        let restored: LookupTable = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.name(), "valid_plans");
+        assert_eq!(restored.batch(), table.batch());
+        assert_eq!(restored.fingerprint(), table.fingerprint());
+
+        let fewer = LookupTable::from_batch("valid_plans", table.batch().slice(0, 1)).unwrap();
+        assert_ne!(fewer.fingerprint(), table.fingerprint());
+
+        let corrupt = serde_json::json!({"name": "valid_plans", "arrow_ipc": "bm90IGFycm93"});
+        assert!(serde_json::from_value::<LookupTable>(corrupt).is_err());
+    }
+
+    #[test]
+    fn test_validation() {
+        let valid = SuiteLookup::new(plans("valid_plans"));
+        assert!(lookup_issues(
+            std::slice::from_ref(&valid),
+            DEFAULT_MAX_LOOKUP_BYTES,
+            &["data"]
+        )
+        .is_empty());
+
+        let issues = lookup_issues(
+            &[
+                valid.clone(),
+                SuiteLookup::new(plans("ValidPlans")),
+                valid.clone(),
+                SuiteLookup::new(plans("data")),
+                SuiteLookup::from_rows("short".to_string(), vec![vec![]], schema()),
+            ],
+            DEFAULT_MAX_LOOKUP_BYTES,
+            &["data"],
+        );
+        let paths: Vec<&str> = issues.iter().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["lookups[1]", "lookups[2]", "lookups[3]", "lookups[4]"]
+        );
+        assert!(issues[0].message.contains("lowercase name"));
+        assert!(issues[1].message.contains("attached more than once"));
+        assert!(issues[2].message.contains("name of the validated table"));
+        assert!(issues[3].message.contains("row 0 has 0 values"));
+
+        let issues = lookup_issues(&[valid], 16, &[]);
+        assert!(
+            issues[0].message.contains("over the limit of 16 bytes"),
+            "{}",
+            issues[0].message
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restores_replaced_tables() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE valid_plans (plan VARCHAR) AS VALUES ('legacy')")
+            .await?
+            .collect()
+            .await?;
+
+        let registered =
+            RegisteredLookups::register(&ctx, &[SuiteLookup::new(plans("valid_plans"))])?;
+        assert_eq!(ctx.table("valid_plans").await?.count().await?, 2);
+
+        registered.restore(&ctx);
+        assert_eq!(ctx.table("valid_plans").await?.count().await?, 1);
+        Ok(())
+    }
+}
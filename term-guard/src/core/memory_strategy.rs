@@ -0,0 +1,524 @@
+//! Memory strategies for constraints whose state grows with the data.
+//!
+//! Most constraints aggregate a table into a handful of counts and run in
+//! constant memory. Exact uniqueness counts and categorical histograms instead
+//! hold every distinct value of their columns while the query runs, which
+//! exceeds the memory pool of a small container on a large table: the query
+//! fails with DataFusion's "Resources exhausted" error. A [`MemoryStrategy`]
+//! set through
+//! [`ConstraintOptions::with_memory_strategy`](super::ConstraintOptions::with_memory_strategy)
+//! bounds that state, each constraint family supporting its own strategy:
+//!
+//! - uniqueness constraints accept [`MemoryStrategy::HashPartitioned`]. Rows are
+//!   hashed by their key into a fixed number of partitions, queried one after
+//!   the other. Equal keys always land in the same partition, so duplicates are
+//!   found within each partition and the counts of the partitions add up to the
+//!   exact counts. Only the keys of one partition are held at a time, at the
+//!   cost of scanning the table once per partition;
+//! - histograms accept [`MemoryStrategy::Sketched`]. Values are counted
+//!   exactly until `max_tracked_values` distinct values have been seen. Beyond
+//!   that, every value is counted in a count-min sketch and the tracked values
+//!   are the most frequent ones by their sketched counts. The histogram is then
+//!   approximate: it lists at most `max_tracked_values` buckets, the counts of
+//!   values admitted after the cap overestimate the true counts by at most
+//!   `epsilon` times the number of rows with probability `1 - delta`, and values
+//!   that were never tracked are missing from the buckets. Such results carry
+//!   [`MEMORY_APPROXIMATE_DETAIL`] in their details.
+//!
+//! Constraints reject the strategies they don't support when they are built.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::core::{Check, ConstraintOptions, MemoryStrategy};
+//! use std::sync::Arc;
+//!
+//! let check = Check::builder("large_table")
+//!     .validates_uniqueness_with_options(
+//!         ["order_id"],
+//!         ConstraintOptions::new()
+//!             .with_threshold(1.0)
+//!             .with_memory_strategy(MemoryStrategy::hash_partitioned(64)),
+//!     )
+//!     .has_histogram_with_options(
+//!         "country",
+//!         Arc::new(|hist| hist.most_common_ratio() < 0.5),
+//!         ConstraintOptions::new().with_memory_strategy(MemoryStrategy::sketched(10_000)),
+//!     )
+//!     .build();
+//! assert_eq!(check.constraints().len(), 2);
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use datafusion::logical_expr::{create_udf, ColumnarValue, ScalarUDF, Volatility};
+use datafusion::prelude::SessionContext;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+
+/// Detail holding the number of hash partitions a result was computed in.
+pub const MEMORY_PARTITIONS_DETAIL: &str = "memory.partitions";
+
+/// Detail set to 1 on results computed from approximate counts.
+pub const MEMORY_APPROXIMATE_DETAIL: &str = "memory.approximate";
+
+/// UDF returning the hash partition of a key.
+const HASH_PARTITION_UDF_NAME: &str = "term_hash_partition";
+
+/// Default relative error of sketched counts.
+const DEFAULT_EPSILON: f64 = 0.001;
+
+/// Default probability of sketched counts exceeding their error bound.
+const DEFAULT_DELTA: f64 = 0.01;
+
+/// How a constraint bounds the memory its query holds.
+///
+/// See the [module documentation](self) for the constraints supporting each
+/// strategy and the accuracy of their results.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum MemoryStrategy {
+    /// Runs a single query and leaves memory management to DataFusion (default).
+    #[default]
+    Unbounded,
+    /// Queries the table in `partitions` hash partitions of the key, one at a
+    /// time. Exact; supported by uniqueness constraints.
+    HashPartitioned {
+        /// Number of partitions, each holding about `1 / partitions` of the keys
+        partitions: u32,
+    },
+    /// Counts at most `max_tracked_values` values exactly and estimates the
+    /// rest with a count-min sketch. Approximate beyond the cap; supported by
+    /// histograms.
+    Sketched {
+        /// Number of distinct values tracked
+        max_tracked_values: usize,
+        /// Overestimate of sketched counts, as a fraction of the row count
+        epsilon: f64,
+        /// Probability of a sketched count exceeding the `epsilon` bound
+        delta: f64,
+    },
+}
+
+impl MemoryStrategy {
+    /// Queries the table in `partitions` hash partitions of the key.
+    pub fn hash_partitioned(partitions: u32) -> Self {
+        Self::HashPartitioned { partitions }
+    }
+
+    /// Tracks at most `max_tracked_values` distinct values, with sketched counts
+    /// within 0.1% of the row count at 99% probability beyond the cap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::MemoryStrategy;
+    ///
+    /// let strategy = MemoryStrategy::sketched(1_000);
+    /// assert_eq!(strategy.to_string(), "sketched (1000 values, epsilon 0.001, delta 0.01)");
+    /// assert!(strategy.validate().is_ok());
+    /// assert!(MemoryStrategy::hash_partitioned(0).validate().is_err());
+    /// ```
+    pub fn sketched(max_tracked_values: usize) -> Self {
+        Self::Sketched {
+            max_tracked_values,
+            epsilon: DEFAULT_EPSILON,
+            delta: DEFAULT_DELTA,
+        }
+    }
+
+    /// Returns whether this is the default strategy.
+    pub fn is_unbounded(&self) -> bool {
+        matches!(self, Self::Unbounded)
+    }
+
+    /// Checks the settings of the strategy.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if there are no partitions, no
+    /// tracked values, or an error bound outside `(0, 1)`.
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            Self::Unbounded => Ok(()),
+            Self::HashPartitioned { partitions: 0 } => Err(TermError::Configuration(
+                "Hash partitioning needs at least one partition".into(),
+            )),
+            Self::HashPartitioned { .. } => Ok(()),
+            Self::Sketched {
+                max_tracked_values: 0,
+                ..
+            } => Err(TermError::Configuration(
+                "Sketched counting needs at least one tracked value".into(),
+            )),
+            Self::Sketched { epsilon, delta, .. } => {
+                for (name, value) in [("epsilon", epsilon), ("delta", delta)] {
+                    if !(value > 0.0 && value < 1.0) {
+                        return Err(TermError::Configuration(format!(
+                            "Sketch {name} must be between 0 and 1 exclusive, got {value}"
+                        )));
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Checks the strategy and that uniqueness constraints support it.
+    pub(crate) fn check_uniqueness(&self) -> Result<()> {
+        self.validate()?;
+        match self {
+            Self::Sketched { .. } => Err(TermError::Configuration(
+                "Uniqueness constraints support the unbounded and hash-partitioned memory strategies, not sketched counting".into(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the strategy and that histograms support it.
+    pub(crate) fn check_histogram(&self) -> Result<()> {
+        self.validate()?;
+        match self {
+            Self::HashPartitioned { .. } => Err(TermError::Configuration(
+                "Histograms support the unbounded and sketched memory strategies, not hash partitioning".into(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for MemoryStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unbounded => write!(f, "unbounded"),
+            Self::HashPartitioned { partitions } => {
+                write!(f, "hash-partitioned ({partitions} partitions)")
+            }
+            Self::Sketched {
+                max_tracked_values,
+                epsilon,
+                delta,
+            } => write!(
+                f,
+                "sketched ({max_tracked_values} values, epsilon {epsilon}, delta {delta})"
+            ),
+        }
+    }
+}
+
+/// Returns the SQL expression of the partition key of `columns`, equal for rows
+/// with equal values in all of them.
+pub(crate) fn partition_key_sql(columns: &[String]) -> Result<String> {
+    let parts = columns
+        .iter()
+        .map(|column| {
+            let column = SqlSecurity::escape_identifier(column)?;
+            Ok(format!("COALESCE(CAST({column} AS VARCHAR), '<NULL>')"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(parts.join(" || '|' || "))
+}
+
+/// Returns a derived table with the rows of `table_name` in `partition` of
+/// `partitions`, to be queried in place of the table.
+pub(crate) fn partition_sql(
+    table_name: &str,
+    key_sql: &str,
+    partition: u32,
+    partitions: u32,
+) -> String {
+    format!(
+        "(SELECT * FROM {table_name} WHERE {HASH_PARTITION_UDF_NAME}({key_sql}, {partitions}) = {partition}) AS term_partition"
+    )
+}
+
+/// Registers the UDF used by [`partition_sql`].
+pub(crate) fn register_partition_function(ctx: &SessionContext) {
+    ctx.register_udf(hash_partition_udf());
+}
+
+fn hash_value<T: Hash + ?Sized>(seed: u64, value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn hash_partition_udf() -> ScalarUDF {
+    create_udf(
+        HASH_PARTITION_UDF_NAME,
+        vec![DataType::Utf8, DataType::Int64],
+        DataType::Int64,
+        Volatility::Immutable,
+        Arc::new(|args: &[ColumnarValue]| {
+            let arrays = ColumnarValue::values_to_arrays(args)?;
+            let keys = cast(&arrays[0], &DataType::Utf8)?;
+            let keys = keys
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .expect("cast to Utf8");
+            let partitions = arrays[1]
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .expect("Int64 argument");
+            let values = (0..keys.len())
+                .map(|i| {
+                    if partitions.is_null(i) || partitions.value(i) <= 0 {
+                        return None;
+                    }
+                    let key = (!keys.is_null(i)).then(|| keys.value(i));
+                    Some((hash_value(0, &key) % partitions.value(i) as u64) as i64)
+                })
+                .collect::<Int64Array>();
+            Ok(ColumnarValue::Array(Arc::new(values) as ArrayRef))
+        }),
+    )
+}
+
+/// Count-min sketch estimating the frequencies of string values.
+///
+/// Estimates never undercount; they overcount by at most `epsilon` times the
+/// number of added values with probability `1 - delta`.
+#[derive(Debug, Clone)]
+pub(crate) struct CountMinSketch {
+    width: usize,
+    depth: usize,
+    counters: Vec<u64>,
+}
+
+impl CountMinSketch {
+    pub(crate) fn new(epsilon: f64, delta: f64) -> Self {
+        let width = (std::f64::consts::E / epsilon).ceil().max(1.0) as usize;
+        let depth = (1.0 / delta).ln().ceil().max(1.0) as usize;
+        Self {
+            width,
+            depth,
+            counters: vec![0; width * depth],
+        }
+    }
+
+    /// Counts one occurrence of `value` and returns its new estimate.
+    pub(crate) fn add(&mut self, value: &str) -> u64 {
+        let mut estimate = u64::MAX;
+        for row in 0..self.depth {
+            let index = row * self.width + (hash_value(row as u64, value) as usize % self.width);
+            self.counters[index] += 1;
+            estimate = estimate.min(self.counters[index]);
+        }
+        estimate
+    }
+
+    /// Returns the estimated number of occurrences of `value`.
+    #[cfg(test)]
+    pub(crate) fn estimate(&self, value: &str) -> u64 {
+        (0..self.depth)
+            .map(|row| {
+                self.counters
+                    [row * self.width + (hash_value(row as u64, value) as usize % self.width)]
+            })
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+/// Frequencies of at most `max_tracked` values, exact until more distinct
+/// values were seen and the most frequent ones by sketched counts after.
+#[derive(Debug)]
+pub(crate) struct BoundedFrequencies {
+    max_tracked: usize,
+    sketch: CountMinSketch,
+    tracked: HashMap<String, u64>,
+    /// Tracked values ordered by count, maintained once the cap was exceeded
+    by_count: BTreeSet<(u64, String)>,
+    approximate: bool,
+}
+
+impl BoundedFrequencies {
+    pub(crate) fn new(max_tracked: usize, epsilon: f64, delta: f64) -> Self {
+        Self {
+            max_tracked,
+            sketch: CountMinSketch::new(epsilon, delta),
+            tracked: HashMap::new(),
+            by_count: BTreeSet::new(),
+            approximate: false,
+        }
+    }
+
+    pub(crate) fn add(&mut self, value: &str) {
+        let estimate = self.sketch.add(value);
+        if let Some(count) = self.tracked.get_mut(value) {
+            if self.approximate {
+                self.by_count.remove(&(*count, value.to_string()));
+                self.by_count.insert((*count + 1, value.to_string()));
+            }
+            *count += 1;
+            return;
+        }
+        if self.tracked.len() < self.max_tracked {
+            self.tracked.insert(value.to_string(), 1);
+            return;
+        }
+        if !self.approximate {
+            self.approximate = true;
+            self.by_count = self
+                .tracked
+                .iter()
+                .map(|(value, count)| (*count, value.clone()))
+                .collect();
+        }
+        // Replace the least frequent tracked value if this one is more frequent
+        let Some((min_count, _)) = self.by_count.first() else {
+            return;
+        };
+        if estimate > *min_count {
+            if let Some((_, evicted)) = self.by_count.pop_first() {
+                self.tracked.remove(&evicted);
+            }
+            self.tracked.insert(value.to_string(), estimate);
+            self.by_count.insert((estimate, value.to_string()));
+        }
+    }
+
+    /// Returns whether values were left untracked, making the counts approximate.
+    pub(crate) fn is_approximate(&self) -> bool {
+        self.approximate
+    }
+
+    /// Returns the tracked values by descending count, then value.
+    pub(crate) fn into_counts(self) -> Vec<(String, u64)> {
+        let mut counts = self.tracked.into_iter().collect::<Vec<_>>();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_and_family_support() {
+        assert!(MemoryStrategy::default().is_unbounded());
+        assert!(MemoryStrategy::hash_partitioned(8)
+            .check_uniqueness()
+            .is_ok());
+        assert!(MemoryStrategy::hash_partitioned(8)
+            .check_histogram()
+            .is_err());
+        assert!(MemoryStrategy::sketched(100).check_histogram().is_ok());
+        assert!(MemoryStrategy::sketched(100).check_uniqueness().is_err());
+        assert!(MemoryStrategy::sketched(0).validate().is_err());
+        let invalid = MemoryStrategy::Sketched {
+            max_tracked_values: 10,
+            epsilon: 0.0,
+            delta: 0.01,
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let strategy = MemoryStrategy::hash_partitioned(16);
+        let json = serde_json::to_string(&strategy).unwrap();
+        assert_eq!(json, r#"{"strategy":"hash_partitioned","partitions":16}"#);
+        assert_eq!(
+            serde_json::from_str::<MemoryStrategy>(&json).unwrap(),
+            strategy
+        );
+    }
+
+    #[test]
+    fn test_count_min_sketch_never_undercounts() {
+        let mut sketch = CountMinSketch::new(0.01, 0.01);
+        for i in 0..1_000 {
+            sketch.add(&format!("v{}", i % 100));
+        }
+        let mut overcount = 0;
+        for i in 0..100 {
+            let estimate = sketch.estimate(&format!("v{i}"));
+            assert!(estimate >= 10, "{estimate}");
+            overcount += estimate - 10;
+        }
+        // Within epsilon times the number of values on average
+        assert!(overcount <= 100 * 10, "{overcount}");
+    }
+
+    #[test]
+    fn test_bounded_frequencies_are_exact_below_the_cap() {
+        let mut frequencies = BoundedFrequencies::new(3, DEFAULT_EPSILON, DEFAULT_DELTA);
+        for value in ["a", "b", "a", "c", "a", "b"] {
+            frequencies.add(value);
+        }
+        assert!(!frequencies.is_approximate());
+        assert_eq!(
+            frequencies.into_counts(),
+            vec![("a".into(), 3), ("b".into(), 2), ("c".into(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_bounded_frequencies_keep_the_heavy_hitters() {
+        let mut frequencies = BoundedFrequencies::new(2, DEFAULT_EPSILON, DEFAULT_DELTA);
+        for i in 0..500 {
+            frequencies.add(&format!("rare{i}"));
+            if i % 2 == 0 {
+                frequencies.add("frequent");
+            }
+            if i % 5 == 0 {
+                frequencies.add("common");
+            }
+        }
+        assert!(frequencies.is_approximate());
+        let counts = frequencies.into_counts();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[0].0, "frequent");
+        assert!(counts[0].1 >= 250);
+        assert_eq!(counts[1].0, "common");
+    }
+
+    #[test]
+    fn test_partition_key() {
+        let key = partition_key_sql(&["a".to_string(), "b".to_string()]).unwrap();
+        assert_eq!(
+            key,
+            r#"COALESCE(CAST("a" AS VARCHAR), '<NULL>') || '|' || COALESCE(CAST("b" AS VARCHAR), '<NULL>')"#
+        );
+        assert!(partition_key_sql(&["a; DROP TABLE t".to_string()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_partitions_cover_every_row_once() {
+        let ctx = SessionContext::new();
+        register_partition_function(&ctx);
+        let key = partition_key_sql(&["value".to_string()]).unwrap();
+        let mut total = 0;
+        for partition in 0..4 {
+            let source = partition_sql("generate_series(1, 1000)", &key, partition, 4);
+            let batches = ctx
+                .sql(&format!("SELECT COUNT(*) FROM {source}"))
+                .await
+                .unwrap()
+                .collect()
+                .await
+                .unwrap();
+            let count = batches[0]
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap()
+                .value(0);
+            assert!(count > 0 && count < 1000, "{count}");
+            total += count;
+        }
+        assert_eq!(total, 1000);
+    }
+}
@@ -0,0 +1,211 @@
+//! Table statistics read from file metadata instead of the data.
+//!
+//! Parquet files record in their footer the number of rows of every row group
+//! and, for each column chunk, usually its number of nulls and its smallest and
+//! largest value. [`ParquetSource`](crate::sources::ParquetSource) aggregates
+//! them over all of its files through
+//! [`DataSource::statistics`](crate::sources::DataSource::statistics). A suite
+//! built with
+//! [`ValidationSuiteBuilder::metadata_only`](super::ValidationSuiteBuilder::metadata_only)
+//! answers the constraints implementing
+//! [`Constraint::evaluate_from_metadata`](super::Constraint::evaluate_from_metadata)
+//! from these statistics without scanning the data: size constraints from the
+//! row count, completeness from the null counts and min or max constraints of
+//! integer columns from the bounds. Every other constraint is scanned as usual.
+//!
+//! # Caveats
+//!
+//! Metadata-only answers are only as good as the footers:
+//!
+//! - Footers describe the files as written. Statistics are only used when the
+//!   run registers the validated table from its attached source and reads it
+//!   whole, without row filters, a column mapping, a derived table or a preview.
+//! - Writers may leave out statistics, for some column chunks or entirely. A
+//!   column missing a null count or bounds in any row group has none, and its
+//!   constraints are scanned.
+//! - Writers may flag bounds as inexact, for example when they truncate them.
+//!   [`MetadataOnlyMode::Exact`] leaves such bounds out, while
+//!   [`MetadataOnlyMode::AllowInexact`] uses them as they are, so a constraint
+//!   may be judged on a bound below the true minimum or above the true maximum.
+//! - Footers do not count NaN or infinite values, so bounds are only read for
+//!   signed integer columns; floating point, decimal and temporal columns are
+//!   scanned.
+//! - A footer contradicting its data, for example after a faulty rewrite, gives
+//!   results a scan would not. Disable metadata-only mode for files from
+//!   writers you do not trust.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::ColumnStatistics;
+
+/// Evidence of statistics read from Parquet footers.
+pub const PARQUET_METADATA_EVIDENCE: &str = "parquet_metadata";
+
+/// Whether a suite answers constraints from metadata statistics, and which.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataOnlyMode {
+    /// Scan the data for every constraint
+    #[default]
+    Disabled,
+    /// Use the row and null counts, and only the bounds flagged as exact
+    Exact,
+    /// Also use bounds the files flag as inexact
+    AllowInexact,
+}
+
+impl MetadataOnlyMode {
+    /// Returns true unless the mode is [`Disabled`](Self::Disabled).
+    pub fn is_enabled(self) -> bool {
+        self != Self::Disabled
+    }
+}
+
+/// Statistics of a single column read from file metadata.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetadataColumnStatistics {
+    /// Number of null values, if every file records it
+    pub null_count: Option<u64>,
+    /// Smallest non-null value, only set for signed integer columns
+    pub min: Option<f64>,
+    /// Largest non-null value, only set for signed integer columns
+    pub max: Option<f64>,
+    /// Whether every file flags its minimum as exact
+    pub min_exact: bool,
+    /// Whether every file flags its maximum as exact
+    pub max_exact: bool,
+}
+
+/// Statistics of a table read from the metadata of its files.
+///
+/// See the [module documentation](self) for when they can stand in for a scan.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::core::{
+///     MetadataColumnStatistics, MetadataOnlyMode, MetadataStatistics, PARQUET_METADATA_EVIDENCE,
+/// };
+///
+/// let mut statistics = MetadataStatistics::new(PARQUET_METADATA_EVIDENCE, 1000, 2);
+/// statistics.insert(
+///     "id",
+///     MetadataColumnStatistics {
+///         null_count: Some(0),
+///         min: Some(1.0),
+///         max: Some(1000.0),
+///         min_exact: true,
+///         max_exact: false,
+///     },
+/// );
+///
+/// let trusted = statistics.trusted(MetadataOnlyMode::Exact);
+/// assert_eq!(trusted.column("id").unwrap().min, Some(1.0));
+/// assert_eq!(trusted.column("id").unwrap().max, None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetadataStatistics {
+    /// Where the statistics come from, such as [`PARQUET_METADATA_EVIDENCE`]
+    pub evidence: String,
+    /// Total number of rows
+    pub row_count: u64,
+    /// Number of files the statistics were read from
+    pub file_count: usize,
+    columns: HashMap<String, MetadataColumnStatistics>,
+}
+
+impl MetadataStatistics {
+    /// Creates statistics without any column.
+    pub fn new(evidence: impl Into<String>, row_count: u64, file_count: usize) -> Self {
+        Self {
+            evidence: evidence.into(),
+            row_count,
+            file_count,
+            columns: HashMap::new(),
+        }
+    }
+
+    /// Adds the statistics for a column, replacing any previous entry.
+    pub fn insert(&mut self, column: impl Into<String>, statistics: MetadataColumnStatistics) {
+        self.columns.insert(column.into(), statistics);
+    }
+
+    /// Returns the statistics for a column, if available.
+    pub fn column(&self, column: &str) -> Option<&MetadataColumnStatistics> {
+        self.columns.get(column)
+    }
+
+    /// Returns the number of columns with statistics.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns true if no column statistics are available.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Returns the statistics `mode` trusts.
+    ///
+    /// [`MetadataOnlyMode::Exact`] drops the bounds not flagged as exact;
+    /// the other modes keep every statistic.
+    pub fn trusted(&self, mode: MetadataOnlyMode) -> Self {
+        let mut trusted = self.clone();
+        if mode == MetadataOnlyMode::Exact {
+            for column in trusted.columns.values_mut() {
+                if !column.min_exact {
+                    column.min = None;
+                }
+                if !column.max_exact {
+                    column.max = None;
+                }
+            }
+        }
+        trusted
+    }
+
+    /// Returns the statistics of a column as [`ColumnStatistics`], if its null
+    /// count is known.
+    pub fn column_statistics(&self, column: &str) -> Option<ColumnStatistics> {
+        let statistics = self.column(column)?;
+        Some(ColumnStatistics {
+            row_count: self.row_count,
+            null_count: statistics.null_count?,
+            min: statistics.min,
+            max: statistics.max,
+            ..ColumnStatistics::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trusted_statistics() {
+        let mut statistics = MetadataStatistics::new(PARQUET_METADATA_EVIDENCE, 10, 1);
+        statistics.insert(
+            "id",
+            MetadataColumnStatistics {
+                null_count: Some(2),
+                min: Some(-5.0),
+                max: Some(7.0),
+                min_exact: false,
+                max_exact: true,
+            },
+        );
+        statistics.insert("name", MetadataColumnStatistics::default());
+
+        let exact = statistics.trusted(MetadataOnlyMode::Exact);
+        let id = exact.column_statistics("id").unwrap();
+        assert_eq!((id.non_null_count(), id.min, id.max), (8, None, Some(7.0)));
+        assert!(exact.column_statistics("name").is_none());
+        assert!(exact.column_statistics("missing").is_none());
+
+        let inexact = statistics.trusted(MetadataOnlyMode::AllowInexact);
+        assert_eq!(inexact.column("id").unwrap().min, Some(-5.0));
+        assert!(!MetadataOnlyMode::default().is_enabled());
+    }
+}
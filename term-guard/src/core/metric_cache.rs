@@ -0,0 +1,570 @@
+//! Sharing of simple table metrics between the constraints of a run.
+//!
+//! Many constraints start from the same counts: a size check, the completeness of
+//! a column and its uniqueness all need the row count of the table. A
+//! [`MetricCache`] memoizes these counts so each one is computed once per run,
+//! however many constraints ask for it.
+//!
+//! The suite creates a fresh cache for every run and attaches it to the
+//! [`ValidationContext`](super::ValidationContext) of each constraint. Constraints
+//! request counts through [`fetch_metrics`], which answers from the cache of the
+//! current context and computes the missing counts in a single query. Outside a
+//! suite run (no cache attached) every request is computed.
+//!
+//! Cached metrics are keyed by table, [`MetricKind`], column set and filter:
+//! - [`MetricKind::RowCount`] computes `COUNT(*)`
+//! - [`MetricKind::NullCount`] computes `COUNT(*) - COUNT(column)`
+//! - [`MetricKind::DistinctCount`] computes `COUNT(DISTINCT column)`, or
+//!   `COUNT(DISTINCT (a, b))` for a column set
+//!
+//! Hits and misses of a run are reported in
+//! [`PerformanceMetrics`](super::PerformanceMetrics).
+//!
+//! A suite configured with a [`SharedStatsCache`] backs its run cache with it:
+//! unfiltered metrics missing from the run are looked up there before being
+//! computed, and computed ones are published for other suites.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use arrow::array::Int64Array;
+use datafusion::prelude::*;
+use tracing::debug;
+
+use super::{collect_with_metrics, current_validation_context};
+use crate::optimizer::SharedStatsCache;
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+
+/// The kind of a cached metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetricKind {
+    /// Number of rows
+    RowCount,
+    /// Number of NULL values of a column
+    NullCount,
+    /// Number of distinct non-NULL values of a column, or of distinct
+    /// combinations of a column set
+    DistinctCount,
+}
+
+impl MetricKind {
+    /// Returns the name of the metric kind.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricKind::RowCount => "row_count",
+            MetricKind::NullCount => "null_count",
+            MetricKind::DistinctCount => "distinct_count",
+        }
+    }
+}
+
+impl fmt::Display for MetricKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Identifies a metric: the table, metric kind, column set and filter.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::core::{MetricKey, MetricKind};
+///
+/// let key = MetricKey::null_count("orders", "email").with_filter("status = 'open'");
+/// assert_eq!(key.kind(), MetricKind::NullCount);
+/// assert_eq!(key.to_string(), "null_count(email) of orders where status = 'open'");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MetricKey {
+    table: String,
+    kind: MetricKind,
+    columns: Vec<String>,
+    filter: Option<String>,
+}
+
+impl MetricKey {
+    /// The number of rows of a table.
+    pub fn row_count(table: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            kind: MetricKind::RowCount,
+            columns: Vec::new(),
+            filter: None,
+        }
+    }
+
+    /// The number of NULL values of a column.
+    pub fn null_count(table: impl Into<String>, column: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            kind: MetricKind::NullCount,
+            columns: vec![column.into()],
+            filter: None,
+        }
+    }
+
+    /// The number of distinct non-NULL values of a column, or of distinct
+    /// combinations of a column set.
+    pub fn distinct_count<I, S>(table: impl Into<String>, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            table: table.into(),
+            kind: MetricKind::DistinctCount,
+            columns: columns.into_iter().map(Into::into).collect(),
+            filter: None,
+        }
+    }
+
+    /// Restricts the metric to the rows matching a SQL predicate.
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Returns the table the metric is computed on.
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Returns the kind of the metric.
+    pub fn kind(&self) -> MetricKind {
+        self.kind
+    }
+
+    /// Returns the columns the metric is computed on.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns the filter of the metric, if any.
+    pub fn filter(&self) -> Option<&str> {
+        self.filter.as_deref()
+    }
+
+    /// Returns the SQL aggregate expression computing the metric.
+    fn expression(&self) -> Result<String> {
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| SqlSecurity::escape_identifier(column))
+            .collect::<Result<Vec<_>>>()?;
+
+        match (self.kind, columns.as_slice()) {
+            (MetricKind::RowCount, []) => Ok("COUNT(*)".to_string()),
+            (MetricKind::NullCount, [column]) => Ok(format!("COUNT(*) - COUNT({column})")),
+            (MetricKind::DistinctCount, [column]) => Ok(format!("COUNT(DISTINCT {column})")),
+            (MetricKind::DistinctCount, columns) if !columns.is_empty() => {
+                Ok(format!("COUNT(DISTINCT ({}))", columns.join(", ")))
+            }
+            _ => Err(TermError::Internal(format!(
+                "Metric {} does not apply to {} column(s)",
+                self.kind,
+                self.columns.len()
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for MetricKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}({}) of {}",
+            self.kind,
+            self.columns.join(", "),
+            self.table
+        )?;
+        if let Some(filter) = &self.filter {
+            write!(f, " where {filter}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A shared cache backing a run cache, with the fingerprints of the tables whose
+/// metrics are shared.
+#[derive(Debug)]
+struct SharedBacking {
+    cache: SharedStatsCache,
+    fingerprints: HashMap<String, String>,
+}
+
+#[derive(Debug, Default)]
+struct MetricCacheInner {
+    values: Mutex<HashMap<MetricKey, u64>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    shared: Option<SharedBacking>,
+}
+
+/// Run-scoped memoization of table metrics.
+///
+/// Cloning the cache shares the underlying values and statistics, so it can be
+/// used concurrently from several constraints. When two constraints miss the same
+/// metric at the same time both compute it and the first stored value is kept;
+/// the counts are deterministic, so the values agree.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::core::{MetricCache, MetricKey};
+/// use datafusion::prelude::*;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let ctx = SessionContext::new();
+/// ctx.sql("CREATE TABLE data AS VALUES (1), (2), (NULL)").await?.collect().await?;
+///
+/// let cache = MetricCache::new();
+/// let keys = [MetricKey::row_count("data"), MetricKey::null_count("data", "column1")];
+/// assert_eq!(cache.fetch(&ctx, &keys).await?, [3, 1]);
+/// assert_eq!(cache.fetch(&ctx, &keys[..1]).await?, [3]);
+///
+/// assert_eq!((cache.hits(), cache.misses()), (1, 2));
+/// # Ok(())
+/// # }
+/// # tokio::runtime::Runtime::new().unwrap().block_on(example()).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MetricCache {
+    inner: Arc<MetricCacheInner>,
+}
+
+impl MetricCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty cache backed by a shared cache for the unfiltered
+    /// metrics of the tables with the given fingerprints.
+    pub(crate) fn backed_by(
+        shared: SharedStatsCache,
+        fingerprints: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            inner: Arc::new(MetricCacheInner {
+                shared: Some(SharedBacking {
+                    cache: shared,
+                    fingerprints,
+                }),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Returns the cached value of a metric without computing it.
+    pub fn get(&self, key: &MetricKey) -> Option<u64> {
+        self.inner
+            .values
+            .lock()
+            .ok()
+            .and_then(|values| values.get(key).copied())
+    }
+
+    /// Returns the values of the given metrics, computing the missing ones.
+    ///
+    /// Missing metrics on the same table and filter are computed together in a
+    /// single query.
+    pub async fn fetch(&self, ctx: &SessionContext, keys: &[MetricKey]) -> Result<Vec<u64>> {
+        let mut values: Vec<Option<u64>> = keys
+            .iter()
+            .map(|key| self.get(key).or_else(|| self.get_shared(key)))
+            .collect();
+        let missing: Vec<MetricKey> = keys
+            .iter()
+            .zip(&values)
+            .filter(|(_, value)| value.is_none())
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        let hits = (keys.len() - missing.len()) as u64;
+        self.inner.hits.fetch_add(hits, Ordering::Relaxed);
+        self.inner
+            .misses
+            .fetch_add(missing.len() as u64, Ordering::Relaxed);
+
+        if !missing.is_empty() {
+            let computed = compute_metrics(ctx, &missing).await?;
+            for (key, value) in missing.iter().zip(&computed) {
+                if let Some((shared, fingerprint)) = self.shared_entry(key) {
+                    shared.insert(fingerprint, key, *value);
+                }
+            }
+            if let Ok(mut cached) = self.inner.values.lock() {
+                for (key, value) in missing.into_iter().zip(computed) {
+                    cached.entry(key).or_insert(value);
+                }
+                for (key, value) in keys.iter().zip(values.iter_mut()) {
+                    if value.is_none() {
+                        *value = cached.get(key).copied();
+                    }
+                }
+            }
+        }
+
+        values
+            .into_iter()
+            .zip(keys)
+            .map(|(value, key)| {
+                value.ok_or_else(|| TermError::Internal(format!("Metric {key} is unavailable")))
+            })
+            .collect()
+    }
+
+    /// Returns the shared cache and table fingerprint a metric is shared under.
+    ///
+    /// Filtered metrics are never shared: their filter may read other tables.
+    fn shared_entry(&self, key: &MetricKey) -> Option<(&SharedStatsCache, &str)> {
+        let shared = self.inner.shared.as_ref()?;
+        if key.filter().is_some() {
+            return None;
+        }
+        let fingerprint = shared.fingerprints.get(key.table())?;
+        Some((&shared.cache, fingerprint.as_str()))
+    }
+
+    /// Looks up a metric in the shared cache, keeping a hit for the rest of the run.
+    fn get_shared(&self, key: &MetricKey) -> Option<u64> {
+        let (shared, fingerprint) = self.shared_entry(key)?;
+        let value = shared.get(fingerprint, key)?;
+        if let Ok(mut values) = self.inner.values.lock() {
+            values.entry(key.clone()).or_insert(value);
+        }
+        Some(value)
+    }
+
+    /// Returns the number of metric requests answered from the cache, or from
+    /// the shared cache backing it.
+    pub fn hits(&self) -> u64 {
+        self.inner.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of metric requests that had to be computed.
+    pub fn misses(&self) -> u64 {
+        self.inner.misses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of cached metrics.
+    pub fn len(&self) -> usize {
+        self.inner
+            .values
+            .lock()
+            .map(|values| values.len())
+            .unwrap_or_default()
+    }
+
+    /// Returns true if no metric is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Returns the values of the given metrics using the cache of the current
+/// validation context.
+///
+/// Without a cache attached to the context the metrics are computed directly.
+/// Either way, the queries are recorded through
+/// [`collect_with_metrics`](super::collect_with_metrics).
+pub async fn fetch_metrics(ctx: &SessionContext, keys: &[MetricKey]) -> Result<Vec<u64>> {
+    match current_validation_context().metric_cache() {
+        Some(cache) => cache.fetch(ctx, keys).await,
+        None => compute_metrics(ctx, keys).await,
+    }
+}
+
+/// Builds the query computing metrics that share a table and filter, one column
+/// per metric in the order of `keys`.
+pub(crate) fn metrics_query(keys: &[&MetricKey]) -> Result<String> {
+    let first = keys
+        .first()
+        .ok_or_else(|| TermError::Internal("No metrics to compute".to_string()))?;
+    if keys
+        .iter()
+        .any(|key| key.table() != first.table() || key.filter() != first.filter())
+    {
+        return Err(TermError::Internal(
+            "Metrics of one query must share their table and filter".to_string(),
+        ));
+    }
+
+    let expressions = keys
+        .iter()
+        .enumerate()
+        .map(|(position, key)| Ok(format!("{} AS m{position}", key.expression()?)))
+        .collect::<Result<Vec<_>>>()?;
+    let mut sql = format!("SELECT {} FROM {}", expressions.join(", "), first.table());
+    if let Some(filter) = first.filter() {
+        sql.push_str(&format!(" WHERE {filter}"));
+    }
+    Ok(sql)
+}
+
+/// Computes metrics, one query per table and filter.
+async fn compute_metrics(ctx: &SessionContext, keys: &[MetricKey]) -> Result<Vec<u64>> {
+    let mut values = vec![0; keys.len()];
+    // Indices of the keys grouped by table and filter
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for (index, key) in keys.iter().enumerate() {
+        let same_source =
+            |other: &MetricKey| other.table() == key.table() && other.filter() == key.filter();
+        match groups.iter_mut().find(|group| same_source(&keys[group[0]])) {
+            Some(group) => group.push(index),
+            None => groups.push(vec![index]),
+        }
+    }
+
+    for indices in groups {
+        let group: Vec<&MetricKey> = indices.iter().map(|&index| &keys[index]).collect();
+        let sql = metrics_query(&group)?;
+        debug!(metric.query = %sql, metric.count = indices.len(), "Computing metrics");
+
+        let df = ctx.sql(&sql).await?;
+        let batches = collect_with_metrics(df).await?;
+        let batch = batches
+            .iter()
+            .find(|batch| batch.num_rows() > 0)
+            .ok_or_else(|| TermError::Internal("Metric query returned no rows".to_string()))?;
+
+        for (position, &index) in indices.iter().enumerate() {
+            let count = batch
+                .column(position)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .ok_or_else(|| {
+                    TermError::Internal(format!("Failed to extract metric {}", keys[index]))
+                })?
+                .value(0);
+            values[index] = count.max(0) as u64;
+        }
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{QueryMetricsCollector, ValidationContext, CURRENT_CONTEXT};
+
+    async fn context() -> SessionContext {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE TABLE data (id INT, name VARCHAR) AS VALUES \
+             (1, 'a'), (2, 'a'), (3, NULL), (3, 'b')",
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_compute_metrics() {
+        let ctx = context().await;
+        let keys = [
+            MetricKey::row_count("data"),
+            MetricKey::null_count("data", "name"),
+            MetricKey::distinct_count("data", ["id"]),
+            MetricKey::distinct_count("data", ["id", "name"]),
+            MetricKey::row_count("data").with_filter("id = 3"),
+        ];
+
+        let values = MetricCache::new().fetch(&ctx, &keys).await.unwrap();
+        assert_eq!(values, [4, 1, 3, 4, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_cache_hits_and_misses() {
+        let ctx = context().await;
+        let cache = MetricCache::new();
+        let collector = QueryMetricsCollector::new();
+        let validation_ctx = ValidationContext::new("data")
+            .with_performance_collector(collector.clone())
+            .with_metric_cache(cache.clone());
+
+        CURRENT_CONTEXT
+            .scope(validation_ctx, async {
+                let row_count = MetricKey::row_count("data");
+                let nulls = MetricKey::null_count("data", "name");
+                assert_eq!(
+                    fetch_metrics(&ctx, &[row_count.clone(), nulls.clone()])
+                        .await
+                        .unwrap(),
+                    [4, 1]
+                );
+                assert_eq!(fetch_metrics(&ctx, &[row_count]).await.unwrap(), [4]);
+                assert_eq!(fetch_metrics(&ctx, &[nulls]).await.unwrap(), [1]);
+            })
+            .await;
+
+        assert_eq!(cache.hits(), 2);
+        assert_eq!(cache.misses(), 2);
+        assert_eq!(cache.len(), 2);
+        // Both misses were answered by one query
+        assert_eq!(collector.snapshot().queries_executed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_fetches_agree() {
+        let ctx = context().await;
+        let cache = MetricCache::new();
+        let key = MetricKey::distinct_count("data", ["id"]);
+
+        let fetches = (0..4).map(|_| cache.fetch(&ctx, std::slice::from_ref(&key)));
+        for values in futures::future::join_all(fetches).await {
+            assert_eq!(values.unwrap(), [3]);
+        }
+        assert_eq!(cache.hits() + cache.misses(), 4);
+        assert_eq!(cache.get(&key), Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_not_cached() {
+        let ctx = context().await;
+        let cache = MetricCache::new();
+        let key = MetricKey::null_count("data", "missing");
+
+        assert!(cache.fetch(&ctx, std::slice::from_ref(&key)).await.is_err());
+        assert!(cache.get(&key).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shared_backing_across_runs() {
+        let ctx = context().await;
+        let shared = SharedStatsCache::new();
+        let fingerprints = HashMap::from([("data".to_string(), "v1".to_string())]);
+        let keys = [
+            MetricKey::row_count("data"),
+            MetricKey::null_count("data", "name"),
+            MetricKey::row_count("data").with_filter("id = 3"),
+        ];
+
+        let first = MetricCache::backed_by(shared.clone(), fingerprints.clone());
+        assert_eq!(first.fetch(&ctx, &keys).await.unwrap(), [4, 1, 2]);
+
+        let second = MetricCache::backed_by(shared.clone(), fingerprints);
+        let collector = QueryMetricsCollector::new();
+        let validation_ctx = ValidationContext::new("data")
+            .with_performance_collector(collector.clone())
+            .with_metric_cache(second.clone());
+        CURRENT_CONTEXT
+            .scope(validation_ctx, async {
+                assert_eq!(fetch_metrics(&ctx, &keys[..2]).await.unwrap(), [4, 1]);
+            })
+            .await;
+
+        assert_eq!(second.hits(), 2);
+        assert_eq!(collector.snapshot().queries_executed, 0);
+        // The filtered row count is not shared
+        assert_eq!(shared.metrics().entries, 2);
+    }
+}
@@ -0,0 +1,385 @@
+//! Values that count as missing besides SQL NULL.
+//!
+//! Files exported from other systems often encode a missing value as an empty
+//! string, a placeholder such as `"N/A"`, or a numeric sentinel such as `-999`.
+//! A [`MissingValuePolicy`] lists those encodings so completeness checks count
+//! them together with NULLs, and reports how many rows each encoding accounts for.
+
+use std::fmt;
+
+use arrow::array::{Array, Int64Array};
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::collect_with_metrics;
+use crate::error::{Result, TermError};
+
+/// Encodings of a missing value that are not SQL NULL.
+///
+/// Sentinels are compared with the value cast to text, so string sentinels also
+/// match numeric columns (`"-999"`), and numeric sentinels are compared with
+/// the value cast to a float, so they also match numbers stored as text.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::core::MissingValuePolicy;
+///
+/// let policy = MissingValuePolicy::default()
+///     .sentinel_strings(["N/A", "NULL"])
+///     .case_insensitive(true)
+///     .sentinel_numbers([-999.0])
+///     .empty_string_as_null(true);
+/// assert!(!policy.is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MissingValuePolicy {
+    /// Strings that stand for a missing value
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sentinel_strings: Vec<String>,
+    /// Whether string sentinels match regardless of case
+    #[serde(default)]
+    pub case_insensitive: bool,
+    /// Numbers that stand for a missing value
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sentinel_numbers: Vec<f64>,
+    /// Whether the empty string stands for a missing value
+    #[serde(default)]
+    pub empty_string_as_null: bool,
+}
+
+impl MissingValuePolicy {
+    /// Creates a policy under which only NULL is missing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds strings that stand for a missing value.
+    pub fn sentinel_strings<I, S>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.sentinel_strings
+            .extend(values.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets whether string sentinels match regardless of case.
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Adds numbers that stand for a missing value.
+    pub fn sentinel_numbers<I>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        self.sentinel_numbers.extend(values);
+        self
+    }
+
+    /// Sets whether the empty string stands for a missing value.
+    pub fn empty_string_as_null(mut self, empty_string_as_null: bool) -> Self {
+        self.empty_string_as_null = empty_string_as_null;
+        self
+    }
+
+    /// Returns whether only NULL counts as missing under this policy.
+    pub fn is_empty(&self) -> bool {
+        self.sentinel_strings.is_empty()
+            && self.sentinel_numbers.is_empty()
+            && !self.empty_string_as_null
+    }
+
+    /// Checks that every numeric sentinel is a finite number.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] for a NaN or infinite sentinel.
+    pub fn validate(&self) -> Result<()> {
+        match self
+            .sentinel_numbers
+            .iter()
+            .find(|value| !value.is_finite())
+        {
+            Some(value) => Err(TermError::Configuration(format!(
+                "Missing value sentinels must be finite numbers, got {value}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the causes of a missing value other than NULL, in the order
+    /// they are attributed.
+    fn causes(&self) -> Vec<MissingCause<'_>> {
+        let mut causes = Vec::new();
+        if self.empty_string_as_null {
+            causes.push(MissingCause::EmptyString);
+        }
+        causes.extend(
+            self.sentinel_strings
+                .iter()
+                .map(|value| MissingCause::String(value)),
+        );
+        causes.extend(
+            self.sentinel_numbers
+                .iter()
+                .map(|&value| MissingCause::Number(value)),
+        );
+        causes
+    }
+
+    /// Returns a predicate matching the non-NULL values of `column` this policy
+    /// treats as missing, or `None` if the policy is empty.
+    pub(crate) fn predicate(&self, column: &str) -> Option<String> {
+        let predicates: Vec<String> = self
+            .causes()
+            .iter()
+            .map(|cause| cause.predicate(column, self.case_insensitive))
+            .collect();
+        (!predicates.is_empty()).then(|| format!("({})", predicates.join(" OR ")))
+    }
+
+    /// Returns the query counting the rows of `table_name` and the missing
+    /// values of `column` by cause.
+    pub(crate) fn counts_query(&self, table_name: &str, column: &str) -> String {
+        let branches: String = self
+            .causes()
+            .iter()
+            .enumerate()
+            .map(|(index, cause)| {
+                format!(
+                    " WHEN {} THEN {}",
+                    cause.predicate(column, self.case_insensitive),
+                    index + 1
+                )
+            })
+            .collect();
+        format!(
+            "SELECT cause, COUNT(*) AS row_count FROM (SELECT CASE WHEN {column} IS NULL THEN 0{branches} ELSE -1 END AS cause FROM {table_name}) GROUP BY cause"
+        )
+    }
+
+    /// Counts the rows of `table_name` and the missing values of `column`.
+    ///
+    /// A value matching several causes is attributed to the first of them:
+    /// NULL, the empty string, then the string and numeric sentinels in the
+    /// order they were added.
+    pub(crate) async fn count(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        column: &str,
+    ) -> Result<MissingValueCounts> {
+        self.validate()?;
+        let causes = self.causes();
+        let batches =
+            collect_with_metrics(ctx.sql(&self.counts_query(table_name, column)).await?).await?;
+
+        let mut counts = MissingValueCounts {
+            total: 0,
+            by_cause: std::iter::once("null".to_string())
+                .chain(causes.iter().map(ToString::to_string))
+                .map(|cause| (cause, 0))
+                .collect(),
+        };
+        for batch in &batches {
+            let (Some(cause), Some(rows)) = (
+                batch.column(0).as_any().downcast_ref::<Int64Array>(),
+                batch.column(1).as_any().downcast_ref::<Int64Array>(),
+            ) else {
+                return Err(TermError::Internal(
+                    "Failed to extract missing value counts".to_string(),
+                ));
+            };
+            for row in 0..batch.num_rows() {
+                let rows_with_cause = rows.value(row).max(0) as u64;
+                counts.total += rows_with_cause;
+                if cause.is_valid(row) {
+                    if let Some((_, count)) = usize::try_from(cause.value(row))
+                        .ok()
+                        .and_then(|index| counts.by_cause.get_mut(index))
+                    {
+                        *count += rows_with_cause;
+                    }
+                }
+            }
+        }
+        Ok(counts)
+    }
+}
+
+impl fmt::Display for MissingValuePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NULL")?;
+        for cause in self.causes() {
+            match cause {
+                MissingCause::EmptyString => write!(f, ", empty string")?,
+                MissingCause::String(value) => write!(f, ", '{value}'")?,
+                MissingCause::Number(value) => write!(f, ", {value}")?,
+            }
+        }
+        if self.case_insensitive && !self.sentinel_strings.is_empty() {
+            write!(f, " (case-insensitive)")?;
+        }
+        Ok(())
+    }
+}
+
+/// An encoding of a missing value other than NULL.
+#[derive(Debug, Clone, Copy)]
+enum MissingCause<'a> {
+    EmptyString,
+    String(&'a str),
+    Number(f64),
+}
+
+impl MissingCause<'_> {
+    fn predicate(&self, column: &str, case_insensitive: bool) -> String {
+        match self {
+            Self::EmptyString => format!("CAST({column} AS VARCHAR) = ''"),
+            Self::String(value) if case_insensitive => format!(
+                "LOWER(CAST({column} AS VARCHAR)) = '{}'",
+                value.to_lowercase().replace('\'', "''")
+            ),
+            Self::String(value) => format!(
+                "CAST({column} AS VARCHAR) = '{}'",
+                value.replace('\'', "''")
+            ),
+            Self::Number(value) => format!("TRY_CAST({column} AS DOUBLE) = {value:?}"),
+        }
+    }
+}
+
+impl fmt::Display for MissingCause<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyString => write!(f, "empty_string"),
+            Self::String(value) => write!(f, "sentinel:{value}"),
+            Self::Number(value) => write!(f, "sentinel:{value}"),
+        }
+    }
+}
+
+/// Row count of a table and missing value counts of one of its columns.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MissingValueCounts {
+    /// Number of rows
+    pub(crate) total: u64,
+    /// Missing values by cause, starting with NULL
+    pub(crate) by_cause: Vec<(String, u64)>,
+}
+
+impl MissingValueCounts {
+    /// Returns the number of missing values of any cause.
+    pub(crate) fn missing(&self) -> u64 {
+        self.by_cause.iter().map(|(_, count)| count).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Float64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::datasource::MemTable;
+    use std::sync::Arc;
+
+    fn context() -> SessionContext {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("label", DataType::Utf8, true),
+            Field::new("age", DataType::Float64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec![
+                    Some("a"),
+                    None,
+                    Some(""),
+                    Some("N/A"),
+                    Some("n/a"),
+                    Some("-999"),
+                ])),
+                Arc::new(Float64Array::from(vec![
+                    Some(31.0),
+                    Some(-999.0),
+                    None,
+                    Some(40.0),
+                    Some(-999.0),
+                    Some(27.0),
+                ])),
+            ],
+        )
+        .unwrap();
+        let ctx = SessionContext::new();
+        ctx.register_table(
+            "data",
+            Arc::new(MemTable::try_new(schema, vec![vec![batch]]).unwrap()),
+        )
+        .unwrap();
+        ctx
+    }
+
+    fn policy() -> MissingValuePolicy {
+        MissingValuePolicy::default()
+            .sentinel_strings(["N/A"])
+            .sentinel_numbers([-999.0])
+            .empty_string_as_null(true)
+    }
+
+    #[tokio::test]
+    async fn test_counts_by_cause() {
+        let ctx = context();
+
+        let counts = policy().count(&ctx, "data", "label").await.unwrap();
+        assert_eq!(counts.total, 6);
+        assert_eq!(
+            counts.by_cause,
+            vec![
+                ("null".to_string(), 1),
+                ("empty_string".to_string(), 1),
+                ("sentinel:N/A".to_string(), 1),
+                ("sentinel:-999".to_string(), 1),
+            ]
+        );
+        assert_eq!(counts.missing(), 4);
+
+        let counts = policy()
+            .case_insensitive(true)
+            .count(&ctx, "data", "label")
+            .await
+            .unwrap();
+        assert_eq!(counts.by_cause[2], ("sentinel:N/A".to_string(), 2));
+
+        let counts = policy().count(&ctx, "data", "age").await.unwrap();
+        assert_eq!(counts.missing(), 3);
+        assert_eq!(counts.by_cause[3], ("sentinel:-999".to_string(), 2));
+    }
+
+    #[test]
+    fn test_predicate_and_validation() {
+        assert!(MissingValuePolicy::new().is_empty());
+        assert_eq!(MissingValuePolicy::new().predicate("c"), None);
+
+        let predicate = MissingValuePolicy::new()
+            .sentinel_strings(["it's"])
+            .predicate("c")
+            .unwrap();
+        assert_eq!(predicate, "(CAST(c AS VARCHAR) = 'it''s')");
+
+        assert_eq!(
+            policy().case_insensitive(true).to_string(),
+            "NULL, empty string, 'N/A', -999 (case-insensitive)"
+        );
+        assert!(policy().validate().is_ok());
+        let err = MissingValuePolicy::new()
+            .sentinel_numbers([f64::NAN])
+            .validate()
+            .unwrap_err();
+        assert!(err.to_string().contains("finite"));
+    }
+}
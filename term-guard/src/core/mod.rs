@@ -12,6 +12,7 @@
 //! - **[`Constraint`]**: Individual validation rules (implemented in the `constraints` module)
 //! - **[`Level`]**: Severity levels for checks (Error, Warning, Info)
 //! - **[`ValidationResult`]**: Results from running a validation suite
+//! - **[`Evaluator`]**: Runs individual constraints without a suite
 //!
 //! ## Architecture
 //!
@@ -102,38 +103,167 @@
 //! - **Failure**: The constraint failed
 //! - **Skipped**: The constraint was skipped (e.g., no data)
 //!
+//! Constraints evaluated on a table without rows are skipped with the message
+//! "table is empty" unless their subject is defined for zero rows, such as the
+//! row count. See [`EmptyTablePolicy`] for the full list and for reporting them
+//! as failures instead.
+//!
 //! ## Performance Considerations
 //!
 //! - Constraints within a check may be optimized to run in a single query
 //! - Use the `with_optimizer(true)` option on ValidationSuite for best performance
 //! - Group related constraints in the same Check when possible
 
+mod anomaly_check;
+mod binding;
 mod check;
+mod check_filter;
+mod check_status;
+mod collection;
+mod column_mapping;
+mod column_path;
+mod column_selector;
+mod column_statistics;
+mod config_report;
 mod constraint;
 mod context;
 mod debug_context;
+mod derived_table;
+mod dimension;
+mod empty_table;
+mod evaluator;
+pub mod extensions;
 mod fluent_builder;
+mod hooks;
+mod known_issue;
 mod level;
+mod lineage;
 mod logical;
+mod lookup;
+mod memory_strategy;
+mod metadata_statistics;
+mod metric_cache;
+mod missing_values;
 mod multi_source;
+mod parameters;
+mod performance;
+mod portfolio;
+mod preflight;
+mod preview;
+mod qualified_table;
+mod quarantine;
+mod ratio_query;
+mod redaction;
+mod regex_cache;
+mod report_delta;
 mod result;
+mod result_cache;
+mod run_options;
+mod scheduling;
+mod session_udfs;
 mod suite;
+mod suite_version;
+mod threshold;
+mod timeline;
 mod unified;
 pub mod validation_context;
 
 pub mod builder_extensions;
 
+pub use anomaly_check::AnomalyCheckOptions;
+pub use binding::{BindingMismatch, BoundSuite, BoundTable, SchemaDifference, TableMismatch};
 pub use check::{Check, CheckBuilder};
-pub use constraint::{Constraint, ConstraintMetadata, ConstraintResult, ConstraintStatus};
+pub use check_filter::CheckFilter;
+pub(crate) use check_status::check_statuses;
+pub use check_status::CheckStatus;
+pub use collection::{
+    collect_limited, CollectedBatches, CollectionLimits, CollectionSummary, COLLECTION_ROWS_DETAIL,
+    COLLECTION_SPILLED_ROWS_DETAIL, COLLECTION_TRUNCATED_DETAIL,
+};
+pub(crate) use column_path::column_expression;
+pub use column_path::ColumnPath;
+pub use column_selector::ColumnSelector;
+pub use column_statistics::{ColumnStatistics, PrecomputedStatistics};
+pub use config_report::{ConfigIssue, ConfigReport, ConfigSeverity};
+pub use constraint::{
+    constraint_id, Constraint, ConstraintMetadata, ConstraintResult, ConstraintStatus,
+};
+pub(crate) use constraint::{describe_list, describe_percent, describe_rows};
 pub use context::{TermContext, TermContextConfig};
 pub use debug_context::{
     DebugContext, DebugInfo, DebugLevel, DebugSummary, ErrorReport, ValidationResultDebugExt,
 };
+pub use dimension::{DimensionSummary, QualityDimension};
+pub(crate) use empty_table::{is_table_empty, skipped_without_values};
+pub use empty_table::{EmptyTablePolicy, EMPTY_TABLE_MESSAGE};
+pub use evaluator::Evaluator;
+pub use extensions::Extensions;
 pub use fluent_builder::{CheckMultiTableExt, MultiTableCheck};
+pub(crate) use hooks::HookChain;
+pub use hooks::{ConstraintInfo, HookDecision, LoggingHook, ValidationHook};
+pub use known_issue::KnownIssue;
 pub use level::Level;
+pub use lineage::{LineageMap, LINEAGE_ALL_COLUMNS};
 pub use logical::{ColumnSpec, ConstraintOptionsBuilder, LogicalOperator, LogicalResult};
-pub use multi_source::{CacheStats, MultiSourceValidator};
-pub use result::{ValidationIssue, ValidationMetrics, ValidationReport, ValidationResult};
-pub use suite::{ValidationSuite, ValidationSuiteBuilder};
+pub(crate) use lookup::{lookup_issues, RegisteredLookups, SuiteLookup};
+pub use lookup::{LookupTable, DEFAULT_MAX_LOOKUP_BYTES};
+pub(crate) use memory_strategy::{
+    partition_key_sql, partition_sql, register_partition_function, BoundedFrequencies,
+};
+pub use memory_strategy::{MemoryStrategy, MEMORY_APPROXIMATE_DETAIL, MEMORY_PARTITIONS_DETAIL};
+pub use metadata_statistics::{
+    MetadataColumnStatistics, MetadataOnlyMode, MetadataStatistics, PARQUET_METADATA_EVIDENCE,
+};
+pub(crate) use metric_cache::metrics_query;
+pub use metric_cache::{fetch_metrics, MetricCache, MetricKey, MetricKind};
+pub use missing_values::MissingValuePolicy;
+pub use multi_source::{
+    CacheStats, MultiSourceValidator, SecondaryTransfer, TableAggregate, TableAggregateFunction,
+    TransferBudget, TransferStrategy,
+};
+pub use parameters::{ParamValue, ParameterBag, ParameterRef};
+pub use performance::{
+    collect_with_metrics, PerformanceMetrics, QueryMetrics, QueryMetricsCollector,
+};
+pub use portfolio::{
+    ConstraintChange, ConstraintFailures, GroupSummary, PortfolioSummary, ReportAggregator,
+    CHECK_TAG_PREFIX, REPORT_METADATA_KEY,
+};
+pub use preflight::{PreflightReport, SourceCheck};
+pub use preview::PreviewSpec;
+pub(crate) use qualified_table::parse_qualified_column;
+pub use qualified_table::QualifiedTable;
+pub use quarantine::{
+    QuarantineSink, QuarantineSummary, QuarantineTarget, DEFAULT_MAX_QUARANTINE_ROWS,
+    DEFAULT_REASON_COLUMN,
+};
+pub use ratio_query::{NullPolicy, RatioCounts, RatioQueryBuilder};
+pub use redaction::{RedactionPolicy, RedactionStrategy, REDACTED};
+pub use regex_cache::{RegexCache, RegexLimits};
+pub use report_delta::{ConstraintDelta, DeltaKind, ReportDelta};
+pub use result::{
+    CachedConstraint, ConstraintOutcome, ExcludedCheck, HookSkippedConstraint, MetadataConstraint,
+    PreviewSkippedConstraint, PreviewSummary, ProfiledConstraint, QuarantinedRows, RecentFlakiness,
+    ValidationIssue, ValidationMetrics, ValidationReport, ValidationResult, REPORT_SCHEMA_VERSION,
+};
+pub use result_cache::ResultCache;
+pub(crate) use result_cache::{hash_parts, source_fingerprint};
+pub use run_options::RunOptions;
+pub(crate) use scheduling::Scheduler;
+pub use scheduling::{
+    schedule, CheckComparator, CheckEstimate, ExecutionHistory, ExecutionOrder, ExecutionSchedule,
+    DEFAULT_CONSTRAINT_DURATION_MS,
+};
+pub(crate) use session_udfs::{udf_issues, RegisteredUdfs, SuiteUdf};
+pub use suite::{CheckConflictPolicy, ValidationSuite, ValidationSuiteBuilder};
+pub(crate) use suite_version::stamp_suite_version;
+pub use suite_version::{
+    diff_suites, ChangeEntry, CheckDiff, ConstraintDiff, DiffKind, ParameterChange,
+    SemanticVersion, SuiteDiff, SuiteVersion, SUITE_VERSION_TAG,
+};
+pub(crate) use threshold::AdjustedThreshold;
+pub use threshold::{IntoThreshold, Threshold, ThresholdAdjustment};
+pub(crate) use timeline::TimelineRecorder;
+pub use timeline::{Timeline, TimelinePhase, TimelineSpan};
 pub use unified::{ConstraintOptions, UnifiedCompletenessBase, UnifiedConstraint};
 pub use validation_context::{current_validation_context, ValidationContext, CURRENT_CONTEXT};
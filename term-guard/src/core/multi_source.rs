@@ -12,6 +12,29 @@
 //! - Caching of intermediate results
 //! - Performance monitoring and telemetry
 //!
+//! # Secondary contexts
+//!
+//! Tables that live in another [`SessionContext`] (for example one configured with
+//! different object stores or credentials) are registered with
+//! [`MultiSourceValidator::with_secondary_context`] and referenced as
+//! `context::table.column`. Before a suite runs, every referenced secondary table is
+//! brought into the primary context under the `context` schema, using one of two
+//! strategies:
+//!
+//! | Constraint | Strategy |
+//! |------------|----------|
+//! | `cross_table_sum` without `group_by` | [`TransferStrategy::Aggregates`] |
+//! | `cross_table_sum` with `group_by` | [`TransferStrategy::Rows`] |
+//! | `foreign_key` | [`TransferStrategy::Rows`] |
+//! | Any other constraint | [`TransferStrategy::Rows`] |
+//!
+//! Aggregates are evaluated inside the secondary context and only their one-row
+//! result is transferred. Constraints opt in through
+//! [`Constraint::table_aggregates`](crate::core::Constraint::table_aggregates). Row
+//! transfers copy the whole table into a `MemTable` and fail once the
+//! [`TransferBudget`] is exceeded, so register the smaller side of a comparison as
+//! the secondary table.
+//!
 //! # Examples
 //!
 //! ```rust
@@ -41,13 +64,18 @@
 //! # }
 //! ```
 
-use crate::core::{ValidationResult, ValidationSuite};
+use crate::core::{QualifiedTable, ValidationResult, ValidationSuite};
 use crate::error::{Result, TermError};
+use crate::security::SqlSecurity;
 use crate::sources::DataSource;
 use crate::telemetry::TermTelemetry;
 use arrow::record_batch::RecordBatch;
+use datafusion::datasource::MemTable;
 use datafusion::prelude::*;
-use std::collections::HashMap;
+use datafusion::sql::TableReference;
+use futures::StreamExt;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::sync::Arc;
 use std::time::Instant;
 use tracing::{debug, info, instrument, span, Level};
@@ -71,6 +99,10 @@ pub struct MultiSourceValidator {
     max_cache_size: usize,
     /// Current cache size in bytes
     current_cache_size: usize,
+    /// Secondary session contexts by name
+    secondary_contexts: HashMap<String, SessionContext>,
+    /// Limits for copying secondary tables into the primary context
+    transfer_budget: TransferBudget,
 }
 
 /// Cached query result with metadata
@@ -100,6 +132,8 @@ impl MultiSourceValidator {
             enable_caching: true,
             max_cache_size: 100 * 1024 * 1024, // 100MB default
             current_cache_size: 0,
+            secondary_contexts: HashMap::new(),
+            transfer_budget: TransferBudget::default(),
         }
     }
 
@@ -121,6 +155,44 @@ impl MultiSourceValidator {
         self
     }
 
+    /// Register a secondary session context.
+    ///
+    /// Constraints reference its tables as `name::table.column`. The tables are
+    /// brought into the primary context under the `name` schema before each suite
+    /// run, see [`prepare_secondary_tables`](Self::prepare_secondary_tables).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use term_guard::core::{Check, MultiSourceValidator, ValidationSuite};
+    /// # use datafusion::prelude::*;
+    /// # async fn example(lakehouse: SessionContext, postgres: SessionContext) -> Result<(), Box<dyn std::error::Error>> {
+    /// let validator = MultiSourceValidator::with_context(lakehouse)
+    ///     .with_secondary_context("pg", postgres);
+    ///
+    /// let suite = ValidationSuite::builder("reconciliation")
+    ///     .check(
+    ///         Check::builder("payments_match")
+    ///             .cross_table_sum("orders.total", "pg::payments.amount")
+    ///             .build()
+    ///     )
+    ///     .build();
+    ///
+    /// let results = validator.run_suite(&suite).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_secondary_context(mut self, name: impl Into<String>, ctx: SessionContext) -> Self {
+        self.secondary_contexts.insert(name.into(), ctx);
+        self
+    }
+
+    /// Set the limits for copying secondary tables into the primary context.
+    pub fn with_transfer_budget(mut self, budget: TransferBudget) -> Self {
+        self.transfer_budget = budget;
+        self
+    }
+
     /// Add a data source to the validator.
     ///
     /// # Arguments
@@ -184,6 +256,222 @@ impl MultiSourceValidator {
         self.sources.keys().cloned().collect()
     }
 
+    /// List all registered secondary contexts.
+    pub fn list_secondary_contexts(&self) -> Vec<String> {
+        self.secondary_contexts.keys().cloned().collect()
+    }
+
+    /// Bring the secondary tables referenced by `suite` into the primary context.
+    ///
+    /// A table is transferred as aggregates when every constraint that references it
+    /// reports compatible [`table_aggregates`](crate::core::Constraint::table_aggregates),
+    /// and as rows otherwise. Tables are re-fetched on every call so each run sees
+    /// current data. [`run_suite`](Self::run_suite) calls this automatically.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a secondary table cannot be read or a row transfer exceeds the
+    /// [`TransferBudget`].
+    #[instrument(skip(self, suite), fields(suite_name = %suite.name()))]
+    pub async fn prepare_secondary_tables(
+        &self,
+        suite: &ValidationSuite,
+    ) -> Result<Vec<SecondaryTransfer>> {
+        if self.secondary_contexts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut transfers = Vec::new();
+        for ((context, table), aggregates) in self.secondary_table_requirements(suite) {
+            let secondary = &self.secondary_contexts[&context];
+            let transfer = match aggregates.filter(|columns| !columns.is_empty()) {
+                Some(columns) => {
+                    self.transfer_aggregates(secondary, &context, &table, &columns)
+                        .await?
+                }
+                None => self.transfer_rows(secondary, &context, &table).await?,
+            };
+            info!(
+                "Transferred secondary table {}::{} as {} ({} rows, {} bytes)",
+                context, table, transfer.strategy, transfer.rows, transfer.bytes
+            );
+            transfers.push(transfer);
+        }
+
+        Ok(transfers)
+    }
+
+    /// Collects the secondary tables a suite references, with the aggregates needed
+    /// from each or `None` when the rows are needed.
+    #[allow(clippy::type_complexity)]
+    fn secondary_table_requirements(
+        &self,
+        suite: &ValidationSuite,
+    ) -> BTreeMap<(String, String), Option<BTreeMap<String, TableAggregateFunction>>> {
+        let mut requirements = BTreeMap::new();
+
+        for constraint in suite.checks().iter().flat_map(|check| check.constraints()) {
+            let aggregates = constraint.table_aggregates();
+            for name in constraint.referenced_tables() {
+                let Some((context, table)) = self.secondary_table(&name) else {
+                    continue;
+                };
+                let columns = aggregates.as_ref().map(|aggregates| {
+                    aggregates
+                        .iter()
+                        .filter(|aggregate| {
+                            self.secondary_table(&aggregate.table).as_ref()
+                                == Some(&(context.clone(), table.clone()))
+                        })
+                        .map(|aggregate| (aggregate.column.clone(), aggregate.function))
+                        .collect::<Vec<_>>()
+                });
+
+                let entry = requirements
+                    .entry((context, table))
+                    .or_insert_with(|| Some(BTreeMap::new()));
+                // The same column cannot stand in for two different aggregates
+                *entry = match (entry.take(), columns) {
+                    (Some(mut existing), Some(columns)) => columns
+                        .into_iter()
+                        .all(|(column, function)| {
+                            *existing.entry(column).or_insert(function) == function
+                        })
+                        .then_some(existing),
+                    _ => None,
+                };
+            }
+        }
+
+        requirements
+    }
+
+    /// Returns the secondary context and table a table reference points to.
+    fn secondary_table(&self, name: &str) -> Option<(String, String)> {
+        let table = QualifiedTable::parse(name).ok()?;
+        let context = table.schema().filter(|schema| {
+            table.catalog().is_none() && self.secondary_contexts.contains_key(*schema)
+        })?;
+        Some((context.to_string(), table.table().to_string()))
+    }
+
+    /// Copies a secondary table into the primary context within the transfer budget.
+    async fn transfer_rows(
+        &self,
+        secondary: &SessionContext,
+        context: &str,
+        table: &str,
+    ) -> Result<SecondaryTransfer> {
+        let sql = format!("SELECT * FROM {}", SqlSecurity::escape_identifier(table)?);
+        let df = secondary.sql(&sql).await.map_err(|e| {
+            TermError::data_source(
+                "multi_source",
+                format!("Failed to read secondary table '{context}::{table}': {e}"),
+            )
+        })?;
+        let schema = df.schema().inner().clone();
+        let mut stream = df.execute_stream().await?;
+
+        let budget = self.transfer_budget;
+        let (mut batches, mut rows, mut bytes) = (Vec::new(), 0, 0);
+        while let Some(batch) = stream.next().await {
+            let batch = batch?;
+            rows += batch.num_rows();
+            bytes += batch.get_array_memory_size();
+            if rows > budget.max_rows || bytes > budget.max_bytes {
+                return Err(TermError::data_source(
+                    "multi_source",
+                    format!(
+                        "Secondary table '{context}::{table}' exceeds the transfer budget of {} rows and {} bytes. \
+                         Raise it with MultiSourceValidator::with_transfer_budget, register the larger table in the \
+                         primary context instead, or use constraints that only need aggregates \
+                         (e.g. cross_table_sum without group_by)",
+                        budget.max_rows, budget.max_bytes
+                    ),
+                ));
+            }
+            batches.push(batch);
+        }
+
+        self.register_secondary_table(context, table, MemTable::try_new(schema, vec![batches])?)
+            .await?;
+        Ok(SecondaryTransfer {
+            context: context.to_string(),
+            table: table.to_string(),
+            strategy: TransferStrategy::Rows,
+            rows,
+            bytes,
+        })
+    }
+
+    /// Evaluates the needed aggregates inside the secondary context and registers
+    /// their one-row result under the original column names.
+    async fn transfer_aggregates(
+        &self,
+        secondary: &SessionContext,
+        context: &str,
+        table: &str,
+        columns: &BTreeMap<String, TableAggregateFunction>,
+    ) -> Result<SecondaryTransfer> {
+        let projections = columns
+            .iter()
+            .map(|(column, function)| {
+                let column = SqlSecurity::escape_identifier(column)?;
+                Ok(format!("{}({column}) AS {column}", function.sql_name()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let sql = format!(
+            "SELECT {} FROM {}",
+            projections.join(", "),
+            SqlSecurity::escape_identifier(table)?
+        );
+
+        let df = secondary.sql(&sql).await.map_err(|e| {
+            TermError::data_source(
+                "multi_source",
+                format!("Failed to aggregate secondary table '{context}::{table}': {e}"),
+            )
+        })?;
+        let schema = df.schema().inner().clone();
+        let batches = df.collect().await?;
+        let rows = batches.iter().map(|batch| batch.num_rows()).sum();
+        let bytes = batches
+            .iter()
+            .map(|batch| batch.get_array_memory_size())
+            .sum();
+
+        self.register_secondary_table(context, table, MemTable::try_new(schema, vec![batches])?)
+            .await?;
+        Ok(SecondaryTransfer {
+            context: context.to_string(),
+            table: table.to_string(),
+            strategy: TransferStrategy::Aggregates,
+            rows,
+            bytes,
+        })
+    }
+
+    /// Registers a transferred table as `context.table` in the primary context,
+    /// replacing the result of an earlier transfer.
+    async fn register_secondary_table(
+        &self,
+        context: &str,
+        table: &str,
+        data: MemTable,
+    ) -> Result<()> {
+        self.ctx
+            .sql(&format!(
+                "CREATE SCHEMA IF NOT EXISTS {}",
+                SqlSecurity::escape_identifier(context)?
+            ))
+            .await?;
+
+        let reference = TableReference::partial(context, table);
+        self.ctx.deregister_table(reference.clone())?;
+        self.ctx.register_table(reference, Arc::new(data))?;
+        Ok(())
+    }
+
     /// Run a validation suite across registered data sources.
     ///
     /// # Arguments
@@ -209,15 +497,18 @@ impl MultiSourceValidator {
             self.cleanup_cache();
         }
 
+        self.prepare_secondary_tables(suite).await?;
+
         // Run the suite with our context
         let result = suite.run(&self.ctx).await?;
 
         match &result {
             ValidationResult::Success { report, .. } => {
                 info!(
-                    "Validation suite '{}' succeeded: {} checks passed",
+                    "Validation suite '{}' succeeded: {}/{} checks passed",
                     suite.name(),
-                    report.metrics.total_checks
+                    report.metrics.passed_checks,
+                    report.metrics.rated_checks()
                 );
             }
             ValidationResult::Failure { report } => {
@@ -346,6 +637,110 @@ impl Default for MultiSourceValidator {
     }
 }
 
+/// Limits for copying a secondary table into the primary context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferBudget {
+    /// Maximum number of rows copied per table
+    pub max_rows: usize,
+    /// Maximum in-memory size in bytes copied per table
+    pub max_bytes: usize,
+}
+
+impl Default for TransferBudget {
+    fn default() -> Self {
+        Self {
+            max_rows: 1_000_000,
+            max_bytes: 256 * 1024 * 1024, // 256MB default
+        }
+    }
+}
+
+/// How a secondary table was brought into the primary context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferStrategy {
+    /// Every row was copied into a `MemTable`
+    Rows,
+    /// Only the aggregates the constraints need were evaluated and copied
+    Aggregates,
+}
+
+impl fmt::Display for TransferStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferStrategy::Rows => write!(f, "rows"),
+            TransferStrategy::Aggregates => write!(f, "aggregates"),
+        }
+    }
+}
+
+/// A secondary table transferred by [`MultiSourceValidator::prepare_secondary_tables`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecondaryTransfer {
+    /// Name of the secondary context
+    pub context: String,
+    /// Name of the table in the secondary context
+    pub table: String,
+    /// Strategy used for the transfer
+    pub strategy: TransferStrategy,
+    /// Number of rows transferred
+    pub rows: usize,
+    /// In-memory size of the transferred batches in bytes
+    pub bytes: usize,
+}
+
+/// An aggregate a constraint reads from one of its referenced tables.
+///
+/// See [`Constraint::table_aggregates`](crate::core::Constraint::table_aggregates).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TableAggregate {
+    /// Table reference as returned by `referenced_tables`
+    pub table: String,
+    /// Column the aggregate is computed over
+    pub column: String,
+    /// Aggregate function
+    pub function: TableAggregateFunction,
+}
+
+impl TableAggregate {
+    /// Create a new table aggregate.
+    pub fn new(
+        table: impl Into<String>,
+        column: impl Into<String>,
+        function: TableAggregateFunction,
+    ) -> Self {
+        Self {
+            table: table.into(),
+            column: column.into(),
+            function,
+        }
+    }
+}
+
+/// Aggregate functions whose result can replace a table's rows.
+///
+/// Re-applying any of these to its own one-row result yields the same value, so a
+/// constraint that aggregates a transferred result computes what it would on the
+/// full table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TableAggregateFunction {
+    /// `SUM(column)`
+    Sum,
+    /// `MIN(column)`
+    Min,
+    /// `MAX(column)`
+    Max,
+}
+
+impl TableAggregateFunction {
+    fn sql_name(&self) -> &'static str {
+        match self {
+            TableAggregateFunction::Sum => "SUM",
+            TableAggregateFunction::Min => "MIN",
+            TableAggregateFunction::Max => "MAX",
+        }
+    }
+}
+
 /// Cache statistics for monitoring.
 #[derive(Debug, Clone)]
 pub struct CacheStats {
@@ -362,6 +757,7 @@ pub struct CacheStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::Check;
     use crate::sources::CsvSource;
     use std::io::Write;
     use tempfile::NamedTempFile;
@@ -435,6 +831,105 @@ mod tests {
         assert_eq!(validator.max_cache_size, 1024 * 1024);
     }
 
+    async fn create_secondary_context() -> Result<SessionContext> {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE TABLE payments (order_id BIGINT, amount DOUBLE) AS VALUES \
+             (1, 100.0), (2, 250.0), (3, 75.5)",
+        )
+        .await?;
+        Ok(ctx)
+    }
+
+    async fn create_primary_context() -> Result<SessionContext> {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE TABLE orders (id BIGINT, total DOUBLE) AS VALUES \
+             (1, 100.0), (2, 250.0), (3, 75.5)",
+        )
+        .await?;
+        Ok(ctx)
+    }
+
+    fn suite(check: Check) -> ValidationSuite {
+        ValidationSuite::builder("cross_context")
+            .check(check)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_cross_table_sum_transfers_aggregates() -> Result<()> {
+        let validator = MultiSourceValidator::with_context(create_primary_context().await?)
+            .with_secondary_context("pg", create_secondary_context().await?);
+        let suite = suite(
+            Check::builder("sums")
+                .cross_table_sum("orders.total", "pg::payments.amount")
+                .build(),
+        );
+
+        let transfers = validator.prepare_secondary_tables(&suite).await?;
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].context, "pg");
+        assert_eq!(transfers[0].table, "payments");
+        assert_eq!(transfers[0].strategy, TransferStrategy::Aggregates);
+        assert_eq!(transfers[0].rows, 1);
+
+        assert!(validator.run_suite(&suite).await?.is_success());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_foreign_key_transfers_rows() -> Result<()> {
+        let validator = MultiSourceValidator::with_context(create_primary_context().await?)
+            .with_secondary_context("pg", create_secondary_context().await?);
+        let suite = suite(
+            Check::builder("references")
+                .foreign_key("pg::payments.order_id", "orders.id")
+                .build(),
+        );
+
+        let transfers = validator.prepare_secondary_tables(&suite).await?;
+        assert_eq!(transfers[0].strategy, TransferStrategy::Rows);
+        assert_eq!(transfers[0].rows, 3);
+
+        assert!(validator.run_suite(&suite).await?.is_success());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_row_transfer_over_budget_fails_with_guidance() -> Result<()> {
+        let validator = MultiSourceValidator::with_context(create_primary_context().await?)
+            .with_secondary_context("pg", create_secondary_context().await?)
+            .with_transfer_budget(TransferBudget {
+                max_rows: 2,
+                ..TransferBudget::default()
+            });
+        let suite = suite(
+            Check::builder("references")
+                .foreign_key("pg::payments.order_id", "orders.id")
+                .build(),
+        );
+
+        let err = validator.run_suite(&suite).await.unwrap_err().to_string();
+        assert!(err.contains("pg::payments"), "{err}");
+        assert!(err.contains("with_transfer_budget"), "{err}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unreferenced_secondary_tables_are_not_transferred() -> Result<()> {
+        let validator = MultiSourceValidator::with_context(create_primary_context().await?)
+            .with_secondary_context("pg", create_secondary_context().await?);
+        let suite = suite(
+            Check::builder("local")
+                .cross_table_sum("orders.total", "orders.total")
+                .build(),
+        );
+
+        assert!(validator.prepare_secondary_tables(&suite).await?.is_empty());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_cache_stats() {
         let validator = MultiSourceValidator::new();
@@ -0,0 +1,330 @@
+//! Named parameters resolved when a validation suite runs.
+//!
+//! Thresholds, assertion bounds and tolerances can refer to a named parameter
+//! instead of a literal value, so the same suite can be promoted between
+//! environments with different settings:
+//!
+//! - [`CompletenessOptions::threshold_param`](super::builder_extensions::CompletenessOptions::threshold_param)
+//!   and [`ConstraintOptions::with_threshold_param`](super::ConstraintOptions::with_threshold_param)
+//! - [`Assertion::greater_than_param`](crate::constraints::Assertion::greater_than_param)
+//!   and the other parameterized assertion constructors
+//! - [`CrossTableSumConstraint::tolerance_param`](crate::constraints::CrossTableSumConstraint::tolerance_param)
+//!
+//! Values come from a [`ParameterBag`] passed to
+//! [`RunOptions::with_parameters`](super::RunOptions::with_parameters),
+//! layered over defaults declared on the suite. Before any query is issued the
+//! suite checks that every referenced parameter is present and within the valid
+//! range of the constraint using it. Resolved values are listed in
+//! [`ValidationReport::parameters`](super::ValidationReport::parameters).
+//!
+//! # Example
+//!
+//! ```rust
+//! use term_guard::core::builder_extensions::CompletenessOptions;
+//! use term_guard::core::{Check, Level, ParameterBag, RunOptions, ValidationSuite};
+//! use datafusion::prelude::*;
+//!
+//! # async fn example(ctx: &SessionContext) -> Result<(), Box<dyn std::error::Error>> {
+//! // Defaults, e.g. read from a config file checked in next to the suite
+//! let defaults = ParameterBag::from_json_str(r#"{ "email_completeness": 0.9 }"#)?;
+//!
+//! let suite = ValidationSuite::builder("customers")
+//!     .parameter_defaults(defaults)
+//!     .check(
+//!         Check::builder("contact")
+//!             .level(Level::Error)
+//!             .completeness(
+//!                 "email",
+//!                 CompletenessOptions::threshold_param("email_completeness")
+//!                     .into_constraint_options(),
+//!             )
+//!             .build(),
+//!     )
+//!     .build();
+//!
+//! // Production requires a stricter threshold
+//! let prod = ParameterBag::new().with("email_completeness", 0.99);
+//! let result = suite
+//!     .run_with(ctx, &RunOptions::new().with_parameters(prod))
+//!     .await?;
+//! assert_eq!(result.report().parameters["email_completeness"], 0.99);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::current_validation_context;
+use crate::error::{Result, TermError};
+
+/// A set of named parameter values.
+///
+/// Serializes as a flat JSON object mapping names to numbers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ParameterBag {
+    values: BTreeMap<String, f64>,
+}
+
+impl ParameterBag {
+    /// Creates an empty parameter bag.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a parameter, replacing any previous value.
+    pub fn with(mut self, name: impl Into<String>, value: f64) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Sets a parameter, replacing any previous value.
+    pub fn set(&mut self, name: impl Into<String>, value: f64) {
+        self.values.insert(name.into(), value);
+    }
+
+    /// Returns the value of a parameter.
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.values.get(name).copied()
+    }
+
+    /// Returns true if the bag contains no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the parameters in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+
+    /// Returns a bag with the values of `overrides` layered over this one.
+    pub fn with_overrides(&self, overrides: &ParameterBag) -> ParameterBag {
+        let mut merged = self.clone();
+        merged
+            .values
+            .extend(overrides.values.iter().map(|(k, v)| (k.clone(), *v)));
+        merged
+    }
+
+    /// Reads a parameter bag from a JSON object of name/number pairs.
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| TermError::Configuration(format!("Invalid parameter bag: {e}")))
+    }
+
+    /// Serializes the parameter bag as a JSON object.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| TermError::Serialization(e.to_string()))
+    }
+
+    /// Returns the value of a referenced parameter, or why it is missing or
+    /// out of its valid range.
+    pub(crate) fn validate(&self, reference: &ParameterRef) -> std::result::Result<f64, String> {
+        let value = self.get(&reference.name).ok_or_else(|| {
+            format!(
+                "Missing value for parameter '{}' used by {}",
+                reference.name, reference.used_by
+            )
+        })?;
+        if !reference.contains(value) {
+            return Err(format!(
+                "Parameter '{}' = {value} is out of range for {}: expected {}",
+                reference.name,
+                reference.used_by,
+                reference.range_description()
+            ));
+        }
+        Ok(value)
+    }
+}
+
+impl<K: Into<String>> FromIterator<(K, f64)> for ParameterBag {
+    fn from_iter<I: IntoIterator<Item = (K, f64)>>(iter: I) -> Self {
+        Self {
+            values: iter.into_iter().map(|(k, v)| (k.into(), v)).collect(),
+        }
+    }
+}
+
+/// A numeric setting given either as a literal value or as a named parameter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ParamValue {
+    /// A literal value
+    Value(f64),
+    /// A parameter resolved from the running suite's [`ParameterBag`]
+    Param(String),
+}
+
+impl ParamValue {
+    /// Creates a reference to a named parameter.
+    pub fn param(name: impl Into<String>) -> Self {
+        ParamValue::Param(name.into())
+    }
+
+    /// Returns the parameter name, if this value refers to one.
+    pub fn param_name(&self) -> Option<&str> {
+        match self {
+            ParamValue::Value(_) => None,
+            ParamValue::Param(name) => Some(name),
+        }
+    }
+
+    /// Resolves the value, reading parameters from the current validation context.
+    pub fn resolve(&self) -> Result<f64> {
+        match self {
+            ParamValue::Value(value) => Ok(*value),
+            ParamValue::Param(name) => current_validation_context()
+                .parameter(name)
+                .ok_or_else(|| TermError::Configuration(format!("Parameter '{name}' is not set"))),
+        }
+    }
+
+    /// Returns a reference describing where the parameter is used, if any.
+    pub(crate) fn reference(&self, used_by: &str) -> Option<ParameterRef> {
+        self.param_name()
+            .map(|name| ParameterRef::new(name, used_by))
+    }
+}
+
+impl From<f64> for ParamValue {
+    fn from(value: f64) -> Self {
+        ParamValue::Value(value)
+    }
+}
+
+impl From<&str> for ParamValue {
+    fn from(name: &str) -> Self {
+        ParamValue::param(name)
+    }
+}
+
+impl From<String> for ParamValue {
+    fn from(name: String) -> Self {
+        ParamValue::Param(name)
+    }
+}
+
+impl fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamValue::Value(value) => write!(f, "{value}"),
+            ParamValue::Param(name) => match current_validation_context().parameter(name) {
+                Some(value) => write!(f, "{value}"),
+                None => write!(f, "${{{name}}}"),
+            },
+        }
+    }
+}
+
+/// A parameter referenced by a constraint, with the range of values it accepts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterRef {
+    /// The parameter name
+    pub name: String,
+    /// What uses the parameter, for error messages (e.g. `completeness threshold`)
+    pub used_by: String,
+    /// Smallest accepted value (inclusive)
+    pub min: Option<f64>,
+    /// Largest accepted value (inclusive)
+    pub max: Option<f64>,
+}
+
+impl ParameterRef {
+    /// Creates an unbounded parameter reference.
+    pub fn new(name: impl Into<String>, used_by: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            used_by: used_by.into(),
+            min: None,
+            max: None,
+        }
+    }
+
+    /// Restricts the accepted values to `min..=max`.
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+
+    /// Restricts the accepted values to `min..`.
+    pub fn at_least(mut self, min: f64) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    fn contains(&self, value: f64) -> bool {
+        value.is_finite()
+            && !self.min.is_some_and(|min| value < min)
+            && !self.max.is_some_and(|max| value > max)
+    }
+
+    fn range_description(&self) -> String {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => format!("a value between {min} and {max}"),
+            (Some(min), None) => format!("a value of at least {min}"),
+            (None, Some(max)) => format!("a value of at most {max}"),
+            (None, None) => "a finite value".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ValidationContext, CURRENT_CONTEXT};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_overrides_and_json_round_trip() {
+        let defaults = ParameterBag::from_json_str(r#"{"a": 0.9, "b": 10}"#).unwrap();
+        let merged = defaults.with_overrides(&ParameterBag::new().with("a", 0.99));
+        assert_eq!(merged.get("a"), Some(0.99));
+        assert_eq!(merged.get("b"), Some(10.0));
+
+        let json = merged.to_json().unwrap();
+        assert_eq!(ParameterBag::from_json_str(&json).unwrap(), merged);
+        assert!(ParameterBag::from_json_str(r#"{"a": "high"}"#).is_err());
+    }
+
+    #[test]
+    fn test_validate_reports_missing_and_out_of_range() {
+        let bag = ParameterBag::new().with("threshold", 1.5);
+        let threshold = ParameterRef::new("threshold", "completeness threshold").range(0.0, 1.0);
+
+        let err = bag.validate(&threshold).unwrap_err();
+        assert!(err.contains("out of range for completeness threshold"));
+
+        let err = ParameterBag::new().validate(&threshold).unwrap_err();
+        assert!(err.contains("Missing value for parameter 'threshold'"));
+
+        let tolerance = ParameterRef::new("tolerance", "tolerance").at_least(0.0);
+        assert_eq!(
+            ParameterBag::new()
+                .with("tolerance", 0.5)
+                .validate(&tolerance),
+            Ok(0.5)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_param_value_resolves_from_context() {
+        let value = ParamValue::param("threshold");
+        assert!(value.resolve().is_err());
+        assert_eq!(value.to_string(), "${threshold}");
+
+        let params = Arc::new(ParameterBag::new().with("threshold", 0.95));
+        let ctx = ValidationContext::new("data").with_parameters(params);
+        let resolved = CURRENT_CONTEXT
+            .scope(ctx, async { (value.resolve().unwrap(), value.to_string()) })
+            .await;
+        assert_eq!(resolved, (0.95, "0.95".to_string()));
+    }
+}
@@ -0,0 +1,251 @@
+//! Execution metrics collected from DataFusion physical plans.
+//!
+//! Constraints and the optimizer execute their queries through
+//! [`collect_with_metrics`], which retains the physical plan after execution
+//! and records DataFusion's plan metrics (rows scanned, bytes read, compute time)
+//! into the collector attached to the current [`ValidationContext`](super::ValidationContext).
+//! The suite aggregates these into [`PerformanceMetrics`] with a per-check breakdown.
+
+use super::current_validation_context;
+use arrow::record_batch::RecordBatch;
+use datafusion::dataframe::DataFrame;
+use datafusion::physical_plan::{collect, ExecutionPlan};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Metrics for one or more executed queries.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryMetrics {
+    /// Number of queries executed
+    pub queries_executed: u64,
+    /// Rows produced by the scan (leaf) operators
+    pub rows_scanned: u64,
+    /// Bytes read from storage, as reported by file scans (e.g. Parquet)
+    pub bytes_scanned: u64,
+    /// Rows returned by the queries
+    pub output_rows: u64,
+    /// CPU time spent across all operators, in nanoseconds
+    pub elapsed_compute_ns: u64,
+}
+
+impl QueryMetrics {
+    /// Extracts metrics from an executed physical plan.
+    ///
+    /// Operators that do not report a metric contribute nothing to it, so
+    /// in-memory scans report no bytes scanned.
+    pub fn from_plan(plan: &dyn ExecutionPlan) -> Self {
+        let mut metrics = Self {
+            queries_executed: 1,
+            output_rows: plan
+                .metrics()
+                .and_then(|m| m.output_rows())
+                .unwrap_or_default() as u64,
+            ..Self::default()
+        };
+        metrics.visit(plan);
+        metrics
+    }
+
+    fn visit(&mut self, plan: &dyn ExecutionPlan) {
+        let children = plan.children();
+        if let Some(set) = plan.metrics() {
+            if children.is_empty() {
+                self.rows_scanned += set.output_rows().unwrap_or_default() as u64;
+            }
+            self.elapsed_compute_ns += set.elapsed_compute().unwrap_or_default() as u64;
+            if let Some(bytes) = set.sum_by_name("bytes_scanned") {
+                self.bytes_scanned += bytes.as_usize() as u64;
+            }
+        }
+        for child in children {
+            self.visit(child.as_ref());
+        }
+    }
+
+    /// Adds the metrics of another measurement to this one.
+    pub fn merge(&mut self, other: &QueryMetrics) {
+        self.queries_executed += other.queries_executed;
+        self.rows_scanned += other.rows_scanned;
+        self.bytes_scanned += other.bytes_scanned;
+        self.output_rows += other.output_rows;
+        self.elapsed_compute_ns += other.elapsed_compute_ns;
+    }
+}
+
+/// Aggregated execution metrics for a validation run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PerformanceMetrics {
+    /// Number of queries executed
+    pub queries_executed: u64,
+    /// Total rows produced by scan operators
+    pub total_rows_scanned: u64,
+    /// Total bytes read from storage
+    pub total_bytes_scanned: u64,
+    /// Total CPU time spent across all operators, in nanoseconds
+    pub total_elapsed_compute_ns: u64,
+    /// Metric requests answered from the run's [`MetricCache`](super::MetricCache)
+    #[serde(default)]
+    pub metric_cache_hits: u64,
+    /// Metric requests the run's [`MetricCache`](super::MetricCache) had to compute
+    #[serde(default)]
+    pub metric_cache_misses: u64,
+    /// Per-check breakdown keyed by check name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub checks: HashMap<String, QueryMetrics>,
+}
+
+impl PerformanceMetrics {
+    /// Creates empty performance metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the metrics gathered while running a check.
+    pub fn record_check(&mut self, check_name: impl Into<String>, metrics: QueryMetrics) {
+        self.queries_executed += metrics.queries_executed;
+        self.total_rows_scanned += metrics.rows_scanned;
+        self.total_bytes_scanned += metrics.bytes_scanned;
+        self.total_elapsed_compute_ns += metrics.elapsed_compute_ns;
+        self.checks
+            .entry(check_name.into())
+            .or_default()
+            .merge(&metrics);
+    }
+
+    /// Returns true if no queries were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.queries_executed == 0
+    }
+
+    /// Returns the totals as named metrics suitable for a metrics backend.
+    pub fn as_metric_map(&self) -> HashMap<String, f64> {
+        let mut map = HashMap::new();
+        map.insert(
+            "performance.queries_executed".to_string(),
+            self.queries_executed as f64,
+        );
+        map.insert(
+            "performance.rows_scanned".to_string(),
+            self.total_rows_scanned as f64,
+        );
+        map.insert(
+            "performance.bytes_scanned".to_string(),
+            self.total_bytes_scanned as f64,
+        );
+        map.insert(
+            "performance.elapsed_compute_ms".to_string(),
+            self.total_elapsed_compute_ns as f64 / 1_000_000.0,
+        );
+        map.insert(
+            "performance.metric_cache_hits".to_string(),
+            self.metric_cache_hits as f64,
+        );
+        map.insert(
+            "performance.metric_cache_misses".to_string(),
+            self.metric_cache_misses as f64,
+        );
+        map
+    }
+}
+
+/// Shared accumulator that receives metrics from executed queries.
+///
+/// Cloning the collector shares the underlying accumulator.
+#[derive(Debug, Clone, Default)]
+pub struct QueryMetricsCollector {
+    inner: Arc<Mutex<QueryMetrics>>,
+}
+
+impl QueryMetricsCollector {
+    /// Creates an empty collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the metrics of an executed query.
+    pub fn record(&self, metrics: &QueryMetrics) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.merge(metrics);
+        }
+    }
+
+    /// Returns the metrics accumulated so far.
+    pub fn snapshot(&self) -> QueryMetrics {
+        self.inner
+            .lock()
+            .map(|inner| inner.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Executes a DataFrame and records its plan metrics in the current validation context.
+///
+/// This is a drop-in replacement for `DataFrame::collect` that keeps the physical
+/// plan around after execution so its metrics can be read. When no collector is
+/// attached to the current context the metrics are simply discarded.
+pub async fn collect_with_metrics(df: DataFrame) -> datafusion::error::Result<Vec<RecordBatch>> {
+    let task_ctx = Arc::new(df.task_ctx());
+    let plan = df.create_physical_plan().await?;
+    let batches = collect(Arc::clone(&plan), task_ctx).await?;
+
+    if let Some(collector) = current_validation_context().performance_collector() {
+        collector.record(&QueryMetrics::from_plan(plan.as_ref()));
+    }
+
+    Ok(batches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ValidationContext, CURRENT_CONTEXT};
+    use datafusion::prelude::*;
+
+    #[test]
+    fn test_record_check_aggregates() {
+        let mut perf = PerformanceMetrics::new();
+        let metrics = QueryMetrics {
+            queries_executed: 2,
+            rows_scanned: 100,
+            bytes_scanned: 2048,
+            output_rows: 2,
+            elapsed_compute_ns: 10,
+        };
+        perf.record_check("a", metrics.clone());
+        perf.record_check("a", metrics.clone());
+        perf.record_check("b", metrics);
+
+        assert_eq!(perf.queries_executed, 6);
+        assert_eq!(perf.total_rows_scanned, 300);
+        assert_eq!(perf.total_bytes_scanned, 6144);
+        assert_eq!(perf.checks["a"].rows_scanned, 200);
+        assert_eq!(perf.checks["b"].queries_executed, 2);
+    }
+
+    #[tokio::test]
+    async fn test_collect_with_metrics_records_into_context() {
+        let ctx = SessionContext::new();
+        let collector = QueryMetricsCollector::new();
+        let validation_ctx =
+            ValidationContext::new("data").with_performance_collector(collector.clone());
+
+        let batches = CURRENT_CONTEXT
+            .scope(validation_ctx, async {
+                let df = ctx.sql("SELECT 1 AS one").await.unwrap();
+                collect_with_metrics(df).await.unwrap()
+            })
+            .await;
+
+        assert_eq!(batches[0].num_rows(), 1);
+        assert_eq!(collector.snapshot().queries_executed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_collect_with_metrics_without_collector() {
+        let ctx = SessionContext::new();
+        let df = ctx.sql("SELECT 1 AS one").await.unwrap();
+        let batches = collect_with_metrics(df).await.unwrap();
+        assert_eq!(batches[0].num_rows(), 1);
+    }
+}
@@ -0,0 +1,1002 @@
+//! Aggregation of many validation reports into a portfolio summary.
+//!
+//! Teams running dozens of suites every night usually want a single rollup
+//! rather than one report per suite. [`ReportAggregator`] collects
+//! [`ValidationReport`]s, tagged with labels such as `dataset` or `pipeline`,
+//! and produces a [`PortfolioSummary`] with:
+//!
+//! - pass rates per group of tags, with the trend against the previous run
+//! - the worst offending constraints across all suites
+//! - constraints that started failing, or were resolved, since the previous run
+//!
+//! Runs of the same suite with the same tags form a series; the latest report
+//! of each series is the current run. Constraints are matched across runs by
+//! suite, check and constraint name.
+//!
+//! Reports can be added directly or loaded from a [`MetricsRepository`] where
+//! they were saved with [`ReportAggregator::save_report`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use term_guard::core::{ReportAggregator, ValidationReport};
+//!
+//! let yesterday = ValidationReport::new("orders_suite");
+//! let today = ValidationReport::new("orders_suite");
+//!
+//! let summary = ReportAggregator::new()
+//!     .group_by(["dataset"])
+//!     .with_tagged_report(yesterday, [("dataset", "orders")])
+//!     .with_tagged_report(today, [("dataset", "orders")])
+//!     .summarize();
+//!
+//! assert_eq!(summary.groups.len(), 1);
+//! println!("{}", summary.to_markdown());
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{ConstraintOutcome, Level, ValidationIssue, ValidationReport};
+use crate::analyzers::{AnalyzerContext, MetricValue};
+use crate::prelude::*;
+use crate::repository::{MetricsQuery, MetricsRepository, ResultKey};
+
+/// Metadata key under which [`ReportAggregator::save_report`] stores a report.
+pub const REPORT_METADATA_KEY: &str = "term.validation_report";
+
+/// Prefix of the tags [`ReportAggregator::save_check_metrics`] derives from a
+/// check's name and metadata.
+pub const CHECK_TAG_PREFIX: &str = "check.";
+
+/// Group value used for reports without a grouping tag.
+const UNTAGGED: &str = "(none)";
+
+/// Default number of worst offending constraints listed in a summary.
+const DEFAULT_MAX_OFFENDERS: usize = 10;
+
+/// A report together with the tags and time of its run.
+#[derive(Debug, Clone)]
+struct TaggedReport {
+    report: ValidationReport,
+    tags: BTreeMap<String, String>,
+    timestamp: Option<DateTime<Utc>>,
+    sequence: usize,
+}
+
+/// Identifies the same constraint across runs.
+type ConstraintKey = (String, String, String);
+
+/// Aggregates validation reports from many suites and runs.
+///
+/// See the [module documentation](self) for how runs are matched.
+#[derive(Debug, Clone)]
+pub struct ReportAggregator {
+    group_by: Vec<String>,
+    max_offenders: usize,
+    runs: Vec<TaggedReport>,
+}
+
+impl ReportAggregator {
+    /// Creates an empty aggregator that puts all reports in a single group.
+    pub fn new() -> Self {
+        Self {
+            group_by: Vec::new(),
+            max_offenders: DEFAULT_MAX_OFFENDERS,
+            runs: Vec::new(),
+        }
+    }
+
+    /// Sets the tags used to group reports, e.g. `["dataset", "pipeline"]`.
+    pub fn group_by<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.group_by = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets how many of the worst offending constraints are listed (default: 10).
+    pub fn max_offenders(mut self, max: usize) -> Self {
+        self.max_offenders = max;
+        self
+    }
+
+    /// Adds an untagged report.
+    pub fn with_report(self, report: ValidationReport) -> Self {
+        self.with_tagged_report(report, std::iter::empty::<(String, String)>())
+    }
+
+    /// Adds a report with tags describing its run.
+    pub fn with_tagged_report<I, K, V>(mut self, report: ValidationReport, tags: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.add_report(report, tags);
+        self
+    }
+
+    /// Adds a report with tags describing its run.
+    ///
+    /// The run time is read from the report's timestamp; reports added with the
+    /// same or an unreadable timestamp are ordered by insertion.
+    pub fn add_report<I, K, V>(&mut self, report: ValidationReport, tags: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let timestamp = DateTime::parse_from_rfc3339(&report.timestamp)
+            .ok()
+            .map(|t| t.with_timezone(&Utc));
+        let tags = tags
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self.push(report, tags, timestamp);
+    }
+
+    /// Loads the reports matched by a repository query.
+    ///
+    /// Only entries saved with [`ReportAggregator::save_report`] are used; their
+    /// result key provides the tags and run time. Returns the number of reports
+    /// loaded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use term_guard::core::ReportAggregator;
+    /// use term_guard::repository::{InMemoryRepository, MetricsRepository};
+    ///
+    /// # async fn example(repository: &InMemoryRepository) -> term_guard::prelude::Result<()> {
+    /// let mut aggregator = ReportAggregator::new().group_by(["dataset", "pipeline"]);
+    /// aggregator
+    ///     .load_from_repository(repository.load().await.with_tag("pipeline", "nightly"))
+    ///     .await?;
+    /// let summary = aggregator.summarize();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn load_from_repository(&mut self, query: MetricsQuery) -> Result<usize> {
+        let mut loaded = 0;
+        for (key, context) in query.execute().await? {
+            let Some(json) = context.metadata().custom.get(REPORT_METADATA_KEY) else {
+                continue;
+            };
+            let report = ValidationReport::from_json_str(json)?;
+            let timestamp = Some(key.as_datetime());
+            self.push(report, key.tags.into_iter().collect(), timestamp);
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+
+    /// Saves a report to a metrics repository so it can be aggregated later.
+    ///
+    /// The report is stored as JSON in the metadata of an otherwise empty
+    /// [`AnalyzerContext`]; the key's tags become the report's tags.
+    pub async fn save_report(
+        repository: &dyn MetricsRepository,
+        key: ResultKey,
+        report: &ValidationReport,
+    ) -> Result<()> {
+        let json =
+            serde_json::to_string(report).map_err(|e| TermError::Serialization(e.to_string()))?;
+        let mut context = AnalyzerContext::with_dataset(report.suite_name.clone());
+        context.metadata_mut().add_custom(REPORT_METADATA_KEY, json);
+        repository.save(key, context).await
+    }
+
+    /// Saves the metrics of every check of a report as a separate entry.
+    ///
+    /// Each entry holds the metrics of the check's constraint outcomes, keyed
+    /// by constraint name (repeated names are numbered `name#2`, `name#3`, ...),
+    /// and is saved under `key` with the check's tags added: `check.name` and
+    /// `check.<key>` for every [metadata](super::CheckBuilder::metadata)
+    /// entry. Tags of `key` take precedence over check tags of the same name.
+    ///
+    /// All tags are validated before anything is saved, so an invalid tag of
+    /// one check leaves the repository unchanged. Returns the number of
+    /// entries saved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use term_guard::core::{ReportAggregator, ValidationReport};
+    /// use term_guard::repository::{InMemoryRepository, MetricsRepository, ResultKey};
+    ///
+    /// # async fn example(report: &ValidationReport) -> term_guard::prelude::Result<()> {
+    /// let repository = InMemoryRepository::new();
+    /// let key = ResultKey::now().with_tag("pipeline", "nightly");
+    /// ReportAggregator::save_check_metrics(&repository, key, report).await?;
+    ///
+    /// let finance = repository
+    ///     .load()
+    ///     .await
+    ///     .with_tag("check.owner", "finance")
+    ///     .execute()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn save_check_metrics(
+        repository: &dyn MetricsRepository,
+        key: ResultKey,
+        report: &ValidationReport,
+    ) -> Result<usize> {
+        let mut checks: BTreeMap<&str, Vec<&ConstraintOutcome>> = BTreeMap::new();
+        for outcome in &report.outcomes {
+            checks
+                .entry(outcome.check_name.as_str())
+                .or_default()
+                .push(outcome);
+        }
+
+        let mut entries = Vec::with_capacity(checks.len());
+        for (check_name, outcomes) in checks {
+            let mut check_key = key.clone();
+            for (tag, value) in report.check_tags(check_name) {
+                check_key.tags.entry(tag).or_insert(value);
+            }
+            if let Err(message) = check_key.validate_tags() {
+                return Err(TermError::repository_validation(
+                    "tags",
+                    format!("Cannot tag the metrics of check '{check_name}': {message}"),
+                    check_key.to_string(),
+                ));
+            }
+
+            let mut context = AnalyzerContext::with_dataset(report.suite_name.clone());
+            let mut seen: BTreeMap<&str, usize> = BTreeMap::new();
+            for outcome in outcomes {
+                let Some(metric) = outcome.metric else {
+                    continue;
+                };
+                let count = seen.entry(outcome.constraint_name.as_str()).or_default();
+                *count += 1;
+                let name = match *count {
+                    1 => outcome.constraint_name.clone(),
+                    n => format!("{}#{n}", outcome.constraint_name),
+                };
+                context.store_metric(name, MetricValue::Double(metric));
+            }
+            entries.push((check_key, context));
+        }
+
+        let saved = entries.len();
+        for (check_key, context) in entries {
+            repository.save(check_key, context).await?;
+        }
+        Ok(saved)
+    }
+
+    fn push(
+        &mut self,
+        report: ValidationReport,
+        tags: BTreeMap<String, String>,
+        timestamp: Option<DateTime<Utc>>,
+    ) {
+        let sequence = self.runs.len();
+        self.runs.push(TaggedReport {
+            report,
+            tags,
+            timestamp,
+            sequence,
+        });
+    }
+
+    /// Produces the portfolio summary of all added reports.
+    pub fn summarize(&self) -> PortfolioSummary {
+        // Runs of the same suite with the same tags, oldest first
+        let mut series: BTreeMap<(&str, &BTreeMap<String, String>), Vec<&TaggedReport>> =
+            BTreeMap::new();
+        for run in &self.runs {
+            series
+                .entry((run.report.suite_name.as_str(), &run.tags))
+                .or_default()
+                .push(run);
+        }
+        for runs in series.values_mut() {
+            runs.sort_by_key(|run| (run.timestamp, run.sequence));
+        }
+
+        let mut groups: BTreeMap<Vec<String>, GroupTotals> = BTreeMap::new();
+        let mut offenders: BTreeMap<ConstraintKey, OffenderTotals> = BTreeMap::new();
+        let mut newly_failing = Vec::new();
+        let mut resolved = Vec::new();
+
+        for runs in series.values() {
+            let current = runs[runs.len() - 1];
+            let previous = runs.len().checked_sub(2).map(|i| runs[i]);
+
+            let totals = groups.entry(self.group_values(&current.tags)).or_default();
+            totals.add_current(&current.report);
+            if let Some(previous) = previous {
+                totals.add_previous(&previous.report);
+            }
+
+            for run in runs {
+                for key in failures(&run.report).keys() {
+                    offenders.entry(key.clone()).or_default().failed_runs += 1;
+                }
+            }
+
+            let current_failures = failures(&current.report);
+            for (key, issue) in &current_failures {
+                let offender = offenders.entry(key.clone()).or_default();
+                offender.failing_now += 1;
+                if !offender.level.is_some_and(|level| level >= issue.level) {
+                    offender.level = Some(issue.level);
+                    offender.message = issue.message.clone();
+                }
+            }
+
+            if let Some(previous) = previous {
+                let previous_failures = failures(&previous.report);
+                for (key, issue) in &current_failures {
+                    if !previous_failures.contains_key(key) {
+                        newly_failing.push(ConstraintChange::new(key, issue, &current.tags));
+                    }
+                }
+                for (key, issue) in &previous_failures {
+                    if !current_failures.contains_key(key) {
+                        resolved.push(ConstraintChange::new(key, issue, &current.tags));
+                    }
+                }
+            }
+        }
+
+        let mut worst_offenders: Vec<ConstraintFailures> = offenders
+            .into_iter()
+            .filter(|(_, totals)| totals.failing_now > 0)
+            .map(|((suite, check, constraint), totals)| ConstraintFailures {
+                suite_name: suite,
+                check_name: check,
+                constraint_name: constraint,
+                level: totals.level.unwrap_or_default(),
+                failing_runs: totals.failing_now,
+                failed_runs_total: totals.failed_runs,
+                message: totals.message,
+            })
+            .collect();
+        worst_offenders.sort_by(|a, b| {
+            b.level
+                .cmp(&a.level)
+                .then(b.failing_runs.cmp(&a.failing_runs))
+                .then(b.failed_runs_total.cmp(&a.failed_runs_total))
+        });
+        worst_offenders.truncate(self.max_offenders);
+
+        let groups = groups
+            .into_iter()
+            .map(|(values, totals)| totals.into_summary(self.group_by.iter().cloned().zip(values)))
+            .collect();
+
+        PortfolioSummary {
+            generated_at: Utc::now().to_rfc3339(),
+            group_by: self.group_by.clone(),
+            groups,
+            worst_offenders,
+            newly_failing,
+            resolved,
+        }
+    }
+
+    fn group_values(&self, tags: &BTreeMap<String, String>) -> Vec<String> {
+        self.group_by
+            .iter()
+            .map(|tag| {
+                tags.get(tag)
+                    .cloned()
+                    .unwrap_or_else(|| UNTAGGED.to_string())
+            })
+            .collect()
+    }
+}
+
+impl Default for ReportAggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the failing constraints of a report, keyed by suite, check and constraint.
+fn failures(report: &ValidationReport) -> BTreeMap<ConstraintKey, &ValidationIssue> {
+    let mut failures = BTreeMap::new();
+    for issue in &report.issues {
+        failures
+            .entry((
+                report.suite_name.clone(),
+                issue.check_name.clone(),
+                issue.constraint_name.clone(),
+            ))
+            .or_insert(issue);
+    }
+    failures
+}
+
+#[derive(Debug, Default)]
+struct GroupTotals {
+    suites: usize,
+    failing_suites: usize,
+    total_checks: usize,
+    passed_checks: usize,
+    failed_checks: usize,
+    skipped_checks: usize,
+    info_findings: usize,
+    waived_checks: usize,
+    previous_total: usize,
+    previous_passed: usize,
+    has_previous: bool,
+}
+
+impl GroupTotals {
+    /// Checks that passed or failed, see [`ValidationMetrics::rated_checks`](super::ValidationMetrics::rated_checks).
+    fn rated_checks(&self) -> usize {
+        self.passed_checks + self.failed_checks
+    }
+
+    fn add_current(&mut self, report: &ValidationReport) {
+        self.suites += 1;
+        if report.has_errors() {
+            self.failing_suites += 1;
+        }
+        self.total_checks += report.metrics.total_checks;
+        self.passed_checks += report.metrics.passed_checks;
+        self.failed_checks += report.metrics.failed_checks;
+        self.skipped_checks += report.metrics.skipped_checks;
+        self.info_findings += report.metrics.info_findings;
+        self.waived_checks += report.metrics.waived_checks;
+    }
+
+    fn add_previous(&mut self, report: &ValidationReport) {
+        self.has_previous = true;
+        self.previous_total += report.metrics.rated_checks();
+        self.previous_passed += report.metrics.passed_checks;
+    }
+
+    fn into_summary(self, tags: impl Iterator<Item = (String, String)>) -> GroupSummary {
+        GroupSummary {
+            tags: tags.collect(),
+            suites: self.suites,
+            failing_suites: self.failing_suites,
+            total_checks: self.total_checks,
+            passed_checks: self.passed_checks,
+            failed_checks: self.failed_checks,
+            skipped_checks: self.skipped_checks,
+            info_findings: self.info_findings,
+            waived_checks: self.waived_checks,
+            pass_rate: pass_rate(self.passed_checks, self.rated_checks()),
+            previous_pass_rate: self
+                .has_previous
+                .then(|| pass_rate(self.previous_passed, self.previous_total)),
+        }
+    }
+}
+
+fn pass_rate(passed: usize, total: usize) -> f64 {
+    if total == 0 {
+        1.0
+    } else {
+        passed as f64 / total as f64
+    }
+}
+
+#[derive(Debug, Default)]
+struct OffenderTotals {
+    level: Option<Level>,
+    message: String,
+    failing_now: usize,
+    failed_runs: usize,
+}
+
+/// Totals of the current runs sharing the same grouping tags.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupSummary {
+    /// Values of the grouping tags (`(none)` for reports without the tag)
+    pub tags: BTreeMap<String, String>,
+    /// Number of suites in the group
+    pub suites: usize,
+    /// Number of suites whose current run has errors
+    pub failing_suites: usize,
+    /// Total number of checks evaluated
+    pub total_checks: usize,
+    /// Number of checks that passed
+    pub passed_checks: usize,
+    /// Number of checks that failed
+    pub failed_checks: usize,
+    /// Number of checks that were skipped
+    pub skipped_checks: usize,
+    /// Number of failed `Level::Info` checks, left out of the pass rate
+    #[serde(default)]
+    pub info_findings: usize,
+    /// Number of checks whose failures were waived by known issues, left out of
+    /// the pass rate
+    #[serde(default)]
+    pub waived_checks: usize,
+    /// Fraction of passed checks among the passed and failed ones (0.0 to 1.0)
+    pub pass_rate: f64,
+    /// Pass rate of the previous runs of the same suites, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_pass_rate: Option<f64>,
+}
+
+impl GroupSummary {
+    /// Change in pass rate since the previous runs.
+    pub fn trend(&self) -> Option<f64> {
+        self.previous_pass_rate
+            .map(|previous| self.pass_rate - previous)
+    }
+
+    /// The group label, e.g. `dataset=orders, pipeline=nightly`.
+    pub fn label(&self) -> String {
+        if self.tags.is_empty() {
+            return "all suites".to_string();
+        }
+        self.tags
+            .iter()
+            .map(|(tag, value)| format!("{tag}={value}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// A constraint failing in the current runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstraintFailures {
+    /// The suite containing the constraint
+    pub suite_name: String,
+    /// The check containing the constraint
+    pub check_name: String,
+    /// The name of the constraint
+    pub constraint_name: String,
+    /// The most severe level it currently fails with
+    pub level: Level,
+    /// Number of current runs in which it fails
+    pub failing_runs: usize,
+    /// Number of aggregated runs, current and previous, in which it failed
+    pub failed_runs_total: usize,
+    /// The failure message of the most severe current failure
+    pub message: String,
+}
+
+/// A constraint whose outcome changed since the previous run of its suite.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstraintChange {
+    /// The suite containing the constraint
+    pub suite_name: String,
+    /// The check containing the constraint
+    pub check_name: String,
+    /// The name of the constraint
+    pub constraint_name: String,
+    /// Tags of the suite's runs
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+    /// Level of the failure (current for new failures, previous for resolved ones)
+    pub level: Level,
+    /// Message of the failure
+    pub message: String,
+}
+
+impl ConstraintChange {
+    fn new(key: &ConstraintKey, issue: &ValidationIssue, tags: &BTreeMap<String, String>) -> Self {
+        Self {
+            suite_name: key.0.clone(),
+            check_name: key.1.clone(),
+            constraint_name: key.2.clone(),
+            tags: tags.clone(),
+            level: issue.level,
+            message: issue.message.clone(),
+        }
+    }
+}
+
+/// Rollup of many validation reports.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioSummary {
+    /// When the summary was produced (ISO 8601 format)
+    pub generated_at: String,
+    /// The tags reports were grouped by
+    pub group_by: Vec<String>,
+    /// Totals per group
+    pub groups: Vec<GroupSummary>,
+    /// Constraints failing in the most current runs, most severe first
+    pub worst_offenders: Vec<ConstraintFailures>,
+    /// Constraints failing now that passed in the previous run
+    pub newly_failing: Vec<ConstraintChange>,
+    /// Constraints that failed in the previous run and pass now
+    pub resolved: Vec<ConstraintChange>,
+}
+
+impl PortfolioSummary {
+    /// Number of suites across all groups.
+    pub fn total_suites(&self) -> usize {
+        self.groups.iter().map(|g| g.suites).sum()
+    }
+
+    /// Fraction of passed checks among the passed and failed ones across all groups.
+    pub fn pass_rate(&self) -> f64 {
+        pass_rate(
+            self.groups.iter().map(|g| g.passed_checks).sum(),
+            self.groups
+                .iter()
+                .map(|g| g.passed_checks + g.failed_checks)
+                .sum(),
+        )
+    }
+
+    /// Serializes the summary as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| TermError::Serialization(e.to_string()))
+    }
+
+    /// Reads a summary from JSON.
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| TermError::Serialization(e.to_string()))
+    }
+
+    /// Formats the summary as plain text.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "Validation portfolio: {} suites, {:.1}% of checks passed",
+            self.total_suites(),
+            self.pass_rate() * 100.0
+        );
+
+        let _ = writeln!(out, "\nGroups:");
+        for group in &self.groups {
+            let _ = writeln!(out, "  {}", group_line(group));
+        }
+
+        write_failures(&mut out, "Worst offenders", &self.worst_offenders, |f| {
+            format!("{}: {}", failure_line(f), f.message)
+        });
+        write_changes(&mut out, "Newly failing", &self.newly_failing, |c| {
+            format!("{} [{}]: {}", change_name(c), c.level, c.message)
+        });
+        write_changes(&mut out, "Resolved", &self.resolved, change_name);
+        out
+    }
+
+    /// Formats the summary as Markdown suitable for chat tools such as Slack.
+    ///
+    /// Uses bold text and bullet lists rather than tables so the output also
+    /// renders in chat clients with limited Markdown support.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "*Validation portfolio*: {} suites, {:.1}% of checks passed",
+            self.total_suites(),
+            self.pass_rate() * 100.0
+        );
+
+        let _ = writeln!(out, "\n*Groups*");
+        for group in &self.groups {
+            let _ = writeln!(out, "• {}", group_line(group));
+        }
+
+        write_failures(&mut out, "*Worst offenders*", &self.worst_offenders, |f| {
+            format!("`{}`: {}", failure_line(f), f.message)
+        });
+        write_changes(&mut out, "*Newly failing*", &self.newly_failing, |c| {
+            format!("`{}` [{}]: {}", change_name(c), c.level, c.message)
+        });
+        write_changes(&mut out, "*Resolved*", &self.resolved, |c| {
+            format!("`{}`", change_name(c))
+        });
+        out
+    }
+}
+
+fn group_line(group: &GroupSummary) -> String {
+    let trend = match group.trend() {
+        Some(trend) => format!(", {:+.1} pts vs previous", trend * 100.0),
+        None => String::new(),
+    };
+    format!(
+        "{}: {}/{} passed ({:.1}%{trend}), {} of {} suites failing",
+        group.label(),
+        group.passed_checks,
+        group.passed_checks + group.failed_checks,
+        group.pass_rate * 100.0,
+        group.failing_suites,
+        group.suites
+    )
+}
+
+fn failure_line(failure: &ConstraintFailures) -> String {
+    format!(
+        "{} / {} / {} [{}] failing in {} run(s)",
+        failure.suite_name,
+        failure.check_name,
+        failure.constraint_name,
+        failure.level,
+        failure.failing_runs
+    )
+}
+
+fn change_name(change: &ConstraintChange) -> String {
+    let name = format!(
+        "{} / {} / {}",
+        change.suite_name, change.check_name, change.constraint_name
+    );
+    if change.tags.is_empty() {
+        name
+    } else {
+        let tags: Vec<String> = change
+            .tags
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect();
+        format!("{name} ({})", tags.join(", "))
+    }
+}
+
+fn write_failures(
+    out: &mut String,
+    title: &str,
+    failures: &[ConstraintFailures],
+    line: impl Fn(&ConstraintFailures) -> String,
+) {
+    if failures.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "\n{title}");
+    for (i, failure) in failures.iter().enumerate() {
+        let _ = writeln!(out, "{}. {}", i + 1, line(failure));
+    }
+}
+
+fn write_changes(
+    out: &mut String,
+    title: &str,
+    changes: &[ConstraintChange],
+    line: impl Fn(&ConstraintChange) -> String,
+) {
+    if changes.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "\n{title} ({})", changes.len());
+    for change in changes {
+        let _ = writeln!(out, "• {}", line(change));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ConstraintStatus, ValidationMetrics};
+    use crate::repository::InMemoryRepository;
+
+    fn report(suite: &str, timestamp: &str, failing: &[(&str, Level)]) -> ValidationReport {
+        let mut report = ValidationReport::new(suite);
+        report.timestamp = timestamp.to_string();
+        report.metrics = ValidationMetrics {
+            total_checks: 4,
+            passed_checks: 4 - failing.len(),
+            failed_checks: failing.len(),
+            ..ValidationMetrics::new()
+        };
+        for (constraint, level) in failing {
+            report.add_issue(ValidationIssue {
+                check_name: "quality".to_string(),
+                constraint_name: constraint.to_string(),
+                level: *level,
+                message: format!("{constraint} failed"),
+                metric: None,
+                cached: false,
+                known_issue: None,
+                check_id: None,
+                constraint_id: None,
+                impacts: Vec::new(),
+            });
+        }
+        report
+    }
+
+    #[test]
+    fn test_summary_compares_latest_runs_per_series() {
+        let summary = ReportAggregator::new()
+            .group_by(["dataset"])
+            // Added out of order; ordering comes from the report timestamps
+            .with_tagged_report(
+                report(
+                    "orders",
+                    "2024-01-02T00:00:00Z",
+                    &[("uniqueness", Level::Error)],
+                ),
+                [("dataset", "orders")],
+            )
+            .with_tagged_report(
+                report(
+                    "orders",
+                    "2024-01-01T00:00:00Z",
+                    &[("completeness", Level::Error)],
+                ),
+                [("dataset", "orders")],
+            )
+            .with_tagged_report(
+                report(
+                    "users",
+                    "2024-01-02T00:00:00Z",
+                    &[("uniqueness", Level::Warning)],
+                ),
+                [("dataset", "users")],
+            )
+            .with_report(report("adhoc", "2024-01-02T00:00:00Z", &[]))
+            .summarize();
+
+        let labels: Vec<String> = summary.groups.iter().map(GroupSummary::label).collect();
+        assert_eq!(
+            labels,
+            vec!["dataset=(none)", "dataset=orders", "dataset=users"]
+        );
+        let orders = &summary.groups[1];
+        assert_eq!((orders.passed_checks, orders.total_checks), (3, 4));
+        assert_eq!(orders.previous_pass_rate, Some(0.75));
+        assert_eq!(orders.trend(), Some(0.0));
+        assert_eq!(summary.groups[2].trend(), None);
+
+        let new: Vec<&str> = summary
+            .newly_failing
+            .iter()
+            .map(|c| c.constraint_name.as_str())
+            .collect();
+        assert_eq!(new, vec!["uniqueness"]);
+        assert_eq!(summary.resolved[0].constraint_name, "completeness");
+
+        // Errors rank above warnings
+        assert_eq!(summary.worst_offenders.len(), 2);
+        assert_eq!(summary.worst_offenders[0].suite_name, "orders");
+        assert_eq!(summary.worst_offenders[0].level, Level::Error);
+    }
+
+    #[test]
+    fn test_worst_offenders_count_runs_across_suites() {
+        let mut aggregator = ReportAggregator::new().max_offenders(1);
+        for dataset in ["a", "b", "c"] {
+            aggregator.add_report(
+                report(
+                    "shared",
+                    "2024-01-01T00:00:00Z",
+                    &[("completeness", Level::Error)],
+                ),
+                [("dataset", dataset)],
+            );
+        }
+        aggregator.add_report(
+            report("other", "2024-01-01T00:00:00Z", &[("size", Level::Error)]),
+            [("dataset", "a")],
+        );
+
+        let summary = aggregator.summarize();
+        assert_eq!(summary.groups.len(), 1);
+        assert_eq!(summary.groups[0].suites, 4);
+        assert_eq!(summary.worst_offenders.len(), 1);
+        assert_eq!(summary.worst_offenders[0].constraint_name, "completeness");
+        assert_eq!(summary.worst_offenders[0].failing_runs, 3);
+    }
+
+    #[test]
+    fn test_formatting_and_json_round_trip() {
+        let summary = ReportAggregator::new()
+            .with_report(report("orders", "2024-01-01T00:00:00Z", &[]))
+            .with_report(report(
+                "orders",
+                "2024-01-02T00:00:00Z",
+                &[("completeness", Level::Error)],
+            ))
+            .summarize();
+
+        let markdown = summary.to_markdown();
+        assert!(markdown.contains("*Validation portfolio*: 1 suites, 75.0% of checks passed"));
+        assert!(markdown.contains("all suites: 3/4 passed (75.0%, -25.0 pts vs previous)"));
+        assert!(markdown.contains("*Newly failing* (1)"));
+        assert!(markdown.contains("`orders / quality / completeness` [error]"));
+        assert!(!markdown.contains("*Resolved*"));
+
+        let text = summary.to_text();
+        assert!(text.contains("1. orders / quality / completeness [error] failing in 1 run(s)"));
+
+        let json = summary.to_json().unwrap();
+        assert_eq!(PortfolioSummary::from_json_str(&json).unwrap(), summary);
+    }
+
+    fn outcome(check: &str, constraint: &str, metric: f64) -> ConstraintOutcome {
+        ConstraintOutcome {
+            check_name: check.to_string(),
+            constraint_name: constraint.to_string(),
+            level: Level::Error,
+            status: ConstraintStatus::Success,
+            waived: false,
+            metric: Some(metric),
+            threshold: None,
+            dimension: None,
+            constraint_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_metrics_are_tagged_with_check_metadata() -> Result<()> {
+        let mut report = ValidationReport::new("orders");
+        report.outcomes = vec![
+            outcome("revenue", "completeness", 1.0),
+            outcome("revenue", "completeness", 0.5),
+            outcome("volume", "size", 42.0),
+        ];
+        report.check_metadata.insert(
+            "revenue".to_string(),
+            BTreeMap::from([
+                ("owner".to_string(), "finance".to_string()),
+                ("name".to_string(), "ignored".to_string()),
+                ("pipeline".to_string(), "ignored".to_string()),
+            ]),
+        );
+
+        let repository = InMemoryRepository::new();
+        let key = ResultKey::new(1_700_000_000_000)
+            .with_tag("pipeline", "nightly")
+            .with_tag("check.pipeline", "run");
+        let saved = ReportAggregator::save_check_metrics(&repository, key, &report).await?;
+        assert_eq!(saved, 2);
+
+        let finance = repository
+            .load()
+            .await
+            .with_tag("check.owner", "finance")
+            .execute()
+            .await?;
+        assert_eq!(finance.len(), 1);
+        let (key, context) = &finance[0];
+        assert_eq!(key.tags["check.name"], "revenue");
+        assert_eq!(key.tags["pipeline"], "nightly");
+        assert_eq!(key.tags["check.pipeline"], "run");
+        assert_eq!(
+            context.get_metric("completeness#2"),
+            Some(&MetricValue::Double(0.5))
+        );
+
+        let volume = repository
+            .load()
+            .await
+            .with_tag("check.name", "volume")
+            .execute()
+            .await?;
+        assert_eq!(volume.len(), 1);
+        assert!(!volume[0].0.tags.contains_key("check.owner"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_check_tags_save_nothing() {
+        let mut report = ValidationReport::new("orders");
+        report.outcomes = vec![
+            outcome("revenue", "size", 1.0),
+            outcome("volume", "size", 2.0),
+        ];
+        report.check_metadata.insert(
+            "volume".to_string(),
+            BTreeMap::from([("owner".to_string(), "x".repeat(2000))]),
+        );
+
+        let repository = InMemoryRepository::new();
+        let error = ReportAggregator::save_check_metrics(&repository, ResultKey::now(), &report)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, TermError::RepositoryValidation { .. }),
+            "{error}"
+        );
+        assert!(error.to_string().contains("check 'volume'"), "{error}");
+        assert!(repository.load().await.execute().await.unwrap().is_empty());
+    }
+}
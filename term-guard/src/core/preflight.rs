@@ -0,0 +1,166 @@
+//! Preflight probes of the sources a suite reads.
+//!
+//! A suite built with
+//! [`with_preflight(true)`](super::ValidationSuiteBuilder::with_preflight)
+//! probes every source attached with
+//! [`ValidationSuiteBuilder::source`](super::ValidationSuiteBuilder::source)
+//! before any constraint runs. If a source cannot be reached, the run fails
+//! with [`TermError::PreflightFailed`] listing every unreachable source at
+//! once, instead of failing constraint after constraint with query errors.
+
+use std::fmt;
+use std::sync::Arc;
+
+use futures::future::join_all;
+use tracing::{debug, warn};
+
+use crate::prelude::*;
+use crate::sources::{DataSource, SourceProbe};
+
+/// The outcome of probing one source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceCheck {
+    /// Name of the table the source is attached as
+    pub name: String,
+    /// Description of the source
+    pub description: String,
+    /// What the probe found, or `None` if it failed
+    pub probe: Option<SourceProbe>,
+    /// Why the source could not be accessed
+    pub error: Option<String>,
+}
+
+impl SourceCheck {
+    /// Returns true if the source was accessed and its data exists.
+    pub fn is_reachable(&self) -> bool {
+        self.probe.as_ref().is_some_and(|probe| probe.exists)
+    }
+
+    /// Returns why the source is unreachable, or `None` if it is reachable.
+    pub fn problem(&self) -> Option<&str> {
+        match (&self.error, &self.probe) {
+            (Some(error), _) => Some(error.as_str()),
+            (None, Some(probe)) if probe.exists => None,
+            _ => Some("no data found"),
+        }
+    }
+}
+
+/// The probes of all sources attached to a suite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreflightReport {
+    /// Name of the suite
+    pub suite: String,
+    /// The probed sources, in the order they were attached
+    pub sources: Vec<SourceCheck>,
+}
+
+impl PreflightReport {
+    /// Probes `sources` concurrently.
+    pub(crate) async fn probe(suite: &str, sources: &[(String, Arc<dyn DataSource>)]) -> Self {
+        let probes = join_all(sources.iter().map(|(_, source)| source.probe())).await;
+        let sources = sources
+            .iter()
+            .zip(probes)
+            .map(|((name, source), probe)| {
+                let (probe, error) = match probe {
+                    Ok(probe) => (Some(probe), None),
+                    Err(e) => (None, Some(e.to_string())),
+                };
+                let check = SourceCheck {
+                    name: name.clone(),
+                    description: source.description(),
+                    probe,
+                    error,
+                };
+                debug!(source = %check.name, reachable = check.is_reachable(), "Probed source");
+                check
+            })
+            .collect();
+        Self {
+            suite: suite.to_string(),
+            sources,
+        }
+    }
+
+    /// Returns true if every source is reachable.
+    pub fn is_success(&self) -> bool {
+        self.sources.iter().all(SourceCheck::is_reachable)
+    }
+
+    /// Returns the sources that are not reachable.
+    pub fn unreachable(&self) -> impl Iterator<Item = &SourceCheck> {
+        self.sources.iter().filter(|check| !check.is_reachable())
+    }
+
+    /// Returns `Ok` if every source is reachable and
+    /// [`TermError::PreflightFailed`] otherwise.
+    pub fn into_result(self) -> Result<Self> {
+        if self.is_success() {
+            return Ok(self);
+        }
+        warn!(suite.name = %self.suite, "{self}");
+        Err(TermError::PreflightFailed(Box::new(self)))
+    }
+}
+
+impl fmt::Display for PreflightReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unreachable: Vec<_> = self.unreachable().collect();
+        write!(
+            f,
+            "{} of {} sources of suite '{}' are unreachable",
+            unreachable.len(),
+            self.sources.len(),
+            self.suite
+        )?;
+        for check in unreachable {
+            write!(
+                f,
+                "\n  - {} ({}): {}",
+                check.name,
+                check.description,
+                check.problem().unwrap_or_default()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::CsvSource;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[tokio::test]
+    async fn test_report_lists_unreachable_sources() -> Result<()> {
+        let mut file = NamedTempFile::with_suffix(".csv")?;
+        writeln!(file, "id\n1")?;
+        let path = file.path().to_string_lossy().to_string();
+
+        let orders: Arc<dyn DataSource> = Arc::new(CsvSource::new(path.clone())?);
+        let customers: Arc<dyn DataSource> = Arc::new(CsvSource::new(format!("{path}.missing"))?);
+        let sources = vec![
+            ("orders".to_string(), orders),
+            ("customers".to_string(), customers),
+        ];
+        let report = PreflightReport::probe("sales", &sources).await;
+        assert!(!report.is_success());
+        assert!(report.sources[0].is_reachable());
+        let unreachable: Vec<_> = report.unreachable().map(|check| &check.name).collect();
+        assert_eq!(unreachable, ["customers"]);
+
+        let message = report.to_string();
+        assert!(message.starts_with("1 of 2 sources of suite 'sales' are unreachable"));
+        assert!(message.contains("customers (CSV file: "), "{message}");
+        assert!(message.contains("no data found"), "{message}");
+
+        match report.into_result() {
+            Err(TermError::PreflightFailed(report)) => assert_eq!(report.sources.len(), 2),
+            other => panic!("expected a preflight failure, got {other:?}"),
+        }
+        Ok(())
+    }
+}
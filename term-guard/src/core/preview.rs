@@ -0,0 +1,77 @@
+//! Preview runs on a sample of the validated table.
+//!
+//! While a suite is being written, running it on the full data to find a typo
+//! in a column name or a mis-scaled threshold is slow. A run with
+//! [`RunOptions::with_preview`](super::RunOptions::with_preview) runs every
+//! check on the first rows of the validated table instead. The rows are copied
+//! into a separate session context, so the caller's context and its tables are
+//! left untouched.
+//!
+//! Constraints whose result on a sample would be misleading, such as row count
+//! assertions and cross-table reconciliations, report
+//! [`Constraint::requires_full_data`](super::Constraint::requires_full_data) and
+//! are skipped. The report of a preview run carries a [`PreviewSummary`].
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use datafusion::datasource::MemTable;
+use datafusion::prelude::*;
+use futures::StreamExt;
+use tracing::debug;
+
+use super::PreviewSummary;
+use crate::prelude::*;
+
+/// Options of a preview run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreviewSpec {
+    /// Maximum number of rows read from the validated table
+    pub limit: usize,
+}
+
+impl PreviewSpec {
+    /// Creates a spec reading at most `limit` rows.
+    pub fn new(limit: usize) -> Self {
+        Self { limit }
+    }
+
+    /// Copies the first rows of `source` into a new context as table `name`.
+    ///
+    /// The new context has the configuration and functions of `ctx` but none of
+    /// its tables.
+    pub(crate) async fn sample(
+        &self,
+        ctx: &SessionContext,
+        name: &str,
+        source: DataFrame,
+    ) -> Result<(SessionContext, PreviewSummary)> {
+        let preview = SessionContext::new_with_config(ctx.copied_config());
+        let state = ctx.state();
+        for udf in state.scalar_functions().values() {
+            preview.register_udf(udf.as_ref().clone());
+        }
+        for udaf in state.aggregate_functions().values() {
+            preview.register_udaf(udaf.as_ref().clone());
+        }
+
+        let df = source.limit(0, Some(self.limit))?;
+        let schema = df.schema().inner().clone();
+        let mut stream = df.execute_stream().await?;
+        let (mut batches, mut rows) = (Vec::new(), 0);
+        while let Some(batch) = stream.next().await {
+            let batch = batch?;
+            rows += batch.num_rows();
+            batches.push(batch);
+        }
+        preview.register_table(name, Arc::new(MemTable::try_new(schema, vec![batches])?))?;
+        debug!(table.name = %name, preview.rows = rows, "Registered preview sample");
+
+        let summary = PreviewSummary {
+            limit: self.limit,
+            sampled_rows: BTreeMap::from([(name.to_string(), rows)]),
+            skipped_constraints: Vec::new(),
+        };
+        Ok((preview, summary))
+    }
+}
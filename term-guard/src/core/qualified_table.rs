@@ -0,0 +1,238 @@
+//! Qualified table references for multi-table constraints.
+//!
+//! Tables can be registered under custom catalogs and schemas
+//! (`my_catalog.staging.orders`). Constraints that join or compare several tables
+//! use [`QualifiedTable`] to parse such names, validate every part with
+//! [`SqlSecurity`], and render them as quoted SQL identifiers.
+
+use super::current_validation_context;
+use crate::error::{Result, TermError};
+use crate::security::SqlSecurity;
+use std::fmt;
+
+/// A table name optionally qualified by a schema and catalog.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::core::QualifiedTable;
+///
+/// let table = QualifiedTable::parse("my_catalog.staging.orders").unwrap();
+/// assert_eq!(table.catalog(), Some("my_catalog"));
+/// assert_eq!(table.schema(), Some("staging"));
+/// assert_eq!(table.table(), "orders");
+/// assert_eq!(table.to_sql().unwrap(), r#""my_catalog"."staging"."orders""#);
+///
+/// let table = QualifiedTable::parse("orders").unwrap().with_default_schema("staging");
+/// assert_eq!(table.to_string(), "staging.orders");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QualifiedTable {
+    catalog: Option<String>,
+    schema: Option<String>,
+    table: String,
+}
+
+impl QualifiedTable {
+    /// Parses a `table`, `schema.table` or `catalog.schema.table` reference.
+    ///
+    /// `context::table` names a table of a secondary session context registered with
+    /// [`MultiSourceValidator::with_secondary_context`](super::MultiSourceValidator::with_secondary_context);
+    /// it refers to the copy in the `context` schema of the primary context.
+    ///
+    /// Every part is validated with [`SqlSecurity::validate_identifier`].
+    pub fn parse(name: &str) -> Result<Self> {
+        if let Some((context, table)) = name.split_once("::") {
+            let parts = split_parts(table)?;
+            let [table] = parts.as_slice() else {
+                return Err(TermError::SecurityError(format!(
+                    "Invalid table reference '{name}': expected context::table"
+                )));
+            };
+            SqlSecurity::validate_identifier(context)?;
+            return Ok(Self {
+                catalog: None,
+                schema: Some(context.to_string()),
+                table: table.to_string(),
+            });
+        }
+
+        let parts = split_parts(name)?;
+        let (catalog, schema, table) = match parts.as_slice() {
+            [table] => (None, None, table),
+            [schema, table] => (None, Some(schema), table),
+            [catalog, schema, table] => (Some(catalog), Some(schema), table),
+            _ => {
+                return Err(TermError::SecurityError(format!(
+                    "Invalid table reference '{name}': expected table, schema.table or catalog.schema.table"
+                )))
+            }
+        };
+
+        Ok(Self {
+            catalog: catalog.map(|c| c.to_string()),
+            schema: schema.map(|s| s.to_string()),
+            table: table.to_string(),
+        })
+    }
+
+    /// Parses a table reference and applies the default schema of the current
+    /// [`ValidationContext`](super::ValidationContext), if any.
+    pub fn resolve(name: &str) -> Result<Self> {
+        let table = Self::parse(name)?;
+        Ok(match current_validation_context().default_schema() {
+            Some(schema) => table.with_default_schema(schema),
+            None => table,
+        })
+    }
+
+    /// Qualifies the table with `schema` if it does not name a schema already.
+    pub fn with_default_schema(mut self, schema: impl Into<String>) -> Self {
+        if self.schema.is_none() {
+            self.schema = Some(schema.into());
+        }
+        self
+    }
+
+    /// Returns the catalog part, if present.
+    pub fn catalog(&self) -> Option<&str> {
+        self.catalog.as_deref()
+    }
+
+    /// Returns the schema part, if present.
+    pub fn schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+
+    /// Returns the unqualified table name.
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Renders the reference as a SQL identifier with every part quoted.
+    pub fn to_sql(&self) -> Result<String> {
+        let parts = [self.catalog.as_deref(), self.schema.as_deref()]
+            .into_iter()
+            .flatten()
+            .chain(std::iter::once(self.table.as_str()))
+            .map(SqlSecurity::escape_identifier)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(parts.join("."))
+    }
+}
+
+impl fmt::Display for QualifiedTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(catalog) = &self.catalog {
+            write!(f, "{catalog}.")?;
+        }
+        if let Some(schema) = &self.schema {
+            write!(f, "{schema}.")?;
+        }
+        write!(f, "{}", self.table)
+    }
+}
+
+/// Splits a qualified column (`[[catalog.]schema.]table.column`) into its table
+/// reference and column name, applying the default schema of the current context.
+pub(crate) fn parse_qualified_column(
+    constraint: &str,
+    qualified_column: &str,
+) -> Result<(QualifiedTable, String)> {
+    let invalid = || {
+        TermError::constraint_evaluation(
+            constraint,
+            format!(
+                "Column must be qualified ([catalog.][schema.]table.column): '{qualified_column}'"
+            ),
+        )
+    };
+
+    let (table, column) = qualified_column.rsplit_once('.').ok_or_else(invalid)?;
+    if table.split('.').count() > 3 {
+        return Err(invalid());
+    }
+
+    SqlSecurity::validate_identifier(column)?;
+    Ok((QualifiedTable::resolve(table)?, column.to_string()))
+}
+
+fn split_parts(name: &str) -> Result<Vec<&str>> {
+    let parts: Vec<&str> = name.split('.').collect();
+    for part in &parts {
+        SqlSecurity::validate_identifier(part)?;
+    }
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ValidationContext, CURRENT_CONTEXT};
+
+    #[test]
+    fn test_parse_table_references() {
+        let table = QualifiedTable::parse("orders").unwrap();
+        assert_eq!((table.catalog(), table.schema()), (None, None));
+        assert_eq!(table.to_sql().unwrap(), r#""orders""#);
+
+        let table = QualifiedTable::parse("staging.orders").unwrap();
+        assert_eq!(table.schema(), Some("staging"));
+        assert_eq!(table.to_sql().unwrap(), r#""staging"."orders""#);
+
+        assert!(QualifiedTable::parse("a.b.c.d").is_err());
+        assert!(QualifiedTable::parse("staging..orders").is_err());
+        assert!(QualifiedTable::parse("staging.orders; DROP TABLE users").is_err());
+    }
+
+    #[test]
+    fn test_parse_secondary_context_references() {
+        let table = QualifiedTable::parse("pg::customers").unwrap();
+        assert_eq!(table.schema(), Some("pg"));
+        assert_eq!(table.table(), "customers");
+        assert_eq!(table.to_sql().unwrap(), r#""pg"."customers""#);
+
+        assert!(QualifiedTable::parse("pg::public.customers").is_err());
+        assert!(QualifiedTable::parse("pg; DROP TABLE x::customers").is_err());
+    }
+
+    #[test]
+    fn test_default_schema_only_applies_to_unqualified_names() {
+        let table = QualifiedTable::parse("orders")
+            .unwrap()
+            .with_default_schema("staging");
+        assert_eq!(table.to_string(), "staging.orders");
+
+        let table = QualifiedTable::parse("public.orders")
+            .unwrap()
+            .with_default_schema("staging");
+        assert_eq!(table.to_string(), "public.orders");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_uses_context_default_schema() {
+        let ctx = ValidationContext::new("orders").with_default_schema("staging");
+        let table = CURRENT_CONTEXT
+            .scope(ctx, async { QualifiedTable::resolve("orders").unwrap() })
+            .await;
+        assert_eq!(table.to_string(), "staging.orders");
+        assert_eq!(
+            QualifiedTable::resolve("orders").unwrap().to_string(),
+            "orders"
+        );
+    }
+
+    #[test]
+    fn test_parse_qualified_column() {
+        let (table, column) = parse_qualified_column("test", "cat.staging.orders.id").unwrap();
+        assert_eq!(table.to_string(), "cat.staging.orders");
+        assert_eq!(column, "id");
+
+        let (table, column) = parse_qualified_column("test", "orders.id").unwrap();
+        assert_eq!(table.to_string(), "orders");
+        assert_eq!(column, "id");
+
+        assert!(parse_qualified_column("test", "id").is_err());
+        assert!(parse_qualified_column("test", "a.b.c.d.id").is_err());
+    }
+}
@@ -0,0 +1,388 @@
+//! Row-level quarantine output for violating rows.
+//!
+//! Row-level constraints (completeness, format, length and containment) can
+//! export every row that violates them to a [`QuarantineSink`], so the rows can
+//! be fixed and re-ingested. Rows are streamed batch by batch from DataFusion
+//! into a Parquet file, a CSV file, or an in-memory table registered in the
+//! session, optionally restricted to selected columns plus a violation reason
+//! column. A row limit guards against exporting an entire table by accident.
+//!
+//! The number of quarantined rows and their location are recorded in
+//! [`ConstraintResult::quarantine`](super::ConstraintResult::quarantine) and
+//! listed in [`ValidationReport::quarantined`](super::ValidationReport::quarantined).
+//!
+//! Aggregate-only constraints (statistics, uniqueness, quantiles, ...) have no
+//! notion of a violating row and do not accept a sink.
+//!
+//! # Example
+//!
+//! ```rust
+//! use term_guard::constraints::{FormatConstraint, LengthConstraint};
+//! use term_guard::core::{Check, ConstraintOptions, QuarantineSink};
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let check = Check::builder("contact_quality")
+//!     .completeness(
+//!         "email",
+//!         ConstraintOptions::new().with_quarantine(QuarantineSink::memory("missing_emails")),
+//!     )
+//!     .with_constraint(
+//!         FormatConstraint::email("email", 0.99)?.with_quarantine(
+//!             QuarantineSink::parquet("/tmp/quarantine/bad_emails.parquet")
+//!                 .columns(vec!["id", "email"])
+//!                 .max_rows(10_000),
+//!         ),
+//!     )
+//!     .with_constraint(
+//!         LengthConstraint::max("name", 100)
+//!             .with_quarantine(QuarantineSink::csv("/tmp/quarantine/long_names.csv")),
+//!     )
+//!     .build();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use arrow::record_batch::RecordBatch;
+use datafusion::datasource::MemTable;
+use datafusion::parquet::arrow::ArrowWriter;
+use datafusion::physical_plan::execute_stream;
+use datafusion::prelude::*;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument, warn};
+
+use super::{current_validation_context, QueryMetrics};
+use crate::error::{Result, TermError};
+use crate::security::SqlSecurity;
+
+/// Default name of the column describing why a row was quarantined.
+pub const DEFAULT_REASON_COLUMN: &str = "violation_reason";
+
+/// Default maximum number of rows written to a sink per constraint.
+pub const DEFAULT_MAX_QUARANTINE_ROWS: usize = 100_000;
+
+/// Where quarantined rows are written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum QuarantineTarget {
+    /// A Parquet file, overwritten on every run
+    Parquet(PathBuf),
+    /// A CSV file with a header row, overwritten on every run
+    Csv(PathBuf),
+    /// An in-memory table registered in the validated `SessionContext`
+    MemTable(String),
+}
+
+/// Destination and shape of the rows exported by a row-level constraint.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuarantineSink {
+    target: QuarantineTarget,
+    columns: Option<Vec<String>>,
+    reason_column: Option<String>,
+    max_rows: usize,
+}
+
+impl QuarantineSink {
+    fn new(target: QuarantineTarget) -> Self {
+        Self {
+            target,
+            columns: None,
+            reason_column: Some(DEFAULT_REASON_COLUMN.to_string()),
+            max_rows: DEFAULT_MAX_QUARANTINE_ROWS,
+        }
+    }
+
+    /// Writes violating rows to a Parquet file.
+    pub fn parquet(path: impl Into<PathBuf>) -> Self {
+        Self::new(QuarantineTarget::Parquet(path.into()))
+    }
+
+    /// Writes violating rows to a CSV file.
+    pub fn csv(path: impl Into<PathBuf>) -> Self {
+        Self::new(QuarantineTarget::Csv(path.into()))
+    }
+
+    /// Registers violating rows as an in-memory table named `table_name`.
+    ///
+    /// An existing table with the same name is replaced.
+    pub fn memory(table_name: impl Into<String>) -> Self {
+        Self::new(QuarantineTarget::MemTable(table_name.into()))
+    }
+
+    /// Restricts the exported rows to the given columns (default: all columns).
+    pub fn columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the name of the violation reason column (default `violation_reason`).
+    pub fn reason_column(mut self, name: impl Into<String>) -> Self {
+        self.reason_column = Some(name.into());
+        self
+    }
+
+    /// Exports the rows without a violation reason column.
+    pub fn without_reason(mut self) -> Self {
+        self.reason_column = None;
+        self
+    }
+
+    /// Sets the maximum number of rows written; further rows are dropped and the
+    /// export is marked as truncated.
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Returns the sink's target.
+    pub fn target(&self) -> &QuarantineTarget {
+        &self.target
+    }
+
+    /// Returns a human-readable location of the sink (file path or table name).
+    pub fn location(&self) -> String {
+        match &self.target {
+            QuarantineTarget::Parquet(path) | QuarantineTarget::Csv(path) => {
+                path.display().to_string()
+            }
+            QuarantineTarget::MemTable(name) => name.clone(),
+        }
+    }
+
+    /// Streams the rows of the validated table matching `violation` into the sink.
+    ///
+    /// `violation` is a SQL predicate selecting violating rows and `reason` a SQL
+    /// expression describing the violation; both must be built from escaped
+    /// identifiers by the calling constraint.
+    #[instrument(skip(self, ctx), fields(location = %self.location()))]
+    pub(crate) async fn write(
+        &self,
+        ctx: &SessionContext,
+        constraint: &str,
+        violation: &str,
+        reason: &str,
+    ) -> Result<QuarantineSummary> {
+        let validation_ctx = current_validation_context();
+        let table_name = validation_ctx.table_name();
+
+        let mut projection = match &self.columns {
+            Some(columns) => columns
+                .iter()
+                .map(|column| SqlSecurity::escape_identifier(column))
+                .collect::<Result<Vec<_>>>()?,
+            None => vec!["*".to_string()],
+        };
+        if let Some(reason_column) = &self.reason_column {
+            let reason_column = SqlSecurity::escape_identifier(reason_column)?;
+            projection.push(format!("{reason} AS {reason_column}"));
+        }
+
+        // One extra row tells whether the limit cut off violations
+        let sql = format!(
+            "SELECT {} FROM {table_name} WHERE {violation} LIMIT {}",
+            projection.join(", "),
+            self.max_rows.saturating_add(1)
+        );
+
+        let df = ctx.sql(&sql).await?;
+        let task_ctx = Arc::new(df.task_ctx());
+        let plan = df.create_physical_plan().await?;
+        let schema = plan.schema();
+        let mut stream = execute_stream(Arc::clone(&plan), task_ctx)?;
+
+        let mut writer = QuarantineWriter::create(&self.target, schema.clone())?;
+        let mut rows = 0usize;
+        let mut truncated = false;
+        while let Some(batch) = stream.next().await {
+            let batch = batch?;
+            let remaining = self.max_rows - rows;
+            if batch.num_rows() > remaining {
+                truncated = true;
+                if remaining > 0 {
+                    writer.write(&batch.slice(0, remaining))?;
+                    rows += remaining;
+                }
+                break;
+            }
+            writer.write(&batch)?;
+            rows += batch.num_rows();
+        }
+        drop(stream);
+
+        if let Some(collector) = validation_ctx.performance_collector() {
+            collector.record(&QueryMetrics::from_plan(plan.as_ref()));
+        }
+
+        writer.finish(ctx, schema)?;
+
+        if truncated {
+            warn!(
+                constraint,
+                max_rows = self.max_rows,
+                location = %self.location(),
+                "Quarantine output truncated at the row limit"
+            );
+        }
+        debug!(constraint, rows, location = %self.location(), "Quarantined violating rows");
+
+        Ok(QuarantineSummary {
+            rows: rows as u64,
+            location: self.location(),
+            truncated,
+        })
+    }
+}
+
+/// Outcome of writing violating rows to a [`QuarantineSink`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantineSummary {
+    /// Number of rows written to the sink
+    pub rows: u64,
+    /// File path or table name the rows were written to
+    pub location: String,
+    /// Whether violating rows were dropped because of the sink's row limit
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+enum QuarantineWriter {
+    Parquet(ArrowWriter<File>),
+    Csv(arrow::csv::Writer<File>, bool),
+    MemTable(String, Vec<RecordBatch>),
+}
+
+impl QuarantineWriter {
+    fn create(target: &QuarantineTarget, schema: arrow::datatypes::SchemaRef) -> Result<Self> {
+        Ok(match target {
+            QuarantineTarget::Parquet(path) => {
+                let file = create_file(path)?;
+                Self::Parquet(ArrowWriter::try_new(file, schema, None).map_err(|e| {
+                    TermError::Internal(format!("Failed to create Parquet quarantine writer: {e}"))
+                })?)
+            }
+            QuarantineTarget::Csv(path) => {
+                Self::Csv(arrow::csv::Writer::new(create_file(path)?), false)
+            }
+            QuarantineTarget::MemTable(name) => Self::MemTable(name.clone(), Vec::new()),
+        })
+    }
+
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        match self {
+            Self::Parquet(writer) => writer.write(batch).map_err(|e| {
+                TermError::Internal(format!("Failed to write quarantined rows: {e}"))
+            })?,
+            Self::Csv(writer, written) => {
+                writer.write(batch)?;
+                *written = true;
+            }
+            Self::MemTable(_, batches) => batches.push(batch.clone()),
+        }
+        Ok(())
+    }
+
+    fn finish(self, ctx: &SessionContext, schema: arrow::datatypes::SchemaRef) -> Result<()> {
+        match self {
+            Self::Parquet(writer) => {
+                writer.close().map_err(|e| {
+                    TermError::Internal(format!("Failed to finish Parquet quarantine file: {e}"))
+                })?;
+            }
+            Self::Csv(mut writer, written) => {
+                // Keep the header even when no row was quarantined
+                if !written {
+                    writer.write(&RecordBatch::new_empty(schema))?;
+                }
+            }
+            Self::MemTable(name, batches) => {
+                let table = MemTable::try_new(schema, vec![batches])?;
+                ctx.deregister_table(name.as_str())?;
+                ctx.register_table(name.as_str(), Arc::new(table))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn create_file(path: &std::path::Path) -> Result<File> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(File::create(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ValidationContext, CURRENT_CONTEXT};
+
+    async fn context() -> SessionContext {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE data (id INT, email VARCHAR) AS VALUES (1, 'a@x.com'), (2, NULL), (3, NULL), (4, NULL)")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        ctx
+    }
+
+    async fn write(ctx: &SessionContext, sink: &QuarantineSink) -> QuarantineSummary {
+        CURRENT_CONTEXT
+            .scope(
+                ValidationContext::new("data"),
+                sink.write(ctx, "test", r#""email" IS NULL"#, "'email is null'"),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_memory_sink_with_selected_columns() {
+        let ctx = context().await;
+        let sink = QuarantineSink::memory("bad_rows").columns(vec!["id"]);
+
+        let summary = write(&ctx, &sink).await;
+        assert_eq!(summary.rows, 3);
+        assert_eq!(summary.location, "bad_rows");
+        assert!(!summary.truncated);
+
+        let batches = ctx
+            .sql("SELECT id, violation_reason FROM bad_rows ORDER BY id")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(batches[0].num_columns(), 2);
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 3);
+
+        // A second run replaces the table
+        write(&ctx, &sink).await;
+        let count = ctx.table("bad_rows").await.unwrap().count().await.unwrap();
+        assert_eq!(count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_max_rows_truncates_output() {
+        let ctx = context().await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("bad_rows.csv");
+        let sink = QuarantineSink::csv(&path).max_rows(2).without_reason();
+
+        let summary = write(&ctx, &sink).await;
+        assert_eq!(summary.rows, 2);
+        assert!(summary.truncated);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().next(), Some("id,email"));
+        assert_eq!(contents.lines().count(), 3);
+    }
+}
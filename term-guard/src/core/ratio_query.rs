@@ -0,0 +1,247 @@
+//! Queries measuring the fraction of rows satisfying a predicate.
+//!
+//! Format, length, containment and nested completeness constraints measure the
+//! ratio of conforming rows to counted rows. [`RatioQueryBuilder`] generates
+//! their SQL, so that they agree on how nulls, row filters and samples are
+//! counted, and extracts the counts as [`RatioCounts`].
+//! Completeness of top-level columns is measured from the row and null counts
+//! shared through the [metric cache](super::MetricCache) instead.
+//!
+//! | [`NullPolicy`] | A null row is |
+//! |----------------|---------------|
+//! | `Violating` | counted and not conforming |
+//! | `Conforming` | counted and conforming |
+//! | `Excluded` | not counted |
+//!
+//! A table without counted rows has no ratio; constraints report it as
+//! [`ConstraintResult::empty_table`](super::ConstraintResult::empty_table) or
+//! skip it.
+
+use arrow::array::{Array, Int64Array};
+use datafusion::prelude::SessionContext;
+
+use super::{collect_with_metrics, PreviewSpec};
+use crate::error::{Result, TermError};
+
+/// How a ratio query counts rows whose column is null.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NullPolicy {
+    /// Null rows are counted and never conform
+    #[default]
+    Violating,
+    /// Null rows are counted and always conform
+    Conforming,
+    /// Null rows are left out of both counts
+    Excluded,
+}
+
+/// Builds the query counting the rows of a table that satisfy a predicate.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::core::{NullPolicy, RatioQueryBuilder};
+///
+/// let sql = RatioQueryBuilder::new("orders", r#""status" IN ('open', 'closed')"#)
+///     .nulls(r#""status""#, NullPolicy::Excluded)
+///     .filter(r#""region" = 'EU'"#)
+///     .sql();
+/// assert_eq!(
+///     sql,
+///     r#"SELECT COUNT(CASE WHEN ("status" IN ('open', 'closed')) THEN 1 END) AS conforming, COUNT(*) AS total FROM orders WHERE "status" IS NOT NULL AND ("region" = 'EU')"#
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct RatioQueryBuilder {
+    table: String,
+    predicate: String,
+    nulls: Option<(String, NullPolicy)>,
+    filter: Option<String>,
+    sample: Option<PreviewSpec>,
+}
+
+impl RatioQueryBuilder {
+    /// Creates a query counting the rows of `table` satisfying `predicate`.
+    ///
+    /// The table name and predicate are inserted into the SQL as given, so
+    /// identifiers must already be escaped. Without [`nulls`](Self::nulls), a
+    /// row conforms when the predicate is true and a null predicate does not
+    /// conform.
+    pub fn new(table: impl Into<String>, predicate: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            predicate: predicate.into(),
+            nulls: None,
+            filter: None,
+            sample: None,
+        }
+    }
+
+    /// Counts the rows where `column` is null according to `policy`.
+    pub fn nulls(mut self, column: impl Into<String>, policy: NullPolicy) -> Self {
+        self.nulls = Some((column.into(), policy));
+        self
+    }
+
+    /// Counts only the rows satisfying `filter`.
+    pub fn filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Counts only the first rows of the table, before the filter is applied.
+    pub fn sample(mut self, spec: PreviewSpec) -> Self {
+        self.sample = Some(spec);
+        self
+    }
+
+    /// Returns the query, with the columns `conforming` and `total`.
+    pub fn sql(&self) -> String {
+        let predicate = &self.predicate;
+        let mut conditions = Vec::new();
+        let conforming = match &self.nulls {
+            Some((column, NullPolicy::Violating)) => {
+                format!("{column} IS NOT NULL AND ({predicate})")
+            }
+            Some((column, NullPolicy::Conforming)) => format!("({predicate}) OR {column} IS NULL"),
+            Some((column, NullPolicy::Excluded)) => {
+                conditions.push(format!("{column} IS NOT NULL"));
+                format!("({predicate})")
+            }
+            None => format!("({predicate})"),
+        };
+        if let Some(filter) = &self.filter {
+            conditions.push(format!("({filter})"));
+        }
+
+        let source = match &self.sample {
+            Some(spec) => format!(
+                "(SELECT * FROM {} LIMIT {}) AS sample",
+                self.table, spec.limit
+            ),
+            None => self.table.clone(),
+        };
+        let mut sql = format!(
+            "SELECT COUNT(CASE WHEN {conforming} THEN 1 END) AS conforming, COUNT(*) AS total FROM {source}"
+        );
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        sql
+    }
+
+    /// Runs the query and returns its counts.
+    pub async fn execute(&self, ctx: &SessionContext) -> Result<RatioCounts> {
+        let batches = collect_with_metrics(ctx.sql(&self.sql()).await?).await?;
+        let Some(batch) = batches.iter().find(|batch| batch.num_rows() > 0) else {
+            return Ok(RatioCounts::default());
+        };
+
+        let count = |index: usize| {
+            batch
+                .column(index)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .filter(|counts| !counts.is_null(0))
+                .map(|counts| counts.value(0).max(0) as u64)
+                .ok_or_else(|| TermError::Internal("Failed to extract ratio counts".to_string()))
+        };
+        Ok(RatioCounts {
+            conforming: count(0)?,
+            total: count(1)?,
+        })
+    }
+}
+
+/// The counts of a ratio query.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RatioCounts {
+    /// Number of counted rows that conform
+    pub conforming: u64,
+    /// Number of counted rows
+    pub total: u64,
+}
+
+impl RatioCounts {
+    /// Returns the number of counted rows that do not conform.
+    pub fn violating(&self) -> u64 {
+        self.total - self.conforming
+    }
+
+    /// Returns the fraction of counted rows that conform, or `None` without
+    /// counted rows.
+    pub fn ratio(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.conforming as f64 / self.total as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_null_policies() {
+        let query = RatioQueryBuilder::new("t", "\"c\" > 0");
+        assert_eq!(
+            query.sql(),
+            "SELECT COUNT(CASE WHEN (\"c\" > 0) THEN 1 END) AS conforming, COUNT(*) AS total FROM t"
+        );
+        assert!(query
+            .clone()
+            .nulls("\"c\"", NullPolicy::Violating)
+            .sql()
+            .contains("WHEN \"c\" IS NOT NULL AND (\"c\" > 0) THEN"));
+        assert!(query
+            .clone()
+            .nulls("\"c\"", NullPolicy::Conforming)
+            .sql()
+            .contains("WHEN (\"c\" > 0) OR \"c\" IS NULL THEN"));
+        assert!(query
+            .nulls("\"c\"", NullPolicy::Excluded)
+            .sql()
+            .ends_with("FROM t WHERE \"c\" IS NOT NULL"));
+    }
+
+    #[test]
+    fn test_filter_and_sample() {
+        let sql = RatioQueryBuilder::new("t", "\"c\" > 0")
+            .filter("\"d\" = 1 OR \"d\" = 2")
+            .sample(PreviewSpec::new(100))
+            .sql();
+        assert!(sql.ends_with(
+            "FROM (SELECT * FROM t LIMIT 100) AS sample WHERE (\"d\" = 1 OR \"d\" = 2)"
+        ));
+    }
+
+    #[test]
+    fn test_counts() {
+        let counts = RatioCounts {
+            conforming: 3,
+            total: 4,
+        };
+        assert_eq!(counts.violating(), 1);
+        assert_eq!(counts.ratio(), Some(0.75));
+        assert_eq!(RatioCounts::default().ratio(), None);
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE t (c INT, d INT) AS VALUES (1, 1), (-1, 1), (NULL, 2), (2, 3)")
+            .await?
+            .collect()
+            .await?;
+
+        let query = RatioQueryBuilder::new("t", "c > 0").nulls("c", NullPolicy::Excluded);
+        let counts = query.execute(&ctx).await?;
+        assert_eq!((counts.conforming, counts.total), (2, 3));
+
+        let counts = query.clone().filter("d = 2").execute(&ctx).await?;
+        assert_eq!(counts, RatioCounts::default());
+
+        let counts = query.sample(PreviewSpec::new(2)).execute(&ctx).await?;
+        assert_eq!((counts.conforming, counts.total), (1, 2));
+        Ok(())
+    }
+}
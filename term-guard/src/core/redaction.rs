@@ -0,0 +1,265 @@
+//! Redaction of column values quoted in validation results.
+//!
+//! Several constraints quote values of the validated data when they fail:
+//! foreign key and join coverage constraints list unmatched keys, cardinality
+//! constraints list the failing keys, expression equality and cross-table sum
+//! constraints list mismatching rows, and category baseline and top-k stability
+//! constraints list values. For columns holding personal data, these messages
+//! would copy the data into logs, reports and whatever consumes them.
+//!
+//! A [`RedactionPolicy`] maps columns to a [`RedactionStrategy`]. Set on a suite
+//! with [`ValidationSuiteBuilder::redaction`](super::ValidationSuiteBuilder::redaction)
+//! or on an [`Evaluator`](super::Evaluator), it is available to constraints
+//! through the [`ValidationContext`](super::ValidationContext), and constraints
+//! apply it while rendering values, before they enter a
+//! [`ConstraintResult`](super::ConstraintResult). Reports, formatters, hooks and
+//! result caches downstream only ever see redacted values.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::core::{RedactionPolicy, RedactionStrategy};
+//!
+//! let policy = RedactionPolicy::new()
+//!     .column("email", RedactionStrategy::mask_middle(2))
+//!     .column("ssn", RedactionStrategy::Drop)
+//!     .columns_matching(|column| column.ends_with("_token"), RedactionStrategy::MaskAll);
+//!
+//! assert_eq!(policy.redact("email", "jane@example.com"), "ja************om");
+//! assert_eq!(policy.redact("customers.ssn", "078-05-1120"), "<redacted>");
+//! assert_eq!(policy.redact("api_token", "abc"), "***");
+//! assert_eq!(policy.redact("country", "NZ"), "NZ");
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
+
+use crate::compliance::PiiHit;
+
+/// Placeholder for values redacted with [`RedactionStrategy::Drop`].
+pub const REDACTED: &str = "<redacted>";
+
+/// How the values of a sensitive column are rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RedactionStrategy {
+    /// Replaces every character with `*`, keeping the length
+    MaskAll,
+    /// Keeps the first and last `visible` characters and masks the rest
+    ///
+    /// Values of at most `2 * visible` characters are masked entirely.
+    MaskMiddle {
+        /// Number of characters kept at each end
+        visible: usize,
+    },
+    /// Replaces the value with a salted SHA-256 digest
+    ///
+    /// Equal values get equal digests, so repeated values can still be told
+    /// apart from distinct ones.
+    Hash {
+        /// Salt prepended to the value before hashing
+        salt: String,
+    },
+    /// Replaces the value with [`REDACTED`], revealing nothing about it
+    Drop,
+}
+
+impl RedactionStrategy {
+    /// Keeps `visible` characters at each end of values.
+    pub fn mask_middle(visible: usize) -> Self {
+        Self::MaskMiddle { visible }
+    }
+
+    /// Hashes values with `salt`.
+    pub fn hash(salt: impl Into<String>) -> Self {
+        Self::Hash { salt: salt.into() }
+    }
+
+    /// Renders `value` according to the strategy.
+    pub fn apply(&self, value: &str) -> String {
+        match self {
+            Self::MaskAll => "*".repeat(value.chars().count()),
+            Self::MaskMiddle { visible } => {
+                let chars: Vec<char> = value.chars().collect();
+                if chars.len() <= 2 * visible {
+                    return "*".repeat(chars.len());
+                }
+                let mut masked: String = chars[..*visible].iter().collect();
+                masked.push_str(&"*".repeat(chars.len() - 2 * visible));
+                masked.extend(&chars[chars.len() - visible..]);
+                masked
+            }
+            Self::Hash { salt } => {
+                let mut hasher = Sha256::new();
+                hasher.update(salt.as_bytes());
+                hasher.update(value.as_bytes());
+                let digest = hex::encode(hasher.finalize());
+                format!("sha256:{}", &digest[..16])
+            }
+            Self::Drop => REDACTED.to_string(),
+        }
+    }
+}
+
+/// Which columns a rule applies to.
+#[derive(Clone)]
+enum ColumnMatcher {
+    Name(String),
+    Predicate(Arc<dyn Fn(&str) -> bool + Send + Sync>),
+}
+
+impl ColumnMatcher {
+    fn matches(&self, column: &str) -> bool {
+        match self {
+            // A qualified name such as `orders.email` matches the rule for `email`
+            Self::Name(name) => {
+                column == name
+                    || column
+                        .strip_suffix(name.as_str())
+                        .is_some_and(|prefix| prefix.ends_with('.'))
+            }
+            Self::Predicate(predicate) => predicate(column),
+        }
+    }
+}
+
+impl fmt::Debug for ColumnMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name(name) => write!(f, "{name:?}"),
+            Self::Predicate(_) => write!(f, "<predicate>"),
+        }
+    }
+}
+
+/// Maps columns to the strategy redacting their values.
+///
+/// Rules are tried in the order they were added and the first matching rule
+/// wins. A rule for a column name also matches the name qualified with a table
+/// or a struct, so a rule for `email` applies to `customers.email`. Columns
+/// without a matching rule are not redacted.
+#[derive(Debug, Clone, Default)]
+pub struct RedactionPolicy {
+    rules: Vec<(ColumnMatcher, RedactionStrategy)>,
+}
+
+impl RedactionPolicy {
+    /// Creates a policy redacting nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Redacts the values of `column` with `strategy`.
+    pub fn column(mut self, column: impl Into<String>, strategy: RedactionStrategy) -> Self {
+        self.rules
+            .push((ColumnMatcher::Name(column.into()), strategy));
+        self
+    }
+
+    /// Redacts the values of every column for which `predicate` returns true.
+    ///
+    /// The predicate receives the column name as the constraint knows it, which
+    /// may be qualified with a table.
+    pub fn columns_matching<F>(mut self, predicate: F, strategy: RedactionStrategy) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.rules
+            .push((ColumnMatcher::Predicate(Arc::new(predicate)), strategy));
+        self
+    }
+
+    /// Redacts the values of every column a [`PiiScan`](crate::compliance::PiiScan)
+    /// found personal data in.
+    pub fn pii_columns<'a, I>(mut self, hits: I, strategy: RedactionStrategy) -> Self
+    where
+        I: IntoIterator<Item = &'a PiiHit>,
+    {
+        for hit in hits {
+            self = self.column(hit.column.clone(), strategy.clone());
+        }
+        self
+    }
+
+    /// Returns true if the policy has no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Returns the strategy redacting `column`, if any.
+    pub fn strategy_for(&self, column: &str) -> Option<&RedactionStrategy> {
+        self.rules
+            .iter()
+            .find(|(matcher, _)| matcher.matches(column))
+            .map(|(_, strategy)| strategy)
+    }
+
+    /// Renders a value of `column`, redacted if the policy covers the column.
+    pub fn redact<'v>(&self, column: &str, value: &'v str) -> Cow<'v, str> {
+        match self.strategy_for(column) {
+            Some(strategy) => Cow::Owned(strategy.apply(value)),
+            None => Cow::Borrowed(value),
+        }
+    }
+
+    /// Renders a value derived from several columns, such as a composite key
+    /// rendered as one string.
+    ///
+    /// The value is redacted with the strategy of the first covered column.
+    pub fn redact_any<'v, S: AsRef<str>>(&self, columns: &[S], value: &'v str) -> Cow<'v, str> {
+        match columns
+            .iter()
+            .find_map(|column| self.strategy_for(column.as_ref()))
+        {
+            Some(strategy) => Cow::Owned(strategy.apply(value)),
+            None => Cow::Borrowed(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compliance::PiiType;
+
+    #[test]
+    fn test_strategies() {
+        assert_eq!(RedactionStrategy::MaskAll.apply("secret"), "******");
+        assert_eq!(RedactionStrategy::mask_middle(1).apply("secret"), "s****t");
+        assert_eq!(RedactionStrategy::mask_middle(3).apply("secret"), "******");
+        assert_eq!(RedactionStrategy::mask_middle(1).apply("žluť"), "ž**ť");
+        assert_eq!(RedactionStrategy::Drop.apply("secret"), REDACTED);
+
+        let hash = RedactionStrategy::hash("pepper");
+        let digest = hash.apply("secret");
+        assert!(digest.starts_with("sha256:"));
+        assert_eq!(digest.len(), "sha256:".len() + 16);
+        assert!(!digest.contains("secret"));
+        assert_eq!(hash.apply("secret"), digest);
+        assert_ne!(RedactionStrategy::hash("salt").apply("secret"), digest);
+    }
+
+    #[test]
+    fn test_column_matching() {
+        let hits = [PiiHit {
+            column: "phone".to_string(),
+            pii_type: PiiType::Phone,
+            count: 3,
+        }];
+        let policy = RedactionPolicy::new()
+            .column("email", RedactionStrategy::Drop)
+            .columns_matching(|column| column.contains("name"), RedactionStrategy::MaskAll)
+            .pii_columns(&hits, RedactionStrategy::mask_middle(2));
+
+        assert_eq!(policy.redact("email", "a@b.c"), REDACTED);
+        assert_eq!(policy.redact("users.email", "a@b.c"), REDACTED);
+        assert_eq!(policy.redact("work_email", "a@b.c"), "a@b.c");
+        assert_eq!(policy.redact("last_name", "Doe"), "***");
+        assert_eq!(policy.redact("phone", "5551234"), "55***34");
+        assert_eq!(policy.redact_any(&["id", "email"], "(1, a@b.c)"), REDACTED);
+        assert_eq!(policy.redact_any(&["id"], "1"), "1");
+        assert!(RedactionPolicy::new().is_empty());
+    }
+}
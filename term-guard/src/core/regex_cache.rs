@@ -0,0 +1,242 @@
+//! Limits on user-supplied regular expressions and reuse of compiled ones.
+//!
+//! The regex engine never backtracks exponentially, but a very long pattern,
+//! or a short one with large counted repetitions such as `(\w{100}){100}`,
+//! still compiles into a program taking a lot of memory and time. Format
+//! constraints compile user patterns under [`RegexLimits`], so such a pattern
+//! is reported as a configuration error instead.
+//!
+//! The suite creates a fresh [`RegexCache`] for every run and attaches it to the
+//! [`ValidationContext`](super::ValidationContext) of each constraint, so a
+//! pattern shared by several constraints is compiled once per run. Outside a
+//! suite run (no cache attached) every pattern is compiled when used.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// Bounds on the size of user-supplied regular expressions.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::core::RegexLimits;
+///
+/// let limits = RegexLimits::default().size_limit(256 * 1024);
+/// assert!(limits.compile(r"^[A-Z]{2}\d{4}$", false).is_ok());
+/// assert!(limits.compile(r"(\w{100}){100}", false).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RegexLimits {
+    /// Maximum length of a pattern in bytes (default: 1000, the most
+    /// [`SqlSecurity`](crate::security::SqlSecurity) accepts)
+    pub max_pattern_length: usize,
+    /// Maximum size of the compiled program in bytes (default: 1 MiB)
+    pub size_limit: usize,
+    /// Maximum size of the lazy DFA cache in bytes (default: 2 MiB)
+    pub dfa_size_limit: usize,
+}
+
+impl Default for RegexLimits {
+    fn default() -> Self {
+        Self {
+            max_pattern_length: 1000,
+            size_limit: 1 << 20,
+            dfa_size_limit: 2 << 20,
+        }
+    }
+}
+
+impl RegexLimits {
+    /// Creates the default limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum length of a pattern in bytes.
+    pub fn max_pattern_length(mut self, length: usize) -> Self {
+        self.max_pattern_length = length;
+        self
+    }
+
+    /// Sets the maximum size of the compiled program in bytes.
+    pub fn size_limit(mut self, bytes: usize) -> Self {
+        self.size_limit = bytes;
+        self
+    }
+
+    /// Sets the maximum size of the lazy DFA cache in bytes.
+    pub fn dfa_size_limit(mut self, bytes: usize) -> Self {
+        self.dfa_size_limit = bytes;
+        self
+    }
+
+    /// Compiles `pattern` within these limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if the pattern is longer than
+    /// `max_pattern_length`, compiles to more than `size_limit` bytes, or is
+    /// not a valid regular expression.
+    pub fn compile(&self, pattern: &str, case_insensitive: bool) -> Result<Regex> {
+        if pattern.len() > self.max_pattern_length {
+            return Err(TermError::Configuration(format!(
+                "Regex pattern is {} bytes long, more than the limit of {} bytes",
+                pattern.len(),
+                self.max_pattern_length
+            )));
+        }
+        RegexBuilder::new(pattern)
+            .case_insensitive(case_insensitive)
+            .size_limit(self.size_limit)
+            .dfa_size_limit(self.dfa_size_limit)
+            .build()
+            .map_err(|e| match e {
+                regex::Error::CompiledTooBig(limit) => TermError::Configuration(format!(
+                    "Regex pattern compiles to more than the size limit of {limit} bytes"
+                )),
+                e => TermError::Configuration(format!("Invalid regex pattern: {e}")),
+            })
+    }
+}
+
+/// Key of a compiled regex: the pattern and the options it was compiled with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct RegexKey {
+    pattern: String,
+    case_insensitive: bool,
+    limits: RegexLimits,
+}
+
+/// Regexes compiled during a run, keyed by pattern and options.
+///
+/// Clones share the same entries and counters.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::core::{RegexCache, RegexLimits};
+///
+/// let cache = RegexCache::new();
+/// let limits = RegexLimits::default();
+/// for _ in 0..10 {
+///     cache.compile(r"^\d{5}$", false, &limits).unwrap();
+/// }
+/// cache.compile(r"^\d{5}$", true, &limits).unwrap();
+/// assert_eq!((cache.compilations(), cache.hits()), (2, 9));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RegexCache {
+    inner: Arc<RegexCacheInner>,
+}
+
+#[derive(Debug, Default)]
+struct RegexCacheInner {
+    regexes: Mutex<HashMap<RegexKey, Arc<Regex>>>,
+    compilations: AtomicU64,
+    hits: AtomicU64,
+}
+
+impl RegexCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the compiled `pattern`, compiling it within `limits` unless it
+    /// was already compiled with the same options.
+    ///
+    /// # Errors
+    ///
+    /// See [`RegexLimits::compile`]. Patterns failing to compile are not cached.
+    pub fn compile(
+        &self,
+        pattern: &str,
+        case_insensitive: bool,
+        limits: &RegexLimits,
+    ) -> Result<Arc<Regex>> {
+        let key = RegexKey {
+            pattern: pattern.to_string(),
+            case_insensitive,
+            limits: *limits,
+        };
+        if let Some(regex) = self
+            .inner
+            .regexes
+            .lock()
+            .ok()
+            .and_then(|regexes| regexes.get(&key).cloned())
+        {
+            self.inner.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(regex);
+        }
+
+        self.inner.compilations.fetch_add(1, Ordering::Relaxed);
+        let regex = Arc::new(limits.compile(pattern, case_insensitive)?);
+        if let Ok(mut regexes) = self.inner.regexes.lock() {
+            regexes.entry(key).or_insert_with(|| Arc::clone(&regex));
+        }
+        Ok(regex)
+    }
+
+    /// Returns how many patterns were compiled, including failed attempts.
+    pub fn compilations(&self) -> u64 {
+        self.inner.compilations.load(Ordering::Relaxed)
+    }
+
+    /// Returns how many requests were answered with an already compiled regex.
+    pub fn hits(&self) -> u64 {
+        self.inner.hits.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits() {
+        let limits = RegexLimits::new().max_pattern_length(8);
+        let err = limits.compile(r"^[a-z]+\d+$", false).unwrap_err();
+        assert!(
+            err.to_string().contains("more than the limit of 8"),
+            "{err}"
+        );
+
+        let err = RegexLimits::new()
+            .size_limit(10 * 1024)
+            .compile(r"([a-z]{100}){100}", false)
+            .unwrap_err();
+        assert!(matches!(err, TermError::Configuration(_)));
+        assert!(err.to_string().contains("size limit"), "{err}");
+
+        let err = RegexLimits::new().compile(r"(unclosed", false).unwrap_err();
+        assert!(err.to_string().contains("Invalid regex pattern"), "{err}");
+
+        let regex = RegexLimits::new().compile(r"^abc$", true).unwrap();
+        assert!(regex.is_match("ABC"));
+    }
+
+    #[test]
+    fn test_cache_keys_on_options() {
+        let cache = RegexCache::new();
+        let limits = RegexLimits::default();
+
+        let first = cache.compile(r"^\d+$", false, &limits).unwrap();
+        let second = cache.clone().compile(r"^\d+$", false, &limits).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        cache
+            .compile(r"^\d+$", false, &limits.size_limit(1 << 16))
+            .unwrap();
+        assert!(cache.compile(r"(", false, &limits).is_err());
+        assert!(cache.compile(r"(", false, &limits).is_err());
+
+        assert_eq!(cache.compilations(), 4);
+        assert_eq!(cache.hits(), 1);
+    }
+}
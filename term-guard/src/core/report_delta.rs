@@ -0,0 +1,491 @@
+//! Changes between a validation report and the report of a previous run.
+//!
+//! When a scheduled run fails, the first question is what changed since the
+//! last one. [`ValidationReport::compare`] matches the constraints of two
+//! reports and classifies each of them as a [`DeltaKind`], with the change of
+//! its metric where both runs recorded one.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{Level, ValidationReport};
+
+/// How a constraint changed between two runs.
+///
+/// Variants are ordered by how much attention they need, which is the order
+/// [`ReportDelta::changes`] lists them in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeltaKind {
+    /// Passed in the previous run and fails now
+    NewlyFailing,
+    /// Failed in both runs
+    StillFailing,
+    /// Failed in the previous run and passes now
+    NewlyPassing,
+    /// Not part of the previous run
+    Added,
+    /// Part of the previous run only
+    Removed,
+    /// Passed in both runs
+    StillPassing,
+}
+
+impl DeltaKind {
+    /// Every kind, in the order changes are listed.
+    pub const ALL: [DeltaKind; 6] = [
+        DeltaKind::NewlyFailing,
+        DeltaKind::StillFailing,
+        DeltaKind::NewlyPassing,
+        DeltaKind::Added,
+        DeltaKind::Removed,
+        DeltaKind::StillPassing,
+    ];
+}
+
+impl fmt::Display for DeltaKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DeltaKind::NewlyFailing => "newly failing",
+            DeltaKind::StillFailing => "still failing",
+            DeltaKind::NewlyPassing => "newly passing",
+            DeltaKind::Added => "added",
+            DeltaKind::Removed => "removed",
+            DeltaKind::StillPassing => "still passing",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The change of one constraint between two runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstraintDelta {
+    /// How the constraint changed
+    pub kind: DeltaKind,
+    /// The name of the check holding the constraint, as of the latest run
+    /// that has it
+    pub check_name: String,
+    /// The name of the constraint, as of the latest run that has it
+    pub constraint_name: String,
+    /// The stable identifier of the constraint, if the report recorded one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraint_id: Option<String>,
+    /// The severity level of the check
+    pub level: Level,
+    /// The check name in the previous run, if the check was renamed since
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_check_name: Option<String>,
+    /// The constraint name in the previous run, if the constraint was renamed since
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_constraint_name: Option<String>,
+    /// The metric observed in the previous run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_metric: Option<f64>,
+    /// The metric observed in the current run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_metric: Option<f64>,
+}
+
+impl ConstraintDelta {
+    /// Returns how much the metric moved since the previous run, if both runs
+    /// recorded one.
+    pub fn metric_delta(&self) -> Option<f64> {
+        Some(self.current_metric? - self.previous_metric?)
+    }
+}
+
+/// The changes between a validation report and the report of a previous run,
+/// see [`ValidationReport::compare`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportDelta {
+    /// Timestamp of the previous run
+    pub previous_timestamp: String,
+    /// Identifier of the previous run, if its report recorded one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_run_id: Option<Uuid>,
+    /// One entry per constraint of either run, ordered by kind, then by check
+    /// and constraint name
+    pub changes: Vec<ConstraintDelta>,
+}
+
+impl ReportDelta {
+    /// Returns the changes of the given kind.
+    pub fn of_kind(&self, kind: DeltaKind) -> Vec<&ConstraintDelta> {
+        self.changes.iter().filter(|c| c.kind == kind).collect()
+    }
+
+    /// Returns the number of changes of the given kind.
+    pub fn count(&self, kind: DeltaKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+
+    /// Returns true if any constraint started or stopped failing, or was added
+    /// or removed.
+    pub fn has_changes(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|c| !matches!(c.kind, DeltaKind::StillFailing | DeltaKind::StillPassing))
+    }
+
+    /// Returns a one-line count of the changes of every kind.
+    pub fn summary(&self) -> String {
+        DeltaKind::ALL
+            .iter()
+            .map(|&kind| format!("{kind}: {}", self.count(kind)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// A constraint of one report, as far as comparisons are concerned.
+struct Entry<'a> {
+    check_name: &'a str,
+    constraint_name: &'a str,
+    constraint_id: Option<&'a str>,
+    level: Level,
+    failing: bool,
+    metric: Option<f64>,
+}
+
+impl<'a> Entry<'a> {
+    /// Lists the constraints of a report.
+    ///
+    /// Reports written before outcomes were recorded only list failures, so
+    /// their issues stand in for the outcomes. Outcomes written before they
+    /// carried identifiers take the identifier of the matching issue.
+    fn of(report: &'a ValidationReport) -> Vec<Self> {
+        if report.outcomes.is_empty() {
+            return report
+                .issues
+                .iter()
+                .map(|issue| Entry {
+                    check_name: &issue.check_name,
+                    constraint_name: &issue.constraint_name,
+                    constraint_id: issue.constraint_id.as_deref(),
+                    level: issue.level,
+                    failing: true,
+                    metric: issue.metric,
+                })
+                .collect();
+        }
+
+        report
+            .outcomes
+            .iter()
+            .map(|outcome| Entry {
+                check_name: &outcome.check_name,
+                constraint_name: &outcome.constraint_name,
+                constraint_id: outcome.constraint_id.as_deref().or_else(|| {
+                    report
+                        .issues
+                        .iter()
+                        .find(|issue| {
+                            issue.check_name == outcome.check_name
+                                && issue.constraint_name == outcome.constraint_name
+                        })
+                        .and_then(|issue| issue.constraint_id.as_deref())
+                }),
+                level: outcome.level,
+                failing: outcome.status.is_failure(),
+                metric: outcome.metric,
+            })
+            .collect()
+    }
+
+    fn delta(&self, kind: DeltaKind) -> ConstraintDelta {
+        ConstraintDelta {
+            kind,
+            check_name: self.check_name.to_string(),
+            constraint_name: self.constraint_name.to_string(),
+            constraint_id: self.constraint_id.map(str::to_string),
+            level: self.level,
+            previous_check_name: None,
+            previous_constraint_name: None,
+            previous_metric: None,
+            current_metric: None,
+        }
+    }
+}
+
+impl ValidationReport {
+    /// Compares this report with the report of a previous run of the suite.
+    ///
+    /// Constraints are matched by their stable identifier where both reports
+    /// recorded one, preferring a match within a check of the same name, and
+    /// by check and constraint name otherwise. A constraint matched by
+    /// identifier under another name is reported with its previous names.
+    /// Skipped constraints count as passing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{
+    ///     ConstraintOutcome, ConstraintStatus, DeltaKind, Level, ValidationReport,
+    /// };
+    ///
+    /// fn report(status: ConstraintStatus, metric: f64) -> ValidationReport {
+    ///     let mut report = ValidationReport::new("orders");
+    ///     report.outcomes.push(ConstraintOutcome {
+    ///         check_name: "emails".to_string(),
+    ///         constraint_name: "completeness".to_string(),
+    ///         level: Level::Error,
+    ///         status,
+    ///         waived: false,
+    ///         metric: Some(metric),
+    ///         threshold: Some(0.95),
+    ///         dimension: None,
+    ///         constraint_id: Some("completeness-0123456789abcdef".to_string()),
+    ///     });
+    ///     report
+    /// }
+    ///
+    /// let yesterday = report(ConstraintStatus::Success, 0.98);
+    /// let today = report(ConstraintStatus::Failure, 0.9);
+    ///
+    /// let delta = today.compare(&yesterday);
+    /// assert_eq!(delta.changes[0].kind, DeltaKind::NewlyFailing);
+    /// assert!((delta.changes[0].metric_delta().unwrap() + 0.08).abs() < 1e-9);
+    /// ```
+    pub fn compare(&self, previous: &ValidationReport) -> ReportDelta {
+        let current = Entry::of(self);
+        let before = Entry::of(previous);
+        let mut matches: Vec<Option<usize>> = vec![None; current.len()];
+        let mut taken = vec![false; before.len()];
+
+        let mut pair = |matches: &mut Vec<Option<usize>>,
+                        accept: &dyn Fn(&Entry<'_>, &Entry<'_>) -> bool| {
+            for (index, entry) in current.iter().enumerate() {
+                if matches[index].is_some() {
+                    continue;
+                }
+                let found = before
+                    .iter()
+                    .enumerate()
+                    .position(|(i, candidate)| !taken[i] && accept(entry, candidate));
+                if let Some(found) = found {
+                    taken[found] = true;
+                    matches[index] = Some(found);
+                }
+            }
+        };
+        let same_id = |a: &Entry<'_>, b: &Entry<'_>| {
+            a.constraint_id.is_some() && a.constraint_id == b.constraint_id
+        };
+        pair(&mut matches, &|a, b| {
+            same_id(a, b) && a.check_name == b.check_name
+        });
+        pair(&mut matches, &same_id);
+        pair(&mut matches, &|a, b| {
+            a.check_name == b.check_name && a.constraint_name == b.constraint_name
+        });
+
+        let mut changes: Vec<ConstraintDelta> = current
+            .iter()
+            .zip(&matches)
+            .map(|(entry, matched)| {
+                let Some(old) = matched.map(|index| &before[index]) else {
+                    let mut delta = entry.delta(DeltaKind::Added);
+                    delta.current_metric = entry.metric;
+                    return delta;
+                };
+                let kind = match (old.failing, entry.failing) {
+                    (false, true) => DeltaKind::NewlyFailing,
+                    (true, true) => DeltaKind::StillFailing,
+                    (true, false) => DeltaKind::NewlyPassing,
+                    (false, false) => DeltaKind::StillPassing,
+                };
+                let mut delta = entry.delta(kind);
+                delta.previous_check_name =
+                    (old.check_name != entry.check_name).then(|| old.check_name.to_string());
+                delta.previous_constraint_name = (old.constraint_name != entry.constraint_name)
+                    .then(|| old.constraint_name.to_string());
+                delta.previous_metric = old.metric;
+                delta.current_metric = entry.metric;
+                delta
+            })
+            .collect();
+        changes.extend(before.iter().zip(&taken).filter(|(_, taken)| !**taken).map(
+            |(entry, _)| {
+                let mut delta = entry.delta(DeltaKind::Removed);
+                delta.previous_metric = entry.metric;
+                delta
+            },
+        ));
+        changes.sort_by(|a, b| {
+            (a.kind, &a.check_name, &a.constraint_name).cmp(&(
+                b.kind,
+                &b.check_name,
+                &b.constraint_name,
+            ))
+        });
+
+        ReportDelta {
+            previous_timestamp: previous.timestamp.clone(),
+            previous_run_id: previous.run_id,
+            changes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ConstraintOutcome, ConstraintStatus, ValidationIssue};
+
+    fn outcome(
+        check: &str,
+        constraint: &str,
+        id: Option<&str>,
+        status: ConstraintStatus,
+        metric: Option<f64>,
+    ) -> ConstraintOutcome {
+        ConstraintOutcome {
+            check_name: check.to_string(),
+            constraint_name: constraint.to_string(),
+            level: Level::Error,
+            status,
+            waived: false,
+            metric,
+            threshold: None,
+            dimension: None,
+            constraint_id: id.map(str::to_string),
+        }
+    }
+
+    fn report(outcomes: Vec<ConstraintOutcome>) -> ValidationReport {
+        let mut report = ValidationReport::new("orders");
+        report.outcomes = outcomes;
+        report
+    }
+
+    fn kinds(delta: &ReportDelta) -> Vec<(DeltaKind, &str)> {
+        delta
+            .changes
+            .iter()
+            .map(|c| (c.kind, c.check_name.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_classifies_every_kind() {
+        use ConstraintStatus::{Failure, Success};
+
+        let previous = report(vec![
+            outcome("a_regressed", "completeness", None, Success, Some(0.99)),
+            outcome("b_broken", "completeness", None, Failure, Some(0.5)),
+            outcome("c_fixed", "completeness", None, Failure, Some(0.7)),
+            outcome("d_fine", "size", None, Success, Some(10.0)),
+            outcome("e_dropped", "size", None, Success, None),
+        ]);
+        let current = report(vec![
+            outcome("d_fine", "size", None, Success, Some(12.0)),
+            outcome("c_fixed", "completeness", None, Success, Some(1.0)),
+            outcome("f_new", "size", None, Failure, None),
+            outcome("b_broken", "completeness", None, Failure, Some(0.25)),
+            outcome("a_regressed", "completeness", None, Failure, Some(0.9)),
+        ]);
+
+        let delta = current.compare(&previous);
+        assert_eq!(
+            kinds(&delta),
+            vec![
+                (DeltaKind::NewlyFailing, "a_regressed"),
+                (DeltaKind::StillFailing, "b_broken"),
+                (DeltaKind::NewlyPassing, "c_fixed"),
+                (DeltaKind::Added, "f_new"),
+                (DeltaKind::Removed, "e_dropped"),
+                (DeltaKind::StillPassing, "d_fine"),
+            ]
+        );
+        assert_eq!(delta.changes[1].metric_delta(), Some(-0.25));
+        assert_eq!(delta.changes[3].metric_delta(), None);
+        assert_eq!(delta.changes[5].metric_delta(), Some(2.0));
+        assert!(delta.has_changes());
+        assert_eq!(delta.count(DeltaKind::StillFailing), 1);
+        assert_eq!(
+            delta.summary(),
+            "newly failing: 1, still failing: 1, newly passing: 1, added: 1, removed: 1, still passing: 1"
+        );
+    }
+
+    #[test]
+    fn test_matches_renamed_checks_by_id() {
+        let previous = report(vec![
+            outcome(
+                "emails",
+                "completeness",
+                Some("completeness-1"),
+                ConstraintStatus::Success,
+                Some(1.0),
+            ),
+            outcome(
+                "ids",
+                "completeness",
+                Some("completeness-2"),
+                ConstraintStatus::Success,
+                Some(1.0),
+            ),
+        ]);
+        let current = report(vec![
+            outcome(
+                "contact_emails",
+                "completeness",
+                Some("completeness-1"),
+                ConstraintStatus::Failure,
+                Some(0.9),
+            ),
+            outcome(
+                "ids",
+                "completeness",
+                Some("completeness-2"),
+                ConstraintStatus::Success,
+                Some(1.0),
+            ),
+        ]);
+
+        let delta = current.compare(&previous);
+        assert_eq!(
+            kinds(&delta),
+            vec![
+                (DeltaKind::NewlyFailing, "contact_emails"),
+                (DeltaKind::StillPassing, "ids"),
+            ]
+        );
+        assert_eq!(
+            delta.changes[0].previous_check_name.as_deref(),
+            Some("emails")
+        );
+        assert_eq!(delta.changes[1].previous_check_name, None);
+        assert!(!current.compare(&current).has_changes());
+    }
+
+    #[test]
+    fn test_falls_back_to_issues_of_legacy_reports() {
+        let mut previous = ValidationReport::new("orders");
+        previous.add_issue(ValidationIssue {
+            check_name: "emails".to_string(),
+            constraint_name: "completeness".to_string(),
+            level: Level::Error,
+            message: "too many nulls".to_string(),
+            metric: Some(0.8),
+            cached: false,
+            known_issue: None,
+            check_id: None,
+            constraint_id: None,
+            impacts: Vec::new(),
+        });
+        let current = report(vec![outcome(
+            "emails",
+            "completeness",
+            Some("completeness-1"),
+            ConstraintStatus::Success,
+            Some(0.97),
+        )]);
+
+        let delta = current.compare(&previous);
+        assert_eq!(kinds(&delta), vec![(DeltaKind::NewlyPassing, "emails")]);
+        assert_eq!(delta.changes[0].previous_metric, Some(0.8));
+    }
+}
@@ -1,10 +1,41 @@
 //! Validation result types.
 
-use super::Level;
+use super::{
+    check_statuses, CheckStatus, ConstraintStatus, DimensionSummary, ExecutionSchedule, KnownIssue,
+    Level, PerformanceMetrics, QualityDimension, Timeline, CHECK_TAG_PREFIX,
+};
+use crate::error::TermError;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use uuid::Uuid;
+
+/// The schema version written to serialized validation reports.
+///
+/// Version history:
+/// - `1`: initial format, without `report_schema_version` or performance metrics
+/// - `2`: adds `report_schema_version` and `metrics.performance`
+/// - `3`: adds `metrics.info_findings`; failed `Level::Info` constraints no longer count
+///   in `metrics.failed_checks`
+/// - `4`: adds `outcomes`, one record per evaluated constraint
+/// - `5`: adds `outcomes[].constraint_id`
+/// - `6`: the check counts of `metrics` count checks rather than constraints, and a
+///   check whose constraints were all skipped counts as skipped only
+/// - `7`: adds `issues[].impacts`
+/// - `8`: adds `issues[].check_id` and `issues[].constraint_id`
+/// - `9`: adds `outcomes[].waived` and `metrics.waived_checks`; checks whose failures
+///   were all waived by a known issue no longer count in `metrics.failed_checks`
+pub const REPORT_SCHEMA_VERSION: u32 = 9;
+
+/// Reports serialized before versioning was introduced carry no version field.
+fn legacy_schema_version() -> u32 {
+    1
+}
 
 /// Metrics collected during validation.
+///
+/// The counts are per check: every executed check counts once, under the
+/// [`CheckStatus`] aggregated from its constraints.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationMetrics {
     /// Total number of checks executed
@@ -12,14 +43,26 @@ pub struct ValidationMetrics {
     /// Number of checks that passed
     pub passed_checks: usize,
     /// Number of checks that failed
+    ///
+    /// Failures of [`Level::Info`] checks are not counted here, see `info_findings`.
     pub failed_checks: usize,
     /// Number of checks that were skipped
     pub skipped_checks: usize,
+    /// Number of failed [`Level::Info`] checks, recorded as observations
+    #[serde(default)]
+    pub info_findings: usize,
+    /// Number of checks whose failures were all waived by a
+    /// [`KnownIssue`](super::KnownIssue)
+    #[serde(default)]
+    pub waived_checks: usize,
     /// Total execution time in milliseconds
     pub execution_time_ms: u64,
     /// Custom metrics collected during validation
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub custom_metrics: HashMap<String, f64>,
+    /// Query execution metrics (rows scanned, bytes read) collected from DataFusion
+    #[serde(default, skip_serializing_if = "PerformanceMetrics::is_empty")]
+    pub performance: PerformanceMetrics,
 }
 
 impl ValidationMetrics {
@@ -30,17 +73,79 @@ impl ValidationMetrics {
             passed_checks: 0,
             failed_checks: 0,
             skipped_checks: 0,
+            info_findings: 0,
+            waived_checks: 0,
             execution_time_ms: 0,
             custom_metrics: HashMap::new(),
+            performance: PerformanceMetrics::new(),
+        }
+    }
+
+    /// Counts the checks of the given constraint outcomes, see [`CheckStatus`].
+    ///
+    /// Checks without outcomes are not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{ConstraintOutcome, ConstraintStatus, Level, ValidationMetrics};
+    ///
+    /// let outcomes: Vec<_> = [ConstraintStatus::Failure, ConstraintStatus::Failure]
+    ///     .into_iter()
+    ///     .map(|status| ConstraintOutcome {
+    ///         check_name: "ids".to_string(),
+    ///         constraint_name: "uniqueness".to_string(),
+    ///         level: Level::Error,
+    ///         status,
+    ///         waived: false,
+    ///         metric: None,
+    ///         threshold: None,
+    ///         dimension: None,
+    ///         constraint_id: None,
+    ///     })
+    ///     .collect();
+    ///
+    /// let metrics = ValidationMetrics::from_outcomes(&outcomes);
+    /// assert_eq!((metrics.total_checks, metrics.failed_checks), (1, 1));
+    /// ```
+    pub fn from_outcomes(outcomes: &[ConstraintOutcome]) -> Self {
+        let mut metrics = Self::new();
+        for (_, status) in check_statuses(outcomes) {
+            metrics.record_check(status);
+        }
+        metrics
+    }
+
+    /// Counts one executed check with the given status.
+    pub fn record_check(&mut self, status: CheckStatus) {
+        self.total_checks += 1;
+        match status {
+            CheckStatus::Passed => self.passed_checks += 1,
+            CheckStatus::Failed => self.failed_checks += 1,
+            CheckStatus::InfoFinding => self.info_findings += 1,
+            CheckStatus::Waived => self.waived_checks += 1,
+            CheckStatus::Skipped => self.skipped_checks += 1,
         }
     }
 
+    /// Returns the number of checks that either passed or failed.
+    ///
+    /// Skipped checks verified nothing, info findings are observations and
+    /// waived checks fail knowingly, so none of them is rated.
+    pub fn rated_checks(&self) -> usize {
+        self.passed_checks + self.failed_checks
+    }
+
     /// Returns the success rate as a percentage (0.0 to 100.0).
+    ///
+    /// This is the share of passed checks among the [rated](Self::rated_checks)
+    /// ones, or 100 when no check was rated.
     pub fn success_rate(&self) -> f64 {
-        if self.total_checks == 0 {
+        let rated_checks = self.rated_checks();
+        if rated_checks == 0 {
             100.0
         } else {
-            (self.passed_checks as f64 / self.total_checks as f64) * 100.0
+            (self.passed_checks as f64 / rated_checks as f64) * 100.0
         }
     }
 }
@@ -65,40 +170,457 @@ pub struct ValidationIssue {
     /// Optional metric value associated with the issue
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metric: Option<f64>,
+    /// Whether the issue comes from a cached result of a previous run
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cached: bool,
+    /// The known issue waiving this failure; waived failures do not fail the run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub known_issue: Option<KnownIssue>,
+    /// The explicit identifier of the check, if one was set with
+    /// [`CheckBuilder::id`](super::CheckBuilder::id)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_id: Option<String>,
+    /// The stable identifier of the constraint, see [`constraint_id`](super::constraint_id)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraint_id: Option<String>,
+    /// Downstream identifiers consuming the columns of the constraint, from the
+    /// suite's [`LineageMap`](super::LineageMap)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub impacts: Vec<String>,
+}
+
+impl ValidationIssue {
+    /// Returns true if the failure is waived by an active known issue.
+    pub fn is_known_issue(&self) -> bool {
+        self.known_issue.is_some()
+    }
+
+    /// Returns the identifier to route the issue by: the explicit check id if set,
+    /// otherwise the constraint id.
+    pub fn routing_id(&self) -> Option<&str> {
+        self.check_id.as_deref().or(self.constraint_id.as_deref())
+    }
+}
+
+/// The recent pass/fail history of a constraint, attached to the reports of
+/// later runs by
+/// [`FlakinessReport::annotate`](crate::analyzers::FlakinessReport::annotate).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentFlakiness {
+    /// Share of consecutive runs whose status differs, from 0 (stable) to 1
+    /// (alternating every run)
+    pub score: f64,
+    /// Number of runs in the history
+    pub runs: usize,
+    /// Number of failed runs
+    pub failures: usize,
+    /// Number of times the status changed between consecutive runs
+    pub flips: usize,
+}
+
+impl fmt::Display for RecentFlakiness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "score {:.2} ({} flips, {} failures in {} runs)",
+            self.score, self.flips, self.failures, self.runs
+        )
+    }
+}
+
+/// The outcome of one evaluated constraint, whether it passed or not.
+///
+/// Outcomes are the input of [`Scorecard`](crate::scorecard::Scorecard), which
+/// weighs observed metrics against thresholds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstraintOutcome {
+    /// The name of the check holding the constraint
+    pub check_name: String,
+    /// The name of the constraint
+    pub constraint_name: String,
+    /// The severity level of the check
+    pub level: Level,
+    /// The status of the constraint; evaluation errors are recorded as failures
+    pub status: ConstraintStatus,
+    /// Whether a [`KnownIssue`](super::KnownIssue) waived the failure of the constraint
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub waived: bool,
+    /// The observed metric, if the constraint reports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metric: Option<f64>,
+    /// The minimum fraction of rows the constraint requires, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<f64>,
+    /// The quality dimension the constraint measures, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimension: Option<QualityDimension>,
+    /// The stable identifier of the constraint, see [`constraint_id`](super::constraint_id)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraint_id: Option<String>,
+}
+
+/// A check left out of a filtered run.
+///
+/// Excluded checks were not executed, so they count neither as passed nor as
+/// failed in the report metrics.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExcludedCheck {
+    /// The name of the check
+    pub check_name: String,
+    /// The severity level of the check
+    pub level: Level,
+    /// Number of constraints that were not run
+    pub constraints: usize,
+}
+
+/// A constraint that was answered from precomputed column statistics
+/// (for example a column profile) instead of issuing its own query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfiledConstraint {
+    /// The name of the check containing the constraint
+    pub check_name: String,
+    /// The name of the constraint
+    pub constraint_name: String,
+    /// The column whose statistics were used
+    pub column: String,
+}
+
+/// A constraint that was answered from file metadata, such as Parquet footer
+/// statistics, instead of scanning the data.
+///
+/// See [`MetadataStatistics`](super::MetadataStatistics) for the caveats.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetadataConstraint {
+    /// The name of the check containing the constraint
+    pub check_name: String,
+    /// The name of the constraint
+    pub constraint_name: String,
+    /// The column whose statistics were used, if the constraint has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    /// Where the statistics came from, such as
+    /// [`PARQUET_METADATA_EVIDENCE`](super::PARQUET_METADATA_EVIDENCE)
+    pub evidence: String,
+}
+
+/// A constraint whose result was reused from a previous run by the result cache.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedConstraint {
+    /// The name of the check containing the constraint
+    pub check_name: String,
+    /// The name of the constraint
+    pub constraint_name: String,
+    /// When the reused result was computed (ISO 8601 format)
+    pub computed_at: String,
+}
+
+/// A constraint skipped because a [`ValidationHook`](super::ValidationHook)
+/// vetoed its evaluation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookSkippedConstraint {
+    /// The name of the check containing the constraint
+    pub check_name: String,
+    /// The name of the constraint
+    pub constraint_name: String,
+    /// The name of the hook that skipped the constraint
+    pub hook: String,
+    /// The reason given by the hook
+    pub reason: String,
+}
+
+/// How a preview run sampled the data, see
+/// [`ValidationSuite::preview`](super::ValidationSuite::preview).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreviewSummary {
+    /// Maximum number of rows read from each table
+    pub limit: usize,
+    /// Number of rows sampled from each table, by table name
+    pub sampled_rows: BTreeMap<String, usize>,
+    /// Constraints skipped because they need the full data
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_constraints: Vec<PreviewSkippedConstraint>,
+}
+
+impl fmt::Display for PreviewSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tables = self
+            .sampled_rows
+            .iter()
+            .map(|(table, rows)| format!("{table}: {rows} rows"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "first {} rows per table ({tables})", self.limit)?;
+        if !self.skipped_constraints.is_empty() {
+            write!(
+                f,
+                ", {} constraint(s) skipped",
+                self.skipped_constraints.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A constraint skipped by a preview run because it needs the full data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreviewSkippedConstraint {
+    /// The name of the check containing the constraint
+    pub check_name: String,
+    /// The name of the constraint
+    pub constraint_name: String,
+}
+
+/// Violating rows exported by a constraint to its quarantine sink.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantinedRows {
+    /// The name of the check containing the constraint
+    pub check_name: String,
+    /// The name of the constraint
+    pub constraint_name: String,
+    /// Number of rows written to the sink
+    pub rows: u64,
+    /// File path or table name the rows were written to
+    pub location: String,
+    /// Whether violating rows were dropped because of the sink's row limit
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
 }
 
 /// A validation report containing all issues found.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationReport {
+    /// The schema version of this report, see [`REPORT_SCHEMA_VERSION`]
+    #[serde(default = "legacy_schema_version")]
+    pub report_schema_version: u32,
     /// The name of the validation suite that was run
     pub suite_name: String,
+    /// The description of the validation suite, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The version of the suite definition, see [`SuiteVersion`](super::SuiteVersion)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suite_version: Option<String>,
+    /// Descriptions of the suite's checks, by check name; checks without a
+    /// description are left out
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub check_descriptions: BTreeMap<String, String>,
+    /// Metadata of the suite's checks, by check name; checks without metadata
+    /// are left out
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub check_metadata: BTreeMap<String, BTreeMap<String, String>>,
     /// Timestamp when the validation was run (ISO 8601 format)
     pub timestamp: String,
     /// Overall validation metrics
     pub metrics: ValidationMetrics,
     /// List of issues found during validation
     pub issues: Vec<ValidationIssue>,
+    /// Outcome of every evaluated constraint
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outcomes: Vec<ConstraintOutcome>,
+    /// Constraints answered from precomputed statistics instead of queries
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiled_constraints: Vec<ProfiledConstraint>,
+    /// Constraints answered from file metadata instead of scanning the data
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metadata_constraints: Vec<MetadataConstraint>,
+    /// Constraints whose results were reused from the result cache
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cached_constraints: Vec<CachedConstraint>,
+    /// Constraints skipped by validation hooks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hook_skipped: Vec<HookSkippedConstraint>,
+    /// Violating rows exported to quarantine sinks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quarantined: Vec<QuarantinedRows>,
+    /// Resolved values of the named parameters used by the suite's constraints
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, f64>,
+    /// Physical column of the validated table for each logical column named in
+    /// the report
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_mapping: BTreeMap<String, String>,
+    /// Checks left out of a filtered run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded_checks: Vec<ExcludedCheck>,
+    /// Order in which the checks ran, recorded when the suite has an
+    /// [`ExecutionOrder`](super::ExecutionOrder) other than the builder order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<ExecutionSchedule>,
+    /// Identifier of the run that produced the report, also recorded on its log events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<Uuid>,
+    /// Set when the report comes from a preview run on a sample of the data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview: Option<PreviewSummary>,
+    /// Recent flakiness of the failing constraints, by constraint id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub flakiness: BTreeMap<String, RecentFlakiness>,
+    /// Whether span export stalled and the run's telemetry is missing spans,
+    /// see [`TermTelemetry::health`](crate::telemetry::TermTelemetry::health)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub telemetry_incomplete: bool,
+    /// Phase timings of the run, recorded when the suite has a timeline enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timeline: Option<Timeline>,
 }
 
 impl ValidationReport {
     /// Creates a new validation report.
     pub fn new(suite_name: impl Into<String>) -> Self {
         Self {
+            report_schema_version: REPORT_SCHEMA_VERSION,
             suite_name: suite_name.into(),
+            description: None,
+            suite_version: None,
+            check_descriptions: BTreeMap::new(),
+            check_metadata: BTreeMap::new(),
             timestamp: chrono::Utc::now().to_rfc3339(),
             metrics: ValidationMetrics::new(),
             issues: Vec::new(),
+            outcomes: Vec::new(),
+            profiled_constraints: Vec::new(),
+            metadata_constraints: Vec::new(),
+            cached_constraints: Vec::new(),
+            hook_skipped: Vec::new(),
+            quarantined: Vec::new(),
+            parameters: BTreeMap::new(),
+            column_mapping: BTreeMap::new(),
+            excluded_checks: Vec::new(),
+            schedule: None,
+            run_id: None,
+            preview: None,
+            flakiness: BTreeMap::new(),
+            telemetry_incomplete: false,
+            timeline: None,
         }
     }
 
+    /// Deserializes a report from JSON, upgrading older schema versions.
+    ///
+    /// Reports written by the current and previous schema versions are accepted,
+    /// and fields added since are filled with their defaults. The returned report
+    /// keeps the version it was written with, as the meaning of some counts
+    /// changed between versions: before version 6 `metrics` counted constraints
+    /// rather than checks, for example. Reports written by a newer version of the
+    /// library are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::ValidationReport;
+    ///
+    /// let legacy = r#"{
+    ///     "suite_name": "orders",
+    ///     "timestamp": "2025-01-01T00:00:00Z",
+    ///     "metrics": {
+    ///         "total_checks": 1, "passed_checks": 1, "failed_checks": 0,
+    ///         "skipped_checks": 0, "execution_time_ms": 12
+    ///     },
+    ///     "issues": []
+    /// }"#;
+    ///
+    /// let report = ValidationReport::from_json_str(legacy).unwrap();
+    /// assert_eq!(report.report_schema_version, 1);
+    /// ```
+    pub fn from_json_str(json: &str) -> crate::prelude::Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(|e| {
+            TermError::Serialization(format!("Failed to parse validation report: {e}"))
+        })?;
+        Self::from_json_value(value)
+    }
+
+    fn from_json_value(mut value: serde_json::Value) -> crate::prelude::Result<Self> {
+        let object = value.as_object_mut().ok_or_else(|| {
+            TermError::Serialization("Validation report must be a JSON object".to_string())
+        })?;
+
+        let version = match object.get("report_schema_version") {
+            None => legacy_schema_version(),
+            Some(v) => v
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| {
+                    TermError::Serialization(format!("Invalid report_schema_version: {v}"))
+                })?,
+        };
+
+        if version == 0 || version > REPORT_SCHEMA_VERSION {
+            return Err(TermError::Serialization(format!(
+                "Unsupported report schema version {version} (supported: 1 to {REPORT_SCHEMA_VERSION})"
+            )));
+        }
+
+        // Formatted JSON lists Info-level issues separately as observations
+        if let Some(serde_json::Value::Array(observations)) = object.remove("observations") {
+            if let Some(serde_json::Value::Array(issues)) = object.get_mut("issues") {
+                issues.extend(observations);
+            }
+        }
+
+        object.insert(
+            "report_schema_version".to_string(),
+            serde_json::Value::from(version),
+        );
+
+        serde_json::from_value(value).map_err(|e| {
+            TermError::Serialization(format!("Failed to deserialize validation report: {e}"))
+        })
+    }
+
+    /// Returns the timeline of the run, if the suite was built with
+    /// [`with_timeline(true)`](super::ValidationSuiteBuilder::with_timeline).
+    pub fn timeline(&self) -> Option<&Timeline> {
+        self.timeline.as_ref()
+    }
+
+    /// Attaches the timeline recorded during the run.
+    pub(crate) fn set_timeline(&mut self, timeline: Option<Timeline>) {
+        self.timeline = timeline;
+    }
+
+    /// Returns the description of the check named `check_name`, if it has one.
+    pub fn check_description(&self, check_name: &str) -> Option<&str> {
+        self.check_descriptions.get(check_name).map(String::as_str)
+    }
+
+    /// Returns the tags identifying the check named `check_name` in stored
+    /// metrics: `check.name` and `check.<key>` for each of its metadata entries.
+    ///
+    /// `check.name` always holds the check's name, even if the check has a
+    /// metadata entry called `name`.
+    pub fn check_tags(&self, check_name: &str) -> BTreeMap<String, String> {
+        let mut tags: BTreeMap<String, String> = self
+            .check_metadata
+            .get(check_name)
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| (format!("{CHECK_TAG_PREFIX}{key}"), value.clone()))
+            .collect();
+        tags.insert(format!("{CHECK_TAG_PREFIX}name"), check_name.to_string());
+        tags
+    }
+
     /// Adds an issue to the report.
     pub fn add_issue(&mut self, issue: ValidationIssue) {
         self.issues.push(issue);
     }
 
-    /// Returns true if there are any error-level issues.
+    /// Aggregates the constraint outcomes into one status per check, in the
+    /// order the checks first appear, see [`CheckStatus`].
+    ///
+    /// Checks without constraints have no outcomes and are left out.
+    pub fn check_statuses(&self) -> Vec<(&str, CheckStatus)> {
+        check_statuses(&self.outcomes)
+    }
+
+    /// Returns true if there are any error-level issues not waived as known issues.
+    ///
+    /// A report with errors makes a [`ValidationResult::Failure`], see
+    /// [`ValidationResult::from_report`].
     pub fn has_errors(&self) -> bool {
-        self.issues.iter().any(|issue| issue.level == Level::Error)
+        self.issues
+            .iter()
+            .any(|issue| issue.level == Level::Error && !issue.is_known_issue())
     }
 
     /// Returns true if there are any warning-level issues.
@@ -108,6 +630,22 @@ impl ValidationReport {
             .any(|issue| issue.level == Level::Warning)
     }
 
+    /// Gets all failures of [`Level::Info`] checks.
+    ///
+    /// These are observations: they never fail the run and are counted in
+    /// `metrics.info_findings` rather than `metrics.failed_checks`.
+    pub fn observations(&self) -> Vec<&ValidationIssue> {
+        self.issues_by_level(Level::Info)
+    }
+
+    /// Gets all failures waived by known issues.
+    pub fn known_issues(&self) -> Vec<&ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.is_known_issue())
+            .collect()
+    }
+
     /// Gets all issues of a specific level.
     pub fn issues_by_level(&self, level: Level) -> Vec<&ValidationIssue> {
         self.issues
@@ -115,6 +653,119 @@ impl ValidationReport {
             .filter(|issue| issue.level == level)
             .collect()
     }
+
+    /// Summarizes the constraint outcomes per quality dimension, in dimension order.
+    ///
+    /// Every failed constraint is reported as one issue. Like
+    /// [`ValidationMetrics::success_rate`], the pass rate leaves out failures of
+    /// [`Level::Info`] checks and failures waived by known issues, which count as
+    /// issues only, and skipped constraints.
+    /// Constraints without a dimension are left out, as are dimensions without
+    /// constraints.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{
+    ///     ConstraintOutcome, ConstraintStatus, Level, QualityDimension, ValidationReport,
+    /// };
+    ///
+    /// let mut report = ValidationReport::new("customers");
+    /// for (name, status) in [
+    ///     ("completeness", ConstraintStatus::Success),
+    ///     ("completeness", ConstraintStatus::Failure),
+    /// ] {
+    ///     report.outcomes.push(ConstraintOutcome {
+    ///         check_name: "contacts".to_string(),
+    ///         constraint_name: name.to_string(),
+    ///         level: Level::Error,
+    ///         status,
+    ///         waived: false,
+    ///         metric: None,
+    ///         threshold: None,
+    ///         dimension: Some(QualityDimension::Completeness),
+    ///         constraint_id: None,
+    ///     });
+    /// }
+    ///
+    /// let summary = report.dimension_summary();
+    /// assert_eq!(summary[0].dimension, QualityDimension::Completeness);
+    /// assert_eq!(summary[0].issues, 1);
+    /// assert_eq!(summary[0].pass_rate, 50.0);
+    /// ```
+    pub fn dimension_summary(&self) -> Vec<DimensionSummary> {
+        let mut summaries: BTreeMap<&QualityDimension, DimensionSummary> = BTreeMap::new();
+        for outcome in &self.outcomes {
+            let Some(dimension) = &outcome.dimension else {
+                continue;
+            };
+            let summary = summaries
+                .entry(dimension)
+                .or_insert_with(|| DimensionSummary::new(dimension.clone()));
+            summary.constraints += 1;
+            match outcome.status {
+                ConstraintStatus::Success => summary.passed += 1,
+                ConstraintStatus::Skipped => summary.skipped += 1,
+                ConstraintStatus::Failure => {
+                    summary.issues += 1;
+                    if outcome.level != Level::Info && !outcome.waived {
+                        summary.failed += 1;
+                    }
+                }
+            }
+        }
+
+        summaries
+            .into_values()
+            .map(|mut summary| {
+                let rated = summary.passed + summary.failed;
+                if rated > 0 {
+                    summary.pass_rate = (summary.passed as f64 / rated as f64) * 100.0;
+                }
+                summary
+            })
+            .collect()
+    }
+
+    /// Groups the issues by [`ValidationIssue::routing_id`].
+    ///
+    /// Issues of checks with an explicit id are grouped under that id, which stays
+    /// the same when the check is renamed. Other issues are grouped under their
+    /// constraint id. Issues without either, such as those added by custom code,
+    /// are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, ConstraintOptions, ValidationSuite};
+    /// use datafusion::prelude::*;
+    ///
+    /// # async fn example(ctx: &SessionContext) -> Result<(), Box<dyn std::error::Error>> {
+    /// let suite = ValidationSuite::builder("orders")
+    ///     .check(
+    ///         Check::builder("Orders completeness")
+    ///             .id("orders.completeness.v1")
+    ///             .completeness("customer_id", ConstraintOptions::new().with_threshold(1.0))
+    ///             .build(),
+    ///     )
+    ///     .build();
+    ///
+    /// let result = suite.run(ctx).await?;
+    /// for (id, issues) in result.report().issues_by_id() {
+    ///     println!("{id}: {} issue(s)", issues.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn issues_by_id(&self) -> BTreeMap<&str, Vec<&ValidationIssue>> {
+        let mut grouped: BTreeMap<&str, Vec<&ValidationIssue>> = BTreeMap::new();
+        for issue in &self.issues {
+            if let Some(id) = issue.routing_id() {
+                grouped.entry(id).or_default().push(issue);
+            }
+        }
+        grouped
+    }
 }
 
 /// The result of running a validation suite.
@@ -146,7 +797,24 @@ impl ValidationResult {
         ValidationResult::Failure { report }
     }
 
+    /// Creates the result of a report: a failure if the report
+    /// [has errors](ValidationReport::has_errors), a success with the report's
+    /// metrics otherwise.
+    ///
+    /// Failures of [`Level::Warning`] and [`Level::Info`] checks and waived
+    /// failures do not fail the validation.
+    pub fn from_report(report: ValidationReport) -> Self {
+        if report.has_errors() {
+            ValidationResult::failure(report)
+        } else {
+            ValidationResult::success(report.metrics.clone(), report)
+        }
+    }
+
     /// Returns true if the validation succeeded.
+    ///
+    /// Results of a suite run succeed unless the report has errors, see
+    /// [`ValidationResult::from_report`].
     pub fn is_success(&self) -> bool {
         matches!(self, ValidationResult::Success { .. })
     }
@@ -172,6 +840,49 @@ impl ValidationResult {
         }
     }
 
+    /// Deserializes a validation result from JSON, upgrading older report schema versions.
+    ///
+    /// This accepts the output of [`to_json`](Self::to_json) and direct
+    /// `serde_json` serialization. See [`ValidationReport::from_json_str`] for the
+    /// supported schema versions.
+    pub fn from_json_str(json: &str) -> crate::prelude::Result<Self> {
+        let mut value: serde_json::Value = serde_json::from_str(json).map_err(|e| {
+            TermError::Serialization(format!("Failed to parse validation result: {e}"))
+        })?;
+
+        let report = value
+            .get_mut("report")
+            .map(serde_json::Value::take)
+            .ok_or_else(|| {
+                TermError::Serialization("Validation result is missing its report".to_string())
+            })?;
+        let report = ValidationReport::from_json_value(report)?;
+
+        let status = value.get("status").and_then(|s| s.as_str()).unwrap_or("");
+        match status {
+            "success" => {
+                let metrics = value
+                    .get_mut("metrics")
+                    .map(serde_json::Value::take)
+                    .ok_or_else(|| {
+                        TermError::Serialization(
+                            "Successful validation result is missing its metrics".to_string(),
+                        )
+                    })?;
+                let metrics = serde_json::from_value(metrics).map_err(|e| {
+                    TermError::Serialization(format!(
+                        "Failed to deserialize validation metrics: {e}"
+                    ))
+                })?;
+                Ok(ValidationResult::success(metrics, report))
+            }
+            "failure" => Ok(ValidationResult::failure(report)),
+            other => Err(TermError::Serialization(format!(
+                "Unknown validation result status '{other}'"
+            ))),
+        }
+    }
+
     /// Formats the validation result as JSON.
     ///
     /// This is a convenience method that uses the `JsonFormatter` to output
@@ -288,7 +999,237 @@ mod tests {
 
         metrics.total_checks = 10;
         metrics.passed_checks = 8;
+        metrics.failed_checks = 2;
         assert_eq!(metrics.success_rate(), 80.0);
+
+        // Info findings and skipped checks are neither passes nor failures
+        metrics.total_checks = 14;
+        metrics.info_findings = 2;
+        metrics.skipped_checks = 2;
+        assert_eq!(metrics.rated_checks(), 10);
+        assert_eq!(metrics.success_rate(), 80.0);
+
+        let mut skipped_only = ValidationMetrics::new();
+        skipped_only.record_check(CheckStatus::Skipped);
+        assert_eq!(skipped_only.rated_checks(), 0);
+        assert_eq!(skipped_only.success_rate(), 100.0);
+    }
+
+    fn outcome(check: &str, level: Level, status: ConstraintStatus) -> ConstraintOutcome {
+        ConstraintOutcome {
+            check_name: check.to_string(),
+            constraint_name: format!("{status:?}").to_lowercase(),
+            level,
+            status,
+            waived: false,
+            metric: None,
+            threshold: None,
+            dimension: None,
+            constraint_id: None,
+        }
+    }
+
+    /// A report as the suite runner builds it: one outcome per constraint, an
+    /// issue per failed constraint and the metrics counted per check.
+    fn synthetic_report(outcomes: Vec<ConstraintOutcome>) -> ValidationReport {
+        let mut report = ValidationReport::new("synthetic");
+        for outcome in &outcomes {
+            if outcome.status == ConstraintStatus::Failure {
+                report.add_issue(ValidationIssue {
+                    check_name: outcome.check_name.clone(),
+                    constraint_name: outcome.constraint_name.clone(),
+                    level: outcome.level,
+                    message: "failed".to_string(),
+                    metric: None,
+                    cached: false,
+                    known_issue: outcome.waived.then(|| KnownIssue::new("JIRA-1234", None)),
+                    check_id: None,
+                    constraint_id: None,
+                    impacts: Vec::new(),
+                });
+            }
+        }
+        report.metrics = ValidationMetrics::from_outcomes(&outcomes);
+        report.outcomes = outcomes;
+        report
+    }
+
+    fn counts(metrics: &ValidationMetrics) -> (usize, usize, usize, usize, usize) {
+        (
+            metrics.total_checks,
+            metrics.passed_checks,
+            metrics.failed_checks,
+            metrics.skipped_checks,
+            metrics.info_findings,
+        )
+    }
+
+    #[test]
+    fn test_metrics_count_checks_not_constraints() {
+        use ConstraintStatus::*;
+
+        let report = synthetic_report(vec![
+            // Two failing constraints fail one check
+            outcome("ids", Level::Error, Failure),
+            outcome("ids", Level::Error, Failure),
+            outcome("ids", Level::Error, Success),
+            // A skipped constraint does not keep its check from passing
+            outcome("amounts", Level::Warning, Success),
+            outcome("amounts", Level::Warning, Skipped),
+            // Nor does it count as passed when nothing else ran
+            outcome("freshness", Level::Error, Skipped),
+            outcome("freshness", Level::Error, Skipped),
+            // Info-level failures are findings, whatever else the check holds
+            outcome("profile", Level::Info, Success),
+            outcome("profile", Level::Info, Failure),
+            outcome("volume", Level::Error, Success),
+        ]);
+
+        assert_eq!(counts(&report.metrics), (5, 2, 1, 1, 1));
+        assert_eq!(report.metrics.success_rate(), 2.0 / 3.0 * 100.0);
+        assert_eq!(
+            report.check_statuses(),
+            vec![
+                ("ids", CheckStatus::Failed),
+                ("amounts", CheckStatus::Passed),
+                ("freshness", CheckStatus::Skipped),
+                ("profile", CheckStatus::InfoFinding),
+                ("volume", CheckStatus::Passed),
+            ]
+        );
+
+        // Every failed check has at least one issue of its own
+        let failed_checks: std::collections::BTreeSet<_> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.level != Level::Info)
+            .map(|issue| issue.check_name.as_str())
+            .collect();
+        assert_eq!(failed_checks.len(), report.metrics.failed_checks);
+        assert_eq!(report.issues.len(), 3);
+
+        let result = ValidationResult::from_report(report);
+        assert!(result.is_failure());
+    }
+
+    #[test]
+    fn test_metrics_over_all_single_check_reports() {
+        use ConstraintStatus::*;
+
+        // Every check of up to three constraints at every level
+        let mut sequences: Vec<Vec<ConstraintStatus>> = vec![vec![]];
+        let mut longest = sequences.clone();
+        for _ in 0..3 {
+            longest = longest
+                .iter()
+                .flat_map(|sequence| {
+                    [Success, Failure, Skipped].map(|status| {
+                        let mut longer = sequence.clone();
+                        longer.push(status);
+                        longer
+                    })
+                })
+                .collect();
+            sequences.extend(longest.iter().cloned());
+        }
+        assert_eq!(sequences.len(), 1 + 3 + 9 + 27);
+
+        for level in [Level::Info, Level::Warning, Level::Error] {
+            for statuses in &sequences {
+                let report = synthetic_report(
+                    statuses
+                        .iter()
+                        .map(|status| outcome("check", level, *status))
+                        .collect(),
+                );
+                let metrics = &report.metrics;
+                let failed = statuses.contains(&Failure);
+                let passed = statuses.contains(&Success);
+                let context = format!("{level:?} {statuses:?}");
+
+                if statuses.is_empty() {
+                    // A check without outcomes is invisible in the report
+                    assert_eq!(counts(metrics), (0, 0, 0, 0, 0), "{context}");
+                } else if failed && level == Level::Info {
+                    assert_eq!(counts(metrics), (1, 0, 0, 0, 1), "{context}");
+                } else if failed {
+                    assert_eq!(counts(metrics), (1, 0, 1, 0, 0), "{context}");
+                } else if passed {
+                    assert_eq!(counts(metrics), (1, 1, 0, 0, 0), "{context}");
+                } else {
+                    assert_eq!(counts(metrics), (1, 0, 0, 1, 0), "{context}");
+                }
+                assert_eq!(
+                    metrics.success_rate(),
+                    if failed && level != Level::Info {
+                        0.0
+                    } else {
+                        100.0
+                    },
+                    "{context}"
+                );
+
+                // Only unwaived Error-level failures fail the validation
+                let result = ValidationResult::from_report(report);
+                assert_eq!(
+                    result.is_success(),
+                    !(failed && level == Level::Error),
+                    "{context}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_report_succeeds() {
+        let report = synthetic_report(vec![]);
+        assert_eq!(counts(&report.metrics), (0, 0, 0, 0, 0));
+        assert_eq!(report.metrics.success_rate(), 100.0);
+        assert!(report.check_statuses().is_empty());
+
+        let mut metrics = ValidationMetrics::new();
+        metrics.record_check(CheckStatus::aggregate(Level::Error, []));
+        assert_eq!(counts(&metrics), (1, 0, 0, 1, 0));
+
+        let result = ValidationResult::from_report(report);
+        assert!(result.is_success());
+        assert_eq!(result.metrics().map(counts), Some((0, 0, 0, 0, 0)));
+    }
+
+    #[test]
+    fn test_waived_failures_are_counted_apart() {
+        let waived = |check| ConstraintOutcome {
+            waived: true,
+            ..outcome(check, Level::Error, ConstraintStatus::Failure)
+        };
+        let report = synthetic_report(vec![
+            waived("revenue"),
+            outcome("revenue", Level::Error, ConstraintStatus::Success),
+            outcome("ids", Level::Error, ConstraintStatus::Success),
+        ]);
+
+        assert_eq!(counts(&report.metrics), (2, 1, 0, 0, 0));
+        assert_eq!(report.metrics.waived_checks, 1);
+        assert_eq!(report.metrics.rated_checks(), 1);
+        assert_eq!(report.metrics.success_rate(), 100.0);
+        assert_eq!(
+            report.check_statuses(),
+            vec![
+                ("revenue", CheckStatus::Waived),
+                ("ids", CheckStatus::Passed)
+            ]
+        );
+        assert_eq!(report.known_issues().len(), 1);
+        assert!(ValidationResult::from_report(report).is_success());
+
+        // A failure the known issue does not cover still fails the check
+        let report = synthetic_report(vec![
+            waived("revenue"),
+            outcome("revenue", Level::Error, ConstraintStatus::Failure),
+        ]);
+        assert_eq!(counts(&report.metrics), (1, 0, 1, 0, 0));
+        assert_eq!(report.metrics.waived_checks, 0);
+        assert!(ValidationResult::from_report(report).is_failure());
     }
 
     #[test]
@@ -303,6 +1244,11 @@ mod tests {
             level: Level::Error,
             message: "Test error".to_string(),
             metric: Some(0.5),
+            cached: false,
+            known_issue: None,
+            check_id: None,
+            constraint_id: None,
+            impacts: Vec::new(),
         });
 
         assert!(report.has_errors());
@@ -337,6 +1283,11 @@ mod tests {
             level: Level::Warning,
             message: "Test warning message".to_string(),
             metric: Some(0.8),
+            cached: false,
+            known_issue: None,
+            check_id: None,
+            constraint_id: None,
+            impacts: Vec::new(),
         });
 
         let result = ValidationResult::success(metrics, report);
@@ -362,4 +1313,170 @@ mod tests {
         assert!(markdown_output.contains("## ✅ Validation Report - PASSED"));
         assert!(markdown_output.contains("test_suite"));
     }
+
+    fn report_with_all_levels() -> ValidationReport {
+        let mut report = ValidationReport::new("données_suite");
+        report.metrics.total_checks = 3;
+        report.metrics.passed_checks = 1;
+        report.metrics.failed_checks = 1;
+        report.metrics.skipped_checks = 1;
+        report
+            .metrics
+            .custom_metrics
+            .insert("completeness.列".to_string(), 0.75);
+        for (level, message) in [
+            (Level::Error, "Ünïcödé failure: 顧客ID が重複しています"),
+            (Level::Warning, "Ratio below threshold ⚠️"),
+            (Level::Info, "Skipped: таблица пуста"),
+        ] {
+            report.add_issue(ValidationIssue {
+                check_name: format!("{level}_check"),
+                constraint_name: "completeness".to_string(),
+                level,
+                message: message.to_string(),
+                metric: Some(0.5),
+                cached: false,
+                known_issue: None,
+                check_id: None,
+                constraint_id: None,
+                impacts: Vec::new(),
+            });
+        }
+        report
+    }
+
+    #[test]
+    fn test_validation_result_round_trip() {
+        let report = report_with_all_levels();
+        let results = [
+            ValidationResult::success(report.metrics.clone(), report.clone()),
+            ValidationResult::failure(report),
+        ];
+
+        for result in results {
+            let json = serde_json::to_string(&result).unwrap();
+            let restored = ValidationResult::from_json_str(&json).unwrap();
+            assert_eq!(restored.is_success(), result.is_success());
+            assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+            let formatted = ValidationResult::from_json_str(&result.to_json().unwrap()).unwrap();
+            assert_eq!(formatted.report().issues.len(), 3);
+            assert_eq!(
+                formatted.report().issues[0].message,
+                "Ünïcödé failure: 顧客ID が重複しています"
+            );
+        }
+    }
+
+    #[test]
+    fn test_constraint_status_round_trip() {
+        use crate::core::{ConstraintResult, ConstraintStatus};
+
+        let results = [
+            ConstraintResult::success_with_metric(1.0),
+            ConstraintResult::failure("Ratio 0.5 < 0.9 für Spalte «名前»"),
+            ConstraintResult::skipped("No data"),
+        ];
+        let expected = [
+            ConstraintStatus::Success,
+            ConstraintStatus::Failure,
+            ConstraintStatus::Skipped,
+        ];
+
+        for (result, status) in results.iter().zip(expected) {
+            let json = serde_json::to_string(result).unwrap();
+            let restored: ConstraintResult = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.status, status);
+            assert_eq!(restored.metric, result.metric);
+            assert_eq!(restored.message, result.message);
+        }
+        assert_eq!(
+            serde_json::to_string(&ConstraintStatus::Skipped).unwrap(),
+            "\"skipped\""
+        );
+    }
+
+    #[test]
+    fn test_report_from_legacy_version() {
+        let legacy = r#"{
+            "suite_name": "legacy",
+            "timestamp": "2025-01-01T00:00:00Z",
+            "metrics": {
+                "total_checks": 2,
+                "passed_checks": 1,
+                "failed_checks": 1,
+                "skipped_checks": 0,
+                "execution_time_ms": 5
+            },
+            "issues": [{
+                "check_name": "c",
+                "constraint_name": "uniqueness",
+                "level": "error",
+                "message": "duplicate ✗"
+            }]
+        }"#;
+
+        let report = ValidationReport::from_json_str(legacy).unwrap();
+        assert_eq!(report.report_schema_version, 1);
+        assert!(report.metrics.custom_metrics.is_empty());
+        assert!(report.metrics.performance.is_empty());
+        assert_eq!(report.issues[0].metric, None);
+
+        let result = ValidationResult::from_json_str(&format!(
+            r#"{{"status": "failure", "report": {legacy}}}"#
+        ))
+        .unwrap();
+        assert!(result.is_failure());
+        assert_eq!(result.report().suite_name, "legacy");
+    }
+
+    #[test]
+    fn test_report_from_version_5_keeps_its_version() {
+        // Version 5 counted constraints: two of the three constraints of the
+        // `ids` check failed
+        let v5 = r#"{
+            "report_schema_version": 5,
+            "suite_name": "orders",
+            "timestamp": "2025-06-01T00:00:00Z",
+            "metrics": {
+                "total_checks": 3,
+                "passed_checks": 1,
+                "failed_checks": 2,
+                "skipped_checks": 0,
+                "info_findings": 0,
+                "execution_time_ms": 7
+            },
+            "issues": [],
+            "outcomes": [
+                {"check_name": "ids", "constraint_name": "a", "level": "error",
+                 "status": "success", "constraint_id": "completeness-1"},
+                {"check_name": "ids", "constraint_name": "b", "level": "error",
+                 "status": "failure", "constraint_id": "uniqueness-2"},
+                {"check_name": "ids", "constraint_name": "c", "level": "error",
+                 "status": "failure", "constraint_id": "uniqueness-3"}
+            ]
+        }"#;
+
+        let report = ValidationReport::from_json_str(v5).unwrap();
+        assert_eq!(report.report_schema_version, 5);
+        assert_eq!(counts(&report.metrics), (3, 1, 2, 0, 0));
+        assert_eq!(report.metrics.waived_checks, 0);
+        assert!(report.outcomes.iter().all(|outcome| !outcome.waived));
+        assert_eq!(report.check_statuses(), vec![("ids", CheckStatus::Failed)]);
+
+        let restored =
+            ValidationReport::from_json_str(&serde_json::to_string(&report).unwrap()).unwrap();
+        assert_eq!(restored.report_schema_version, 5);
+    }
+
+    #[test]
+    fn test_report_from_newer_version_is_rejected() {
+        let mut value = serde_json::to_value(ValidationReport::new("future")).unwrap();
+        value["report_schema_version"] = serde_json::json!(REPORT_SCHEMA_VERSION + 1);
+
+        let err = ValidationReport::from_json_str(&value.to_string()).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unsupported report schema version"));
+    }
 }
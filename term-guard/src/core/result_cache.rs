@@ -0,0 +1,513 @@
+//! Caching of constraint results for unchanged tables.
+//!
+//! A [`ResultCache`] lets a [`ValidationSuite`](super::ValidationSuite) skip
+//! constraints whose inputs have not changed since a previous run. Before running,
+//! the suite fingerprints its configuration and the table it validates; results
+//! stored under the same pair of fingerprints are reused instead of re-evaluated.
+//!
+//! Table fingerprints come from, in order of preference:
+//! 1. A version registered with [`ResultCache::with_table_version`]
+//! 2. The file manifest (path, size, modification time) of file-backed tables,
+//!    such as those registered by the CSV, Parquet and JSON sources
+//!
+//! [Lookup tables](super::LookupTable) embedded in the suite are fingerprinted
+//! by their contents.
+//!
+//! Tables without a fingerprint (for example in-memory tables without a
+//! registered version) are never cached. Constraints that read additional tables
+//! (see [`Constraint::referenced_tables`](super::Constraint::referenced_tables))
+//! are only reused when the fingerprints of all involved tables match.
+//!
+//! Results are persisted through the [`StateStore`] abstraction used by
+//! incremental analysis, one state partition per suite/table fingerprint pair.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use term_guard::analyzers::incremental::FileSystemStateStore;
+//! use term_guard::constraints::Assertion;
+//! use term_guard::core::{Check, ResultCache, ValidationSuite};
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let cache = ResultCache::new(FileSystemStateStore::new("/tmp/term_result_cache")?)
+//!     .with_ttl(Duration::from_secs(24 * 60 * 60));
+//!
+//! let suite = ValidationSuite::builder("orders")
+//!     .table_name("orders")
+//!     .check(Check::builder("basics").has_size(Assertion::GreaterThan(0.0)).build())
+//!     .with_result_cache(cache)
+//!     .build();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use datafusion::datasource::listing::ListingTable;
+use datafusion::prelude::*;
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+
+use super::{Check, ConstraintResult, LookupTable, ParameterBag};
+use crate::analyzers::incremental::{StateMap, StateStore};
+use crate::prelude::*;
+
+/// Key of the cached results within a state partition.
+const RESULTS_KEY: &str = "constraint_results";
+
+/// A cache of constraint results keyed by suite and table fingerprints.
+///
+/// The cache is cheap to clone and can be shared between suites and
+/// concurrent runs; all clones use the same underlying store.
+#[derive(Clone)]
+pub struct ResultCache {
+    store: Arc<dyn StateStore>,
+    ttl: Option<Duration>,
+    table_versions: HashMap<String, String>,
+}
+
+impl ResultCache {
+    /// Creates a cache persisting results in the given store.
+    pub fn new(store: impl StateStore + 'static) -> Self {
+        Self::from_store(Arc::new(store))
+    }
+
+    /// Creates a cache from a shared store.
+    pub fn from_store(store: Arc<dyn StateStore>) -> Self {
+        Self {
+            store,
+            ttl: None,
+            table_versions: HashMap::new(),
+        }
+    }
+
+    /// Sets how long cached results stay valid (default: forever).
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Registers a version for a table, used instead of its file manifest.
+    ///
+    /// Use this for tables that are not file-backed, or when the producer of the
+    /// data already tracks a version (e.g. a partition id or snapshot number).
+    pub fn with_table_version(
+        mut self,
+        table_name: impl Into<String>,
+        version: impl Into<String>,
+    ) -> Self {
+        self.table_versions
+            .insert(table_name.into(), version.into());
+        self
+    }
+
+    /// Computes the fingerprint of a table, or `None` if it cannot be fingerprinted.
+    pub async fn table_fingerprint(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+    ) -> Result<Option<String>> {
+        if let Some(version) = self.table_versions.get(table_name) {
+            return Ok(Some(hash_parts(["version", version])));
+        }
+
+        source_fingerprint(ctx, table_name).await
+    }
+
+    /// Loads reusable results for a suite run.
+    ///
+    /// Returns `None` if the validated table cannot be fingerprinted. Errors from
+    /// the store are logged and treated as a cache miss.
+    pub(crate) async fn lookup(
+        &self,
+        ctx: &SessionContext,
+        suite_fingerprint: &str,
+        table_name: &str,
+        checks: &[Arc<Check>],
+        lookups: &[&LookupTable],
+    ) -> Result<Option<CacheRun>> {
+        let Some(table_fingerprint) = self.table_fingerprint(ctx, table_name).await? else {
+            debug!(table = %table_name, "Table has no fingerprint, result cache disabled");
+            return Ok(None);
+        };
+        let partition = hash_parts([suite_fingerprint, table_fingerprint.as_str()]);
+
+        // Fingerprints of the additional tables read by each constraint
+        let mut dependencies = HashMap::new();
+        let mut table_fingerprints: HashMap<String, Option<String>> = lookups
+            .iter()
+            .map(|lookup| {
+                let fingerprint = hash_parts(["lookup", lookup.fingerprint()]);
+                (lookup.name().to_string(), Some(fingerprint))
+            })
+            .collect();
+        for (check_index, check) in checks.iter().enumerate() {
+            for (constraint_index, constraint) in check.constraints().iter().enumerate() {
+                let mut fingerprints = BTreeMap::new();
+                let mut cacheable = true;
+                for table in constraint.referenced_tables() {
+                    if table == table_name {
+                        continue;
+                    }
+                    if !table_fingerprints.contains_key(&table) {
+                        let fingerprint = self.table_fingerprint(ctx, &table).await?;
+                        table_fingerprints.insert(table.clone(), fingerprint);
+                    }
+                    match &table_fingerprints[&table] {
+                        Some(fingerprint) => {
+                            fingerprints.insert(table, fingerprint.clone());
+                        }
+                        None => cacheable = false,
+                    }
+                }
+                if cacheable {
+                    dependencies.insert((check_index, constraint_index), fingerprints);
+                }
+            }
+        }
+
+        let stored = match self.store.load_state(&partition).await {
+            Ok(state) => state
+                .get(RESULTS_KEY)
+                .and_then(|bytes| match serde_json::from_slice::<Vec<CachedEntry>>(bytes) {
+                    Ok(entries) => Some(entries),
+                    Err(e) => {
+                        warn!(partition = %partition, error = %e, "Ignoring unreadable cache entry");
+                        None
+                    }
+                })
+                .unwrap_or_default(),
+            Err(e) => {
+                warn!(partition = %partition, error = %e, "Failed to load cached results");
+                Vec::new()
+            }
+        };
+
+        let now = Utc::now();
+        let mut hits = HashMap::new();
+        for entry in stored {
+            let key = (entry.check_index, entry.constraint_index);
+            let Some(current) = dependencies.get(&key) else {
+                continue;
+            };
+            let expired = self.ttl.is_some_and(|ttl| {
+                (now - entry.computed_at)
+                    .to_std()
+                    .is_ok_and(|age| age > ttl)
+            });
+            if !expired && &entry.dependencies == current {
+                hits.insert(key, entry);
+            }
+        }
+
+        debug!(
+            partition = %partition,
+            hits = hits.len(),
+            "Loaded cached constraint results"
+        );
+
+        Ok(Some(CacheRun {
+            partition,
+            dependencies,
+            hits,
+            entries: Vec::new(),
+            modified: false,
+        }))
+    }
+
+    /// Persists the results of a suite run.
+    pub(crate) async fn store(&self, run: CacheRun) {
+        if !run.modified {
+            return;
+        }
+        let bytes = match serde_json::to_vec(&run.entries) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!(error = %e, "Failed to serialize cached results");
+                return;
+            }
+        };
+        let mut state = StateMap::new();
+        state.insert(RESULTS_KEY.to_string(), bytes);
+        if let Err(e) = self.store.save_state(&run.partition, state).await {
+            warn!(partition = %run.partition, error = %e, "Failed to store cached results");
+        }
+    }
+}
+
+impl fmt::Debug for ResultCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResultCache")
+            .field("ttl", &self.ttl)
+            .field("table_versions", &self.table_versions)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Fingerprints the file manifest of a file-backed table.
+///
+/// Returns `None` for tables that are not registered or not backed by files.
+pub(crate) async fn source_fingerprint(
+    ctx: &SessionContext,
+    table_name: &str,
+) -> Result<Option<String>> {
+    let provider = match ctx.table_provider(table_name).await {
+        Ok(provider) => provider,
+        Err(_) => return Ok(None),
+    };
+    let Some(listing) = provider.as_any().downcast_ref::<ListingTable>() else {
+        return Ok(None);
+    };
+
+    let state = ctx.state();
+    let extension = &listing.options().file_extension;
+    let mut files = Vec::new();
+    for url in listing.table_paths() {
+        let store = state.runtime_env().object_store(url)?;
+        let mut objects = url
+            .list_all_files(&state, store.as_ref(), extension)
+            .await?;
+        while let Some(object) = objects.try_next().await? {
+            files.push(format!(
+                "{}|{}|{}|{}",
+                object.location,
+                object.size,
+                object.last_modified.to_rfc3339(),
+                object.e_tag.unwrap_or_default()
+            ));
+        }
+    }
+    files.sort();
+
+    Ok(Some(hash_parts(
+        std::iter::once("manifest").chain(files.iter().map(String::as_str)),
+    )))
+}
+
+/// Computes the fingerprint of a suite's configuration.
+///
+/// The fingerprint covers the suite name, the validated table, its column
+/// mapping, the contents of its lookup tables and the `Debug` representation of
+/// every check and constraint, so any configuration change invalidates cached
+/// results.
+pub(crate) fn suite_fingerprint(
+    name: &str,
+    table_name: &str,
+    column_mapping: &BTreeMap<String, String>,
+    checks: &[Arc<Check>],
+    parameters: &ParameterBag,
+    lookups: &[&LookupTable],
+) -> String {
+    let mut parts = vec![name.to_string(), table_name.to_string()];
+    for (logical, physical) in column_mapping {
+        parts.push(format!("{logical}->{physical}"));
+    }
+    for lookup in lookups {
+        parts.push(format!("lookup|{}|{}", lookup.name(), lookup.fingerprint()));
+    }
+    // Parameterized constraints only record parameter names, so include the values
+    for (param, value) in parameters.iter() {
+        parts.push(format!("{param}={value}"));
+    }
+    for check in checks {
+        parts.push(format!("{}|{:?}", check.name(), check.level()));
+        for constraint in check.constraints() {
+            parts.push(format!("{constraint:?}"));
+        }
+    }
+    hash_parts(parts.iter().map(String::as_str))
+}
+
+pub(crate) fn hash_parts<'a>(parts: impl IntoIterator<Item = &'a str>) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update([0u8]);
+    }
+    hex::encode(&hasher.finalize()[..16])
+}
+
+/// A stored constraint result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedEntry {
+    check_index: usize,
+    constraint_index: usize,
+    dependencies: BTreeMap<String, String>,
+    pub(crate) computed_at: DateTime<Utc>,
+    pub(crate) result: ConstraintResult,
+}
+
+/// Cache state for a single suite run.
+#[derive(Debug)]
+pub(crate) struct CacheRun {
+    partition: String,
+    dependencies: HashMap<(usize, usize), BTreeMap<String, String>>,
+    hits: HashMap<(usize, usize), CachedEntry>,
+    entries: Vec<CachedEntry>,
+    modified: bool,
+}
+
+impl CacheRun {
+    /// Returns the cached result for a constraint, if it can be reused.
+    pub(crate) fn get(
+        &mut self,
+        check_index: usize,
+        constraint_index: usize,
+    ) -> Option<&CachedEntry> {
+        let entry = self.hits.get(&(check_index, constraint_index))?;
+        self.entries.push(entry.clone());
+        Some(entry)
+    }
+
+    /// Carries the valid entries of a check that was not run over to the stored results.
+    pub(crate) fn keep_check(&mut self, check_index: usize) {
+        let mut kept: Vec<CachedEntry> = self
+            .hits
+            .iter()
+            .filter(|((index, _), _)| *index == check_index)
+            .map(|(_, entry)| entry.clone())
+            .collect();
+        kept.sort_by_key(|entry| entry.constraint_index);
+        self.entries.extend(kept);
+    }
+
+    /// Records a freshly computed result.
+    pub(crate) fn insert(
+        &mut self,
+        check_index: usize,
+        constraint_index: usize,
+        result: &ConstraintResult,
+    ) {
+        let Some(dependencies) = self.dependencies.get(&(check_index, constraint_index)) else {
+            return;
+        };
+        self.entries.push(CachedEntry {
+            check_index,
+            constraint_index,
+            dependencies: dependencies.clone(),
+            computed_at: Utc::now(),
+            result: result.clone(),
+        });
+        self.modified = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::incremental::FileSystemStateStore;
+    use crate::constraints::Assertion;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion::scalar::ScalarValue;
+
+    fn checks(min: f64) -> Vec<Arc<Check>> {
+        vec![Arc::new(
+            Check::builder("amounts")
+                .has_min("amount", Assertion::GreaterThan(min))
+                .build(),
+        )]
+    }
+
+    #[test]
+    fn test_suite_fingerprint_tracks_configuration() {
+        let params = ParameterBag::new();
+        let mapping = BTreeMap::new();
+        let fingerprint = suite_fingerprint("suite", "data", &mapping, &checks(0.0), &params, &[]);
+        assert_eq!(
+            fingerprint,
+            suite_fingerprint("suite", "data", &mapping, &checks(0.0), &params, &[])
+        );
+        assert_ne!(
+            fingerprint,
+            suite_fingerprint("suite", "data", &mapping, &checks(1.0), &params, &[])
+        );
+        assert_ne!(
+            fingerprint,
+            suite_fingerprint("suite", "other", &mapping, &checks(0.0), &params, &[])
+        );
+        assert_ne!(
+            fingerprint,
+            suite_fingerprint("other", "data", &mapping, &checks(0.0), &params, &[])
+        );
+        assert_ne!(
+            fingerprint,
+            suite_fingerprint(
+                "suite",
+                "data",
+                &mapping,
+                &checks(0.0),
+                &ParameterBag::new().with("min_amount", 1.0),
+                &[]
+            )
+        );
+        assert_ne!(
+            fingerprint,
+            suite_fingerprint(
+                "suite",
+                "data",
+                &BTreeMap::from([("amount".to_string(), "amt".to_string())]),
+                &checks(0.0),
+                &params,
+                &[]
+            )
+        );
+
+        let plans = |plan: &str| {
+            let schema = Arc::new(Schema::new(vec![Field::new("plan", DataType::Utf8, false)]));
+            LookupTable::try_new("valid_plans", vec![vec![ScalarValue::from(plan)]], schema)
+                .unwrap()
+        };
+        let (free, pro) = (plans("free"), plans("pro"));
+        let with_lookup = |lookup: &LookupTable| {
+            suite_fingerprint("suite", "data", &mapping, &checks(0.0), &params, &[lookup])
+        };
+        assert_ne!(fingerprint, with_lookup(&free));
+        assert_eq!(with_lookup(&free), with_lookup(&plans("free")));
+        assert_ne!(with_lookup(&free), with_lookup(&pro));
+    }
+
+    #[tokio::test]
+    async fn test_table_fingerprint_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResultCache::new(FileSystemStateStore::new(dir.path()).unwrap())
+            .with_table_version("versioned", "42");
+
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE memory AS VALUES (1)")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+
+        let csv_path = dir.path().join("data.csv");
+        std::fs::write(&csv_path, "a\n1\n").unwrap();
+        ctx.register_csv("files", csv_path.to_str().unwrap(), CsvReadOptions::new())
+            .await
+            .unwrap();
+
+        assert!(cache
+            .table_fingerprint(&ctx, "versioned")
+            .await
+            .unwrap()
+            .is_some());
+        assert_eq!(cache.table_fingerprint(&ctx, "memory").await.unwrap(), None);
+        assert_eq!(
+            cache.table_fingerprint(&ctx, "missing").await.unwrap(),
+            None
+        );
+
+        let before = cache.table_fingerprint(&ctx, "files").await.unwrap();
+        assert!(before.is_some());
+        std::fs::write(&csv_path, "a\n1\n2\n").unwrap();
+        let after = cache.table_fingerprint(&ctx, "files").await.unwrap();
+        assert_ne!(before, after);
+    }
+}
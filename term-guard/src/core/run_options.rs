@@ -0,0 +1,206 @@
+//! Per-run options of a validation suite.
+//!
+//! [`ValidationSuite::run`](super::ValidationSuite::run) runs every check with
+//! the suite's own settings. [`RunOptions`] collects what can change from one
+//! run of the same suite to the next, and is passed to
+//! [`ValidationSuite::run_with`](super::ValidationSuite::run_with).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::constraints::Assertion;
+//! use term_guard::core::{Check, CheckFilter, ParameterBag, RunOptions, ValidationSuite};
+//! use datafusion::prelude::*;
+//!
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! let ctx = SessionContext::new();
+//! ctx.sql("CREATE TABLE data (id INT) AS VALUES (1), (2)").await?.collect().await?;
+//!
+//! let suite = ValidationSuite::builder("orders")
+//!     .parameter_default("min_rows", 100.0)
+//!     .check(
+//!         Check::builder("volume")
+//!             .tag("smoke")
+//!             .has_size(Assertion::greater_than_or_equal_param("min_rows"))
+//!             .build(),
+//!     )
+//!     .check(Check::builder("not_empty").has_size(Assertion::GreaterThan(0.0)).build())
+//!     .build();
+//!
+//! let options = RunOptions::new()
+//!     .with_parameters(ParameterBag::new().with("min_rows", 2.0))
+//!     .with_filter(CheckFilter::new().tag("smoke"));
+//! let result = suite.run_with(&ctx, &options).await?;
+//! assert!(result.is_success());
+//! assert_eq!(result.report().excluded_checks[0].check_name, "not_empty");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+
+use std::collections::BTreeMap;
+
+use super::{
+    column_mapping, CheckFilter, Extensions, ParameterBag, PrecomputedStatistics, PreviewSpec,
+};
+
+/// Options of a single run of a validation suite.
+///
+/// The default options run every check of the suite on the full data, with
+/// the suite's parameter defaults and without extensions.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    parameters: ParameterBag,
+    extensions: Extensions,
+    statistics: Option<PrecomputedStatistics>,
+    filter: Option<CheckFilter>,
+    column_mapping: Option<BTreeMap<String, String>>,
+    preview: Option<PreviewSpec>,
+}
+
+impl RunOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets values for the suite's named parameters.
+    ///
+    /// `parameters` are layered over the suite's
+    /// [`parameter_defaults`](super::ValidationSuiteBuilder::parameter_defaults).
+    /// Every parameter referenced by a constraint must have a value within that
+    /// constraint's valid range; otherwise the run returns
+    /// [`TermError::InvalidConfiguration`](crate::error::TermError::InvalidConfiguration)
+    /// listing every missing or invalid parameter before any query runs. The
+    /// values used are listed in
+    /// [`ValidationReport::parameters`](super::ValidationReport::parameters).
+    pub fn with_parameters(mut self, parameters: ParameterBag) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// Sets per-run dependencies for custom constraints.
+    ///
+    /// Every constraint is evaluated with
+    /// [`Constraint::evaluate_with`](super::Constraint::evaluate_with), receiving
+    /// `extensions`; built-in constraints ignore them. Cached results are keyed
+    /// on the data and parameters only, so a suite with a
+    /// [`ResultCache`](super::ResultCache) may reuse a result computed with
+    /// different extensions.
+    pub fn with_extensions(mut self, extensions: Extensions) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Answers constraints from precomputed statistics.
+    ///
+    /// Constraints whose
+    /// [`Constraint::evaluate_from_statistics`](super::Constraint::evaluate_from_statistics)
+    /// returns a result for their column's statistics do not issue queries; all
+    /// others are evaluated normally. Constraints answered this way are listed in
+    /// [`ValidationReport::profiled_constraints`](super::ValidationReport::profiled_constraints).
+    pub fn with_statistics(mut self, statistics: PrecomputedStatistics) -> Self {
+        self.statistics = Some(statistics);
+        self
+    }
+
+    /// Runs only the checks selected by `filter`.
+    ///
+    /// Checks left out are listed in
+    /// [`ValidationReport::excluded_checks`](super::ValidationReport::excluded_checks)
+    /// and do not count towards the report metrics, so the success rate covers
+    /// only the executed checks. Use [`CheckFilter::failed_in`] to rerun the
+    /// checks that failed in a previous report.
+    pub fn with_filter(mut self, filter: CheckFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Sets the column mapping of this run.
+    ///
+    /// `mapping` replaces the mapping set with
+    /// [`with_column_mapping`](super::ValidationSuiteBuilder::with_column_mapping),
+    /// so one suite can validate sources that name their columns differently.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, ConstraintOptions, RunOptions, ValidationSuite};
+    /// use datafusion::prelude::*;
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let ctx = SessionContext::new();
+    /// ctx.sql("CREATE TABLE data (cust_id INT) AS VALUES (1), (2)").await?.collect().await?;
+    ///
+    /// let suite = ValidationSuite::builder("customers")
+    ///     .check(
+    ///         Check::builder("ids")
+    ///             .completeness("customer_id", ConstraintOptions::new().with_threshold(1.0))
+    ///             .build(),
+    ///     )
+    ///     .build();
+    ///
+    /// let options = RunOptions::new().with_column_mapping([("customer_id", "cust_id")]);
+    /// let result = suite.run_with(&ctx, &options).await?;
+    /// assert!(result.is_success());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn with_column_mapping<I, K, V>(mut self, mapping: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.column_mapping = Some(column_mapping::collect(mapping));
+        self
+    }
+
+    /// Runs the suite on the first rows of the validated table for fast feedback.
+    ///
+    /// At most `spec.limit` rows of the validated table, or of the derived table
+    /// if the suite has one, are copied into a separate session context that the
+    /// checks run against; the caller's context and its tables are not modified.
+    /// Constraints that [require the full data](super::Constraint::requires_full_data),
+    /// such as size assertions and cross-table reconciliations, are skipped. The
+    /// result cache is not used.
+    ///
+    /// The report is a normal report with
+    /// [`ValidationReport::preview`](super::ValidationReport::preview) set to the
+    /// sampled row counts and the skipped constraints. Passing a preview does not
+    /// mean the full run passes, but errors such as unknown columns surface the
+    /// same way.
+    pub fn with_preview(mut self, spec: PreviewSpec) -> Self {
+        self.preview = Some(spec);
+        self
+    }
+
+    /// Returns the parameter values of the run.
+    pub fn parameters(&self) -> &ParameterBag {
+        &self.parameters
+    }
+
+    /// Returns the extensions passed to the constraints.
+    pub fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+
+    /// Returns the precomputed statistics, if set.
+    pub fn statistics(&self) -> Option<&PrecomputedStatistics> {
+        self.statistics.as_ref()
+    }
+
+    /// Returns the check filter, if set.
+    pub fn filter(&self) -> Option<&CheckFilter> {
+        self.filter.as_ref()
+    }
+
+    /// Returns the column mapping of this run, if set.
+    pub fn column_mapping(&self) -> Option<&BTreeMap<String, String>> {
+        self.column_mapping.as_ref()
+    }
+
+    /// Returns the preview spec, if the run is a preview.
+    pub fn preview(&self) -> Option<PreviewSpec> {
+        self.preview
+    }
+}
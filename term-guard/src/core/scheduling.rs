@@ -0,0 +1,476 @@
+//! Ordering of the checks of a suite run.
+//!
+//! Checks run in the order they were added to the suite by default, so in a
+//! long suite the checks most likely to fail may only report at the very end.
+//! An [`ExecutionOrder`] set with
+//! [`ValidationSuiteBuilder::execution_order`](super::ValidationSuiteBuilder::execution_order)
+//! reorders them using an [`ExecutionHistory`] of previous runs: the expected
+//! duration and failure rate of every check.
+//!
+//! The history is read from the reports a
+//! [`MetricsRepository`] holds, saved with
+//! [`ReportAggregator::save_report`](super::ReportAggregator::save_report).
+//! Checks without history get defaults: a duration proportional to their
+//! number of constraints and an even chance of failing, so new checks run
+//! early under [`ExecutionOrder::LikelyFailuresFirst`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use term_guard::core::{ExecutionOrder, ValidationSuite};
+//! use term_guard::repository::InMemoryRepository;
+//!
+//! let suite = ValidationSuite::builder("orders")
+//!     .execution_order(ExecutionOrder::LikelyFailuresFirst)
+//!     .execution_history(Arc::new(InMemoryRepository::new()))
+//!     .build();
+//! assert_eq!(suite.execution_order().name(), "likely_failures_first");
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use super::{Check, ConstraintStatus, TimelinePhase, ValidationReport, REPORT_METADATA_KEY};
+use crate::prelude::*;
+use crate::repository::MetricsRepository;
+
+/// Expected duration of a constraint without history, in milliseconds.
+pub const DEFAULT_CONSTRAINT_DURATION_MS: f64 = 100.0;
+
+/// Compares the estimates of two checks; checks comparing as less run first.
+pub type CheckComparator = Arc<dyn Fn(&CheckEstimate, &CheckEstimate) -> Ordering + Send + Sync>;
+
+/// The order in which the checks of a suite run.
+#[derive(Clone, Default)]
+pub enum ExecutionOrder {
+    /// The order in which the checks were added to the suite
+    #[default]
+    BuilderOrder,
+    /// Checks with the shortest expected duration first
+    FastestFirst,
+    /// Checks with the highest failure rate per expected millisecond first,
+    /// so that cheap, frequently failing checks report as early as possible
+    LikelyFailuresFirst,
+    /// Checks ordered by a comparator of their estimates
+    Custom(CheckComparator),
+}
+
+impl ExecutionOrder {
+    /// Orders checks with `comparator`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::ExecutionOrder;
+    ///
+    /// // Checks with many constraints first
+    /// let order = ExecutionOrder::custom(|a, b| b.constraints.cmp(&a.constraints));
+    /// assert_eq!(order.name(), "custom");
+    /// ```
+    pub fn custom<F>(comparator: F) -> Self
+    where
+        F: Fn(&CheckEstimate, &CheckEstimate) -> Ordering + Send + Sync + 'static,
+    {
+        Self::Custom(Arc::new(comparator))
+    }
+
+    /// Returns the name of the order as recorded in reports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BuilderOrder => "builder_order",
+            Self::FastestFirst => "fastest_first",
+            Self::LikelyFailuresFirst => "likely_failures_first",
+            Self::Custom(_) => "custom",
+        }
+    }
+
+    /// Sorts `estimates` into execution order; ties keep the builder order.
+    pub fn sort(&self, estimates: &mut [CheckEstimate]) {
+        match self {
+            Self::BuilderOrder => estimates.sort_by_key(|estimate| estimate.position),
+            Self::FastestFirst => estimates.sort_by(|a, b| {
+                a.expected_duration_ms
+                    .total_cmp(&b.expected_duration_ms)
+                    .then(a.position.cmp(&b.position))
+            }),
+            Self::LikelyFailuresFirst => estimates.sort_by(|a, b| {
+                b.failures_per_ms()
+                    .total_cmp(&a.failures_per_ms())
+                    .then(a.position.cmp(&b.position))
+            }),
+            Self::Custom(comparator) => {
+                estimates.sort_by(|a, b| comparator(a, b).then(a.position.cmp(&b.position)))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ExecutionOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// What is expected of a check, based on its history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckEstimate {
+    /// The name of the check
+    pub check_name: String,
+    /// Position of the check in the suite
+    pub position: usize,
+    /// Number of constraints of the check
+    pub constraints: usize,
+    /// Expected duration of the check in milliseconds
+    pub expected_duration_ms: f64,
+    /// Estimated probability that the check fails
+    pub failure_rate: f64,
+    /// Number of previous runs the estimate is based on
+    pub history_runs: usize,
+}
+
+impl CheckEstimate {
+    /// Expected failures per millisecond spent running the check.
+    fn failures_per_ms(&self) -> f64 {
+        self.failure_rate / self.expected_duration_ms.max(f64::MIN_POSITIVE)
+    }
+}
+
+/// The execution order of a run, recorded in its report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionSchedule {
+    /// Name of the [`ExecutionOrder`] used
+    pub order: String,
+    /// Estimates of the checks, in the order they ran
+    pub checks: Vec<CheckEstimate>,
+}
+
+/// Outcomes and durations of a check across previous runs.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CheckHistory {
+    runs: usize,
+    failures: usize,
+    timed_runs: usize,
+    total_duration_ms: f64,
+}
+
+/// Outcomes and durations of checks in previous runs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExecutionHistory {
+    checks: HashMap<String, CheckHistory>,
+}
+
+impl ExecutionHistory {
+    /// Creates an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the history of `suite_name` from the reports saved in `repository`.
+    pub async fn load(repository: &dyn MetricsRepository, suite_name: &str) -> Result<Self> {
+        let mut history = Self::new();
+        for (_, context) in repository.load().await.execute().await? {
+            let Some(json) = context.metadata().custom.get(REPORT_METADATA_KEY) else {
+                continue;
+            };
+            let report = ValidationReport::from_json_str(json)?;
+            if report.suite_name == suite_name {
+                history.record_report(&report);
+            }
+        }
+        Ok(history)
+    }
+
+    /// Adds the checks of a previous run.
+    ///
+    /// A check failed if any of its constraints failed. Its duration is taken
+    /// from the report's timeline if it has one, and from the compute time of
+    /// its queries otherwise.
+    pub fn record_report(&mut self, report: &ValidationReport) {
+        let mut failed: HashMap<&str, bool> = HashMap::new();
+        for outcome in &report.outcomes {
+            *failed.entry(outcome.check_name.as_str()).or_default() |=
+                outcome.status == ConstraintStatus::Failure;
+        }
+        for (check_name, failed) in failed {
+            let timed = report.timeline().and_then(|timeline| {
+                timeline
+                    .spans()
+                    .iter()
+                    .find(|span| span.phase == TimelinePhase::Check && span.name == check_name)
+                    .map(|span| span.duration_us as f64 / 1_000.0)
+            });
+            let duration_ms = timed.or_else(|| {
+                let performance = report.metrics.performance.checks.get(check_name)?;
+                Some(performance.elapsed_compute_ns as f64 / 1_000_000.0)
+            });
+            self.record(check_name, failed, duration_ms);
+        }
+    }
+
+    /// Adds one run of a check.
+    pub fn record(
+        &mut self,
+        check_name: impl Into<String>,
+        failed: bool,
+        duration_ms: Option<f64>,
+    ) {
+        let history = self.checks.entry(check_name.into()).or_default();
+        history.runs += 1;
+        history.failures += usize::from(failed);
+        if let Some(duration_ms) = duration_ms.filter(|duration| duration.is_finite()) {
+            history.timed_runs += 1;
+            history.total_duration_ms += duration_ms.max(0.0);
+        }
+    }
+
+    /// Returns true if no run was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+
+    /// Estimates the duration and failure rate of `check` at `position` in the
+    /// suite.
+    ///
+    /// The failure rate is smoothed towards one half, so that a check which
+    /// passed its only run is not deemed certain to pass.
+    pub fn estimate(&self, check: &Check, position: usize) -> CheckEstimate {
+        let constraints = check.constraints().len();
+        let history = self.checks.get(check.name());
+        let runs = history.map_or(0, |history| history.runs);
+        let failures = history.map_or(0, |history| history.failures);
+        let expected_duration_ms = match history {
+            Some(history) if history.timed_runs > 0 => {
+                history.total_duration_ms / history.timed_runs as f64
+            }
+            _ => constraints.max(1) as f64 * DEFAULT_CONSTRAINT_DURATION_MS,
+        };
+        CheckEstimate {
+            check_name: check.name().to_string(),
+            position,
+            constraints,
+            expected_duration_ms,
+            failure_rate: (failures as f64 + 1.0) / (runs as f64 + 2.0),
+            history_runs: runs,
+        }
+    }
+}
+
+/// How a suite orders its checks, and where it reads their history.
+#[derive(Clone, Default)]
+pub(crate) struct Scheduler {
+    pub(crate) order: ExecutionOrder,
+    pub(crate) repository: Option<Arc<dyn MetricsRepository>>,
+}
+
+impl Scheduler {
+    /// Returns the indices of `checks` in execution order, and the schedule
+    /// to record in the report unless the builder order is kept.
+    pub(crate) async fn schedule(
+        &self,
+        suite_name: &str,
+        checks: &[Arc<Check>],
+    ) -> (Vec<usize>, Option<ExecutionSchedule>) {
+        if matches!(self.order, ExecutionOrder::BuilderOrder) {
+            return ((0..checks.len()).collect(), None);
+        }
+        let history = match &self.repository {
+            Some(repository) => ExecutionHistory::load(repository.as_ref(), suite_name)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!(error = %e, "Cannot load check history, using default estimates");
+                    ExecutionHistory::new()
+                }),
+            None => ExecutionHistory::new(),
+        };
+        let schedule = schedule(&self.order, &history, checks);
+        debug!(
+            execution.order = %schedule.order,
+            checks = ?schedule.checks.iter().map(|estimate| &estimate.check_name).collect::<Vec<_>>(),
+            "Scheduled checks"
+        );
+        let indices = schedule
+            .checks
+            .iter()
+            .map(|estimate| estimate.position)
+            .collect();
+        (indices, Some(schedule))
+    }
+}
+
+impl fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("order", &self.order)
+            .field("has_repository", &self.repository.is_some())
+            .finish()
+    }
+}
+
+/// Orders `checks` by `order` using the estimates derived from `history`.
+pub fn schedule(
+    order: &ExecutionOrder,
+    history: &ExecutionHistory,
+    checks: &[Arc<Check>],
+) -> ExecutionSchedule {
+    let mut estimates: Vec<_> = checks
+        .iter()
+        .enumerate()
+        .map(|(position, check)| history.estimate(check, position))
+        .collect();
+    order.sort(&mut estimates);
+    ExecutionSchedule {
+        order: order.name().to_string(),
+        checks: estimates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::Assertion;
+
+    fn checks(names: &[&str]) -> Vec<Arc<Check>> {
+        names
+            .iter()
+            .map(|name| {
+                Arc::new(
+                    Check::builder(*name)
+                        .has_size(Assertion::GreaterThan(0.0))
+                        .build(),
+                )
+            })
+            .collect()
+    }
+
+    fn order(schedule: &ExecutionSchedule) -> Vec<&str> {
+        schedule
+            .checks
+            .iter()
+            .map(|estimate| estimate.check_name.as_str())
+            .collect()
+    }
+
+    /// Records `runs` runs of each check with a fixed duration, failing the
+    /// given share of them.
+    fn simulated_history(runs: usize, checks: &[(&str, f64, f64)]) -> ExecutionHistory {
+        let mut history = ExecutionHistory::new();
+        for run in 0..runs {
+            for (name, duration_ms, failure_probability) in checks {
+                let failed = (run as f64) < failure_probability * runs as f64;
+                history.record(*name, failed, Some(*duration_ms));
+            }
+        }
+        history
+    }
+
+    #[test]
+    fn test_simulated_schedules() {
+        let suite = checks(&["slow_stable", "fast_flaky", "slow_flaky", "fast_stable"]);
+        let history = simulated_history(
+            20,
+            &[
+                ("slow_stable", 30_000.0, 0.0),
+                ("fast_flaky", 200.0, 0.4),
+                ("slow_flaky", 20_000.0, 0.8),
+                ("fast_stable", 100.0, 0.05),
+            ],
+        );
+
+        let builder = schedule(&ExecutionOrder::BuilderOrder, &history, &suite);
+        assert_eq!(
+            order(&builder),
+            ["slow_stable", "fast_flaky", "slow_flaky", "fast_stable"]
+        );
+
+        let fastest = schedule(&ExecutionOrder::FastestFirst, &history, &suite);
+        assert_eq!(
+            order(&fastest),
+            ["fast_stable", "fast_flaky", "slow_flaky", "slow_stable"]
+        );
+
+        // Failures per millisecond: the fast flaky check beats the slow check
+        // failing more often, and a rarely failing fast check beats both slow ones
+        let likely = schedule(&ExecutionOrder::LikelyFailuresFirst, &history, &suite);
+        assert_eq!(
+            order(&likely),
+            ["fast_flaky", "fast_stable", "slow_flaky", "slow_stable"]
+        );
+        assert_eq!(likely.order, "likely_failures_first");
+        assert_eq!(likely.checks[0].history_runs, 20);
+        assert_eq!(likely.checks[0].expected_duration_ms, 200.0);
+
+        let custom = ExecutionOrder::custom(|a, b| b.failure_rate.total_cmp(&a.failure_rate));
+        assert_eq!(
+            order(&schedule(&custom, &history, &suite)),
+            ["slow_flaky", "fast_flaky", "fast_stable", "slow_stable"]
+        );
+    }
+
+    #[test]
+    fn test_checks_without_history_use_defaults() {
+        let suite = checks(&["known", "new"]);
+        let history = simulated_history(10, &[("known", 50.0, 0.0)]);
+
+        let estimate = history.estimate(&suite[1], 1);
+        assert_eq!(estimate.history_runs, 0);
+        assert_eq!(estimate.failure_rate, 0.5);
+        assert_eq!(
+            estimate.expected_duration_ms,
+            DEFAULT_CONSTRAINT_DURATION_MS
+        );
+
+        // An unknown check may well fail, so it runs before a cheap stable one
+        let likely = schedule(&ExecutionOrder::LikelyFailuresFirst, &history, &suite);
+        assert_eq!(order(&likely), ["new", "known"]);
+
+        // Without any history the estimates tie and the builder order is kept
+        let empty = ExecutionHistory::new();
+        let likely = schedule(&ExecutionOrder::LikelyFailuresFirst, &empty, &suite);
+        assert_eq!(order(&likely), ["known", "new"]);
+    }
+
+    #[test]
+    fn test_record_report() {
+        let mut report = ValidationReport::new("orders");
+        for (check, status) in [
+            ("volume", ConstraintStatus::Success),
+            ("amounts", ConstraintStatus::Success),
+            ("amounts", ConstraintStatus::Failure),
+        ] {
+            report.outcomes.push(crate::core::ConstraintOutcome {
+                check_name: check.to_string(),
+                constraint_name: "size".to_string(),
+                level: crate::core::Level::Error,
+                status,
+                waived: false,
+                metric: None,
+                threshold: None,
+                dimension: None,
+                constraint_id: None,
+            });
+        }
+        report.metrics.performance.record_check(
+            "volume",
+            crate::core::QueryMetrics {
+                elapsed_compute_ns: 4_000_000,
+                ..Default::default()
+            },
+        );
+
+        let mut history = ExecutionHistory::new();
+        history.record_report(&report);
+        let suite = checks(&["volume", "amounts"]);
+        let volume = history.estimate(&suite[0], 0);
+        assert_eq!(volume.expected_duration_ms, 4.0);
+        assert_eq!(volume.failure_rate, 1.0 / 3.0);
+        let amounts = history.estimate(&suite[1], 1);
+        assert_eq!(amounts.failure_rate, 2.0 / 3.0);
+        assert_eq!(amounts.expected_duration_ms, DEFAULT_CONSTRAINT_DURATION_MS);
+    }
+}
@@ -0,0 +1,195 @@
+//! Scalar UDFs a suite registers on the session context for its runs.
+//!
+//! A suite built with
+//! [`ValidationSuiteBuilder::with_udf`](super::ValidationSuiteBuilder::with_udf)
+//! registers its UDFs before any source, row filter or constraint is planned,
+//! so custom SQL constraints, row filters and derived tables can call them. The
+//! run removes them when it finishes and restores any function of the same name
+//! registered before, including a built-in one. Suites running concurrently on
+//! one session context should therefore not attach different UDFs under the
+//! same name.
+
+use std::sync::Arc;
+
+use datafusion::logical_expr::ScalarUDF;
+use datafusion::prelude::SessionContext;
+use tracing::debug;
+
+use super::unified::configuration_message;
+use super::ConfigIssue;
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+
+/// A UDF attached to a suite, with the name SQL expressions call it by.
+#[derive(Debug, Clone)]
+pub(crate) struct SuiteUdf {
+    name: String,
+    udf: Arc<ScalarUDF>,
+}
+
+impl SuiteUdf {
+    pub(crate) fn new(name: String, udf: ScalarUDF) -> Self {
+        Self {
+            name,
+            udf: Arc::new(udf),
+        }
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Checks that the name can be called from SQL expressions and is the name
+    /// the UDF was created with.
+    fn validate(&self) -> Result<()> {
+        SqlSecurity::validate_function_name(&self.name)
+            .map_err(|e| TermError::Configuration(format!("UDF '{}': {e}", self.name)))?;
+        if self.udf.name() != self.name {
+            return Err(TermError::Configuration(format!(
+                "UDF '{}' was created with the name '{}'; create it with the name SQL expressions call it by",
+                self.name,
+                self.udf.name()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Returns an issue for every invalid UDF attached to a suite, located under `udfs[i]`.
+pub(crate) fn udf_issues(udfs: &[SuiteUdf]) -> Vec<ConfigIssue> {
+    udfs.iter()
+        .enumerate()
+        .filter_map(|(index, udf)| {
+            let e = udf.validate().err()?;
+            Some(ConfigIssue::error(
+                format!("udfs[{index}]"),
+                configuration_message(e),
+            ))
+        })
+        .collect()
+}
+
+/// Functions a run registered, with the functions they replaced.
+#[derive(Debug, Default)]
+pub(crate) struct RegisteredUdfs {
+    /// Every name a registered UDF answers to, with the function previously
+    /// registered under it
+    replaced: Vec<(String, Option<Arc<ScalarUDF>>)>,
+}
+
+impl RegisteredUdfs {
+    /// Registers `udfs` on the session context.
+    pub(crate) fn register(ctx: &SessionContext, udfs: &[SuiteUdf]) -> Self {
+        if udfs.is_empty() {
+            return Self::default();
+        }
+        let state = ctx.state();
+        let mut replaced = Vec::new();
+        for udf in udfs {
+            for name in
+                std::iter::once(udf.udf.name()).chain(udf.udf.aliases().iter().map(String::as_str))
+            {
+                replaced.push((
+                    name.to_string(),
+                    state.scalar_functions().get(name).cloned(),
+                ));
+            }
+            ctx.register_udf(udf.udf.as_ref().clone());
+            debug!(udf.name = %udf.name, "Registered suite UDF");
+        }
+        Self { replaced }
+    }
+
+    /// Removes the registered UDFs and restores the functions they replaced.
+    pub(crate) fn restore(self, ctx: &SessionContext) {
+        // Deregistering a function also removes its aliases, so every name is
+        // cleared before any previous function is registered again
+        for (name, _) in &self.replaced {
+            ctx.deregister_udf(name);
+        }
+        for previous in self
+            .replaced
+            .into_iter()
+            .filter_map(|(_, previous)| previous)
+        {
+            ctx.register_udf(previous.as_ref().clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::DataType;
+    use datafusion::logical_expr::{create_udf, ColumnarValue, Volatility};
+
+    fn udf(name: &str) -> ScalarUDF {
+        create_udf(
+            name,
+            vec![DataType::Utf8],
+            DataType::Boolean,
+            Volatility::Immutable,
+            Arc::new(|_: &[ColumnarValue]| {
+                Ok(ColumnarValue::Scalar(
+                    datafusion::scalar::ScalarValue::Boolean(Some(true)),
+                ))
+            }),
+        )
+    }
+
+    #[test]
+    fn test_validation() {
+        let valid = SuiteUdf::new("vin_valid".to_string(), udf("vin_valid"));
+        assert!(udf_issues(&[valid]).is_empty());
+
+        let issues = udf_issues(&[
+            SuiteUdf::new("VinValid".to_string(), udf("VinValid")),
+            SuiteUdf::new("vin_check".to_string(), udf("vin_valid")),
+        ]);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].path, "udfs[0]");
+        assert!(
+            issues[0]
+                .message
+                .contains("Invalid function name 'VinValid'"),
+            "{}",
+            issues[0].message
+        );
+        assert_eq!(issues[1].path, "udfs[1]");
+        assert!(
+            issues[1]
+                .message
+                .contains("UDF 'vin_check' was created with the name 'vin_valid'"),
+            "{}",
+            issues[1].message
+        );
+    }
+
+    #[test]
+    fn test_restores_replaced_functions() {
+        let ctx = SessionContext::new();
+        let builtin = ctx
+            .state()
+            .scalar_functions()
+            .get("upper")
+            .cloned()
+            .unwrap();
+
+        let registered = RegisteredUdfs::register(
+            &ctx,
+            &[
+                SuiteUdf::new("upper".to_string(), udf("upper")),
+                SuiteUdf::new("vin_valid".to_string(), udf("vin_valid")),
+            ],
+        );
+        let functions = ctx.state().scalar_functions().clone();
+        assert!(!Arc::ptr_eq(&functions["upper"], &builtin));
+        assert!(functions.contains_key("vin_valid"));
+
+        registered.restore(&ctx);
+        let functions = ctx.state().scalar_functions().clone();
+        assert_eq!(functions["upper"].name(), builtin.name());
+        assert_eq!(functions["upper"].signature(), builtin.signature());
+        assert!(!functions.contains_key("vin_valid"));
+    }
+}
@@ -1,16 +1,42 @@
 //! Validation suite for organizing and running checks.
 
 use super::{
-    result::{ValidationIssue, ValidationMetrics, ValidationReport},
-    Check, ConstraintStatus, Level, ValidationResult,
+    anomaly_check::AnomalyCheckConstraint,
+    column_mapping, constraint_id,
+    derived_table::DerivedTable,
+    lookup_issues,
+    result::{
+        ConstraintOutcome, ExcludedCheck, PreviewSkippedConstraint, PreviewSummary,
+        ValidationIssue, ValidationMetrics, ValidationReport,
+    },
+    result_cache::{hash_parts, source_fingerprint, suite_fingerprint, CacheRun},
+    udf_issues, AnomalyCheckOptions, BoundSuite, CachedConstraint, Check, CheckFilter, CheckStatus,
+    CollectionLimits, ConfigIssue, ConfigReport, Constraint, ConstraintInfo, ConstraintResult,
+    ConstraintStatus, EmptyTablePolicy, Evaluator, ExecutionOrder, Extensions, HookChain,
+    HookSkippedConstraint, Level, LineageMap, LookupTable, MetadataConstraint, MetadataOnlyMode,
+    MetadataStatistics, ParameterBag, PrecomputedStatistics, PreflightReport, ProfiledConstraint,
+    QualifiedTable, QuarantinedRows, QueryMetricsCollector, RedactionPolicy, RegisteredLookups,
+    RegisteredUdfs, ResultCache, RunOptions, Scheduler, SuiteLookup, SuiteUdf, SuiteVersion,
+    TimelinePhase, TimelineRecorder, ValidationHook, ValidationResult, DEFAULT_MAX_LOOKUP_BYTES,
+    SUITE_VERSION_TAG,
 };
 // use crate::optimizer::QueryOptimizer; // TODO: Re-enable once TermContext integration is resolved
+use crate::analyzers::anomaly::AnomalyDetectionStrategy;
+use crate::analyzers::{Analyzer, MetricValue};
+use crate::optimizer::SharedStatsCache;
 use crate::prelude::*;
+use crate::repository::MetricsRepository;
+use crate::sources::{filter_rows, DataSource};
 use crate::telemetry::{utils, TermSpan, TermTelemetry};
+use arrow::datatypes::SchemaRef;
+use datafusion::logical_expr::ScalarUDF;
 use datafusion::prelude::*;
+use datafusion::scalar::ScalarValue;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use std::time::Instant;
-use tracing::{debug, error, info, instrument, warn};
+use tracing::{debug, error, info, info_span, instrument, warn, Instrument};
+use uuid::Uuid;
 
 /// A collection of validation checks to be run together.
 ///
@@ -51,28 +77,132 @@ pub struct ValidationSuite {
     use_optimizer: bool,
     /// The name of the table to validate (defaults to "data")
     table_name: String,
+    /// Schema prepended to unqualified table names in multi-table constraints
+    default_schema: Option<String>,
+    /// Optional cache of constraint results from previous runs
+    result_cache: Option<ResultCache>,
+    /// Optional table statistics shared with other suites
+    shared_stats: Option<SharedStatsCache>,
+    /// Default values for named parameters, overridable at run time
+    parameter_defaults: ParameterBag,
+    /// Query registered as the validated table for the duration of a run
+    derived_table: Option<DerivedTable>,
+    /// Physical column of the validated table for each logical column
+    column_mapping: BTreeMap<String, String>,
+    /// SQL predicates restricting the validated rows
+    row_filters: Vec<String>,
+    /// Tags recorded on the run span and available from the validation context
+    tags: Arc<BTreeMap<String, String>>,
+    /// Whether runs record a timeline of their phases
+    record_timeline: bool,
+    /// How constraints skipped because the table is empty are reported
+    empty_table_policy: EmptyTablePolicy,
+    /// Hooks run before and after each constraint
+    hooks: HookChain,
+    /// Redaction of values quoted in constraint results
+    redaction: Option<Arc<RedactionPolicy>>,
+    /// Downstream consumers of the validated columns, attached to issues
+    lineage: Option<Arc<LineageMap>>,
+    /// Sources of the tables the suite reads, by table name
+    sources: Vec<(String, Arc<dyn DataSource>)>,
+    /// Whether runs probe the attached sources first
+    preflight: bool,
+    /// Order in which the checks run
+    scheduler: Scheduler,
+    /// Whether runs answer constraints from the metadata of the validated table's source
+    metadata_only: MetadataOnlyMode,
+    /// Scalar UDFs registered on the session context for the duration of a run
+    udfs: Vec<SuiteUdf>,
+    /// Reference tables registered on the session context for the duration of a run
+    lookups: Vec<SuiteLookup>,
+    /// Limit on the encoded size of each lookup table
+    max_lookup_bytes: usize,
+    /// Bounds on the result sets constraints collect
+    collection_limits: Arc<CollectionLimits>,
+    /// Version of the suite definition with its change journal
+    version: Option<SuiteVersion>,
+}
+
+/// How [`ValidationSuite::merge`] handles a check whose name is already used
+/// by a check of the suite it is merged into.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CheckConflictPolicy {
+    /// Fail the merge, listing every conflicting check
+    #[default]
+    Error,
+    /// Prefix the name of the incoming check with the name of its suite, such
+    /// as `orders.not_null_id`, adding a numeric suffix if that is taken too
+    Rename,
 }
 
 impl ValidationSuite {
     /// Runs the validation suite sequentially without optimization.
+    #[allow(clippy::too_many_arguments)]
     async fn run_sequential(
         &self,
         ctx: &SessionContext,
+        statistics: Option<&PrecomputedStatistics>,
+        metadata: Option<&MetadataStatistics>,
+        parameters: &Arc<ParameterBag>,
+        extensions: &Arc<Extensions>,
+        filter: Option<&CheckFilter>,
+        order: &[usize],
+        mut cache: Option<&mut CacheRun>,
         report: &mut ValidationReport,
         metrics: &mut ValidationMetrics,
-        has_errors: &mut bool,
         #[allow(unused_variables)] start_time: &Instant,
         _suite_span: &mut TermSpan,
+        timeline: &mut TimelineRecorder,
     ) -> Result<()> {
-        for check in &self.checks {
+        // Constraints share the context, span handling and metric cache of the run
+        let mut evaluator = Evaluator::for_run(
+            ctx,
+            &self.table_name,
+            self.default_schema.as_deref(),
+            Arc::clone(parameters),
+            Arc::clone(extensions),
+            Arc::clone(&self.tags),
+            self.telemetry.clone(),
+            self.empty_table_policy,
+        )
+        .with_redaction(self.redaction.clone())
+        .with_collection_limits(Arc::clone(&self.collection_limits));
+        if let Some(shared_stats) = &self.shared_stats {
+            evaluator =
+                evaluator.with_metric_cache(shared_stats.metric_cache(ctx, &self.table_name).await);
+        }
+
+        for &check_index in order {
+            let check = &self.checks[check_index];
+            if filter.is_some_and(|filter| !filter.matches(check)) {
+                debug!(
+                    event = "check_excluded",
+                    check.name = %check.name(),
+                    "Check excluded from filtered run"
+                );
+                // Keep cached results of excluded checks for later runs
+                if let Some(cache) = cache.as_deref_mut() {
+                    cache.keep_check(check_index);
+                }
+                report.excluded_checks.push(ExcludedCheck {
+                    check_name: check.name().to_string(),
+                    level: check.level(),
+                    constraints: check.constraints().len(),
+                });
+                continue;
+            }
+
             debug!(
+                event = "check_started",
                 check.name = %check.name(),
                 check.level = ?check.level(),
                 check.constraints = check.constraints().len(),
                 "Running validation check"
             );
+            let mut statuses = Vec::with_capacity(check.constraints().len());
             #[allow(unused_variables)]
             let check_start = Instant::now();
+            let check_timeline_span = timeline.enter(TimelinePhase::Check, check.name());
 
             // Create telemetry span for the check
             let _check_span = if let Some(telemetry) = &self.telemetry {
@@ -81,36 +211,188 @@ impl ValidationSuite {
                 TermSpan::noop()
             };
 
-            for constraint in check.constraints() {
-                metrics.total_checks += 1;
+            // Collect DataFusion execution metrics for all queries issued by this check
+            let performance_collector = QueryMetricsCollector::new();
 
-                // Create telemetry span for the constraint
-                let mut constraint_span = if let Some(telemetry) = &self.telemetry {
-                    // Try to extract column information from constraint metadata if available
-                    let column = None; // TODO: Extract from constraint if possible
-                    telemetry.start_constraint_span(constraint.name(), column)
-                } else {
-                    TermSpan::noop()
+            for (constraint_index, constraint) in check.constraints().iter().enumerate() {
+                let constraint_label = check.constraint_label(constraint_index);
+                let constraint_id = constraint_id(constraint.as_ref());
+                let constraint_timeline_span =
+                    timeline.enter(TimelinePhase::Constraint, constraint_label);
+                let impacts = self.lineage.as_deref().map_or_else(Vec::new, |lineage| {
+                    constraint_impacts(
+                        lineage,
+                        &self.table_name,
+                        constraint.as_ref(),
+                        &report.column_mapping,
+                    )
+                });
+
+                let mut constraint_span = evaluator.start_span(constraint.as_ref());
+                let validation_ctx = evaluator.validation_context(Some(&performance_collector));
+
+                // Hooks may veto the constraint before anything else answers it
+                let hook_info = (!self.hooks.is_empty()).then(|| ConstraintInfo {
+                    suite_name: self.name.clone(),
+                    check_name: check.name().to_string(),
+                    level: check.level(),
+                    constraint_name: constraint_label.to_string(),
+                    constraint_type: constraint.name().to_string(),
+                    constraint_id: constraint_id.clone(),
+                    column: constraint.column().map(str::to_string),
+                    table_name: self.table_name.clone(),
+                    referenced_tables: constraint.referenced_tables(),
+                    impacts: impacts.clone(),
+                });
+                let hook_skip = match &hook_info {
+                    Some(info) => self.hooks.before(info).await,
+                    None => None,
                 };
 
-                // Run constraint evaluation with the proper table context
-                let validation_ctx = crate::core::ValidationContext::new(self.table_name.clone());
-                let result = crate::core::validation_context::CURRENT_CONTEXT
-                    .scope(validation_ctx, constraint.evaluate(ctx))
-                    .await;
+                // Answer from precomputed statistics when the constraint supports it
+                let precomputed = constraint.column().and_then(|column| {
+                    let stats = statistics.and_then(|stats| stats.column(column))?;
+                    crate::core::validation_context::CURRENT_CONTEXT
+                        .sync_scope(validation_ctx.clone(), || {
+                            constraint.evaluate_from_statistics(stats)
+                        })
+                        .map(|result| (column, result))
+                });
+                // Otherwise answer from file metadata when the constraint supports it
+                let from_metadata =
+                    metadata
+                        .filter(|_| precomputed.is_none())
+                        .and_then(|metadata| {
+                            crate::core::validation_context::CURRENT_CONTEXT
+                                .sync_scope(validation_ctx.clone(), || {
+                                    constraint.evaluate_from_metadata(metadata)
+                                })
+                                .map(|result| (metadata.evidence.as_str(), result))
+                        });
+
+                // Reuse the result of a previous run when the inputs are unchanged
+                let cached = cache
+                    .as_deref_mut()
+                    .and_then(|cache| cache.get(check_index, constraint_index))
+                    .map(|entry| (entry.computed_at.to_rfc3339(), entry.result.clone()));
+                let from_cache = cached.is_some() && hook_skip.is_none();
+
+                let skipped_in_preview = hook_skip.is_none()
+                    && constraint.requires_full_data()
+                    && report.preview.as_mut().is_some_and(|preview| {
+                        preview.skipped_constraints.push(PreviewSkippedConstraint {
+                            check_name: check.name().to_string(),
+                            constraint_name: constraint_label.to_string(),
+                        });
+                        true
+                    });
+
+                let result = if let Some((hook, reason)) = hook_skip.clone() {
+                    debug!(
+                        constraint.name = %constraint.name(),
+                        check.name = %check.name(),
+                        hook.name = %hook,
+                        skip.reason = %reason,
+                        "Constraint skipped by hook"
+                    );
+                    report.hook_skipped.push(HookSkippedConstraint {
+                        check_name: check.name().to_string(),
+                        constraint_name: constraint_label.to_string(),
+                        hook,
+                        reason: reason.clone(),
+                    });
+                    Ok(ConstraintResult::skipped(reason))
+                } else if skipped_in_preview {
+                    Ok(ConstraintResult::skipped(format!(
+                        "Skipped in preview: {constraint_label} needs the full data"
+                    )))
+                } else if let Some((computed_at, result)) = cached {
+                    debug!(
+                        constraint.name = %constraint.name(),
+                        check.name = %check.name(),
+                        cached.computed_at = %computed_at,
+                        "Constraint result reused from cache"
+                    );
+                    report.cached_constraints.push(CachedConstraint {
+                        check_name: check.name().to_string(),
+                        constraint_name: constraint_label.to_string(),
+                        computed_at,
+                    });
+                    Ok(result)
+                } else if let Some((column, result)) = precomputed {
+                    debug!(
+                        constraint.name = %constraint.name(),
+                        check.name = %check.name(),
+                        constraint.column = %column,
+                        "Constraint answered from precomputed statistics"
+                    );
+                    report.profiled_constraints.push(ProfiledConstraint {
+                        check_name: check.name().to_string(),
+                        constraint_name: constraint_label.to_string(),
+                        column: column.to_string(),
+                    });
+                    Ok(result)
+                } else if let Some((evidence, result)) = from_metadata {
+                    debug!(
+                        constraint.name = %constraint.name(),
+                        check.name = %check.name(),
+                        evidence = %evidence,
+                        "Constraint answered from file metadata"
+                    );
+                    report.metadata_constraints.push(MetadataConstraint {
+                        check_name: check.name().to_string(),
+                        constraint_name: constraint_label.to_string(),
+                        column: constraint.column().map(str::to_string),
+                        evidence: evidence.to_string(),
+                    });
+                    Ok(result.with_detail(format!("evidence.{evidence}"), 1.0))
+                } else {
+                    evaluator.execute(constraint.as_ref(), validation_ctx).await
+                };
+                timeline.exit(constraint_timeline_span);
 
                 match result {
-                    Ok(result) => {
-                        // Record constraint result in telemetry
-                        if let Some(telemetry) = &self.telemetry {
-                            if telemetry.detailed_metrics {
-                                utils::record_constraint_result(&mut constraint_span, &result);
+                    Ok(mut result) => {
+                        result.constraint_id = Some(constraint_id.clone());
+                        if !from_cache && hook_skip.is_none() {
+                            if let Some(cache) = cache.as_deref_mut() {
+                                cache.insert(check_index, constraint_index, &result);
                             }
                         }
+                        let result = evaluator.finish(result, &mut constraint_span);
+                        if let Some(info) = &hook_info {
+                            self.hooks.after(info, &result).await;
+                        }
+                        let now = chrono::Utc::now();
+                        let waived = result.status == ConstraintStatus::Failure
+                            && check
+                                .known_issue()
+                                .is_some_and(|known_issue| !known_issue.is_expired_at(now));
+                        report.outcomes.push(ConstraintOutcome {
+                            check_name: check.name().to_string(),
+                            constraint_name: constraint_label.to_string(),
+                            level: check.level(),
+                            status: result.status,
+                            waived,
+                            metric: result.metric,
+                            threshold: threshold(constraint.as_ref()),
+                            dimension: check.constraint_dimension(constraint_index),
+                            constraint_id: Some(constraint_id.clone()),
+                        });
+
+                        if let Some(quarantine) = &result.quarantine {
+                            report.quarantined.push(QuarantinedRows {
+                                check_name: check.name().to_string(),
+                                constraint_name: constraint_label.to_string(),
+                                rows: quarantine.rows,
+                                location: quarantine.location.clone(),
+                                truncated: quarantine.truncated,
+                            });
+                        }
 
+                        statuses.push((result.status, waived));
                         match result.status {
                             ConstraintStatus::Success => {
-                                metrics.passed_checks += 1;
                                 debug!(
                                     constraint.name = %constraint.name(),
                                     check.name = %check.name(),
@@ -138,31 +420,67 @@ impl ValidationSuite {
                                 }
                             }
                             ConstraintStatus::Failure => {
-                                metrics.failed_checks += 1;
-                                let failure_message = result.message.clone().unwrap_or_else(|| {
-                                    let name = constraint.name();
-                                    format!("Constraint {name} failed")
-                                });
-                                let issue = ValidationIssue {
+                                let mut failure_message =
+                                    result.message.clone().unwrap_or_else(|| {
+                                        format!("Constraint {constraint_label} failed")
+                                    });
+                                if let Some(physical) =
+                                    mapped_columns(constraint.as_ref(), &report.column_mapping)
+                                {
+                                    failure_message
+                                        .push_str(&format!(" (physical columns: {physical})"));
+                                }
+                                let mut issue = ValidationIssue {
                                     check_name: check.name().to_string(),
-                                    constraint_name: constraint.name().to_string(),
+                                    constraint_name: constraint_label.to_string(),
                                     level: check.level(),
                                     message: failure_message.clone(),
                                     metric: result.metric,
+                                    cached: from_cache,
+                                    known_issue: None,
+                                    check_id: check.id().map(str::to_string),
+                                    constraint_id: Some(constraint_id),
+                                    impacts,
                                 };
-
-                                if check.level() == Level::Error {
-                                    *has_errors = true;
+                                if let Some(known_issue) = check.known_issue() {
+                                    known_issue.annotate(&mut issue, now);
                                 }
+                                let known_issue = issue
+                                    .known_issue
+                                    .as_ref()
+                                    .map(|known| known.ticket.as_str());
 
-                                warn!(
-                                    constraint.name = %constraint.name(),
-                                    check.name = %check.name(),
-                                    check.level = ?check.level(),
-                                    failure.message = %issue.message,
-                                    constraint.metric = ?result.metric,
-                                    "Constraint failed"
-                                );
+                                if check.level() == Level::Error && !waived {
+                                    error!(
+                                        event = "constraint_failed",
+                                        constraint.name = %constraint_label,
+                                        check.name = %check.name(),
+                                        check.level = ?check.level(),
+                                        failure.message = %issue.message,
+                                        constraint.metric = result.metric,
+                                        "Constraint failed"
+                                    );
+                                } else if check.level() == Level::Info {
+                                    info!(
+                                        event = "constraint_observed",
+                                        constraint.name = %constraint_label,
+                                        check.name = %check.name(),
+                                        failure.message = %issue.message,
+                                        constraint.metric = result.metric,
+                                        "Informational constraint failed"
+                                    );
+                                } else {
+                                    warn!(
+                                        event = "constraint_failed",
+                                        constraint.name = %constraint_label,
+                                        check.name = %check.name(),
+                                        check.level = ?check.level(),
+                                        failure.message = %issue.message,
+                                        constraint.metric = result.metric,
+                                        known_issue,
+                                        "Constraint failed"
+                                    );
+                                }
                                 report.add_issue(issue);
 
                                 // Record failure in metrics
@@ -189,7 +507,6 @@ impl ValidationSuite {
                                 }
                             }
                             ConstraintStatus::Skipped => {
-                                metrics.skipped_checks += 1;
                                 debug!(
                                     constraint.name = %constraint.name(),
                                     check.name = %check.name(),
@@ -202,8 +519,7 @@ impl ValidationSuite {
                         // Record custom metrics
                         if let Some(metric_value) = result.metric {
                             let check_name = check.name();
-                            let constraint_name = constraint.name();
-                            let metric_name = format!("{check_name}.{constraint_name}");
+                            let metric_name = format!("{check_name}.{constraint_label}");
                             metrics
                                 .custom_metrics
                                 .insert(metric_name.clone(), metric_value);
@@ -231,20 +547,38 @@ impl ValidationSuite {
                     Err(e) => {
                         // Record error in telemetry
                         constraint_span.record_error(&e as &dyn std::error::Error);
+                        if let Some(info) = &hook_info {
+                            let result = ConstraintResult::failure(format!(
+                                "Error evaluating constraint: {e}"
+                            ));
+                            self.hooks.after(info, &result).await;
+                        }
 
-                        metrics.failed_checks += 1;
+                        // Known issues waive constraint failures, not broken evaluations
+                        statuses.push((ConstraintStatus::Failure, false));
+                        report.outcomes.push(ConstraintOutcome {
+                            check_name: check.name().to_string(),
+                            constraint_name: constraint_label.to_string(),
+                            level: check.level(),
+                            status: ConstraintStatus::Failure,
+                            waived: false,
+                            metric: None,
+                            threshold: threshold(constraint.as_ref()),
+                            dimension: check.constraint_dimension(constraint_index),
+                            constraint_id: Some(constraint_id.clone()),
+                        });
                         let issue = ValidationIssue {
                             check_name: check.name().to_string(),
-                            constraint_name: constraint.name().to_string(),
+                            constraint_name: constraint_label.to_string(),
                             level: check.level(),
                             message: format!("Error evaluating constraint: {e}"),
                             metric: None,
+                            cached: false,
+                            known_issue: None,
+                            check_id: check.id().map(str::to_string),
+                            constraint_id: Some(constraint_id),
+                            impacts,
                         };
-
-                        if check.level() == Level::Error {
-                            *has_errors = true;
-                        }
-
                         error!(
                             constraint.name = %constraint.name(),
                             check.name = %check.name(),
@@ -257,6 +591,19 @@ impl ValidationSuite {
                 }
             }
 
+            timeline.exit(check_timeline_span);
+            metrics
+                .performance
+                .record_check(check.name(), performance_collector.snapshot());
+            let status = CheckStatus::aggregate_with_waivers(check.level(), statuses);
+            metrics.record_check(status);
+            debug!(
+                event = "check_finished",
+                check.name = %check.name(),
+                check.status = %status,
+                "Finished validation check"
+            );
+
             // Record check duration in metrics
             #[cfg(feature = "telemetry")]
             if let Some(telemetry) = &self.telemetry {
@@ -274,6 +621,18 @@ impl ValidationSuite {
             }
         }
 
+        let metric_cache = evaluator.metric_cache();
+        metrics.performance.metric_cache_hits = metric_cache.hits();
+        metrics.performance.metric_cache_misses = metric_cache.misses();
+        if metric_cache.hits() > 0 {
+            debug!(
+                event = "metric_cache",
+                metric_cache.hits = metric_cache.hits(),
+                metric_cache.misses = metric_cache.misses(),
+                "Reused metrics between constraints"
+            );
+        }
+
         Ok(())
     }
 
@@ -293,13 +652,19 @@ impl ValidationSuite {
         if let Some(telemetry) = &self.telemetry {
             if let Some(metrics_collector) = telemetry.metrics() {
                 let suite_duration = start_time.elapsed().as_secs_f64();
-                let attrs = vec![
+                let mut attrs = vec![
                     opentelemetry::KeyValue::new("suite.name", self.name.clone()),
                     opentelemetry::KeyValue::new("suite.passed", !has_errors),
                     opentelemetry::KeyValue::new("checks.total", metrics.total_checks as i64),
                     opentelemetry::KeyValue::new("checks.passed", metrics.passed_checks as i64),
                     opentelemetry::KeyValue::new("checks.failed", metrics.failed_checks as i64),
                 ];
+                if let Some(version) = &self.version {
+                    attrs.push(opentelemetry::KeyValue::new(
+                        "suite.version",
+                        version.version.to_string(),
+                    ));
+                }
                 metrics_collector.record_validation_duration(suite_duration, &attrs);
 
                 // Record validation failure if there were errors
@@ -323,10 +688,12 @@ impl ValidationSuite {
         }
 
         info!(
+            event = "run_finished",
             suite.name = %self.name,
             metrics.passed = metrics.passed_checks,
             metrics.failed = metrics.failed_checks,
             metrics.skipped = metrics.skipped_checks,
+            metrics.info_findings = metrics.info_findings,
             metrics.total = metrics.total_checks,
             metrics.duration_ms = metrics.execution_time_ms,
             metrics.success_rate = %format!("{:.2}%", metrics.success_rate()),
@@ -362,11 +729,251 @@ impl ValidationSuite {
         self.description.as_deref()
     }
 
+    /// Returns the name of the table this suite validates.
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    /// Returns the tags recorded on every run of the suite.
+    pub fn tags(&self) -> &BTreeMap<String, String> {
+        &self.tags
+    }
+
+    /// Returns a copy of the suite whose runs also record `tags`, overriding
+    /// suite tags with the same key.
+    #[cfg(feature = "scheduler")]
+    pub(crate) fn with_run_tags<I>(&self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut suite = self.clone();
+        Arc::make_mut(&mut suite.tags).extend(tags);
+        suite
+    }
+
+    /// Returns the policy redacting values quoted in constraint results, if any.
+    pub fn redaction(&self) -> Option<&RedactionPolicy> {
+        self.redaction.as_deref()
+    }
+
+    /// Returns the lineage attached to the issues of the suite, if any.
+    pub fn lineage(&self) -> Option<&LineageMap> {
+        self.lineage.as_deref()
+    }
+
+    /// Returns the bounds on the result sets constraints collect.
+    pub fn collection_limits(&self) -> &CollectionLimits {
+        &self.collection_limits
+    }
+
+    /// Returns the version of the suite definition, if one was set.
+    pub fn version(&self) -> Option<&SuiteVersion> {
+        self.version.as_ref()
+    }
+
+    /// Returns the order in which the checks run.
+    pub fn execution_order(&self) -> &ExecutionOrder {
+        &self.scheduler.order
+    }
+
+    /// Returns true if runs probe the attached sources before any constraint runs.
+    pub fn preflight_enabled(&self) -> bool {
+        self.preflight
+    }
+
+    /// Returns the attached sources with the names of their tables.
+    pub fn sources(&self) -> &[(String, Arc<dyn DataSource>)] {
+        &self.sources
+    }
+
+    /// Returns the predicates restricting the rows of the validated table.
+    pub fn row_filters(&self) -> &[String] {
+        &self.row_filters
+    }
+
+    /// Returns whether runs answer constraints from file metadata.
+    pub fn metadata_only(&self) -> MetadataOnlyMode {
+        self.metadata_only
+    }
+
+    /// Returns the names of the UDFs registered for each run.
+    pub fn udf_names(&self) -> Vec<&str> {
+        self.udfs.iter().map(SuiteUdf::name).collect()
+    }
+
+    /// Returns the lookup tables registered for each run.
+    ///
+    /// Lookup tables whose rows do not fit their schema are left out; the suite
+    /// fails to run with a configuration error naming them.
+    pub fn lookups(&self) -> Vec<&LookupTable> {
+        self.lookups.iter().filter_map(SuiteLookup::table).collect()
+    }
+
+    /// Probes every attached source, whether or not preflight is enabled.
+    ///
+    /// See [`DataSource::probe`].
+    pub async fn preflight(&self) -> PreflightReport {
+        PreflightReport::probe(&self.name, &self.sources).await
+    }
+
+    /// Returns the default values of the suite's named parameters.
+    pub fn parameter_defaults(&self) -> &ParameterBag {
+        &self.parameter_defaults
+    }
+
     /// Returns the checks in this validation suite.
     pub fn checks(&self) -> &[Arc<Check>] {
         &self.checks
     }
 
+    /// Combines this suite with the checks of `other`, such as a shared baseline
+    /// with the additions of one table.
+    ///
+    /// The checks of `other` run after those of this suite, and the descriptions
+    /// of both suites are combined. Tags, UDFs and lookup tables of `other` are
+    /// added unless this suite already has one with the same key or name. Every
+    /// other setting, such as the table name, telemetry and hooks, is kept from
+    /// this suite.
+    ///
+    /// # Errors
+    ///
+    /// With [`CheckConflictPolicy::Error`], returns
+    /// [`TermError::InvalidConfiguration`] listing every check of `other` whose
+    /// name is already used by a check of this suite.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, CheckConflictPolicy, ValidationSuite};
+    ///
+    /// let baseline = ValidationSuite::builder("baseline")
+    ///     .check(Check::builder("row_count").build())
+    ///     .build();
+    /// let orders = ValidationSuite::builder("orders")
+    ///     .check(Check::builder("row_count").build())
+    ///     .check(Check::builder("order_ids").build())
+    ///     .build();
+    ///
+    /// let suite = baseline.merge(orders, CheckConflictPolicy::Rename).unwrap();
+    /// let names: Vec<&str> = suite.checks().iter().map(|check| check.name()).collect();
+    /// assert_eq!(names, ["row_count", "orders.row_count", "order_ids"]);
+    /// ```
+    pub fn merge(mut self, other: ValidationSuite, policy: CheckConflictPolicy) -> Result<Self> {
+        let mut names: BTreeSet<String> = self
+            .checks
+            .iter()
+            .map(|check| check.name().to_string())
+            .collect();
+        let mut report = ConfigReport::new(format!("suite '{}'", self.name));
+        let offset = self.checks.len();
+        for (index, check) in other.checks.into_iter().enumerate() {
+            if !names.contains(check.name()) {
+                names.insert(check.name().to_string());
+                self.checks.push(check);
+                continue;
+            }
+            match policy {
+                CheckConflictPolicy::Error => report.push(
+                    ConfigIssue::error(
+                        format!("checks[{}]", offset + index),
+                        format!(
+                            "Check '{}' of suite '{}' has the name of a check of suite '{}'",
+                            check.name(),
+                            other.name,
+                            self.name
+                        ),
+                    )
+                    .in_check(check.name()),
+                ),
+                CheckConflictPolicy::Rename => {
+                    let prefixed = format!("{}.{}", other.name, check.name());
+                    let mut name = prefixed.clone();
+                    let mut suffix = 1;
+                    while names.contains(&name) {
+                        suffix += 1;
+                        name = format!("{prefixed}_{suffix}");
+                    }
+                    debug!(
+                        suite.name = %self.name,
+                        check.name = %check.name(),
+                        check.renamed = %name,
+                        "Renamed merged check"
+                    );
+                    names.insert(name.clone());
+                    self.checks.push(Arc::new(check.renamed(name)));
+                }
+            }
+        }
+        report.into_result()?;
+
+        self.description = match (self.description, other.description) {
+            (Some(own), Some(other)) => Some(format!("{own}; {other}")),
+            (own, other) => own.or(other),
+        };
+        let tags = Arc::make_mut(&mut self.tags);
+        for (key, value) in other.tags.iter() {
+            tags.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+        extend_by_name(&mut self.udfs, other.udfs, SuiteUdf::name);
+        extend_by_name(&mut self.lookups, other.lookups, SuiteLookup::name);
+        Ok(self)
+    }
+
+    /// Returns the suite without the check named `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::InvalidConfiguration`] if the suite has no such check.
+    pub fn without_check(mut self, name: &str) -> Result<Self> {
+        let index = self.check_position(name)?;
+        self.checks.remove(index);
+        Ok(self)
+    }
+
+    /// Returns the suite with the check named `name` replaced by `check`, which
+    /// runs at the same position.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::InvalidConfiguration`] if the suite has no such
+    /// check, or if `check` has the name of another check of the suite.
+    pub fn with_check_replaced(mut self, name: &str, check: Check) -> Result<Self> {
+        let index = self.check_position(name)?;
+        if check.name() != name && self.check_position(check.name()).is_ok() {
+            return Err(self.check_error(
+                check.name(),
+                format!(
+                    "Suite '{}' already has a check named '{}'",
+                    self.name,
+                    check.name()
+                ),
+            ));
+        }
+        self.checks[index] = Arc::new(check);
+        Ok(self)
+    }
+
+    /// Returns the position of the check named `name`.
+    fn check_position(&self, name: &str) -> Result<usize> {
+        self.checks
+            .iter()
+            .position(|check| check.name() == name)
+            .ok_or_else(|| {
+                self.check_error(
+                    name,
+                    format!("Suite '{}' has no check named '{name}'", self.name),
+                )
+            })
+    }
+
+    /// Returns a configuration error about the check named `name`, located at
+    /// that name.
+    fn check_error(&self, name: &str, message: String) -> TermError {
+        let mut report = ConfigReport::new(format!("suite '{}'", self.name));
+        report.push(ConfigIssue::error(name, message).in_check(name));
+        TermError::InvalidConfiguration(Box::new(report))
+    }
+
     /// Returns whether telemetry is enabled for this suite.
     pub fn telemetry_enabled(&self) -> bool {
         self.telemetry.is_some()
@@ -382,8 +989,67 @@ impl ValidationSuite {
         self.use_optimizer
     }
 
+    /// Returns whether runs of this suite record a timeline.
+    pub fn timeline_enabled(&self) -> bool {
+        self.record_timeline
+    }
+
+    /// Returns how constraints skipped because the table is empty are reported.
+    pub fn empty_table_policy(&self) -> EmptyTablePolicy {
+        self.empty_table_policy
+    }
+
+    /// Returns the physical column of the validated table for each logical
+    /// column, as set with [`with_column_mapping`](ValidationSuiteBuilder::with_column_mapping).
+    pub fn column_mapping(&self) -> &BTreeMap<String, String> {
+        &self.column_mapping
+    }
+
+    /// Returns the names of the tables the suite reads.
+    ///
+    /// These are the validated table and the tables referenced by multi-table
+    /// constraints, with the suite's default schema applied to unqualified names.
+    /// Tables read by the query of a derived table are not listed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, ValidationSuite};
+    ///
+    /// let suite = ValidationSuite::builder("orders")
+    ///     .table_name("orders")
+    ///     .check(
+    ///         Check::builder("integrity")
+    ///             .foreign_key("orders.customer_id", "customers.id")
+    ///             .build(),
+    ///     )
+    ///     .build();
+    ///
+    /// let tables: Vec<_> = suite.expected_tables().into_iter().collect();
+    /// assert_eq!(tables, ["customers", "orders"]);
+    /// ```
+    pub fn expected_tables(&self) -> BTreeSet<String> {
+        let referenced = self
+            .checks
+            .iter()
+            .flat_map(|check| check.constraints())
+            .flat_map(|constraint| constraint.referenced_tables())
+            .map(
+                |name| match (QualifiedTable::parse(&name), &self.default_schema) {
+                    (Ok(table), Some(schema)) => table.with_default_schema(schema).to_string(),
+                    (Ok(table), None) => table.to_string(),
+                    (Err(_), _) => name,
+                },
+            );
+        std::iter::once(self.table_name.clone())
+            .chain(referenced)
+            .collect()
+    }
+
     /// Runs the validation suite against the provided data.
     ///
+    /// This is [`run_with`](Self::run_with) with the default [`RunOptions`].
+    ///
     /// # Arguments
     ///
     /// * `ctx` - The DataFusion session context containing the data to validate
@@ -391,13 +1057,478 @@ impl ValidationSuite {
     /// # Returns
     ///
     /// A `Result` containing the validation result or an error
-    #[instrument(skip(self, ctx), fields(
+    pub async fn run(&self, ctx: &SessionContext) -> Result<ValidationResult> {
+        self.run_with(ctx, &RunOptions::new()).await
+    }
+
+    /// Runs the validation suite with per-run options.
+    ///
+    /// `options` set the parameter values, extensions, precomputed statistics,
+    /// check filter, column mapping and preview sampling of this run; see
+    /// [`RunOptions`] for each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{
+    ///     Check, CheckFilter, ConstraintOptions, PreviewSpec, RunOptions, ValidationSuite,
+    /// };
+    /// use term_guard::constraints::Assertion;
+    /// use datafusion::prelude::*;
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let ctx = SessionContext::new();
+    /// ctx.sql("CREATE TABLE data (id INT) AS VALUES (1), (2), (3)").await?.collect().await?;
+    ///
+    /// let suite = ValidationSuite::builder("orders")
+    ///     .check(
+    ///         Check::builder("ids")
+    ///             .has_size(Assertion::Equals(3.0))
+    ///             .completeness("id", ConstraintOptions::new().with_threshold(1.0))
+    ///             .build(),
+    ///     )
+    ///     .check(Check::builder("not_empty").has_size(Assertion::GreaterThan(0.0)).build())
+    ///     .build();
+    ///
+    /// let options = RunOptions::new()
+    ///     .with_filter(CheckFilter::new().include("ids")?)
+    ///     .with_preview(PreviewSpec { limit: 2 });
+    /// let result = suite.run_with(&ctx, &options).await?;
+    /// let preview = result.report().preview.as_ref().unwrap();
+    /// assert_eq!(preview.sampled_rows["data"], 2);
+    /// assert_eq!(preview.skipped_constraints.len(), 1);
+    /// assert_eq!(result.report().excluded_checks[0].check_name, "not_empty");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    #[instrument(skip(self, ctx, options), fields(
         suite.name = %self.name,
         suite.checks = self.checks.len(),
-        telemetry.enabled = self.telemetry_enabled()
+        telemetry.enabled = self.telemetry_enabled(),
+        extensions.len = options.extensions().len(),
+        preview.limit = options.preview().map(|spec| spec.limit)
     ))]
-    pub async fn run(&self, ctx: &SessionContext) -> Result<ValidationResult> {
+    pub async fn run_with(
+        &self,
+        ctx: &SessionContext,
+        options: &RunOptions,
+    ) -> Result<ValidationResult> {
+        self.run_internal(ctx, options).await
+    }
+
+    /// Binds the suite to the schemas of its tables in `ctx`.
+    ///
+    /// The schema of every table listed by [`expected_tables`](Self::expected_tables)
+    /// is captured; for a suite with a derived table, the schema of its query is
+    /// captured for the validated table. [`BoundSuite::run`] then refuses to run
+    /// against a context whose tables differ, which guards suites cached in
+    /// long-running services against being run on the wrong data.
+    ///
+    /// Returns a configuration error if a table is not registered in `ctx`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::Assertion;
+    /// use term_guard::core::{Check, ValidationSuite};
+    /// use term_guard::error::TermError;
+    /// use datafusion::prelude::*;
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let tenant_a = SessionContext::new();
+    /// tenant_a.sql("CREATE TABLE data (id INT) AS VALUES (1), (2)").await?.collect().await?;
+    /// let tenant_b = SessionContext::new();
+    /// tenant_b.sql("CREATE TABLE data (id VARCHAR) AS VALUES ('x')").await?.collect().await?;
+    ///
+    /// let suite = ValidationSuite::builder("ids")
+    ///     .check(Check::builder("volume").has_size(Assertion::GreaterThan(0.0)).build())
+    ///     .build()
+    ///     .bind(&tenant_a)
+    ///     .await?;
+    ///
+    /// assert!(suite.run(&tenant_a).await?.is_success());
+    /// let error = suite.run(&tenant_b).await.unwrap_err();
+    /// assert!(matches!(error, TermError::BindingMismatch(_)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub async fn bind(&self, ctx: &SessionContext) -> Result<BoundSuite> {
+        BoundSuite::capture(self.clone(), ctx, false).await
+    }
+
+    /// Binds the suite like [`bind`](Self::bind) and also records the
+    /// fingerprints of its tables.
+    ///
+    /// Fingerprints are computed like those of the [`ResultCache`]: from versions
+    /// registered with the suite's result cache, or from the file manifest of
+    /// file-backed tables. A run then also fails if such a table now reads other
+    /// files, even with the same schema. Tables without a fingerprint are only
+    /// compared by schema.
+    pub async fn bind_with_fingerprints(&self, ctx: &SessionContext) -> Result<BoundSuite> {
+        BoundSuite::capture(self.clone(), ctx, true).await
+    }
+
+    /// Returns the schema of a table the suite reads, or `None` if `ctx` has no
+    /// such table.
+    ///
+    /// The schema of the validated table of a suite with a derived table is the
+    /// schema of the derived query.
+    pub(crate) async fn table_schema(
+        &self,
+        ctx: &SessionContext,
+        table: &str,
+    ) -> Result<Option<SchemaRef>> {
+        if let (Some(derived), true) = (&self.derived_table, table == self.table_name) {
+            let df = derived.plan(ctx, &self.table_name).await?;
+            return Ok(Some(df.schema().inner().clone()));
+        }
+        Ok(ctx
+            .table_provider(table)
+            .await
+            .ok()
+            .map(|provider| provider.schema()))
+    }
+
+    /// Returns the fingerprint of a table the suite reads, if it has one.
+    pub(crate) async fn table_fingerprint(
+        &self,
+        ctx: &SessionContext,
+        table: &str,
+    ) -> Result<Option<String>> {
+        match &self.result_cache {
+            Some(cache) => cache.table_fingerprint(ctx, table).await,
+            None => source_fingerprint(ctx, table).await,
+        }
+    }
+
+    /// Merges run-time parameters over the defaults and checks them against
+    /// every parameter referenced by the suite's constraints, adding a missing
+    /// or out-of-range parameter to `report` for every constraint using it.
+    ///
+    /// Returns only the valid parameters that are actually referenced.
+    fn resolve_parameters(
+        &self,
+        overrides: &ParameterBag,
+        report: &mut ConfigReport,
+    ) -> ParameterBag {
+        let merged = self.parameter_defaults.with_overrides(overrides);
+        let mut resolved = ParameterBag::new();
+        for check in &self.checks {
+            for (index, constraint) in check.constraints().iter().enumerate() {
+                for reference in constraint.parameters() {
+                    match merged.validate(&reference) {
+                        Ok(value) => resolved.set(reference.name, value),
+                        Err(message) => report.push(
+                            ConfigIssue::error(format!("parameters.{}", reference.name), message)
+                                .in_check(check.name())
+                                .at_constraint(index),
+                        ),
+                    }
+                }
+            }
+        }
+        resolved
+    }
+
+    /// Collects the problems of the suite's checks, UDFs and lookup tables.
+    fn config_report(&self) -> ConfigReport {
+        let mut report = ConfigReport::new(format!("suite '{}'", self.name));
+        report.extend(check_issues(&self.checks));
+        report.extend(udf_issues(&self.udfs));
+        let reserved = reserved_table_names(&self.table_name, &self.sources);
+        report.extend(lookup_issues(
+            &self.lookups,
+            self.max_lookup_bytes,
+            &reserved,
+        ));
+        report
+    }
+
+    async fn run_internal(
+        &self,
+        ctx: &SessionContext,
+        options: &RunOptions,
+    ) -> Result<ValidationResult> {
+        // Fail before issuing any query if a check, UDF or parameter is invalid,
+        // reporting every problem at once
+        let mut report = self.config_report();
+        let parameters = self.resolve_parameters(options.parameters(), &mut report);
+        report.into_result()?;
+        let parameters = Arc::new(parameters);
+        if self.preflight {
+            self.preflight().await.into_result()?;
+        }
+        let statistics = options.statistics();
+        let filter = options.filter();
+        let preview = options.preview();
+        let extensions = Arc::new(options.extensions().clone());
+        let column_mapping = options.column_mapping().unwrap_or(&self.column_mapping);
+
+        // Every event of the run carries the run ID through this span
+        let run_id = Uuid::new_v4();
+        let span = info_span!(
+            "validation_run",
+            run_id = %run_id,
+            suite.name = %self.name,
+            table.name = %self.table_name,
+            suite.tags = %self.formatted_tags(),
+        );
+
+        let mut timeline = TimelineRecorder::new(self.record_timeline);
+        timeline.enter(TimelinePhase::Run, &self.name);
+
+        let udfs = RegisteredUdfs::register(ctx, &self.udfs);
+        let lookups = match RegisteredLookups::register(ctx, &self.lookups) {
+            Ok(lookups) => lookups,
+            Err(e) => {
+                udfs.restore(ctx);
+                return Err(e);
+            }
+        };
+        let registered = match self.register_sources(ctx, &mut timeline).await {
+            Ok(registered) => registered,
+            Err(e) => {
+                lookups.restore(ctx);
+                udfs.restore(ctx);
+                return Err(e);
+            }
+        };
+        let metadata = if preview.is_none() {
+            self.metadata_statistics(&registered, column_mapping).await
+        } else {
+            None
+        };
+        // Row filters the source of the validated table did not apply
+        let row_filters: &[String] = if registered.filtered {
+            &[]
+        } else {
+            &self.row_filters
+        };
+
+        let result = async {
+            if let Some(spec) = preview {
+                // Sample into a separate context, leaving the caller's tables untouched
+                let registration =
+                    timeline.enter(TimelinePhase::SourceRegistration, &self.table_name);
+                let source = self.plan_source(ctx, column_mapping, row_filters).await?;
+                let (preview_ctx, summary) = spec.sample(ctx, &self.table_name, source).await?;
+                timeline.exit(registration);
+                return self
+                    .run_checks(
+                        &preview_ctx,
+                        statistics,
+                        None,
+                        parameters,
+                        extensions,
+                        filter,
+                        column_mapping,
+                        Some(summary),
+                        run_id,
+                        timeline,
+                    )
+                    .await;
+            }
+
+            if !column_mapping.is_empty() || !row_filters.is_empty() {
+                // Fail before any constraint runs if the mapping or the row filters do
+                // not fit the table
+                let registration =
+                    timeline.enter(TimelinePhase::SourceRegistration, &self.table_name);
+                if self.derived_table.is_some() && ctx.table_exist(self.table_name.as_str())? {
+                    return Err(TermError::Configuration(format!(
+                        "Cannot register derived table '{}': a table with this name already exists",
+                        self.table_name
+                    )));
+                }
+                let view = self.plan_source(ctx, column_mapping, row_filters).await?;
+                let replaced = column_mapping::register(ctx, &self.table_name, view)?;
+                timeline.exit(registration);
+                let result = self
+                    .run_checks(
+                        ctx,
+                        statistics,
+                        None,
+                        parameters,
+                        extensions,
+                        filter,
+                        column_mapping,
+                        None,
+                        run_id,
+                        timeline,
+                    )
+                    .await;
+                column_mapping::restore(ctx, &self.table_name, replaced);
+                return result;
+            }
+
+            let Some(derived) = &self.derived_table else {
+                return self
+                    .run_checks(
+                        ctx,
+                        statistics,
+                        metadata.as_ref(),
+                        parameters,
+                        extensions,
+                        filter,
+                        column_mapping,
+                        None,
+                        run_id,
+                        timeline,
+                    )
+                    .await;
+            };
+            // Fail before any constraint runs if the derived table cannot be planned
+            let registration = timeline.enter(TimelinePhase::SourceRegistration, &self.table_name);
+            derived.register(ctx, &self.table_name).await?;
+            timeline.exit(registration);
+            let result = self
+                .run_checks(
+                    ctx,
+                    statistics,
+                    None,
+                    parameters,
+                    extensions,
+                    filter,
+                    column_mapping,
+                    None,
+                    run_id,
+                    timeline,
+                )
+                .await;
+            DerivedTable::deregister(ctx, &self.table_name);
+            result
+        }
+        .instrument(span)
+        .await;
+        registered.deregister(ctx);
+        lookups.restore(ctx);
+        udfs.restore(ctx);
+        result
+    }
+
+    /// Reads the metadata statistics of the validated table's source, if the
+    /// suite is in metadata-only mode and the statistics describe the table.
+    ///
+    /// That is the case when this run registered the table from its source and
+    /// reads it whole: row filters, a column mapping or a derived table change
+    /// the rows or columns the metadata describes. A source failing to read its
+    /// statistics is logged, and the constraints are scanned instead.
+    async fn metadata_statistics(
+        &self,
+        registered: &RegisteredSources,
+        column_mapping: &BTreeMap<String, String>,
+    ) -> Option<MetadataStatistics> {
+        if !self.metadata_only.is_enabled()
+            || self.derived_table.is_some()
+            || !self.row_filters.is_empty()
+            || !column_mapping.is_empty()
+            || !registered.tables.contains(&self.table_name)
+        {
+            return None;
+        }
+        let (_, source) = self
+            .sources
+            .iter()
+            .find(|(name, _)| *name == self.table_name)?;
+        match source.statistics().await {
+            Ok(statistics) => statistics.map(|statistics| statistics.trusted(self.metadata_only)),
+            Err(e) => {
+                warn!(
+                    table.name = %self.table_name,
+                    error = %e,
+                    "Failed to read metadata statistics, scanning instead"
+                );
+                None
+            }
+        }
+    }
+
+    /// Registers the attached sources whose tables the session context does
+    /// not have yet.
+    ///
+    /// The source of the validated table receives the row filters, unless the
+    /// suite validates a derived table. If a source fails to register, the
+    /// tables registered before it are deregistered again.
+    async fn register_sources(
+        &self,
+        ctx: &SessionContext,
+        timeline: &mut TimelineRecorder,
+    ) -> Result<RegisteredSources> {
+        let mut registered = RegisteredSources::default();
+        for (name, source) in &self.sources {
+            if ctx.table_exist(name.as_str())? {
+                continue;
+            }
+            let filtered = *name == self.table_name
+                && self.derived_table.is_none()
+                && !self.row_filters.is_empty();
+            let predicates: &[String] = if filtered { &self.row_filters } else { &[] };
+
+            let registration = timeline.enter(TimelinePhase::SourceRegistration, name);
+            let outcome = source
+                .register_with_predicates(ctx, name, predicates, self.telemetry.as_ref())
+                .await;
+            timeline.exit(registration);
+            if let Err(e) = outcome {
+                registered.deregister(ctx);
+                return Err(e);
+            }
+            debug!(table.name = %name, source = %source.description(), "Registered attached source");
+            registered.tables.push(name.clone());
+            registered.filtered |= filtered;
+        }
+        Ok(registered)
+    }
+
+    /// Plans the validated table: the derived table if the suite has one, with
+    /// `row_filters` and then `column_mapping` applied.
+    async fn plan_source(
+        &self,
+        ctx: &SessionContext,
+        column_mapping: &BTreeMap<String, String>,
+        row_filters: &[String],
+    ) -> Result<DataFrame> {
+        let source = match &self.derived_table {
+            Some(derived) => derived.plan(ctx, &self.table_name).await?,
+            None => ctx.table(self.table_name.as_str()).await?,
+        };
+        let source = filter_rows(source, row_filters, &self.table_name)?;
+        if column_mapping.is_empty() {
+            return Ok(source);
+        }
+        column_mapping::apply(
+            source,
+            &self.table_name,
+            column_mapping,
+            &column_mapping::referenced_columns(&self.checks),
+        )
+    }
+
+    /// Renders the tags as `key=value` pairs for log fields.
+    fn formatted_tags(&self) -> String {
+        self.tags
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn run_checks(
+        &self,
+        ctx: &SessionContext,
+        statistics: Option<&PrecomputedStatistics>,
+        metadata: Option<&MetadataStatistics>,
+        parameters: Arc<ParameterBag>,
+        extensions: Arc<Extensions>,
+        filter: Option<&CheckFilter>,
+        column_mapping: &BTreeMap<String, String>,
+        preview: Option<PreviewSummary>,
+        run_id: Uuid,
+        mut timeline: TimelineRecorder,
+    ) -> Result<ValidationResult> {
         info!(
+            event = "run_started",
             suite.name = %self.name,
             suite.checks = self.checks.len(),
             suite.description = ?self.description,
@@ -424,10 +1555,16 @@ impl ValidationSuite {
         #[cfg(feature = "telemetry")]
         if let Some(telemetry) = &self.telemetry {
             if let Some(metrics) = telemetry.metrics() {
-                let attrs = vec![
+                let mut attrs = vec![
                     opentelemetry::KeyValue::new("suite.name", self.name.clone()),
                     opentelemetry::KeyValue::new("check.count", self.checks.len() as i64),
                 ];
+                if let Some(version) = &self.version {
+                    attrs.push(opentelemetry::KeyValue::new(
+                        "suite.version",
+                        version.version.to_string(),
+                    ));
+                }
                 metrics.increment_validation_runs(&attrs);
 
                 // Try to get row count from the data table
@@ -450,54 +1587,124 @@ impl ValidationSuite {
         }
 
         let mut report = ValidationReport::new(&self.name);
+        report.run_id = Some(run_id);
+        report.description = self.description.clone();
+        report.suite_version = self.version.as_ref().map(|v| v.version.to_string());
+        report.check_descriptions = self
+            .checks
+            .iter()
+            .filter_map(|check| {
+                let description = check.description()?;
+                Some((check.name().to_string(), description.to_string()))
+            })
+            .collect();
+        report.check_metadata = self
+            .checks
+            .iter()
+            .filter(|check| !check.metadata().is_empty())
+            .map(|check| (check.name().to_string(), check.metadata().clone()))
+            .collect();
+        report.preview = preview;
+        report.column_mapping = column_mapping.clone();
+        report.parameters = parameters
+            .iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
         let mut metrics = ValidationMetrics::new();
-        let mut has_errors = false;
 
-        // Use optimizer if enabled
-        if self.use_optimizer {
-            // TODO: Implement optimized execution once TermContext integration is resolved
-            // For now, fall back to sequential execution
+        // Results computed on a sample must be neither reused nor stored
+        let mut cache_run = match &self.result_cache {
+            Some(cache) if report.preview.is_none() => {
+                let lookups = self.lookups();
+                let mut fingerprint = suite_fingerprint(
+                    &self.name,
+                    &self.table_name,
+                    column_mapping,
+                    &self.checks,
+                    &parameters,
+                    &lookups,
+                );
+                // Results rendered without redaction must not be served to a redacting suite
+                if let Some(policy) = &self.redaction {
+                    fingerprint = hash_parts([fingerprint.as_str(), &format!("{policy:?}")]);
+                }
+                // Nor results computed from differently truncated result sets
+                if *self.collection_limits != CollectionLimits::default() {
+                    let limits = format!("{:?}", self.collection_limits);
+                    fingerprint = hash_parts([fingerprint.as_str(), &limits]);
+                }
+                cache
+                    .lookup(ctx, &fingerprint, &self.table_name, &self.checks, &lookups)
+                    .await?
+            }
+            _ => None,
+        };
+
+        let (order, schedule) = self.scheduler.schedule(&self.name, &self.checks).await;
+        report.schedule = schedule;
+
+        // Use optimizer if enabled
+        if self.use_optimizer {
+            // TODO: Implement optimized execution once TermContext integration is resolved
+            // For now, fall back to sequential execution
+            let analysis = timeline.enter(TimelinePhase::OptimizerAnalysis, &self.name);
             warn!("Query optimizer is not yet implemented, falling back to sequential execution");
+            timeline.exit(analysis);
             self.run_sequential(
                 ctx,
+                statistics,
+                metadata,
+                &parameters,
+                &extensions,
+                filter,
+                &order,
+                cache_run.as_mut(),
                 &mut report,
                 &mut metrics,
-                &mut has_errors,
                 &start_time,
                 &mut suite_span,
+                &mut timeline,
             )
             .await?;
         } else {
             // Non-optimized execution path
             self.run_sequential(
                 ctx,
+                statistics,
+                metadata,
+                &parameters,
+                &extensions,
+                filter,
+                &order,
+                cache_run.as_mut(),
                 &mut report,
                 &mut metrics,
-                &mut has_errors,
                 &start_time,
                 &mut suite_span,
+                &mut timeline,
             )
             .await?;
         }
 
+        let assembly = timeline.enter(TimelinePhase::ReportAssembly, &self.name);
+        if let (Some(cache), Some(run)) = (&self.result_cache, cache_run) {
+            cache.store(run).await;
+        }
+
         metrics.execution_time_ms = start_time.elapsed().as_millis() as u64;
         report.metrics = metrics.clone();
 
         // Record final metrics and complete
+        let has_errors = report.has_errors();
         self.record_final_metrics(&metrics, has_errors, &start_time, &mut suite_span);
+        report.telemetry_incomplete = self
+            .telemetry
+            .as_ref()
+            .is_some_and(|telemetry| telemetry.health().degraded);
+        timeline.exit(assembly);
+        report.set_timeline(timeline.finish());
 
-        info!(
-            suite.name = %self.name,
-            metrics.passed = metrics.passed_checks,
-            metrics.failed = metrics.failed_checks,
-            "Validation suite completed (optimized)"
-        );
-
-        if has_errors {
-            Ok(ValidationResult::failure(report))
-        } else {
-            Ok(ValidationResult::success(metrics, report))
-        }
+        Ok(ValidationResult::from_report(report))
     }
 }
 
@@ -535,6 +1742,28 @@ pub struct ValidationSuiteBuilder {
     telemetry: Option<Arc<TermTelemetry>>,
     use_optimizer: bool,
     table_name: String,
+    default_schema: Option<String>,
+    result_cache: Option<ResultCache>,
+    shared_stats: Option<SharedStatsCache>,
+    parameter_defaults: ParameterBag,
+    derived_table: Option<DerivedTable>,
+    column_mapping: BTreeMap<String, String>,
+    row_filters: Vec<String>,
+    tags: BTreeMap<String, String>,
+    record_timeline: bool,
+    empty_table_policy: EmptyTablePolicy,
+    hooks: HookChain,
+    redaction: Option<Arc<RedactionPolicy>>,
+    lineage: Option<LineageMap>,
+    sources: Vec<(String, Arc<dyn DataSource>)>,
+    preflight: bool,
+    scheduler: Scheduler,
+    metadata_only: MetadataOnlyMode,
+    udfs: Vec<SuiteUdf>,
+    lookups: Vec<SuiteLookup>,
+    max_lookup_bytes: usize,
+    collection_limits: CollectionLimits,
+    version: Option<SuiteVersion>,
 }
 
 impl ValidationSuiteBuilder {
@@ -547,9 +1776,72 @@ impl ValidationSuiteBuilder {
             telemetry: None,
             use_optimizer: false,
             table_name: "data".to_string(),
+            default_schema: None,
+            result_cache: None,
+            shared_stats: None,
+            parameter_defaults: ParameterBag::new(),
+            derived_table: None,
+            column_mapping: BTreeMap::new(),
+            row_filters: Vec::new(),
+            tags: BTreeMap::new(),
+            record_timeline: false,
+            empty_table_policy: EmptyTablePolicy::default(),
+            hooks: HookChain::default(),
+            redaction: None,
+            lineage: None,
+            sources: Vec::new(),
+            preflight: false,
+            scheduler: Scheduler::default(),
+            metadata_only: MetadataOnlyMode::default(),
+            udfs: Vec::new(),
+            lookups: Vec::new(),
+            max_lookup_bytes: DEFAULT_MAX_LOOKUP_BYTES,
+            collection_limits: CollectionLimits::default(),
+            version: None,
         }
     }
 
+    /// Adds the checks of `suite` to the builder, such as to start from a shared
+    /// baseline and add table-specific checks.
+    ///
+    /// The UDFs and lookup tables of `suite` are added unless the builder already
+    /// has one with the same name, and so are its tags and description. Other
+    /// settings of
+    /// `suite`, such as its table name and telemetry, are not copied.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, ValidationSuite};
+    ///
+    /// let baseline = ValidationSuite::builder("baseline")
+    ///     .check(Check::builder("ids").build())
+    ///     .build();
+    /// let suite = ValidationSuite::builder("orders")
+    ///     .extend_from(&baseline)
+    ///     .check(Check::builder("order_ids").build())
+    ///     .build();
+    /// assert_eq!(suite.checks().len(), 2);
+    /// ```
+    pub fn extend_from(mut self, suite: &ValidationSuite) -> Self {
+        self.checks.extend(suite.checks.iter().cloned());
+        if self.description.is_none() {
+            self.description = suite.description.clone();
+        }
+        for (key, value) in suite.tags.iter() {
+            self.tags
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        extend_by_name(&mut self.udfs, suite.udfs.iter().cloned(), SuiteUdf::name);
+        extend_by_name(
+            &mut self.lookups,
+            suite.lookups.iter().cloned(),
+            SuiteLookup::name,
+        );
+        self
+    }
+
     /// Sets the description for the validation suite.
     ///
     /// # Arguments
@@ -560,6 +1852,28 @@ impl ValidationSuiteBuilder {
         self
     }
 
+    /// Adds a tag describing the suite, such as environment or owning team.
+    ///
+    /// Tags are recorded on the `validation_run` span wrapping every run, next
+    /// to the run ID, and are available to constraints through
+    /// [`ValidationContext::tag`](crate::core::ValidationContext::tag).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::ValidationSuite;
+    ///
+    /// let suite = ValidationSuite::builder("orders")
+    ///     .tag("env", "prod")
+    ///     .tag("team", "billing")
+    ///     .build();
+    /// assert_eq!(suite.tags()["env"], "prod");
+    /// ```
+    pub fn tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.insert(key.into(), value.into());
+        self
+    }
+
     /// Sets the table name to validate.
     ///
     /// By default, validation runs against a table named "data". Use this method
@@ -584,6 +1898,112 @@ impl ValidationSuiteBuilder {
         self
     }
 
+    /// Validates the result of a SQL query instead of a registered table.
+    ///
+    /// Before each run the query is registered as a view named `name`, which
+    /// becomes the suite's table name, and the view is removed when the run
+    /// finishes. The query must be a single `SELECT` statement (see
+    /// [`SqlSecurity::validate_query`](crate::security::SqlSecurity::validate_query)).
+    /// If it cannot be parsed or planned, or a table named `name` already exists,
+    /// the run fails with the view name and the underlying error before any
+    /// constraint is evaluated.
+    ///
+    /// Concurrent runs of the suite must use separate session contexts, since
+    /// each run registers the view under the same name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::Assertion;
+    /// use term_guard::core::{Check, ValidationSuite};
+    ///
+    /// let suite = ValidationSuite::builder("enriched_orders")
+    ///     .derived_table(
+    ///         "orders_with_segment",
+    ///         "SELECT o.*, c.segment FROM orders o JOIN customers c USING (customer_id)",
+    ///     )
+    ///     .check(Check::builder("volume").has_size(Assertion::GreaterThan(0.0)).build())
+    ///     .build();
+    /// assert_eq!(suite.table_name(), "orders_with_segment");
+    /// ```
+    pub fn derived_table(mut self, name: impl Into<String>, sql: impl Into<String>) -> Self {
+        self.table_name = name.into();
+        self.derived_table = Some(DerivedTable::Sql(sql.into()));
+        self
+    }
+
+    /// Validates a DataFusion `DataFrame` instead of a registered table.
+    ///
+    /// Behaves like [`derived_table`](Self::derived_table), registering the
+    /// already planned `DataFrame` as a view named `name` for each run.
+    pub fn derived_table_from_df(mut self, name: impl Into<String>, df: DataFrame) -> Self {
+        self.table_name = name.into();
+        self.derived_table = Some(DerivedTable::DataFrame(Arc::new(df)));
+        self
+    }
+
+    /// Maps the logical columns referenced by the checks to physical columns of
+    /// the validated table.
+    ///
+    /// The same suite can then validate tables that name a column differently,
+    /// such as `cust_id` in one source and `customer_id` in another. Reports use
+    /// the logical names; failure messages of mapped columns also name the
+    /// physical column. A run fails before any constraint runs if a mapped
+    /// physical column does not exist or if a referenced column is neither
+    /// mapped nor present in the table.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use term_guard::core::{Check, ConstraintOptions, ValidationSuite};
+    ///
+    /// let suite = ValidationSuite::builder("customers")
+    ///     .with_column_mapping(HashMap::from([("customer_id", "cust_id")]))
+    ///     .check(
+    ///         Check::builder("ids")
+    ///             .completeness("customer_id", ConstraintOptions::new().with_threshold(1.0))
+    ///             .build(),
+    ///     )
+    ///     .build();
+    /// assert_eq!(suite.column_mapping()["customer_id"], "cust_id");
+    /// ```
+    pub fn with_column_mapping<I, K, V>(mut self, mapping: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.column_mapping = column_mapping::collect(mapping);
+        self
+    }
+
+    /// Sets the schema used for unqualified table names in multi-table constraints.
+    ///
+    /// Constraints such as foreign keys, cross-table sums and join coverage resolve
+    /// table names like `orders` to `staging.orders` when generating SQL. Names that
+    /// already include a schema are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, ValidationSuite};
+    ///
+    /// let suite = ValidationSuite::builder("staging_validation")
+    ///     .table_name("staging.orders")
+    ///     .default_schema("staging")
+    ///     .check(
+    ///         Check::builder("referential_integrity")
+    ///             .foreign_key("orders.customer_id", "customers.id")
+    ///             .build(),
+    ///     )
+    ///     .build();
+    /// ```
+    pub fn default_schema(mut self, schema: impl Into<String>) -> Self {
+        self.default_schema = Some(schema.into());
+        self
+    }
+
     /// Adds a check to the validation suite.
     ///
     /// # Arguments
@@ -607,6 +2027,66 @@ impl ValidationSuiteBuilder {
         self
     }
 
+    /// Adds an anomaly check on an analyzer metric, like Deequ's `addAnomalyCheck`.
+    ///
+    /// At run time the analyzer's metric is computed on the validated table and
+    /// compared by `strategy` against its history in `repository`, filtered by the
+    /// tags in `options`. The result is reported as an `anomaly_check` constraint in
+    /// the check named by [`AnomalyCheckOptions::new`], which is created if the
+    /// suite has no check with that name yet. Anomalies fail the constraint; with
+    /// less history than [`AnomalyCheckOptions::min_history`] it is skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use term_guard::analyzers::anomaly::RelativeRateOfChangeStrategy;
+    /// use term_guard::analyzers::basic::CompletenessAnalyzer;
+    /// use term_guard::core::{AnomalyCheckOptions, ValidationSuite};
+    /// use term_guard::repository::InMemoryRepository;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let suite = ValidationSuite::builder("customers")
+    ///     .anomaly_check(
+    ///         CompletenessAnalyzer::new("email"),
+    ///         RelativeRateOfChangeStrategy::new(0.1)?,
+    ///         Arc::new(InMemoryRepository::new()),
+    ///         AnomalyCheckOptions::new("email_quality").min_history(5),
+    ///     )
+    ///     .build();
+    /// assert_eq!(suite.checks()[0].name(), "email_quality");
+    /// # Ok(())
+    /// # }
+    /// # example().unwrap();
+    /// ```
+    pub fn anomaly_check<A>(
+        mut self,
+        analyzer: A,
+        strategy: impl AnomalyDetectionStrategy + 'static,
+        repository: Arc<dyn MetricsRepository>,
+        options: AnomalyCheckOptions,
+    ) -> Self
+    where
+        A: Analyzer + 'static,
+        A::Metric: Into<MetricValue> + 'static,
+    {
+        let check_name = options.check_name().to_string();
+        let level = options.check_level();
+        let constraint = Arc::new(AnomalyCheckConstraint::new(
+            analyzer, strategy, repository, options,
+        ));
+
+        match self.checks.iter_mut().find(|c| c.name() == check_name) {
+            Some(check) => Arc::make_mut(check).push_constraint(constraint),
+            None => {
+                let mut check = Check::builder(check_name).level(level).build();
+                check.push_constraint(constraint);
+                self.checks.push(Arc::new(check));
+            }
+        }
+        self
+    }
+
     /// Sets the telemetry configuration for the suite.
     ///
     /// # Arguments
@@ -641,12 +2121,556 @@ impl ValidationSuiteBuilder {
         self
     }
 
+    /// Sets whether runs record a timeline of their phases.
+    ///
+    /// The timeline times source registration, optimizer analysis, every check
+    /// and constraint, and report assembly without requiring a tracing backend.
+    /// It is available from [`ValidationReport::timeline`](super::ValidationReport::timeline)
+    /// and exports to the Chrome trace format. See the [`Timeline`](super::Timeline) docs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::ValidationSuite;
+    ///
+    /// let suite = ValidationSuite::builder("nightly")
+    ///     .with_timeline(true)
+    ///     .build();
+    /// assert!(suite.timeline_enabled());
+    /// ```
+    pub fn with_timeline(mut self, enabled: bool) -> Self {
+        self.record_timeline = enabled;
+        self
+    }
+
+    /// Sets how constraints skipped because the table is empty are reported.
+    ///
+    /// Constraints measuring a fraction of rows or a statistic of values skip
+    /// with the message "table is empty" when there are no rows, see
+    /// [`EmptyTablePolicy`]. [`EmptyTablePolicy::Fail`] reports them as failures
+    /// for pipelines where empty input is itself an error, and
+    /// [`EmptyTablePolicy::Pass`] as successes. Other skipped results are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{Check, EmptyTablePolicy, ValidationSuite};
+    /// use term_guard::core::ConstraintOptions;
+    ///
+    /// let suite = ValidationSuite::builder("daily_orders")
+    ///     .on_empty(EmptyTablePolicy::Fail)
+    ///     .check(
+    ///         Check::builder("ids")
+    ///             .completeness("order_id", ConstraintOptions::new().with_threshold(1.0))
+    ///             .build(),
+    ///     )
+    ///     .build();
+    /// assert_eq!(suite.empty_table_policy(), EmptyTablePolicy::Fail);
+    /// ```
+    pub fn on_empty(mut self, policy: EmptyTablePolicy) -> Self {
+        self.empty_table_policy = policy;
+        self
+    }
+
+    /// Enables caching of constraint results across runs.
+    ///
+    /// Before running, the suite fingerprints its configuration and the validated
+    /// table. Constraint results stored by a previous run with the same
+    /// fingerprints are reused instead of re-evaluated and listed in
+    /// [`ValidationReport::cached_constraints`](super::ValidationReport::cached_constraints).
+    /// Tables that cannot be fingerprinted are always validated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use term_guard::analyzers::incremental::FileSystemStateStore;
+    /// use term_guard::core::{ResultCache, ValidationSuite};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = ResultCache::new(FileSystemStateStore::new("/tmp/term_result_cache")?)
+    ///     .with_table_version("orders", "2024-01-15");
+    ///
+    /// let suite = ValidationSuite::builder("cached_suite")
+    ///     .table_name("orders")
+    ///     .with_result_cache(cache)
+    ///     .build();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_result_cache(mut self, cache: ResultCache) -> Self {
+        self.result_cache = Some(cache);
+        self
+    }
+
+    /// Shares basic table statistics with other suites through `cache`.
+    ///
+    /// Suites given clones of the same [`SharedStatsCache`] reuse each other's
+    /// unfiltered metrics of the validated table, such as its row count and the
+    /// NULL counts of its columns, instead of recomputing them. See
+    /// [`SharedStatsCache`] for how entries are keyed, expire and are invalidated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::ValidationSuite;
+    /// use term_guard::optimizer::SharedStatsCache;
+    ///
+    /// let stats = SharedStatsCache::new();
+    /// let finance = ValidationSuite::builder("finance")
+    ///     .table_name("orders")
+    ///     .with_shared_stats(stats.clone())
+    ///     .build();
+    /// let logistics = ValidationSuite::builder("logistics")
+    ///     .table_name("orders")
+    ///     .with_shared_stats(stats)
+    ///     .build();
+    /// ```
+    pub fn with_shared_stats(mut self, cache: SharedStatsCache) -> Self {
+        self.shared_stats = Some(cache);
+        self
+    }
+
+    /// Registers a hook called before and after each constraint.
+    ///
+    /// Hooks run in the order they are registered; see [`ValidationHook`] for
+    /// how their decisions and failures are handled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{LoggingHook, ValidationSuite};
+    ///
+    /// let suite = ValidationSuite::builder("orders")
+    ///     .with_hook(LoggingHook::new())
+    ///     .build();
+    /// ```
+    pub fn with_hook(mut self, hook: impl ValidationHook + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Redacts the values of sensitive columns that constraints quote in their
+    /// results, such as the unmatched keys listed by a foreign key constraint.
+    ///
+    /// Values are redacted before they enter a [`ConstraintResult`], so the
+    /// report, its formatters and hooks never see them. See [`RedactionPolicy`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{RedactionPolicy, RedactionStrategy, ValidationSuite};
+    ///
+    /// let suite = ValidationSuite::builder("customers")
+    ///     .redaction(
+    ///         RedactionPolicy::new()
+    ///             .column("email", RedactionStrategy::mask_middle(2))
+    ///             .column("ssn", RedactionStrategy::Drop),
+    ///     )
+    ///     .build();
+    /// assert!(suite.redaction().is_some());
+    /// ```
+    pub fn redaction(mut self, policy: RedactionPolicy) -> Self {
+        self.redaction = Some(Arc::new(policy));
+        self
+    }
+
+    /// Attaches the downstream consumers of the validated columns to the
+    /// issues of the suite.
+    ///
+    /// Every issue and every [`ConstraintInfo`] passed to hooks lists the
+    /// identifiers the lineage records for the table and columns of its
+    /// constraint in [`ValidationIssue::impacts`]. See [`LineageMap`] for how
+    /// constraints are matched, including under a
+    /// [`column_mapping`](Self::with_column_mapping).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{LineageMap, ValidationSuite};
+    ///
+    /// let suite = ValidationSuite::builder("orders")
+    ///     .table_name("orders")
+    ///     .with_lineage(
+    ///         LineageMap::new()
+    ///             .with("orders", "amount", "finance_daily_dashboard")
+    ///             .with("orders", "amount", "revenue_model"),
+    ///     )
+    ///     .build();
+    /// assert!(suite.lineage().is_some());
+    /// ```
+    pub fn with_lineage(mut self, lineage: LineageMap) -> Self {
+        self.lineage = Some(lineage);
+        self
+    }
+
+    /// Bounds the result sets constraints collect, such as histogram buckets or
+    /// the examples of violating keys.
+    ///
+    /// A constraint whose result set exceeds the limits completes on the rows it
+    /// collected and records the truncation in its details. See
+    /// [`CollectionLimits`].
+    pub fn collection_limits(mut self, limits: CollectionLimits) -> Self {
+        self.collection_limits = limits;
+        self
+    }
+
+    /// Sets the version of the suite definition and its change journal.
+    ///
+    /// The version is stamped into every report of the suite as
+    /// [`ValidationReport::suite_version`], recorded as the `suite.version`
+    /// attribute of its telemetry metrics and tagged as [`SUITE_VERSION_TAG`] onto
+    /// the metrics constraints save to a repository. Compare two definitions with
+    /// [`diff_suites`](super::diff_suites).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{SuiteVersion, ValidationSuite};
+    ///
+    /// let suite = ValidationSuite::builder("customers")
+    ///     .version(SuiteVersion::parse("1.3.0").unwrap())
+    ///     .build();
+    /// assert_eq!(suite.version().unwrap().version.to_string(), "1.3.0");
+    /// assert_eq!(suite.tags()["suite_version"], "1.3.0");
+    /// ```
+    pub fn version(mut self, version: SuiteVersion) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Attaches the source the table `name` is read from.
+    ///
+    /// Attached sources are probed before each run when
+    /// [`with_preflight`](Self::with_preflight) is enabled. A run registers
+    /// the sources whose tables the session context does not have yet, and
+    /// deregisters them when it finishes; tables the caller registered are
+    /// left as they are.
+    pub fn source(mut self, name: impl Into<String>, source: impl DataSource + 'static) -> Self {
+        self.sources.push((name.into(), Arc::new(source)));
+        self
+    }
+
+    /// Validates only the rows of the validated table matching `predicate`.
+    ///
+    /// The predicate is a SQL boolean expression over the columns of the
+    /// table, before any [`column_mapping`](Self::column_mapping) applies.
+    /// Calling this several times keeps the rows matching every predicate.
+    ///
+    /// When the run registers the table from an attached
+    /// [`source`](Self::source), the predicates are passed to
+    /// [`DataSource::register_with_predicates`], so a Parquet source prunes
+    /// the row groups and pages they rule out and reads fewer bytes. Otherwise
+    /// the table is replaced by a filtered view for the duration of the run,
+    /// and for a derived table the predicates filter the result of its query;
+    /// filters within that query reach the scans of the tables it reads
+    /// through the query planner.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use term_guard::core::ValidationSuite;
+    /// use term_guard::sources::ParquetSource;
+    ///
+    /// # async fn example() -> term_guard::error::Result<()> {
+    /// let suite = ValidationSuite::builder("daily_events")
+    ///     .table_name("events")
+    ///     .source("events", ParquetSource::from_glob("events/*.parquet").await?)
+    ///     .row_filter("event_date = '2024-03-01'")
+    ///     .build();
+    /// assert_eq!(suite.row_filters(), ["event_date = '2024-03-01'"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn row_filter(mut self, predicate: impl Into<String>) -> Self {
+        self.row_filters.push(predicate.into());
+        self
+    }
+
+    /// Answers constraints from the file metadata of the validated table's
+    /// source where possible, instead of scanning the data.
+    ///
+    /// When the run registers the validated table from its attached
+    /// [`source`](Self::source) and reads it whole, without row filters, a
+    /// column mapping, a derived table or a preview, it asks the source for
+    /// [`DataSource::statistics`]. Constraints whose
+    /// [`Constraint::evaluate_from_metadata`] returns a result from them do not
+    /// issue queries: size constraints, completeness when every file records
+    /// null counts, and min or max constraints of integer columns. Their results
+    /// carry the detail `evidence.<evidence>`, such as
+    /// `evidence.parquet_metadata`, and they are listed in
+    /// [`ValidationReport::metadata_constraints`](super::ValidationReport::metadata_constraints).
+    /// Every other constraint, and every constraint of a source without
+    /// statistics, is scanned as usual.
+    ///
+    /// See [`MetadataStatistics`] for the caveats. Defaults to
+    /// [`MetadataOnlyMode::Disabled`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use term_guard::core::{MetadataOnlyMode, ValidationSuite};
+    /// use term_guard::sources::ParquetSource;
+    ///
+    /// # async fn example() -> term_guard::error::Result<()> {
+    /// let suite = ValidationSuite::builder("events")
+    ///     .table_name("events")
+    ///     .source("events", ParquetSource::from_glob("events/*.parquet").await?)
+    ///     .metadata_only(MetadataOnlyMode::Exact)
+    ///     .build();
+    /// assert!(suite.metadata_only().is_enabled());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn metadata_only(mut self, mode: MetadataOnlyMode) -> Self {
+        self.metadata_only = mode;
+        self
+    }
+
+    /// Registers a scalar UDF on the session context for the duration of each run.
+    ///
+    /// The UDF is registered before any source, row filter or constraint is
+    /// planned, so expressions such as
+    /// [`satisfies`](super::CheckBuilder::satisfies) can call it by `name`, and is
+    /// removed when the run finishes; a function of the same name registered
+    /// before the run, including a built-in one, is restored.
+    ///
+    /// `name` must be the name the UDF was created with, and a lowercase
+    /// identifier accepted by
+    /// [`SqlSecurity::validate_function_name`](crate::security::SqlSecurity::validate_function_name).
+    /// Otherwise [`try_build`](Self::try_build) and every run fail with
+    /// [`TermError::InvalidConfiguration`]. Custom SQL expressions calling a function
+    /// that is neither built in nor registered fail with
+    /// [`TermError::SecurityError`].
+    ///
+    /// The `udfs` feature provides UDFs for common validation rules in
+    /// `term_guard::udfs`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    ///
+    /// use datafusion::arrow::array::{ArrayRef, BooleanArray, StringArray};
+    /// use datafusion::arrow::datatypes::DataType;
+    /// use datafusion::logical_expr::{create_udf, ColumnarValue, Volatility};
+    /// use term_guard::core::{Check, ValidationSuite};
+    ///
+    /// // True for SKUs of two letters and four digits
+    /// let valid_sku = create_udf(
+    ///     "valid_sku",
+    ///     vec![DataType::Utf8],
+    ///     DataType::Boolean,
+    ///     Volatility::Immutable,
+    ///     Arc::new(|args: &[ColumnarValue]| {
+    ///         let arrays = ColumnarValue::values_to_arrays(args)?;
+    ///         let skus = arrays[0].as_any().downcast_ref::<StringArray>().unwrap();
+    ///         let valid: BooleanArray = skus
+    ///             .iter()
+    ///             .map(|sku| {
+    ///                 sku.map(|sku| sku.len() == 6 && sku[2..].bytes().all(|b| b.is_ascii_digit()))
+    ///             })
+    ///             .collect();
+    ///         Ok(ColumnarValue::Array(Arc::new(valid) as ArrayRef))
+    ///     }),
+    /// );
+    ///
+    /// let suite = ValidationSuite::builder("catalog")
+    ///     .with_udf("valid_sku", valid_sku)
+    ///     .check(
+    ///         Check::builder("skus")
+    ///             .satisfies("valid_sku(sku)", Some("SKUs are two letters and four digits"))
+    ///             .build(),
+    ///     )
+    ///     .try_build()
+    ///     .unwrap();
+    /// assert_eq!(suite.udf_names(), ["valid_sku"]);
+    /// ```
+    pub fn with_udf(mut self, name: impl Into<String>, udf: ScalarUDF) -> Self {
+        self.udfs.push(SuiteUdf::new(name.into(), udf));
+        self
+    }
+
+    /// Registers each scalar UDF under its own name, see
+    /// [`with_udf`](Self::with_udf).
+    pub fn with_udfs<I>(mut self, udfs: I) -> Self
+    where
+        I: IntoIterator<Item = ScalarUDF>,
+    {
+        for udf in udfs {
+            self = self.with_udf(udf.name().to_string(), udf);
+        }
+        self
+    }
+
+    /// Embeds a reference table in the suite.
+    ///
+    /// Each run registers the rows as an in-memory table named `name` before any
+    /// source is registered, so that
+    /// [`ContainmentConstraint::in_table`](crate::constraints::ContainmentConstraint::in_table),
+    /// foreign keys and custom SQL can read it without any external file. The
+    /// table is removed when the run finishes; a table of the same name
+    /// registered before the run is restored. Every row holds one value per
+    /// field of `schema`.
+    ///
+    /// The rows are part of the suite definition, see [`LookupTable`]. If they
+    /// do not fit the schema, the name is not a lowercase identifier or is
+    /// taken by the validated table or a source, or the table encodes to more
+    /// than [`max_lookup_bytes`](Self::max_lookup_bytes), then
+    /// [`try_build`](Self::try_build) and every run fail with
+    /// [`TermError::InvalidConfiguration`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    ///
+    /// use arrow::datatypes::{DataType, Field, Schema};
+    /// use datafusion::scalar::ScalarValue;
+    /// use term_guard::constraints::ContainmentConstraint;
+    /// use term_guard::core::{Check, ValidationSuite};
+    ///
+    /// let schema = Arc::new(Schema::new(vec![Field::new("plan", DataType::Utf8, false)]));
+    /// let suite = ValidationSuite::builder("accounts")
+    ///     .with_lookup(
+    ///         "valid_plans",
+    ///         vec![
+    ///             vec![ScalarValue::from("free")],
+    ///             vec![ScalarValue::from("pro")],
+    ///         ],
+    ///         schema,
+    ///     )
+    ///     .check(
+    ///         Check::builder("plans")
+    ///             .constraint(ContainmentConstraint::in_table("plan", "valid_plans", "plan"))
+    ///             .build(),
+    ///     )
+    ///     .try_build()
+    ///     .unwrap();
+    /// assert_eq!(suite.lookups()[0].num_rows(), 2);
+    /// ```
+    pub fn with_lookup(
+        mut self,
+        name: impl Into<String>,
+        rows: Vec<Vec<ScalarValue>>,
+        schema: SchemaRef,
+    ) -> Self {
+        self.lookups
+            .push(SuiteLookup::from_rows(name.into(), rows, schema));
+        self
+    }
+
+    /// Embeds a lookup table built or deserialized beforehand, see
+    /// [`with_lookup`](Self::with_lookup).
+    pub fn with_lookup_table(mut self, table: LookupTable) -> Self {
+        self.lookups.push(SuiteLookup::new(table));
+        self
+    }
+
+    /// Sets the limit on the size of each lookup table's Arrow IPC encoding.
+    ///
+    /// Defaults to [`DEFAULT_MAX_LOOKUP_BYTES`]. Reference data larger than a
+    /// few megabytes is better attached as a [`source`](Self::source).
+    pub fn max_lookup_bytes(mut self, bytes: usize) -> Self {
+        self.max_lookup_bytes = bytes;
+        self
+    }
+
+    /// Probes the attached sources before any constraint runs.
+    ///
+    /// When enabled, a run first calls [`DataSource::probe`] on every source
+    /// attached with [`source`](Self::source), concurrently. If any source
+    /// cannot be accessed or holds no data, the run fails with
+    /// [`TermError::PreflightFailed`] naming every unreachable source, before
+    /// a query is issued. Defaults to disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use term_guard::core::ValidationSuite;
+    /// use term_guard::sources::{CsvSource, ParquetSource};
+    ///
+    /// # fn example() -> term_guard::error::Result<()> {
+    /// let suite = ValidationSuite::builder("orders")
+    ///     .table_name("orders")
+    ///     .source("orders", ParquetSource::new("exports/orders.parquet")?)
+    ///     .source("customers", CsvSource::new("exports/customers.csv")?)
+    ///     .with_preflight(true)
+    ///     .build();
+    /// assert!(suite.preflight_enabled());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_preflight(mut self, enabled: bool) -> Self {
+        self.preflight = enabled;
+        self
+    }
+
+    /// Sets the order in which the checks run.
+    ///
+    /// Orders other than [`ExecutionOrder::BuilderOrder`] estimate each check
+    /// from the history set with [`execution_history`](Self::execution_history),
+    /// or from defaults without one, and record the order used in
+    /// [`ValidationReport::schedule`](super::ValidationReport::schedule).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::{ExecutionOrder, ValidationSuite};
+    ///
+    /// let suite = ValidationSuite::builder("orders")
+    ///     .execution_order(ExecutionOrder::FastestFirst)
+    ///     .build();
+    /// assert_eq!(suite.execution_order().name(), "fastest_first");
+    /// ```
+    pub fn execution_order(mut self, order: ExecutionOrder) -> Self {
+        self.scheduler.order = order;
+        self
+    }
+
+    /// Reads the durations and outcomes of previous runs from `repository`
+    /// to order the checks.
+    ///
+    /// Only reports of this suite saved with
+    /// [`ReportAggregator::save_report`](super::ReportAggregator::save_report)
+    /// are used. If the history cannot be loaded, the run uses default
+    /// estimates.
+    pub fn execution_history(mut self, repository: Arc<dyn MetricsRepository>) -> Self {
+        self.scheduler.repository = Some(repository);
+        self
+    }
+
+    /// Sets default values for the suite's named parameters.
+    ///
+    /// Defaults are typically loaded with [`ParameterBag::from_json_str`] from a
+    /// configuration file kept next to the suite. Values passed to
+    /// [`RunOptions::with_parameters`] take precedence.
+    pub fn parameter_defaults(mut self, defaults: ParameterBag) -> Self {
+        self.parameter_defaults = defaults;
+        self
+    }
+
+    /// Sets the default value of a single named parameter.
+    pub fn parameter_default(mut self, name: impl Into<String>, value: f64) -> Self {
+        self.parameter_defaults.set(name, value);
+        self
+    }
+
     /// Builds the `ValidationSuite` instance.
     ///
+    /// Checks with invalid builder arguments, such as an out-of-range threshold, or
+    /// sharing a name with another check make every run of the suite fail before any
+    /// query is issued. Use
+    /// [`try_build`](Self::try_build) to report them here instead.
+    ///
     /// # Returns
     ///
     /// The constructed `ValidationSuite`
     pub fn build(self) -> ValidationSuite {
+        let mut tags = self.tags;
+        if let Some(version) = &self.version {
+            tags.entry(SUITE_VERSION_TAG.to_string())
+                .or_insert_with(|| version.version.to_string());
+        }
         ValidationSuite {
             name: self.name,
             description: self.description,
@@ -654,10 +2678,166 @@ impl ValidationSuiteBuilder {
             telemetry: self.telemetry,
             use_optimizer: self.use_optimizer,
             table_name: self.table_name,
+            default_schema: self.default_schema,
+            result_cache: self.result_cache,
+            shared_stats: self.shared_stats,
+            parameter_defaults: self.parameter_defaults,
+            derived_table: self.derived_table,
+            column_mapping: self.column_mapping,
+            row_filters: self.row_filters,
+            tags: Arc::new(tags),
+            record_timeline: self.record_timeline,
+            empty_table_policy: self.empty_table_policy,
+            hooks: self.hooks,
+            redaction: self.redaction,
+            lineage: self.lineage.map(Arc::new),
+            sources: self.sources,
+            preflight: self.preflight,
+            scheduler: self.scheduler,
+            metadata_only: self.metadata_only,
+            udfs: self.udfs,
+            lookups: self.lookups,
+            max_lookup_bytes: self.max_lookup_bytes,
+            collection_limits: Arc::new(self.collection_limits),
+            version: self.version,
+        }
+    }
+
+    /// Builds the `ValidationSuite` instance, failing if any check has invalid
+    /// builder arguments or reuses the name of another check.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::InvalidConfiguration`] listing the check, constraint
+    /// and column of every invalid argument, see [`Check::configuration_issues`],
+    /// every check reusing the name of another, and every invalid UDF and lookup
+    /// table.
+    pub fn try_build(self) -> Result<ValidationSuite> {
+        let mut report = ConfigReport::new(format!("suite '{}'", self.name));
+        report.extend(check_issues(&self.checks));
+        report.extend(udf_issues(&self.udfs));
+        let reserved = reserved_table_names(&self.table_name, &self.sources);
+        report.extend(lookup_issues(
+            &self.lookups,
+            self.max_lookup_bytes,
+            &reserved,
+        ));
+        report.into_result()?;
+        Ok(self.build())
+    }
+}
+
+/// Returns the names lookup tables cannot take: the validated table and the
+/// tables of the attached sources.
+fn reserved_table_names<'a>(
+    table_name: &'a str,
+    sources: &'a [(String, Arc<dyn DataSource>)],
+) -> Vec<&'a str> {
+    std::iter::once(table_name)
+        .chain(sources.iter().map(|(name, _)| name.as_str()))
+        .collect()
+}
+
+/// Describes the mapped columns a constraint reads, such as
+/// `customer_id from cust_id`.
+fn mapped_columns(
+    constraint: &dyn Constraint,
+    column_mapping: &BTreeMap<String, String>,
+) -> Option<String> {
+    if column_mapping.is_empty() {
+        return None;
+    }
+    let mut columns = constraint.metadata().columns;
+    if let Some(column) = constraint.column() {
+        if !columns.iter().any(|name| name == column) {
+            columns.insert(0, column.to_string());
+        }
+    }
+    column_mapping::describe(&columns, column_mapping)
+}
+
+/// Returns the identifiers `lineage` records for the table and columns of
+/// `constraint`, under their logical and their mapped physical names.
+fn constraint_impacts(
+    lineage: &LineageMap,
+    table: &str,
+    constraint: &dyn Constraint,
+    column_mapping: &BTreeMap<String, String>,
+) -> Vec<String> {
+    let mut columns = constraint.metadata().columns;
+    columns.extend(constraint.column().map(str::to_string));
+    let physical: Vec<String> = columns
+        .iter()
+        .filter_map(|column| column_mapping.get(column).cloned())
+        .collect();
+    columns.extend(physical);
+    lineage.impacts(table, &columns)
+}
+
+/// Returns the minimum fraction of rows a constraint requires, if its metadata
+/// records a numeric threshold.
+fn threshold(constraint: &dyn Constraint) -> Option<f64> {
+    constraint
+        .metadata()
+        .custom
+        .get("threshold")
+        .and_then(|threshold| threshold.parse().ok())
+}
+
+/// Tables a run registered from the attached sources of its suite.
+#[derive(Debug, Default)]
+struct RegisteredSources {
+    /// Names of the registered tables
+    tables: Vec<String>,
+    /// Whether the source of the validated table applied the row filters
+    filtered: bool,
+}
+
+impl RegisteredSources {
+    /// Removes the registered tables from the session context.
+    fn deregister(&self, ctx: &SessionContext) {
+        for table in &self.tables {
+            if let Err(e) = ctx.deregister_table(table.as_str()) {
+                warn!(table.name = %table, error = %e, "Failed to deregister attached source");
+            }
         }
     }
 }
 
+/// Appends each of `items` unless `own` already has an item of the same name.
+fn extend_by_name<T>(own: &mut Vec<T>, items: impl IntoIterator<Item = T>, name: fn(&T) -> &str) {
+    for item in items {
+        if !own.iter().any(|existing| name(existing) == name(&item)) {
+            own.push(item);
+        }
+    }
+}
+
+/// Returns every configuration issue recorded by the checks' builders, and every
+/// check reusing the name of an earlier one, located under `checks[i]`.
+fn check_issues(checks: &[Arc<Check>]) -> impl Iterator<Item = ConfigIssue> + '_ {
+    let mut seen = BTreeSet::new();
+    checks.iter().enumerate().flat_map(move |(index, check)| {
+        let prefix = format!("checks[{index}]");
+        let duplicate = (!seen.insert(check.name())).then(|| {
+            ConfigIssue::error(
+                prefix.clone(),
+                format!(
+                    "Check name '{}' is used by more than one check of the suite",
+                    check.name()
+                ),
+            )
+            .in_check(check.name())
+        });
+        let issues: Vec<ConfigIssue> = check
+            .configuration_issues()
+            .iter()
+            .map(|issue| issue.clone().under(&prefix))
+            .collect();
+        duplicate.into_iter().chain(issues)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -681,6 +2861,232 @@ mod tests {
         assert!(!suite.telemetry_enabled()); // Telemetry is disabled by default (BYOT pattern)
     }
 
+    fn check_names(suite: &ValidationSuite) -> Vec<&str> {
+        suite.checks().iter().map(|check| check.name()).collect()
+    }
+
+    #[test]
+    fn test_merge_suites() {
+        let baseline = || {
+            ValidationSuite::builder("baseline")
+                .description("Applied to every table")
+                .tag("team", "platform")
+                .check(Check::builder("row_count").build())
+                .check(Check::builder("ids").build())
+                .build()
+        };
+        let orders = || {
+            ValidationSuite::builder("orders")
+                .description("Order checks")
+                .tag("team", "sales")
+                .tag("domain", "orders")
+                .check(Check::builder("ids").build())
+                .check(Check::builder("amounts").build())
+                .build()
+        };
+
+        let err = baseline()
+            .merge(orders(), CheckConflictPolicy::Error)
+            .unwrap_err();
+        let TermError::InvalidConfiguration(report) = err else {
+            panic!("expected an invalid configuration, got {err}");
+        };
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].path, "checks[2]");
+        assert_eq!(report.issues[0].check.as_deref(), Some("ids"));
+
+        let suite = baseline()
+            .merge(orders(), CheckConflictPolicy::Rename)
+            .unwrap();
+        assert_eq!(
+            check_names(&suite),
+            ["row_count", "ids", "orders.ids", "amounts"]
+        );
+        assert_eq!(
+            suite.description(),
+            Some("Applied to every table; Order checks")
+        );
+        assert_eq!(suite.tags()["team"], "platform");
+        assert_eq!(suite.tags()["domain"], "orders");
+
+        // A renamed check whose new name is taken gets a numeric suffix
+        let suite = suite.merge(orders(), CheckConflictPolicy::Rename).unwrap();
+        assert_eq!(
+            check_names(&suite),
+            [
+                "row_count",
+                "ids",
+                "orders.ids",
+                "amounts",
+                "orders.ids_2",
+                "orders.amounts"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extend_from_and_tweak_baseline() {
+        let baseline = ValidationSuite::builder("baseline")
+            .description("Applied to every table")
+            .tag("team", "platform")
+            .check(Check::builder("row_count").build())
+            .check(Check::builder("ids").build())
+            .check(Check::builder("freshness").build())
+            .build();
+
+        let suite = ValidationSuite::builder("orders")
+            .tag("team", "sales")
+            .extend_from(&baseline)
+            .check(Check::builder("amounts").build())
+            .build();
+        assert_eq!(
+            check_names(&suite),
+            ["row_count", "ids", "freshness", "amounts"]
+        );
+        assert_eq!(suite.description(), Some("Applied to every table"));
+        assert_eq!(suite.tags()["team"], "sales");
+
+        let suite = suite
+            .without_check("freshness")
+            .unwrap()
+            .with_check_replaced(
+                "ids",
+                Check::builder("order_ids").level(Level::Warning).build(),
+            )
+            .unwrap();
+        assert_eq!(check_names(&suite), ["row_count", "order_ids", "amounts"]);
+        assert_eq!(suite.checks()[1].level(), Level::Warning);
+
+        let err = suite.clone().without_check("missing").unwrap_err();
+        let TermError::InvalidConfiguration(report) = err else {
+            panic!("expected an invalid configuration, got {err}");
+        };
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].path, "missing");
+        assert_eq!(report.issues[0].check.as_deref(), Some("missing"));
+        assert!(report.issues[0]
+            .message
+            .contains("has no check named 'missing'"));
+
+        let err = suite
+            .with_check_replaced("row_count", Check::builder("amounts").build())
+            .unwrap_err();
+        let TermError::InvalidConfiguration(report) = err else {
+            panic!("expected an invalid configuration, got {err}");
+        };
+        assert_eq!(report.issues[0].path, "amounts");
+        assert!(report.issues[0]
+            .message
+            .contains("already has a check named 'amounts'"));
+    }
+
+    #[test]
+    fn test_extend_from_keeps_udfs_and_lookups_unique() {
+        use arrow::datatypes::{DataType, Field, Schema};
+        use datafusion::logical_expr::{create_udf, ColumnarValue, Volatility};
+
+        let identity = || {
+            create_udf(
+                "identity",
+                vec![DataType::Utf8],
+                DataType::Utf8,
+                Volatility::Immutable,
+                Arc::new(|args: &[ColumnarValue]| Ok(args[0].clone())),
+            )
+        };
+        let schema = Arc::new(Schema::new(vec![Field::new("plan", DataType::Utf8, false)]));
+        let plans = |plans: &[&str]| -> Vec<Vec<ScalarValue>> {
+            plans
+                .iter()
+                .map(|plan| vec![ScalarValue::from(*plan)])
+                .collect()
+        };
+
+        let baseline = ValidationSuite::builder("baseline")
+            .with_udf("identity", identity())
+            .with_lookup("plans", plans(&["free", "pro"]), schema.clone())
+            .build();
+        let suite = ValidationSuite::builder("orders")
+            .with_udf("identity", identity())
+            .with_lookup("plans", plans(&["free"]), schema)
+            .extend_from(&baseline)
+            .extend_from(&baseline)
+            .try_build()
+            .unwrap();
+        assert_eq!(suite.udf_names(), ["identity"]);
+        assert_eq!(suite.lookups().len(), 1);
+        // The builder's own lookup table is kept
+        assert_eq!(suite.lookups()[0].num_rows(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_check_names_are_unique() {
+        let checks = || {
+            vec![
+                Check::builder("ids").build(),
+                Check::builder("amounts").build(),
+                Check::builder("ids").build(),
+            ]
+        };
+
+        let err = ValidationSuite::builder("test_suite")
+            .checks(checks())
+            .try_build()
+            .unwrap_err();
+        let TermError::InvalidConfiguration(report) = err else {
+            panic!("expected an invalid configuration, got {err}");
+        };
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].path, "checks[2]");
+        assert!(report.issues[0]
+            .message
+            .contains("Check name 'ids' is used by more than one check"));
+
+        // Suites built without validation fail to run
+        let suite = ValidationSuite::builder("test_suite")
+            .checks(checks())
+            .build();
+        let err = suite.run(&SessionContext::new()).await.unwrap_err();
+        assert!(matches!(err, TermError::InvalidConfiguration(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_threshold_fails_before_run() {
+        use crate::core::ConstraintOptions;
+
+        let checks = || {
+            vec![
+                Check::builder("contact")
+                    .completeness("email", ConstraintOptions::new().with_threshold(95.0))
+                    .build(),
+                Check::builder("ids")
+                    .validates_uniqueness(vec!["id"], 1.0)
+                    .build(),
+            ]
+        };
+
+        let err = ValidationSuite::builder("test_suite")
+            .checks(checks())
+            .try_build()
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Check 'contact', constraint completeness on column 'email'"),
+            "{err}"
+        );
+        assert!(err.contains("from_percent(95)"), "{err}");
+
+        let suite = ValidationSuite::builder("test_suite")
+            .checks(checks())
+            .build();
+        let err = suite.run(&SessionContext::new()).await.unwrap_err();
+        let TermError::InvalidConfiguration(report) = err else {
+            panic!("expected an invalid configuration, got {err}");
+        };
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].path, "checks[0].constraints[0]");
+    }
+
     #[cfg(feature = "telemetry")]
     #[test]
     fn test_validation_suite_with_telemetry() {
@@ -691,6 +3097,251 @@ mod tests {
         assert!(suite.telemetry_enabled());
     }
 
+    #[tokio::test]
+    async fn test_validation_suite_records_scan_metrics() {
+        use arrow::array::Int64Array;
+        use arrow::datatypes::{DataType, Field, Schema};
+        use arrow::record_batch::RecordBatch;
+        use parquet::arrow::ArrowWriter;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.parquet");
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, true)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int64Array::from_iter_values(0..100))],
+        )
+        .unwrap();
+        let mut writer =
+            ArrowWriter::try_new(std::fs::File::create(&path).unwrap(), schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let ctx = SessionContext::new();
+        ctx.register_parquet(
+            "data",
+            path.to_str().unwrap(),
+            ParquetReadOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        let suite = ValidationSuite::builder("scan_metrics")
+            .check(
+                Check::builder("id_checks")
+                    .has_mean("id", crate::constraints::Assertion::Equals(49.5))
+                    .build(),
+            )
+            .build();
+        let result = suite.run(&ctx).await.unwrap();
+        let performance = &result.report().metrics.performance;
+
+        assert_eq!(performance.queries_executed, 1);
+        assert_eq!(performance.total_rows_scanned, 100);
+        assert!(performance.total_bytes_scanned > 0);
+        assert!(performance.checks.contains_key("id_checks"));
+
+        let json = result.to_json().unwrap();
+        assert!(json.contains("total_rows_scanned"));
+    }
+
+    #[tokio::test]
+    async fn test_validation_suite_timeline() {
+        use crate::constraints::Assertion;
+        use crate::core::ConstraintOptions;
+
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE orders (id INT) AS VALUES (1), (2), (3)")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+
+        let builder = || {
+            ValidationSuite::builder("timeline")
+                .derived_table("data", "SELECT id FROM orders WHERE id > 1")
+                .with_optimizer(true)
+                .check(
+                    Check::builder("volume")
+                        .has_size(Assertion::Equals(2.0))
+                        .completeness("id", ConstraintOptions::new().with_threshold(1.0))
+                        .build(),
+                )
+                .check(
+                    Check::builder("keys")
+                        .validates_uniqueness(vec!["id"], 1.0)
+                        .build(),
+                )
+        };
+
+        let result = builder().build().run(&ctx).await.unwrap();
+        assert!(result.report().timeline().is_none());
+
+        let result = builder()
+            .with_timeline(true)
+            .build()
+            .run(&ctx)
+            .await
+            .unwrap();
+        let timeline = result.report().timeline().unwrap();
+        let phases: Vec<_> = timeline.spans().iter().map(|span| span.phase).collect();
+        assert_eq!(
+            phases,
+            [
+                TimelinePhase::Run,
+                TimelinePhase::SourceRegistration,
+                TimelinePhase::OptimizerAnalysis,
+                TimelinePhase::Check,
+                TimelinePhase::Constraint,
+                TimelinePhase::Constraint,
+                TimelinePhase::Check,
+                TimelinePhase::Constraint,
+                TimelinePhase::ReportAssembly,
+            ]
+        );
+
+        // Constraints nest in their check, checks in the run
+        let volume: Vec<_> = timeline.children(3).map(|span| span.depth).collect();
+        assert_eq!(volume, [2, 2]);
+        assert_eq!(timeline.children(0).count(), 5);
+        for span in &timeline.spans()[1..] {
+            let parent = &timeline.spans()[span.parent.unwrap()];
+            assert!(span.start_us >= parent.start_us && span.end_us() <= parent.end_us());
+        }
+        assert_eq!(timeline.spans()[1].name, "data");
+        assert_eq!(timeline.spans()[6].name, "keys");
+    }
+
+    #[tokio::test]
+    async fn test_preview_runs_on_sample() {
+        use crate::constraints::Assertion;
+        use crate::core::{ConstraintOptions, PreviewSpec};
+
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE TABLE orders (id INT, email VARCHAR) AS VALUES (1, 'a'), (2, 'b'), (3, NULL)",
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+        let suite = ValidationSuite::builder("preview")
+            .derived_table("data", "SELECT * FROM orders ORDER BY id")
+            .check(
+                Check::builder("volume")
+                    .has_size(Assertion::Equals(3.0))
+                    .build(),
+            )
+            .check(
+                Check::builder("contact")
+                    .completeness("email", ConstraintOptions::new().with_threshold(1.0))
+                    .completeness("phone", ConstraintOptions::new().with_threshold(1.0))
+                    .build(),
+            )
+            .build();
+
+        let result = suite
+            .run_with(&ctx, &RunOptions::new().with_preview(PreviewSpec::new(2)))
+            .await
+            .unwrap();
+        let report = result.report();
+        let preview = report.preview.as_ref().unwrap();
+        assert_eq!(preview.limit, 2);
+        assert_eq!(preview.sampled_rows["data"], 2);
+        assert_eq!(
+            preview.skipped_constraints,
+            [PreviewSkippedConstraint {
+                check_name: "volume".to_string(),
+                constraint_name: "size".to_string(),
+            }]
+        );
+        // `volume` only holds the size constraint skipped in previews; the
+        // misconfigured column fails `contact`
+        assert_eq!(report.metrics.skipped_checks, 1);
+        assert_eq!(report.metrics.passed_checks, 0);
+        assert_eq!(report.metrics.failed_checks, 1);
+        // The misconfigured column is reported like in a full run
+        assert_eq!(report.issues.len(), 1);
+        assert!(report.issues[0].message.contains("phone"));
+
+        // The caller's context is left as it was
+        assert!(!ctx.table_exist("data").unwrap());
+        let rows = ctx.table("orders").await.unwrap().count().await.unwrap();
+        assert_eq!(rows, 3);
+
+        let full = suite.run(&ctx).await.unwrap();
+        assert!(full.report().preview.is_none());
+        assert_eq!(full.report().metrics.skipped_checks, 0);
+    }
+
+    #[tokio::test]
+    async fn test_issue_ids_survive_check_rename() {
+        use crate::core::ConstraintOptions;
+
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE data (id INT, email VARCHAR) AS VALUES (1, 'a'), (2, NULL)")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+
+        let run = |check_name: &'static str| {
+            let ctx = ctx.clone();
+            async move {
+                ValidationSuite::builder("ids")
+                    .check(
+                        Check::builder(check_name)
+                            .id("contacts.email.v1")
+                            .completeness("email", ConstraintOptions::new().with_threshold(1.0))
+                            .build(),
+                    )
+                    .check(
+                        Check::builder("unnamed")
+                            .completeness("email", ConstraintOptions::new().with_threshold(0.9))
+                            .build(),
+                    )
+                    .build()
+                    .run(&ctx)
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let before = run("Email completeness").await;
+        let after = run("Contact email must be present").await;
+
+        let before = before.report().issues_by_id();
+        let after = after.report().issues_by_id();
+        assert_eq!(
+            before.keys().collect::<Vec<_>>(),
+            after.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(before["contacts.email.v1"].len(), 1);
+        assert_eq!(
+            after["contacts.email.v1"][0].check_name,
+            "Contact email must be present"
+        );
+
+        // Without an explicit id, issues route by the derived constraint id
+        let unnamed = after
+            .values()
+            .flatten()
+            .find(|issue| issue.check_name == "unnamed")
+            .unwrap();
+        assert_eq!(unnamed.check_id, None);
+        let constraint_id = unnamed.constraint_id.as_deref().unwrap();
+        assert!(constraint_id.starts_with("completeness-"));
+        assert!(after.contains_key(constraint_id));
+        assert_ne!(
+            after["contacts.email.v1"][0].constraint_id.as_deref(),
+            Some(constraint_id)
+        );
+    }
+
     #[test]
     fn test_validation_suite_with_optimizer() {
         let suite = ValidationSuite::builder("test_suite")
@@ -707,4 +3358,243 @@ mod tests {
         let suite_default = ValidationSuite::builder("test_suite").build();
         assert!(!suite_default.optimizer_enabled());
     }
+
+    #[tokio::test]
+    async fn test_dimension_summary() {
+        use crate::constraints::Assertion;
+        use crate::core::{ConstraintOptions, QualityDimension};
+
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE data (id INT) AS VALUES (1), (2), (2), (NULL)")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+
+        let suite = ValidationSuite::builder("dimensions")
+            .check(
+                Check::builder("ids")
+                    .has_size(Assertion::Equals(4.0))
+                    .completeness("id", ConstraintOptions::new().with_threshold(0.5))
+                    .validates_uniqueness(vec!["id"], 1.0)
+                    .build(),
+            )
+            .check(
+                Check::builder("ranges")
+                    .dimension(QualityDimension::Accuracy)
+                    .has_min("id", Assertion::GreaterThanOrEqual(1.0))
+                    .build(),
+            )
+            .build();
+
+        let result = suite.run(&ctx).await.unwrap();
+        let summary: Vec<_> = result
+            .report()
+            .dimension_summary()
+            .into_iter()
+            .map(|d| (d.dimension, d.constraints, d.issues, d.pass_rate))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (QualityDimension::Completeness, 1, 0, 100.0),
+                (QualityDimension::Uniqueness, 1, 1, 0.0),
+                (QualityDimension::Accuracy, 1, 0, 100.0),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_report_carries_descriptions() {
+        use crate::constraints::Assertion;
+
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE data (id INT) AS VALUES (1), (2)")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+
+        let suite = ValidationSuite::builder("described")
+            .description("Identifiers of the nightly export")
+            .check(
+                Check::builder("size")
+                    .description("The export is never empty")
+                    .metadata("owner", "data-platform")
+                    .has_size(Assertion::GreaterThan(0.0))
+                    .build(),
+            )
+            .check(
+                Check::builder("undocumented")
+                    .has_size(Assertion::LessThan(10.0))
+                    .build(),
+            )
+            .build();
+
+        let result = suite.run(&ctx).await.unwrap();
+        let report = result.report();
+        assert_eq!(
+            report.description.as_deref(),
+            Some("Identifiers of the nightly export")
+        );
+        assert_eq!(
+            report.check_description("size"),
+            Some("The export is never empty")
+        );
+        assert_eq!(report.check_description("undocumented"), None);
+        assert_eq!(report.check_descriptions.len(), 1);
+        assert_eq!(report.check_metadata["size"]["owner"], "data-platform");
+        assert_eq!(report.check_tags("undocumented").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shared_stats_across_suites() {
+        use crate::constraints::{Assertion, CompletenessConstraint};
+
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE data (id INT, email VARCHAR) AS VALUES (1, 'a'), (2, NULL)")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+
+        let stats = SharedStatsCache::new();
+        let suite = |name: &str| {
+            ValidationSuite::builder(name)
+                .check(
+                    Check::builder("basics")
+                        .level(Level::Error)
+                        .has_size(Assertion::Equals(2.0))
+                        .constraint(CompletenessConstraint::with_threshold("email", 0.5))
+                        .build(),
+                )
+                .with_shared_stats(stats.clone())
+                .build()
+        };
+
+        let first = suite("team_a").run(&ctx).await.unwrap();
+        assert_eq!(first.report().metrics.performance.metric_cache_misses, 2);
+        let second = suite("team_b").run(&ctx).await.unwrap();
+        assert_eq!(second.report().metrics.performance.metric_cache_misses, 0);
+        assert_eq!(second.report().metrics.performance.metric_cache_hits, 3);
+
+        let metrics = stats.metrics();
+        assert_eq!((metrics.hits, metrics.misses), (2, 2));
+
+        // Re-registering the table starts over
+        ctx.deregister_table("data").unwrap();
+        ctx.sql("CREATE TABLE data (id INT, email VARCHAR) AS VALUES (1, 'a')")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        let third = suite("team_c").run(&ctx).await.unwrap();
+        assert!(third.is_failure());
+        let metrics = stats.metrics();
+        assert_eq!(metrics.misses, 4);
+        // The entries of the dropped registration were released
+        assert_eq!(metrics.stale_evictions, 2);
+        assert_eq!(metrics.entries, 2);
+    }
+
+    #[tokio::test]
+    async fn test_hooks_skip_and_observe_constraints() {
+        use crate::constraints::{Assertion, CompletenessConstraint};
+        use crate::core::HookDecision;
+        use async_trait::async_trait;
+        use std::sync::Mutex;
+
+        struct Panicking;
+
+        #[async_trait]
+        impl ValidationHook for Panicking {
+            async fn before_constraint(&self, _info: &ConstraintInfo) -> HookDecision {
+                panic!("audit service unavailable");
+            }
+
+            async fn after_constraint(&self, _info: &ConstraintInfo, _result: &ConstraintResult) {
+                panic!("audit service unavailable");
+            }
+        }
+
+        struct SkipColumn(&'static str);
+
+        #[async_trait]
+        impl ValidationHook for SkipColumn {
+            fn name(&self) -> &str {
+                "skip_column"
+            }
+
+            async fn before_constraint(&self, info: &ConstraintInfo) -> HookDecision {
+                if info.column.as_deref() == Some(self.0) {
+                    HookDecision::Skip {
+                        reason: format!("column '{}' is under review", self.0),
+                    }
+                } else {
+                    HookDecision::Proceed
+                }
+            }
+        }
+
+        #[derive(Default)]
+        struct Recorder(Mutex<Vec<(String, ConstraintStatus)>>);
+
+        #[async_trait]
+        impl ValidationHook for Arc<Recorder> {
+            async fn after_constraint(&self, info: &ConstraintInfo, result: &ConstraintResult) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((info.constraint_type.clone(), result.status));
+            }
+        }
+
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE data (id INT, email VARCHAR) AS VALUES (1, 'a'), (2, NULL)")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+
+        let recorder = Arc::new(Recorder::default());
+        let suite = ValidationSuite::builder("hooked")
+            .check(
+                Check::builder("basics")
+                    .has_size(Assertion::Equals(2.0))
+                    .constraint(CompletenessConstraint::complete("email"))
+                    .build(),
+            )
+            .with_hook(Panicking)
+            .with_hook(SkipColumn("email"))
+            .with_hook(Arc::clone(&recorder))
+            .build();
+
+        let result = suite.run(&ctx).await.unwrap();
+        assert!(result.is_success());
+        let report = result.report();
+        // A skipped constraint does not keep its check from passing
+        assert_eq!(report.metrics.total_checks, 1);
+        assert_eq!(report.metrics.passed_checks, 1);
+        assert_eq!(report.metrics.skipped_checks, 0);
+        assert_eq!(report.hook_skipped.len(), 1);
+        assert_eq!(report.hook_skipped[0].hook, "skip_column");
+        assert_eq!(
+            report.hook_skipped[0].reason,
+            "column 'email' is under review"
+        );
+
+        let observed = recorder.0.lock().unwrap().clone();
+        assert_eq!(
+            observed,
+            [
+                ("size".to_string(), ConstraintStatus::Success),
+                ("completeness".to_string(), ConstraintStatus::Skipped),
+            ]
+        );
+    }
 }
@@ -0,0 +1,962 @@
+//! Versions of suite definitions and the changes between them.
+//!
+//! Audits ask when a definition changed and who approved it: when did the
+//! threshold on email completeness go from 0.95 to 0.99? A [`SuiteVersion`]
+//! attached to a suite names the version of its definition and carries a
+//! journal of [`ChangeEntry`]s. The version is stamped into every
+//! [`ValidationReport`](super::ValidationReport) of the suite, onto the
+//! telemetry metrics of its runs, and under [`SUITE_VERSION_TAG`] onto the
+//! metrics constraints save to a [`MetricsRepository`](crate::repository::MetricsRepository),
+//! so historical results can be traced back to the definition that produced them.
+//!
+//! [`diff_suites`] compares two definitions structurally, matching constraints
+//! by their [`constraint_id`], and [`SuiteDiff::to_markdown`] renders the
+//! result for review tickets.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use term_guard::core::{
+//!     diff_suites, ChangeEntry, Check, ConstraintOptions, SuiteVersion, ValidationSuite,
+//! };
+//!
+//! # fn example() -> term_guard::prelude::Result<()> {
+//! let before = ValidationSuite::builder("customers")
+//!     .version(SuiteVersion::parse("1.2.0")?)
+//!     .check(
+//!         Check::builder("emails")
+//!             .completeness("email", ConstraintOptions::new().with_threshold(0.95))
+//!             .build(),
+//!     )
+//!     .build();
+//!
+//! let after = ValidationSuite::builder("customers")
+//!     .version(SuiteVersion::parse("1.3.0")?.change(
+//!         ChangeEntry::new(
+//!             "1.3.0".parse()?,
+//!             NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+//!             "jane",
+//!             "Require every email",
+//!         )
+//!         .approved_by("data-governance"),
+//!     ))
+//!     .check(
+//!         Check::builder("emails")
+//!             .completeness("email", ConstraintOptions::new().with_threshold(0.99))
+//!             .build(),
+//!     )
+//!     .build();
+//!
+//! let diff = diff_suites(&before, &after);
+//! assert_eq!(diff.constraints.len(), 1);
+//! assert!(diff.to_markdown().contains("approved by data-governance"));
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::{constraint_id, current_validation_context, Check, ValidationSuite};
+use crate::prelude::*;
+use crate::repository::ResultKey;
+
+/// Tag holding the suite version on metrics saved during a run of a versioned suite.
+pub const SUITE_VERSION_TAG: &str = "suite_version";
+
+/// A semantic version, `MAJOR.MINOR.PATCH` with optional pre-release and build
+/// identifiers, as specified by <https://semver.org>.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::core::SemanticVersion;
+///
+/// let version: SemanticVersion = "2.1.0-rc.1".parse().unwrap();
+/// assert_eq!((version.major, version.minor, version.patch), (2, 1, 0));
+/// assert_eq!(version.pre_release.as_deref(), Some("rc.1"));
+/// assert!("2.1".parse::<SemanticVersion>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SemanticVersion {
+    /// Incremented for incompatible changes
+    pub major: u64,
+    /// Incremented for backward compatible additions
+    pub minor: u64,
+    /// Incremented for backward compatible fixes
+    pub patch: u64,
+    /// Pre-release identifiers, such as `rc.1`
+    pub pre_release: Option<String>,
+    /// Build metadata, such as a commit hash
+    pub build: Option<String>,
+}
+
+impl SemanticVersion {
+    /// Creates the version `major.minor.patch`.
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre_release: None,
+            build: None,
+        }
+    }
+}
+
+impl FromStr for SemanticVersion {
+    type Err = TermError;
+
+    fn from_str(version: &str) -> Result<Self> {
+        let invalid = || {
+            TermError::Configuration(format!(
+                "Invalid semantic version '{version}': expected MAJOR.MINOR.PATCH with optional -pre-release and +build identifiers"
+            ))
+        };
+        let (rest, build) = match version.split_once('+') {
+            Some((rest, build)) => (rest, Some(build)),
+            None => (version, None),
+        };
+        let (core, pre_release) = match rest.split_once('-') {
+            Some((core, pre_release)) => (core, Some(pre_release)),
+            None => (rest, None),
+        };
+
+        let numbers = core
+            .split('.')
+            .map(|part| {
+                let digits = !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+                // Leading zeros are not allowed
+                if !digits || (part.len() > 1 && part.starts_with('0')) {
+                    return Err(invalid());
+                }
+                part.parse::<u64>().map_err(|_| invalid())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let [major, minor, patch] = numbers[..] else {
+            return Err(invalid());
+        };
+
+        let identifiers = |value: Option<&str>| -> Result<Option<String>> {
+            match value {
+                None => Ok(None),
+                Some(value) => {
+                    let valid = value.split('.').all(|identifier| {
+                        !identifier.is_empty()
+                            && identifier
+                                .bytes()
+                                .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+                    });
+                    if valid {
+                        Ok(Some(value.to_string()))
+                    } else {
+                        Err(invalid())
+                    }
+                }
+            }
+        };
+
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            pre_release: identifiers(pre_release)?,
+            build: identifiers(build)?,
+        })
+    }
+}
+
+impl TryFrom<String> for SemanticVersion {
+    type Error = TermError;
+
+    fn try_from(version: String) -> Result<Self> {
+        version.parse()
+    }
+}
+
+impl From<SemanticVersion> for String {
+    fn from(version: SemanticVersion) -> Self {
+        version.to_string()
+    }
+}
+
+impl fmt::Display for SemanticVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre_release) = &self.pre_release {
+            write!(f, "-{pre_release}")?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{build}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An entry of the change journal of a suite.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeEntry {
+    /// The version introducing the change
+    pub version: SemanticVersion,
+    /// When the change was made
+    pub date: NaiveDate,
+    /// Who made the change
+    pub author: String,
+    /// Who approved the change, if it was approved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved_by: Option<String>,
+    /// What changed and why
+    pub summary: String,
+}
+
+impl ChangeEntry {
+    /// Creates an entry for a change made by `author` on `date`.
+    pub fn new(
+        version: SemanticVersion,
+        date: NaiveDate,
+        author: impl Into<String>,
+        summary: impl Into<String>,
+    ) -> Self {
+        Self {
+            version,
+            date,
+            author: author.into(),
+            approved_by: None,
+            summary: summary.into(),
+        }
+    }
+
+    /// Records who approved the change.
+    pub fn approved_by(mut self, approver: impl Into<String>) -> Self {
+        self.approved_by = Some(approver.into());
+        self
+    }
+}
+
+impl fmt::Display for ChangeEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}, {}", self.version, self.date, self.author)?;
+        if let Some(approver) = &self.approved_by {
+            write!(f, ", approved by {approver}")?;
+        }
+        write!(f, "): {}", self.summary)
+    }
+}
+
+/// The version of a suite definition with its change journal, see
+/// [`ValidationSuiteBuilder::version`](super::ValidationSuiteBuilder::version).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuiteVersion {
+    /// The version of the definition
+    pub version: SemanticVersion,
+    /// The changes that led to this version, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changelog: Vec<ChangeEntry>,
+}
+
+impl SuiteVersion {
+    /// Creates a version without journal entries.
+    pub fn new(version: SemanticVersion) -> Self {
+        Self {
+            version,
+            changelog: Vec::new(),
+        }
+    }
+
+    /// Parses the version from a string such as `"1.3.0"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if `version` is not a semantic version.
+    pub fn parse(version: &str) -> Result<Self> {
+        Ok(Self::new(version.parse()?))
+    }
+
+    /// Appends an entry to the change journal.
+    pub fn change(mut self, entry: ChangeEntry) -> Self {
+        self.changelog.push(entry);
+        self
+    }
+}
+
+/// Tags `key` with the version of the suite running the current constraint, if
+/// it has one.
+pub(crate) fn stamp_suite_version(key: ResultKey) -> ResultKey {
+    match current_validation_context().tag(SUITE_VERSION_TAG) {
+        Some(version) => key.with_tag(SUITE_VERSION_TAG, version),
+        None => key,
+    }
+}
+
+/// How an element of a suite definition changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffKind {
+    /// Only in the new definition
+    Added,
+    /// Only in the old definition
+    Removed,
+    /// In both definitions, with different settings
+    Modified,
+}
+
+impl fmt::Display for DiffKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DiffKind::Added => "added",
+            DiffKind::Removed => "removed",
+            DiffKind::Modified => "modified",
+        })
+    }
+}
+
+/// A setting that differs between two definitions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParameterChange {
+    /// The name of the setting, such as `threshold`
+    pub name: String,
+    /// The value in the old definition, if it had the setting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// The value in the new definition, if it has the setting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+impl fmt::Display for ParameterChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = |value: &Option<String>| value.clone().unwrap_or_else(|| "unset".to_string());
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.name,
+            value(&self.before),
+            value(&self.after)
+        )
+    }
+}
+
+/// A check added, removed or modified between two definitions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckDiff {
+    /// How the check changed
+    pub kind: DiffKind,
+    /// The name of the check, as of the latest definition that has it
+    pub check_name: String,
+    /// The changed settings of a modified check, such as its level
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<ParameterChange>,
+}
+
+/// A constraint added, removed or modified between two definitions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstraintDiff {
+    /// How the constraint changed
+    pub kind: DiffKind,
+    /// The name of the check holding the constraint, as of the latest definition
+    /// that has it
+    pub check_name: String,
+    /// The name of the constraint
+    pub constraint_name: String,
+    /// The stable identifier of the constraint in the old definition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_id: Option<String>,
+    /// The stable identifier of the constraint in the new definition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_id: Option<String>,
+    /// What the constraint required in the old definition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_description: Option<String>,
+    /// What the constraint requires in the new definition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_description: Option<String>,
+    /// The parameters of a modified constraint that changed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ParameterChange>,
+}
+
+/// The structural changes between two suite definitions, see [`diff_suites`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuiteDiff {
+    /// The name of the new suite
+    pub suite_name: String,
+    /// The version of the old definition, if it had one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_version: Option<SemanticVersion>,
+    /// The version of the new definition, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_version: Option<SemanticVersion>,
+    /// Checks added, removed or modified
+    pub checks: Vec<CheckDiff>,
+    /// Constraints added, removed or modified, including those of added and
+    /// removed checks
+    pub constraints: Vec<ConstraintDiff>,
+    /// Changed suite settings: the validated table, row filters and parameter
+    /// defaults
+    pub parameters: Vec<ParameterChange>,
+    /// Journal entries of the new definition missing from the old one
+    pub changelog: Vec<ChangeEntry>,
+}
+
+impl SuiteDiff {
+    /// Returns true if the definitions are structurally the same.
+    ///
+    /// New journal entries alone do not count as changes.
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty() && self.constraints.is_empty() && self.parameters.is_empty()
+    }
+
+    /// Formats the changes as Markdown for review tickets.
+    pub fn to_markdown(&self) -> String {
+        let version = |version: &Option<SemanticVersion>| {
+            version
+                .as_ref()
+                .map_or_else(|| "unversioned".to_string(), ToString::to_string)
+        };
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "## Suite `{}`: {} -> {}",
+            self.suite_name,
+            version(&self.previous_version),
+            version(&self.current_version)
+        );
+        if self.is_empty() {
+            let _ = writeln!(out, "\nNo structural changes.");
+        }
+
+        if !self.checks.is_empty() {
+            let _ = writeln!(out, "\n### Checks\n");
+            for check in &self.checks {
+                let _ = write!(out, "- {} `{}`", capitalized(check.kind), check.check_name);
+                write_parameters(&mut out, &check.changes);
+            }
+        }
+
+        if !self.constraints.is_empty() {
+            let _ = writeln!(out, "\n### Constraints\n");
+            for constraint in &self.constraints {
+                let _ = write!(
+                    out,
+                    "- {} `{}` / `{}`",
+                    capitalized(constraint.kind),
+                    constraint.check_name,
+                    constraint.constraint_name
+                );
+                write_parameters(&mut out, &constraint.parameters);
+                match (
+                    &constraint.previous_description,
+                    &constraint.current_description,
+                ) {
+                    (Some(before), Some(after)) if before != after => {
+                        let _ = writeln!(out, "  - Before: {before}");
+                        let _ = writeln!(out, "  - After: {after}");
+                    }
+                    (_, Some(description)) | (Some(description), None) => {
+                        let _ = writeln!(out, "  - {description}");
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+
+        if !self.parameters.is_empty() {
+            let _ = writeln!(out, "\n### Suite settings\n");
+            for parameter in &self.parameters {
+                let _ = writeln!(out, "- {}", code_parameter(parameter));
+            }
+        }
+
+        if !self.changelog.is_empty() {
+            let _ = writeln!(out, "\n### Changelog\n");
+            for entry in &self.changelog {
+                let _ = writeln!(out, "- {entry}");
+            }
+        }
+        out
+    }
+}
+
+fn capitalized(kind: DiffKind) -> &'static str {
+    match kind {
+        DiffKind::Added => "Added",
+        DiffKind::Removed => "Removed",
+        DiffKind::Modified => "Modified",
+    }
+}
+
+fn code_parameter(parameter: &ParameterChange) -> String {
+    let value = |value: &Option<String>| {
+        value
+            .as_ref()
+            .map_or_else(|| "unset".to_string(), |v| format!("`{v}`"))
+    };
+    format!(
+        "{}: {} -> {}",
+        parameter.name,
+        value(&parameter.before),
+        value(&parameter.after)
+    )
+}
+
+/// Ends a Markdown list item with its changed parameters, if any.
+fn write_parameters(out: &mut String, parameters: &[ParameterChange]) {
+    if !parameters.is_empty() {
+        let parameters: Vec<String> = parameters.iter().map(code_parameter).collect();
+        let _ = write!(out, ": {}", parameters.join(", "));
+    }
+    let _ = writeln!(out);
+}
+
+/// A constraint of a check, as far as diffs are concerned.
+struct Entry {
+    name: String,
+    id: String,
+    column: Option<String>,
+    description: String,
+    parameters: BTreeMap<String, String>,
+}
+
+impl Entry {
+    fn of(check: &Check) -> Vec<Self> {
+        check
+            .constraints()
+            .iter()
+            .enumerate()
+            .map(|(index, constraint)| {
+                let metadata = constraint.metadata();
+                let mut parameters: BTreeMap<String, String> =
+                    metadata.custom.into_iter().collect();
+                if !metadata.columns.is_empty() {
+                    parameters.insert("columns".to_string(), metadata.columns.join(", "));
+                }
+                Entry {
+                    name: check.constraint_label(index).to_string(),
+                    id: constraint_id(constraint.as_ref()),
+                    column: constraint.column().map(str::to_string),
+                    description: constraint.describe(),
+                    parameters,
+                }
+            })
+            .collect()
+    }
+
+    fn diff(&self, kind: DiffKind, check_name: &str) -> ConstraintDiff {
+        let (previous, current) = match kind {
+            DiffKind::Removed => (Some(self), None),
+            _ => (None, Some(self)),
+        };
+        ConstraintDiff {
+            kind,
+            check_name: check_name.to_string(),
+            constraint_name: self.name.clone(),
+            previous_id: previous.map(|e| e.id.clone()),
+            current_id: current.map(|e| e.id.clone()),
+            previous_description: previous.map(|e| e.description.clone()),
+            current_description: current.map(|e| e.description.clone()),
+            parameters: Vec::new(),
+        }
+    }
+}
+
+/// Lists the keys whose values differ between two maps.
+fn changed_parameters(
+    before: &BTreeMap<String, String>,
+    after: &BTreeMap<String, String>,
+) -> Vec<ParameterChange> {
+    let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter(|name| before.get(*name) != after.get(*name))
+        .map(|name| ParameterChange {
+            name: name.clone(),
+            before: before.get(name).cloned(),
+            after: after.get(name).cloned(),
+        })
+        .collect()
+}
+
+/// Compares the constraints of a check present in both definitions.
+///
+/// Constraints with the same identifier are unchanged. The remaining ones are
+/// paired by name and column, in order, as modifications; the rest were added
+/// or removed.
+fn diff_constraints(old: &Check, new: &Check, diffs: &mut Vec<ConstraintDiff>) {
+    let before = Entry::of(old);
+    let after = Entry::of(new);
+    let mut taken = vec![false; before.len()];
+    let mut matched = vec![None; after.len()];
+
+    for (index, entry) in after.iter().enumerate() {
+        if let Some(found) = (0..before.len()).find(|&i| !taken[i] && before[i].id == entry.id) {
+            taken[found] = true;
+            matched[index] = Some(found);
+        }
+    }
+    for (index, entry) in after.iter().enumerate() {
+        if matched[index].is_some() {
+            continue;
+        }
+        let found = (0..before.len()).find(|&i| {
+            !taken[i] && before[i].name == entry.name && before[i].column == entry.column
+        });
+        let Some(found) = found else {
+            diffs.push(entry.diff(DiffKind::Added, new.name()));
+            continue;
+        };
+        taken[found] = true;
+        let previous = &before[found];
+        diffs.push(ConstraintDiff {
+            kind: DiffKind::Modified,
+            check_name: new.name().to_string(),
+            constraint_name: entry.name.clone(),
+            previous_id: Some(previous.id.clone()),
+            current_id: Some(entry.id.clone()),
+            previous_description: Some(previous.description.clone()),
+            current_description: Some(entry.description.clone()),
+            parameters: changed_parameters(&previous.parameters, &entry.parameters),
+        });
+    }
+    for (entry, _) in before.iter().zip(&taken).filter(|(_, taken)| !**taken) {
+        diffs.push(entry.diff(DiffKind::Removed, new.name()));
+    }
+}
+
+/// The settings of a check compared by diffs.
+fn check_settings(check: &Check) -> BTreeMap<String, String> {
+    let mut settings = BTreeMap::from([
+        ("name".to_string(), check.name().to_string()),
+        ("level".to_string(), check.level().to_string()),
+    ]);
+    if let Some(description) = check.description() {
+        settings.insert("description".to_string(), description.to_string());
+    }
+    settings
+}
+
+/// The settings of a suite compared by diffs.
+fn suite_settings(suite: &ValidationSuite) -> BTreeMap<String, String> {
+    let mut settings = BTreeMap::from([("table".to_string(), suite.table_name().to_string())]);
+    if !suite.row_filters().is_empty() {
+        settings.insert("row_filters".to_string(), suite.row_filters().join(" AND "));
+    }
+    for (name, value) in suite.parameter_defaults().iter() {
+        settings.insert(format!("parameter.{name}"), value.to_string());
+    }
+    for lookup in suite.lookups() {
+        settings.insert(
+            format!("lookup.{}", lookup.name()),
+            lookup.fingerprint().to_string(),
+        );
+    }
+    settings
+}
+
+/// Compares two suite definitions structurally.
+///
+/// Checks are matched by their [`id`](Check::id), or by name when they have
+/// none. Constraints of matched checks are matched by their [`constraint_id`],
+/// which changes with any of their parameters; a constraint whose identifier
+/// changed is reported as modified when a constraint of the same name on the same
+/// column existed before, with the parameters from its
+/// [`metadata`](super::Constraint::metadata) that differ.
+///
+/// The journal entries of `new` that `old` does not have are listed as the
+/// changelog of the diff.
+pub fn diff_suites(old: &ValidationSuite, new: &ValidationSuite) -> SuiteDiff {
+    let key = |check: &Check| check.id().unwrap_or(check.name()).to_string();
+    let mut checks = Vec::new();
+    let mut constraints = Vec::new();
+
+    for check in new.checks() {
+        let previous = old.checks().iter().find(|c| key(c) == key(check));
+        let Some(previous) = previous else {
+            checks.push(CheckDiff {
+                kind: DiffKind::Added,
+                check_name: check.name().to_string(),
+                changes: Vec::new(),
+            });
+            for entry in Entry::of(check) {
+                constraints.push(entry.diff(DiffKind::Added, check.name()));
+            }
+            continue;
+        };
+        let changes = changed_parameters(&check_settings(previous), &check_settings(check));
+        if !changes.is_empty() {
+            checks.push(CheckDiff {
+                kind: DiffKind::Modified,
+                check_name: check.name().to_string(),
+                changes,
+            });
+        }
+        diff_constraints(previous, check, &mut constraints);
+    }
+    for check in old.checks() {
+        if new.checks().iter().any(|c| key(c) == key(check)) {
+            continue;
+        }
+        checks.push(CheckDiff {
+            kind: DiffKind::Removed,
+            check_name: check.name().to_string(),
+            changes: Vec::new(),
+        });
+        for entry in Entry::of(check) {
+            constraints.push(entry.diff(DiffKind::Removed, check.name()));
+        }
+    }
+
+    let previous_changelog = old.version().map_or(&[][..], |v| &v.changelog[..]);
+    let changelog = new
+        .version()
+        .map(|v| {
+            v.changelog
+                .iter()
+                .filter(|entry| !previous_changelog.contains(entry))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    SuiteDiff {
+        suite_name: new.name().to_string(),
+        previous_version: old.version().map(|v| v.version.clone()),
+        current_version: new.version().map(|v| v.version.clone()),
+        checks,
+        constraints,
+        parameters: changed_parameters(&suite_settings(old), &suite_settings(new)),
+        changelog,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ConstraintOptions, Level, ValidationContext, CURRENT_CONTEXT};
+    use std::sync::Arc;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    #[test]
+    fn test_semantic_versions() {
+        let version: SemanticVersion = "1.10.0-alpha.1+build.5".parse().unwrap();
+        assert_eq!((version.major, version.minor, version.patch), (1, 10, 0));
+        assert_eq!(version.pre_release.as_deref(), Some("alpha.1"));
+        assert_eq!(version.build.as_deref(), Some("build.5"));
+        assert_eq!(version.to_string(), "1.10.0-alpha.1+build.5");
+        assert_eq!(
+            serde_json::to_string(&version).unwrap(),
+            "\"1.10.0-alpha.1+build.5\""
+        );
+
+        for invalid in [
+            "1.2",
+            "1.2.3.4",
+            "01.2.3",
+            "1.2.x",
+            "1.2.3-",
+            "1.2.3+a..b",
+            "",
+        ] {
+            assert!(
+                invalid.parse::<SemanticVersion>().is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+        assert!(serde_json::from_str::<SemanticVersion>("\"v1.0.0\"").is_err());
+    }
+
+    #[test]
+    fn test_threshold_change_is_a_modification() {
+        let before = ValidationSuite::builder("customers")
+            .version(SuiteVersion::parse("1.2.0").unwrap())
+            .check(
+                Check::builder("emails")
+                    .completeness("email", ConstraintOptions::new().with_threshold(0.95))
+                    .validates_uniqueness(["id"], 1.0)
+                    .build(),
+            )
+            .build();
+        let after = ValidationSuite::builder("customers")
+            .version(
+                SuiteVersion::parse("1.3.0").unwrap().change(
+                    ChangeEntry::new(
+                        "1.3.0".parse().unwrap(),
+                        date(2),
+                        "jane",
+                        "Raise email completeness",
+                    )
+                    .approved_by("bob"),
+                ),
+            )
+            .check(
+                Check::builder("emails")
+                    .level(Level::Error)
+                    .completeness("email", ConstraintOptions::new().with_threshold(0.99))
+                    .validates_uniqueness(["id"], 1.0)
+                    .build(),
+            )
+            .build();
+
+        let diff = diff_suites(&before, &after);
+        assert_eq!(diff.previous_version.unwrap().to_string(), "1.2.0");
+        assert_eq!(diff.checks.len(), 1);
+        assert_eq!(diff.checks[0].kind, DiffKind::Modified);
+        assert_eq!(diff.checks[0].changes[0].name, "level");
+
+        assert_eq!(diff.constraints.len(), 1);
+        let constraint = &diff.constraints[0];
+        assert_eq!(constraint.kind, DiffKind::Modified);
+        assert_eq!(constraint.constraint_name, "completeness");
+        assert_ne!(constraint.previous_id, constraint.current_id);
+        assert_eq!(
+            constraint.parameters,
+            vec![ParameterChange {
+                name: "threshold".to_string(),
+                before: Some("0.95".to_string()),
+                after: Some("0.99".to_string()),
+            }]
+        );
+        assert_eq!(diff.changelog.len(), 1);
+        assert!(diff.parameters.is_empty());
+    }
+
+    #[test]
+    fn test_added_and_removed_checks_and_settings() {
+        let before = ValidationSuite::builder("orders")
+            .parameter_default("max_nulls", 0.1)
+            .check(
+                Check::builder("legacy")
+                    .validates_email("contact", 1.0)
+                    .build(),
+            )
+            .check(
+                Check::builder("ids")
+                    .validates_uniqueness(["id"], 1.0)
+                    .build(),
+            )
+            .build();
+        let after = ValidationSuite::builder("orders")
+            .table_name("orders_v2")
+            .check(
+                Check::builder("ids")
+                    .validates_uniqueness(["id"], 1.0)
+                    .build(),
+            )
+            .check(
+                Check::builder("amounts")
+                    .validates_uuid("order_id", 1.0)
+                    .build(),
+            )
+            .build();
+
+        let diff = diff_suites(&before, &after);
+        let checks: Vec<(DiffKind, &str)> = diff
+            .checks
+            .iter()
+            .map(|c| (c.kind, c.check_name.as_str()))
+            .collect();
+        assert_eq!(
+            checks,
+            [(DiffKind::Added, "amounts"), (DiffKind::Removed, "legacy")]
+        );
+        let constraints: Vec<(DiffKind, &str)> = diff
+            .constraints
+            .iter()
+            .map(|c| (c.kind, c.check_name.as_str()))
+            .collect();
+        assert_eq!(
+            constraints,
+            [(DiffKind::Added, "amounts"), (DiffKind::Removed, "legacy")]
+        );
+        let settings: Vec<String> = diff.parameters.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            settings,
+            [
+                "parameter.max_nulls: 0.1 -> unset",
+                "table: data -> orders_v2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_identical_suites_have_no_changes() {
+        let suite = || {
+            ValidationSuite::builder("orders")
+                .check(
+                    Check::builder("ids")
+                        .validates_uniqueness(["id"], 1.0)
+                        .build(),
+                )
+                .build()
+        };
+        let diff = diff_suites(&suite(), &suite());
+        assert!(diff.is_empty());
+        assert!(diff.to_markdown().contains("No structural changes."));
+    }
+
+    #[test]
+    fn test_markdown() {
+        let before = ValidationSuite::builder("customers")
+            .version(SuiteVersion::parse("1.2.0").unwrap())
+            .check(
+                Check::builder("emails")
+                    .completeness("email", ConstraintOptions::new().with_threshold(0.95))
+                    .build(),
+            )
+            .build();
+        let after = ValidationSuite::builder("customers")
+            .version(
+                SuiteVersion::parse("1.3.0").unwrap().change(
+                    ChangeEntry::new("1.3.0".parse().unwrap(), date(2), "jane", "Stricter")
+                        .approved_by("bob"),
+                ),
+            )
+            .check(
+                Check::builder("emails")
+                    .completeness("email", ConstraintOptions::new().with_threshold(0.99))
+                    .build(),
+            )
+            .build();
+
+        let markdown = diff_suites(&before, &after).to_markdown();
+        assert!(
+            markdown.starts_with("## Suite `customers`: 1.2.0 -> 1.3.0\n"),
+            "{markdown}"
+        );
+        assert!(
+            markdown
+                .contains("- Modified `emails` / `completeness`: threshold: `0.95` -> `0.99`\n"),
+            "{markdown}"
+        );
+        assert!(
+            markdown.contains("- 1.3.0 (2026-03-02, jane, approved by bob): Stricter\n"),
+            "{markdown}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_saved_metrics_are_stamped_with_the_suite_version() {
+        let tags = BTreeMap::from([(SUITE_VERSION_TAG.to_string(), "1.3.0".to_string())]);
+        let key = CURRENT_CONTEXT
+            .scope(
+                ValidationContext::new("data").with_tags(Arc::new(tags)),
+                async { stamp_suite_version(ResultKey::new(0)) },
+            )
+            .await;
+        assert_eq!(key.get_tag(SUITE_VERSION_TAG), Some("1.3.0"));
+
+        let key = stamp_suite_version(ResultKey::new(0));
+        assert_eq!(key.get_tag(SUITE_VERSION_TAG), None);
+    }
+}
@@ -0,0 +1,496 @@
+//! Typed thresholds for ratio-based constraints.
+//!
+//! Most constraints pass when at least a given fraction of rows satisfies them.
+//! [`Threshold`] holds such a fraction and guarantees it lies within `0.0..=1.0`.
+//! Builder methods accept anything implementing [`IntoThreshold`], so a value
+//! mis-scaled as a percentage (`95` instead of `0.95`) becomes a configuration
+//! error reported by [`CheckBuilder::try_build`](super::CheckBuilder::try_build)
+//! and before a suite runs, rather than a panic while the check is built.
+//!
+//! On small tables a single bad row moves the ratio a long way: one NULL among
+//! 50 rows is 2%. A [`ThresholdAdjustment`] relaxes a ratio threshold for such
+//! samples, and results checked against an adjusted threshold report both the
+//! nominal and the effective threshold.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{describe_percent, ConstraintResult};
+use crate::error::{Result, TermError};
+
+/// A fraction between 0.0 and 1.0.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::core::Threshold;
+///
+/// assert_eq!(Threshold::new(0.95).unwrap().value(), 0.95);
+/// assert_eq!(Threshold::from_percent(95.0).unwrap().value(), 0.95);
+///
+/// let err = Threshold::new(95.0).unwrap_err();
+/// assert!(err.to_string().contains("Threshold::from_percent(95)"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Threshold(f64);
+
+impl Threshold {
+    /// Creates a threshold from a fraction.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if `value` is not between 0.0 and 1.0.
+    /// Values between 1 and 100 are likely percentages, and the message suggests
+    /// [`Threshold::from_percent`].
+    pub fn new(value: f64) -> Result<Self> {
+        if (0.0..=1.0).contains(&value) {
+            return Ok(Self(value));
+        }
+
+        let mut message = format!("Threshold must be between 0.0 and 1.0, got {value}");
+        if value > 1.0 && value <= 100.0 {
+            message.push_str(&format!(
+                "; use Threshold::from_percent({value}) if this is a percentage"
+            ));
+        }
+        Err(TermError::Configuration(message))
+    }
+
+    /// Creates a threshold from a percentage between 0 and 100.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if `percent` is not between 0 and 100.
+    pub fn from_percent(percent: f64) -> Result<Self> {
+        if !(0.0..=100.0).contains(&percent) {
+            return Err(TermError::Configuration(format!(
+                "Threshold percentage must be between 0 and 100, got {percent}"
+            )));
+        }
+        Ok(Self(percent / 100.0))
+    }
+
+    /// Returns the fraction.
+    pub fn value(self) -> f64 {
+        self.0
+    }
+}
+
+impl TryFrom<f64> for Threshold {
+    type Error = TermError;
+
+    fn try_from(value: f64) -> Result<Self> {
+        Self::new(value)
+    }
+}
+
+impl From<Threshold> for f64 {
+    fn from(threshold: Threshold) -> Self {
+        threshold.0
+    }
+}
+
+impl fmt::Display for Threshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Conversion into a [`Threshold`], accepted by builder methods.
+///
+/// Implemented for every `TryInto<Threshold>` whose error converts into
+/// [`TermError`], which covers `f64` and `Threshold` itself.
+pub trait IntoThreshold {
+    /// Converts the value, failing if it is out of range.
+    fn into_threshold(self) -> Result<Threshold>;
+}
+
+impl<T> IntoThreshold for T
+where
+    T: TryInto<Threshold>,
+    T::Error: Into<TermError>,
+{
+    fn into_threshold(self) -> Result<Threshold> {
+        self.try_into().map_err(Into::into)
+    }
+}
+
+/// Small-sample leniency for a ratio threshold.
+///
+/// Ratio constraints pass when the share of passing rows reaches their
+/// threshold. An adjustment lowers that threshold by how much a sample of the
+/// table's size can be trusted, so a handful of bad rows fails a large table
+/// but not a tiny one. The threshold a ratio is actually compared with is the
+/// *effective* threshold; it never exceeds the configured one.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::core::ThresholdAdjustment;
+///
+/// let wilson = ThresholdAdjustment::WilsonLowerBound { confidence: 0.95 };
+/// // One bad row in 50 passes a 99% threshold, two do not
+/// assert_eq!(wilson.effective_threshold(0.99, 50), 0.98);
+/// // Large samples are held to the configured threshold
+/// assert!(wilson.effective_threshold(0.99, 1_000_000) > 0.989);
+///
+/// let floor = ThresholdAdjustment::MinFailingRows(3);
+/// assert_eq!(floor.effective_threshold(1.0, 10), 0.8);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThresholdAdjustment {
+    /// Fails only when the data is significantly worse than the threshold.
+    ///
+    /// The constraint passes unless the lower bound of the Wilson score
+    /// interval for the share of violating rows, at `confidence` (such as
+    /// 0.95), exceeds the share the threshold allows. A threshold of 1.0
+    /// allows no violations, so it is not relaxed.
+    WilsonLowerBound {
+        /// Confidence level of the interval, strictly between 0.0 and 1.0
+        confidence: f64,
+    },
+    /// Fails only when at least this many rows violate the constraint.
+    ///
+    /// Ratios reaching the threshold still pass whatever the number of
+    /// violating rows.
+    MinFailingRows(u64),
+}
+
+impl ThresholdAdjustment {
+    /// Returns the name of the adjustment, such as `"wilson_lower_bound"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::WilsonLowerBound { .. } => "wilson_lower_bound",
+            Self::MinFailingRows(_) => "min_failing_rows",
+        }
+    }
+
+    /// Checks the adjustment's parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if a Wilson confidence is not
+    /// strictly between 0.0 and 1.0.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::WilsonLowerBound { confidence } if !(*confidence > 0.0 && *confidence < 1.0) => {
+                Err(TermError::Configuration(format!(
+                    "Wilson lower bound confidence must be between 0.0 and 1.0 (exclusive), got {confidence}"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the lowest ratio that passes `threshold` over `rows` rows.
+    ///
+    /// The result lies between 0.0 and `threshold`. Without rows there is no
+    /// sample to adjust for, and `threshold` is returned unchanged.
+    pub fn effective_threshold(&self, threshold: f64, rows: u64) -> f64 {
+        if rows == 0 {
+            return threshold;
+        }
+        let allowed_failures = match *self {
+            Self::WilsonLowerBound { confidence } => {
+                let z = normal_quantile(1.0 - (1.0 - confidence) / 2.0);
+                wilson_allowed_failures(threshold, rows, z)
+            }
+            Self::MinFailingRows(min) => min.saturating_sub(1).min(rows),
+        };
+        threshold.min((rows - allowed_failures) as f64 / rows as f64)
+    }
+}
+
+impl fmt::Display for ThresholdAdjustment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WilsonLowerBound { confidence } => write!(
+                f,
+                "Wilson lower bound at {} confidence",
+                describe_percent(*confidence)
+            ),
+            Self::MinFailingRows(1) => write!(f, "minimum of 1 failing row"),
+            Self::MinFailingRows(min) => write!(f, "minimum of {min} failing rows"),
+        }
+    }
+}
+
+/// Returns the most violating rows out of `rows` whose Wilson lower bound stays
+/// within the share `threshold` allows.
+fn wilson_allowed_failures(threshold: f64, rows: u64, z: f64) -> u64 {
+    let allowed_share = 1.0 - threshold;
+    let n = rows as f64;
+    let lower_bound = |failures: u64| {
+        let p = failures as f64 / n;
+        let z2 = z * z;
+        let center = p + z2 / (2.0 * n);
+        let margin = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+        (center - margin) / (1.0 + z2 / n)
+    };
+
+    // The bound grows with the number of failures and is 0.0 without any
+    let (mut passing, mut failing) = (0, rows + 1);
+    while failing - passing > 1 {
+        let middle = passing + (failing - passing) / 2;
+        if lower_bound(middle) <= allowed_share + f64::EPSILON {
+            passing = middle;
+        } else {
+            failing = middle;
+        }
+    }
+    passing
+}
+
+/// Returns the standard normal quantile of `p`, using Acklam's approximation.
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    }
+}
+
+/// A ratio threshold together with the adjustment applied to it for one sample.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AdjustedThreshold {
+    nominal: f64,
+    effective: f64,
+    adjustment: Option<ThresholdAdjustment>,
+}
+
+impl AdjustedThreshold {
+    /// Applies `adjustment`, if any, to `threshold` for a sample of `rows` rows.
+    pub(crate) fn new(
+        threshold: f64,
+        adjustment: Option<ThresholdAdjustment>,
+        rows: u64,
+    ) -> Result<Self> {
+        let effective = match &adjustment {
+            Some(adjustment) => {
+                adjustment.validate()?;
+                adjustment.effective_threshold(threshold, rows)
+            }
+            None => threshold,
+        };
+        Ok(Self {
+            nominal: threshold,
+            effective,
+            adjustment,
+        })
+    }
+
+    /// Returns the threshold ratios are compared with.
+    pub(crate) fn effective(&self) -> f64 {
+        self.effective
+    }
+
+    /// Returns true if `ratio` reaches the effective threshold.
+    pub(crate) fn passes(&self, ratio: f64) -> bool {
+        ratio >= self.effective
+    }
+
+    /// Records the adjustment in the details and message of `result`.
+    ///
+    /// Failures and passes below the nominal threshold explain the adjustment
+    /// in their message; every adjusted result carries both thresholds in its
+    /// details.
+    pub(crate) fn annotate(&self, result: ConstraintResult, ratio: f64) -> ConstraintResult {
+        let Some(adjustment) = self.adjustment else {
+            return result;
+        };
+        let mut result = result
+            .with_detail("nominal_threshold", self.nominal)
+            .with_detail("effective_threshold", self.effective);
+        result = match adjustment {
+            ThresholdAdjustment::WilsonLowerBound { confidence } => {
+                result.with_detail("wilson_confidence", confidence)
+            }
+            ThresholdAdjustment::MinFailingRows(min) => {
+                result.with_detail("min_failing_rows", min as f64)
+            }
+        };
+
+        let note = format!(
+            "effective threshold {:.3} after the {adjustment} adjustment of threshold {:.3}",
+            self.effective, self.nominal
+        );
+        result.message = match result.message.take() {
+            Some(message) => Some(format!("{message} ({note})")),
+            None if ratio < self.nominal => Some(format!(
+                "Ratio {ratio:.3} is below threshold {:.3} but reaches the {note}",
+                self.nominal
+            )),
+            None => None,
+        };
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_accepts_fractions() {
+        assert_eq!(Threshold::new(0.0).unwrap().value(), 0.0);
+        assert_eq!(Threshold::new(1.0).unwrap().value(), 1.0);
+        assert_eq!(f64::from(Threshold::new(0.25).unwrap()), 0.25);
+    }
+
+    #[test]
+    fn test_new_rejects_out_of_range_values() {
+        let message = Threshold::new(-0.1).unwrap_err().to_string();
+        assert!(message.contains("Threshold must be between 0.0 and 1.0, got -0.1"));
+        assert!(!message.contains("from_percent"));
+
+        let message = Threshold::new(95.0).unwrap_err().to_string();
+        assert!(message.contains("got 95"));
+        assert!(message.contains("Threshold::from_percent(95)"));
+
+        let message = Threshold::new(250.0).unwrap_err().to_string();
+        assert!(!message.contains("from_percent"));
+
+        assert!(Threshold::new(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_from_percent() {
+        assert_eq!(Threshold::from_percent(95.0).unwrap().value(), 0.95);
+        assert_eq!(Threshold::from_percent(100.0).unwrap().value(), 1.0);
+        assert!(Threshold::from_percent(101.0).is_err());
+        assert!(Threshold::from_percent(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_into_threshold() {
+        assert_eq!(0.5_f64.into_threshold().unwrap().value(), 0.5);
+        assert_eq!(
+            Threshold::from_percent(50.0)
+                .unwrap()
+                .into_threshold()
+                .unwrap()
+                .value(),
+            0.5
+        );
+        assert!(1.5_f64.into_threshold().is_err());
+    }
+
+    #[test]
+    fn test_normal_quantile() {
+        assert!((normal_quantile(0.975) - 1.959_964).abs() < 1e-5);
+        assert!((normal_quantile(0.5)).abs() < 1e-9);
+        assert!((normal_quantile(0.005) + 2.575_829).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_wilson_lower_bound_relaxes_small_samples() {
+        let wilson = ThresholdAdjustment::WilsonLowerBound { confidence: 0.95 };
+        assert_eq!(wilson.effective_threshold(0.99, 50), 0.98);
+        assert_eq!(wilson.effective_threshold(0.9, 10), 0.8);
+        assert_eq!(wilson.effective_threshold(0.99, 0), 0.99);
+
+        let large = wilson.effective_threshold(0.99, 100_000);
+        assert!(large > 0.989 && large <= 0.99);
+
+        // No violations are allowed by a threshold of 1.0
+        assert_eq!(wilson.effective_threshold(1.0, 10), 1.0);
+    }
+
+    #[test]
+    fn test_min_failing_rows() {
+        let floor = ThresholdAdjustment::MinFailingRows(3);
+        assert_eq!(floor.effective_threshold(1.0, 10), 0.8);
+        assert_eq!(floor.effective_threshold(0.5, 10), 0.5);
+        assert_eq!(floor.effective_threshold(0.99, 2), 0.0);
+        assert_eq!(floor.effective_threshold(0.99, 10_000), 0.99);
+        assert_eq!(
+            ThresholdAdjustment::MinFailingRows(0).effective_threshold(0.9, 10),
+            0.9
+        );
+    }
+
+    #[test]
+    fn test_adjustment_validation_and_display() {
+        let wilson = ThresholdAdjustment::WilsonLowerBound { confidence: 0.95 };
+        assert!(wilson.validate().is_ok());
+        assert_eq!(wilson.to_string(), "Wilson lower bound at 95% confidence");
+        assert_eq!(
+            ThresholdAdjustment::MinFailingRows(2).to_string(),
+            "minimum of 2 failing rows"
+        );
+
+        for confidence in [0.0, 1.0, 95.0, f64::NAN] {
+            let error = ThresholdAdjustment::WilsonLowerBound { confidence }
+                .validate()
+                .unwrap_err();
+            assert!(matches!(error, TermError::Configuration(_)));
+        }
+    }
+
+    #[test]
+    fn test_annotate_lenient_pass() {
+        let adjusted = AdjustedThreshold::new(
+            0.99,
+            Some(ThresholdAdjustment::WilsonLowerBound { confidence: 0.95 }),
+            50,
+        )
+        .unwrap();
+        assert!(adjusted.passes(0.98));
+        assert!(!adjusted.passes(0.96));
+
+        let result = adjusted.annotate(ConstraintResult::success_with_metric(0.98), 0.98);
+        assert_eq!(result.details["nominal_threshold"], 0.99);
+        assert_eq!(result.details["effective_threshold"], 0.98);
+        assert_eq!(result.details["wilson_confidence"], 0.95);
+        let message = result.message.unwrap();
+        assert!(message.contains("below threshold 0.990"));
+        assert!(message.contains("Wilson lower bound at 95% confidence"));
+
+        let unadjusted = AdjustedThreshold::new(0.99, None, 50).unwrap();
+        let result = unadjusted.annotate(ConstraintResult::success_with_metric(1.0), 1.0);
+        assert!(result.details.is_empty());
+        assert!(result.message.is_none());
+    }
+}
@@ -0,0 +1,271 @@
+//! Built-in run timeline for environments without a tracing backend.
+//!
+//! When a suite is built with
+//! [`with_timeline(true)`](super::ValidationSuiteBuilder::with_timeline), each run
+//! records when its phases started and ended: source registration, optimizer
+//! analysis, every check and constraint, and report assembly. The spans are
+//! available from [`ValidationReport::timeline`](super::ValidationReport::timeline)
+//! and can be exported with [`Timeline::to_chrome_trace_json`] for viewing in
+//! `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+//!
+//! Without the option no timestamps are taken, so the cost of a disabled
+//! timeline is a branch per span.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::core::{Check, TimelinePhase, ValidationSuite};
+//! use term_guard::constraints::Assertion;
+//! use datafusion::prelude::*;
+//!
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! let ctx = SessionContext::new();
+//! ctx.sql("CREATE TABLE data (id INT) AS VALUES (1), (2)").await?.collect().await?;
+//!
+//! let suite = ValidationSuite::builder("orders")
+//!     .with_timeline(true)
+//!     .check(Check::builder("volume").has_size(Assertion::Equals(2.0)).build())
+//!     .build();
+//!
+//! let result = suite.run(&ctx).await?;
+//! let timeline = result.report().timeline().unwrap();
+//! assert_eq!(timeline.spans()[0].phase, TimelinePhase::Run);
+//!
+//! // Save as a .json file and open it in chrome://tracing or Perfetto
+//! let trace = timeline.to_chrome_trace_json()?;
+//! assert!(trace.contains("traceEvents"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// The part of a run a [`TimelineSpan`] measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelinePhase {
+    /// The whole run
+    Run,
+    /// Registering the validated table, such as a derived table
+    SourceRegistration,
+    /// Planning the execution of the suite's constraints
+    OptimizerAnalysis,
+    /// A check and the constraints it groups
+    Check,
+    /// Evaluating a single constraint
+    Constraint,
+    /// Storing cached results and computing the report metrics
+    ReportAssembly,
+}
+
+impl TimelinePhase {
+    /// Returns the phase name used as the event category in trace exports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Run => "run",
+            Self::SourceRegistration => "source_registration",
+            Self::OptimizerAnalysis => "optimizer_analysis",
+            Self::Check => "check",
+            Self::Constraint => "constraint",
+            Self::ReportAssembly => "report_assembly",
+        }
+    }
+}
+
+/// A timed phase of a run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineSpan {
+    /// Suite, table, check or constraint name, depending on the phase
+    pub name: String,
+    /// What the span measures
+    pub phase: TimelinePhase,
+    /// Start of the span in microseconds since the run started
+    pub start_us: u64,
+    /// Length of the span in microseconds
+    pub duration_us: u64,
+    /// Number of enclosing spans; the run span has depth 0
+    pub depth: usize,
+    /// Index of the enclosing span in [`Timeline::spans`]
+    pub parent: Option<usize>,
+}
+
+impl TimelineSpan {
+    /// Returns the end of the span in microseconds since the run started.
+    pub fn end_us(&self) -> u64 {
+        self.start_us + self.duration_us
+    }
+}
+
+/// The spans recorded during a run, ordered by start time.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timeline {
+    spans: Vec<TimelineSpan>,
+}
+
+impl Timeline {
+    /// Returns the recorded spans, parents before their children.
+    pub fn spans(&self) -> &[TimelineSpan] {
+        &self.spans
+    }
+
+    /// Returns the spans directly enclosed by the span at `index`.
+    pub fn children(&self, index: usize) -> impl Iterator<Item = &TimelineSpan> {
+        self.spans
+            .iter()
+            .filter(move |span| span.parent == Some(index))
+    }
+
+    /// Returns the spans of the given phase.
+    pub fn spans_of(&self, phase: TimelinePhase) -> impl Iterator<Item = &TimelineSpan> {
+        self.spans.iter().filter(move |span| span.phase == phase)
+    }
+
+    /// Exports the timeline in the Chrome trace event format.
+    ///
+    /// Every span becomes a complete (`"ph": "X"`) event on a single thread, so
+    /// viewers nest constraints under their checks by time.
+    pub fn to_chrome_trace_json(&self) -> Result<String> {
+        let events: Vec<serde_json::Value> = self
+            .spans
+            .iter()
+            .map(|span| {
+                serde_json::json!({
+                    "name": span.name,
+                    "cat": span.phase.as_str(),
+                    "ph": "X",
+                    "ts": span.start_us,
+                    "dur": span.duration_us,
+                    "pid": 1,
+                    "tid": 1,
+                    "args": { "depth": span.depth },
+                })
+            })
+            .collect();
+
+        serde_json::to_string(&serde_json::json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        }))
+        .map_err(|e| TermError::Serialization(format!("Failed to export timeline: {e}")))
+    }
+}
+
+/// Records spans during a run; does nothing unless enabled.
+#[derive(Debug)]
+pub(crate) struct TimelineRecorder {
+    origin: Option<Instant>,
+    spans: Vec<TimelineSpan>,
+    open: Vec<usize>,
+}
+
+impl TimelineRecorder {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            origin: enabled.then(Instant::now),
+            spans: Vec::new(),
+            open: Vec::new(),
+        }
+    }
+
+    /// Opens a span nested in the innermost open span.
+    pub(crate) fn enter(&mut self, phase: TimelinePhase, name: &str) -> Option<usize> {
+        let origin = self.origin?;
+        let index = self.spans.len();
+        self.spans.push(TimelineSpan {
+            name: name.to_string(),
+            phase,
+            start_us: origin.elapsed().as_micros() as u64,
+            duration_us: 0,
+            depth: self.open.len(),
+            parent: self.open.last().copied(),
+        });
+        self.open.push(index);
+        Some(index)
+    }
+
+    /// Closes a span opened by [`enter`](Self::enter), along with any spans still open inside it.
+    pub(crate) fn exit(&mut self, span: Option<usize>) {
+        let (Some(origin), Some(index)) = (self.origin, span) else {
+            return;
+        };
+        let now = origin.elapsed().as_micros() as u64;
+        while let Some(open) = self.open.pop() {
+            let span = &mut self.spans[open];
+            span.duration_us = now.saturating_sub(span.start_us);
+            if open == index {
+                break;
+            }
+        }
+    }
+
+    /// Closes all open spans and returns the timeline, if enabled.
+    pub(crate) fn finish(mut self) -> Option<Timeline> {
+        if let Some(&root) = self.open.first() {
+            self.exit(Some(root));
+        }
+        self.origin?;
+        Some(Timeline { spans: self.spans })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_recorder() {
+        let mut recorder = TimelineRecorder::new(false);
+        let span = recorder.enter(TimelinePhase::Run, "suite");
+        assert_eq!(span, None);
+        recorder.exit(span);
+        assert_eq!(recorder.finish(), None);
+    }
+
+    #[test]
+    fn test_nesting() {
+        let mut recorder = TimelineRecorder::new(true);
+        let run = recorder.enter(TimelinePhase::Run, "suite");
+        let check = recorder.enter(TimelinePhase::Check, "volume");
+        let constraint = recorder.enter(TimelinePhase::Constraint, "size");
+        recorder.exit(constraint);
+        // Leaves the check open; closing the run closes it too
+        recorder.enter(TimelinePhase::Constraint, "completeness");
+        recorder.exit(check);
+        let assembly = recorder.enter(TimelinePhase::ReportAssembly, "suite");
+        recorder.exit(assembly);
+        let timeline = recorder.finish().unwrap();
+
+        let spans = timeline.spans();
+        assert_eq!(spans.len(), 5);
+        assert_eq!(spans[0].depth, 0);
+        assert_eq!(spans[1].parent, run);
+        assert_eq!(spans[2].parent, check);
+        assert_eq!(spans[3].parent, check);
+        assert_eq!(spans[4].parent, run);
+        assert_eq!(timeline.children(1).count(), 2);
+        assert_eq!(timeline.spans_of(TimelinePhase::Constraint).count(), 2);
+        for span in &spans[1..] {
+            let parent = &spans[span.parent.unwrap()];
+            assert!(span.start_us >= parent.start_us && span.end_us() <= parent.end_us());
+        }
+    }
+
+    #[test]
+    fn test_chrome_trace_json() {
+        let mut recorder = TimelineRecorder::new(true);
+        let run = recorder.enter(TimelinePhase::Run, "suite");
+        recorder.enter(TimelinePhase::Check, "volume");
+        recorder.exit(run);
+        let json = recorder.finish().unwrap().to_chrome_trace_json().unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let events = value["traceEvents"].as_array().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1]["name"], "volume");
+        assert_eq!(events[1]["cat"], "check");
+        assert_eq!(events[1]["ph"], "X");
+        assert_eq!(events[1]["args"]["depth"], 1);
+    }
+}
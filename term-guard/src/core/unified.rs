@@ -3,7 +3,10 @@
 //! This module provides the foundation for the unified constraint API,
 //! including base traits, common options, and shared functionality.
 
-use super::{ColumnSpec, Constraint, ConstraintResult, LogicalOperator};
+use super::{
+    ColumnSpec, Constraint, ConstraintResult, IntoThreshold, LogicalOperator, MemoryStrategy,
+    QuarantineSink, ThresholdAdjustment,
+};
 use crate::core::current_validation_context;
 use crate::prelude::*;
 use async_trait::async_trait;
@@ -54,6 +57,10 @@ pub trait UnifiedConstraint: Constraint {
 
                 for column in &columns {
                     let result = self.evaluate_column(ctx, column).await?;
+                    // An empty table is not a per-column outcome to combine
+                    if result.is_empty_table() {
+                        return Ok(result);
+                    }
                     results.push((column.to_string(), result.status.is_success()));
                     if let Some(metric) = result.metric {
                         metrics.push(metric);
@@ -110,12 +117,18 @@ pub trait UnifiedConstraint: Constraint {
                         status: crate::core::ConstraintStatus::Success,
                         metric: combined_metric,
                         message,
+                        quarantine: None,
+                        constraint_id: None,
+                        details: Default::default(),
                     })
                 } else {
                     Ok(ConstraintResult {
                         status: crate::core::ConstraintStatus::Failure,
                         metric: combined_metric,
                         message,
+                        quarantine: None,
+                        constraint_id: None,
+                        details: Default::default(),
                     })
                 }
             }
@@ -133,10 +146,20 @@ pub struct ConstraintOptions {
     pub operator: Option<LogicalOperator>,
     /// Threshold value (interpretation depends on constraint type)
     pub threshold: Option<f64>,
+    /// Named parameter supplying the threshold at run time, overriding `threshold`
+    pub threshold_param: Option<String>,
+    /// Small-sample leniency applied to the threshold, for ratio constraints
+    pub threshold_adjustment: Option<ThresholdAdjustment>,
     /// Additional boolean flags
     pub flags: HashMap<String, bool>,
     /// Additional string options
     pub options: HashMap<String, String>,
+    /// Sink receiving violating rows, for row-level constraints
+    pub quarantine: Option<QuarantineSink>,
+    /// How the constraint bounds the memory of its query
+    pub memory_strategy: MemoryStrategy,
+    /// Why the threshold passed to `with_threshold` was rejected
+    threshold_error: Option<String>,
 }
 
 impl ConstraintOptions {
@@ -152,8 +175,39 @@ impl ConstraintOptions {
     }
 
     /// Sets the threshold value.
-    pub fn with_threshold(mut self, threshold: f64) -> Self {
-        self.threshold = Some(threshold);
+    ///
+    /// Accepts an `f64` fraction or a [`Threshold`](super::Threshold). An out-of-range
+    /// value is kept as an error and reported by [`validate`](Self::validate), which
+    /// [`CheckBuilder`](super::CheckBuilder) methods call before adding the constraint.
+    pub fn with_threshold(mut self, threshold: impl IntoThreshold) -> Self {
+        match threshold.into_threshold() {
+            Ok(threshold) => {
+                self.threshold = Some(threshold.value());
+                self.threshold_error = None;
+            }
+            Err(e) => {
+                self.threshold = None;
+                self.threshold_error = Some(configuration_message(e));
+            }
+        }
+        self
+    }
+
+    /// Reads the threshold from the named parameter when the suite runs.
+    ///
+    /// See [`ParameterBag`](super::ParameterBag) for how parameters are supplied.
+    pub fn with_threshold_param(mut self, name: impl Into<String>) -> Self {
+        self.threshold_param = Some(name.into());
+        self
+    }
+
+    /// Relaxes the threshold for small samples.
+    ///
+    /// Applies to ratio constraints such as completeness; see
+    /// [`ThresholdAdjustment`] for the available adjustments. An invalid
+    /// adjustment is reported by [`validate`](Self::validate).
+    pub fn with_threshold_adjustment(mut self, adjustment: ThresholdAdjustment) -> Self {
+        self.threshold_adjustment = Some(adjustment);
         self
     }
 
@@ -169,6 +223,26 @@ impl ConstraintOptions {
         self
     }
 
+    /// Exports every violating row to `sink` when the constraint is evaluated.
+    ///
+    /// Only row-level constraints accept a quarantine sink; see
+    /// [`QuarantineSink`] for details.
+    pub fn with_quarantine(mut self, sink: QuarantineSink) -> Self {
+        self.quarantine = Some(sink);
+        self
+    }
+
+    /// Bounds the memory the constraint's query holds, for uniqueness
+    /// constraints and histograms.
+    ///
+    /// See [`MemoryStrategy`] for the strategies each constraint family
+    /// supports and the accuracy of their results. An invalid strategy is
+    /// reported by [`validate`](Self::validate).
+    pub fn with_memory_strategy(mut self, strategy: MemoryStrategy) -> Self {
+        self.memory_strategy = strategy;
+        self
+    }
+
     /// Returns the operator or a default value.
     pub fn operator_or(&self, default: LogicalOperator) -> LogicalOperator {
         self.operator.unwrap_or(default)
@@ -188,6 +262,30 @@ impl ConstraintOptions {
     pub fn option(&self, name: &str) -> Option<&str> {
         self.options.get(name).map(|s| s.as_str())
     }
+
+    /// Checks the options, failing if an invalid threshold, threshold
+    /// adjustment or memory strategy was set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] describing the rejected setting.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(message) = &self.threshold_error {
+            return Err(TermError::Configuration(message.clone()));
+        }
+        if let Some(adjustment) = &self.threshold_adjustment {
+            adjustment.validate()?;
+        }
+        self.memory_strategy.validate()
+    }
+}
+
+/// Returns the message of a configuration error without its "Configuration error" prefix.
+pub(crate) fn configuration_message(error: TermError) -> String {
+    match error {
+        TermError::Configuration(message) => message,
+        other => other.to_string(),
+    }
 }
 
 /// Helper macro for implementing common constraint patterns.
@@ -314,6 +412,38 @@ mod tests {
         assert_eq!(options.option("unknown"), None);
     }
 
+    #[test]
+    fn test_threshold_adjustment_option() {
+        let options = ConstraintOptions::new()
+            .with_threshold(0.99)
+            .with_threshold_adjustment(ThresholdAdjustment::MinFailingRows(2));
+        assert_eq!(
+            options.threshold_adjustment,
+            Some(ThresholdAdjustment::MinFailingRows(2))
+        );
+        assert!(options.validate().is_ok());
+
+        let options = ConstraintOptions::new()
+            .with_threshold_adjustment(ThresholdAdjustment::WilsonLowerBound { confidence: 1.5 });
+        assert!(options.validate().is_err());
+    }
+
+    #[test]
+    fn test_memory_strategy_option() {
+        let options = ConstraintOptions::new();
+        assert!(options.memory_strategy.is_unbounded());
+
+        let options = options.with_memory_strategy(MemoryStrategy::hash_partitioned(32));
+        assert_eq!(
+            options.memory_strategy,
+            MemoryStrategy::HashPartitioned { partitions: 32 }
+        );
+        assert!(options.validate().is_ok());
+
+        let options = ConstraintOptions::new().with_memory_strategy(MemoryStrategy::sketched(0));
+        assert!(options.validate().is_err());
+    }
+
     #[test]
     fn test_column_spec_with_options() {
         let single = ColumnSpec::Single("user_id".to_string());
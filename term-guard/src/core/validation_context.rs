@@ -3,6 +3,12 @@
 //! This module provides a context object that can be used to pass runtime
 //! information (like table names) to constraints during evaluation.
 
+use super::{
+    CollectionLimits, Extensions, MetricCache, ParameterBag, QueryMetricsCollector,
+    RedactionPolicy, RegexCache,
+};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 /// Runtime context for validation operations.
@@ -14,6 +20,24 @@ use std::sync::Arc;
 pub struct ValidationContext {
     /// The name of the table being validated
     table_name: Arc<str>,
+    /// Schema prepended to unqualified table names in multi-table constraints
+    default_schema: Option<Arc<str>>,
+    /// Optional collector receiving execution metrics of queries run in this context
+    performance: Option<QueryMetricsCollector>,
+    /// Named parameters resolved by constraints at execution time
+    parameters: Option<Arc<ParameterBag>>,
+    /// Per-run dependencies passed to the suite
+    extensions: Option<Arc<Extensions>>,
+    /// Tags of the suite, such as environment or owning team
+    tags: Option<Arc<BTreeMap<String, String>>>,
+    /// Metrics shared between the constraints of a run
+    metric_cache: Option<MetricCache>,
+    /// Redaction of values quoted in results
+    redaction: Option<Arc<RedactionPolicy>>,
+    /// Regexes compiled by the constraints of a run
+    regex_cache: Option<RegexCache>,
+    /// Bounds on the result sets constraints collect
+    collection_limits: Arc<CollectionLimits>,
 }
 
 impl ValidationContext {
@@ -34,6 +58,15 @@ impl ValidationContext {
     pub fn new(table_name: impl Into<Arc<str>>) -> Self {
         Self {
             table_name: table_name.into(),
+            default_schema: None,
+            performance: None,
+            parameters: None,
+            extensions: None,
+            tags: None,
+            metric_cache: None,
+            redaction: None,
+            regex_cache: None,
+            collection_limits: Arc::default(),
         }
     }
 
@@ -57,6 +90,164 @@ impl ValidationContext {
     pub fn table_name(&self) -> &str {
         &self.table_name
     }
+
+    /// Sets the schema used for unqualified table names in multi-table constraints.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::core::ValidationContext;
+    ///
+    /// let ctx = ValidationContext::new("orders").with_default_schema("staging");
+    /// assert_eq!(ctx.default_schema(), Some("staging"));
+    /// ```
+    pub fn with_default_schema(mut self, schema: impl Into<Arc<str>>) -> Self {
+        self.default_schema = Some(schema.into());
+        self
+    }
+
+    /// Returns the default schema for unqualified table names, if any.
+    pub fn default_schema(&self) -> Option<&str> {
+        self.default_schema.as_deref()
+    }
+
+    /// Attaches a collector that receives the execution metrics of queries
+    /// executed through [`collect_with_metrics`](super::collect_with_metrics).
+    pub fn with_performance_collector(mut self, collector: QueryMetricsCollector) -> Self {
+        self.performance = Some(collector);
+        self
+    }
+
+    /// Returns the performance collector attached to this context, if any.
+    pub fn performance_collector(&self) -> Option<&QueryMetricsCollector> {
+        self.performance.as_ref()
+    }
+
+    /// Sets the named parameters constraints resolve at execution time.
+    pub fn with_parameters(mut self, parameters: Arc<ParameterBag>) -> Self {
+        self.parameters = Some(parameters);
+        self
+    }
+
+    /// Returns the value of a named parameter, if set.
+    pub fn parameter(&self, name: &str) -> Option<f64> {
+        self.parameters.as_ref().and_then(|params| params.get(name))
+    }
+
+    /// Sets the per-run dependencies available to constraints.
+    pub fn with_extensions(mut self, extensions: Arc<Extensions>) -> Self {
+        self.extensions = Some(extensions);
+        self
+    }
+
+    /// Returns the per-run dependencies of type `T`, if set.
+    pub fn extension<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.extensions.as_ref().and_then(|ext| ext.get::<T>())
+    }
+
+    /// Sets the tags describing the run, such as environment or owning team.
+    pub fn with_tags(mut self, tags: Arc<BTreeMap<String, String>>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Returns the value of a tag, if set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::BTreeMap;
+    /// use std::sync::Arc;
+    /// use term_guard::core::ValidationContext;
+    ///
+    /// let tags = BTreeMap::from([("env".to_string(), "prod".to_string())]);
+    /// let ctx = ValidationContext::new("orders").with_tags(Arc::new(tags));
+    /// assert_eq!(ctx.tag("env"), Some("prod"));
+    /// assert_eq!(ctx.tag("team"), None);
+    /// ```
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        self.tags
+            .as_ref()
+            .and_then(|tags| tags.get(key))
+            .map(String::as_str)
+    }
+
+    /// Attaches the cache of table metrics shared by the constraints of a run.
+    ///
+    /// See [`fetch_metrics`](super::fetch_metrics).
+    pub fn with_metric_cache(mut self, cache: MetricCache) -> Self {
+        self.metric_cache = Some(cache);
+        self
+    }
+
+    /// Returns the metric cache attached to this context, if any.
+    pub fn metric_cache(&self) -> Option<&MetricCache> {
+        self.metric_cache.as_ref()
+    }
+
+    /// Attaches the cache of regexes compiled by the constraints of a run.
+    pub fn with_regex_cache(mut self, cache: RegexCache) -> Self {
+        self.regex_cache = Some(cache);
+        self
+    }
+
+    /// Returns the regex cache attached to this context, if any.
+    pub fn regex_cache(&self) -> Option<&RegexCache> {
+        self.regex_cache.as_ref()
+    }
+
+    /// Sets the bounds on the result sets constraints collect.
+    ///
+    /// See [`collect_limited`](super::collect_limited).
+    pub fn with_collection_limits(mut self, limits: Arc<CollectionLimits>) -> Self {
+        self.collection_limits = limits;
+        self
+    }
+
+    /// Returns the bounds on the result sets constraints collect, the default
+    /// limits unless set.
+    pub fn collection_limits(&self) -> &CollectionLimits {
+        &self.collection_limits
+    }
+
+    /// Sets the policy redacting values that constraints quote in their results.
+    pub fn with_redaction(mut self, policy: Arc<RedactionPolicy>) -> Self {
+        self.redaction = Some(policy);
+        self
+    }
+
+    /// Renders a value of `column` for a result, redacted if the run's
+    /// [`RedactionPolicy`] covers the column.
+    ///
+    /// Constraints quoting values of the data in their results must render them
+    /// through this method or [`redact_any`](Self::redact_any).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use term_guard::core::{RedactionPolicy, RedactionStrategy, ValidationContext};
+    ///
+    /// let policy = RedactionPolicy::new().column("email", RedactionStrategy::Drop);
+    /// let ctx = ValidationContext::new("users").with_redaction(Arc::new(policy));
+    /// assert_eq!(ctx.redact("email", "jane@example.com"), "<redacted>");
+    /// assert_eq!(ctx.redact("country", "NZ"), "NZ");
+    /// ```
+    pub fn redact<'v>(&self, column: &str, value: &'v str) -> Cow<'v, str> {
+        match &self.redaction {
+            Some(policy) => policy.redact(column, value),
+            None => Cow::Borrowed(value),
+        }
+    }
+
+    /// Renders a value derived from several columns, redacted if the run's
+    /// [`RedactionPolicy`] covers any of them.
+    pub fn redact_any<'v, S: AsRef<str>>(&self, columns: &[S], value: &'v str) -> Cow<'v, str> {
+        match &self.redaction {
+            Some(policy) => policy.redact_any(columns, value),
+            None => Cow::Borrowed(value),
+        }
+    }
 }
 
 impl Default for ValidationContext {
@@ -93,6 +93,18 @@ pub enum TermError {
     #[error("Security error: {0}")]
     SecurityError(String),
 
+    /// Error when the tables of a context differ from those a suite was bound to.
+    #[error("Suite binding mismatch: {0}")]
+    BindingMismatch(Box<crate::core::BindingMismatch>),
+
+    /// Error when sources attached to a suite are unreachable before a run.
+    #[error("Preflight failed: {0}")]
+    PreflightFailed(Box<crate::core::PreflightReport>),
+
+    /// Error when a configuration has problems, listing every problem found.
+    #[error("Invalid configuration: {0}")]
+    InvalidConfiguration(Box<crate::core::ConfigReport>),
+
     /// Error from repository operations.
     #[error("Repository error ({operation} on {repository_type}): {message}")]
     Repository {
@@ -288,6 +300,14 @@ impl TermError {
     }
 }
 
+/// Conversion from an infallible conversion error, so that `TryInto` bounds
+/// can accept both fallible and infallible conversions.
+impl From<std::convert::Infallible> for TermError {
+    fn from(err: std::convert::Infallible) -> Self {
+        match err {}
+    }
+}
+
 /// Conversion from AnalyzerError to TermError.
 impl From<crate::analyzers::AnalyzerError> for TermError {
     fn from(err: crate::analyzers::AnalyzerError) -> Self {
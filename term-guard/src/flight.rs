@@ -0,0 +1,446 @@
+//! Remote validation over Arrow Flight.
+//!
+//! This module moves validation off the host producing the data. A
+//! [`FlightValidationServer`] holds pre-registered [`ValidationSuite`]s; a
+//! client streams record batches to it with `DoPut` or `DoExchange`, naming the
+//! suite in the [`FlightDescriptor`] of the stream, and receives the resulting
+//! [`ValidationReport`] as JSON:
+//!
+//! - `DoPut` answers with one `PutResult` whose `app_metadata` holds the report
+//! - `DoExchange` answers with one `FlightData` message whose `app_metadata`
+//!   holds the report
+//!
+//! The suite is named by the first segment of a path descriptor, or by a command
+//! descriptor holding the name as UTF-8. The received batches are buffered like
+//! a [`FlightSource`], spilling to disk beyond a memory budget, and registered
+//! under the suite's [`table_name`](ValidationSuite::table_name).
+//!
+//! Calls are authenticated by a [`FlightAuthenticator`] hook run on the request
+//! metadata; [`FlightValidationServer::with_bearer_token`] installs one checking
+//! an `authorization: Bearer <token>` header.
+//!
+//! Requires the `flight` feature.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use term_guard::core::{Check, ValidationSuite};
+//! use term_guard::core::builder_extensions::CompletenessOptions;
+//! use term_guard::flight::{FlightValidationClient, FlightValidationServer};
+//!
+//! # async fn example(
+//! #     batches: Vec<arrow::record_batch::RecordBatch>,
+//! # ) -> term_guard::prelude::Result<()> {
+//! let suite = ValidationSuite::builder("orders")
+//!     .table_name("orders")
+//!     .check(
+//!         Check::builder("ids")
+//!             .completeness("order_id", CompletenessOptions::full().into_constraint_options())
+//!             .build(),
+//!     )
+//!     .build();
+//!
+//! // On the worker
+//! let listener = tokio::net::TcpListener::bind("0.0.0.0:50051").await?;
+//! let server = FlightValidationServer::new()
+//!     .register_suite(suite)
+//!     .with_bearer_token("secret");
+//! tokio::spawn(server.serve(listener));
+//!
+//! // On the ingestion host
+//! let mut client =
+//!     FlightValidationClient::connect("http://worker:50051", Some("secret".into())).await?;
+//! let report = client
+//!     .validate("orders", futures::stream::iter(batches.into_iter().map(Ok)))
+//!     .await?;
+//! println!("{} issues", report.issues.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::{ValidationReport, ValidationSuite};
+use crate::prelude::*;
+use crate::security::SecureString;
+use crate::sources::{connect_flight, flight_error, DataSource, FlightSource, StreamOptions};
+use arrow::record_batch::RecordBatch;
+use arrow_flight::decode::FlightRecordBatchStream;
+use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::error::FlightError;
+use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
+use arrow_flight::{
+    Action, ActionType, Criteria, Empty, FlightClient, FlightData, FlightDescriptor, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PollInfo, PutResult, SchemaResult, Ticket,
+};
+use datafusion::prelude::SessionContext;
+use futures::stream::{self, BoxStream, Stream, StreamExt, TryStreamExt};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::metadata::MetadataMap;
+use tonic::{Request, Response, Status, Streaming};
+use tracing::{debug, instrument};
+
+/// Hook authenticating the calls of a [`FlightValidationServer`].
+///
+/// It receives the metadata of each request and rejects the call by returning
+/// an error status, typically [`Status::unauthenticated`].
+pub type FlightAuthenticator =
+    Arc<dyn Fn(&MetadataMap) -> std::result::Result<(), Status> + Send + Sync>;
+
+/// A Flight service running pre-registered suites on the batches streamed to it.
+///
+/// See the [module documentation](self) for the protocol.
+#[derive(Clone, Default)]
+pub struct FlightValidationServer {
+    suites: HashMap<String, Arc<ValidationSuite>>,
+    buffer: StreamOptions,
+    authenticator: Option<FlightAuthenticator>,
+}
+
+impl FlightValidationServer {
+    /// Creates a server without suites or authentication.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a suite under its name, replacing any suite of the same name.
+    pub fn register_suite(mut self, suite: ValidationSuite) -> Self {
+        self.suites
+            .insert(suite.name().to_string(), Arc::new(suite));
+        self
+    }
+
+    /// Sets the memory budget and spill directory of the received batches.
+    pub fn with_buffer_options(mut self, options: StreamOptions) -> Self {
+        self.buffer = options;
+        self
+    }
+
+    /// Authenticates every call with `authenticator`.
+    pub fn with_authenticator(mut self, authenticator: FlightAuthenticator) -> Self {
+        self.authenticator = Some(authenticator);
+        self
+    }
+
+    /// Requires an `authorization: Bearer <token>` header on every call.
+    pub fn with_bearer_token(self, token: impl Into<SecureString>) -> Self {
+        let token = token.into();
+        self.with_authenticator(Arc::new(move |metadata: &MetadataMap| {
+            let presented = metadata
+                .get("authorization")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "));
+            match presented {
+                Some(presented) if constant_time_eq(presented, token.expose()) => Ok(()),
+                _ => Err(Status::unauthenticated("Invalid or missing bearer token")),
+            }
+        }))
+    }
+
+    /// Returns the names of the registered suites, sorted.
+    pub fn suite_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.suites.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Wraps the server into a service for a [`tonic`] server.
+    pub fn into_service(self) -> FlightServiceServer<Self> {
+        FlightServiceServer::new(self)
+    }
+
+    /// Serves the connections accepted by `listener` until an error occurs.
+    pub async fn serve(self, listener: TcpListener) -> Result<()> {
+        tonic::transport::Server::builder()
+            .add_service(self.into_service())
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+            .map_err(|e| {
+                TermError::data_source_with_source("flight", "Flight server failed", Box::new(e))
+            })
+    }
+
+    fn authenticate<T>(&self, request: &Request<T>) -> std::result::Result<(), Status> {
+        match &self.authenticator {
+            Some(authenticator) => authenticator(request.metadata()),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs the suite named by the first message of `input` on the whole stream.
+    #[instrument(skip(self, input))]
+    async fn validate_stream(
+        &self,
+        mut input: Streaming<FlightData>,
+    ) -> std::result::Result<ValidationReport, Status> {
+        let first = input
+            .message()
+            .await?
+            .ok_or_else(|| Status::invalid_argument("The stream holds no messages"))?;
+        let suite_name = suite_name(first.flight_descriptor.as_ref())?;
+        let suite = self
+            .suites
+            .get(&suite_name)
+            .ok_or_else(|| Status::not_found(format!("No suite named '{suite_name}'")))?;
+
+        let data = stream::once(async { Ok(first) })
+            .chain(input)
+            .map_err(FlightError::from);
+        let source = FlightSource::receive(
+            &format!("client of suite '{suite_name}'"),
+            FlightRecordBatchStream::new_from_flight_data(data),
+            &self.buffer,
+        )
+        .await
+        .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        debug!(
+            suite = %suite_name,
+            rows = source.num_rows(),
+            spilled = source.is_spilled(),
+            "Received Flight stream"
+        );
+
+        let ctx = SessionContext::new();
+        source
+            .register(&ctx, suite.table_name())
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let result = suite
+            .run(&ctx)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(result.report().clone())
+    }
+}
+
+impl fmt::Debug for FlightValidationServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlightValidationServer")
+            .field("suites", &self.suite_names())
+            .field("buffer", &self.buffer)
+            .field("authenticated", &self.authenticator.is_some())
+            .finish()
+    }
+}
+
+#[tonic::async_trait]
+impl FlightService for FlightValidationServer {
+    type HandshakeStream = BoxStream<'static, std::result::Result<HandshakeResponse, Status>>;
+    type ListFlightsStream = BoxStream<'static, std::result::Result<FlightInfo, Status>>;
+    type DoGetStream = BoxStream<'static, std::result::Result<FlightData, Status>>;
+    type DoPutStream = BoxStream<'static, std::result::Result<PutResult, Status>>;
+    type DoActionStream = BoxStream<'static, std::result::Result<arrow_flight::Result, Status>>;
+    type ListActionsStream = BoxStream<'static, std::result::Result<ActionType, Status>>;
+    type DoExchangeStream = BoxStream<'static, std::result::Result<FlightData, Status>>;
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> std::result::Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented("Handshake is not supported"))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> std::result::Result<Response<Self::ListFlightsStream>, Status> {
+        Err(Status::unimplemented("ListFlights is not supported"))
+    }
+
+    async fn get_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> std::result::Result<Response<FlightInfo>, Status> {
+        Err(Status::unimplemented("GetFlightInfo is not supported"))
+    }
+
+    async fn poll_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> std::result::Result<Response<PollInfo>, Status> {
+        Err(Status::unimplemented("PollFlightInfo is not supported"))
+    }
+
+    async fn get_schema(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> std::result::Result<Response<SchemaResult>, Status> {
+        Err(Status::unimplemented("GetSchema is not supported"))
+    }
+
+    async fn do_get(
+        &self,
+        _request: Request<Ticket>,
+    ) -> std::result::Result<Response<Self::DoGetStream>, Status> {
+        Err(Status::unimplemented("DoGet is not supported"))
+    }
+
+    async fn do_put(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> std::result::Result<Response<Self::DoPutStream>, Status> {
+        self.authenticate(&request)?;
+        let report = encode_report(&self.validate_stream(request.into_inner()).await?)?;
+        let result = PutResult {
+            app_metadata: report.into(),
+        };
+        Ok(Response::new(stream::once(async { Ok(result) }).boxed()))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> std::result::Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("DoAction is not supported"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> std::result::Result<Response<Self::ListActionsStream>, Status> {
+        Err(Status::unimplemented("ListActions is not supported"))
+    }
+
+    async fn do_exchange(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> std::result::Result<Response<Self::DoExchangeStream>, Status> {
+        self.authenticate(&request)?;
+        let report = encode_report(&self.validate_stream(request.into_inner()).await?)?;
+        let data = FlightData::new().with_app_metadata(report);
+        Ok(Response::new(stream::once(async { Ok(data) }).boxed()))
+    }
+}
+
+/// A client sending record batches to a [`FlightValidationServer`].
+#[derive(Debug)]
+pub struct FlightValidationClient {
+    client: FlightClient,
+}
+
+impl FlightValidationClient {
+    /// Connects to the server at `endpoint`, sending `bearer_token` if set.
+    pub async fn connect(endpoint: &str, bearer_token: Option<SecureString>) -> Result<Self> {
+        Ok(Self {
+            client: connect_flight(endpoint, bearer_token.as_ref()).await?,
+        })
+    }
+
+    /// Streams `batches` to the server with `DoPut` and returns the report of
+    /// the suite named `suite_name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::DataSource`] if the server rejects the call, for
+    /// example because no suite has that name, and the error of `batches` if
+    /// the stream fails.
+    pub async fn validate<S>(&mut self, suite_name: &str, batches: S) -> Result<ValidationReport>
+    where
+        S: Stream<Item = Result<RecordBatch>> + Send + 'static,
+    {
+        let data = FlightDataEncoderBuilder::new()
+            .with_flight_descriptor(Some(FlightDescriptor::new_path(vec![
+                suite_name.to_string()
+            ])))
+            .build(batches.map_err(|e| FlightError::ExternalError(Box::new(e))));
+
+        let mut results = self
+            .client
+            .do_put(data)
+            .await
+            .map_err(|e| flight_error("DoPut failed", e))?;
+        let result = results
+            .try_next()
+            .await
+            .map_err(|e| flight_error("DoPut failed", e))?
+            .ok_or_else(|| {
+                TermError::data_source("flight", "The server returned no validation report")
+            })?;
+        decode_report(&result.app_metadata)
+    }
+}
+
+/// Returns the suite name carried by a descriptor.
+fn suite_name(descriptor: Option<&FlightDescriptor>) -> std::result::Result<String, Status> {
+    let descriptor = descriptor.ok_or_else(|| {
+        Status::invalid_argument("The first message must carry a descriptor naming the suite")
+    })?;
+    match descriptor.path.first() {
+        Some(name) => Ok(name.clone()),
+        None => String::from_utf8(descriptor.cmd.to_vec())
+            .ok()
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| Status::invalid_argument("The descriptor does not name a suite")),
+    }
+}
+
+fn encode_report(report: &ValidationReport) -> std::result::Result<Vec<u8>, Status> {
+    serde_json::to_vec(report).map_err(|e| Status::internal(e.to_string()))
+}
+
+/// Decodes a report sent by a [`FlightValidationServer`].
+pub fn decode_report(metadata: &[u8]) -> Result<ValidationReport> {
+    let json = std::str::from_utf8(metadata).map_err(|e| {
+        TermError::Serialization(format!("Validation report is not valid UTF-8: {e}"))
+    })?;
+    ValidationReport::from_json_str(json)
+}
+
+/// Compares two strings in time independent of where they differ.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |difference, (x, y)| difference | (x ^ y))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suite_name_from_descriptor() {
+        let path = FlightDescriptor::new_path(vec!["orders".to_string(), "extra".to_string()]);
+        assert_eq!(suite_name(Some(&path)).unwrap(), "orders");
+
+        let command = FlightDescriptor::new_cmd("customers");
+        assert_eq!(suite_name(Some(&command)).unwrap(), "customers");
+
+        let error = suite_name(None).unwrap_err();
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+        let error = suite_name(Some(&FlightDescriptor::new_cmd(""))).unwrap_err();
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[test]
+    fn test_bearer_token_authenticator() {
+        let server = FlightValidationServer::new().with_bearer_token("secret");
+
+        let mut request = Request::new(());
+        assert_eq!(
+            server.authenticate(&request).unwrap_err().code(),
+            tonic::Code::Unauthenticated
+        );
+
+        request
+            .metadata_mut()
+            .insert("authorization", "Bearer wrong".parse().unwrap());
+        assert!(server.authenticate(&request).is_err());
+
+        request
+            .metadata_mut()
+            .insert("authorization", "Bearer secret".parse().unwrap());
+        assert!(server.authenticate(&request).is_ok());
+    }
+
+    #[test]
+    fn test_report_round_trip() {
+        let report = ValidationReport::new("orders");
+        let decoded = decode_report(&encode_report(&report).unwrap()).unwrap();
+        assert_eq!(decoded.suite_name, "orders");
+        assert!(decode_report(b"not json").is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! Data contract documents generated from validation suites.
+//!
+//! A [`ValidationSuite`] states what its table must satisfy, so it can double as the
+//! dataset's documented data contract. [`ContractFormatter`] renders the definition
+//! of a suite, not the results of a run, as Markdown: the suite and table names,
+//! the descriptions of the suite and its checks, and the requirement of every
+//! constraint as returned by [`Constraint::describe`](crate::core::Constraint::describe).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::core::{Check, ConstraintOptions, Level, ValidationSuite};
+//! use term_guard::formatters::ContractFormatter;
+//!
+//! let suite = ValidationSuite::builder("customers")
+//!     .table_name("customers")
+//!     .description("Customer master data")
+//!     .check(
+//!         Check::builder("contact_details")
+//!             .level(Level::Error)
+//!             .validates_email("email", 0.99)
+//!             .build(),
+//!     )
+//!     .build();
+//!
+//! let contract = ContractFormatter::new().format(&suite).unwrap();
+//! assert!(contract.starts_with("# Data Contract: customers"));
+//! assert!(contract.contains("email must match an email format for at least 99% of rows"));
+//! ```
+
+use crate::core::ValidationSuite;
+use crate::prelude::*;
+use std::fmt::Write;
+
+/// Renders a validation suite as a Markdown data contract.
+///
+/// Each check gets a section with its level, its description and a table listing
+/// the label, requirement and quality dimension of every constraint. Checks and
+/// constraints appear in the order they were added to the suite, so the document
+/// only changes when the suite does.
+#[derive(Debug, Clone)]
+pub struct ContractFormatter {
+    heading_level: u8,
+}
+
+impl ContractFormatter {
+    /// Creates a contract formatter whose title is a level 1 heading.
+    pub fn new() -> Self {
+        Self { heading_level: 1 }
+    }
+
+    /// Sets the heading level of the title; checks are one level below.
+    pub fn with_heading_level(mut self, level: u8) -> Self {
+        self.heading_level = level.clamp(1, 6);
+        self
+    }
+
+    /// Renders the data contract of `suite`.
+    pub fn format(&self, suite: &ValidationSuite) -> Result<String> {
+        let mut output = String::new();
+        let h = "#".repeat(self.heading_level as usize);
+
+        writeln!(output, "{h} Data Contract: {}", suite.name()).unwrap();
+        if let Some(description) = suite.description() {
+            writeln!(output).unwrap();
+            writeln!(output, "{description}").unwrap();
+        }
+
+        writeln!(output).unwrap();
+        writeln!(output, "- **Table:** {}", suite.table_name()).unwrap();
+        let referenced: Vec<String> = suite
+            .expected_tables()
+            .into_iter()
+            .filter(|table| table != suite.table_name())
+            .collect();
+        if !referenced.is_empty() {
+            writeln!(output, "- **Referenced Tables:** {}", referenced.join(", ")).unwrap();
+        }
+        writeln!(output, "- **Checks:** {}", suite.checks().len()).unwrap();
+
+        for check in suite.checks() {
+            writeln!(output).unwrap();
+            writeln!(output, "{h}# {}", check.name()).unwrap();
+            writeln!(output).unwrap();
+            if let Some(description) = check.description() {
+                writeln!(output, "{description}").unwrap();
+                writeln!(output).unwrap();
+            }
+            writeln!(output, "- **Level:** {:?}", check.level()).unwrap();
+            writeln!(output).unwrap();
+
+            if check.constraints().is_empty() {
+                writeln!(output, "_No constraints._").unwrap();
+                continue;
+            }
+            writeln!(output, "| Constraint | Requirement | Dimension |").unwrap();
+            writeln!(output, "|------------|-------------|-----------|").unwrap();
+            for (index, constraint) in check.constraints().iter().enumerate() {
+                let dimension = check
+                    .constraint_dimension(index)
+                    .map_or_else(|| "-".to_string(), |dimension| dimension.to_string());
+                writeln!(
+                    output,
+                    "| {} | {} | {} |",
+                    table_cell(check.constraint_label(index)),
+                    table_cell(&constraint.describe()),
+                    table_cell(&dimension)
+                )
+                .unwrap();
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+impl Default for ContractFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Escapes text for a Markdown table cell, which must stay on one line.
+fn table_cell(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Check, Level};
+
+    #[test]
+    fn test_table_cell_escaping() {
+        assert_eq!(table_cell("a || b"), "a \\|\\| b");
+        assert_eq!(table_cell("SELECT 1\n  FROM t"), "SELECT 1 FROM t");
+    }
+
+    #[test]
+    fn test_check_without_constraints() {
+        let suite = ValidationSuite::builder("empty")
+            .table_name("data")
+            .check(Check::builder("placeholder").level(Level::Info).build())
+            .build();
+
+        let contract = ContractFormatter::new()
+            .with_heading_level(2)
+            .format(&suite)
+            .unwrap();
+        assert!(contract.starts_with("## Data Contract: empty\n"));
+        assert!(contract.contains("### placeholder\n\n- **Level:** Info\n\n_No constraints._\n"));
+        assert!(!contract.contains("Referenced Tables"));
+    }
+}
@@ -0,0 +1,1613 @@
+//! Result formatting and reporting for Term validation results.
+//!
+//! This module provides different formatters for validation results, allowing
+//! users to output results in various formats like JSON, human-readable text,
+//! or Markdown for documentation purposes. The [`sarif`] module writes results
+//! as SARIF logs for code scanning integrations. The [`contract`] module renders
+//! the definition of a suite, rather than its results, as a data contract document.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::formatters::{ResultFormatter, JsonFormatter, HumanFormatter};
+//! use term_guard::core::ValidationResult;
+//!
+//! let formatter = HumanFormatter::new();
+//! // let result: ValidationResult = /* ... */;
+//! // let output = formatter.format(&result);
+//! ```
+
+use crate::core::{
+    DeltaKind, KnownIssue, Level, RecentFlakiness, ReportDelta, ValidationIssue, ValidationReport,
+    ValidationResult,
+};
+use crate::prelude::*;
+use crate::scorecard::Scorecard;
+use serde_json;
+use std::fmt::Write;
+
+pub mod contract;
+pub mod sarif;
+
+pub use contract::ContractFormatter;
+pub use sarif::SarifFormatter;
+
+/// Configuration options for formatting validation results.
+#[derive(Debug, Clone)]
+pub struct FormatterConfig {
+    /// Include detailed metrics in output
+    pub include_metrics: bool,
+    /// Include individual issue details
+    pub include_issues: bool,
+    /// Include custom metrics from constraints
+    pub include_custom_metrics: bool,
+    /// Maximum number of issues to display (-1 for all)
+    pub max_issues: i32,
+    /// Whether to use colorized output (for human formatter)
+    pub use_colors: bool,
+    /// Whether to include timestamps in output
+    pub include_timestamps: bool,
+    /// Scorecard used to render a quality score, if any
+    pub scorecard: Option<Scorecard>,
+    /// Report of a previous run to render the changes since, if any
+    pub previous_report: Option<ValidationReport>,
+}
+
+impl Default for FormatterConfig {
+    fn default() -> Self {
+        Self {
+            include_metrics: true,
+            include_issues: true,
+            include_custom_metrics: true,
+            max_issues: -1, // Show all issues by default
+            use_colors: true,
+            include_timestamps: true,
+            scorecard: None,
+            previous_report: None,
+        }
+    }
+}
+
+impl FormatterConfig {
+    /// Creates a minimal configuration showing only summary.
+    pub fn minimal() -> Self {
+        Self {
+            include_metrics: true,
+            include_issues: false,
+            include_custom_metrics: false,
+            max_issues: 0,
+            use_colors: false,
+            include_timestamps: false,
+            scorecard: None,
+            previous_report: None,
+        }
+    }
+
+    /// Creates a detailed configuration showing everything.
+    pub fn detailed() -> Self {
+        Self {
+            include_metrics: true,
+            include_issues: true,
+            include_custom_metrics: true,
+            max_issues: -1,
+            use_colors: true,
+            include_timestamps: true,
+            scorecard: None,
+            previous_report: None,
+        }
+    }
+
+    /// Creates a configuration suitable for CI/CD environments.
+    pub fn ci() -> Self {
+        Self {
+            include_metrics: true,
+            include_issues: true,
+            include_custom_metrics: false,
+            max_issues: 50, // Limit output in CI
+            use_colors: false,
+            include_timestamps: true,
+            scorecard: None,
+            previous_report: None,
+        }
+    }
+
+    /// Sets whether to include detailed metrics.
+    pub fn with_metrics(mut self, include: bool) -> Self {
+        self.include_metrics = include;
+        self
+    }
+
+    /// Sets whether to include individual issues.
+    pub fn with_issues(mut self, include: bool) -> Self {
+        self.include_issues = include;
+        self
+    }
+
+    /// Sets the maximum number of issues to display.
+    pub fn with_max_issues(mut self, max: i32) -> Self {
+        self.max_issues = max;
+        self
+    }
+
+    /// Sets whether to use colorized output.
+    pub fn with_colors(mut self, use_colors: bool) -> Self {
+        self.use_colors = use_colors;
+        self
+    }
+
+    /// Renders the quality score computed by `scorecard` with the results.
+    ///
+    /// JSON output gains a `quality_score` field; the human and Markdown
+    /// formatters add a section with the overall and per-check scores.
+    pub fn with_scorecard(mut self, scorecard: Scorecard) -> Self {
+        self.scorecard = Some(scorecard);
+        self
+    }
+
+    /// Renders the changes since `previous`, the report of an earlier run of the
+    /// suite, see [`ValidationReport::compare`].
+    ///
+    /// JSON output gains a `changes_since_previous` field; the human formatter
+    /// starts with a section listing the constraints that started or stopped
+    /// failing, still fail, or were added or removed.
+    pub fn with_previous_report(mut self, previous: ValidationReport) -> Self {
+        self.previous_report = Some(previous);
+        self
+    }
+}
+
+/// Trait for formatting validation results into different output formats.
+///
+/// This trait provides a uniform interface for converting validation results
+/// into various formats like JSON, human-readable text, or Markdown.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::formatters::{ResultFormatter, JsonFormatter};
+/// use term_guard::core::ValidationResult;
+///
+/// struct MyCustomFormatter;
+///
+/// impl ResultFormatter for MyCustomFormatter {
+///     fn format(&self, result: &ValidationResult) -> term_guard::prelude::Result<String> {
+///         let success = result.is_success();
+///         Ok(format!("Custom format: {success}"))
+///     }
+/// }
+/// ```
+pub trait ResultFormatter {
+    /// Formats a validation result into a string representation.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The validation result to format
+    ///
+    /// # Returns
+    ///
+    /// A formatted string representation of the result
+    fn format(&self, result: &ValidationResult) -> Result<String>;
+
+    /// Formats a validation result with custom configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The validation result to format
+    /// * `config` - Configuration options for formatting
+    ///
+    /// # Returns
+    ///
+    /// A formatted string representation of the result
+    fn format_with_config(
+        &self,
+        result: &ValidationResult,
+        _config: &FormatterConfig,
+    ) -> Result<String> {
+        // Default implementation ignores config and uses standard format
+        self.format(result)
+    }
+}
+
+/// Formats validation results as structured JSON.
+///
+/// This formatter outputs the complete validation result as JSON,
+/// making it suitable for programmatic consumption and integration
+/// with other tools.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::formatters::{ResultFormatter, JsonFormatter, FormatterConfig};
+/// # use term_guard::core::{ValidationResult, ValidationReport, ValidationMetrics};
+/// # let metrics = ValidationMetrics::new();
+/// # let report = ValidationReport::new("test");
+/// # let result = ValidationResult::success(metrics, report);
+///
+/// let formatter = JsonFormatter::new();
+/// let json_output = formatter.format(&result).unwrap();
+/// println!("{}", json_output);
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonFormatter {
+    config: FormatterConfig,
+    pretty: bool,
+}
+
+impl JsonFormatter {
+    /// Creates a new JSON formatter with default configuration.
+    pub fn new() -> Self {
+        Self {
+            config: FormatterConfig::default(),
+            pretty: true,
+        }
+    }
+
+    /// Creates a new JSON formatter with the specified configuration.
+    pub fn with_config(config: FormatterConfig) -> Self {
+        Self {
+            config,
+            pretty: true,
+        }
+    }
+
+    /// Sets whether to use pretty-printed JSON.
+    pub fn with_pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+}
+
+impl Default for JsonFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResultFormatter for JsonFormatter {
+    fn format(&self, result: &ValidationResult) -> Result<String> {
+        self.format_with_config(result, &self.config)
+    }
+
+    fn format_with_config(
+        &self,
+        result: &ValidationResult,
+        config: &FormatterConfig,
+    ) -> Result<String> {
+        // Create a filtered representation based on config
+        let filtered_result = filter_result_for_config(result, config);
+        let mut value = serde_json::to_value(&filtered_result)
+            .map_err(|e| TermError::Internal(format!("Failed to serialize result to JSON: {e}")))?;
+        split_observations(&mut value);
+        if let Some(previous) = &config.previous_report {
+            let delta = serde_json::to_value(result.report().compare(previous)).map_err(|e| {
+                TermError::Internal(format!("Failed to serialize report delta to JSON: {e}"))
+            })?;
+            if let Some(object) = value.as_object_mut() {
+                object.insert("changes_since_previous".to_string(), delta);
+            }
+        }
+        if let Some(scorecard) = &config.scorecard {
+            let score = serde_json::to_value(scorecard.score(result.report())).map_err(|e| {
+                TermError::Internal(format!("Failed to serialize quality score to JSON: {e}"))
+            })?;
+            if let Some(object) = value.as_object_mut() {
+                object.insert("quality_score".to_string(), score);
+            }
+        }
+        let dimensions = result.report().dimension_summary();
+        if config.include_metrics && !dimensions.is_empty() {
+            let dimensions = serde_json::to_value(dimensions).map_err(|e| {
+                TermError::Internal(format!(
+                    "Failed to serialize dimension summary to JSON: {e}"
+                ))
+            })?;
+            if let Some(object) = value.as_object_mut() {
+                object.insert("dimension_summary".to_string(), dimensions);
+            }
+        }
+
+        if self.pretty {
+            serde_json::to_string_pretty(&value).map_err(|e| {
+                TermError::Internal(format!("Failed to serialize result to JSON: {e}"))
+            })
+        } else {
+            serde_json::to_string(&value).map_err(|e| {
+                TermError::Internal(format!("Failed to serialize result to JSON: {e}"))
+            })
+        }
+    }
+}
+
+/// Formats validation results in a human-readable format suitable for console output.
+///
+/// This formatter creates nicely formatted, colorized output that's easy to read
+/// in terminals and logs. It includes summary information, issue details, and
+/// optional metrics.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::formatters::{ResultFormatter, HumanFormatter, FormatterConfig};
+/// # use term_guard::core::{ValidationResult, ValidationReport, ValidationMetrics};
+/// # let metrics = ValidationMetrics::new();
+/// # let report = ValidationReport::new("test");
+/// # let result = ValidationResult::success(metrics, report);
+///
+/// let formatter = HumanFormatter::new();
+/// let human_output = formatter.format(&result).unwrap();
+/// println!("{}", human_output);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HumanFormatter {
+    config: FormatterConfig,
+}
+
+impl HumanFormatter {
+    /// Creates a new human formatter with default configuration.
+    pub fn new() -> Self {
+        Self {
+            config: FormatterConfig::default(),
+        }
+    }
+
+    /// Creates a new human formatter with the specified configuration.
+    pub fn with_config(config: FormatterConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Default for HumanFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResultFormatter for HumanFormatter {
+    fn format(&self, result: &ValidationResult) -> Result<String> {
+        self.format_with_config(result, &self.config)
+    }
+
+    fn format_with_config(
+        &self,
+        result: &ValidationResult,
+        config: &FormatterConfig,
+    ) -> Result<String> {
+        let mut output = String::new();
+        let report = result.report();
+
+        // Header
+        writeln!(output).unwrap();
+        if result.is_success() {
+            if config.use_colors {
+                writeln!(output, "✅ \x1b[32mValidation PASSED\x1b[0m").unwrap();
+            } else {
+                writeln!(output, "✅ Validation PASSED").unwrap();
+            }
+        } else if config.use_colors {
+            writeln!(output, "❌ \x1b[31mValidation FAILED\x1b[0m").unwrap();
+        } else {
+            writeln!(output, "❌ Validation FAILED").unwrap();
+        }
+
+        writeln!(output).unwrap();
+        writeln!(output, "Suite: {}", report.suite_name).unwrap();
+        if let Some(description) = &report.description {
+            writeln!(output, "Description: {description}").unwrap();
+        }
+        if let Some(preview) = &report.preview {
+            writeln!(output, "Preview: {preview}").unwrap();
+        }
+
+        if config.include_timestamps {
+            writeln!(output, "Timestamp: {}", report.timestamp).unwrap();
+        }
+
+        // Changes since the previous run
+        if let Some(previous) = &config.previous_report {
+            write_delta(&mut output, &report.compare(previous));
+        }
+
+        // Metrics summary
+        if config.include_metrics {
+            writeln!(output).unwrap();
+            writeln!(output, "📊 Summary Statistics:").unwrap();
+            for row in summary_rows(report) {
+                match row.color.filter(|_| config.use_colors) {
+                    Some(color) => writeln!(
+                        output,
+                        "   {}{}: \x1b[{color}m{}\x1b[0m",
+                        row.icon, row.label, row.value
+                    ),
+                    None => writeln!(output, "   {}{}: {}", row.icon, row.label, row.value),
+                }
+                .unwrap();
+            }
+        }
+
+        // Quality score
+        if let Some(scorecard) = &config.scorecard {
+            let score = scorecard.score(report);
+            writeln!(output).unwrap();
+            writeln!(output, "🏅 Quality Score: {:.1}/100", score.overall).unwrap();
+            for check in &score.per_check {
+                writeln!(
+                    output,
+                    "   {}: {:.1} (weight {})",
+                    check.check_name, check.score, check.weight
+                )
+                .unwrap();
+            }
+        }
+
+        // Quality dimensions
+        let dimensions = report.dimension_summary();
+        if config.include_metrics && !dimensions.is_empty() {
+            writeln!(output).unwrap();
+            writeln!(output, "📐 Quality Dimensions:").unwrap();
+            for dimension in &dimensions {
+                writeln!(
+                    output,
+                    "   {}: {:.1}% passed, {} issue(s) in {} constraint(s)",
+                    dimension.dimension,
+                    dimension.pass_rate,
+                    dimension.issues,
+                    dimension.constraints
+                )
+                .unwrap();
+            }
+        }
+
+        // Custom metrics
+        if config.include_custom_metrics && !report.metrics.custom_metrics.is_empty() {
+            writeln!(output).unwrap();
+            writeln!(output, "📈 Custom Metrics:").unwrap();
+            for (name, value) in &report.metrics.custom_metrics {
+                writeln!(output, "   {name}: {value:.3}").unwrap();
+            }
+        }
+
+        // Info-level failures are observations and get their own section
+        let (observations, issues): (Vec<&ValidationIssue>, Vec<&ValidationIssue>) = report
+            .issues
+            .iter()
+            .partition(|issue| issue.level == Level::Info);
+
+        // Issues
+        if config.include_issues && !issues.is_empty() {
+            writeln!(output).unwrap();
+            writeln!(output, "🔍 Issues Found:").unwrap();
+
+            let issues_to_show = if config.max_issues < 0 {
+                issues.as_slice()
+            } else {
+                let max = config.max_issues as usize;
+                &issues[..std::cmp::min(max, issues.len())]
+            };
+
+            for (i, issue) in issues_to_show.iter().enumerate() {
+                writeln!(output).unwrap();
+                let level_symbol = match issue.level {
+                    _ if issue.is_known_issue() => {
+                        if config.use_colors {
+                            "\x1b[90m🔕\x1b[0m"
+                        } else {
+                            "🔕"
+                        }
+                    }
+                    Level::Error => {
+                        if config.use_colors {
+                            "\x1b[31m🚨\x1b[0m"
+                        } else {
+                            "🚨"
+                        }
+                    }
+                    Level::Warning => {
+                        if config.use_colors {
+                            "\x1b[33m⚠️\x1b[0m"
+                        } else {
+                            "⚠️"
+                        }
+                    }
+                    Level::Info => {
+                        if config.use_colors {
+                            "\x1b[34mℹ️\x1b[0m"
+                        } else {
+                            "ℹ️"
+                        }
+                    }
+                };
+
+                writeln!(
+                    output,
+                    "   {level_symbol} Issue #{}: {}",
+                    i + 1,
+                    issue.constraint_name
+                )
+                .unwrap();
+                writeln!(output, "      Check: {}", issue.check_name).unwrap();
+                if let Some(description) = report.check_description(&issue.check_name) {
+                    writeln!(output, "      Check Description: {description}").unwrap();
+                }
+                writeln!(output, "      Level: {:?}", issue.level).unwrap();
+                writeln!(output, "      Message: {}", issue.message).unwrap();
+                if let Some(known_issue) = &issue.known_issue {
+                    writeln!(
+                        output,
+                        "      Known Issue: {}",
+                        known_issue_text(known_issue)
+                    )
+                    .unwrap();
+                }
+                if !issue.impacts.is_empty() {
+                    writeln!(output, "      Impacts: {}", issue.impacts.join(", ")).unwrap();
+                }
+                if let Some(flakiness) = issue_flakiness(report, issue) {
+                    writeln!(output, "      Flakiness: {flakiness}").unwrap();
+                }
+
+                if let Some(metric) = issue.metric {
+                    writeln!(output, "      Metric: {metric:.3}").unwrap();
+                }
+            }
+
+            if issues.len() > issues_to_show.len() {
+                writeln!(output).unwrap();
+                writeln!(
+                    output,
+                    "   ... and {} more issues (use --max-issues to show more)",
+                    issues.len() - issues_to_show.len()
+                )
+                .unwrap();
+            }
+        }
+
+        // Observations
+        if config.include_issues && !observations.is_empty() {
+            writeln!(output).unwrap();
+            writeln!(output, "ℹ️  Observations:").unwrap();
+
+            let observations_to_show = if config.max_issues < 0 {
+                observations.as_slice()
+            } else {
+                let max = config.max_issues as usize;
+                &observations[..std::cmp::min(max, observations.len())]
+            };
+
+            for (i, observation) in observations_to_show.iter().enumerate() {
+                writeln!(output).unwrap();
+                let symbol = if config.use_colors {
+                    "\x1b[34mℹ️\x1b[0m"
+                } else {
+                    "ℹ️"
+                };
+                writeln!(
+                    output,
+                    "   {symbol} Observation #{}: {}",
+                    i + 1,
+                    observation.constraint_name
+                )
+                .unwrap();
+                writeln!(output, "      Check: {}", observation.check_name).unwrap();
+                if let Some(description) = report.check_description(&observation.check_name) {
+                    writeln!(output, "      Check Description: {description}").unwrap();
+                }
+                writeln!(output, "      Message: {}", observation.message).unwrap();
+
+                if let Some(metric) = observation.metric {
+                    writeln!(output, "      Metric: {metric:.3}").unwrap();
+                }
+            }
+
+            if observations.len() > observations_to_show.len() {
+                writeln!(output).unwrap();
+                writeln!(
+                    output,
+                    "   ... and {} more observations (use --max-issues to show more)",
+                    observations.len() - observations_to_show.len()
+                )
+                .unwrap();
+            }
+        }
+
+        writeln!(output).unwrap();
+        Ok(output)
+    }
+}
+
+/// Formats validation results as Markdown suitable for documentation.
+///
+/// This formatter creates Markdown output that can be included in reports,
+/// documentation, or README files. It provides a clean, structured format
+/// with proper heading hierarchy.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::formatters::{ResultFormatter, MarkdownFormatter};
+/// # use term_guard::core::{ValidationResult, ValidationReport, ValidationMetrics};
+/// # let metrics = ValidationMetrics::new();
+/// # let report = ValidationReport::new("test");
+/// # let result = ValidationResult::success(metrics, report);
+///
+/// let formatter = MarkdownFormatter::new();
+/// let markdown_output = formatter.format(&result).unwrap();
+/// println!("{}", markdown_output);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MarkdownFormatter {
+    config: FormatterConfig,
+    heading_level: u8,
+}
+
+impl MarkdownFormatter {
+    /// Creates a new Markdown formatter with default configuration.
+    pub fn new() -> Self {
+        Self {
+            config: FormatterConfig::default(),
+            heading_level: 2,
+        }
+    }
+
+    /// Creates a new Markdown formatter with the specified configuration.
+    pub fn with_config(config: FormatterConfig) -> Self {
+        Self {
+            config,
+            heading_level: 2,
+        }
+    }
+
+    /// Sets the base heading level for the output.
+    pub fn with_heading_level(mut self, level: u8) -> Self {
+        self.heading_level = level.clamp(1, 6);
+        self
+    }
+}
+
+impl Default for MarkdownFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResultFormatter for MarkdownFormatter {
+    fn format(&self, result: &ValidationResult) -> Result<String> {
+        self.format_with_config(result, &self.config)
+    }
+
+    fn format_with_config(
+        &self,
+        result: &ValidationResult,
+        config: &FormatterConfig,
+    ) -> Result<String> {
+        let mut output = String::new();
+        let report = result.report();
+        let h = "#".repeat(self.heading_level as usize);
+
+        // Main heading
+        if result.is_success() {
+            writeln!(output, "{h} ✅ Validation Report - PASSED").unwrap();
+        } else {
+            writeln!(output, "{h} ❌ Validation Report - FAILED").unwrap();
+        }
+
+        writeln!(output).unwrap();
+        writeln!(output, "**Suite:** {}", report.suite_name).unwrap();
+        if let Some(description) = &report.description {
+            writeln!(output, "**Description:** {description}").unwrap();
+        }
+        if let Some(preview) = &report.preview {
+            writeln!(output, "**Preview:** {preview}").unwrap();
+        }
+
+        if config.include_timestamps {
+            writeln!(output, "**Timestamp:** {}", report.timestamp).unwrap();
+        }
+
+        // Summary table
+        if config.include_metrics {
+            writeln!(output).unwrap();
+            writeln!(output, "{h}# Summary").unwrap();
+            writeln!(output).unwrap();
+            writeln!(output, "| Metric | Value |").unwrap();
+            writeln!(output, "|--------|-------|").unwrap();
+            for row in summary_rows(report) {
+                writeln!(output, "| {} | {} |", row.label, row.value).unwrap();
+            }
+        }
+
+        // Quality score
+        if let Some(scorecard) = &config.scorecard {
+            let score = scorecard.score(report);
+            writeln!(output).unwrap();
+            writeln!(output, "{h}# Quality Score: {:.1}/100", score.overall).unwrap();
+            writeln!(output).unwrap();
+            writeln!(output, "| Check | Level | Weight | Score |").unwrap();
+            writeln!(output, "|-------|-------|--------|-------|").unwrap();
+            for check in &score.per_check {
+                writeln!(
+                    output,
+                    "| {} | {:?} | {} | {:.1} |",
+                    check.check_name, check.level, check.weight, check.score
+                )
+                .unwrap();
+            }
+        }
+
+        // Quality dimensions
+        let dimensions = report.dimension_summary();
+        if config.include_metrics && !dimensions.is_empty() {
+            writeln!(output).unwrap();
+            writeln!(output, "{h}# Quality Dimensions").unwrap();
+            writeln!(output).unwrap();
+            writeln!(
+                output,
+                "| Dimension | Constraints | Passed | Failed | Skipped | Issues | Pass Rate |"
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "|-----------|-------------|--------|--------|---------|--------|-----------|"
+            )
+            .unwrap();
+            for dimension in &dimensions {
+                writeln!(
+                    output,
+                    "| {} | {} | {} | {} | {} | {} | {:.1}% |",
+                    dimension.dimension,
+                    dimension.constraints,
+                    dimension.passed,
+                    dimension.failed,
+                    dimension.skipped,
+                    dimension.issues,
+                    dimension.pass_rate
+                )
+                .unwrap();
+            }
+        }
+
+        // Custom metrics
+        if config.include_custom_metrics && !report.metrics.custom_metrics.is_empty() {
+            writeln!(output).unwrap();
+            writeln!(output, "{h}# Custom Metrics").unwrap();
+            writeln!(output).unwrap();
+            writeln!(output, "| Metric | Value |").unwrap();
+            writeln!(output, "|--------|-------|").unwrap();
+            for (name, value) in &report.metrics.custom_metrics {
+                writeln!(output, "| {name} | {value:.3} |").unwrap();
+            }
+        }
+
+        // Info-level failures are observations and get their own section
+        let (observations, issues): (Vec<&ValidationIssue>, Vec<&ValidationIssue>) = report
+            .issues
+            .iter()
+            .partition(|issue| issue.level == Level::Info);
+
+        // Issues
+        if config.include_issues && !issues.is_empty() {
+            writeln!(output).unwrap();
+            writeln!(output, "{h}# Issues").unwrap();
+            writeln!(output).unwrap();
+
+            let issues_to_show = if config.max_issues < 0 {
+                issues.as_slice()
+            } else {
+                let max = config.max_issues as usize;
+                &issues[..std::cmp::min(max, issues.len())]
+            };
+
+            for (i, issue) in issues_to_show.iter().enumerate() {
+                let level_emoji = match issue.level {
+                    _ if issue.is_known_issue() => "🔕",
+                    Level::Error => "🚨",
+                    Level::Warning => "⚠️",
+                    Level::Info => "ℹ️",
+                };
+
+                writeln!(
+                    output,
+                    "{h}## {level_emoji} Issue #{}: {}",
+                    i + 1,
+                    issue.constraint_name
+                )
+                .unwrap();
+                writeln!(output).unwrap();
+                writeln!(output, "- **Check:** {}", issue.check_name).unwrap();
+                if let Some(description) = report.check_description(&issue.check_name) {
+                    writeln!(output, "- **Check Description:** {description}").unwrap();
+                }
+                writeln!(output, "- **Level:** {:?}", issue.level).unwrap();
+                writeln!(output, "- **Message:** {}", issue.message).unwrap();
+                if let Some(known_issue) = &issue.known_issue {
+                    writeln!(
+                        output,
+                        "- **Known Issue:** {}",
+                        known_issue_text(known_issue)
+                    )
+                    .unwrap();
+                }
+                if !issue.impacts.is_empty() {
+                    writeln!(output, "- **Impacts:** {}", issue.impacts.join(", ")).unwrap();
+                }
+                if let Some(flakiness) = issue_flakiness(report, issue) {
+                    writeln!(output, "- **Flakiness:** {flakiness}").unwrap();
+                }
+
+                if let Some(metric) = issue.metric {
+                    writeln!(output, "- **Metric:** {metric:.3}").unwrap();
+                }
+
+                writeln!(output).unwrap();
+            }
+
+            if issues.len() > issues_to_show.len() {
+                writeln!(
+                    output,
+                    "> **Note:** {} additional issues not shown in this report.",
+                    issues.len() - issues_to_show.len()
+                )
+                .unwrap();
+                writeln!(output).unwrap();
+            }
+        }
+
+        // Observations
+        if config.include_issues && !observations.is_empty() {
+            writeln!(output).unwrap();
+            writeln!(output, "{h}# Observations").unwrap();
+            writeln!(output).unwrap();
+
+            let observations_to_show = if config.max_issues < 0 {
+                observations.as_slice()
+            } else {
+                let max = config.max_issues as usize;
+                &observations[..std::cmp::min(max, observations.len())]
+            };
+
+            for observation in observations_to_show {
+                write!(
+                    output,
+                    "- ℹ️ **{}** ({}): {}",
+                    observation.constraint_name, observation.check_name, observation.message
+                )
+                .unwrap();
+                if let Some(metric) = observation.metric {
+                    write!(output, " (metric: {metric:.3})").unwrap();
+                }
+                writeln!(output).unwrap();
+            }
+
+            if observations.len() > observations_to_show.len() {
+                writeln!(output).unwrap();
+                writeln!(
+                    output,
+                    "> **Note:** {} additional observations not shown in this report.",
+                    observations.len() - observations_to_show.len()
+                )
+                .unwrap();
+            }
+            writeln!(output).unwrap();
+        }
+
+        Ok(output)
+    }
+}
+
+/// One row of the summary statistics of a report.
+struct SummaryRow {
+    /// Icon the human formatter prints before the label
+    icon: &'static str,
+    label: &'static str,
+    value: String,
+    /// ANSI color the human formatter uses for the value, if any
+    color: Option<&'static str>,
+}
+
+/// The summary statistics shared by the human and Markdown formatters.
+///
+/// Counts are per check, as recorded in the report's metrics, see
+/// [`CheckStatus`](crate::core::CheckStatus). Info findings and checks waived by
+/// known issues are only listed when there are any.
+fn summary_rows(report: &ValidationReport) -> Vec<SummaryRow> {
+    let metrics = &report.metrics;
+    let row = |icon, label, value: usize, color| SummaryRow {
+        icon,
+        label,
+        value: value.to_string(),
+        color,
+    };
+    let mut rows = vec![
+        row("", "Total Checks", metrics.total_checks, None),
+        row("✅ ", "Passed", metrics.passed_checks, Some("32")),
+        row("❌ ", "Failed", metrics.failed_checks, Some("31")),
+        row("⏭️  ", "Skipped", metrics.skipped_checks, Some("33")),
+    ];
+    if metrics.info_findings > 0 {
+        rows.push(row("ℹ️  ", "Info Findings", metrics.info_findings, None));
+    }
+    if metrics.waived_checks > 0 {
+        rows.push(row("🔕 ", "Known Issues", metrics.waived_checks, None));
+    }
+    rows.push(SummaryRow {
+        icon: "",
+        label: "Success Rate",
+        value: format!("{:.1}%", metrics.success_rate()),
+        color: None,
+    });
+    rows.push(SummaryRow {
+        icon: "",
+        label: "Execution Time",
+        value: format!("{}ms", metrics.execution_time_ms),
+        color: None,
+    });
+    rows
+}
+
+/// Describes the known issue waiving a failure.
+fn known_issue_text(known_issue: &KnownIssue) -> String {
+    match known_issue.until {
+        Some(until) => format!(
+            "{} (waived until {})",
+            known_issue.ticket,
+            until.to_rfc3339()
+        ),
+        None => format!("{} (waived)", known_issue.ticket),
+    }
+}
+
+/// Writes the changes since the previous run, leaving out constraints that
+/// passed both times.
+fn write_delta(output: &mut String, delta: &ReportDelta) {
+    writeln!(output).unwrap();
+    writeln!(
+        output,
+        "🔄 Changes Since Previous Run ({}):",
+        delta.previous_timestamp
+    )
+    .unwrap();
+    writeln!(output, "   {}", delta.summary()).unwrap();
+    for change in &delta.changes {
+        let icon = match change.kind {
+            DeltaKind::NewlyFailing | DeltaKind::StillFailing => "❌",
+            DeltaKind::NewlyPassing => "✅",
+            DeltaKind::Added => "➕",
+            DeltaKind::Removed => "➖",
+            DeltaKind::StillPassing => continue,
+        };
+        write!(
+            output,
+            "   {icon} {}: {} / {}",
+            change.kind, change.check_name, change.constraint_name
+        )
+        .unwrap();
+        if change.previous_check_name.is_some() || change.previous_constraint_name.is_some() {
+            write!(
+                output,
+                " (was {} / {})",
+                change
+                    .previous_check_name
+                    .as_deref()
+                    .unwrap_or(&change.check_name),
+                change
+                    .previous_constraint_name
+                    .as_deref()
+                    .unwrap_or(&change.constraint_name)
+            )
+            .unwrap();
+        }
+        match (change.previous_metric, change.current_metric) {
+            (Some(previous), Some(current)) => write!(
+                output,
+                " (metric {previous:.4} → {current:.4}, {:+.4})",
+                current - previous
+            )
+            .unwrap(),
+            (None, Some(metric)) | (Some(metric), None) => {
+                write!(output, " (metric {metric:.4})").unwrap()
+            }
+            (None, None) => {}
+        }
+        writeln!(output).unwrap();
+    }
+}
+
+/// Moves Info-level issues of a serialized result into a separate `observations` list.
+///
+/// [`ValidationResult::from_json_str`] merges them back into the report's issues.
+fn split_observations(value: &mut serde_json::Value) {
+    let Some(report) = value.get_mut("report").and_then(|r| r.as_object_mut()) else {
+        return;
+    };
+    let Some(serde_json::Value::Array(issues)) = report.get_mut("issues") else {
+        return;
+    };
+
+    let (observations, remaining): (Vec<_>, Vec<_>) =
+        std::mem::take(issues).into_iter().partition(|issue| {
+            issue.get("level").and_then(|level| level.as_str()) == Some(Level::Info.as_str())
+        });
+    *issues = remaining;
+    if !observations.is_empty() {
+        report.insert(
+            "observations".to_string(),
+            serde_json::Value::Array(observations),
+        );
+    }
+}
+
+/// Helper function to filter validation result based on configuration.
+fn filter_result_for_config(
+    result: &ValidationResult,
+    config: &FormatterConfig,
+) -> ValidationResult {
+    match result {
+        ValidationResult::Success { metrics, report } => {
+            let filtered_report = filter_report(report, config);
+            let filtered_metrics = if config.include_metrics {
+                metrics.clone()
+            } else {
+                let mut minimal_metrics = metrics.clone();
+                if !config.include_custom_metrics {
+                    minimal_metrics.custom_metrics.clear();
+                }
+                minimal_metrics
+            };
+            ValidationResult::Success {
+                metrics: filtered_metrics,
+                report: filtered_report,
+            }
+        }
+        ValidationResult::Failure { report } => ValidationResult::Failure {
+            report: filter_report(report, config),
+        },
+    }
+}
+
+/// Returns the recent flakiness of the constraint of an issue, if the report
+/// was annotated with it.
+fn issue_flakiness<'a>(
+    report: &'a ValidationReport,
+    issue: &ValidationIssue,
+) -> Option<&'a RecentFlakiness> {
+    report.flakiness.get(issue.constraint_id.as_deref()?)
+}
+
+/// Helper function to filter validation report based on configuration.
+fn filter_report(report: &ValidationReport, config: &FormatterConfig) -> ValidationReport {
+    let mut filtered_report = report.clone();
+
+    if !config.include_issues {
+        filtered_report.issues.clear();
+    } else if config.max_issues >= 0 {
+        let max = config.max_issues as usize;
+        filtered_report.issues.truncate(max);
+    }
+
+    if !config.include_custom_metrics {
+        filtered_report.metrics.custom_metrics.clear();
+    }
+
+    if !config.include_timestamps {
+        filtered_report.timestamp = String::new();
+    }
+
+    filtered_report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{CheckStatus, Level, ValidationIssue, ValidationMetrics, ValidationReport};
+
+    fn create_test_result() -> ValidationResult {
+        let mut metrics = ValidationMetrics::new();
+        metrics.total_checks = 10;
+        metrics.passed_checks = 8;
+        metrics.failed_checks = 2;
+        metrics.skipped_checks = 0;
+        metrics.execution_time_ms = 150;
+        metrics
+            .custom_metrics
+            .insert("data.completeness".to_string(), 0.85);
+
+        let mut report = ValidationReport::new("test_suite");
+        report.add_issue(ValidationIssue {
+            check_name: "completeness_check".to_string(),
+            constraint_name: "completeness".to_string(),
+            level: Level::Error,
+            message: "Column has insufficient completeness".to_string(),
+            metric: Some(0.75),
+            cached: false,
+            known_issue: None,
+            check_id: None,
+            constraint_id: None,
+            impacts: Vec::new(),
+        });
+
+        report.add_issue(ValidationIssue {
+            check_name: "size_check".to_string(),
+            constraint_name: "size".to_string(),
+            level: Level::Warning,
+            message: "Dataset size is below expected range".to_string(),
+            metric: Some(150.0),
+            cached: false,
+            known_issue: None,
+            check_id: None,
+            constraint_id: None,
+            impacts: Vec::new(),
+        });
+
+        report.metrics = metrics.clone();
+        ValidationResult::failure(report)
+    }
+
+    #[test]
+    fn test_formatter_config() {
+        let config = FormatterConfig::default();
+        assert!(config.include_metrics);
+        assert!(config.include_issues);
+        assert!(config.use_colors);
+
+        let minimal = FormatterConfig::minimal();
+        assert!(minimal.include_metrics);
+        assert!(!minimal.include_issues);
+        assert!(!minimal.use_colors);
+
+        let ci = FormatterConfig::ci();
+        assert!(!ci.use_colors);
+        assert_eq!(ci.max_issues, 50);
+    }
+
+    #[test]
+    fn test_json_formatter() {
+        let result = create_test_result();
+        let formatter = JsonFormatter::new();
+
+        let output = formatter.format(&result).unwrap();
+        assert!(output.contains("\"status\": \"failure\""));
+        assert!(output.contains("\"test_suite\""));
+        assert!(output.contains("completeness_check"));
+
+        // Test with config
+        let config = FormatterConfig::minimal();
+        let output = formatter.format_with_config(&result, &config).unwrap();
+        assert!(output.contains("\"status\": \"failure\""));
+    }
+
+    #[test]
+    fn test_human_formatter() {
+        let result = create_test_result();
+        let formatter = HumanFormatter::new();
+
+        let output = formatter.format(&result).unwrap();
+        assert!(output.contains("Validation FAILED"));
+        assert!(output.contains("test_suite"));
+        assert!(output.contains("Total Checks: 10"));
+        assert!(output.contains("completeness_check"));
+
+        // Test without colors
+        let config = FormatterConfig::default().with_colors(false);
+        let output = formatter.format_with_config(&result, &config).unwrap();
+        assert!(output.contains("Validation FAILED"));
+        assert!(!output.contains("\x1b["));
+    }
+
+    #[test]
+    fn test_markdown_formatter() {
+        let result = create_test_result();
+        let formatter = MarkdownFormatter::new();
+
+        let output = formatter.format(&result).unwrap();
+        assert!(output.contains("## ❌ Validation Report - FAILED"));
+        assert!(output.contains("**Suite:** test_suite"));
+        assert!(output.contains("| Total Checks | 10 |"));
+        assert!(output.contains("### 🚨 Issue #1: completeness"));
+
+        // Test with different heading level
+        let formatter = MarkdownFormatter::new().with_heading_level(1);
+        let output = formatter.format(&result).unwrap();
+        assert!(output.contains("# ❌ Validation Report - FAILED"));
+    }
+
+    #[test]
+    fn test_formatters_agree_on_check_counts() {
+        use crate::core::{ConstraintOutcome, ConstraintStatus};
+
+        let mut report = ValidationReport::new("test_suite");
+        for (check, level, status) in [
+            ("ids", Level::Error, ConstraintStatus::Failure),
+            ("ids", Level::Error, ConstraintStatus::Failure),
+            ("amounts", Level::Warning, ConstraintStatus::Success),
+            ("amounts", Level::Warning, ConstraintStatus::Skipped),
+            ("freshness", Level::Warning, ConstraintStatus::Skipped),
+            ("profile", Level::Info, ConstraintStatus::Failure),
+        ] {
+            report.outcomes.push(ConstraintOutcome {
+                check_name: check.to_string(),
+                constraint_name: "constraint".to_string(),
+                level,
+                status,
+                waived: false,
+                metric: None,
+                threshold: None,
+                dimension: None,
+                constraint_id: None,
+            });
+            if status == ConstraintStatus::Failure {
+                report.add_issue(ValidationIssue {
+                    check_name: check.to_string(),
+                    constraint_name: "constraint".to_string(),
+                    level,
+                    message: "failed".to_string(),
+                    metric: None,
+                    cached: false,
+                    known_issue: None,
+                    check_id: None,
+                    constraint_id: None,
+                    impacts: Vec::new(),
+                });
+            }
+        }
+        report.metrics = ValidationMetrics::from_outcomes(&report.outcomes);
+        let result = ValidationResult::from_report(report);
+        assert!(result.is_failure());
+
+        let config = FormatterConfig::default().with_colors(false);
+        let human = HumanFormatter::new()
+            .format_with_config(&result, &config)
+            .unwrap();
+        for line in [
+            "   Total Checks: 4",
+            "   ✅ Passed: 1",
+            "   ❌ Failed: 1",
+            "   ⏭️  Skipped: 1",
+            "   ℹ️  Info Findings: 1",
+            "   Success Rate: 50.0%",
+        ] {
+            assert!(human.contains(line), "{line}");
+        }
+
+        let markdown = MarkdownFormatter::new().format(&result).unwrap();
+        for row in [
+            "| Total Checks | 4 |",
+            "| Passed | 1 |",
+            "| Failed | 1 |",
+            "| Skipped | 1 |",
+            "| Info Findings | 1 |",
+            "| Success Rate | 50.0% |",
+        ] {
+            assert!(markdown.contains(row), "{row}");
+        }
+
+        let json: serde_json::Value =
+            serde_json::from_str(&JsonFormatter::new().format(&result).unwrap()).unwrap();
+        let metrics = &json["report"]["metrics"];
+        assert_eq!(metrics["total_checks"], 4);
+        assert_eq!(metrics["passed_checks"], 1);
+        assert_eq!(metrics["failed_checks"], 1);
+        assert_eq!(metrics["skipped_checks"], 1);
+        assert_eq!(metrics["info_findings"], 1);
+    }
+
+    #[test]
+    fn test_colored_summary() {
+        let output = HumanFormatter::new().format(&create_test_result()).unwrap();
+        assert!(output.contains("   ✅ Passed: \x1b[32m8\x1b[0m"));
+        assert!(output.contains("   ❌ Failed: \x1b[31m2\x1b[0m"));
+        assert!(output.contains("   Total Checks: 10\n"));
+    }
+
+    #[test]
+    fn test_config_max_issues() {
+        let result = create_test_result();
+        let config = FormatterConfig::default().with_max_issues(1);
+
+        let formatter = HumanFormatter::new();
+        let output = formatter.format_with_config(&result, &config).unwrap();
+        assert!(output.contains("Issue #1"));
+        assert!(output.contains("... and 1 more issues"));
+    }
+
+    #[test]
+    fn test_known_issue_rendering() {
+        let mut report = ValidationReport::new("test_suite");
+        report.add_issue(ValidationIssue {
+            check_name: "order_totals".to_string(),
+            constraint_name: "sum".to_string(),
+            level: Level::Error,
+            message: "sum 10 does not equal 12".to_string(),
+            metric: Some(10.0),
+            cached: false,
+            known_issue: Some(KnownIssue::new("JIRA-1234", None)),
+            check_id: None,
+            constraint_id: None,
+            impacts: Vec::new(),
+        });
+        report.metrics.record_check(CheckStatus::Waived);
+        assert!(!report.has_errors());
+        let result = ValidationResult::success(report.metrics.clone(), report);
+
+        let config = FormatterConfig::default().with_colors(false);
+        let output = HumanFormatter::new()
+            .format_with_config(&result, &config)
+            .unwrap();
+        assert!(output.contains("🔕 Known Issues: 1"));
+        assert!(output.contains("🔕 Issue #1: sum"));
+        assert!(output.contains("Known Issue: JIRA-1234 (waived)"));
+
+        let output = MarkdownFormatter::new().format(&result).unwrap();
+        assert!(output.contains("| Known Issues | 1 |"));
+        assert!(output.contains("### 🔕 Issue #1: sum"));
+        assert!(output.contains("- **Known Issue:** JIRA-1234 (waived)"));
+
+        let output = JsonFormatter::new()
+            .with_pretty(false)
+            .format(&result)
+            .unwrap();
+        assert!(output.contains("\"known_issue\":{\"ticket\":\"JIRA-1234\"}"));
+    }
+
+    #[test]
+    fn test_impacts_rendering() {
+        let mut report = ValidationReport::new("orders");
+        report.add_issue(ValidationIssue {
+            check_name: "amounts".to_string(),
+            constraint_name: "completeness".to_string(),
+            level: Level::Error,
+            message: "Completeness 0.9 is below 1.0".to_string(),
+            metric: Some(0.9),
+            cached: false,
+            known_issue: None,
+            check_id: None,
+            constraint_id: None,
+            impacts: vec![
+                "finance_daily_dashboard".to_string(),
+                "revenue_model".to_string(),
+            ],
+        });
+        let result = ValidationResult::failure(report);
+
+        let config = FormatterConfig::default().with_colors(false);
+        let output = HumanFormatter::new()
+            .format_with_config(&result, &config)
+            .unwrap();
+        assert!(output.contains("Impacts: finance_daily_dashboard, revenue_model"));
+
+        let output = MarkdownFormatter::new().format(&result).unwrap();
+        assert!(output.contains("- **Impacts:** finance_daily_dashboard, revenue_model"));
+
+        let output = JsonFormatter::new()
+            .with_pretty(false)
+            .format(&result)
+            .unwrap();
+        assert!(output.contains("\"impacts\":[\"finance_daily_dashboard\",\"revenue_model\"]"));
+
+        // Issues without lineage render no impacts
+        let output = HumanFormatter::new()
+            .format_with_config(&create_test_result(), &config)
+            .unwrap();
+        assert!(!output.contains("Impacts:"));
+    }
+
+    #[test]
+    fn test_flakiness_rendering() {
+        let mut report = ValidationReport::new("orders");
+        report.add_issue(ValidationIssue {
+            check_name: "arrivals".to_string(),
+            constraint_name: "completeness".to_string(),
+            level: Level::Error,
+            message: "Completeness 0.97 is below 0.99".to_string(),
+            metric: Some(0.97),
+            cached: false,
+            known_issue: None,
+            check_id: None,
+            constraint_id: Some("a1b2".to_string()),
+            impacts: Vec::new(),
+        });
+        report.flakiness.insert(
+            "a1b2".to_string(),
+            RecentFlakiness {
+                score: 0.5,
+                runs: 9,
+                failures: 4,
+                flips: 4,
+            },
+        );
+        let result = ValidationResult::failure(report);
+
+        let config = FormatterConfig::default().with_colors(false);
+        let output = HumanFormatter::new()
+            .format_with_config(&result, &config)
+            .unwrap();
+        assert!(output.contains("Flakiness: score 0.50 (4 flips, 4 failures in 9 runs)"));
+
+        let output = MarkdownFormatter::new().format(&result).unwrap();
+        assert!(output.contains("- **Flakiness:** score 0.50"));
+
+        // Reports that were not annotated render no flakiness
+        let output = HumanFormatter::new()
+            .format_with_config(&create_test_result(), &config)
+            .unwrap();
+        assert!(!output.contains("Flakiness:"));
+    }
+
+    #[test]
+    fn test_quality_score_rendering() {
+        use crate::core::{ConstraintOutcome, ConstraintStatus};
+
+        let mut result = create_test_result();
+        if let ValidationResult::Failure { report } = &mut result {
+            report.outcomes = vec![ConstraintOutcome {
+                check_name: "completeness_check".to_string(),
+                constraint_name: "completeness".to_string(),
+                level: Level::Error,
+                status: ConstraintStatus::Failure,
+                waived: false,
+                metric: Some(0.75),
+                threshold: Some(1.0),
+                dimension: None,
+                constraint_id: None,
+            }];
+        }
+        let config = FormatterConfig::default()
+            .with_colors(false)
+            .with_scorecard(Scorecard::new());
+
+        let output = HumanFormatter::new()
+            .format_with_config(&result, &config)
+            .unwrap();
+        assert!(output.contains("🏅 Quality Score: 75.0/100"));
+        assert!(output.contains("   completeness_check: 75.0 (weight 3)"));
+
+        let output = MarkdownFormatter::new()
+            .format_with_config(&result, &config)
+            .unwrap();
+        assert!(output.contains("### Quality Score: 75.0/100"));
+        assert!(output.contains("| completeness_check | Error | 3 | 75.0 |"));
+
+        let output = JsonFormatter::new()
+            .with_pretty(false)
+            .format_with_config(&result, &config)
+            .unwrap();
+        assert!(output.contains("\"quality_score\":{"));
+        assert!(output.contains("\"overall\":75.0"));
+        assert!(output.contains("\"methodology_version\":1"));
+
+        let output = HumanFormatter::new().format(&result).unwrap();
+        assert!(!output.contains("Quality Score"));
+    }
+
+    #[test]
+    fn test_report_delta_rendering() {
+        use crate::core::{ConstraintOutcome, ConstraintStatus};
+
+        let result = create_test_result();
+        let mut previous = ValidationReport::new("test_suite");
+        previous.timestamp = "2025-01-01T00:00:00Z".to_string();
+        for (check, constraint, metric) in [
+            ("completeness_check", "completeness", Some(0.9)),
+            ("old_check", "size", None),
+        ] {
+            previous.outcomes.push(ConstraintOutcome {
+                check_name: check.to_string(),
+                constraint_name: constraint.to_string(),
+                level: Level::Error,
+                status: ConstraintStatus::Success,
+                waived: false,
+                metric,
+                threshold: None,
+                dimension: None,
+                constraint_id: None,
+            });
+        }
+        let config = FormatterConfig::default()
+            .with_colors(false)
+            .with_previous_report(previous);
+
+        let output = HumanFormatter::new()
+            .format_with_config(&result, &config)
+            .unwrap();
+        let delta = output
+            .find("🔄 Changes Since Previous Run (2025-01-01T00:00:00Z):")
+            .unwrap();
+        assert!(delta < output.find("📊 Summary Statistics:").unwrap());
+        assert!(output.contains(
+            "   ❌ newly failing: completeness_check / completeness (metric 0.9000 → 0.7500, -0.1500)"
+        ));
+        assert!(output.contains("   ➕ added: size_check / size (metric 150.0000)"));
+        assert!(output.contains("   ➖ removed: old_check / size\n"));
+
+        let output = JsonFormatter::new()
+            .with_pretty(false)
+            .format_with_config(&result, &config)
+            .unwrap();
+        assert!(output.contains("\"changes_since_previous\":{"));
+        assert!(output.contains("\"kind\":\"newly_failing\""));
+
+        let output = HumanFormatter::new().format(&result).unwrap();
+        assert!(!output.contains("Changes Since Previous Run"));
+    }
+
+    #[test]
+    fn test_dimension_summary_rendering() {
+        use crate::core::{ConstraintOutcome, ConstraintStatus, QualityDimension};
+
+        let mut result = create_test_result();
+        if let ValidationResult::Failure { report } = &mut result {
+            for status in [ConstraintStatus::Success, ConstraintStatus::Failure] {
+                report.outcomes.push(ConstraintOutcome {
+                    check_name: "completeness_check".to_string(),
+                    constraint_name: "completeness".to_string(),
+                    level: Level::Error,
+                    status,
+                    waived: false,
+                    metric: None,
+                    threshold: None,
+                    dimension: Some(QualityDimension::Completeness),
+                    constraint_id: None,
+                });
+            }
+        }
+        let config = FormatterConfig::default().with_colors(false);
+
+        let output = HumanFormatter::new()
+            .format_with_config(&result, &config)
+            .unwrap();
+        assert!(output.contains("📐 Quality Dimensions:"));
+        assert!(output.contains("   completeness: 50.0% passed, 1 issue(s) in 2 constraint(s)"));
+
+        let output = MarkdownFormatter::new()
+            .format_with_config(&result, &config)
+            .unwrap();
+        assert!(output.contains("### Quality Dimensions"));
+        assert!(output.contains("| completeness | 2 | 1 | 1 | 0 | 1 | 50.0% |"));
+
+        let output = JsonFormatter::new()
+            .with_pretty(false)
+            .format_with_config(&result, &config)
+            .unwrap();
+        assert!(output.contains("\"dimension_summary\":[{"));
+        assert!(output.contains("\"dimension\":\"completeness\""));
+        assert!(output.contains("\"pass_rate\":50.0"));
+
+        let output = HumanFormatter::new()
+            .format_with_config(&result, &config.with_metrics(false))
+            .unwrap();
+        assert!(!output.contains("Quality Dimensions"));
+    }
+
+    #[test]
+    fn test_description_rendering() {
+        let mut result = create_test_result();
+        if let ValidationResult::Failure { report } = &mut result {
+            report.description = Some("Nightly customer export".to_string());
+            report.check_descriptions.insert(
+                "completeness_check".to_string(),
+                "Required fields are filled in".to_string(),
+            );
+        }
+        let config = FormatterConfig::default().with_colors(false);
+
+        let output = HumanFormatter::new()
+            .format_with_config(&result, &config)
+            .unwrap();
+        assert!(output.contains("Description: Nightly customer export"));
+        assert!(output.contains("      Check Description: Required fields are filled in"));
+        assert_eq!(output.matches("Check Description:").count(), 1);
+
+        let output = MarkdownFormatter::new()
+            .format_with_config(&result, &config)
+            .unwrap();
+        assert!(output.contains("**Description:** Nightly customer export"));
+        assert!(output.contains("- **Check Description:** Required fields are filled in"));
+
+        let output = JsonFormatter::new()
+            .with_pretty(false)
+            .format_with_config(&result, &config)
+            .unwrap();
+        assert!(output.contains("\"description\":\"Nightly customer export\""));
+        assert!(output.contains(
+            "\"check_descriptions\":{\"completeness_check\":\"Required fields are filled in\"}"
+        ));
+    }
+}
@@ -0,0 +1,436 @@
+//! SARIF output for code scanning integrations.
+//!
+//! [`SarifFormatter`] renders the issues of a validation run as a
+//! [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! log, the format code scanning services such as GitHub ingest. Every
+//! constraint type becomes a rule and every issue a result located in a
+//! configurable artifact, such as the dataset (`datasets/orders.parquet`) or
+//! the file defining the suite, at the line of the check when it is known.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::core::{Check, ValidationSuite};
+//! use term_guard::formatters::{ResultFormatter, SarifFormatter};
+//! # use term_guard::core::{Level, ValidationIssue, ValidationReport, ValidationResult};
+//!
+//! let suite = ValidationSuite::builder("orders")
+//!     .check(Check::builder("ids").validates_uniqueness(["order_id"], 1.0).build())
+//!     .build();
+//! # let mut report = ValidationReport::new("orders");
+//! # report.add_issue(ValidationIssue {
+//! #     check_name: "ids".to_string(),
+//! #     constraint_name: "uniqueness".to_string(),
+//! #     level: Level::Error,
+//! #     message: "order_id is not unique".to_string(),
+//! #     metric: Some(0.98),
+//! #     cached: false,
+//! #     known_issue: None,
+//! #     check_id: None,
+//! #     constraint_id: None,
+//! #     impacts: Vec::new(),
+//! # });
+//! # let result = ValidationResult::failure(report);
+//!
+//! let formatter = SarifFormatter::new("datasets/orders.parquet")
+//!     .with_suite(&suite)
+//!     .with_check_line("ids", 12);
+//! let sarif = formatter.format(&result).unwrap();
+//! assert!(sarif.contains("\"version\": \"2.1.0\""));
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Map, Value};
+
+use super::{filter_report, FormatterConfig, ResultFormatter};
+use crate::core::{Level, ValidationIssue, ValidationResult, ValidationSuite};
+use crate::prelude::*;
+
+/// The SARIF version written by [`SarifFormatter`].
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// The location of the SARIF 2.1.0 JSON schema.
+pub const SARIF_SCHEMA_URI: &str =
+    "https://docs.oasis-open.org/sarif/sarif/v2.1.0/errata01/os/schemas/sarif-schema-2.1.0.json";
+
+/// Default cap on the results reported per rule.
+pub const DEFAULT_MAX_RESULTS_PER_RULE: usize = 1000;
+
+/// Formats validation results as a SARIF 2.1.0 log.
+///
+/// Rules are identified by the constraint type, such as `completeness`, and
+/// described with [`Constraint::describe`](crate::core::Constraint::describe)
+/// for the constraints of the suite passed to [`with_suite`](Self::with_suite).
+/// Results take their level from the check: [`Level::Error`] becomes `error`,
+/// [`Level::Warning`] `warning` and [`Level::Info`] `note`. Failures waived by
+/// a known issue carry an accepted external suppression.
+///
+/// At most [`DEFAULT_MAX_RESULTS_PER_RULE`] results are reported per rule by
+/// default; the number left out is reported as a tool execution notification.
+#[derive(Debug, Clone)]
+pub struct SarifFormatter {
+    config: FormatterConfig,
+    artifact_uri: String,
+    check_lines: BTreeMap<String, u32>,
+    rule_descriptions: BTreeMap<String, Vec<String>>,
+    max_results_per_rule: usize,
+}
+
+impl SarifFormatter {
+    /// Creates a SARIF formatter locating every result in `artifact_uri`, a
+    /// path relative to the repository root such as `datasets/orders.parquet`.
+    pub fn new(artifact_uri: impl Into<String>) -> Self {
+        Self {
+            config: FormatterConfig::default(),
+            artifact_uri: artifact_uri.into(),
+            check_lines: BTreeMap::new(),
+            rule_descriptions: BTreeMap::new(),
+            max_results_per_rule: DEFAULT_MAX_RESULTS_PER_RULE,
+        }
+    }
+
+    /// Sets the formatting configuration; issues are filtered as by the other
+    /// formatters.
+    pub fn with_config(mut self, config: FormatterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Describes the rules with the constraints of `suite`.
+    pub fn with_suite(mut self, suite: &ValidationSuite) -> Self {
+        for check in suite.checks() {
+            for constraint in check.constraints() {
+                let descriptions = self
+                    .rule_descriptions
+                    .entry(constraint.name().to_string())
+                    .or_default();
+                let description = constraint.describe();
+                if !descriptions.contains(&description) {
+                    descriptions.push(description);
+                }
+            }
+        }
+        self
+    }
+
+    /// Locates the results of `check` at `line` of the artifact, typically the
+    /// line defining the check in the suite configuration file.
+    pub fn with_check_line(mut self, check: impl Into<String>, line: u32) -> Self {
+        self.check_lines.insert(check.into(), line.max(1));
+        self
+    }
+
+    /// Sets the cap on the results reported per rule.
+    pub fn with_max_results_per_rule(mut self, max: usize) -> Self {
+        self.max_results_per_rule = max;
+        self
+    }
+
+    /// Builds the SARIF log as a JSON value.
+    pub fn to_sarif(&self, result: &ValidationResult, config: &FormatterConfig) -> Value {
+        let report = filter_report(result.report(), config);
+
+        let mut issues_by_rule: BTreeMap<String, Vec<&ValidationIssue>> = self
+            .rule_descriptions
+            .keys()
+            .map(|rule| (rule.clone(), Vec::new()))
+            .collect();
+        for issue in &report.issues {
+            issues_by_rule
+                .entry(rule_id(issue).to_string())
+                .or_default()
+                .push(issue);
+        }
+
+        let mut rules = Vec::new();
+        let mut results = Vec::new();
+        let mut notifications = Vec::new();
+        for (rule_index, (rule, issues)) in issues_by_rule.iter().enumerate() {
+            rules.push(self.rule(rule, issues));
+            for issue in issues.iter().take(self.max_results_per_rule) {
+                results.push(self.result(&report.suite_name, rule, rule_index, issue));
+            }
+            let truncated = issues.len().saturating_sub(self.max_results_per_rule);
+            if truncated > 0 {
+                notifications.push(json!({
+                    "level": "warning",
+                    "message": {
+                        "text": format!(
+                            "{truncated} of {} results of rule '{rule}' were left out; at most {} results are reported per rule",
+                            issues.len(),
+                            self.max_results_per_rule
+                        )
+                    },
+                    "descriptor": { "id": rule },
+                }));
+            }
+        }
+
+        let mut invocation = json!({ "executionSuccessful": true });
+        if !notifications.is_empty() {
+            invocation["toolExecutionNotifications"] = Value::Array(notifications);
+        }
+        let mut properties = Map::new();
+        properties.insert("suite".to_string(), json!(report.suite_name));
+        if let Some(version) = &report.suite_version {
+            properties.insert("suiteVersion".to_string(), json!(version));
+        }
+        if let Some(run_id) = report.run_id {
+            properties.insert("runId".to_string(), json!(run_id.to_string()));
+        }
+
+        json!({
+            "$schema": SARIF_SCHEMA_URI,
+            "version": SARIF_VERSION,
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "term-guard",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                        "rules": rules,
+                    }
+                },
+                "invocations": [invocation],
+                "results": results,
+                "properties": properties,
+            }]
+        })
+    }
+
+    fn rule(&self, rule: &str, issues: &[&ValidationIssue]) -> Value {
+        let mut descriptor = json!({
+            "id": rule,
+            "name": rule,
+            "shortDescription": { "text": format!("Term {rule} constraint") },
+            "defaultConfiguration": {
+                "level": sarif_level(issues.iter().map(|issue| issue.level).max().unwrap_or_default())
+            },
+            "properties": { "tags": ["data-quality"] },
+        });
+        if let Some(descriptions) = self.rule_descriptions.get(rule) {
+            descriptor["fullDescription"] = json!({ "text": descriptions.join("\n") });
+        }
+        descriptor
+    }
+
+    fn result(
+        &self,
+        suite_name: &str,
+        rule: &str,
+        rule_index: usize,
+        issue: &ValidationIssue,
+    ) -> Value {
+        let mut physical_location = json!({ "artifactLocation": { "uri": self.artifact_uri } });
+        if let Some(line) = self.check_lines.get(&issue.check_name) {
+            physical_location["region"] = json!({ "startLine": line });
+        }
+
+        let mut properties = Map::new();
+        properties.insert("check".to_string(), json!(issue.check_name));
+        properties.insert("constraint".to_string(), json!(issue.constraint_name));
+        if let Some(metric) = issue.metric.filter(|metric| metric.is_finite()) {
+            properties.insert("metric".to_string(), json!(metric));
+        }
+        if let Some(check_id) = &issue.check_id {
+            properties.insert("checkId".to_string(), json!(check_id));
+        }
+        if !issue.impacts.is_empty() {
+            properties.insert("impacts".to_string(), json!(issue.impacts));
+        }
+
+        let mut result = json!({
+            "ruleId": rule,
+            "ruleIndex": rule_index,
+            "level": sarif_level(issue.level),
+            "message": { "text": issue.message },
+            "locations": [{
+                "physicalLocation": physical_location,
+                "logicalLocations": [{
+                    "name": issue.check_name,
+                    "fullyQualifiedName": format!("{suite_name}.{}", issue.check_name),
+                    "kind": "object",
+                }],
+            }],
+            "properties": properties,
+        });
+        if let Some(constraint_id) = &issue.constraint_id {
+            result["partialFingerprints"] = json!({ "termConstraintId/v1": constraint_id });
+        }
+        if let Some(known_issue) = &issue.known_issue {
+            let justification = match known_issue.until {
+                Some(until) => format!(
+                    "Known issue {} until {}",
+                    known_issue.ticket,
+                    until.to_rfc3339()
+                ),
+                None => format!("Known issue {}", known_issue.ticket),
+            };
+            result["suppressions"] = json!([{
+                "kind": "external",
+                "status": "accepted",
+                "justification": justification,
+            }]);
+        }
+        result
+    }
+}
+
+impl ResultFormatter for SarifFormatter {
+    fn format(&self, result: &ValidationResult) -> Result<String> {
+        self.format_with_config(result, &self.config)
+    }
+
+    fn format_with_config(
+        &self,
+        result: &ValidationResult,
+        config: &FormatterConfig,
+    ) -> Result<String> {
+        serde_json::to_string_pretty(&self.to_sarif(result, config))
+            .map_err(|e| TermError::Internal(format!("Failed to serialize SARIF log: {e}")))
+    }
+}
+
+/// Returns the rule of an issue: the constraint type, taken from the prefix of
+/// its constraint id, or the constraint name for issues without one.
+fn rule_id(issue: &ValidationIssue) -> &str {
+    issue
+        .constraint_id
+        .as_deref()
+        .and_then(|id| id.rsplit_once('-'))
+        .map_or(issue.constraint_name.as_str(), |(kind, _)| kind)
+}
+
+fn sarif_level(level: Level) -> &'static str {
+    match level {
+        Level::Error => "error",
+        Level::Warning => "warning",
+        Level::Info => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{KnownIssue, ValidationReport};
+
+    fn issue(check: &str, constraint_id: Option<&str>, level: Level) -> ValidationIssue {
+        ValidationIssue {
+            check_name: check.to_string(),
+            constraint_name: "label".to_string(),
+            level,
+            message: format!("{check} failed"),
+            metric: Some(0.5),
+            cached: false,
+            known_issue: None,
+            check_id: None,
+            constraint_id: constraint_id.map(str::to_string),
+            impacts: Vec::new(),
+        }
+    }
+
+    fn result(issues: Vec<ValidationIssue>) -> ValidationResult {
+        let mut report = ValidationReport::new("orders");
+        for issue in issues {
+            report.add_issue(issue);
+        }
+        ValidationResult::failure(report)
+    }
+
+    #[test]
+    fn test_rules_and_levels() {
+        let sarif = SarifFormatter::new("datasets/orders.parquet").to_sarif(
+            &result(vec![
+                issue("ids", Some("uniqueness-0011223344556677"), Level::Error),
+                issue("amounts", Some("has_min-8899aabbccddeeff"), Level::Info),
+                issue("legacy", None, Level::Warning),
+            ]),
+            &FormatterConfig::default(),
+        );
+        let run = &sarif["runs"][0];
+
+        let rules: Vec<_> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|rule| rule["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(rules, ["has_min", "label", "uniqueness"]);
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results[0]["ruleId"], "has_min");
+        assert_eq!(results[0]["ruleIndex"], 0);
+        assert_eq!(results[0]["level"], "note");
+        assert_eq!(results[1]["level"], "warning");
+        assert_eq!(results[2]["level"], "error");
+        assert_eq!(
+            results[2]["partialFingerprints"]["termConstraintId/v1"],
+            "uniqueness-0011223344556677"
+        );
+        assert!(results[1].get("partialFingerprints").is_none());
+        assert!(run["invocations"][0]
+            .get("toolExecutionNotifications")
+            .is_none());
+    }
+
+    #[test]
+    fn test_results_are_capped_per_rule() {
+        let issues = (0..5)
+            .map(|i| issue(&format!("check_{i}"), Some("completeness-00"), Level::Error))
+            .chain(std::iter::once(issue(
+                "ids",
+                Some("uniqueness-00"),
+                Level::Error,
+            )))
+            .collect();
+        let sarif = SarifFormatter::new("orders.csv")
+            .with_max_results_per_rule(2)
+            .to_sarif(&result(issues), &FormatterConfig::default());
+        let run = &sarif["runs"][0];
+
+        assert_eq!(run["results"].as_array().unwrap().len(), 3);
+        let notifications = run["invocations"][0]["toolExecutionNotifications"]
+            .as_array()
+            .unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0]["descriptor"]["id"], "completeness");
+        assert_eq!(
+            notifications[0]["message"]["text"],
+            "3 of 5 results of rule 'completeness' were left out; at most 2 results are reported per rule"
+        );
+    }
+
+    #[test]
+    fn test_locations_and_suppressions() {
+        let mut waived = issue("ids", Some("uniqueness-00"), Level::Error);
+        waived.known_issue = Some(KnownIssue::new("DQ-42", None));
+        let sarif = SarifFormatter::new("config/orders.yaml")
+            .with_check_line("ids", 7)
+            .to_sarif(
+                &result(vec![waived, issue("amounts", None, Level::Warning)]),
+                &FormatterConfig::default(),
+            );
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+
+        let location = &results[1]["locations"][0];
+        assert_eq!(
+            location["physicalLocation"]["artifactLocation"]["uri"],
+            "config/orders.yaml"
+        );
+        assert_eq!(location["physicalLocation"]["region"]["startLine"], 7);
+        assert_eq!(
+            location["logicalLocations"][0]["fullyQualifiedName"],
+            "orders.ids"
+        );
+        assert_eq!(
+            results[1]["suppressions"][0]["justification"],
+            "Known issue DQ-42"
+        );
+
+        // Checks without a known line have no region
+        assert!(results[0]["locations"][0]["physicalLocation"]
+            .get("region")
+            .is_none());
+    }
+}
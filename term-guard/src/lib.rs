@@ -132,12 +132,19 @@
 //!   - Type Inference Engine: Automatic data type detection with confidence scores
 //!   - Column Profiler: Three-pass algorithm for comprehensive column analysis
 //!   - Basic & Advanced Analyzers: Metrics computation (mean, entropy, correlation, etc.)
+//! - **`compliance`**: PII scanning helpers that generate compliance checks
 //! - **`core`**: Core types like `Check`, `ValidationSuite`, and `ConstraintResult`
 //! - **`constraints`**: All validation constraint implementations
 //! - **`sources`**: Data source connectors and loaders
+//! - **`migration`**: Conversion of Deequ checks, dbt tests and Great Expectations suites into suites (requires the `migration` feature)
+//! - **`streaming`**: Micro-batch validation of record batch streams (requires the `streaming` feature)
+//! - **`flight`**: Remote validation workers fed over Arrow Flight (requires the `flight` feature)
+//! - **`scheduler`**: Periodic validation runs inside a tokio service (requires the `scheduler` feature)
 //! - **`optimizer`**: Query optimization engine
+//! - **`scorecard`**: Weighted 0–100 quality scores computed from validation reports
 //! - **`telemetry`**: OpenTelemetry integration
 //! - **`formatters`**: Result formatting utilities
+//! - **`capabilities`**: Runtime discovery of the optional features the library was compiled with
 //!
 //! ## Examples
 //!
@@ -164,19 +171,40 @@
 //!     .validates_uniqueness(vec!["id"], 1.0)
 //!     .build();
 //! ```
+//!
+//! Checks exported from Deequ as JSON are converted into a suite by
+//! `migration::from_deequ_json`, which requires the `migration` feature. The
+//! same feature converts dbt `schema.yml` tests and Great Expectations suites
+//! with `migration::from_dbt_schema_yaml` and
+//! `migration::from_great_expectations_json`.
 
 pub mod analyzers;
+pub mod capabilities;
+pub mod compliance;
 pub mod constraints;
 pub mod core;
 pub mod error;
+#[cfg(feature = "flight")]
+pub mod flight;
 pub mod formatters;
 pub mod logging;
+#[cfg(feature = "migration")]
+pub mod migration;
 pub mod optimizer;
 pub mod prelude;
 pub mod repository;
+#[cfg(feature = "scheduler")]
+pub mod scheduler;
+pub mod scorecard;
 pub mod security;
 pub mod sources;
+#[cfg(feature = "streaming")]
+pub mod streaming;
 pub mod telemetry;
+#[cfg(feature = "udfs")]
+pub mod udfs;
+
+pub use capabilities::{capabilities, Capabilities};
 
 #[cfg(test)]
 pub mod test_helpers;
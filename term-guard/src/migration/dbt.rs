@@ -0,0 +1,543 @@
+//! Conversion of the tests declared in dbt `schema.yml` files.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+
+use super::{warnings_report, MigrationWarning};
+use crate::constraints::{
+    CompletenessConstraint, ContainmentConstraint, ForeignKeyConstraint, UniquenessConstraint,
+};
+use crate::core::{Check, CheckBuilder, ConfigReport, Constraint, Level, ValidationSuite};
+use crate::error::{Result, TermError};
+
+/// A `ref()` or `source()` call naming the parent of a relationships test,
+/// such as `ref('customers')` or `source('raw', 'customers')`.
+static RELATION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(ref|source)\s*\((.*)\)\s*$").expect("valid relation pattern"));
+
+/// Configs that change which rows a test looks at or when it fails, which
+/// Term constraints cannot express.
+const UNSUPPORTED_CONFIGS: [&str; 3] = ["where", "error_if", "warn_if"];
+
+/// Suites converted from the tests of a dbt `schema.yml` file.
+#[derive(Debug)]
+pub struct DbtMigration {
+    /// One suite per model, seed, snapshot or source table with tests, paired
+    /// with the name of the table it validates
+    pub suites: Vec<(String, ValidationSuite)>,
+    /// The tests left out of the suites
+    pub warnings: Vec<MigrationWarning>,
+}
+
+impl DbtMigration {
+    /// Lists the warnings as a report with paths into the schema file.
+    pub fn config_report(&self) -> ConfigReport {
+        warnings_report("dbt schema".to_string(), &self.warnings)
+    }
+}
+
+/// Converts the tests of a dbt `schema.yml` file into [`ValidationSuite`]s.
+///
+/// Every model, seed, snapshot and source table with tests becomes a suite
+/// named after it and validating the table of the same name; source tables
+/// use their `identifier` if they have one. Column tests become a check named
+/// after the column, model tests a check named after the model. Tests with
+/// `severity: warn` go to a separate check of [`Level::Warning`] with
+/// ` (warn)` appended to its name.
+///
+/// dbt's built-in generic tests are converted:
+///
+/// | dbt | Term |
+/// |-----|------|
+/// | `not_null` | [`CompletenessConstraint`] |
+/// | `unique` | [`UniquenessConstraint`] with full uniqueness |
+/// | `accepted_values` | [`ContainmentConstraint`] |
+/// | `relationships` | [`ForeignKeyConstraint`] allowing NULL keys |
+///
+/// Tests are read from `tests` and `data_tests`, with their arguments either
+/// inline or under `arguments`, and their severity either inline or under
+/// `config`. The parent of a relationships test must be a `ref()` or
+/// `source()` call; it is validated as the table of the same name.
+///
+/// Other tests, such as singular tests or tests from packages like
+/// `dbt_utils`, and tests configured with `where`, `error_if` or `warn_if`
+/// are left out and reported in [`DbtMigration::warnings`].
+///
+/// # Errors
+///
+/// Returns [`TermError::Parse`] if the input is not a YAML properties file.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::migration::from_dbt_schema_yaml;
+///
+/// let migration = from_dbt_schema_yaml(
+///     r#"
+/// version: 2
+/// models:
+///   - name: orders
+///     columns:
+///       - name: order_id
+///         tests: [unique, not_null]
+///       - name: status
+///         tests:
+///           - accepted_values:
+///               values: ['placed', 'shipped', 'completed']
+///               config:
+///                 severity: warn
+///           - dbt_utils.not_empty_string
+/// "#,
+/// )?;
+///
+/// let (table, suite) = &migration.suites[0];
+/// assert_eq!(table, "orders");
+/// assert_eq!(suite.checks()[0].constraints().len(), 2);
+/// assert_eq!(suite.checks()[1].name(), "status (warn)");
+/// assert_eq!(migration.warnings[0].constraint, "dbt_utils.not_empty_string");
+/// # Ok::<(), term_guard::error::TermError>(())
+/// ```
+pub fn from_dbt_schema_yaml(yaml: &str) -> Result<DbtMigration> {
+    let schema: DbtSchema = serde_yaml::from_str(yaml)
+        .map_err(|e| TermError::Parse(format!("Invalid dbt schema file: {e}")))?;
+
+    // Relationships may point at source tables declared anywhere in the file
+    let sources: HashMap<(&str, &str), &str> = schema
+        .sources
+        .iter()
+        .flat_map(|source| {
+            source.tables.iter().map(move |table| {
+                (
+                    (source.name.as_str(), table.name.as_str()),
+                    table.table_name(),
+                )
+            })
+        })
+        .collect();
+
+    let tables = indexed("models", &schema.models)
+        .chain(indexed("seeds", &schema.seeds))
+        .chain(indexed("snapshots", &schema.snapshots))
+        .chain(
+            schema
+                .sources
+                .iter()
+                .enumerate()
+                .flat_map(|(index, source)| {
+                    indexed("tables", &source.tables)
+                        .map(move |(path, table)| (format!("sources[{index}].{path}"), table))
+                }),
+        );
+
+    let mut migration = DbtMigration {
+        suites: Vec::new(),
+        warnings: Vec::new(),
+    };
+    for (path, table) in tables {
+        if let Some(suite) = table.to_suite(&path, &sources, &mut migration.warnings) {
+            migration
+                .suites
+                .push((table.table_name().to_string(), suite));
+        }
+    }
+    Ok(migration)
+}
+
+/// Pairs every item of a list with its path, such as `models[0]`.
+fn indexed<'a, T>(key: &'a str, items: &'a [T]) -> impl Iterator<Item = (String, &'a T)> + 'a {
+    items
+        .iter()
+        .enumerate()
+        .map(move |(index, item)| (format!("{key}[{index}]"), item))
+}
+
+#[derive(Deserialize)]
+struct DbtSchema {
+    #[serde(default)]
+    models: Vec<DbtTable>,
+    #[serde(default)]
+    seeds: Vec<DbtTable>,
+    #[serde(default)]
+    snapshots: Vec<DbtTable>,
+    #[serde(default)]
+    sources: Vec<DbtSource>,
+}
+
+#[derive(Deserialize)]
+struct DbtSource {
+    name: String,
+    #[serde(default)]
+    tables: Vec<DbtTable>,
+}
+
+#[derive(Deserialize)]
+struct DbtTable {
+    name: String,
+    #[serde(default)]
+    identifier: Option<String>,
+    #[serde(default)]
+    tests: Vec<Value>,
+    #[serde(default)]
+    data_tests: Vec<Value>,
+    #[serde(default)]
+    columns: Vec<DbtColumn>,
+}
+
+#[derive(Deserialize)]
+struct DbtColumn {
+    name: String,
+    #[serde(default)]
+    tests: Vec<Value>,
+    #[serde(default)]
+    data_tests: Vec<Value>,
+}
+
+impl DbtTable {
+    fn table_name(&self) -> &str {
+        self.identifier.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Converts the tests of the table at `path` in the schema file.
+    fn to_suite(
+        &self,
+        path: &str,
+        sources: &HashMap<(&str, &str), &str>,
+        warnings: &mut Vec<MigrationWarning>,
+    ) -> Option<ValidationSuite> {
+        let table = self.table_name();
+        let tests = indexed("tests", &self.tests)
+            .chain(indexed("data_tests", &self.data_tests))
+            .map(|(test_path, test)| (None, format!("{path}.{test_path}"), test))
+            .chain(
+                indexed("columns", &self.columns).flat_map(|(column_path, column)| {
+                    indexed("tests", &column.tests)
+                        .chain(indexed("data_tests", &column.data_tests))
+                        .map(move |(test_path, test)| {
+                            (
+                                Some(column.name.as_str()),
+                                format!("{path}.{column_path}.{test_path}"),
+                                test,
+                            )
+                        })
+                }),
+            );
+
+        let location = |column: Option<&str>| match column {
+            Some(column) => format!("{table}.{column}"),
+            None => table.to_string(),
+        };
+        let mut checks: Vec<(String, Level, Vec<Arc<dyn Constraint>>)> = Vec::new();
+        let mut has_tests = false;
+        for (column, test_path, value) in tests {
+            has_tests = true;
+            let test = match DbtTest::parse(value) {
+                Ok(test) => test,
+                Err(reason) => {
+                    warnings.push(
+                        MigrationWarning::new(location(column), "test", reason).at(&test_path),
+                    );
+                    continue;
+                }
+            };
+            let column = test.column_name().or(column);
+            let location = location(column);
+
+            let level = match test.severity() {
+                Ok(level) => level,
+                Err(reason) => {
+                    warnings
+                        .push(MigrationWarning::new(&location, &test.name, reason).at(&test_path));
+                    Level::Error
+                }
+            };
+            let constraint = match test.to_constraint(table, column, sources) {
+                Ok(constraint) => constraint,
+                Err(reason) => {
+                    warnings
+                        .push(MigrationWarning::new(&location, &test.name, reason).at(&test_path));
+                    continue;
+                }
+            };
+
+            let mut name = column.unwrap_or(&self.name).to_string();
+            if level == Level::Warning {
+                name.push_str(" (warn)");
+            }
+            match checks.iter_mut().find(|(check, _, _)| *check == name) {
+                Some((_, _, constraints)) => constraints.push(constraint),
+                None => checks.push((name, level, vec![constraint])),
+            }
+        }
+        if !has_tests {
+            return None;
+        }
+
+        let mut suite = ValidationSuite::builder(&self.name).table_name(table);
+        for (name, level, constraints) in checks {
+            let check = constraints.into_iter().fold(
+                Check::builder(name).level(level),
+                CheckBuilder::arc_constraint,
+            );
+            suite = suite.check(check.build());
+        }
+        Some(suite.build())
+    }
+}
+
+/// A generic test applied to a model or column.
+struct DbtTest {
+    name: String,
+    arguments: Mapping,
+    config: Mapping,
+}
+
+impl DbtTest {
+    /// Reads a test written as its name, as `{name: {arguments}}` or with its
+    /// name under `test_name`.
+    fn parse(value: &Value) -> std::result::Result<Self, String> {
+        let (name, mut arguments) = match value {
+            Value::String(name) => (name.clone(), Mapping::new()),
+            Value::Mapping(mapping) if mapping.contains_key("test_name") => {
+                let mut arguments = mapping.clone();
+                let name = arguments.remove("test_name").unwrap_or_default();
+                (scalar(&name)?, arguments)
+            }
+            Value::Mapping(mapping) if mapping.len() == 1 => {
+                let (name, arguments) = mapping.iter().next().expect("one entry");
+                let arguments = match arguments {
+                    Value::Mapping(arguments) => arguments.clone(),
+                    Value::Null => Mapping::new(),
+                    _ => {
+                        return Err(format!(
+                            "the arguments of {} are not a mapping",
+                            scalar(name)?
+                        ))
+                    }
+                };
+                (scalar(name)?, arguments)
+            }
+            _ => return Err("unrecognized test definition".to_string()),
+        };
+
+        let config = match arguments.remove("config") {
+            Some(Value::Mapping(config)) => config,
+            _ => Mapping::new(),
+        };
+        // dbt 1.10 moved the arguments of generic tests under `arguments`
+        if let Some(Value::Mapping(nested)) = arguments.remove("arguments") {
+            arguments.extend(nested);
+        }
+        Ok(Self {
+            name,
+            arguments,
+            config,
+        })
+    }
+
+    /// Returns a setting that may be given as an argument or a config.
+    fn setting(&self, key: &str) -> Option<&Value> {
+        self.config.get(key).or_else(|| self.arguments.get(key))
+    }
+
+    fn column_name(&self) -> Option<&str> {
+        self.arguments.get("column_name").and_then(Value::as_str)
+    }
+
+    fn severity(&self) -> std::result::Result<Level, String> {
+        match self.setting("severity").and_then(Value::as_str) {
+            None => Ok(Level::Error),
+            Some(severity) if severity.eq_ignore_ascii_case("error") => Ok(Level::Error),
+            Some(severity) if severity.eq_ignore_ascii_case("warn") => Ok(Level::Warning),
+            Some(severity) => Err(format!("unknown severity '{severity}', using error")),
+        }
+    }
+
+    fn argument(&self, key: &str) -> std::result::Result<&Value, String> {
+        self.arguments
+            .get(key)
+            .ok_or_else(|| format!("{} needs a '{key}' argument", self.name))
+    }
+
+    fn to_constraint(
+        &self,
+        table: &str,
+        column: Option<&str>,
+        sources: &HashMap<(&str, &str), &str>,
+    ) -> std::result::Result<Arc<dyn Constraint>, String> {
+        if let Some(config) = UNSUPPORTED_CONFIGS
+            .iter()
+            .find(|config| self.setting(config).is_some())
+        {
+            return Err(format!(
+                "tests configured with '{config}' have no Term equivalent"
+            ));
+        }
+        let builtin = self.name.strip_prefix("dbt.").unwrap_or(&self.name);
+        if !matches!(
+            builtin,
+            "not_null" | "unique" | "accepted_values" | "relationships"
+        ) {
+            return Err(format!("{} is not a built-in dbt test", self.name));
+        }
+        let column = column.ok_or_else(|| format!("{} needs a column", self.name))?;
+
+        let constraint: Arc<dyn Constraint> = match builtin {
+            "not_null" => Arc::new(CompletenessConstraint::complete(column)),
+            "unique" => Arc::new(
+                UniquenessConstraint::full_uniqueness(column, 1.0).map_err(|e| e.to_string())?,
+            ),
+            "accepted_values" => {
+                let values = match self.argument("values")? {
+                    Value::Sequence(values) => values
+                        .iter()
+                        .map(scalar)
+                        .collect::<std::result::Result<Vec<_>, _>>()?,
+                    _ => return Err("the values of accepted_values are not a list".to_string()),
+                };
+                Arc::new(ContainmentConstraint::new(column, values))
+            }
+            _ => {
+                let to = scalar(self.argument("to")?)?;
+                let field = scalar(self.argument("field")?)?;
+                let parent = parent_table(&to, sources)?;
+                Arc::new(
+                    ForeignKeyConstraint::new(
+                        format!("{table}.{column}"),
+                        format!("{parent}.{field}"),
+                    )
+                    .allow_nulls(true),
+                )
+            }
+        };
+        Ok(constraint)
+    }
+}
+
+/// Returns the table named by a `ref()` or `source()` call.
+fn parent_table(
+    relation: &str,
+    sources: &HashMap<(&str, &str), &str>,
+) -> std::result::Result<String, String> {
+    let captures = RELATION
+        .captures(relation)
+        .ok_or_else(|| format!("the parent '{relation}' is not a ref() or source() call"))?;
+    // Keyword arguments such as `v=2` select versions, not tables
+    let arguments: Vec<&str> = captures[2]
+        .split(',')
+        .map(|argument| argument.trim())
+        .filter(|argument| !argument.contains('='))
+        .map(|argument| argument.trim_matches(|c| c == '\'' || c == '"'))
+        .collect();
+    match (&captures[1], arguments.as_slice()) {
+        ("ref", [.., model]) if !model.is_empty() => Ok(model.to_string()),
+        ("source", [source, table]) => Ok(sources
+            .get(&(*source, *table))
+            .copied()
+            .unwrap_or(*table)
+            .to_string()),
+        _ => Err(format!("cannot read the parent table of '{relation}'")),
+    }
+}
+
+/// Renders a scalar YAML value as a string.
+fn scalar(value: &Value) -> std::result::Result<String, String> {
+    match value {
+        Value::String(value) => Ok(value.clone()),
+        Value::Number(value) => Ok(value.to_string()),
+        Value::Bool(value) => Ok(value.to_string()),
+        other => Err(format!("expected a single value, found {other:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test(yaml: &str) -> DbtTest {
+        DbtTest::parse(&serde_yaml::from_str(yaml).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_test_definitions() {
+        let not_null = test("not_null");
+        assert_eq!(not_null.name, "not_null");
+        assert!(not_null.arguments.is_empty());
+
+        let accepted =
+            test("accepted_values: {values: [1, 2], severity: warn, config: {where: 'id > 1'}}");
+        assert_eq!(accepted.name, "accepted_values");
+        assert_eq!(accepted.severity(), Ok(Level::Warning));
+        assert!(accepted.setting("where").is_some());
+
+        let nested = test(
+            "{test_name: relationships, name: customer_fk, arguments: {to: \"ref('customers')\", field: id}}",
+        );
+        assert_eq!(nested.name, "relationships");
+        assert_eq!(nested.arguments.get("field"), Some(&Value::from("id")));
+        assert!(!nested.arguments.contains_key("test_name"));
+
+        assert_eq!(
+            test("unique: {config: {severity: fatal}}").severity(),
+            Err("unknown severity 'fatal', using error".to_string())
+        );
+        assert!(DbtTest::parse(&Value::Bool(true)).is_err());
+    }
+
+    #[test]
+    fn test_parent_tables() {
+        let sources = HashMap::from([(("raw", "customers"), "raw_customers")]);
+        let parent = |relation: &str| parent_table(relation, &sources);
+
+        assert_eq!(parent("ref('customers')"), Ok("customers".to_string()));
+        assert_eq!(
+            parent("ref(\"shop\", \"orders\")"),
+            Ok("orders".to_string())
+        );
+        assert_eq!(parent("ref('orders', v=2)"), Ok("orders".to_string()));
+        assert_eq!(
+            parent("source('raw', 'customers')"),
+            Ok("raw_customers".to_string())
+        );
+        assert_eq!(
+            parent("source('raw', 'payments')"),
+            Ok("payments".to_string())
+        );
+        assert!(parent("customers").is_err());
+        assert!(parent("source('raw')").is_err());
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        assert!(matches!(
+            from_dbt_schema_yaml("models: 1"),
+            Err(TermError::Parse(_))
+        ));
+
+        let migration = from_dbt_schema_yaml(
+            "models:\n  - name: orders\n    tests:\n      - unique\n      - [not_null]\n",
+        )
+        .unwrap();
+        let warnings: Vec<_> = migration.warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            warnings,
+            [
+                "check 'orders', constraint unique: unique needs a column",
+                "check 'orders', constraint test: unrecognized test definition",
+            ]
+        );
+        assert!(migration.suites[0].1.checks().is_empty());
+
+        let paths: Vec<_> = migration
+            .config_report()
+            .issues
+            .into_iter()
+            .map(|issue| issue.path)
+            .collect();
+        assert_eq!(paths, ["models[0].tests[0]", "models[0].tests[1]"]);
+    }
+}
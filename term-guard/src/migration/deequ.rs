@@ -0,0 +1,493 @@
+//! Conversion of Deequ checks exported as JSON.
+
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{warnings_report, MigrationWarning};
+use crate::constraints::{
+    Assertion, CompletenessConstraint, ContainmentConstraint, CustomSqlConstraint,
+    FormatConstraint, SizeConstraint, StatisticType, StatisticalConstraint, UniquenessConstraint,
+    UniquenessOptions, UniquenessType,
+};
+use crate::core::{Check, ConfigReport, Constraint, Level, ValidationSuite};
+use crate::error::{Result, TermError};
+
+/// Name of the suite when the definitions do not name one.
+const DEFAULT_SUITE_NAME: &str = "deequ_migration";
+
+/// An assertion lambda comparing its argument with a number, such as `_ >= 0.95`
+/// or `x => x == 1.0`.
+static ASSERTION_LAMBDA: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:\w+\s*=>\s*)?\w+\s*(==|!=|>=|<=|>|<)\s*(-?\d+(?:\.\d+)?(?:[eE][+-]?\d+)?)\s*$",
+    )
+    .expect("valid assertion lambda pattern")
+});
+
+/// A suite converted from Deequ checks.
+#[derive(Debug)]
+pub struct DeequMigration {
+    /// The converted suite, with one Term check per Deequ check
+    pub suite: ValidationSuite,
+    /// The constraints left out of the suite
+    pub warnings: Vec<MigrationWarning>,
+}
+
+impl DeequMigration {
+    /// Lists the warnings as a report with paths into the Deequ checks.
+    pub fn config_report(&self) -> ConfigReport {
+        warnings_report(
+            format!("Deequ suite '{}'", self.suite.name()),
+            &self.warnings,
+        )
+    }
+}
+
+/// Converts Deequ checks exported as JSON into a [`ValidationSuite`].
+///
+/// The input is either an array of checks or an object with the checks under
+/// `checks` and an optional suite `name`. Each check has a `description`, a
+/// `level` (`Error` or `Warning`, `Error` if absent) and a list of constraints
+/// named by `type` after the Deequ `Check` method that created them:
+///
+/// | Deequ | Term |
+/// |-------|------|
+/// | `isComplete`, `hasCompleteness` | [`CompletenessConstraint`] |
+/// | `isUnique`, `hasUniqueness` | [`UniquenessConstraint`] with full uniqueness |
+/// | `isContainedIn` with `allowedValues` | [`ContainmentConstraint`] |
+/// | `hasMin`, `hasMax`, `hasMean`, `hasSum`, `hasStandardDeviation` | [`StatisticalConstraint`] |
+/// | `hasPattern` | [`FormatConstraint::regex`] |
+/// | `hasSize` | [`SizeConstraint`] |
+/// | `satisfies` | [`CustomSqlConstraint`] with `constraintName` as hint |
+///
+/// Assertions are serialized either as an object such as
+/// `{"operator": ">=", "value": 0.95}` or `{"operator": "between", "lower": 1,
+/// "upper": 10}`, or as the source of a lambda comparing its argument with a
+/// number, such as `"_ >= 0.95"`. Term checks fractions of rows against a
+/// minimum, so assertions on them must be `>=`, `>` (read as `>=`) or `== 1.0`.
+/// Constraints taking an optional assertion in Deequ default to `== 1.0`.
+///
+/// Constraints of other types, with missing fields or with assertions Term
+/// cannot express are left out and reported in [`DeequMigration::warnings`].
+///
+/// # Errors
+///
+/// Returns [`TermError::Parse`] if the input is not JSON of the shape above.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::migration::from_deequ_json;
+///
+/// let migration = from_deequ_json(
+///     r#"{
+///         "name": "orders",
+///         "checks": [{
+///             "description": "order integrity",
+///             "level": "Error",
+///             "constraints": [
+///                 {"type": "isComplete", "column": "order_id"},
+///                 {"type": "hasCompleteness", "column": "email", "assertion": "_ >= 0.95"},
+///                 {"type": "isNonNegative", "column": "amount"}
+///             ]
+///         }]
+///     }"#,
+/// )?;
+///
+/// assert_eq!(migration.suite.checks()[0].constraints().len(), 2);
+/// assert_eq!(migration.warnings[0].constraint, "isNonNegative(amount)");
+/// # Ok::<(), term_guard::error::TermError>(())
+/// ```
+pub fn from_deequ_json(json: &str) -> Result<DeequMigration> {
+    let document: DeequDocument = serde_json::from_str(json)
+        .map_err(|e| TermError::Parse(format!("Invalid Deequ check definitions: {e}")))?;
+    let (name, checks) = match document {
+        DeequDocument::Suite { name, checks } => (name, checks),
+        DeequDocument::Checks(checks) => (None, checks),
+    };
+
+    let mut warnings = Vec::new();
+    let mut suite =
+        ValidationSuite::builder(name.unwrap_or_else(|| DEFAULT_SUITE_NAME.to_string()));
+    for (check_index, check) in checks.iter().enumerate() {
+        let level = match check.level.as_deref() {
+            None => Level::Error,
+            Some(level) if level.eq_ignore_ascii_case("error") => Level::Error,
+            Some(level) if level.eq_ignore_ascii_case("warning") => Level::Warning,
+            Some(level) => {
+                warnings.push(
+                    MigrationWarning::new(
+                        &check.description,
+                        "level",
+                        format!("unknown check level '{level}', using Error"),
+                    )
+                    .at(format!("checks[{check_index}].level")),
+                );
+                Level::Error
+            }
+        };
+
+        let mut builder = Check::builder(&check.description).level(level);
+        for (index, value) in check.constraints.iter().enumerate() {
+            let converted = DeequConstraint::deserialize(value)
+                .map_err(|e| {
+                    let name = value
+                        .get("type")
+                        .and_then(Value::as_str)
+                        .map_or_else(|| format!("#{}", index + 1), str::to_string);
+                    (name, format!("invalid constraint definition: {e}"))
+                })
+                .and_then(|constraint| {
+                    constraint
+                        .to_constraint()
+                        .map_err(|reason| (constraint.label(), reason))
+                });
+            match converted {
+                Ok(constraint) => builder = builder.arc_constraint(constraint),
+                Err((name, reason)) => warnings.push(
+                    MigrationWarning::new(&check.description, name, reason)
+                        .at(format!("checks[{check_index}].constraints[{index}]")),
+                ),
+            }
+        }
+        suite = suite.check(builder.build());
+    }
+
+    Ok(DeequMigration {
+        suite: suite.build(),
+        warnings,
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DeequDocument {
+    Suite {
+        #[serde(default)]
+        name: Option<String>,
+        checks: Vec<DeequCheck>,
+    },
+    Checks(Vec<DeequCheck>),
+}
+
+#[derive(Deserialize)]
+struct DeequCheck {
+    description: String,
+    #[serde(default)]
+    level: Option<String>,
+    #[serde(default)]
+    constraints: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeequConstraint {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    column: Option<String>,
+    #[serde(default)]
+    columns: Vec<String>,
+    #[serde(default)]
+    assertion: Option<DeequAssertion>,
+    #[serde(default)]
+    allowed_values: Vec<String>,
+    #[serde(default)]
+    pattern: Option<String>,
+    #[serde(default)]
+    column_condition: Option<String>,
+    #[serde(default)]
+    constraint_name: Option<String>,
+    #[serde(default)]
+    hint: Option<String>,
+}
+
+impl DeequConstraint {
+    /// Returns the name of the constraint in warnings, such as `hasMin(amount)`.
+    fn label(&self) -> String {
+        let columns = self.all_columns();
+        if columns.is_empty() {
+            self.kind.clone()
+        } else {
+            format!("{}({})", self.kind, columns.join(", "))
+        }
+    }
+
+    fn all_columns(&self) -> Vec<String> {
+        self.column.iter().chain(&self.columns).cloned().collect()
+    }
+
+    fn column(&self) -> std::result::Result<&str, String> {
+        self.column
+            .as_deref()
+            .ok_or_else(|| format!("{} needs a column", self.kind))
+    }
+
+    fn assertion(&self) -> std::result::Result<Assertion, String> {
+        self.assertion
+            .as_ref()
+            .ok_or_else(|| format!("{} needs an assertion", self.kind))?
+            .to_assertion()
+    }
+
+    /// Returns the minimum fraction of rows required by the assertion.
+    fn threshold(&self) -> std::result::Result<f64, String> {
+        let assertion = self.assertion()?;
+        match assertion {
+            Assertion::GreaterThanOrEqual(threshold) | Assertion::GreaterThan(threshold)
+                if (0.0..=1.0).contains(&threshold) =>
+            {
+                Ok(threshold)
+            }
+            Assertion::Equals(threshold) if threshold == 1.0 => Ok(1.0),
+            other => Err(format!(
+                "the assertion '{other}' is not a minimum fraction of rows"
+            )),
+        }
+    }
+
+    /// Returns the threshold of an optional assertion, which Deequ defaults to `== 1.0`.
+    fn optional_threshold(&self) -> std::result::Result<f64, String> {
+        match self.assertion {
+            Some(_) => self.threshold(),
+            None => Ok(1.0),
+        }
+    }
+
+    /// Checks that the constraint holds for all rows, the only case Term supports.
+    fn require_all_rows(&self) -> std::result::Result<(), String> {
+        match self.optional_threshold()? {
+            threshold if threshold == 1.0 => Ok(()),
+            threshold => Err(format!(
+                "Term checks {} on all rows, not on a fraction of {threshold}",
+                self.kind
+            )),
+        }
+    }
+
+    fn statistic(
+        &self,
+        statistic: StatisticType,
+    ) -> std::result::Result<Arc<dyn Constraint>, String> {
+        let constraint = StatisticalConstraint::new(self.column()?, statistic, self.assertion()?)
+            .map_err(|e| e.to_string())?;
+        Ok(Arc::new(constraint))
+    }
+
+    fn to_constraint(&self) -> std::result::Result<Arc<dyn Constraint>, String> {
+        let constraint: Arc<dyn Constraint> = match self.kind.as_str() {
+            "isComplete" => Arc::new(CompletenessConstraint::complete(self.column()?)),
+            "hasCompleteness" => Arc::new(CompletenessConstraint::with_threshold(
+                self.column()?,
+                self.threshold()?,
+            )),
+            "isUnique" | "hasUniqueness" => {
+                let columns = self.all_columns();
+                if columns.is_empty() {
+                    return Err(format!("{} needs a column", self.kind));
+                }
+                let threshold = if self.kind == "isUnique" {
+                    1.0
+                } else {
+                    self.threshold()?
+                };
+                let constraint = UniquenessConstraint::new(
+                    columns,
+                    UniquenessType::FullUniqueness { threshold },
+                    UniquenessOptions::default(),
+                )
+                .map_err(|e| e.to_string())?;
+                Arc::new(constraint)
+            }
+            "isContainedIn" => {
+                if self.allowed_values.is_empty() {
+                    return Err("only isContainedIn with allowedValues is supported".to_string());
+                }
+                self.require_all_rows()?;
+                Arc::new(ContainmentConstraint::new(
+                    self.column()?,
+                    self.allowed_values.clone(),
+                ))
+            }
+            "hasMin" => self.statistic(StatisticType::Min)?,
+            "hasMax" => self.statistic(StatisticType::Max)?,
+            "hasMean" => self.statistic(StatisticType::Mean)?,
+            "hasSum" => self.statistic(StatisticType::Sum)?,
+            "hasStandardDeviation" => self.statistic(StatisticType::StandardDeviation)?,
+            "hasPattern" => {
+                let pattern = self
+                    .pattern
+                    .as_deref()
+                    .ok_or("hasPattern needs a pattern")?;
+                let constraint =
+                    FormatConstraint::regex(self.column()?, pattern, self.optional_threshold()?)
+                        .map_err(|e| e.to_string())?;
+                Arc::new(constraint)
+            }
+            "hasSize" => Arc::new(SizeConstraint::new(self.assertion()?)),
+            "satisfies" => {
+                let condition = self
+                    .column_condition
+                    .as_deref()
+                    .ok_or("satisfies needs a columnCondition")?;
+                self.require_all_rows()?;
+                let hint = self.constraint_name.as_ref().or(self.hint.as_ref());
+                let constraint =
+                    CustomSqlConstraint::new(condition, hint).map_err(|e| e.to_string())?;
+                Arc::new(constraint)
+            }
+            other => return Err(format!("{other} has no Term equivalent")),
+        };
+        Ok(constraint)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DeequAssertion {
+    Lambda(String),
+    Operator {
+        operator: String,
+        #[serde(default)]
+        value: Option<f64>,
+        #[serde(default)]
+        lower: Option<f64>,
+        #[serde(default)]
+        upper: Option<f64>,
+    },
+}
+
+impl DeequAssertion {
+    fn to_assertion(&self) -> std::result::Result<Assertion, String> {
+        match self {
+            DeequAssertion::Lambda(lambda) => {
+                let captures = ASSERTION_LAMBDA.captures(lambda).ok_or_else(|| {
+                    format!("the assertion '{lambda}' is not a comparison with a number")
+                })?;
+                let value = captures[2]
+                    .parse()
+                    .map_err(|_| format!("invalid number in the assertion '{lambda}'"))?;
+                comparison(&captures[1], value)
+            }
+            DeequAssertion::Operator {
+                operator,
+                value,
+                lower,
+                upper,
+            } => match (operator.as_str(), value, lower, upper) {
+                ("between", _, Some(lower), Some(upper)) => Ok(Assertion::Between(*lower, *upper)),
+                (operator, Some(value), _, _) => comparison(operator, *value),
+                (operator, ..) => Err(format!("the assertion '{operator}' has no value")),
+            },
+        }
+    }
+}
+
+fn comparison(operator: &str, value: f64) -> std::result::Result<Assertion, String> {
+    match operator {
+        "==" => Ok(Assertion::Equals(value)),
+        "!=" => Ok(Assertion::NotEquals(value)),
+        ">" => Ok(Assertion::GreaterThan(value)),
+        ">=" => Ok(Assertion::GreaterThanOrEqual(value)),
+        "<" => Ok(Assertion::LessThan(value)),
+        "<=" => Ok(Assertion::LessThanOrEqual(value)),
+        other => Err(format!("unknown assertion operator '{other}'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assertion(json: &str) -> std::result::Result<Assertion, String> {
+        serde_json::from_str::<DeequAssertion>(json)
+            .unwrap()
+            .to_assertion()
+    }
+
+    #[test]
+    fn test_assertions() {
+        assert_eq!(
+            assertion(r#""_ >= 0.95""#),
+            Ok(Assertion::GreaterThanOrEqual(0.95))
+        );
+        assert_eq!(assertion(r#""x => x == 1.0""#), Ok(Assertion::Equals(1.0)));
+        assert_eq!(assertion(r#""_<-2e3""#), Ok(Assertion::LessThan(-2000.0)));
+        assert_eq!(
+            assertion(r#"{"operator": "between", "lower": 1, "upper": 10}"#),
+            Ok(Assertion::Between(1.0, 10.0))
+        );
+        assert_eq!(
+            assertion(r#"{"operator": "!=", "value": 0}"#),
+            Ok(Assertion::NotEquals(0.0))
+        );
+        assert!(assertion(r#""_ >= 0.9 && _ <= 1.0""#).is_err());
+        assert!(assertion(r#"{"operator": "~", "value": 1}"#).is_err());
+        assert!(assertion(r#"{"operator": "between", "lower": 1}"#).is_err());
+    }
+
+    #[test]
+    fn test_fraction_assertions() {
+        let constraint = |assertion: &str| {
+            serde_json::from_str::<DeequConstraint>(&format!(
+                r#"{{"type": "hasCompleteness", "column": "email", "assertion": "{assertion}"}}"#
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(constraint("_ >= 0.9").threshold(), Ok(0.9));
+        assert_eq!(constraint("_ > 0.9").threshold(), Ok(0.9));
+        assert_eq!(constraint("_ == 1.0").threshold(), Ok(1.0));
+        assert!(constraint("_ < 0.1").threshold().is_err());
+        assert!(constraint("_ >= 2").threshold().is_err());
+        assert!(constraint("_ == 0.5").require_all_rows().is_err());
+    }
+
+    #[test]
+    fn test_invalid_definitions() {
+        assert!(matches!(
+            from_deequ_json("{\"checks\": 1}"),
+            Err(TermError::Parse(_))
+        ));
+
+        let migration = from_deequ_json(
+            r#"[{
+                "description": "broken",
+                "level": "Critical",
+                "constraints": [{"column": "id"}, {"type": "hasMin", "column": "id"}]
+            }]"#,
+        )
+        .unwrap();
+        assert_eq!(migration.suite.name(), DEFAULT_SUITE_NAME);
+        assert_eq!(migration.suite.checks()[0].level(), Level::Error);
+
+        let warnings: Vec<_> = migration.warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(
+            warnings[0],
+            "check 'broken', constraint level: unknown check level 'Critical', using Error"
+        );
+        assert!(warnings[1].starts_with("check 'broken', constraint #1: invalid constraint"));
+        assert_eq!(
+            warnings[2],
+            "check 'broken', constraint hasMin(id): hasMin needs an assertion"
+        );
+
+        let report = migration.config_report();
+        assert!(!report.has_errors());
+        let paths: Vec<_> = report.issues.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "checks[0].level",
+                "checks[0].constraints[0]",
+                "checks[0].constraints[1]"
+            ]
+        );
+        assert_eq!(report.issues[2].check.as_deref(), Some("broken"));
+    }
+}
@@ -0,0 +1,442 @@
+//! Conversion of Great Expectations expectation suites saved as JSON.
+
+use std::sync::Arc;
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::{warnings_report, MigrationWarning};
+use crate::constraints::{
+    Assertion, CompletenessConstraint, ContainmentConstraint, FormatConstraint, SizeConstraint,
+    StatisticType, StatisticalConstraint, UniquenessConstraint,
+};
+use crate::core::{Check, ConfigReport, Constraint, Level, ValidationSuite};
+use crate::error::{Result, TermError};
+
+/// Name of the suite when the expectation suite has none.
+const DEFAULT_SUITE_NAME: &str = "great_expectations_migration";
+
+/// A suite converted from a Great Expectations expectation suite.
+#[derive(Debug)]
+pub struct GreatExpectationsMigration {
+    /// The converted suite, with all expectations in one check named after it
+    pub suite: ValidationSuite,
+    /// The expectations left out of the suite
+    pub warnings: Vec<MigrationWarning>,
+}
+
+impl GreatExpectationsMigration {
+    /// Lists the warnings as a report with paths into the expectation suite.
+    pub fn config_report(&self) -> ConfigReport {
+        warnings_report(
+            format!("expectation suite '{}'", self.suite.name()),
+            &self.warnings,
+        )
+    }
+}
+
+/// Converts a Great Expectations expectation suite saved as JSON into a
+/// [`ValidationSuite`].
+///
+/// Both the legacy format, with `expectation_suite_name` and
+/// `expectation_type`, and the 1.x format, with `name` and `type`, are read.
+/// The expectations become the constraints of a single [`Level::Error`] check
+/// named after the suite:
+///
+/// | Great Expectations | Term |
+/// |--------------------|------|
+/// | `expect_column_values_to_not_be_null` | [`CompletenessConstraint`] |
+/// | `expect_column_values_to_be_unique`, `expect_compound_columns_to_be_unique` | [`UniquenessConstraint`] with full uniqueness |
+/// | `expect_column_values_to_be_in_set` | [`ContainmentConstraint`] |
+/// | `expect_column_values_to_match_regex` | [`FormatConstraint::regex`] |
+/// | `expect_column_values_to_be_between` | [`StatisticalConstraint`]s on the minimum and maximum |
+/// | `expect_column_min_to_be_between`, `..._max_...`, `..._mean_...`, `..._sum_...`, `..._stdev_...` | [`StatisticalConstraint`] |
+/// | `expect_table_row_count_to_equal`, `expect_table_row_count_to_be_between` | [`SizeConstraint`] |
+///
+/// `mostly` becomes the threshold of constraints checking a fraction of rows;
+/// expectations on all values, such as `expect_column_values_to_be_in_set`,
+/// must not set it below 1.
+///
+/// Other expectations, expectations with a `row_condition`, and bounds that
+/// are evaluation parameters instead of numbers are left out and reported in
+/// [`GreatExpectationsMigration::warnings`].
+///
+/// # Errors
+///
+/// Returns [`TermError::Parse`] if the input is not an expectation suite.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::migration::from_great_expectations_json;
+///
+/// let migration = from_great_expectations_json(
+///     r#"{
+///         "expectation_suite_name": "orders.warning",
+///         "expectations": [
+///             {
+///                 "expectation_type": "expect_column_values_to_not_be_null",
+///                 "kwargs": {"column": "email", "mostly": 0.95}
+///             },
+///             {
+///                 "expectation_type": "expect_column_values_to_be_dateutil_parseable",
+///                 "kwargs": {"column": "created_at"}
+///             }
+///         ]
+///     }"#,
+/// )?;
+///
+/// assert_eq!(migration.suite.checks()[0].constraints().len(), 1);
+/// assert_eq!(
+///     migration.warnings[0].constraint,
+///     "expect_column_values_to_be_dateutil_parseable(created_at)"
+/// );
+/// # Ok::<(), term_guard::error::TermError>(())
+/// ```
+pub fn from_great_expectations_json(json: &str) -> Result<GreatExpectationsMigration> {
+    let document: ExpectationSuite = serde_json::from_str(json)
+        .map_err(|e| TermError::Parse(format!("Invalid expectation suite: {e}")))?;
+    let name = document
+        .expectation_suite_name
+        .or(document.name)
+        .unwrap_or_else(|| DEFAULT_SUITE_NAME.to_string());
+
+    let mut warnings = Vec::new();
+    let mut check = Check::builder(&name).level(Level::Error);
+    for (index, value) in document.expectations.iter().enumerate() {
+        let converted = Expectation::deserialize(value)
+            .map_err(|e| {
+                let label = value
+                    .get("expectation_type")
+                    .or_else(|| value.get("type"))
+                    .and_then(Value::as_str)
+                    .map_or_else(|| format!("#{}", index + 1), str::to_string);
+                (label, format!("invalid expectation: {e}"))
+            })
+            .and_then(|expectation| {
+                expectation
+                    .to_constraints()
+                    .map_err(|reason| (expectation.label(), reason))
+            });
+        match converted {
+            Ok(constraints) => {
+                for constraint in constraints {
+                    check = check.arc_constraint(constraint);
+                }
+            }
+            Err((label, reason)) => warnings.push(
+                MigrationWarning::new(&name, label, reason).at(format!("expectations[{index}]")),
+            ),
+        }
+    }
+
+    Ok(GreatExpectationsMigration {
+        suite: ValidationSuite::builder(&name).check(check.build()).build(),
+        warnings,
+    })
+}
+
+#[derive(Deserialize)]
+struct ExpectationSuite {
+    #[serde(default)]
+    expectation_suite_name: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    expectations: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+struct Expectation {
+    #[serde(alias = "type")]
+    expectation_type: String,
+    #[serde(default)]
+    kwargs: Map<String, Value>,
+}
+
+impl Expectation {
+    /// Returns the name of the expectation in warnings, such as
+    /// `expect_column_values_to_be_unique(id)`.
+    fn label(&self) -> String {
+        let columns = match (self.kwargs.get("column"), self.kwargs.get("column_list")) {
+            (Some(Value::String(column)), _) => column.clone(),
+            (_, Some(Value::Array(columns))) => columns
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => return self.expectation_type.clone(),
+        };
+        format!("{}({columns})", self.expectation_type)
+    }
+
+    fn column(&self) -> std::result::Result<&str, String> {
+        self.kwargs
+            .get("column")
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("{} needs a column", self.expectation_type))
+    }
+
+    /// Returns the fraction of rows given by `mostly`, 1 if absent.
+    fn mostly(&self) -> std::result::Result<f64, String> {
+        match self.kwargs.get("mostly") {
+            None | Some(Value::Null) => Ok(1.0),
+            Some(value) => match value.as_f64() {
+                Some(mostly) if (0.0..=1.0).contains(&mostly) => Ok(mostly),
+                _ => Err(format!("mostly {value} is not a fraction of rows")),
+            },
+        }
+    }
+
+    /// Checks that the expectation holds for all rows, the only case Term supports.
+    fn require_all_rows(&self) -> std::result::Result<(), String> {
+        match self.mostly()? {
+            mostly if mostly == 1.0 => Ok(()),
+            mostly => Err(format!(
+                "Term checks {} on all rows, not on a fraction of {mostly}",
+                self.expectation_type
+            )),
+        }
+    }
+
+    fn number(&self, key: &str) -> std::result::Result<Option<f64>, String> {
+        match self.kwargs.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => value
+                .as_f64()
+                .map(Some)
+                .ok_or_else(|| format!("{key} {value} is not a number")),
+        }
+    }
+
+    fn flag(&self, key: &str) -> bool {
+        self.kwargs
+            .get(key)
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    }
+
+    /// Returns the assertion on a value between `min_value` and `max_value`.
+    fn bounds(&self) -> std::result::Result<Assertion, String> {
+        let (strict_min, strict_max) = (self.flag("strict_min"), self.flag("strict_max"));
+        match (self.number("min_value")?, self.number("max_value")?) {
+            (Some(min), Some(max)) if !strict_min && !strict_max => {
+                Ok(Assertion::Between(min, max))
+            }
+            (Some(_), Some(_)) => Err("strict bounds on both sides are not supported".to_string()),
+            (Some(min), None) if strict_min => Ok(Assertion::GreaterThan(min)),
+            (Some(min), None) => Ok(Assertion::GreaterThanOrEqual(min)),
+            (None, Some(max)) if strict_max => Ok(Assertion::LessThan(max)),
+            (None, Some(max)) => Ok(Assertion::LessThanOrEqual(max)),
+            (None, None) => Err(format!(
+                "{} needs min_value or max_value",
+                self.expectation_type
+            )),
+        }
+    }
+
+    fn statistic(
+        &self,
+        statistic: StatisticType,
+        assertion: Assertion,
+    ) -> std::result::Result<Arc<dyn Constraint>, String> {
+        let constraint = StatisticalConstraint::new(self.column()?, statistic, assertion)
+            .map_err(|e| e.to_string())?;
+        Ok(Arc::new(constraint))
+    }
+
+    fn to_constraints(&self) -> std::result::Result<Vec<Arc<dyn Constraint>>, String> {
+        if self
+            .kwargs
+            .get("row_condition")
+            .is_some_and(|c| !c.is_null())
+        {
+            return Err("expectations with a row_condition have no Term equivalent".to_string());
+        }
+        let kind = self.expectation_type.as_str();
+        let constraint: Arc<dyn Constraint> = match kind {
+            "expect_column_values_to_not_be_null" => Arc::new(
+                CompletenessConstraint::with_threshold(self.column()?, self.mostly()?),
+            ),
+            "expect_column_values_to_be_unique" => Arc::new(
+                UniquenessConstraint::full_uniqueness(self.column()?, self.mostly()?)
+                    .map_err(|e| e.to_string())?,
+            ),
+            "expect_compound_columns_to_be_unique" => {
+                let columns: Vec<&str> = match self.kwargs.get("column_list") {
+                    Some(Value::Array(columns)) => {
+                        columns.iter().filter_map(Value::as_str).collect()
+                    }
+                    _ => Vec::new(),
+                };
+                if columns.is_empty() {
+                    return Err(format!("{kind} needs a column_list"));
+                }
+                Arc::new(
+                    UniquenessConstraint::full_uniqueness_multi(columns, self.mostly()?)
+                        .map_err(|e| e.to_string())?,
+                )
+            }
+            "expect_column_values_to_be_in_set" => {
+                let values = match self.kwargs.get("value_set") {
+                    Some(Value::Array(values)) => values
+                        .iter()
+                        .map(|value| match value {
+                            Value::String(value) => Ok(value.clone()),
+                            Value::Number(_) | Value::Bool(_) => Ok(value.to_string()),
+                            other => Err(format!("the value {other} is not a scalar")),
+                        })
+                        .collect::<std::result::Result<Vec<_>, _>>()?,
+                    _ => return Err(format!("{kind} needs a value_set list")),
+                };
+                self.require_all_rows()?;
+                Arc::new(ContainmentConstraint::new(self.column()?, values))
+            }
+            "expect_column_values_to_match_regex" => {
+                let regex = self
+                    .kwargs
+                    .get("regex")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| format!("{kind} needs a regex"))?;
+                Arc::new(
+                    FormatConstraint::regex(self.column()?, regex, self.mostly()?)
+                        .map_err(|e| e.to_string())?,
+                )
+            }
+            "expect_column_values_to_be_between" => {
+                // All values lie within the bounds exactly when the minimum and maximum do
+                self.require_all_rows()?;
+                let (strict_min, strict_max) = (self.flag("strict_min"), self.flag("strict_max"));
+                let mut constraints = Vec::new();
+                if let Some(min) = self.number("min_value")? {
+                    let assertion = if strict_min {
+                        Assertion::GreaterThan(min)
+                    } else {
+                        Assertion::GreaterThanOrEqual(min)
+                    };
+                    constraints.push(self.statistic(StatisticType::Min, assertion)?);
+                }
+                if let Some(max) = self.number("max_value")? {
+                    let assertion = if strict_max {
+                        Assertion::LessThan(max)
+                    } else {
+                        Assertion::LessThanOrEqual(max)
+                    };
+                    constraints.push(self.statistic(StatisticType::Max, assertion)?);
+                }
+                if constraints.is_empty() {
+                    return Err(format!("{kind} needs min_value or max_value"));
+                }
+                return Ok(constraints);
+            }
+            "expect_column_min_to_be_between" => {
+                self.statistic(StatisticType::Min, self.bounds()?)?
+            }
+            "expect_column_max_to_be_between" => {
+                self.statistic(StatisticType::Max, self.bounds()?)?
+            }
+            "expect_column_mean_to_be_between" => {
+                self.statistic(StatisticType::Mean, self.bounds()?)?
+            }
+            "expect_column_sum_to_be_between" => {
+                self.statistic(StatisticType::Sum, self.bounds()?)?
+            }
+            "expect_column_stdev_to_be_between" => {
+                self.statistic(StatisticType::StandardDeviation, self.bounds()?)?
+            }
+            "expect_table_row_count_to_be_between" => Arc::new(SizeConstraint::new(self.bounds()?)),
+            "expect_table_row_count_to_equal" => {
+                let value = self
+                    .number("value")?
+                    .ok_or_else(|| format!("{kind} needs a value"))?;
+                Arc::new(SizeConstraint::new(Assertion::Equals(value)))
+            }
+            other => return Err(format!("{other} has no Term equivalent")),
+        };
+        Ok(vec![constraint])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expectation(json: &str) -> Expectation {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_bounds() {
+        let bounds = |kwargs: &str| {
+            expectation(&format!(
+                r#"{{"type": "expect_column_min_to_be_between", "kwargs": {kwargs}}}"#
+            ))
+            .bounds()
+        };
+
+        assert_eq!(
+            bounds(r#"{"min_value": 1, "max_value": 10}"#),
+            Ok(Assertion::Between(1.0, 10.0))
+        );
+        assert_eq!(
+            bounds(r#"{"min_value": 0, "strict_min": true}"#),
+            Ok(Assertion::GreaterThan(0.0))
+        );
+        assert_eq!(
+            bounds(r#"{"min_value": null, "max_value": 5}"#),
+            Ok(Assertion::LessThanOrEqual(5.0))
+        );
+        assert!(bounds(r#"{"min_value": 1, "max_value": 10, "strict_max": true}"#).is_err());
+        assert!(bounds(r#"{"min_value": {"$PARAMETER": "min_amount"}}"#).is_err());
+        assert!(bounds("{}").is_err());
+    }
+
+    #[test]
+    fn test_mostly() {
+        let mostly = |value: &str| {
+            expectation(&format!(
+                r#"{{"expectation_type": "expect_column_values_to_be_in_set", "kwargs": {{"column": "status", "value_set": ["a", 1], "mostly": {value}}}}}"#
+            ))
+        };
+
+        assert_eq!(mostly("0.9").mostly(), Ok(0.9));
+        assert_eq!(mostly("null").mostly(), Ok(1.0));
+        assert!(mostly("1.5").mostly().is_err());
+        assert!(mostly("0.9").to_constraints().is_err());
+        assert_eq!(mostly("1").to_constraints().map(|c| c.len()), Ok(1));
+    }
+
+    #[test]
+    fn test_invalid_suites() {
+        assert!(matches!(
+            from_great_expectations_json(r#"{"expectations": 1}"#),
+            Err(TermError::Parse(_))
+        ));
+
+        let migration = from_great_expectations_json(
+            r#"{
+                "expectations": [
+                    {"kwargs": {"column": "id"}},
+                    {"type": "expect_column_values_to_not_be_null", "kwargs": {"column": "id", "row_condition": "id > 1"}},
+                    {"type": "expect_column_values_to_be_between", "kwargs": {"column": "id"}}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(migration.suite.name(), DEFAULT_SUITE_NAME);
+        assert!(migration.suite.checks()[0].constraints().is_empty());
+
+        let warnings: Vec<_> = migration.warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].starts_with(
+            "check 'great_expectations_migration', constraint #1: invalid expectation"
+        ));
+        assert_eq!(
+            warnings[1],
+            "check 'great_expectations_migration', constraint expect_column_values_to_not_be_null(id): expectations with a row_condition have no Term equivalent"
+        );
+        assert_eq!(
+            warnings[2],
+            "check 'great_expectations_migration', constraint expect_column_values_to_be_between(id): expect_column_values_to_be_between needs min_value or max_value"
+        );
+    }
+}
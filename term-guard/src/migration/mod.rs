@@ -0,0 +1,90 @@
+//! Conversion of check definitions written for other data quality tools.
+//!
+//! Migrations translate as much of a definition as Term can express and report
+//! the rest as [`MigrationWarning`]s, so a large catalogue of checks can be
+//! imported at once and the leftovers rewritten by hand.
+//!
+//! Currently supported:
+//!
+//! - **Deequ**: checks exported as JSON, see [`from_deequ_json`]
+//! - **dbt**: tests declared in `schema.yml` files, see [`from_dbt_schema_yaml`]
+//! - **Great Expectations**: expectation suites saved as JSON, see
+//!   [`from_great_expectations_json`]
+//!
+//! Each migration also lists its warnings as a [`ConfigReport`] whose issue
+//! paths point into the source definition, such as `checks[0].constraints[2]`.
+
+use crate::core::{ConfigIssue, ConfigReport};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+mod dbt;
+mod deequ;
+mod great_expectations;
+
+pub use dbt::{from_dbt_schema_yaml, DbtMigration};
+pub use deequ::{from_deequ_json, DeequMigration};
+pub use great_expectations::{from_great_expectations_json, GreatExpectationsMigration};
+
+/// A part of a check definition that was not migrated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationWarning {
+    /// Name of the check holding the constraint
+    pub check: String,
+    /// Name of the constraint in the source definition
+    pub constraint: String,
+    /// Why the constraint was left out
+    pub reason: String,
+    /// Location of the constraint in the source definition, such as
+    /// `models[0].columns[1].tests[0]`
+    #[serde(default)]
+    pub path: String,
+}
+
+impl MigrationWarning {
+    pub(crate) fn new(
+        check: impl Into<String>,
+        constraint: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            check: check.into(),
+            constraint: constraint.into(),
+            reason: reason.into(),
+            path: String::new(),
+        }
+    }
+
+    /// Sets the location of the constraint in the source definition.
+    pub(crate) fn at(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+}
+
+impl From<&MigrationWarning> for ConfigIssue {
+    fn from(warning: &MigrationWarning) -> Self {
+        ConfigIssue::warning(
+            &warning.path,
+            format!("{}: {}", warning.constraint, warning.reason),
+        )
+        .in_check(&warning.check)
+    }
+}
+
+/// Lists the warnings of a migration as a report on `subject`.
+fn warnings_report(subject: String, warnings: &[MigrationWarning]) -> ConfigReport {
+    let mut report = ConfigReport::new(subject);
+    report.extend(warnings.iter().map(ConfigIssue::from));
+    report
+}
+
+impl fmt::Display for MigrationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "check '{}', constraint {}: {}",
+            self.check, self.constraint, self.reason
+        )
+    }
+}
@@ -1,6 +1,6 @@
 //! Query analysis for constraint optimization.
 
-use crate::core::Constraint;
+use crate::core::{Constraint, TermContext};
 use crate::prelude::TermError;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -98,6 +98,8 @@ impl QueryAnalyzer {
             "mean" => vec![AggregationType::Avg],
             "sum" => vec![AggregationType::Sum],
             "standard_deviation" => vec![AggregationType::StdDev],
+            "count" | "null_count" => vec![AggregationType::Count],
+            "approx_distinct" => vec![AggregationType::CountDistinct],
             "quantile" => vec![AggregationType::Count], // Simplified
             "entropy" => vec![AggregationType::Count],  // Simplified
             "mutual_information" => vec![AggregationType::Count], // Simplified
@@ -115,10 +117,7 @@ impl QueryAnalyzer {
         );
 
         // Most constraints can be combined except for complex ones
-        let is_combinable = !matches!(
-            constraint_name,
-            "quantile" | "entropy" | "mutual_information" | "anomaly_detection"
-        );
+        let is_combinable = non_combinable_reason(constraint_name).is_none();
 
         Ok(ConstraintAnalysis {
             name,
@@ -146,12 +145,88 @@ impl QueryAnalyzer {
         }
     }
 
+    /// Checks that constraints computing numeric statistics target numeric columns.
+    ///
+    /// Returns a configuration error message for every constraint whose column has a
+    /// non-numeric type, keyed by constraint name. Such constraints would make the
+    /// combined query of their whole group fail type coercion. Tables or columns the
+    /// context does not know are left for execution to report.
+    pub async fn check_column_types(
+        &self,
+        analyses: &[ConstraintAnalysis],
+        ctx: &TermContext,
+    ) -> HashMap<String, String> {
+        let mut schemas = HashMap::new();
+        let mut mismatches = HashMap::new();
+
+        for analysis in analyses {
+            let statistic = analysis.constraint.name();
+            if !requires_numeric_column(statistic) {
+                continue;
+            }
+            let Some(column) = analysis.constraint.column() else {
+                continue;
+            };
+
+            if !schemas.contains_key(&analysis.table_name) {
+                let schema = match ctx.inner().table(analysis.table_name.as_str()).await {
+                    Ok(df) => Some(df.schema().clone()),
+                    Err(_) => None,
+                };
+                schemas.insert(analysis.table_name.clone(), schema);
+            }
+            let Some(schema) = &schemas[&analysis.table_name] else {
+                continue;
+            };
+
+            if let Ok(field) = schema.field_with_unqualified_name(column) {
+                if !field.data_type().is_numeric() {
+                    mismatches.insert(
+                        analysis.name.clone(),
+                        format!(
+                            "Configuration error: {statistic} requires a numeric column, \
+                             but '{column}' has type {}",
+                            field.data_type()
+                        ),
+                    );
+                }
+            }
+        }
+
+        mismatches
+    }
+
     /// Clears the analysis cache.
     pub fn clear_cache(&mut self) {
         self.cache.clear();
     }
 }
 
+/// Returns why constraints of the given type cannot share a combined query.
+///
+/// Returns `None` for constraints that can be combined.
+pub fn non_combinable_reason(constraint_name: &str) -> Option<&'static str> {
+    match constraint_name {
+        "quantile" => Some("quantiles need a sorted scan of the column"),
+        "entropy" => Some("entropy needs the value distribution of the column"),
+        "mutual_information" => Some("mutual information needs the joint value distribution"),
+        "anomaly_detection" => Some("anomaly detection compares against historical metrics"),
+        "multi_statistical" => Some("computes all of its statistics in a single query already"),
+        _ => None,
+    }
+}
+
+/// Returns whether constraints of the given type compute a statistic that only
+/// exists for numeric columns.
+///
+/// `min` and `max` are not included, since they are defined for any ordered type.
+pub fn requires_numeric_column(constraint_name: &str) -> bool {
+    matches!(
+        constraint_name,
+        "mean" | "sum" | "standard_deviation" | "variance" | "median" | "percentile"
+    )
+}
+
 impl Default for QueryAnalyzer {
     fn default() -> Self {
         Self::new()
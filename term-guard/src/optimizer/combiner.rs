@@ -2,7 +2,7 @@
 
 use crate::optimizer::analyzer::{AggregationType, ConstraintAnalysis};
 use crate::prelude::TermError;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// A group of constraints that can be executed together.
 #[derive(Debug)]
@@ -38,7 +38,7 @@ impl QueryCombiner {
         let mut groups = Vec::new();
         let mut processed = HashSet::new();
 
-        // Group by table first
+        // Group by table first, in table name order so plans are stable
         let by_table = self.group_by_table(analyses);
 
         for (table, table_constraints) in by_table {
@@ -79,8 +79,8 @@ impl QueryCombiner {
     fn group_by_table(
         &self,
         analyses: Vec<ConstraintAnalysis>,
-    ) -> HashMap<String, Vec<ConstraintAnalysis>> {
-        let mut by_table: HashMap<String, Vec<ConstraintAnalysis>> = HashMap::new();
+    ) -> BTreeMap<String, Vec<ConstraintAnalysis>> {
+        let mut by_table: BTreeMap<String, Vec<ConstraintAnalysis>> = BTreeMap::new();
 
         for analysis in analyses {
             by_table
@@ -1,19 +1,49 @@
 //! Optimized query execution.
 
-use crate::core::{ConstraintResult, ConstraintStatus, TermContext};
+use crate::core::{
+    collect_with_metrics, current_validation_context, ConstraintResult, ConstraintStatus,
+    QueryMetrics, QueryMetricsCollector, TermContext, CURRENT_CONTEXT,
+};
+use crate::optimizer::analyzer::ConstraintAnalysis;
 use crate::optimizer::combiner::ConstraintGroup;
 use crate::optimizer::stats_cache::StatsCache;
 use crate::prelude::TermError;
 use arrow::array::*;
 use arrow::datatypes::DataType;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
+
+/// Default for [`OptimizedExecutor::max_fallback_constraints`], matching the
+/// combiner's maximum group size.
+pub const DEFAULT_MAX_FALLBACK_CONSTRAINTS: usize = 20;
+
+/// Results of a group, keyed by constraint name.
+type ConstraintResults = HashMap<String, ConstraintResult>;
+
+/// A combined query that failed and whose constraints were evaluated one by one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupFallback {
+    /// Names of the constraints in the group
+    pub constraints: Vec<String>,
+    /// The combined query that failed
+    pub combined_sql: String,
+    /// The error the combined query failed with
+    pub error: String,
+    /// Constraints whose own evaluation failed as well, so the combined query
+    /// most likely failed because of them
+    pub failed_constraints: Vec<String>,
+}
 
 /// Executes optimized query groups.
 #[derive(Debug)]
 pub struct OptimizedExecutor {
     /// Whether to enable predicate pushdown
     pub enable_pushdown: bool,
+    /// Largest group whose constraints are evaluated individually when the
+    /// combined query fails; larger groups fail with the combined error.
+    /// `0` disables the fallback.
+    pub max_fallback_constraints: usize,
 }
 
 impl OptimizedExecutor {
@@ -21,10 +51,16 @@ impl OptimizedExecutor {
     pub fn new() -> Self {
         Self {
             enable_pushdown: true,
+            max_fallback_constraints: DEFAULT_MAX_FALLBACK_CONSTRAINTS,
         }
     }
 
     /// Executes a group of constraints.
+    ///
+    /// If the combined query of the group fails, the constraints are evaluated
+    /// individually (see [`max_fallback_constraints`](Self::max_fallback_constraints)):
+    /// constraints that fail on their own get a failure naming them and their column,
+    /// the others get their real results.
     #[instrument(skip(self, group, ctx, cache))]
     pub async fn execute_group(
         &self,
@@ -32,67 +68,161 @@ impl OptimizedExecutor {
         ctx: &TermContext,
         cache: &mut StatsCache,
     ) -> Result<HashMap<String, ConstraintResult>, TermError> {
-        let mut results = HashMap::new();
+        Ok(self.execute_group_with_fallback(group, ctx, cache).await?.0)
+    }
+
+    /// Executes a group of constraints and returns the DataFusion execution
+    /// metrics of every query issued for the group alongside the results.
+    pub async fn execute_group_with_metrics(
+        &self,
+        group: ConstraintGroup,
+        ctx: &TermContext,
+        cache: &mut StatsCache,
+    ) -> Result<(HashMap<String, ConstraintResult>, QueryMetrics), TermError> {
+        let (results, metrics, _) = self.execute_group_diagnosed(group, ctx, cache).await?;
+        Ok((results, metrics))
+    }
 
+    /// Like [`execute_group_with_metrics`](Self::execute_group_with_metrics), but
+    /// also returns the fallback taken if the combined query failed.
+    pub(crate) async fn execute_group_diagnosed(
+        &self,
+        group: ConstraintGroup,
+        ctx: &TermContext,
+        cache: &mut StatsCache,
+    ) -> Result<(ConstraintResults, QueryMetrics, Option<GroupFallback>), TermError> {
+        let collector = QueryMetricsCollector::new();
+        let validation_ctx =
+            current_validation_context().with_performance_collector(collector.clone());
+        let (results, fallback) = CURRENT_CONTEXT
+            .scope(
+                validation_ctx,
+                self.execute_group_with_fallback(group, ctx, cache),
+            )
+            .await?;
+        Ok((results, collector.snapshot(), fallback))
+    }
+
+    /// Executes a group, falling back to individual evaluation if its combined
+    /// query fails.
+    async fn execute_group_with_fallback(
+        &self,
+        group: ConstraintGroup,
+        ctx: &TermContext,
+        cache: &mut StatsCache,
+    ) -> Result<(ConstraintResults, Option<GroupFallback>), TermError> {
         if group.constraints.len() == 1 && group.combined_sql.is_empty() {
             // Single non-combinable constraint - execute normally
             let constraint = &group.constraints[0];
             let result = constraint.constraint.evaluate(ctx.inner()).await?;
+            return Ok((HashMap::from([(constraint.name.clone(), result)]), None));
+        }
+
+        let error = match self.execute_combined(&group, ctx, cache).await {
+            Ok(results) => return Ok((results, None)),
+            Err(e) => e,
+        };
+
+        let names: Vec<String> = group.constraints.iter().map(|c| c.name.clone()).collect();
+        if group.constraints.len() > self.max_fallback_constraints {
+            return Err(TermError::constraint_evaluation(
+                names.join(", "),
+                format!("Combined query failed: {error}"),
+            ));
+        }
+
+        warn!(
+            constraints = %names.join(", "),
+            error = %error,
+            "Combined query failed, evaluating constraints individually"
+        );
+
+        let mut results = HashMap::new();
+        let mut failed_constraints = Vec::new();
+        for constraint in &group.constraints {
+            let result = match constraint.constraint.evaluate(ctx.inner()).await {
+                Ok(result) => result,
+                Err(e) => {
+                    failed_constraints.push(constraint.name.clone());
+                    ConstraintResult::failure(individual_error_message(constraint, &e))
+                }
+            };
             results.insert(constraint.name.clone(), result);
+        }
+
+        let fallback = GroupFallback {
+            constraints: names,
+            combined_sql: group.combined_sql,
+            error: error.to_string(),
+            failed_constraints,
+        };
+        Ok((results, Some(fallback)))
+    }
+
+    /// Executes the combined query of a group and maps its row back to the constraints.
+    async fn execute_combined(
+        &self,
+        group: &ConstraintGroup,
+        ctx: &TermContext,
+        cache: &mut StatsCache,
+    ) -> Result<HashMap<String, ConstraintResult>, TermError> {
+        let mut results = HashMap::new();
+
+        // Combined query execution with potential predicate pushdown
+        debug!("Executing combined query: {}", group.combined_sql);
+
+        // Check cache for common statistics
+        let table_name = &group.constraints[0].table_name;
+        let cache_key = format!("table:{table_name}");
+        let cached_stats = cache.get(&cache_key);
+
+        // Apply predicate pushdown if enabled
+        let optimized_sql = if self.enable_pushdown {
+            self.apply_predicate_pushdown(group)?
         } else {
-            // Combined query execution with potential predicate pushdown
-            debug!("Executing combined query: {}", group.combined_sql);
-
-            // Check cache for common statistics
-            let table_name = &group.constraints[0].table_name;
-            let cache_key = format!("table:{table_name}");
-            let cached_stats = cache.get(&cache_key);
-
-            // Apply predicate pushdown if enabled
-            let optimized_sql = if self.enable_pushdown {
-                self.apply_predicate_pushdown(&group)?
-            } else {
-                group.combined_sql.clone()
-            };
+            group.combined_sql.clone()
+        };
 
-            debug!("Optimized SQL with pushdown: {}", optimized_sql);
-
-            // Execute the optimized query
-            let df = ctx.inner().sql(&optimized_sql).await?;
-            let batches = df.collect().await?;
-
-            if batches.is_empty() {
-                // Handle empty results
-                for constraint in &group.constraints {
-                    results.insert(
-                        constraint.name.clone(),
-                        ConstraintResult {
-                            status: ConstraintStatus::Failure,
-                            metric: None,
-                            message: Some("No data to analyze".to_string()),
-                        },
-                    );
-                }
-            } else {
-                // Extract results and map back to constraints
-                let batch = &batches[0];
-                let row_results = self.extract_row_results(batch)?;
-
-                // Update cache with total count if available
-                if let Some(total_count) = row_results.get("total_count") {
-                    cache.set(cache_key, *total_count);
-                }
+        debug!("Optimized SQL with pushdown: {}", optimized_sql);
 
-                // Map results to each constraint
-                for constraint in &group.constraints {
-                    let result = self.map_result_to_constraint(
-                        constraint,
-                        &row_results,
-                        &group.result_mapping,
-                        cached_stats,
-                    )?;
-                    results.insert(constraint.name.clone(), result);
-                }
+        // Execute the optimized query
+        let df = ctx.inner().sql(&optimized_sql).await?;
+        let batches = collect_with_metrics(df).await?;
+
+        if batches.is_empty() {
+            // Handle empty results
+            for constraint in &group.constraints {
+                results.insert(
+                    constraint.name.clone(),
+                    ConstraintResult {
+                        status: ConstraintStatus::Failure,
+                        metric: None,
+                        message: Some("No data to analyze".to_string()),
+                        quarantine: None,
+                        constraint_id: None,
+                        details: Default::default(),
+                    },
+                );
+            }
+        } else {
+            // Extract results and map back to constraints
+            let batch = &batches[0];
+            let row_results = self.extract_row_results(batch)?;
+
+            // Update cache with total count if available
+            if let Some(total_count) = row_results.get("total_count") {
+                cache.set(cache_key, *total_count);
+            }
+
+            // Map results to each constraint
+            for constraint in &group.constraints {
+                let result = self.map_result_to_constraint(
+                    constraint,
+                    &row_results,
+                    &group.result_mapping,
+                    cached_stats,
+                )?;
+                results.insert(constraint.name.clone(), result);
             }
         }
 
@@ -142,7 +272,7 @@ impl OptimizedExecutor {
     /// Maps query results to a constraint result.
     fn map_result_to_constraint(
         &self,
-        constraint: &crate::optimizer::analyzer::ConstraintAnalysis,
+        constraint: &ConstraintAnalysis,
         row_results: &HashMap<String, f64>,
         result_mapping: &HashMap<String, String>,
         cached_stats: Option<f64>,
@@ -173,6 +303,9 @@ impl OptimizedExecutor {
                     status: ConstraintStatus::Success,
                     metric,
                     message: None,
+                    quarantine: None,
+                    constraint_id: None,
+                    details: Default::default(),
                 })
             }
             _ => {
@@ -182,6 +315,9 @@ impl OptimizedExecutor {
                     status: ConstraintStatus::Success,
                     metric: Some(1.0),
                     message: None,
+                    quarantine: None,
+                    constraint_id: None,
+                    details: Default::default(),
                 })
             }
         }
@@ -237,28 +373,25 @@ impl OptimizedExecutor {
             // For constraints with predicates, extract partition-friendly conditions
             if constraint.has_predicates {
                 match constraint.constraint.name() {
-                    "compliance" => {
+                    "compliance"
                         // Compliance constraints often have conditions that can be pushed
                         // In a real implementation, we'd parse the constraint configuration
                         // For now, add a placeholder
-                        if !constraint.columns.is_empty() {
+                        if !constraint.columns.is_empty() => {
                             // Example: push down non-null checks for completeness-like constraints
                             predicates.push(format!("{} IS NOT NULL", constraint.columns[0]));
                         }
-                    }
-                    "pattern_match" => {
+                    "pattern_match"
                         // Pattern matching might have LIKE predicates
-                        if !constraint.columns.is_empty() {
+                        if !constraint.columns.is_empty() => {
                             // Placeholder for pattern predicates
                             // In real implementation, extract from constraint config
                         }
-                    }
-                    "containment" => {
+                    "containment"
                         // Containment might have IN or BETWEEN predicates
-                        if !constraint.columns.is_empty() {
+                        if !constraint.columns.is_empty() => {
                             // Placeholder for containment predicates
                         }
-                    }
                     _ => {}
                 }
             }
@@ -306,31 +439,58 @@ impl OptimizedExecutor {
             explanation.push_str(&format!("\n  Combined SQL:\n    {}\n", group.combined_sql));
 
             // Get the logical plan
-            if !group.combined_sql.is_empty() {
-                match ctx.inner().sql(&group.combined_sql).await {
-                    Ok(df) => {
-                        let logical_plan = df.logical_plan();
-                        explanation.push_str(&format!(
-                            "\n  Logical Plan:\n{}\n",
-                            logical_plan.display_indent()
-                        ));
-                    }
-                    Err(e) => {
-                        // If we can't parse the SQL, just show it without the logical plan
-                        explanation
-                            .push_str(&format!("\n  Logical Plan: Unable to generate ({e})\n"));
-                    }
+            match self.logical_plan(group, ctx).await {
+                Some(Ok(logical_plan)) => {
+                    explanation.push_str(&format!("\n  Logical Plan:\n{logical_plan}\n"));
                 }
+                Some(Err(e)) => {
+                    // If we can't parse the SQL, just show it without the logical plan
+                    explanation.push_str(&format!("\n  Logical Plan: Unable to generate ({e})\n"));
+                }
+                None => {}
             }
         }
 
         Ok(explanation)
     }
 
+    /// Returns the logical plan of a group's combined query, or why it could
+    /// not be generated. Returns `None` for groups without a combined query.
+    pub(crate) async fn logical_plan(
+        &self,
+        group: &ConstraintGroup,
+        ctx: &TermContext,
+    ) -> Option<Result<String, String>> {
+        if group.combined_sql.is_empty() {
+            return None;
+        }
+        Some(match ctx.inner().sql(&group.combined_sql).await {
+            Ok(df) => Ok(df.logical_plan().display_indent().to_string()),
+            Err(e) => Err(e.to_string()),
+        })
+    }
+
     /// Enables or disables predicate pushdown.
     pub fn set_pushdown_enabled(&mut self, enabled: bool) {
         self.enable_pushdown = enabled;
     }
+
+    /// Sets the largest group evaluated constraint by constraint when its combined
+    /// query fails. `0` disables the fallback.
+    pub fn set_max_fallback_constraints(&mut self, max: usize) {
+        self.max_fallback_constraints = max;
+    }
+}
+
+/// Describes the error a constraint failed with when evaluated on its own.
+fn individual_error_message(constraint: &ConstraintAnalysis, error: &TermError) -> String {
+    match constraint.constraint.column() {
+        Some(column) => format!(
+            "Error evaluating constraint '{}' on column '{column}': {error}",
+            constraint.name
+        ),
+        None => format!("Error evaluating constraint '{}': {error}", constraint.name),
+    }
 }
 
 impl Default for OptimizedExecutor {
@@ -347,5 +507,9 @@ mod tests {
     fn test_executor_creation() {
         let executor = OptimizedExecutor::new();
         assert!(executor.enable_pushdown);
+        assert_eq!(
+            executor.max_fallback_constraints,
+            DEFAULT_MAX_FALLBACK_CONSTRAINTS
+        );
     }
 }
@@ -4,24 +4,32 @@
 //! validation queries by:
 //! - Combining multiple checks into single table scans
 //! - Implementing predicate pushdown for partitioned data
-//! - Caching statistics across validation runs
-//! - Providing query plan explanations for debugging
-
-use crate::core::{Check, Constraint, ConstraintResult, TermContext};
+//! - Caching statistics across validation runs, optionally shared between
+//!   suites and optimizers through a [`SharedStatsCache`]
+//! - Providing query plan explanations for debugging, as text or as a
+//!   structured [`OptimizationPlan`]
+//! - Evaluating constraints individually when a combined query fails, recorded
+//!   as [`GroupFallback`] diagnostics
+
+use crate::core::{
+    Check, Constraint, ConstraintResult, MetricKey, PerformanceMetrics, TermContext,
+};
 use crate::prelude::TermError;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::instrument;
+use tracing::{instrument, warn};
 
 pub mod analyzer;
 pub mod combiner;
 pub mod executor;
+pub mod plan;
 pub mod stats_cache;
 
 pub use analyzer::QueryAnalyzer;
 pub use combiner::QueryCombiner;
-pub use executor::OptimizedExecutor;
-pub use stats_cache::StatsCache;
+pub use executor::{GroupFallback, OptimizedExecutor, DEFAULT_MAX_FALLBACK_CONSTRAINTS};
+pub use plan::{GroupCost, OptimizationPlan, PlanCacheStats, PlanGroup, PushdownStatus};
+pub use stats_cache::{SharedStatsCache, SharedStatsMetrics, StatsCache};
 
 /// Query optimizer for validation constraints.
 #[derive(Debug)]
@@ -30,6 +38,9 @@ pub struct QueryOptimizer {
     combiner: QueryCombiner,
     executor: OptimizedExecutor,
     stats_cache: StatsCache,
+    shared_stats: Option<SharedStatsCache>,
+    performance: PerformanceMetrics,
+    fallbacks: Vec<GroupFallback>,
 }
 
 impl QueryOptimizer {
@@ -40,9 +51,21 @@ impl QueryOptimizer {
             combiner: QueryCombiner::new(),
             executor: OptimizedExecutor::new(),
             stats_cache: StatsCache::new(),
+            shared_stats: None,
+            performance: PerformanceMetrics::new(),
+            fallbacks: Vec::new(),
         }
     }
 
+    /// Shares table row counts with suites and other optimizers through `cache`.
+    ///
+    /// Before a group runs, the row count of its table is looked up in the shared
+    /// cache; row counts computed by the group are published to it.
+    pub fn with_shared_stats(mut self, cache: SharedStatsCache) -> Self {
+        self.shared_stats = Some(cache);
+        self
+    }
+
     /// Optimizes and executes a set of checks.
     ///
     /// This method:
@@ -51,6 +74,11 @@ impl QueryOptimizer {
     /// 3. Combines queries to minimize table scans
     /// 4. Executes optimized queries and maps results back to constraints
     ///
+    /// Constraints computing a numeric statistic on a non-numeric column fail with
+    /// a configuration error before any query runs. If a combined query fails anyway,
+    /// its constraints are evaluated individually and the failure is recorded in
+    /// [`fallbacks`](Self::fallbacks).
+    ///
     /// # Arguments
     ///
     /// * `checks` - The validation checks to optimize and execute
@@ -71,17 +99,54 @@ impl QueryOptimizer {
         // Analyze constraints to identify optimization opportunities
         let analysis = self.analyzer.analyze(&constraints)?;
 
+        // Fail misconfigured constraints up front so they don't break a combined query
+        let mut results = HashMap::new();
+        let mut type_errors = self.analyzer.check_column_types(&analysis, ctx).await;
+        let analysis: Vec<_> = analysis
+            .into_iter()
+            .filter(|a| match type_errors.remove(&a.name) {
+                Some(message) => {
+                    results.insert(a.name.clone(), ConstraintResult::failure(message));
+                    false
+                }
+                None => true,
+            })
+            .collect();
+
         // Group constraints by optimization strategy
         let groups = self.combiner.group_constraints(analysis)?;
 
         // Execute optimized queries
-        let mut results = HashMap::new();
+        self.performance = PerformanceMetrics::new();
+        self.fallbacks.clear();
 
         for group in groups {
-            let group_results = self
+            let group_key = Self::group_key(&group);
+            let table = group.constraints[0].table_name.clone();
+            let cache_key = format!("table:{table}");
+            let row_count = MetricKey::row_count(table.as_str());
+
+            // Seed the optimizer's cache with a row count shared by another run
+            let shared = self.shared_fingerprint(&table, ctx).await;
+            let shared_hit = shared
+                .as_ref()
+                .and_then(|(cache, fingerprint)| cache.get(fingerprint, &row_count));
+            if let Some(count) = shared_hit {
+                self.stats_cache.set(cache_key.clone(), count as f64);
+            }
+
+            let (group_results, group_metrics, fallback) = self
                 .executor
-                .execute_group(group, ctx, &mut self.stats_cache)
+                .execute_group_diagnosed(group, ctx, &mut self.stats_cache)
                 .await?;
+
+            if let (Some((cache, fingerprint)), None) = (&shared, shared_hit) {
+                if let Some(count) = self.stats_cache.get(&cache_key) {
+                    cache.insert(fingerprint, &row_count, count as u64);
+                }
+            }
+            self.performance.record_check(group_key, group_metrics);
+            self.fallbacks.extend(fallback);
             results.extend(group_results);
         }
 
@@ -89,6 +154,63 @@ impl QueryOptimizer {
         Ok(self.map_results_to_checks(checks, results))
     }
 
+    /// Returns the execution metrics collected by the last call to
+    /// [`optimize_and_execute`](Self::optimize_and_execute).
+    ///
+    /// Groups whose constraints all belong to one check are keyed by that check's
+    /// name; groups spanning several checks are keyed by the check names joined with `+`.
+    pub fn performance_metrics(&self) -> &PerformanceMetrics {
+        &self.performance
+    }
+
+    /// Returns the combined queries that failed during the last call to
+    /// [`optimize_and_execute`](Self::optimize_and_execute), with the original
+    /// error and the constraints it was attributed to.
+    pub fn fallbacks(&self) -> &[GroupFallback] {
+        &self.fallbacks
+    }
+
+    /// Returns the executor, e.g. to configure the fallback.
+    pub fn executor_mut(&mut self) -> &mut OptimizedExecutor {
+        &mut self.executor
+    }
+
+    /// Returns the shared cache and the fingerprint under which the statistics
+    /// of `table` are shared, if the optimizer shares statistics.
+    async fn shared_fingerprint(
+        &self,
+        table: &str,
+        ctx: &TermContext,
+    ) -> Option<(SharedStatsCache, String)> {
+        let cache = self.shared_stats.as_ref()?;
+        match cache.fingerprint(ctx.inner(), table).await {
+            Ok(fingerprint) => fingerprint.map(|fingerprint| (cache.clone(), fingerprint)),
+            Err(e) => {
+                warn!(
+                    table = %table,
+                    error = %e,
+                    "Failed to fingerprint table, not sharing its statistics"
+                );
+                None
+            }
+        }
+    }
+
+    /// Returns the breakdown key for a constraint group.
+    fn group_key(group: &combiner::ConstraintGroup) -> String {
+        let mut check_names: Vec<&str> = Vec::new();
+        for constraint in &group.constraints {
+            let check_name = constraint
+                .name
+                .split_once('.')
+                .map_or(constraint.name.as_str(), |(check, _)| check);
+            if !check_names.contains(&check_name) {
+                check_names.push(check_name);
+            }
+        }
+        check_names.join("+")
+    }
+
     /// Extracts all constraints from checks.
     fn extract_constraints(&self, checks: &[Check]) -> Vec<(String, Arc<dyn Constraint>)> {
         let mut constraints = Vec::new();
@@ -137,78 +259,151 @@ impl QueryOptimizer {
     /// - Which constraints were grouped together
     /// - What optimizations were applied
     /// - The resulting query plans
+    ///
+    /// The text is rendered from [`optimization_plan`](Self::optimization_plan).
     pub async fn explain_plan(
         &mut self,
         checks: &[Check],
         ctx: &TermContext,
     ) -> Result<String, TermError> {
+        Ok(self.optimization_plan(checks, ctx).await?.to_text())
+    }
+
+    /// Explains the query optimization plan as JSON.
+    ///
+    /// See [`OptimizationPlan`] for the fields.
+    pub async fn explain_plan_json(
+        &mut self,
+        checks: &[Check],
+        ctx: &TermContext,
+    ) -> Result<serde_json::Value, TermError> {
+        Ok(self.optimization_plan(checks, ctx).await?.to_json())
+    }
+
+    /// Builds the structured optimization plan for a set of checks.
+    ///
+    /// The plan lists every query group with its table, member constraints, why
+    /// constraints run standalone, predicate pushdown and cache status, and a
+    /// rough cost estimate. Groups are ordered by table name, so plans of the
+    /// same checks can be compared to catch grouping regressions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use term_guard::constraints::{Assertion, CompletenessConstraint};
+    /// use term_guard::core::{Check, TermContext};
+    /// use term_guard::optimizer::QueryOptimizer;
+    ///
+    /// # async fn example() -> term_guard::prelude::Result<()> {
+    /// let check = Check::builder("basics")
+    ///     .constraint(CompletenessConstraint::with_threshold("id", 1.0))
+    ///     .has_size(Assertion::GreaterThan(0.0))
+    ///     .build();
+    ///
+    /// let ctx = TermContext::new()?;
+    /// let plan = QueryOptimizer::new().optimization_plan(&[check], &ctx).await?;
+    /// assert_eq!(plan.groups[0].constraints, ["basics.completeness", "basics.size"]);
+    /// assert_eq!(plan.estimated_scans(), 1);
+    /// # Ok(())
+    /// # }
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(example()).unwrap();
+    /// ```
+    pub async fn optimization_plan(
+        &mut self,
+        checks: &[Check],
+        ctx: &TermContext,
+    ) -> Result<OptimizationPlan, TermError> {
         let constraints = self.extract_constraints(checks);
         let analysis = self.analyzer.analyze(&constraints)?;
-        let groups = self.combiner.group_constraints(analysis.clone())?;
-
-        let mut explanation = String::new();
-        explanation.push_str("Query Optimization Plan\n");
-        explanation.push_str("======================\n\n");
+        let combinable_constraints = analysis.iter().filter(|a| a.is_combinable).count();
+        let groups = self.combiner.group_constraints(analysis)?;
 
-        // Summary statistics
-        explanation.push_str(&format!("Total Checks: {}\n", checks.len()));
-        explanation.push_str(&format!("Total Constraints: {}\n", constraints.len()));
-        explanation.push_str(&format!("Optimized Groups: {}\n", groups.len()));
+        let mut plan_groups = Vec::with_capacity(groups.len());
+        for group in &groups {
+            plan_groups.push(self.plan_group(group, ctx).await);
+        }
 
-        let combinable = analysis.iter().filter(|a| a.is_combinable).count();
+        let cache_stats = self.stats_cache.stats();
+        Ok(OptimizationPlan {
+            total_checks: checks.len(),
+            total_constraints: constraints.len(),
+            combinable_constraints,
+            groups: plan_groups,
+            cache: PlanCacheStats {
+                total_entries: cache_stats.total_entries,
+                active_entries: cache_stats.active_entries,
+                expired_entries: cache_stats.expired_entries,
+            },
+        })
+    }
 
-        explanation.push_str(&format!("Combinable Constraints: {combinable}\n"));
-        explanation.push_str(&format!(
-            "Optimization Ratio: {:.1}%\n\n",
-            if constraints.is_empty() {
-                0.0
+    /// Describes a single query group of the plan.
+    async fn plan_group(&self, group: &combiner::ConstraintGroup, ctx: &TermContext) -> PlanGroup {
+        let table = group.constraints[0].table_name.clone();
+        let combined = !group.combined_sql.is_empty();
+
+        let standalone_reason = match group.constraints.as_slice() {
+            [single] if !single.is_combinable => Some(
+                analyzer::non_combinable_reason(single.constraint.name())
+                    .unwrap_or("marked as non-combinable by the analyzer")
+                    .to_string(),
+            ),
+            [_] => Some("no compatible constraint on the same table".to_string()),
+            _ => None,
+        };
+
+        let predicate_pushdown = if !group.constraints.iter().any(|c| c.has_predicates) {
+            PushdownStatus::NotApplicable
+        } else if self.executor.enable_pushdown {
+            PushdownStatus::Enabled
+        } else {
+            PushdownStatus::Disabled
+        };
+
+        let mut columns_touched: Vec<String> = group
+            .constraints
+            .iter()
+            .flat_map(|c| c.columns.iter().cloned())
+            .collect();
+        columns_touched.sort();
+        columns_touched.dedup();
+        let estimated_cost = plan::GroupCost {
+            table_scans: 1,
+            scans_saved: if combined {
+                group.constraints.len() - 1
             } else {
-                (groups.len() as f64 / constraints.len() as f64) * 100.0
-            }
-        ));
-
-        // Detailed group information
-        for (i, group) in groups.iter().enumerate() {
-            explanation.push_str(&format!(
-                "Group {}: {} constraints\n",
-                i + 1,
-                group.constraints.len()
-            ));
-            explanation.push_str(&format!("  Table: {}\n", group.constraints[0].table_name));
-
-            // Show optimization benefits
-            if group.constraints.len() > 1 {
-                explanation.push_str(&format!(
-                    "  Benefit: {} table scans reduced to 1\n",
-                    group.constraints.len()
-                ));
-            }
-
-            // Show predicate pushdown info if applicable
-            let has_predicates = group.constraints.iter().any(|c| c.has_predicates);
-            if has_predicates && self.executor.enable_pushdown {
-                explanation.push_str("  Predicate Pushdown: Enabled\n");
+                0
+            },
+            columns_touched,
+            aggregations: group.constraints.iter().map(|c| c.aggregations.len()).sum(),
+        };
+
+        let (logical_plan, logical_plan_error) = match self.executor.logical_plan(group, ctx).await
+        {
+            Some(Ok(plan)) => (Some(plan), None),
+            Some(Err(e)) => (None, Some(e)),
+            None => (None, None),
+        };
+
+        let shared_hit = match self.shared_fingerprint(&table, ctx).await {
+            Some((cache, fingerprint)) => {
+                cache.contains(&fingerprint, &MetricKey::row_count(table.as_str()))
             }
-
-            explanation.push_str(&self.executor.explain_group(group, ctx).await?);
-            explanation.push('\n');
+            None => false,
+        };
+
+        PlanGroup {
+            constraints: group.constraints.iter().map(|c| c.name.clone()).collect(),
+            cache_hit: shared_hit || self.stats_cache.get(&format!("table:{table}")).is_some(),
+            table,
+            combined,
+            standalone_reason,
+            combined_sql: combined.then(|| group.combined_sql.clone()),
+            predicate_pushdown,
+            estimated_cost,
+            logical_plan,
+            logical_plan_error,
         }
-
-        // Cache statistics
-        let cache_stats = self.stats_cache.stats();
-        explanation.push_str("Cache Statistics\n");
-        explanation.push_str("----------------\n");
-        explanation.push_str(&format!("  Total Entries: {}\n", cache_stats.total_entries));
-        explanation.push_str(&format!(
-            "  Active Entries: {}\n",
-            cache_stats.active_entries
-        ));
-        explanation.push_str(&format!(
-            "  Expired Entries: {}\n\n",
-            cache_stats.expired_entries
-        ));
-
-        Ok(explanation)
     }
 }
 
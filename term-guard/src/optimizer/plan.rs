@@ -0,0 +1,221 @@
+//! Structured optimization plans.
+//!
+//! [`OptimizationPlan`] describes how the optimizer groups constraints into
+//! queries. It serializes to JSON for assertions in CI or rendering in a UI, and
+//! [`QueryOptimizer::explain_plan`](super::QueryOptimizer::explain_plan) renders
+//! the same structure as text so both views stay consistent.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// The optimization plan for a set of checks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptimizationPlan {
+    /// Number of checks in the plan
+    pub total_checks: usize,
+    /// Number of constraints across all checks
+    pub total_constraints: usize,
+    /// Number of constraints the analyzer considers combinable
+    pub combinable_constraints: usize,
+    /// Query groups in execution order
+    pub groups: Vec<PlanGroup>,
+    /// State of the statistics cache when the plan was built
+    pub cache: PlanCacheStats,
+}
+
+/// A group of constraints executed together.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanGroup {
+    /// Table the group reads
+    pub table: String,
+    /// Names of the member constraints, as `check.constraint`
+    pub constraints: Vec<String>,
+    /// Whether the members share a combined query
+    pub combined: bool,
+    /// Why the group holds a single constraint, if it does
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub standalone_reason: Option<String>,
+    /// The combined query, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub combined_sql: Option<String>,
+    /// Predicate pushdown status of the group
+    pub predicate_pushdown: PushdownStatus,
+    /// Whether table statistics for the group were found in the cache of the
+    /// optimizer or in its shared cache
+    pub cache_hit: bool,
+    /// Rough cost estimate of the group
+    pub estimated_cost: GroupCost,
+    /// DataFusion logical plan of the combined query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logical_plan: Option<String>,
+    /// Why the logical plan could not be generated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logical_plan_error: Option<String>,
+}
+
+/// Predicate pushdown status of a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PushdownStatus {
+    /// No member constraint has predicates
+    NotApplicable,
+    /// Predicates are pushed down to the scan
+    Enabled,
+    /// Members have predicates but pushdown is disabled
+    Disabled,
+}
+
+/// Rough cost estimate of a group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupCost {
+    /// Table scans the group is expected to issue
+    pub table_scans: usize,
+    /// Scans avoided compared to running every member separately
+    pub scans_saved: usize,
+    /// Columns read by the members, sorted
+    pub columns_touched: Vec<String>,
+    /// Aggregate expressions computed by the members
+    pub aggregations: usize,
+}
+
+/// Statistics cache entries at planning time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanCacheStats {
+    /// Total number of entries
+    pub total_entries: usize,
+    /// Number of active (non-expired) entries
+    pub active_entries: usize,
+    /// Number of expired entries
+    pub expired_entries: usize,
+}
+
+impl OptimizationPlan {
+    /// Number of query groups divided by the number of constraints, in percent.
+    pub fn optimization_ratio(&self) -> f64 {
+        if self.total_constraints == 0 {
+            0.0
+        } else {
+            (self.groups.len() as f64 / self.total_constraints as f64) * 100.0
+        }
+    }
+
+    /// Total table scans expected across all groups.
+    pub fn estimated_scans(&self) -> usize {
+        self.groups
+            .iter()
+            .map(|group| group.estimated_cost.table_scans)
+            .sum()
+    }
+
+    /// Serializes the plan to a JSON value.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+
+    /// Renders the plan as human-readable text.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        out.push_str("Query Optimization Plan\n");
+        out.push_str("======================\n\n");
+
+        let _ = writeln!(out, "Total Checks: {}", self.total_checks);
+        let _ = writeln!(out, "Total Constraints: {}", self.total_constraints);
+        let _ = writeln!(out, "Optimized Groups: {}", self.groups.len());
+        let _ = writeln!(
+            out,
+            "Combinable Constraints: {}",
+            self.combinable_constraints
+        );
+        let _ = writeln!(out, "Estimated Table Scans: {}", self.estimated_scans());
+        let _ = writeln!(
+            out,
+            "Optimization Ratio: {:.1}%\n",
+            self.optimization_ratio()
+        );
+
+        for (i, group) in self.groups.iter().enumerate() {
+            group.write_text(i + 1, &mut out);
+            out.push('\n');
+        }
+
+        out.push_str("Cache Statistics\n");
+        out.push_str("----------------\n");
+        let _ = writeln!(out, "  Total Entries: {}", self.cache.total_entries);
+        let _ = writeln!(out, "  Active Entries: {}", self.cache.active_entries);
+        let _ = writeln!(out, "  Expired Entries: {}\n", self.cache.expired_entries);
+        out
+    }
+}
+
+impl PlanGroup {
+    fn write_text(&self, number: usize, out: &mut String) {
+        let _ = writeln!(
+            out,
+            "Group {number}: {} constraints",
+            self.constraints.len()
+        );
+        let _ = writeln!(out, "  Table: {}", self.table);
+
+        let cost = &self.estimated_cost;
+        if cost.scans_saved > 0 {
+            let _ = writeln!(
+                out,
+                "  Benefit: {} table scans reduced to {}",
+                cost.scans_saved + cost.table_scans,
+                cost.table_scans
+            );
+        }
+        let columns = if cost.columns_touched.is_empty() {
+            "-".to_string()
+        } else {
+            cost.columns_touched.join(", ")
+        };
+        let _ = writeln!(
+            out,
+            "  Estimated Cost: {} scan(s), {} aggregation(s), columns: {columns}",
+            cost.table_scans, cost.aggregations
+        );
+
+        match self.predicate_pushdown {
+            PushdownStatus::Enabled => out.push_str("  Predicate Pushdown: Enabled\n"),
+            PushdownStatus::Disabled => out.push_str("  Predicate Pushdown: Disabled\n"),
+            PushdownStatus::NotApplicable => {}
+        }
+        if self.cache_hit {
+            out.push_str("  Cached Statistics: Hit\n");
+        }
+
+        if !self.combined {
+            let reason = self
+                .standalone_reason
+                .as_deref()
+                .unwrap_or("non-combinable");
+            let _ = writeln!(
+                out,
+                "  - {} ({reason}, executed individually)",
+                self.constraints[0]
+            );
+            return;
+        }
+
+        out.push_str("  Combined constraints:\n");
+        for constraint in &self.constraints {
+            let _ = writeln!(out, "    - {constraint}");
+        }
+        if let Some(reason) = &self.standalone_reason {
+            let _ = writeln!(out, "  Standalone: {reason}");
+        }
+        if let Some(sql) = &self.combined_sql {
+            let _ = writeln!(out, "\n  Combined SQL:\n    {sql}");
+        }
+        match (&self.logical_plan, &self.logical_plan_error) {
+            (Some(plan), _) => {
+                let _ = writeln!(out, "\n  Logical Plan:\n{plan}");
+            }
+            (None, Some(error)) => {
+                let _ = writeln!(out, "\n  Logical Plan: Unable to generate ({error})");
+            }
+            (None, None) => {}
+        }
+    }
+}
@@ -1,8 +1,21 @@
 //! Statistics caching for query optimization.
+//!
+//! A [`StatsCache`] belongs to a single optimizer. A [`SharedStatsCache`] is shared
+//! between suites and optimizers of a process, so suites validating the same table
+//! back-to-back compute its basic statistics once.
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
 use std::time::{Duration, Instant};
 
+use datafusion::datasource::TableProvider;
+use datafusion::prelude::SessionContext;
+use tracing::{debug, warn};
+
+use crate::core::{hash_parts, source_fingerprint, MetricCache, MetricKey};
+use crate::prelude::Result;
+
 /// Cache entry with timestamp.
 #[derive(Debug, Clone)]
 struct CacheEntry {
@@ -79,6 +92,26 @@ impl StatsCache {
             .retain(|_, entry| now.duration_since(entry.timestamp) < self.ttl);
     }
 
+    /// Removes the entry of `key` if it has expired, returning whether it did.
+    pub fn remove_if_expired(&mut self, key: &str) -> bool {
+        let expired = self
+            .cache
+            .get(key)
+            .is_some_and(|entry| entry.timestamp.elapsed() >= self.ttl);
+        if expired {
+            self.cache.remove(key);
+        }
+        expired
+    }
+
+    /// Removes the entries whose key matches `predicate`, returning how many
+    /// were removed.
+    pub fn remove_matching(&mut self, predicate: impl Fn(&str) -> bool) -> usize {
+        let before = self.cache.len();
+        self.cache.retain(|key, _| !predicate(key));
+        before - self.cache.len()
+    }
+
     /// Gets the current size of the cache.
     pub fn size(&self) -> usize {
         self.cache.len()
@@ -130,6 +163,293 @@ impl Default for StatsCache {
     }
 }
 
+/// Counters of a [`SharedStatsCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SharedStatsMetrics {
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups that found no valid entry
+    pub misses: u64,
+    /// Entries removed because their TTL had passed or their table was dropped
+    pub stale_evictions: u64,
+    /// Entries removed by [`SharedStatsCache::invalidate_table`] or
+    /// [`SharedStatsCache::clear`]
+    pub invalidations: u64,
+    /// Number of entries currently cached, including expired ones not looked up yet
+    pub entries: usize,
+}
+
+#[derive(Debug)]
+struct SharedStatsInner {
+    cache: Mutex<StatsCache>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    stale_evictions: AtomicU64,
+    invalidations: AtomicU64,
+    /// Providers of the fingerprinted registrations, by fingerprint
+    registrations: Mutex<HashMap<String, Weak<dyn TableProvider>>>,
+}
+
+/// Basic table statistics shared between suites and optimizers of a process.
+///
+/// Suites given the same cache with
+/// [`with_shared_stats`](crate::core::ValidationSuiteBuilder::with_shared_stats)
+/// reuse each other's unfiltered metrics of the validated table, such as its row
+/// count and the NULL counts of its columns, so a size or completeness constraint
+/// only queries the table in the first suite. The
+/// [`QueryOptimizer`](super::QueryOptimizer) shares row counts the same way.
+///
+/// Entries are keyed by the table name and the fingerprint of its registration,
+/// which covers the registered table provider, its schema and, for file-backed
+/// tables, the file manifest (path, size, modification time). Registering a table
+/// again therefore starts over, and changed files are never served stale
+/// statistics. Entries of a registration are dropped with it.
+///
+/// Tables modified in place, e.g. by `INSERT INTO` a memory table, keep their
+/// fingerprint; call [`invalidate_table`](Self::invalidate_table) after such
+/// changes. Entries also expire after the TTL of the cache.
+///
+/// Cloning the cache shares the entries and counters, so it can be handed to any
+/// number of suites and runs, including concurrent ones.
+///
+/// # Examples
+///
+/// ```rust
+/// use term_guard::constraints::Assertion;
+/// use term_guard::core::{Check, ValidationSuite};
+/// use term_guard::optimizer::SharedStatsCache;
+/// use datafusion::prelude::*;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let ctx = SessionContext::new();
+/// ctx.sql("CREATE TABLE data AS VALUES (1), (2), (3)").await?.collect().await?;
+///
+/// let stats = SharedStatsCache::new();
+/// let suite = |name: &str| {
+///     ValidationSuite::builder(name)
+///         .check(Check::builder("size").has_size(Assertion::GreaterThan(0.0)).build())
+///         .with_shared_stats(stats.clone())
+///         .build()
+/// };
+///
+/// suite("team_a").run(&ctx).await?;
+/// suite("team_b").run(&ctx).await?;
+///
+/// let metrics = stats.metrics();
+/// assert_eq!((metrics.hits, metrics.misses), (1, 1));
+/// # Ok(())
+/// # }
+/// # tokio::runtime::Runtime::new().unwrap().block_on(example()).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedStatsCache {
+    inner: Arc<SharedStatsInner>,
+}
+
+impl SharedStatsCache {
+    /// Creates a shared cache with the defaults of [`StatsCache::new`].
+    pub fn new() -> Self {
+        Self::from_cache(StatsCache::new())
+    }
+
+    /// Creates a shared cache with a custom TTL and capacity.
+    pub fn with_config(ttl: Duration, max_entries: usize) -> Self {
+        Self::from_cache(StatsCache::with_config(ttl, max_entries))
+    }
+
+    fn from_cache(cache: StatsCache) -> Self {
+        Self {
+            inner: Arc::new(SharedStatsInner {
+                cache: Mutex::new(cache),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+                stale_evictions: AtomicU64::new(0),
+                invalidations: AtomicU64::new(0),
+                registrations: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Computes the registration fingerprint of a table, or `None` if no table
+    /// of that name is registered.
+    pub async fn fingerprint(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+    ) -> Result<Option<String>> {
+        let provider = match ctx.table_provider(table_name).await {
+            Ok(provider) => provider,
+            Err(_) => return Ok(None),
+        };
+        let identity = format!("{:p}", Arc::as_ptr(&provider));
+        let manifest = source_fingerprint(ctx, table_name)
+            .await?
+            .unwrap_or_default();
+        let schema = format!("{:?}", provider.schema());
+        let fingerprint = hash_parts([
+            table_name,
+            identity.as_str(),
+            manifest.as_str(),
+            schema.as_str(),
+        ]);
+
+        self.release_dropped_registrations();
+        // The weak reference keeps the provider's address from being reused by
+        // another registration while entries under this fingerprint exist
+        if let Ok(mut registrations) = self.inner.registrations.lock() {
+            registrations
+                .entry(fingerprint.clone())
+                .or_insert_with(|| Arc::downgrade(&provider));
+        }
+        Ok(Some(fingerprint))
+    }
+
+    /// Returns the cached value of a metric of the table with the given
+    /// fingerprint, counting the lookup in the [`metrics`](Self::metrics).
+    pub fn get(&self, fingerprint: &str, key: &MetricKey) -> Option<u64> {
+        let entry_key = Self::entry_key(fingerprint, key);
+        let Ok(mut cache) = self.inner.cache.lock() else {
+            return None;
+        };
+
+        if cache.remove_if_expired(&entry_key) {
+            self.inner.stale_evictions.fetch_add(1, Ordering::Relaxed);
+        }
+        let value = cache.get(&entry_key);
+        let counter = if value.is_some() {
+            &self.inner.hits
+        } else {
+            &self.inner.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value.map(|value| value as u64)
+    }
+
+    /// Returns whether a valid value of a metric is cached, without counting
+    /// the lookup.
+    pub fn contains(&self, fingerprint: &str, key: &MetricKey) -> bool {
+        let entry_key = Self::entry_key(fingerprint, key);
+        self.inner
+            .cache
+            .lock()
+            .is_ok_and(|cache| cache.get(&entry_key).is_some())
+    }
+
+    /// Stores the value of a metric of the table with the given fingerprint.
+    pub fn insert(&self, fingerprint: &str, key: &MetricKey, value: u64) {
+        if let Ok(mut cache) = self.inner.cache.lock() {
+            cache.set(Self::entry_key(fingerprint, key), value as f64);
+        }
+    }
+
+    /// Removes every entry of a table, whatever its fingerprint, returning how
+    /// many were removed.
+    pub fn invalidate_table(&self, table_name: &str) -> usize {
+        let prefix = format!("{table_name}\0");
+        let removed = self
+            .inner
+            .cache
+            .lock()
+            .map(|mut cache| cache.remove_matching(|key| key.starts_with(&prefix)))
+            .unwrap_or_default();
+        self.inner
+            .invalidations
+            .fetch_add(removed as u64, Ordering::Relaxed);
+        debug!(table = %table_name, removed, "Invalidated shared statistics");
+        removed
+    }
+
+    /// Removes every entry.
+    pub fn clear(&self) {
+        if let Ok(mut cache) = self.inner.cache.lock() {
+            let removed = cache.size();
+            cache.clear();
+            self.inner
+                .invalidations
+                .fetch_add(removed as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the counters of the cache.
+    pub fn metrics(&self) -> SharedStatsMetrics {
+        SharedStatsMetrics {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            stale_evictions: self.inner.stale_evictions.load(Ordering::Relaxed),
+            invalidations: self.inner.invalidations.load(Ordering::Relaxed),
+            entries: self
+                .inner
+                .cache
+                .lock()
+                .map(|cache| cache.size())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Creates the metric cache of a run validating `table_name`, backed by this
+    /// cache.
+    ///
+    /// Tables that cannot be fingerprinted get a run cache without sharing.
+    pub(crate) async fn metric_cache(&self, ctx: &SessionContext, table_name: &str) -> MetricCache {
+        match self.fingerprint(ctx, table_name).await {
+            Ok(Some(fingerprint)) => MetricCache::backed_by(
+                self.clone(),
+                HashMap::from([(table_name.to_string(), fingerprint)]),
+            ),
+            Ok(None) => MetricCache::new(),
+            Err(e) => {
+                warn!(
+                    table = %table_name,
+                    error = %e,
+                    "Failed to fingerprint table, not sharing its statistics"
+                );
+                MetricCache::new()
+            }
+        }
+    }
+
+    /// Removes the entries of registrations whose table was dropped, then
+    /// forgets the registrations.
+    fn release_dropped_registrations(&self) {
+        let Ok(mut registrations) = self.inner.registrations.lock() else {
+            return;
+        };
+        let dropped: Vec<String> = registrations
+            .iter()
+            .filter(|(_, provider)| provider.strong_count() == 0)
+            .map(|(fingerprint, _)| fingerprint.clone())
+            .collect();
+        if dropped.is_empty() {
+            return;
+        }
+
+        if let Ok(mut cache) = self.inner.cache.lock() {
+            for fingerprint in &dropped {
+                let marker = format!("\0{fingerprint}\0");
+                let removed = cache.remove_matching(|key| key.contains(&marker));
+                self.inner
+                    .stale_evictions
+                    .fetch_add(removed as u64, Ordering::Relaxed);
+            }
+        }
+        for fingerprint in dropped {
+            registrations.remove(&fingerprint);
+        }
+    }
+
+    /// Returns the key of an entry; the table name comes first so a table's
+    /// entries can be invalidated together.
+    fn entry_key(fingerprint: &str, key: &MetricKey) -> String {
+        format!("{}\0{fingerprint}\0{key}", key.table())
+    }
+}
+
+impl Default for SharedStatsCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,4 +503,43 @@ mod tests {
         assert_eq!(stats.active_entries, 2);
         assert_eq!(stats.expired_entries, 0);
     }
+
+    #[test]
+    fn test_shared_cache_hits_and_stale_evictions() {
+        let cache = SharedStatsCache::with_config(Duration::from_millis(50), 10);
+        let rows = MetricKey::row_count("orders");
+
+        assert_eq!(cache.get("v1", &rows), None);
+        cache.insert("v1", &rows, 42);
+        assert_eq!(cache.get("v1", &rows), Some(42));
+        // Another registration of the table does not see the entry
+        assert_eq!(cache.get("v2", &rows), None);
+
+        std::thread::sleep(Duration::from_millis(80));
+        assert!(!cache.contains("v1", &rows));
+        assert_eq!(cache.get("v1", &rows), None);
+
+        let metrics = cache.metrics();
+        assert_eq!(metrics.hits, 1);
+        assert_eq!(metrics.misses, 3);
+        assert_eq!(metrics.stale_evictions, 1);
+        assert_eq!(metrics.entries, 0);
+    }
+
+    #[test]
+    fn test_shared_cache_invalidation() {
+        let cache = SharedStatsCache::new();
+        cache.insert("v1", &MetricKey::row_count("orders"), 3);
+        cache.insert("v1", &MetricKey::null_count("orders", "email"), 1);
+        cache.insert("v1", &MetricKey::row_count("orders_archive"), 7);
+
+        assert_eq!(cache.invalidate_table("orders"), 2);
+        assert!(!cache.contains("v1", &MetricKey::row_count("orders")));
+        assert!(cache.contains("v1", &MetricKey::row_count("orders_archive")));
+
+        cache.clear();
+        let metrics = cache.metrics();
+        assert_eq!(metrics.invalidations, 3);
+        assert_eq!(metrics.entries, 0);
+    }
 }
@@ -56,6 +56,9 @@ mod optimizer_tests {
                 status: ConstraintStatus::Success,
                 metric: Some(1.0),
                 message: None,
+                quarantine: None,
+                constraint_id: None,
+                details: Default::default(),
             },
             _sql: "SELECT COUNT(*) FROM data".to_string(),
         };
@@ -66,6 +69,9 @@ mod optimizer_tests {
                 status: ConstraintStatus::Success,
                 metric: Some(0.95),
                 message: None,
+                quarantine: None,
+                constraint_id: None,
+                details: Default::default(),
             },
             _sql: "SELECT COUNT(*), COUNT(column) FROM data".to_string(),
         };
@@ -101,6 +107,9 @@ mod analyzer_tests {
                 status: ConstraintStatus::Success,
                 metric: Some(1.0),
                 message: None,
+                quarantine: None,
+                constraint_id: None,
+                details: Default::default(),
             },
             _sql: "SELECT COUNT(*) FROM data".to_string(),
         });
@@ -149,6 +158,9 @@ mod analyzer_tests {
                     status: ConstraintStatus::Success,
                     metric: Some(1.0),
                     message: None,
+                    quarantine: None,
+                    constraint_id: None,
+                    details: Default::default(),
                 },
                 _sql: String::new(),
             });
@@ -228,6 +240,38 @@ mod combiner_tests {
         assert!(non_combinable_group.is_some());
     }
 
+    #[test]
+    fn test_multi_statistical_constraint_is_one_unit() {
+        use crate::constraints::{Assertion, MultiStatisticalConstraint, StatisticType};
+
+        let analyzer = QueryAnalyzer::new();
+        let multi = MultiStatisticalConstraint::new(
+            "amount",
+            vec![
+                (StatisticType::Min, Assertion::GreaterThan(0.0)),
+                (StatisticType::Count, Assertion::GreaterThan(0.0)),
+                (StatisticType::ApproxDistinct, Assertion::GreaterThan(1.0)),
+            ],
+        )
+        .unwrap();
+        let analyses = vec![
+            create_test_analysis("c1", "data", true),
+            analyzer
+                .analyze_constraint("multi".to_string(), Arc::new(multi))
+                .unwrap(),
+            create_test_analysis("c2", "data", true),
+        ];
+        assert!(!analyses[1].is_combinable);
+
+        let groups = QueryCombiner::new().group_constraints(analyses).unwrap();
+        let multi_group = groups
+            .iter()
+            .find(|g| g.constraints.iter().any(|c| c.name == "multi"))
+            .unwrap();
+        assert_eq!(multi_group.constraints.len(), 1);
+        assert!(multi_group.combined_sql.is_empty());
+    }
+
     fn create_test_analysis(name: &str, table: &str, combinable: bool) -> ConstraintAnalysis {
         ConstraintAnalysis {
             name: name.to_string(),
@@ -237,6 +281,9 @@ mod combiner_tests {
                     status: ConstraintStatus::Success,
                     metric: Some(1.0),
                     message: None,
+                    quarantine: None,
+                    constraint_id: None,
+                    details: Default::default(),
                 },
                 _sql: String::new(),
             }),
@@ -359,3 +406,301 @@ mod stats_cache_tests {
         assert_eq!(cache.size(), 0);
     }
 }
+
+#[cfg(test)]
+mod plan_tests {
+    use super::*;
+    use crate::constraints::{Assertion, CompletenessConstraint};
+    use crate::core::TermContext;
+    use serde_json::json;
+
+    fn orders_check() -> Check {
+        Check::builder("orders")
+            .level(Level::Error)
+            .constraint(CompletenessConstraint::with_threshold("id", 1.0))
+            .has_size(Assertion::GreaterThan(0.0))
+            .has_approx_quantile("amount", 0.5, Assertion::GreaterThan(0.0))
+            .has_min("amount", Assertion::GreaterThan(0.0))
+            .has_max("amount", Assertion::LessThan(1000.0))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_grouping_decisions() {
+        let ctx = TermContext::new().unwrap();
+        let mut optimizer = QueryOptimizer::new();
+        let plan = optimizer
+            .optimization_plan(&[orders_check()], &ctx)
+            .await
+            .unwrap();
+
+        let groups: Vec<_> = plan
+            .groups
+            .iter()
+            .map(|g| {
+                json!({
+                    "constraints": g.constraints,
+                    "combined": g.combined,
+                    "standalone_reason": g.standalone_reason,
+                })
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                json!({
+                    "constraints": ["orders.completeness", "orders.size"],
+                    "combined": true,
+                    "standalone_reason": null,
+                }),
+                json!({
+                    "constraints": ["orders.min"],
+                    "combined": true,
+                    "standalone_reason": "no compatible constraint on the same table",
+                }),
+                json!({
+                    "constraints": ["orders.max"],
+                    "combined": true,
+                    "standalone_reason": "no compatible constraint on the same table",
+                }),
+                json!({
+                    "constraints": ["orders.quantile"],
+                    "combined": false,
+                    "standalone_reason": "quantiles need a sorted scan of the column",
+                }),
+            ]
+        );
+
+        assert_eq!(plan.total_constraints, 5);
+        assert_eq!(plan.combinable_constraints, 4);
+        assert_eq!(plan.estimated_scans(), 4);
+
+        let first = &plan.groups[0];
+        assert_eq!(first.table, "data");
+        assert_eq!(first.estimated_cost.scans_saved, 1);
+        assert_eq!(first.estimated_cost.aggregations, 2);
+        assert_eq!(
+            first.combined_sql.as_deref(),
+            Some("SELECT COUNT(*) as total_count FROM data")
+        );
+        assert_eq!(first.predicate_pushdown, PushdownStatus::NotApplicable);
+        assert!(!first.cache_hit);
+        // The context has no "data" table
+        assert!(first.logical_plan.is_none());
+        assert!(first.logical_plan_error.is_some());
+        assert!(plan.groups[3].combined_sql.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_json_and_text_come_from_the_same_plan() {
+        let ctx = TermContext::new().unwrap();
+        let mut optimizer = QueryOptimizer::new();
+        optimizer.stats_cache.set("table:data".to_string(), 10.0);
+        optimizer.executor.set_pushdown_enabled(false);
+
+        let containment = MockConstraint {
+            name: "containment".to_string(),
+            result: ConstraintResult::success(),
+            _sql: String::new(),
+        };
+        let checks = [Check::builder("values").constraint(containment).build()];
+
+        let plan = optimizer.optimization_plan(&checks, &ctx).await.unwrap();
+        let json = optimizer.explain_plan_json(&checks, &ctx).await.unwrap();
+        let text = optimizer.explain_plan(&checks, &ctx).await.unwrap();
+
+        assert_eq!(json, plan.to_json());
+        assert_eq!(text, plan.to_text());
+        assert_eq!(json["groups"][0]["predicate_pushdown"], "disabled");
+        assert_eq!(json["groups"][0]["cache_hit"], true);
+        assert_eq!(json["cache"]["active_entries"], 1);
+        assert!(text.contains("Predicate Pushdown: Disabled"));
+        assert!(text.contains("Cached Statistics: Hit"));
+
+        let parsed: OptimizationPlan = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, plan);
+    }
+}
+
+#[cfg(test)]
+mod fallback_tests {
+    use super::*;
+    use crate::constraints::{Assertion, StatisticalConstraint};
+    use crate::core::TermContext;
+    use crate::optimizer::analyzer::ConstraintAnalysis;
+    use crate::optimizer::combiner::ConstraintGroup;
+    use arrow::array::{Float64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+
+    fn context() -> TermContext {
+        let ctx = TermContext::new().unwrap();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("amount", DataType::Float64, true),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Float64Array::from(vec![1.0, 2.0, 3.0])),
+                Arc::new(StringArray::from(vec!["a", "b", "c"])),
+            ],
+        )
+        .unwrap();
+        ctx.inner().register_batch("data", batch).unwrap();
+        ctx
+    }
+
+    fn analysis(name: &str, constraint: StatisticalConstraint) -> ConstraintAnalysis {
+        QueryAnalyzer::new()
+            .analyze_constraint(name.to_string(), Arc::new(constraint))
+            .unwrap()
+    }
+
+    /// A group whose combined query references a column that does not exist.
+    fn broken_group() -> ConstraintGroup {
+        ConstraintGroup {
+            constraints: vec![
+                analysis(
+                    "amounts.min",
+                    StatisticalConstraint::min("amount", Assertion::Equals(1.0)).unwrap(),
+                ),
+                analysis(
+                    "totals.sum",
+                    StatisticalConstraint::sum("missing", Assertion::Equals(6.0)).unwrap(),
+                ),
+            ],
+            combined_sql: "SELECT COUNT(*) as total_count, SUM(missing) as s FROM data".to_string(),
+            result_mapping: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_combined_query_falls_back_per_constraint() {
+        let ctx = context();
+        let executor = OptimizedExecutor::new();
+        let mut cache = StatsCache::new();
+
+        let (results, _, fallback) = executor
+            .execute_group_diagnosed(broken_group(), &ctx, &mut cache)
+            .await
+            .unwrap();
+
+        // The healthy constraint gets its real result
+        assert_eq!(results["amounts.min"].status, ConstraintStatus::Success);
+        assert_eq!(results["amounts.min"].metric, Some(1.0));
+
+        let failed = &results["totals.sum"];
+        assert_eq!(failed.status, ConstraintStatus::Failure);
+        let message = failed.message.as_deref().unwrap();
+        assert!(
+            message.contains("'totals.sum' on column 'missing'"),
+            "{message}"
+        );
+
+        let fallback = fallback.unwrap();
+        assert_eq!(fallback.constraints, ["amounts.min", "totals.sum"]);
+        assert_eq!(fallback.failed_constraints, ["totals.sum"]);
+        assert!(fallback.combined_sql.contains("SUM(missing)"));
+        assert!(fallback.error.contains("missing"), "{}", fallback.error);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_is_bounded() {
+        let ctx = context();
+        let mut executor = OptimizedExecutor::new();
+        executor.set_max_fallback_constraints(1);
+        let mut cache = StatsCache::new();
+
+        let error = executor
+            .execute_group(broken_group(), &ctx, &mut cache)
+            .await
+            .unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("amounts.min, totals.sum"), "{message}");
+        assert!(message.contains("Combined query failed"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn test_plan_reports_shared_statistics() {
+        let ctx = context();
+        let shared = SharedStatsCache::new();
+        let fingerprint = shared
+            .fingerprint(ctx.inner(), "data")
+            .await
+            .unwrap()
+            .unwrap();
+        shared.insert(&fingerprint, &MetricKey::row_count("data"), 3);
+
+        let checks = [Check::builder("amounts")
+            .constraint(StatisticalConstraint::min("amount", Assertion::Equals(1.0)).unwrap())
+            .build()];
+        let plan = QueryOptimizer::new()
+            .optimization_plan(&checks, &ctx)
+            .await
+            .unwrap();
+        assert!(!plan.groups[0].cache_hit);
+
+        let plan = QueryOptimizer::new()
+            .with_shared_stats(shared.clone())
+            .optimization_plan(&checks, &ctx)
+            .await
+            .unwrap();
+        assert!(plan.groups[0].cache_hit);
+        // Planning does not count as a lookup
+        assert_eq!(shared.metrics().hits, 0);
+    }
+
+    #[tokio::test]
+    async fn test_numeric_statistic_on_string_column_is_flagged() {
+        let ctx = context();
+        let analyses = vec![
+            analysis(
+                "names.mean",
+                StatisticalConstraint::mean("name", Assertion::GreaterThan(0.0)).unwrap(),
+            ),
+            analysis(
+                "names.max",
+                StatisticalConstraint::max("name", Assertion::GreaterThan(0.0)).unwrap(),
+            ),
+            analysis(
+                "amounts.mean",
+                StatisticalConstraint::mean("amount", Assertion::Equals(2.0)).unwrap(),
+            ),
+        ];
+
+        let mismatches = QueryAnalyzer::new()
+            .check_column_types(&analyses, &ctx)
+            .await;
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(
+            mismatches["names.mean"],
+            "Configuration error: mean requires a numeric column, but 'name' has type Utf8"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_optimize_and_execute_reports_misconfigured_constraints() {
+        let ctx = context();
+        let checks = [
+            Check::builder("names")
+                .has_mean("name", Assertion::GreaterThan(0.0))
+                .build(),
+            Check::builder("amounts")
+                .has_mean("amount", Assertion::Equals(2.0))
+                .build(),
+        ];
+
+        let mut optimizer = QueryOptimizer::new();
+        let results = optimizer.optimize_and_execute(&checks, &ctx).await.unwrap();
+
+        let names = &results["names"][0];
+        assert_eq!(names.status, ConstraintStatus::Failure);
+        assert!(names
+            .message
+            .as_deref()
+            .unwrap()
+            .contains("'name' has type Utf8"));
+        assert_eq!(results["amounts"][0].status, ConstraintStatus::Success);
+    }
+}
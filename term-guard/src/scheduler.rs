@@ -0,0 +1,923 @@
+//! Periodic validation inside a running tokio service.
+//!
+//! A [`ValidationScheduler`] runs a [`ValidationSuite`] on a [`Schedule`] — a
+//! fixed interval or a cron expression — against a fresh [`SessionContext`]
+//! built by a factory for every run. Results are delivered with the
+//! [`ResultKey`] of their run, through a channel or a callback.
+//!
+//! Runs execute as their own tasks, so a slow run never delays the schedule.
+//! A tick arriving while the previous run is still going is handled according
+//! to the [`OverlapPolicy`]: skipped, or queued until that run finishes. Every
+//! run records the [`SCHEDULE_RUN_TAG`] and [`SCHEDULE_TIME_TAG`] tags, which
+//! constraints see through [`ValidationContext::tag`](crate::core::ValidationContext::tag)
+//! and which are copied onto the result key.
+//!
+//! Runs that fail with an error, for example because the context factory
+//! could not register a table, are logged and produce no result.
+//!
+//! The scheduler stops when [`SchedulerHandle::shutdown`] is called or the
+//! handle is dropped. [`ShutdownMode::Finish`] lets the run in flight complete
+//! and deliver its result; [`ShutdownMode::Cancel`] aborts it.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use datafusion::prelude::*;
+//! use std::time::Duration;
+//! use term_guard::constraints::Assertion;
+//! use term_guard::core::{Check, ValidationSuite};
+//! use term_guard::scheduler::{OverlapPolicy, ShutdownMode, ValidationScheduler};
+//!
+//! # async fn example() -> term_guard::prelude::Result<()> {
+//! let suite = ValidationSuite::builder("orders")
+//!     .table_name("orders")
+//!     .check(
+//!         Check::builder("volume")
+//!             .has_size(Assertion::GreaterThan(0.0))
+//!             .build(),
+//!     )
+//!     .build();
+//!
+//! let (handle, mut results) = ValidationScheduler::new(
+//!     suite,
+//!     || async {
+//!         let ctx = SessionContext::new();
+//!         ctx.register_csv("orders", "data/orders.csv", CsvReadOptions::new())
+//!             .await?;
+//!         Ok(ctx)
+//!     },
+//!     Duration::from_secs(15 * 60),
+//! )
+//! .jitter(Duration::from_secs(30))
+//! .overlap(OverlapPolicy::Skip)
+//! .spawn()?;
+//!
+//! while let Some((key, result)) = results.recv().await {
+//!     println!("run at {}: {}", key.as_datetime(), result.is_success());
+//! }
+//! handle.shutdown(ShutdownMode::Finish).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::{ValidationResult, ValidationSuite};
+use crate::prelude::*;
+use crate::repository::ResultKey;
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use datafusion::prelude::SessionContext;
+use futures::future::{BoxFuture, FutureExt};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tokio::task::{JoinError, JoinHandle};
+use tokio::time::Instant;
+use tracing::{debug, error, info, warn};
+
+/// The tag recording the sequence number of a scheduled run, starting at 1.
+pub const SCHEDULE_RUN_TAG: &str = "schedule.run";
+
+/// The tag recording the time a run was scheduled for, in RFC 3339 format.
+///
+/// This is the time of the tick, before any jitter delays the run.
+pub const SCHEDULE_TIME_TAG: &str = "schedule.scheduled_at";
+
+/// When a [`ValidationScheduler`] runs its suite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schedule {
+    /// Runs when the scheduler starts and then every interval
+    Every(Duration),
+    /// Runs at the times matching a cron expression, in UTC
+    Cron(CronSchedule),
+}
+
+impl Schedule {
+    /// Parses a cron expression, see [`CronSchedule`].
+    pub fn cron(expression: &str) -> Result<Self> {
+        expression.parse().map(Schedule::Cron)
+    }
+
+    fn first(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Schedule::Every(_) => Some(now),
+            Schedule::Cron(cron) => cron.next_after(now),
+        }
+    }
+
+    fn next(&self, previous: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Schedule::Every(interval) => chrono::Duration::from_std(*interval)
+                .ok()
+                .and_then(|interval| previous.checked_add_signed(interval)),
+            Schedule::Cron(cron) => cron.next_after(previous),
+        }
+    }
+}
+
+impl From<Duration> for Schedule {
+    fn from(interval: Duration) -> Self {
+        Schedule::Every(interval)
+    }
+}
+
+impl From<CronSchedule> for Schedule {
+    fn from(cron: CronSchedule) -> Self {
+        Schedule::Cron(cron)
+    }
+}
+
+/// A five-field cron expression: minute, hour, day of month, month and day of
+/// week.
+///
+/// Each field accepts `*`, single values, ranges such as `1-5`, steps such as
+/// `*/15` or `0-30/10`, and comma-separated lists of these. Days of the week
+/// run from 0 (Sunday) to 6, with 7 also meaning Sunday. As in classic cron,
+/// when both the day of month and the day of week are restricted, a day
+/// matching either runs. The shorthands `@hourly`, `@daily`, `@weekly`,
+/// `@monthly` and `@yearly` are accepted too. Times are evaluated in UTC.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use term_guard::scheduler::CronSchedule;
+///
+/// let cron: CronSchedule = "*/15 9-17 * * 1-5".parse().unwrap();
+/// let friday_evening = Utc.with_ymd_and_hms(2024, 3, 8, 17, 50, 0).unwrap();
+/// assert_eq!(
+///     cron.next_after(friday_evening),
+///     Some(Utc.with_ymd_and_hms(2024, 3, 11, 9, 0, 0).unwrap())
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    minutes: CronField,
+    hours: CronField,
+    days_of_month: CronField,
+    months: CronField,
+    days_of_week: CronField,
+}
+
+impl CronSchedule {
+    /// Returns the first time after `after` matching the expression, or `None`
+    /// if no time in the next five years does, as for `0 0 30 2 *`.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let limit = after.year() + 5;
+        let mut time = after
+            .naive_utc()
+            .with_second(0)?
+            .with_nanosecond(0)?
+            .checked_add_signed(chrono::Duration::minutes(1))?;
+        while time.year() <= limit {
+            if !self.months.contains(time.month()) {
+                let (year, month) = match time.month() {
+                    12 => (time.year() + 1, 1),
+                    month => (time.year(), month + 1),
+                };
+                time = start_of_day(NaiveDate::from_ymd_opt(year, month, 1)?)?;
+            } else if !self.day_matches(time.date()) {
+                time = start_of_day(time.date().checked_add_days(Days::new(1))?)?;
+            } else if !self.hours.contains(time.hour()) {
+                time = time.with_minute(0)? + chrono::Duration::hours(1);
+            } else if !self.minutes.contains(time.minute()) {
+                time += chrono::Duration::minutes(1);
+            } else {
+                return Some(Utc.from_utc_datetime(&time));
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let day_of_month = self.days_of_month.contains(date.day());
+        let day_of_week = self
+            .days_of_week
+            .contains(date.weekday().num_days_from_sunday());
+        if self.days_of_month.restricted && self.days_of_week.restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = TermError;
+
+    fn from_str(expression: &str) -> Result<Self> {
+        let expanded = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let invalid = |reason: String| {
+            TermError::Configuration(format!("Invalid cron expression '{expression}': {reason}"))
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields[..] else {
+            return Err(invalid(format!(
+                "expected 5 fields, found {}",
+                fields.len()
+            )));
+        };
+        let mut days_of_week = CronField::parse(days_of_week, 0, 7).map_err(&invalid)?;
+        // 7 is Sunday as well
+        if days_of_week.contains(7) {
+            days_of_week.bits |= 1;
+        }
+        Ok(Self {
+            expression: expression.trim().to_string(),
+            minutes: CronField::parse(minutes, 0, 59).map_err(&invalid)?,
+            hours: CronField::parse(hours, 0, 23).map_err(&invalid)?,
+            days_of_month: CronField::parse(days_of_month, 1, 31).map_err(&invalid)?,
+            months: CronField::parse(months, 1, 12).map_err(&invalid)?,
+            days_of_week,
+        })
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+fn start_of_day(date: NaiveDate) -> Option<NaiveDateTime> {
+    date.and_hms_opt(0, 0, 0)
+}
+
+/// The values one field of a cron expression matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CronField {
+    bits: u64,
+    /// Whether the field was anything but `*`
+    restricted: bool,
+}
+
+impl CronField {
+    fn parse(spec: &str, min: u32, max: u32) -> std::result::Result<Self, String> {
+        let mut bits = 0u64;
+        for part in spec.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => {
+                    let step: u32 = step
+                        .parse()
+                        .ok()
+                        .filter(|step| *step > 0)
+                        .ok_or_else(|| format!("invalid step in '{part}'"))?;
+                    (range, step)
+                }
+                None => (part, 1),
+            };
+            let value = |text: &str| {
+                text.parse::<u32>()
+                    .ok()
+                    .filter(|value| (min..=max).contains(value))
+                    .ok_or_else(|| format!("'{text}' is not a value from {min} to {max}"))
+            };
+            let (start, end) = match range {
+                "*" => (min, max),
+                range => match range.split_once('-') {
+                    Some((start, end)) => (value(start)?, value(end)?),
+                    // A single value with a step runs to the end of the range
+                    None if part.contains('/') => (value(range)?, max),
+                    None => {
+                        let value = value(range)?;
+                        (value, value)
+                    }
+                },
+            };
+            if start > end {
+                return Err(format!("range '{range}' is empty"));
+            }
+            for value in (start..=end).step_by(step as usize) {
+                bits |= 1 << value;
+            }
+        }
+        Ok(Self {
+            bits,
+            restricted: spec != "*",
+        })
+    }
+
+    fn contains(&self, value: u32) -> bool {
+        value < 64 && self.bits & (1 << value) != 0
+    }
+}
+
+/// What the scheduler does with a tick arriving while a run is in flight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlapPolicy {
+    /// Drops the tick
+    #[default]
+    Skip,
+    /// Starts the run as soon as the run in flight finishes
+    ///
+    /// At most one run waits; ticks arriving while one is waiting are skipped.
+    Queue,
+}
+
+/// How [`SchedulerHandle::shutdown`] treats the run in flight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownMode {
+    /// Waits for the run in flight to complete and deliver its result
+    Finish,
+    /// Aborts the run in flight
+    Cancel,
+}
+
+/// Counts of what a scheduler did, returned when it shuts down.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SchedulerStats {
+    /// Runs that completed and delivered a result
+    pub completed: u64,
+    /// Runs that failed with an error
+    pub failed: u64,
+    /// Ticks dropped because a run was in flight, including a queued run
+    /// dropped at shutdown
+    pub skipped: u64,
+    /// Runs aborted by [`ShutdownMode::Cancel`]
+    pub cancelled: u64,
+}
+
+type ContextFactory = Arc<dyn Fn() -> BoxFuture<'static, Result<SessionContext>> + Send + Sync>;
+type ResultCallback = Arc<dyn Fn(ResultKey, ValidationResult) + Send + Sync>;
+
+/// Runs a validation suite periodically.
+///
+/// See the [module documentation](self) for how runs are scheduled.
+pub struct ValidationScheduler {
+    suite: Arc<ValidationSuite>,
+    ctx_factory: ContextFactory,
+    schedule: Schedule,
+    jitter: Duration,
+    overlap: OverlapPolicy,
+}
+
+impl fmt::Debug for ValidationScheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidationScheduler")
+            .field("suite", &self.suite.name())
+            .field("schedule", &self.schedule)
+            .field("jitter", &self.jitter)
+            .field("overlap", &self.overlap)
+            .finish()
+    }
+}
+
+impl ValidationScheduler {
+    /// Creates a scheduler running `suite` on `schedule`, against a context
+    /// built by `ctx_factory` for every run.
+    ///
+    /// `schedule` is a [`Duration`] for a fixed interval or a [`Schedule`].
+    pub fn new<F, Fut>(
+        suite: ValidationSuite,
+        ctx_factory: F,
+        schedule: impl Into<Schedule>,
+    ) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<SessionContext>> + Send + 'static,
+    {
+        Self {
+            suite: Arc::new(suite),
+            ctx_factory: Arc::new(move || ctx_factory().boxed()),
+            schedule: schedule.into(),
+            jitter: Duration::ZERO,
+            overlap: OverlapPolicy::default(),
+        }
+    }
+
+    /// Delays each run by up to `jitter` after its tick, to spread the load of
+    /// many schedulers ticking at the same time. Defaults to no jitter.
+    ///
+    /// The delay is derived from the suite name and the tick, so it is stable
+    /// across restarts.
+    pub fn jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets what happens to ticks arriving while a run is in flight. Defaults
+    /// to [`OverlapPolicy::Skip`].
+    pub fn overlap(mut self, overlap: OverlapPolicy) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Starts the scheduler, delivering results through the returned channel.
+    ///
+    /// The channel is unbounded so a slow consumer never delays the schedule.
+    /// It closes once the scheduler has shut down and delivered its last result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schedule is a zero interval.
+    pub fn spawn(
+        self,
+    ) -> Result<(
+        SchedulerHandle,
+        mpsc::UnboundedReceiver<(ResultKey, ValidationResult)>,
+    )> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let handle = self.spawn_with(move |key, result| {
+            if sender.send((key, result)).is_err() {
+                debug!("Scheduled result dropped, the receiver is closed");
+            }
+        })?;
+        Ok((handle, receiver))
+    }
+
+    /// Starts the scheduler, passing every result to `callback`.
+    ///
+    /// The callback runs on the task of the run that produced the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schedule is a zero interval.
+    pub fn spawn_with(
+        self,
+        callback: impl Fn(ResultKey, ValidationResult) + Send + Sync + 'static,
+    ) -> Result<SchedulerHandle> {
+        if self.schedule == Schedule::Every(Duration::ZERO) {
+            return Err(TermError::Configuration(
+                "The interval of a validation schedule must not be zero".to_string(),
+            ));
+        }
+        let (shutdown, shutdown_requested) = watch::channel(None);
+        let task = tokio::spawn(self.drive(Arc::new(callback), shutdown_requested));
+        Ok(SchedulerHandle { shutdown, task })
+    }
+
+    async fn drive(
+        self,
+        callback: ResultCallback,
+        mut shutdown_requested: watch::Receiver<Option<ShutdownMode>>,
+    ) -> SchedulerStats {
+        let clock = Clock::start();
+        let mut stats = SchedulerStats::default();
+        let mut next = self.schedule.first(clock.now());
+        let mut in_flight: Option<JoinHandle<bool>> = None;
+        let mut queued: Option<DateTime<Utc>> = None;
+        let mut sequence = 0u64;
+        info!(
+            suite.name = %self.suite.name(),
+            schedule = ?self.schedule,
+            "Validation scheduler started"
+        );
+
+        loop {
+            let tick = next.map(|at| clock.instant_at(at) + self.jitter_for(at));
+            tokio::select! {
+                biased;
+                finished = run_finished(&mut in_flight) => {
+                    in_flight = None;
+                    stats.record(finished);
+                    if let Some(at) = queued.take() {
+                        sequence += 1;
+                        in_flight = Some(self.start_run(at, sequence, &callback));
+                    }
+                }
+                mode = shutdown_mode(&mut shutdown_requested) => {
+                    if queued.take().is_some() {
+                        stats.skipped += 1;
+                    }
+                    if let Some(run) = in_flight.take() {
+                        if mode == ShutdownMode::Cancel {
+                            run.abort();
+                        }
+                        stats.record(run.await);
+                    }
+                    info!(
+                        suite.name = %self.suite.name(),
+                        ?mode,
+                        ?stats,
+                        "Validation scheduler stopped"
+                    );
+                    return stats;
+                }
+                _ = tokio::time::sleep_until(tick.unwrap_or_else(Instant::now)), if tick.is_some() => {
+                    let Some(at) = next else { continue };
+                    next = self.schedule.next(at);
+                    if in_flight.is_none() {
+                        sequence += 1;
+                        in_flight = Some(self.start_run(at, sequence, &callback));
+                    } else if self.overlap == OverlapPolicy::Queue && queued.is_none() {
+                        debug!(scheduled_at = %at, "Previous run in flight, queueing run");
+                        queued = Some(at);
+                    } else {
+                        warn!(
+                            suite.name = %self.suite.name(),
+                            scheduled_at = %at,
+                            "Previous run in flight, skipping run"
+                        );
+                        stats.skipped += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Spawns the run scheduled for `at`, returning whether it delivered a result.
+    fn start_run(
+        &self,
+        at: DateTime<Utc>,
+        sequence: u64,
+        callback: &ResultCallback,
+    ) -> JoinHandle<bool> {
+        let run_tags = [
+            (SCHEDULE_RUN_TAG.to_string(), sequence.to_string()),
+            (SCHEDULE_TIME_TAG.to_string(), at.to_rfc3339()),
+        ];
+        let suite = self.suite.with_run_tags(run_tags);
+        let ctx_factory = Arc::clone(&self.ctx_factory);
+        let callback = Arc::clone(callback);
+        tokio::spawn(async move {
+            debug!(suite.name = %suite.name(), run = sequence, "Starting scheduled run");
+            let result = match ctx_factory().await {
+                Ok(ctx) => suite.run(&ctx).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(result) => {
+                    let key = ResultKey::new(at.timestamp_millis()).with_tags(suite.tags().clone());
+                    callback(key, result);
+                    true
+                }
+                Err(e) => {
+                    error!(
+                        suite.name = %suite.name(),
+                        run = sequence,
+                        error = %e,
+                        "Scheduled validation run failed"
+                    );
+                    false
+                }
+            }
+        })
+    }
+
+    fn jitter_for(&self, at: DateTime<Utc>) -> Duration {
+        let max = self.jitter.as_millis() as u64;
+        if max == 0 {
+            return Duration::ZERO;
+        }
+        let mut hasher = DefaultHasher::new();
+        (self.suite.name(), at.timestamp_millis()).hash(&mut hasher);
+        Duration::from_millis(hasher.finish() % (max + 1))
+    }
+}
+
+/// Stops a running [`ValidationScheduler`].
+///
+/// Dropping the handle stops the scheduler as [`ShutdownMode::Cancel`] does.
+#[derive(Debug)]
+pub struct SchedulerHandle {
+    shutdown: watch::Sender<Option<ShutdownMode>>,
+    task: JoinHandle<SchedulerStats>,
+}
+
+impl SchedulerHandle {
+    /// Stops the scheduler and waits for it to wind down.
+    ///
+    /// No run starts after this is called. A queued run is dropped, and the
+    /// run in flight is completed or aborted according to `mode`.
+    pub async fn shutdown(self, mode: ShutdownMode) -> Result<SchedulerStats> {
+        // Fails only if the scheduler has already stopped
+        let _ = self.shutdown.send(Some(mode));
+        self.task
+            .await
+            .map_err(|e| TermError::Internal(format!("Validation scheduler failed: {e}")))
+    }
+}
+
+/// Maps the wall-clock times of the schedule to instants of the tokio clock,
+/// so schedules follow tokio's time, including when it is paused in tests.
+struct Clock {
+    wall: DateTime<Utc>,
+    instant: Instant,
+}
+
+impl Clock {
+    fn start() -> Self {
+        Self {
+            wall: Utc::now(),
+            instant: Instant::now(),
+        }
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        self.wall
+            + chrono::Duration::from_std(self.instant.elapsed())
+                .unwrap_or_else(|_| chrono::Duration::zero())
+    }
+
+    fn instant_at(&self, at: DateTime<Utc>) -> Instant {
+        self.instant + (at - self.wall).to_std().unwrap_or_default()
+    }
+}
+
+impl SchedulerStats {
+    fn record(&mut self, finished: std::result::Result<bool, JoinError>) {
+        match finished {
+            Ok(true) => self.completed += 1,
+            Ok(false) => self.failed += 1,
+            Err(e) if e.is_cancelled() => self.cancelled += 1,
+            Err(e) => {
+                error!(error = %e, "Scheduled validation run panicked");
+                self.failed += 1;
+            }
+        }
+    }
+}
+
+/// Waits for the run in flight, if any.
+async fn run_finished(
+    in_flight: &mut Option<JoinHandle<bool>>,
+) -> std::result::Result<bool, JoinError> {
+    match in_flight {
+        Some(run) => run.await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Waits for a shutdown request; a dropped handle requests cancellation.
+async fn shutdown_mode(requested: &mut watch::Receiver<Option<ShutdownMode>>) -> ShutdownMode {
+    loop {
+        if let Some(mode) = *requested.borrow_and_update() {
+            return mode;
+        }
+        if requested.changed().await.is_err() {
+            return ShutdownMode::Cancel;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::Assertion;
+    use crate::core::Check;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn cron(expression: &str) -> CronSchedule {
+        expression.parse().unwrap()
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 31, 23, 59, 30).unwrap();
+        assert_eq!(
+            cron("* * * * *").next_after(start),
+            Some(at(2024, 2, 1, 0, 0))
+        );
+        assert_eq!(
+            cron("*/15 * * * *").next_after(at(2024, 1, 1, 10, 15)),
+            Some(at(2024, 1, 1, 10, 30))
+        );
+        assert_eq!(
+            cron("0 6,18 * * *").next_after(at(2024, 1, 1, 6, 0)),
+            Some(at(2024, 1, 1, 18, 0))
+        );
+        // Leap day
+        assert_eq!(
+            cron("0 0 29 2 *").next_after(at(2024, 3, 1, 0, 0)),
+            Some(at(2028, 2, 29, 0, 0))
+        );
+        assert_eq!(cron("0 0 30 2 *").next_after(start), None);
+        assert_eq!(
+            cron("@monthly").next_after(start),
+            Some(at(2024, 2, 1, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_cron_days_of_week() {
+        // 2024-01-06 is a Saturday
+        let saturday = at(2024, 1, 6, 12, 0);
+        assert_eq!(
+            cron("0 9 * * 1-5").next_after(saturday),
+            Some(at(2024, 1, 8, 9, 0))
+        );
+        assert_eq!(cron("0 9 * * 7"), cron("0 9 * * 0"));
+        assert_eq!(
+            cron("0 9 * * 7").next_after(saturday),
+            Some(at(2024, 1, 7, 9, 0))
+        );
+        // Either the 15th or a Monday
+        assert_eq!(
+            cron("0 0 15 * 1").next_after(saturday),
+            Some(at(2024, 1, 8, 0, 0))
+        );
+        assert_eq!(
+            cron("0 0 15 * 1").next_after(at(2024, 1, 8, 0, 0)),
+            Some(at(2024, 1, 15, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_cron_rejects_invalid_expressions() {
+        for expression in [
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+        ] {
+            let error = expression.parse::<CronSchedule>().unwrap_err();
+            assert!(
+                error.to_string().contains("Invalid cron expression"),
+                "{expression}: {error}"
+            );
+        }
+        assert!(Schedule::cron("0 0 * * *").is_ok());
+    }
+
+    fn suite() -> ValidationSuite {
+        ValidationSuite::builder("scheduled")
+            .table_name("events")
+            .check(
+                Check::builder("size")
+                    .has_size(Assertion::GreaterThan(0.0))
+                    .build(),
+            )
+            .build()
+    }
+
+    /// Builds a context holding `events`, taking `delay` to do so.
+    async fn events(delay: Duration) -> Result<SessionContext> {
+        tokio::time::sleep(delay).await;
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE events (id INT) AS VALUES (1), (2)")
+            .await?
+            .collect()
+            .await?;
+        Ok(ctx)
+    }
+
+    fn run_tag(key: &ResultKey) -> &str {
+        key.get_tag(SCHEDULE_RUN_TAG).unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fixed_interval_tags_runs() {
+        let (handle, mut results) =
+            ValidationScheduler::new(suite(), || events(Duration::ZERO), Duration::from_secs(60))
+                .spawn()
+                .unwrap();
+
+        let (first, result) = results.recv().await.unwrap();
+        assert!(result.is_success());
+        let (second, _) = results.recv().await.unwrap();
+        assert_eq!(run_tag(&first), "1");
+        assert_eq!(run_tag(&second), "2");
+        assert_eq!(second.timestamp - first.timestamp, 60_000);
+        let scheduled_at =
+            DateTime::parse_from_rfc3339(first.get_tag(SCHEDULE_TIME_TAG).unwrap()).unwrap();
+        assert_eq!(scheduled_at.timestamp_millis(), first.timestamp);
+
+        let stats = handle.shutdown(ShutdownMode::Cancel).await.unwrap();
+        assert_eq!(stats.completed, 2);
+        assert_eq!(stats.skipped, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_overlapping_ticks_are_skipped() {
+        // Runs take 25s and ticks come every 10s
+        let (handle, mut results) = ValidationScheduler::new(
+            suite(),
+            || events(Duration::from_secs(25)),
+            Duration::from_secs(10),
+        )
+        .spawn()
+        .unwrap();
+
+        let (first, _) = results.recv().await.unwrap();
+        assert_eq!(run_tag(&first), "1");
+
+        // The ticks at 10s and 20s were skipped; the one at 30s started run 2
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        let stats = handle.shutdown(ShutdownMode::Finish).await.unwrap();
+        assert_eq!(
+            stats,
+            SchedulerStats {
+                completed: 2,
+                failed: 0,
+                skipped: 2,
+                cancelled: 0,
+            }
+        );
+
+        let (second, _) = results.recv().await.unwrap();
+        assert_eq!(run_tag(&second), "2");
+        assert_eq!(second.timestamp - first.timestamp, 30_000);
+        assert!(results.recv().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_overlapping_ticks_are_queued() {
+        // Runs take 24s and ticks come every 10s
+        let (handle, mut results) = ValidationScheduler::new(
+            suite(),
+            || events(Duration::from_secs(24)),
+            Duration::from_secs(10),
+        )
+        .overlap(OverlapPolicy::Queue)
+        .spawn()
+        .unwrap();
+
+        let (first, _) = results.recv().await.unwrap();
+        let (second, _) = results.recv().await.unwrap();
+        // Run 2 waited for run 1 and kept the time of its tick
+        assert_eq!(run_tag(&second), "2");
+        assert_eq!(second.timestamp - first.timestamp, 10_000);
+
+        // Run 3, for the tick at 30s, is in flight and gets aborted
+        let stats = handle.shutdown(ShutdownMode::Cancel).await.unwrap();
+        assert_eq!(
+            stats,
+            SchedulerStats {
+                completed: 2,
+                failed: 0,
+                skipped: 2,
+                cancelled: 1,
+            }
+        );
+        assert!(results.recv().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_runs_are_counted() {
+        let (handle, mut results) = ValidationScheduler::new(
+            suite(),
+            || async { Err(TermError::Configuration("source unavailable".to_string())) },
+            Duration::from_secs(10),
+        )
+        .spawn()
+        .unwrap();
+
+        tokio::time::sleep(Duration::from_secs(25)).await;
+        let stats = handle.shutdown(ShutdownMode::Finish).await.unwrap();
+        assert_eq!(stats.failed, 3);
+        assert_eq!(stats.completed, 0);
+        assert!(results.recv().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cron_schedule_and_callback() {
+        let delivered = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&delivered);
+        let handle = ValidationScheduler::new(
+            suite(),
+            || events(Duration::ZERO),
+            Schedule::cron("* * * * *").unwrap(),
+        )
+        .jitter(Duration::from_secs(5))
+        .spawn_with(move |key, _| recorded.lock().unwrap().push(key))
+        .unwrap();
+
+        tokio::time::sleep(Duration::from_secs(150)).await;
+        handle.shutdown(ShutdownMode::Finish).await.unwrap();
+
+        let keys = delivered.lock().unwrap();
+        assert!(keys.len() >= 2, "{keys:?}");
+        for key in keys.iter() {
+            assert_eq!(key.timestamp % 60_000, 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zero_interval_is_rejected() {
+        let error = ValidationScheduler::new(suite(), || events(Duration::ZERO), Duration::ZERO)
+            .spawn()
+            .unwrap_err();
+        assert!(error.to_string().contains("must not be zero"));
+    }
+
+    #[test]
+    fn test_jitter_is_bounded_and_stable() {
+        let scheduler =
+            ValidationScheduler::new(suite(), || events(Duration::ZERO), Duration::from_secs(60))
+                .jitter(Duration::from_secs(30));
+        for minute in 0..60 {
+            let tick = at(2024, 1, 1, 0, minute);
+            let jitter = scheduler.jitter_for(tick);
+            assert!(jitter <= Duration::from_secs(30));
+            assert_eq!(jitter, scheduler.jitter_for(tick));
+        }
+    }
+}
@@ -0,0 +1,385 @@
+//! Quality scores summarizing validation reports.
+//!
+//! A pass/fail count says little about how far a dataset is from its targets: a
+//! completeness of 0.97 against a threshold of 0.99 and a completeness of 0.10
+//! both count as one failure. A [`Scorecard`] turns the constraint outcomes of a
+//! [`ValidationReport`] into a [`QualityScore`] from 0 to 100, weighting each
+//! constraint by the level of its check.
+//!
+//! # Methodology
+//!
+//! Version 1 of the methodology ([`METHODOLOGY_VERSION`]) scores each
+//! constraint from 0 to 1:
+//!
+//! - A passed constraint scores 1.
+//! - A failed constraint with a threshold, such as completeness, uniqueness,
+//!   containment or format constraints, scores its observed fraction divided by
+//!   the threshold, so a completeness of 0.97 against 0.99 scores 0.979.
+//! - Any other failed constraint scores 0. Constraints without a natural ratio,
+//!   such as custom SQL expressions, schema checks and assertions on statistics,
+//!   are scored pass/fail, as are constraints that failed to evaluate.
+//! - Skipped constraints are left out.
+//!
+//! Each constraint weighs as much as its check: 3 for [`Level::Error`], 1 for
+//! [`Level::Warning`] and 0 for [`Level::Info`] unless configured otherwise. The
+//! overall score is the weighted mean of the constraint scores, times 100. A
+//! report without weighted constraints scores 100.
+//!
+//! # Example
+//!
+//! ```rust
+//! use term_guard::core::{Level, ValidationReport};
+//! use term_guard::scorecard::Scorecard;
+//!
+//! let scorecard = Scorecard::new()
+//!     .level_weight(Level::Info, 0.5)
+//!     .check_weight("Critical identifiers", 10.0);
+//!
+//! let score = scorecard.score(&ValidationReport::new("orders"));
+//! assert_eq!(score.overall, 100.0);
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analyzers::{AnalyzerContext, MetricValue};
+use crate::core::{ConstraintOutcome, ConstraintStatus, Level, ValidationReport};
+use crate::prelude::*;
+use crate::repository::{MetricsRepository, ResultKey};
+
+/// Version of the scoring rules, recorded in every [`QualityScore`].
+///
+/// The version changes whenever the same report would score differently, so
+/// scores of different versions should not be compared.
+pub const METHODOLOGY_VERSION: u32 = 1;
+
+/// Weights used to compute a [`QualityScore`] from a report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scorecard {
+    error_weight: f64,
+    warning_weight: f64,
+    info_weight: f64,
+    check_weights: HashMap<String, f64>,
+}
+
+impl Default for Scorecard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scorecard {
+    /// Creates a scorecard weighting error checks 3, warnings 1 and info checks 0.
+    pub fn new() -> Self {
+        Self {
+            error_weight: 3.0,
+            warning_weight: 1.0,
+            info_weight: 0.0,
+            check_weights: HashMap::new(),
+        }
+    }
+
+    /// Sets the weight of the constraints of checks at `level`.
+    ///
+    /// Negative and NaN weights are treated as 0.
+    pub fn level_weight(mut self, level: Level, weight: f64) -> Self {
+        let weight = weight.max(0.0);
+        match level {
+            Level::Error => self.error_weight = weight,
+            Level::Warning => self.warning_weight = weight,
+            Level::Info => self.info_weight = weight,
+        }
+        self
+    }
+
+    /// Sets the weight of the constraints of the check named `check`,
+    /// overriding the weight of its level.
+    ///
+    /// Negative and NaN weights are treated as 0.
+    pub fn check_weight(mut self, check: impl Into<String>, weight: f64) -> Self {
+        self.check_weights.insert(check.into(), weight.max(0.0));
+        self
+    }
+
+    /// Returns the weight of each constraint of a check.
+    pub fn weight(&self, check: &str, level: Level) -> f64 {
+        if let Some(weight) = self.check_weights.get(check) {
+            return *weight;
+        }
+        match level {
+            Level::Error => self.error_weight,
+            Level::Warning => self.warning_weight,
+            Level::Info => self.info_weight,
+        }
+    }
+
+    /// Scores the constraint outcomes of a report.
+    ///
+    /// Reports written before version 4 of the report schema carry no outcomes
+    /// and score 100.
+    pub fn score(&self, report: &ValidationReport) -> QualityScore {
+        let mut per_check: Vec<CheckScore> = Vec::new();
+        let mut totals: Vec<(f64, usize)> = Vec::new();
+
+        for outcome in &report.outcomes {
+            let index = match per_check
+                .iter()
+                .position(|check| check.check_name == outcome.check_name)
+            {
+                Some(index) => index,
+                None => {
+                    per_check.push(CheckScore {
+                        check_name: outcome.check_name.clone(),
+                        level: outcome.level,
+                        weight: self.weight(&outcome.check_name, outcome.level),
+                        score: 100.0,
+                        constraints: 0,
+                    });
+                    totals.push((0.0, 0));
+                    per_check.len() - 1
+                }
+            };
+            if let Some(score) = constraint_score(outcome) {
+                totals[index].0 += score;
+                totals[index].1 += 1;
+            }
+        }
+
+        let (mut weighted, mut total_weight) = (0.0, 0.0);
+        for (check, (sum, count)) in per_check.iter_mut().zip(totals) {
+            check.constraints = count;
+            if count > 0 {
+                check.score = sum / count as f64 * 100.0;
+                weighted += check.weight * sum;
+                total_weight += check.weight * count as f64;
+            }
+        }
+
+        QualityScore {
+            overall: if total_weight > 0.0 {
+                weighted / total_weight * 100.0
+            } else {
+                100.0
+            },
+            per_check,
+            methodology_version: METHODOLOGY_VERSION,
+        }
+    }
+}
+
+/// Scores one constraint from 0 to 1, or `None` if it was skipped.
+fn constraint_score(outcome: &ConstraintOutcome) -> Option<f64> {
+    match outcome.status {
+        ConstraintStatus::Skipped => None,
+        ConstraintStatus::Success => Some(1.0),
+        ConstraintStatus::Failure => Some(match (outcome.metric, outcome.threshold) {
+            (Some(metric), Some(threshold))
+                if threshold > 0.0 && threshold <= 1.0 && (0.0..threshold).contains(&metric) =>
+            {
+                metric / threshold
+            }
+            _ => 0.0,
+        }),
+    }
+}
+
+/// The quality score of a validation report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityScore {
+    /// Weighted score of all constraints, from 0 to 100
+    pub overall: f64,
+    /// Scores of the checks, in report order
+    pub per_check: Vec<CheckScore>,
+    /// Version of the scoring rules, see [`METHODOLOGY_VERSION`]
+    pub methodology_version: u32,
+}
+
+/// The score of one check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckScore {
+    /// The name of the check
+    pub check_name: String,
+    /// The severity level of the check
+    pub level: Level,
+    /// The weight of each constraint of the check
+    pub weight: f64,
+    /// Mean score of the check's constraints, from 0 to 100
+    pub score: f64,
+    /// Number of scored constraints; skipped constraints are not counted
+    pub constraints: usize,
+}
+
+impl QualityScore {
+    /// Returns the score as metrics, keyed `quality_score.overall` and
+    /// `quality_score.check.<check name>`.
+    ///
+    /// The methodology version is stored as `quality_score.methodology_version`.
+    pub fn to_metrics(&self, dataset: impl Into<String>) -> AnalyzerContext {
+        let mut metrics = AnalyzerContext::with_dataset(dataset);
+        metrics.store_metric("quality_score.overall", MetricValue::Double(self.overall));
+        metrics.store_metric(
+            "quality_score.methodology_version",
+            MetricValue::Long(i64::from(self.methodology_version)),
+        );
+        for check in &self.per_check {
+            metrics.store_metric(
+                format!("quality_score.check.{}", check.check_name),
+                MetricValue::Double(check.score),
+            );
+        }
+        metrics
+    }
+
+    /// Saves the score to a metrics repository under `key`.
+    ///
+    /// See [`to_metrics`](Self::to_metrics) for the metric names.
+    pub async fn save(
+        &self,
+        repository: &dyn MetricsRepository,
+        key: ResultKey,
+        dataset: impl Into<String>,
+    ) -> Result<()> {
+        repository.save(key, self.to_metrics(dataset)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::InMemoryRepository;
+
+    fn outcome(
+        check: &str,
+        level: Level,
+        status: ConstraintStatus,
+        metric: Option<f64>,
+        threshold: Option<f64>,
+    ) -> ConstraintOutcome {
+        ConstraintOutcome {
+            check_name: check.to_string(),
+            constraint_name: "constraint".to_string(),
+            level,
+            status,
+            waived: false,
+            metric,
+            threshold,
+            dimension: None,
+            constraint_id: None,
+        }
+    }
+
+    fn report() -> ValidationReport {
+        let mut report = ValidationReport::new("orders");
+        report.outcomes = vec![
+            outcome(
+                "completeness",
+                Level::Error,
+                ConstraintStatus::Failure,
+                Some(0.97),
+                Some(0.99),
+            ),
+            outcome(
+                "completeness",
+                Level::Error,
+                ConstraintStatus::Success,
+                Some(1.0),
+                Some(0.99),
+            ),
+            outcome(
+                "sql",
+                Level::Warning,
+                ConstraintStatus::Failure,
+                Some(0.5),
+                None,
+            ),
+            outcome("sql", Level::Warning, ConstraintStatus::Skipped, None, None),
+            outcome(
+                "observations",
+                Level::Info,
+                ConstraintStatus::Failure,
+                None,
+                None,
+            ),
+        ];
+        report
+    }
+
+    #[test]
+    fn test_constraint_scores() {
+        let scored = |status, metric, threshold| {
+            constraint_score(&outcome("c", Level::Error, status, metric, threshold))
+        };
+        let partial = scored(ConstraintStatus::Failure, Some(0.97), Some(0.99)).unwrap();
+        assert!((partial - 0.9798).abs() < 1e-4);
+        assert_eq!(scored(ConstraintStatus::Success, None, None), Some(1.0));
+        assert_eq!(scored(ConstraintStatus::Skipped, None, None), None);
+        assert_eq!(
+            scored(ConstraintStatus::Failure, None, Some(0.9)),
+            Some(0.0)
+        );
+        // Metrics above the threshold belong to constraints with an upper bound
+        assert_eq!(
+            scored(ConstraintStatus::Failure, Some(0.5), Some(0.1)),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn test_weighted_score() {
+        let score = Scorecard::new().score(&report());
+        assert_eq!(score.methodology_version, METHODOLOGY_VERSION);
+
+        // (3 * 0.9798 + 3 * 1 + 1 * 0) / 7, the info check weighs nothing
+        let expected = (3.0 * 0.97 / 0.99 + 3.0) / 7.0 * 100.0;
+        assert!((score.overall - expected).abs() < 1e-9);
+
+        let checks: Vec<_> = score
+            .per_check
+            .iter()
+            .map(|check| (check.check_name.as_str(), check.constraints, check.weight))
+            .collect();
+        assert_eq!(
+            checks,
+            vec![
+                ("completeness", 2, 3.0),
+                ("sql", 1, 1.0),
+                ("observations", 1, 0.0)
+            ]
+        );
+        assert_eq!(score.per_check[1].score, 0.0);
+    }
+
+    #[test]
+    fn test_weight_overrides() {
+        let score = Scorecard::new()
+            .check_weight("completeness", 0.0)
+            .level_weight(Level::Info, -1.0)
+            .score(&report());
+        assert_eq!(score.overall, 0.0);
+        assert_eq!(score.per_check[2].weight, 0.0);
+
+        let empty = Scorecard::new().score(&ValidationReport::new("empty"));
+        assert_eq!(empty.overall, 100.0);
+        assert!(empty.per_check.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_save_to_repository() -> Result<()> {
+        let repository = InMemoryRepository::new();
+        let score = Scorecard::new().score(&report());
+        let key = ResultKey::new(1_700_000_000_000).with_tag("dataset", "orders");
+        score.save(&repository, key.clone(), "orders").await?;
+
+        let stored = repository.get(&key).await?.unwrap();
+        assert_eq!(
+            stored.get_metric("quality_score.check.sql"),
+            Some(&MetricValue::Double(0.0))
+        );
+        assert_eq!(
+            stored.get_metric("quality_score.methodology_version"),
+            Some(&MetricValue::Long(1))
+        );
+        Ok(())
+    }
+}
@@ -208,6 +208,110 @@ impl SqlSecurity {
         Ok(())
     }
 
+    /// Validates that SQL text is a single read-only query.
+    ///
+    /// Used for user-provided queries such as derived tables, where a whole
+    /// `SELECT` (including joins and `WITH` clauses) is expected. The text is
+    /// parsed and rejected unless it consists of exactly one query statement, so
+    /// DDL, DML and stacked statements cannot be smuggled in.
+    pub fn validate_query(sql: &str) -> Result<()> {
+        use datafusion::sql::sqlparser::{ast::Statement, dialect::GenericDialect, parser::Parser};
+
+        if sql.contains('\0') {
+            return Err(TermError::SecurityError(
+                "SQL query cannot contain null bytes".to_string(),
+            ));
+        }
+
+        let statements = Parser::parse_sql(&GenericDialect {}, sql)
+            .map_err(|e| TermError::SecurityError(format!("Failed to parse SQL query: {e}")))?;
+        match statements.as_slice() {
+            [Statement::Query(_)] => Ok(()),
+            [_] => Err(TermError::SecurityError(
+                "Only SELECT queries are allowed".to_string(),
+            )),
+            statements => Err(TermError::SecurityError(format!(
+                "Expected a single SQL query, found {} statements",
+                statements.len()
+            ))),
+        }
+    }
+
+    /// Validates the name a user-defined function is registered and called by.
+    ///
+    /// Names must be lowercase SQL identifiers, which the SQL planner resolves
+    /// without quoting, and must pass [`validate_sql_expression`](Self::validate_sql_expression),
+    /// so that expressions calling the function are accepted.
+    pub fn validate_function_name(name: &str) -> Result<()> {
+        static FUNCTION_NAME_REGEX: Lazy<Regex> = Lazy::new(|| {
+            // This regex is compile-time constant and known to be valid
+            #[allow(clippy::expect_used)]
+            Regex::new(r"^[a-z_][a-z0-9_]{0,62}$")
+                .expect("Hard-coded regex pattern should be valid")
+        });
+        if !FUNCTION_NAME_REGEX.is_match(name) {
+            return Err(TermError::SecurityError(format!(
+                "Invalid function name '{name}': expected at most 63 lowercase letters, digits and underscores, starting with a letter or underscore"
+            )));
+        }
+        Self::check_dangerous_sql_patterns(name).map_err(|e| {
+            TermError::SecurityError(format!(
+                "Function name '{name}' cannot be called from SQL expressions: {e}"
+            ))
+        })
+    }
+
+    /// Validates that an expression only calls functions the session knows.
+    ///
+    /// The expression is parsed and every function it calls is looked up with
+    /// `is_registered`, such as built-in functions and the UDFs registered on the
+    /// session context. Unquoted names are looked up in lowercase, as the SQL
+    /// planner resolves them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::SecurityError`] naming the first unknown function, or if
+    /// the expression cannot be parsed.
+    pub fn validate_function_calls(
+        expression: &str,
+        is_registered: impl Fn(&str) -> bool,
+    ) -> Result<()> {
+        use datafusion::sql::sqlparser::{
+            ast::{visit_expressions, Expr},
+            dialect::GenericDialect,
+            parser::Parser,
+        };
+        use std::ops::ControlFlow;
+
+        let expr = Parser::new(&GenericDialect {})
+            .try_with_sql(expression)
+            .and_then(|mut parser| parser.parse_expr())
+            .map_err(|e| {
+                TermError::SecurityError(format!("Failed to parse SQL expression: {e}"))
+            })?;
+        let unknown = visit_expressions(&expr, |expr| match expr {
+            Expr::Function(function) => {
+                let name = function.name.to_string();
+                let name = match name.strip_prefix('"').and_then(|n| n.strip_suffix('"')) {
+                    Some(quoted) => quoted.to_string(),
+                    None => name.to_lowercase(),
+                };
+                if is_registered(&name) {
+                    ControlFlow::Continue(())
+                } else {
+                    ControlFlow::Break(name)
+                }
+            }
+            _ => ControlFlow::Continue(()),
+        });
+        match unknown {
+            ControlFlow::Break(name) => Err(TermError::SecurityError(format!(
+                "SQL expression calls unknown function '{name}'"
+            ))),
+            ControlFlow::Continue(()) => Ok(()),
+        }
+    }
+
     /// Checks for dangerous patterns in identifiers.
     fn check_dangerous_patterns(identifier: &str) -> Result<()> {
         let identifier_lower = identifier.to_lowercase();
@@ -494,6 +598,54 @@ mod tests {
         assert!(SqlSecurity::validate_sql_expression("EXEC sp_droplogin").is_err());
     }
 
+    #[test]
+    fn test_query_validation() {
+        assert!(SqlSecurity::validate_query(
+            "SELECT o.*, c.segment FROM orders o JOIN customers c USING (customer_id)"
+        )
+        .is_ok());
+        assert!(SqlSecurity::validate_query("WITH t AS (SELECT 1 AS x) SELECT x FROM t").is_ok());
+
+        assert!(SqlSecurity::validate_query("DROP TABLE orders").is_err());
+        assert!(SqlSecurity::validate_query("SELECT 1; DELETE FROM orders").is_err());
+        assert!(SqlSecurity::validate_query("CREATE VIEW v AS SELECT 1").is_err());
+        assert!(SqlSecurity::validate_query("").is_err());
+    }
+
+    #[test]
+    fn test_function_name_validation() {
+        assert!(SqlSecurity::validate_function_name("vin_checksum").is_ok());
+        assert!(SqlSecurity::validate_function_name("_is_valid2").is_ok());
+
+        assert!(SqlSecurity::validate_function_name("VinChecksum").is_err());
+        assert!(SqlSecurity::validate_function_name("vin checksum").is_err());
+        assert!(SqlSecurity::validate_function_name("schema.vin").is_err());
+        assert!(SqlSecurity::validate_function_name(&"a".repeat(64)).is_err());
+        let err = SqlSecurity::validate_function_name("drop_rows").unwrap_err();
+        assert!(err.to_string().contains("cannot be called"), "{err}");
+    }
+
+    #[test]
+    fn test_function_call_validation() {
+        let known = |name: &str| matches!(name, "upper" | "length" | "vin_checksum");
+
+        assert!(SqlSecurity::validate_function_calls("price > 0", known).is_ok());
+        assert!(SqlSecurity::validate_function_calls(
+            "vin_checksum(UPPER(vin)) AND LENGTH(vin) = 17",
+            known
+        )
+        .is_ok());
+
+        let err = SqlSecurity::validate_function_calls("load_data(vin) > 0", known).unwrap_err();
+        assert!(
+            err.to_string().contains("unknown function 'load_data'"),
+            "{err}"
+        );
+        // Quoted names are case sensitive
+        assert!(SqlSecurity::validate_function_calls(r#""UPPER"(vin) = 'A'"#, known).is_err());
+        assert!(SqlSecurity::validate_function_calls("price >", known).is_err());
+    }
+
     #[test]
     fn test_input_validation() {
         // Valid inputs
@@ -5,7 +5,7 @@
 
 use crate::prelude::*;
 use crate::security::SecureString;
-use crate::sources::DataSource;
+use crate::sources::{DataSource, SourceProbe};
 use async_trait::async_trait;
 use datafusion::arrow::datatypes::Schema;
 use datafusion::prelude::SessionContext;
@@ -25,6 +25,70 @@ use object_store::azure::MicrosoftAzureBuilder;
 use object_store::{ObjectStore, RetryConfig};
 use url::Url;
 
+/// Probes the object at `key`, or the objects under the prefix `key`, and reads
+/// the schema of the first one the way registration reads it.
+///
+/// `store_url` is the URL the store is registered under and `object_url` the
+/// URL registration reads.
+#[cfg(any(feature = "s3", feature = "gcs", feature = "azure"))]
+async fn probe_object(
+    store: &Arc<dyn ObjectStore>,
+    source_type: &str,
+    store_url: &Url,
+    object_url: &str,
+    key: &str,
+) -> Result<SourceProbe> {
+    use super::probe::unreadable;
+    use datafusion::prelude::{CsvReadOptions, NdJsonReadOptions};
+    use futures::StreamExt;
+    use object_store::path::Path as ObjectPath;
+
+    let inaccessible = |e: object_store::Error| {
+        TermError::data_source_with_source(
+            source_type,
+            format!("Cannot access {object_url}: {e}"),
+            Box::new(e),
+        )
+    };
+
+    let location = ObjectPath::from(key);
+    let probe = match store.head(&location).await {
+        Ok(meta) => SourceProbe::files(1, meta.size),
+        Err(object_store::Error::NotFound { .. }) => {
+            let mut objects = store.list(Some(&location));
+            let (mut count, mut bytes) = (0, 0);
+            while let Some(meta) = objects.next().await {
+                bytes += meta.map_err(inaccessible)?.size;
+                count += 1;
+            }
+            if count == 0 {
+                return Ok(SourceProbe::missing());
+            }
+            SourceProbe::files(count, bytes)
+        }
+        Err(e) => return Err(inaccessible(e)),
+    };
+
+    let ctx = SessionContext::new();
+    ctx.runtime_env()
+        .object_store_registry
+        .register_store(store_url, Arc::clone(store));
+    let path = key.to_lowercase();
+    let df = if path.ends_with(".parquet") {
+        ctx.read_parquet(object_url, Default::default()).await
+    } else if path.ends_with(".csv") || path.ends_with(".csv.gz") {
+        ctx.read_csv(object_url, CsvReadOptions::default()).await
+    } else if path.ends_with(".json") || path.ends_with(".jsonl") {
+        ctx.read_json(object_url, NdJsonReadOptions::default())
+            .await
+    } else {
+        // Registration reports the unsupported format
+        return Ok(probe);
+    };
+    let df = df.map_err(|e| unreadable(source_type, object_url, e.into()))?;
+    Ok(probe.with_schema(df.schema().inner()))
+}
+
 /// S3 authentication configuration.
 #[derive(Debug, Clone)]
 pub enum S3Auth {
@@ -236,6 +300,18 @@ impl DataSource for S3Source {
     fn description(&self) -> String {
         format!("S3 source: s3://{}/{}", self.config.bucket, self.config.key)
     }
+
+    /// Heads the object, or lists the prefix, and reads the first object's
+    /// footer or header.
+    async fn probe(&self) -> Result<SourceProbe> {
+        let s3_url = format!("s3://{}/{}", self.config.bucket, self.config.key);
+        let url = Url::parse(&s3_url).map_err(|e| TermError::DataSource {
+            source_type: "S3".to_string(),
+            message: format!("Invalid S3 URL: {e}"),
+            source: Some(Box::new(e)),
+        })?;
+        probe_object(&self.object_store, "S3", &url, &s3_url, &self.config.key).await
+    }
 }
 
 /// Google Cloud Storage authentication configuration.
@@ -412,6 +488,25 @@ impl DataSource for GcsSource {
             self.config.bucket, self.config.object
         )
     }
+
+    /// Heads the object, or lists the prefix, and reads the first object's
+    /// footer or header.
+    async fn probe(&self) -> Result<SourceProbe> {
+        let gcs_url = format!("gs://{}/{}", self.config.bucket, self.config.object);
+        let url = Url::parse(&gcs_url).map_err(|e| TermError::DataSource {
+            source_type: "GCS".to_string(),
+            message: format!("Invalid GCS URL: {e}"),
+            source: Some(Box::new(e)),
+        })?;
+        probe_object(
+            &self.object_store,
+            "GCS",
+            &url,
+            &gcs_url,
+            &self.config.object,
+        )
+        .await
+    }
 }
 
 /// Azure Blob Storage authentication configuration.
@@ -627,6 +722,30 @@ impl DataSource for AzureBlobSource {
             self.config.account, self.config.container, self.config.blob
         )
     }
+
+    /// Heads the blob, or lists the prefix, and reads the first blob's footer
+    /// or header.
+    async fn probe(&self) -> Result<SourceProbe> {
+        let azure_url = format!(
+            "az://{}/{}/{}",
+            self.config.account, self.config.container, self.config.blob
+        );
+        let container = &self.config.container;
+        let base_url = format!("az://{container}");
+        let url = Url::parse(&base_url).map_err(|e| TermError::DataSource {
+            source_type: "Azure".to_string(),
+            message: format!("Invalid Azure URL: {e}"),
+            source: Some(Box::new(e)),
+        })?;
+        probe_object(
+            &self.object_store,
+            "Azure",
+            &url,
+            &azure_url,
+            &self.config.blob,
+        )
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,13 @@
 //! CSV file source implementation.
 
-use super::{CompressionType, DataSource};
+use super::file_glob::{describe_files, expand_globs};
+use super::inference::{resolve_types, FileSchemas};
+use super::probe::{probe_files, unreadable};
+use super::schema_merge::{is_uniform, merge_schemas, union_files};
+use super::{
+    CompressionType, DataSource, FileManifest, InferenceReport, SchemaMergePolicy, SchemaReport,
+    SourceProbe,
+};
 use crate::prelude::*;
 use async_trait::async_trait;
 use datafusion::arrow::datatypes::Schema;
@@ -9,7 +16,8 @@ use datafusion::datasource::listing::{
     ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
 };
 use datafusion::prelude::*;
-use std::sync::Arc;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use std::sync::{Arc, RwLock};
 use tracing::{debug, info, instrument};
 
 /// Options for configuring CSV file reading.
@@ -29,8 +37,26 @@ pub struct CsvOptions {
     pub schema: Option<Arc<Schema>>,
     /// Compression type (default: Auto)
     pub compression: CompressionType,
-    /// Maximum records to read for schema inference
+    /// Maximum records to read for schema inference, from each file
     pub schema_infer_max_records: usize,
+    /// Whether files whose inferred types differ fail registration (default: false)
+    ///
+    /// By default each conflicting column is read as a type every file fits, and the
+    /// conflict is recorded in the [`InferenceReport`]. Only applies to multi-file
+    /// sources without an explicit `schema`.
+    pub strict_inference: bool,
+    /// Maximum number of files whose types are inferred at once (default: 8)
+    pub inference_concurrency: usize,
+    /// How files with different schemas are reconciled (default: Strict)
+    ///
+    /// Only applies to multi-file sources without an explicit `schema`.
+    pub schema_merge: SchemaMergePolicy,
+    /// Patterns of files left out when expanding glob patterns, such as `*_tmp.csv`
+    pub exclude: Vec<String>,
+    /// Maximum number of files glob patterns may match (default: [`DEFAULT_MAX_FILES`])
+    ///
+    /// [`DEFAULT_MAX_FILES`]: super::DEFAULT_MAX_FILES
+    pub max_files: Option<usize>,
 }
 
 impl Default for CsvOptions {
@@ -44,6 +70,11 @@ impl Default for CsvOptions {
             schema: None,
             compression: CompressionType::Auto,
             schema_infer_max_records: 1000,
+            strict_inference: false,
+            inference_concurrency: 8,
+            schema_merge: SchemaMergePolicy::default(),
+            exclude: Vec::new(),
+            max_files: None,
         }
     }
 }
@@ -53,7 +84,7 @@ impl Default for CsvOptions {
 /// # Examples
 ///
 /// ```rust,ignore
-/// use term_guard::sources::{CsvSource, CsvOptions};
+/// use term_guard::sources::{CsvSource, CsvOptions, SchemaMergePolicy};
 ///
 /// # async fn example() -> Result<()> {
 /// // Simple CSV file
@@ -69,6 +100,24 @@ impl Default for CsvOptions {
 ///
 /// // Multiple files with glob pattern
 /// let source = CsvSource::from_glob("data/*.csv")?;
+///
+/// // Partitioned files, without temporary files and success markers
+/// let options = CsvOptions {
+///     exclude: vec!["*_tmp.csv".to_string(), "**/_SUCCESS".to_string()],
+///     max_files: Some(10_000),
+///     ..Default::default()
+/// };
+/// let source = CsvSource::from_glob_with_options("data/**/date=*/*.csv", options).await?;
+///
+/// // Files where a column was added mid-month
+/// let source = CsvSource::from_glob("data/2024-03-*.csv")
+///     .await?
+///     .with_schema_merge_policy(SchemaMergePolicy::UnionNullable);
+///
+/// // Files whose columns must have the same inferred type in every file
+/// let source = CsvSource::from_glob("data/2024-03-*.csv")
+///     .await?
+///     .strict_inference(true);
 /// # Ok(())
 /// # }
 /// ```
@@ -77,6 +126,9 @@ pub struct CsvSource {
     paths: Vec<String>,
     options: CsvOptions,
     inferred_schema: Option<Arc<Schema>>,
+    schema_report: Arc<RwLock<Option<SchemaReport>>>,
+    inference_report: Arc<RwLock<Option<InferenceReport>>>,
+    manifest: Option<FileManifest>,
 }
 
 impl CsvSource {
@@ -86,6 +138,9 @@ impl CsvSource {
             paths: vec![path.into()],
             options: CsvOptions::default(),
             inferred_schema: None,
+            schema_report: Arc::default(),
+            inference_report: Arc::default(),
+            manifest: None,
         })
     }
 
@@ -95,6 +150,9 @@ impl CsvSource {
             paths: vec![path.into()],
             options,
             inferred_schema: None,
+            schema_report: Arc::default(),
+            inference_report: Arc::default(),
+            manifest: None,
         })
     }
 
@@ -109,20 +167,44 @@ impl CsvSource {
             paths,
             options: CsvOptions::default(),
             inferred_schema: None,
+            schema_report: Arc::default(),
+            inference_report: Arc::default(),
+            manifest: None,
         })
     }
 
     /// Creates a CSV source from a glob pattern.
     pub async fn from_glob(pattern: impl Into<String>) -> Result<Self> {
-        let patterns = vec![pattern.into()];
-        let paths = super::expand_globs(&patterns).await?;
-        Self::from_paths(paths)
+        Self::from_globs(vec![pattern.into()]).await
     }
 
     /// Creates a CSV source from multiple glob patterns.
+    ///
+    /// Patterns starting with `!` exclude the files they match.
     pub async fn from_globs(patterns: Vec<String>) -> Result<Self> {
-        let paths = super::expand_globs(&patterns).await?;
-        Self::from_paths(paths)
+        Self::from_globs_with_options(patterns, CsvOptions::default()).await
+    }
+
+    /// Creates a CSV source from a glob pattern with custom options.
+    pub async fn from_glob_with_options(
+        pattern: impl Into<String>,
+        options: CsvOptions,
+    ) -> Result<Self> {
+        Self::from_globs_with_options(vec![pattern.into()], options).await
+    }
+
+    /// Creates a CSV source from multiple glob patterns with custom options.
+    ///
+    /// The `exclude` and `max_files` options apply to the expansion of the patterns.
+    pub async fn from_globs_with_options(
+        patterns: Vec<String>,
+        options: CsvOptions,
+    ) -> Result<Self> {
+        let (paths, manifest) =
+            expand_globs(&patterns, &options.exclude, options.max_files).await?;
+        let mut source = Self::from_paths(paths)?.with_custom_options(options);
+        source.manifest = Some(manifest);
+        Ok(source)
     }
 
     /// Sets custom options for this CSV source.
@@ -131,6 +213,122 @@ impl CsvSource {
         self
     }
 
+    /// Sets how files with different schemas are reconciled.
+    pub fn with_schema_merge_policy(mut self, policy: SchemaMergePolicy) -> Self {
+        self.options.schema_merge = policy;
+        self
+    }
+
+    /// Sets whether files whose inferred types differ fail registration.
+    pub fn strict_inference(mut self, strict: bool) -> Self {
+        self.options.strict_inference = strict;
+        self
+    }
+
+    /// Sets the maximum number of files whose types are inferred at once.
+    pub fn inference_concurrency(mut self, concurrency: usize) -> Self {
+        self.options.inference_concurrency = concurrency;
+        self
+    }
+
+    /// Returns how the inferred types of the files were reconciled, once the source
+    /// has been registered or [`infer_types`](Self::infer_types) has run.
+    pub fn inference_report(&self) -> Option<InferenceReport> {
+        self.inference_report.read().ok()?.clone()
+    }
+
+    /// Infers the types of every file and compares them, without registering a table.
+    ///
+    /// With [`strict_inference`](Self::strict_inference), conflicting types are an
+    /// error naming the files.
+    pub async fn infer_types(&self) -> Result<InferenceReport> {
+        let frames = self.read_files(&SessionContext::new()).await?;
+        let files = frame_schemas(&frames);
+        let (report, _) = self.record_inference(&files)?;
+        Ok(report)
+    }
+
+    /// Reads each file on its own, with the schema inferred from that file.
+    ///
+    /// Files are read concurrently, at most `inference_concurrency` at a time.
+    async fn read_files(&self, ctx: &SessionContext) -> Result<Vec<(String, DataFrame)>> {
+        stream::iter(self.paths.iter().cloned())
+            .map(|path| async move {
+                let frame = self.read_file(ctx, &path).await?;
+                Ok::<_, TermError>((path, frame))
+            })
+            .buffered(self.options.inference_concurrency.max(1))
+            .try_collect()
+            .await
+    }
+
+    /// Resolves the types that differ between `files` and records the report.
+    fn record_inference(
+        &self,
+        files: &[(String, Arc<Schema>)],
+    ) -> Result<(InferenceReport, FileSchemas)> {
+        let (report, files) = resolve_types(
+            "csv",
+            files,
+            self.options.schema_infer_max_records,
+            self.options.strict_inference,
+        )?;
+        if let Ok(mut stored) = self.inference_report.write() {
+            *stored = Some(report.clone());
+        }
+        Ok((report, files))
+    }
+
+    /// Reads one file with the schema inferred from it.
+    async fn read_file(&self, ctx: &SessionContext, path: &str) -> Result<DataFrame> {
+        let extension = std::path::Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| format!(".{ext}"))
+            .unwrap_or_default();
+
+        let mut csv_options = CsvReadOptions::new()
+            .has_header(self.options.has_header)
+            .delimiter(self.options.delimiter)
+            .quote(self.options.quote)
+            .schema_infer_max_records(self.options.schema_infer_max_records)
+            .file_extension(&extension);
+
+        if let Some(escape) = self.options.escape {
+            csv_options = csv_options.escape(escape);
+        }
+        if let Some(comment) = self.options.comment {
+            csv_options = csv_options.comment(comment);
+        }
+
+        Ok(ctx.read_csv(path, csv_options).await?)
+    }
+
+    /// Resolves the types that differ between files, merges the schemas of all files
+    /// and records both reports.
+    ///
+    /// Returns the files aligned to the merged schema, or `None` if every file already
+    /// has the same schema or an explicit schema is set.
+    async fn merged_frames(&self, ctx: &SessionContext) -> Result<Option<DataFrame>> {
+        if self.options.schema.is_some() {
+            return Ok(None);
+        }
+
+        let frames = self.read_files(ctx).await?;
+        let (_, files) = self.record_inference(&frame_schemas(&frames))?;
+        let report = merge_schemas(self.options.schema_merge, "csv", &files)?;
+        let schema = report.schema.clone();
+        if let Ok(mut stored) = self.schema_report.write() {
+            *stored = Some(report);
+        }
+
+        if is_uniform(&files) {
+            return Ok(None);
+        }
+        let frames = frames.into_iter().map(|(_, frame)| frame).collect();
+        union_files(frames, &schema).map(Some)
+    }
+
     /// Infers schema from the CSV files.
     #[instrument(skip(self))]
     #[allow(dead_code)]
@@ -221,6 +419,14 @@ impl CsvSource {
     }
 }
 
+/// Pairs each file with the schema inferred from it.
+fn frame_schemas(frames: &[(String, DataFrame)]) -> FileSchemas {
+    frames
+        .iter()
+        .map(|(path, frame)| (path.clone(), frame.schema().inner().clone()))
+        .collect()
+}
+
 #[async_trait]
 impl DataSource for CsvSource {
     #[instrument(skip(self, ctx, telemetry), fields(
@@ -324,17 +530,18 @@ impl DataSource for CsvSource {
                 let table = ListingTable::try_new(config)?;
                 ctx.register_table(table_name, Arc::new(table))?;
             }
+        } else if let Some(frames) = self.merged_frames(ctx).await? {
+            // Files with different schemas - CSV columns are read by position, so each
+            // file is read with its own schema and aligned to the merged one
+            ctx.register_table(table_name, frames.into_view())?;
         } else {
-            // Multiple files - use ListingTable
-            // For multiple files, we need to use the directory path, not a specific file
-            let first_path = std::path::Path::new(&self.paths[0]);
-            let dir_path = first_path
-                .parent()
-                .ok_or_else(|| TermError::Configuration("Invalid file path".to_string()))?;
-            let dir_path_str = dir_path.to_str().ok_or_else(|| {
-                TermError::Configuration("Path contains invalid UTF-8".to_string())
-            })?;
-            let table_path = ListingTableUrl::parse(dir_path_str)?;
+            // Multiple files - use a ListingTable over exactly these files, so files
+            // next to them that were excluded or not matched are not read
+            let table_paths = self
+                .paths
+                .iter()
+                .map(ListingTableUrl::parse)
+                .collect::<std::result::Result<Vec<_>, _>>()?;
 
             // Determine the file extension from the actual files
             let extension = if self.paths[0].ends_with(".tsv") {
@@ -351,13 +558,15 @@ impl DataSource for CsvSource {
             // Infer schema if not provided
             let schema = if let Some(schema) = &self.options.schema {
                 schema.clone()
+            } else if let Some(report) = self.schema_report() {
+                report.schema
             } else {
                 // Infer schema using a mutable clone
                 let mut source_clone = self.clone();
                 source_clone.infer_schema().await?
             };
 
-            let config = ListingTableConfig::new(table_path)
+            let config = ListingTableConfig::new_with_multi_paths(table_paths)
                 .with_listing_options(listing_options)
                 .with_schema(schema);
 
@@ -386,10 +595,47 @@ impl DataSource for CsvSource {
         if self.paths.len() == 1 {
             let path = &self.paths[0];
             format!("CSV file: {path}")
+        } else if let Some(report) = self.schema_report() {
+            let files = describe_files(self.paths.len(), self.manifest.as_ref());
+            match self.inference_report() {
+                Some(inference) if inference.has_conflicts() => {
+                    format!("CSV files: {files} ({report}; {inference})")
+                }
+                _ => format!("CSV files: {files} ({report})"),
+            }
+        } else if self.options.schema.is_none() {
+            let files = describe_files(self.paths.len(), self.manifest.as_ref());
+            let policy = self.options.schema_merge;
+            format!("CSV files: {files} (schema merge: {policy})")
         } else {
-            let count = self.paths.len();
-            format!("CSV files: {count} files")
+            let files = describe_files(self.paths.len(), self.manifest.as_ref());
+            format!("CSV files: {files}")
+        }
+    }
+
+    fn schema_report(&self) -> Option<SchemaReport> {
+        self.schema_report.read().ok()?.clone()
+    }
+
+    fn file_manifest(&self) -> Option<FileManifest> {
+        self.manifest.clone()
+    }
+
+    /// Reads the metadata of every file and the header of the first one.
+    async fn probe(&self) -> Result<SourceProbe> {
+        let probe = probe_files("CSV", &self.paths).await?;
+        if !probe.exists {
+            return Ok(probe);
+        }
+        if let Some(schema) = &self.options.schema {
+            return Ok(probe.with_schema(schema));
         }
+        let first_path = &self.paths[0];
+        let df = self
+            .read_file(&SessionContext::new(), first_path)
+            .await
+            .map_err(|e| unreadable("CSV", first_path, e))?;
+        Ok(probe.with_schema(df.schema().inner()))
     }
 }
 
@@ -469,4 +715,19 @@ mod tests {
         let batches = df.collect().await.unwrap();
         assert!(!batches.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_csv_probe() {
+        let file = create_test_csv().await;
+        let path = file.path().to_str().unwrap().to_string();
+
+        let probe = CsvSource::new(path.clone()).unwrap().probe().await.unwrap();
+        assert!(probe.exists);
+        assert_eq!(probe.file_count, Some(1));
+        assert_eq!(probe.bytes, Some(file.as_file().metadata().unwrap().len()));
+        assert!(probe.schema_hash.is_some());
+
+        let missing = CsvSource::new(format!("{path}.missing")).unwrap();
+        assert!(!missing.probe().await.unwrap().exists);
+    }
 }
@@ -0,0 +1,208 @@
+//! Delta Lake table source implementation.
+//!
+//! A Delta table is a directory of Parquet files plus a transaction log
+//! (`_delta_log`) recording which files make up each version of the table. A
+//! [`DeltaSource`] reads the log to resolve the requested snapshot and registers
+//! exactly the files and schema of that snapshot, so files written by later
+//! commits, or removed by earlier ones, are never read.
+//!
+//! Queries on the registered table are planned by the Delta provider, which
+//! prunes data files using the partition values recorded in the log. A suite
+//! filtering the table, for example through a
+//! [derived table](crate::core::ValidationSuiteBuilder::derived_table) with a
+//! `WHERE` clause on a partition column, only reads the matching partitions.
+
+use super::{DataSource, SnapshotSelector, TableSnapshot};
+use crate::prelude::*;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use datafusion::arrow::datatypes::Schema;
+use datafusion::prelude::*;
+use deltalake::{DeltaTable, DeltaTableError};
+use std::sync::{Arc, OnceLock, RwLock};
+use tracing::{debug, instrument};
+
+/// A Delta Lake table, read at a chosen snapshot.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use term_guard::sources::{DataSource, DeltaSource};
+///
+/// # async fn example(ctx: &SessionContext) -> Result<()> {
+/// // The version the report was built from
+/// let source = DeltaSource::new("s3://lake/orders").version(412);
+/// source.register(ctx, "orders").await?;
+///
+/// // The table as it was at the end of the last quarter
+/// let as_of = "2024-12-31T23:59:59Z".parse()?;
+/// let source = DeltaSource::new("/data/lake/orders").timestamp(as_of);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DeltaSource {
+    location: String,
+    selector: SnapshotSelector,
+    schema: OnceLock<Arc<Schema>>,
+    snapshot: RwLock<Option<TableSnapshot>>,
+}
+
+impl DeltaSource {
+    /// Creates a source reading the latest snapshot of the table at `location`.
+    ///
+    /// The location is a local path or a URL such as `s3://bucket/table`.
+    pub fn new(location: impl Into<String>) -> Self {
+        Self {
+            location: location.into(),
+            selector: SnapshotSelector::Latest,
+            schema: OnceLock::new(),
+            snapshot: RwLock::new(None),
+        }
+    }
+
+    /// Reads the snapshot with `version`.
+    pub fn version(mut self, version: u64) -> Self {
+        self.selector = SnapshotSelector::Version(version);
+        self
+    }
+
+    /// Reads the last snapshot committed at or before `timestamp`.
+    pub fn timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.selector = SnapshotSelector::AsOf(timestamp);
+        self
+    }
+
+    /// Returns the location of the table.
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Returns which snapshot the source reads.
+    pub fn selector(&self) -> SnapshotSelector {
+        self.selector
+    }
+
+    /// Returns the snapshot registered last, with its version and data files.
+    pub fn snapshot(&self) -> Option<TableSnapshot> {
+        self.snapshot.read().ok()?.clone()
+    }
+
+    /// Reads the transaction log up to the selected snapshot.
+    async fn open(&self) -> Result<DeltaTable> {
+        let url = deltalake::ensure_table_uri(&self.location)
+            .map_err(|e| self.error("resolve the location of", e))?;
+        let table = match self.selector {
+            SnapshotSelector::Latest => deltalake::open_table(url).await,
+            SnapshotSelector::Version(version) => {
+                let version = i64::try_from(version).map_err(|_| {
+                    TermError::Configuration(format!(
+                        "Delta table version {version} is out of range"
+                    ))
+                })?;
+                deltalake::open_table_with_version(url, version).await
+            }
+            SnapshotSelector::AsOf(timestamp) => {
+                deltalake::open_table_with_ds(url, timestamp.to_rfc3339()).await
+            }
+        };
+        table.map_err(|e| self.error(&format!("open {} of", self.selector), e))
+    }
+
+    fn error(&self, action: &str, error: DeltaTableError) -> TermError {
+        TermError::data_source_with_source(
+            "Delta",
+            format!(
+                "Failed to {action} Delta table '{}': {error}",
+                self.location
+            ),
+            Box::new(error),
+        )
+    }
+}
+
+#[async_trait]
+impl DataSource for DeltaSource {
+    #[instrument(skip(self, ctx, telemetry), fields(table_name = %table_name, source_type = "delta", location = %self.location, selector = %self.selector))]
+    async fn register_with_telemetry(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        telemetry: Option<&Arc<TermTelemetry>>,
+    ) -> Result<()> {
+        // Create telemetry span for data source loading
+        let mut _datasource_span = if let Some(tel) = telemetry {
+            tel.start_datasource_span("delta", table_name)
+        } else {
+            TermSpan::noop()
+        };
+
+        let table = self.open().await?;
+        let version = table
+            .version()
+            .and_then(|version| u64::try_from(version).ok())
+            .ok_or_else(|| {
+                TermError::data_source(
+                    "Delta",
+                    format!("Delta table '{}' has no committed version", self.location),
+                )
+            })?;
+        let files: Vec<String> = table
+            .get_file_uris()
+            .map_err(|e| self.error("list the files of", e))?
+            .collect();
+        debug!(
+            version,
+            files = files.len(),
+            "Resolved Delta table snapshot"
+        );
+
+        ctx.register_table(table_name, Arc::new(table))?;
+        let schema = ctx.table_provider(table_name).await?.schema();
+        let _ = self.schema.set(schema);
+        if let Ok(mut snapshot) = self.snapshot.write() {
+            *snapshot = Some(TableSnapshot {
+                selector: self.selector,
+                version,
+                files,
+            });
+        }
+        Ok(())
+    }
+
+    fn schema(&self) -> Option<&Arc<Schema>> {
+        self.schema.get()
+    }
+
+    fn description(&self) -> String {
+        match self.snapshot() {
+            Some(snapshot) => format!("Delta table: {} ({snapshot})", self.location),
+            None => format!("Delta table: {} ({})", self.location, self.selector),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selectors() {
+        let source = DeltaSource::new("lake/orders");
+        assert_eq!(source.selector(), SnapshotSelector::Latest);
+        assert_eq!(source.description(), "Delta table: lake/orders (latest)");
+
+        let source = source.version(412);
+        assert_eq!(source.selector(), SnapshotSelector::Version(412));
+        assert_eq!(
+            source.description(),
+            "Delta table: lake/orders (version 412)"
+        );
+
+        let as_of = "2025-01-02T00:00:00Z".parse().unwrap();
+        let source = source.timestamp(as_of);
+        assert_eq!(source.selector(), SnapshotSelector::AsOf(as_of));
+        assert!(source.snapshot().is_none());
+        assert!(source.schema().is_none());
+    }
+}
@@ -0,0 +1,764 @@
+//! Excel and OpenDocument workbook source implementation.
+//!
+//! Worksheets are read with [calamine](https://docs.rs/calamine), which supports
+//! `.xlsx`, `.xlsm`, `.xlsb`, `.xls` and `.ods` files. The selected cells are
+//! converted into a single Arrow record batch and registered as an in-memory table.
+//!
+//! # Type mapping
+//!
+//! Column types are inferred from the cells of the first
+//! [`schema_infer_max_records`](ExcelOptions::schema_infer_max_records) data rows,
+//! similar to the CSV path:
+//!
+//! | Cells                                         | Arrow type                    |
+//! |-----------------------------------------------|-------------------------------|
+//! | Whole numbers                                 | `Int64`                       |
+//! | Numbers, some with a fractional part          | `Float64`                     |
+//! | Booleans                                      | `Boolean`                     |
+//! | Dates without a time of day                   | `Date32`                      |
+//! | Dates, some with a time of day                | `Timestamp(Millisecond)`      |
+//! | Durations (time values such as `[h]:mm`)      | `Duration(Millisecond)`       |
+//! | Text, or a mix of the kinds above             | `Utf8`                        |
+//!
+//! Excel stores dates as serial numbers counted from either the 1900 or the 1904
+//! epoch, depending on the workbook; both are converted to the same calendar dates.
+//! Empty cells and error cells (such as `#DIV/0!`) are read as nulls, and columns
+//! without any value are read as `Utf8`.
+//!
+//! # Layout
+//!
+//! - Trailing empty rows and columns are trimmed.
+//! - A merged cell holds its value in the top-left cell; the other cells of the
+//!   merged area are read as nulls.
+//! - Rows above the header row are skipped. Without a header row, columns are
+//!   named `column_1`, `column_2`, ... as in headerless CSV files.
+
+use super::DataSource;
+use crate::prelude::*;
+use arrow::array::{
+    ArrayRef, BooleanArray, Date32Array, DurationMillisecondArray, Float64Array, Int64Array,
+    StringArray, TimestampMillisecondArray,
+};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use calamine::{open_workbook_auto, Data, ExcelDateTime, Range, Reader};
+use chrono::{NaiveDate, NaiveDateTime};
+use datafusion::datasource::MemTable;
+use datafusion::prelude::*;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+use tracing::{debug, instrument};
+
+const MILLIS_PER_DAY: f64 = 86_400_000.0;
+
+/// Selects the worksheet of a workbook to read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SheetSelector {
+    /// The sheet at a zero-based position in the workbook
+    Index(usize),
+    /// The sheet with the given name
+    Name(String),
+}
+
+impl Default for SheetSelector {
+    fn default() -> Self {
+        Self::Index(0)
+    }
+}
+
+impl From<usize> for SheetSelector {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
+impl From<&str> for SheetSelector {
+    fn from(name: &str) -> Self {
+        Self::Name(name.to_string())
+    }
+}
+
+impl From<String> for SheetSelector {
+    fn from(name: String) -> Self {
+        Self::Name(name)
+    }
+}
+
+impl fmt::Display for SheetSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Index(index) => write!(f, "#{index}"),
+            Self::Name(name) => write!(f, "'{name}'"),
+        }
+    }
+}
+
+/// Options for configuring workbook reading.
+#[derive(Debug, Clone)]
+pub struct ExcelOptions {
+    /// Worksheet to read (default: the first sheet)
+    pub sheet: SheetSelector,
+    /// Zero-based row of the selected cells holding the column names (default: `Some(0)`).
+    /// Rows above it are skipped; `None` reads every row as data.
+    pub header_row: Option<usize>,
+    /// Cells to read in A1 notation, such as `"B2:F500"`, or `"B2"` to read from B2
+    /// to the end of the sheet (default: all used cells)
+    pub cell_range: Option<String>,
+    /// Schema to use (if None, will be inferred)
+    pub schema: Option<Arc<Schema>>,
+    /// Maximum data rows to read for schema inference
+    pub schema_infer_max_records: usize,
+}
+
+impl Default for ExcelOptions {
+    fn default() -> Self {
+        Self {
+            sheet: SheetSelector::default(),
+            header_row: Some(0),
+            cell_range: None,
+            schema: None,
+            schema_infer_max_records: 1000,
+        }
+    }
+}
+
+/// A worksheet of an Excel or OpenDocument workbook.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use term_guard::sources::{ExcelOptions, ExcelSource};
+///
+/// # async fn example() -> Result<()> {
+/// // First sheet, column names in the first row
+/// let source = ExcelSource::new("reports/customers.xlsx")?;
+///
+/// // Named sheet with a title block above the header, limited to columns A to F
+/// let source = ExcelSource::new("reports/finance.xlsx")?
+///     .with_sheet("Q3")
+///     .with_header_row(Some(2))
+///     .with_cell_range("A1:F5000");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExcelSource {
+    path: String,
+    options: ExcelOptions,
+    inferred_schema: Option<Arc<Schema>>,
+}
+
+impl ExcelSource {
+    /// Creates a new source reading the first sheet of a workbook.
+    pub fn new(path: impl Into<String>) -> Result<Self> {
+        Self::with_options(path, ExcelOptions::default())
+    }
+
+    /// Creates a new source with custom options.
+    pub fn with_options(path: impl Into<String>, options: ExcelOptions) -> Result<Self> {
+        if let Some(cell_range) = &options.cell_range {
+            parse_cell_range(cell_range)?;
+        }
+        Ok(Self {
+            path: path.into(),
+            options,
+            inferred_schema: None,
+        })
+    }
+
+    /// Selects the worksheet to read, by name or zero-based index.
+    pub fn with_sheet(mut self, sheet: impl Into<SheetSelector>) -> Self {
+        self.options.sheet = sheet.into();
+        self.inferred_schema = None;
+        self
+    }
+
+    /// Sets the zero-based row holding the column names, or `None` for no header.
+    pub fn with_header_row(mut self, header_row: Option<usize>) -> Self {
+        self.options.header_row = header_row;
+        self.inferred_schema = None;
+        self
+    }
+
+    /// Limits reading to the given cells in A1 notation.
+    ///
+    /// Invalid ranges are reported when the source is registered.
+    pub fn with_cell_range(mut self, cell_range: impl Into<String>) -> Self {
+        self.options.cell_range = Some(cell_range.into());
+        self.inferred_schema = None;
+        self
+    }
+
+    /// Infers the schema of the selected cells and keeps it for [`DataSource::schema`].
+    #[instrument(skip(self), fields(path = %self.path))]
+    pub async fn infer_schema(&mut self) -> Result<Arc<Schema>> {
+        if let Some(schema) = &self.options.schema {
+            return Ok(schema.clone());
+        }
+        if let Some(schema) = &self.inferred_schema {
+            return Ok(schema.clone());
+        }
+
+        let batch = self.read().await?;
+        let schema = batch.schema();
+        self.inferred_schema = Some(schema.clone());
+        Ok(schema)
+    }
+
+    /// Reads the selected cells on the blocking thread pool.
+    async fn read(&self) -> Result<RecordBatch> {
+        let path = self.path.clone();
+        let options = self.options.clone();
+        tokio::task::spawn_blocking(move || read_sheet(&path, &options))
+            .await
+            .map_err(|e| TermError::Internal(format!("Failed to read workbook: {e}")))?
+    }
+}
+
+#[async_trait]
+impl DataSource for ExcelSource {
+    #[instrument(skip(self, ctx, telemetry), fields(table_name = %table_name, source_type = "excel", path = %self.path))]
+    async fn register_with_telemetry(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        telemetry: Option<&Arc<TermTelemetry>>,
+    ) -> Result<()> {
+        let mut _datasource_span = if let Some(tel) = telemetry {
+            tel.start_datasource_span("excel", table_name)
+        } else {
+            TermSpan::noop()
+        };
+
+        let batch = self.read().await?;
+        debug!(
+            rows = batch.num_rows(),
+            columns = batch.num_columns(),
+            "Read worksheet"
+        );
+        let table = MemTable::try_new(batch.schema(), vec![vec![batch]])?;
+        ctx.register_table(table_name, Arc::new(table))?;
+        Ok(())
+    }
+
+    fn schema(&self) -> Option<&Arc<Schema>> {
+        self.options
+            .schema
+            .as_ref()
+            .or(self.inferred_schema.as_ref())
+    }
+
+    fn description(&self) -> String {
+        let mut description = format!("Excel file: {} (sheet {}", self.path, self.options.sheet);
+        if let Some(cell_range) = &self.options.cell_range {
+            description.push_str(&format!(", cells {cell_range}"));
+        }
+        description.push(')');
+        description
+    }
+}
+
+/// Zero-based, inclusive cell coordinates as (row, column).
+type CellBounds = ((u32, u32), Option<(u32, u32)>);
+
+/// Parses `"B2:F500"` or `"B2"` into zero-based coordinates.
+fn parse_cell_range(cell_range: &str) -> Result<CellBounds> {
+    let invalid = || {
+        TermError::Configuration(format!(
+            "Invalid cell range '{cell_range}': expected A1 notation such as 'B2:F500' or 'B2'"
+        ))
+    };
+
+    let mut parts = cell_range.split(':');
+    let start = parts.next().and_then(parse_cell).ok_or_else(invalid)?;
+    let end = match parts.next() {
+        Some(end) => Some(parse_cell(end).ok_or_else(invalid)?),
+        None => None,
+    };
+    if parts.next().is_some() || end.is_some_and(|end| end.0 < start.0 || end.1 < start.1) {
+        return Err(invalid());
+    }
+    Ok((start, end))
+}
+
+/// Parses a cell reference such as `"AB12"` into zero-based (row, column).
+fn parse_cell(cell: &str) -> Option<(u32, u32)> {
+    let cell = cell.trim().replace('$', "");
+    let split = cell.find(|c: char| c.is_ascii_digit())?;
+    let (letters, digits) = cell.split_at(split);
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let column = letters.chars().try_fold(0u32, |acc, c| {
+        acc.checked_mul(26)?
+            .checked_add(c.to_ascii_uppercase() as u32 - 'A' as u32 + 1)
+    })?;
+    let row: u32 = digits.parse().ok()?;
+    (row > 0).then(|| (row - 1, column - 1))
+}
+
+/// Formats zero-based (row, column) as an A1 reference.
+fn cell_name((row, column): (u32, u32)) -> String {
+    let mut letters = Vec::new();
+    let mut n = column + 1;
+    while n > 0 {
+        let rem = (n - 1) % 26;
+        letters.push((b'A' + rem as u8) as char);
+        n = (n - 1) / 26;
+    }
+    letters.reverse();
+    format!("{}{}", letters.into_iter().collect::<String>(), row + 1)
+}
+
+fn excel_error(path: &str, message: impl Into<String>) -> TermError {
+    TermError::data_source("Excel", format!("{}: {}", path, message.into()))
+}
+
+/// Reads the selected cells of a workbook into a record batch.
+fn read_sheet(path: &str, options: &ExcelOptions) -> Result<RecordBatch> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| {
+        TermError::data_source_with_source(
+            "Excel",
+            format!("Failed to open workbook '{path}'"),
+            Box::new(e),
+        )
+    })?;
+
+    let sheet_names = workbook.sheet_names();
+    let sheet = match &options.sheet {
+        SheetSelector::Name(name) => sheet_names.iter().find(|s| *s == name),
+        SheetSelector::Index(index) => sheet_names.get(*index),
+    }
+    .cloned()
+    .ok_or_else(|| {
+        excel_error(
+            path,
+            format!(
+                "sheet {} not found; available sheets: {}",
+                options.sheet,
+                sheet_names.join(", ")
+            ),
+        )
+    })?;
+
+    let range = workbook.worksheet_range(&sheet).map_err(|e| {
+        TermError::data_source_with_source(
+            "Excel",
+            format!("Failed to read sheet '{sheet}' of '{path}'"),
+            Box::new(e),
+        )
+    })?;
+
+    let grid = Grid::new(&range, options.cell_range.as_deref())?;
+    grid.to_record_batch(options)
+        .map_err(|message| excel_error(path, format!("sheet '{sheet}': {message}")))
+}
+
+/// The selected cells of a worksheet, with trailing empty rows and columns trimmed.
+struct Grid<'a> {
+    range: &'a Range<Data>,
+    /// Absolute position of the top-left selected cell
+    origin: (u32, u32),
+    height: usize,
+    width: usize,
+}
+
+impl<'a> Grid<'a> {
+    fn new(range: &'a Range<Data>, cell_range: Option<&str>) -> Result<Self> {
+        let (Some(used_start), Some(used_end)) = (range.start(), range.end()) else {
+            return Ok(Self {
+                range,
+                origin: (0, 0),
+                height: 0,
+                width: 0,
+            });
+        };
+
+        let (origin, end) = match cell_range {
+            Some(cell_range) => {
+                let (start, end) = parse_cell_range(cell_range)?;
+                let end = end.unwrap_or(used_end);
+                (start, (end.0.min(used_end.0), end.1.min(used_end.1)))
+            }
+            None => (used_start, used_end),
+        };
+
+        let mut grid = Self {
+            range,
+            origin,
+            height: (end.0 + 1).saturating_sub(origin.0) as usize,
+            width: (end.1 + 1).saturating_sub(origin.1) as usize,
+        };
+        while grid.height > 0 && (0..grid.width).all(|c| is_empty(grid.cell(grid.height - 1, c))) {
+            grid.height -= 1;
+        }
+        while grid.width > 0 && (0..grid.height).all(|r| is_empty(grid.cell(r, grid.width - 1))) {
+            grid.width -= 1;
+        }
+        Ok(grid)
+    }
+
+    fn position(&self, row: usize, column: usize) -> (u32, u32) {
+        (self.origin.0 + row as u32, self.origin.1 + column as u32)
+    }
+
+    fn cell(&self, row: usize, column: usize) -> &Data {
+        self.range
+            .get_value(self.position(row, column))
+            .unwrap_or(&Data::Empty)
+    }
+
+    fn to_record_batch(&self, options: &ExcelOptions) -> std::result::Result<RecordBatch, String> {
+        let first_data_row = match options.header_row {
+            Some(header_row) if header_row >= self.height => {
+                return Err(format!(
+                    "header row {header_row} is outside the {} non-empty rows read",
+                    self.height
+                ))
+            }
+            Some(header_row) => header_row + 1,
+            None => 0,
+        };
+        let rows = first_data_row..self.height;
+
+        let schema = match &options.schema {
+            Some(schema) if schema.fields().len() != self.width => {
+                return Err(format!(
+                    "the schema has {} fields but {} columns were read",
+                    schema.fields().len(),
+                    self.width
+                ))
+            }
+            Some(schema) => schema.clone(),
+            None => {
+                let names = self.column_names(options.header_row);
+                let infer_rows = rows.clone().take(options.schema_infer_max_records);
+                let fields: Vec<Field> = names
+                    .into_iter()
+                    .enumerate()
+                    .map(|(column, name)| {
+                        let kind = infer_rows
+                            .clone()
+                            .filter_map(|row| CellKind::of(self.cell(row, column)))
+                            .reduce(CellKind::merge);
+                        Field::new(name, kind.map_or(DataType::Utf8, CellKind::data_type), true)
+                    })
+                    .collect();
+                Arc::new(Schema::new(fields))
+            }
+        };
+
+        let columns = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(column, field)| self.column(column, rows.clone(), field))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        RecordBatch::try_new_with_options(
+            schema,
+            columns,
+            &arrow::record_batch::RecordBatchOptions::new().with_row_count(Some(rows.len())),
+        )
+        .map_err(|e| e.to_string())
+    }
+
+    /// Column names from the header row, made unique, or `column_N` without a header.
+    fn column_names(&self, header_row: Option<usize>) -> Vec<String> {
+        let mut seen = HashSet::new();
+        (0..self.width)
+            .map(|column| {
+                let name = header_row
+                    .map(|row| cell_text(self.cell(row, column)))
+                    .map(|name| name.trim().to_string())
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| format!("column_{}", column + 1));
+
+                let mut unique = name.clone();
+                let mut suffix = 2;
+                while !seen.insert(unique.clone()) {
+                    unique = format!("{name}_{suffix}");
+                    suffix += 1;
+                }
+                unique
+            })
+            .collect()
+    }
+
+    /// Converts the cells of one column into an array of the field's type.
+    fn column(
+        &self,
+        column: usize,
+        rows: std::ops::Range<usize>,
+        field: &Field,
+    ) -> std::result::Result<ArrayRef, String> {
+        let mismatch = |row: usize, cell: &Data| {
+            format!(
+                "cell {} holds {} but column '{}' is {}",
+                cell_name(self.position(row, column)),
+                describe(cell),
+                field.name(),
+                field.data_type()
+            )
+        };
+        macro_rules! convert {
+            ($array:ty, $convert:expr) => {{
+                let values = rows
+                    .map(|row| {
+                        let cell = self.cell(row, column);
+                        match cell {
+                            Data::Empty | Data::Error(_) => Ok(None),
+                            _ => $convert(cell).map(Some).ok_or_else(|| mismatch(row, cell)),
+                        }
+                    })
+                    .collect::<std::result::Result<Vec<_>, String>>()?;
+                Arc::new(<$array>::from(values)) as ArrayRef
+            }};
+        }
+
+        Ok(match field.data_type() {
+            DataType::Int64 => convert!(Int64Array, cell_int),
+            DataType::Float64 => convert!(Float64Array, cell_float),
+            DataType::Boolean => convert!(BooleanArray, |cell: &Data| match cell {
+                Data::Bool(value) => Some(*value),
+                _ => None,
+            }),
+            DataType::Date32 => convert!(Date32Array, |cell: &Data| cell_datetime(cell)
+                .map(|datetime| (datetime.date() - unix_epoch()).num_days() as i32)),
+            DataType::Timestamp(TimeUnit::Millisecond, None) => {
+                convert!(TimestampMillisecondArray, |cell: &Data| cell_datetime(cell)
+                    .map(|datetime| datetime.and_utc().timestamp_millis()))
+            }
+            DataType::Duration(TimeUnit::Millisecond) => {
+                convert!(DurationMillisecondArray, |cell: &Data| match cell {
+                    Data::DateTime(value) if value.is_duration() => {
+                        Some((value.as_f64() * MILLIS_PER_DAY).round() as i64)
+                    }
+                    _ => None,
+                })
+            }
+            DataType::Utf8 => convert!(StringArray, |cell: &Data| Some(cell_text(cell))),
+            other => {
+                return Err(format!(
+                    "column '{}' has unsupported type {other}",
+                    field.name()
+                ))
+            }
+        })
+    }
+}
+
+/// The kind of value a cell holds, used for type inference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CellKind {
+    Boolean,
+    Integer,
+    Float,
+    Date,
+    Timestamp,
+    Duration,
+    Text,
+}
+
+impl CellKind {
+    fn of(cell: &Data) -> Option<Self> {
+        Some(match cell {
+            Data::Empty | Data::Error(_) => return None,
+            Data::Bool(_) => Self::Boolean,
+            Data::Int(_) => Self::Integer,
+            Data::Float(_) if cell_int(cell).is_some() => Self::Integer,
+            Data::Float(_) => Self::Float,
+            Data::DateTime(value) if value.is_duration() => Self::Duration,
+            Data::DateTime(_) | Data::DateTimeIso(_) => match cell_datetime(cell) {
+                Some(datetime) if datetime.time() == chrono::NaiveTime::MIN => Self::Date,
+                Some(_) => Self::Timestamp,
+                None => Self::Text,
+            },
+            Data::String(_) | Data::DurationIso(_) => Self::Text,
+        })
+    }
+
+    fn merge(self, other: Self) -> Self {
+        use CellKind::*;
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Integer, Float) | (Float, Integer) => Float,
+            (Date, Timestamp) | (Timestamp, Date) => Timestamp,
+            _ => Text,
+        }
+    }
+
+    fn data_type(self) -> DataType {
+        match self {
+            Self::Boolean => DataType::Boolean,
+            Self::Integer => DataType::Int64,
+            Self::Float => DataType::Float64,
+            Self::Date => DataType::Date32,
+            Self::Timestamp => DataType::Timestamp(TimeUnit::Millisecond, None),
+            Self::Duration => DataType::Duration(TimeUnit::Millisecond),
+            Self::Text => DataType::Utf8,
+        }
+    }
+}
+
+fn is_empty(cell: &Data) -> bool {
+    matches!(cell, Data::Empty)
+}
+
+fn unix_epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid date")
+}
+
+fn cell_int(cell: &Data) -> Option<i64> {
+    match cell {
+        Data::Int(value) => Some(*value),
+        Data::Float(value) if value.fract() == 0.0 && value.abs() < 9_007_199_254_740_992.0 => {
+            Some(*value as i64)
+        }
+        _ => None,
+    }
+}
+
+fn cell_float(cell: &Data) -> Option<f64> {
+    match cell {
+        Data::Int(value) => Some(*value as f64),
+        Data::Float(value) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Converts date cells, honoring the 1900 or 1904 epoch of the workbook.
+fn cell_datetime(cell: &Data) -> Option<NaiveDateTime> {
+    match cell {
+        Data::DateTime(value) if value.is_datetime() => excel_datetime(value),
+        Data::DateTimeIso(value) => NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .ok()
+                    .map(|date| date.and_time(chrono::NaiveTime::MIN))
+            }),
+        _ => None,
+    }
+}
+
+fn excel_datetime(value: &ExcelDateTime) -> Option<NaiveDateTime> {
+    let (year, month, day, hour, minute, second, milli) = value.to_ymd_hms_milli();
+    NaiveDate::from_ymd_opt(year.into(), month.into(), day.into())?.and_hms_milli_opt(
+        hour.into(),
+        minute.into(),
+        second.into(),
+        milli.into(),
+    )
+}
+
+/// Renders a cell as text, for `Utf8` columns and column names.
+fn cell_text(cell: &Data) -> String {
+    match cell {
+        Data::Empty => String::new(),
+        Data::String(value) | Data::DateTimeIso(value) | Data::DurationIso(value) => value.clone(),
+        Data::Bool(value) => value.to_string(),
+        Data::Int(value) => value.to_string(),
+        Data::Float(value) => match cell_int(cell) {
+            Some(value) => value.to_string(),
+            None => value.to_string(),
+        },
+        Data::DateTime(value) => match cell_datetime(cell) {
+            Some(datetime) if datetime.time() == chrono::NaiveTime::MIN => {
+                datetime.date().to_string()
+            }
+            Some(datetime) => datetime.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            None => value.to_string(),
+        },
+        Data::Error(error) => error.to_string(),
+    }
+}
+
+/// Describes a cell for type mismatch errors.
+fn describe(cell: &Data) -> String {
+    let kind = match cell {
+        Data::Bool(_) => "a boolean",
+        Data::Int(_) | Data::Float(_) => "a number",
+        Data::DateTime(value) if value.is_duration() => "a duration",
+        Data::DateTime(_) | Data::DateTimeIso(_) => "a date",
+        _ => "text",
+    };
+    format!("{kind} ({})", cell_text(cell))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use calamine::ExcelDateTimeType;
+
+    #[test]
+    fn test_parse_cell_range() {
+        assert_eq!(parse_cell("A1"), Some((0, 0)));
+        assert_eq!(parse_cell("$AB$12"), Some((11, 27)));
+        assert_eq!(parse_cell("A0"), None);
+        assert_eq!(parse_cell("12"), None);
+
+        assert_eq!(
+            parse_cell_range("B2:F500").unwrap(),
+            ((1, 1), Some((499, 5)))
+        );
+        assert_eq!(parse_cell_range("c3").unwrap(), ((2, 2), None));
+        assert!(parse_cell_range("F5:B2").is_err());
+        assert!(parse_cell_range("A1:B2:C3").is_err());
+
+        for cell in ["A1", "Z9", "AA10", "XFD1048576"] {
+            assert_eq!(cell_name(parse_cell(cell).unwrap()), cell);
+        }
+    }
+
+    #[test]
+    fn test_cell_kind_inference() {
+        let date = |value, is_1904| {
+            Data::DateTime(ExcelDateTime::new(
+                value,
+                ExcelDateTimeType::DateTime,
+                is_1904,
+            ))
+        };
+
+        assert_eq!(CellKind::of(&Data::Float(3.0)), Some(CellKind::Integer));
+        assert_eq!(CellKind::of(&Data::Float(3.5)), Some(CellKind::Float));
+        assert_eq!(CellKind::of(&date(45_000.0, false)), Some(CellKind::Date));
+        assert_eq!(
+            CellKind::of(&date(45_000.25, false)),
+            Some(CellKind::Timestamp)
+        );
+        assert_eq!(CellKind::of(&Data::Empty), None);
+        assert_eq!(CellKind::Integer.merge(CellKind::Float), CellKind::Float);
+        assert_eq!(CellKind::Date.merge(CellKind::Integer), CellKind::Text);
+
+        // The same calendar date in both epochs
+        let expected = NaiveDate::from_ymd_opt(2023, 3, 15).unwrap();
+        assert_eq!(
+            cell_datetime(&date(45_000.0, false)).unwrap().date(),
+            expected
+        );
+        assert_eq!(
+            cell_datetime(&date(43_538.0, true)).unwrap().date(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_cell_text() {
+        assert_eq!(cell_text(&Data::Float(42.0)), "42");
+        assert_eq!(cell_text(&Data::Float(1.5)), "1.5");
+        assert_eq!(cell_text(&Data::Bool(true)), "true");
+        assert_eq!(
+            cell_text(&Data::DateTime(ExcelDateTime::new(
+                45_000.5,
+                ExcelDateTimeType::DateTime,
+                false
+            ))),
+            "2023-03-15 12:00:00.000"
+        );
+    }
+}
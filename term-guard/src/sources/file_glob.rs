@@ -0,0 +1,419 @@
+//! Expansion of glob patterns into the files of a source.
+//!
+//! Patterns are expanded with the `glob` crate, except for patterns with a
+//! recursive `**` component such as `data/**/year=*/month=*/*.parquet`. Those
+//! are matched while walking the directory tree below their literal prefix, so
+//! that symlinked directories are followed but each directory is read only
+//! once, even when symlinks form a cycle.
+//!
+//! Patterns prefixed with `!`, either in the pattern list or in the `exclude`
+//! option of a source, remove matching files. An exclusion without a `/` is
+//! matched against the file name (`!*_tmp.parquet`); one with a `/` is matched
+//! against the whole path, with a leading `**/` added to relative patterns
+//! (`!**/_SUCCESS`, `!staging/*.csv`).
+//!
+//! Expansion stops with an error once more than the maximum number of files
+//! match, [`DEFAULT_MAX_FILES`] unless the source options set `max_files`.
+//! Matched files are returned in lexicographic order.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::prelude::*;
+
+/// Maximum number of files a set of glob patterns may match unless the source
+/// options set a different limit.
+pub const DEFAULT_MAX_FILES: usize = 100_000;
+
+/// Characters that make a path component a pattern rather than a literal name.
+const WILDCARDS: &[char] = &['*', '?', '['];
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// The files read by a source created from glob patterns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileManifest {
+    /// Patterns the files were matched with, exclusions included
+    pub patterns: Vec<String>,
+    /// Number of matched files
+    pub file_count: usize,
+    /// Total size of the matched files in bytes
+    pub total_bytes: u64,
+    /// Number of files matched by a pattern but removed by an exclusion
+    pub excluded_count: usize,
+}
+
+impl fmt::Display for FileManifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} files, {} bytes", self.file_count, self.total_bytes)?;
+        if self.excluded_count > 0 {
+            write!(f, ", {} excluded", self.excluded_count)?;
+        }
+        Ok(())
+    }
+}
+
+/// An exclusion pattern and the part of a path it is matched against.
+struct Exclusion {
+    pattern: Pattern,
+    whole_path: bool,
+}
+
+impl Exclusion {
+    fn parse(raw: &str) -> Result<Self> {
+        let raw = raw.strip_prefix('!').unwrap_or(raw);
+        let whole_path = raw.contains('/');
+        let source = if whole_path && !raw.starts_with('/') && !raw.starts_with("**") {
+            format!("**/{raw}")
+        } else {
+            raw.to_string()
+        };
+        let pattern = Pattern::new(&source).map_err(|e| {
+            TermError::Configuration(format!("Invalid exclusion pattern '{raw}': {e}"))
+        })?;
+        Ok(Self {
+            pattern,
+            whole_path,
+        })
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        if self.whole_path {
+            self.pattern.matches_path_with(path, MATCH_OPTIONS)
+        } else {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| self.pattern.matches_with(name, MATCH_OPTIONS))
+        }
+    }
+}
+
+/// Matched files in lexicographic order, with the limit and exclusions applied
+/// as they are added.
+struct Matches {
+    files: BTreeMap<String, u64>,
+    exclusions: Vec<Exclusion>,
+    excluded: HashSet<String>,
+    max_files: usize,
+}
+
+impl Matches {
+    fn add(&mut self, path: &Path, size: u64) -> Result<()> {
+        let Some(path_str) = path.to_str() else {
+            return Ok(());
+        };
+        if self
+            .exclusions
+            .iter()
+            .any(|exclusion| exclusion.matches(path))
+        {
+            self.excluded.insert(path_str.to_string());
+            return Ok(());
+        }
+        self.files.insert(path_str.to_string(), size);
+        if self.files.len() > self.max_files {
+            return Err(TermError::Configuration(format!(
+                "Glob patterns match more than {} files; narrow the patterns, add exclusions \
+                 or raise max_files",
+                self.max_files
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Expands `patterns` into the paths of the files they match.
+///
+/// Patterns starting with `!` and the patterns in `exclude` remove files. Fails
+/// if a pattern is invalid, if more than `max_files` (default
+/// [`DEFAULT_MAX_FILES`]) files match, or if no file matches.
+pub(crate) async fn expand_globs(
+    patterns: &[String],
+    exclude: &[String],
+    max_files: Option<usize>,
+) -> Result<(Vec<String>, FileManifest)> {
+    let (exclusions, includes): (Vec<&String>, Vec<&String>) = patterns
+        .iter()
+        .partition(|pattern| pattern.starts_with('!'));
+
+    let mut matches = Matches {
+        files: BTreeMap::new(),
+        exclusions: exclusions
+            .into_iter()
+            .chain(exclude)
+            .map(|raw| Exclusion::parse(raw))
+            .collect::<Result<_>>()?,
+        excluded: HashSet::new(),
+        max_files: max_files.unwrap_or(DEFAULT_MAX_FILES),
+    };
+
+    for pattern in includes {
+        if pattern.split('/').any(|component| component == "**") {
+            walk_recursive(pattern, &mut matches)?;
+        } else {
+            expand_simple(pattern, &mut matches)?;
+        }
+    }
+
+    if matches.files.is_empty() {
+        return Err(TermError::DataSource {
+            source_type: "file".to_string(),
+            message: "No files found matching glob patterns".to_string(),
+            source: None,
+        });
+    }
+
+    let manifest = FileManifest {
+        patterns: patterns
+            .iter()
+            .cloned()
+            .chain(exclude.iter().map(|raw| {
+                if raw.starts_with('!') {
+                    raw.clone()
+                } else {
+                    format!("!{raw}")
+                }
+            }))
+            .collect(),
+        file_count: matches.files.len(),
+        total_bytes: matches.files.values().sum(),
+        excluded_count: matches.excluded.len(),
+    };
+    debug!(
+        glob.patterns = ?manifest.patterns,
+        glob.files = manifest.file_count,
+        glob.bytes = manifest.total_bytes,
+        glob.excluded = manifest.excluded_count,
+        "Expanded glob patterns"
+    );
+    Ok((matches.files.into_keys().collect(), manifest))
+}
+
+/// Expands a pattern without a recursive component with the `glob` crate.
+fn expand_simple(pattern: &str, matches: &mut Matches) -> Result<()> {
+    let entries = glob::glob(pattern)
+        .map_err(|e| TermError::Configuration(format!("Invalid glob pattern '{pattern}': {e}")))?;
+
+    for entry in entries {
+        let path =
+            entry.map_err(|e| TermError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+        if let Ok(metadata) = std::fs::metadata(&path) {
+            if metadata.is_file() {
+                matches.add(&path, metadata.len())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Matches a pattern with a `**` component against the files below its literal
+/// prefix, reading every directory once.
+fn walk_recursive(pattern: &str, matches: &mut Matches) -> Result<()> {
+    let compiled = Pattern::new(pattern)
+        .map_err(|e| TermError::Configuration(format!("Invalid glob pattern '{pattern}': {e}")))?;
+
+    let prefix: Vec<&str> = pattern
+        .split('/')
+        .take_while(|component| !component.contains(WILDCARDS))
+        .collect();
+    let base = PathBuf::from(prefix.join("/"));
+
+    let mut visited = HashSet::new();
+    let mut pending = vec![base];
+    while let Some(dir) = pending.pop() {
+        let read_path = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir.as_path()
+        };
+        // Symlinks are followed, so a directory reached twice closes a cycle
+        let Ok(canonical) = std::fs::canonicalize(read_path) else {
+            continue;
+        };
+        if !visited.insert(canonical) {
+            debug!(glob.directory = %read_path.display(), "Skipping directory already visited");
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(read_path) else {
+            continue;
+        };
+
+        for entry in entries {
+            let entry = entry?;
+            let path = dir.join(entry.file_name());
+            let Ok(metadata) = std::fs::metadata(&path) else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(path);
+            } else if metadata.is_file() && compiled.matches_path_with(&path, MATCH_OPTIONS) {
+                matches.add(&path, metadata.len())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Describes the number of files of a source, with their size when known.
+pub(crate) fn describe_files(count: usize, manifest: Option<&FileManifest>) -> String {
+    match manifest {
+        Some(manifest) => manifest.to_string(),
+        None => format!("{count} files"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Creates a partitioned layout with temporary and marker files.
+    fn fixture() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let files = [
+            "data/year=2024/month=01/a.parquet",
+            "data/year=2024/month=01/a_tmp.parquet",
+            "data/year=2024/month=02/b.parquet",
+            "data/year=2024/month=02/_SUCCESS",
+            "data/year=2025/month=01/c.parquet",
+            "data/year=2025/notes.txt",
+            "data/top.parquet",
+        ];
+        for file in files {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "0123456789").unwrap();
+        }
+        dir
+    }
+
+    fn pattern(dir: &TempDir, pattern: &str) -> String {
+        format!("{}/{pattern}", dir.path().display())
+    }
+
+    fn relative(dir: &TempDir, paths: &[String]) -> Vec<String> {
+        let root = format!("{}/", dir.path().display());
+        paths
+            .iter()
+            .map(|path| path.strip_prefix(&root).unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_recursive_patterns() {
+        let dir = fixture();
+
+        let (paths, manifest) = expand_globs(&[pattern(&dir, "data/**/*.parquet")], &[], None)
+            .await
+            .unwrap();
+        assert_eq!(
+            relative(&dir, &paths),
+            vec![
+                "data/top.parquet",
+                "data/year=2024/month=01/a.parquet",
+                "data/year=2024/month=01/a_tmp.parquet",
+                "data/year=2024/month=02/b.parquet",
+                "data/year=2025/month=01/c.parquet",
+            ]
+        );
+        assert_eq!(manifest.file_count, 5);
+        assert_eq!(manifest.total_bytes, 50);
+
+        let (paths, _) = expand_globs(
+            &[pattern(&dir, "data/**/year=*/month=*/*.parquet")],
+            &[],
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(paths.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_exclusions() {
+        let dir = fixture();
+        let patterns = vec![pattern(&dir, "data/**/*"), "!**/_SUCCESS".to_string()];
+        let exclude = vec!["*_tmp.parquet".to_string(), "!*.txt".to_string()];
+
+        let (paths, manifest) = expand_globs(&patterns, &exclude, None).await.unwrap();
+        assert_eq!(
+            relative(&dir, &paths),
+            vec![
+                "data/top.parquet",
+                "data/year=2024/month=01/a.parquet",
+                "data/year=2024/month=02/b.parquet",
+                "data/year=2025/month=01/c.parquet",
+            ]
+        );
+        assert_eq!(manifest.excluded_count, 3);
+        assert_eq!(manifest.to_string(), "4 files, 40 bytes, 3 excluded");
+        assert_eq!(
+            manifest.patterns[1..],
+            ["!**/_SUCCESS", "!*_tmp.parquet", "!*.txt"]
+        );
+
+        // Exclusions with a separator match the whole path
+        let exclude = vec!["year=2024/**".to_string()];
+        let (paths, _) = expand_globs(&[pattern(&dir, "data/**/*.parquet")], &exclude, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            relative(&dir, &paths),
+            vec!["data/top.parquet", "data/year=2025/month=01/c.parquet"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_max_files() {
+        let dir = fixture();
+        let patterns = vec![pattern(&dir, "data/**/*.parquet")];
+
+        let error = expand_globs(&patterns, &[], Some(4)).await.unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Glob patterns match more than 4 files"));
+
+        // Excluded files do not count towards the limit
+        let exclude = vec!["*_tmp.parquet".to_string()];
+        let (paths, _) = expand_globs(&patterns, &exclude, Some(4)).await.unwrap();
+        assert_eq!(paths.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_patterns_are_deduplicated() {
+        let dir = fixture();
+        let patterns = vec![
+            pattern(&dir, "data/*.parquet"),
+            pattern(&dir, "data/**/*.parquet"),
+        ];
+
+        let (paths, manifest) = expand_globs(&patterns, &[], None).await.unwrap();
+        assert_eq!(paths.len(), 5);
+        assert_eq!(manifest.file_count, 5);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_symlink_cycles_are_read_once() {
+        let dir = fixture();
+        std::os::unix::fs::symlink(
+            dir.path().join("data"),
+            dir.path().join("data/year=2025/loop"),
+        )
+        .unwrap();
+
+        let (paths, _) = expand_globs(&[pattern(&dir, "data/**/c.parquet")], &[], None)
+            .await
+            .unwrap();
+        assert_eq!(paths.len(), 1);
+    }
+}
@@ -0,0 +1,289 @@
+//! Arrow Flight source implementation.
+//!
+//! [`FlightSource`] fetches record batches from an Arrow Flight service with
+//! `DoGet`, either for a ticket the caller already holds or for the endpoints a
+//! `GetFlightInfo` call returns for a descriptor. Like a
+//! [`StreamSource`](super::StreamSource), the batches are fetched once, when the
+//! source is created, and kept in memory up to
+//! [`StreamOptions::max_memory_bytes`]; beyond that they are spilled to an Arrow
+//! IPC file that is removed when the last clone of the source is dropped.
+
+use super::stream::{BatchBuffer, Decoded, StreamData};
+use super::{DataSource, StreamOptions};
+use crate::prelude::*;
+use crate::security::SecureString;
+use arrow::datatypes::Schema;
+use arrow::record_batch::RecordBatch;
+use arrow_flight::decode::FlightRecordBatchStream;
+use arrow_flight::error::FlightError;
+use arrow_flight::{FlightClient, FlightDescriptor, Ticket};
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use futures::{Stream, StreamExt};
+use std::sync::Arc;
+use tonic::transport::Endpoint;
+use tracing::{debug, instrument};
+
+/// Scheme of a Flight location asking clients to reuse their connection.
+const REUSE_CONNECTION_SCHEME: &str = "arrow-flight-reuse-connection://";
+
+/// What to fetch from a Flight service.
+#[derive(Debug, Clone)]
+pub enum FlightRequest {
+    /// Calls `DoGet` with a ticket issued by the service
+    Ticket(Vec<u8>),
+    /// Calls `GetFlightInfo` for the descriptor, then `DoGet` for each of the
+    /// endpoints it returns, in order
+    Descriptor(FlightDescriptor),
+}
+
+impl FlightRequest {
+    /// Requests the data of a ticket.
+    pub fn ticket(ticket: impl Into<Vec<u8>>) -> Self {
+        Self::Ticket(ticket.into())
+    }
+
+    /// Requests the dataset the service exposes under a path.
+    pub fn path<I, S>(path: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Descriptor(FlightDescriptor::new_path(
+            path.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// Requests the dataset produced by a service-specific command.
+    pub fn command(command: impl Into<Vec<u8>>) -> Self {
+        Self::Descriptor(FlightDescriptor::new_cmd(command.into()))
+    }
+}
+
+/// Options for fetching from a Flight service.
+#[derive(Debug, Clone, Default)]
+pub struct FlightOptions {
+    /// Memory budget and spill directory of the fetched batches
+    pub buffer: StreamOptions,
+    /// Token sent as `authorization: Bearer <token>` with every call
+    pub bearer_token: Option<SecureString>,
+}
+
+/// A data source reading record batches from an Arrow Flight service.
+///
+/// Requires the `flight` feature.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use term_guard::sources::{DataSource, FlightOptions, FlightRequest, FlightSource};
+///
+/// # async fn example() -> Result<()> {
+/// let source = FlightSource::fetch(
+///     "http://ingest.internal:50051",
+///     FlightRequest::path(["orders", "2024-06-01"]),
+///     FlightOptions {
+///         bearer_token: Some("secret".into()),
+///         ..Default::default()
+///     },
+/// )
+/// .await?;
+///
+/// let ctx = SessionContext::new();
+/// source.register(&ctx, "orders").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FlightSource {
+    origin: String,
+    schema: Arc<Schema>,
+    data: StreamData,
+    num_rows: usize,
+}
+
+impl FlightSource {
+    /// Connects to the service at `endpoint` and fetches the requested data.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::DataSource`] if the service cannot be reached or
+    /// rejects a call, and an I/O error if the batches cannot be spilled.
+    #[instrument(skip(request, options), fields(endpoint = %endpoint))]
+    pub async fn fetch(
+        endpoint: &str,
+        request: FlightRequest,
+        options: FlightOptions,
+    ) -> Result<Self> {
+        let mut client = connect_flight(endpoint, options.bearer_token.as_ref()).await?;
+
+        let tickets = match request {
+            FlightRequest::Ticket(ticket) => vec![(None, Ticket::new(ticket))],
+            FlightRequest::Descriptor(descriptor) => {
+                let info = client
+                    .get_flight_info(descriptor)
+                    .await
+                    .map_err(|e| flight_error("GetFlightInfo failed", e))?;
+                info.endpoint
+                    .into_iter()
+                    .filter_map(|endpoint| {
+                        let location = endpoint
+                            .location
+                            .into_iter()
+                            .map(|location| location.uri)
+                            .find(|uri| !uri.starts_with(REUSE_CONNECTION_SCHEME));
+                        endpoint.ticket.map(|ticket| (location, ticket))
+                    })
+                    .collect()
+            }
+        };
+
+        let mut streams = Vec::with_capacity(tickets.len());
+        for (location, ticket) in tickets {
+            let stream = match location {
+                Some(location) => {
+                    connect_flight(&location, options.bearer_token.as_ref())
+                        .await?
+                        .do_get(ticket)
+                        .await
+                }
+                None => client.do_get(ticket).await,
+            };
+            streams.push(stream.map_err(|e| flight_error("DoGet failed", e))?);
+        }
+
+        let decoded =
+            buffer_batches(futures::stream::iter(streams).flatten(), &options.buffer).await?;
+        debug!(
+            rows = decoded.num_rows,
+            spilled = decoded.data.is_spilled(),
+            "Fetched Flight stream"
+        );
+
+        Ok(Self::from_decoded(endpoint, decoded))
+    }
+
+    /// Buffers the batches of a Flight stream received by a server.
+    pub(crate) async fn receive(
+        origin: &str,
+        mut stream: FlightRecordBatchStream,
+        options: &StreamOptions,
+    ) -> Result<Self> {
+        let mut decoded = buffer_batches(&mut stream, options).await?;
+        // A stream without batches may still have sent its schema
+        if decoded.num_rows == 0 {
+            if let Some(schema) = stream.schema() {
+                decoded.schema = schema.clone();
+            }
+        }
+        Ok(Self::from_decoded(origin, decoded))
+    }
+
+    fn from_decoded(origin: &str, decoded: Decoded) -> Self {
+        Self {
+            origin: origin.to_string(),
+            schema: decoded.schema,
+            data: decoded.data,
+            num_rows: decoded.num_rows,
+        }
+    }
+
+    /// Returns the number of rows fetched.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns whether the batches exceeded the memory budget and were spilled to disk.
+    pub fn is_spilled(&self) -> bool {
+        self.data.is_spilled()
+    }
+}
+
+#[async_trait]
+impl DataSource for FlightSource {
+    #[instrument(skip(self, ctx, telemetry), fields(table_name = %table_name, source_type = "flight"))]
+    async fn register_with_telemetry(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        telemetry: Option<&Arc<TermTelemetry>>,
+    ) -> Result<()> {
+        let mut _datasource_span = if let Some(tel) = telemetry {
+            tel.start_datasource_span("flight", table_name)
+        } else {
+            TermSpan::noop()
+        };
+
+        self.data.register(ctx, table_name, &self.schema, "flight")
+    }
+
+    fn schema(&self) -> Option<&Arc<Schema>> {
+        Some(&self.schema)
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "Arrow Flight stream from {} ({} rows, {})",
+            self.origin,
+            self.num_rows,
+            self.data.storage()
+        )
+    }
+}
+
+/// Opens a client to `endpoint`, authenticated with `bearer_token` if set.
+pub(crate) async fn connect_flight(
+    endpoint: &str,
+    bearer_token: Option<&SecureString>,
+) -> Result<FlightClient> {
+    let channel = Endpoint::from_shared(endpoint.to_string())
+        .map_err(|e| {
+            TermError::data_source_with_source(
+                "flight",
+                format!("Invalid Flight endpoint '{endpoint}'"),
+                Box::new(e),
+            )
+        })?
+        .connect()
+        .await
+        .map_err(|e| {
+            TermError::data_source_with_source(
+                "flight",
+                format!("Failed to connect to Flight service at '{endpoint}'"),
+                Box::new(e),
+            )
+        })?;
+
+    let mut client = FlightClient::new(channel);
+    if let Some(token) = bearer_token {
+        client
+            .add_header("authorization", &format!("Bearer {}", token.expose()))
+            .map_err(|e| flight_error("Invalid bearer token", e))?;
+    }
+    Ok(client)
+}
+
+/// Buffers a stream of batches, taking the schema of the first one.
+///
+/// A stream without batches has an empty schema.
+async fn buffer_batches<S>(batches: S, options: &StreamOptions) -> Result<Decoded>
+where
+    S: Stream<Item = std::result::Result<RecordBatch, FlightError>>,
+{
+    futures::pin_mut!(batches);
+    let mut buffer: Option<BatchBuffer<'_>> = None;
+    while let Some(batch) = batches.next().await {
+        let batch = batch.map_err(|e| flight_error("Failed to decode Flight stream", e))?;
+        buffer
+            .get_or_insert_with(|| BatchBuffer::new(batch.schema(), options))
+            .push(batch)?;
+    }
+    buffer
+        .unwrap_or_else(|| BatchBuffer::new(Arc::new(Schema::empty()), options))
+        .finish()
+}
+
+/// Wraps a Flight error into a data source error.
+pub(crate) fn flight_error(message: &str, error: FlightError) -> TermError {
+    TermError::data_source_with_source("flight", message, Box::new(error))
+}
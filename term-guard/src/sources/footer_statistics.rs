@@ -0,0 +1,248 @@
+//! Statistics aggregated from the footers of Parquet files.
+//!
+//! Only the footers are read, never the data pages. See
+//! [`MetadataStatistics`] for how suites use the result and its caveats.
+
+use std::collections::HashMap;
+use std::fs::File;
+
+use datafusion::parquet::basic::{ConvertedType, LogicalType, Type as PhysicalType};
+use datafusion::parquet::file::reader::{FileReader, SerializedFileReader};
+use datafusion::parquet::file::statistics::Statistics;
+use datafusion::parquet::schema::types::ColumnDescriptor;
+
+use super::probe::unreadable;
+use crate::core::{MetadataColumnStatistics, MetadataStatistics, PARQUET_METADATA_EVIDENCE};
+use crate::prelude::*;
+
+/// Statistics of a column gathered over the row groups read so far.
+#[derive(Debug)]
+struct ColumnAccumulator {
+    /// Number of files with the column
+    files: usize,
+    /// Sum of the null counts, `None` once a column chunk lacks one
+    null_count: Option<u64>,
+    /// Smallest and largest value seen so far
+    bounds: Option<(f64, f64)>,
+    /// Whether the column is a signed integer column whose every column chunk
+    /// with values recorded its bounds
+    bounds_known: bool,
+    min_exact: bool,
+    max_exact: bool,
+}
+
+impl ColumnAccumulator {
+    fn new() -> Self {
+        Self {
+            files: 0,
+            null_count: Some(0),
+            bounds: None,
+            bounds_known: true,
+            min_exact: true,
+            max_exact: true,
+        }
+    }
+
+    fn add_chunk(&mut self, rows: u64, statistics: Option<&Statistics>) {
+        let null_count = statistics.and_then(Statistics::null_count_opt);
+        self.null_count = self.null_count.zip(null_count).map(|(a, b)| a + b);
+        // A chunk holding only nulls has no bounds to record
+        if rows == 0 || null_count == Some(rows) {
+            return;
+        }
+        let Some((statistics, (min, max))) =
+            statistics.and_then(|stats| integer_bounds(stats).map(|bounds| (stats, bounds)))
+        else {
+            self.bounds_known = false;
+            return;
+        };
+        self.bounds = Some(match self.bounds {
+            Some((low, high)) => (low.min(min), high.max(max)),
+            None => (min, max),
+        });
+        self.min_exact &= statistics.min_is_exact();
+        self.max_exact &= statistics.max_is_exact();
+    }
+
+    fn finish(self) -> MetadataColumnStatistics {
+        let bounds = self.bounds.filter(|_| self.bounds_known);
+        MetadataColumnStatistics {
+            null_count: self.null_count,
+            min: bounds.map(|(min, _)| min),
+            max: bounds.map(|(_, max)| max),
+            min_exact: self.min_exact,
+            max_exact: self.max_exact,
+        }
+    }
+}
+
+/// Returns the bounds of a chunk of an integer column, if it records both.
+fn integer_bounds(statistics: &Statistics) -> Option<(f64, f64)> {
+    match statistics {
+        Statistics::Int32(stats) => Some((*stats.min_opt()? as f64, *stats.max_opt()? as f64)),
+        Statistics::Int64(stats) => Some((*stats.min_opt()? as f64, *stats.max_opt()? as f64)),
+        _ => None,
+    }
+}
+
+/// Returns true for plain signed integer columns.
+///
+/// Dates, times, timestamps and decimals are stored as integers too, but their
+/// bounds are not the values constraints compare. Unsigned integers are left
+/// out because older writers ordered their statistics as signed.
+fn is_signed_integer(column: &ColumnDescriptor) -> bool {
+    matches!(
+        column.physical_type(),
+        PhysicalType::INT32 | PhysicalType::INT64
+    ) && match column.logical_type() {
+        Some(LogicalType::Integer {
+            is_signed: true, ..
+        }) => true,
+        Some(_) => false,
+        None => matches!(
+            column.converted_type(),
+            ConvertedType::NONE
+                | ConvertedType::INT_8
+                | ConvertedType::INT_16
+                | ConvertedType::INT_32
+                | ConvertedType::INT_64
+        ),
+    }
+}
+
+/// Returns the name of a top-level, non-repeated column.
+///
+/// Leaves of nested columns are left out: their null counts do not count the
+/// rows where a parent is null.
+fn top_level_name(column: &ColumnDescriptor) -> Option<&str> {
+    (column.max_rep_level() == 0 && column.path().parts().len() == 1).then(|| column.name())
+}
+
+/// Reads the footers of `paths` and aggregates their statistics.
+///
+/// Only top-level columns present in every file are kept. A column has a null
+/// count if every column chunk records one, and bounds if it is a signed
+/// integer column and every column chunk holding values records them.
+pub(crate) fn read_footer_statistics(paths: &[String]) -> Result<MetadataStatistics> {
+    let mut row_count = 0;
+    let mut columns: HashMap<String, ColumnAccumulator> = HashMap::new();
+
+    for path in paths {
+        let file = File::open(path).map_err(|e| unreadable("Parquet", path, e.into()))?;
+        let reader = SerializedFileReader::new(file).map_err(|e| {
+            TermError::data_source_with_source(
+                "Parquet",
+                format!("Cannot read the footer of '{path}': {e}"),
+                Box::new(e),
+            )
+        })?;
+        let metadata = reader.metadata();
+        row_count += metadata.file_metadata().num_rows().max(0) as u64;
+
+        for column in metadata.file_metadata().schema_descr().columns() {
+            if let Some(name) = top_level_name(column) {
+                let accumulator = columns
+                    .entry(name.to_string())
+                    .or_insert_with(ColumnAccumulator::new);
+                accumulator.files += 1;
+                accumulator.bounds_known &= is_signed_integer(column);
+            }
+        }
+        for row_group in metadata.row_groups() {
+            let rows = row_group.num_rows().max(0) as u64;
+            for chunk in row_group.columns() {
+                let Some(name) = top_level_name(chunk.column_descr()) else {
+                    continue;
+                };
+                if let Some(accumulator) = columns.get_mut(name) {
+                    accumulator.add_chunk(rows, chunk.statistics());
+                }
+            }
+        }
+    }
+
+    let mut statistics = MetadataStatistics::new(PARQUET_METADATA_EVIDENCE, row_count, paths.len());
+    for (name, accumulator) in columns {
+        if accumulator.files == paths.len() {
+            statistics.insert(name, accumulator.finish());
+        }
+    }
+    Ok(statistics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::parquet::arrow::ArrowWriter;
+    use datafusion::parquet::file::properties::{EnabledStatistics, WriterProperties};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, ids: Vec<Option<i64>>, statistics: bool) -> String {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        let names = StringArray::from(vec![Some("a"); ids.len()]);
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int64Array::from(ids)), Arc::new(names)],
+        )
+        .unwrap();
+        let mut properties = WriterProperties::builder().set_max_row_group_size(2);
+        if !statistics {
+            properties = properties.set_statistics_enabled(EnabledStatistics::None);
+        }
+        let path = dir.path().join(name);
+        let file = File::create(&path).unwrap();
+        let mut writer = ArrowWriter::try_new(file, schema, Some(properties.build())).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        path.display().to_string()
+    }
+
+    #[test]
+    fn test_aggregates_row_groups_and_files() {
+        let dir = TempDir::new().unwrap();
+        let paths = vec![
+            write(
+                &dir,
+                "a.parquet",
+                vec![Some(5), None, None, None, Some(-3)],
+                true,
+            ),
+            write(&dir, "b.parquet", vec![Some(40), Some(7)], true),
+        ];
+
+        let statistics = read_footer_statistics(&paths).unwrap();
+        assert_eq!(statistics.evidence, PARQUET_METADATA_EVIDENCE);
+        assert_eq!((statistics.row_count, statistics.file_count), (7, 2));
+        let id = statistics.column("id").unwrap();
+        assert_eq!(id.null_count, Some(3));
+        assert_eq!((id.min, id.max), (Some(-3.0), Some(40.0)));
+        assert!(id.min_exact && id.max_exact);
+        let name = statistics.column("name").unwrap();
+        assert_eq!((name.null_count, name.min), (Some(0), None));
+    }
+
+    #[test]
+    fn test_missing_statistics() {
+        let dir = TempDir::new().unwrap();
+        let paths = vec![
+            write(&dir, "a.parquet", vec![Some(1), Some(2)], true),
+            write(&dir, "b.parquet", vec![Some(3), None], false),
+        ];
+
+        let statistics = read_footer_statistics(&paths).unwrap();
+        assert_eq!(statistics.row_count, 4);
+        assert_eq!(statistics.column("id").unwrap().null_count, None);
+        assert_eq!(statistics.column("id").unwrap().max, None);
+
+        let err = read_footer_statistics(&[dir.path().join("missing").display().to_string()])
+            .unwrap_err();
+        assert!(err.to_string().contains("Cannot read"), "{err}");
+    }
+}
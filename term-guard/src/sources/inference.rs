@@ -0,0 +1,294 @@
+//! Type inference across the files of a multi-file source.
+//!
+//! Each file of a glob has its types inferred from its own first rows, so the same
+//! column can come out as `Int64` in one file and `Utf8` in another. Before any table
+//! is registered, [`InferenceReport`] lists such columns with the type inferred from
+//! each file, and the type every file is read as: the widened type where one exists,
+//! such as `Float64` for `Int64` and `Float64`, and `Utf8` otherwise, since every CSV
+//! value can be read as text.
+
+use super::schema_merge::widen;
+use crate::prelude::*;
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use tracing::info;
+
+/// Each file of a source paired with its schema.
+pub(crate) type FileSchemas = Vec<(String, Arc<Schema>)>;
+
+/// Number of files listed by name when describing a conflict.
+const LISTED_FILES: usize = 3;
+
+/// A column whose inferred type differs between files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeConflict {
+    /// Name of the column
+    pub column: String,
+    /// Type inferred from each file holding the column, in file order
+    pub files: Vec<(String, DataType)>,
+    /// Type the column is read as in every file
+    pub resolved: DataType,
+}
+
+impl TypeConflict {
+    /// Returns the distinct inferred types, in first-seen order, with the files
+    /// each was inferred from.
+    pub fn types(&self) -> Vec<(&DataType, Vec<&str>)> {
+        let mut types: Vec<(&DataType, Vec<&str>)> = Vec::new();
+        for (path, data_type) in &self.files {
+            match types.iter_mut().find(|(seen, _)| *seen == data_type) {
+                Some((_, paths)) => paths.push(path.as_str()),
+                None => types.push((data_type, vec![path.as_str()])),
+            }
+        }
+        types
+    }
+}
+
+impl fmt::Display for TypeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let types = self
+            .types()
+            .into_iter()
+            .map(|(data_type, paths)| format!("{data_type} in {}", list_files(&paths)))
+            .collect::<Vec<_>>();
+        write!(
+            f,
+            "column '{}' is {}, read as {}",
+            self.column,
+            types.join(", "),
+            self.resolved
+        )
+    }
+}
+
+/// The outcome of inferring the types of every file of a source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InferenceReport {
+    /// Number of files whose types were inferred
+    pub file_count: usize,
+    /// Maximum number of rows read from each file
+    pub sample_rows: usize,
+    /// Columns whose inferred type differs between files
+    pub conflicts: Vec<TypeConflict>,
+}
+
+impl InferenceReport {
+    /// Returns true if any column has different types in different files.
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+impl fmt::Display for InferenceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "types inferred from up to {} rows of each of {} files",
+            self.sample_rows, self.file_count
+        )?;
+        if self.has_conflicts() {
+            let conflicts = self
+                .conflicts
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            write!(f, "; conflicts: {}", conflicts.join("; "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the types inferred from each of `files` and resolves the conflicts.
+///
+/// Returns the report and the schemas of the files with every conflicting column set
+/// to its resolved type. With `strict`, conflicts are an error naming the files.
+pub(crate) fn resolve_types(
+    source_type: &str,
+    files: &[(String, Arc<Schema>)],
+    sample_rows: usize,
+    strict: bool,
+) -> Result<(InferenceReport, FileSchemas)> {
+    // Columns in first-seen order, with the type inferred from each file
+    let mut columns: Vec<TypeConflict> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for (path, schema) in files {
+        for field in schema.fields() {
+            let position = *positions.entry(field.name().clone()).or_insert_with(|| {
+                columns.push(TypeConflict {
+                    column: field.name().clone(),
+                    files: Vec::new(),
+                    resolved: field.data_type().clone(),
+                });
+                columns.len() - 1
+            });
+            let column = &mut columns[position];
+            column.resolved = widen(&column.resolved, field.data_type()).unwrap_or(DataType::Utf8);
+            column.files.push((path.clone(), field.data_type().clone()));
+        }
+    }
+
+    let conflicts: Vec<TypeConflict> = columns
+        .into_iter()
+        .filter(|column| {
+            column
+                .files
+                .iter()
+                .any(|(_, data_type)| *data_type != column.resolved)
+        })
+        .collect();
+    let report = InferenceReport {
+        file_count: files.len(),
+        sample_rows,
+        conflicts,
+    };
+    if !report.has_conflicts() {
+        return Ok((report, files.to_vec()));
+    }
+
+    if strict {
+        let conflicts = report
+            .conflicts
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        return Err(TermError::DataSource {
+            source_type: source_type.to_string(),
+            message: format!(
+                "Files have conflicting inferred types: {}. Disable strict inference to read \
+                 each column as a type every file fits, or set an explicit schema",
+                conflicts.join("; ")
+            ),
+            source: None,
+        });
+    }
+
+    info!(
+        source.type = %source_type,
+        source.files = files.len(),
+        "Resolved conflicting inferred types ({report})"
+    );
+    let resolved: HashMap<&str, &DataType> = report
+        .conflicts
+        .iter()
+        .map(|conflict| (conflict.column.as_str(), &conflict.resolved))
+        .collect();
+    let files = files
+        .iter()
+        .map(|(path, schema)| {
+            let fields: Vec<Field> = schema
+                .fields()
+                .iter()
+                .map(|field| match resolved.get(field.name().as_str()) {
+                    Some(data_type) => field.as_ref().clone().with_data_type((*data_type).clone()),
+                    None => field.as_ref().clone(),
+                })
+                .collect();
+            (path.clone(), Arc::new(Schema::new(fields)))
+        })
+        .collect();
+    Ok((report, files))
+}
+
+/// Lists the file names of `paths`, naming at most a few of them.
+fn list_files(paths: &[&str]) -> String {
+    let names: Vec<&str> = paths
+        .iter()
+        .take(LISTED_FILES)
+        .map(|&path| {
+            std::path::Path::new(path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(path)
+        })
+        .collect();
+    match paths.len() - names.len() {
+        0 => names.join(", "),
+        more => format!("{} and {more} more", names.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, fields: Vec<(&str, DataType)>) -> (String, Arc<Schema>) {
+        let fields: Vec<Field> = fields
+            .into_iter()
+            .map(|(name, data_type)| Field::new(name, data_type, true))
+            .collect();
+        (path.to_string(), Arc::new(Schema::new(fields)))
+    }
+
+    fn files() -> Vec<(String, Arc<Schema>)> {
+        (1..=7)
+            .map(|n| {
+                let id = if n == 7 {
+                    DataType::Utf8
+                } else {
+                    DataType::Int64
+                };
+                let amount = if n == 2 {
+                    DataType::Float64
+                } else {
+                    DataType::Int64
+                };
+                file(
+                    &format!("data/{n}.csv"),
+                    vec![("id", id), ("amount", amount), ("name", DataType::Utf8)],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_resolves_conflicts() {
+        let (report, resolved) = resolve_types("csv", &files(), 100, false).unwrap();
+        assert_eq!(report.file_count, 7);
+        assert_eq!(report.conflicts.len(), 2);
+
+        let id = &report.conflicts[0];
+        assert_eq!(id.column, "id");
+        assert_eq!(id.resolved, DataType::Utf8);
+        assert_eq!(id.files.len(), 7);
+        assert_eq!(id.files[6], ("data/7.csv".to_string(), DataType::Utf8));
+        assert_eq!(
+            id.to_string(),
+            "column 'id' is Int64 in 1.csv, 2.csv, 3.csv and 3 more, Utf8 in 7.csv, read as Utf8"
+        );
+        assert_eq!(report.conflicts[1].resolved, DataType::Float64);
+
+        for (_, schema) in &resolved {
+            assert_eq!(schema.field(0).data_type(), &DataType::Utf8);
+            assert_eq!(schema.field(1).data_type(), &DataType::Float64);
+            assert_eq!(schema.field(2).data_type(), &DataType::Utf8);
+        }
+        assert!(report
+            .to_string()
+            .starts_with("types inferred from up to 100 rows of each of 7 files; conflicts:"));
+    }
+
+    #[test]
+    fn test_strict_names_files() {
+        let error = resolve_types("csv", &files(), 100, true)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("Utf8 in 7.csv"), "{error}");
+        assert!(
+            error.contains("column 'amount' is Int64 in 1.csv"),
+            "{error}"
+        );
+
+        // Files that agree pass strict inference untouched
+        let files = &files()[2..6];
+        let (report, resolved) = resolve_types("csv", files, 100, true).unwrap();
+        assert!(!report.has_conflicts());
+        assert_eq!(resolved, files);
+        assert_eq!(
+            report.to_string(),
+            "types inferred from up to 100 rows of each of 4 files"
+        );
+    }
+}
@@ -1,6 +1,8 @@
 //! JSON and NDJSON file source implementation.
 
-use super::{CompressionType, DataSource};
+use super::file_glob::{describe_files, expand_globs};
+use super::probe::{probe_files, unreadable};
+use super::{CompressionType, DataSource, FileManifest, SourceProbe};
 use crate::prelude::*;
 use async_trait::async_trait;
 use datafusion::arrow::datatypes::Schema;
@@ -52,6 +54,12 @@ pub struct JsonOptions {
     pub compression: CompressionType,
     /// Maximum records to read for schema inference
     pub schema_infer_max_records: usize,
+    /// Patterns of files left out when expanding glob patterns, such as `*_tmp.jsonl`
+    pub exclude: Vec<String>,
+    /// Maximum number of files glob patterns may match (default: [`DEFAULT_MAX_FILES`])
+    ///
+    /// [`DEFAULT_MAX_FILES`]: super::DEFAULT_MAX_FILES
+    pub max_files: Option<usize>,
 }
 
 impl Default for JsonOptions {
@@ -61,6 +69,8 @@ impl Default for JsonOptions {
             schema: None,
             compression: CompressionType::Auto,
             schema_infer_max_records: 1000,
+            exclude: Vec::new(),
+            max_files: None,
         }
     }
 }
@@ -85,6 +95,13 @@ impl Default for JsonOptions {
 ///
 /// // Compressed NDJSON files with glob
 /// let source = JsonSource::from_glob("logs/*.jsonl.gz").await?;
+///
+/// // Every day of logs, without files still being written
+/// let options = JsonOptions {
+///     exclude: vec!["*.partial.jsonl".to_string()],
+///     ..Default::default()
+/// };
+/// let source = JsonSource::from_glob_with_options("logs/**/*.jsonl", options).await?;
 /// # Ok(())
 /// # }
 /// ```
@@ -93,6 +110,7 @@ pub struct JsonSource {
     paths: Vec<String>,
     options: JsonOptions,
     inferred_schema: Option<Arc<Schema>>,
+    manifest: Option<FileManifest>,
 }
 
 impl JsonSource {
@@ -108,6 +126,7 @@ impl JsonSource {
                 ..Default::default()
             },
             inferred_schema: None,
+            manifest: None,
         })
     }
 
@@ -117,6 +136,7 @@ impl JsonSource {
             paths: vec![path.into()],
             options,
             inferred_schema: None,
+            manifest: None,
         })
     }
 
@@ -138,20 +158,46 @@ impl JsonSource {
                 ..Default::default()
             },
             inferred_schema: None,
+            manifest: None,
         })
     }
 
     /// Creates a JSON source from a glob pattern.
     pub async fn from_glob(pattern: impl Into<String>) -> Result<Self> {
-        let patterns = vec![pattern.into()];
-        let paths = super::expand_globs(&patterns).await?;
-        Self::from_paths(paths)
+        Self::from_globs(vec![pattern.into()]).await
     }
 
     /// Creates a JSON source from multiple glob patterns.
+    ///
+    /// Patterns starting with `!` exclude the files they match. The format is
+    /// detected from the first matched file.
     pub async fn from_globs(patterns: Vec<String>) -> Result<Self> {
-        let paths = super::expand_globs(&patterns).await?;
-        Self::from_paths(paths)
+        let (paths, manifest) = expand_globs(&patterns, &[], None).await?;
+        let mut source = Self::from_paths(paths)?;
+        source.manifest = Some(manifest);
+        Ok(source)
+    }
+
+    /// Creates a JSON source from a glob pattern with custom options.
+    pub async fn from_glob_with_options(
+        pattern: impl Into<String>,
+        options: JsonOptions,
+    ) -> Result<Self> {
+        Self::from_globs_with_options(vec![pattern.into()], options).await
+    }
+
+    /// Creates a JSON source from multiple glob patterns with custom options.
+    ///
+    /// The `exclude` and `max_files` options apply to the expansion of the patterns.
+    pub async fn from_globs_with_options(
+        patterns: Vec<String>,
+        options: JsonOptions,
+    ) -> Result<Self> {
+        let (paths, manifest) =
+            expand_globs(&patterns, &options.exclude, options.max_files).await?;
+        let mut source = Self::from_paths(paths)?.with_custom_options(options);
+        source.manifest = Some(manifest);
+        Ok(source)
     }
 
     /// Sets custom options for this JSON source.
@@ -365,10 +411,45 @@ impl DataSource for JsonSource {
             let path = &self.paths[0];
             format!("{format_str} file: {path}")
         } else {
-            let count = self.paths.len();
-            format!("{format_str} files: {count} files")
+            let files = describe_files(self.paths.len(), self.manifest.as_ref());
+            format!("{format_str} files: {files}")
         }
     }
+
+    fn file_manifest(&self) -> Option<FileManifest> {
+        self.manifest.clone()
+    }
+
+    /// Reads the metadata of every file and infers the schema of the first one.
+    ///
+    /// The schema of regular JSON files is not read, their registration
+    /// reports that the format is not supported.
+    async fn probe(&self) -> Result<SourceProbe> {
+        let probe = probe_files("JSON", &self.paths).await?;
+        if !probe.exists {
+            return Ok(probe);
+        }
+        if let Some(schema) = &self.options.schema {
+            return Ok(probe.with_schema(schema));
+        }
+        if self.options.format != JsonFormatType::NdJson {
+            return Ok(probe);
+        }
+
+        let first_path = &self.paths[0];
+        let extension = std::path::Path::new(first_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| format!(".{ext}"))
+            .unwrap_or_default();
+        let mut options = NdJsonReadOptions::default().file_extension(&extension);
+        options.schema_infer_max_records = self.options.schema_infer_max_records;
+        let df = SessionContext::new()
+            .read_json(first_path.as_str(), options)
+            .await
+            .map_err(|e| unreadable("JSON", first_path, e.into()))?;
+        Ok(probe.with_schema(df.schema().inner()))
+    }
 }
 
 #[cfg(test)]
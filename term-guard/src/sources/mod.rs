@@ -1,8 +1,33 @@
 //! Data source connectors for Term validation library.
 //!
 //! This module provides implementations for various data sources including
-//! file formats (CSV, Parquet, JSON) with support for compression and glob patterns.
+//! file formats (CSV, Parquet, JSON) with support for compression and glob patterns,
+//! and Excel workbooks with the `excel` feature. [`StreamSource`] reads CSV and NDJSON
+//! from standard input or any other asynchronous byte stream.
+//!
+//! With the `delta` feature, `DeltaSource` reads a Delta Lake table at a chosen
+//! version or timestamp, see [`SnapshotSelector`].
+//!
+//! With the `flight` feature, `FlightSource` fetches record batches from an Arrow
+//! Flight service.
+//!
+//! Glob patterns may recurse with `**` and exclude files with `!` patterns; see
+//! [`FileManifest`] for what a glob-based source records about the files it matched.
+//! [`CsvSource`] infers the types of every file of a glob and reports the columns
+//! whose types differ between files, see [`InferenceReport`].
+//!
+//! [`DataSource::probe`] checks cheaply that a source is reachable before any
+//! query reads it, see [`SourceProbe`].
+//!
+//! [`DataSource::register_with_predicates`] registers a source restricted to
+//! the rows matching a suite's row filters, pushing them into the scan where the
+//! source supports it.
+//!
+//! [`DataSource::statistics`] returns statistics read from file metadata, which
+//! suites in metadata-only mode use to answer constraints without scanning the
+//! data, see [`MetadataStatistics`].
 
+use crate::core::MetadataStatistics;
 use crate::prelude::*;
 use async_trait::async_trait;
 use datafusion::arrow::datatypes::Schema;
@@ -11,9 +36,17 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 mod csv;
+mod file_glob;
+mod footer_statistics;
+mod inference;
 mod joined;
 mod json;
 mod parquet;
+mod probe;
+mod pushdown;
+mod schema_merge;
+mod snapshot;
+mod stream;
 
 #[cfg(feature = "database")]
 mod database;
@@ -21,10 +54,26 @@ mod database;
 #[cfg(feature = "cloud-storage")]
 mod cloud;
 
+#[cfg(feature = "excel")]
+mod excel;
+
+#[cfg(feature = "delta")]
+mod delta;
+
+#[cfg(feature = "flight")]
+mod flight;
+
 pub use csv::{CsvOptions, CsvSource};
+pub use file_glob::{FileManifest, DEFAULT_MAX_FILES};
+pub use inference::{InferenceReport, TypeConflict};
 pub use joined::{JoinCondition, JoinType, JoinedSource};
 pub use json::{JsonOptions, JsonSource};
 pub use parquet::{ParquetOptions, ParquetSource};
+pub use probe::{schema_hash, SourceProbe};
+pub(crate) use pushdown::filter_rows;
+pub use schema_merge::{SchemaMergePolicy, SchemaReport, WidenedColumn};
+pub use snapshot::{SnapshotSelector, TableSnapshot};
+pub use stream::{StreamFormat, StreamOptions, StreamSource, DEFAULT_STREAM_MEMORY_LIMIT};
 
 #[cfg(feature = "database")]
 pub use database::{DatabaseConfig, DatabaseSource};
@@ -38,6 +87,18 @@ pub use database::MySqlSource;
 #[cfg(all(feature = "database", feature = "sqlite"))]
 pub use database::SqliteSource;
 
+#[cfg(feature = "excel")]
+pub use excel::{ExcelOptions, ExcelSource, SheetSelector};
+
+#[cfg(feature = "delta")]
+pub use delta::DeltaSource;
+
+#[cfg(feature = "flight")]
+pub use flight::{FlightOptions, FlightRequest, FlightSource};
+
+#[cfg(feature = "flight")]
+pub(crate) use flight::{connect_flight, flight_error};
+
 #[cfg(feature = "cloud-storage")]
 pub use cloud::{AzureConfig, GcsConfig, S3Config};
 
@@ -102,6 +163,42 @@ pub trait DataSource: Debug + Send + Sync {
         telemetry: Option<&Arc<TermTelemetry>>,
     ) -> Result<()>;
 
+    /// Registers this data source restricted to the rows matching `predicates`.
+    ///
+    /// Predicates are SQL boolean expressions over the columns of the source,
+    /// such as `event_date = '2024-01-02'`; the registered table holds only the
+    /// rows matching all of them. Sources that can skip data with them push
+    /// them into their scans, which Parquet sources do to prune row groups and
+    /// pages by their statistics.
+    ///
+    /// The default implementation registers the source with
+    /// [`register_with_telemetry`](Self::register_with_telemetry) and replaces
+    /// it with a view filtering its rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermError::Configuration`] if a predicate cannot be planned
+    /// against the source, for example because it names a missing column.
+    async fn register_with_predicates(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        predicates: &[String],
+        telemetry: Option<&Arc<TermTelemetry>>,
+    ) -> Result<()> {
+        self.register_with_telemetry(ctx, table_name, telemetry)
+            .await?;
+        if predicates.is_empty() {
+            return Ok(());
+        }
+        let df = ctx.table(table_name).await?;
+        let filtered = filter_rows(df, predicates, table_name);
+        // Leave nothing registered if a predicate is invalid
+        ctx.deregister_table(table_name)?;
+        ctx.register_table(table_name, filtered?.into_view())?;
+        Ok(())
+    }
+
     /// Returns the schema of this data source if known.
     ///
     /// This may return `None` if schema inference hasn't been performed yet.
@@ -109,6 +206,48 @@ pub trait DataSource: Debug + Send + Sync {
 
     /// Returns a human-readable description of this data source.
     fn description(&self) -> String;
+
+    /// Returns how the files of this source were reconciled into one table.
+    ///
+    /// Sources that read several files with a [`SchemaMergePolicy`] return the report
+    /// of their last registration. Other sources return `None`.
+    fn schema_report(&self) -> Option<SchemaReport> {
+        None
+    }
+
+    /// Returns the files this source matched with its glob patterns.
+    ///
+    /// File sources created from glob patterns return the number and total size of
+    /// the matched files. Other sources return `None`.
+    fn file_manifest(&self) -> Option<FileManifest> {
+        None
+    }
+
+    /// Returns statistics of the source's data read from file metadata,
+    /// without scanning the data.
+    ///
+    /// Suites built with
+    /// [`metadata_only`](crate::core::ValidationSuiteBuilder::metadata_only)
+    /// answer eligible constraints from them; see [`MetadataStatistics`] for
+    /// the caveats. Parquet sources aggregate the statistics of their file
+    /// footers. The default implementation returns `None`.
+    async fn statistics(&self) -> Result<Option<MetadataStatistics>> {
+        Ok(None)
+    }
+
+    /// Checks cheaply that the data of this source is reachable.
+    ///
+    /// File and cloud sources verify that their files or objects exist and can
+    /// be read, reading only metadata and the header or footer of the first
+    /// one. An error means the source cannot be accessed, for example for lack
+    /// of permissions; a probe whose [`exists`](SourceProbe::exists) is false
+    /// means it was accessed but holds no data.
+    ///
+    /// The default implementation checks nothing and returns
+    /// [`SourceProbe::unchecked`].
+    async fn probe(&self) -> Result<SourceProbe> {
+        Ok(SourceProbe::unchecked())
+    }
 }
 
 /// Common compression formats supported by file sources.
@@ -163,39 +302,6 @@ impl CompressionType {
     }
 }
 
-/// Utility function to expand glob patterns into file paths.
-pub(crate) async fn expand_globs(patterns: &[String]) -> Result<Vec<String>> {
-    use glob::glob;
-
-    let mut paths = Vec::new();
-    for pattern in patterns {
-        let matches = glob(pattern).map_err(|e| {
-            TermError::Configuration(format!("Invalid glob pattern '{pattern}': {e}"))
-        })?;
-
-        for entry in matches {
-            let path = entry
-                .map_err(|e| TermError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
-
-            if path.is_file() {
-                if let Some(path_str) = path.to_str() {
-                    paths.push(path_str.to_string());
-                }
-            }
-        }
-    }
-
-    if paths.is_empty() {
-        return Err(TermError::DataSource {
-            source_type: "file".to_string(),
-            message: "No files found matching glob patterns".to_string(),
-            source: None,
-        });
-    }
-
-    Ok(paths)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
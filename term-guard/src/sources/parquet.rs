@@ -1,15 +1,22 @@
 //! Parquet file source implementation.
 
-use super::DataSource;
+use super::file_glob::{describe_files, expand_globs};
+use super::footer_statistics::read_footer_statistics;
+use super::probe::{probe_files, unreadable};
+use super::pushdown::filter_rows;
+use super::schema_merge::{is_uniform, merge_schemas};
+use super::{DataSource, FileManifest, SchemaMergePolicy, SchemaReport, SourceProbe};
+use crate::core::MetadataStatistics;
 use crate::prelude::*;
 use async_trait::async_trait;
 use datafusion::arrow::datatypes::Schema;
+use datafusion::config::TableParquetOptions;
 use datafusion::datasource::file_format::parquet::ParquetFormat;
 use datafusion::datasource::listing::{
     ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
 };
 use datafusion::prelude::*;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use tracing::instrument;
 
 /// Options for configuring Parquet file reading.
@@ -23,6 +30,16 @@ pub struct ParquetOptions {
     pub batch_size: usize,
     /// Maximum number of threads to use for reading
     pub max_threads: Option<usize>,
+    /// How files with different schemas are reconciled (default: Strict)
+    ///
+    /// Only applies to multi-file sources without an explicit `schema`.
+    pub schema_merge: SchemaMergePolicy,
+    /// Patterns of files left out when expanding glob patterns, such as `*_tmp.parquet`
+    pub exclude: Vec<String>,
+    /// Maximum number of files glob patterns may match (default: [`DEFAULT_MAX_FILES`])
+    ///
+    /// [`DEFAULT_MAX_FILES`]: super::DEFAULT_MAX_FILES
+    pub max_files: Option<usize>,
 }
 
 impl ParquetOptions {
@@ -33,6 +50,9 @@ impl ParquetOptions {
             enable_pruning: true,
             batch_size: 8192,
             max_threads: None,
+            schema_merge: SchemaMergePolicy::default(),
+            exclude: Vec::new(),
+            max_files: None,
         }
     }
 }
@@ -42,7 +62,7 @@ impl ParquetOptions {
 /// # Examples
 ///
 /// ```rust,ignore
-/// use term_guard::sources::{ParquetSource, ParquetOptions};
+/// use term_guard::sources::{ParquetOptions, ParquetSource, SchemaMergePolicy};
 ///
 /// # async fn example() -> Result<()> {
 /// // Simple Parquet file
@@ -58,6 +78,20 @@ impl ParquetOptions {
 ///
 /// // Multiple files with glob pattern
 /// let source = ParquetSource::from_glob("data/year=2023/*.parquet").await?;
+///
+/// // Partitioned files, without temporary files and success markers
+/// let options = ParquetOptions {
+///     exclude: vec!["*_tmp.parquet".to_string(), "**/_SUCCESS".to_string()],
+///     max_files: Some(10_000),
+///     ..ParquetOptions::new()
+/// };
+/// let source =
+///     ParquetSource::from_glob_with_options("data/**/year=*/month=*/*.parquet", options).await?;
+///
+/// // Keep only the columns every file has
+/// let source = ParquetSource::from_glob("data/year=2024/*.parquet")
+///     .await?
+///     .with_schema_merge_policy(SchemaMergePolicy::Intersection);
 /// # Ok(())
 /// # }
 /// ```
@@ -66,6 +100,8 @@ pub struct ParquetSource {
     paths: Vec<String>,
     options: ParquetOptions,
     metadata_schema: Option<Arc<Schema>>,
+    schema_report: Arc<RwLock<Option<SchemaReport>>>,
+    manifest: Option<FileManifest>,
 }
 
 impl ParquetSource {
@@ -75,6 +111,8 @@ impl ParquetSource {
             paths: vec![path.into()],
             options: ParquetOptions::new(),
             metadata_schema: None,
+            schema_report: Arc::default(),
+            manifest: None,
         })
     }
 
@@ -84,6 +122,8 @@ impl ParquetSource {
             paths: vec![path.into()],
             options,
             metadata_schema: None,
+            schema_report: Arc::default(),
+            manifest: None,
         })
     }
 
@@ -98,20 +138,43 @@ impl ParquetSource {
             paths,
             options: ParquetOptions::new(),
             metadata_schema: None,
+            schema_report: Arc::default(),
+            manifest: None,
         })
     }
 
     /// Creates a Parquet source from a glob pattern.
     pub async fn from_glob(pattern: impl Into<String>) -> Result<Self> {
-        let patterns = vec![pattern.into()];
-        let paths = super::expand_globs(&patterns).await?;
-        Self::from_paths(paths)
+        Self::from_globs(vec![pattern.into()]).await
     }
 
     /// Creates a Parquet source from multiple glob patterns.
+    ///
+    /// Patterns starting with `!` exclude the files they match.
     pub async fn from_globs(patterns: Vec<String>) -> Result<Self> {
-        let paths = super::expand_globs(&patterns).await?;
-        Self::from_paths(paths)
+        Self::from_globs_with_options(patterns, ParquetOptions::new()).await
+    }
+
+    /// Creates a Parquet source from a glob pattern with custom options.
+    pub async fn from_glob_with_options(
+        pattern: impl Into<String>,
+        options: ParquetOptions,
+    ) -> Result<Self> {
+        Self::from_globs_with_options(vec![pattern.into()], options).await
+    }
+
+    /// Creates a Parquet source from multiple glob patterns with custom options.
+    ///
+    /// The `exclude` and `max_files` options apply to the expansion of the patterns.
+    pub async fn from_globs_with_options(
+        patterns: Vec<String>,
+        options: ParquetOptions,
+    ) -> Result<Self> {
+        let (paths, manifest) =
+            expand_globs(&patterns, &options.exclude, options.max_files).await?;
+        let mut source = Self::from_paths(paths)?.with_custom_options(options);
+        source.manifest = Some(manifest);
+        Ok(source)
     }
 
     /// Sets custom options for this Parquet source.
@@ -120,6 +183,92 @@ impl ParquetSource {
         self
     }
 
+    /// Sets how files with different schemas are reconciled.
+    pub fn with_schema_merge_policy(mut self, policy: SchemaMergePolicy) -> Self {
+        self.options.schema_merge = policy;
+        self
+    }
+
+    /// Merges the schemas read from each file's metadata and records the report.
+    ///
+    /// Returns the merged report, or `None` if every file already has the same schema or
+    /// an explicit schema is set.
+    async fn merge_file_schemas(&self, ctx: &SessionContext) -> Result<Option<SchemaReport>> {
+        if self.options.schema.is_some() {
+            return Ok(None);
+        }
+
+        let mut files = Vec::with_capacity(self.paths.len());
+        for path in &self.paths {
+            let df = ctx
+                .read_parquet(path.as_str(), ParquetReadOptions::default())
+                .await?;
+            files.push((path.clone(), df.schema().inner().clone()));
+        }
+
+        let report = merge_schemas(self.options.schema_merge, "parquet", &files)?;
+        if let Ok(mut stored) = self.schema_report.write() {
+            *stored = Some(report.clone());
+        }
+        Ok((!is_uniform(&files)).then_some(report))
+    }
+
+    /// Builds a listing table reading exactly the files of this source.
+    ///
+    /// Files next to them that were excluded or not matched are not read. Files
+    /// with different schemas are read with the merged schema, Parquet columns
+    /// being matched by name.
+    async fn listing_table(
+        &self,
+        ctx: &SessionContext,
+        format: ParquetFormat,
+    ) -> Result<ListingTable> {
+        let table_paths = self
+            .paths
+            .iter()
+            .map(ListingTableUrl::parse)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let listing_options = ListingOptions::new(Arc::new(format)).with_file_extension(".parquet");
+
+        let merged = if self.paths.len() > 1 {
+            self.merge_file_schemas(ctx).await?
+        } else {
+            None
+        };
+        // Infer schema if not provided
+        let schema = if let Some(report) = merged {
+            report.schema
+        } else if let Some(schema) = &self.options.schema {
+            schema.clone()
+        } else if let Some(report) = self.schema_report() {
+            report.schema
+        } else {
+            // Infer schema using a mutable clone
+            let mut source_clone = self.clone();
+            source_clone.read_metadata_schema().await?
+        };
+
+        let config = ListingTableConfig::new_with_multi_paths(table_paths)
+            .with_listing_options(listing_options)
+            .with_schema(schema);
+
+        Ok(ListingTable::try_new(config)?)
+    }
+
+    /// Returns the format of scans that evaluate row filters while decoding.
+    ///
+    /// Besides pruning row groups by their statistics, such scans skip pages
+    /// using the page index and decode the other columns only for matching rows.
+    fn pushdown_format(&self) -> ParquetFormat {
+        let mut options = TableParquetOptions::default();
+        options.global.pruning = self.options.enable_pruning;
+        options.global.enable_page_index = true;
+        options.global.pushdown_filters = true;
+        options.global.reorder_filters = true;
+        ParquetFormat::new().with_options(options)
+    }
+
     /// Reads schema from Parquet file metadata.
     #[instrument(skip(self))]
     #[allow(dead_code)]
@@ -172,39 +321,39 @@ impl DataSource for ParquetSource {
             ctx.register_parquet(table_name, &self.paths[0], options)
                 .await?;
         } else {
-            // Multiple files - use ListingTable
-            // For multiple files, we need to use the directory path, not a specific file
-            let first_path = std::path::Path::new(&self.paths[0]);
-            let dir_path = first_path
-                .parent()
-                .ok_or_else(|| TermError::Configuration("Invalid file path".to_string()))?;
-            let dir_path_str = dir_path.to_str().ok_or_else(|| {
-                TermError::Configuration("Path contains invalid UTF-8".to_string())
-            })?;
-            let table_path = ListingTableUrl::parse(dir_path_str)?;
-
             let format = ParquetFormat::new().with_enable_pruning(self.options.enable_pruning);
+            let table = self.listing_table(ctx, format).await?;
+            ctx.register_table(table_name, Arc::new(table))?;
+        }
 
-            let listing_options =
-                ListingOptions::new(Arc::new(format)).with_file_extension(".parquet");
-
-            // Infer schema if not provided
-            let schema = if let Some(schema) = &self.options.schema {
-                schema.clone()
-            } else {
-                // Infer schema using a mutable clone
-                let mut source_clone = self.clone();
-                source_clone.read_metadata_schema().await?
-            };
-
-            let config = ListingTableConfig::new(table_path)
-                .with_listing_options(listing_options)
-                .with_schema(schema);
+        Ok(())
+    }
 
-            let table = ListingTable::try_new(config)?;
-            ctx.register_table(table_name, Arc::new(table))?;
+    /// Registers a listing table whose scans push `predicates` down, even for a
+    /// single file, so row groups and pages they rule out are not read.
+    #[instrument(skip(self, ctx, predicates, telemetry), fields(table_name = %table_name, source_type = "parquet", file_count = self.paths.len()))]
+    async fn register_with_predicates(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        predicates: &[String],
+        telemetry: Option<&Arc<TermTelemetry>>,
+    ) -> Result<()> {
+        if predicates.is_empty() {
+            return self
+                .register_with_telemetry(ctx, table_name, telemetry)
+                .await;
         }
+        let mut _datasource_span = if let Some(tel) = telemetry {
+            tel.start_datasource_span("parquet", table_name)
+        } else {
+            TermSpan::noop()
+        };
 
+        let table = self.listing_table(ctx, self.pushdown_format()).await?;
+        let df = ctx.read_table(Arc::new(table))?;
+        let filtered = filter_rows(df, predicates, table_name)?;
+        ctx.register_table(table_name, filtered.into_view())?;
         Ok(())
     }
 
@@ -219,11 +368,53 @@ impl DataSource for ParquetSource {
         if self.paths.len() == 1 {
             let path = &self.paths[0];
             format!("Parquet file: {path}")
+        } else if let Some(report) = self.schema_report() {
+            let files = describe_files(self.paths.len(), self.manifest.as_ref());
+            format!("Parquet files: {files} ({report})")
+        } else if self.options.schema.is_none() {
+            let files = describe_files(self.paths.len(), self.manifest.as_ref());
+            let policy = self.options.schema_merge;
+            format!("Parquet files: {files} (schema merge: {policy})")
         } else {
-            let count = self.paths.len();
-            format!("Parquet files: {count} files")
+            let files = describe_files(self.paths.len(), self.manifest.as_ref());
+            format!("Parquet files: {files}")
         }
     }
+
+    fn schema_report(&self) -> Option<SchemaReport> {
+        self.schema_report.read().ok()?.clone()
+    }
+
+    fn file_manifest(&self) -> Option<FileManifest> {
+        self.manifest.clone()
+    }
+
+    /// Aggregates the row counts, null counts and integer bounds recorded in
+    /// the footers of the files, on the blocking thread pool.
+    async fn statistics(&self) -> Result<Option<MetadataStatistics>> {
+        let paths = self.paths.clone();
+        tokio::task::spawn_blocking(move || read_footer_statistics(&paths))
+            .await
+            .map_err(|e| TermError::Internal(format!("Failed to read Parquet footers: {e}")))?
+            .map(Some)
+    }
+
+    /// Reads the metadata of every file and the footer of the first one.
+    async fn probe(&self) -> Result<SourceProbe> {
+        let probe = probe_files("Parquet", &self.paths).await?;
+        if !probe.exists {
+            return Ok(probe);
+        }
+        if let Some(schema) = &self.options.schema {
+            return Ok(probe.with_schema(schema));
+        }
+        let first_path = &self.paths[0];
+        let df = SessionContext::new()
+            .read_parquet(first_path.as_str(), ParquetReadOptions::default())
+            .await
+            .map_err(|e| unreadable("Parquet", first_path, e.into()))?;
+        Ok(probe.with_schema(df.schema().inner()))
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,158 @@
+//! Reachability probes of data sources.
+//!
+//! A misconfigured bucket or a missing file is otherwise only noticed once
+//! DataFusion plans and partially executes the queries of a suite, where it
+//! surfaces as a confusing error of whichever constraint ran first.
+//! [`DataSource::probe`](super::DataSource::probe) checks a source up front and
+//! cheaply: file sources read the metadata of their files and the header or
+//! footer of the first one, cloud sources issue a head or list request and
+//! read the first object the same way.
+//!
+//! Suites probe their attached sources before running when preflight is
+//! enabled, see
+//! [`ValidationSuiteBuilder::with_preflight`](crate::core::ValidationSuiteBuilder::with_preflight).
+
+use std::io::ErrorKind;
+
+use datafusion::arrow::datatypes::Schema;
+
+use crate::core::hash_parts;
+use crate::prelude::*;
+
+/// What a probe found out about a data source.
+///
+/// Fields a source cannot determine cheaply are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceProbe {
+    /// Whether the data of the source exists
+    pub exists: bool,
+    /// Total size of the source's files or objects in bytes
+    pub bytes: Option<u64>,
+    /// Number of files or objects the source reads
+    pub file_count: Option<usize>,
+    /// Hash of the schema read from the first file, see [`schema_hash`]
+    pub schema_hash: Option<String>,
+}
+
+impl SourceProbe {
+    /// A probe of a source that cannot be checked cheaply.
+    ///
+    /// The source is assumed to exist; registration reports any problem.
+    pub fn unchecked() -> Self {
+        Self {
+            exists: true,
+            bytes: None,
+            file_count: None,
+            schema_hash: None,
+        }
+    }
+
+    /// A probe of a reachable source whose data does not exist.
+    pub fn missing() -> Self {
+        Self {
+            exists: false,
+            ..Self::unchecked()
+        }
+    }
+
+    /// A probe of existing files.
+    pub fn files(file_count: usize, bytes: u64) -> Self {
+        Self {
+            exists: true,
+            bytes: Some(bytes),
+            file_count: Some(file_count),
+            schema_hash: None,
+        }
+    }
+
+    /// Records the hash of `schema`.
+    pub fn with_schema(mut self, schema: &Schema) -> Self {
+        self.schema_hash = Some(schema_hash(schema));
+        self
+    }
+}
+
+/// Hashes the names, data types and nullability of the fields of `schema`.
+///
+/// Equal schemas have equal hashes, so comparing the hash of two probes tells
+/// whether a source changed shape between them.
+pub fn schema_hash(schema: &Schema) -> String {
+    let fields: Vec<String> = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            format!(
+                "{}:{}:{}",
+                field.name(),
+                field.data_type(),
+                field.is_nullable()
+            )
+        })
+        .collect();
+    hash_parts(fields.iter().map(String::as_str))
+}
+
+/// Reads the metadata of local files.
+///
+/// Returns a [`SourceProbe::missing`] probe if there are no paths or a file
+/// does not exist, and an error if a file cannot be accessed.
+pub(crate) async fn probe_files(source_type: &str, paths: &[String]) -> Result<SourceProbe> {
+    if paths.is_empty() {
+        return Ok(SourceProbe::missing());
+    }
+    let mut bytes = 0;
+    for path in paths {
+        match tokio::fs::metadata(path).await {
+            Ok(metadata) => bytes += metadata.len(),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(SourceProbe::missing()),
+            Err(e) => {
+                return Err(TermError::data_source_with_source(
+                    source_type,
+                    format!("Cannot access '{path}': {e}"),
+                    Box::new(e),
+                ))
+            }
+        }
+    }
+    Ok(SourceProbe::files(paths.len(), bytes))
+}
+
+/// Wraps an error reading the first file of a source during a probe.
+pub(crate) fn unreadable(source_type: &str, path: &str, error: TermError) -> TermError {
+    TermError::data_source_with_source(
+        source_type,
+        format!("Cannot read '{path}': {error}"),
+        Box::new(error),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::arrow::datatypes::{DataType, Field};
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[tokio::test]
+    async fn test_probe_files() -> Result<()> {
+        let mut file = NamedTempFile::new()?;
+        file.write_all(b"id\n1\n")?;
+        let path = file.path().to_string_lossy().to_string();
+
+        let probe = probe_files("CSV", std::slice::from_ref(&path)).await?;
+        assert_eq!(probe, SourceProbe::files(1, 5));
+
+        let missing = format!("{path}.missing");
+        assert!(!probe_files("CSV", &[path, missing]).await?.exists);
+        assert!(!probe_files("CSV", &[]).await?.exists);
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_hash() {
+        let schema = Schema::new(vec![Field::new("id", DataType::Int64, false)]);
+        let nullable = Schema::new(vec![Field::new("id", DataType::Int64, true)]);
+        assert_eq!(schema_hash(&schema), schema_hash(&schema.clone()));
+        assert_ne!(schema_hash(&schema), schema_hash(&nullable));
+    }
+}
@@ -0,0 +1,75 @@
+//! Row filters applied by data sources at registration.
+//!
+//! A suite that validates only part of a table, such as yesterday's partition,
+//! passes its row filters to the source of the table through
+//! [`DataSource::register_with_predicates`](super::DataSource::register_with_predicates).
+//! The registered table then holds only the matching rows, and sources that
+//! can use the filters to skip data do so: Parquet sources prune row groups
+//! and pages whose statistics rule every row out.
+
+use datafusion::prelude::*;
+
+use crate::prelude::*;
+use crate::security::SqlSecurity;
+
+/// Keeps the rows of `df` matching every predicate.
+///
+/// Predicates are SQL boolean expressions over the columns of `df`.
+///
+/// # Errors
+///
+/// Returns [`TermError::Configuration`] if a predicate is unsafe or cannot be
+/// planned against `df`, for example because it names a missing column.
+pub(crate) fn filter_rows(df: DataFrame, predicates: &[String], table: &str) -> Result<DataFrame> {
+    let mut filtered = df;
+    for predicate in predicates {
+        SqlSecurity::validate_sql_expression(predicate)?;
+        let expr = filtered.parse_sql_expr(predicate).map_err(|e| {
+            TermError::Configuration(format!(
+                "Invalid row filter '{predicate}' for table '{table}': {e}"
+            ))
+        })?;
+        filtered = filtered.filter(expr).map_err(|e| {
+            TermError::Configuration(format!(
+                "Invalid row filter '{predicate}' for table '{table}': {e}"
+            ))
+        })?;
+    }
+    Ok(filtered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn context() -> SessionContext {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE events (id INT, event_date VARCHAR) AS VALUES (1, '2024-01-01'), (2, '2024-01-02'), (3, '2024-01-02')")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_filter_rows() {
+        let ctx = context().await;
+        let df = ctx.table("events").await.unwrap();
+
+        let filtered = filter_rows(
+            df.clone(),
+            &[
+                "event_date = '2024-01-02'".to_string(),
+                "id > 2".to_string(),
+            ],
+            "events",
+        )
+        .unwrap();
+        assert_eq!(filtered.count().await.unwrap(), 1);
+
+        let err = filter_rows(df, &["missing_column = 1".to_string()], "events").unwrap_err();
+        assert!(err.to_string().contains("Invalid row filter"), "{err}");
+    }
+}
@@ -0,0 +1,543 @@
+//! Schema reconciliation for file sources that read several files.
+//!
+//! A glob often matches files written at different times, and a column added mid-month
+//! means the files no longer share one schema. [`SchemaMergePolicy`] decides how such
+//! files are combined into a single table, and [`SchemaReport`] records what was done so
+//! that validation authors know which columns they are actually validating.
+
+use crate::prelude::*;
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::common::{Column, ScalarValue};
+use datafusion::logical_expr::{cast, lit, Expr};
+use datafusion::prelude::DataFrame;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// How a multi-file source reconciles files whose schemas differ.
+///
+/// Types that differ only in width are widened to fit every file: `Int32` and `Int64`
+/// become `Int64`, integers mixed with floats become `Float64`. Types that cannot be
+/// widened into each other, such as `Int64` and `Utf8`, are incompatible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SchemaMergePolicy {
+    /// Every file must have the same columns with the same types.
+    ///
+    /// Registration fails on the first file that differs, naming the differing fields.
+    #[default]
+    Strict,
+    /// Keep every column of every file.
+    ///
+    /// Rows from files that lack a column read it as null. Incompatible types fail
+    /// registration.
+    UnionNullable,
+    /// Keep only the columns present in every file.
+    ///
+    /// The other columns are dropped with a warning, as are common columns whose
+    /// types are incompatible.
+    Intersection,
+}
+
+impl SchemaMergePolicy {
+    /// Returns the name of this policy.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Strict => "strict",
+            Self::UnionNullable => "union_nullable",
+            Self::Intersection => "intersection",
+        }
+    }
+}
+
+impl fmt::Display for SchemaMergePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A column whose type was widened so that every file fits it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WidenedColumn {
+    /// Name of the column
+    pub name: String,
+    /// Narrower types found in some of the files
+    pub from: Vec<DataType>,
+    /// Type of the column in the registered table
+    pub to: DataType,
+}
+
+/// Describes how the files of a source were reconciled into one table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaReport {
+    /// The policy that was applied
+    pub policy: SchemaMergePolicy,
+    /// Number of files read
+    pub file_count: usize,
+    /// Schema of the registered table
+    pub schema: Arc<Schema>,
+    /// Columns missing from some files, read as null for those files
+    pub null_filled: Vec<String>,
+    /// Columns whose type was widened
+    pub widened: Vec<WidenedColumn>,
+    /// Columns present in some files that are not part of the table
+    pub dropped: Vec<String>,
+}
+
+impl SchemaReport {
+    /// Returns true if any column was null-filled, widened or dropped.
+    pub fn is_adjusted(&self) -> bool {
+        !self.null_filled.is_empty() || !self.widened.is_empty() || !self.dropped.is_empty()
+    }
+}
+
+impl fmt::Display for SchemaReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "schema merge: {}", self.policy)?;
+        if !self.null_filled.is_empty() {
+            write!(f, "; null-filled: {}", self.null_filled.join(", "))?;
+        }
+        if !self.widened.is_empty() {
+            let widened = self
+                .widened
+                .iter()
+                .map(|column| format!("{} -> {}", column.name, column.to))
+                .collect::<Vec<_>>();
+            write!(f, "; widened: {}", widened.join(", "))?;
+        }
+        if !self.dropped.is_empty() {
+            write!(f, "; dropped: {}", self.dropped.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if every file has the same columns, in the same order, with the same types.
+pub(crate) fn is_uniform(files: &[(String, Arc<Schema>)]) -> bool {
+    let Some((_, first)) = files.first() else {
+        return true;
+    };
+    files.iter().skip(1).all(|(_, schema)| {
+        schema.fields().len() == first.fields().len()
+            && schema
+                .fields()
+                .iter()
+                .zip(first.fields().iter())
+                .all(|(a, b)| a.name() == b.name() && a.data_type() == b.data_type())
+    })
+}
+
+/// Merges the schemas of `files` according to `policy`.
+///
+/// `files` pairs each path with its own schema; the first file determines column order.
+pub(crate) fn merge_schemas(
+    policy: SchemaMergePolicy,
+    source_type: &str,
+    files: &[(String, Arc<Schema>)],
+) -> Result<SchemaReport> {
+    let Some((first_path, first)) = files.first() else {
+        return Err(TermError::Configuration(
+            "At least one file is required to merge schemas".to_string(),
+        ));
+    };
+
+    if policy == SchemaMergePolicy::Strict {
+        for (path, schema) in files.iter().skip(1) {
+            let differences = differences(first, schema);
+            if !differences.is_empty() {
+                return Err(TermError::DataSource {
+                    source_type: source_type.to_string(),
+                    message: format!(
+                        "Schema of '{path}' does not match '{first_path}': {}. \
+                         Use SchemaMergePolicy::UnionNullable or SchemaMergePolicy::Intersection \
+                         to reconcile the files",
+                        differences.join("; ")
+                    ),
+                    source: None,
+                });
+            }
+        }
+    }
+
+    // Columns in first-seen order, with every type each file declared
+    let mut columns: Vec<MergedColumn> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for (path, schema) in files {
+        for field in schema.fields() {
+            let position = *positions.entry(field.name().clone()).or_insert_with(|| {
+                columns.push(MergedColumn::new(field.name()));
+                columns.len() - 1
+            });
+            columns[position].add(path, field);
+        }
+    }
+
+    let mut fields = Vec::new();
+    let mut null_filled = Vec::new();
+    let mut widened = Vec::new();
+    let mut dropped = Vec::new();
+    for column in columns {
+        let in_every_file = column.files == files.len();
+        if policy == SchemaMergePolicy::Intersection && !in_every_file {
+            dropped.push(column.name);
+            continue;
+        }
+
+        let data_type = match column.widened_type() {
+            Ok(data_type) => data_type,
+            Err(_) if policy == SchemaMergePolicy::Intersection => {
+                dropped.push(column.name);
+                continue;
+            }
+            Err((path, found, expected)) => {
+                return Err(TermError::DataSource {
+                    source_type: source_type.to_string(),
+                    message: format!(
+                        "Column '{}' is {found} in '{path}' but {expected} in earlier files, \
+                         and the types cannot be merged",
+                        column.name
+                    ),
+                    source: None,
+                });
+            }
+        };
+
+        let narrower: Vec<DataType> = column
+            .types
+            .iter()
+            .filter(|data_type_in_file| **data_type_in_file != data_type)
+            .cloned()
+            .collect();
+        if !narrower.is_empty() {
+            widened.push(WidenedColumn {
+                name: column.name.clone(),
+                from: narrower,
+                to: data_type.clone(),
+            });
+        }
+        if !in_every_file {
+            null_filled.push(column.name.clone());
+        }
+
+        fields.push(Field::new(
+            column.name,
+            data_type,
+            column.nullable || !in_every_file,
+        ));
+    }
+
+    if fields.is_empty() {
+        return Err(TermError::DataSource {
+            source_type: source_type.to_string(),
+            message: format!("The {} files have no columns in common", files.len()),
+            source: None,
+        });
+    }
+
+    let report = SchemaReport {
+        policy,
+        file_count: files.len(),
+        schema: Arc::new(Schema::new(fields)),
+        null_filled,
+        widened,
+        dropped,
+    };
+
+    if !report.dropped.is_empty() {
+        warn!(
+            source.type = %source_type,
+            source.files = files.len(),
+            dropped = ?report.dropped,
+            "Dropped columns not shared by every file"
+        );
+    }
+    if report.is_adjusted() {
+        info!(
+            source.type = %source_type,
+            source.files = files.len(),
+            "Reconciled file schemas ({report})"
+        );
+    }
+
+    Ok(report)
+}
+
+/// Aligns each file to `schema` and concatenates them.
+///
+/// Columns are cast to their merged type; columns a file lacks are read as null.
+pub(crate) fn union_files(frames: Vec<DataFrame>, schema: &Schema) -> Result<DataFrame> {
+    let mut aligned: Option<DataFrame> = None;
+    for frame in frames {
+        let projection = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let value = if frame
+                    .schema()
+                    .has_column_with_unqualified_name(field.name())
+                {
+                    Expr::Column(Column::new_unqualified(field.name()))
+                } else {
+                    lit(ScalarValue::try_from(field.data_type())?)
+                };
+                Ok(cast(value, field.data_type().clone()).alias(field.name()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let frame = frame.select(projection)?;
+        aligned = Some(match aligned {
+            Some(aligned) => aligned.union(frame)?,
+            None => frame,
+        });
+    }
+
+    aligned.ok_or_else(|| {
+        TermError::Configuration("At least one file is required to merge schemas".to_string())
+    })
+}
+
+/// A column as seen across all files.
+struct MergedColumn {
+    name: String,
+    /// Distinct types, in first-seen order, with the first file declaring each
+    types: Vec<DataType>,
+    paths: Vec<String>,
+    nullable: bool,
+    files: usize,
+}
+
+impl MergedColumn {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            types: Vec::new(),
+            paths: Vec::new(),
+            nullable: false,
+            files: 0,
+        }
+    }
+
+    fn add(&mut self, path: &str, field: &Field) {
+        if !self.types.contains(field.data_type()) {
+            self.types.push(field.data_type().clone());
+            self.paths.push(path.to_string());
+        }
+        self.nullable |= field.is_nullable();
+        self.files += 1;
+    }
+
+    /// Returns the type every file fits, or the first file whose type does not fit,
+    /// its type and the type of the earlier files.
+    fn widened_type(&self) -> std::result::Result<DataType, (&str, &DataType, DataType)> {
+        let mut data_type = self.types[0].clone();
+        for (found, path) in self.types.iter().zip(&self.paths).skip(1) {
+            data_type = widen(&data_type, found).ok_or((path.as_str(), found, data_type))?;
+        }
+        Ok(data_type)
+    }
+}
+
+/// Describes how `schema` differs from `expected`, as a list of human-readable differences.
+fn differences(expected: &Schema, schema: &Schema) -> Vec<String> {
+    let mut differences = Vec::new();
+    for field in expected.fields() {
+        match schema.field_with_name(field.name()) {
+            Ok(other) if other.data_type() != field.data_type() => differences.push(format!(
+                "column '{}' is {} instead of {}",
+                field.name(),
+                other.data_type(),
+                field.data_type()
+            )),
+            Ok(_) => {}
+            Err(_) => differences.push(format!("column '{}' is missing", field.name())),
+        }
+    }
+    for field in schema.fields() {
+        if expected.field_with_name(field.name()).is_err() {
+            differences.push(format!(
+                "unexpected column '{}' ({})",
+                field.name(),
+                field.data_type()
+            ));
+        }
+    }
+
+    let same_order = expected
+        .fields()
+        .iter()
+        .map(|field| field.name())
+        .eq(schema.fields().iter().map(|field| field.name()));
+    if differences.is_empty() && !same_order {
+        differences.push("columns are in a different order".to_string());
+    }
+    differences
+}
+
+/// Returns the narrowest type both `a` and `b` can be read as, if there is one.
+pub(super) fn widen(a: &DataType, b: &DataType) -> Option<DataType> {
+    if a == b {
+        return Some(a.clone());
+    }
+
+    match (a, b) {
+        (DataType::Null, other) | (other, DataType::Null) => Some(other.clone()),
+        _ if a.is_floating() && b.is_floating() => Some(wider(a, b)),
+        _ if a.is_numeric() && b.is_numeric() && (a.is_floating() || b.is_floating()) => {
+            (a.is_integer() || b.is_integer()).then_some(DataType::Float64)
+        }
+        _ if a.is_signed_integer() && b.is_signed_integer() => Some(wider(a, b)),
+        _ if a.is_unsigned_integer() && b.is_unsigned_integer() => Some(wider(a, b)),
+        _ if a.is_integer() && b.is_integer() => {
+            let (signed, unsigned) = if a.is_signed_integer() {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            if signed.primitive_width() > unsigned.primitive_width() {
+                Some(signed.clone())
+            } else if unsigned.primitive_width() < Some(8) {
+                Some(DataType::Int64)
+            } else {
+                None
+            }
+        }
+        (
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View,
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View,
+        ) => {
+            if matches!(a, DataType::LargeUtf8) || matches!(b, DataType::LargeUtf8) {
+                Some(DataType::LargeUtf8)
+            } else {
+                Some(DataType::Utf8View)
+            }
+        }
+        _ => None,
+    }
+}
+
+fn wider(a: &DataType, b: &DataType) -> DataType {
+    if b.primitive_width() > a.primitive_width() {
+        b.clone()
+    } else {
+        a.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, fields: Vec<(&str, DataType)>) -> (String, Arc<Schema>) {
+        let fields: Vec<Field> = fields
+            .into_iter()
+            .map(|(name, data_type)| Field::new(name, data_type, true))
+            .collect();
+        (path.to_string(), Arc::new(Schema::new(fields)))
+    }
+
+    fn files() -> Vec<(String, Arc<Schema>)> {
+        vec![
+            file(
+                "a.csv",
+                vec![("id", DataType::Int32), ("amount", DataType::Int32)],
+            ),
+            file(
+                "b.csv",
+                vec![
+                    ("id", DataType::Int32),
+                    ("amount", DataType::Int64),
+                    ("region", DataType::Utf8),
+                ],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_strict_names_mismatching_file() {
+        assert!(is_uniform(&files()[..1]));
+        assert!(!is_uniform(&files()));
+
+        let error = merge_schemas(SchemaMergePolicy::Strict, "csv", &files())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("'b.csv' does not match 'a.csv'"), "{error}");
+        assert!(error.contains("column 'amount' is Int64 instead of Int32"));
+        assert!(error.contains("unexpected column 'region' (Utf8)"));
+    }
+
+    #[test]
+    fn test_union_nullable_widens_and_fills() {
+        let report = merge_schemas(SchemaMergePolicy::UnionNullable, "csv", &files()).unwrap();
+
+        let names: Vec<&String> = report.schema.fields().iter().map(|f| f.name()).collect();
+        assert_eq!(names, ["id", "amount", "region"]);
+        assert_eq!(
+            report.schema.field_with_name("amount").unwrap().data_type(),
+            &DataType::Int64
+        );
+        assert_eq!(report.null_filled, ["region"]);
+        assert_eq!(
+            report.widened,
+            [WidenedColumn {
+                name: "amount".to_string(),
+                from: vec![DataType::Int32],
+                to: DataType::Int64,
+            }]
+        );
+        assert!(report.dropped.is_empty());
+        assert_eq!(
+            report.to_string(),
+            "schema merge: union_nullable; null-filled: region; widened: amount -> Int64"
+        );
+    }
+
+    #[test]
+    fn test_intersection_drops_columns() {
+        let mut files = files();
+        files.push(file(
+            "c.csv",
+            vec![
+                ("id", DataType::Utf8),
+                ("amount", DataType::Float64),
+                ("region", DataType::Utf8),
+            ],
+        ));
+
+        let report = merge_schemas(SchemaMergePolicy::Intersection, "csv", &files).unwrap();
+        let names: Vec<&String> = report.schema.fields().iter().map(|f| f.name()).collect();
+        assert_eq!(names, ["amount"]);
+        assert_eq!(report.schema.field(0).data_type(), &DataType::Float64);
+        assert_eq!(report.dropped, ["id", "region"]);
+        assert!(report.null_filled.is_empty());
+
+        let error = merge_schemas(SchemaMergePolicy::UnionNullable, "csv", &files)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("Column 'id' is Utf8 in 'c.csv' but Int32 in earlier files"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_widen() {
+        assert_eq!(
+            widen(&DataType::Int32, &DataType::Int64),
+            Some(DataType::Int64)
+        );
+        assert_eq!(
+            widen(&DataType::UInt32, &DataType::Int32),
+            Some(DataType::Int64)
+        );
+        assert_eq!(
+            widen(&DataType::Int64, &DataType::Float32),
+            Some(DataType::Float64)
+        );
+        assert_eq!(
+            widen(&DataType::Null, &DataType::Utf8),
+            Some(DataType::Utf8)
+        );
+        assert_eq!(widen(&DataType::UInt64, &DataType::Int64), None);
+        assert_eq!(widen(&DataType::Int64, &DataType::Utf8), None);
+    }
+}
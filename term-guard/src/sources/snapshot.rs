@@ -0,0 +1,49 @@
+//! Snapshot selection for versioned table formats.
+//!
+//! Table formats such as Delta Lake keep a log of commits, each producing a
+//! snapshot of the table: a schema and an exact list of data files. A
+//! [`SnapshotSelector`] picks the snapshot a source registers, so a suite can
+//! validate the version a report was built from instead of whatever files
+//! currently exist. [`TableSnapshot`] records which snapshot was registered.
+
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+/// Which snapshot of a versioned table to read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnapshotSelector {
+    /// The most recent snapshot
+    #[default]
+    Latest,
+    /// The snapshot with this version
+    Version(u64),
+    /// The last snapshot committed at or before this time
+    AsOf(DateTime<Utc>),
+}
+
+impl fmt::Display for SnapshotSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Latest => write!(f, "latest"),
+            Self::Version(version) => write!(f, "version {version}"),
+            Self::AsOf(timestamp) => write!(f, "as of {}", timestamp.to_rfc3339()),
+        }
+    }
+}
+
+/// The snapshot of a versioned table a source registered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableSnapshot {
+    /// The selector the snapshot was resolved from
+    pub selector: SnapshotSelector,
+    /// The version of the snapshot
+    pub version: u64,
+    /// The data files of the snapshot
+    pub files: Vec<String>,
+}
+
+impl fmt::Display for TableSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "version {}, {} files", self.version, self.files.len())
+    }
+}
@@ -0,0 +1,694 @@
+//! CSV and NDJSON stream source implementation.
+//!
+//! [`StreamSource`] reads records from any [`AsyncRead`], such as standard input
+//! in `curl ... | term-validate`. A stream has no file name to detect the format or
+//! compression from, so both are given explicitly through a [`StreamFormat`] built
+//! from the options of [`CsvSource`](super::CsvSource) and
+//! [`JsonSource`](super::JsonSource).
+//!
+//! The stream is read once, when the source is created, and decoded into Arrow
+//! record batches. Batches are kept in memory up to
+//! [`StreamOptions::max_memory_bytes`]; a larger stream is written to an Arrow IPC
+//! file in [`StreamOptions::spill_dir`] instead, which is removed when the last
+//! clone of the source is dropped. Either way the source registers a table that
+//! can be queried any number of times, like a file source.
+
+use super::json::JsonFormatType;
+use super::{CompressionType, CsvOptions, DataSource, JsonOptions};
+use crate::prelude::*;
+use arrow::datatypes::Schema;
+use arrow::error::ArrowError;
+use arrow::ipc::writer::FileWriter;
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use datafusion::datasource::file_format::arrow::ArrowFormat;
+use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
+use datafusion::datasource::listing::{
+    ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+};
+use datafusion::datasource::MemTable;
+use datafusion::prelude::*;
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::sync::mpsc;
+use tracing::{debug, instrument};
+
+/// Default for [`StreamOptions::max_memory_bytes`]: 256 MiB.
+pub const DEFAULT_STREAM_MEMORY_LIMIT: usize = 256 * 1024 * 1024;
+
+/// Size of the chunks read from the stream.
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Number of chunks read ahead of the decoder.
+const CHANNEL_CAPACITY: usize = 16;
+
+/// Format of the records in a stream.
+///
+/// Each format takes the options of the matching file source. Their `compression`
+/// must name the compression of the stream: [`CompressionType::Auto`] is rejected
+/// because there is no file extension to detect it from.
+#[derive(Debug, Clone)]
+pub enum StreamFormat {
+    /// CSV records; the multi-file options `schema_merge`, `exclude` and
+    /// `max_files` are ignored
+    Csv(CsvOptions),
+    /// Newline-delimited JSON records; the multi-file options `exclude` and
+    /// `max_files` are ignored
+    Json(JsonOptions),
+}
+
+impl StreamFormat {
+    fn compression(&self) -> CompressionType {
+        match self {
+            StreamFormat::Csv(options) => options.compression,
+            StreamFormat::Json(options) => options.compression,
+        }
+    }
+
+    fn schema(&self) -> Option<&Arc<Schema>> {
+        match self {
+            StreamFormat::Csv(options) => options.schema.as_ref(),
+            StreamFormat::Json(options) => options.schema.as_ref(),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            StreamFormat::Csv(_) => "CSV",
+            StreamFormat::Json(_) => "NDJSON",
+        }
+    }
+}
+
+/// Options for buffering a stream.
+#[derive(Debug, Clone)]
+pub struct StreamOptions {
+    /// Memory the decoded record batches may use before they are spilled to disk
+    /// (default: [`DEFAULT_STREAM_MEMORY_LIMIT`])
+    pub max_memory_bytes: usize,
+    /// Directory of the spill file (default: the system temporary directory)
+    pub spill_dir: Option<PathBuf>,
+    /// Maximum number of records per record batch (default: 8192)
+    pub batch_size: usize,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            max_memory_bytes: DEFAULT_STREAM_MEMORY_LIMIT,
+            spill_dir: None,
+            batch_size: 8192,
+        }
+    }
+}
+
+/// A data source reading CSV or NDJSON records from an asynchronous byte stream.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use term_guard::sources::{
+///     CompressionType, DataSource, JsonOptions, StreamFormat, StreamSource,
+/// };
+///
+/// # async fn example() -> Result<()> {
+/// // gunzip and validate `curl https://example.com/events.ndjson.gz | validate`
+/// let source = StreamSource::stdin(StreamFormat::Json(JsonOptions {
+///     compression: CompressionType::Gzip,
+///     ..Default::default()
+/// }))
+/// .await?;
+///
+/// let ctx = SessionContext::new();
+/// source.register(&ctx, "events").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamSource {
+    format: StreamFormat,
+    schema: Arc<Schema>,
+    data: StreamData,
+    num_rows: usize,
+}
+
+/// Record batches buffered in memory or spilled to an Arrow IPC file.
+#[derive(Debug, Clone)]
+pub(super) enum StreamData {
+    Memory(Vec<RecordBatch>),
+    Spilled(Arc<SpillFile>),
+}
+
+impl StreamData {
+    /// Registers the batches as a table.
+    ///
+    /// `source_type` names the source in errors.
+    pub(super) fn register(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        schema: &Arc<Schema>,
+        source_type: &str,
+    ) -> Result<()> {
+        match self {
+            StreamData::Memory(batches) => {
+                let table = MemTable::try_new(schema.clone(), vec![batches.clone()])?;
+                ctx.register_table(table_name, Arc::new(table))?;
+            }
+            StreamData::Spilled(file) => {
+                let path = file.path.to_str().ok_or_else(|| TermError::DataSource {
+                    source_type: source_type.to_string(),
+                    message: format!("Spill file path is not valid UTF-8: {:?}", file.path),
+                    source: None,
+                })?;
+                let table_path = ListingTableUrl::parse(path)?;
+                let listing_options =
+                    ListingOptions::new(Arc::new(ArrowFormat)).with_file_extension(".arrow");
+                let config = ListingTableConfig::new(table_path)
+                    .with_listing_options(listing_options)
+                    .with_schema(schema.clone());
+                let table = ListingTable::try_new(config)?;
+                ctx.register_table(table_name, Arc::new(table))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Describes where the batches are kept, such as `"in memory"`.
+    pub(super) fn storage(&self) -> String {
+        match self {
+            StreamData::Memory(_) => "in memory".to_string(),
+            StreamData::Spilled(file) => format!("spilled to {}", file.path.display()),
+        }
+    }
+
+    /// Returns whether the batches were spilled to disk.
+    pub(super) fn is_spilled(&self) -> bool {
+        matches!(self, StreamData::Spilled(_))
+    }
+}
+
+/// A spill file, removed when dropped.
+#[derive(Debug)]
+pub(super) struct SpillFile {
+    path: PathBuf,
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+impl StreamSource {
+    /// Reads a stream with the default [`StreamOptions`].
+    pub async fn read<R>(reader: R, format: StreamFormat) -> Result<Self>
+    where
+        R: AsyncRead + Send,
+    {
+        Self::read_with_options(reader, format, StreamOptions::default()).await
+    }
+
+    /// Reads a stream to its end, decoding it into record batches.
+    ///
+    /// The schema is taken from the format options if set, and otherwise inferred
+    /// from their `schema_infer_max_records` first records.
+    #[instrument(skip(reader, format, options), fields(format = format.name()))]
+    pub async fn read_with_options<R>(
+        reader: R,
+        format: StreamFormat,
+        options: StreamOptions,
+    ) -> Result<Self>
+    where
+        R: AsyncRead + Send,
+    {
+        let compression = file_compression_type(format.compression())?;
+        if let StreamFormat::Json(json_options) = &format {
+            if json_options.format == JsonFormatType::Json {
+                return Err(TermError::NotSupported(
+                    "Regular JSON format is not supported for streams. Please use NDJSON format."
+                        .to_string(),
+                ));
+            }
+        }
+        if options.batch_size == 0 {
+            return Err(TermError::Configuration(
+                "Stream batch size must be greater than 0".to_string(),
+            ));
+        }
+
+        let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+        let decoder = {
+            let format = format.clone();
+            tokio::task::spawn_blocking(move || {
+                decode(ChannelReader::new(receiver), compression, &format, &options)
+            })
+        };
+
+        let mut reader = Box::pin(reader);
+        let mut buffer = vec![0; READ_CHUNK_SIZE];
+        loop {
+            let chunk = match reader.read(&mut buffer).await {
+                Ok(0) => break,
+                Ok(n) => Ok(buffer[..n].to_vec()),
+                Err(e) => Err(e),
+            };
+            let failed = chunk.is_err();
+            // A closed channel means the decoder stopped early; it reports why below
+            if sender.send(chunk).await.is_err() || failed {
+                break;
+            }
+        }
+        drop(sender);
+
+        let decoded = decoder
+            .await
+            .map_err(|e| TermError::Internal(format!("Stream decoder failed: {e}")))??;
+        debug!(
+            rows = decoded.num_rows,
+            spilled = decoded.data.is_spilled(),
+            "Read stream"
+        );
+
+        Ok(Self {
+            format,
+            schema: decoded.schema,
+            data: decoded.data,
+            num_rows: decoded.num_rows,
+        })
+    }
+
+    /// Reads standard input with the default [`StreamOptions`].
+    pub async fn stdin(format: StreamFormat) -> Result<Self> {
+        Self::read(tokio::io::stdin(), format).await
+    }
+
+    /// Returns the number of records read from the stream.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns whether the records exceeded the memory limit and were spilled to disk.
+    pub fn is_spilled(&self) -> bool {
+        self.data.is_spilled()
+    }
+}
+
+#[async_trait]
+impl DataSource for StreamSource {
+    #[instrument(skip(self, ctx, telemetry), fields(table_name = %table_name, source_type = "stream"))]
+    async fn register_with_telemetry(
+        &self,
+        ctx: &SessionContext,
+        table_name: &str,
+        telemetry: Option<&Arc<TermTelemetry>>,
+    ) -> Result<()> {
+        let mut _datasource_span = if let Some(tel) = telemetry {
+            tel.start_datasource_span("stream", table_name)
+        } else {
+            TermSpan::noop()
+        };
+
+        self.data.register(ctx, table_name, &self.schema, "stream")
+    }
+
+    fn schema(&self) -> Option<&Arc<Schema>> {
+        Some(&self.schema)
+    }
+
+    fn description(&self) -> String {
+        let compression = match self.format.compression() {
+            CompressionType::Gzip => ", gzip",
+            CompressionType::Zstd => ", zstd",
+            CompressionType::Bzip2 => ", bzip2",
+            _ => "",
+        };
+        format!(
+            "{} stream ({} rows{compression}, {})",
+            self.format.name(),
+            self.num_rows,
+            self.data.storage()
+        )
+    }
+}
+
+/// Maps the compression of a stream to the decoder DataFusion uses for files.
+fn file_compression_type(compression: CompressionType) -> Result<FileCompressionType> {
+    match compression {
+        CompressionType::None => Ok(FileCompressionType::UNCOMPRESSED),
+        CompressionType::Gzip => Ok(FileCompressionType::GZIP),
+        CompressionType::Zstd => Ok(FileCompressionType::ZSTD),
+        CompressionType::Bzip2 => Ok(FileCompressionType::BZIP2),
+        CompressionType::Auto => Err(TermError::Configuration(
+            "Stream compression cannot be detected automatically; set it to None, Gzip, Zstd or Bzip2"
+                .to_string(),
+        )),
+        CompressionType::Lz4 | CompressionType::Snappy => Err(TermError::NotSupported(format!(
+            "{compression:?} compression is not supported for streams"
+        ))),
+    }
+}
+
+/// Record batches collected by a [`BatchBuffer`].
+pub(super) struct Decoded {
+    pub(super) schema: Arc<Schema>,
+    pub(super) data: StreamData,
+    pub(super) num_rows: usize,
+}
+
+/// Decodes the stream on a blocking thread.
+fn decode(
+    reader: ChannelReader,
+    compression: FileCompressionType,
+    format: &StreamFormat,
+    options: &StreamOptions,
+) -> Result<Decoded> {
+    let mut reader = RecordingReader {
+        inner: compression.convert_read(reader)?,
+        recorded: Vec::new(),
+    };
+    let schema = match format.schema() {
+        Some(schema) => schema.clone(),
+        None => Arc::new(infer_schema(&mut reader, format)?),
+    };
+    // Replay the bytes read during schema inference
+    // The readers are blocking, so this is `std::io::Read::chain`, not the tokio one
+    let reader = Read::chain(Cursor::new(reader.recorded), reader.inner);
+
+    let batches: Box<dyn Iterator<Item = std::result::Result<RecordBatch, ArrowError>>> =
+        match format {
+            StreamFormat::Csv(csv_options) => {
+                let mut builder = arrow::csv::ReaderBuilder::new(schema.clone())
+                    .with_header(csv_options.has_header)
+                    .with_delimiter(csv_options.delimiter)
+                    .with_quote(csv_options.quote)
+                    .with_batch_size(options.batch_size);
+                if let Some(escape) = csv_options.escape {
+                    builder = builder.with_escape(escape);
+                }
+                if let Some(comment) = csv_options.comment {
+                    builder = builder.with_comment(comment);
+                }
+                Box::new(builder.build(reader)?)
+            }
+            StreamFormat::Json(_) => Box::new(
+                arrow::json::ReaderBuilder::new(schema.clone())
+                    .with_batch_size(options.batch_size)
+                    .build(BufReader::new(reader))?,
+            ),
+        };
+
+    let mut buffer = BatchBuffer::new(schema.clone(), options);
+    for batch in batches {
+        buffer.push(batch?)?;
+    }
+    buffer.finish()
+}
+
+fn infer_schema(reader: &mut impl Read, format: &StreamFormat) -> Result<Schema> {
+    let schema = match format {
+        StreamFormat::Csv(csv_options) => {
+            let mut csv_format = arrow::csv::reader::Format::default()
+                .with_header(csv_options.has_header)
+                .with_delimiter(csv_options.delimiter)
+                .with_quote(csv_options.quote);
+            if let Some(escape) = csv_options.escape {
+                csv_format = csv_format.with_escape(escape);
+            }
+            if let Some(comment) = csv_options.comment {
+                csv_format = csv_format.with_comment(comment);
+            }
+            csv_format
+                .infer_schema(reader, Some(csv_options.schema_infer_max_records))?
+                .0
+        }
+        StreamFormat::Json(json_options) => {
+            arrow::json::reader::infer_json_schema(
+                BufReader::new(reader),
+                Some(json_options.schema_infer_max_records),
+            )?
+            .0
+        }
+    };
+    Ok(schema)
+}
+
+/// Collects record batches in memory, moving them to a spill file once they
+/// exceed the memory limit.
+pub(super) struct BatchBuffer<'a> {
+    schema: Arc<Schema>,
+    options: &'a StreamOptions,
+    batches: Vec<RecordBatch>,
+    memory: usize,
+    num_rows: usize,
+    spill: Option<(FileWriter<File>, SpillFile)>,
+}
+
+impl<'a> BatchBuffer<'a> {
+    pub(super) fn new(schema: Arc<Schema>, options: &'a StreamOptions) -> Self {
+        Self {
+            schema,
+            options,
+            batches: Vec::new(),
+            memory: 0,
+            num_rows: 0,
+            spill: None,
+        }
+    }
+
+    pub(super) fn push(&mut self, batch: RecordBatch) -> Result<()> {
+        self.num_rows += batch.num_rows();
+        if let Some((writer, _)) = &mut self.spill {
+            writer.write(&batch)?;
+            return Ok(());
+        }
+
+        self.memory += batch.get_array_memory_size();
+        self.batches.push(batch);
+        if self.memory > self.options.max_memory_bytes {
+            let dir = self
+                .options
+                .spill_dir
+                .clone()
+                .unwrap_or_else(std::env::temp_dir);
+            let spill_file = SpillFile {
+                path: dir.join(format!("term-stream-{}.arrow", uuid::Uuid::new_v4())),
+            };
+            debug!(
+                memory = self.memory,
+                path = %spill_file.path.display(),
+                "Spilling stream to disk"
+            );
+            let mut writer = FileWriter::try_new(File::create(&spill_file.path)?, &self.schema)?;
+            for batch in self.batches.drain(..) {
+                writer.write(&batch)?;
+            }
+            self.memory = 0;
+            self.spill = Some((writer, spill_file));
+        }
+        Ok(())
+    }
+
+    pub(super) fn finish(self) -> Result<Decoded> {
+        let data = match self.spill {
+            Some((mut writer, spill_file)) => {
+                writer.finish()?;
+                StreamData::Spilled(Arc::new(spill_file))
+            }
+            None => StreamData::Memory(self.batches),
+        };
+        Ok(Decoded {
+            schema: self.schema,
+            data,
+            num_rows: self.num_rows,
+        })
+    }
+}
+
+/// A blocking reader over the chunks sent by the async side of the stream.
+struct ChannelReader {
+    receiver: mpsc::Receiver<std::io::Result<Vec<u8>>>,
+    chunk: Vec<u8>,
+    position: usize,
+}
+
+impl ChannelReader {
+    fn new(receiver: mpsc::Receiver<std::io::Result<Vec<u8>>>) -> Self {
+        Self {
+            receiver,
+            chunk: Vec::new(),
+            position: 0,
+        }
+    }
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        while self.position == self.chunk.len() {
+            match self.receiver.blocking_recv() {
+                Some(chunk) => {
+                    self.chunk = chunk?;
+                    self.position = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.chunk.len() - self.position);
+        buf[..n].copy_from_slice(&self.chunk[self.position..self.position + n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// A reader that keeps a copy of the bytes read through it.
+struct RecordingReader<R> {
+    inner: R,
+    recorded: Vec<u8>,
+}
+
+impl<R: Read> Read for RecordingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.recorded.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::{DataType, Field};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn ndjson_rows(count: usize) -> String {
+        (0..count)
+            .map(|i| format!("{{\"id\": {i}, \"name\": \"user_{i}\"}}\n"))
+            .collect()
+    }
+
+    async fn count_rows(source: &StreamSource) -> i64 {
+        let ctx = SessionContext::new();
+        source.register(&ctx, "stream").await.unwrap();
+        let batches = ctx
+            .sql("SELECT COUNT(*) FROM stream")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<arrow::array::Int64Array>()
+            .unwrap()
+            .value(0)
+    }
+
+    #[tokio::test]
+    async fn test_gzip_ndjson_stream() {
+        let data = gzip(ndjson_rows(100).as_bytes());
+        let format = StreamFormat::Json(JsonOptions {
+            compression: CompressionType::Gzip,
+            ..Default::default()
+        });
+
+        let source = StreamSource::read(data.as_slice(), format).await.unwrap();
+
+        assert_eq!(source.num_rows(), 100);
+        assert!(!source.is_spilled());
+        let schema = source.schema().unwrap();
+        assert_eq!(
+            schema.field_with_name("id").unwrap().data_type(),
+            &DataType::Int64
+        );
+        assert!(source
+            .description()
+            .contains("NDJSON stream (100 rows, gzip"));
+        assert_eq!(count_rows(&source).await, 100);
+    }
+
+    #[tokio::test]
+    async fn test_stream_spills_beyond_memory_limit() {
+        let spill_dir = tempfile::tempdir().unwrap();
+        let data = gzip(ndjson_rows(1000).as_bytes());
+        let format = StreamFormat::Json(JsonOptions {
+            compression: CompressionType::Gzip,
+            ..Default::default()
+        });
+        let options = StreamOptions {
+            max_memory_bytes: 1024,
+            spill_dir: Some(spill_dir.path().to_path_buf()),
+            batch_size: 100,
+        };
+
+        let source = StreamSource::read_with_options(data.as_slice(), format, options)
+            .await
+            .unwrap();
+
+        assert!(source.is_spilled());
+        assert_eq!(std::fs::read_dir(spill_dir.path()).unwrap().count(), 1);
+        assert_eq!(count_rows(&source).await, 1000);
+
+        drop(source);
+        assert_eq!(std::fs::read_dir(spill_dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_csv_stream_with_explicit_schema() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Utf8, false),
+            Field::new("amount", DataType::Float64, true),
+        ]));
+        let format = StreamFormat::Csv(CsvOptions {
+            schema: Some(schema.clone()),
+            compression: CompressionType::None,
+            ..Default::default()
+        });
+
+        let data = b"id,amount\n1,10.5\n2,\n3,7.25\n";
+        let source = StreamSource::read(&data[..], format).await.unwrap();
+
+        assert_eq!(source.schema(), Some(&schema));
+        assert_eq!(source.num_rows(), 3);
+        assert_eq!(count_rows(&source).await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_stream_requires_explicit_compression() {
+        let format = StreamFormat::Json(JsonOptions::default());
+        let result = StreamSource::read(&b""[..], format).await;
+        assert!(matches!(result, Err(TermError::Configuration(_))));
+
+        let format = StreamFormat::Json(JsonOptions {
+            format: JsonFormatType::Json,
+            compression: CompressionType::None,
+            ..Default::default()
+        });
+        let result = StreamSource::read(&b"[]"[..], format).await;
+        assert!(matches!(result, Err(TermError::NotSupported(_))));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_stream_reports_error() {
+        let format = StreamFormat::Json(JsonOptions {
+            compression: CompressionType::Gzip,
+            ..Default::default()
+        });
+        let result = StreamSource::read(&b"not gzip"[..], format).await;
+        assert!(result.is_err());
+    }
+}
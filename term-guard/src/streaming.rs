@@ -0,0 +1,532 @@
+//! Micro-batch validation for streaming sources.
+//!
+//! This module validates an unbounded stream of Arrow [`RecordBatch`]es by
+//! buffering it into micro-batches, registering each micro-batch as an in-memory
+//! table and running a [`ValidationSuite`] against it. Bridging from Kafka,
+//! Redpanda or any other broker into a `Stream` of batches is left to the caller.
+//!
+//! Micro-batches are closed when they reach a row count or when a time window
+//! elapses, whichever comes first. Each emitted [`WindowedValidationResult`] is
+//! tagged with the bounds of the window it covers.
+//!
+//! Constraints are evaluated per window and have no memory of earlier windows.
+//! The one exception is [`CrossWindowUniqueness`], which keeps a bounded bloom
+//! filter of keys seen so far to catch duplicates spanning windows.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use term_guard::core::{Check, ValidationSuite};
+//! use term_guard::core::builder_extensions::CompletenessOptions;
+//! use term_guard::streaming::MicroBatchValidator;
+//! use futures::StreamExt;
+//! use std::time::Duration;
+//!
+//! # async fn example(
+//! #     batches: impl futures::Stream<Item = term_guard::prelude::Result<arrow::record_batch::RecordBatch>> + Send + Unpin + 'static,
+//! # ) -> term_guard::prelude::Result<()> {
+//! let suite = ValidationSuite::builder("events")
+//!     .table_name("events")
+//!     .check(
+//!         Check::builder("ids")
+//!             .completeness("event_id", CompletenessOptions::full().into_constraint_options())
+//!             .build(),
+//!     )
+//!     .build();
+//!
+//! let mut results = MicroBatchValidator::new(suite)
+//!     .max_rows(10_000)
+//!     .max_window(Duration::from_secs(60))
+//!     .with_cross_window_uniqueness(vec!["event_id"], 1_000_000, 0.01)?
+//!     .validate(batches);
+//!
+//! while let Some(windowed) = results.next().await {
+//!     let windowed = windowed?;
+//!     println!("window {}: {}", windowed.window.index, windowed.result.is_success());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::{
+    CheckStatus, ConstraintOutcome, ConstraintStatus, Level, ValidationIssue, ValidationResult,
+    ValidationSuite,
+};
+use crate::prelude::*;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::array_value_to_string;
+use chrono::{DateTime, Utc};
+use datafusion::datasource::MemTable;
+use datafusion::prelude::SessionContext;
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{debug, instrument};
+
+/// The bounds of a micro-batch window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowBounds {
+    /// Zero-based index of the window in the stream
+    pub index: u64,
+    /// Wall-clock time at which the first batch of the window was received
+    pub start: DateTime<Utc>,
+    /// Wall-clock time at which the window was closed
+    pub end: DateTime<Utc>,
+    /// Number of record batches in the window
+    pub batches: usize,
+    /// Number of rows in the window
+    pub rows: usize,
+}
+
+/// The validation result of a single micro-batch window.
+#[derive(Debug, Clone)]
+pub struct WindowedValidationResult {
+    /// The window that was validated
+    pub window: WindowBounds,
+    /// The result of running the suite against the window
+    pub result: ValidationResult,
+}
+
+/// A bloom filter with a fixed bit budget.
+#[derive(Debug, Clone)]
+struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
+}
+
+impl BloomFilter {
+    /// Sizes a filter for `expected_items` at the given false positive rate.
+    fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        let n = expected_items.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits = (-(n * false_positive_rate.ln()) / (ln2 * ln2))
+            .ceil()
+            .max(64.0) as u64;
+        let num_hashes = ((num_bits as f64 / n) * ln2).round().max(1.0) as u32;
+        Self {
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            num_bits,
+            num_hashes,
+        }
+    }
+
+    fn positions(&self, key: &str) -> impl Iterator<Item = u64> + '_ {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let h1 = hasher.finish();
+        let mut hasher = DefaultHasher::new();
+        (key, 0x9e37_79b9_7f4a_7c15_u64).hash(&mut hasher);
+        let h2 = hasher.finish() | 1;
+        (0..self.num_hashes as u64)
+            .map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % self.num_bits)
+    }
+
+    /// Inserts a key and returns true if it was possibly present already.
+    fn check_and_insert(&mut self, key: &str) -> bool {
+        let positions: Vec<u64> = self.positions(key).collect();
+        let mut present = true;
+        for pos in positions {
+            let (word, bit) = ((pos / 64) as usize, pos % 64);
+            if self.bits[word] & (1 << bit) == 0 {
+                present = false;
+                self.bits[word] |= 1 << bit;
+            }
+        }
+        present
+    }
+}
+
+/// Detects duplicate keys across micro-batch windows.
+///
+/// Keys are tracked in a bloom filter sized for `expected_keys` at the configured
+/// false positive rate, so memory stays bounded regardless of stream length. A
+/// reported duplicate is a true duplicate with probability `1 - false_positive_rate`
+/// as long as fewer than `expected_keys` distinct keys have been seen; beyond that
+/// the false positive rate grows. Duplicates within a single window are left to the
+/// suite's own uniqueness constraints.
+#[derive(Debug, Clone)]
+pub struct CrossWindowUniqueness {
+    columns: Vec<String>,
+    false_positive_rate: f64,
+    filter: BloomFilter,
+}
+
+impl CrossWindowUniqueness {
+    /// Creates a tracker for the given key columns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no columns are given or the false positive rate is
+    /// not in the open interval (0, 1).
+    pub fn new<I, S>(columns: I, expected_keys: usize, false_positive_rate: f64) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let columns: Vec<String> = columns.into_iter().map(Into::into).collect();
+        if columns.is_empty() {
+            return Err(TermError::Configuration(
+                "Cross-window uniqueness requires at least one key column".to_string(),
+            ));
+        }
+        if !(false_positive_rate > 0.0 && false_positive_rate < 1.0) {
+            return Err(TermError::Configuration(format!(
+                "False positive rate must be between 0 and 1 (exclusive), got {false_positive_rate}"
+            )));
+        }
+        Ok(Self {
+            columns,
+            false_positive_rate,
+            filter: BloomFilter::new(expected_keys, false_positive_rate),
+        })
+    }
+
+    /// Returns the configured false positive rate.
+    pub fn false_positive_rate(&self) -> f64 {
+        self.false_positive_rate
+    }
+
+    /// Records the keys of a window and returns how many were seen in earlier windows.
+    fn observe(&mut self, batches: &[RecordBatch]) -> Result<usize> {
+        let mut window_keys = std::collections::HashSet::new();
+        let mut duplicates = 0;
+        for batch in batches {
+            let indices = self
+                .columns
+                .iter()
+                .map(|c| {
+                    batch
+                        .schema()
+                        .index_of(c)
+                        .map_err(|_| TermError::ColumnNotFound { column: c.clone() })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            for row in 0..batch.num_rows() {
+                let mut parts = Vec::with_capacity(indices.len());
+                for &i in &indices {
+                    parts.push(array_value_to_string(batch.column(i), row)?);
+                }
+                let key = parts.join("\u{1f}");
+                if window_keys.insert(key.clone()) && self.filter.check_and_insert(&key) {
+                    duplicates += 1;
+                }
+            }
+        }
+        Ok(duplicates)
+    }
+}
+
+/// Validates a stream of record batches in micro-batches.
+#[derive(Debug)]
+pub struct MicroBatchValidator {
+    suite: Arc<ValidationSuite>,
+    max_rows: usize,
+    max_window: Option<Duration>,
+    uniqueness: Option<CrossWindowUniqueness>,
+}
+
+impl MicroBatchValidator {
+    /// Creates a validator that runs `suite` against every micro-batch.
+    ///
+    /// Each micro-batch is registered under the suite's table name. By default
+    /// windows close every 10,000 rows and there is no time limit.
+    pub fn new(suite: ValidationSuite) -> Self {
+        Self {
+            suite: Arc::new(suite),
+            max_rows: 10_000,
+            max_window: None,
+            uniqueness: None,
+        }
+    }
+
+    /// Closes a window once it holds at least this many rows.
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows.max(1);
+        self
+    }
+
+    /// Closes a window once this much time has passed since its first batch.
+    pub fn max_window(mut self, window: Duration) -> Self {
+        self.max_window = Some(window);
+        self
+    }
+
+    /// Tracks the given key columns across windows to detect cross-window duplicates.
+    ///
+    /// Duplicates are reported as an error-level issue on the window where they appear.
+    /// See [`CrossWindowUniqueness`] for the false positive semantics.
+    pub fn with_cross_window_uniqueness<I, S>(
+        mut self,
+        columns: I,
+        expected_keys: usize,
+        false_positive_rate: f64,
+    ) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.uniqueness = Some(CrossWindowUniqueness::new(
+            columns,
+            expected_keys,
+            false_positive_rate,
+        )?);
+        Ok(self)
+    }
+
+    /// Consumes the input stream and yields one result per micro-batch window.
+    ///
+    /// An error from the input stream is forwarded and ends the output stream.
+    pub fn validate<S>(self, input: S) -> impl Stream<Item = Result<WindowedValidationResult>>
+    where
+        S: Stream<Item = Result<RecordBatch>> + Send + Unpin + 'static,
+    {
+        let state = StreamState {
+            validator: self,
+            input,
+            index: 0,
+            done: false,
+        };
+        stream::unfold(state, |mut state| async move {
+            if state.done {
+                return None;
+            }
+            match state.next_window().await {
+                Ok(Some((bounds, batches))) => {
+                    let result = state.validator.validate_window(bounds, batches).await;
+                    if result.is_err() {
+                        state.done = true;
+                    }
+                    Some((result, state))
+                }
+                Ok(None) => None,
+                Err(e) => {
+                    state.done = true;
+                    Some((Err(e), state))
+                }
+            }
+        })
+    }
+
+    #[instrument(skip(self, batches), fields(window.index = window.index, window.rows = window.rows))]
+    async fn validate_window(
+        &mut self,
+        window: WindowBounds,
+        batches: Vec<RecordBatch>,
+    ) -> Result<WindowedValidationResult> {
+        let ctx = SessionContext::new();
+        let schema = batches[0].schema();
+        let table = MemTable::try_new(schema, vec![batches.clone()])?;
+        ctx.register_table(self.suite.table_name(), Arc::new(table))?;
+
+        let mut result = self.suite.run(&ctx).await?;
+
+        if let Some(uniqueness) = &mut self.uniqueness {
+            let duplicates = uniqueness.observe(&batches)?;
+            if duplicates > 0 {
+                debug!(duplicates, "Cross-window duplicate keys detected");
+                let issue = ValidationIssue {
+                    check_name: "cross_window_uniqueness".to_string(),
+                    constraint_name: "cross_window_uniqueness".to_string(),
+                    level: Level::Error,
+                    message: format!(
+                        "{duplicates} key(s) in columns [{}] were already seen in earlier windows (false positive rate {})",
+                        uniqueness.columns.join(", "),
+                        uniqueness.false_positive_rate
+                    ),
+                    metric: Some(duplicates as f64),
+                    cached: false,
+                    known_issue: None,
+                    check_id: None,
+                    constraint_id: None,
+                    impacts: self.suite.lineage().map_or_else(Vec::new, |lineage| {
+                        lineage.impacts(self.suite.table_name(), &uniqueness.columns)
+                    }),
+                };
+                let mut report = result.report().clone();
+                report.outcomes.push(ConstraintOutcome {
+                    check_name: issue.check_name.clone(),
+                    constraint_name: issue.constraint_name.clone(),
+                    level: issue.level,
+                    status: ConstraintStatus::Failure,
+                    waived: false,
+                    metric: issue.metric,
+                    threshold: None,
+                    dimension: None,
+                    constraint_id: None,
+                });
+                report.metrics.record_check(CheckStatus::Failed);
+                report.add_issue(issue);
+                result = ValidationResult::from_report(report);
+            }
+        }
+
+        Ok(WindowedValidationResult { window, result })
+    }
+}
+
+struct StreamState<S> {
+    validator: MicroBatchValidator,
+    input: S,
+    index: u64,
+    done: bool,
+}
+
+impl<S> StreamState<S>
+where
+    S: Stream<Item = Result<RecordBatch>> + Send + Unpin,
+{
+    /// Buffers the next window, returning `None` once the input is exhausted.
+    async fn next_window(&mut self) -> Result<Option<(WindowBounds, Vec<RecordBatch>)>> {
+        let mut batches = Vec::new();
+        let mut rows = 0;
+        let mut start = None;
+        let mut deadline = None;
+
+        while rows < self.validator.max_rows {
+            let next = match deadline {
+                Some(deadline) => {
+                    match tokio::time::timeout_at(deadline, self.input.next()).await {
+                        Ok(next) => next,
+                        Err(_) => break,
+                    }
+                }
+                None => self.input.next().await,
+            };
+
+            match next {
+                Some(Ok(batch)) => {
+                    if start.is_none() {
+                        start = Some(Utc::now());
+                        deadline = self.validator.max_window.map(|w| Instant::now() + w);
+                    }
+                    if batch.num_rows() == 0 {
+                        continue;
+                    }
+                    rows += batch.num_rows();
+                    batches.push(batch);
+                }
+                Some(Err(e)) => return Err(e),
+                None => {
+                    self.done = true;
+                    break;
+                }
+            }
+        }
+
+        if batches.is_empty() {
+            return Ok(None);
+        }
+
+        let bounds = WindowBounds {
+            index: self.index,
+            start: start.unwrap_or_else(Utc::now),
+            end: Utc::now(),
+            batches: batches.len(),
+            rows,
+        };
+        self.index += 1;
+        Ok(Some((bounds, batches)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::Assertion;
+    use crate::core::Check;
+    use arrow::array::Int64Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn batch(ids: Vec<i64>) -> Result<RecordBatch> {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        Ok(RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int64Array::from(ids))],
+        )?)
+    }
+
+    fn suite() -> ValidationSuite {
+        ValidationSuite::builder("stream")
+            .check(
+                Check::builder("size")
+                    .has_size(Assertion::GreaterThan(0.0))
+                    .build(),
+            )
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_windows_by_row_count() {
+        let input = stream::iter(vec![
+            batch(vec![1, 2]),
+            batch(vec![3, 4]),
+            batch(vec![5, 6]),
+        ]);
+        let results: Vec<_> = MicroBatchValidator::new(suite())
+            .max_rows(4)
+            .validate(input)
+            .collect()
+            .await;
+
+        assert_eq!(results.len(), 2);
+        let first = results[0].as_ref().unwrap();
+        assert_eq!(first.window.index, 0);
+        assert_eq!(first.window.rows, 4);
+        assert!(first.result.is_success());
+        let second = results[1].as_ref().unwrap();
+        assert_eq!(second.window.index, 1);
+        assert_eq!(second.window.rows, 2);
+    }
+
+    #[tokio::test]
+    async fn test_cross_window_duplicates() {
+        let input = stream::iter(vec![batch(vec![1, 2, 3]), batch(vec![3, 4, 5])]);
+        let results: Vec<_> = MicroBatchValidator::new(suite())
+            .max_rows(3)
+            .with_cross_window_uniqueness(vec!["id"], 1_000, 0.001)
+            .unwrap()
+            .validate(input)
+            .collect()
+            .await;
+
+        assert!(results[0].as_ref().unwrap().result.is_success());
+        let second = &results[1].as_ref().unwrap().result;
+        assert!(second.is_failure());
+        assert_eq!(second.report().issues[0].metric, Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_input_error_ends_stream() {
+        let input = stream::iter(vec![
+            batch(vec![1]),
+            Err(TermError::Internal("broker disconnected".to_string())),
+            batch(vec![2]),
+        ]);
+        let results: Vec<_> = MicroBatchValidator::new(suite())
+            .validate(input)
+            .collect()
+            .await;
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    #[test]
+    fn test_bloom_filter() {
+        let mut filter = BloomFilter::new(100, 0.01);
+        assert!(!filter.check_and_insert("a"));
+        assert!(filter.check_and_insert("a"));
+        assert!(!filter.check_and_insert("b"));
+    }
+
+    #[test]
+    fn test_invalid_false_positive_rate() {
+        assert!(CrossWindowUniqueness::new(vec!["id"], 10, 0.0).is_err());
+        assert!(CrossWindowUniqueness::new(Vec::<String>::new(), 10, 0.1).is_err());
+    }
+}
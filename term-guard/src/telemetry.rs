@@ -38,6 +38,17 @@
 //! - Active validations (tracked internally)
 //! - `data.validation.memory` - Memory usage of validation process (Linux only)
 //!
+//! # Export Failures
+//!
+//! Ending a span may export it synchronously, for example with a simple span
+//! processor, which stalls for as long as the exporter's endpoint takes to time
+//! out. Term therefore ends spans on a background export thread, so validation
+//! never waits for an exporter. When several consecutive span ends take longer
+//! than the slow export threshold (see [`TermTelemetry::with_circuit_breaker`]),
+//! a circuit breaker trips: a single warning is logged, no further spans are
+//! created, and [`TermTelemetry::health`] reports the telemetry as degraded.
+//! Validation reports of affected runs set `telemetry_incomplete`.
+//!
 //! # Examples
 //!
 //! ## Basic Tracing Only
@@ -89,9 +100,19 @@ use opentelemetry::{
 };
 
 #[cfg(feature = "telemetry")]
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+#[cfg(feature = "telemetry")]
+use std::sync::{mpsc, Arc};
 #[cfg(feature = "telemetry")]
-use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Span ends slower than this count towards tripping the circuit breaker.
+#[cfg(feature = "telemetry")]
+const DEFAULT_SLOW_EXPORT_THRESHOLD: Duration = Duration::from_millis(50);
+
+/// Consecutive slow span ends after which span creation is disabled.
+#[cfg(feature = "telemetry")]
+const DEFAULT_MAX_SLOW_EXPORTS: u32 = 3;
 
 /// Gets current memory usage in bytes.
 #[cfg(feature = "telemetry")]
@@ -317,6 +338,106 @@ impl Drop for ActiveValidationGuard {
     }
 }
 
+/// State of span export, as reported by [`TermTelemetry::health`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TelemetryHealth {
+    /// Whether the circuit breaker tripped and span creation is disabled
+    pub degraded: bool,
+    /// Spans ended by the export thread
+    pub spans_exported: u64,
+    /// Span ends that took longer than the slow export threshold
+    pub slow_exports: u64,
+    /// Spans not created because the telemetry is degraded
+    pub spans_skipped: u64,
+}
+
+/// A span handed to the export thread with the time it ended.
+#[cfg(feature = "telemetry")]
+type PendingSpan = (BoxedSpan, SystemTime);
+
+/// Circuit breaker state shared between a [`TermTelemetry`] and its export thread.
+#[cfg(feature = "telemetry")]
+#[derive(Debug)]
+struct ExportHealth {
+    degraded: AtomicBool,
+    consecutive_slow: AtomicU32,
+    spans_exported: AtomicU64,
+    slow_exports: AtomicU64,
+    spans_skipped: AtomicU64,
+    slow_threshold_us: AtomicU64,
+    max_slow_exports: AtomicU32,
+}
+
+#[cfg(feature = "telemetry")]
+impl ExportHealth {
+    fn new() -> Self {
+        Self {
+            degraded: AtomicBool::new(false),
+            consecutive_slow: AtomicU32::new(0),
+            spans_exported: AtomicU64::new(0),
+            slow_exports: AtomicU64::new(0),
+            spans_skipped: AtomicU64::new(0),
+            slow_threshold_us: AtomicU64::new(DEFAULT_SLOW_EXPORT_THRESHOLD.as_micros() as u64),
+            max_slow_exports: AtomicU32::new(DEFAULT_MAX_SLOW_EXPORTS),
+        }
+    }
+
+    fn is_degraded(&self) -> bool {
+        self.degraded.load(Ordering::Relaxed)
+    }
+
+    /// Records how long ending a span took and trips the breaker on repeated slow ends.
+    fn record_export(&self, elapsed: Duration) {
+        self.spans_exported.fetch_add(1, Ordering::Relaxed);
+        if elapsed.as_micros() as u64 <= self.slow_threshold_us.load(Ordering::Relaxed) {
+            self.consecutive_slow.store(0, Ordering::Relaxed);
+            return;
+        }
+
+        self.slow_exports.fetch_add(1, Ordering::Relaxed);
+        let consecutive = self.consecutive_slow.fetch_add(1, Ordering::Relaxed) + 1;
+        if consecutive >= self.max_slow_exports.load(Ordering::Relaxed)
+            && !self.degraded.swap(true, Ordering::Relaxed)
+        {
+            tracing::warn!(
+                event = "telemetry_degraded",
+                telemetry.slow_exports = consecutive,
+                telemetry.last_export_ms = elapsed.as_millis() as u64,
+                "Span export is not keeping up, disabling span creation; telemetry will be incomplete"
+            );
+        }
+    }
+
+    fn snapshot(&self) -> TelemetryHealth {
+        TelemetryHealth {
+            degraded: self.is_degraded(),
+            spans_exported: self.spans_exported.load(Ordering::Relaxed),
+            slow_exports: self.slow_exports.load(Ordering::Relaxed),
+            spans_skipped: self.spans_skipped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Starts the thread that ends spans off the validation path.
+///
+/// Returns `None` if the thread cannot be spawned, in which case spans are
+/// ended where they are dropped.
+#[cfg(feature = "telemetry")]
+fn spawn_export_thread(health: Arc<ExportHealth>) -> Option<mpsc::Sender<PendingSpan>> {
+    let (sender, receiver) = mpsc::channel::<PendingSpan>();
+    std::thread::Builder::new()
+        .name("term-telemetry-export".to_string())
+        .spawn(move || {
+            for (mut span, end_time) in receiver {
+                let started = Instant::now();
+                span.end_with_timestamp(end_time);
+                health.record_export(started.elapsed());
+            }
+        })
+        .ok()?;
+    Some(sender)
+}
+
 /// Configuration for Term's telemetry integration.
 ///
 /// This struct follows the BYOT (Bring Your Own Tracer) pattern where
@@ -330,6 +451,12 @@ pub struct TermTelemetry {
     #[cfg(feature = "telemetry")]
     metrics: Option<Arc<ValidationMetrics>>,
 
+    #[cfg(feature = "telemetry")]
+    health: Arc<ExportHealth>,
+
+    #[cfg(feature = "telemetry")]
+    exporter: Option<mpsc::Sender<PendingSpan>>,
+
     /// Whether to record detailed constraint-level metrics
     pub detailed_metrics: bool,
 
@@ -360,9 +487,12 @@ impl TermTelemetry {
     /// ```
     #[cfg(feature = "telemetry")]
     pub fn new(tracer: BoxedTracer) -> Self {
+        let health = Arc::new(ExportHealth::new());
         Self {
             tracer,
             metrics: None,
+            exporter: spawn_export_thread(Arc::clone(&health)),
+            health,
             detailed_metrics: true,
             record_timing: true,
             custom_attributes: std::collections::HashMap::new(),
@@ -379,6 +509,10 @@ impl TermTelemetry {
             tracer: opentelemetry::global::tracer("noop"),
             #[cfg(feature = "telemetry")]
             metrics: None,
+            #[cfg(feature = "telemetry")]
+            health: Arc::new(ExportHealth::new()),
+            #[cfg(feature = "telemetry")]
+            exporter: None,
             detailed_metrics: false,
             record_timing: false,
             custom_attributes: std::collections::HashMap::new(),
@@ -412,6 +546,68 @@ impl TermTelemetry {
         self.metrics.as_ref()
     }
 
+    /// Configures the circuit breaker that disables span creation when export stalls.
+    ///
+    /// Ending a span that takes longer than `slow_threshold` counts as a slow export;
+    /// after `max_slow_exports` consecutive slow exports no further spans are created.
+    /// The defaults are 50ms and 3.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use term_guard::telemetry::TermTelemetry;
+    ///
+    /// let telemetry = TermTelemetry::new(opentelemetry::global::tracer("term"))
+    ///     .with_circuit_breaker(Duration::from_millis(100), 5);
+    /// assert!(!telemetry.health().degraded);
+    /// ```
+    #[cfg(feature = "telemetry")]
+    pub fn with_circuit_breaker(self, slow_threshold: Duration, max_slow_exports: u32) -> Self {
+        self.health
+            .slow_threshold_us
+            .store(slow_threshold.as_micros() as u64, Ordering::Relaxed);
+        self.health
+            .max_slow_exports
+            .store(max_slow_exports.max(1), Ordering::Relaxed);
+        self
+    }
+
+    /// Returns the state of span export.
+    ///
+    /// `degraded` is set once the circuit breaker has tripped; from then on no
+    /// spans are created by this configuration. Without the `telemetry` feature
+    /// the telemetry is always reported as healthy.
+    pub fn health(&self) -> TelemetryHealth {
+        #[cfg(feature = "telemetry")]
+        {
+            self.health.snapshot()
+        }
+        #[cfg(not(feature = "telemetry"))]
+        {
+            TelemetryHealth::default()
+        }
+    }
+
+    /// Starts a span unless the circuit breaker has tripped.
+    #[cfg(feature = "telemetry")]
+    fn open_span(&self, name: String) -> Option<BoxedSpan> {
+        if self.health.is_degraded() {
+            self.health.spans_skipped.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        Some(self.tracer.start(name))
+    }
+
+    /// Wraps a span so that it is ended on the export thread.
+    #[cfg(feature = "telemetry")]
+    fn wrap_span(&self, span: BoxedSpan) -> TermSpan {
+        TermSpan {
+            span: Some(span),
+            exporter: self.exporter.clone(),
+        }
+    }
+
     /// Sets whether to record detailed constraint-level metrics.
     pub fn with_detailed_metrics(mut self, enabled: bool) -> Self {
         self.detailed_metrics = enabled;
@@ -451,7 +647,9 @@ impl TermTelemetry {
     /// * `check_count` - Number of checks in the suite
     #[cfg(feature = "telemetry")]
     pub fn start_suite_span(&self, suite_name: &str, check_count: usize) -> TermSpan {
-        let mut span = self.tracer.start(format!("validation_suite.{suite_name}"));
+        let Some(mut span) = self.open_span(format!("validation_suite.{suite_name}")) else {
+            return TermSpan::noop();
+        };
 
         // Add standard attributes
         span.set_attribute(opentelemetry::KeyValue::new(
@@ -469,7 +667,7 @@ impl TermTelemetry {
             span.set_attribute(opentelemetry::KeyValue::new(key.clone(), value.clone()));
         }
 
-        TermSpan::new(span)
+        self.wrap_span(span)
     }
 
     /// Creates a span when telemetry feature is disabled.
@@ -481,7 +679,9 @@ impl TermTelemetry {
     /// Creates a new span for a validation check.
     #[cfg(feature = "telemetry")]
     pub fn start_check_span(&self, check_name: &str, constraint_count: usize) -> TermSpan {
-        let mut span = self.tracer.start(format!("validation_check.{check_name}"));
+        let Some(mut span) = self.open_span(format!("validation_check.{check_name}")) else {
+            return TermSpan::noop();
+        };
 
         span.set_attribute(opentelemetry::KeyValue::new(
             "validation.check.name",
@@ -497,7 +697,7 @@ impl TermTelemetry {
             span.set_attribute(opentelemetry::KeyValue::new(key.clone(), value.clone()));
         }
 
-        TermSpan::new(span)
+        self.wrap_span(span)
     }
 
     #[cfg(not(feature = "telemetry"))]
@@ -508,9 +708,10 @@ impl TermTelemetry {
     /// Creates a new span for constraint evaluation.
     #[cfg(feature = "telemetry")]
     pub fn start_constraint_span(&self, constraint_name: &str, column: Option<&str>) -> TermSpan {
-        let mut span = self
-            .tracer
-            .start(format!("validation_constraint.{constraint_name}"));
+        let Some(mut span) = self.open_span(format!("validation_constraint.{constraint_name}"))
+        else {
+            return TermSpan::noop();
+        };
 
         span.set_attribute(opentelemetry::KeyValue::new(
             "validation.constraint.name",
@@ -532,7 +733,7 @@ impl TermTelemetry {
             span.set_attribute(opentelemetry::KeyValue::new(key.clone(), value.clone()));
         }
 
-        TermSpan::new(span)
+        self.wrap_span(span)
     }
 
     #[cfg(not(feature = "telemetry"))]
@@ -543,7 +744,9 @@ impl TermTelemetry {
     /// Creates a new span for data source operations.
     #[cfg(feature = "telemetry")]
     pub fn start_datasource_span(&self, source_type: &str, table_name: &str) -> TermSpan {
-        let mut span = self.tracer.start(format!("data_source.{source_type}"));
+        let Some(mut span) = self.open_span(format!("data_source.{source_type}")) else {
+            return TermSpan::noop();
+        };
 
         span.set_attribute(opentelemetry::KeyValue::new(
             "data_source.type",
@@ -562,7 +765,7 @@ impl TermTelemetry {
             span.set_attribute(opentelemetry::KeyValue::new(key.clone(), value.clone()));
         }
 
-        TermSpan::new(span)
+        self.wrap_span(span)
     }
 
     #[cfg(not(feature = "telemetry"))]
@@ -579,6 +782,10 @@ impl Clone for TermTelemetry {
             tracer: opentelemetry::global::tracer("noop"), // Use noop tracer for clones
             #[cfg(feature = "telemetry")]
             metrics: self.metrics.clone(), // Metrics can be shared via Arc
+            #[cfg(feature = "telemetry")]
+            health: Arc::clone(&self.health),
+            #[cfg(feature = "telemetry")]
+            exporter: self.exporter.clone(),
             detailed_metrics: self.detailed_metrics,
             record_timing: self.record_timing,
             custom_attributes: self.custom_attributes.clone(),
@@ -589,24 +796,25 @@ impl Clone for TermTelemetry {
 /// regardless of whether the telemetry feature is enabled.
 pub struct TermSpan {
     #[cfg(feature = "telemetry")]
-    span: BoxedSpan,
+    span: Option<BoxedSpan>,
+
+    /// Export thread that ends the span, if any
+    #[cfg(feature = "telemetry")]
+    exporter: Option<mpsc::Sender<PendingSpan>>,
 
     #[cfg(not(feature = "telemetry"))]
     _phantom: std::marker::PhantomData<()>,
 }
 
 impl TermSpan {
-    #[cfg(feature = "telemetry")]
-    fn new(span: BoxedSpan) -> Self {
-        Self { span }
-    }
-
     /// Creates a no-op span that does nothing.
     /// This is used when telemetry is disabled.
     pub fn noop() -> Self {
         Self {
             #[cfg(feature = "telemetry")]
-            span: opentelemetry::global::tracer("noop").start("noop"),
+            span: Some(opentelemetry::global::tracer("noop").start("noop")),
+            #[cfg(feature = "telemetry")]
+            exporter: None,
             #[cfg(not(feature = "telemetry"))]
             _phantom: std::marker::PhantomData,
         }
@@ -615,7 +823,9 @@ impl TermSpan {
     /// Records an event on this span.
     #[cfg(feature = "telemetry")]
     pub fn add_event(&mut self, name: impl Into<String>, attributes: Vec<opentelemetry::KeyValue>) {
-        self.span.add_event(name.into(), attributes);
+        if let Some(span) = &mut self.span {
+            span.add_event(name.into(), attributes);
+        }
     }
 
     #[cfg(not(feature = "telemetry"))]
@@ -626,7 +836,9 @@ impl TermSpan {
     /// Sets an attribute on this span.
     #[cfg(feature = "telemetry")]
     pub fn set_attribute(&mut self, kv: opentelemetry::KeyValue) {
-        self.span.set_attribute(kv);
+        if let Some(span) = &mut self.span {
+            span.set_attribute(kv);
+        }
     }
 
     #[cfg(not(feature = "telemetry"))]
@@ -637,7 +849,9 @@ impl TermSpan {
     /// Sets the status of this span.
     #[cfg(feature = "telemetry")]
     pub fn set_status(&mut self, status: Status) {
-        self.span.set_status(status);
+        if let Some(span) = &mut self.span {
+            span.set_status(status);
+        }
     }
 
     #[cfg(not(feature = "telemetry"))]
@@ -648,10 +862,12 @@ impl TermSpan {
     /// Records an error on this span.
     #[cfg(feature = "telemetry")]
     pub fn record_error(&mut self, error: &dyn std::error::Error) {
-        self.span.record_error(error);
-        self.span.set_status(Status::Error {
-            description: error.to_string().into(),
-        });
+        if let Some(span) = &mut self.span {
+            span.record_error(error);
+            span.set_status(Status::Error {
+                description: error.to_string().into(),
+            });
+        }
     }
 
     #[cfg(not(feature = "telemetry"))]
@@ -663,7 +879,20 @@ impl TermSpan {
 impl Drop for TermSpan {
     #[cfg(feature = "telemetry")]
     fn drop(&mut self) {
-        self.span.end();
+        let Some(mut span) = self.span.take() else {
+            return;
+        };
+        // Ending may block on export, so the export thread ends the span with its end time
+        match &self.exporter {
+            Some(exporter) => {
+                if let Err(mpsc::SendError((mut span, end_time))) =
+                    exporter.send((span, SystemTime::now()))
+                {
+                    span.end_with_timestamp(end_time);
+                }
+            }
+            None => span.end(),
+        }
     }
 
     #[cfg(not(feature = "telemetry"))]
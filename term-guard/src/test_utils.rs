@@ -10,6 +10,7 @@ use datafusion::datasource::MemTable;
 use datafusion::prelude::*;
 use std::sync::Arc;
 
+use crate::core::{Constraint, ConstraintResult, Evaluator};
 use crate::prelude::*;
 
 /// Scale factors for TPC-H data generation.
@@ -747,9 +748,121 @@ pub async fn create_test_context() -> Result<SessionContext> {
     Ok(ctx)
 }
 
+/// Evaluates a constraint concurrently and asserts that every evaluation
+/// returns the same result.
+///
+/// The constraint is evaluated once on its own, then `parallelism` times at
+/// once in separate Tokio tasks sharing `ctx`; on a multi-threaded runtime the
+/// tasks run on different threads. Results are compared on status, message and
+/// metric, allowing metrics a relative difference of 1e-9 since parallel
+/// aggregation may add values in a different order. Errors are compared on
+/// their message. The constraint validates the table `data`, the default table
+/// of a suite.
+///
+/// Returns the common result, or the common error if every evaluation failed.
+///
+/// # Panics
+///
+/// Panics if an evaluation panics or if two evaluations disagree.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use term_guard::constraints::CompletenessConstraint;
+/// use term_guard::test_utils::assert_constraint_concurrent_safe;
+/// use datafusion::prelude::*;
+///
+/// #[tokio::test(flavor = "multi_thread")]
+/// async fn completeness_is_concurrent_safe() {
+///     let ctx = SessionContext::new();
+///     // ... register the table `data` ...
+///     assert_constraint_concurrent_safe(CompletenessConstraint::complete("id"), &ctx, 16)
+///         .await
+///         .unwrap();
+/// }
+/// ```
+pub async fn assert_constraint_concurrent_safe<C>(
+    constraint: C,
+    ctx: &SessionContext,
+    parallelism: usize,
+) -> Result<ConstraintResult>
+where
+    C: Constraint + 'static,
+{
+    assert!(parallelism > 0, "parallelism must be at least 1");
+    let constraint: Arc<dyn Constraint> = Arc::new(constraint);
+    let baseline = Evaluator::new(ctx).evaluate_dyn(constraint.as_ref()).await;
+
+    let tasks: Vec<_> = (0..parallelism)
+        .map(|_| {
+            let constraint = Arc::clone(&constraint);
+            let ctx = ctx.clone();
+            tokio::spawn(
+                async move { Evaluator::new(&ctx).evaluate_dyn(constraint.as_ref()).await },
+            )
+        })
+        .collect();
+
+    for (index, task) in tasks.into_iter().enumerate() {
+        let result = task.await.unwrap_or_else(|e| {
+            panic!(
+                "concurrent evaluation {index} of {} panicked: {e}",
+                constraint.name()
+            )
+        });
+        assert!(
+            same_result(&baseline, &result),
+            "concurrent evaluation {index} of {} disagrees with a sequential one: {result:?} != {baseline:?}",
+            constraint.name()
+        );
+    }
+    baseline
+}
+
+/// Returns true if two evaluations of a constraint agree.
+fn same_result(left: &Result<ConstraintResult>, right: &Result<ConstraintResult>) -> bool {
+    match (left, right) {
+        (Ok(left), Ok(right)) => {
+            let same_metric = match (left.metric, right.metric) {
+                (Some(a), Some(b)) => a == b || (a - b).abs() <= 1e-9 * a.abs().max(b.abs()),
+                (a, b) => a.is_none() && b.is_none(),
+            };
+            left.status == right.status && left.message == right.message && same_metric
+        }
+        (Err(left), Err(right)) => left.to_string() == right.to_string(),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A constraint whose result depends on how often it was evaluated before.
+    #[derive(Debug, Default)]
+    struct CountingConstraint {
+        evaluations: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl Constraint for CountingConstraint {
+        async fn evaluate(&self, _ctx: &SessionContext) -> Result<ConstraintResult> {
+            let evaluations = self.evaluations.fetch_add(1, Ordering::SeqCst);
+            Ok(ConstraintResult::success_with_metric(evaluations as f64))
+        }
+
+        fn name(&self) -> &str {
+            "counting"
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[should_panic(expected = "disagrees with a sequential one")]
+    async fn test_stateful_constraint_is_detected() {
+        let ctx = SessionContext::new();
+        let _ = assert_constraint_concurrent_safe(CountingConstraint::default(), &ctx, 4).await;
+    }
 
     #[tokio::test]
     async fn test_scale_factor_values() {
@@ -0,0 +1,211 @@
+//! Scalar UDFs for common validation rules, behind the `udfs` feature.
+//!
+//! Attach them to a suite with
+//! [`ValidationSuiteBuilder::with_udfs`](crate::core::ValidationSuiteBuilder::with_udfs)
+//! or [`with_udf`](crate::core::ValidationSuiteBuilder::with_udf) and call them
+//! from custom SQL expressions:
+//!
+//! | Function | Returns |
+//! |----------|---------|
+//! | `luhn_check(text)` | Whether the digits pass the Luhn checksum of card and account numbers; spaces and hyphens between digits are ignored |
+//! | `is_parsable_date(text, format)` | Whether the text is a date or date and time in the `chrono` format, such as `'%Y-%m-%d'` |
+//! | `levenshtein(a, b)` | The number of single-character insertions, deletions and substitutions turning `a` into `b` |
+//!
+//! Every function returns NULL when an argument is NULL. DataFusion has a
+//! built-in `levenshtein` too; the one registered here replaces it for the
+//! duration of a run and counts characters the same way, returning a 64-bit
+//! integer.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use term_guard::core::{Check, ValidationSuite};
+//! use term_guard::udfs;
+//!
+//! let suite = ValidationSuite::builder("payments")
+//!     .with_udfs(udfs::all())
+//!     .check(
+//!         Check::builder("cards")
+//!             .satisfies("luhn_check(card_number)", Some("Card numbers fail the checksum"))
+//!             .satisfies("is_parsable_date(expiry, '%Y-%m-%d')", None::<String>)
+//!             .build(),
+//!     )
+//!     .build();
+//! assert_eq!(suite.udf_names(), ["luhn_check", "is_parsable_date", "levenshtein"]);
+//! ```
+
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, BooleanArray, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use chrono::{NaiveDate, NaiveDateTime};
+use datafusion::logical_expr::{create_udf, ColumnarValue, ScalarUDF, Volatility};
+
+/// Name of the UDF checking the Luhn checksum.
+pub const LUHN_CHECK: &str = "luhn_check";
+
+/// Name of the UDF checking that text parses as a date.
+pub const IS_PARSABLE_DATE: &str = "is_parsable_date";
+
+/// Name of the UDF computing the edit distance of two strings.
+pub const LEVENSHTEIN: &str = "levenshtein";
+
+/// Returns every UDF of this module.
+pub fn all() -> Vec<ScalarUDF> {
+    vec![luhn_check(), is_parsable_date(), levenshtein()]
+}
+
+/// Creates `luhn_check(text)`, returning whether the digits of each value pass
+/// the Luhn checksum.
+pub fn luhn_check() -> ScalarUDF {
+    create_udf(
+        LUHN_CHECK,
+        vec![DataType::Utf8],
+        DataType::Boolean,
+        Volatility::Immutable,
+        Arc::new(|args: &[ColumnarValue]| {
+            let arrays = ColumnarValue::values_to_arrays(args)?;
+            let values = utf8(&arrays[0])?;
+            let values = string_array(&values);
+            let valid: BooleanArray = values.iter().map(|v| v.map(is_luhn_valid)).collect();
+            Ok(ColumnarValue::Array(Arc::new(valid) as ArrayRef))
+        }),
+    )
+}
+
+/// Creates `is_parsable_date(text, format)`, returning whether each value is a
+/// date, or a date and time, in the `chrono` format of the second argument.
+pub fn is_parsable_date() -> ScalarUDF {
+    create_udf(
+        IS_PARSABLE_DATE,
+        vec![DataType::Utf8, DataType::Utf8],
+        DataType::Boolean,
+        Volatility::Immutable,
+        Arc::new(|args: &[ColumnarValue]| {
+            let arrays = ColumnarValue::values_to_arrays(args)?;
+            let (values, formats) = (utf8(&arrays[0])?, utf8(&arrays[1])?);
+            let (values, formats) = (string_array(&values), string_array(&formats));
+            let parsable: BooleanArray = values
+                .iter()
+                .zip(formats.iter())
+                .map(|(value, format)| Some(is_parsable(value?, format?)))
+                .collect();
+            Ok(ColumnarValue::Array(Arc::new(parsable) as ArrayRef))
+        }),
+    )
+}
+
+/// Creates `levenshtein(a, b)`, returning the edit distance of each pair of
+/// values in characters.
+pub fn levenshtein() -> ScalarUDF {
+    create_udf(
+        LEVENSHTEIN,
+        vec![DataType::Utf8, DataType::Utf8],
+        DataType::Int64,
+        Volatility::Immutable,
+        Arc::new(|args: &[ColumnarValue]| {
+            let arrays = ColumnarValue::values_to_arrays(args)?;
+            let (left, right) = (utf8(&arrays[0])?, utf8(&arrays[1])?);
+            let (left, right) = (string_array(&left), string_array(&right));
+            let distances: Int64Array = left
+                .iter()
+                .zip(right.iter())
+                .map(|(a, b)| Some(edit_distance(a?, b?) as i64))
+                .collect();
+            Ok(ColumnarValue::Array(Arc::new(distances) as ArrayRef))
+        }),
+    )
+}
+
+fn utf8(array: &ArrayRef) -> datafusion::error::Result<ArrayRef> {
+    Ok(cast(array, &DataType::Utf8)?)
+}
+
+fn string_array(array: &ArrayRef) -> &StringArray {
+    array
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .expect("cast to Utf8")
+}
+
+/// Returns whether the digits of `value` pass the Luhn checksum.
+///
+/// Spaces and hyphens are ignored; any other character, or fewer than two
+/// digits, fails the check.
+fn is_luhn_valid(value: &str) -> bool {
+    let mut digits = 0;
+    let mut sum = 0;
+    for c in value.chars().rev().filter(|c| !matches!(c, ' ' | '-')) {
+        let Some(digit) = c.to_digit(10) else {
+            return false;
+        };
+        // Every second digit from the right is doubled
+        sum += match (digits % 2 == 1, digit * 2) {
+            (true, doubled) if doubled > 9 => doubled - 9,
+            (true, doubled) => doubled,
+            (false, _) => digit,
+        };
+        digits += 1;
+    }
+    digits >= 2 && sum % 10 == 0
+}
+
+/// Returns whether `value` is a date, or a date and time, in `format`.
+fn is_parsable(value: &str, format: &str) -> bool {
+    NaiveDate::parse_from_str(value, format).is_ok()
+        || NaiveDateTime::parse_from_str(value, format).is_ok()
+}
+
+/// Returns the Levenshtein distance of `a` and `b` in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_luhn() {
+        for valid in [
+            "4539 1488 0343 6467",
+            "79927398713",
+            "5555-5555-5555-4444",
+            "00",
+        ] {
+            assert!(is_luhn_valid(valid), "{valid}");
+        }
+        for invalid in ["4539 1488 0343 6468", "79927398710", "7992739871x", "0", ""] {
+            assert!(!is_luhn_valid(invalid), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_parsable_dates() {
+        assert!(is_parsable("2024-02-29", "%Y-%m-%d"));
+        assert!(is_parsable("29/02/2024 13:45", "%d/%m/%Y %H:%M"));
+        assert!(!is_parsable("2023-02-29", "%Y-%m-%d"));
+        assert!(!is_parsable("2024-02-29", "%d/%m/%Y"));
+        assert!(!is_parsable("2024-02-29 extra", "%Y-%m-%d"));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("straße", "strasse"), 2);
+        assert_eq!(edit_distance("same", "same"), 0);
+    }
+}
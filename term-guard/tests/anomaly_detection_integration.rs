@@ -1,7 +1,7 @@
 //! Integration tests for anomaly detection with AnalysisRunner.
 
 use chrono::{Duration, Utc};
-use term_guard::analyzers::{anomaly::*, basic::*, AnalysisRunner, MetricValue};
+use term_guard::analyzers::{anomaly::*, basic::*, AnalysisRunner, AnalyzerContext, MetricValue};
 use term_guard::test_utils::{create_tpc_h_context, ScaleFactor};
 
 #[tokio::test]
@@ -128,6 +128,7 @@ async fn test_anomaly_detection_memory_limits() {
         max_metrics: 5,
         max_points_per_metric: 10,
         max_age_seconds: 60, // 1 minute
+        max_memory_bytes: None,
     };
     let repo = InMemoryMetricsRepository::with_config(config);
 
@@ -330,3 +331,176 @@ async fn test_anomaly_detection_pattern_matching() {
         "Should detect completeness anomalies via pattern matching"
     );
 }
+
+async fn context_with_rows(rows: usize) -> datafusion::prelude::SessionContext {
+    let ctx = datafusion::prelude::SessionContext::new();
+    let values = (0..rows)
+        .map(|i| format!("({i})"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    ctx.sql(&format!("CREATE TABLE orders AS VALUES {values}"))
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    ctx
+}
+
+#[tokio::test]
+async fn test_suite_anomaly_check_catches_row_count_drop() {
+    use std::sync::Arc;
+    use term_guard::core::{AnomalyCheckOptions, Level, ValidationSuite};
+    use term_guard::repository::{InMemoryRepository, MetricsRepository};
+
+    let repository = Arc::new(InMemoryRepository::new());
+    let suite = ValidationSuite::builder("daily_orders")
+        .table_name("orders")
+        .anomaly_check(
+            SizeAnalyzer::new(),
+            RelativeRateOfChangeStrategy::new(0.5).unwrap(),
+            repository.clone(),
+            AnomalyCheckOptions::new("volume")
+                .level(Level::Error)
+                .min_history(3)
+                .with_tag("dataset", "orders"),
+        )
+        .build();
+
+    // Warm-up: the first three runs only build up history
+    for rows in [1000, 1050, 980] {
+        let result = suite.run(&context_with_rows(rows).await).await.unwrap();
+        assert!(result.is_success());
+        assert_eq!(result.report().metrics.skipped_checks, 1);
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+
+    // A normal run is evaluated against the history and passes
+    let result = suite.run(&context_with_rows(1010).await).await.unwrap();
+    assert!(result.is_success());
+    assert_eq!(result.report().metrics.passed_checks, 1);
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+    // A 10x drop in row count is flagged
+    let result = suite.run(&context_with_rows(101).await).await.unwrap();
+    assert!(result.is_failure());
+    let issue = &result.report().issues[0];
+    assert_eq!(issue.check_name, "volume");
+    assert_eq!(issue.constraint_name, "anomaly_check");
+    assert_eq!(issue.metric, Some(101.0));
+    assert!(issue.message.contains("Rate of decrease"));
+
+    // Every run appended its metric to the repository
+    assert_eq!(repository.list_keys().await.unwrap().len(), 5);
+
+    // Runs with other tags do not share history
+    let other = ValidationSuite::builder("daily_orders")
+        .table_name("orders")
+        .anomaly_check(
+            SizeAnalyzer::new(),
+            RelativeRateOfChangeStrategy::new(0.5).unwrap(),
+            repository.clone(),
+            AnomalyCheckOptions::new("volume")
+                .min_history(1)
+                .with_tag("dataset", "returns")
+                .save_metric(false),
+        )
+        .build();
+    let result = other.run(&context_with_rows(5).await).await.unwrap();
+    assert_eq!(result.report().metrics.skipped_checks, 1);
+    assert_eq!(repository.list_keys().await.unwrap().len(), 5);
+}
+
+#[tokio::test]
+async fn test_retention_bounds_memory_under_sustained_load() {
+    const POINTS: i64 = 1_000_000;
+    const BUDGET: usize = 64 * 1024;
+
+    let repo = InMemoryMetricsRepository::with_config(InMemoryMetricsConfig {
+        max_points_per_metric: 200,
+        max_memory_bytes: Some(BUDGET),
+        ..Default::default()
+    });
+
+    let metrics = ["size", "completeness.a", "completeness.b", "mean.amount"];
+    let start = Utc::now() - Duration::seconds(POINTS + 60);
+    for i in 0..POINTS {
+        let name = metrics[(i % 4) as usize];
+        repo.store_metric(
+            name,
+            MetricValue::Long(1_000 + i % 7),
+            start + Duration::seconds(i),
+        )
+        .await
+        .unwrap();
+    }
+
+    let stats = repo.memory_stats().await;
+    assert!(stats.estimated_memory_bytes <= BUDGET);
+    assert_eq!(stats.total_metrics, 4);
+    assert_eq!(
+        stats.total_data_points as u64 + stats.evictions.total(),
+        POINTS as u64
+    );
+    assert!(stats.evictions.by_memory > 0);
+    assert_eq!(stats.evictions.by_age, 0);
+
+    // Only the most recent points survive
+    let history = repo
+        .get_metric_history("size", None, None, None)
+        .await
+        .unwrap();
+    assert!(history.len() >= 10);
+    assert!(history.len() <= 200);
+    assert!(history.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+    assert_eq!(
+        history.last().unwrap().timestamp,
+        stats.newest_data_point.unwrap() - Duration::seconds(3)
+    );
+
+    // Detection on the retained history stays sane
+    let detector = ZScoreDetector::new(3.0);
+    let normal = detector
+        .detect("size", &MetricValue::Long(1_003), &history)
+        .await
+        .unwrap();
+    assert!(normal.is_none());
+    let outlier = detector
+        .detect("size", &MetricValue::Long(5_000), &history)
+        .await
+        .unwrap();
+    assert!(outlier.is_some());
+}
+
+#[tokio::test]
+async fn test_truncated_history_is_not_reported_as_anomaly() {
+    let repo = InMemoryMetricsRepository::with_config(InMemoryMetricsConfig {
+        max_points_per_metric: 5,
+        ..Default::default()
+    });
+    let now = Utc::now();
+    for i in 0..50 {
+        repo.store_metric(
+            "size",
+            MetricValue::Long(100 + i % 3),
+            now - Duration::minutes(60 - i),
+        )
+        .await
+        .unwrap();
+    }
+
+    let detector = AnomalyDetectionRunner::builder()
+        .repository(Box::new(repo.clone()))
+        .add_detector("size", Box::new(ZScoreDetector::new(2.0)))
+        .build()
+        .unwrap();
+
+    let mut context = AnalyzerContext::new();
+    context.store_metric("size", MetricValue::Long(10_000));
+    let anomalies = detector.detect_anomalies(&context).await.unwrap();
+    assert!(anomalies.is_empty());
+
+    let stats = repo.memory_stats().await;
+    assert_eq!(stats.total_data_points, 5);
+    assert_eq!(stats.evictions.by_count, 46);
+}
@@ -227,7 +227,15 @@ async fn test_constraint_failures() {
     if let ValidationResult::Failure { report } = &results {
         assert!(!report.issues.is_empty());
         let issue = &report.issues[0];
-        assert!(issue.message.contains("does not satisfy assertion"));
+        assert!(
+            issue
+                .message
+                .to_lowercase()
+                .contains("distinct count of column 'n_nationkey' is "),
+            "{}",
+            issue.message
+        );
+        assert!(issue.message.contains(", expected > 1000 ("));
     }
 }
 
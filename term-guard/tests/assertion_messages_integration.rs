@@ -0,0 +1,146 @@
+//! Snapshot tests of the failure messages of constraints asserting on a value.
+//!
+//! Every such constraint explains its failures through `Assertion::explain`, so
+//! the messages share one form: the observed value, the expected value and how
+//! far apart they are.
+
+use datafusion::prelude::*;
+use term_guard::constraints::{
+    ApproxCountDistinctConstraint, Assertion, ColumnCountConstraint, CorrelationConstraint,
+    CustomSqlConstraint, ManifestSizeConstraint, QuantileConstraint, SizeConstraint, SizeTolerance,
+    StaticProvider, StatisticalConstraint, TrueRateConstraint, UniquenessConstraint,
+};
+use term_guard::core::{Constraint, ConstraintStatus, Evaluator};
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql(
+        "CREATE TABLE data (id BIGINT, price DOUBLE, category VARCHAR, is_active BOOLEAN) AS VALUES
+            (1, 10.0, 'a', true),
+            (2, 20.0, 'a', true),
+            (3, 30.0, 'b', false),
+            (4, 40.0, 'b', true),
+            (5, 50.0, 'c', false)",
+    )
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+    ctx
+}
+
+/// Evaluates a constraint expected to fail and returns its message.
+async fn failure_message(ctx: &SessionContext, constraint: impl Constraint) -> String {
+    let result = Evaluator::new(ctx).evaluate(constraint).await.unwrap();
+    assert_eq!(result.status, ConstraintStatus::Failure, "{result:?}");
+    result.message.unwrap()
+}
+
+#[tokio::test]
+async fn test_table_shape_messages() {
+    let ctx = create_context().await;
+
+    assert_eq!(
+        failure_message(&ctx, SizeConstraint::new(Assertion::GreaterThan(10.0))).await,
+        "Row count is 5, expected > 10 (5 below)"
+    );
+    assert_eq!(
+        failure_message(&ctx, ColumnCountConstraint::new(Assertion::Equals(3.0))).await,
+        "Column count is 4, expected 3 (1 above)"
+    );
+    assert_eq!(
+        failure_message(
+            &ctx,
+            ManifestSizeConstraint::new(StaticProvider::new(10.0), SizeTolerance::AtLeast),
+        )
+        .await,
+        "Row count is 5, expected ≥ 10 (5 below) (expected 10 from static value 10, at least)"
+    );
+}
+
+#[tokio::test]
+async fn test_column_statistic_messages() {
+    let ctx = create_context().await;
+
+    assert_eq!(
+        failure_message(
+            &ctx,
+            StatisticalConstraint::mean("price", Assertion::Between(35.0, 45.0)).unwrap(),
+        )
+        .await,
+        "Mean of column 'price' is 30, expected between 35 and 45 (5 below)"
+    );
+    assert_eq!(
+        failure_message(
+            &ctx,
+            QuantileConstraint::median("price", Assertion::LessThan(25.0)).unwrap(),
+        )
+        .await,
+        "Quantile 0.5 of column 'price' is 30, expected < 25 (5 above)"
+    );
+    assert_eq!(
+        failure_message(
+            &ctx,
+            ApproxCountDistinctConstraint::new("category", Assertion::GreaterThan(5.0)),
+        )
+        .await,
+        "Approximate distinct count of column 'category' is 3, expected > 5 (2 below)"
+    );
+}
+
+#[tokio::test]
+async fn test_ratio_messages() {
+    let ctx = create_context().await;
+
+    assert_eq!(
+        failure_message(
+            &ctx,
+            UniquenessConstraint::distinctness(["category"], Assertion::GreaterThanOrEqual(0.9))
+                .unwrap(),
+        )
+        .await,
+        "Distinctness ratio of column 'category' is 0.6, expected ≥ 0.9 (0.3 below)"
+    );
+    assert_eq!(
+        failure_message(
+            &ctx,
+            TrueRateConstraint::new("is_active", Assertion::GreaterThanOrEqual(0.8)).unwrap(),
+        )
+        .await,
+        "True rate of column 'is_active' is 0.6, expected ≥ 0.8 (0.2 below) \
+         (true: 3, false: 2, null: 0)"
+    );
+
+    // The observed correlation is subject to rounding
+    let message = failure_message(
+        &ctx,
+        CorrelationConstraint::pearson("id", "price", Assertion::LessThan(0.5)).unwrap(),
+    )
+    .await;
+    assert!(
+        message.starts_with("Pearson correlation between 'id' and 'price' is "),
+        "{message}"
+    );
+    assert!(message.contains(", expected < 0.5 ("), "{message}");
+    assert!(message.ends_with(" above)"), "{message}");
+}
+
+#[tokio::test]
+async fn test_custom_sql_message() {
+    let ctx = create_context().await;
+
+    assert_eq!(
+        failure_message(
+            &ctx,
+            CustomSqlConstraint::scalar_query(
+                "SELECT max(price) FROM data",
+                Assertion::LessThanOrEqual(45.0),
+            )
+            .unwrap(),
+        )
+        .await,
+        "Query result is 50, expected ≤ 45 (5 above) (mode: scalar query). \
+         Query: 'SELECT max(price) FROM data'"
+    );
+}
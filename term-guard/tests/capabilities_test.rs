@@ -0,0 +1,87 @@
+//! Tests for runtime feature discovery.
+//!
+//! The assertions hold for any feature combination; CI runs this file without
+//! default features, with `s3,excel` and with all features.
+
+use term_guard::{capabilities, Capabilities};
+
+#[test]
+fn test_flags_match_the_compiled_features() {
+    let capabilities = capabilities();
+    assert_eq!(capabilities.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        capabilities.report_schema_version,
+        term_guard::core::REPORT_SCHEMA_VERSION
+    );
+
+    let expected = [
+        ("cloud-storage", cfg!(feature = "cloud-storage")),
+        ("s3", cfg!(feature = "s3")),
+        ("gcs", cfg!(feature = "gcs")),
+        ("azure", cfg!(feature = "azure")),
+        ("database", cfg!(feature = "database")),
+        ("postgres", cfg!(feature = "postgres")),
+        ("mysql", cfg!(feature = "mysql")),
+        ("sqlite", cfg!(feature = "sqlite")),
+        ("delta", cfg!(feature = "delta")),
+        ("excel", cfg!(feature = "excel")),
+        ("flight", cfg!(feature = "flight")),
+        ("migration", cfg!(feature = "migration")),
+        ("scheduler", cfg!(feature = "scheduler")),
+        ("streaming", cfg!(feature = "streaming")),
+        ("telemetry", cfg!(feature = "telemetry")),
+        ("udfs", cfg!(feature = "udfs")),
+        ("yaml", cfg!(feature = "yaml")),
+    ];
+    assert_eq!(capabilities.features(), expected);
+    for (feature, enabled) in expected {
+        assert_eq!(capabilities.is_enabled(feature), enabled, "{feature}");
+        assert_eq!(capabilities.require(feature).is_ok(), enabled, "{feature}");
+    }
+}
+
+#[cfg(not(any(feature = "s3", feature = "delta")))]
+#[test]
+fn test_without_optional_features() {
+    let capabilities = capabilities();
+    assert!(!capabilities.s3);
+    assert!(!capabilities.delta);
+
+    let message = capabilities.require("s3").unwrap_err().to_string();
+    assert!(
+        message.contains("compiled without the 's3' feature; rebuild with --features s3"),
+        "{message}"
+    );
+}
+
+#[cfg(all(feature = "s3", feature = "excel"))]
+#[test]
+fn test_with_s3_and_excel() {
+    let capabilities = capabilities();
+    // `s3` enables `cloud-storage`
+    assert!(capabilities.s3 && capabilities.cloud_storage && capabilities.excel);
+    assert!(capabilities
+        .enabled_features()
+        .starts_with(&["cloud-storage", "s3"]));
+    capabilities.require("s3").unwrap();
+    capabilities.require("excel").unwrap();
+}
+
+#[test]
+fn test_unknown_features() {
+    let capabilities = capabilities();
+    assert!(!capabilities.is_enabled("nexus"));
+    let message = capabilities.require("nexus").unwrap_err().to_string();
+    assert!(message.contains("Unknown feature 'nexus'"), "{message}");
+}
+
+#[test]
+fn test_serialization_round_trip() {
+    let capabilities = capabilities();
+    let json = serde_json::to_value(&capabilities).unwrap();
+    assert_eq!(json["s3"], cfg!(feature = "s3"));
+    assert_eq!(json["cloud_storage"], cfg!(feature = "cloud-storage"));
+
+    let parsed: Capabilities = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, capabilities);
+}
@@ -0,0 +1,136 @@
+//! Integration tests for running selected checks of a suite.
+
+use datafusion::prelude::*;
+use term_guard::constraints::Assertion;
+use term_guard::core::{Check, CheckFilter, Level, RunOptions, ValidationSuite};
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql("CREATE TABLE data (revenue DOUBLE, cost DOUBLE) AS VALUES (-5.0, 2.0), (10.0, 3.0)")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    ctx
+}
+
+fn suite() -> ValidationSuite {
+    ValidationSuite::builder("finance")
+        .check(
+            Check::builder("finance_revenue")
+                .level(Level::Error)
+                .tag("finance")
+                .has_min("revenue", Assertion::GreaterThanOrEqual(0.0))
+                .build(),
+        )
+        .check(
+            Check::builder("finance_costs")
+                .level(Level::Warning)
+                .tag("finance")
+                .tag("slow")
+                .has_max("cost", Assertion::LessThan(100.0))
+                .has_size(Assertion::Equals(2.0))
+                .build(),
+        )
+        .check(
+            Check::builder("volume")
+                .level(Level::Info)
+                .has_size(Assertion::GreaterThan(0.0))
+                .build(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_rerun_failed_checks() {
+    let ctx = create_context().await;
+    let suite = suite();
+
+    let full = suite.run(&ctx).await.unwrap();
+    assert!(full.is_failure());
+    assert_eq!(full.report().metrics.total_checks, 3);
+    assert!(full.report().excluded_checks.is_empty());
+
+    let rerun = suite
+        .run_with(
+            &ctx,
+            &RunOptions::new().with_filter(CheckFilter::failed_in(full.report())),
+        )
+        .await
+        .unwrap();
+    let report = rerun.report();
+    assert!(rerun.is_failure());
+    assert_eq!(report.metrics.total_checks, 1);
+    assert_eq!(report.metrics.success_rate(), 0.0);
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].check_name, "finance_revenue");
+
+    // Unexecuted checks are listed as excluded, not counted as passed
+    let excluded: Vec<_> = report
+        .excluded_checks
+        .iter()
+        .map(|check| (check.check_name.as_str(), check.level, check.constraints))
+        .collect();
+    assert_eq!(
+        excluded,
+        vec![
+            ("finance_costs", Level::Warning, 2),
+            ("volume", Level::Info, 1)
+        ]
+    );
+    assert_eq!(report.metrics.passed_checks, 0);
+}
+
+#[tokio::test]
+async fn test_filter_by_name_level_and_tag() {
+    let ctx = create_context().await;
+    let suite = suite();
+
+    // Success rate covers only the executed checks
+    let passing = suite
+        .run_with(
+            &ctx,
+            &RunOptions::new().with_filter(
+                CheckFilter::new()
+                    .include("finance_*")
+                    .unwrap()
+                    .exclude("*_revenue")
+                    .unwrap(),
+            ),
+        )
+        .await
+        .unwrap();
+    assert!(passing.is_success());
+    assert_eq!(passing.report().metrics.total_checks, 1);
+    assert_eq!(passing.report().metrics.success_rate(), 100.0);
+    assert_eq!(passing.report().excluded_checks.len(), 2);
+
+    let fast_finance = suite
+        .run_with(
+            &ctx,
+            &RunOptions::new().with_filter(CheckFilter::new().tag("finance").exclude_tag("slow")),
+        )
+        .await
+        .unwrap();
+    assert!(fast_finance.is_failure());
+    assert_eq!(fast_finance.report().metrics.total_checks, 1);
+
+    let info_only = suite
+        .run_with(
+            &ctx,
+            &RunOptions::new().with_filter(CheckFilter::new().level(Level::Info)),
+        )
+        .await
+        .unwrap();
+    assert!(info_only.is_success());
+    assert_eq!(info_only.report().metrics.passed_checks, 1);
+
+    // A filter selecting every check behaves like a full run
+    let all = suite
+        .run_with(&ctx, &RunOptions::new().with_filter(CheckFilter::new()))
+        .await
+        .unwrap();
+    assert_eq!(all.report().metrics.total_checks, 3);
+    assert!(all.report().excluded_checks.is_empty());
+}
@@ -0,0 +1,97 @@
+//! Integration tests for expanding check templates over column lists.
+
+use arrow::datatypes::DataType;
+use datafusion::prelude::*;
+use term_guard::constraints::Assertion;
+use term_guard::core::{Check, ColumnSelector, ConstraintOptions, Level, ValidationSuite};
+
+const MEASURES: usize = 40;
+
+/// Registers a fact table with `MEASURES` float measures, where `m_13` is
+/// negative in one row and `m_27` is constant.
+async fn create_context() -> SessionContext {
+    let columns: Vec<String> = (0..MEASURES).map(|i| format!("m_{i:02} DOUBLE")).collect();
+    let rows: Vec<String> = (0..3)
+        .map(|row| {
+            let values: Vec<String> = (0..MEASURES)
+                .map(|i| match (i, row) {
+                    (13, 1) => "-1.0".to_string(),
+                    (27, _) => "5.0".to_string(),
+                    _ => format!("{}.5", i + row),
+                })
+                .collect();
+            format!("({row}, 'label', {})", values.join(", "))
+        })
+        .collect();
+
+    let ctx = SessionContext::new();
+    let sql = format!(
+        "CREATE TABLE facts (id INT, label VARCHAR, {}) AS VALUES {}",
+        columns.join(", "),
+        rows.join(", ")
+    );
+    ctx.sql(&sql).await.unwrap().collect().await.unwrap();
+    ctx
+}
+
+#[tokio::test]
+async fn test_template_over_discovered_columns() {
+    let ctx = create_context().await;
+
+    let measures = ColumnSelector::new()
+        .data_type(DataType::Float64)
+        .name_matches("^m_")
+        .unwrap()
+        .resolve(&ctx, "facts")
+        .await
+        .unwrap();
+    assert_eq!(measures.len(), MEASURES);
+
+    let check = Check::builder("measures")
+        .level(Level::Error)
+        .apply_to_columns(&measures, |builder, column| {
+            builder
+                .completeness(column, ConstraintOptions::new().with_threshold(0.99))
+                .has_min(column, Assertion::GreaterThanOrEqual(0.0))
+                .has_standard_deviation(column, Assertion::GreaterThan(0.0))
+        })
+        .build();
+    assert_eq!(check.constraints().len(), 3 * MEASURES);
+
+    let result = ValidationSuite::builder("wide_table")
+        .table_name("facts")
+        .check(check)
+        .build()
+        .run(&ctx)
+        .await
+        .unwrap();
+
+    assert!(result.is_failure());
+    let report = result.report();
+    assert_eq!(report.metrics.failed_checks, 1);
+    let passed = report
+        .outcomes
+        .iter()
+        .filter(|outcome| outcome.status.is_success())
+        .count();
+    assert_eq!(passed, 3 * MEASURES - 2);
+
+    let mut failed: Vec<_> = report
+        .issues
+        .iter()
+        .map(|issue| issue.constraint_name.as_str())
+        .collect();
+    failed.sort();
+    assert_eq!(failed, vec!["min.m_13", "standard_deviation.m_27"]);
+    assert!(report
+        .metrics
+        .custom_metrics
+        .contains_key("measures.completeness.m_00"));
+}
+
+#[tokio::test]
+async fn test_resolve_unknown_table_fails() {
+    let ctx = SessionContext::new();
+    let err = ColumnSelector::new().resolve(&ctx, "missing").await;
+    assert!(err.is_err());
+}
@@ -0,0 +1,104 @@
+//! Integration tests for the limits on result sets collected by constraints.
+
+use std::sync::Arc;
+
+use datafusion::prelude::*;
+use term_guard::constraints::{Histogram, HistogramConstraint};
+use term_guard::core::{
+    Check, CollectionLimits, ConstraintResult, ConstraintStatus, Evaluator, ValidationSuite,
+    COLLECTION_ROWS_DETAIL, COLLECTION_SPILLED_ROWS_DETAIL, COLLECTION_TRUNCATED_DETAIL,
+};
+
+/// Two thousand distinct categories, each appearing once or twice.
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql(
+        "CREATE TABLE events AS
+         SELECT CAST(value % 2000 AS VARCHAR) AS category FROM generate_series(1, 3000)",
+    )
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+    ctx
+}
+
+fn histogram() -> HistogramConstraint {
+    HistogramConstraint::new(
+        "category",
+        Arc::new(|hist: &Histogram| hist.bucket_count() > 0),
+    )
+}
+
+/// A budget far below the size of the histogram, in batches of 16 rows.
+fn low_budget() -> CollectionLimits {
+    CollectionLimits::new().max_bytes(8 * 1024).batch_size(16)
+}
+
+async fn evaluate(ctx: &SessionContext, limits: CollectionLimits) -> ConstraintResult {
+    Evaluator::new(ctx)
+        .table("events")
+        .collection_limits(limits)
+        .evaluate(histogram())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_low_byte_budget_truncates_the_histogram() {
+    let ctx = create_context().await;
+
+    let complete = evaluate(&ctx, CollectionLimits::default()).await;
+    assert_eq!(complete.status, ConstraintStatus::Success);
+    assert!(complete.details.is_empty(), "{:?}", complete.details);
+
+    let truncated = evaluate(&ctx, low_budget()).await;
+    assert_eq!(truncated.status, ConstraintStatus::Success);
+    assert_eq!(truncated.details[COLLECTION_TRUNCATED_DETAIL], 1.0);
+    let rows = truncated.details[COLLECTION_ROWS_DETAIL];
+    assert!(rows > 0.0 && rows < 2000.0, "{rows}");
+    assert!(!truncated
+        .details
+        .contains_key(COLLECTION_SPILLED_ROWS_DETAIL));
+    assert_ne!(truncated.metric, complete.metric);
+
+    // The histogram query orders its buckets, so runs keep the same prefix
+    let again = evaluate(&ctx, low_budget()).await;
+    assert_eq!(again.metric, truncated.metric);
+    assert_eq!(again.details, truncated.details);
+}
+
+#[tokio::test]
+async fn test_spilling_keeps_the_complete_histogram() {
+    let ctx = create_context().await;
+    let spill_dir = tempfile::tempdir().unwrap();
+
+    let complete = evaluate(&ctx, CollectionLimits::default()).await;
+    let spilled = evaluate(&ctx, low_budget().spill_to(spill_dir.path())).await;
+    assert_eq!(spilled.metric, complete.metric);
+    assert!(!spilled.details.contains_key(COLLECTION_TRUNCATED_DETAIL));
+    assert!(spilled.details[COLLECTION_SPILLED_ROWS_DETAIL] > 0.0);
+
+    // Spill files are removed once the constraint has read them
+    assert_eq!(std::fs::read_dir(spill_dir.path()).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn test_suite_applies_its_collection_limits() {
+    let ctx = create_context().await;
+    let suite = ValidationSuite::builder("events")
+        .table_name("events")
+        .collection_limits(low_budget())
+        .check(
+            Check::builder("categories")
+                .has_histogram("category", Arc::new(|hist| hist.bucket_count() > 0))
+                .build(),
+        )
+        .build();
+    assert_eq!(suite.collection_limits(), &low_budget());
+
+    let result = suite.run(&ctx).await.unwrap();
+    let truncated = evaluate(&ctx, low_budget()).await;
+    assert_eq!(result.report().outcomes[0].metric, truncated.metric);
+}
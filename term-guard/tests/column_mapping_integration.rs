@@ -0,0 +1,152 @@
+//! Integration tests for validating tables through a column mapping.
+
+use datafusion::prelude::*;
+use std::collections::HashMap;
+use term_guard::constraints::Assertion;
+use term_guard::core::{Check, ConstraintOptions, Level, RunOptions, ValidationSuite};
+use term_guard::error::TermError;
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    for sql in [
+        "CREATE TABLE vendor_a (cust_id INT, mail VARCHAR, amount DOUBLE) AS VALUES
+            (1, 'a@example.com', 10.0), (2, NULL, 20.0), (3, 'c@example.com', 30.0)",
+        "CREATE TABLE vendor_b (customer_id INT, email VARCHAR, amount DOUBLE) AS VALUES
+            (1, 'a@example.com', 10.0), (2, 'b@example.com', 20.0)",
+    ] {
+        ctx.sql(sql).await.unwrap().collect().await.unwrap();
+    }
+    ctx
+}
+
+fn customer_suite(table: &str) -> ValidationSuite {
+    ValidationSuite::builder("customers")
+        .table_name(table)
+        .check(
+            Check::builder("customers")
+                .level(Level::Error)
+                .completeness("customer_id", ConstraintOptions::new().with_threshold(1.0))
+                .completeness("email", ConstraintOptions::new().with_threshold(1.0))
+                .has_min("amount", Assertion::GreaterThan(0.0))
+                .build(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_one_suite_validates_differently_named_sources() {
+    let ctx = create_context().await;
+
+    let result = customer_suite("vendor_b").run(&ctx).await.unwrap();
+    assert!(result.is_success());
+
+    let result = customer_suite("vendor_a")
+        .run_with(
+            &ctx,
+            &RunOptions::new().with_column_mapping(HashMap::from([
+                ("customer_id", "cust_id"),
+                ("email", "mail"),
+            ])),
+        )
+        .await
+        .unwrap();
+    assert!(!result.is_success());
+    let report = result.report();
+    assert_eq!(report.metrics.failed_checks, 1);
+    let passed = report
+        .outcomes
+        .iter()
+        .filter(|outcome| outcome.status.is_success())
+        .count();
+    assert_eq!(passed, 2);
+    assert_eq!(report.column_mapping["email"], "mail");
+
+    // Reports use the logical name and mention the physical column
+    let issue = &report.issues[0];
+    assert!(issue.message.contains("email"), "{}", issue.message);
+    assert!(
+        issue
+            .message
+            .contains("(physical columns: email from mail)"),
+        "{}",
+        issue.message
+    );
+
+    // The physical table is registered again after the run
+    let schema = ctx.table("vendor_a").await.unwrap().schema().clone();
+    assert!(schema.field_with_unqualified_name("cust_id").is_ok());
+}
+
+#[tokio::test]
+async fn test_builder_mapping_applies_to_every_run() {
+    let ctx = create_context().await;
+    let suite = ValidationSuite::builder("customers")
+        .table_name("vendor_a")
+        .with_column_mapping([("customer_id", "cust_id")])
+        .check(
+            Check::builder("ids")
+                .validates_uniqueness(["customer_id"], 1.0)
+                .has_size(Assertion::Equals(3.0))
+                .build(),
+        )
+        .build();
+
+    for _ in 0..2 {
+        let result = suite.run(&ctx).await.unwrap();
+        assert!(result.is_success());
+    }
+
+    // A per-run mapping replaces the suite's mapping
+    let err = suite
+        .run_with(
+            &ctx,
+            &RunOptions::new().with_column_mapping([("customer_id", "id")]),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, TermError::InvalidConfiguration(_)));
+    assert!(err.to_string().contains("customer_id -> id"), "{err}");
+    assert!(ctx.table_exist("vendor_a").unwrap());
+}
+
+#[tokio::test]
+async fn test_unmapped_columns_fail_fast() {
+    let ctx = create_context().await;
+
+    let err = customer_suite("vendor_a")
+        .run_with(
+            &ctx,
+            &RunOptions::new().with_column_mapping([("customer_id", "cust_id")]),
+        )
+        .await
+        .unwrap_err();
+    let TermError::InvalidConfiguration(report) = &err else {
+        panic!("expected an invalid configuration, got {err}");
+    };
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].path, "column_mapping.email");
+    assert!(
+        err.to_string().contains(
+            "Column 'email' referenced by the suite is not in table 'vendor_a' and has no mapping"
+        ),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn test_mapping_over_derived_table() {
+    let ctx = create_context().await;
+    let suite = ValidationSuite::builder("positive")
+        .derived_table("positive", "SELECT * FROM vendor_a WHERE amount > 15")
+        .with_column_mapping([("customer_id", "cust_id")])
+        .check(
+            Check::builder("ids")
+                .completeness("customer_id", ConstraintOptions::new().with_threshold(1.0))
+                .has_size(Assertion::Equals(2.0))
+                .build(),
+        )
+        .build();
+
+    assert!(suite.run(&ctx).await.unwrap().is_success());
+    assert!(!ctx.table_exist("positive").unwrap());
+}
@@ -45,18 +45,24 @@ async fn test_unified_completeness_backward_compatibility() {
                 .level(Level::Warning)
                 .constraint(CompletenessConstraint::complete("c_custkey"))
                 .constraint(CompletenessConstraint::with_threshold("c_name", 0.95))
-                .constraint(CompletenessConstraint::new(
-                    vec!["c_custkey", "c_name"],
-                    ConstraintOptions::new()
-                        .with_operator(LogicalOperator::All)
-                        .with_threshold(1.0),
-                ))
-                .constraint(CompletenessConstraint::new(
-                    vec!["c_phone", "c_comment"],
-                    ConstraintOptions::new()
-                        .with_operator(LogicalOperator::Any)
-                        .with_threshold(1.0),
-                ))
+                .constraint(
+                    CompletenessConstraint::new(
+                        vec!["c_custkey", "c_name"],
+                        ConstraintOptions::new()
+                            .with_operator(LogicalOperator::All)
+                            .with_threshold(1.0),
+                    )
+                    .unwrap(),
+                )
+                .constraint(
+                    CompletenessConstraint::new(
+                        vec!["c_phone", "c_comment"],
+                        ConstraintOptions::new()
+                            .with_operator(LogicalOperator::Any)
+                            .with_threshold(1.0),
+                    )
+                    .unwrap(),
+                )
                 .build(),
         )
         .build();
@@ -77,12 +83,15 @@ async fn test_unified_completeness_all_operator() {
             Check::builder("key_fields_complete")
                 .level(Level::Error)
                 // All key fields must be 100% complete
-                .constraint(CompletenessConstraint::new(
-                    vec!["l_orderkey", "l_partkey", "l_suppkey"],
-                    ConstraintOptions::new()
-                        .with_operator(LogicalOperator::All)
-                        .with_threshold(1.0),
-                ))
+                .constraint(
+                    CompletenessConstraint::new(
+                        vec!["l_orderkey", "l_partkey", "l_suppkey"],
+                        ConstraintOptions::new()
+                            .with_operator(LogicalOperator::All)
+                            .with_threshold(1.0),
+                    )
+                    .unwrap(),
+                )
                 .build(),
         )
         .build();
@@ -145,12 +154,15 @@ async fn test_unified_completeness_any_operator() {
             Check::builder("contact_method_available")
                 .level(Level::Warning)
                 // At least one contact method must be available (100% for that column)
-                .constraint(CompletenessConstraint::new(
-                    vec!["email", "phone", "address"],
-                    ConstraintOptions::new()
-                        .with_operator(LogicalOperator::Any)
-                        .with_threshold(0.4), // At least 40% complete in any column
-                ))
+                .constraint(
+                    CompletenessConstraint::new(
+                        vec!["email", "phone", "address"],
+                        ConstraintOptions::new()
+                            .with_operator(LogicalOperator::Any)
+                            .with_threshold(0.4), // At least 40% complete in any column
+                    )
+                    .unwrap(),
+                )
                 .build(),
         )
         .build();
@@ -171,18 +183,21 @@ async fn test_unified_completeness_at_least_operator() {
             Check::builder("order_fields_completeness")
                 .level(Level::Info)
                 // At least 3 out of these 5 fields must be 100% complete
-                .constraint(CompletenessConstraint::new(
-                    vec![
-                        "o_orderkey",
-                        "o_custkey",
-                        "o_orderstatus",
-                        "o_totalprice",
-                        "o_orderdate",
-                    ],
-                    ConstraintOptions::new()
-                        .with_operator(LogicalOperator::AtLeast(3))
-                        .with_threshold(1.0),
-                ))
+                .constraint(
+                    CompletenessConstraint::new(
+                        vec![
+                            "o_orderkey",
+                            "o_custkey",
+                            "o_orderstatus",
+                            "o_totalprice",
+                            "o_orderdate",
+                        ],
+                        ConstraintOptions::new()
+                            .with_operator(LogicalOperator::AtLeast(3))
+                            .with_threshold(1.0),
+                    )
+                    .unwrap(),
+                )
                 .build(),
         )
         .build();
@@ -233,12 +248,15 @@ async fn test_unified_completeness_exactly_operator() {
             Check::builder("exactly_two_complete")
                 .level(Level::Info)
                 // Exactly 2 columns must be 100% complete
-                .constraint(CompletenessConstraint::new(
-                    vec!["col1", "col2", "col3", "col4"],
-                    ConstraintOptions::new()
-                        .with_operator(LogicalOperator::Exactly(2))
-                        .with_threshold(1.0),
-                ))
+                .constraint(
+                    CompletenessConstraint::new(
+                        vec!["col1", "col2", "col3", "col4"],
+                        ConstraintOptions::new()
+                            .with_operator(LogicalOperator::Exactly(2))
+                            .with_threshold(1.0),
+                    )
+                    .unwrap(),
+                )
                 .build(),
         )
         .build();
@@ -289,12 +307,15 @@ async fn test_unified_completeness_at_most_operator() {
             Check::builder("optional_fields_limit")
                 .level(Level::Warning)
                 // At most 2 optional fields should be fully complete
-                .constraint(CompletenessConstraint::new(
-                    vec!["optional_1", "optional_2", "optional_3"],
-                    ConstraintOptions::new()
-                        .with_operator(LogicalOperator::AtMost(2))
-                        .with_threshold(1.0),
-                ))
+                .constraint(
+                    CompletenessConstraint::new(
+                        vec!["optional_1", "optional_2", "optional_3"],
+                        ConstraintOptions::new()
+                            .with_operator(LogicalOperator::AtMost(2))
+                            .with_threshold(1.0),
+                    )
+                    .unwrap(),
+                )
                 .build(),
         )
         .build();
@@ -332,12 +353,15 @@ async fn test_unified_completeness_failure_scenarios() {
             Check::builder("completeness_failures")
                 .level(Level::Error)
                 // This should fail - both columns must be 80% complete
-                .constraint(CompletenessConstraint::new(
-                    vec!["col1", "col2"],
-                    ConstraintOptions::new()
-                        .with_operator(LogicalOperator::All)
-                        .with_threshold(0.8),
-                ))
+                .constraint(
+                    CompletenessConstraint::new(
+                        vec!["col1", "col2"],
+                        ConstraintOptions::new()
+                            .with_operator(LogicalOperator::All)
+                            .with_threshold(0.8),
+                    )
+                    .unwrap(),
+                )
                 .build(),
         )
         .build();
@@ -365,19 +389,25 @@ async fn test_unified_completeness_with_varying_thresholds() {
             Check::builder("supplier_completeness")
                 .level(Level::Info)
                 // At least 2 columns must be 95% complete
-                .constraint(CompletenessConstraint::new(
-                    vec!["s_name", "s_address", "s_phone", "s_comment"],
-                    ConstraintOptions::new()
-                        .with_operator(LogicalOperator::AtLeast(2))
-                        .with_threshold(0.95),
-                ))
+                .constraint(
+                    CompletenessConstraint::new(
+                        vec!["s_name", "s_address", "s_phone", "s_comment"],
+                        ConstraintOptions::new()
+                            .with_operator(LogicalOperator::AtLeast(2))
+                            .with_threshold(0.95),
+                    )
+                    .unwrap(),
+                )
                 // All critical fields must be 100% complete
-                .constraint(CompletenessConstraint::new(
-                    vec!["s_suppkey", "s_name"],
-                    ConstraintOptions::new()
-                        .with_operator(LogicalOperator::All)
-                        .with_threshold(1.0),
-                ))
+                .constraint(
+                    CompletenessConstraint::new(
+                        vec!["s_suppkey", "s_name"],
+                        ConstraintOptions::new()
+                            .with_operator(LogicalOperator::All)
+                            .with_threshold(1.0),
+                    )
+                    .unwrap(),
+                )
                 .build(),
         )
         .build();
@@ -402,12 +432,15 @@ async fn test_unified_completeness_performance_comparison() {
         .check(
             Check::builder("unified_performance")
                 .constraint(CompletenessConstraint::with_threshold("l_quantity", 0.99))
-                .constraint(CompletenessConstraint::new(
-                    vec!["l_orderkey", "l_partkey", "l_suppkey", "l_linenumber"],
-                    ConstraintOptions::new()
-                        .with_operator(LogicalOperator::All)
-                        .with_threshold(1.0),
-                ))
+                .constraint(
+                    CompletenessConstraint::new(
+                        vec!["l_orderkey", "l_partkey", "l_suppkey", "l_linenumber"],
+                        ConstraintOptions::new()
+                            .with_operator(LogicalOperator::All)
+                            .with_threshold(1.0),
+                    )
+                    .unwrap(),
+                )
                 .build(),
         )
         .build();
@@ -0,0 +1,113 @@
+//! Integration tests for reporting every configuration problem of a suite at once.
+
+use datafusion::prelude::*;
+use term_guard::constraints::Assertion;
+use term_guard::core::builder_extensions::CompletenessOptions;
+use term_guard::core::{
+    Check, ConfigReport, ConfigSeverity, ConstraintOptions, ParameterBag, RunOptions,
+    ValidationSuite, ValidationSuiteBuilder,
+};
+use term_guard::error::TermError;
+
+/// A suite with two invalid thresholds, an invalid UDF name and two
+/// parameterized constraints.
+fn suite_with_problems() -> ValidationSuiteBuilder {
+    let upper = datafusion::functions::string::upper().as_ref().clone();
+    ValidationSuite::builder("customers")
+        .table_name("missing_table")
+        .with_udf("Upper-Case", upper)
+        .check(
+            Check::builder("contact")
+                .completeness("email", ConstraintOptions::new().with_threshold(95.0))
+                .validates_email("email", 1.5)
+                .build(),
+        )
+        .check(
+            Check::builder("volume")
+                .has_size(Assertion::between_params("min_rows", "max_rows"))
+                .completeness(
+                    "email",
+                    CompletenessOptions::threshold_param("email_completeness")
+                        .into_constraint_options(),
+                )
+                .build(),
+        )
+}
+
+fn into_report(err: TermError) -> ConfigReport {
+    match err {
+        TermError::InvalidConfiguration(report) => *report,
+        other => panic!("expected an invalid configuration, got {other}"),
+    }
+}
+
+#[tokio::test]
+async fn test_every_problem_is_reported_at_once() {
+    let ctx = SessionContext::new();
+    let parameters = ParameterBag::new()
+        .with("min_rows", 1.0)
+        .with("email_completeness", 1.5);
+
+    // The table does not exist, so the run must fail on the configuration
+    let report = into_report(
+        suite_with_problems()
+            .build()
+            .run_with(&ctx, &RunOptions::new().with_parameters(parameters))
+            .await
+            .unwrap_err(),
+    );
+    assert_eq!(report.subject, "suite 'customers'");
+    assert_eq!(report.errors().count(), 5, "{report}");
+
+    let located: Vec<_> = report
+        .issues
+        .iter()
+        .map(|issue| {
+            (
+                issue.path.as_str(),
+                issue.check.as_deref(),
+                issue.constraint_index,
+            )
+        })
+        .collect();
+    assert_eq!(
+        located,
+        [
+            ("checks[0].constraints[0]", Some("contact"), Some(0)),
+            ("checks[0].constraints[1]", Some("contact"), Some(1)),
+            ("udfs[0]", None, None),
+            ("parameters.max_rows", Some("volume"), Some(0)),
+            ("parameters.email_completeness", Some("volume"), Some(1)),
+        ]
+    );
+    assert!(report.issues[2]
+        .message
+        .contains("Invalid function name 'Upper-Case'"));
+    assert!(report.issues[3]
+        .message
+        .contains("Missing value for parameter 'max_rows'"));
+    assert!(report.issues[4]
+        .message
+        .contains("Parameter 'email_completeness' = 1.5 is out of range"));
+
+    let message = report.to_string();
+    assert!(message.starts_with("suite 'customers' has 5 errors\n"));
+    assert_eq!(message.lines().count(), 6, "{message}");
+    assert!(message.contains("  - error at parameters.max_rows: Missing value"));
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!(json["issues"].as_array().unwrap().len(), 5);
+    assert_eq!(json["issues"][0]["severity"], "error");
+    assert_eq!(json["issues"][4]["constraint_index"], 1);
+}
+
+#[test]
+fn test_try_build_reports_check_and_udf_problems() {
+    // Parameters are only known when the suite runs
+    let report = into_report(suite_with_problems().try_build().unwrap_err());
+    assert_eq!(report.errors().count(), 3, "{report}");
+    assert!(report
+        .issues
+        .iter()
+        .all(|issue| issue.severity == ConfigSeverity::Error));
+}
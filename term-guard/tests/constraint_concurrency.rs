@@ -0,0 +1,167 @@
+//! Concurrent evaluation of the built-in constraints.
+//!
+//! Every constraint family is evaluated many times at once against a shared
+//! context; each evaluation must return the same result as a sequential one.
+
+use datafusion::prelude::*;
+use term_guard::constraints::{
+    ApproxCountDistinctConstraint, Assertion, CompletenessConstraint, ContainmentConstraint,
+    CorrelationConstraint, CrossTableSumConstraint, CustomSqlConstraint, DataTypeConstraint,
+    ForeignKeyConstraint, FormatConstraint, FormatOptions, FormatType, LengthConstraint,
+    QuantileConstraint, SizeConstraint, StatisticalConstraint, UniquenessConstraint,
+};
+use term_guard::core::{ConstraintResult, ConstraintStatus};
+use term_guard::prelude::*;
+use term_guard::test_utils::assert_constraint_concurrent_safe;
+
+const PARALLELISM: usize = 16;
+
+async fn create_context() -> Result<SessionContext> {
+    let ctx = SessionContext::new();
+    for sql in [
+        "CREATE TABLE data (id BIGINT, customer_id BIGINT, email VARCHAR, status VARCHAR, amount DOUBLE, score DOUBLE) AS VALUES
+            (1, 10, 'a@example.com', 'open', 10.0, 1.0),
+            (2, 10, 'b@example.com', 'closed', 20.5, 2.1),
+            (3, 11, 'not-an-email', 'open', 30.25, 2.9),
+            (4, 12, NULL, 'pending', 40.0, 4.2),
+            (5, 13, 'e@example.com', 'closed', 55.5, 5.0)",
+        "CREATE TABLE customers (id BIGINT) AS VALUES (10), (11), (12), (13)",
+        "CREATE TABLE orders (total DOUBLE) AS VALUES (100.0), (50.5)",
+        "CREATE TABLE payments (amount DOUBLE) AS VALUES (75.0), (75.5)",
+    ] {
+        ctx.sql(sql).await?.collect().await?;
+    }
+    Ok(ctx)
+}
+
+async fn assert_status(
+    constraint: impl term_guard::core::Constraint + 'static,
+    expected: ConstraintStatus,
+) -> Result<ConstraintResult> {
+    let ctx = create_context().await?;
+    let result = assert_constraint_concurrent_safe(constraint, &ctx, PARALLELISM).await?;
+    assert_eq!(result.status, expected, "{result:?}");
+    Ok(result)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_completeness_and_size() -> Result<()> {
+    assert_status(
+        CompletenessConstraint::with_threshold("email", 0.9),
+        ConstraintStatus::Failure,
+    )
+    .await?;
+    assert_status(
+        SizeConstraint::new(Assertion::Equals(5.0)),
+        ConstraintStatus::Success,
+    )
+    .await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_uniqueness_and_cardinality() -> Result<()> {
+    assert_status(
+        UniquenessConstraint::full_uniqueness("id", 1.0)?,
+        ConstraintStatus::Success,
+    )
+    .await?;
+    assert_status(
+        UniquenessConstraint::full_uniqueness("customer_id", 1.0)?,
+        ConstraintStatus::Failure,
+    )
+    .await?;
+    assert_status(
+        ApproxCountDistinctConstraint::new("status", Assertion::Equals(3.0)),
+        ConstraintStatus::Success,
+    )
+    .await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_containment_and_length() -> Result<()> {
+    assert_status(
+        ContainmentConstraint::new("status", vec!["open", "closed"]),
+        ConstraintStatus::Failure,
+    )
+    .await?;
+    assert_status(
+        LengthConstraint::between("status", 4, 7),
+        ConstraintStatus::Success,
+    )
+    .await?;
+    Ok(())
+}
+
+// Format and custom SQL constraints share process-wide regex caches
+#[tokio::test(flavor = "multi_thread")]
+async fn test_format_and_custom_sql() -> Result<()> {
+    assert_status(
+        FormatConstraint::new("email", FormatType::Email, 0.9, FormatOptions::default())?,
+        ConstraintStatus::Failure,
+    )
+    .await?;
+    assert_status(
+        FormatConstraint::new(
+            "status",
+            FormatType::Regex("^[a-z]+$".to_string()),
+            1.0,
+            FormatOptions::default(),
+        )?,
+        ConstraintStatus::Success,
+    )
+    .await?;
+    assert_status(
+        CustomSqlConstraint::new("amount > 0", None::<String>)?,
+        ConstraintStatus::Success,
+    )
+    .await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_statistics() -> Result<()> {
+    let mean = assert_status(
+        StatisticalConstraint::mean("amount", Assertion::GreaterThan(0.0))?,
+        ConstraintStatus::Success,
+    )
+    .await?;
+    assert!((mean.metric.unwrap() - 31.25).abs() < 1e-9);
+    assert_status(
+        StatisticalConstraint::standard_deviation("amount", Assertion::LessThan(1.0))?,
+        ConstraintStatus::Failure,
+    )
+    .await?;
+    assert_status(
+        QuantileConstraint::median("amount", Assertion::Between(20.0, 41.0))?,
+        ConstraintStatus::Success,
+    )
+    .await?;
+    assert_status(
+        CorrelationConstraint::pearson("amount", "score", Assertion::GreaterThan(0.9))?,
+        ConstraintStatus::Success,
+    )
+    .await?;
+    assert_status(
+        DataTypeConstraint::non_negative("amount")?,
+        ConstraintStatus::Success,
+    )
+    .await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multi_table() -> Result<()> {
+    assert_status(
+        ForeignKeyConstraint::new("data.customer_id", "customers.id"),
+        ConstraintStatus::Success,
+    )
+    .await?;
+    assert_status(
+        CrossTableSumConstraint::new("orders.total", "payments.amount"),
+        ConstraintStatus::Success,
+    )
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//! Golden-file test for the data contract generated from a validation suite.
+
+use term_guard::constraints::{Assertion, ContainmentConstraint};
+use term_guard::core::{Check, ConstraintOptions, Level, ValidationSuite};
+use term_guard::formatters::ContractFormatter;
+
+const GOLDEN: &str = include_str!("fixtures/contract/customer_contract.md");
+
+fn customer_suite() -> ValidationSuite {
+    ValidationSuite::builder("customer_contract")
+        .table_name("customers")
+        .description("Customer master data published to the CRM every night.")
+        .check(
+            Check::builder("identity")
+                .level(Level::Error)
+                .description("Every customer is identified exactly once.")
+                .validates_uniqueness(vec!["customer_id"], 1.0)
+                .completeness("customer_id", ConstraintOptions::new().with_threshold(1.0))
+                .build(),
+        )
+        .check(
+            Check::builder("contact_details")
+                .level(Level::Warning)
+                .description("Contact details are usable for outreach.")
+                .validates_email("email", 0.99)
+                .has_length_between("name", 1, 100)
+                .constraint(ContainmentConstraint::new(
+                    "status",
+                    vec!["active", "inactive"],
+                ))
+                .build(),
+        )
+        .check(
+            Check::builder("business_rules")
+                .level(Level::Info)
+                .has_size(Assertion::GreaterThanOrEqual(100.0))
+                .has_mean("lifetime_value", Assertion::Between(0.0, 10000.0))
+                .foreign_key("customers.region_id", "regions.id")
+                .satisfies("signup_date <= CURRENT_DATE", None::<String>)
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn test_contract_matches_golden_file() {
+    let contract = ContractFormatter::new().format(&customer_suite()).unwrap();
+    // Set UPDATE_GOLDEN=1 to rewrite the golden file after an intended change
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/contract/customer_contract.md"
+            ),
+            &contract,
+        )
+        .unwrap();
+        return;
+    }
+    assert_eq!(contract, GOLDEN);
+}
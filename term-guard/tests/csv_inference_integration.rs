@@ -0,0 +1,153 @@
+//! Integration tests for type inference across the files of a CSV glob.
+//!
+//! The fixture `tests/fixtures/csv_inference` holds eight files of events. Every
+//! file but `events-07.csv` has integer ids, which has ids like `A7-001`, and
+//! `events-03.csv` has one fractional amount.
+
+use arrow::array::{Array, Float64Array, Int64Array, StringArray};
+use arrow::datatypes::DataType;
+use datafusion::prelude::*;
+use std::path::Path;
+use term_guard::sources::{CsvSource, DataSource};
+
+fn fixture_glob() -> String {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/csv_inference");
+    format!("{}/events-*.csv", dir.display())
+}
+
+#[tokio::test]
+async fn test_conflicting_types_are_widened() {
+    let source = CsvSource::from_glob(fixture_glob()).await.unwrap();
+    let ctx = SessionContext::new();
+    source.register(&ctx, "events").await.unwrap();
+
+    let report = source.inference_report().unwrap();
+    assert_eq!(report.file_count, 8);
+    assert_eq!(report.sample_rows, 1000);
+    assert_eq!(report.conflicts.len(), 2);
+
+    let id = &report.conflicts[0];
+    assert_eq!(id.column, "id");
+    assert_eq!(id.resolved, DataType::Utf8);
+    assert!(id.files[0].0.ends_with("events-01.csv"));
+    assert_eq!(id.files[0].1, DataType::Int64);
+    assert!(id.files[6].0.ends_with("events-07.csv"));
+    assert_eq!(id.files[6].1, DataType::Utf8);
+    let amount = &report.conflicts[1];
+    assert_eq!(amount.column, "amount");
+    assert_eq!(amount.resolved, DataType::Float64);
+
+    let description = source.description();
+    assert!(
+        description.contains(
+            "column 'id' is Int64 in events-01.csv, events-02.csv, events-03.csv and 4 more, \
+             Utf8 in events-07.csv, read as Utf8"
+        ),
+        "{description}"
+    );
+    assert!(description.contains("column 'amount' is Int64 in events-01.csv"));
+
+    // Every file is read with the widened types, including the rows of the last file
+    let batches = ctx
+        .sql("SELECT COUNT(*), SUM(amount), COUNT(*) FILTER (WHERE id LIKE 'A7-%') FROM events")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    let count = |column: usize| {
+        batches[0]
+            .column(column)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap()
+            .value(0)
+    };
+    assert_eq!(count(0), 24);
+    assert_eq!(count(2), 3);
+    let total = batches[0]
+        .column(1)
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .unwrap();
+    assert_eq!(total.value(0), 1108.5);
+
+    let batches = ctx
+        .sql("SELECT id FROM events ORDER BY id LIMIT 1")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    let ids = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(ids.value(0), "101");
+}
+
+#[tokio::test]
+async fn test_strict_inference_fails_before_registration() {
+    let source = CsvSource::from_glob(fixture_glob())
+        .await
+        .unwrap()
+        .strict_inference(true);
+
+    // Conflicts are reported upfront, naming the files
+    let error = source.infer_types().await.unwrap_err().to_string();
+    assert!(error.contains("conflicting inferred types"), "{error}");
+    assert!(error.contains("Utf8 in events-07.csv"), "{error}");
+    assert!(error.contains("Float64 in events-03.csv"), "{error}");
+
+    let ctx = SessionContext::new();
+    let error = source
+        .register(&ctx, "events")
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("events-07.csv"), "{error}");
+    assert!(!ctx.table_exist("events").unwrap());
+    assert!(source.schema_report().is_none());
+}
+
+#[tokio::test]
+async fn test_inference_reads_every_file() {
+    // One file at a time finds the same conflicts as the default concurrency
+    let source = CsvSource::from_glob(fixture_glob())
+        .await
+        .unwrap()
+        .inference_concurrency(1);
+    let sequential = source.infer_types().await.unwrap();
+    let concurrent = CsvSource::from_glob(fixture_glob())
+        .await
+        .unwrap()
+        .infer_types()
+        .await
+        .unwrap();
+    assert_eq!(sequential, concurrent);
+    assert_eq!(source.inference_report(), Some(sequential));
+
+    // Files that agree have no conflicts and strict inference accepts them
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/csv_inference");
+    let source = CsvSource::from_paths(vec![
+        dir.join("events-01.csv").display().to_string(),
+        dir.join("events-02.csv").display().to_string(),
+    ])
+    .unwrap()
+    .strict_inference(true);
+    let ctx = SessionContext::new();
+    source.register(&ctx, "events").await.unwrap();
+    let report = source.inference_report().unwrap();
+    assert!(!report.has_conflicts());
+    assert!(!source.description().contains("conflicts"));
+
+    let batches = ctx
+        .sql("SELECT id FROM events")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    assert_eq!(batches[0].column(0).data_type(), &DataType::Int64);
+}
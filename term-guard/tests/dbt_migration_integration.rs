@@ -0,0 +1,136 @@
+//! Integration tests for the conversion of dbt schema tests.
+
+#[cfg(feature = "migration")]
+mod dbt_migration_tests {
+    use datafusion::prelude::*;
+    use term_guard::core::{Level, ValidationSuite};
+    use term_guard::migration::from_dbt_schema_yaml;
+
+    const JAFFLE_SHOP: &str = include_str!("fixtures/dbt/jaffle_shop_schema.yml");
+    const SOURCES: &str = include_str!("fixtures/dbt/sources.yml");
+
+    fn checks(suite: &ValidationSuite) -> Vec<(&str, Level, usize)> {
+        suite
+            .checks()
+            .iter()
+            .map(|check| (check.name(), check.level(), check.constraints().len()))
+            .collect()
+    }
+
+    #[test]
+    fn test_jaffle_shop_models_are_converted() {
+        let migration = from_dbt_schema_yaml(JAFFLE_SHOP).unwrap();
+        assert!(migration.warnings.is_empty(), "{:?}", migration.warnings);
+
+        let tables: Vec<_> = migration.suites.iter().map(|(table, _)| table).collect();
+        assert_eq!(tables, ["customers", "orders"]);
+
+        let (_, customers) = &migration.suites[0];
+        assert_eq!(customers.table_name(), "customers");
+        assert_eq!(checks(customers), [("customer_id", Level::Error, 2)]);
+
+        let (_, orders) = &migration.suites[1];
+        assert_eq!(
+            checks(orders),
+            [
+                ("order_id", Level::Error, 2),
+                ("customer_id", Level::Error, 2),
+                ("status", Level::Error, 1),
+                ("amount", Level::Error, 1),
+                ("credit_card_amount", Level::Error, 1),
+                ("coupon_amount", Level::Error, 1),
+                ("bank_transfer_amount", Level::Error, 1),
+                ("gift_card_amount", Level::Error, 1),
+            ]
+        );
+        assert_eq!(
+            orders.checks()[1].constraints()[1].describe(),
+            "orders.customer_id must reference an existing customers.customer_id or be null"
+        );
+    }
+
+    #[test]
+    fn test_sources_keep_severity_and_report_unsupported_tests() {
+        let migration = from_dbt_schema_yaml(SOURCES).unwrap();
+        let tables: Vec<_> = migration.suites.iter().map(|(table, _)| table).collect();
+        assert_eq!(tables, ["raw_customers", "raw_orders", "payment"]);
+
+        assert_eq!(
+            checks(&migration.suites[1].1),
+            [
+                ("id", Level::Error, 2),
+                ("user_id", Level::Error, 1),
+                ("status (warn)", Level::Warning, 1),
+            ]
+        );
+        assert_eq!(
+            migration.suites[1].1.checks()[1].constraints()[0].describe(),
+            "raw_orders.user_id must reference an existing raw_customers.id or be null"
+        );
+
+        assert_eq!(
+            checks(&migration.suites[2].1),
+            [
+                ("id", Level::Error, 1),
+                ("orderid (warn)", Level::Warning, 1),
+                ("orderid", Level::Error, 1),
+            ]
+        );
+
+        let warnings: Vec<_> = migration.warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            warnings,
+            [
+                "check 'raw_orders', constraint dbt_utils.recency: dbt_utils.recency is not a built-in dbt test",
+                "check 'raw_orders.status', constraint not_null: tests configured with 'where' have no Term equivalent",
+                "check 'payment.paymentmethod', constraint dbt_expectations.expect_column_values_to_be_in_set: dbt_expectations.expect_column_values_to_be_in_set is not a built-in dbt test",
+            ]
+        );
+    }
+
+    async fn create_context(orphan_customer: bool) -> SessionContext {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE TABLE customers (customer_id INT, first_name VARCHAR) AS VALUES
+                (1, 'Michael'), (2, 'Shawn'), (3, 'Kathleen')",
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+        let customer = if orphan_customer { 9 } else { 3 };
+        ctx.sql(&format!(
+            "CREATE TABLE orders (order_id INT, customer_id INT, status VARCHAR, amount DOUBLE,
+                credit_card_amount DOUBLE, coupon_amount DOUBLE, bank_transfer_amount DOUBLE,
+                gift_card_amount DOUBLE) AS VALUES
+                (1, 1, 'returned', 10.0, 10.0, 0.0, 0.0, 0.0),
+                (2, 2, 'completed', 20.0, 0.0, 20.0, 0.0, 0.0),
+                (3, {customer}, 'placed', 30.0, 0.0, 0.0, 15.0, 15.0)"
+        ))
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_converted_suites_run() {
+        let migration = from_dbt_schema_yaml(JAFFLE_SHOP).unwrap();
+
+        let ctx = create_context(false).await;
+        for (table, suite) in &migration.suites {
+            let result = suite.run(&ctx).await.unwrap();
+            assert!(result.is_success(), "{table}: {:?}", result.report().issues);
+        }
+
+        let ctx = create_context(true).await;
+        let result = migration.suites[1].1.run(&ctx).await.unwrap();
+        assert!(result.is_failure());
+        let issues = &result.report().issues;
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].check_name, "customer_id");
+    }
+}
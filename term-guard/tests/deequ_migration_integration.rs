@@ -0,0 +1,137 @@
+//! Integration tests for the conversion of Deequ check definitions.
+
+#[cfg(feature = "migration")]
+mod deequ_migration_tests {
+    use datafusion::prelude::*;
+    use term_guard::core::{Constraint, Level};
+    use term_guard::migration::from_deequ_json;
+
+    const ORDERS: &str = include_str!("fixtures/deequ/orders_checks.json");
+    const CUSTOMERS: &str = include_str!("fixtures/deequ/customers_checks.json");
+
+    fn custom(constraint: &dyn Constraint, key: &str) -> String {
+        let metadata = constraint.metadata();
+        metadata
+            .custom
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| panic!("{} has no {key}: {metadata:?}", constraint.name()))
+    }
+
+    #[test]
+    fn test_orders_checks_are_converted() {
+        let migration = from_deequ_json(ORDERS).unwrap();
+        let suite = &migration.suite;
+        assert_eq!(suite.name(), "orders_daily");
+        assert_eq!(suite.checks().len(), 2);
+
+        let integrity = &suite.checks()[0];
+        assert_eq!(integrity.name(), "Order integrity");
+        assert_eq!(integrity.level(), Level::Error);
+        let expected = [
+            ("assertion", "greater than or equal to 1"),
+            ("threshold", "1"),
+            ("threshold", "1"),
+            ("threshold", "0.95"),
+            ("allowed_values", "[placed, shipped, delivered, cancelled]"),
+            ("expression", "amount >= 0"),
+        ];
+        assert_eq!(integrity.constraints().len(), expected.len());
+        for (constraint, (key, value)) in integrity.constraints().iter().zip(expected) {
+            assert_eq!(custom(constraint.as_ref(), key), value);
+        }
+        assert_eq!(
+            custom(integrity.constraints()[5].as_ref(), "hint"),
+            "non-negative amounts"
+        );
+        assert_eq!(
+            integrity.constraints()[3].metadata().columns,
+            vec!["customer_email"]
+        );
+
+        let amounts = &suite.checks()[1];
+        assert_eq!(amounts.level(), Level::Warning);
+        let expected = [
+            ("assertion", "greater than or equal to 0"),
+            ("assertion", "less than or equal to 10000"),
+            ("assertion", "between 10 and 500"),
+            ("assertion", "greater than 0"),
+            ("assertion", "less than 1000"),
+            ("threshold", "0.9"),
+            ("threshold", "0.8"),
+        ];
+        assert_eq!(amounts.constraints().len(), expected.len());
+        for (constraint, (key, value)) in amounts.constraints().iter().zip(expected) {
+            assert_eq!(custom(constraint.as_ref(), key), value);
+        }
+        assert_eq!(
+            amounts.constraints()[6].metadata().columns,
+            vec!["customer_email", "order_date"]
+        );
+
+        let warnings: Vec<_> = migration
+            .warnings
+            .iter()
+            .map(|w| (w.check.as_str(), w.constraint.as_str()))
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                ("Order amounts", "hasApproxQuantile(amount)"),
+                ("Order amounts", "hasCompleteness(coupon_code)"),
+            ]
+        );
+        assert_eq!(
+            migration.warnings[1].reason,
+            "the assertion 'less than 0.2' is not a minimum fraction of rows"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_constraints_do_not_fail_the_import() {
+        let migration = from_deequ_json(CUSTOMERS).unwrap();
+        let checks = migration.suite.checks();
+        assert_eq!(migration.suite.name(), "deequ_migration");
+        assert_eq!(checks[0].constraints().len(), 2);
+        assert_eq!(checks[1].constraints().len(), 1);
+
+        let warnings: Vec<_> = migration.warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            warnings,
+            vec![
+                "check 'Customer keys', constraint hasDataType(customer_id): hasDataType has no Term equivalent",
+                "check 'Customer profile', constraint isContainedIn(age): only isContainedIn with allowedValues is supported",
+                "check 'Customer profile', constraint satisfies: Term checks satisfies on all rows, not on a fraction of 0.99",
+                "check 'Customer profile', constraint hasMin(age): the assertion '_ >= 18 && _ <= 21' is not a comparison with a number",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_converted_suite_runs() {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE TABLE data (order_id INT, customer_email VARCHAR, status VARCHAR, amount DOUBLE, order_date VARCHAR, coupon_code VARCHAR) AS VALUES
+                (1, 'ana@example.com', 'placed', 25.0, '2024-05-01', NULL),
+                (2, 'ben@example.com', 'shipped', 120.5, '2024-05-01', 'SPRING'),
+                (3, 'ana@example.com', 'delivered', 42.0, '2024-05-02', NULL),
+                (4, 'cy@example.com', 'cancelled', 310.0, '2024-05-02', NULL)",
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+        let migration = from_deequ_json(ORDERS).unwrap();
+        let result = migration.suite.run(&ctx).await.unwrap();
+        assert!(result.is_success(), "{:?}", result.report().issues);
+        let report = result.report();
+        assert_eq!(report.metrics.passed_checks, migration.suite.checks().len());
+        assert_eq!(report.outcomes.len(), 13);
+        assert!(report
+            .outcomes
+            .iter()
+            .all(|outcome| outcome.status.is_success()));
+    }
+}
@@ -0,0 +1,178 @@
+//! Integration tests for the Delta Lake source.
+//!
+//! The fixture `tests/fixtures/delta/orders` is a Delta table partitioned by
+//! `region` with three versions:
+//! - `0`: orders 1 and 2 in `eu`, order 3 in `us` with a negative amount
+//! - `1`: appends orders 4 and 5 in `eu`
+//! - `2`: overwrites `us` with order 3 corrected and order 6
+
+#[cfg(feature = "delta")]
+mod delta_tests {
+    use arrow::array::Int64Array;
+    use chrono::{DateTime, Utc};
+    use datafusion::prelude::*;
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+    use term_guard::constraints::Assertion;
+    use term_guard::core::{Check, ValidationSuite};
+    use term_guard::sources::{DataSource, DeltaSource, SnapshotSelector};
+
+    fn fixture() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/delta/orders")
+    }
+
+    fn location(path: &Path) -> String {
+        path.to_string_lossy().to_string()
+    }
+
+    async fn count(ctx: &SessionContext, sql: &str) -> i64 {
+        let batches = ctx.sql(sql).await.unwrap().collect().await.unwrap();
+        batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap()
+            .value(0)
+    }
+
+    /// Copies the fixture, dating commit `n` to `2025-01-0{n+1}T00:00:00Z`.
+    ///
+    /// Timestamps select snapshots by commit time, which a checkout does not
+    /// preserve.
+    fn dated_copy(dir: &TempDir) -> PathBuf {
+        fn copy(from: &Path, to: &Path) {
+            std::fs::create_dir_all(to).unwrap();
+            for entry in std::fs::read_dir(from).unwrap() {
+                let entry = entry.unwrap();
+                let target = to.join(entry.file_name());
+                if entry.file_type().unwrap().is_dir() {
+                    copy(&entry.path(), &target);
+                } else {
+                    std::fs::copy(entry.path(), target).unwrap();
+                }
+            }
+        }
+
+        let table = dir.path().join("orders");
+        copy(&fixture(), &table);
+        for version in 0..3u64 {
+            let commit = table.join(format!("_delta_log/{version:020}.json"));
+            let committed_at =
+                SystemTime::UNIX_EPOCH + Duration::from_secs(1_735_689_600 + version * 86_400);
+            std::fs::File::options()
+                .write(true)
+                .open(commit)
+                .unwrap()
+                .set_modified(committed_at)
+                .unwrap();
+        }
+        table
+    }
+
+    #[tokio::test]
+    async fn test_versions_register_their_own_files() {
+        let ctx = SessionContext::new();
+        for (version, rows, files) in [(0, 3, 2), (1, 5, 3), (2, 6, 3)] {
+            let source = DeltaSource::new(location(&fixture())).version(version);
+            let table = format!("orders_v{version}");
+            source.register(&ctx, &table).await.unwrap();
+
+            let snapshot = source.snapshot().unwrap();
+            assert_eq!(snapshot.version, version);
+            assert_eq!(snapshot.files.len(), files);
+            assert_eq!(
+                count(&ctx, &format!("SELECT COUNT(*) FROM {table}")).await,
+                rows
+            );
+        }
+
+        // The removed file of version 0 is not read by later versions
+        assert_eq!(
+            count(&ctx, "SELECT COUNT(*) FROM orders_v2 WHERE amount < 0").await,
+            0
+        );
+
+        let latest = DeltaSource::new(location(&fixture()));
+        latest.register(&ctx, "orders").await.unwrap();
+        assert_eq!(latest.snapshot().unwrap().version, 2);
+        let schema = latest.schema().unwrap();
+        assert!(schema.field_with_name("region").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_validate_a_pinned_version() {
+        let suite = ValidationSuite::builder("orders")
+            .table_name("orders")
+            .check(
+                Check::builder("amounts")
+                    .has_min("amount", Assertion::GreaterThanOrEqual(0.0))
+                    .build(),
+            )
+            .build();
+
+        let ctx = SessionContext::new();
+        DeltaSource::new(location(&fixture()))
+            .version(1)
+            .register(&ctx, "orders")
+            .await
+            .unwrap();
+        assert!(suite.run(&ctx).await.unwrap().is_failure());
+
+        let ctx = SessionContext::new();
+        DeltaSource::new(location(&fixture()))
+            .version(2)
+            .register(&ctx, "orders")
+            .await
+            .unwrap();
+        assert!(suite.run(&ctx).await.unwrap().is_success());
+    }
+
+    #[tokio::test]
+    async fn test_timestamp_selects_the_last_earlier_commit() {
+        let dir = TempDir::new().unwrap();
+        let table = dated_copy(&dir);
+
+        let as_of: DateTime<Utc> = "2025-01-02T12:00:00Z".parse().unwrap();
+        let source = DeltaSource::new(location(&table)).timestamp(as_of);
+        let ctx = SessionContext::new();
+        source.register(&ctx, "orders").await.unwrap();
+
+        let snapshot = source.snapshot().unwrap();
+        assert_eq!(snapshot.selector, SnapshotSelector::AsOf(as_of));
+        assert_eq!(snapshot.version, 1);
+        assert_eq!(count(&ctx, "SELECT COUNT(*) FROM orders").await, 5);
+    }
+
+    #[tokio::test]
+    async fn test_partition_filter_of_derived_table() {
+        let ctx = SessionContext::new();
+        DeltaSource::new(location(&fixture()))
+            .register(&ctx, "orders")
+            .await
+            .unwrap();
+
+        let suite = ValidationSuite::builder("eu_orders")
+            .derived_table("eu_orders", "SELECT * FROM orders WHERE region = 'eu'")
+            .check(
+                Check::builder("size")
+                    .has_size(Assertion::Equals(4.0))
+                    .build(),
+            )
+            .build();
+        assert!(suite.run(&ctx).await.unwrap().is_success());
+    }
+
+    #[tokio::test]
+    async fn test_missing_version_is_reported() {
+        let source = DeltaSource::new(location(&fixture())).version(7);
+        let error = source
+            .register(&SessionContext::new(), "orders")
+            .await
+            .unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("version 7"), "{message}");
+        assert!(message.contains("orders"), "{message}");
+        assert!(source.snapshot().is_none());
+    }
+}
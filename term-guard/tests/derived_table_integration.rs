@@ -0,0 +1,110 @@
+//! Integration tests for validating derived tables defined by a query.
+
+use datafusion::prelude::*;
+use term_guard::constraints::Assertion;
+use term_guard::core::{Check, ConstraintOptions, Level, ValidationSuite};
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    for sql in [
+        "CREATE TABLE orders (order_id INT, customer_id INT, amount DOUBLE) AS VALUES
+            (1, 10, 25.0), (2, 11, 40.0), (3, 12, -5.0)",
+        "CREATE TABLE customers (customer_id INT, segment VARCHAR) AS VALUES
+            (10, 'retail'), (11, 'wholesale'), (12, NULL)",
+    ] {
+        ctx.sql(sql).await.unwrap().collect().await.unwrap();
+    }
+    ctx
+}
+
+fn segment_check() -> Check {
+    Check::builder("segments")
+        .level(Level::Error)
+        .completeness("segment", ConstraintOptions::new().with_threshold(0.6))
+        .has_size(Assertion::Equals(3.0))
+        .build()
+}
+
+#[tokio::test]
+async fn test_suite_validates_sql_view() {
+    let ctx = create_context().await;
+    let suite = ValidationSuite::builder("enriched_orders")
+        .derived_table(
+            "orders_with_segment",
+            "SELECT o.*, c.segment FROM orders o JOIN customers c USING (customer_id)",
+        )
+        .check(segment_check())
+        .build();
+
+    let result = suite.run(&ctx).await.unwrap();
+    assert!(result.is_success());
+    assert_eq!(result.report().metrics.passed_checks, 1);
+    assert_eq!(result.report().outcomes.len(), 2);
+
+    // The view only exists while the suite runs
+    assert!(!ctx.table_exist("orders_with_segment").unwrap());
+    assert!(suite.run(&ctx).await.unwrap().is_success());
+}
+
+#[tokio::test]
+async fn test_suite_validates_dataframe() {
+    let ctx = create_context().await;
+    let positive = ctx
+        .table("orders")
+        .await
+        .unwrap()
+        .filter(col("amount").gt(lit(0.0)))
+        .unwrap();
+
+    let suite = ValidationSuite::builder("positive_orders")
+        .derived_table_from_df("positive_orders", positive)
+        .check(
+            Check::builder("volume")
+                .has_size(Assertion::Equals(2.0))
+                .has_min("amount", Assertion::GreaterThan(0.0))
+                .build(),
+        )
+        .build();
+
+    let result = suite.run(&ctx).await.unwrap();
+    assert!(result.is_success());
+    assert!(!ctx.table_exist("positive_orders").unwrap());
+}
+
+#[tokio::test]
+async fn test_invalid_derived_sql_fails_before_constraints() {
+    let ctx = create_context().await;
+
+    let err = ValidationSuite::builder("broken")
+        .derived_table("broken_view", "SELECT missing_column FROM orders")
+        .check(segment_check())
+        .build()
+        .run(&ctx)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Failed to plan derived table 'broken_view'"));
+    assert!(err.contains("missing_column"));
+
+    let err = ValidationSuite::builder("unsafe")
+        .derived_table("unsafe_view", "SELECT 1; DROP TABLE orders")
+        .check(segment_check())
+        .build()
+        .run(&ctx)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Invalid SQL for derived table 'unsafe_view'"));
+    assert!(ctx.table_exist("orders").unwrap());
+
+    // Existing tables are never replaced
+    let err = ValidationSuite::builder("clash")
+        .derived_table("customers", "SELECT * FROM orders")
+        .check(segment_check())
+        .build()
+        .run(&ctx)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("a table with this name already exists"));
+}
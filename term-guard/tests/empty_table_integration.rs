@@ -0,0 +1,236 @@
+//! Integration tests for the evaluation of constraints on tables without rows.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use datafusion::prelude::*;
+use tempfile::TempDir;
+use term_guard::analyzers::advanced::ReferenceDistribution;
+use term_guard::analyzers::incremental::FileSystemStateStore;
+use term_guard::constraints::{
+    ApproxCountDistinctConstraint, Assertion, CardinalityConstraint, CardinalitySpec,
+    CategoryBaselineConstraint, ColumnCountConstraint, CompletenessConstraint,
+    ContainmentConstraint, CorrelationConstraint, CrossTableSumConstraint, CustomSqlConstraint,
+    DataTypeConstraint, DistributionStabilityConstraint, EqualityTolerance,
+    ExpressionEqualityConstraint, ForeignKeyConstraint, FormatConstraint, HistogramConstraint,
+    JoinCoverageConstraint, LengthConstraint, ManifestSizeConstraint, MultiStatisticalConstraint,
+    NullRateComparisonConstraint, QuantileConstraint, SizeConstraint, SizeTolerance,
+    StaticProvider, StatisticType, StatisticalConstraint, TemporalGapConstraint,
+    TemporalOrderingConstraint, TimeWindow, TimeWindowConstraint, UniquenessConstraint,
+};
+use term_guard::core::{
+    Check, Constraint, ConstraintOptions, ConstraintResult, ConstraintStatus, EmptyTablePolicy,
+    Level, ValidationContext, ValidationSuite, CURRENT_CONTEXT, EMPTY_TABLE_MESSAGE,
+};
+
+async fn context() -> SessionContext {
+    let ctx = SessionContext::new();
+    for table in ["data", "parents"] {
+        ctx.sql(&format!(
+            "CREATE TABLE {table} (id INT, amount DOUBLE, name VARCHAR, category VARCHAR, \
+             created_at TIMESTAMP, shipped_at TIMESTAMP)"
+        ))
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    }
+    ctx
+}
+
+async fn evaluate(ctx: &SessionContext, constraint: &dyn Constraint) -> ConstraintResult {
+    CURRENT_CONTEXT
+        .scope(ValidationContext::new("data"), constraint.evaluate(ctx))
+        .await
+        .unwrap_or_else(|e| panic!("{} failed: {e}", constraint.name()))
+}
+
+fn skipped_constraints(store: &TempDir) -> Vec<Box<dyn Constraint>> {
+    let store = Arc::new(FileSystemStateStore::new(store.path()).unwrap());
+    let reference = ReferenceDistribution::from_frequencies([("a", 0.5), ("b", 0.5)]).unwrap();
+
+    vec![
+        // Ratios of rows
+        Box::new(CompletenessConstraint::with_threshold("name", 0.9)),
+        Box::new(UniquenessConstraint::full_uniqueness("id", 1.0).unwrap()),
+        Box::new(FormatConstraint::email("name", 0.9).unwrap()),
+        Box::new(LengthConstraint::min("name", 1)),
+        Box::new(ContainmentConstraint::new("category", ["a", "b"])),
+        Box::new(DataTypeConstraint::non_negative("amount").unwrap()),
+        Box::new(DataTypeConstraint::type_consistency("name", 0.9).unwrap()),
+        Box::new(CustomSqlConstraint::new("amount > 0", None::<String>).unwrap()),
+        Box::new(
+            ExpressionEqualityConstraint::new("amount", "id * 2", EqualityTolerance::exact())
+                .unwrap(),
+        ),
+        Box::new(
+            TimeWindowConstraint::inside(
+                "created_at",
+                "UTC",
+                TimeWindow::new("09:00", "17:00").unwrap(),
+            )
+            .unwrap(),
+        ),
+        Box::new(TemporalOrderingConstraint::new("data").before_after("created_at", "shipped_at")),
+        Box::new(CardinalityConstraint::new(
+            "data.id",
+            "parents.id",
+            CardinalitySpec::ManyToOne,
+        )),
+        Box::new(JoinCoverageConstraint::new("data", "parents").on("id", "id")),
+        Box::new(NullRateComparisonConstraint::new("parents", "data", 0.1)),
+        // Aggregates without a value
+        Box::new(StatisticalConstraint::min("amount", Assertion::GreaterThan(0.0)).unwrap()),
+        Box::new(StatisticalConstraint::max("amount", Assertion::LessThan(10.0)).unwrap()),
+        Box::new(StatisticalConstraint::mean("amount", Assertion::GreaterThan(0.0)).unwrap()),
+        Box::new(StatisticalConstraint::sum("amount", Assertion::GreaterThan(0.0)).unwrap()),
+        Box::new(
+            StatisticalConstraint::standard_deviation("amount", Assertion::LessThan(1.0)).unwrap(),
+        ),
+        Box::new(StatisticalConstraint::variance("amount", Assertion::LessThan(1.0)).unwrap()),
+        Box::new(StatisticalConstraint::median("amount", Assertion::GreaterThan(0.0)).unwrap()),
+        Box::new(
+            MultiStatisticalConstraint::new(
+                "amount",
+                vec![
+                    (StatisticType::Min, Assertion::GreaterThan(0.0)),
+                    (StatisticType::Count, Assertion::Equals(0.0)),
+                ],
+            )
+            .unwrap(),
+        ),
+        Box::new(QuantileConstraint::median("amount", Assertion::GreaterThan(0.0)).unwrap()),
+        Box::new(
+            CorrelationConstraint::pearson("id", "amount", Assertion::GreaterThan(0.5)).unwrap(),
+        ),
+        Box::new(HistogramConstraint::new(
+            "category",
+            Arc::new(|histogram| histogram.bucket_count() > 0),
+        )),
+        Box::new(TemporalGapConstraint::new(
+            "created_at",
+            Duration::from_secs(3600),
+        )),
+        Box::new(DistributionStabilityConstraint::new("category", reference)),
+        Box::new(CategoryBaselineConstraint::new("orders", "category", store)),
+    ]
+}
+
+fn evaluated_constraints() -> Vec<Box<dyn Constraint>> {
+    vec![
+        Box::new(SizeConstraint::new(Assertion::Equals(0.0))),
+        Box::new(ManifestSizeConstraint::new(
+            StaticProvider::new(0.0),
+            SizeTolerance::Exact,
+        )),
+        Box::new(ColumnCountConstraint::new(Assertion::Equals(6.0))),
+        Box::new(DataTypeConstraint::specific_type("name", "Utf8View").unwrap()),
+        Box::new(
+            StatisticalConstraint::new("amount", StatisticType::Count, Assertion::Equals(0.0))
+                .unwrap(),
+        ),
+        Box::new(
+            StatisticalConstraint::new("amount", StatisticType::NullCount, Assertion::Equals(0.0))
+                .unwrap(),
+        ),
+        Box::new(ApproxCountDistinctConstraint::new(
+            "id",
+            Assertion::Equals(0.0),
+        )),
+        Box::new(ForeignKeyConstraint::new("data.id", "parents.id")),
+        Box::new(CrossTableSumConstraint::new(
+            "data.amount",
+            "parents.amount",
+        )),
+    ]
+}
+
+#[tokio::test]
+async fn test_constraints_measuring_rows_skip_empty_tables() {
+    let ctx = context().await;
+    let store = TempDir::new().unwrap();
+
+    for constraint in skipped_constraints(&store) {
+        let result = evaluate(&ctx, constraint.as_ref()).await;
+        assert!(
+            result.is_empty_table(),
+            "{}: {:?} {:?}",
+            constraint.name(),
+            result.status,
+            result.message
+        );
+        assert_eq!(result.status, ConstraintStatus::Skipped);
+        assert_eq!(result.message.as_deref(), Some(EMPTY_TABLE_MESSAGE));
+    }
+}
+
+#[tokio::test]
+async fn test_constraints_defined_for_zero_rows_evaluate_empty_tables() {
+    let ctx = context().await;
+
+    for constraint in evaluated_constraints() {
+        let result = evaluate(&ctx, constraint.as_ref()).await;
+        assert_eq!(
+            result.status,
+            ConstraintStatus::Success,
+            "{}: {:?}",
+            constraint.name(),
+            result.message
+        );
+    }
+}
+
+fn suite(policy: EmptyTablePolicy) -> ValidationSuite {
+    ValidationSuite::builder("orders")
+        .on_empty(policy)
+        .check(
+            Check::builder("orders")
+                .level(Level::Error)
+                .completeness("name", ConstraintOptions::new().with_threshold(1.0))
+                .has_size(Assertion::Equals(0.0))
+                .build(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_suite_skips_empty_tables_by_default() {
+    let ctx = context().await;
+
+    let result = suite(EmptyTablePolicy::default()).run(&ctx).await.unwrap();
+    assert!(result.is_success());
+    let report = result.report();
+    let statuses: Vec<_> = report
+        .outcomes
+        .iter()
+        .map(|outcome| outcome.status)
+        .collect();
+    assert_eq!(
+        statuses,
+        [ConstraintStatus::Skipped, ConstraintStatus::Success]
+    );
+    // The size constraint verified the empty table, so the check passes
+    assert_eq!(report.metrics.passed_checks, 1);
+    assert_eq!(report.metrics.skipped_checks, 0);
+}
+
+#[tokio::test]
+async fn test_suite_fails_empty_tables_on_request() {
+    let ctx = context().await;
+
+    let result = suite(EmptyTablePolicy::Fail).run(&ctx).await.unwrap();
+    assert!(!result.is_success());
+    let issues = &result.report().issues;
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].message, EMPTY_TABLE_MESSAGE);
+
+    let result = suite(EmptyTablePolicy::Pass).run(&ctx).await.unwrap();
+    assert!(result.is_success());
+    assert_eq!(result.report().metrics.passed_checks, 1);
+    assert!(result
+        .report()
+        .outcomes
+        .iter()
+        .all(|outcome| outcome.status.is_success()));
+}
@@ -242,7 +242,14 @@ async fn test_exact_quantile_failure_cases() {
 
     if let ValidationResult::Failure { report } = &results {
         assert!(!report.issues.is_empty());
-        assert!(report.issues[0].message.contains("does not"));
+        assert!(
+            report.issues[0]
+                .message
+                .contains("Quantile 0.5 of column 'p_retailprice' is "),
+            "{}",
+            report.issues[0].message
+        );
+        assert!(report.issues[0].message.contains(", expected < 0 ("));
     }
 }
 
@@ -0,0 +1,298 @@
+//! Integration tests for the Excel workbook source.
+
+#[cfg(feature = "excel")]
+mod excel_tests {
+    use arrow::array::{Array, BooleanArray, Date32Array, Float64Array, Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion::prelude::*;
+    use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+    use term_guard::constraints::{FormatOptions, FormatType};
+    use term_guard::core::{Check, ConstraintOptions, Level, ValidationSuite};
+    use term_guard::sources::{DataSource, ExcelOptions, ExcelSource};
+
+    /// A workbook with a cover sheet and an "Orders" sheet whose header sits
+    /// below a merged title and a blank row.
+    fn orders_workbook(dir: &TempDir) -> PathBuf {
+        let path = dir.path().join("orders.xlsx");
+        let mut workbook = Workbook::new();
+        let date = Format::new().set_num_format("yyyy-mm-dd");
+        let blank = Format::new().set_bold();
+
+        let cover = workbook.add_worksheet();
+        cover.set_name("Summary").unwrap();
+        cover.write_string(0, 0, "See the Orders sheet").unwrap();
+
+        let sheet = workbook.add_worksheet();
+        sheet.set_name("Orders").unwrap();
+        sheet
+            .merge_range(0, 0, 0, 3, "Q1 orders, with commas", &Format::new())
+            .unwrap();
+        for (col, name) in ["id", "email", "amount", "ordered_on", "paid", "region"]
+            .into_iter()
+            .enumerate()
+        {
+            sheet.write_string(2, col as u16, name).unwrap();
+        }
+
+        let rows = [
+            (1.0, "ann@example.com", 10.5, (2024, 1, 15), true),
+            (2.0, "bob@example.com", 20.0, (2024, 2, 1), false),
+            (3.0, "not, an email", 30.0, (2024, 2, 29), true),
+        ];
+        for (i, (id, email, amount, (y, m, d), paid)) in rows.into_iter().enumerate() {
+            let row = 3 + i as u32;
+            sheet.write_number(row, 0, id).unwrap();
+            sheet.write_string(row, 1, email).unwrap();
+            sheet.write_number(row, 2, amount).unwrap();
+            sheet
+                .write_datetime_with_format(
+                    row,
+                    3,
+                    ExcelDateTime::from_ymd(y, m, d).unwrap(),
+                    &date,
+                )
+                .unwrap();
+            sheet.write_boolean(row, 4, paid).unwrap();
+        }
+        sheet.write_string(3, 5, "EU").unwrap();
+        // Region spans the last two orders
+        sheet.merge_range(4, 5, 5, 5, "US", &Format::new()).unwrap();
+        // Formatted but empty cells extend the used range
+        sheet.write_blank(9, 8, &blank).unwrap();
+
+        workbook.save(&path).unwrap();
+        path
+    }
+
+    async fn register(source: &ExcelSource) -> term_guard::prelude::Result<SessionContext> {
+        let ctx = SessionContext::new();
+        source.register(&ctx, "orders").await?;
+        Ok(ctx)
+    }
+
+    #[tokio::test]
+    async fn test_typed_columns() {
+        let dir = TempDir::new().unwrap();
+        let path = orders_workbook(&dir);
+        let source = ExcelSource::new(path.to_str().unwrap())
+            .unwrap()
+            .with_sheet("Orders")
+            .with_header_row(Some(2));
+        assert!(source.description().contains("(sheet 'Orders')"));
+
+        let ctx = register(&source).await.unwrap();
+        let df = ctx.table("orders").await.unwrap();
+        let schema = df.schema().inner().clone();
+        let types: Vec<_> = schema
+            .fields()
+            .iter()
+            .map(|field| (field.name().as_str(), field.data_type().clone()))
+            .collect();
+        assert_eq!(
+            types,
+            [
+                ("id", DataType::Int64),
+                ("email", DataType::Utf8),
+                ("amount", DataType::Float64),
+                ("ordered_on", DataType::Date32),
+                ("paid", DataType::Boolean),
+                ("region", DataType::Utf8),
+            ]
+        );
+
+        let batches = df
+            .sort_by(vec![col("id")])
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        let batch = &batches[0];
+        // Trailing blank rows and columns are trimmed
+        assert_eq!(batch.num_rows(), 3);
+
+        let ids = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(ids.values(), &[1, 2, 3]);
+        let emails = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(emails.value(2), "not, an email");
+        let amounts = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(amounts.values(), &[10.5, 20.0, 30.0]);
+        let dates = batch
+            .column(3)
+            .as_any()
+            .downcast_ref::<Date32Array>()
+            .unwrap();
+        assert_eq!(
+            dates.value_as_date(2),
+            chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+        );
+        let paid = batch
+            .column(4)
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap();
+        assert!(!paid.value(1));
+
+        // A merged cell's value is in its top-left cell only
+        let regions = batch
+            .column(5)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(regions.value(0), "EU");
+        assert_eq!(regions.value(1), "US");
+        assert!(regions.is_null(2));
+    }
+
+    #[tokio::test]
+    async fn test_sheet_selection() {
+        let dir = TempDir::new().unwrap();
+        let path = orders_workbook(&dir);
+        let path = path.to_str().unwrap();
+
+        let ctx = register(&ExcelSource::new(path).unwrap()).await.unwrap();
+        let df = ctx.table("orders").await.unwrap();
+        assert_eq!(df.schema().field(0).name(), "See the Orders sheet");
+
+        let mut source = ExcelSource::new(path)
+            .unwrap()
+            .with_sheet(1)
+            .with_header_row(Some(2));
+        assert!(source.schema().is_none());
+        let schema = source.infer_schema().await.unwrap();
+        assert_eq!(schema.fields().len(), 6);
+        assert!(source.schema().is_some());
+
+        let error = register(&ExcelSource::new(path).unwrap().with_sheet("Missing"))
+            .await
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("sheet 'Missing' not found"), "{error}");
+        assert!(
+            error.contains("available sheets: Summary, Orders"),
+            "{error}"
+        );
+
+        let error = register(&ExcelSource::new(path).unwrap().with_sheet(5))
+            .await
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("sheet #5 not found"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_cell_range_without_header() {
+        let dir = TempDir::new().unwrap();
+        let path = orders_workbook(&dir);
+        let options = ExcelOptions {
+            sheet: "Orders".into(),
+            header_row: None,
+            cell_range: Some("A5:C6".to_string()),
+            ..Default::default()
+        };
+        let source = ExcelSource::with_options(path.to_str().unwrap(), options).unwrap();
+        assert!(source.description().contains("cells A5:C6"));
+
+        let ctx = register(&source).await.unwrap();
+        let batches = ctx
+            .sql("SELECT column_1, column_3 FROM orders ORDER BY column_1")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        let ids = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(ids.values(), &[2, 3]);
+        let amounts = batches[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(amounts.values(), &[20, 30]);
+
+        assert!(ExcelSource::new("orders.xlsx")
+            .unwrap()
+            .with_cell_range("C5:A1")
+            .description()
+            .contains("C5:A1"));
+        let options = ExcelOptions {
+            cell_range: Some("not a range".to_string()),
+            ..Default::default()
+        };
+        assert!(ExcelSource::with_options("orders.xlsx", options).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_explicit_schema_mismatch() {
+        let dir = TempDir::new().unwrap();
+        let path = orders_workbook(&dir);
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("email", DataType::Utf8, true),
+            Field::new("amount", DataType::Int64, true),
+            Field::new("ordered_on", DataType::Date32, true),
+            Field::new("paid", DataType::Boolean, true),
+            Field::new("region", DataType::Utf8, true),
+        ]));
+        let options = ExcelOptions {
+            sheet: "Orders".into(),
+            header_row: Some(2),
+            schema: Some(schema),
+            ..Default::default()
+        };
+        let source = ExcelSource::with_options(path.to_str().unwrap(), options).unwrap();
+
+        let error = register(&source).await.err().unwrap().to_string();
+        assert!(
+            error.contains("cell C4 holds a number (10.5) but column 'amount' is Int64"),
+            "{error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_workbook() {
+        let dir = TempDir::new().unwrap();
+        let path = orders_workbook(&dir);
+        let source = ExcelSource::new(path.to_str().unwrap())
+            .unwrap()
+            .with_sheet("Orders")
+            .with_header_row(Some(2));
+        let ctx = SessionContext::new();
+        source.register(&ctx, "data").await.unwrap();
+
+        let suite = ValidationSuite::builder("orders_workbook")
+            .check(
+                Check::builder("contacts")
+                    .level(Level::Error)
+                    .completeness("email", ConstraintOptions::new().with_threshold(1.0))
+                    .has_format("email", FormatType::Email, 0.9, FormatOptions::default())
+                    .build(),
+            )
+            .build();
+
+        let result = suite.run(&ctx).await.unwrap();
+        let issues = &result.report().issues;
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert_eq!(issues[0].constraint_name, "email");
+    }
+}
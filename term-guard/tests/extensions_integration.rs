@@ -0,0 +1,148 @@
+//! Integration tests for passing per-run dependencies to custom constraints.
+
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use term_guard::constraints::Assertion;
+use term_guard::core::{
+    current_validation_context, Check, Constraint, ConstraintResult, ConstraintStatus, Extensions,
+    Level, RunOptions, ValidationSuite,
+};
+use term_guard::prelude::*;
+
+/// Client for the service providing today's expected row count.
+#[async_trait]
+trait RowCountService: Send + Sync {
+    async fn expected_rows(&self, table: &str) -> Result<i64>;
+}
+
+/// Mock service recording how often it was called.
+struct MockRowCountService {
+    expected: i64,
+    calls: AtomicUsize,
+}
+
+#[async_trait]
+impl RowCountService for MockRowCountService {
+    async fn expected_rows(&self, table: &str) -> Result<i64> {
+        assert_eq!(table, "events");
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(self.expected)
+    }
+}
+
+/// Compares the row count with the count reported by the service.
+#[derive(Debug)]
+struct ExpectedRowCountConstraint;
+
+#[async_trait]
+impl Constraint for ExpectedRowCountConstraint {
+    async fn evaluate(&self, _ctx: &SessionContext) -> Result<ConstraintResult> {
+        Ok(ConstraintResult::skipped("No row count service configured"))
+    }
+
+    async fn evaluate_with(
+        &self,
+        ctx: &SessionContext,
+        extensions: &Extensions,
+    ) -> Result<ConstraintResult> {
+        let Some(service) = extensions.get::<Arc<dyn RowCountService>>() else {
+            return self.evaluate(ctx).await;
+        };
+        let table = current_validation_context().table_name().to_string();
+        let expected = service.expected_rows(&table).await?;
+        let actual = ctx.table(table.as_str()).await?.count().await? as i64;
+
+        if actual == expected {
+            Ok(ConstraintResult::success_with_metric(actual as f64))
+        } else {
+            Ok(ConstraintResult::failure_with_metric(
+                actual as f64,
+                format!("Expected {expected} rows, found {actual}"),
+            ))
+        }
+    }
+
+    fn name(&self) -> &str {
+        "expected_row_count"
+    }
+}
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql("CREATE TABLE events (id INT) AS VALUES (1), (2), (3)")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    ctx
+}
+
+fn suite() -> ValidationSuite {
+    ValidationSuite::builder("events")
+        .table_name("events")
+        .check(
+            Check::builder("volume")
+                .level(Level::Error)
+                .with_constraint(ExpectedRowCountConstraint)
+                .has_size(Assertion::GreaterThan(0.0))
+                .build(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_constraint_uses_injected_client() {
+    let ctx = create_context().await;
+    let suite = suite();
+
+    let service = Arc::new(MockRowCountService {
+        expected: 3,
+        calls: AtomicUsize::new(0),
+    });
+    let extensions = Extensions::new().with(service.clone() as Arc<dyn RowCountService>);
+    let result = suite
+        .run_with(&ctx, &RunOptions::new().with_extensions(extensions))
+        .await
+        .unwrap();
+    assert!(result.is_success());
+    assert_eq!(result.report().metrics.passed_checks, 1);
+    assert_eq!(service.calls.load(Ordering::SeqCst), 1);
+
+    // The same suite is reused with a different client
+    let stale = Extensions::new().with(Arc::new(MockRowCountService {
+        expected: 5,
+        calls: AtomicUsize::new(0),
+    }) as Arc<dyn RowCountService>);
+    let result = suite
+        .run_with(&ctx, &RunOptions::new().with_extensions(stale))
+        .await
+        .unwrap();
+    assert!(result.is_failure());
+    assert_eq!(
+        result.report().issues[0].message,
+        "Expected 5 rows, found 3"
+    );
+}
+
+#[tokio::test]
+async fn test_constraint_without_extensions_falls_back_to_evaluate() {
+    let ctx = create_context().await;
+    let result = suite().run(&ctx).await.unwrap();
+
+    assert!(result.is_success());
+    let statuses: Vec<_> = result
+        .report()
+        .outcomes
+        .iter()
+        .map(|outcome| outcome.status)
+        .collect();
+    assert_eq!(
+        statuses,
+        [ConstraintStatus::Skipped, ConstraintStatus::Success]
+    );
+    assert_eq!(result.report().metrics.passed_checks, 1);
+    assert_eq!(result.report().metrics.skipped_checks, 0);
+}
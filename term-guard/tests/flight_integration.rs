@@ -0,0 +1,328 @@
+//! Integration tests for remote validation over Arrow Flight.
+
+#[cfg(feature = "flight")]
+mod flight_tests {
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use arrow_flight::encode::FlightDataEncoderBuilder;
+    use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
+    use arrow_flight::{
+        Action, ActionType, Criteria, Empty, FlightClient, FlightData, FlightDescriptor,
+        FlightEndpoint, FlightInfo, HandshakeRequest, HandshakeResponse, PollInfo, PutResult,
+        SchemaResult, Ticket,
+    };
+    use datafusion::prelude::*;
+    use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use term_guard::constraints::Assertion;
+    use term_guard::core::builder_extensions::CompletenessOptions;
+    use term_guard::core::{Check, Level, ValidationSuite};
+    use term_guard::flight::{decode_report, FlightValidationClient, FlightValidationServer};
+    use term_guard::sources::{
+        DataSource, FlightOptions, FlightRequest, FlightSource, StreamOptions,
+    };
+    use tokio::net::TcpListener;
+    use tonic::{Request, Response, Status, Streaming};
+
+    fn orders(ids: std::ops::Range<i64>) -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("customer", DataType::Utf8, true),
+        ]));
+        let customers: Vec<Option<String>> = ids
+            .clone()
+            .map(|id| (id % 10 != 0).then(|| format!("customer_{id}")))
+            .collect();
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from_iter_values(ids)),
+                Arc::new(StringArray::from(customers)),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn orders_suite() -> ValidationSuite {
+        ValidationSuite::builder("orders")
+            .table_name("orders")
+            .check(
+                Check::builder("volume")
+                    .level(Level::Error)
+                    .has_size(Assertion::Equals(20.0))
+                    .build(),
+            )
+            .check(
+                Check::builder("customers")
+                    .level(Level::Error)
+                    .completeness(
+                        "customer",
+                        CompletenessOptions::full().into_constraint_options(),
+                    )
+                    .build(),
+            )
+            .build()
+    }
+
+    async fn start(service: FlightServiceServer<impl FlightService>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address: SocketAddr = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+        format!("http://{address}")
+    }
+
+    async fn start_validation_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = FlightValidationServer::new()
+            .register_suite(orders_suite())
+            .with_bearer_token("secret");
+        tokio::spawn(server.serve(listener));
+        format!("http://{address}")
+    }
+
+    fn batches() -> impl futures::Stream<Item = term_guard::prelude::Result<RecordBatch>> {
+        stream::iter(vec![Ok(orders(0..10)), Ok(orders(10..20))])
+    }
+
+    #[tokio::test]
+    async fn test_do_put_returns_the_report() {
+        let endpoint = start_validation_server().await;
+        let mut client = FlightValidationClient::connect(&endpoint, Some("secret".into()))
+            .await
+            .unwrap();
+
+        let report = client.validate("orders", batches()).await.unwrap();
+
+        assert_eq!(report.suite_name, "orders");
+        assert_eq!(report.metrics.total_checks, 2);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].check_name, "customers");
+    }
+
+    #[tokio::test]
+    async fn test_do_exchange_returns_the_report_in_metadata() {
+        let endpoint = start_validation_server().await;
+        let channel = tonic::transport::Endpoint::from_shared(endpoint)
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        let mut client = FlightClient::new(channel);
+        client.add_header("authorization", "Bearer secret").unwrap();
+
+        let data = FlightDataEncoderBuilder::new()
+            .with_flight_descriptor(Some(FlightDescriptor::new_cmd("orders")))
+            .build(stream::iter(vec![Ok(orders(0..20))]));
+        let mut response = client.do_exchange(data).await.unwrap().into_inner();
+
+        let message = response.try_next().await.unwrap().unwrap();
+        let report = decode_report(&message.inner.app_metadata).unwrap();
+        assert_eq!(report.issues.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_server_rejects_bad_tokens_and_unknown_suites() {
+        let endpoint = start_validation_server().await;
+
+        let mut client = FlightValidationClient::connect(&endpoint, Some("wrong".into()))
+            .await
+            .unwrap();
+        let error = client.validate("orders", batches()).await.unwrap_err();
+        assert!(format!("{error:?}").contains("bearer token"), "{error:?}");
+
+        let mut client = FlightValidationClient::connect(&endpoint, Some("secret".into()))
+            .await
+            .unwrap();
+        let error = client.validate("invoices", batches()).await.unwrap_err();
+        assert!(format!("{error:?}").contains("No suite named 'invoices'"));
+    }
+
+    /// A Flight service serving 1000 orders under the path `orders`.
+    #[derive(Clone)]
+    struct OrdersService;
+
+    #[tonic::async_trait]
+    impl FlightService for OrdersService {
+        type HandshakeStream = BoxStream<'static, Result<HandshakeResponse, Status>>;
+        type ListFlightsStream = BoxStream<'static, Result<FlightInfo, Status>>;
+        type DoGetStream = BoxStream<'static, Result<FlightData, Status>>;
+        type DoPutStream = BoxStream<'static, Result<PutResult, Status>>;
+        type DoActionStream = BoxStream<'static, Result<arrow_flight::Result, Status>>;
+        type ListActionsStream = BoxStream<'static, Result<ActionType, Status>>;
+        type DoExchangeStream = BoxStream<'static, Result<FlightData, Status>>;
+
+        async fn handshake(
+            &self,
+            _request: Request<Streaming<HandshakeRequest>>,
+        ) -> Result<Response<Self::HandshakeStream>, Status> {
+            Err(Status::unimplemented("handshake"))
+        }
+
+        async fn list_flights(
+            &self,
+            _request: Request<Criteria>,
+        ) -> Result<Response<Self::ListFlightsStream>, Status> {
+            Err(Status::unimplemented("list_flights"))
+        }
+
+        async fn get_flight_info(
+            &self,
+            request: Request<FlightDescriptor>,
+        ) -> Result<Response<FlightInfo>, Status> {
+            if request.get_ref().path != ["orders"] {
+                return Err(Status::not_found("unknown dataset"));
+            }
+            let info = FlightInfo::new()
+                .with_endpoint(FlightEndpoint::new().with_ticket(Ticket::new("orders-0")))
+                .with_endpoint(FlightEndpoint::new().with_ticket(Ticket::new("orders-1")));
+            Ok(Response::new(info))
+        }
+
+        async fn poll_flight_info(
+            &self,
+            _request: Request<FlightDescriptor>,
+        ) -> Result<Response<PollInfo>, Status> {
+            Err(Status::unimplemented("poll_flight_info"))
+        }
+
+        async fn get_schema(
+            &self,
+            _request: Request<FlightDescriptor>,
+        ) -> Result<Response<SchemaResult>, Status> {
+            Err(Status::unimplemented("get_schema"))
+        }
+
+        async fn do_get(
+            &self,
+            request: Request<Ticket>,
+        ) -> Result<Response<Self::DoGetStream>, Status> {
+            let offset = match request.get_ref().ticket.as_ref() {
+                b"orders-0" => 0,
+                b"orders-1" => 500,
+                _ => return Err(Status::not_found("unknown ticket")),
+            };
+            let batches = (0..5).map(move |i| {
+                let start = offset + i * 100;
+                Ok(orders(start..start + 100))
+            });
+            let data = FlightDataEncoderBuilder::new()
+                .build(stream::iter(batches))
+                .map_err(Status::from);
+            Ok(Response::new(data.boxed()))
+        }
+
+        async fn do_put(
+            &self,
+            _request: Request<Streaming<FlightData>>,
+        ) -> Result<Response<Self::DoPutStream>, Status> {
+            Err(Status::unimplemented("do_put"))
+        }
+
+        async fn do_action(
+            &self,
+            _request: Request<Action>,
+        ) -> Result<Response<Self::DoActionStream>, Status> {
+            Err(Status::unimplemented("do_action"))
+        }
+
+        async fn list_actions(
+            &self,
+            _request: Request<Empty>,
+        ) -> Result<Response<Self::ListActionsStream>, Status> {
+            Err(Status::unimplemented("list_actions"))
+        }
+
+        async fn do_exchange(
+            &self,
+            _request: Request<Streaming<FlightData>>,
+        ) -> Result<Response<Self::DoExchangeStream>, Status> {
+            Err(Status::unimplemented("do_exchange"))
+        }
+    }
+
+    async fn count_rows(source: &FlightSource) -> i64 {
+        let ctx = SessionContext::new();
+        source.register(&ctx, "orders").await.unwrap();
+        let batches = ctx
+            .sql("SELECT COUNT(*) FROM orders")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap()
+            .value(0)
+    }
+
+    #[tokio::test]
+    async fn test_flight_source_fetches_a_ticket() {
+        let endpoint = start(FlightServiceServer::new(OrdersService)).await;
+
+        let source = FlightSource::fetch(
+            &endpoint,
+            FlightRequest::ticket("orders-1"),
+            FlightOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(source.num_rows(), 500);
+        assert!(!source.is_spilled());
+        assert!(source.description().contains("(500 rows, in memory)"));
+        assert_eq!(count_rows(&source).await, 500);
+    }
+
+    #[tokio::test]
+    async fn test_flight_source_spills_every_endpoint_of_a_descriptor() {
+        let endpoint = start(FlightServiceServer::new(OrdersService)).await;
+        let spill_dir = tempfile::tempdir().unwrap();
+        let options = FlightOptions {
+            buffer: StreamOptions {
+                max_memory_bytes: 1024,
+                spill_dir: Some(spill_dir.path().to_path_buf()),
+                ..Default::default()
+            },
+            bearer_token: None,
+        };
+
+        let source = FlightSource::fetch(&endpoint, FlightRequest::path(["orders"]), options)
+            .await
+            .unwrap();
+
+        assert_eq!(source.num_rows(), 1000);
+        assert!(source.is_spilled());
+        assert_eq!(count_rows(&source).await, 1000);
+
+        let ctx = SessionContext::new();
+        source.register(&ctx, "orders").await.unwrap();
+        let result = orders_suite().run(&ctx).await.unwrap();
+        assert!(result.is_failure());
+
+        drop(source);
+        assert_eq!(std::fs::read_dir(spill_dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_flight_source_reports_unknown_datasets() {
+        let endpoint = start(FlightServiceServer::new(OrdersService)).await;
+        let error = FlightSource::fetch(
+            &endpoint,
+            FlightRequest::path(["invoices"]),
+            FlightOptions::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(error.to_string().contains("GetFlightInfo failed"));
+    }
+}
@@ -0,0 +1,69 @@
+//! Integration tests for the conversion of Great Expectations suites.
+
+#[cfg(feature = "migration")]
+mod great_expectations_migration_tests {
+    use datafusion::prelude::*;
+    use term_guard::migration::from_great_expectations_json;
+
+    const ORDERS: &str = include_str!("fixtures/great_expectations/orders_suite.json");
+    const CUSTOMERS: &str = include_str!("fixtures/great_expectations/customers_suite_v1.json");
+
+    #[test]
+    fn test_orders_suite_is_converted() {
+        let migration = from_great_expectations_json(ORDERS).unwrap();
+        let suite = &migration.suite;
+        assert_eq!(suite.name(), "orders.critical");
+        assert_eq!(suite.checks().len(), 1);
+        assert_eq!(suite.checks()[0].name(), "orders.critical");
+        assert_eq!(suite.checks()[0].constraints().len(), 10);
+
+        let warnings: Vec<_> = migration.warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            warnings,
+            [
+                "check 'orders.critical', constraint expect_column_values_to_be_dateutil_parseable(order_date): expect_column_values_to_be_dateutil_parseable has no Term equivalent",
+                "check 'orders.critical', constraint expect_column_max_to_be_between(amount): max_value {\"$PARAMETER\":\"max_order_amount\"} is not a number",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_suites_in_the_v1_format_are_converted() {
+        let migration = from_great_expectations_json(CUSTOMERS).unwrap();
+        assert_eq!(migration.suite.name(), "customers");
+        assert_eq!(migration.suite.checks()[0].constraints().len(), 2);
+        assert_eq!(migration.warnings.len(), 1);
+        assert_eq!(
+            migration.warnings[0].reason,
+            "Term checks expect_column_values_to_be_in_set on all rows, not on a fraction of 0.95"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_converted_suite_runs() {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE TABLE data (order_id INT, customer_email VARCHAR, status VARCHAR, amount DOUBLE) AS VALUES
+                (1, 'ana@example.com', 'placed', 25.0),
+                (2, 'ben@example.com', 'shipped', 120.5),
+                (3, NULL, 'delivered', 42.0),
+                (4, 'cy@example.com', 'cancelled', 310.0)",
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+        let migration = from_great_expectations_json(ORDERS).unwrap();
+        let result = migration.suite.run(&ctx).await.unwrap();
+        assert!(result.is_success(), "{:?}", result.report().issues);
+        let report = result.report();
+        assert_eq!(report.metrics.passed_checks, migration.suite.checks().len());
+        assert_eq!(report.outcomes.len(), 10);
+        assert!(report
+            .outcomes
+            .iter()
+            .all(|outcome| outcome.status.is_success()));
+    }
+}
@@ -0,0 +1,86 @@
+//! Integration tests for failures of `Level::Info` checks.
+
+use datafusion::prelude::*;
+use term_guard::constraints::Assertion;
+use term_guard::core::{Check, Level, ValidationResult, ValidationSuite};
+use term_guard::formatters::{HumanFormatter, MarkdownFormatter, ResultFormatter};
+
+async fn run_suite() -> ValidationResult {
+    let ctx = SessionContext::new();
+    ctx.sql("CREATE TABLE data (amount DOUBLE) AS VALUES (5.0), (10.0)")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    ValidationSuite::builder("orders")
+        .check(
+            Check::builder("volume")
+                .level(Level::Error)
+                .has_size(Assertion::Equals(2.0))
+                .build(),
+        )
+        .check(
+            Check::builder("amounts")
+                .level(Level::Warning)
+                .has_min("amount", Assertion::GreaterThan(6.0))
+                .build(),
+        )
+        .check(
+            Check::builder("profile")
+                .level(Level::Info)
+                .has_mean("amount", Assertion::GreaterThan(100.0))
+                .build(),
+        )
+        .build()
+        .run(&ctx)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_info_failures_are_observations() {
+    let result = run_suite().await;
+    assert!(result.is_success());
+
+    let report = result.report();
+    assert_eq!(report.metrics.total_checks, 3);
+    assert_eq!(report.metrics.passed_checks, 1);
+    assert_eq!(report.metrics.failed_checks, 1);
+    assert_eq!(report.metrics.info_findings, 1);
+    // The observation is left out of the success rate: 1 of 2 rated checks passed
+    assert_eq!(report.metrics.success_rate(), 50.0);
+
+    let observations = report.observations();
+    assert_eq!(observations.len(), 1);
+    assert_eq!(observations[0].check_name, "profile");
+}
+
+#[tokio::test]
+async fn test_formatters_list_observations_separately() {
+    let result = run_suite().await;
+
+    let human = HumanFormatter::new().format(&result).unwrap();
+    assert!(human.contains("Info Findings: 1"), "{human}");
+    let (issues, observations) = human.split_once("Observations:").unwrap();
+    assert!(issues.contains("Check: amounts"));
+    assert!(!issues.contains("Check: profile"));
+    assert!(observations.contains("Observation #1"));
+    assert!(observations.contains("Check: profile"));
+
+    let markdown = MarkdownFormatter::new().format(&result).unwrap();
+    assert!(markdown.contains("| Info Findings | 1 |"));
+    assert!(markdown.contains("### Observations"));
+
+    let json = result.to_json().unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["report"]["metrics"]["info_findings"], 1);
+    assert_eq!(value["report"]["issues"].as_array().unwrap().len(), 1);
+    assert_eq!(value["report"]["observations"][0]["check_name"], "profile");
+
+    // Observations are merged back into the report's issues when read
+    let restored = ValidationResult::from_json_str(&json).unwrap();
+    assert_eq!(restored.report().issues.len(), 2);
+    assert_eq!(restored.report().observations().len(), 1);
+}
@@ -645,7 +645,7 @@ mod performance_tests {
 
         for table in ["orders", "customer", "lineitem", "part", "supplier"] {
             builder = builder.check(
-                Check::builder("check")
+                Check::builder(format!("{table}_size"))
                     .level(Level::Warning)
                     .description(format!("Check {table}"))
                     .constraint(SizeConstraint::new(Assertion::GreaterThan(0.0)))
@@ -1164,21 +1164,21 @@ mod result_tests {
 
         let suite = ValidationSuite::builder("mixed_levels")
             .check(
-                Check::builder("check")
+                Check::builder("critical")
                     .level(Level::Error)
                     .description("Critical check")
                     .constraint(CompletenessConstraint::with_threshold("o_orderkey", 0.95))
                     .build(),
             )
             .check(
-                Check::builder("check")
+                Check::builder("warning")
                     .level(Level::Warning)
                     .description("Warning check")
                     .constraint(SizeConstraint::new(Assertion::GreaterThan(1000000.0))) // Will fail
                     .build(),
             )
             .check(
-                Check::builder("check")
+                Check::builder("info")
                     .level(Level::Info)
                     .description("Info check")
                     .constraint(
@@ -0,0 +1,120 @@
+//! Integration tests for checks waived by known issues.
+
+use chrono::{DateTime, Duration, Utc};
+use datafusion::prelude::*;
+use term_guard::constraints::Assertion;
+use term_guard::core::{Check, CheckStatus, Level, ValidationSuite};
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql("CREATE TABLE data (revenue DOUBLE) AS VALUES (-5.0), (10.0)")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    ctx
+}
+
+fn suite(until: Option<DateTime<Utc>>) -> ValidationSuite {
+    ValidationSuite::builder("finance")
+        .check(
+            Check::builder("revenue")
+                .level(Level::Error)
+                .known_issue("JIRA-1234", until)
+                .has_min("revenue", Assertion::GreaterThanOrEqual(0.0))
+                .build(),
+        )
+        .check(
+            Check::builder("volume")
+                .level(Level::Error)
+                .has_size(Assertion::Equals(2.0))
+                .build(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_active_known_issue_does_not_fail_run() {
+    let ctx = create_context().await;
+
+    for until in [None, Some(Utc::now() + Duration::days(30))] {
+        let result = suite(until).run(&ctx).await.unwrap();
+        assert!(result.is_success());
+
+        let report = result.report();
+        assert!(!report.has_errors());
+        assert_eq!(report.metrics.failed_checks, 0);
+        assert_eq!(report.metrics.waived_checks, 1);
+        assert_eq!(report.metrics.success_rate(), 100.0);
+        assert_eq!(
+            report.check_statuses(),
+            vec![
+                ("revenue", CheckStatus::Waived),
+                ("volume", CheckStatus::Passed)
+            ]
+        );
+
+        let known_issues = report.known_issues();
+        assert_eq!(known_issues.len(), 1);
+        assert_eq!(known_issues[0].check_name, "revenue");
+        assert_eq!(known_issues[0].level, Level::Error);
+        let known_issue = known_issues[0].known_issue.as_ref().unwrap();
+        assert_eq!(known_issue.ticket, "JIRA-1234");
+        assert_eq!(known_issue.until, until);
+    }
+}
+
+#[tokio::test]
+async fn test_expired_known_issue_fails_run() {
+    let ctx = create_context().await;
+
+    let result = suite(Some(Utc::now() - Duration::days(1)))
+        .run(&ctx)
+        .await
+        .unwrap();
+    assert!(result.is_failure());
+
+    let report = result.report();
+    assert!(report.known_issues().is_empty());
+    assert_eq!(report.metrics.failed_checks, 1);
+    assert_eq!(report.metrics.waived_checks, 0);
+    let issues = report.issues_by_level(Level::Error);
+    assert_eq!(issues.len(), 1);
+    assert!(
+        issues[0]
+            .message
+            .contains("known issue JIRA-1234 waiver expired at"),
+        "{}",
+        issues[0].message
+    );
+}
+
+#[tokio::test]
+async fn test_known_issue_does_not_waive_evaluation_errors() {
+    let ctx = create_context().await;
+    let suite = ValidationSuite::builder("finance")
+        .check(
+            Check::builder("revenue")
+                .level(Level::Error)
+                .known_issue("JIRA-1234", None)
+                .has_min("missing", Assertion::GreaterThanOrEqual(0.0))
+                .build(),
+        )
+        .build();
+
+    let result = suite.run(&ctx).await.unwrap();
+    assert!(result.is_failure());
+
+    let report = result.report();
+    assert!(report.known_issues().is_empty());
+    assert_eq!(report.metrics.failed_checks, 1);
+    assert_eq!(report.metrics.waived_checks, 0);
+    let issues = report.issues_by_level(Level::Error);
+    assert_eq!(issues.len(), 1);
+    assert!(
+        issues[0].message.starts_with("Error evaluating constraint"),
+        "{}",
+        issues[0].message
+    );
+}
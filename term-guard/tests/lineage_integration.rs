@@ -0,0 +1,171 @@
+//! Integration tests for attaching lineage hints to validation issues.
+
+use async_trait::async_trait;
+use datafusion::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use term_guard::constraints::Assertion;
+use term_guard::core::{
+    Check, ConstraintInfo, ConstraintOptions, ConstraintResult, ConstraintStatus, Level,
+    LineageMap, RunOptions, ValidationHook, ValidationSuite, LINEAGE_ALL_COLUMNS,
+};
+use term_guard::formatters::{FormatterConfig, HumanFormatter, ResultFormatter};
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    for sql in [
+        "CREATE TABLE orders (id INT, amount DOUBLE, status VARCHAR) AS VALUES
+            (1, 10.0, 'paid'), (2, NULL, 'paid'), (3, 30.0, NULL)",
+        "CREATE TABLE vendor_orders (order_id INT, total DOUBLE, state VARCHAR) AS VALUES
+            (1, 10.0, 'paid'), (2, NULL, 'paid')",
+    ] {
+        ctx.sql(sql).await.unwrap().collect().await.unwrap();
+    }
+    ctx
+}
+
+fn lineage() -> LineageMap {
+    LineageMap::new()
+        .with("orders", "amount", "finance_daily_dashboard")
+        .with("orders", "amount", "revenue_model")
+        .with("orders", "status", "ops_board")
+}
+
+fn orders_suite(table: &str, lineage: LineageMap) -> ValidationSuite {
+    ValidationSuite::builder("orders")
+        .table_name(table)
+        .with_lineage(lineage)
+        .check(
+            Check::builder("amounts")
+                .level(Level::Error)
+                .completeness("amount", ConstraintOptions::new().with_threshold(1.0))
+                .build(),
+        )
+        .check(
+            Check::builder("volume")
+                .level(Level::Warning)
+                .has_size(Assertion::Equals(10.0))
+                .build(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_issues_list_the_impacted_downstream_consumers() {
+    let ctx = create_context().await;
+    let result = orders_suite("orders", lineage()).run(&ctx).await.unwrap();
+
+    let report = result.report();
+    assert_eq!(report.issues.len(), 2);
+    let amounts = &report.issues[0];
+    assert_eq!(amounts.check_name, "amounts");
+    assert_eq!(
+        amounts.impacts,
+        vec!["finance_daily_dashboard", "revenue_model"]
+    );
+    // The size constraint validates no column
+    assert!(report.issues[1].impacts.is_empty());
+
+    let config = FormatterConfig::default().with_colors(false);
+    let output = HumanFormatter::new()
+        .format_with_config(&result, &config)
+        .unwrap();
+    assert!(output.contains("Impacts: finance_daily_dashboard, revenue_model"));
+}
+
+#[tokio::test]
+async fn test_whole_table_lineage_applies_to_every_constraint() {
+    let ctx = create_context().await;
+    let lineage = lineage().with("ORDERS", LINEAGE_ALL_COLUMNS, "orders_snapshot");
+    let result = orders_suite("orders", lineage).run(&ctx).await.unwrap();
+
+    let report = result.report();
+    assert_eq!(
+        report.issues[0].impacts,
+        vec![
+            "finance_daily_dashboard",
+            "orders_snapshot",
+            "revenue_model"
+        ]
+    );
+    assert_eq!(report.issues[1].impacts, vec!["orders_snapshot"]);
+}
+
+#[tokio::test]
+async fn test_matching_follows_column_mappings_and_ignores_case() {
+    let ctx = create_context().await;
+
+    // Lineage recorded under the logical table and column names
+    let result = orders_suite("vendor_orders", LineageMap::new())
+        .run_with(
+            &ctx,
+            &RunOptions::new().with_column_mapping(HashMap::from([("amount", "total")])),
+        )
+        .await
+        .unwrap();
+    assert!(result.report().issues[0].impacts.is_empty());
+
+    let lineage = LineageMap::new()
+        .with("Vendor_Orders", "AMOUNT", "finance_daily_dashboard")
+        .with("analytics.vendor_orders", "Total", "revenue_model");
+    let result = orders_suite("vendor_orders", lineage)
+        .run_with(
+            &ctx,
+            &RunOptions::new().with_column_mapping(HashMap::from([("amount", "total")])),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        result.report().issues[0].impacts,
+        vec!["finance_daily_dashboard", "revenue_model"]
+    );
+}
+
+/// Records the impacts hooks see for failed constraints.
+#[derive(Debug, Clone, Default)]
+struct ImpactRecorder {
+    messages: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl ValidationHook for ImpactRecorder {
+    async fn after_constraint(&self, info: &ConstraintInfo, result: &ConstraintResult) {
+        if result.status == ConstraintStatus::Failure && !info.impacts.is_empty() {
+            self.messages.lock().unwrap().push(format!(
+                "{} failed, impacts: {}",
+                info.check_name,
+                info.impacts.join(", ")
+            ));
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_hooks_see_the_impacts() {
+    let ctx = create_context().await;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("lineage.json");
+    std::fs::write(
+        &path,
+        r#"{"orders": {"amount": ["finance_daily_dashboard", "revenue_model"]}}"#,
+    )
+    .unwrap();
+
+    let recorder = ImpactRecorder::default();
+    let suite = ValidationSuite::builder("orders")
+        .table_name("orders")
+        .with_lineage(LineageMap::from_file(&path).unwrap())
+        .with_hook(recorder.clone())
+        .check(
+            Check::builder("amounts")
+                .completeness("amount", ConstraintOptions::new().with_threshold(1.0))
+                .build(),
+        )
+        .build();
+    suite.run(&ctx).await.unwrap();
+
+    assert_eq!(
+        *recorder.messages.lock().unwrap(),
+        vec!["amounts failed, impacts: finance_daily_dashboard, revenue_model"]
+    );
+}
@@ -0,0 +1,165 @@
+//! Integration tests for lookup tables embedded in suite definitions.
+//!
+//! Every suite here validates an in-memory table against reference data it
+//! carries itself, without reading any file.
+
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::prelude::*;
+use datafusion::scalar::ScalarValue;
+use term_guard::constraints::{ContainmentConstraint, ForeignKeyConstraint};
+use term_guard::core::{Check, ConstraintStatus, LookupTable, ValidationSuite};
+use term_guard::error::TermError;
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql(
+        "CREATE TABLE accounts (id INT, plan VARCHAR) AS VALUES
+            (1, 'free'), (2, 'pro'), (3, 'enterprise'), (4, NULL), (5, 'pro')",
+    )
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+    ctx
+}
+
+fn plan_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("plan", DataType::Utf8, false),
+        Field::new("seats", DataType::Int64, true),
+    ]))
+}
+
+fn plan_rows(plans: &[&str]) -> Vec<Vec<ScalarValue>> {
+    plans
+        .iter()
+        .map(|plan| vec![ScalarValue::from(*plan), ScalarValue::Int64(Some(1))])
+        .collect()
+}
+
+fn plans_check() -> Check {
+    Check::builder("plans")
+        .constraint(ContainmentConstraint::in_table(
+            "plan",
+            "valid_plans",
+            "plan",
+        ))
+        .build()
+}
+
+#[tokio::test]
+async fn test_containment_in_embedded_table() {
+    let ctx = create_context().await;
+    let suite = ValidationSuite::builder("accounts")
+        .table_name("accounts")
+        .with_lookup(
+            "valid_plans",
+            plan_rows(&["free", "pro", "pro"]),
+            plan_schema(),
+        )
+        .check(plans_check())
+        .try_build()
+        .unwrap();
+
+    let result = suite.run(&ctx).await.unwrap();
+    let outcome = &result.report().outcomes[0];
+    assert_eq!(outcome.status, ConstraintStatus::Failure);
+    // The null plan is not counted, the duplicate 'pro' does not inflate matches
+    assert_eq!(outcome.metric, Some(0.75));
+    let issue = &result.report().issues[0];
+    assert!(
+        issue
+            .message
+            .contains("1 values are not in valid_plans.plan"),
+        "{}",
+        issue.message
+    );
+
+    // The lookup table only exists for the duration of the run
+    assert!(!ctx.table_exist("valid_plans").unwrap());
+
+    let suite = ValidationSuite::builder("accounts")
+        .table_name("accounts")
+        .with_lookup(
+            "valid_plans",
+            plan_rows(&["free", "pro", "enterprise"]),
+            plan_schema(),
+        )
+        .check(plans_check())
+        .check(
+            Check::builder("references")
+                .constraint(
+                    ForeignKeyConstraint::new("accounts.plan", "valid_plans.plan")
+                        .allow_nulls(true),
+                )
+                .build(),
+        )
+        .build();
+    let result = suite.run(&ctx).await.unwrap();
+    assert!(result.is_success(), "{:?}", result.report().issues);
+}
+
+#[tokio::test]
+async fn test_serialized_lookup_table_runs_the_same() {
+    let ctx = create_context().await;
+    let table =
+        LookupTable::try_new("valid_plans", plan_rows(&["free", "pro"]), plan_schema()).unwrap();
+    let json = serde_json::to_string(&table).unwrap();
+    let restored: LookupTable = serde_json::from_str(&json).unwrap();
+
+    let suite = ValidationSuite::builder("accounts")
+        .table_name("accounts")
+        .with_lookup_table(restored)
+        .check(plans_check())
+        .build();
+    assert_eq!(suite.lookups()[0].fingerprint(), table.fingerprint());
+
+    let result = suite.run(&ctx).await.unwrap();
+    assert_eq!(result.report().outcomes[0].metric, Some(0.75));
+}
+
+#[tokio::test]
+async fn test_invalid_lookup_tables_are_configuration_errors() {
+    let result = ValidationSuite::builder("accounts")
+        .table_name("accounts")
+        .with_lookup("valid_plans", plan_rows(&["free", "pro"]), plan_schema())
+        .with_lookup("accounts", plan_rows(&["free"]), plan_schema())
+        .with_lookup(
+            "seats",
+            vec![vec![ScalarValue::from("free")]],
+            plan_schema(),
+        )
+        .max_lookup_bytes(64)
+        .check(plans_check())
+        .try_build();
+
+    let Err(TermError::InvalidConfiguration(report)) = result else {
+        panic!("expected an invalid configuration");
+    };
+    let issues: Vec<(&str, &str)> = report
+        .issues
+        .iter()
+        .map(|issue| (issue.path.as_str(), issue.message.as_str()))
+        .collect();
+    assert_eq!(issues.len(), 3, "{report}");
+    assert_eq!(issues[0].0, "lookups[0]");
+    assert!(
+        issues[0].1.contains("over the limit of 64 bytes"),
+        "{report}"
+    );
+    assert_eq!(issues[1].0, "lookups[1]");
+    assert!(
+        issues[1].1.contains("has the name of the validated table"),
+        "{report}"
+    );
+    assert_eq!(issues[2].0, "lookups[2]");
+    assert!(
+        issues[2]
+            .1
+            .contains("row 0 has 1 values, the schema has 2 fields"),
+        "{report}"
+    );
+}
@@ -0,0 +1,107 @@
+//! Integration tests for memory strategies under a constrained memory pool.
+
+use std::sync::Arc;
+
+use datafusion::datasource::MemTable;
+use datafusion::execution::disk_manager::{DiskManagerBuilder, DiskManagerMode};
+use datafusion::execution::memory_pool::GreedyMemoryPool;
+use datafusion::execution::runtime_env::RuntimeEnvBuilder;
+use datafusion::prelude::*;
+use term_guard::constraints::{
+    Assertion, HistogramConstraint, UniquenessConstraint, UniquenessOptions, UniquenessType,
+};
+use term_guard::core::{
+    Constraint, ConstraintResult, ConstraintStatus, Evaluator, MemoryStrategy,
+    MEMORY_APPROXIMATE_DETAIL, MEMORY_PARTITIONS_DETAIL,
+};
+use term_guard::prelude::*;
+
+/// Memory available to queries, far below the state of an exact count over
+/// the distinct keys.
+const POOL_BYTES: usize = 2 * 1024 * 1024;
+
+/// A session with a small memory pool and no spilling, holding 200,000 distinct
+/// keys and a `hot` key occurring 1,000 times.
+async fn create_constrained_context() -> SessionContext {
+    let source = SessionContext::new();
+    let batches = source
+        .sql(
+            "SELECT CONCAT('key-', CAST(value AS VARCHAR)) AS id
+             FROM generate_series(1, 200000)
+             UNION ALL SELECT 'hot' AS id FROM generate_series(1, 1000)",
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    let table = MemTable::try_new(batches[0].schema(), vec![batches]).unwrap();
+
+    let runtime = RuntimeEnvBuilder::new()
+        .with_memory_pool(Arc::new(GreedyMemoryPool::new(POOL_BYTES)))
+        .with_disk_manager_builder(
+            DiskManagerBuilder::default().with_mode(DiskManagerMode::Disabled),
+        )
+        .build_arc()
+        .unwrap();
+    let ctx =
+        SessionContext::new_with_config_rt(SessionConfig::new().with_target_partitions(1), runtime);
+    ctx.register_table("keys", Arc::new(table)).unwrap();
+    ctx
+}
+
+async fn evaluate(ctx: &SessionContext, constraint: impl Constraint) -> Result<ConstraintResult> {
+    Evaluator::new(ctx).table("keys").evaluate(constraint).await
+}
+
+fn uniqueness(strategy: MemoryStrategy) -> UniquenessConstraint {
+    UniquenessConstraint::new(
+        ["id"],
+        UniquenessType::UniqueValueRatio(Assertion::GreaterThan(0.99)),
+        UniquenessOptions::new().with_memory_strategy(strategy),
+    )
+    .unwrap()
+}
+
+fn assert_resources_exhausted(result: Result<ConstraintResult>) {
+    let error = result.expect_err("the unbounded query fits in the memory pool");
+    assert!(error.to_string().contains("Resources exhausted"), "{error}");
+}
+
+#[tokio::test]
+async fn test_hash_partitioned_uniqueness_fits_the_memory_pool() {
+    let ctx = create_constrained_context().await;
+
+    assert_resources_exhausted(evaluate(&ctx, uniqueness(MemoryStrategy::Unbounded)).await);
+
+    let result = evaluate(&ctx, uniqueness(MemoryStrategy::hash_partitioned(64)))
+        .await
+        .unwrap();
+    assert_eq!(result.status, ConstraintStatus::Success);
+    // Every key but the hot one occurs exactly once
+    assert_eq!(result.metric, Some(200_000.0 / 201_000.0));
+    assert_eq!(result.details[MEMORY_PARTITIONS_DETAIL], 64.0);
+}
+
+#[tokio::test]
+async fn test_sketched_histogram_fits_the_memory_pool() {
+    let ctx = create_constrained_context().await;
+    let histogram = |strategy: Option<MemoryStrategy>| {
+        let constraint = HistogramConstraint::new(
+            "id",
+            Arc::new(|hist| hist.bucket_count() > 0 && hist.top_n(1)[0].0 == "hot"),
+        );
+        match strategy {
+            Some(strategy) => constraint.with_memory_strategy(strategy).unwrap(),
+            None => constraint,
+        }
+    };
+
+    assert_resources_exhausted(evaluate(&ctx, histogram(None)).await);
+
+    let result = evaluate(&ctx, histogram(Some(MemoryStrategy::sketched(1_000))))
+        .await
+        .unwrap();
+    assert_eq!(result.status, ConstraintStatus::Success);
+    assert_eq!(result.details[MEMORY_APPROXIMATE_DETAIL], 1.0);
+}
@@ -0,0 +1,84 @@
+//! Integration tests for the sharing of metrics between constraints of a run.
+
+use datafusion::prelude::*;
+use term_guard::constraints::Assertion;
+use term_guard::core::{Check, ConstraintOptions, ValidationSuite};
+
+async fn context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql("CREATE TABLE data (id INT, name VARCHAR) AS VALUES (1, 'a'), (2, 'b'), (3, NULL), (4, 'd')")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    ctx
+}
+
+fn suite() -> ValidationSuite {
+    ValidationSuite::builder("customers")
+        .check(
+            Check::builder("volume")
+                .has_size(Assertion::Equals(4.0))
+                .build(),
+        )
+        .check(
+            Check::builder("names")
+                .completeness("name", ConstraintOptions::new().with_threshold(0.75))
+                .build(),
+        )
+        .check(
+            Check::builder("ids")
+                .validates_uniqueness(["id"], 1.0)
+                .validates_distinctness(["id"], Assertion::Equals(1.0))
+                .build(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_constraints_share_counts_within_a_run() {
+    let ctx = context().await;
+    let suite = suite();
+
+    let result = suite.run(&ctx).await.unwrap();
+    assert!(result.is_success(), "{:?}", result.report().issues);
+
+    // Row count, NULL count of name and distinct count of id are each computed once;
+    // the row count is reused three times and the distinct count once
+    let performance = &result.report().metrics.performance;
+    assert_eq!(performance.metric_cache_misses, 3);
+    assert_eq!(performance.metric_cache_hits, 4);
+    assert_eq!(performance.queries_executed, 3);
+
+    let metrics = performance.as_metric_map();
+    assert_eq!(metrics["performance.metric_cache_hits"], 4.0);
+
+    let json = result.to_json().unwrap();
+    assert!(json.contains("\"metric_cache_hits\": 4"), "{json}");
+
+    // The cache is scoped to a run: a new run recomputes everything
+    let second = suite.run(&ctx).await.unwrap();
+    let performance = &second.report().metrics.performance;
+    assert_eq!(performance.metric_cache_misses, 3);
+    assert_eq!(performance.metric_cache_hits, 4);
+}
+
+#[tokio::test]
+async fn test_shared_counts_keep_results() {
+    let ctx = context().await;
+    let suite = ValidationSuite::builder("customers")
+        .check(
+            Check::builder("strict")
+                .completeness("name", ConstraintOptions::new().with_threshold(1.0))
+                .has_size(Assertion::GreaterThan(10.0))
+                .build(),
+        )
+        .build();
+
+    let result = suite.run(&ctx).await.unwrap();
+    let issues = &result.report().issues;
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].metric, Some(0.75));
+    assert_eq!(issues[1].metric, Some(4.0));
+}
@@ -50,10 +50,13 @@ mod metrics_tests {
             .check(
                 Check::builder("completeness_check")
                     .level(Level::Warning)
-                    .constraint(CompletenessConstraint::new(
-                        "username",
-                        ConstraintOptions::new().with_threshold(0.9),
-                    ))
+                    .constraint(
+                        CompletenessConstraint::new(
+                            "username",
+                            ConstraintOptions::new().with_threshold(0.9),
+                        )
+                        .unwrap(),
+                    )
                     .build(),
             )
             .check(
@@ -0,0 +1,54 @@
+//! Runs a suite on a local CSV file with only the features the build enables.
+//!
+//! CI runs this test for `term-guard` alone with `--no-default-features`, and
+//! once per optional feature, so neither the core validation path nor a single
+//! feature can come to depend on another feature without the build failing.
+
+use datafusion::prelude::*;
+use std::fs;
+use term_guard::constraints::Assertion;
+use term_guard::core::{Check, ConstraintOptions, Level, ValidationSuite};
+use term_guard::sources::CsvSource;
+
+#[tokio::test]
+async fn test_suite_runs_on_local_csv() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("orders.csv");
+    fs::write(
+        &path,
+        "id,email,amount\n1,a@example.com,10.5\n2,b@example.com,20.0\n3,,7.25\n",
+    )
+    .unwrap();
+
+    let suite = ValidationSuite::builder("orders")
+        .table_name("orders")
+        .source(
+            "orders",
+            CsvSource::new(path.to_string_lossy().to_string()).unwrap(),
+        )
+        .check(
+            Check::builder("volume")
+                .level(Level::Error)
+                .has_size(Assertion::Equals(3.0))
+                .validates_uniqueness(vec!["id"], 1.0)
+                .build(),
+        )
+        .check(
+            Check::builder("contact")
+                .level(Level::Warning)
+                .completeness("email", ConstraintOptions::new().with_threshold(1.0))
+                .build(),
+        )
+        .build();
+
+    let ctx = SessionContext::new();
+    let result = suite.run(&ctx).await.unwrap();
+    let report = result.report();
+    assert_eq!(report.metrics.total_checks, 2);
+    assert_eq!(report.metrics.passed_checks, 1);
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].check_name, "contact");
+
+    // The attached source is deregistered after the run
+    assert!(!ctx.table_exist("orders").unwrap());
+}
@@ -0,0 +1,109 @@
+//! Integration tests for counting empty strings and sentinels as missing values.
+
+use std::sync::Arc;
+
+use arrow::array::StringArray;
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion::datasource::MemTable;
+use datafusion::prelude::*;
+use term_guard::constraints::CompletenessConstraint;
+use term_guard::core::builder_extensions::CompletenessOptions;
+use term_guard::core::{
+    Check, Constraint, ConstraintStatus, Level, MissingValuePolicy, ValidationSuite,
+};
+
+/// Registers `data` with an `age` column read from a CSV without type inference.
+fn create_context() -> SessionContext {
+    let ages = vec![
+        Some("34"),
+        Some(""),
+        Some("N/A"),
+        Some("null"),
+        Some("-999"),
+        None,
+        Some("51"),
+        Some("27"),
+    ];
+    let schema = Arc::new(Schema::new(vec![Field::new("age", DataType::Utf8, true)]));
+    let batch =
+        RecordBatch::try_new(schema.clone(), vec![Arc::new(StringArray::from(ages))]).unwrap();
+
+    let ctx = SessionContext::new();
+    let table = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+    ctx.register_table("data", Arc::new(table)).unwrap();
+    ctx
+}
+
+fn policy() -> MissingValuePolicy {
+    MissingValuePolicy::default()
+        .sentinel_strings(["N/A", "NULL"])
+        .case_insensitive(true)
+        .sentinel_numbers([-999.0])
+        .empty_string_as_null(true)
+}
+
+#[tokio::test]
+async fn test_sentinels_count_as_missing() {
+    let ctx = create_context();
+
+    // Only one SQL NULL: the column looks almost complete
+    let plain = CompletenessConstraint::with_threshold("age", 0.5);
+    let result = plain.evaluate(&ctx).await.unwrap();
+    assert_eq!(result.metric, Some(0.875));
+
+    let result = plain
+        .with_missing_value_policy(policy())
+        .evaluate(&ctx)
+        .await
+        .unwrap();
+    assert_eq!(result.status, ConstraintStatus::Failure);
+    assert_eq!(result.metric, Some(0.375));
+    assert_eq!(result.details["missing.null"], 1.0);
+    assert_eq!(result.details["missing.empty_string"], 1.0);
+    assert_eq!(result.details["missing.sentinel:N/A"], 1.0);
+    assert_eq!(result.details["missing.sentinel:NULL"], 1.0);
+    assert_eq!(result.details["missing.sentinel:-999"], 1.0);
+}
+
+#[tokio::test]
+async fn test_sentinels_are_case_sensitive_by_default() {
+    let ctx = create_context();
+
+    let result = CompletenessConstraint::with_threshold("age", 0.5)
+        .with_missing_value_policy(MissingValuePolicy::new().sentinel_strings(["NULL"]))
+        .evaluate(&ctx)
+        .await
+        .unwrap();
+    assert_eq!(result.metric, Some(0.875));
+    assert_eq!(result.details["missing.sentinel:NULL"], 0.0);
+}
+
+#[tokio::test]
+async fn test_completeness_with_missing_in_a_suite() {
+    let ctx = create_context();
+    let suite = ValidationSuite::builder("ages")
+        .check(
+            Check::builder("age_present")
+                .level(Level::Error)
+                .completeness_with_missing("age", CompletenessOptions::threshold(0.95), policy())
+                .build(),
+        )
+        .build();
+
+    let result = suite.run(&ctx).await.unwrap();
+    assert!(result.is_failure());
+}
+
+#[test]
+fn test_invalid_sentinels_are_configuration_errors() {
+    let check = Check::builder("ages")
+        .completeness_with_missing(
+            "age",
+            CompletenessOptions::full(),
+            MissingValuePolicy::new().sentinel_numbers([f64::INFINITY]),
+        )
+        .try_build();
+    let err = check.unwrap_err();
+    assert!(err.to_string().contains("finite"), "{err}");
+}
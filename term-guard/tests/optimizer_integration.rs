@@ -42,7 +42,8 @@ mod tests {
 
         assert!(!result.is_failure());
         assert_eq!(result.report().metrics.failed_checks, 0);
-        assert_eq!(result.report().metrics.total_checks, 5);
+        assert_eq!(result.report().metrics.total_checks, 1);
+        assert_eq!(result.report().outcomes.len(), 5);
 
         // Now run without optimizer for comparison
         let suite_no_opt = ValidationSuite::builder("no_optimizer_test")
@@ -66,7 +67,8 @@ mod tests {
 
         assert!(result_no_opt.is_success());
         assert_eq!(result_no_opt.report().metrics.failed_checks, 0);
-        assert_eq!(result_no_opt.report().metrics.total_checks, 5);
+        assert_eq!(result_no_opt.report().metrics.total_checks, 1);
+        assert_eq!(result_no_opt.report().outcomes.len(), 5);
 
         // The optimized version should theoretically be faster
         // (though in practice with small data, overhead might make it slower)
@@ -132,7 +134,8 @@ mod tests {
         let result = suite.run(&ctx).await.unwrap();
 
         assert!(!result.is_failure());
-        assert_eq!(result.report().metrics.total_checks, 5);
+        assert_eq!(result.report().metrics.total_checks, 1);
+        assert_eq!(result.report().outcomes.len(), 5);
 
         // All metrics should have values
         assert!(result
@@ -198,7 +201,8 @@ mod tests {
 
         // Note: These checks will fail because TPC-H data is registered as a single "data" table
         // In a real scenario with multiple tables, the optimizer would group by table
-        assert_eq!(result.report().metrics.total_checks, 6);
+        assert_eq!(result.report().metrics.total_checks, 3);
+        assert_eq!(result.report().outcomes.len(), 6);
     }
 
     #[tokio::test]
@@ -290,8 +294,9 @@ mod tests {
         let result = suite.run(&ctx).await.unwrap();
 
         assert!(!result.is_failure());
-        assert_eq!(result.report().metrics.total_checks, 3);
-        assert_eq!(result.report().metrics.passed_checks, 3);
+        assert_eq!(result.report().metrics.total_checks, 1);
+        assert_eq!(result.report().metrics.passed_checks, 1);
+        assert_eq!(result.report().outcomes.len(), 3);
 
         // Clean up
         std::fs::remove_file(&temp_file).ok();
@@ -0,0 +1,150 @@
+//! Integration tests for named parameters resolved when a suite runs.
+
+use datafusion::prelude::*;
+use term_guard::constraints::{Assertion, CrossTableSumConstraint};
+use term_guard::core::builder_extensions::CompletenessOptions;
+use term_guard::core::{Check, Level, ParameterBag, RunOptions, ValidationSuite};
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    for sql in [
+        "CREATE TABLE data (id DOUBLE, email VARCHAR) AS VALUES
+            (1, 'a@example.com'), (2, 'b@example.com'), (3, 'c@example.com'),
+            (4, 'd@example.com'), (5, 'e@example.com'), (6, 'f@example.com'),
+            (7, 'g@example.com'), (8, 'h@example.com'), (9, 'i@example.com'),
+            (10, NULL)",
+        "CREATE TABLE orders (id INT, total DOUBLE) AS VALUES (1, 100.005)",
+        "CREATE TABLE payments (id INT, amount DOUBLE) AS VALUES (1, 100.001)",
+    ] {
+        ctx.sql(sql).await.unwrap().collect().await.unwrap();
+    }
+    ctx
+}
+
+fn completeness_suite() -> ValidationSuite {
+    let defaults = ParameterBag::from_json_str(r#"{ "email_completeness": 0.8 }"#).unwrap();
+    ValidationSuite::builder("parameters")
+        .table_name("data")
+        .parameter_defaults(defaults)
+        .check(
+            Check::builder("contact")
+                .level(Level::Error)
+                .completeness(
+                    "email",
+                    CompletenessOptions::threshold_param("email_completeness")
+                        .into_constraint_options(),
+                )
+                .build(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_thresholds_differ_between_environments() {
+    let ctx = create_context().await;
+    let suite = completeness_suite();
+
+    // Defaults come from the suite configuration
+    let dev = suite.run(&ctx).await.unwrap();
+    assert!(dev.is_success());
+    assert_eq!(dev.report().parameters["email_completeness"], 0.8);
+
+    // Run-time values override the defaults
+    let prod = ParameterBag::new().with("email_completeness", 0.95);
+    let result = suite
+        .run_with(&ctx, &RunOptions::new().with_parameters(prod))
+        .await
+        .unwrap();
+    assert!(result.is_failure());
+    assert_eq!(result.report().parameters["email_completeness"], 0.95);
+    assert!(result.report().issues[0]
+        .message
+        .contains("threshold 95.00%"));
+}
+
+#[tokio::test]
+async fn test_missing_and_out_of_range_parameters_fail_upfront() {
+    let ctx = create_context().await;
+    let suite = ValidationSuite::builder("parameters")
+        .table_name("missing_table")
+        .check(
+            Check::builder("volume")
+                .has_size(Assertion::between_params("min_rows", "max_rows"))
+                .build(),
+        )
+        .build();
+
+    // The table does not exist, so any error must come from parameter validation
+    let err = suite
+        .run_with(
+            &ctx,
+            &RunOptions::new().with_parameters(ParameterBag::new().with("min_rows", 1.0)),
+        )
+        .await
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Missing value for parameter 'max_rows' used by size assertion"));
+
+    let err = completeness_suite()
+        .run_with(
+            &ctx,
+            &RunOptions::new().with_parameters(ParameterBag::new().with("email_completeness", 1.5)),
+        )
+        .await
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Parameter 'email_completeness' = 1.5 is out of range"));
+}
+
+#[tokio::test]
+async fn test_assertion_bounds_and_tolerances_from_parameters() {
+    let ctx = create_context().await;
+    let suite = ValidationSuite::builder("parameters")
+        .table_name("data")
+        .parameter_default("sum_tolerance", 0.0)
+        .check(
+            Check::builder("volume")
+                .level(Level::Error)
+                .has_size(Assertion::between_params("min_rows", "max_rows"))
+                .has_max("id", Assertion::less_than_or_equal_param("max_id"))
+                .with_constraint(
+                    CrossTableSumConstraint::new("orders.total", "payments.amount")
+                        .tolerance_param("sum_tolerance"),
+                )
+                .build(),
+        )
+        .build();
+
+    let params: ParameterBag = [("min_rows", 5.0), ("max_rows", 20.0), ("max_id", 10.0)]
+        .into_iter()
+        .collect();
+    let result = suite
+        .run_with(&ctx, &RunOptions::new().with_parameters(params.clone()))
+        .await
+        .unwrap();
+    assert!(result.is_failure());
+    assert_eq!(result.report().issues.len(), 1);
+    assert_eq!(result.report().issues[0].constraint_name, "cross_table_sum");
+
+    let relaxed = params.with("sum_tolerance", 0.01);
+    let result = suite
+        .run_with(&ctx, &RunOptions::new().with_parameters(relaxed.clone()))
+        .await
+        .unwrap();
+    assert!(result.is_success());
+    assert_eq!(
+        result.report().parameters.keys().collect::<Vec<_>>(),
+        vec!["max_id", "max_rows", "min_rows", "sum_tolerance"]
+    );
+
+    let err = suite
+        .run_with(
+            &ctx,
+            &RunOptions::new().with_parameters(relaxed.with("sum_tolerance", -1.0)),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("expected a value of at least 0"));
+}
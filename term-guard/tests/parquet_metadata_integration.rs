@@ -0,0 +1,249 @@
+//! Integration tests for answering constraints from Parquet footer statistics.
+
+use arrow::array::{ArrayRef, Float64Array, Int32Array, Int64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use datafusion::parquet::arrow::ArrowWriter;
+use datafusion::parquet::file::properties::{EnabledStatistics, WriterProperties};
+use datafusion::prelude::*;
+use std::fs::File;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use term_guard::constraints::Assertion;
+use term_guard::core::builder_extensions::CompletenessOptions;
+use term_guard::core::{
+    Check, ConstraintInfo, ConstraintResult, Level, MetadataOnlyMode, ValidationHook,
+    ValidationResult, ValidationSuite, PARQUET_METADATA_EVIDENCE,
+};
+use term_guard::sources::{DataSource, ParquetSource};
+
+const ROWS_PER_FILE: i64 = 1000;
+
+/// Two files of orders with row groups of 250 rows.
+///
+/// One quantity in twenty and one amount in twenty-five is null.
+fn orders(statistics: bool) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("quantity", DataType::Int32, true),
+        Field::new("amount", DataType::Float64, true),
+    ]));
+    let mut properties = WriterProperties::builder().set_max_row_group_size(250);
+    if !statistics {
+        properties = properties.set_statistics_enabled(EnabledStatistics::None);
+    }
+    let properties = properties.build();
+
+    for file in 0..2 {
+        let ids: Vec<i64> = (file * ROWS_PER_FILE + 1..=(file + 1) * ROWS_PER_FILE).collect();
+        let quantities: Vec<Option<i32>> = ids
+            .iter()
+            .map(|id| (id % 20 != 0).then_some((id % 50) as i32))
+            .collect();
+        let amounts: Vec<Option<f64>> = ids
+            .iter()
+            .map(|id| (id % 25 != 0).then_some(*id as f64 * 1.5))
+            .collect();
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from(ids)),
+            Arc::new(Int32Array::from(quantities)),
+            Arc::new(Float64Array::from(amounts)),
+        ];
+        let batch = RecordBatch::try_new(schema.clone(), columns).unwrap();
+
+        let path = dir.path().join(format!("part-{file}.parquet"));
+        let mut writer = ArrowWriter::try_new(
+            File::create(path).unwrap(),
+            schema.clone(),
+            Some(properties.clone()),
+        )
+        .unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
+    dir
+}
+
+async fn orders_source(dir: &TempDir) -> ParquetSource {
+    ParquetSource::from_glob(format!("{}/*.parquet", dir.path().display()))
+        .await
+        .unwrap()
+}
+
+/// The metadata evidence detail of a constraint, keyed by `check/constraint`.
+type Evidence = (String, Option<f64>);
+
+/// Records the metadata evidence detail of every constraint result.
+#[derive(Debug, Clone, Default)]
+struct EvidenceRecorder {
+    evidence: Arc<Mutex<Vec<Evidence>>>,
+}
+
+#[async_trait]
+impl ValidationHook for EvidenceRecorder {
+    async fn after_constraint(&self, info: &ConstraintInfo, result: &ConstraintResult) {
+        let detail = format!("evidence.{PARQUET_METADATA_EVIDENCE}");
+        self.evidence.lock().unwrap().push((
+            format!("{}/{}", info.check_name, info.constraint_name),
+            result.details.get(&detail).copied(),
+        ));
+    }
+}
+
+async fn run_orders(
+    dir: &TempDir,
+    mode: MetadataOnlyMode,
+    row_filter: Option<&str>,
+    recorder: &EvidenceRecorder,
+) -> ValidationResult {
+    let mut builder = ValidationSuite::builder("orders")
+        .table_name("orders")
+        .source("orders", orders_source(dir).await)
+        .metadata_only(mode)
+        .with_hook(recorder.clone())
+        .check(
+            Check::builder("volume")
+                .has_size(Assertion::Equals(2.0 * ROWS_PER_FILE as f64))
+                .build(),
+        )
+        .check(
+            Check::builder("quantities")
+                .completeness(
+                    "quantity",
+                    CompletenessOptions::threshold(0.9).into_constraint_options(),
+                )
+                .has_max("quantity", Assertion::LessThan(50.0))
+                .has_min("id", Assertion::Equals(1.0))
+                .build(),
+        )
+        .check(
+            Check::builder("amounts")
+                .level(Level::Error)
+                .completeness(
+                    "amount",
+                    CompletenessOptions::full().into_constraint_options(),
+                )
+                .has_min("amount", Assertion::GreaterThan(0.0))
+                .build(),
+        );
+    if let Some(predicate) = row_filter {
+        builder = builder.row_filter(predicate);
+    }
+    builder.build().run(&SessionContext::new()).await.unwrap()
+}
+
+fn outcomes(result: &ValidationResult) -> Vec<String> {
+    result
+        .report()
+        .outcomes
+        .iter()
+        .map(|o| {
+            format!(
+                "{}/{}: {:?} {:?}",
+                o.check_name, o.constraint_name, o.status, o.metric
+            )
+        })
+        .collect()
+}
+
+fn answered_from_metadata(result: &ValidationResult) -> Vec<(String, Option<String>)> {
+    result
+        .report()
+        .metadata_constraints
+        .iter()
+        .map(|c| {
+            assert_eq!(c.evidence, PARQUET_METADATA_EVIDENCE);
+            (c.check_name.clone(), c.column.clone())
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_metadata_answers_match_scans() {
+    let dir = orders(true);
+    let recorder = EvidenceRecorder::default();
+    let from_metadata = run_orders(&dir, MetadataOnlyMode::Exact, None, &recorder).await;
+    let scanned = run_orders(
+        &dir,
+        MetadataOnlyMode::Disabled,
+        None,
+        &EvidenceRecorder::default(),
+    )
+    .await;
+
+    assert_eq!(outcomes(&from_metadata), outcomes(&scanned));
+    assert!(
+        !from_metadata.is_success(),
+        "{:?}",
+        outcomes(&from_metadata)
+    );
+    assert!(scanned.report().metadata_constraints.is_empty());
+
+    // The min of a float column is scanned: footers do not count NaN values
+    let column = |name: &str| Some(name.to_string());
+    assert_eq!(
+        answered_from_metadata(&from_metadata),
+        vec![
+            ("volume".to_string(), None),
+            ("quantities".to_string(), column("quantity")),
+            ("quantities".to_string(), column("quantity")),
+            ("quantities".to_string(), column("id")),
+            ("amounts".to_string(), column("amount")),
+        ]
+    );
+    let evidence = recorder.evidence.lock().unwrap();
+    assert_eq!(evidence.iter().filter(|(_, e)| *e == Some(1.0)).count(), 5);
+    assert_eq!(evidence.last().unwrap().1, None);
+}
+
+#[tokio::test]
+async fn test_files_without_statistics_are_scanned() {
+    let dir = orders(false);
+    let statistics = orders_source(&dir)
+        .await
+        .statistics()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(statistics.row_count, 2000);
+    assert_eq!(statistics.column("quantity").unwrap().null_count, None);
+
+    let from_metadata = run_orders(
+        &dir,
+        MetadataOnlyMode::AllowInexact,
+        None,
+        &EvidenceRecorder::default(),
+    )
+    .await;
+    let scanned = run_orders(
+        &dir,
+        MetadataOnlyMode::Disabled,
+        None,
+        &EvidenceRecorder::default(),
+    )
+    .await;
+
+    assert_eq!(outcomes(&from_metadata), outcomes(&scanned));
+    // The row count is always in the footer
+    assert_eq!(
+        answered_from_metadata(&from_metadata),
+        vec![("volume".to_string(), None)]
+    );
+}
+
+#[tokio::test]
+async fn test_row_filters_disable_metadata_answers() {
+    let dir = orders(true);
+    let result = run_orders(
+        &dir,
+        MetadataOnlyMode::Exact,
+        Some("id <= 1500"),
+        &EvidenceRecorder::default(),
+    )
+    .await;
+
+    assert!(result.report().metadata_constraints.is_empty());
+    assert_eq!(result.report().outcomes[0].metric, Some(1500.0));
+}
@@ -0,0 +1,206 @@
+//! Integration tests for pushing suite row filters into Parquet scans.
+
+use arrow::array::{ArrayRef, Float64Array, Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion::parquet::arrow::ArrowWriter;
+use datafusion::parquet::file::properties::WriterProperties;
+use datafusion::prelude::*;
+use std::fs::{self, File};
+use std::sync::Arc;
+use tempfile::TempDir;
+use term_guard::constraints::Assertion;
+use term_guard::core::builder_extensions::CompletenessOptions;
+use term_guard::core::{Check, Level, ValidationResult, ValidationSuite};
+use term_guard::sources::{DataSource, ParquetOptions, ParquetSource};
+
+const DATES: [&str; 3] = ["2024-03-01", "2024-03-02", "2024-03-03"];
+const ROWS_PER_DAY: i64 = 4000;
+
+/// One file per day under `event_date=<day>/`, each with row groups of 1000 rows.
+fn partitioned_events() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("event_date", DataType::Utf8, false),
+        Field::new("id", DataType::Int64, false),
+        Field::new("amount", DataType::Float64, true),
+    ]));
+    let properties = WriterProperties::builder()
+        .set_max_row_group_size(1000)
+        .build();
+
+    for (day, date) in DATES.iter().enumerate() {
+        let first_id = day as i64 * ROWS_PER_DAY;
+        let ids: Vec<i64> = (first_id..first_id + ROWS_PER_DAY).collect();
+        let amounts: Vec<Option<f64>> = ids
+            .iter()
+            .map(|id| (id % 10 != 0 || day != 1).then_some(*id as f64 / 10.0))
+            .collect();
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(vec![*date; ids.len()])),
+            Arc::new(Int64Array::from(ids)),
+            Arc::new(Float64Array::from(amounts)),
+        ];
+        let batch = RecordBatch::try_new(schema.clone(), columns).unwrap();
+
+        let partition = dir.path().join(format!("event_date={date}"));
+        fs::create_dir(&partition).unwrap();
+        let file = File::create(partition.join("part-0.parquet")).unwrap();
+        let mut writer =
+            ArrowWriter::try_new(file, schema.clone(), Some(properties.clone())).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
+    dir
+}
+
+async fn events_source(dir: &TempDir, enable_pruning: bool) -> ParquetSource {
+    let pattern = format!("{}/**/*.parquet", dir.path().display());
+    let options = ParquetOptions {
+        enable_pruning,
+        ..ParquetOptions::new()
+    };
+    ParquetSource::from_glob_with_options(pattern, options)
+        .await
+        .unwrap()
+}
+
+fn daily_checks() -> Vec<Check> {
+    vec![
+        Check::builder("volume")
+            .level(Level::Error)
+            .has_size(Assertion::Equals(ROWS_PER_DAY as f64))
+            .build(),
+        Check::builder("amounts")
+            .level(Level::Warning)
+            .completeness(
+                "amount",
+                CompletenessOptions::threshold(0.85).into_constraint_options(),
+            )
+            .has_min("id", Assertion::Equals(ROWS_PER_DAY as f64))
+            .build(),
+    ]
+}
+
+fn outcomes(result: &ValidationResult) -> Vec<String> {
+    result
+        .report()
+        .outcomes
+        .iter()
+        .map(|o| {
+            format!(
+                "{}/{}: {:?} {:?}",
+                o.check_name, o.constraint_name, o.status, o.metric
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_row_filter_prunes_parquet_row_groups() {
+    let dir = partitioned_events();
+
+    // Pushed down: the suite registers the source with its row filter
+    let pruned_suite = daily_checks()
+        .into_iter()
+        .fold(
+            ValidationSuite::builder("daily_events")
+                .table_name("events")
+                .source("events", events_source(&dir, true).await),
+            |builder, check| builder.check(check),
+        )
+        .row_filter("event_date = '2024-03-02'")
+        .build();
+    let ctx = SessionContext::new();
+    let pruned = pruned_suite.run(&ctx).await.unwrap();
+    assert!(!ctx.table_exist("events").unwrap());
+
+    // Unpruned: the caller registers every row group, the suite filters a view
+    let unpruned_suite = daily_checks()
+        .into_iter()
+        .fold(
+            ValidationSuite::builder("daily_events").table_name("events"),
+            |builder, check| builder.check(check),
+        )
+        .row_filter("event_date = '2024-03-02'")
+        .build();
+    let ctx = SessionContext::new();
+    events_source(&dir, false)
+        .await
+        .register(&ctx, "events")
+        .await
+        .unwrap();
+    let unpruned = unpruned_suite.run(&ctx).await.unwrap();
+    assert_eq!(
+        ctx.table("events").await.unwrap().count().await.unwrap(),
+        12_000
+    );
+
+    assert!(pruned.is_success(), "{:?}", outcomes(&pruned));
+    assert_eq!(outcomes(&pruned), outcomes(&unpruned));
+    assert_eq!(pruned.report().outcomes[1].metric, Some(0.9));
+
+    // DataFusion pushes the filter of the view into the unpruned scan too, which
+    // skips the files of other days by their statistics, so compare both with
+    // the scans of the whole table
+    let full_suite = daily_checks()
+        .into_iter()
+        .fold(
+            ValidationSuite::builder("all_events").table_name("events"),
+            |builder, check| builder.check(check),
+        )
+        .build();
+    let full = full_suite.run(&ctx).await.unwrap();
+
+    let bytes = |result: &ValidationResult| result.report().metrics.performance.total_bytes_scanned;
+    let (pruned_bytes, unpruned_bytes, full_bytes) =
+        (bytes(&pruned), bytes(&unpruned), bytes(&full));
+    assert!(pruned_bytes > 0);
+    assert!(
+        pruned_bytes <= unpruned_bytes && pruned_bytes < full_bytes,
+        "pruned scans read {pruned_bytes} bytes, unpruned scans {unpruned_bytes}, full scans {full_bytes}"
+    );
+}
+
+#[tokio::test]
+async fn test_register_with_predicates_matches_unfiltered_query() {
+    let dir = partitioned_events();
+    let predicates = vec![
+        "event_date = '2024-03-03'".to_string(),
+        "id >= 9500".to_string(),
+    ];
+
+    let ctx = SessionContext::new();
+    let source = events_source(&dir, true).await;
+    source
+        .register_with_predicates(&ctx, "recent", &predicates, None)
+        .await
+        .unwrap();
+    source.register(&ctx, "events").await.unwrap();
+
+    let count = |sql: &'static str| {
+        let ctx = ctx.clone();
+        async move { ctx.sql(sql).await.unwrap().count().await.unwrap() }
+    };
+    assert_eq!(count("SELECT * FROM recent").await, 2500);
+    assert_eq!(
+        count("SELECT * FROM events WHERE event_date = '2024-03-03' AND id >= 9500").await,
+        2500
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_row_filter_fails_the_run() {
+    let dir = partitioned_events();
+    let suite = ValidationSuite::builder("daily_events")
+        .table_name("events")
+        .source("events", events_source(&dir, true).await)
+        .row_filter("missing_column = 1")
+        .check(daily_checks().remove(0))
+        .build();
+
+    let ctx = SessionContext::new();
+    let err = suite.run(&ctx).await.unwrap_err();
+    assert!(err.to_string().contains("Invalid row filter"), "{err}");
+    assert!(!ctx.table_exist("events").unwrap());
+}
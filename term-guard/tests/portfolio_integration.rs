@@ -0,0 +1,91 @@
+//! Integration tests for aggregating validation reports across suites and runs.
+
+use datafusion::prelude::*;
+use term_guard::constraints::Assertion;
+use term_guard::core::{
+    Check, ConstraintOptions, Level, ReportAggregator, ValidationReport, ValidationSuite,
+};
+use term_guard::repository::{InMemoryRepository, MetricsRepository, ResultKey};
+
+async fn run_suite(suite_name: &str, rows: &str) -> ValidationReport {
+    let ctx = SessionContext::new();
+    ctx.sql(&format!(
+        "CREATE TABLE data (id INT, email VARCHAR) AS VALUES {rows}"
+    ))
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+
+    let suite = ValidationSuite::builder(suite_name)
+        .check(
+            Check::builder("quality")
+                .level(Level::Error)
+                .completeness("email", ConstraintOptions::new().with_threshold(1.0))
+                .has_size(Assertion::GreaterThanOrEqual(2.0))
+                .build(),
+        )
+        .build();
+    suite.run(&ctx).await.unwrap().report().clone()
+}
+
+#[tokio::test]
+async fn test_portfolio_from_repository() {
+    let repository = InMemoryRepository::new();
+    let day = 24 * 60 * 60 * 1000;
+
+    // Yesterday every suite passed; today orders lost an email and users shrank
+    let runs = [
+        (0, "orders", "(1, 'a@x.io'), (2, 'b@x.io')"),
+        (0, "users", "(1, 'a@x.io'), (2, 'b@x.io')"),
+        (day, "orders", "(1, 'a@x.io'), (2, NULL)"),
+        (day, "users", "(1, 'a@x.io')"),
+    ];
+    for (offset, dataset, rows) in runs {
+        let report = run_suite("nightly_suite", rows).await;
+        let key = ResultKey::new(1_700_000_000_000 + offset)
+            .with_tag("dataset", dataset)
+            .with_tag("pipeline", "nightly");
+        ReportAggregator::save_report(&repository, key, &report)
+            .await
+            .unwrap();
+    }
+
+    let mut aggregator = ReportAggregator::new().group_by(["dataset"]);
+    let loaded = aggregator
+        .load_from_repository(repository.load().await.with_tag("pipeline", "nightly"))
+        .await
+        .unwrap();
+    assert_eq!(loaded, 4);
+
+    let summary = aggregator.summarize();
+    let groups: Vec<(String, usize, Option<f64>)> = summary
+        .groups
+        .iter()
+        .map(|g| (g.label(), g.passed_checks, g.trend()))
+        .collect();
+    assert_eq!(
+        groups,
+        vec![
+            ("dataset=orders".to_string(), 0, Some(-1.0)),
+            ("dataset=users".to_string(), 0, Some(-1.0)),
+        ]
+    );
+
+    let newly_failing: Vec<(&str, &str)> = summary
+        .newly_failing
+        .iter()
+        .map(|c| (c.tags["dataset"].as_str(), c.constraint_name.as_str()))
+        .collect();
+    assert_eq!(
+        newly_failing,
+        vec![("orders", "completeness"), ("users", "size")]
+    );
+    assert!(summary.resolved.is_empty());
+    assert_eq!(summary.worst_offenders.len(), 2);
+
+    let markdown = summary.to_markdown();
+    assert!(markdown.contains("*Newly failing* (2)"));
+    assert!(markdown.contains("dataset=orders: 0/1 passed (0.0%, -100.0 pts vs previous)"));
+}
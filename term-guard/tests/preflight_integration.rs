@@ -0,0 +1,111 @@
+//! Integration tests for probing the sources of a suite before a run.
+
+use std::io::Write;
+
+use datafusion::prelude::*;
+use tempfile::NamedTempFile;
+use term_guard::constraints::Assertion;
+use term_guard::core::{Check, Level, ValidationSuite};
+use term_guard::error::TermError;
+use term_guard::sources::{CsvSource, DataSource, ParquetSource};
+
+fn orders_csv() -> NamedTempFile {
+    let mut file = NamedTempFile::with_suffix(".csv").unwrap();
+    writeln!(file, "id,amount").unwrap();
+    writeln!(file, "1,10.0").unwrap();
+    writeln!(file, "2,20.0").unwrap();
+    file.flush().unwrap();
+    file
+}
+
+fn path(file: &NamedTempFile) -> String {
+    file.path().to_str().unwrap().to_string()
+}
+
+fn suite(orders: &str, customers: &str, preflight: bool) -> ValidationSuite {
+    ValidationSuite::builder("orders")
+        .table_name("orders")
+        .source("orders", CsvSource::new(orders).unwrap())
+        .source("customers", ParquetSource::new(customers).unwrap())
+        .with_preflight(preflight)
+        .check(
+            Check::builder("volume")
+                .level(Level::Error)
+                .has_size(Assertion::Equals(2.0))
+                .build(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_preflight_reports_every_unreachable_source() {
+    let file = orders_csv();
+    let missing_orders = format!("{}.missing", path(&file));
+    let missing_customers = format!("{}.parquet", path(&file));
+
+    let ctx = SessionContext::new();
+    let error = suite(&missing_orders, &missing_customers, true)
+        .run(&ctx)
+        .await
+        .unwrap_err();
+
+    let TermError::PreflightFailed(report) = &error else {
+        panic!("expected a preflight failure, got {error}");
+    };
+    let unreachable: Vec<_> = report
+        .unreachable()
+        .map(|check| check.name.as_str())
+        .collect();
+    assert_eq!(unreachable, ["orders", "customers"]);
+
+    let message = error.to_string();
+    assert!(message.contains("2 of 2 sources"), "{message}");
+    assert!(message.contains(&missing_orders), "{message}");
+    assert!(message.contains(&missing_customers), "{message}");
+}
+
+#[tokio::test]
+async fn test_reachable_sources_pass_preflight() {
+    let file = orders_csv();
+    let orders = CsvSource::new(path(&file)).unwrap();
+    let ctx = SessionContext::new();
+    orders.register(&ctx, "orders").await.unwrap();
+
+    let suite = ValidationSuite::builder("orders")
+        .table_name("orders")
+        .source("orders", orders)
+        .with_preflight(true)
+        .check(
+            Check::builder("volume")
+                .level(Level::Error)
+                .has_size(Assertion::Equals(2.0))
+                .build(),
+        )
+        .build();
+
+    let report = suite.preflight().await;
+    assert!(report.is_success());
+    let probe = report.sources[0].probe.as_ref().unwrap();
+    assert_eq!(probe.file_count, Some(1));
+    assert!(probe.schema_hash.is_some());
+
+    assert!(suite.run(&ctx).await.unwrap().is_success());
+}
+
+#[tokio::test]
+async fn test_preflight_is_disabled_by_default() {
+    let file = orders_csv();
+    let missing = format!("{}.missing", path(&file));
+    let ctx = SessionContext::new();
+    for table in ["orders", "customers"] {
+        CsvSource::new(path(&file))
+            .unwrap()
+            .register(&ctx, table)
+            .await
+            .unwrap();
+    }
+
+    // The unreachable sources are not probed, the suite reads the registered tables
+    let result = suite(&missing, &missing, false).run(&ctx).await.unwrap();
+    assert!(result.is_success());
+}
@@ -0,0 +1,175 @@
+//! Integration tests for ProfileAndValidate.
+
+use arrow::array::{Float64Array, Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion::datasource::MemTable;
+use datafusion::prelude::*;
+use std::sync::Arc;
+use term_guard::analyzers::{ColumnProfiler, ProfileAndValidate};
+use term_guard::constraints::Assertion;
+use term_guard::core::builder_extensions::CompletenessOptions;
+use term_guard::core::{Check, Level, ValidationSuite};
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("amount", DataType::Float64, true),
+        Field::new("category", DataType::Utf8, true),
+        Field::new("email", DataType::Utf8, true),
+    ]));
+
+    let ids: Vec<i64> = (1..=100).collect();
+    let amounts: Vec<Option<f64>> = (1..=100)
+        .map(|i| (i % 10 != 0).then_some(i as f64 * 2.5))
+        .collect();
+    let categories: Vec<&str> = (0..100).map(|i| ["A", "B", "C"][i % 3]).collect();
+    let emails: Vec<String> = (0..100).map(|i| format!("user{i}@example.com")).collect();
+
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int64Array::from(ids)),
+            Arc::new(Float64Array::from(amounts)),
+            Arc::new(StringArray::from(categories)),
+            Arc::new(StringArray::from(emails)),
+        ],
+    )
+    .unwrap();
+
+    let table = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+    ctx.register_table("orders", Arc::new(table)).unwrap();
+    ctx
+}
+
+fn create_suite() -> ValidationSuite {
+    ValidationSuite::builder("orders_quality")
+        .table_name("orders")
+        .check(
+            Check::builder("profile_answerable")
+                .completeness("id", CompletenessOptions::full().into_constraint_options())
+                .completeness(
+                    "amount",
+                    CompletenessOptions::threshold(0.8).into_constraint_options(),
+                )
+                .has_min("amount", Assertion::GreaterThanOrEqual(0.0))
+                .has_max("amount", Assertion::LessThanOrEqual(250.0))
+                .has_approx_count_distinct("category", Assertion::LessThan(10.0))
+                .has_consistent_data_type("category", 0.9)
+                .build(),
+        )
+        .check(
+            Check::builder("query_only")
+                .validates_regex("email", r"^[a-z0-9]+@example\.com$", 1.0)
+                .build(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_profile_answers_reduce_executed_queries() {
+    let ctx = create_context().await;
+
+    let separate = ProfileAndValidate::new(ColumnProfiler::new(), create_suite())
+        .use_profile(false)
+        .run(&ctx)
+        .await
+        .unwrap();
+    let integrated = ProfileAndValidate::new(ColumnProfiler::new(), create_suite())
+        .run(&ctx)
+        .await
+        .unwrap();
+
+    assert!(separate.validation.is_success());
+    assert!(integrated.validation.is_success());
+
+    // Profiling work is identical in both modes
+    assert_eq!(
+        separate.profiling_metrics.queries_executed,
+        integrated.profiling_metrics.queries_executed
+    );
+    assert!(integrated.profiling_metrics.queries_executed > 0);
+
+    // Six constraints are answered from the profile, only the regex check runs a query
+    assert!(separate.profiled_constraints().is_empty());
+    assert_eq!(integrated.profiled_constraints().len(), 6);
+    assert!(integrated
+        .profiled_constraints()
+        .iter()
+        .all(|c| c.check_name == "profile_answerable"));
+    let validation_queries = |result: &term_guard::analyzers::ProfileAndValidateResult| {
+        result
+            .validation
+            .report()
+            .metrics
+            .performance
+            .queries_executed
+    };
+    assert_eq!(validation_queries(&separate), 7);
+    assert_eq!(validation_queries(&integrated), 1);
+    assert_eq!(
+        separate.queries_executed() - integrated.queries_executed(),
+        6
+    );
+}
+
+#[tokio::test]
+async fn test_profile_answers_match_query_results() {
+    let ctx = create_context().await;
+
+    let separate = ProfileAndValidate::new(ColumnProfiler::new(), create_suite())
+        .use_profile(false)
+        .run(&ctx)
+        .await
+        .unwrap();
+    let integrated = ProfileAndValidate::new(ColumnProfiler::new(), create_suite())
+        .run(&ctx)
+        .await
+        .unwrap();
+
+    let separate_metrics = &separate.validation.report().metrics.custom_metrics;
+    let integrated_metrics = &integrated.validation.report().metrics.custom_metrics;
+    for key in [
+        "profile_answerable.completeness",
+        "profile_answerable.min",
+        "profile_answerable.max",
+        "profile_answerable.approx_count_distinct",
+    ] {
+        assert_eq!(
+            separate_metrics.get(key),
+            integrated_metrics.get(key),
+            "metric {key} differs"
+        );
+    }
+    assert_eq!(integrated_metrics["profile_answerable.min"], 2.5);
+    assert_eq!(integrated_metrics["profile_answerable.max"], 247.5);
+}
+
+#[tokio::test]
+async fn test_profile_failures_are_reported() {
+    let ctx = create_context().await;
+    let suite = ValidationSuite::builder("strict")
+        .table_name("orders")
+        .check(
+            Check::builder("strict_completeness")
+                .level(Level::Error)
+                .completeness(
+                    "amount",
+                    CompletenessOptions::full().into_constraint_options(),
+                )
+                .build(),
+        )
+        .build();
+
+    let result = ProfileAndValidate::new(ColumnProfiler::new(), suite)
+        .run(&ctx)
+        .await
+        .unwrap();
+
+    assert!(result.validation.is_failure());
+    assert_eq!(result.profiled_constraints().len(), 1);
+    let issues = &result.validation.report().issues;
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0].message.contains("completeness 90.00%"));
+}
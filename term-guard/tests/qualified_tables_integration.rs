@@ -0,0 +1,158 @@
+//! Integration tests for multi-table constraints on tables registered under
+//! custom catalogs and schemas.
+
+use datafusion::prelude::*;
+use term_guard::constraints::{
+    CrossTableSumConstraint, ForeignKeyConstraint, JoinCoverageConstraint,
+};
+use term_guard::core::{Check, Constraint, ConstraintStatus, Level, ValidationSuite};
+
+async fn run_sql(ctx: &SessionContext, sql: &str) {
+    ctx.sql(sql).await.unwrap().collect().await.unwrap();
+}
+
+/// Registers `orders` and `customers` in the default schema, in `staging`, and in
+/// `my_catalog.staging`, each with different contents.
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+
+    // Default schema: every order references an existing customer
+    run_sql(&ctx, "CREATE TABLE customers (id BIGINT)").await;
+    run_sql(&ctx, "INSERT INTO customers VALUES (1), (2), (3)").await;
+    run_sql(
+        &ctx,
+        "CREATE TABLE orders (customer_id BIGINT, total DOUBLE)",
+    )
+    .await;
+    run_sql(&ctx, "INSERT INTO orders VALUES (1, 10.0), (2, 20.0)").await;
+
+    // staging: one order references a missing customer
+    run_sql(&ctx, "CREATE SCHEMA staging").await;
+    run_sql(&ctx, "CREATE TABLE staging.customers (id BIGINT)").await;
+    run_sql(&ctx, "INSERT INTO staging.customers VALUES (1)").await;
+    run_sql(
+        &ctx,
+        "CREATE TABLE staging.orders (customer_id BIGINT, total DOUBLE)",
+    )
+    .await;
+    run_sql(
+        &ctx,
+        "INSERT INTO staging.orders VALUES (1, 10.0), (9, 99.0)",
+    )
+    .await;
+
+    // my_catalog.staging: two orders reference missing customers
+    run_sql(&ctx, "CREATE DATABASE my_catalog").await;
+    run_sql(&ctx, "CREATE SCHEMA my_catalog.staging").await;
+    run_sql(
+        &ctx,
+        "CREATE TABLE my_catalog.staging.customers (id BIGINT)",
+    )
+    .await;
+    run_sql(&ctx, "INSERT INTO my_catalog.staging.customers VALUES (1)").await;
+    run_sql(
+        &ctx,
+        "CREATE TABLE my_catalog.staging.orders (customer_id BIGINT, total DOUBLE)",
+    )
+    .await;
+    run_sql(
+        &ctx,
+        "INSERT INTO my_catalog.staging.orders VALUES (1, 10.0), (7, 1.0), (8, 2.0)",
+    )
+    .await;
+
+    ctx
+}
+
+#[tokio::test]
+async fn test_foreign_key_targets_the_qualified_tables() {
+    let ctx = create_context().await;
+
+    let default = ForeignKeyConstraint::new("orders.customer_id", "customers.id");
+    let result = default.evaluate(&ctx).await.unwrap();
+    assert_eq!(result.status, ConstraintStatus::Success);
+
+    let staging = ForeignKeyConstraint::new("staging.orders.customer_id", "staging.customers.id");
+    let result = staging.evaluate(&ctx).await.unwrap();
+    assert_eq!(result.status, ConstraintStatus::Failure);
+    assert_eq!(result.metric, Some(1.0));
+
+    let catalog = ForeignKeyConstraint::new(
+        "my_catalog.staging.orders.customer_id",
+        "my_catalog.staging.customers.id",
+    );
+    let result = catalog.evaluate(&ctx).await.unwrap();
+    assert_eq!(result.status, ConstraintStatus::Failure);
+    assert_eq!(result.metric, Some(2.0));
+}
+
+#[tokio::test]
+async fn test_cross_table_sum_compares_tables_with_the_same_name() {
+    let ctx = create_context().await;
+
+    let constraint = CrossTableSumConstraint::new("orders.total", "staging.orders.total");
+    let result = constraint.evaluate(&ctx).await.unwrap();
+    assert_eq!(result.status, ConstraintStatus::Failure);
+    assert_eq!(result.metric, Some(79.0));
+
+    let constraint =
+        CrossTableSumConstraint::new("staging.orders.total", "my_catalog.staging.orders.total")
+            .group_by(vec!["customer_id"]);
+    let result = constraint.evaluate(&ctx).await.unwrap();
+    assert_eq!(result.status, ConstraintStatus::Failure);
+}
+
+#[tokio::test]
+async fn test_join_coverage_targets_the_qualified_tables() {
+    let ctx = create_context().await;
+
+    let default = JoinCoverageConstraint::new("orders", "customers").on("customer_id", "id");
+    assert_eq!(default.evaluate(&ctx).await.unwrap().metric, Some(1.0));
+
+    let catalog =
+        JoinCoverageConstraint::new("my_catalog.staging.orders", "my_catalog.staging.customers")
+            .on("customer_id", "id");
+    let metric = catalog.evaluate(&ctx).await.unwrap().metric.unwrap();
+    assert!((metric - 1.0 / 3.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_suite_default_schema_qualifies_unqualified_tables() {
+    let ctx = create_context().await;
+
+    let suite = |default_schema: Option<&str>| {
+        let mut builder = ValidationSuite::builder("referential_integrity")
+            .table_name("orders")
+            .check(
+                Check::builder("orders_have_customers")
+                    .level(Level::Error)
+                    .foreign_key("orders.customer_id", "customers.id")
+                    .build(),
+            );
+        if let Some(schema) = default_schema {
+            builder = builder.default_schema(schema);
+        }
+        builder.build()
+    };
+
+    assert!(suite(None).run(&ctx).await.unwrap().is_success());
+
+    let result = suite(Some("staging")).run(&ctx).await.unwrap();
+    assert!(result.is_failure());
+    let issue = &result.report().issues[0];
+    assert_eq!(issue.metric, Some(1.0));
+}
+
+#[tokio::test]
+async fn test_qualified_names_are_validated() {
+    let ctx = create_context().await;
+
+    let constraint = ForeignKeyConstraint::new(
+        "staging.orders; DROP TABLE customers.customer_id",
+        "customers.id",
+    );
+    assert!(constraint.evaluate(&ctx).await.is_err());
+
+    let constraint = ForeignKeyConstraint::new("a.b.c.orders.customer_id", "customers.id");
+    assert!(constraint.evaluate(&ctx).await.is_err());
+}
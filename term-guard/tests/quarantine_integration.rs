@@ -0,0 +1,149 @@
+//! Integration tests for exporting violating rows to quarantine sinks.
+
+use datafusion::prelude::*;
+use term_guard::constraints::{ContainmentConstraint, FormatConstraint, LengthConstraint};
+use term_guard::core::{
+    Check, Constraint, ConstraintOptions, Level, QuarantineSink, ValidationContext,
+    ValidationSuite, CURRENT_CONTEXT,
+};
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql(
+        "CREATE TABLE data (id INT, email VARCHAR, status VARCHAR, code VARCHAR) AS VALUES
+            (1, 'alice@example.com', 'active', 'AB12'),
+            (2, NULL, 'active', 'CD34'),
+            (3, 'not-an-email', 'deleted', 'EF5'),
+            (4, 'bob@example.com', 'unknown', 'GH78'),
+            (5, NULL, NULL, NULL)",
+    )
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+    ctx
+}
+
+async fn count_rows(ctx: &SessionContext, sql: &str) -> usize {
+    ctx.sql(sql)
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap()
+        .iter()
+        .map(|batch| batch.num_rows())
+        .sum()
+}
+
+#[tokio::test]
+async fn test_suite_quarantines_violating_rows_from_each_constraint() {
+    let ctx = create_context().await;
+    let dir = tempfile::tempdir().unwrap();
+    let emails_path = dir.path().join("bad_emails.parquet");
+    let codes_path = dir.path().join("bad_codes.csv");
+
+    let suite = ValidationSuite::builder("quarantine")
+        .table_name("data")
+        .check(
+            Check::builder("row_quality")
+                .level(Level::Error)
+                .completeness(
+                    "email",
+                    ConstraintOptions::new()
+                        .with_quarantine(QuarantineSink::memory("missing_emails")),
+                )
+                .with_constraint(
+                    FormatConstraint::email("email", 1.0)
+                        .unwrap()
+                        .with_quarantine(
+                            QuarantineSink::parquet(&emails_path).columns(vec!["id", "email"]),
+                        ),
+                )
+                .with_constraint(
+                    LengthConstraint::exactly("code", 4)
+                        .with_quarantine(QuarantineSink::csv(&codes_path)),
+                )
+                .with_constraint(
+                    ContainmentConstraint::new("status", vec!["active", "inactive"])
+                        .with_quarantine(
+                            QuarantineSink::memory("bad_statuses").reason_column("why"),
+                        ),
+                )
+                .build(),
+        )
+        .build();
+
+    let result = suite.run(&ctx).await.unwrap();
+    assert!(result.is_failure());
+
+    let quarantined = &result.report().quarantined;
+    let rows: Vec<(&str, u64)> = quarantined
+        .iter()
+        .map(|q| (q.constraint_name.as_str(), q.rows))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            ("completeness", 2),
+            ("email", 1),
+            ("exact_length", 1),
+            ("containment", 2)
+        ]
+    );
+    assert!(quarantined.iter().all(|q| !q.truncated));
+    assert_eq!(quarantined[1].location, emails_path.display().to_string());
+
+    // In-memory sinks are registered in the validated session
+    assert_eq!(
+        count_rows(
+            &ctx,
+            "SELECT id FROM missing_emails WHERE violation_reason = 'email is null'"
+        )
+        .await,
+        2
+    );
+    assert_eq!(
+        count_rows(
+            &ctx,
+            "SELECT status FROM bad_statuses WHERE why = 'value not in the allowed set'"
+        )
+        .await,
+        2
+    );
+
+    // File sinks can be read back
+    let emails = ctx
+        .read_parquet(emails_path.to_str().unwrap(), ParquetReadOptions::default())
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    let schema = emails[0].schema();
+    let columns: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+    assert_eq!(columns, vec!["id", "email", "violation_reason"]);
+
+    let codes = std::fs::read_to_string(&codes_path).unwrap();
+    assert!(codes.starts_with("id,email,status,code,violation_reason"));
+    assert!(codes.contains("EF5"));
+}
+
+#[tokio::test]
+async fn test_quarantine_summary_is_attached_to_the_result() {
+    let ctx = create_context().await;
+
+    let constraint = LengthConstraint::min("code", 1)
+        .with_quarantine(QuarantineSink::memory("empty_codes").max_rows(10));
+    let result = CURRENT_CONTEXT
+        .scope(ValidationContext::new("data"), constraint.evaluate(&ctx))
+        .await
+        .unwrap();
+
+    assert!(result.status.is_success());
+    let summary = result.quarantine.unwrap();
+    assert_eq!(summary.rows, 0);
+    assert_eq!(summary.location, "empty_codes");
+    assert_eq!(count_rows(&ctx, "SELECT * FROM empty_codes").await, 0);
+}
@@ -0,0 +1,166 @@
+//! Conformance of the ratio constraints built on `RatioQueryBuilder`.
+//!
+//! Completeness, format, length and containment measure the fraction of rows
+//! satisfying a predicate and differ only in how they count nulls. These tests
+//! evaluate all of them on the same fixtures.
+
+use std::sync::Arc;
+
+use arrow::array::StringArray;
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion::datasource::MemTable;
+use datafusion::prelude::*;
+use term_guard::constraints::{
+    CompletenessConstraint, ContainmentConstraint, FormatConstraint, FormatOptions, FormatType,
+    LengthConstraint,
+};
+use term_guard::core::{
+    Check, Constraint, ConstraintStatus, NullPolicy, RatioQueryBuilder, ValidationSuite,
+    EMPTY_TABLE_MESSAGE,
+};
+
+/// Registers `data` with the columns `region` and `code`.
+fn create_context(rows: &[(&str, Option<&str>)]) -> SessionContext {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("region", DataType::Utf8, false),
+        Field::new("code", DataType::Utf8, true),
+    ]));
+    let regions: Vec<&str> = rows.iter().map(|(region, _)| *region).collect();
+    let codes: Vec<Option<&str>> = rows.iter().map(|(_, code)| *code).collect();
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(StringArray::from(regions)),
+            Arc::new(StringArray::from(codes)),
+        ],
+    )
+    .unwrap();
+
+    let ctx = SessionContext::new();
+    let table = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+    ctx.register_table("data", Arc::new(table)).unwrap();
+    ctx
+}
+
+/// Currency codes of two regions, with nulls and malformed codes in both.
+fn create_mixed_context() -> SessionContext {
+    create_context(&[
+        ("EU", Some("EUR")),
+        ("EU", Some("GBP")),
+        ("EU", None),
+        ("EU", Some("euro")),
+        ("US", Some("USD")),
+        ("US", None),
+        ("US", Some("US")),
+    ])
+}
+
+/// The ratio constraints on `code`, in the order completeness, format, length
+/// and containment.
+fn ratio_constraints() -> Vec<Arc<dyn Constraint>> {
+    vec![
+        Arc::new(CompletenessConstraint::with_threshold("code", 1.0)),
+        Arc::new(
+            FormatConstraint::new(
+                "code",
+                FormatType::Regex("^[A-Z]{3}$".to_string()),
+                1.0,
+                FormatOptions::new().null_is_valid(false),
+            )
+            .unwrap(),
+        ),
+        Arc::new(LengthConstraint::exactly("code", 3)),
+        Arc::new(ContainmentConstraint::new("code", ["EUR", "GBP", "USD"])),
+    ]
+}
+
+async fn evaluate_all(ctx: &SessionContext) -> Vec<(ConstraintStatus, Option<f64>, String)> {
+    let mut results = Vec::new();
+    for constraint in ratio_constraints() {
+        let result = constraint.evaluate(ctx).await.unwrap();
+        results.push((
+            result.status,
+            result.metric,
+            result.message.unwrap_or_default(),
+        ));
+    }
+    results
+}
+
+#[tokio::test]
+async fn test_empty_table() {
+    let ctx = create_context(&[]);
+    for (status, metric, message) in evaluate_all(&ctx).await {
+        assert_eq!(status, ConstraintStatus::Skipped);
+        assert_eq!(metric, None);
+        assert_eq!(message, EMPTY_TABLE_MESSAGE);
+    }
+}
+
+#[tokio::test]
+async fn test_all_null_column() {
+    let ctx = create_context(&[("EU", None), ("EU", None), ("US", None)]);
+    let results = evaluate_all(&ctx).await;
+
+    // Nulls violate completeness and format, conform to length and are not
+    // counted by containment
+    assert_eq!(results[0].0, ConstraintStatus::Failure);
+    assert_eq!(results[0].1, Some(0.0));
+    assert_eq!(results[1].0, ConstraintStatus::Failure);
+    assert_eq!(results[1].1, Some(0.0));
+    assert_eq!(results[2].0, ConstraintStatus::Success);
+    assert_eq!(results[2].1, Some(1.0));
+    assert_eq!(results[3].0, ConstraintStatus::Skipped);
+    assert_eq!(results[3].2, "No non-null data to validate");
+}
+
+#[tokio::test]
+async fn test_filtered_subsets() {
+    let ctx = create_mixed_context();
+    let expected = [
+        ("EU", [0.75, 0.5, 0.75, 2.0 / 3.0]),
+        ("US", [2.0 / 3.0, 1.0 / 3.0, 2.0 / 3.0, 0.5]),
+    ];
+
+    for (region, metrics) in expected {
+        let mut check = Check::builder("codes");
+        for constraint in ratio_constraints() {
+            check = check.arc_constraint(constraint);
+        }
+        let suite = ValidationSuite::builder("currencies")
+            .row_filter(format!("region = '{region}'"))
+            .check(check.build())
+            .build();
+        let result = suite.run(&ctx).await.unwrap();
+
+        let observed: Vec<Option<f64>> = result
+            .report()
+            .outcomes
+            .iter()
+            .map(|outcome| outcome.metric)
+            .collect();
+        assert_eq!(observed, metrics.map(Some), "region {region}");
+    }
+}
+
+#[tokio::test]
+async fn test_builder_filter_matches_suite_row_filter() {
+    let ctx = create_mixed_context();
+    let counts = RatioQueryBuilder::new("data", r#""code" IN ('EUR', 'GBP', 'USD')"#)
+        .nulls(r#""code""#, NullPolicy::Excluded)
+        .filter("region = 'US'")
+        .execute(&ctx)
+        .await
+        .unwrap();
+    assert_eq!((counts.conforming, counts.total), (1, 2));
+    assert_eq!(counts.ratio(), Some(0.5));
+
+    let counts = RatioQueryBuilder::new("data", r#""code" IN ('EUR', 'GBP', 'USD')"#)
+        .nulls(r#""code""#, NullPolicy::Excluded)
+        .filter("region = 'APAC'")
+        .execute(&ctx)
+        .await
+        .unwrap();
+    assert_eq!(counts.ratio(), None);
+}
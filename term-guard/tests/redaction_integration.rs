@@ -0,0 +1,99 @@
+//! Integration tests for the redaction of sensitive values in reports.
+
+use datafusion::prelude::*;
+use term_guard::constraints::{CardinalityConstraint, CardinalitySpec};
+use term_guard::core::{
+    Check, Level, RedactionPolicy, RedactionStrategy, ValidationSuite, REDACTED,
+};
+
+const EMAILS: [&str; 2] = ["jane.doe@example.com", "john.roe@example.com"];
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql(
+        "CREATE TABLE customers (email VARCHAR) AS VALUES
+            ('alice@example.com'), ('bob@example.com')",
+    )
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+    ctx.sql(&format!(
+        "CREATE TABLE orders (id INT, customer_email VARCHAR) AS VALUES
+            (1, 'alice@example.com'), (2, '{}'), (3, '{}'), (4, '{}')",
+        EMAILS[0], EMAILS[1], EMAILS[1]
+    ))
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+    ctx
+}
+
+fn suite(policy: Option<RedactionPolicy>) -> ValidationSuite {
+    let mut builder = ValidationSuite::builder("orders")
+        .table_name("orders")
+        .check(
+            Check::builder("customers")
+                .level(Level::Error)
+                .foreign_key("orders.customer_email", "customers.email")
+                .constraint(CardinalityConstraint::new(
+                    "orders.customer_email",
+                    "customers.email",
+                    CardinalitySpec::ManyToOne,
+                ))
+                .build(),
+        );
+    if let Some(policy) = policy {
+        builder = builder.redaction(policy);
+    }
+    builder.build()
+}
+
+async fn serialized_report(policy: Option<RedactionPolicy>) -> String {
+    let ctx = create_context().await;
+    let result = suite(policy).run(&ctx).await.unwrap();
+    assert!(result.is_failure());
+    serde_json::to_string(result.report()).unwrap()
+}
+
+#[tokio::test]
+async fn test_unredacted_report_quotes_values() {
+    let report = serialized_report(None).await;
+    for email in EMAILS {
+        assert!(report.contains(email), "{report}");
+    }
+}
+
+#[tokio::test]
+async fn test_redacted_values_never_appear_in_report() {
+    let strategies = [
+        RedactionStrategy::Drop,
+        RedactionStrategy::MaskAll,
+        RedactionStrategy::mask_middle(2),
+        RedactionStrategy::hash("pepper"),
+    ];
+    for strategy in strategies {
+        let policy = RedactionPolicy::new().column("customer_email", strategy.clone());
+        let report = serialized_report(Some(policy)).await;
+        for email in EMAILS {
+            assert!(!report.contains(email), "{strategy:?}: {report}");
+        }
+        if strategy == RedactionStrategy::Drop {
+            assert!(report.contains(REDACTED), "{report}");
+        }
+        if let RedactionStrategy::Hash { .. } = strategy {
+            assert!(report.contains(&strategy.apply(EMAILS[0])), "{report}");
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_policy_for_other_columns_leaves_values() {
+    let policy = RedactionPolicy::new().column("ssn", RedactionStrategy::Drop);
+    let report = serialized_report(Some(policy)).await;
+    assert!(report.contains(EMAILS[0]), "{report}");
+    assert!(!report.contains(REDACTED), "{report}");
+}
@@ -0,0 +1,99 @@
+//! Integration tests for limits on user-supplied regex patterns and their reuse within a run.
+
+use datafusion::prelude::*;
+use term_guard::constraints::{FormatConstraint, FormatOptions, FormatType};
+use term_guard::core::{
+    Check, Constraint, ConstraintStatus, Evaluator, RegexCache, RegexLimits, ValidationSuite,
+};
+use term_guard::error::TermError;
+
+/// Expands to ten thousand word characters, far beyond the default size limit.
+const PATHOLOGICAL_PATTERN: &str = r"(\w{100}){100}";
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql("CREATE TABLE data (code VARCHAR) AS VALUES ('AB1234'), ('CD5678'), ('bad'), (NULL)")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    ctx
+}
+
+#[tokio::test]
+async fn test_oversized_pattern_is_a_configuration_error() {
+    let check = Check::builder("product_codes")
+        .validates_regex("code", PATHOLOGICAL_PATTERN, 0.9)
+        .build();
+    let suite = ValidationSuite::builder("catalog").check(check).build();
+
+    let err = suite.run(&create_context().await).await.unwrap_err();
+    let TermError::InvalidConfiguration(report) = err else {
+        panic!("expected a configuration error, got {err}");
+    };
+    let message = report.to_string();
+    assert!(
+        message.contains("Check 'product_codes', constraint validates_regex on column 'code'"),
+        "{message}"
+    );
+    assert!(message.contains("size limit"), "{message}");
+}
+
+#[test]
+fn test_configured_limits_reject_a_pattern_the_defaults_accept() {
+    let err = Check::builder("product_codes")
+        .validates_regex_with_options(
+            "code",
+            r"^[A-Z]{2}\d{4}$",
+            0.9,
+            FormatOptions::new().with_regex_limits(RegexLimits::new().max_pattern_length(10)),
+        )
+        .try_build()
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("validates_regex_with_options on column 'code'"),
+        "{err}"
+    );
+    assert!(err.to_string().contains("limit of 10 bytes"), "{err}");
+}
+
+#[tokio::test]
+async fn test_repeated_pattern_compiles_once_per_run() {
+    let ctx = create_context().await;
+    let cache = RegexCache::new();
+
+    let constraints: Vec<Box<dyn Constraint>> = (0..10)
+        .map(|_| {
+            Box::new(FormatConstraint::regex("code", r"^[A-Z]{2}\d{4}$", 0.5).unwrap())
+                as Box<dyn Constraint>
+        })
+        .collect();
+    let results = Evaluator::new(&ctx)
+        .regex_cache(cache.clone())
+        .evaluate_all(constraints)
+        .await
+        .unwrap();
+
+    assert!(results
+        .iter()
+        .all(|result| result.status == ConstraintStatus::Success));
+    assert_eq!(cache.compilations(), 1);
+    assert_eq!(cache.hits(), 9);
+
+    // Case-insensitive matching compiles the pattern separately
+    let case_insensitive = FormatConstraint::new(
+        "code",
+        FormatType::Regex(r"^[A-Z]{2}\d{4}$".to_string()),
+        0.5,
+        FormatOptions::case_insensitive(),
+    )
+    .unwrap();
+    Evaluator::new(&ctx)
+        .regex_cache(cache.clone())
+        .evaluate(case_insensitive)
+        .await
+        .unwrap();
+    assert_eq!(cache.compilations(), 2);
+}
@@ -0,0 +1,120 @@
+//! Integration tests for comparing a validation report with a previous run.
+
+use datafusion::prelude::*;
+use term_guard::constraints::Assertion;
+use term_guard::core::builder_extensions::CompletenessOptions;
+use term_guard::core::{Check, DeltaKind, Level, ValidationResult, ValidationSuite};
+use term_guard::formatters::{FormatterConfig, HumanFormatter, ResultFormatter};
+
+async fn create_context(rows: &str) -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql(&format!(
+        "CREATE TABLE data (id INT, email VARCHAR) AS VALUES {rows}"
+    ))
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+    ctx
+}
+
+fn completeness(check: &str, column: &str) -> Check {
+    Check::builder(check)
+        .level(Level::Error)
+        .completeness(
+            column,
+            CompletenessOptions::full().into_constraint_options(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_changes_between_nightly_runs() {
+    let yesterday = ValidationSuite::builder("nightly")
+        .check(completeness("emails", "email"))
+        .check(completeness("ids", "id"))
+        .check(
+            Check::builder("volume")
+                .has_size(Assertion::Equals(3.0))
+                .build(),
+        )
+        .build();
+    let ctx =
+        create_context("(1, 'a@example.com'), (2, 'b@example.com'), (3, 'c@example.com')").await;
+    let previous = yesterday.run(&ctx).await.unwrap();
+    assert!(previous.is_success());
+
+    // The previous report is loaded back from storage
+    let previous = ValidationResult::from_json_str(&previous.to_json().unwrap())
+        .unwrap()
+        .report()
+        .clone();
+
+    // The emails check was renamed, the volume check replaced
+    let today = ValidationSuite::builder("nightly")
+        .check(completeness("contact_emails", "email"))
+        .check(completeness("ids", "id"))
+        .check(
+            Check::builder("volume_floor")
+                .has_size(Assertion::GreaterThan(1.0))
+                .build(),
+        )
+        .build();
+    let ctx = create_context(
+        "(1, 'a@example.com'), (2, NULL), (3, 'c@example.com'), (4, 'd@example.com')",
+    )
+    .await;
+    let current = today.run(&ctx).await.unwrap();
+
+    let delta = current.report().compare(&previous);
+    let kinds: Vec<(DeltaKind, &str)> = delta
+        .changes
+        .iter()
+        .map(|c| (c.kind, c.check_name.as_str()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (DeltaKind::NewlyFailing, "contact_emails"),
+            (DeltaKind::Added, "volume_floor"),
+            (DeltaKind::Removed, "volume"),
+            (DeltaKind::StillPassing, "ids"),
+        ]
+    );
+
+    let emails = &delta.changes[0];
+    assert_eq!(emails.previous_check_name.as_deref(), Some("emails"));
+    assert_eq!(emails.metric_delta(), Some(-0.25));
+    assert_eq!(delta.previous_timestamp, previous.timestamp);
+
+    let output = HumanFormatter::new()
+        .format_with_config(
+            &current,
+            &FormatterConfig::default()
+                .with_colors(false)
+                .with_previous_report(previous),
+        )
+        .unwrap();
+    assert!(output.contains(
+        "   ❌ newly failing: contact_emails / completeness (was emails / completeness) \
+         (metric 1.0000 → 0.7500, -0.2500)"
+    ));
+    assert!(!output.contains("still passing: ids"));
+}
+
+#[tokio::test]
+async fn test_identical_runs_have_no_changes() {
+    let suite = ValidationSuite::builder("nightly")
+        .check(completeness("emails", "email"))
+        .build();
+    let ctx = create_context("(1, NULL), (2, 'b@example.com')").await;
+
+    let first = suite.run(&ctx).await.unwrap();
+    let second = suite.run(&ctx).await.unwrap();
+
+    let delta = second.report().compare(first.report());
+    assert!(!delta.has_changes());
+    assert_eq!(delta.count(DeltaKind::StillFailing), 1);
+    assert_eq!(delta.changes[0].metric_delta(), Some(0.0));
+}
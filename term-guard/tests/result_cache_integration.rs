@@ -0,0 +1,238 @@
+//! Integration tests for the validation result cache.
+
+use datafusion::prelude::*;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use term_guard::analyzers::incremental::FileSystemStateStore;
+use term_guard::constraints::Assertion;
+use term_guard::core::builder_extensions::CompletenessOptions;
+use term_guard::core::{Check, Level, ResultCache, ValidationResult, ValidationSuite};
+
+fn write_csv(path: &Path, contents: &str) {
+    std::fs::write(path, contents).unwrap();
+}
+
+async fn create_context(dir: &Path) -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.register_csv(
+        "orders",
+        dir.join("orders.csv").to_str().unwrap(),
+        CsvReadOptions::new(),
+    )
+    .await
+    .unwrap();
+    ctx.register_csv(
+        "customers",
+        dir.join("customers.csv").to_str().unwrap(),
+        CsvReadOptions::new(),
+    )
+    .await
+    .unwrap();
+    ctx
+}
+
+fn setup_data() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    write_csv(
+        &dir.path().join("orders.csv"),
+        "order_id,customer_id,amount\n1,1,10.0\n2,2,20.0\n3,1,30.0\n",
+    );
+    write_csv(
+        &dir.path().join("customers.csv"),
+        "id,name\n1,Alice\n2,Bob\n",
+    );
+    dir
+}
+
+fn create_suite(cache: ResultCache) -> ValidationSuite {
+    ValidationSuite::builder("orders_suite")
+        .table_name("orders")
+        .check(
+            Check::builder("orders_basics")
+                .level(Level::Error)
+                .completeness(
+                    "order_id",
+                    CompletenessOptions::full().into_constraint_options(),
+                )
+                .has_min("amount", Assertion::GreaterThan(0.0))
+                .build(),
+        )
+        .check(
+            Check::builder("referential_integrity")
+                .level(Level::Error)
+                .foreign_key("orders.customer_id", "customers.id")
+                .build(),
+        )
+        .with_result_cache(cache)
+        .build()
+}
+
+fn queries_executed(result: &ValidationResult) -> u64 {
+    result.report().metrics.performance.queries_executed
+}
+
+#[tokio::test]
+async fn test_unchanged_tables_reuse_results() {
+    let data = setup_data();
+    let cache_dir = TempDir::new().unwrap();
+    let cache = ResultCache::new(FileSystemStateStore::new(cache_dir.path()).unwrap());
+    let suite = create_suite(cache);
+
+    let first = suite.run(&create_context(data.path()).await).await.unwrap();
+    assert!(first.is_success());
+    assert!(first.report().cached_constraints.is_empty());
+    assert!(queries_executed(&first) > 0);
+
+    let second = suite.run(&create_context(data.path()).await).await.unwrap();
+    assert!(second.is_success());
+    assert_eq!(second.report().cached_constraints.len(), 3);
+    assert_eq!(queries_executed(&second), 0);
+    assert_eq!(
+        first.report().metrics.custom_metrics,
+        second.report().metrics.custom_metrics
+    );
+}
+
+#[tokio::test]
+async fn test_changed_table_invalidates_results() {
+    let data = setup_data();
+    let cache_dir = TempDir::new().unwrap();
+    let cache = ResultCache::new(FileSystemStateStore::new(cache_dir.path()).unwrap());
+    let suite = create_suite(cache);
+
+    suite.run(&create_context(data.path()).await).await.unwrap();
+
+    write_csv(
+        &data.path().join("orders.csv"),
+        "order_id,customer_id,amount\n1,1,10.0\n2,2,-5.0\n",
+    );
+    let result = suite.run(&create_context(data.path()).await).await.unwrap();
+    assert!(result.is_failure());
+    assert!(result.report().cached_constraints.is_empty());
+}
+
+#[tokio::test]
+async fn test_referenced_table_change_invalidates_cross_table_constraints() {
+    let data = setup_data();
+    let cache_dir = TempDir::new().unwrap();
+    let cache = ResultCache::new(FileSystemStateStore::new(cache_dir.path()).unwrap());
+    let suite = create_suite(cache);
+
+    suite.run(&create_context(data.path()).await).await.unwrap();
+
+    // Only the parent table of the foreign key changes
+    write_csv(
+        &data.path().join("customers.csv"),
+        "id,name\n1,Alice\n3,Carol\n",
+    );
+    let result = suite.run(&create_context(data.path()).await).await.unwrap();
+
+    let cached: Vec<&str> = result
+        .report()
+        .cached_constraints
+        .iter()
+        .map(|c| c.check_name.as_str())
+        .collect();
+    assert_eq!(cached, vec!["orders_basics", "orders_basics"]);
+    assert!(result.is_failure());
+    let issue = &result.report().issues[0];
+    assert_eq!(issue.constraint_name, "foreign_key");
+    assert!(!issue.cached);
+
+    // The failure itself is cached on the next run
+    let rerun = suite.run(&create_context(data.path()).await).await.unwrap();
+    assert_eq!(rerun.report().cached_constraints.len(), 3);
+    assert!(rerun.report().issues[0].cached);
+}
+
+#[tokio::test]
+async fn test_ttl_expires_results() {
+    let data = setup_data();
+    let cache_dir = TempDir::new().unwrap();
+    let cache = ResultCache::new(FileSystemStateStore::new(cache_dir.path()).unwrap())
+        .with_ttl(Duration::ZERO);
+    let suite = create_suite(cache);
+
+    suite.run(&create_context(data.path()).await).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let result = suite.run(&create_context(data.path()).await).await.unwrap();
+    assert!(result.report().cached_constraints.is_empty());
+}
+
+#[tokio::test]
+async fn test_user_provided_versions_for_memory_tables() {
+    let cache_dir = TempDir::new().unwrap();
+    let store = Arc::new(FileSystemStateStore::new(cache_dir.path()).unwrap());
+
+    let ctx = SessionContext::new();
+    ctx.sql("CREATE TABLE events AS VALUES (1), (2), (3)")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    let suite_for = |cache: ResultCache| {
+        ValidationSuite::builder("events_suite")
+            .table_name("events")
+            .check(
+                Check::builder("size")
+                    .has_size(Assertion::Equals(3.0))
+                    .build(),
+            )
+            .with_result_cache(cache)
+            .build()
+    };
+
+    // Without a version, in-memory tables are not cached
+    let unversioned = suite_for(ResultCache::from_store(store.clone()));
+    unversioned.run(&ctx).await.unwrap();
+    let result = unversioned.run(&ctx).await.unwrap();
+    assert!(result.report().cached_constraints.is_empty());
+
+    let v1 = suite_for(ResultCache::from_store(store.clone()).with_table_version("events", "v1"));
+    v1.run(&ctx).await.unwrap();
+    assert_eq!(
+        v1.run(&ctx)
+            .await
+            .unwrap()
+            .report()
+            .cached_constraints
+            .len(),
+        1
+    );
+
+    let v2 = suite_for(ResultCache::from_store(store).with_table_version("events", "v2"));
+    assert!(v2
+        .run(&ctx)
+        .await
+        .unwrap()
+        .report()
+        .cached_constraints
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_concurrent_runs_share_cache() {
+    let data = setup_data();
+    let cache_dir = TempDir::new().unwrap();
+    let cache = ResultCache::new(FileSystemStateStore::new(cache_dir.path()).unwrap());
+    let suite = Arc::new(create_suite(cache));
+
+    let mut handles = Vec::new();
+    for _ in 0..4 {
+        let suite = suite.clone();
+        let dir = data.path().to_path_buf();
+        handles.push(tokio::spawn(async move {
+            suite.run(&create_context(&dir).await).await.unwrap()
+        }));
+    }
+    for handle in handles {
+        assert!(handle.await.unwrap().is_success());
+    }
+
+    let result = suite.run(&create_context(data.path()).await).await.unwrap();
+    assert_eq!(result.report().cached_constraints.len(), 3);
+}
@@ -0,0 +1,164 @@
+//! Integration tests for SARIF output, validated against the SARIF 2.1.0 schema.
+
+use datafusion::prelude::*;
+use serde_json::Value;
+use term_guard::constraints::{Assertion, CompletenessConstraint};
+use term_guard::core::{Check, Constraint, ConstraintOptions, Level, ValidationSuite};
+use term_guard::formatters::{ResultFormatter, SarifFormatter};
+
+/// The SARIF 2.1.0 schema definitions of the objects the formatter writes.
+const SARIF_SCHEMA: &str = include_str!("fixtures/sarif/sarif-schema-2.1.0-subset.json");
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql(
+        "CREATE TABLE data (order_id INT, email VARCHAR, amount DOUBLE) AS VALUES
+            (1, 'a@example.com', 10.0), (2, NULL, 20.0), (2, 'c@example.com', -5.0),
+            (3, NULL, NULL)",
+    )
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+    ctx
+}
+
+fn orders_suite() -> ValidationSuite {
+    ValidationSuite::builder("orders")
+        .check(
+            Check::builder("identity")
+                .level(Level::Error)
+                .validates_uniqueness(vec!["order_id"], 1.0)
+                .completeness("email", ConstraintOptions::new().with_threshold(1.0))
+                .build(),
+        )
+        .check(
+            Check::builder("amounts")
+                .level(Level::Warning)
+                .completeness("amount", ConstraintOptions::new().with_threshold(1.0))
+                .has_min("amount", Assertion::GreaterThanOrEqual(0.0))
+                .build(),
+        )
+        .check(
+            Check::builder("volume")
+                .level(Level::Info)
+                .has_size(Assertion::GreaterThanOrEqual(100.0))
+                .build(),
+        )
+        .build()
+}
+
+fn assert_valid_sarif(sarif: &Value) {
+    let schema: Value = serde_json::from_str(SARIF_SCHEMA).unwrap();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    let errors: Vec<String> = validator
+        .iter_errors(sarif)
+        .map(|error| error.to_string())
+        .collect();
+    assert!(errors.is_empty(), "invalid SARIF: {errors:#?}");
+}
+
+#[tokio::test]
+async fn test_sarif_log_for_failing_suite() {
+    let ctx = create_context().await;
+    let suite = orders_suite();
+    let result = suite.run(&ctx).await.unwrap();
+    assert!(result.is_failure());
+
+    let output = SarifFormatter::new("datasets/orders.parquet")
+        .with_suite(&suite)
+        .with_check_line("identity", 4)
+        .format(&result)
+        .unwrap();
+    let sarif: Value = serde_json::from_str(&output).unwrap();
+    assert_valid_sarif(&sarif);
+
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "term-guard");
+    assert_eq!(run["properties"]["suite"], "orders");
+
+    // Every constraint type of the suite is a rule described by its constraints
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    let completeness = rules
+        .iter()
+        .find(|rule| rule["id"] == "completeness")
+        .expect("completeness rule");
+    let description = completeness["fullDescription"]["text"].as_str().unwrap();
+    assert!(description.contains(&CompletenessConstraint::with_threshold("email", 1.0).describe()));
+    assert!(description.contains(&CompletenessConstraint::with_threshold("amount", 1.0).describe()));
+    assert_eq!(completeness["defaultConfiguration"]["level"], "error");
+
+    // Every failing constraint is a result with the level of its check
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), result.report().issues.len());
+    for result in results {
+        let rule_index = result["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(rules[rule_index]["id"], result["ruleId"]);
+        let check = result["properties"]["check"].as_str().unwrap();
+        let expected_level = match check {
+            "identity" => "error",
+            "amounts" => "warning",
+            _ => "note",
+        };
+        assert_eq!(result["level"], expected_level, "{result}");
+
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(
+            location["artifactLocation"]["uri"],
+            "datasets/orders.parquet"
+        );
+        if check == "identity" {
+            assert_eq!(location["region"]["startLine"], 4);
+        } else {
+            assert!(location.get("region").is_none());
+        }
+        assert!(result["partialFingerprints"]["termConstraintId/v1"].is_string());
+    }
+}
+
+#[tokio::test]
+async fn test_large_reports_are_truncated_per_rule() {
+    let ctx = create_context().await;
+    let mut check = Check::builder("many").level(Level::Error);
+    for threshold in 1..=20 {
+        check = check.has_size(Assertion::GreaterThanOrEqual(f64::from(threshold) * 100.0));
+    }
+    let suite = ValidationSuite::builder("orders")
+        .check(check.build())
+        .build();
+    let result = suite.run(&ctx).await.unwrap();
+    assert_eq!(result.report().issues.len(), 20);
+
+    let output = SarifFormatter::new("datasets/orders.parquet")
+        .with_max_results_per_rule(5)
+        .format(&result)
+        .unwrap();
+    let sarif: Value = serde_json::from_str(&output).unwrap();
+    assert_valid_sarif(&sarif);
+
+    let run = &sarif["runs"][0];
+    assert_eq!(run["results"].as_array().unwrap().len(), 5);
+    let notification = &run["invocations"][0]["toolExecutionNotifications"][0];
+    assert_eq!(notification["descriptor"]["id"], "size");
+    assert!(notification["message"]["text"]
+        .as_str()
+        .unwrap()
+        .starts_with("15 of 20 results of rule 'size' were left out"));
+}
+
+#[test]
+fn test_schema_rejects_invalid_logs() {
+    let invalid = serde_json::json!({
+        "version": "2.1.0",
+        "runs": [{
+            "tool": { "driver": { "name": "term-guard" } },
+            "results": [{ "ruleId": "size", "level": "critical", "message": { "text": "x" } }]
+        }]
+    });
+    let schema: Value = serde_json::from_str(SARIF_SCHEMA).unwrap();
+    assert!(!jsonschema::validator_for(&schema)
+        .unwrap()
+        .is_valid(&invalid));
+}
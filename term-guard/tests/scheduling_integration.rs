@@ -0,0 +1,157 @@
+//! Integration tests for ordering the checks of a suite by their history.
+
+use std::sync::Arc;
+
+use datafusion::prelude::*;
+use term_guard::constraints::Assertion;
+use term_guard::core::{
+    Check, ConstraintOutcome, ConstraintStatus, ExecutionOrder, Level, ReportAggregator,
+    ValidationReport, ValidationSuite,
+};
+use term_guard::repository::{InMemoryRepository, ResultKey};
+
+const CHECKS: [&str; 3] = ["volume", "amounts", "identifiers"];
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql("CREATE TABLE orders (id INT, amount DOUBLE) AS VALUES (1, 10.0), (2, -5.0)")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    ctx
+}
+
+/// A previous run of the suite in which only `failing` failed.
+fn previous_run(failing: &str) -> ValidationReport {
+    let mut report = ValidationReport::new("orders");
+    for check in CHECKS {
+        report.outcomes.push(ConstraintOutcome {
+            check_name: check.to_string(),
+            constraint_name: "constraint".to_string(),
+            level: Level::Error,
+            status: if check == failing {
+                ConstraintStatus::Failure
+            } else {
+                ConstraintStatus::Success
+            },
+            waived: false,
+            metric: None,
+            threshold: None,
+            dimension: None,
+            constraint_id: None,
+        });
+    }
+    report
+}
+
+fn suite(order: ExecutionOrder, repository: Arc<InMemoryRepository>) -> ValidationSuite {
+    ValidationSuite::builder("orders")
+        .table_name("orders")
+        .execution_order(order)
+        .execution_history(repository)
+        .check(
+            Check::builder("volume")
+                .level(Level::Error)
+                .has_size(Assertion::Equals(2.0))
+                .build(),
+        )
+        .check(
+            Check::builder("amounts")
+                .level(Level::Error)
+                .has_min("amount", Assertion::GreaterThanOrEqual(0.0))
+                .build(),
+        )
+        .check(
+            Check::builder("identifiers")
+                .level(Level::Error)
+                .validates_uniqueness(["id"], 1.0)
+                .build(),
+        )
+        .build()
+}
+
+fn executed_checks(report: &ValidationReport) -> Vec<&str> {
+    report
+        .outcomes
+        .iter()
+        .map(|outcome| outcome.check_name.as_str())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_historically_failing_checks_run_first() {
+    let repository = Arc::new(InMemoryRepository::new());
+    for (run, failing) in ["amounts", "amounts", "identifiers", "amounts"]
+        .into_iter()
+        .enumerate()
+    {
+        ReportAggregator::save_report(
+            repository.as_ref(),
+            ResultKey::new(1_700_000_000_000 + run as i64),
+            &previous_run(failing),
+        )
+        .await
+        .unwrap();
+    }
+
+    let ctx = create_context().await;
+    let result = suite(ExecutionOrder::LikelyFailuresFirst, repository)
+        .run(&ctx)
+        .await
+        .unwrap();
+    let report = result.report();
+    assert!(result.is_failure());
+
+    let schedule = report.schedule.as_ref().unwrap();
+    assert_eq!(schedule.order, "likely_failures_first");
+    let scheduled: Vec<_> = schedule
+        .checks
+        .iter()
+        .map(|estimate| estimate.check_name.as_str())
+        .collect();
+    assert_eq!(scheduled, ["amounts", "identifiers", "volume"]);
+    assert_eq!(schedule.checks[0].history_runs, 4);
+    assert_eq!(executed_checks(report), scheduled);
+}
+
+#[tokio::test]
+async fn test_builder_order_is_kept_by_default() {
+    let ctx = create_context().await;
+    let suite = ValidationSuite::builder("orders")
+        .table_name("orders")
+        .check(
+            Check::builder("volume")
+                .has_size(Assertion::Equals(2.0))
+                .build(),
+        )
+        .check(
+            Check::builder("identifiers")
+                .validates_uniqueness(["id"], 1.0)
+                .build(),
+        )
+        .build();
+
+    let result = suite.run(&ctx).await.unwrap();
+    assert!(result.report().schedule.is_none());
+    assert_eq!(executed_checks(result.report()), ["volume", "identifiers"]);
+}
+
+#[tokio::test]
+async fn test_custom_order_without_history() {
+    let ctx = create_context().await;
+    let order = ExecutionOrder::custom(|a, b| b.check_name.cmp(&a.check_name));
+    let result = suite(order, Arc::new(InMemoryRepository::new()))
+        .run(&ctx)
+        .await
+        .unwrap();
+
+    let schedule = result.report().schedule.as_ref().unwrap();
+    assert_eq!(schedule.order, "custom");
+    assert!(schedule.checks.iter().all(|check| check.history_runs == 0));
+    assert_eq!(
+        executed_checks(result.report()),
+        ["volume", "identifiers", "amounts"]
+    );
+}
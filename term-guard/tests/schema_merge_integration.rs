@@ -0,0 +1,323 @@
+//! Integration tests for multi-file sources whose files have different schemas.
+
+use arrow::array::{Array, ArrayRef, Float64Array, Int32Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion::parquet::arrow::ArrowWriter;
+use datafusion::prelude::*;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+use term_guard::sources::{CsvSource, DataSource, ParquetSource, SchemaMergePolicy};
+
+fn write_csv(dir: &Path, name: &str, lines: &[&str]) {
+    let mut file = File::create(dir.join(name)).unwrap();
+    for line in lines {
+        writeln!(file, "{line}").unwrap();
+    }
+}
+
+fn write_parquet(dir: &Path, name: &str, columns: Vec<(&str, ArrayRef)>) {
+    let fields: Vec<Field> = columns
+        .iter()
+        .map(|(name, array)| Field::new(*name, array.data_type().clone(), true))
+        .collect();
+    let schema = Arc::new(Schema::new(fields));
+    let arrays = columns.into_iter().map(|(_, array)| array).collect();
+    let batch = RecordBatch::try_new(schema.clone(), arrays).unwrap();
+
+    let file = File::create(dir.join(name)).unwrap();
+    let mut writer = ArrowWriter::try_new(file, schema, None).unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+}
+
+/// Two CSV files, the second of which adds a `region` column.
+fn csv_added_column() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    write_csv(dir.path(), "2024-03-01.csv", &["id,amount", "1,10", "2,20"]);
+    write_csv(
+        dir.path(),
+        "2024-03-15.csv",
+        &["id,amount,region", "3,30,EU", "4,40,US"],
+    );
+    dir
+}
+
+/// Two Parquet files, the second of which adds a `region` column.
+fn parquet_added_column() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    write_parquet(
+        dir.path(),
+        "2024-03-01.parquet",
+        vec![
+            ("id", Arc::new(Int32Array::from(vec![1, 2]))),
+            ("amount", Arc::new(Int32Array::from(vec![10, 20]))),
+        ],
+    );
+    write_parquet(
+        dir.path(),
+        "2024-03-15.parquet",
+        vec![
+            ("id", Arc::new(Int32Array::from(vec![3, 4]))),
+            ("amount", Arc::new(Int32Array::from(vec![30, 40]))),
+            ("region", Arc::new(StringArray::from(vec!["EU", "US"]))),
+        ],
+    );
+    dir
+}
+
+async fn query(ctx: &SessionContext, sql: &str) -> Vec<ArrayRef> {
+    let batches = ctx.sql(sql).await.unwrap().collect().await.unwrap();
+    let batch = arrow::compute::concat_batches(&batches[0].schema(), &batches).unwrap();
+    batch.columns().to_vec()
+}
+
+fn strings(array: &ArrayRef) -> Vec<Option<String>> {
+    let array = cast(array, &DataType::Utf8).unwrap();
+    let strings = array.as_any().downcast_ref::<StringArray>().unwrap();
+    (0..strings.len())
+        .map(|i| (!strings.is_null(i)).then(|| strings.value(i).to_string()))
+        .collect()
+}
+
+async fn register(source: &dyn DataSource) -> term_guard::prelude::Result<SessionContext> {
+    let ctx = SessionContext::new();
+    source.register(&ctx, "data").await?;
+    Ok(ctx)
+}
+
+#[tokio::test]
+async fn test_csv_added_column() {
+    let dir = csv_added_column();
+    let pattern = format!("{}/*.csv", dir.path().display());
+
+    // Strict names the odd file and the differing field
+    let source = CsvSource::from_glob(&pattern).await.unwrap();
+    assert!(source.description().contains("schema merge: strict"));
+    let error = register(&source).await.err().unwrap().to_string();
+    assert!(error.contains("2024-03-15.csv"), "{error}");
+    assert!(error.contains("unexpected column 'region'"), "{error}");
+
+    // UnionNullable keeps the new column, null for the older file
+    let source = CsvSource::from_glob(&pattern)
+        .await
+        .unwrap()
+        .with_schema_merge_policy(SchemaMergePolicy::UnionNullable);
+    let ctx = register(&source).await.unwrap();
+    let report = source.schema_report().unwrap();
+    assert_eq!(report.null_filled, ["region"]);
+    assert!(source
+        .description()
+        .contains("schema merge: union_nullable; null-filled: region"));
+
+    let columns = query(&ctx, "SELECT id, region FROM data ORDER BY id").await;
+    assert_eq!(
+        strings(&columns[1]),
+        [None, None, Some("EU".to_string()), Some("US".to_string())]
+    );
+
+    // Intersection drops it
+    let source = CsvSource::from_glob(&pattern)
+        .await
+        .unwrap()
+        .with_schema_merge_policy(SchemaMergePolicy::Intersection);
+    let ctx = register(&source).await.unwrap();
+    assert_eq!(source.schema_report().unwrap().dropped, ["region"]);
+    assert!(source.description().contains("dropped: region"));
+
+    let columns = query(&ctx, "SELECT * FROM data ORDER BY id").await;
+    assert_eq!(columns.len(), 2);
+    assert_eq!(columns[0].len(), 4);
+}
+
+#[tokio::test]
+async fn test_csv_widened_type() {
+    // CSV inference reads every integer column as Int64, so widening shows up as
+    // Int64 -> Float64 rather than Int32 -> Int64
+    let dir = TempDir::new().unwrap();
+    write_csv(dir.path(), "a.csv", &["id,amount", "1,10", "2,20"]);
+    write_csv(dir.path(), "b.csv", &["id,amount", "3,30.5"]);
+    let pattern = format!("{}/*.csv", dir.path().display());
+
+    let source = CsvSource::from_glob(&pattern)
+        .await
+        .unwrap()
+        .strict_inference(true);
+    let error = register(&source).await.err().unwrap().to_string();
+    assert!(
+        error.contains("column 'amount' is Int64 in a.csv, Float64 in b.csv"),
+        "{error}"
+    );
+
+    // Types are reconciled before the merge policy applies, so even Strict widens them
+    let source = CsvSource::from_glob(&pattern).await.unwrap();
+    let ctx = register(&source).await.unwrap();
+    let conflicts = source.inference_report().unwrap().conflicts;
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].resolved, DataType::Float64);
+    assert!(source.schema_report().unwrap().widened.is_empty());
+    assert!(source
+        .description()
+        .contains("schema merge: strict; types inferred from up to 1000 rows of each of 2 files"));
+
+    let columns = query(&ctx, "SELECT SUM(amount) FROM data").await;
+    let total = columns[0].as_any().downcast_ref::<Float64Array>().unwrap();
+    assert_eq!(total.value(0), 60.5);
+}
+
+#[tokio::test]
+async fn test_csv_incompatible_type() {
+    let dir = TempDir::new().unwrap();
+    write_csv(dir.path(), "a.csv", &["id,amount", "1,10"]);
+    write_csv(dir.path(), "b.csv", &["id,amount", "2,unknown"]);
+    let pattern = format!("{}/*.csv", dir.path().display());
+
+    let source = CsvSource::from_glob(&pattern)
+        .await
+        .unwrap()
+        .with_schema_merge_policy(SchemaMergePolicy::UnionNullable)
+        .strict_inference(true);
+    let error = register(&source).await.err().unwrap().to_string();
+    assert!(error.contains("column 'amount' is Int64"), "{error}");
+    assert!(error.contains("Utf8 in b.csv"), "{error}");
+
+    // Columns without a common numeric type are read as text
+    let source = CsvSource::from_glob(&pattern)
+        .await
+        .unwrap()
+        .with_schema_merge_policy(SchemaMergePolicy::Intersection);
+    let ctx = register(&source).await.unwrap();
+    assert!(source.schema_report().unwrap().dropped.is_empty());
+    assert_eq!(
+        source.inference_report().unwrap().conflicts[0].resolved,
+        DataType::Utf8
+    );
+
+    let columns = query(&ctx, "SELECT * FROM data ORDER BY id").await;
+    assert_eq!(columns.len(), 2);
+    let ids = columns[0].as_any().downcast_ref::<Int64Array>().unwrap();
+    assert_eq!(ids.values(), &[1, 2]);
+    assert_eq!(
+        strings(&columns[1]),
+        [Some("10".to_string()), Some("unknown".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn test_parquet_added_column() {
+    let dir = parquet_added_column();
+    let pattern = format!("{}/*.parquet", dir.path().display());
+
+    let source = ParquetSource::from_glob(&pattern).await.unwrap();
+    let error = register(&source).await.err().unwrap().to_string();
+    assert!(error.contains("2024-03-15.parquet"), "{error}");
+    assert!(error.contains("unexpected column 'region'"), "{error}");
+
+    let source = ParquetSource::from_glob(&pattern)
+        .await
+        .unwrap()
+        .with_schema_merge_policy(SchemaMergePolicy::UnionNullable);
+    let ctx = register(&source).await.unwrap();
+    assert_eq!(source.schema_report().unwrap().null_filled, ["region"]);
+    assert!(source.description().contains("null-filled: region"));
+
+    let columns = query(&ctx, "SELECT id, region FROM data ORDER BY id").await;
+    assert_eq!(
+        strings(&columns[1]),
+        [None, None, Some("EU".to_string()), Some("US".to_string())]
+    );
+
+    let source = ParquetSource::from_glob(&pattern)
+        .await
+        .unwrap()
+        .with_schema_merge_policy(SchemaMergePolicy::Intersection);
+    let ctx = register(&source).await.unwrap();
+    assert_eq!(source.schema_report().unwrap().dropped, ["region"]);
+
+    let columns = query(&ctx, "SELECT * FROM data ORDER BY id").await;
+    assert_eq!(columns.len(), 2);
+    assert_eq!(columns[0].len(), 4);
+}
+
+#[tokio::test]
+async fn test_parquet_widened_type() {
+    let dir = TempDir::new().unwrap();
+    write_parquet(
+        dir.path(),
+        "a.parquet",
+        vec![("amount", Arc::new(Int32Array::from(vec![10, 20])))],
+    );
+    write_parquet(
+        dir.path(),
+        "b.parquet",
+        vec![("amount", Arc::new(Int64Array::from(vec![5_000_000_000])))],
+    );
+    let pattern = format!("{}/*.parquet", dir.path().display());
+
+    let source = ParquetSource::from_glob(&pattern).await.unwrap();
+    let error = register(&source).await.err().unwrap().to_string();
+    assert!(
+        error.contains("column 'amount' is Int64 instead of Int32"),
+        "{error}"
+    );
+
+    let source = ParquetSource::from_glob(&pattern)
+        .await
+        .unwrap()
+        .with_schema_merge_policy(SchemaMergePolicy::UnionNullable);
+    let ctx = register(&source).await.unwrap();
+    let report = source.schema_report().unwrap();
+    assert_eq!(report.widened[0].from, [DataType::Int32]);
+    assert_eq!(report.widened[0].to, DataType::Int64);
+    assert!(source.description().contains("widened: amount -> Int64"));
+
+    let columns = query(&ctx, "SELECT SUM(amount) FROM data").await;
+    let total = columns[0].as_any().downcast_ref::<Int64Array>().unwrap();
+    assert_eq!(total.value(0), 5_000_000_030);
+}
+
+#[tokio::test]
+async fn test_parquet_incompatible_type() {
+    let dir = TempDir::new().unwrap();
+    write_parquet(
+        dir.path(),
+        "a.parquet",
+        vec![
+            ("id", Arc::new(Int32Array::from(vec![1]))),
+            ("amount", Arc::new(Int32Array::from(vec![10]))),
+        ],
+    );
+    write_parquet(
+        dir.path(),
+        "b.parquet",
+        vec![
+            ("id", Arc::new(Int32Array::from(vec![2]))),
+            ("amount", Arc::new(StringArray::from(vec!["unknown"]))),
+        ],
+    );
+    let pattern = format!("{}/*.parquet", dir.path().display());
+
+    let source = ParquetSource::from_glob(&pattern)
+        .await
+        .unwrap()
+        .with_schema_merge_policy(SchemaMergePolicy::UnionNullable);
+    let error = register(&source).await.err().unwrap().to_string();
+    assert!(error.contains("Column 'amount'"), "{error}");
+    assert!(error.contains("b.parquet"), "{error}");
+
+    let source = ParquetSource::from_glob(&pattern)
+        .await
+        .unwrap()
+        .with_schema_merge_policy(SchemaMergePolicy::Intersection);
+    let ctx = register(&source).await.unwrap();
+    assert_eq!(source.schema_report().unwrap().dropped, ["amount"]);
+
+    let columns = query(&ctx, "SELECT * FROM data ORDER BY id").await;
+    assert_eq!(columns.len(), 1);
+    let ids = columns[0].as_any().downcast_ref::<Int32Array>().unwrap();
+    assert_eq!(ids.values(), &[1, 2]);
+}
@@ -10,7 +10,10 @@ use std::io::Write;
 use std::sync::Arc;
 use tempfile::TempDir;
 use term_guard::prelude::*;
-use term_guard::sources::{CsvSource, DataSource, JsonSource, ParquetSource};
+use term_guard::sources::{
+    CompressionType, CsvOptions, CsvSource, DataSource, JsonOptions, JsonSource, ParquetSource,
+    StreamFormat, StreamOptions, StreamSource,
+};
 
 // Database features disabled until properly implemented
 // #[cfg(feature = "database")]
@@ -270,8 +273,6 @@ async fn test_sources_with_term_context() {
 
 #[tokio::test]
 async fn test_csv_custom_delimiter() {
-    use term_guard::sources::CsvOptions;
-
     let dir = create_csv_test_data();
     let tsv_path = format!("{}/data.tsv", dir.path().display());
 
@@ -318,6 +319,117 @@ async fn test_empty_glob_pattern() {
     }
 }
 
+#[tokio::test]
+async fn test_csv_source_recursive_glob_with_exclusions() {
+    let dir = create_csv_test_data();
+    let nested = dir.path().join("day=2");
+    std::fs::create_dir(&nested).unwrap();
+    for (name, id) in [("data3.csv", 5), ("data3_tmp.csv", 6)] {
+        let mut file = File::create(nested.join(name)).unwrap();
+        writeln!(file, "id,name,value").unwrap();
+        writeln!(file, "{id},Frank,500").unwrap();
+    }
+
+    let options = CsvOptions {
+        exclude: vec!["*_tmp.csv".to_string()],
+        ..Default::default()
+    };
+    let pattern = format!("{}/**/*.csv", dir.path().display());
+    let source = CsvSource::from_glob_with_options(&pattern, options)
+        .await
+        .unwrap();
+
+    let manifest = source.file_manifest().unwrap();
+    assert_eq!(manifest.file_count, 3);
+    assert_eq!(manifest.excluded_count, 1);
+    assert!(source.description().contains("3 files"));
+
+    // The excluded file sits next to a matched one and must not be read
+    let ctx = SessionContext::new();
+    source.register(&ctx, "csv_data").await.unwrap();
+    let batches = ctx
+        .sql("SELECT COUNT(*), MAX(id) FROM csv_data")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    let count = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<arrow::array::Int64Array>()
+        .unwrap()
+        .value(0);
+    let max_id = batches[0]
+        .column(1)
+        .as_any()
+        .downcast_ref::<arrow::array::Int64Array>()
+        .unwrap()
+        .value(0);
+    assert_eq!(count, 5);
+    assert_eq!(max_id, 5);
+
+    let options = CsvOptions {
+        max_files: Some(2),
+        ..Default::default()
+    };
+    let error = CsvSource::from_glob_with_options(&pattern, options)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("more than 2 files"));
+}
+
+#[tokio::test]
+async fn test_gzip_ndjson_stream_validates_like_a_file() {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use term_guard::constraints::Assertion;
+    use term_guard::core::{Check, ConstraintOptions, Level, ValidationSuite};
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    for i in 0..500 {
+        if i % 100 == 0 {
+            writeln!(encoder, r#"{{"id": {i}, "email": null}}"#).unwrap();
+        } else {
+            writeln!(encoder, r#"{{"id": {i}, "email": "user{i}@example.com"}}"#).unwrap();
+        }
+    }
+    let bytes = encoder.finish().unwrap();
+
+    let format = StreamFormat::Json(JsonOptions {
+        compression: CompressionType::Gzip,
+        schema_infer_max_records: 10,
+        ..Default::default()
+    });
+    let spill_dir = TempDir::new().unwrap();
+    let options = StreamOptions {
+        max_memory_bytes: 4096,
+        spill_dir: Some(spill_dir.path().to_path_buf()),
+        batch_size: 64,
+    };
+    let source = StreamSource::read_with_options(bytes.as_slice(), format, options)
+        .await
+        .unwrap();
+    assert!(source.is_spilled());
+    assert_eq!(source.num_rows(), 500);
+
+    let ctx = SessionContext::new();
+    source.register(&ctx, "data").await.unwrap();
+
+    let suite = ValidationSuite::builder("stream")
+        .table_name("data")
+        .check(
+            Check::builder("events")
+                .level(Level::Error)
+                .has_size(Assertion::Equals(500.0))
+                .completeness("email", ConstraintOptions::new().with_threshold(0.99))
+                .build(),
+        )
+        .build();
+    let result = suite.run(&ctx).await.unwrap();
+    assert!(result.is_success());
+}
+
 // Database integration tests
 // Database features not implemented yet, so we disable these tests
 #[cfg(test)]
@@ -191,8 +191,8 @@ async fn test_failure_logging() {
 
     // Check failure logging
     assert!(
-        combined_logs.contains(r#""level":"WARN""#),
-        "Should have warning for failure"
+        combined_logs.contains(r#""level":"ERROR""#),
+        "Should log error-level failures at ERROR"
     );
     assert!(
         combined_logs.contains(r#""message":"Constraint failed""#),
@@ -260,3 +260,92 @@ async fn test_metrics_in_logs() {
         "Should log success rate"
     );
 }
+
+#[tokio::test]
+async fn test_run_lifecycle_events() {
+    let capture = LogCapture::new();
+    let capture_clone = capture.logs.clone();
+
+    let subscriber = tracing_subscriber::fmt()
+        .json()
+        .with_writer(move || LogCapture {
+            logs: capture_clone.clone(),
+        })
+        .with_env_filter("info")
+        .finish();
+
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let ctx = create_test_context().await;
+    let suite = ValidationSuite::builder("lifecycle_suite")
+        .tag("env", "test")
+        .tag("team", "data")
+        .check(
+            Check::builder("required_fields")
+                .level(Level::Error)
+                .constraint(CompletenessConstraint::with_threshold("username", 0.95))
+                .build(),
+        )
+        .check(
+            Check::builder("soft_fields")
+                .level(Level::Warning)
+                .constraint(CompletenessConstraint::with_threshold("age", 0.95))
+                .build(),
+        )
+        .build();
+
+    let result = suite.run(&ctx).await.unwrap();
+    let run_id = result.report().run_id.expect("report carries the run ID");
+
+    let events: Vec<serde_json::Value> = capture
+        .captured_logs()
+        .join("")
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let lifecycle = |name: &str| -> Vec<&serde_json::Value> {
+        events
+            .iter()
+            .filter(|event| event["fields"]["event"] == name)
+            .collect()
+    };
+
+    // Every lifecycle event runs inside the run span
+    for name in ["run_started", "constraint_failed", "run_finished"] {
+        for event in lifecycle(name) {
+            let span = &event["span"];
+            assert_eq!(span["name"], "validation_run");
+            assert_eq!(span["run_id"], run_id.to_string());
+            assert_eq!(span["suite.name"], "lifecycle_suite");
+            assert_eq!(span["suite.tags"], "env=test,team=data");
+        }
+    }
+
+    let started = lifecycle("run_started");
+    assert_eq!(started.len(), 1);
+    assert_eq!(started[0]["level"], "INFO");
+
+    let failures = lifecycle("constraint_failed");
+    assert_eq!(failures.len(), 2);
+    let required = &failures[0];
+    assert_eq!(required["level"], "ERROR");
+    assert_eq!(required["fields"]["check.name"], "required_fields");
+    assert_eq!(required["fields"]["constraint.name"], "completeness");
+    assert_eq!(required["fields"]["constraint.metric"], 0.8);
+    assert!(required["fields"]["failure.message"].is_string());
+    let soft = &failures[1];
+    assert_eq!(soft["level"], "WARN");
+    assert_eq!(soft["fields"]["check.name"], "soft_fields");
+    assert_eq!(soft["fields"]["constraint.metric"], 0.9);
+
+    let finished = lifecycle("run_finished");
+    assert_eq!(finished.len(), 1);
+    assert_eq!(finished[0]["level"], "INFO");
+    assert_eq!(finished[0]["fields"]["metrics.failed"], 2);
+    assert_eq!(finished[0]["fields"]["metrics.total"], 2);
+    assert_eq!(finished[0]["fields"]["suite.result"], "failed");
+
+    // Each run gets its own ID
+    let rerun = suite.run(&ctx).await.unwrap();
+    assert_ne!(rerun.report().run_id, Some(run_id));
+}
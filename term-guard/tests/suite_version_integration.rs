@@ -0,0 +1,79 @@
+//! Integration tests for versioned suite definitions.
+
+use chrono::NaiveDate;
+use datafusion::prelude::*;
+use term_guard::core::{
+    diff_suites, ChangeEntry, Check, ConstraintOptions, DiffKind, SuiteVersion, ValidationSuite,
+};
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql(
+        "CREATE TABLE customers AS
+         SELECT * FROM (VALUES ('a@example.com'), ('b@example.com'), (NULL)) AS t(email)",
+    )
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+    ctx
+}
+
+fn suite(version: &str, threshold: f64) -> ValidationSuite {
+    let mut suite_version = SuiteVersion::parse(version).unwrap();
+    if version != "1.2.0" {
+        suite_version = suite_version.change(
+            ChangeEntry::new(
+                version.parse().unwrap(),
+                NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+                "jane",
+                "Require every email",
+            )
+            .approved_by("data-governance"),
+        );
+    }
+    ValidationSuite::builder("customers")
+        .table_name("customers")
+        .version(suite_version)
+        .check(
+            Check::builder("emails")
+                .completeness("email", ConstraintOptions::new().with_threshold(threshold))
+                .build(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_reports_carry_the_suite_version() {
+    let ctx = create_context().await;
+    let result = suite("1.3.0", 0.5).run(&ctx).await.unwrap();
+    assert_eq!(result.report().suite_version.as_deref(), Some("1.3.0"));
+
+    let json = serde_json::to_value(result.report()).unwrap();
+    assert_eq!(json["suite_version"], "1.3.0");
+
+    let unversioned = ValidationSuite::builder("customers")
+        .table_name("customers")
+        .build();
+    let result = unversioned.run(&ctx).await.unwrap();
+    assert_eq!(result.report().suite_version, None);
+}
+
+#[tokio::test]
+async fn test_threshold_change_is_diffed() {
+    let diff = diff_suites(&suite("1.2.0", 0.95), &suite("1.3.0", 0.99));
+    assert_eq!(diff.current_version.as_ref().unwrap().to_string(), "1.3.0");
+    assert!(diff.checks.is_empty());
+    assert_eq!(diff.constraints.len(), 1);
+    assert_eq!(diff.constraints[0].kind, DiffKind::Modified);
+    assert_eq!(diff.changelog.len(), 1);
+
+    let markdown = diff.to_markdown();
+    assert!(markdown.contains("1.2.0 -> 1.3.0"), "{markdown}");
+    assert!(markdown.contains("`0.95` -> `0.99`"), "{markdown}");
+    assert!(
+        markdown.contains("approved by data-governance"),
+        "{markdown}"
+    );
+}
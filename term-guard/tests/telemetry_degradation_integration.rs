@@ -0,0 +1,157 @@
+//! Integration tests for telemetry with an unreachable span exporter.
+
+#[cfg(feature = "telemetry")]
+mod telemetry_degradation_tests {
+    use datafusion::prelude::*;
+    use opentelemetry::global::BoxedTracer;
+    use opentelemetry::trace::TracerProvider;
+    use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
+    use opentelemetry_sdk::trace::{SdkTracerProvider, SpanData, SpanExporter};
+    use std::net::{SocketAddr, TcpStream};
+    use std::time::{Duration, Instant};
+    use term_guard::constraints::Assertion;
+    use term_guard::core::{Check, ConstraintOptions, ValidationResult, ValidationSuite};
+    use term_guard::telemetry::TermTelemetry;
+
+    /// How long an export to the unreachable endpoint takes to fail.
+    const CONNECT_TIMEOUT: Duration = Duration::from_millis(200);
+
+    /// Exports every span to an unroutable collector address, like an OTLP
+    /// exporter pointed at a dead endpoint behind a simple span processor.
+    #[derive(Debug)]
+    struct UnreachableExporter {
+        delay: Duration,
+    }
+
+    impl SpanExporter for UnreachableExporter {
+        fn export(
+            &self,
+            _batch: Vec<SpanData>,
+        ) -> impl std::future::Future<Output = OTelSdkResult> + Send {
+            let started = Instant::now();
+            if !self.delay.is_zero() {
+                let endpoint: SocketAddr = "10.255.255.1:4317".parse().unwrap();
+                let _ = TcpStream::connect_timeout(&endpoint, self.delay);
+                // Sandboxes may refuse the address at once; wait out the timeout regardless
+                std::thread::sleep(self.delay.saturating_sub(started.elapsed()));
+            }
+            std::future::ready(if self.delay.is_zero() {
+                Ok(())
+            } else {
+                Err(OTelSdkError::InternalFailure(
+                    "connection to collector timed out".to_string(),
+                ))
+            })
+        }
+    }
+
+    fn telemetry(delay: Duration) -> TermTelemetry {
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(UnreachableExporter { delay })
+            .build();
+        TermTelemetry::new(BoxedTracer::new(Box::new(provider.tracer("term"))))
+    }
+
+    fn suite(telemetry: Option<TermTelemetry>) -> ValidationSuite {
+        let mut builder = ValidationSuite::builder("telemetry_degradation");
+        if let Some(telemetry) = telemetry {
+            builder = builder.with_telemetry(telemetry);
+        }
+        for i in 0..10 {
+            builder = builder.check(
+                Check::builder(format!("check_{i}"))
+                    .has_size(Assertion::Equals(100.0))
+                    .completeness("id", ConstraintOptions::new().with_threshold(1.0))
+                    .build(),
+            );
+        }
+        builder.build()
+    }
+
+    async fn create_context() -> SessionContext {
+        let ctx = SessionContext::new();
+        let df = ctx
+            .sql("SELECT * FROM generate_series(1, 100) AS t(id)")
+            .await
+            .unwrap();
+        ctx.register_table("data", df.into_view()).unwrap();
+        ctx
+    }
+
+    async fn timed_run(
+        suite: &ValidationSuite,
+        ctx: &SessionContext,
+    ) -> (Duration, ValidationResult) {
+        let started = Instant::now();
+        let result = suite.run(ctx).await.unwrap();
+        (started.elapsed(), result)
+    }
+
+    async fn wait_until(condition: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !condition() && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_exporter_does_not_block_validation() {
+        let ctx = create_context().await;
+        let (baseline, _) = timed_run(&suite(None), &ctx).await;
+
+        // 31 spans exported inline would take over 6 seconds
+        let suite = suite(Some(telemetry(CONNECT_TIMEOUT)));
+        let (elapsed, result) = timed_run(&suite, &ctx).await;
+        assert!(result.is_success());
+        assert!(
+            elapsed < baseline * 5 + Duration::from_millis(500),
+            "run took {elapsed:?}, baseline {baseline:?}"
+        );
+
+        // The export thread trips the circuit breaker after three slow exports
+        let telemetry = suite.telemetry().unwrap();
+        wait_until(|| telemetry.health().degraded).await;
+        let health = telemetry.health();
+        assert!(health.degraded);
+        assert!(health.slow_exports >= 3);
+
+        // Later runs create no spans and note the missing telemetry
+        let (elapsed, result) = timed_run(&suite, &ctx).await;
+        assert!(result.report().telemetry_incomplete);
+        assert!(telemetry.health().spans_skipped >= 31);
+        assert!(elapsed < baseline * 5 + Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_responsive_exporter_stays_healthy() {
+        let ctx = create_context().await;
+        let suite = suite(Some(telemetry(Duration::ZERO)));
+        let (_, result) = timed_run(&suite, &ctx).await;
+        assert!(!result.report().telemetry_incomplete);
+
+        let telemetry = suite.telemetry().unwrap();
+        wait_until(|| telemetry.health().spans_exported >= 31).await;
+        let health = telemetry.health();
+        assert_eq!(health.spans_exported, 31);
+        assert!(!health.degraded);
+        assert_eq!(health.spans_skipped, 0);
+    }
+
+    #[test]
+    fn test_circuit_breaker_threshold() {
+        let telemetry =
+            telemetry(Duration::from_millis(20)).with_circuit_breaker(Duration::from_millis(5), 2);
+
+        for i in 0..3 {
+            drop(telemetry.start_check_span(&format!("check_{i}"), 1));
+        }
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !telemetry.health().degraded && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(telemetry.health().degraded);
+
+        drop(telemetry.start_check_span("after_trip", 1));
+        assert_eq!(telemetry.health().spans_skipped, 1);
+    }
+}
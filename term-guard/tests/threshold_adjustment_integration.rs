@@ -0,0 +1,177 @@
+//! Integration tests for relaxing ratio thresholds on small samples.
+
+use std::sync::Arc;
+
+use arrow::array::StringArray;
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion::datasource::MemTable;
+use datafusion::prelude::*;
+use term_guard::constraints::{
+    CompletenessConstraint, ContainmentConstraint, FormatConstraint, FormatOptions, FormatType,
+    UniquenessConstraint, UniquenessOptions, UniquenessType,
+};
+use term_guard::core::{
+    Check, Constraint, ConstraintOptions, ConstraintStatus, Level, ThresholdAdjustment,
+    ValidationSuite,
+};
+
+const WILSON: ThresholdAdjustment = ThresholdAdjustment::WilsonLowerBound { confidence: 0.95 };
+
+/// Registers `data` with `rows` email addresses, the first `bad` of which are NULL.
+fn create_context(rows: usize, bad: usize) -> SessionContext {
+    let values: Vec<Option<String>> = (0..rows)
+        .map(|row| (row >= bad).then(|| format!("user{row}@example.com")))
+        .collect();
+    let schema = Arc::new(Schema::new(vec![Field::new("email", DataType::Utf8, true)]));
+    let batch =
+        RecordBatch::try_new(schema.clone(), vec![Arc::new(StringArray::from(values))]).unwrap();
+
+    let ctx = SessionContext::new();
+    let table = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+    ctx.register_table("data", Arc::new(table)).unwrap();
+    ctx
+}
+
+fn completeness(adjustment: ThresholdAdjustment) -> CompletenessConstraint {
+    CompletenessConstraint::new(
+        "email",
+        ConstraintOptions::new()
+            .with_threshold(0.99)
+            .with_threshold_adjustment(adjustment),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_wilson_passes_one_bad_row_in_a_tiny_table() {
+    let ctx = create_context(50, 1);
+
+    let result = completeness(WILSON).evaluate(&ctx).await.unwrap();
+    assert_eq!(result.status, ConstraintStatus::Success);
+    assert_eq!(result.metric, Some(0.98));
+    assert_eq!(result.details["nominal_threshold"], 0.99);
+    assert_eq!(result.details["effective_threshold"], 0.98);
+    assert_eq!(result.details["wilson_confidence"], 0.95);
+    let message = result.message.unwrap();
+    assert!(message.contains("below threshold 0.990"), "{message}");
+    assert!(message.contains("Wilson lower bound at 95% confidence"));
+
+    // Without the adjustment the same table fails
+    let result = CompletenessConstraint::with_threshold("email", 0.99)
+        .evaluate(&ctx)
+        .await
+        .unwrap();
+    assert_eq!(result.status, ConstraintStatus::Failure);
+    assert!(result.details.is_empty());
+}
+
+#[tokio::test]
+async fn test_wilson_fails_two_bad_rows_in_a_tiny_table() {
+    let ctx = create_context(50, 2);
+
+    let result = completeness(WILSON).evaluate(&ctx).await.unwrap();
+    assert_eq!(result.status, ConstraintStatus::Failure);
+    let message = result.message.unwrap();
+    assert!(message.contains("is below threshold 99.00%"), "{message}");
+    assert!(message.contains("effective threshold 0.980"), "{message}");
+}
+
+#[tokio::test]
+async fn test_wilson_holds_large_tables_to_the_threshold() {
+    let ctx = create_context(10_000, 150);
+
+    let result = completeness(WILSON).evaluate(&ctx).await.unwrap();
+    assert_eq!(result.status, ConstraintStatus::Failure);
+    let effective = result.details["effective_threshold"];
+    assert!(effective > 0.987 && effective < 0.99, "{effective}");
+}
+
+#[tokio::test]
+async fn test_min_failing_rows_across_ratio_constraints() {
+    let ctx = create_context(20, 2);
+    let floor = ThresholdAdjustment::MinFailingRows(3);
+
+    let format = FormatConstraint::new(
+        "email",
+        FormatType::Email,
+        1.0,
+        FormatOptions::strict().with_threshold_adjustment(floor),
+    )
+    .unwrap();
+    let result = format.evaluate(&ctx).await.unwrap();
+    assert_eq!(result.status, ConstraintStatus::Success);
+    assert_eq!(result.details["min_failing_rows"], 3.0);
+
+    let allowed: Vec<String> = (2..19)
+        .map(|row| format!("user{row}@example.com"))
+        .collect();
+    let containment = ContainmentConstraint::new("email", allowed);
+    let result = containment
+        .clone()
+        .with_threshold_adjustment(floor)
+        .evaluate(&ctx)
+        .await
+        .unwrap();
+    assert_eq!(result.status, ConstraintStatus::Success);
+    let result = containment
+        .with_threshold_adjustment(ThresholdAdjustment::MinFailingRows(1))
+        .evaluate(&ctx)
+        .await
+        .unwrap();
+    assert_eq!(result.status, ConstraintStatus::Failure);
+    assert!(result
+        .message
+        .unwrap()
+        .contains("minimum of 1 failing row adjustment"));
+
+    let uniqueness = UniquenessConstraint::new(
+        ["email"],
+        UniquenessType::FullUniqueness { threshold: 1.0 },
+        UniquenessOptions::new().with_threshold_adjustment(floor),
+    )
+    .unwrap();
+    let result = uniqueness.evaluate(&ctx).await.unwrap();
+    assert_eq!(result.status, ConstraintStatus::Success);
+    assert_eq!(result.details["effective_threshold"], 0.9);
+}
+
+#[tokio::test]
+async fn test_adjusted_constraints_in_a_suite() {
+    let ctx = create_context(50, 1);
+    let suite = ValidationSuite::builder("small_sample")
+        .check(
+            Check::builder("emails")
+                .level(Level::Error)
+                .constraint(completeness(WILSON))
+                .build(),
+        )
+        .build();
+
+    let result = suite.run(&ctx).await.unwrap();
+    assert!(result.is_success());
+}
+
+#[test]
+fn test_invalid_adjustments_are_rejected() {
+    let invalid = ThresholdAdjustment::WilsonLowerBound { confidence: 95.0 };
+
+    let options = ConstraintOptions::new()
+        .with_threshold(0.99)
+        .with_threshold_adjustment(invalid);
+    assert!(options.validate().is_err());
+
+    assert!(FormatConstraint::new(
+        "email",
+        FormatType::Email,
+        0.99,
+        FormatOptions::new().with_threshold_adjustment(invalid),
+    )
+    .is_err());
+    assert!(UniquenessConstraint::new(
+        ["email"],
+        UniquenessType::FullUniqueness { threshold: 0.99 },
+        UniquenessOptions::new().with_threshold_adjustment(invalid),
+    )
+    .is_err());
+}
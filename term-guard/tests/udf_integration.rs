@@ -0,0 +1,197 @@
+//! Integration tests for scalar UDFs registered by suites for their runs.
+
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, BooleanArray, StringArray};
+use arrow::datatypes::DataType;
+use datafusion::logical_expr::{create_udf, ColumnarValue, ScalarUDF, Volatility};
+use datafusion::prelude::*;
+use term_guard::core::{Check, Level, ValidationResult, ValidationSuite};
+use term_guard::error::TermError;
+
+/// Transliteration of the letters of a VIN to the values its check digit sums.
+fn vin_value(c: char) -> Option<u32> {
+    match c {
+        '0'..='9' => c.to_digit(10),
+        'A' | 'J' => Some(1),
+        'B' | 'K' | 'S' => Some(2),
+        'C' | 'L' | 'T' => Some(3),
+        'D' | 'M' | 'U' => Some(4),
+        'E' | 'N' | 'V' => Some(5),
+        'F' | 'W' => Some(6),
+        'G' | 'P' | 'X' => Some(7),
+        'H' | 'Y' => Some(8),
+        'R' | 'Z' => Some(9),
+        _ => None,
+    }
+}
+
+/// Returns whether the ninth character of a North American VIN is its check digit.
+fn is_valid_vin(vin: &str) -> bool {
+    const WEIGHTS: [u32; 17] = [8, 7, 6, 5, 4, 3, 2, 10, 0, 9, 8, 7, 6, 5, 4, 3, 2];
+    let chars: Vec<char> = vin.chars().collect();
+    if chars.len() != 17 {
+        return false;
+    }
+    let Some(sum) = chars
+        .iter()
+        .zip(WEIGHTS)
+        .map(|(c, weight)| vin_value(*c).map(|value| value * weight))
+        .sum::<Option<u32>>()
+    else {
+        return false;
+    };
+    match sum % 11 {
+        10 => chars[8] == 'X',
+        digit => chars[8].to_digit(10) == Some(digit),
+    }
+}
+
+/// `vin_checksum(vin)`, replacing the nested CASE expressions of a check digit in SQL.
+fn vin_checksum_udf() -> ScalarUDF {
+    create_udf(
+        "vin_checksum",
+        vec![DataType::Utf8],
+        DataType::Boolean,
+        Volatility::Immutable,
+        Arc::new(|args: &[ColumnarValue]| {
+            let arrays = ColumnarValue::values_to_arrays(args)?;
+            let vins = arrays[0]
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .expect("Utf8 argument");
+            let valid: BooleanArray = vins.iter().map(|vin| vin.map(is_valid_vin)).collect();
+            Ok(ColumnarValue::Array(Arc::new(valid) as ArrayRef))
+        }),
+    )
+}
+
+async fn create_context() -> SessionContext {
+    let ctx = SessionContext::new();
+    ctx.sql(
+        "CREATE TABLE vehicles (vin VARCHAR, card VARCHAR, registered VARCHAR) AS VALUES
+            ('1M8GDM9AXKP042788', '4539 1488 0343 6467', '2024-03-01'),
+            ('11111111111111111', '79927398713', '2024-02-29'),
+            ('1HGCM82633A004352', '5555-5555-5555-4444', '2024-13-01'),
+            ('1M8GDM9AXKP042789', '4539 1488 0343 6468', 'yesterday')",
+    )
+    .await
+    .unwrap()
+    .collect()
+    .await
+    .unwrap();
+    ctx
+}
+
+fn vin_check() -> Check {
+    Check::builder("vins")
+        .level(Level::Error)
+        .satisfies(
+            "vin_checksum(vin) AND LENGTH(vin) = 17",
+            Some("VINs must carry a valid check digit"),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_udf_in_satisfies_expression() {
+    let ctx = create_context().await;
+    let suite = ValidationSuite::builder("fleet")
+        .table_name("vehicles")
+        .with_udf("vin_checksum", vin_checksum_udf())
+        .check(vin_check())
+        .build();
+    assert_eq!(suite.udf_names(), ["vin_checksum"]);
+
+    let result = suite.run(&ctx).await.unwrap();
+    let ValidationResult::Failure { report } = &result else {
+        panic!("expected a failure, got {result:?}");
+    };
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(
+        report.issues[0].message,
+        "VINs must carry a valid check digit (1 rows failed the condition)"
+    );
+    assert_eq!(report.outcomes[0].metric, Some(0.75));
+
+    // The UDF only lives for the run
+    assert!(!ctx.state().scalar_functions().contains_key("vin_checksum"));
+    let result = suite.run(&ctx).await.unwrap();
+    assert_eq!(result.report().outcomes[0].metric, Some(0.75));
+}
+
+#[tokio::test]
+async fn test_unregistered_function_is_rejected() {
+    let ctx = create_context().await;
+    let suite = ValidationSuite::builder("fleet")
+        .table_name("vehicles")
+        .check(vin_check())
+        .build();
+
+    let result = suite.run(&ctx).await.unwrap();
+    let report = result.report();
+    assert_eq!(report.issues.len(), 1);
+    assert!(
+        report.issues[0]
+            .message
+            .contains("SQL expression calls unknown function 'vin_checksum'"),
+        "{}",
+        report.issues[0].message
+    );
+}
+
+#[test]
+fn test_invalid_udf_names() {
+    let err = ValidationSuite::builder("fleet")
+        .with_udf("vin_check", vin_checksum_udf())
+        .check(vin_check())
+        .try_build()
+        .unwrap_err();
+    let TermError::InvalidConfiguration(report) = err else {
+        panic!("expected a configuration error, got {err}");
+    };
+    assert_eq!(report.issues[0].path, "udfs[0]");
+    assert!(
+        report.issues[0]
+            .message
+            .contains("UDF 'vin_check' was created with the name 'vin_checksum'"),
+        "{report}"
+    );
+
+    let err = ValidationSuite::builder("fleet")
+        .with_udf("Vin-Checksum", vin_checksum_udf())
+        .try_build()
+        .unwrap_err();
+    assert!(err.to_string().contains("Invalid function name"), "{err}");
+}
+
+#[cfg(feature = "udfs")]
+mod builtin_udfs {
+    use super::*;
+    use term_guard::udfs;
+
+    #[tokio::test]
+    async fn test_builtin_udfs() {
+        let ctx = create_context().await;
+        let suite = ValidationSuite::builder("fleet")
+            .table_name("vehicles")
+            .with_udfs(udfs::all())
+            .check(
+                Check::builder("payments")
+                    .satisfies("luhn_check(card)", None::<String>)
+                    .satisfies("is_parsable_date(registered, '%Y-%m-%d')", None::<String>)
+                    .satisfies("levenshtein(vin, '1M8GDM9AXKP042788') <= 1", None::<String>)
+                    .build(),
+            )
+            .build();
+
+        let result = suite.run(&ctx).await.unwrap();
+        let metrics: Vec<Option<f64>> = result
+            .report()
+            .outcomes
+            .iter()
+            .map(|outcome| outcome.metric)
+            .collect();
+        assert_eq!(metrics, [Some(0.75), Some(0.5), Some(0.5)]);
+    }
+}